@@ -7,7 +7,7 @@
 use super::*;
 use frame_benchmarking::v2::*;
 use frame_system::RawOrigin;
-use frame_support::BoundedVec;
+use frame_support::{traits::Currency, BoundedVec};
 
 #[benchmarks]
 mod benchmarks {
@@ -79,7 +79,8 @@ mod benchmarks {
             new_percentages,
             title_cid,
             desc_cid,
-            rationale_cid
+            rationale_cid,
+            0
         );
     }
 
@@ -100,5 +101,55 @@ mod benchmarks {
         resume_governance(RawOrigin::Root);
     }
 
+    #[benchmark]
+    fn claim_vested_rewards(n: Linear<1, 52>) {
+        let caller: T::AccountId = whitelisted_caller();
+        let referee: T::AccountId = account("referee", 0, 0);
+        let amount = T::Currency::minimum_balance().saturating_mul(100u32.into());
+        let _ = T::Currency::make_free_balance_be(
+            &Pallet::<T>::escrow_account(),
+            amount.saturating_mul((n + 1).into()),
+        );
+        // 每个周期一条未解锁奖励（最多一年 52 周）
+        for cycle in 0..n {
+            UnvestedRewards::<T>::insert((&referee, &caller, cycle), amount);
+        }
+        TotalUnvested::<T>::put(amount.saturating_mul(n.into()));
+
+        // 至少一次付费行为，再以签到累积到激活阈值
+        pallet_affiliate_referral::Pallet::<T>::record_activity(
+            &referee,
+            pallet_affiliate_referral::ActivityKind::Order,
+        );
+        for _ in 0..<T as pallet_affiliate_referral::Config>::ActivationThreshold::get() {
+            pallet_affiliate_referral::Pallet::<T>::record_activity(
+                &referee,
+                pallet_affiliate_referral::ActivityKind::CheckIn,
+            );
+        }
+
+        #[extrinsic_call]
+        claim_vested_rewards(RawOrigin::Signed(caller), referee, n);
+    }
+
+    #[benchmark]
+    fn clawback_unvested(n: Linear<1, 100>) {
+        let referee: T::AccountId = account("referee", 0, 0);
+        let amount = T::Currency::minimum_balance().saturating_mul(100u32.into());
+        let _ = T::Currency::make_free_balance_be(
+            &Pallet::<T>::escrow_account(),
+            amount.saturating_mul((n + 1).into()),
+        );
+
+        for i in 0..n {
+            let referrer: T::AccountId = account("referrer", i, 0);
+            UnvestedRewards::<T>::insert((&referee, &referrer, 0u32), amount);
+        }
+        TotalUnvested::<T>::put(amount.saturating_mul(n.into()));
+
+        #[extrinsic_call]
+        clawback_unvested(RawOrigin::Root, referee, n);
+    }
+
     impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -19,6 +19,7 @@ impl<T: Config> Pallet<T> {
     /// - buyer: 购买者/供奉者
     /// - gross_amount: 总金额
     /// - duration_weeks: 供奉时长（可选）
+    /// - source: 收益来源（用于收益报表）
     ///
     /// 扣费项目：
     /// - 销毁：5%
@@ -31,6 +32,7 @@ impl<T: Config> Pallet<T> {
         buyer: &T::AccountId,
         gross_amount: BalanceOf<T>,
         duration_weeks: Option<u32>,
+        source: statement::EarningSource,
     ) -> Result<BalanceOf<T>, DispatchError> {
         if gross_amount.is_zero() {
             return Ok(BalanceOf::<T>::zero());
@@ -74,7 +76,10 @@ impl<T: Config> Pallet<T> {
         }
 
         // 根据结算模式分配
-        let distributed = Self::distribute_by_mode(buyer, distributable, duration_weeks)?;
+        let distributed = Self::distribute_by_mode(buyer, distributable, duration_weeks, source)?;
+
+        // 计入购买者活跃度（本次奖励按分配前的激活状态处理）
+        pallet_affiliate_referral::Pallet::<T>::record_activity(buyer, source.activity_kind());
 
         // 未分配的金额转入国库（无推荐人或推荐人无效时）
         let undistributed = distributable.saturating_sub(distributed);
@@ -112,7 +117,12 @@ impl<T: Config> Pallet<T> {
         }
 
         // 会员费100%即时分成，无系统扣费
-        let distributed = Self::do_instant_distribute(buyer, amount, 15);
+        let distributed =
+            Self::do_instant_distribute(buyer, amount, 15, statement::EarningSource::Membership);
+        pallet_affiliate_referral::Pallet::<T>::record_activity(
+            buyer,
+            pallet_affiliate_referral::ActivityKind::Membership,
+        );
 
         Ok(distributed)
     }
@@ -123,25 +133,27 @@ impl<T: Config> Pallet<T> {
     /// - buyer: 购买者
     /// - distributable_amount: 可分配金额
     /// - duration_weeks: 供奉时长
+    /// - source: 收益来源
     ///
     /// 返回：实际分配总额
     fn distribute_by_mode(
         buyer: &T::AccountId,
         distributable_amount: BalanceOf<T>,
         duration_weeks: Option<u32>,
+        source: statement::EarningSource,
     ) -> Result<BalanceOf<T>, DispatchError> {
         let mode = SettlementMode::<T>::get();
 
         match mode {
             crate::types::SettlementMode::Weekly => {
                 // 全周结算模式
-                Self::do_report_consumption(buyer, distributable_amount, duration_weeks, 15);
+                Self::do_report_consumption(buyer, distributable_amount, duration_weeks, 15, source);
                 Ok(BalanceOf::<T>::zero()) // 周结算时立即返回0，实际结算在周末
             }
 
             crate::types::SettlementMode::Instant => {
                 // 全即时分成模式
-                let distributed = Self::do_instant_distribute(buyer, distributable_amount, 15);
+                let distributed = Self::do_instant_distribute(buyer, distributable_amount, 15, source);
                 Ok(distributed)
             }
 
@@ -155,14 +167,14 @@ impl<T: Config> Pallet<T> {
 
                 // 前N层即时分成
                 let instant_distributed = if instant_levels > 0 {
-                    Self::do_instant_distribute(buyer, distributable_amount, instant_levels)
+                    Self::do_instant_distribute(buyer, distributable_amount, instant_levels, source)
                 } else {
                     BalanceOf::<T>::zero()
                 };
 
                 // 后M层周结算
                 if weekly_levels > 0 {
-                    Self::do_report_consumption(buyer, distributable_amount, duration_weeks, weekly_levels);
+                    Self::do_report_consumption(buyer, distributable_amount, duration_weeks, weekly_levels, source);
                 }
 
                 Ok(instant_distributed)
@@ -7,10 +7,21 @@
 //! ## 核心功能
 //!
 //! - **提案创建**：持币大户、社区联署可发起提案
-//! - **投票机制**：加权投票（持币70% + 参与20% + 贡献10%）+ 信念投票
+//! - **投票机制**：持币加权 + 信念投票（锁定时长换取倍数），投票金额在锁定期内被锁定
+//! - **委托投票**：将投票权委托给他人，被委托人投票时计入委托权重
+//! - **密封投票**：可选 commit-reveal 模式，投票期只提交承诺，揭示期公开，防止跟风投票
+//! - **参与率门槛**：锁定金额占总发行量的比例低于 `MinTurnout` 时提案不通过
 //! - **自动执行**：通过后自动生效，无需人工干预
 //! - **紧急机制**：技术委员会可紧急暂停治理（但无法否决提案）
 //!
+//! ## 提案生命周期（两类提案共用同一投票引擎）
+//!
+//! ```text
+//! Discussion ──(DiscussionPeriod)──▶ Voting ──(VotingPeriod)──▶ [Revealing ──(RevealPeriod)──▶] 计票
+//!                                                                       │
+//!                                         Approved → 待执行 → Executed ◀┴▶ Rejected（退押金 + 冷却期）
+//! ```
+//!
 //! ## 安全保障
 //!
 //! - **唯一修改通道**：关键参数只能通过治理提案修改
@@ -21,9 +32,18 @@
 
 use super::*;
 use crate::types::LevelPercents;
-use frame_support::{pallet_prelude::*, traits::Currency};
+use frame_support::{
+    pallet_prelude::*,
+    traits::{Currency, LockIdentifier, LockableCurrency, WithdrawReasons},
+};
 use sp_runtime::Perbill;
 
+/// 治理投票锁 ID（投票与委托共用一把锁，锁定金额取最大值）
+pub const GOVERNANCE_LOCK_ID: LockIdentifier = *b"affgovrn";
+
+/// 提案被拒绝后发起人的冷却期（30天）
+pub const REJECTION_COOLDOWN_BLOCKS: u32 = 432_000;
+
 /// 提案状态
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum ProposalStatus {
@@ -39,10 +59,54 @@ pub enum ProposalStatus {
     Cancelled,
     /// 已执行
     Executed,
+    /// 揭示中（密封投票）
+    Revealing,
+}
+
+/// 提案族
+///
+/// 两类提案共用 `NextProposalId`，提案ID全局唯一，投票引擎据此定位提案所属存储。
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ProposalFamily {
+    /// 分成比例调整
+    Percentage,
+    /// 年费价格调整
+    MembershipPrice,
+}
+
+/// 计票后待分批清理的投票数据
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct VoteCleanup {
+    /// 提案ID
+    pub proposal_id: u64,
+    /// 提案族
+    pub family: ProposalFamily,
+    /// 是否仍需清理投票记录（提案通过时保留投票记录，只清理承诺）
+    pub clear_votes: bool,
+}
+
+/// 投票方式
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum BallotMode {
+    /// 公开投票
+    Open,
+    /// 密封投票（commit-reveal）
+    Sealed,
+}
+
+impl BallotMode {
+    /// 从 u8 编码解析（0=Open, 1=Sealed）
+    pub fn from_u8(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(BallotMode::Open),
+            1 => Some(BallotMode::Sealed),
+            _ => None,
+        }
+    }
 }
 
 /// 投票选项
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum Vote {
     /// 支持
     Aye,
@@ -61,10 +125,20 @@ impl Vote {
             Vote::Abstain => 2,
         }
     }
+
+    /// 从 u8 编码解析（0=Aye, 1=Nay, 2=Abstain）
+    pub fn from_u8(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(Vote::Aye),
+            1 => Some(Vote::Nay),
+            2 => Some(Vote::Abstain),
+            _ => None,
+        }
+    }
 }
 
 /// 信念投票（锁定时长换取权重倍数）
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum Conviction {
     /// 不锁定，权重 x1
     None,
@@ -108,6 +182,55 @@ impl Conviction {
             Conviction::Locked6x => 32,
         }
     }
+
+    /// 从 u8 编码解析（0=None, 1=Locked1x, ..., 6=Locked6x）
+    pub fn from_u8(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(Conviction::None),
+            1 => Some(Conviction::Locked1x),
+            2 => Some(Conviction::Locked2x),
+            3 => Some(Conviction::Locked3x),
+            4 => Some(Conviction::Locked4x),
+            5 => Some(Conviction::Locked5x),
+            6 => Some(Conviction::Locked6x),
+            _ => None,
+        }
+    }
+
+    /// 转换为 u8 编码（用于事件）
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            Conviction::None => 0,
+            Conviction::Locked1x => 1,
+            Conviction::Locked2x => 2,
+            Conviction::Locked3x => 3,
+            Conviction::Locked4x => 4,
+            Conviction::Locked5x => 5,
+            Conviction::Locked6x => 6,
+        }
+    }
+
+    /// 按信念倍数计算投票权重
+    pub fn votes(&self, capital: u128) -> u128 {
+        capital.saturating_mul(self.multiplier()).saturating_div(10)
+    }
+}
+
+/// 投票委托
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct Delegation<T: Config> {
+    /// 被委托人
+    pub target: T::AccountId,
+
+    /// 委托（锁定）金额
+    pub balance: BalanceOf<T>,
+
+    /// 信念投票
+    pub conviction: Conviction,
+
+    /// 已撤销委托时的解锁区块
+    pub unlock_at: Option<BlockNumberFor<T>>,
 }
 
 /// 分成比例调整提案
@@ -152,6 +275,12 @@ pub struct PercentageAdjustmentProposal<T: Config> {
 
     /// 投票结束时间
     pub voting_end: Option<BlockNumberFor<T>>,
+
+    /// 投票方式
+    pub ballot_mode: BallotMode,
+
+    /// 揭示结束时间（仅密封投票）
+    pub reveal_end: Option<BlockNumberFor<T>>,
 }
 
 /// 投票记录
@@ -167,9 +296,12 @@ pub struct VoteRecord<T: Config> {
     /// 信念投票
     pub conviction: Conviction,
 
-    /// 投票权重
+    /// 投票权重（含信念倍数与委托权重）
     pub weight: u128,
 
+    /// 锁定金额（含委托金额，用于参与率）
+    pub capital: u128,
+
     /// 投票时间
     pub timestamp: BlockNumberFor<T>,
 }
@@ -186,7 +318,7 @@ pub struct VoteTally {
     /// 弃权票权重
     pub abstain_votes: u128,
 
-    /// 总投票权重
+    /// 参与金额（投票锁定金额合计，不含信念倍数）
     pub total_turnout: u128,
 }
 
@@ -200,13 +332,23 @@ impl VoteTally {
         Perbill::from_rational(self.aye_votes, total)
     }
 
-    /// 计算参与率（总投票 / 总投票权）
+    /// 计算参与率（参与金额 / 总发行量）
     pub fn participation_rate(&self, total_power: u128) -> Perbill {
         if total_power == 0 {
             return Perbill::zero();
         }
         Perbill::from_rational(self.total_turnout, total_power)
     }
+
+    /// 计入一张选票
+    pub fn add(&mut self, vote: Vote, weight: u128, capital: u128) {
+        match vote {
+            Vote::Aye => self.aye_votes = self.aye_votes.saturating_add(weight),
+            Vote::Nay => self.nay_votes = self.nay_votes.saturating_add(weight),
+            Vote::Abstain => self.abstain_votes = self.abstain_votes.saturating_add(weight),
+        }
+        self.total_turnout = self.total_turnout.saturating_add(capital);
+    }
 }
 
 /// 比例变更历史记录
@@ -360,138 +502,570 @@ impl<T: Config> Pallet<T> {
     // 所有分成比例提案现在都使用统一的全民投票机制
 
     // ========================================
-    // 投票权重计算
+    // 投票引擎（两类提案共用）
     // ========================================
 
-    /// 计算账户的总投票权重
-    /// 持币权重（70%） + 参与权重（20%） + 贡献权重（10%）
-    pub fn calculate_total_voting_power(account: &T::AccountId) -> u128 {
-        let stake_weight = Self::calculate_stake_weight(account)
-            .saturating_mul(70)
-            .saturating_div(100);
+    /// 安排投票阶段：返回（投票开始, 投票结束, 揭示结束）
+    pub fn schedule_ballot(
+        now: BlockNumberFor<T>,
+        mode: BallotMode,
+    ) -> (BlockNumberFor<T>, BlockNumberFor<T>, Option<BlockNumberFor<T>>) {
+        let voting_start = now.saturating_add(T::DiscussionPeriod::get());
+        let voting_end = voting_start.saturating_add(T::VotingPeriod::get());
+        let reveal_end = match mode {
+            BallotMode::Open => None,
+            BallotMode::Sealed => Some(voting_end.saturating_add(T::RevealPeriod::get())),
+        };
+        (voting_start, voting_end, reveal_end)
+    }
 
-        let participation_weight = Self::calculate_participation_weight(account)
-            .saturating_mul(20)
-            .saturating_div(100);
+    /// 查找提案的投票信息
+    pub fn ballot_info(proposal_id: u64) -> Option<BallotInfo<BlockNumberFor<T>>> {
+        if let Some(p) = ActiveProposals::<T>::get(proposal_id) {
+            return Some(BallotInfo {
+                family: ProposalFamily::Percentage,
+                closes_at: p.reveal_end.or(p.voting_end).unwrap_or(p.created_at),
+                status: p.status,
+                mode: p.ballot_mode,
+            });
+        }
+        ActiveMembershipPriceProposals::<T>::get(proposal_id).map(|p| BallotInfo {
+            family: ProposalFamily::MembershipPrice,
+            closes_at: p.reveal_end.or(p.voting_end).unwrap_or(p.created_at),
+            status: p.status,
+            mode: p.ballot_mode,
+        })
+    }
 
-        let contribution_weight = Self::calculate_contribution_weight(account)
-            .saturating_mul(10)
-            .saturating_div(100);
+    /// 信念锁定区块数
+    pub fn conviction_lock_blocks(conviction: Conviction) -> BlockNumberFor<T> {
+        let blocks_per_week: u32 = BlocksPerWeek::<T>::get().saturated_into();
+        conviction.lock_weeks().saturating_mul(blocks_per_week).into()
+    }
 
-        stake_weight
-            .saturating_add(participation_weight)
-            .saturating_add(contribution_weight)
+    /// 密封投票承诺
+    ///
+    /// `blake2_256(SCALE(proposal_id, voter, vote_type, conviction_type, balance, salt))`
+    pub fn ballot_commitment(
+        proposal_id: u64,
+        voter: &T::AccountId,
+        vote_type: u8,
+        conviction_type: u8,
+        balance: BalanceOf<T>,
+        salt: &[u8; 32],
+    ) -> [u8; 32] {
+        sp_core::blake2_256(&(proposal_id, voter, vote_type, conviction_type, balance, salt).encode())
     }
 
-    /// 计算持币权重（平方根，避免巨鲸垄断）
-    fn calculate_stake_weight(account: &T::AccountId) -> u128 {
-        let balance = T::Currency::free_balance(account);
-        let balance_u128: u128 = balance.saturated_into();
+    /// 计入一张选票（公开投票直接调用，密封投票在揭示时调用）
+    ///
+    /// 权重 = 锁定金额 × 信念倍数 + 委托给投票人的权重；
+    /// 锁定金额在计票后再锁定信念对应的周数。
+    pub(crate) fn do_cast_vote(
+        proposal_id: u64,
+        who: &T::AccountId,
+        vote: Vote,
+        conviction: Conviction,
+        balance: BalanceOf<T>,
+    ) -> DispatchResult {
+        let info = Self::ballot_info(proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+        ensure!(!Delegations::<T>::contains_key(who), Error::<T>::VoterIsDelegating);
+        ensure!(
+            !balance.is_zero() && balance <= T::Currency::free_balance(who),
+            Error::<T>::InvalidVoteBalance
+        );
+        match info.family {
+            ProposalFamily::Percentage => ensure!(
+                !ProposalVotes::<T>::contains_key(proposal_id, who),
+                Error::<T>::AlreadyVoted
+            ),
+            ProposalFamily::MembershipPrice => ensure!(
+                !MembershipPriceProposalVotes::<T>::contains_key(proposal_id, who),
+                Error::<T>::MembershipPriceAlreadyVoted
+            ),
+        }
+
+        let own: u128 = balance.saturated_into();
+        let (delegated_capital, delegated_votes) = DelegatedPower::<T>::get(who);
+        let capital = own.saturating_add(delegated_capital);
+        let weight = conviction.votes(own).saturating_add(delegated_votes);
+
+        let unlock_block = info.closes_at.saturating_add(Self::conviction_lock_blocks(conviction));
+        VoteLocks::<T>::insert(who, proposal_id, (balance, unlock_block));
+        Self::update_governance_lock(who);
+
+        let record = VoteRecord {
+            voter: who.clone(),
+            vote,
+            conviction,
+            weight,
+            capital,
+            timestamp: <frame_system::Pallet<T>>::block_number(),
+        };
+
+        match info.family {
+            ProposalFamily::Percentage => {
+                ProposalVotes::<T>::insert(proposal_id, who, &record);
+                crate::pallet::VoteTally::<T>::mutate(proposal_id, |tally| {
+                    tally.add(vote, weight, capital)
+                });
+                Self::deposit_event(Event::VoteCast {
+                    proposal_id,
+                    voter: who.clone(),
+                    vote_type: vote.to_u8(),
+                    weight,
+                });
+            },
+            ProposalFamily::MembershipPrice => {
+                MembershipPriceProposalVotes::<T>::insert(proposal_id, who, &record);
+                MembershipPriceVoteTally::<T>::mutate(proposal_id, |tally| {
+                    tally.add(vote, weight, capital)
+                });
+                Self::deposit_event(Event::MembershipPriceVoteCast {
+                    proposal_id,
+                    voter: who.clone(),
+                    vote: vote.to_u8(),
+                    conviction: conviction.to_u8(),
+                    voting_power: weight.saturated_into(),
+                });
+            },
+        }
+
+        // 投票历史（仅作参考记录，满额后不再追加）
+        VoteHistory::<T>::mutate(who, |history| {
+            let _ = history.try_push(proposal_id);
+        });
+
+        Ok(())
+    }
 
-        // 平方根权重
-        let sqrt_balance = Self::integer_sqrt(balance_u128);
+    /// 委托投票权
+    pub(crate) fn do_delegate(
+        who: &T::AccountId,
+        target: T::AccountId,
+        conviction: Conviction,
+        balance: BalanceOf<T>,
+    ) -> DispatchResult {
+        ensure!(*who != target, Error::<T>::CannotDelegateToSelf);
+        ensure!(!Delegations::<T>::contains_key(who), Error::<T>::AlreadyDelegating);
+        // 不允许委托链：被委托人不能处于委托中，委托人不能已有他人委托
+        ensure!(
+            !Delegations::<T>::contains_key(&target) && DelegatedPower::<T>::get(who) == (0, 0),
+            Error::<T>::DelegationChainNotAllowed
+        );
+        ensure!(
+            !balance.is_zero() && balance <= T::Currency::free_balance(who),
+            Error::<T>::InvalidVoteBalance
+        );
+        // 已亲自参与的投票尚未结束时不能委托，防止同一提案重复计票
+        ensure!(!Self::has_running_votes(who), Error::<T>::ActiveVotesExist);
+
+        let capital: u128 = balance.saturated_into();
+        DelegatedPower::<T>::mutate(&target, |(total_capital, total_votes)| {
+            *total_capital = total_capital.saturating_add(capital);
+            *total_votes = total_votes.saturating_add(conviction.votes(capital));
+        });
+        Delegations::<T>::insert(
+            who,
+            Delegation { target: target.clone(), balance, conviction, unlock_at: None },
+        );
+        Self::update_governance_lock(who);
+
+        Self::deposit_event(Event::VotesDelegated {
+            delegator: who.clone(),
+            target,
+            conviction: conviction.to_u8(),
+            balance,
+        });
+        Ok(())
+    }
 
-        // 权重上限：相当于100万 DUST 的权重
-        let max_weight = 1000u128; // sqrt(1,000,000) = 1000
+    /// 撤销委托
+    ///
+    /// 委托金额继续锁定，直到信念锁定期与一个完整投票周期（投票期 + 揭示期）中较长者结束：
+    /// 被委托人在撤销前用这部分权重投出的选票计票后，委托人才能亲自投票。
+    pub(crate) fn do_undelegate(who: &T::AccountId) -> DispatchResult {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let (target, unlock_at) = Delegations::<T>::try_mutate(who, |maybe| -> Result<_, DispatchError> {
+            let delegation = maybe.as_mut().ok_or(Error::<T>::NotDelegating)?;
+            ensure!(delegation.unlock_at.is_none(), Error::<T>::NotDelegating);
+
+            let capital: u128 = delegation.balance.saturated_into();
+            DelegatedPower::<T>::mutate(&delegation.target, |(total_capital, total_votes)| {
+                *total_capital = total_capital.saturating_sub(capital);
+                *total_votes = total_votes.saturating_sub(delegation.conviction.votes(capital));
+            });
+
+            let ballot_span = T::VotingPeriod::get().saturating_add(T::RevealPeriod::get());
+            let lock_blocks = Self::conviction_lock_blocks(delegation.conviction).max(ballot_span);
+            let unlock_at = now.saturating_add(lock_blocks);
+            delegation.unlock_at = Some(unlock_at);
+            Ok((delegation.target.clone(), unlock_at))
+        })?;
+
+        Self::deposit_event(Event::VotesUndelegated { delegator: who.clone(), target, unlock_at });
+        Ok(())
+    }
 
-        sqrt_balance.min(max_weight)
+    /// 是否有尚未计票的亲自投票
+    fn has_running_votes(who: &T::AccountId) -> bool {
+        VoteLocks::<T>::iter_prefix(who).any(|(proposal_id, _)| {
+            Self::ballot_info(proposal_id).is_some_and(|info| {
+                matches!(info.status, ProposalStatus::Voting | ProposalStatus::Revealing)
+            })
+        })
     }
 
-    /// 计算参与权重（历史投票次数）
-    fn calculate_participation_weight(account: &T::AccountId) -> u128 {
-        let vote_count = VoteHistory::<T>::get(account).len() as u128;
+    /// 按当前投票锁与委托重设治理锁（取最大值）
+    pub(crate) fn update_governance_lock(who: &T::AccountId) {
+        let mut amount = Delegations::<T>::get(who)
+            .map(|delegation| delegation.balance)
+            .unwrap_or_else(Zero::zero);
+        for (_, (locked, _)) in VoteLocks::<T>::iter_prefix(who) {
+            amount = amount.max(locked);
+        }
 
-        match vote_count {
-            0..=2 => 10,      // 新手
-            3..=5 => 25,      // 活跃
-            6..=10 => 50,     // 资深
-            _ => 100,         // 元老
+        if amount.is_zero() {
+            T::Currency::remove_lock(GOVERNANCE_LOCK_ID, who);
+        } else {
+            T::Currency::set_lock(GOVERNANCE_LOCK_ID, who, amount, WithdrawReasons::all());
         }
     }
 
-    /// 计算贡献权重（推荐贡献 + 委员会成员）
-    fn calculate_contribution_weight(account: &T::AccountId) -> u128 {
-        let mut weight = 0u128;
+    // ========================================
+    // 阶段推进与计票
+    // ========================================
+
+    /// 推进所有活跃提案的阶段（每个区块开始时调用）
+    ///
+    /// 计票后的投票记录与承诺不在计票时一次性删除，而是排队后每个区块最多清理
+    /// `MaxVoteCleanupPerBlock` 条，权重按实际清理条数计算。
+    pub(crate) fn advance_proposals(now: BlockNumberFor<T>) -> Weight {
+        let percentage_ids = ActiveProposalIds::<T>::get();
+        let membership_ids = ActiveMembershipPriceProposalIds::<T>::get();
+        let count = (percentage_ids.len() + membership_ids.len()) as u64;
+
+        for proposal_id in percentage_ids {
+            Self::advance_percentage_proposal(proposal_id, now);
+        }
+        for proposal_id in membership_ids {
+            Self::advance_membership_price_proposal(proposal_id, now);
+        }
 
-        // 推荐贡献（每个成功推荐 +2 分，最多50人 = 100分）
-        let referral_count = Self::count_successful_referrals(account);
-        weight = weight.saturating_add(referral_count.min(50).saturating_mul(2));
+        T::DbWeight::get()
+            .reads(2 + count)
+            .saturating_add(Self::process_vote_cleanup())
+    }
 
-        // TODO: 技术委员会成员额外投票权重 +200
-        // 注意：虽然技术委员会有额外权重，但无法否决任何治理提案
-        // 所有提案都必须达到全民投票的参与率和支持率门槛
-        // if Self::is_council_member(account) {
-        //     weight = weight.saturating_add(200);
-        // }
+    /// 清理队列已满时暂缓计票，下个区块重试
+    fn vote_cleanup_queue_full() -> bool {
+        VoteCleanupQueue::<T>::decode_len().unwrap_or(0) >= T::MaxActiveProposals::get() as usize
+    }
 
-        weight.min(300)
+    /// 计票结束后将投票数据加入清理队列
+    fn queue_vote_cleanup(proposal_id: u64, family: ProposalFamily, clear_votes: bool) {
+        VoteCleanupQueue::<T>::mutate(|queue| {
+            let _ = queue.try_push(VoteCleanup { proposal_id, family, clear_votes });
+        });
     }
 
-    /// 计算整数平方根（牛顿迭代法）
-    fn integer_sqrt(n: u128) -> u128 {
-        if n == 0 {
-            return 0;
+    /// 按 `MaxVoteCleanupPerBlock` 分批清理投票记录与密封投票承诺，返回实际消耗的权重
+    ///
+    /// 投票记录用 `clear_prefix` 游标跨区块续删；承诺逐条删除并退还押金。
+    pub(crate) fn process_vote_cleanup() -> Weight {
+        let db = T::DbWeight::get();
+        let mut weight = db.reads(1);
+        let mut queue = VoteCleanupQueue::<T>::get();
+        if queue.is_empty() {
+            return weight;
         }
 
-        let mut x = n;
-        let mut y = (x + 1) / 2;
+        let mut budget = T::MaxVoteCleanupPerBlock::get();
+        while budget > 0 {
+            let Some(task) = queue.first_mut() else {
+                break;
+            };
 
-        while y < x {
-            x = y;
-            y = (x + n / x) / 2;
+            if task.clear_votes {
+                let cursor = VoteCleanupCursor::<T>::get();
+                let result = match task.family {
+                    ProposalFamily::Percentage =>
+                        ProposalVotes::<T>::clear_prefix(task.proposal_id, budget, cursor.as_ref().map(|c| c.as_slice())),
+                    ProposalFamily::MembershipPrice => MembershipPriceProposalVotes::<T>::clear_prefix(
+                        task.proposal_id,
+                        budget,
+                        cursor.as_ref().map(|c| c.as_slice()),
+                    ),
+                };
+                weight.saturating_accrue(db.reads_writes(result.loops as u64 + 1, result.unique as u64 + 1));
+                budget = budget.saturating_sub(result.loops);
+
+                match result.maybe_cursor {
+                    Some(raw) => {
+                        // 本区块额度用尽，记录游标下个区块继续
+                        match BoundedVec::<u8, ConstU32<256>>::try_from(raw) {
+                            Ok(cursor) => VoteCleanupCursor::<T>::put(cursor),
+                            Err(_) => VoteCleanupCursor::<T>::kill(),
+                        }
+                        break;
+                    },
+                    None => {
+                        VoteCleanupCursor::<T>::kill();
+                        task.clear_votes = false;
+                    },
+                }
+            } else {
+                let mut drained = 0u32;
+                for (voter, (_, deposit)) in
+                    BallotCommitments::<T>::drain_prefix(task.proposal_id).take(budget as usize)
+                {
+                    T::Currency::unreserve(&voter, deposit);
+                    drained = drained.saturating_add(1);
+                }
+                weight.saturating_accrue(db.reads_writes(2 * drained as u64 + 1, 2 * drained as u64));
+
+                if drained < budget {
+                    queue.remove(0);
+                }
+                budget = budget.saturating_sub(drained);
+            }
         }
 
-        x
+        VoteCleanupQueue::<T>::put(queue);
+        weight.saturating_add(db.writes(1))
     }
 
-    /// 统计成功推荐数量
-    /// 通过遍历 Sponsors 存储，统计 sponsor == account 的数量
-    fn count_successful_referrals(account: &T::AccountId) -> u128 {
-        // 遍历 Sponsors 存储，统计该账户作为推荐人的次数
-        let mut count = 0u128;
-        for (_who, sponsor) in pallet_affiliate_referral::Sponsors::<T>::iter() {
-            if &sponsor == account {
-                count = count.saturating_add(1);
-            }
+    fn advance_percentage_proposal(proposal_id: u64, now: BlockNumberFor<T>) {
+        let Some(mut proposal) = ActiveProposals::<T>::get(proposal_id) else {
+            return;
+        };
+
+        match PhaseStep::next(
+            &proposal.status,
+            proposal.ballot_mode,
+            proposal.voting_start,
+            proposal.voting_end,
+            proposal.reveal_end,
+            now,
+        ) {
+            PhaseStep::Stay => {},
+            PhaseStep::Enter(status) => {
+                proposal.status = status.clone();
+                ActiveProposals::<T>::insert(proposal_id, &proposal);
+                Self::note_phase_change(proposal_id, status);
+            },
+            PhaseStep::Tally => {
+                if Self::vote_cleanup_queue_full() {
+                    return;
+                }
+                let tally = crate::pallet::VoteTally::<T>::get(proposal_id);
+                let approval_rate = tally.approval_rate();
+                let participation_rate = tally.participation_rate(Self::total_issuance());
+
+                let passed = Self::check_proposal_passed(&proposal, &tally);
+                if passed {
+                    // 待执行队列已满时保持原状态，下个区块重试
+                    if ReadyProposalIds::<T>::try_mutate(|ids| ids.try_push(proposal_id)).is_err() {
+                        return;
+                    }
+                    proposal.status = ProposalStatus::Approved;
+                    ActiveProposals::<T>::insert(proposal_id, &proposal);
+                    ReadyForExecution::<T>::insert(proposal_id, &proposal);
+                    Self::deposit_event(Event::ProposalPassed {
+                        proposal_id,
+                        approval_rate,
+                        participation_rate,
+                        effective_block: proposal.effective_block,
+                    });
+                } else {
+                    ActiveProposals::<T>::remove(proposal_id);
+                    crate::pallet::VoteTally::<T>::remove(proposal_id);
+                    Self::return_proposal_deposit(&proposal_id);
+                    Self::start_rejection_cooldown(&proposal.proposer, now);
+                    Self::deposit_event(Event::ProposalRejected {
+                        proposal_id,
+                        approval_rate,
+                        participation_rate,
+                    });
+                }
+
+                Self::queue_vote_cleanup(proposal_id, ProposalFamily::Percentage, !passed);
+                ActiveProposalIds::<T>::mutate(|ids| ids.retain(|&id| id != proposal_id));
+                ActiveProposalsByAccount::<T>::mutate(&proposal.proposer, |ids| {
+                    ids.retain(|&id| id != proposal_id)
+                });
+            },
+        }
+    }
+
+    fn advance_membership_price_proposal(proposal_id: u64, now: BlockNumberFor<T>) {
+        let Some(mut proposal) = ActiveMembershipPriceProposals::<T>::get(proposal_id) else {
+            return;
+        };
+
+        match PhaseStep::next(
+            &proposal.status,
+            proposal.ballot_mode,
+            proposal.voting_start,
+            proposal.voting_end,
+            proposal.reveal_end,
+            now,
+        ) {
+            PhaseStep::Stay => {},
+            PhaseStep::Enter(status) => {
+                proposal.status = status.clone();
+                ActiveMembershipPriceProposals::<T>::insert(proposal_id, &proposal);
+                Self::note_phase_change(proposal_id, status);
+            },
+            PhaseStep::Tally => {
+                if Self::vote_cleanup_queue_full() {
+                    return;
+                }
+                let tally = MembershipPriceVoteTally::<T>::get(proposal_id);
+                let approval_rate = tally.approval_rate();
+                let participation_rate = tally.participation_rate(Self::total_issuance());
+
+                let passed = Self::check_membership_price_proposal_passed(&proposal, &tally);
+                if passed {
+                    if ReadyMembershipPriceProposalIds::<T>::try_mutate(|ids| ids.try_push(proposal_id))
+                        .is_err()
+                    {
+                        return;
+                    }
+                    proposal.status = ProposalStatus::Approved;
+                    ActiveMembershipPriceProposals::<T>::insert(proposal_id, &proposal);
+                    ReadyForMembershipPriceExecution::<T>::insert(proposal_id, &proposal);
+                    Self::deposit_event(Event::MembershipPriceProposalPassed {
+                        proposal_id,
+                        approval_rate,
+                        participation_rate,
+                        effective_block: proposal.effective_block,
+                    });
+                } else {
+                    ActiveMembershipPriceProposals::<T>::remove(proposal_id);
+                    MembershipPriceVoteTally::<T>::remove(proposal_id);
+                    Self::return_membership_price_proposal_deposit(&proposal_id);
+                    Self::start_rejection_cooldown(&proposal.proposer, now);
+                    Self::deposit_event(Event::MembershipPriceProposalRejected {
+                        proposal_id,
+                        approval_rate,
+                        participation_rate,
+                    });
+                }
+
+                Self::queue_vote_cleanup(proposal_id, ProposalFamily::MembershipPrice, !passed);
+                ActiveMembershipPriceProposalIds::<T>::mutate(|ids| ids.retain(|&id| id != proposal_id));
+                ActiveProposalsByAccount::<T>::mutate(&proposal.proposer, |ids| {
+                    ids.retain(|&id| id != proposal_id)
+                });
+            },
+        }
+    }
+
+    /// 阶段变更事件
+    fn note_phase_change(proposal_id: u64, status: ProposalStatus) {
+        match status {
+            ProposalStatus::Voting => Self::deposit_event(Event::ProposalVotingStarted { proposal_id }),
+            ProposalStatus::Revealing => Self::deposit_event(Event::ProposalRevealStarted { proposal_id }),
+            _ => {},
         }
-        count
+    }
+
+    /// 提案被拒绝后发起人进入冷却期
+    fn start_rejection_cooldown(proposer: &T::AccountId, now: BlockNumberFor<T>) {
+        ProposalCooldown::<T>::insert(proposer, now.saturating_add(REJECTION_COOLDOWN_BLOCKS.into()));
+    }
+
+    /// 总发行量（参与率基准）
+    fn total_issuance() -> u128 {
+        T::Currency::total_issuance().saturated_into()
     }
 
     // ========================================
     // 通过条件检查
     // ========================================
 
-    /// 检查分成比例提案是否通过（技术委员会无法否决，所有提案都使用全民投票）
-    pub fn check_proposal_passed(
-        _proposal: &PercentageAdjustmentProposal<T>,
-        tally: &VoteTally,
-    ) -> bool {
-        // 🔥 2025-11-13 重要修改：删除微调提案的技术委员会否决权
-        // 所有分成比例提案现在都必须通过全民投票，技术委员会无法否决
-
-        // 全民投票机制：最低参与率要求
-        // 总投票权 = 总发行量的平方根（归一化处理，避免巨鲸主导）
-        // 使用 pallet-balances 总发行量作为基准
-        let total_issuance: u128 = T::Currency::total_issuance().saturated_into();
-        let total_power = Self::integer_sqrt(total_issuance).max(100000u128);
-        let participation = tally.participation_rate(total_power);
-
-        // 最低参与率门槛：15%
-        if participation < Perbill::from_percent(15) {
+    /// 计票规则（两类提案共用）
+    ///
+    /// - 参与率（锁定金额 / 总发行量）低于 `MinTurnout` 时不通过
+    /// - 自适应支持率门槛：参与率越高，通过门槛越低
+    /// - `relaxed` 为 true 时支持率门槛降低 5%
+    pub fn tally_passes(tally: &VoteTally, relaxed: bool) -> bool {
+        let participation = tally.participation_rate(Self::total_issuance());
+        if participation < T::MinTurnout::get() {
             return false;
         }
 
-        // 自适应阈值：参与率越高，通过门槛越低
         let required_approval = if participation >= Perbill::from_percent(50) {
             Perbill::from_percent(50) // 50%参与 → 50%支持
         } else if participation >= Perbill::from_percent(30) {
             Perbill::from_percent(55) // 30%参与 → 55%支持
         } else {
-            Perbill::from_percent(60) // 15%参与 → 60%支持
+            Perbill::from_percent(60) // 低参与 → 60%支持
+        };
+        let required_approval = if relaxed {
+            required_approval.saturating_sub(Perbill::from_percent(5))
+        } else {
+            required_approval
         };
 
         tally.approval_rate() >= required_approval
     }
+
+    /// 检查分成比例提案是否通过（技术委员会无法否决，所有提案都使用全民投票）
+    pub fn check_proposal_passed(
+        _proposal: &PercentageAdjustmentProposal<T>,
+        tally: &VoteTally,
+    ) -> bool {
+        // 🔥 2025-11-13 重要修改：删除微调提案的技术委员会否决权
+        // 所有分成比例提案现在都必须通过全民投票，技术委员会无法否决
+        Self::tally_passes(tally, false)
+    }
+}
+
+/// 提案投票信息（投票引擎查询结果）
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct BallotInfo<BlockNumber> {
+    /// 提案族
+    pub family: ProposalFamily,
+    /// 提案状态
+    pub status: ProposalStatus,
+    /// 投票方式
+    pub mode: BallotMode,
+    /// 计票区块（投票结束或揭示结束）
+    pub closes_at: BlockNumber,
+}
+
+/// 阶段推进结果
+enum PhaseStep {
+    /// 保持当前阶段
+    Stay,
+    /// 进入新阶段
+    Enter(ProposalStatus),
+    /// 计票
+    Tally,
+}
+
+impl PhaseStep {
+    fn next<BlockNumber: PartialOrd + Copy>(
+        status: &ProposalStatus,
+        mode: BallotMode,
+        voting_start: Option<BlockNumber>,
+        voting_end: Option<BlockNumber>,
+        reveal_end: Option<BlockNumber>,
+        now: BlockNumber,
+    ) -> Self {
+        let reached = |at: Option<BlockNumber>| at.is_some_and(|at| now >= at);
+        match status {
+            ProposalStatus::Discussion if reached(voting_start) => PhaseStep::Enter(ProposalStatus::Voting),
+            ProposalStatus::Voting if reached(voting_end) => match mode {
+                BallotMode::Open => PhaseStep::Tally,
+                BallotMode::Sealed => PhaseStep::Enter(ProposalStatus::Revealing),
+            },
+            ProposalStatus::Revealing if !matches!(reveal_end, Some(at) if now < at) => PhaseStep::Tally,
+            _ => PhaseStep::Stay,
+        }
+    }
 }
 
 // ========================================
@@ -580,6 +1154,12 @@ pub struct MembershipPriceProposal<T: Config> {
 
     /// 投票结束时间
     pub voting_end: Option<BlockNumberFor<T>>,
+
+    /// 投票方式
+    pub ballot_mode: BallotMode,
+
+    /// 揭示结束时间（仅密封投票）
+    pub reveal_end: Option<BlockNumberFor<T>>,
 }
 
 impl<T: Config> MembershipPriceProposal<T> {
@@ -628,6 +1208,8 @@ impl<T: Config> MembershipPriceProposal<T> {
             created_at: current_block,
             voting_start: None,
             voting_end: None,
+            ballot_mode: BallotMode::Open,
+            reveal_end: None,
         })
     }
 
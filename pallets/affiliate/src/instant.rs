@@ -3,7 +3,7 @@
 //! 功能：
 //! - 实时转账分配
 //! - 推荐链验证
-//! - 立即到账
+//! - 立即到账（购买者未激活时暂存待解锁）
 //!
 //! 整合自：pallet-affiliate-instant
 
@@ -20,12 +20,14 @@ impl<T: Config> Pallet<T> {
     /// - buyer: 购买者/供奉者
     /// - distributable_amount: 可分配金额（已扣除系统费用）
     /// - levels: 分配层数（用于 Hybrid 模式）
+    /// - source: 收益来源（记入收益报表）
     ///
     /// 返回：实际分配总额
     pub fn do_instant_distribute(
         buyer: &T::AccountId,
         distributable_amount: BalanceOf<T>,
         levels: u8,
+        source: statement::EarningSource,
     ) -> BalanceOf<T> {
         // 获取推荐链
         let referral_chain = Self::get_referral_chain(buyer);
@@ -35,6 +37,8 @@ impl<T: Config> Pallet<T> {
 
         let mut total_distributed = BalanceOf::<T>::zero();
         let levels_to_process = levels.min(15) as usize;
+        let cycle = Self::current_cycle();
+        let vested = Self::is_buyer_vested(buyer);
 
         // 逐层分配
         for (index, referrer) in referral_chain.iter().enumerate().take(levels_to_process) {
@@ -62,6 +66,13 @@ impl<T: Config> Pallet<T> {
                 continue;
             }
 
+            // 购买者未激活或被标记：留存托管账户，待解锁
+            if !vested {
+                Self::hold_unvested_reward(cycle, referrer, buyer, index as u8 + 1, source, share);
+                total_distributed = total_distributed.saturating_add(share);
+                continue;
+            }
+
             // 立即转账
             if let Err(_) = T::Currency::transfer(
                 &T::EscrowPalletId::get().into_account_truncating(),
@@ -74,6 +85,7 @@ impl<T: Config> Pallet<T> {
             }
 
             total_distributed = total_distributed.saturating_add(share);
+            Self::record_earning(cycle, referrer, buyer, index as u8 + 1, source, share, true);
 
             // 发射事件
             Self::deposit_event(Event::InstantRewardDistributed {
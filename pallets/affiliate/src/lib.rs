@@ -25,6 +25,8 @@
 //! │  ⚡ 即时分成      →  instant.rs                           │
 //! │  📅 周结算        →  weekly.rs                            │
 //! │  📊 统一分配入口  →  distribute.rs                        │
+//! │  🧾 收益报表      →  statement.rs                         │
+//! │  🔒 奖励解锁/追回 →  vesting.rs                           │
 //! └──────────────────────────────────────────────────────────┘
 //! ```
 //!
@@ -71,14 +73,17 @@ mod escrow;
 mod instant;
 mod weekly;
 mod distribute;
+mod vesting;
 pub mod governance;  // 新增：治理模块，使用 pub mod 避免重复导出
+pub mod statement;
+pub mod runtime_api;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
 // 导出特定的治理类型，避免冲突
 pub use governance::{
-    Vote, Conviction, ProposalStatus,
+    Vote, Conviction, ProposalStatus, BallotMode, Delegation,
     PercentageAdjustmentProposal, VoteRecord, PercentageChangeRecord
 };
 
@@ -106,6 +111,11 @@ pub mod pallet {
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// 每个区块开始时推进提案阶段（讨论 → 投票 → 揭示 → 计票）
+        fn on_initialize(block_number: BlockNumberFor<T>) -> Weight {
+            Self::advance_proposals(block_number)
+        }
+
         /// 每个区块结束时执行，检查并执行已通过的提案
         fn on_finalize(block_number: BlockNumberFor<T>) {
             // 检查是否有需要执行的提案
@@ -137,6 +147,18 @@ pub mod pallet {
                     }
                 }
             }
+
+            // 年费价格提案（执行函数内部发射事件）
+            for (proposal_id, proposal) in ReadyForMembershipPriceExecution::<T>::iter() {
+                if proposal.effective_block <= block_number &&
+                    Self::execute_membership_price_change(&proposal_id, &proposal).is_ok()
+                {
+                    ReadyForMembershipPriceExecution::<T>::remove(&proposal_id);
+                    ReadyMembershipPriceProposalIds::<T>::mutate(|ids| ids.retain(|&id| id != proposal_id));
+                    ActiveMembershipPriceProposals::<T>::remove(&proposal_id);
+                    Self::return_membership_price_proposal_deposit(&proposal_id);
+                }
+            }
         }
 
         /// 🆕 空闲时清理过期数据（存储膨胀防护）
@@ -158,6 +180,13 @@ pub mod pallet {
                 );
             }
 
+            // 归档过期收益报表（每次最多处理20个账户报表）
+            if remaining_weight.saturating_sub(weight_used).ref_time() > base_weight.ref_time() * 20 {
+                weight_used = weight_used.saturating_add(
+                    Self::archive_old_statements(20)
+                );
+            }
+
             weight_used
         }
     }
@@ -168,7 +197,9 @@ pub mod pallet {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
         /// 货币系统（支持锁定和保留）
-        type Currency: Currency<Self::AccountId> + frame_support::traits::ReservableCurrency<Self::AccountId>;
+        type Currency: Currency<Self::AccountId>
+            + frame_support::traits::ReservableCurrency<Self::AccountId>
+            + frame_support::traits::LockableCurrency<Self::AccountId, Moment = BlockNumberFor<Self>>;
 
         /// 托管 PalletId（派生独立的托管账户）
         #[pallet::constant]
@@ -223,6 +254,46 @@ pub mod pallet {
         /// 保证金计算器（统一的 USD 价值动态计算）
         type DepositCalculator: pallet_trading_common::DepositCalculator<BalanceOf<Self>>;
 
+        // ========================================
+        // 治理投票周期配置
+        // ========================================
+
+        /// 讨论期（提案创建后到开始投票的区块数，可取消）
+        #[pallet::constant]
+        type DiscussionPeriod: Get<BlockNumberFor<Self>>;
+
+        /// 投票期区块数（密封投票时为提交承诺期）
+        #[pallet::constant]
+        type VotingPeriod: Get<BlockNumberFor<Self>>;
+
+        /// 揭示期区块数（仅密封投票）
+        #[pallet::constant]
+        type RevealPeriod: Get<BlockNumberFor<Self>>;
+
+        /// 最低参与率（投票锁定金额 / 总发行量）
+        #[pallet::constant]
+        type MinTurnout: Get<sp_runtime::Perbill>;
+
+        /// 密封投票承诺押金（揭示或计票后清理承诺时退还）
+        #[pallet::constant]
+        type VoteCommitmentDeposit: Get<BalanceOf<Self>>;
+
+        /// 计票后每个区块最多清理的投票记录 / 承诺条数
+        #[pallet::constant]
+        type MaxVoteCleanupPerBlock: Get<u32>;
+
+        // ========================================
+        // 收益报表配置
+        // ========================================
+
+        /// 单个周期报表最多记录的下线数（超出部分合并统计）
+        #[pallet::constant]
+        type MaxStatementDownlines: Get<u32>;
+
+        /// 每个账户保留的归档报表摘要数
+        #[pallet::constant]
+        type MaxArchivedStatements: Get<u32>;
+
         /// 权重信息
         type WeightInfo: crate::weights::WeightInfo;
     }
@@ -344,6 +415,63 @@ pub mod pallet {
     #[pallet::storage]
     pub type TotalWeeklyDistributed<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
+    // === 收益报表存储（4个）===
+
+    /// 周期收益报表：(周编号, 推荐人) → 报表（保留 HistoryRetentionWeeks 周）
+    #[pallet::storage]
+    pub type CycleStatements<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        u32,  // cycle
+        Blake2_128Concat,
+        T::AccountId,
+        statement::EarningStatement<T>,
+        ValueQuery,
+    >;
+
+    /// 归档报表摘要：账户 → 最近 MaxArchivedStatements 个周期
+    #[pallet::storage]
+    pub type ArchivedStatements<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<statement::ArchivedStatement<T>, T::MaxArchivedStatements>,
+        ValueQuery,
+    >;
+
+    /// 账户累计收益（待结算 / 已结算）
+    #[pallet::storage]
+    pub type AccountEarningTotals<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        statement::EarningTotals<T>,
+        ValueQuery,
+    >;
+
+    /// 下一个待归档的周期
+    #[pallet::storage]
+    pub type StatementArchiveCursor<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    // === 奖励解锁存储（2个）===
+
+    /// 未解锁推荐奖励：(被推荐人, 推荐人, 周编号) → 金额（资金留存在托管账户）
+    #[pallet::storage]
+    pub type UnvestedRewards<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, T::AccountId>,  // referee
+            NMapKey<Blake2_128Concat, T::AccountId>,  // referrer
+            NMapKey<Twox64Concat, u32>,               // cycle
+        ),
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// 未解锁奖励总额
+    #[pallet::storage]
+    pub type TotalUnvested<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
     // === 治理存储（12个）===
 
     /// 下一个提案ID
@@ -576,6 +704,48 @@ pub mod pallet {
         (BalanceOf<T>, BlockNumberFor<T>),  // (locked_amount, unlock_block)
     >;
 
+    /// 投票委托：委托人 → 委托信息
+    #[pallet::storage]
+    pub type Delegations<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        governance::Delegation<T>,
+    >;
+
+    /// 被委托人收到的委托合计：(委托金额, 委托权重)
+    #[pallet::storage]
+    pub type DelegatedPower<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        (u128, u128),
+        ValueQuery,
+    >;
+
+    /// 密封投票承诺：(提案ID, 投票人) → (blake2_256 承诺哈希, 押金)
+    #[pallet::storage]
+    pub type BallotCommitments<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64,
+        Blake2_128Concat,
+        T::AccountId,
+        ([u8; 32], BalanceOf<T>),
+    >;
+
+    /// 计票后待分批清理的投票数据（队首优先）
+    #[pallet::storage]
+    pub type VoteCleanupQueue<T: Config> = StorageValue<
+        _,
+        BoundedVec<governance::VoteCleanup, T::MaxActiveProposals>,
+        ValueQuery,
+    >;
+
+    /// 队首投票记录的 `clear_prefix` 游标
+    #[pallet::storage]
+    pub type VoteCleanupCursor<T: Config> = StorageValue<_, BoundedVec<u8, ConstU32<256>>>;
+
     // ========================================
     // 默认值
     // ========================================
@@ -656,6 +826,26 @@ pub mod pallet {
             amount: BalanceOf<T>,
         },
 
+        // === 奖励解锁事件 ===
+        /// 被推荐人未激活，奖励暂存待解锁
+        RewardVesting {
+            referrer: T::AccountId,
+            buyer: T::AccountId,
+            level: u8,
+            amount: BalanceOf<T>,
+        },
+        /// 已解锁奖励被领取
+        VestedRewardsClaimed {
+            referrer: T::AccountId,
+            referee: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// 未解锁奖励被治理追回至国库
+        UnvestedRewardsClawedBack {
+            referee: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+
         // === 周结算事件 ===
         /// 周期已结算
         CycleSettled {
@@ -762,6 +952,53 @@ pub mod pallet {
             new_prices_usdt: [u64; 4],
             effective_block: BlockNumberFor<T>,
         },
+
+        // ========================================
+        // 投票引擎事件
+        // ========================================
+
+        /// 提案进入投票期
+        ProposalVotingStarted {
+            proposal_id: u64,
+        },
+
+        /// 密封投票进入揭示期
+        ProposalRevealStarted {
+            proposal_id: u64,
+        },
+
+        /// 密封投票承诺已提交
+        VoteCommitted {
+            proposal_id: u64,
+            voter: T::AccountId,
+        },
+
+        /// 投票权已委托
+        /// conviction: 0=None, 1=Locked1x, ..., 6=Locked6x
+        VotesDelegated {
+            delegator: T::AccountId,
+            target: T::AccountId,
+            conviction: u8,
+            balance: BalanceOf<T>,
+        },
+
+        /// 委托已撤销（委托金额在 unlock_at 之后可解锁）
+        VotesUndelegated {
+            delegator: T::AccountId,
+            target: T::AccountId,
+            unlock_at: BlockNumberFor<T>,
+        },
+
+        /// 投票锁定已解除
+        VoteUnlocked {
+            who: T::AccountId,
+            proposal_id: u64,
+        },
+
+        /// 委托锁定已解除
+        DelegationUnlocked {
+            who: T::AccountId,
+        },
     }
 
     // ========================================
@@ -861,6 +1098,48 @@ pub mod pallet {
         InvalidVoteType,
         /// 无效的信念投票类型（必须为 0-6）
         InvalidConvictionType,
+
+        // === 投票引擎错误 ===
+        /// 无效的投票方式（必须为 0=Open, 1=Sealed）
+        InvalidBallotMode,
+        /// 密封投票提案必须通过 commit/reveal 投票
+        SealedBallotRequired,
+        /// 提案不是密封投票
+        NotSealedBallot,
+        /// 不在揭示期
+        RevealNotActive,
+        /// 未找到投票承诺
+        CommitmentNotFound,
+        /// 揭示内容与承诺不符
+        RevealMismatch,
+        /// 投票金额为0或超过可用余额
+        InvalidVoteBalance,
+        /// 已委托投票权，不能亲自投票
+        VoterIsDelegating,
+        /// 不能委托给自己
+        CannotDelegateToSelf,
+        /// 不允许委托链
+        DelegationChainNotAllowed,
+        /// 已存在委托
+        AlreadyDelegating,
+        /// 没有生效中的委托
+        NotDelegating,
+        /// 委托仍在锁定期
+        DelegationStillLocked,
+        /// 没有该提案的投票锁定
+        VoteLockNotFound,
+        /// 投票仍在锁定期
+        VoteStillLocked,
+        /// 存在尚未计票的投票
+        ActiveVotesExist,
+
+        // === 奖励解锁错误 ===
+        /// 被推荐人尚未激活或已被标记为女巫
+        RefereeNotVested,
+        /// 没有未解锁的奖励
+        NoUnvestedRewards,
+        /// 单次处理条目上限必须大于 0
+        InvalidMaxEntries,
     }
 
     // ========================================
@@ -1034,6 +1313,43 @@ pub mod pallet {
             Ok(())
         }
 
+        // === 奖励解锁接口（2个）===
+
+        /// 函数级中文注释：领取已解锁的推荐奖励
+        ///
+        /// 参数：
+        /// - `referee`: 产生奖励的被推荐人（需已激活且未被标记）
+        /// - `max_entries`: 单次最多领取的奖励条目数，剩余条目可再次调用领取
+        ///
+        /// 权重按 `max_entries` 预收，按实际处理条目数退还。
+        #[pallet::call_index(31)]
+        #[pallet::weight(T::WeightInfo::claim_vested_rewards(*max_entries))]
+        pub fn claim_vested_rewards(
+            origin: OriginFor<T>,
+            referee: T::AccountId,
+            max_entries: u32,
+        ) -> DispatchResultWithPostInfo {
+            let referrer = ensure_signed(origin)?;
+            let processed = Self::do_claim_vested_rewards(&referrer, &referee, max_entries)?;
+            Ok(Some(T::WeightInfo::claim_vested_rewards(processed)).into())
+        }
+
+        /// 函数级中文注释：追回被推荐人名下所有未解锁奖励（治理）
+        ///
+        /// 资金从托管账户转入国库，适用于被判定为女巫的被推荐人。
+        /// 单次最多处理 `max_entries` 条，条目较多时分批调用。
+        #[pallet::call_index(32)]
+        #[pallet::weight(T::WeightInfo::clawback_unvested(*max_entries))]
+        pub fn clawback_unvested(
+            origin: OriginFor<T>,
+            referee: T::AccountId,
+            max_entries: u32,
+        ) -> DispatchResultWithPostInfo {
+            T::AdminOrigin::ensure_origin(origin)?;
+            let processed = Self::do_clawback_unvested(&referee, max_entries)?;
+            Ok(Some(T::WeightInfo::clawback_unvested(processed)).into())
+        }
+
         // === 治理接口（5个）===
 
         /// 发起分成比例调整提案
@@ -1048,6 +1364,7 @@ pub mod pallet {
         /// - `title_cid`: 提案标题 IPFS CID
         /// - `description_cid`: 提案详情 IPFS CID
         /// - `rationale_cid`: 提案理由 IPFS CID
+        /// - `ballot_mode_type`: 投票方式（0=Open 公开投票, 1=Sealed 密封投票）
         #[pallet::call_index(50)]
         #[pallet::weight(Weight::from_parts(10_000_000, 0))]
         pub fn propose_percentage_adjustment(
@@ -1056,8 +1373,11 @@ pub mod pallet {
             title_cid: BoundedVec<u8, ConstU32<64>>,
             description_cid: BoundedVec<u8, ConstU32<64>>,
             rationale_cid: BoundedVec<u8, ConstU32<64>>,
+            ballot_mode_type: u8,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
+            let ballot_mode = governance::BallotMode::from_u8(ballot_mode_type)
+                .ok_or(Error::<T>::InvalidBallotMode)?;
 
             // 检查治理是否暂停
             ensure!(
@@ -1088,6 +1408,9 @@ pub mod pallet {
             // 创建提案
             let proposal_id = NextProposalId::<T>::get();
             let current_block = <frame_system::Pallet<T>>::block_number();
+            let (voting_start, voting_end, reveal_end) =
+                Self::schedule_ballot(current_block, ballot_mode);
+            let closes_at = reveal_end.unwrap_or(voting_end);
 
             let proposal = governance::PercentageAdjustmentProposal {
                 proposal_id,
@@ -1095,17 +1418,21 @@ pub mod pallet {
                 title_cid,
                 description_cid,
                 new_percentages: new_percentages.clone(),
-                effective_block: current_block + 43200u32.into(), // 3天后生效
+                effective_block: closes_at + 43200u32.into(), // 计票后3天生效
                 rationale_cid,
                 impact_analysis_cid: None,
                 status: governance::ProposalStatus::Discussion,
                 is_major: false, // 🔥 2025-11-13：统一设为false，因为现在都是全民投票
                 created_at: current_block,
-                voting_start: None,
-                voting_end: None,
+                voting_start: Some(voting_start),
+                voting_end: Some(voting_end),
+                ballot_mode,
+                reveal_end,
             };
 
             // 存储提案
+            ActiveProposalIds::<T>::try_mutate(|ids| ids.try_push(proposal_id))
+                .map_err(|_| Error::<T>::TooManyActiveProposals)?;
             ActiveProposals::<T>::insert(proposal_id, &proposal);
             ProposalDeposits::<T>::insert(proposal_id, (&who, deposit_amount));
 
@@ -1131,19 +1458,24 @@ pub mod pallet {
             Ok(())
         }
 
-        /// 对分成比例提案投票
+        /// 对分成比例提案投票（公开投票）
+        ///
+        /// 锁定 `balance` 参与投票，权重 = `balance` × 信念倍数 + 收到的委托权重。
+        /// 锁定金额在计票后继续锁定信念对应的周数，之后通过 `unlock_vote` 解锁。
         ///
         /// 参数:
         /// - `proposal_id`: 提案ID
         /// - `vote_type`: 投票选项（0=Aye, 1=Nay, 2=Abstain）
         /// - `conviction_type`: 信念投票（0=None, 1=Locked1x, ..., 6=Locked6x）
+        /// - `balance`: 锁定投票的金额
         #[pallet::call_index(51)]
-        #[pallet::weight(Weight::from_parts(5_000_000, 0))]
+        #[pallet::weight(T::WeightInfo::vote_on_percentage_proposal())]
         pub fn vote_on_percentage_proposal(
             origin: OriginFor<T>,
             proposal_id: u64,
             vote_type: u8,
             conviction_type: u8,
+            balance: BalanceOf<T>,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -1161,103 +1493,16 @@ pub mod pallet {
                 proposal.status == governance::ProposalStatus::Voting,
                 Error::<T>::VotingNotActive
             );
-
-            // 检查是否已投票
             ensure!(
-                !ProposalVotes::<T>::contains_key(proposal_id, &who),
-                Error::<T>::AlreadyVoted
+                proposal.ballot_mode == governance::BallotMode::Open,
+                Error::<T>::SealedBallotRequired
             );
 
-            // 转换 vote_type 为 Vote enum
-            let vote = match vote_type {
-                0 => governance::Vote::Aye,
-                1 => governance::Vote::Nay,
-                2 => governance::Vote::Abstain,
-                _ => return Err(Error::<T>::InvalidVoteType.into()),
-            };
-
-            // 转换 conviction_type 为 Conviction enum
-            let conviction = match conviction_type {
-                0 => governance::Conviction::None,
-                1 => governance::Conviction::Locked1x,
-                2 => governance::Conviction::Locked2x,
-                3 => governance::Conviction::Locked3x,
-                4 => governance::Conviction::Locked4x,
-                5 => governance::Conviction::Locked5x,
-                6 => governance::Conviction::Locked6x,
-                _ => return Err(Error::<T>::InvalidConvictionType.into()),
-            };
-
-            // 计算投票权重
-            let base_weight = Self::calculate_total_voting_power(&who);
-            let conviction_multiplier = conviction.multiplier();
-            let final_weight = base_weight
-                .saturating_mul(conviction_multiplier)
-                .saturating_div(10); // 除以10因为multiplier是10倍
-
-            // P2: 实现信念投票锁定
-            if conviction != governance::Conviction::None {
-                let lock_weeks = conviction.lock_weeks();
-                let blocks_per_week = BlocksPerWeek::<T>::get();
-                let lock_blocks: BlockNumberFor<T> = (lock_weeks as u32)
-                    .saturating_mul(blocks_per_week.saturated_into())
-                    .into();
-                let unlock_block = <frame_system::Pallet<T>>::block_number()
-                    .saturating_add(lock_blocks);
-                
-                // 计算锁定金额（基于余额的10%）
-                let balance = T::Currency::free_balance(&who);
-                let ten: BalanceOf<T> = 10u32.into();
-                let lock_amount = balance / ten;
-                
-                // 记录锁定信息（用于后续解锁）
-                VoteLocks::<T>::insert(&who, proposal_id, (lock_amount, unlock_block));
-            }
-
-            // 记录投票
-            let vote_record = governance::VoteRecord {
-                voter: who.clone(),
-                vote: vote.clone(),
-                conviction,
-                weight: final_weight,
-                timestamp: <frame_system::Pallet<T>>::block_number(),
-            };
-
-            ProposalVotes::<T>::insert(proposal_id, &who, &vote_record);
-
-            // 更新统计
-            VoteTally::<T>::mutate(proposal_id, |tally| {
-                match vote {
-                    governance::Vote::Aye => {
-                        tally.aye_votes = tally.aye_votes.saturating_add(final_weight);
-                    }
-                    governance::Vote::Nay => {
-                        tally.nay_votes = tally.nay_votes.saturating_add(final_weight);
-                    }
-                    governance::Vote::Abstain => {
-                        tally.abstain_votes = tally.abstain_votes.saturating_add(final_weight);
-                    }
-                }
-                tally.total_turnout = tally.total_turnout.saturating_add(final_weight);
-            });
+            let vote = governance::Vote::from_u8(vote_type).ok_or(Error::<T>::InvalidVoteType)?;
+            let conviction = governance::Conviction::from_u8(conviction_type)
+                .ok_or(Error::<T>::InvalidConvictionType)?;
 
-            // 更新投票历史
-            VoteHistory::<T>::try_mutate(&who, |history| -> DispatchResult {
-                history
-                    .try_push(proposal_id)
-                    .map_err(|_| Error::<T>::TooManyActiveProposals)?;
-                Ok(())
-            })?;
-
-            // 发射事件
-            Self::deposit_event(Event::VoteCast {
-                proposal_id,
-                voter: who,
-                vote_type: vote.to_u8(),
-                weight: final_weight,
-            });
-
-            Ok(())
+            Self::do_cast_vote(proposal_id, &who, vote, conviction, balance)
         }
 
         /// 取消提案（仅提案发起人可调用）
@@ -1285,6 +1530,7 @@ pub mod pallet {
 
             // 移除提案
             ActiveProposals::<T>::remove(proposal_id);
+            ActiveProposalIds::<T>::mutate(|ids| ids.retain(|&id| id != proposal_id));
 
             // 更新账户统计
             ActiveProposalsByAccount::<T>::mutate(&who, |proposals| {
@@ -1299,6 +1545,148 @@ pub mod pallet {
             Ok(())
         }
 
+        // ========================================
+        // 投票引擎接口（两类提案共用）
+        // ========================================
+
+        /// 提交密封投票承诺（投票期内）
+        ///
+        /// `commitment = blake2_256(SCALE(proposal_id, who, vote_type, conviction_type, balance, salt))`，
+        /// 可通过 `Pallet::ballot_commitment` 计算。投票期内可重复提交以覆盖旧承诺。
+        /// 首次提交时保留 `VoteCommitmentDeposit`，揭示或计票后清理承诺时退还。
+        #[pallet::call_index(53)]
+        #[pallet::weight(T::WeightInfo::commit_vote())]
+        pub fn commit_vote(
+            origin: OriginFor<T>,
+            proposal_id: u64,
+            commitment: [u8; 32],
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(!GovernancePaused::<T>::get(), Error::<T>::GovernancePausedError);
+
+            let info = Self::ballot_info(proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+            ensure!(info.mode == governance::BallotMode::Sealed, Error::<T>::NotSealedBallot);
+            ensure!(info.status == governance::ProposalStatus::Voting, Error::<T>::VotingNotActive);
+            ensure!(!Delegations::<T>::contains_key(&who), Error::<T>::VoterIsDelegating);
+
+            let deposit = match BallotCommitments::<T>::get(proposal_id, &who) {
+                Some((_, deposit)) => deposit,
+                None => {
+                    let deposit = T::VoteCommitmentDeposit::get();
+                    T::Currency::reserve(&who, deposit)?;
+                    deposit
+                },
+            };
+            BallotCommitments::<T>::insert(proposal_id, &who, (commitment, deposit));
+
+            Self::deposit_event(Event::VoteCommitted { proposal_id, voter: who });
+            Ok(())
+        }
+
+        /// 揭示密封投票（揭示期内）
+        ///
+        /// 揭示内容与承诺一致时按公开投票规则计票（锁定 `balance`），并退还承诺押金。
+        #[pallet::call_index(54)]
+        #[pallet::weight(T::WeightInfo::reveal_vote())]
+        pub fn reveal_vote(
+            origin: OriginFor<T>,
+            proposal_id: u64,
+            vote_type: u8,
+            conviction_type: u8,
+            balance: BalanceOf<T>,
+            salt: [u8; 32],
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let info = Self::ballot_info(proposal_id).ok_or(Error::<T>::ProposalNotFound)?;
+            ensure!(info.status == governance::ProposalStatus::Revealing, Error::<T>::RevealNotActive);
+
+            let (commitment, deposit) = BallotCommitments::<T>::get(proposal_id, &who)
+                .ok_or(Error::<T>::CommitmentNotFound)?;
+            ensure!(
+                commitment ==
+                    Self::ballot_commitment(proposal_id, &who, vote_type, conviction_type, balance, &salt),
+                Error::<T>::RevealMismatch
+            );
+
+            let vote = governance::Vote::from_u8(vote_type).ok_or(Error::<T>::InvalidVoteType)?;
+            let conviction = governance::Conviction::from_u8(conviction_type)
+                .ok_or(Error::<T>::InvalidConvictionType)?;
+
+            // 先退还承诺押金，揭示金额可包含押金部分
+            BallotCommitments::<T>::remove(proposal_id, &who);
+            T::Currency::unreserve(&who, deposit);
+            Self::do_cast_vote(proposal_id, &who, vote, conviction, balance)
+        }
+
+        /// 委托投票权
+        ///
+        /// 锁定 `balance`，被委托人之后的每张选票都计入 `balance` × 信念倍数的权重。
+        /// 委托期间委托人不能亲自投票；不允许委托链。
+        #[pallet::call_index(55)]
+        #[pallet::weight(T::WeightInfo::delegate_votes())]
+        pub fn delegate_votes(
+            origin: OriginFor<T>,
+            target: T::AccountId,
+            conviction_type: u8,
+            balance: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let conviction = governance::Conviction::from_u8(conviction_type)
+                .ok_or(Error::<T>::InvalidConvictionType)?;
+            Self::do_delegate(&who, target, conviction, balance)
+        }
+
+        /// 撤销委托
+        ///
+        /// 立即停止计入被委托人权重，委托金额在锁定期结束后通过 `unlock_delegation` 解锁。
+        #[pallet::call_index(56)]
+        #[pallet::weight(T::WeightInfo::undelegate_votes())]
+        pub fn undelegate_votes(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_undelegate(&who)
+        }
+
+        /// 解除单个提案的投票锁定（锁定期结束后）
+        #[pallet::call_index(57)]
+        #[pallet::weight(T::WeightInfo::unlock_vote())]
+        pub fn unlock_vote(origin: OriginFor<T>, proposal_id: u64) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let (_, unlock_block) = VoteLocks::<T>::get(&who, proposal_id)
+                .ok_or(Error::<T>::VoteLockNotFound)?;
+            ensure!(
+                <frame_system::Pallet<T>>::block_number() >= unlock_block,
+                Error::<T>::VoteStillLocked
+            );
+
+            VoteLocks::<T>::remove(&who, proposal_id);
+            Self::update_governance_lock(&who);
+
+            Self::deposit_event(Event::VoteUnlocked { who, proposal_id });
+            Ok(())
+        }
+
+        /// 解除已撤销委托的锁定（锁定期结束后）
+        #[pallet::call_index(58)]
+        #[pallet::weight(T::WeightInfo::unlock_delegation())]
+        pub fn unlock_delegation(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let delegation = Delegations::<T>::get(&who).ok_or(Error::<T>::NotDelegating)?;
+            let unlock_at = delegation.unlock_at.ok_or(Error::<T>::DelegationStillLocked)?;
+            ensure!(
+                <frame_system::Pallet<T>>::block_number() >= unlock_at,
+                Error::<T>::DelegationStillLocked
+            );
+
+            Delegations::<T>::remove(&who);
+            Self::update_governance_lock(&who);
+
+            Self::deposit_event(Event::DelegationUnlocked { who });
+            Ok(())
+        }
+
         /// 紧急暂停治理（仅技术委员会超级多数可调用）
         #[pallet::call_index(60)]
         #[pallet::weight(Weight::from_parts(2_000_000, 0))]
@@ -1339,6 +1727,8 @@ pub mod pallet {
         // ========================================
 
         /// 发起年费价格调整提案
+        ///
+        /// - `ballot_mode_type`: 投票方式（0=Open 公开投票, 1=Sealed 密封投票）
         #[pallet::call_index(70)]
         #[pallet::weight(Weight::from_parts(100_000_000, 0))]
         pub fn propose_membership_price_adjustment(
@@ -1347,8 +1737,11 @@ pub mod pallet {
             title_cid: BoundedVec<u8, ConstU32<64>>,
             description_cid: BoundedVec<u8, ConstU32<64>>,
             rationale_cid: BoundedVec<u8, ConstU32<64>>,
+            ballot_mode_type: u8,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
+            let ballot_mode = governance::BallotMode::from_u8(ballot_mode_type)
+                .ok_or(Error::<T>::InvalidBallotMode)?;
 
             // 检查治理是否暂停
             ensure!(
@@ -1381,7 +1774,7 @@ pub mod pallet {
 
             // 创建提案
             let current_block = frame_system::Pallet::<T>::block_number();
-            let proposal = governance::MembershipPriceProposal::<T>::new(
+            let mut proposal = governance::MembershipPriceProposal::<T>::new(
                 proposal_id,
                 who.clone(),
                 title_cid.clone(),
@@ -1391,6 +1784,16 @@ pub mod pallet {
                 current_block,
             ).map_err(|_| Error::<T>::PriceOutOfRange)?;
 
+            // 安排投票阶段，执行延迟从计票时起算
+            let (voting_start, voting_end, reveal_end) =
+                Self::schedule_ballot(current_block, ballot_mode);
+            let execution_delay = proposal.effective_block.saturating_sub(current_block);
+            proposal.effective_block = reveal_end.unwrap_or(voting_end).saturating_add(execution_delay);
+            proposal.voting_start = Some(voting_start);
+            proposal.voting_end = Some(voting_end);
+            proposal.ballot_mode = ballot_mode;
+            proposal.reveal_end = reveal_end;
+
             // 计算押金
             let deposit = proposal.calculate_deposit();
 
@@ -1398,6 +1801,8 @@ pub mod pallet {
             T::Currency::reserve(&who, deposit)?;
 
             // 存储提案
+            ActiveMembershipPriceProposalIds::<T>::try_mutate(|ids| ids.try_push(proposal_id))
+                .map_err(|_| Error::<T>::TooManyActiveProposals)?;
             ActiveMembershipPriceProposals::<T>::insert(proposal_id, &proposal);
             MembershipPriceProposalDeposits::<T>::insert(proposal_id, (&who, deposit));
 
@@ -1422,14 +1827,17 @@ pub mod pallet {
             Ok(())
         }
 
-        /// 对年费价格提案投票
+        /// 对年费价格提案投票（公开投票）
+        ///
+        /// 参数同 `vote_on_percentage_proposal`。
         #[pallet::call_index(71)]
-        #[pallet::weight(Weight::from_parts(50_000_000, 0))]
+        #[pallet::weight(T::WeightInfo::vote_on_membership_price_proposal())]
         pub fn vote_on_membership_price_proposal(
             origin: OriginFor<T>,
             proposal_id: u64,
             vote_type: u8,
             conviction_type: u8,
+            balance: BalanceOf<T>,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -1439,87 +1847,24 @@ pub mod pallet {
                 Error::<T>::GovernancePausedError
             );
 
-            // 验证提案是否存在
-            let _proposal = ActiveMembershipPriceProposals::<T>::get(proposal_id)
+            // 验证提案存在且在投票期
+            let proposal = ActiveMembershipPriceProposals::<T>::get(proposal_id)
                 .ok_or(Error::<T>::MembershipPriceProposalNotFound)?;
 
-            // 检查是否已投票
             ensure!(
-                !MembershipPriceProposalVotes::<T>::contains_key(proposal_id, &who),
-                Error::<T>::MembershipPriceAlreadyVoted
+                proposal.status == governance::ProposalStatus::Voting,
+                Error::<T>::MembershipPriceVotingNotActive
+            );
+            ensure!(
+                proposal.ballot_mode == governance::BallotMode::Open,
+                Error::<T>::SealedBallotRequired
             );
 
-            // 验证投票类型 (0=Aye, 1=Nay, 2=Abstain)
-            ensure!(vote_type <= 2, Error::<T>::InvalidVoteType);
-
-            // 验证信念投票类型 (0-6)
-            ensure!(conviction_type <= 6, Error::<T>::InvalidConvictionType);
-
-            // 转换投票类型
-            let vote = match vote_type {
-                0 => governance::Vote::Aye,
-                1 => governance::Vote::Nay,
-                _ => governance::Vote::Abstain,
-            };
-
-            let conviction = match conviction_type {
-                0 => governance::Conviction::None,
-                1 => governance::Conviction::Locked1x,
-                2 => governance::Conviction::Locked2x,
-                3 => governance::Conviction::Locked3x,
-                4 => governance::Conviction::Locked4x,
-                5 => governance::Conviction::Locked5x,
-                _ => governance::Conviction::Locked6x,
-            };
-
-            // 计算投票权重
-            let base_weight = Self::calculate_total_voting_power(&who);
-            let conviction_multiplier = conviction.multiplier();
-            let final_weight = base_weight.saturating_mul(conviction_multiplier).saturating_div(10);
-
-            // 创建投票记录
-            let vote_record = governance::VoteRecord {
-                voter: who.clone(),
-                vote: vote.clone(),
-                conviction: conviction.clone(),
-                weight: final_weight,
-                timestamp: frame_system::Pallet::<T>::block_number(),
-            };
-
-            // 存储投票
-            MembershipPriceProposalVotes::<T>::insert(proposal_id, &who, &vote_record);
-
-            // 更新投票统计
-            MembershipPriceVoteTally::<T>::mutate(proposal_id, |tally| {
-                match vote {
-                    governance::Vote::Aye => {
-                        tally.aye_votes = tally.aye_votes.saturating_add(final_weight);
-                    }
-                    governance::Vote::Nay => {
-                        tally.nay_votes = tally.nay_votes.saturating_add(final_weight);
-                    }
-                    governance::Vote::Abstain => {
-                        tally.abstain_votes = tally.abstain_votes.saturating_add(final_weight);
-                    }
-                }
-                tally.total_turnout = tally.total_turnout.saturating_add(final_weight);
-            });
-
-            // 更新账户投票历史
-            VoteHistory::<T>::mutate(&who, |history| {
-                let _ = history.try_push(proposal_id);
-            });
-
-            // 发射事件
-            Self::deposit_event(Event::MembershipPriceVoteCast {
-                proposal_id,
-                voter: who,
-                vote: vote.to_u8(),
-                conviction: conviction_type,
-                voting_power: final_weight.saturated_into(),
-            });
+            let vote = governance::Vote::from_u8(vote_type).ok_or(Error::<T>::InvalidVoteType)?;
+            let conviction = governance::Conviction::from_u8(conviction_type)
+                .ok_or(Error::<T>::InvalidConvictionType)?;
 
-            Ok(())
+            Self::do_cast_vote(proposal_id, &who, vote, conviction, balance)
         }
 
         /// 取消年费价格提案（仅提案发起人可调用）
@@ -1550,6 +1895,7 @@ pub mod pallet {
 
             // 清理提案数据
             ActiveMembershipPriceProposals::<T>::remove(proposal_id);
+            ActiveMembershipPriceProposalIds::<T>::mutate(|ids| ids.retain(|&id| id != proposal_id));
             MembershipPriceVoteTally::<T>::remove(proposal_id);
 
             // 清理投票记录
@@ -1861,64 +2207,13 @@ pub mod pallet {
         }
 
         /// 检查年费价格提案是否通过（技术委员会不可干预）
+        ///
+        /// 与分成比例提案使用同一计票规则，微调提案（非重大）支持率门槛降低5%。
         pub fn check_membership_price_proposal_passed(
             proposal: &governance::MembershipPriceProposal<T>,
             tally: &governance::VoteTally,
         ) -> bool {
-            // 直接调用内部检查逻辑
-            Self::check_membership_price_proposal_passed_internal(proposal, tally)
-        }
-
-        /// 年费价格提案通过检查的内部实现
-        fn check_membership_price_proposal_passed_internal(
-            proposal: &governance::MembershipPriceProposal<T>,
-            tally: &governance::VoteTally,
-        ) -> bool {
-            // 总投票权 = 总发行量的平方根（归一化处理，避免巨鲸主导）
-            let total_issuance: u128 = T::Currency::total_issuance().saturated_into();
-            let total_power = Self::integer_sqrt_internal(total_issuance).max(100000u128);
-            let participation = tally.participation_rate(total_power);
-
-            // 最低参与率要求：15%
-            if participation < sp_runtime::Perbill::from_percent(15) {
-                return false;
-            }
-
-            // 年费价格治理的自适应阈值（基于参与率）
-            // 注意：无论是微调还是重大提案都使用相同的全民投票逻辑
-            let required_approval = if participation >= sp_runtime::Perbill::from_percent(50) {
-                sp_runtime::Perbill::from_percent(50) // 50%参与 → 50%支持
-            } else if participation >= sp_runtime::Perbill::from_percent(30) {
-                sp_runtime::Perbill::from_percent(55) // 30%参与 → 55%支持
-            } else {
-                sp_runtime::Perbill::from_percent(60) // 15%参与 → 60%支持
-            };
-
-            // 对于微调年费价格提案，适当降低门槛以便于调整
-            let final_approval = if !proposal.is_major {
-                // 微调提案：在原基础上降低5%
-                required_approval.saturating_sub(sp_runtime::Perbill::from_percent(5))
-            } else {
-                // 重大提案：使用标准阈值
-                required_approval
-            };
-
-            tally.approval_rate() >= final_approval
-        }
-
-        /// 计算整数平方根（牛顿迭代法）
-        /// 用于归一化投票权重计算
-        fn integer_sqrt_internal(n: u128) -> u128 {
-            if n == 0 {
-                return 0;
-            }
-            let mut x = n;
-            let mut y = (x + 1) / 2;
-            while y < x {
-                x = y;
-                y = (x + n / x) / 2;
-            }
-            x
+            Self::tally_passes(tally, !proposal.is_major)
         }
 
         /// 计算提案押金金额（50 USDT 等值的 DUST）
@@ -1946,7 +2241,7 @@ impl<T: Config> types::AffiliateDistributor<T::AccountId, u128, BlockNumberFor<T
     fn distribute_rewards(
         buyer: &T::AccountId,
         amount: u128,
-        _target: Option<(u8, u64)>,
+        target: Option<(u8, u64)>,
     ) -> Result<u128, sp_runtime::DispatchError> {
         // 转换金额类型
         let balance_amount: BalanceOf<T> = amount.saturated_into();
@@ -1956,11 +2251,19 @@ impl<T: Config> types::AffiliateDistributor<T::AccountId, u128, BlockNumberFor<T
         }
 
         // 调用统一分配入口，根据结算模式自动选择分配方式
-        let distributed = Self::do_distribute_rewards(buyer, balance_amount, None)?;
+        let source = statement::EarningSource::from_target(target);
+        let distributed = Self::do_distribute_rewards(buyer, balance_amount, None, source)?;
         
         // 转换回 u128 返回
         Ok(distributed.saturated_into())
     }
+
+    fn note_check_in(who: &T::AccountId) {
+        pallet_affiliate_referral::Pallet::<T>::record_activity(
+            who,
+            pallet_affiliate_referral::ActivityKind::CheckIn,
+        );
+    }
 }
 
 // 🆕 2025-12-30：MembershipProvider trait 已移动到 pallet-affiliate-referral
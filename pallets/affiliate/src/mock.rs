@@ -5,7 +5,7 @@
 use crate as pallet_affiliate;
 use frame_support::{
     parameter_types,
-    traits::ConstU32,
+    traits::{ConstU32, ConstU64, Hooks},
     PalletId,
 };
 use sp_runtime::{
@@ -141,16 +141,52 @@ parameter_types! {
     
     /// 提案过期区块数（7天）
     pub const ProposalExpiry: u64 = 100800;
+
+    /// 讨论期（测试缩短为10个区块）
+    pub const DiscussionPeriod: u64 = 10;
+
+    /// 投票期（测试缩短为20个区块）
+    pub const VotingPeriod: u64 = 20;
+
+    /// 揭示期（测试缩短为10个区块）
+    pub const RevealPeriod: u64 = 10;
+
+    /// 最低参与率 20%
+    pub const MinTurnout: sp_runtime::Perbill = sp_runtime::Perbill::from_percent(20);
+
+    /// 密封投票承诺押金 1 DUST
+    pub const VoteCommitmentDeposit: u128 = 1_000_000_000_000;
+
+    /// 每个区块最多清理的投票数据条数（测试缩小为2）
+    pub const MaxVoteCleanupPerBlock: u32 = 2;
+
+    /// 单个报表最多记录的下线数（测试缩小为2）
+    pub const MaxStatementDownlines: u32 = 2;
+
+    /// 每个账户保留的归档报表数
+    pub const MaxArchivedStatements: u32 = 4;
 }
 
 // ========================================
 // Referral Pallet 配置
 // ========================================
 
+parameter_types! {
+    /// 激活所需活跃度积分（默认 0：所有购买者视为已激活，解锁测试中调高）
+    pub static ActivationThreshold: u32 = 0;
+}
+
 impl pallet_affiliate_referral::Config for Test {
     type MembershipProvider = MockMembershipProvider;
     type MaxCodeLen = MaxCodeLen;
     type MaxSearchHops = MaxSearchHops;
+    type GovernanceOrigin = frame_system::EnsureRoot<u64>;
+    type FundingReporterOrigin = frame_system::EnsureRoot<u64>;
+    type BindingWindow = ConstU64<10>;
+    type MaxBindingsPerWindow = ConstU32<5>;
+    type ActivationThreshold = ActivationThreshold;
+    type ClusterThreshold = ConstU32<3>;
+    type MaxFundingReports = ConstU32<10>;
     type WeightInfo = ();
 }
 
@@ -198,9 +234,17 @@ impl pallet_affiliate::Config for Test {
     type MaxReadyProposals = MaxReadyProposals;
     type HistoryRetentionWeeks = HistoryRetentionWeeks;
     type ProposalExpiry = ProposalExpiry;
-    type ProposalDeposit = frame_support::traits::ConstU128<50_000_000_000_000_000_000>; // 50 DUST
+    type ProposalDeposit = frame_support::traits::ConstU128<50_000_000_000_000>; // 50 DUST
     type ProposalDepositUsd = frame_support::traits::ConstU64<50_000_000>; // 50 USDT
     type DepositCalculator = (); // 使用空实现，返回兜底值
+    type DiscussionPeriod = DiscussionPeriod;
+    type VotingPeriod = VotingPeriod;
+    type RevealPeriod = RevealPeriod;
+    type MinTurnout = MinTurnout;
+    type VoteCommitmentDeposit = VoteCommitmentDeposit;
+    type MaxVoteCleanupPerBlock = MaxVoteCleanupPerBlock;
+    type MaxStatementDownlines = MaxStatementDownlines;
+    type MaxArchivedStatements = MaxArchivedStatements;
     type WeightInfo = ();
 }
 
@@ -258,12 +302,16 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 
 /// 函数级中文注释：前进到指定区块
 ///
+/// 每个区块依次执行 Affiliate 的 on_finalize / on_initialize（推进治理提案阶段）。
+///
 /// **参数**：
 /// - `n`: 目标区块号
 pub fn run_to_block(n: u64) {
     while System::block_number() < n {
+        Affiliate::on_finalize(System::block_number());
         System::set_block_number(System::block_number() + 1);
         Timestamp::set_timestamp((System::block_number() * 6000) as u64);
+        Affiliate::on_initialize(System::block_number());
     }
 }
 
@@ -0,0 +1,62 @@
+//! 联盟计酬 Runtime API 定义
+//!
+//! 提供链下查询接口，用于前端收益看板（周期报表、归档摘要、累计收益）
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use crate::statement::EarningSource;
+
+sp_api::decl_runtime_apis! {
+    /// 联盟计酬 Runtime API
+    pub trait AffiliateApi<AccountId, Balance>
+    where
+        AccountId: Codec,
+        Balance: Codec,
+    {
+        /// 获取当前周期编号
+        fn current_cycle() -> u32;
+
+        /// 获取账户指定周期的收益报表（未归档）
+        fn get_statement(who: AccountId, cycle: u32) -> Option<StatementInfo<AccountId, Balance>>;
+
+        /// 获取账户保留期内的全部周期报表（按周期升序）
+        fn get_recent_statements(who: AccountId) -> Vec<StatementInfo<AccountId, Balance>>;
+
+        /// 获取账户的归档报表摘要（按周期升序）
+        fn get_archived_statements(who: AccountId) -> Vec<ArchivedStatementInfo<Balance>>;
+
+        /// 获取账户累计收益
+        fn get_earning_totals(who: AccountId) -> EarningTotalsInfo<Balance>;
+    }
+}
+
+/// 周期收益报表 (用于 API 返回)
+#[derive(codec::Encode, codec::Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+pub struct StatementInfo<AccountId, Balance> {
+    pub cycle: u32,
+    /// 按层级（下标0 = 第1层）
+    pub by_level: Vec<Balance>,
+    pub by_source: Vec<(EarningSource, Balance)>,
+    pub by_downline: Vec<(AccountId, Balance)>,
+    /// 超出下线上限后的合计
+    pub other_downlines: Balance,
+    pub pending: Balance,
+    pub settled: Balance,
+}
+
+/// 归档报表摘要 (用于 API 返回)
+#[derive(codec::Encode, codec::Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+pub struct ArchivedStatementInfo<Balance> {
+    pub cycle: u32,
+    pub by_source: Vec<(EarningSource, Balance)>,
+    pub pending: Balance,
+    pub settled: Balance,
+}
+
+/// 累计收益 (用于 API 返回)
+#[derive(codec::Encode, codec::Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+pub struct EarningTotalsInfo<Balance> {
+    pub pending: Balance,
+    pub settled: Balance,
+}
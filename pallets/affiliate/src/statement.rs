@@ -0,0 +1,360 @@
+//! 函数级中文注释：联盟收益报表子模块
+//!
+//! 功能：
+//! - 按周期（与周结算相同的周编号）为每个推荐人记录收益报表
+//! - 报表维度：按层级、按来源（会员/占卜订单/直播礼物）、按下线
+//! - 待结算（周结算应得）与已结算（即时到账 + 周结算已发放）金额
+//! - 超过 `HistoryRetentionWeeks` 的报表在 on_idle 中归档为摘要（有界，保留最近 `MaxArchivedStatements` 条）
+//!
+//! 报表只做记账，不影响资金流向；前端通过 Runtime API 读取。
+
+use super::*;
+use crate::runtime_api::{ArchivedStatementInfo, EarningTotalsInfo, StatementInfo};
+use sp_runtime::traits::{Saturating, Zero, SaturatedConversion};
+use sp_std::vec::Vec;
+
+/// 占卜订单的目标域（`AffiliateDistributor::distribute_rewards` 的 target.0）
+pub const DIVINATION_ORDER_DOMAIN: u8 = 15;
+
+/// 直播礼物的目标域
+pub const LIVESTREAM_GIFT_DOMAIN: u8 = 16;
+
+/// 收益来源数量
+pub const EARNING_SOURCE_COUNT: usize = 4;
+
+/// 收益来源
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum EarningSource {
+    /// 会员费 / 订阅
+    Membership,
+    /// 占卜订单
+    DivinationOrder,
+    /// 直播礼物
+    LivestreamGift,
+    /// 其他
+    Other,
+}
+
+impl EarningSource {
+    /// 按分配目标识别来源（未指定目标的分配来自会员费与订阅）
+    pub fn from_target(target: Option<(u8, u64)>) -> Self {
+        match target {
+            None => EarningSource::Membership,
+            Some((DIVINATION_ORDER_DOMAIN, _)) => EarningSource::DivinationOrder,
+            Some((LIVESTREAM_GIFT_DOMAIN, _)) => EarningSource::LivestreamGift,
+            Some(_) => EarningSource::Other,
+        }
+    }
+
+    /// 在 `by_source` 数组中的下标
+    pub fn index(&self) -> usize {
+        match self {
+            EarningSource::Membership => 0,
+            EarningSource::DivinationOrder => 1,
+            EarningSource::LivestreamGift => 2,
+            EarningSource::Other => 3,
+        }
+    }
+
+    /// 购买者产生该来源收益时计入的活跃行为
+    pub fn activity_kind(&self) -> pallet_affiliate_referral::ActivityKind {
+        match self {
+            EarningSource::Membership => pallet_affiliate_referral::ActivityKind::Membership,
+            _ => pallet_affiliate_referral::ActivityKind::Order,
+        }
+    }
+}
+
+/// 周期收益报表
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct EarningStatement<T: Config> {
+    /// 按层级（下标0 = 第1层）
+    pub by_level: [BalanceOf<T>; 15],
+
+    /// 按来源（下标见 `EarningSource::index`）
+    pub by_source: [BalanceOf<T>; EARNING_SOURCE_COUNT],
+
+    /// 按下线（产生收益的购买者），按首次出现顺序
+    pub by_downline: BoundedVec<(T::AccountId, BalanceOf<T>), T::MaxStatementDownlines>,
+
+    /// 超出下线上限后的合计
+    pub other_downlines: BalanceOf<T>,
+
+    /// 待结算（周结算应得，尚未发放）
+    pub pending: BalanceOf<T>,
+
+    /// 已结算（即时到账 + 周结算已发放）
+    pub settled: BalanceOf<T>,
+}
+
+impl<T: Config> Default for EarningStatement<T> {
+    fn default() -> Self {
+        Self {
+            by_level: [Zero::zero(); 15],
+            by_source: [Zero::zero(); EARNING_SOURCE_COUNT],
+            by_downline: BoundedVec::default(),
+            other_downlines: Zero::zero(),
+            pending: Zero::zero(),
+            settled: Zero::zero(),
+        }
+    }
+}
+
+impl<T: Config> EarningStatement<T> {
+    /// 本周期收益合计
+    pub fn total(&self) -> BalanceOf<T> {
+        self.pending.saturating_add(self.settled)
+    }
+}
+
+/// 按来源数组展开为 (来源, 金额) 列表
+fn source_pairs<Balance: Copy>(by_source: &[Balance; EARNING_SOURCE_COUNT]) -> Vec<(EarningSource, Balance)> {
+    [
+        EarningSource::Membership,
+        EarningSource::DivinationOrder,
+        EarningSource::LivestreamGift,
+        EarningSource::Other,
+    ]
+    .into_iter()
+    .map(|source| (source, by_source[source.index()]))
+    .collect()
+}
+
+/// 归档报表摘要
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct ArchivedStatement<T: Config> {
+    /// 周期
+    pub cycle: u32,
+
+    /// 按来源
+    pub by_source: [BalanceOf<T>; EARNING_SOURCE_COUNT],
+
+    /// 待结算
+    pub pending: BalanceOf<T>,
+
+    /// 已结算
+    pub settled: BalanceOf<T>,
+}
+
+/// 账户累计收益
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+pub struct EarningTotals<T: Config> {
+    /// 待结算
+    pub pending: BalanceOf<T>,
+
+    /// 已结算
+    pub settled: BalanceOf<T>,
+}
+
+impl<T: Config> Default for EarningTotals<T> {
+    fn default() -> Self {
+        Self { pending: Zero::zero(), settled: Zero::zero() }
+    }
+}
+
+/// 函数级中文注释：收益报表实现
+impl<T: Config> Pallet<T> {
+    /// 函数级中文注释：当前周期编号（与周结算一致）
+    pub fn current_cycle() -> u32 {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let blocks_per_week: u32 = BlocksPerWeek::<T>::get().saturated_into();
+        if blocks_per_week == 0 {
+            return 0;
+        }
+        now.saturated_into::<u32>() / blocks_per_week
+    }
+
+    /// 函数级中文注释：记录一笔推荐收益
+    ///
+    /// 参数：
+    /// - cycle: 周期编号
+    /// - referrer: 获得收益的推荐人
+    /// - downline: 产生收益的购买者
+    /// - level: 层级（1-15）
+    /// - source: 收益来源
+    /// - amount: 金额
+    /// - settled: 是否已到账（即时分成为 true，周结算记账为 false）
+    pub(crate) fn record_earning(
+        cycle: u32,
+        referrer: &T::AccountId,
+        downline: &T::AccountId,
+        level: u8,
+        source: EarningSource,
+        amount: BalanceOf<T>,
+        settled: bool,
+    ) {
+        if amount.is_zero() {
+            return;
+        }
+
+        CycleStatements::<T>::mutate(cycle, referrer, |statement| {
+            let index = (level.saturating_sub(1) as usize).min(14);
+            statement.by_level[index] = statement.by_level[index].saturating_add(amount);
+            statement.by_source[source.index()] =
+                statement.by_source[source.index()].saturating_add(amount);
+
+            if let Some(entry) = statement.by_downline.iter_mut().find(|(who, _)| who == downline) {
+                entry.1 = entry.1.saturating_add(amount);
+            } else if statement.by_downline.try_push((downline.clone(), amount)).is_err() {
+                statement.other_downlines = statement.other_downlines.saturating_add(amount);
+            }
+
+            if settled {
+                statement.settled = statement.settled.saturating_add(amount);
+            } else {
+                statement.pending = statement.pending.saturating_add(amount);
+            }
+        });
+
+        AccountEarningTotals::<T>::mutate(referrer, |totals| {
+            if settled {
+                totals.settled = totals.settled.saturating_add(amount);
+            } else {
+                totals.pending = totals.pending.saturating_add(amount);
+            }
+        });
+    }
+
+    /// 函数级中文注释：周结算发放（或奖励解锁领取）后，将报表中的待结算转为已结算
+    pub(crate) fn record_settlement(cycle: u32, account: &T::AccountId, amount: BalanceOf<T>) {
+        Self::release_pending(cycle, account, amount, true);
+    }
+
+    /// 函数级中文注释：未解锁奖励被追回后，从报表的待结算中扣除
+    pub(crate) fn record_clawback(cycle: u32, account: &T::AccountId, amount: BalanceOf<T>) {
+        Self::release_pending(cycle, account, amount, false);
+    }
+
+    /// 函数级中文注释：扣减待结算金额，`settle` 为 true 时转入已结算
+    fn release_pending(cycle: u32, account: &T::AccountId, amount: BalanceOf<T>, settle: bool) {
+        if CycleStatements::<T>::contains_key(cycle, account) {
+            CycleStatements::<T>::mutate(cycle, account, |statement| {
+                let moved = amount.min(statement.pending);
+                statement.pending = statement.pending.saturating_sub(moved);
+                if settle {
+                    statement.settled = statement.settled.saturating_add(moved);
+                }
+            });
+        } else {
+            // 报表已归档
+            ArchivedStatements::<T>::mutate(account, |archive| {
+                if let Some(entry) = archive.iter_mut().find(|entry| entry.cycle == cycle) {
+                    let moved = amount.min(entry.pending);
+                    entry.pending = entry.pending.saturating_sub(moved);
+                    if settle {
+                        entry.settled = entry.settled.saturating_add(moved);
+                    }
+                }
+            });
+        }
+
+        AccountEarningTotals::<T>::mutate(account, |totals| {
+            let moved = amount.min(totals.pending);
+            totals.pending = totals.pending.saturating_sub(moved);
+            if settle {
+                totals.settled = totals.settled.saturating_add(moved);
+            }
+        });
+    }
+
+    /// 函数级中文注释：归档过期报表（每次最多处理 max_accounts 个账户报表）
+    ///
+    /// 早于 `当前周期 - HistoryRetentionWeeks` 的报表压缩为摘要，
+    /// 写入账户的归档列表（满额时丢弃最早的摘要）。
+    pub fn archive_old_statements(max_accounts: u32) -> Weight {
+        let cutoff = Self::current_cycle().saturating_sub(T::HistoryRetentionWeeks::get());
+        let mut cycle = StatementArchiveCursor::<T>::get();
+        let mut archived = 0u32;
+        let mut scanned = 0u32;
+
+        while cycle < cutoff && archived < max_accounts && scanned < max_accounts {
+            scanned = scanned.saturating_add(1);
+
+            let drained: Vec<_> = CycleStatements::<T>::drain_prefix(cycle)
+                .take(max_accounts.saturating_sub(archived) as usize)
+                .collect();
+
+            for (account, statement) in drained {
+                ArchivedStatements::<T>::mutate(&account, |archive| {
+                    if archive.is_full() {
+                        archive.remove(0);
+                    }
+                    let _ = archive.try_push(ArchivedStatement {
+                        cycle,
+                        by_source: statement.by_source,
+                        pending: statement.pending,
+                        settled: statement.settled,
+                    });
+                });
+                archived = archived.saturating_add(1);
+            }
+
+            if CycleStatements::<T>::iter_prefix(cycle).next().is_some() {
+                break;
+            }
+            cycle = cycle.saturating_add(1);
+        }
+
+        StatementArchiveCursor::<T>::put(cycle);
+
+        T::DbWeight::get().reads_writes(
+            2u64.saturating_add(scanned as u64).saturating_add(archived as u64),
+            1u64.saturating_add(archived.saturating_mul(2) as u64),
+        )
+    }
+
+    // ========================================
+    // 查询（供 Runtime API 使用）
+    // ========================================
+
+    /// 函数级中文注释：查询账户指定周期的收益报表
+    pub fn statement_info(
+        who: &T::AccountId,
+        cycle: u32,
+    ) -> Option<StatementInfo<T::AccountId, BalanceOf<T>>> {
+        if !CycleStatements::<T>::contains_key(cycle, who) {
+            return None;
+        }
+        let statement = CycleStatements::<T>::get(cycle, who);
+        Some(StatementInfo {
+            cycle,
+            by_level: statement.by_level.to_vec(),
+            by_source: source_pairs(&statement.by_source),
+            by_downline: statement.by_downline.into_inner(),
+            other_downlines: statement.other_downlines,
+            pending: statement.pending,
+            settled: statement.settled,
+        })
+    }
+
+    /// 函数级中文注释：查询账户保留期内的全部周期报表（按周期升序）
+    pub fn recent_statements(who: &T::AccountId) -> Vec<StatementInfo<T::AccountId, BalanceOf<T>>> {
+        let current = Self::current_cycle();
+        let first = current
+            .saturating_sub(T::HistoryRetentionWeeks::get())
+            .max(StatementArchiveCursor::<T>::get());
+        (first..=current).filter_map(|cycle| Self::statement_info(who, cycle)).collect()
+    }
+
+    /// 函数级中文注释：查询账户的归档报表摘要
+    pub fn archived_statements(who: &T::AccountId) -> Vec<ArchivedStatementInfo<BalanceOf<T>>> {
+        ArchivedStatements::<T>::get(who)
+            .into_iter()
+            .map(|entry| ArchivedStatementInfo {
+                cycle: entry.cycle,
+                by_source: source_pairs(&entry.by_source),
+                pending: entry.pending,
+                settled: entry.settled,
+            })
+            .collect()
+    }
+
+    /// 函数级中文注释：查询账户累计收益
+    pub fn earning_totals(who: &T::AccountId) -> EarningTotalsInfo<BalanceOf<T>> {
+        let totals = AccountEarningTotals::<T>::get(who);
+        EarningTotalsInfo { pending: totals.pending, settled: totals.settled }
+    }
+}
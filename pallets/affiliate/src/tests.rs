@@ -333,6 +333,525 @@ fn test_resume_governance() {
     });
 }
 
+// ========================================
+// 治理投票引擎测试
+// ========================================
+
+/// 发起一个分成比例提案（在区块1发起：第11块开始投票，第31块投票结束，密封投票第41块揭示结束）
+fn propose(proposer: u64, ballot_mode_type: u8) -> u64 {
+    let percentages: crate::types::LevelPercents =
+        vec![25, 20, 15, 10, 5, 3, 2, 2, 2, 1, 1, 1, 1, 1, 1].try_into().unwrap();
+    let cid: BoundedVec<u8, frame_support::traits::ConstU32<64>> = b"QmCid".to_vec().try_into().unwrap();
+    let proposal_id = NextProposalId::<Test>::get();
+    assert_ok!(Affiliate::propose_percentage_adjustment(
+        RuntimeOrigin::signed(proposer),
+        percentages,
+        cid.clone(),
+        cid.clone(),
+        cid,
+        ballot_mode_type,
+    ));
+    proposal_id
+}
+
+const DUST: u128 = 1_000_000_000_000;
+
+#[test]
+fn test_proposal_lifecycle_open_vote_passes() {
+    new_test_ext().execute_with(|| {
+        let id = propose(1, 0);
+        assert!(ActiveProposalIds::<Test>::get().contains(&id));
+
+        // 讨论期不能投票
+        assert_noop!(
+            Affiliate::vote_on_percentage_proposal(RuntimeOrigin::signed(2), id, 0, 0, 1_000 * DUST),
+            Error::<Test>::VotingNotActive
+        );
+
+        run_to_block(11);
+        assert_eq!(ActiveProposals::<Test>::get(id).unwrap().status, crate::ProposalStatus::Voting);
+
+        assert_ok!(Affiliate::vote_on_percentage_proposal(RuntimeOrigin::signed(2), id, 0, 0, 10_000 * DUST));
+        assert_ok!(Affiliate::vote_on_percentage_proposal(RuntimeOrigin::signed(3), id, 0, 0, 10_000 * DUST));
+        assert_ok!(Affiliate::vote_on_percentage_proposal(RuntimeOrigin::signed(4), id, 1, 0, 5_000 * DUST));
+        assert_noop!(
+            Affiliate::vote_on_percentage_proposal(RuntimeOrigin::signed(4), id, 0, 0, 1_000 * DUST),
+            Error::<Test>::AlreadyVoted
+        );
+
+        let tally = VoteTally::<Test>::get(id);
+        assert_eq!(tally.aye_votes, 20_000 * DUST);
+        assert_eq!(tally.nay_votes, 5_000 * DUST);
+        assert_eq!(tally.total_turnout, 25_000 * DUST);
+
+        run_to_block(31);
+        let proposal = ActiveProposals::<Test>::get(id).unwrap();
+        assert_eq!(proposal.status, crate::ProposalStatus::Approved);
+        assert!(ReadyForExecution::<Test>::contains_key(id));
+        assert!(!ActiveProposalIds::<Test>::get().contains(&id));
+    });
+}
+
+#[test]
+fn test_proposal_rejected_below_min_turnout() {
+    new_test_ext().execute_with(|| {
+        let id = propose(1, 0);
+        let reserved = Balances::reserved_balance(1);
+        assert!(reserved > 0);
+
+        run_to_block(11);
+        // 全票支持但参与金额不足总发行量的 20%
+        assert_ok!(Affiliate::vote_on_percentage_proposal(RuntimeOrigin::signed(2), id, 0, 0, 10_000 * DUST));
+
+        run_to_block(31);
+        assert!(ActiveProposals::<Test>::get(id).is_none());
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(ProposalCooldown::<Test>::get(1), Some(31 + crate::governance::REJECTION_COOLDOWN_BLOCKS as u64));
+    });
+}
+
+#[test]
+fn test_conviction_multiplies_weight_and_locks_balance() {
+    new_test_ext().execute_with(|| {
+        let id = propose(1, 0);
+        run_to_block(11);
+
+        assert_noop!(
+            Affiliate::vote_on_percentage_proposal(RuntimeOrigin::signed(2), id, 0, 1, 20_000 * DUST),
+            Error::<Test>::InvalidVoteBalance
+        );
+        assert_ok!(Affiliate::vote_on_percentage_proposal(RuntimeOrigin::signed(2), id, 0, 1, 4_000 * DUST));
+
+        let record = ProposalVotes::<Test>::get(id, 2).unwrap();
+        assert_eq!(record.weight, 6_000 * DUST); // x1.5
+        assert_eq!(record.capital, 4_000 * DUST);
+
+        // 锁定 = 计票区块 + 1周
+        let (locked, unlock_block) = VoteLocks::<Test>::get(2, id).unwrap();
+        assert_eq!(locked, 4_000 * DUST);
+        assert_eq!(unlock_block, 31 + 100800);
+        assert_eq!(pallet_balances::Locks::<Test>::get(2)[0].amount, 4_000 * DUST);
+
+        assert_noop!(
+            Affiliate::unlock_vote(RuntimeOrigin::signed(2), id),
+            Error::<Test>::VoteStillLocked
+        );
+    });
+}
+
+#[test]
+fn test_unlock_vote_after_tally() {
+    new_test_ext().execute_with(|| {
+        let id = propose(1, 0);
+        run_to_block(11);
+        assert_ok!(Affiliate::vote_on_percentage_proposal(RuntimeOrigin::signed(2), id, 0, 0, 3_000 * DUST));
+        assert_eq!(pallet_balances::Locks::<Test>::get(2).len(), 1);
+
+        run_to_block(31);
+        assert_ok!(Affiliate::unlock_vote(RuntimeOrigin::signed(2), id));
+        assert!(VoteLocks::<Test>::get(2, id).is_none());
+        assert!(pallet_balances::Locks::<Test>::get(2).is_empty());
+    });
+}
+
+#[test]
+fn test_sealed_ballot_commit_reveal() {
+    new_test_ext().execute_with(|| {
+        let id = propose(1, 1);
+        run_to_block(11);
+
+        assert_noop!(
+            Affiliate::vote_on_percentage_proposal(RuntimeOrigin::signed(2), id, 0, 0, 10_000 * DUST),
+            Error::<Test>::SealedBallotRequired
+        );
+
+        let salt = [7u8; 32];
+        let amount = 10_000 * DUST;
+        for voter in [2u64, 3, 4] {
+            let commitment = Affiliate::ballot_commitment(id, &voter, 0, 0, amount, &salt);
+            assert_ok!(Affiliate::commit_vote(RuntimeOrigin::signed(voter), id, commitment));
+        }
+        // 提交承诺保留押金，覆盖旧承诺不重复保留
+        assert_eq!(Balances::reserved_balance(2), VoteCommitmentDeposit::get());
+        let commitment = Affiliate::ballot_commitment(id, &2, 0, 0, amount, &salt);
+        assert_ok!(Affiliate::commit_vote(RuntimeOrigin::signed(2), id, commitment));
+        assert_eq!(Balances::reserved_balance(2), VoteCommitmentDeposit::get());
+        // 提交期内不计票
+        assert_eq!(VoteTally::<Test>::get(id).total_turnout, 0);
+        assert_noop!(
+            Affiliate::reveal_vote(RuntimeOrigin::signed(2), id, 0, 0, amount, salt),
+            Error::<Test>::RevealNotActive
+        );
+
+        run_to_block(31);
+        assert_eq!(ActiveProposals::<Test>::get(id).unwrap().status, crate::ProposalStatus::Revealing);
+        assert_noop!(
+            Affiliate::reveal_vote(RuntimeOrigin::signed(2), id, 1, 0, amount, salt),
+            Error::<Test>::RevealMismatch
+        );
+        assert_noop!(
+            Affiliate::reveal_vote(RuntimeOrigin::signed(5), id, 0, 0, amount, salt),
+            Error::<Test>::CommitmentNotFound
+        );
+        assert_ok!(Affiliate::reveal_vote(RuntimeOrigin::signed(2), id, 0, 0, amount, salt));
+        assert_ok!(Affiliate::reveal_vote(RuntimeOrigin::signed(3), id, 0, 0, amount, salt));
+        assert_ok!(Affiliate::reveal_vote(RuntimeOrigin::signed(4), id, 0, 0, amount, salt));
+        assert!(BallotCommitments::<Test>::get(id, 2).is_none());
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(VoteTally::<Test>::get(id).aye_votes, 30_000 * DUST);
+
+        run_to_block(41);
+        assert_eq!(ActiveProposals::<Test>::get(id).unwrap().status, crate::ProposalStatus::Approved);
+    });
+}
+
+#[test]
+fn test_unrevealed_commitment_deposit_refunded_after_tally() {
+    new_test_ext().execute_with(|| {
+        let id = propose(1, 1);
+        run_to_block(11);
+
+        let salt = [7u8; 32];
+        let commitment = Affiliate::ballot_commitment(id, &5, 0, 0, 1_000 * DUST, &salt);
+        assert_ok!(Affiliate::commit_vote(RuntimeOrigin::signed(5), id, commitment));
+        assert_eq!(Balances::reserved_balance(5), VoteCommitmentDeposit::get());
+
+        // 未揭示，计票后清理承诺并退还押金
+        run_to_block(41);
+        assert!(ActiveProposals::<Test>::get(id).is_none());
+        assert!(BallotCommitments::<Test>::get(id, 5).is_none());
+        assert_eq!(Balances::reserved_balance(5), 0);
+        assert!(VoteCleanupQueue::<Test>::get().is_empty());
+    });
+}
+
+#[test]
+fn test_rejected_proposal_votes_cleared_in_batches() {
+    new_test_ext().execute_with(|| {
+        let id = propose(1, 0);
+        run_to_block(11);
+        for voter in 2u64..=6 {
+            assert_ok!(Affiliate::vote_on_percentage_proposal(RuntimeOrigin::signed(voter), id, 0, 0, DUST));
+        }
+
+        // 参与率不足被拒绝，投票记录每个区块最多清理 MaxVoteCleanupPerBlock 条
+        run_to_block(31);
+        assert!(ActiveProposals::<Test>::get(id).is_none());
+        assert_eq!(ProposalVotes::<Test>::iter_prefix(id).count(), 3);
+        assert_eq!(VoteCleanupQueue::<Test>::get().len(), 1);
+
+        run_to_block(32);
+        assert_eq!(ProposalVotes::<Test>::iter_prefix(id).count(), 1);
+
+        run_to_block(34);
+        assert_eq!(ProposalVotes::<Test>::iter_prefix(id).count(), 0);
+        assert!(VoteCleanupQueue::<Test>::get().is_empty());
+        assert!(VoteCleanupCursor::<Test>::get().is_none());
+    });
+}
+
+#[test]
+fn test_delegated_power_counts_for_target() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Affiliate::delegate_votes(RuntimeOrigin::signed(3), 3, 0, 1_000 * DUST),
+            Error::<Test>::CannotDelegateToSelf
+        );
+        assert_ok!(Affiliate::delegate_votes(RuntimeOrigin::signed(3), 2, 2, 10_000 * DUST));
+        assert_eq!(DelegatedPower::<Test>::get(2), (10_000 * DUST, 20_000 * DUST));
+        // 不允许委托链
+        assert_noop!(
+            Affiliate::delegate_votes(RuntimeOrigin::signed(4), 3, 0, 1_000 * DUST),
+            Error::<Test>::DelegationChainNotAllowed
+        );
+        assert_noop!(
+            Affiliate::delegate_votes(RuntimeOrigin::signed(2), 5, 0, 1_000 * DUST),
+            Error::<Test>::DelegationChainNotAllowed
+        );
+
+        let id = propose(1, 0);
+        run_to_block(11);
+        assert_noop!(
+            Affiliate::vote_on_percentage_proposal(RuntimeOrigin::signed(3), id, 1, 0, 1_000 * DUST),
+            Error::<Test>::VoterIsDelegating
+        );
+        assert_ok!(Affiliate::vote_on_percentage_proposal(RuntimeOrigin::signed(2), id, 0, 0, 1_000 * DUST));
+
+        let tally = VoteTally::<Test>::get(id);
+        assert_eq!(tally.aye_votes, 21_000 * DUST);
+        assert_eq!(tally.total_turnout, 11_000 * DUST);
+    });
+}
+
+#[test]
+fn test_undelegate_and_unlock_delegation() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Affiliate::delegate_votes(RuntimeOrigin::signed(3), 2, 0, 5_000 * DUST));
+        assert_eq!(pallet_balances::Locks::<Test>::get(3)[0].amount, 5_000 * DUST);
+        assert_noop!(
+            Affiliate::unlock_delegation(RuntimeOrigin::signed(3)),
+            Error::<Test>::DelegationStillLocked
+        );
+
+        assert_ok!(Affiliate::undelegate_votes(RuntimeOrigin::signed(3)));
+        assert_eq!(DelegatedPower::<Test>::get(2), (0, 0));
+        // 无信念锁定时至少锁定一个完整投票周期（投票期 + 揭示期）
+        assert_eq!(Delegations::<Test>::get(3).unwrap().unlock_at, Some(1 + 30));
+        assert_noop!(
+            Affiliate::undelegate_votes(RuntimeOrigin::signed(3)),
+            Error::<Test>::NotDelegating
+        );
+
+        run_to_block(31);
+        assert_ok!(Affiliate::unlock_delegation(RuntimeOrigin::signed(3)));
+        assert!(Delegations::<Test>::get(3).is_none());
+        assert!(pallet_balances::Locks::<Test>::get(3).is_empty());
+    });
+}
+
+// ========================================
+// 收益报表测试
+// ========================================
+
+use crate::statement::EarningSource;
+use frame_support::traits::Currency;
+
+/// 建立推荐链 1 → 2 → 3，并为托管账户注资
+fn setup_chain_and_escrow() {
+    assert_ok!(Affiliate::claim_code(RuntimeOrigin::signed(2), b"BOBCODE1".to_vec()));
+    assert_ok!(Affiliate::claim_code(RuntimeOrigin::signed(3), b"CHARLIE1".to_vec()));
+    assert_ok!(Affiliate::bind_sponsor(RuntimeOrigin::signed(1), b"BOBCODE1".to_vec()));
+    assert_ok!(Affiliate::bind_sponsor(RuntimeOrigin::signed(2), b"CHARLIE1".to_vec()));
+    let _ = Balances::deposit_creating(&Affiliate::escrow_account(), 10_000 * DUST);
+}
+
+#[test]
+fn test_earning_source_from_target() {
+    assert_eq!(EarningSource::from_target(None), EarningSource::Membership);
+    assert_eq!(EarningSource::from_target(Some((15, 7))), EarningSource::DivinationOrder);
+    assert_eq!(EarningSource::from_target(Some((16, 7))), EarningSource::LivestreamGift);
+    assert_eq!(EarningSource::from_target(Some((3, 7))), EarningSource::Other);
+}
+
+#[test]
+fn test_instant_distribution_records_statement() {
+    new_test_ext().execute_with(|| {
+        setup_chain_and_escrow();
+
+        Affiliate::do_instant_distribute(&1, 1_000 * DUST, 15, EarningSource::DivinationOrder);
+
+        let cycle = Affiliate::current_cycle();
+        let l1 = CycleStatements::<Test>::get(cycle, 2);
+        assert_eq!(l1.by_level[0], 300 * DUST);
+        assert_eq!(l1.by_source[EarningSource::DivinationOrder.index()], 300 * DUST);
+        assert_eq!(l1.by_downline.to_vec(), vec![(1, 300 * DUST)]);
+        assert_eq!(l1.settled, 300 * DUST);
+        assert_eq!(l1.pending, 0);
+
+        let l2 = CycleStatements::<Test>::get(cycle, 3);
+        assert_eq!(l2.by_level[1], 250 * DUST);
+        assert_eq!(AccountEarningTotals::<Test>::get(3).settled, 250 * DUST);
+
+        let info = Affiliate::recent_statements(&2);
+        assert_eq!(info.len(), 1);
+        assert_eq!(info[0].by_source[1], (EarningSource::DivinationOrder, 300 * DUST));
+    });
+}
+
+#[test]
+fn test_weekly_statement_pending_then_settled() {
+    new_test_ext().execute_with(|| {
+        setup_chain_and_escrow();
+
+        Affiliate::do_report_consumption(&1, 1_000 * DUST, None, 15, EarningSource::Membership);
+
+        let cycle = Affiliate::current_cycle();
+        assert_eq!(CycleStatements::<Test>::get(cycle, 2).pending, 200 * DUST);
+        assert_eq!(AccountEarningTotals::<Test>::get(2).pending, 200 * DUST);
+
+        assert_ok!(Affiliate::settle_cycle(RuntimeOrigin::signed(9), cycle, 10));
+
+        let statement = CycleStatements::<Test>::get(cycle, 2);
+        assert_eq!(statement.pending, 0);
+        assert_eq!(statement.settled, 200 * DUST);
+        let totals = Affiliate::earning_totals(&2);
+        assert_eq!(totals.pending, 0);
+        assert_eq!(totals.settled, 200 * DUST);
+    });
+}
+
+#[test]
+fn test_statement_downlines_are_bounded() {
+    new_test_ext().execute_with(|| {
+        setup_chain_and_escrow();
+        assert_ok!(Affiliate::bind_sponsor(RuntimeOrigin::signed(4), b"BOBCODE1".to_vec()));
+        assert_ok!(Affiliate::bind_sponsor(RuntimeOrigin::signed(5), b"BOBCODE1".to_vec()));
+
+        for buyer in [1u64, 4, 5, 1] {
+            Affiliate::do_instant_distribute(&buyer, 100 * DUST, 1, EarningSource::Membership);
+        }
+
+        let statement = CycleStatements::<Test>::get(Affiliate::current_cycle(), 2);
+        assert_eq!(statement.by_downline.to_vec(), vec![(1, 60 * DUST), (4, 30 * DUST)]);
+        assert_eq!(statement.other_downlines, 30 * DUST);
+        assert_eq!(statement.total(), 120 * DUST);
+    });
+}
+
+#[test]
+fn test_old_statements_are_archived() {
+    new_test_ext().execute_with(|| {
+        setup_chain_and_escrow();
+        assert_ok!(Affiliate::set_blocks_per_week(RuntimeOrigin::root(), 10));
+
+        Affiliate::do_instant_distribute(&1, 1_000 * DUST, 1, EarningSource::Membership);
+        assert!(CycleStatements::<Test>::contains_key(0, 2));
+
+        // 保留 12 周：第14周时第0、1周报表过期
+        System::set_block_number(140);
+        Affiliate::archive_old_statements(20);
+
+        assert!(!CycleStatements::<Test>::contains_key(0, 2));
+        assert_eq!(StatementArchiveCursor::<Test>::get(), 2);
+        let archive = Affiliate::archived_statements(&2);
+        assert_eq!(archive.len(), 1);
+        assert_eq!(archive[0].cycle, 0);
+        assert_eq!(archive[0].settled, 300 * DUST);
+        assert!(Affiliate::recent_statements(&2).is_empty());
+    });
+}
+
+// ========================================
+// 奖励解锁与追回测试
+// ========================================
+
+use pallet_affiliate_referral::ActivityKind;
+
+#[test]
+fn test_unvested_instant_rewards_claimed_after_activation() {
+    new_test_ext().execute_with(|| {
+        ActivationThreshold::set(5);
+        setup_chain_and_escrow();
+        let escrow_before = escrow_balance();
+
+        Affiliate::do_instant_distribute(&1, 1_000 * DUST, 2, EarningSource::DivinationOrder);
+
+        // 购买者未激活：奖励留存托管账户
+        assert_eq!(escrow_balance(), escrow_before);
+        assert_eq!(UnvestedRewards::<Test>::get((1, 2, 0)), 300 * DUST);
+        assert_eq!(UnvestedRewards::<Test>::get((1, 3, 0)), 250 * DUST);
+        assert_eq!(TotalUnvested::<Test>::get(), 550 * DUST);
+        assert_eq!(AccountEarningTotals::<Test>::get(2).pending, 300 * DUST);
+        assert_noop!(
+            Affiliate::claim_vested_rewards(RuntimeOrigin::signed(2), 1, 10),
+            Error::<Test>::RefereeNotVested
+        );
+
+        // 购买会员后激活，推荐人领取
+        Referral::record_activity(&1, ActivityKind::Membership);
+        let bob_before = balance_of(2);
+        assert_ok!(Affiliate::claim_vested_rewards(RuntimeOrigin::signed(2), 1, 10));
+
+        assert_eq!(balance_of(2), bob_before + 300 * DUST);
+        let totals = Affiliate::earning_totals(&2);
+        assert_eq!((totals.pending, totals.settled), (0, 300 * DUST));
+        assert_eq!(TotalUnvested::<Test>::get(), 250 * DUST);
+        assert_noop!(
+            Affiliate::claim_vested_rewards(RuntimeOrigin::signed(2), 1, 10),
+            Error::<Test>::NoUnvestedRewards
+        );
+    });
+}
+
+#[test]
+fn test_flagged_referee_rewards_clawed_back() {
+    new_test_ext().execute_with(|| {
+        setup_chain_and_escrow();
+        assert_ok!(Referral::set_account_flag(RuntimeOrigin::root(), 1, Some(true)));
+
+        Affiliate::do_report_consumption(&1, 1_000 * DUST, None, 2, EarningSource::Membership);
+
+        // 被标记的购买者不进入周结算
+        assert_eq!(Entitlement::<Test>::get(0, 2), 0);
+        assert_eq!(UnvestedRewards::<Test>::get((1, 2, 0)), 200 * DUST);
+        assert_eq!(UnvestedRewards::<Test>::get((1, 3, 0)), 100 * DUST);
+        assert_noop!(
+            Affiliate::claim_vested_rewards(RuntimeOrigin::signed(2), 1, 10),
+            Error::<Test>::RefereeNotVested
+        );
+        assert_noop!(
+            Affiliate::clawback_unvested(RuntimeOrigin::signed(2), 1, 10),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        let treasury_before = balance_of(999);
+        assert_ok!(Affiliate::clawback_unvested(RuntimeOrigin::root(), 1, 10));
+
+        assert_eq!(balance_of(999), treasury_before + 300 * DUST);
+        assert_eq!(TotalUnvested::<Test>::get(), 0);
+        let totals = Affiliate::earning_totals(&2);
+        assert_eq!((totals.pending, totals.settled), (0, 0));
+        System::assert_has_event(RuntimeEvent::Affiliate(crate::Event::UnvestedRewardsClawedBack {
+            referee: 1,
+            amount: 300 * DUST,
+        }));
+        assert_noop!(
+            Affiliate::clawback_unvested(RuntimeOrigin::root(), 1, 10),
+            Error::<Test>::NoUnvestedRewards
+        );
+    });
+}
+
+#[test]
+fn test_clawback_unvested_in_batches() {
+    new_test_ext().execute_with(|| {
+        setup_chain_and_escrow();
+        assert_ok!(Referral::set_account_flag(RuntimeOrigin::root(), 1, Some(true)));
+
+        Affiliate::do_report_consumption(&1, 1_000 * DUST, None, 2, EarningSource::Membership);
+        assert_eq!(TotalUnvested::<Test>::get(), 300 * DUST);
+        assert_noop!(
+            Affiliate::clawback_unvested(RuntimeOrigin::root(), 1, 0),
+            Error::<Test>::InvalidMaxEntries
+        );
+
+        // 每次只追回一条，剩余条目留待下次调用
+        let treasury_before = balance_of(999);
+        assert_ok!(Affiliate::clawback_unvested(RuntimeOrigin::root(), 1, 1));
+        assert_eq!(UnvestedRewards::<Test>::iter_prefix((1,)).count(), 1);
+        let first = treasury_before + 300 * DUST - TotalUnvested::<Test>::get();
+        assert_eq!(balance_of(999), first);
+
+        assert_ok!(Affiliate::clawback_unvested(RuntimeOrigin::root(), 1, 1));
+        assert_eq!(UnvestedRewards::<Test>::iter_prefix((1,)).count(), 0);
+        assert_eq!(balance_of(999), treasury_before + 300 * DUST);
+        assert_eq!(TotalUnvested::<Test>::get(), 0);
+        assert_noop!(
+            Affiliate::clawback_unvested(RuntimeOrigin::root(), 1, 1),
+            Error::<Test>::NoUnvestedRewards
+        );
+    });
+}
+
+#[test]
+fn test_distribution_and_check_in_record_activity() {
+    new_test_ext().execute_with(|| {
+        use crate::types::AffiliateDistributor;
+        setup_chain_and_escrow();
+
+        assert_ok!(<Affiliate as AffiliateDistributor<u64, u128, u64>>::distribute_rewards(
+            &1,
+            100 * DUST,
+            Some((15, 1)),
+        ));
+        <Affiliate as AffiliateDistributor<u64, u128, u64>>::note_check_in(&1);
+
+        let record = Referral::referee_activity(1);
+        assert_eq!((record.orders, record.memberships, record.check_ins), (1, 0, 1));
+        assert_eq!(record.points, 4);
+    });
+}
+
 // ========================================
 // 默认值测试
 // ========================================
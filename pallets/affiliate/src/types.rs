@@ -100,5 +100,10 @@ pub trait AffiliateDistributor<AccountId, Balance, BlockNumber> {
         amount: Balance,
         target: Option<(u8, u64)>,
     ) -> Result<Balance, sp_runtime::DispatchError>;
+
+    /// 记录一次签到（计入被推荐人活跃度，用于推荐奖励解锁）
+    ///
+    /// 默认空实现，不关心活跃度的实现方无需覆盖。
+    fn note_check_in(_who: &AccountId) {}
 }
 
@@ -0,0 +1,134 @@
+//! 函数级中文注释：推荐奖励解锁子模块
+//!
+//! 功能：
+//! - 被推荐人未激活（或被标记为女巫）时，推荐奖励暂存在托管账户
+//! - 被推荐人激活后，推荐人自行领取
+//! - 治理追回未解锁奖励至国库
+//!
+//! 激活与女巫标记由 pallet-affiliate-referral 维护。
+
+use super::*;
+use frame_support::traits::{Currency, ExistenceRequirement};
+use sp_runtime::traits::{AccountIdConversion, Saturating, Zero};
+
+/// 函数级中文注释：奖励解锁实现
+impl<T: Config> Pallet<T> {
+    /// 函数级中文注释：购买者带来的推荐奖励是否可直接到账
+    pub(crate) fn is_buyer_vested(buyer: &T::AccountId) -> bool {
+        pallet_affiliate_referral::Pallet::<T>::is_vested(buyer)
+    }
+
+    /// 函数级中文注释：暂存一笔未解锁奖励（资金保留在托管账户）
+    ///
+    /// 参数：
+    /// - cycle: 周编号
+    /// - referrer: 推荐人
+    /// - buyer: 未激活的购买者
+    /// - level: 层级（1-15）
+    /// - source: 收益来源
+    /// - amount: 金额
+    pub(crate) fn hold_unvested_reward(
+        cycle: u32,
+        referrer: &T::AccountId,
+        buyer: &T::AccountId,
+        level: u8,
+        source: statement::EarningSource,
+        amount: BalanceOf<T>,
+    ) {
+        UnvestedRewards::<T>::mutate((buyer, referrer, cycle), |balance| {
+            *balance = balance.saturating_add(amount);
+        });
+        TotalUnvested::<T>::mutate(|total| *total = total.saturating_add(amount));
+        Self::record_earning(cycle, referrer, buyer, level, source, amount, false);
+
+        Self::deposit_event(Event::RewardVesting {
+            referrer: referrer.clone(),
+            buyer: buyer.clone(),
+            level,
+            amount,
+        });
+    }
+
+    /// 函数级中文注释：推荐人领取某被推荐人名下已解锁的奖励
+    ///
+    /// 单次最多处理 `max_entries` 条，剩余条目留待下次调用继续领取。
+    /// 返回实际处理的条目数。
+    pub fn do_claim_vested_rewards(
+        referrer: &T::AccountId,
+        referee: &T::AccountId,
+        max_entries: u32,
+    ) -> Result<u32, DispatchError> {
+        ensure!(max_entries > 0, Error::<T>::InvalidMaxEntries);
+        ensure!(Self::is_buyer_vested(referee), Error::<T>::RefereeNotVested);
+
+        let entries: sp_std::vec::Vec<(u32, BalanceOf<T>)> =
+            UnvestedRewards::<T>::drain_prefix((referee, referrer))
+                .take(max_entries as usize)
+                .collect();
+        let processed = entries.len() as u32;
+        let total = entries
+            .iter()
+            .fold(BalanceOf::<T>::zero(), |acc, (_, amount)| acc.saturating_add(*amount));
+        ensure!(!total.is_zero(), Error::<T>::NoUnvestedRewards);
+
+        T::Currency::transfer(
+            &T::EscrowPalletId::get().into_account_truncating(),
+            referrer,
+            total,
+            ExistenceRequirement::KeepAlive,
+        )?;
+
+        for (cycle, amount) in entries {
+            Self::record_settlement(cycle, referrer, amount);
+        }
+        TotalUnvested::<T>::mutate(|sum| *sum = sum.saturating_sub(total));
+
+        Self::deposit_event(Event::VestedRewardsClaimed {
+            referrer: referrer.clone(),
+            referee: referee.clone(),
+            amount: total,
+        });
+
+        Ok(processed)
+    }
+
+    /// 函数级中文注释：追回某被推荐人名下所有推荐人的未解锁奖励至国库
+    ///
+    /// 单次最多处理 `max_entries` 条，剩余条目留待下次调用继续追回。
+    /// 返回实际处理的条目数。
+    pub fn do_clawback_unvested(
+        referee: &T::AccountId,
+        max_entries: u32,
+    ) -> Result<u32, DispatchError> {
+        ensure!(max_entries > 0, Error::<T>::InvalidMaxEntries);
+
+        let entries: sp_std::vec::Vec<((T::AccountId, u32), BalanceOf<T>)> =
+            UnvestedRewards::<T>::drain_prefix((referee,))
+                .take(max_entries as usize)
+                .collect();
+        let processed = entries.len() as u32;
+        let total = entries
+            .iter()
+            .fold(BalanceOf::<T>::zero(), |acc, (_, amount)| acc.saturating_add(*amount));
+        ensure!(!total.is_zero(), Error::<T>::NoUnvestedRewards);
+
+        T::Currency::transfer(
+            &T::EscrowPalletId::get().into_account_truncating(),
+            &T::TreasuryAccount::get(),
+            total,
+            ExistenceRequirement::KeepAlive,
+        )?;
+
+        for ((referrer, cycle), amount) in entries {
+            Self::record_clawback(cycle, &referrer, amount);
+        }
+        TotalUnvested::<T>::mutate(|sum| *sum = sum.saturating_sub(total));
+
+        Self::deposit_event(Event::UnvestedRewardsClawedBack {
+            referee: referee.clone(),
+            amount: total,
+        });
+
+        Ok(processed)
+    }
+}
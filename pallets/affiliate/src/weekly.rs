@@ -15,7 +15,7 @@
 //! - 保留：简化的直推计数
 
 use super::*;
-use sp_runtime::traits::{Saturating, Zero, SaturatedConversion};
+use sp_runtime::traits::{Saturating, Zero};
 use pallet_affiliate_referral::MembershipProvider;
 
 /// 函数级中文注释：周结算实现
@@ -27,6 +27,7 @@ impl<T: Config> Pallet<T> {
     /// - distributable_amount: 可分配金额（已扣除系统费用）
     /// - duration_weeks: 供奉时长（周）
     /// - levels: 分配层数（用于 Hybrid 模式）
+    /// - source: 收益来源（记入收益报表）
     ///
     /// 功能：
     /// - 计算当前周编号
@@ -37,11 +38,10 @@ impl<T: Config> Pallet<T> {
         distributable_amount: BalanceOf<T>,
         duration_weeks: Option<u32>,
         levels: u8,
+        source: statement::EarningSource,
     ) {
         // 计算当前周编号
-        let now = <frame_system::Pallet<T>>::block_number();
-        let blocks_per_week = BlocksPerWeek::<T>::get();
-        let current_cycle = (now.saturated_into::<u32>()) / (blocks_per_week.saturated_into::<u32>());
+        let current_cycle = Self::current_cycle();
 
         // 获取推荐链
         let referral_chain = Self::get_referral_chain(buyer);
@@ -50,6 +50,7 @@ impl<T: Config> Pallet<T> {
         let level_percents = WeeklyLevelPercents::<T>::get();
 
         let levels_to_process = levels.min(15) as usize;
+        let vested = Self::is_buyer_vested(buyer);
 
         // 逐层累计应得
         for (index, referrer) in referral_chain.iter().enumerate().take(levels_to_process) {
@@ -89,17 +90,23 @@ impl<T: Config> Pallet<T> {
                 continue;
             }
 
-            // 累计应得金额
-            let is_new_entry = Entitlement::<T>::get(current_cycle, referrer).is_zero();
-            Entitlement::<T>::mutate(current_cycle, referrer, |balance| {
-                *balance = balance.saturating_add(share);
-            });
-
-            // P2: 添加到周期账户列表（用于高效结算迭代）
-            if is_new_entry {
-                CycleAccounts::<T>::mutate(current_cycle, |accounts| {
-                    let _ = accounts.try_push(referrer.clone());
+            if vested {
+                // 累计应得金额
+                let is_new_entry = Entitlement::<T>::get(current_cycle, referrer).is_zero();
+                Entitlement::<T>::mutate(current_cycle, referrer, |balance| {
+                    *balance = balance.saturating_add(share);
                 });
+                Self::record_earning(current_cycle, referrer, buyer, index as u8 + 1, source, share, false);
+
+                // P2: 添加到周期账户列表（用于高效结算迭代）
+                if is_new_entry {
+                    CycleAccounts::<T>::mutate(current_cycle, |accounts| {
+                        let _ = accounts.try_push(referrer.clone());
+                    });
+                }
+            } else {
+                // 购买者未激活或被标记：不进入周结算，暂存待解锁
+                Self::hold_unvested_reward(current_cycle, referrer, buyer, index as u8 + 1, source, share);
             }
 
             // 如果是第一层，且有时长，更新直推活跃数
@@ -176,9 +183,10 @@ impl<T: Config> Pallet<T> {
             Self::do_batch_withdraw(&transfers)
                 .map_err(|_| Error::<T>::WithdrawFailed)?;
 
-            // 清理已结算账户的 Entitlement
-            for (account, _) in &transfers {
+            // 清理已结算账户的 Entitlement，报表待结算转为已结算
+            for (account, amount) in &transfers {
                 Entitlement::<T>::remove(cycle, account);
+                Self::record_settlement(cycle, account, *amount);
             }
 
             // 更新累计统计
@@ -20,6 +20,14 @@ pub trait WeightInfo {
     fn propose_membership_price_adjustment() -> Weight;
     fn vote_on_membership_price_proposal() -> Weight;
     fn cancel_membership_price_proposal() -> Weight;
+    fn commit_vote() -> Weight;
+    fn reveal_vote() -> Weight;
+    fn delegate_votes() -> Weight;
+    fn undelegate_votes() -> Weight;
+    fn unlock_vote() -> Weight;
+    fn unlock_delegation() -> Weight;
+    fn claim_vested_rewards(n: u32) -> Weight;
+    fn clawback_unvested(n: u32) -> Weight;
 }
 
 /// 默认权重实现（用于测试）
@@ -66,6 +74,32 @@ impl WeightInfo for () {
     fn cancel_membership_price_proposal() -> Weight {
         Weight::from_parts(20_000, 0)
     }
+    fn commit_vote() -> Weight {
+        Weight::from_parts(20_000, 0)
+    }
+    fn reveal_vote() -> Weight {
+        Weight::from_parts(40_000, 0)
+    }
+    fn delegate_votes() -> Weight {
+        Weight::from_parts(30_000, 0)
+    }
+    fn undelegate_votes() -> Weight {
+        Weight::from_parts(20_000, 0)
+    }
+    fn unlock_vote() -> Weight {
+        Weight::from_parts(20_000, 0)
+    }
+    fn unlock_delegation() -> Weight {
+        Weight::from_parts(20_000, 0)
+    }
+    fn claim_vested_rewards(n: u32) -> Weight {
+        Weight::from_parts(30_000, 0)
+            .saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n.into()))
+    }
+    fn clawback_unvested(n: u32) -> Weight {
+        Weight::from_parts(40_000, 0)
+            .saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n.into()))
+    }
 }
 
 /// Substrate 权重实现
@@ -123,11 +157,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     }
 
     fn vote_on_percentage_proposal() -> Weight {
-        // 读取: GovernancePaused, ActiveProposals, ProposalVotes
-        // 写入: ProposalVotes, VoteTally, VoteHistory
-        Weight::from_parts(30_000, 0)
-            .saturating_add(T::DbWeight::get().reads(3))
-            .saturating_add(T::DbWeight::get().writes(3))
+        // 读取: GovernancePaused, ActiveProposals, Delegations, ProposalVotes, DelegatedPower, BlocksPerWeek, VoteLocks(前缀), Balances
+        // 写入: VoteLocks, Locks, ProposalVotes, VoteTally, VoteHistory
+        Weight::from_parts(40_000, 0)
+            .saturating_add(T::DbWeight::get().reads(10))
+            .saturating_add(T::DbWeight::get().writes(5))
     }
 
     fn cancel_proposal() -> Weight {
@@ -159,11 +193,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     }
 
     fn vote_on_membership_price_proposal() -> Weight {
-        // 读取: GovernancePaused, ActiveMembershipPriceProposals, MembershipPriceProposalVotes
-        // 写入: MembershipPriceProposalVotes, MembershipPriceVoteTally, VoteHistory
-        Weight::from_parts(30_000, 0)
-            .saturating_add(T::DbWeight::get().reads(3))
-            .saturating_add(T::DbWeight::get().writes(3))
+        // 读取: GovernancePaused, ActiveMembershipPriceProposals, Delegations, MembershipPriceProposalVotes, DelegatedPower, BlocksPerWeek, VoteLocks(前缀), Balances
+        // 写入: VoteLocks, Locks, MembershipPriceProposalVotes, MembershipPriceVoteTally, VoteHistory
+        Weight::from_parts(40_000, 0)
+            .saturating_add(T::DbWeight::get().reads(10))
+            .saturating_add(T::DbWeight::get().writes(5))
     }
 
     fn cancel_membership_price_proposal() -> Weight {
@@ -173,4 +207,74 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(2))
             .saturating_add(T::DbWeight::get().writes(4))
     }
+
+    fn commit_vote() -> Weight {
+        // 读取: GovernancePaused, ActiveProposals/ActiveMembershipPriceProposals, Delegations, BallotCommitments, Balances(保留押金)
+        // 写入: BallotCommitments, Balances(保留押金)
+        Weight::from_parts(25_000, 0)
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    fn reveal_vote() -> Weight {
+        // 读取: BallotCommitments + 计票（同 vote_on_percentage_proposal）+ Balances(退还押金)
+        // 写入: BallotCommitments + 计票 + Balances(退还押金)
+        Weight::from_parts(55_000, 0)
+            .saturating_add(T::DbWeight::get().reads(12))
+            .saturating_add(T::DbWeight::get().writes(7))
+    }
+
+    fn delegate_votes() -> Weight {
+        // 读取: Delegations(2), DelegatedPower, VoteLocks(前缀), 提案状态, Balances
+        // 写入: DelegatedPower, Delegations, Locks
+        Weight::from_parts(30_000, 0)
+            .saturating_add(T::DbWeight::get().reads(8))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+
+    fn undelegate_votes() -> Weight {
+        // 读取: Delegations, DelegatedPower, BlocksPerWeek
+        // 写入: Delegations, DelegatedPower
+        Weight::from_parts(20_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    fn unlock_vote() -> Weight {
+        // 读取: VoteLocks, Delegations, VoteLocks(前缀)
+        // 写入: VoteLocks, Locks
+        Weight::from_parts(20_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    fn unlock_delegation() -> Weight {
+        // 读取: Delegations, VoteLocks(前缀)
+        // 写入: Delegations, Locks
+        Weight::from_parts(20_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// - 固定：RefereeActivity, AccountFlags, FundedBy, Sponsors, 托管余额；写 TotalUnvested, 余额(2)
+    /// - 每条：读写 UnvestedRewards, CycleStatements, AccountEarningTotals
+    fn claim_vested_rewards(n: u32) -> Weight {
+        Weight::from_parts(30_000, 0)
+            .saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().reads(3u64.saturating_mul(n.into())))
+            .saturating_add(T::DbWeight::get().writes(3))
+            .saturating_add(T::DbWeight::get().writes(3u64.saturating_mul(n.into())))
+    }
+
+    /// - 固定：托管余额；写 TotalUnvested, 余额(2)
+    /// - 每条：读写 UnvestedRewards, CycleStatements, AccountEarningTotals
+    fn clawback_unvested(n: u32) -> Weight {
+        Weight::from_parts(40_000, 0)
+            .saturating_add(Weight::from_parts(5_000, 0).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().reads(3u64.saturating_mul(n.into())))
+            .saturating_add(T::DbWeight::get().writes(3))
+            .saturating_add(T::DbWeight::get().writes(3u64.saturating_mul(n.into())))
+    }
 }
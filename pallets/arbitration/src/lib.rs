@@ -6,12 +6,13 @@ extern crate alloc;
 
 pub use pallet::*;
 pub mod weights;
+pub mod runtime_api;
 
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
     use crate::weights::WeightInfo;
-    use frame_support::traits::{EnsureOrigin, fungible::{Inspect as FungibleInspect, Mutate as FungibleMutate, MutateHold as FungibleMutateHold}};
+    use frame_support::traits::{EnsureOrigin, UnixTime, fungible::{Inspect as FungibleInspect, Mutate as FungibleMutate, MutateHold as FungibleMutateHold}};
     use frame_support::{pallet_prelude::*, BoundedVec};
     use frame_system::pallet_prelude::*;
     use pallet_escrow::pallet::Escrow as EscrowTrait;
@@ -19,6 +20,9 @@ pub mod pallet {
     use pallet_trading_common::PricingProvider;
     use sp_runtime::{Saturating, SaturatedConversion};
     use pallet_storage_lifecycle::block_to_year_month;
+    use crate::runtime_api::CaseTimeline;
+    use pallet_reputation::traits::ReputationSink;
+    use pallet_reputation::types::{ReputationDomain, ReputationSignal, Severity};
     // 基准模块在 pallet 外部声明；此处不在 proc-macro 输入中声明子模块，避免 E0658
 
     #[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
@@ -405,6 +409,8 @@ pub mod pallet {
         fn get_order_amount(domain: [u8; 8], id: u64) -> Result<Balance, DispatchError>;
         /// 🆕 获取做市商ID（用于信用分更新，仅OTC域有效）
         fn get_maker_id(_domain: [u8; 8], _id: u64) -> Option<u64> { None }
+        /// 🆕 获取案件双方账户（用于时间线展示与聊天会话关联）
+        fn get_parties(_domain: [u8; 8], _id: u64) -> Option<(AccountId, AccountId)> { None }
     }
 
     #[pallet::config]
@@ -466,6 +472,16 @@ pub mod pallet {
         /// - 做市商败诉时扣除信用分
         /// - 做市商胜诉时可选加分
         type CreditUpdater: CreditUpdater;
+
+        /// 🆕 时间戳来源（案件时间线记录 Unix 秒）
+        type Timestamp: UnixTime;
+
+        /// 🆕 每个案件时间线最多保留的条目数（超出时丢弃最早条目）
+        #[pallet::constant]
+        type MaxTimelineEntries: Get<u32>;
+
+        /// 跨域信誉上报（仲裁 / 投诉败诉方）
+        type Reputation: ReputationSink<Self::AccountId>;
     }
     
     /// 信用分更新接口
@@ -482,6 +498,25 @@ pub mod pallet {
         fn record_maker_dispute_result(_: u64, _: u64, _: bool) -> DispatchResult { Ok(()) }
     }
 
+    /// 🆕 案件时间线记录接口
+    ///
+    /// 业务 pallet（OTC、Swap）在订单状态变化、提交凭证时调用，
+    /// 仲裁模块自身在发起争议、追加证据、投诉流转、裁决时调用；
+    /// 委员会通过 `ArbitrationApi::get_case_timeline` 一次性读取完整时间线。
+    /// 时间线自业务对象创建起记录，业务对象归档时清理。
+    pub trait DisputeTimeline<AccountId, Hash> {
+        /// 追加一条时间线记录
+        fn record(domain: [u8; 8], id: u64, kind: TimelineEventKind<Hash>, actor: Option<AccountId>);
+        /// 清理时间线（业务对象归档时调用）
+        fn clear(domain: [u8; 8], id: u64);
+    }
+
+    /// 空实现（用于不需要时间线的场景）
+    impl<AccountId, Hash> DisputeTimeline<AccountId, Hash> for () {
+        fn record(_: [u8; 8], _: u64, _: TimelineEventKind<Hash>, _: Option<AccountId>) {}
+        fn clear(_: [u8; 8], _: u64) {}
+    }
+
     pub type BalanceOf<T> =
         <<T as pallet_escrow::pallet::Config>::Currency as frame_support::traits::Currency<
             <T as frame_system::Config>::AccountId,
@@ -546,6 +581,42 @@ pub mod pallet {
         pub has_responded: bool,
     }
 
+    /// 🆕 案件时间线事件类型
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+    pub enum TimelineEventKind<Hash> {
+        /// 业务对象创建（订单/兑换）
+        Created,
+        /// 业务状态变化（状态码由业务 pallet 定义，与其 OrderStateChanged 等事件一致）
+        StateChanged { from: u8, to: u8 },
+        /// 发起争议
+        DisputeOpened,
+        /// 被诉方应诉
+        DisputeResponded,
+        /// 关联 pallet-evidence 的 evidence_id
+        EvidenceId(u64),
+        /// 业务 pallet 直接提交的证据 CID（仅存哈希）
+        EvidenceCid(Hash),
+        /// 关联私密付款凭证（pallet-evidence 私密内容 ID）
+        PaymentProof(u64),
+        /// 投诉状态变化
+        Complaint { complaint_id: u64, status: ComplaintStatus },
+        /// 仲裁裁决（0=Release, 1=Refund, 2=Partial）
+        Arbitrated { decision: u8, bps: Option<u16> },
+    }
+
+    /// 🆕 案件时间线条目
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+    pub struct TimelineEntry<AccountId, BlockNumber, Hash> {
+        /// 事件类型
+        pub kind: TimelineEventKind<Hash>,
+        /// 触发账户（系统自动触发时为 None）
+        pub actor: Option<AccountId>,
+        /// 区块号
+        pub block: BlockNumber,
+        /// Unix 时间戳（秒）
+        pub timestamp: u64,
+    }
+
     #[pallet::pallet]
     pub struct Pallet<T>(_);
 
@@ -593,6 +664,21 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    /// 🆕 案件时间线：(domain, object_id) => 按时间顺序排列的条目
+    ///
+    /// 自业务对象创建起记录，条目数受 `MaxTimelineEntries` 限制；
+    /// 业务对象归档时由业务 pallet 调用 `DisputeTimeline::clear` 清理
+    #[pallet::storage]
+    pub type CaseTimelines<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        [u8; 8],
+        Blake2_128Concat,
+        u64,
+        BoundedVec<TimelineEntry<T::AccountId, BlockNumberFor<T>, T::Hash>, T::MaxTimelineEntries>,
+        ValueQuery,
+    >;
+
     // ==================== 🆕 存储膨胀防护：归档存储 ====================
 
     /// 下一个归档ID
@@ -815,6 +901,7 @@ pub mod pallet {
             );
             Disputed::<T>::insert(domain, id, ());
             // 证据仅留 CID；如需可扩展附加存储（MVP 省略内容）
+            Self::push_timeline(domain, id, TimelineEventKind::DisputeOpened, Some(_who));
             Self::deposit_event(Event::Disputed { domain, id });
             Ok(())
         }
@@ -859,6 +946,18 @@ pub mod pallet {
                 // 忽略错误，信用更新失败不影响主流程
                 let _ = T::CreditUpdater::record_maker_dispute_result(maker_id, id, maker_win);
             }
+
+            // 跨域信誉：败诉方记一次争议败诉（部分裁决双方均有责任，不上报）
+            if let Some((buyer, seller)) = T::Router::get_parties(domain, id) {
+                let loser = match decision {
+                    Decision::Release => Some(buyer),
+                    Decision::Refund => Some(seller),
+                    Decision::Partial(_) => None,
+                };
+                if let Some(loser) = loser {
+                    T::Reputation::report(&loser, ReputationDomain::Arbitration, ReputationSignal::DisputeLost);
+                }
+            }
             
             let out = match decision {
                 Decision::Release => (0, None),
@@ -866,6 +965,13 @@ pub mod pallet {
                 Decision::Partial(p) => (2, Some(p)),
             };
 
+            Self::push_timeline(
+                domain,
+                id,
+                TimelineEventKind::Arbitrated { decision: out.0, bps: out.1 },
+                None,
+            );
+
             // 🆕 归档已完成的仲裁并清理存储
             Self::archive_and_cleanup(domain, id, out.0, out.1.unwrap_or(0));
 
@@ -910,6 +1016,8 @@ pub mod pallet {
                     .map_err(|_| Error::<T>::AlreadyDisputed)?; // 复用错误占位，避免新增错误枚举
                 Ok(())
             })?;
+            Self::push_timeline(domain, id, TimelineEventKind::DisputeOpened, Some(_who.clone()));
+            Self::push_timeline(domain, id, TimelineEventKind::EvidenceId(evidence_id), Some(_who));
             Self::deposit_event(Event::Disputed { domain, id });
             Ok(())
         }
@@ -926,7 +1034,7 @@ pub mod pallet {
             id: u64,
             evidence_id: u64,
         ) -> DispatchResult {
-            let _who = ensure_signed(origin)?;
+            let who = ensure_signed(origin)?;
             ensure!(
                 Disputed::<T>::get(domain, id).is_some(),
                 Error::<T>::NotDisputed
@@ -936,6 +1044,7 @@ pub mod pallet {
                     .map_err(|_| Error::<T>::AlreadyDisputed)?;
                 Ok(())
             })?;
+            Self::push_timeline(domain, id, TimelineEventKind::EvidenceId(evidence_id), Some(who));
             Ok(())
         }
 
@@ -1025,6 +1134,14 @@ pub mod pallet {
                 Ok(())
             })?;
 
+            Self::push_timeline(domain, id, TimelineEventKind::DisputeOpened, Some(initiator.clone()));
+            Self::push_timeline(
+                domain,
+                id,
+                TimelineEventKind::EvidenceId(evidence_id),
+                Some(initiator.clone()),
+            );
+
             // 11. 触发事件
             Self::deposit_event(Event::DisputeWithDepositInitiated {
                 domain,
@@ -1100,6 +1217,14 @@ pub mod pallet {
                 Ok(())
             })?;
 
+            Self::push_timeline(domain, id, TimelineEventKind::DisputeResponded, Some(respondent.clone()));
+            Self::push_timeline(
+                domain,
+                id,
+                TimelineEventKind::EvidenceId(counter_evidence_id),
+                Some(respondent.clone()),
+            );
+
             // 10. 触发事件
             Self::deposit_event(Event::RespondentDepositLocked {
                 domain,
@@ -1211,6 +1336,13 @@ pub mod pallet {
                 stats.total_complaints = stats.total_complaints.saturating_add(1);
             });
 
+            Self::push_timeline(
+                domain,
+                object_id,
+                TimelineEventKind::Complaint { complaint_id, status: ComplaintStatus::Submitted },
+                Some(complainant.clone()),
+            );
+
             // 10. 触发事件
             Self::deposit_event(Event::ComplaintFiled {
                 complaint_id,
@@ -1257,6 +1389,7 @@ pub mod pallet {
                 complaint.details_cid = response_cid;
                 complaint.status = ComplaintStatus::Responded;
                 complaint.updated_at = now;
+                Self::push_complaint_timeline(complaint, Some(respondent.clone()));
 
                 Self::deposit_event(Event::ComplaintResponded {
                     complaint_id,
@@ -1289,6 +1422,7 @@ pub mod pallet {
                 let now = frame_system::Pallet::<T>::block_number();
                 complaint.status = ComplaintStatus::Withdrawn;
                 complaint.updated_at = now;
+                Self::push_complaint_timeline(complaint, Some(who.clone()));
 
                 Self::deposit_event(Event::ComplaintWithdrawn { complaint_id });
 
@@ -1326,6 +1460,7 @@ pub mod pallet {
                 complaint.details_cid = settlement_cid;
                 complaint.status = ComplaintStatus::ResolvedSettlement;
                 complaint.updated_at = now;
+                Self::push_complaint_timeline(complaint, Some(who.clone()));
 
                 // 更新统计
                 DomainStats::<T>::mutate(complaint.domain, |stats| {
@@ -1364,6 +1499,7 @@ pub mod pallet {
                 let now = frame_system::Pallet::<T>::block_number();
                 complaint.status = ComplaintStatus::Arbitrating;
                 complaint.updated_at = now;
+                Self::push_complaint_timeline(complaint, Some(who.clone()));
 
                 Self::deposit_event(Event::ComplaintEscalated { complaint_id });
 
@@ -1407,6 +1543,22 @@ pub mod pallet {
                     _ => ComplaintStatus::ResolvedSettlement,
                 };
                 complaint.updated_at = now;
+                Self::push_complaint_timeline(complaint, None);
+
+                // 跨域信誉：被投诉方败诉记争议败诉，投诉被驳回记一次轻微违规（恶意投诉）
+                match decision {
+                    0 => T::Reputation::report(
+                        &complaint.respondent,
+                        ReputationDomain::Arbitration,
+                        ReputationSignal::DisputeLost,
+                    ),
+                    1 => T::Reputation::report(
+                        &complaint.complainant,
+                        ReputationDomain::Arbitration,
+                        ReputationSignal::Violation(Severity::Minor),
+                    ),
+                    _ => {},
+                }
 
                 // 处理投诉押金
                 if let Some(deposit_amount) = ComplaintDeposits::<T>::take(complaint_id) {
@@ -1707,6 +1859,84 @@ pub mod pallet {
             reason
         }
 
+        // ============================================================================
+        // 🆕 案件时间线辅助函数
+        // ============================================================================
+
+        /// 函数级中文注释：向案件时间线追加一条记录
+        /// - 时间线已满时丢弃最早的条目，保证最近的流转始终可见
+        pub fn push_timeline(
+            domain: [u8; 8],
+            id: u64,
+            kind: TimelineEventKind<T::Hash>,
+            actor: Option<T::AccountId>,
+        ) {
+            let entry = TimelineEntry {
+                kind,
+                actor,
+                block: frame_system::Pallet::<T>::block_number(),
+                timestamp: T::Timestamp::now().as_secs(),
+            };
+            CaseTimelines::<T>::mutate(domain, id, |entries| {
+                if entries.is_full() && !entries.is_empty() {
+                    entries.remove(0);
+                }
+                let _ = entries.try_push(entry);
+            });
+        }
+
+        /// 函数级中文注释：记录投诉状态变化到其关联对象的时间线
+        fn push_complaint_timeline(complaint: &Complaint<T>, actor: Option<T::AccountId>) {
+            Self::push_timeline(
+                complaint.domain,
+                complaint.object_id,
+                TimelineEventKind::Complaint {
+                    complaint_id: complaint.id,
+                    status: complaint.status.clone(),
+                },
+                actor,
+            );
+        }
+
+        /// 函数级中文注释：组装案件时间线视图（供 Runtime API 使用）
+        /// - evidence_ids 合并活跃引用与时间线中的历史引用（裁决后 EvidenceIds 已清理）
+        /// - 参与方由 Router 提供；聊天会话由 runtime 依据参与方补充
+        pub fn case_timeline(
+            domain: [u8; 8],
+            id: u64,
+        ) -> Option<CaseTimeline<T::AccountId, BlockNumberFor<T>, T::Hash>> {
+            let entries = CaseTimelines::<T>::get(domain, id).into_inner();
+            let disputed = Disputed::<T>::contains_key(domain, id);
+            if entries.is_empty() && !disputed {
+                return None;
+            }
+
+            let mut evidence_ids: alloc::vec::Vec<u64> = EvidenceIds::<T>::get(domain, id).into_inner();
+            let mut complaint_ids: alloc::vec::Vec<u64> = alloc::vec::Vec::new();
+            for entry in entries.iter() {
+                match entry.kind {
+                    TimelineEventKind::EvidenceId(evidence_id) if !evidence_ids.contains(&evidence_id) => {
+                        evidence_ids.push(evidence_id);
+                    },
+                    TimelineEventKind::Complaint { complaint_id, .. } if !complaint_ids.contains(&complaint_id) => {
+                        complaint_ids.push(complaint_id);
+                    },
+                    _ => {},
+                }
+            }
+
+            Some(CaseTimeline {
+                domain,
+                id,
+                parties: T::Router::get_parties(domain, id),
+                disputed,
+                entries,
+                evidence_ids,
+                complaint_ids,
+                chat_session: None,
+            })
+        }
+
         /// 🆕 存储膨胀防护：归档仲裁并清理存储
         fn archive_and_cleanup(domain: [u8; 8], id: u64, decision: u8, partial_bps: u16) {
             let current_block: u32 = frame_system::Pallet::<T>::block_number().saturated_into();
@@ -1736,7 +1966,7 @@ pub mod pallet {
                 }
             });
 
-            // 清理原始存储
+            // 清理原始存储（时间线保留至业务对象归档）
             Disputed::<T>::remove(domain, id);
             EvidenceIds::<T>::remove(domain, id);
             TwoWayDeposits::<T>::remove(domain, id);
@@ -1826,6 +2056,7 @@ pub mod pallet {
                     complaint.status = ComplaintStatus::Expired;
                     complaint.updated_at = now;
                     Complaints::<T>::insert(complaint_id, &complaint);
+                    Self::push_complaint_timeline(&complaint, None);
 
                     // 更新统计
                     DomainStats::<T>::mutate(complaint.domain, |stats| {
@@ -1842,6 +2073,16 @@ pub mod pallet {
         }
     }
 
+    impl<T: Config> DisputeTimeline<T::AccountId, T::Hash> for Pallet<T> {
+        fn record(domain: [u8; 8], id: u64, kind: TimelineEventKind<T::Hash>, actor: Option<T::AccountId>) {
+            Self::push_timeline(domain, id, kind, actor);
+        }
+
+        fn clear(domain: [u8; 8], id: u64) {
+            CaseTimelines::<T>::remove(domain, id);
+        }
+    }
+
     // ==================== 🆕 Phase 4: Hooks 实现 ====================
 
     #[pallet::hooks]
@@ -0,0 +1,40 @@
+//! 仲裁模块 Runtime API 定义
+//!
+//! 为仲裁委员会界面提供案件时间线的一次性查询
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use crate::pallet::TimelineEntry;
+
+sp_api::decl_runtime_apis! {
+    /// 仲裁模块 Runtime API
+    pub trait ArbitrationApi<AccountId, BlockNumber, Hash>
+    where
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Hash: Codec,
+    {
+        /// 获取案件时间线（状态流转、证据引用、投诉、聊天会话），案件不存在时返回 None
+        fn get_case_timeline(domain: [u8; 8], id: u64) -> Option<CaseTimeline<AccountId, BlockNumber, Hash>>;
+    }
+}
+
+/// 案件时间线 (用于 API 返回)
+#[derive(codec::Encode, codec::Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+pub struct CaseTimeline<AccountId, BlockNumber, Hash> {
+    pub domain: [u8; 8],
+    pub id: u64,
+    /// 案件双方（OTC: 买家/做市商，Swap: 用户/做市商）
+    pub parties: Option<(AccountId, AccountId)>,
+    /// 是否处于仲裁登记中
+    pub disputed: bool,
+    /// 按时间顺序排列的条目
+    pub entries: Vec<TimelineEntry<AccountId, BlockNumber, Hash>>,
+    /// 本案引用的全部 evidence_id（pallet-evidence）
+    pub evidence_ids: Vec<u64>,
+    /// 针对本对象提交过的投诉ID
+    pub complaint_ids: Vec<u64>,
+    /// 双方的私聊会话ID（pallet-chat-core，会话存在时返回）
+    pub chat_session: Option<Hash>,
+}
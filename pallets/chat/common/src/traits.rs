@@ -4,7 +4,7 @@
 
 use sp_std::vec::Vec;
 use frame_support::dispatch::DispatchResult;
-use crate::types::ChatUserId;
+use crate::types::{ChatUserId, GroupInboxItem};
 
 /// 函数级详细中文注释：聊天权限检查trait
 ///
@@ -170,6 +170,30 @@ pub trait GroupMemberCheck<AccountId> {
     fn get_group_members(group_id: u64) -> Vec<AccountId>;
 }
 
+/// 函数级详细中文注释：群聊收件箱提供者trait
+///
+/// 由 pallet-chat-group 实现，供 pallet-chat-core 合并未读计数和收件箱
+pub trait GroupInboxProvider<AccountId, BlockNumber> {
+    /// 函数级中文注释：获取用户所在群组的收件箱条目
+    ///
+    /// # 参数
+    /// - `user`: 用户账户
+    ///
+    /// # 返回
+    /// - 群组条目列表（未排序）
+    fn group_inbox(user: &AccountId) -> Vec<GroupInboxItem<BlockNumber>>;
+
+    /// 函数级中文注释：获取用户在指定群组的未读消息数
+    ///
+    /// # 参数
+    /// - `group_id`: 群组ID
+    /// - `user`: 用户账户
+    ///
+    /// # 返回
+    /// - 未读消息数（非成员返回0）
+    fn group_unread_count(group_id: u64, user: &AccountId) -> u32;
+}
+
 /// 函数级详细中文注释：空实现（用于不需要权限检查的场景）
 impl<AccountId> ChatPermissionCheck<AccountId> for () {
     fn can_send_message(_sender: &AccountId, _receiver: &AccountId) -> bool {
@@ -186,3 +210,13 @@ impl<AccountId> FriendshipCheck<AccountId> for () {
         false
     }
 }
+
+impl<AccountId, BlockNumber> GroupInboxProvider<AccountId, BlockNumber> for () {
+    fn group_inbox(_user: &AccountId) -> Vec<GroupInboxItem<BlockNumber>> {
+        Vec::new()
+    }
+
+    fn group_unread_count(_group_id: u64, _user: &AccountId) -> u32 {
+        0
+    }
+}
@@ -176,6 +176,21 @@ impl EncryptionMode {
     }
 }
 
+/// 函数级详细中文注释：群聊收件箱条目
+///
+/// 由 pallet-chat-group 提供，pallet-chat-core 与私聊会话合并成统一收件箱
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, RuntimeDebug)]
+pub struct GroupInboxItem<BlockNumber> {
+    /// 群组ID
+    pub group_id: u64,
+    /// 最后活跃区块（最后一条消息的区块，无消息时为加入区块）
+    pub last_active: BlockNumber,
+    /// 最后一条消息ID（无消息时为None）
+    pub last_message_id: Option<u64>,
+    /// 该成员的未读消息数
+    pub unread: u32,
+}
+
 /// 函数级详细中文注释：聊天用户ID类型
 ///
 /// 11位数字的用户ID，范围：10,000,000,000 - 99,999,999,999
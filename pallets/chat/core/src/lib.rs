@@ -18,6 +18,10 @@
 //! - ✅ 消息软删除
 //! - ✅ 未读计数
 //! - ✅ 批量标记已读
+//! - ✅ 消息编辑（编辑窗口内，保留编辑历史CID指针）
+//! - ✅ 表情回应（按消息聚合计数）
+//! - ✅ 阅后即焚（按会话设置消息存活时长，到期由区块钩子自动清理）
+//! - ✅ 统一收件箱（私聊 + 群聊按最后活跃排序，合并未读计数，见 `runtime_api`）
 //! 
 //! ## 架构设计
 //! 
@@ -42,15 +46,20 @@ mod tests;
 pub mod weights;
 pub use weights::{WeightInfo, SubstrateWeight};
 
+pub mod runtime_api;
+
+pub mod migrations;
+
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{pallet_prelude::*, BoundedVec, traits::{Randomness, UnixTime}};
 use frame_system::pallet_prelude::*;
 use scale_info::TypeInfo;
-use sp_runtime::traits::{Hash, Saturating};
+use sp_runtime::traits::{Hash, One, Saturating};
 use sp_std::convert::TryInto;
 
 // 使用 chat-common 的共享类型
 pub use pallet_chat_common::MessageType;
+use pallet_chat_common::GroupInboxProvider;
 
 /// 聊天用户ID类型定义 - 11位数字
 pub type ChatUserId = u64;
@@ -152,6 +161,14 @@ pub struct MessageMeta<T: Config> {
 	pub is_deleted_by_receiver: bool,
 	/// 回复的消息ID（可选）
 	pub reply_to: Option<u64>,
+	/// 编辑次数（0表示未编辑）
+	pub edit_count: u32,
+	/// 最后编辑时间（区块高度）
+	pub edited_at: Option<BlockNumberFor<T>>,
+	/// 编辑历史CID（加密的历史版本列表，由前端在每次编辑时追加并重新上传）
+	pub edit_history_cid: Option<BoundedVec<u8, <T as Config>::MaxCidLen>>,
+	/// 过期时间（区块高度，阅后即焚会话中的消息才有）
+	pub expires_at: Option<BlockNumberFor<T>>,
 }
 
 /// 函数级详细中文注释：会话信息结构
@@ -170,8 +187,14 @@ pub struct Session<T: Config> {
 	pub created_at: BlockNumberFor<T>,
 	/// 是否归档
 	pub is_archived: bool,
+	/// 阅后即焚时长（区块数，None表示关闭）
+	/// - 只影响设置之后发送的消息
+	pub disappear_after: Option<BlockNumberFor<T>>,
 }
 
+/// 函数级详细中文注释：表情回应（UTF-8编码的单个表情）
+pub type ReactionOf<T> = BoundedVec<u8, <T as Config>::MaxReactionLen>;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -180,7 +203,12 @@ pub mod pallet {
 
 	// MessageType 已移至 pallet-chat-common，通过 pub use 重新导出
 
+	/// 存储版本
+	/// - v1：消息追加编辑与过期字段，会话追加阅后即焚设置（见 `migrations`）
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -233,6 +261,39 @@ pub mod pallet {
 		/// 用户个性签名最大长度
 		#[pallet::constant]
 		type MaxSignatureLength: Get<u32>;
+
+		/// 消息编辑窗口（区块数）
+		/// 例如：150个区块 ≈ 15分钟，超过后不可再编辑
+		#[pallet::constant]
+		type EditWindow: Get<BlockNumberFor<Self>>;
+
+		/// 单条消息最多编辑次数
+		#[pallet::constant]
+		type MaxEditsPerMessage: Get<u32>;
+
+		/// 表情回应最大字节数（UTF-8编码，组合表情可能较长）
+		#[pallet::constant]
+		type MaxReactionLen: Get<u32>;
+
+		/// 每个用户对单条消息最多的不同表情数
+		#[pallet::constant]
+		type MaxReactionsPerUser: Get<u32>;
+
+		/// 阅后即焚最短时长（区块数）
+		#[pallet::constant]
+		type MinDisappearTimer: Get<BlockNumberFor<Self>>;
+
+		/// 阅后即焚最长时长（区块数）
+		#[pallet::constant]
+		type MaxDisappearTimer: Get<BlockNumberFor<Self>>;
+
+		/// 每个区块最多清理的过期消息数（限制钩子权重）
+		#[pallet::constant]
+		type MaxExpiredPerBlock: Get<u32>;
+
+		/// 群聊收件箱提供者（由 pallet-chat-group 实现）
+		/// 用于合并私聊与群聊的未读计数和收件箱
+		type GroupInbox: GroupInboxProvider<Self::AccountId, BlockNumberFor<Self>>;
 	}
 
 	/// 函数级详细中文注释：消息元数据存储
@@ -391,6 +452,66 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// 函数级详细中文注释：消息表情回应聚合计数
+	/// - Key1: 消息ID
+	/// - Key2: 表情
+	/// - Value: 回应人数
+	#[pallet::storage]
+	pub type MessageReactions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u64,                  // message_id
+		Blake2_128Concat,
+		ReactionOf<T>,
+		u32,
+		ValueQuery,
+	>;
+
+	/// 函数级详细中文注释：用户对消息的表情回应
+	/// - Key1: 消息ID
+	/// - Key2: 账户
+	/// - Value: 该用户已回应的表情列表（用于去重和撤销）
+	#[pallet::storage]
+	pub type UserReactions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u64,                  // message_id
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<ReactionOf<T>, T::MaxReactionsPerUser>,
+		ValueQuery,
+	>;
+
+	/// 函数级详细中文注释：阅后即焚过期队列
+	/// - Key1: 过期区块
+	/// - Key2: 消息ID
+	/// - Value: () 标记
+	/// - 由 on_initialize 按 ExpiryCursor 顺序清理
+	#[pallet::storage]
+	pub type MessageExpiryQueue<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,    // expires_at
+		Blake2_128Concat,
+		u64,                  // message_id
+		(),
+		OptionQuery,
+	>;
+
+	/// 函数级详细中文注释：过期队列游标
+	/// - 指向尚未清理完毕的最早过期区块
+	/// - 单个区块过期消息超过 MaxExpiredPerBlock 时游标停留，后续区块继续清理
+	#[pallet::storage]
+	pub type ExpiryCursor<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// 函数级详细中文注释：清理到期的阅后即焚消息（权重受 MaxExpiredPerBlock 限制）
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			Self::expire_messages(now)
+		}
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -625,6 +746,12 @@ pub mod pallet {
 
 			// 创建消息（增强版，包含ChatUserId）
 			let now = <frame_system::Pallet<T>>::block_number();
+
+			// 阅后即焚会话：计算过期时间
+			let expires_at = Sessions::<T>::get(session_id)
+				.and_then(|s| s.disappear_after)
+				.map(|after| now.saturating_add(after));
+
 			let message = MessageMeta {
 				sender: sender.clone(),
 				receiver: receiver.clone(),
@@ -638,11 +765,20 @@ pub mod pallet {
 				is_deleted_by_sender: false,
 				is_deleted_by_receiver: false,
 				reply_to: None,
+				edit_count: 0,
+				edited_at: None,
+				edit_history_cid: None,
+				expires_at,
 			};
 
 			// 存储消息
 			Messages::<T>::insert(msg_id, message);
 
+			// 加入过期队列
+			if let Some(at) = expires_at {
+				MessageExpiryQueue::<T>::insert(at, msg_id, ());
+			}
+
 			// 更新会话
 			Sessions::<T>::try_mutate(session_id, |maybe_session| -> DispatchResult {
 				let session = maybe_session.as_mut().ok_or(Error::<T>::SessionNotFound)?;
@@ -1026,9 +1162,8 @@ pub mod pallet {
 			}
 
 			// 移除消息
-			for (msg_id, session_id) in messages_to_remove.iter() {
-				Messages::<T>::remove(msg_id);
-				SessionMessages::<T>::remove(session_id, msg_id);
+			for (msg_id, _session_id) in messages_to_remove.iter() {
+				Self::purge_message(*msg_id);
 			}
 
 			Self::deposit_event(Event::OldMessagesCleanedUp {
@@ -1260,6 +1395,227 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// 函数级详细中文注释：编辑消息
+		///
+		/// # 参数
+		/// - `msg_id`: 消息ID
+		/// - `new_content_cid`: 新的IPFS CID（加密的新内容）
+		/// - `edit_history_cid`: 编辑历史CID（加密的历史版本列表，包含被替换的旧CID）
+		///
+		/// # 流程
+		/// 1. 验证调用者是发送方且消息未被发送方删除
+		/// 2. 验证在编辑窗口内且未超过编辑次数上限
+		/// 3. 验证两个CID均已加密
+		/// 4. 替换内容CID并更新编辑历史指针
+		/// 5. 触发事件
+		///
+		/// # 说明
+		/// - 链上只保留最新内容和历史指针，旧版本由前端写入历史CID
+		/// - 编辑不会改变已读状态和过期时间
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::edit_message())]
+		pub fn edit_message(
+			origin: OriginFor<T>,
+			msg_id: u64,
+			new_content_cid: Vec<u8>,
+			edit_history_cid: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(Self::is_cid_encrypted(&new_content_cid), Error::<T>::CidNotEncrypted);
+			ensure!(Self::is_cid_encrypted(&edit_history_cid), Error::<T>::CidNotEncrypted);
+
+			let new_cid: BoundedVec<u8, T::MaxCidLen> = new_content_cid
+				.try_into()
+				.map_err(|_| Error::<T>::CidTooLong)?;
+			let history_cid: BoundedVec<u8, T::MaxCidLen> = edit_history_cid
+				.try_into()
+				.map_err(|_| Error::<T>::CidTooLong)?;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			let edit_count = Messages::<T>::try_mutate(msg_id, |maybe_msg| -> Result<u32, DispatchError> {
+				let msg = maybe_msg.as_mut().ok_or(Error::<T>::MessageNotFound)?;
+
+				ensure!(msg.sender == who, Error::<T>::NotSender);
+				ensure!(!msg.is_deleted_by_sender, Error::<T>::MessageAlreadyDeleted);
+				ensure!(
+					now.saturating_sub(msg.sent_at) <= T::EditWindow::get(),
+					Error::<T>::EditWindowExpired
+				);
+				ensure!(
+					msg.edit_count < T::MaxEditsPerMessage::get(),
+					Error::<T>::TooManyEdits
+				);
+
+				msg.content_cid = new_cid.clone();
+				msg.edit_history_cid = Some(history_cid.clone());
+				msg.edit_count = msg.edit_count.saturating_add(1);
+				msg.edited_at = Some(now);
+
+				Ok(msg.edit_count)
+			})?;
+
+			Self::deposit_event(Event::MessageEdited {
+				msg_id,
+				editor: who,
+				new_cid,
+				edit_history_cid: history_cid,
+				edit_count,
+			});
+
+			Ok(())
+		}
+
+		/// 函数级详细中文注释：添加表情回应
+		///
+		/// # 参数
+		/// - `msg_id`: 消息ID
+		/// - `reaction`: 表情（UTF-8编码）
+		///
+		/// # 流程
+		/// 1. 验证表情有效
+		/// 2. 验证调用者是消息发送方或接收方，且自己未删除该消息
+		/// 3. 记录用户回应（去重，受 MaxReactionsPerUser 限制）
+		/// 4. 增加聚合计数
+		/// 5. 触发事件
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::add_reaction())]
+		pub fn add_reaction(
+			origin: OriginFor<T>,
+			msg_id: u64,
+			reaction: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let reaction = Self::validate_reaction(reaction)?;
+			Self::ensure_can_react(msg_id, &who)?;
+
+			UserReactions::<T>::try_mutate(msg_id, &who, |reactions| -> DispatchResult {
+				ensure!(!reactions.contains(&reaction), Error::<T>::AlreadyReacted);
+				reactions
+					.try_push(reaction.clone())
+					.map_err(|_| Error::<T>::TooManyReactions)?;
+				Ok(())
+			})?;
+
+			let count = MessageReactions::<T>::mutate(msg_id, &reaction, |count| {
+				*count = count.saturating_add(1);
+				*count
+			});
+
+			Self::deposit_event(Event::ReactionAdded {
+				msg_id,
+				who,
+				reaction,
+				count,
+			});
+
+			Ok(())
+		}
+
+		/// 函数级详细中文注释：撤销表情回应
+		///
+		/// # 参数
+		/// - `msg_id`: 消息ID
+		/// - `reaction`: 要撤销的表情
+		///
+		/// # 流程
+		/// 1. 从用户回应列表中移除
+		/// 2. 减少聚合计数（归零时删除）
+		/// 3. 触发事件
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::remove_reaction())]
+		pub fn remove_reaction(
+			origin: OriginFor<T>,
+			msg_id: u64,
+			reaction: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let reaction: ReactionOf<T> = reaction
+				.try_into()
+				.map_err(|_| Error::<T>::InvalidReaction)?;
+
+			UserReactions::<T>::try_mutate_exists(msg_id, &who, |maybe_reactions| -> DispatchResult {
+				let reactions = maybe_reactions.as_mut().ok_or(Error::<T>::ReactionNotFound)?;
+				let pos = reactions
+					.iter()
+					.position(|r| r == &reaction)
+					.ok_or(Error::<T>::ReactionNotFound)?;
+				reactions.remove(pos);
+				if reactions.is_empty() {
+					*maybe_reactions = None;
+				}
+				Ok(())
+			})?;
+
+			let count = MessageReactions::<T>::mutate_exists(msg_id, &reaction, |maybe_count| {
+				let count = maybe_count.unwrap_or(0).saturating_sub(1);
+				*maybe_count = if count == 0 { None } else { Some(count) };
+				count
+			});
+
+			Self::deposit_event(Event::ReactionRemoved {
+				msg_id,
+				who,
+				reaction,
+				count,
+			});
+
+			Ok(())
+		}
+
+		/// 函数级详细中文注释：设置会话阅后即焚时长
+		///
+		/// # 参数
+		/// - `session_id`: 会话ID
+		/// - `disappear_after`: 消息存活区块数（None表示关闭）
+		///
+		/// # 流程
+		/// 1. 验证会话存在且调用者是参与者
+		/// 2. 验证时长在 [MinDisappearTimer, MaxDisappearTimer] 范围内
+		/// 3. 更新会话设置
+		/// 4. 触发事件
+		///
+		/// # 说明
+		/// - 任一参与者均可修改，对方通过事件得知
+		/// - 只影响之后发送的消息，已发送消息保持原过期时间
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::set_disappearing_timer())]
+		pub fn set_disappearing_timer(
+			origin: OriginFor<T>,
+			session_id: T::Hash,
+			disappear_after: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			if let Some(after) = disappear_after {
+				ensure!(
+					after >= T::MinDisappearTimer::get() && after <= T::MaxDisappearTimer::get(),
+					Error::<T>::InvalidDisappearTimer
+				);
+			}
+
+			Sessions::<T>::try_mutate(session_id, |maybe_session| -> DispatchResult {
+				let session = maybe_session.as_mut().ok_or(Error::<T>::SessionNotFound)?;
+				ensure!(
+					session.participants.contains(&who),
+					Error::<T>::NotSessionParticipant
+				);
+				session.disappear_after = disappear_after;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::DisappearingTimerSet {
+				session_id,
+				operator: who,
+				disappear_after,
+			});
+
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -1384,6 +1740,7 @@ pub mod pallet {
 				last_active: now,
 				created_at: now,
 				is_archived: false,
+				disappear_after: None,
 			};
 
 			Sessions::<T>::insert(session_id, session);
@@ -1521,6 +1878,99 @@ pub mod pallet {
 			}
 		}
 
+		/// 函数级详细中文注释：查询未读汇总（私聊会话 + 群聊）
+		///
+		/// # 参数
+		/// - `user`: 用户账户
+		///
+		/// # 返回
+		/// - UnreadSummary: 未读总数及各会话/群组的未读数（只包含未读数>0的条目）
+		pub fn get_unread_summary(user: T::AccountId) -> runtime_api::UnreadSummary<T::Hash> {
+			let sessions: Vec<(T::Hash, u32)> = UserSessions::<T>::iter_key_prefix(&user)
+				.map(|sid| (sid, UnreadCount::<T>::get((user.clone(), sid))))
+				.filter(|(_, unread)| *unread > 0)
+				.collect();
+			let groups: Vec<(u64, u32)> = T::GroupInbox::group_inbox(&user)
+				.into_iter()
+				.map(|item| (item.group_id, item.unread))
+				.filter(|(_, unread)| *unread > 0)
+				.collect();
+
+			let total = sessions
+				.iter()
+				.map(|(_, n)| *n)
+				.chain(groups.iter().map(|(_, n)| *n))
+				.fold(0u32, |acc, n| acc.saturating_add(n));
+
+			runtime_api::UnreadSummary { total, sessions, groups }
+		}
+
+		/// 函数级详细中文注释：查询未读总数（私聊会话 + 群聊）
+		pub fn get_total_unread(user: T::AccountId) -> u32 {
+			Self::get_unread_summary(user).total
+		}
+
+		/// 函数级详细中文注释：查询指定群组的未读数
+		pub fn get_group_unread(group_id: u64, user: T::AccountId) -> u32 {
+			T::GroupInbox::group_unread_count(group_id, &user)
+		}
+
+		/// 函数级详细中文注释：查询统一收件箱
+		///
+		/// # 参数
+		/// - `user`: 用户账户
+		/// - `offset`: 偏移量（从0开始）
+		/// - `limit`: 每页数量（最多100条）
+		///
+		/// # 返回
+		/// - Vec<InboxEntry>: 私聊会话与群聊按最后活跃区块倒序
+		///
+		/// # 说明
+		/// 私聊和群聊都以区块高度记录最后活跃时间，可直接合并排序；
+		/// 活跃区块相同时私聊在前、再按ID排序，保证分页稳定
+		pub fn get_inbox(
+			user: T::AccountId,
+			offset: u32,
+			limit: u32,
+		) -> Vec<runtime_api::InboxEntry<T::Hash, BlockNumberFor<T>>> {
+			use runtime_api::{Conversation, InboxEntry};
+
+			let mut entries: Vec<InboxEntry<T::Hash, BlockNumberFor<T>>> =
+				UserSessions::<T>::iter_key_prefix(&user)
+					.filter_map(|sid| Sessions::<T>::get(sid))
+					.map(|session| InboxEntry {
+						conversation: Conversation::Private(session.id),
+						last_active: session.last_active,
+						last_message_id: Some(session.last_message_id),
+						unread: UnreadCount::<T>::get((user.clone(), session.id)),
+						is_archived: session.is_archived,
+					})
+					.collect();
+
+			entries.extend(T::GroupInbox::group_inbox(&user).into_iter().map(|item| InboxEntry {
+				conversation: Conversation::Group(item.group_id),
+				last_active: item.last_active,
+				last_message_id: item.last_message_id,
+				unread: item.unread,
+				is_archived: false,
+			}));
+
+			entries.sort_by(|a, b| {
+				b.last_active.cmp(&a.last_active).then_with(|| match (&a.conversation, &b.conversation) {
+					(Conversation::Private(x), Conversation::Private(y)) => x.cmp(y),
+					(Conversation::Group(x), Conversation::Group(y)) => x.cmp(y),
+					(Conversation::Private(_), Conversation::Group(_)) => core::cmp::Ordering::Less,
+					(Conversation::Group(_), Conversation::Private(_)) => core::cmp::Ordering::Greater,
+				})
+			});
+
+			entries
+				.into_iter()
+				.skip(offset as usize)
+				.take(limit.min(100) as usize)
+				.collect()
+		}
+
 		/// 函数级详细中文注释：检查用户是否被拉黑
 		/// 
 		/// # 参数
@@ -1547,6 +1997,119 @@ pub mod pallet {
 				.collect()
 		}
 
+		/// 函数级详细中文注释：校验表情回应
+		///
+		/// # 说明
+		/// - 不能为空，不能超过 MaxReactionLen 字节
+		/// - 必须是有效的UTF-8（链上不校验是否为表情，由前端渲染过滤）
+		fn validate_reaction(reaction: Vec<u8>) -> Result<ReactionOf<T>, DispatchError> {
+			ensure!(!reaction.is_empty(), Error::<T>::InvalidReaction);
+			ensure!(core::str::from_utf8(&reaction).is_ok(), Error::<T>::InvalidReaction);
+			reaction.try_into().map_err(|_| Error::<T>::InvalidReaction.into())
+		}
+
+		/// 函数级详细中文注释：检查用户能否对消息回应
+		///
+		/// # 说明
+		/// 只有消息双方可以回应，且自己已删除的消息不能回应
+		fn ensure_can_react(msg_id: u64, who: &T::AccountId) -> DispatchResult {
+			let msg = Messages::<T>::get(msg_id).ok_or(Error::<T>::MessageNotFound)?;
+			if msg.sender == *who {
+				ensure!(!msg.is_deleted_by_sender, Error::<T>::MessageAlreadyDeleted);
+			} else if msg.receiver == *who {
+				ensure!(!msg.is_deleted_by_receiver, Error::<T>::MessageAlreadyDeleted);
+			} else {
+				return Err(Error::<T>::NotAuthorized.into());
+			}
+			Ok(())
+		}
+
+		/// 函数级详细中文注释：查询消息的表情回应聚合
+		///
+		/// # 返回
+		/// - Vec<(表情, 回应人数)>
+		pub fn get_message_reactions(msg_id: u64) -> Vec<(Vec<u8>, u32)> {
+			MessageReactions::<T>::iter_prefix(msg_id)
+				.map(|(reaction, count)| (reaction.into_inner(), count))
+				.collect()
+		}
+
+		/// 函数级详细中文注释：彻底移除消息及其关联数据
+		///
+		/// # 说明
+		/// - 移除消息、会话索引、表情回应和过期队列条目
+		/// - 未读消息同时扣减接收方未读计数
+		/// - 表情回应条目数受 2 × MaxReactionsPerUser 限制，清理有界
+		pub(crate) fn purge_message(msg_id: u64) {
+			let Some(msg) = Messages::<T>::take(msg_id) else {
+				return;
+			};
+
+			SessionMessages::<T>::remove(msg.session_id, msg_id);
+
+			if !msg.is_read {
+				UnreadCount::<T>::mutate((msg.receiver.clone(), msg.session_id), |count| {
+					*count = count.saturating_sub(1);
+				});
+			}
+
+			if let Some(at) = msg.expires_at {
+				MessageExpiryQueue::<T>::remove(at, msg_id);
+			}
+
+			let _ = MessageReactions::<T>::clear_prefix(msg_id, u32::MAX, None);
+			let _ = UserReactions::<T>::clear_prefix(msg_id, u32::MAX, None);
+		}
+
+		/// 函数级详细中文注释：清理到期的阅后即焚消息
+		///
+		/// # 流程
+		/// 1. 从 ExpiryCursor 开始逐个区块处理过期队列
+		/// 2. 每个区块最多清理 MaxExpiredPerBlock 条消息、扫描同样数量的区块
+		/// 3. 某区块清理完毕后游标前移，否则下个区块继续
+		///
+		/// # 返回
+		/// - 实际消耗的权重
+		pub(crate) fn expire_messages(now: BlockNumberFor<T>) -> Weight {
+			let limit = T::MaxExpiredPerBlock::get();
+			let mut cursor = ExpiryCursor::<T>::get().unwrap_or(now);
+			let mut expired = 0u32;
+			let mut scanned = 0u32;
+
+			while cursor <= now && expired < limit && scanned < limit {
+				scanned = scanned.saturating_add(1);
+
+				let budget = limit.saturating_sub(expired);
+				let batch: Vec<u64> = MessageExpiryQueue::<T>::iter_key_prefix(cursor)
+					.take(budget as usize)
+					.collect();
+				let drained = (batch.len() as u32) < budget;
+
+				for msg_id in batch.iter() {
+					MessageExpiryQueue::<T>::remove(cursor, msg_id);
+					Self::purge_message(*msg_id);
+				}
+				expired = expired.saturating_add(batch.len() as u32);
+
+				if !batch.is_empty() {
+					Self::deposit_event(Event::MessagesExpired {
+						block: cursor,
+						count: batch.len() as u32,
+					});
+				}
+
+				if !drained {
+					break;
+				}
+				cursor = cursor.saturating_add(One::one());
+			}
+
+			ExpiryCursor::<T>::put(cursor);
+
+			T::WeightInfo::expire_messages(expired)
+				.saturating_add(T::DbWeight::get().reads_writes(scanned as u64 + 1, 1))
+		}
+
 		// ===== ChatUserId 相关功能 =====
 
 		/// 函数级详细中文注释：生成11位数聊天用户ID
@@ -0,0 +1,131 @@
+//! # Chat Core 存储迁移
+//!
+//! 函数级详细中文注释：
+//! - v0 → v1：`MessageMeta` 追加编辑与阅后即焚字段，`Session` 追加 `disappear_after`
+//! - 旧数据按默认值补齐：未编辑、无编辑历史、不过期、阅后即焚关闭
+
+use super::*;
+use frame_support::{
+	migrations::VersionedMigration,
+	traits::UncheckedOnRuntimeUpgrade,
+};
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// 函数级详细中文注释：v0 存储布局
+pub mod v0 {
+	use super::*;
+
+	/// v0 消息元数据（无编辑、过期字段）
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct MessageMeta<T: Config> {
+		pub sender: T::AccountId,
+		pub receiver: T::AccountId,
+		pub sender_chat_id: Option<ChatUserId>,
+		pub receiver_chat_id: Option<ChatUserId>,
+		pub content_cid: BoundedVec<u8, <T as Config>::MaxCidLen>,
+		pub session_id: T::Hash,
+		pub msg_type: MessageType,
+		pub sent_at: BlockNumberFor<T>,
+		pub is_read: bool,
+		pub is_deleted_by_sender: bool,
+		pub is_deleted_by_receiver: bool,
+		pub reply_to: Option<u64>,
+	}
+
+	/// v0 会话信息（无阅后即焚设置）
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+	#[scale_info(skip_type_params(T))]
+	pub struct Session<T: Config> {
+		pub id: T::Hash,
+		pub participants: BoundedVec<T::AccountId, ConstU32<2>>,
+		pub last_message_id: u64,
+		pub last_active: BlockNumberFor<T>,
+		pub created_at: BlockNumberFor<T>,
+		pub is_archived: bool,
+	}
+}
+
+/// 函数级详细中文注释：v0 → v1 迁移实现（不检查版本，由 `MigrateV0ToV1` 包装）
+pub struct InnerMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+
+impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let mut translated = 0u64;
+
+		Messages::<T>::translate::<v0::MessageMeta<T>, _>(|_, old| {
+			translated += 1;
+			Some(MessageMeta {
+				sender: old.sender,
+				receiver: old.receiver,
+				sender_chat_id: old.sender_chat_id,
+				receiver_chat_id: old.receiver_chat_id,
+				content_cid: old.content_cid,
+				session_id: old.session_id,
+				msg_type: old.msg_type,
+				sent_at: old.sent_at,
+				is_read: old.is_read,
+				is_deleted_by_sender: old.is_deleted_by_sender,
+				is_deleted_by_receiver: old.is_deleted_by_receiver,
+				reply_to: old.reply_to,
+				edit_count: 0,
+				edited_at: None,
+				edit_history_cid: None,
+				expires_at: None,
+			})
+		});
+
+		Sessions::<T>::translate::<v0::Session<T>, _>(|_, old| {
+			translated += 1;
+			Some(Session {
+				id: old.id,
+				participants: old.participants,
+				last_message_id: old.last_message_id,
+				last_active: old.last_active,
+				created_at: old.created_at,
+				is_archived: old.is_archived,
+				disappear_after: None,
+			})
+		});
+
+		log::info!(
+			target: "runtime::chat-core",
+			"migrated {} messages and sessions to v1",
+			translated
+		);
+
+		T::DbWeight::get().reads_writes(translated, translated)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		let messages = Messages::<T>::iter_keys().count() as u64;
+		let sessions = Sessions::<T>::iter_keys().count() as u64;
+		Ok((messages, sessions).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let (messages, sessions): (u64, u64) = Decode::decode(&mut &state[..])
+			.map_err(|_| sp_runtime::TryRuntimeError::Other("invalid pre-upgrade state"))?;
+		ensure!(
+			Messages::<T>::iter_values().count() as u64 == messages,
+			"some messages failed to decode after migration"
+		);
+		ensure!(
+			Sessions::<T>::iter_values().count() as u64 == sessions,
+			"some sessions failed to decode after migration"
+		);
+		Ok(())
+	}
+}
+
+/// 函数级详细中文注释：v0 → v1 版本化迁移（仅在链上版本为 0 时执行，完成后写入版本 1）
+pub type MigrateV0ToV1<T> = VersionedMigration<
+	0,
+	1,
+	InnerMigrateV0ToV1<T>,
+	Pallet<T>,
+	<T as frame_system::Config>::DbWeight,
+>;
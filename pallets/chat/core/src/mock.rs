@@ -71,6 +71,19 @@ parameter_types! {
 	pub const MaxMessagesPerWindow: u32 = 10;
 	/// 消息过期时间：1000个区块（测试用）
 	pub const MessageExpirationTime: u64 = 1000;
+	/// 编辑窗口：10个区块（测试用）
+	pub const EditWindow: u64 = 10;
+	/// 每条消息最多编辑3次
+	pub const MaxEditsPerMessage: u32 = 3;
+	/// 表情最大字节数
+	pub const MaxReactionLen: u32 = 16;
+	/// 每个用户对单条消息最多2种表情
+	pub const MaxReactionsPerUser: u32 = 2;
+	/// 阅后即焚时长范围：5 - 1000个区块
+	pub const MinDisappearTimer: u64 = 5;
+	pub const MaxDisappearTimer: u64 = 1000;
+	/// 每个区块最多清理3条过期消息（测试游标延续）
+	pub const MaxExpiredPerBlock: u32 = 3;
 }
 
 /// 简单的测试用随机数生成器
@@ -116,6 +129,48 @@ impl pallet_chat::Config for Test {
 	type UnixTime = TestTime;
 	type MaxNicknameLength = frame_support::traits::ConstU32<64>;
 	type MaxSignatureLength = frame_support::traits::ConstU32<256>;
+	type EditWindow = EditWindow;
+	type MaxEditsPerMessage = MaxEditsPerMessage;
+	type MaxReactionLen = MaxReactionLen;
+	type MaxReactionsPerUser = MaxReactionsPerUser;
+	type MinDisappearTimer = MinDisappearTimer;
+	type MaxDisappearTimer = MaxDisappearTimer;
+	type MaxExpiredPerBlock = MaxExpiredPerBlock;
+	type GroupInbox = MockGroupInbox;
+}
+
+/// 函数级详细中文注释：模拟群聊收件箱
+/// 用于测试私聊与群聊合并，测试中通过 set_mock_groups 设置
+pub struct MockGroupInbox;
+
+std::thread_local! {
+	static MOCK_GROUPS: core::cell::RefCell<Vec<(u64, pallet_chat_common::GroupInboxItem<u64>)>> =
+		core::cell::RefCell::new(Vec::new());
+}
+
+/// 设置某账户所在的模拟群组
+pub fn set_mock_groups(user: u64, items: Vec<pallet_chat_common::GroupInboxItem<u64>>) {
+	MOCK_GROUPS.with(|groups| {
+		let mut groups = groups.borrow_mut();
+		groups.retain(|(u, _)| *u != user);
+		groups.extend(items.into_iter().map(|item| (user, item)));
+	});
+}
+
+impl pallet_chat_common::GroupInboxProvider<u64, u64> for MockGroupInbox {
+	fn group_inbox(user: &u64) -> Vec<pallet_chat_common::GroupInboxItem<u64>> {
+		MOCK_GROUPS.with(|groups| {
+			groups.borrow().iter().filter(|(u, _)| u == user).map(|(_, item)| item.clone()).collect()
+		})
+	}
+
+	fn group_unread_count(group_id: u64, user: &u64) -> u32 {
+		Self::group_inbox(user)
+			.into_iter()
+			.find(|item| item.group_id == group_id)
+			.map(|item| item.unread)
+			.unwrap_or(0)
+	}
 }
 
 /// 函数级详细中文注释：构建测试存储
@@ -0,0 +1,55 @@
+//! 聊天模块 Runtime API 定义
+//!
+//! 合并私聊会话与群聊，提供统一的未读计数和收件箱查询
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    /// 聊天模块 Runtime API
+    pub trait ChatApi<AccountId, Hash, BlockNumber>
+    where
+        AccountId: Codec,
+        Hash: Codec,
+        BlockNumber: Codec,
+    {
+        /// 获取未读汇总（私聊会话 + 群聊）
+        fn get_unread_summary(user: AccountId) -> UnreadSummary<Hash>;
+
+        /// 获取未读总数（私聊会话 + 群聊）
+        fn get_total_unread(user: AccountId) -> u32;
+
+        /// 获取指定群组的未读数
+        fn get_group_unread(group_id: u64, user: AccountId) -> u32;
+
+        /// 获取收件箱（私聊会话与群聊按最后活跃时间倒序，分页，每页最多100条）
+        fn get_inbox(user: AccountId, offset: u32, limit: u32) -> Vec<InboxEntry<Hash, BlockNumber>>;
+    }
+}
+
+/// 会话类型
+#[derive(codec::Encode, codec::Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+pub enum Conversation<Hash> {
+    /// 私聊会话
+    Private(Hash),
+    /// 群聊
+    Group(u64),
+}
+
+/// 收件箱条目 (用于 API 返回)
+#[derive(codec::Encode, codec::Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+pub struct InboxEntry<Hash, BlockNumber> {
+    pub conversation: Conversation<Hash>,
+    pub last_active: BlockNumber,
+    pub last_message_id: Option<u64>,
+    pub unread: u32,
+    pub is_archived: bool,  // 群聊恒为 false
+}
+
+/// 未读汇总 (用于 API 返回)
+#[derive(codec::Encode, codec::Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo, Default)]
+pub struct UnreadSummary<Hash> {
+    pub total: u32,
+    pub sessions: Vec<(Hash, u32)>,  // 仅包含未读数 > 0 的会话
+    pub groups: Vec<(u64, u32)>,     // 仅包含未读数 > 0 的群组
+}
@@ -1494,3 +1494,402 @@ fn test_automatic_chat_user_creation() {
 	});
 }
 
+
+// ============================================================================
+// 消息编辑 / 表情回应 / 阅后即焚测试
+// ============================================================================
+
+/// 发送一条 ALICE → BOB 的消息，返回会话ID
+fn send_alice_to_bob(id: u8) -> sp_core::H256 {
+	assert_ok!(Chat::send_message(
+		RuntimeOrigin::signed(ALICE),
+		BOB,
+		encrypted_cid(id),
+		0,
+		None
+	));
+	Chat::get_session_id(&ALICE, &BOB)
+}
+
+#[test]
+fn test_edit_message_works() {
+	new_test_ext().execute_with(|| {
+		send_alice_to_bob(1);
+
+		System::set_block_number(5);
+		assert_ok!(Chat::edit_message(
+			RuntimeOrigin::signed(ALICE),
+			0,
+			encrypted_cid(2),
+			encrypted_cid(100),
+		));
+
+		let msg = Chat::get_message(0).unwrap();
+		assert_eq!(msg.content_cid.to_vec(), encrypted_cid(2));
+		assert_eq!(msg.edit_history_cid.map(|c| c.to_vec()), Some(encrypted_cid(100)));
+		assert_eq!(msg.edit_count, 1);
+		assert_eq!(msg.edited_at, Some(5));
+		// 编辑不影响已读状态
+		assert_eq!(msg.is_read, false);
+
+		System::assert_last_event(
+			Event::MessageEdited {
+				msg_id: 0,
+				editor: ALICE,
+				new_cid: encrypted_cid(2).try_into().unwrap(),
+				edit_history_cid: encrypted_cid(100).try_into().unwrap(),
+				edit_count: 1,
+			}.into()
+		);
+	});
+}
+
+#[test]
+fn test_edit_message_checks() {
+	new_test_ext().execute_with(|| {
+		send_alice_to_bob(1);
+
+		// 只有发送方可以编辑
+		assert_noop!(
+			Chat::edit_message(RuntimeOrigin::signed(BOB), 0, encrypted_cid(2), encrypted_cid(100)),
+			Error::<Test>::NotSender
+		);
+
+		// 新CID必须加密
+		assert_noop!(
+			Chat::edit_message(RuntimeOrigin::signed(ALICE), 0, unencrypted_cid(), encrypted_cid(100)),
+			Error::<Test>::CidNotEncrypted
+		);
+
+		// 编辑次数上限（mock：3次）
+		for i in 0..3 {
+			assert_ok!(Chat::edit_message(
+				RuntimeOrigin::signed(ALICE),
+				0,
+				encrypted_cid(10 + i),
+				encrypted_cid(100 + i),
+			));
+		}
+		assert_noop!(
+			Chat::edit_message(RuntimeOrigin::signed(ALICE), 0, encrypted_cid(20), encrypted_cid(120)),
+			Error::<Test>::TooManyEdits
+		);
+	});
+}
+
+#[test]
+fn test_edit_message_window_and_deleted() {
+	new_test_ext().execute_with(|| {
+		send_alice_to_bob(1);
+		send_alice_to_bob(2);
+
+		// 超过编辑窗口（mock：10个区块）
+		System::set_block_number(12);
+		assert_noop!(
+			Chat::edit_message(RuntimeOrigin::signed(ALICE), 0, encrypted_cid(3), encrypted_cid(100)),
+			Error::<Test>::EditWindowExpired
+		);
+
+		// 发送方已删除的消息不能编辑
+		System::set_block_number(2);
+		assert_ok!(Chat::delete_message(RuntimeOrigin::signed(ALICE), 1));
+		assert_noop!(
+			Chat::edit_message(RuntimeOrigin::signed(ALICE), 1, encrypted_cid(3), encrypted_cid(100)),
+			Error::<Test>::MessageAlreadyDeleted
+		);
+	});
+}
+
+#[test]
+fn test_reactions_aggregate_per_message() {
+	new_test_ext().execute_with(|| {
+		send_alice_to_bob(1);
+		let thumbs = "👍".as_bytes().to_vec();
+		let heart = "❤️".as_bytes().to_vec();
+
+		assert_ok!(Chat::add_reaction(RuntimeOrigin::signed(ALICE), 0, thumbs.clone()));
+		assert_ok!(Chat::add_reaction(RuntimeOrigin::signed(BOB), 0, thumbs.clone()));
+		assert_ok!(Chat::add_reaction(RuntimeOrigin::signed(BOB), 0, heart.clone()));
+
+		let mut reactions = Chat::get_message_reactions(0);
+		reactions.sort();
+		let mut expected = vec![(thumbs.clone(), 2), (heart.clone(), 1)];
+		expected.sort();
+		assert_eq!(reactions, expected);
+
+		// 重复回应
+		assert_noop!(
+			Chat::add_reaction(RuntimeOrigin::signed(ALICE), 0, thumbs.clone()),
+			Error::<Test>::AlreadyReacted
+		);
+
+		// 撤销回应，计数归零后条目被删除
+		assert_ok!(Chat::remove_reaction(RuntimeOrigin::signed(BOB), 0, heart.clone()));
+		assert_eq!(Chat::get_message_reactions(0), vec![(thumbs.clone(), 2)]);
+		System::assert_last_event(
+			Event::ReactionRemoved {
+				msg_id: 0,
+				who: BOB,
+				reaction: heart.clone().try_into().unwrap(),
+				count: 0,
+			}.into()
+		);
+
+		assert_noop!(
+			Chat::remove_reaction(RuntimeOrigin::signed(BOB), 0, heart),
+			Error::<Test>::ReactionNotFound
+		);
+	});
+}
+
+#[test]
+fn test_reaction_checks() {
+	new_test_ext().execute_with(|| {
+		send_alice_to_bob(1);
+
+		// 非消息双方不能回应
+		assert_noop!(
+			Chat::add_reaction(RuntimeOrigin::signed(CHARLIE), 0, "👍".as_bytes().to_vec()),
+			Error::<Test>::NotAuthorized
+		);
+
+		// 空表情、过长表情、非UTF-8
+		assert_noop!(
+			Chat::add_reaction(RuntimeOrigin::signed(BOB), 0, vec![]),
+			Error::<Test>::InvalidReaction
+		);
+		assert_noop!(
+			Chat::add_reaction(RuntimeOrigin::signed(BOB), 0, vec![b'a'; 17]),
+			Error::<Test>::InvalidReaction
+		);
+		assert_noop!(
+			Chat::add_reaction(RuntimeOrigin::signed(BOB), 0, vec![0xff, 0xfe]),
+			Error::<Test>::InvalidReaction
+		);
+
+		// 每个用户最多2种表情
+		assert_ok!(Chat::add_reaction(RuntimeOrigin::signed(BOB), 0, "👍".as_bytes().to_vec()));
+		assert_ok!(Chat::add_reaction(RuntimeOrigin::signed(BOB), 0, "😂".as_bytes().to_vec()));
+		assert_noop!(
+			Chat::add_reaction(RuntimeOrigin::signed(BOB), 0, "🎉".as_bytes().to_vec()),
+			Error::<Test>::TooManyReactions
+		);
+
+		// 自己已删除的消息不能回应
+		assert_ok!(Chat::delete_message(RuntimeOrigin::signed(ALICE), 0));
+		assert_noop!(
+			Chat::add_reaction(RuntimeOrigin::signed(ALICE), 0, "👍".as_bytes().to_vec()),
+			Error::<Test>::MessageAlreadyDeleted
+		);
+	});
+}
+
+#[test]
+fn test_set_disappearing_timer_checks() {
+	new_test_ext().execute_with(|| {
+		let session_id = send_alice_to_bob(1);
+
+		// 超出范围（mock：5 - 1000）
+		assert_noop!(
+			Chat::set_disappearing_timer(RuntimeOrigin::signed(ALICE), session_id, Some(4)),
+			Error::<Test>::InvalidDisappearTimer
+		);
+		assert_noop!(
+			Chat::set_disappearing_timer(RuntimeOrigin::signed(ALICE), session_id, Some(1001)),
+			Error::<Test>::InvalidDisappearTimer
+		);
+
+		// 非参与者
+		assert_noop!(
+			Chat::set_disappearing_timer(RuntimeOrigin::signed(CHARLIE), session_id, Some(10)),
+			Error::<Test>::NotSessionParticipant
+		);
+
+		assert_ok!(Chat::set_disappearing_timer(RuntimeOrigin::signed(BOB), session_id, Some(10)));
+		assert_eq!(Chat::get_session(session_id).unwrap().disappear_after, Some(10));
+		System::assert_last_event(
+			Event::DisappearingTimerSet {
+				session_id,
+				operator: BOB,
+				disappear_after: Some(10),
+			}.into()
+		);
+
+		// 之前发送的消息不受影响
+		assert_eq!(Chat::get_message(0).unwrap().expires_at, None);
+
+		// 关闭
+		assert_ok!(Chat::set_disappearing_timer(RuntimeOrigin::signed(ALICE), session_id, None));
+		assert_eq!(Chat::get_session(session_id).unwrap().disappear_after, None);
+	});
+}
+
+#[test]
+fn test_disappearing_messages_expire_in_hook() {
+	use frame_support::traits::Hooks;
+
+	new_test_ext().execute_with(|| {
+		let session_id = send_alice_to_bob(0);
+		assert_ok!(Chat::set_disappearing_timer(RuntimeOrigin::signed(ALICE), session_id, Some(10)));
+
+		// 再发送4条阅后即焚消息（过期区块 = 11）
+		for i in 1..5 {
+			send_alice_to_bob(i);
+		}
+		assert_ok!(Chat::add_reaction(RuntimeOrigin::signed(BOB), 1, "👍".as_bytes().to_vec()));
+		assert_eq!(Chat::get_message(1).unwrap().expires_at, Some(11));
+		assert_eq!(Chat::get_unread_count(BOB, Some(session_id)), 5);
+
+		// 未到期前不清理
+		Chat::on_initialize(10);
+		assert!(Chat::get_message(1).is_some());
+
+		// 到期：每块最多清理3条，剩余1条由下一区块继续
+		Chat::on_initialize(11);
+		assert_eq!(
+			(1..5u64).filter(|id| Chat::get_message(*id).is_none()).count(),
+			3
+		);
+		System::assert_has_event(Event::MessagesExpired { block: 11, count: 3 }.into());
+
+		Chat::on_initialize(12);
+		for id in 1..5u64 {
+			assert!(Chat::get_message(id).is_none());
+		}
+
+		// 关联数据一并清理
+		assert!(Chat::get_message_reactions(1).is_empty());
+		assert_eq!(Chat::list_messages_by_session(session_id, 0, 100), vec![0]);
+		assert_eq!(Chat::get_unread_count(BOB, Some(session_id)), 1);
+		// 普通消息不受影响
+		assert!(Chat::get_message(0).is_some());
+	});
+}
+
+#[test]
+fn test_cleanup_old_messages_clears_reactions() {
+	new_test_ext().execute_with(|| {
+		send_alice_to_bob(1);
+		assert_ok!(Chat::add_reaction(RuntimeOrigin::signed(BOB), 0, "👍".as_bytes().to_vec()));
+		assert_ok!(Chat::delete_message(RuntimeOrigin::signed(ALICE), 0));
+		assert_ok!(Chat::delete_message(RuntimeOrigin::signed(BOB), 0));
+
+		System::set_block_number(1002);
+		assert_ok!(Chat::cleanup_old_messages(RuntimeOrigin::signed(CHARLIE), 10));
+
+		assert!(Chat::get_message(0).is_none());
+		assert!(Chat::get_message_reactions(0).is_empty());
+	});
+}
+
+// ============================================================================
+// 统一收件箱 / 未读汇总测试
+// ============================================================================
+
+#[test]
+fn test_inbox_merges_sessions_and_groups() {
+	use crate::runtime_api::Conversation;
+	use pallet_chat_common::GroupInboxItem;
+
+	new_test_ext().execute_with(|| {
+		// 区块1：ALICE → BOB
+		let ab = send_alice_to_bob(1);
+
+		// 区块5：CHARLIE → BOB
+		System::set_block_number(5);
+		assert_ok!(Chat::send_message(RuntimeOrigin::signed(CHARLIE), BOB, encrypted_cid(2), 0, None));
+		assert_ok!(Chat::send_message(RuntimeOrigin::signed(CHARLIE), BOB, encrypted_cid(3), 0, None));
+		let cb = Chat::get_session_id(&CHARLIE, &BOB);
+
+		// BOB 所在的两个群：区块3活跃（2条未读）、区块9活跃（无未读）
+		set_mock_groups(BOB, vec![
+			GroupInboxItem { group_id: 100, last_active: 3, last_message_id: Some(4), unread: 2 },
+			GroupInboxItem { group_id: 200, last_active: 9, last_message_id: None, unread: 0 },
+		]);
+
+		let inbox = Chat::get_inbox(BOB, 0, 10);
+		let order: Vec<Conversation<sp_core::H256>> = inbox.iter().map(|e| e.conversation.clone()).collect();
+		assert_eq!(order, vec![
+			Conversation::Group(200),
+			Conversation::Private(cb),
+			Conversation::Group(100),
+			Conversation::Private(ab),
+		]);
+		assert_eq!(inbox[1].unread, 2);
+		assert_eq!(inbox[1].last_message_id, Some(2));
+
+		// 分页
+		let page = Chat::get_inbox(BOB, 3, 10);
+		assert_eq!(page.len(), 1);
+		assert_eq!(page[0].conversation, Conversation::Private(ab));
+
+		// 未读汇总：私聊 1 + 2，群聊 2（未读为0的群不出现）
+		let summary = Chat::get_unread_summary(BOB);
+		assert_eq!(summary.total, 5);
+		assert_eq!(summary.sessions.len(), 2);
+		assert_eq!(summary.groups, vec![(100, 2)]);
+		assert_eq!(Chat::get_total_unread(BOB), 5);
+		assert_eq!(Chat::get_group_unread(100, BOB), 2);
+
+		// 标记私聊已读后总数减少
+		assert_ok!(Chat::mark_session_as_read(RuntimeOrigin::signed(BOB), cb));
+		assert_eq!(Chat::get_total_unread(BOB), 3);
+	});
+}
+
+// ============================================================================
+// 存储迁移测试
+// ============================================================================
+
+#[test]
+fn test_migrate_v0_to_v1_fills_new_fields_with_defaults() {
+	new_test_ext().execute_with(|| {
+		use crate::migrations::{v0, MigrateV0ToV1};
+		use frame_support::{
+			storage::unhashed,
+			traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		};
+
+		StorageVersion::new(0).put::<Chat>();
+		let session_id = sp_core::H256::repeat_byte(7);
+
+		// 以 v0 布局写入旧数据
+		let old_msg = v0::MessageMeta::<Test> {
+			sender: ALICE,
+			receiver: BOB,
+			sender_chat_id: None,
+			receiver_chat_id: None,
+			content_cid: encrypted_cid(1).try_into().unwrap(),
+			session_id,
+			msg_type: MessageType::Text,
+			sent_at: 1,
+			is_read: false,
+			is_deleted_by_sender: false,
+			is_deleted_by_receiver: false,
+			reply_to: None,
+		};
+		unhashed::put(&crate::Messages::<Test>::hashed_key_for(0u64), &old_msg);
+		let old_session = v0::Session::<Test> {
+			id: session_id,
+			participants: vec![ALICE, BOB].try_into().unwrap(),
+			last_message_id: 0,
+			last_active: 1,
+			created_at: 1,
+			is_archived: false,
+		};
+		unhashed::put(&crate::Sessions::<Test>::hashed_key_for(session_id), &old_session);
+
+		MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+		let msg = Chat::get_message(0).unwrap();
+		assert_eq!((msg.sender, msg.receiver), (ALICE, BOB));
+		assert_eq!(msg.edit_count, 0);
+		assert_eq!(msg.edited_at, None);
+		assert_eq!(msg.edit_history_cid, None);
+		assert_eq!(msg.expires_at, None);
+		let session = crate::Sessions::<Test>::get(session_id).unwrap();
+		assert_eq!(session.participants.to_vec(), vec![ALICE, BOB]);
+		assert_eq!(session.disappear_after, None);
+		assert_eq!(Chat::on_chain_storage_version(), StorageVersion::new(1));
+	});
+}
@@ -19,6 +19,11 @@ pub trait WeightInfo {
     fn update_chat_profile() -> Weight;
     fn set_user_status() -> Weight;
     fn update_privacy_settings() -> Weight;
+    fn edit_message() -> Weight;
+    fn add_reaction() -> Weight;
+    fn remove_reaction() -> Weight;
+    fn set_disappearing_timer() -> Weight;
+    fn expire_messages(n: u32) -> Weight;
 }
 
 /// Substrate 权重实现
@@ -91,6 +96,32 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(1))
             .saturating_add(T::DbWeight::get().writes(1))
     }
+    fn edit_message() -> Weight {
+        Weight::from_parts(50_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn add_reaction() -> Weight {
+        Weight::from_parts(40_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn remove_reaction() -> Weight {
+        Weight::from_parts(40_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn set_disappearing_timer() -> Weight {
+        Weight::from_parts(25_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn expire_messages(n: u32) -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(Weight::from_parts(60_000_000, 0).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().reads(2 * n as u64))
+            .saturating_add(T::DbWeight::get().writes(6 * n as u64))
+    }
 }
 
 /// 默认权重实现（用于测试）
@@ -108,4 +139,9 @@ impl WeightInfo for () {
     fn update_chat_profile() -> Weight { Weight::from_parts(50_000_000, 0) }
     fn set_user_status() -> Weight { Weight::from_parts(25_000_000, 0) }
     fn update_privacy_settings() -> Weight { Weight::from_parts(25_000_000, 0) }
+    fn edit_message() -> Weight { Weight::from_parts(50_000_000, 0) }
+    fn add_reaction() -> Weight { Weight::from_parts(40_000_000, 0) }
+    fn remove_reaction() -> Weight { Weight::from_parts(40_000_000, 0) }
+    fn set_disappearing_timer() -> Weight { Weight::from_parts(25_000_000, 0) }
+    fn expire_messages(n: u32) -> Weight { Weight::from_parts(10_000_000 + 60_000_000 * n as u64, 0) }
 }
@@ -0,0 +1,54 @@
+//! # 错误类型定义
+
+/// 群加密错误类型
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupCryptoError {
+    // ==================== 密钥错误 ====================
+    /// 无效的公钥
+    InvalidPublicKey,
+    /// 密钥派生失败
+    KeyDerivationFailed,
+    /// 接收方未发布抗量子预密钥
+    MissingPostQuantumKey,
+    /// 本地没有该纪元的密钥
+    UnknownEpoch(u32),
+    /// 纪元号不是递增的
+    StaleEpoch,
+
+    // ==================== 加密错误 ====================
+    /// 加密失败
+    EncryptionFailed,
+    /// 解密失败（密钥错误或数据被篡改）
+    DecryptionFailed,
+    /// 抗量子 KEM 失败
+    KemFailed,
+
+    // ==================== 格式错误 ====================
+    /// 不支持的协议版本
+    UnsupportedVersion(u8),
+    /// 数据格式错误
+    MalformedPayload,
+}
+
+impl core::fmt::Display for GroupCryptoError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidPublicKey => write!(f, "Invalid public key"),
+            Self::KeyDerivationFailed => write!(f, "Key derivation failed"),
+            Self::MissingPostQuantumKey => write!(f, "Recipient has no post-quantum prekey"),
+            Self::UnknownEpoch(epoch) => write!(f, "No key for epoch {}", epoch),
+            Self::StaleEpoch => write!(f, "Epoch is not newer than the current one"),
+            Self::EncryptionFailed => write!(f, "Encryption failed"),
+            Self::DecryptionFailed => write!(f, "Decryption failed"),
+            Self::KemFailed => write!(f, "Post-quantum KEM failed"),
+            Self::UnsupportedVersion(v) => write!(f, "Unsupported version: {}", v),
+            Self::MalformedPayload => write!(f, "Malformed payload"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GroupCryptoError {}
+
+/// 群加密结果类型
+pub type GroupCryptoResult<T> = Result<T, GroupCryptoError>;
@@ -0,0 +1,382 @@
+//! # 群密钥环模块
+//!
+//! 客户端按纪元保存群密钥：
+//! - 群主在链上纪元递增后调用 `rotate` 生成新纪元密钥并为每个成员包装
+//! - 成员从链上读取 `WrappedGroupKeys` 后调用 `accept_wrapped_key`
+//! - 历史消息按密文头中的纪元号选择密钥解密
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use rand_core::{CryptoRng, RngCore};
+
+use crate::error::{GroupCryptoError, GroupCryptoResult};
+use crate::keys::{MemberKeys, PostQuantumKem, PrekeyBundle};
+use crate::message::{decrypt_message, encrypt_message, message_epoch};
+use crate::wrap::{unwrap_epoch_key, wrap_epoch_key, WrapContext};
+use crate::EPOCH_KEY_SIZE;
+
+/// 单个群组的本地密钥环
+pub struct GroupKeyRing {
+    /// 群组ID
+    group_id: u64,
+    /// 纪元号 -> 纪元密钥
+    keys: BTreeMap<u32, [u8; EPOCH_KEY_SIZE]>,
+}
+
+impl GroupKeyRing {
+    /// 创建空密钥环
+    pub fn new(group_id: u64) -> Self {
+        Self { group_id, keys: BTreeMap::new() }
+    }
+
+    /// 群组ID
+    pub fn group_id(&self) -> u64 {
+        self.group_id
+    }
+
+    /// 本地已知的最新纪元
+    pub fn current_epoch(&self) -> Option<u32> {
+        self.keys.keys().next_back().copied()
+    }
+
+    /// 是否持有指定纪元的密钥
+    pub fn has_epoch(&self, epoch: u32) -> bool {
+        self.keys.contains_key(&epoch)
+    }
+
+    /// 群主：为新纪元生成密钥并为每个成员包装
+    ///
+    /// # 参数
+    /// - `epoch`: 链上 `GroupKeyStates` 的当前纪元
+    /// - `members`: 当前成员及其链上预密钥（群主自己也应包含在内）
+    /// - `kem`: 抗量子 KEM（军用级群组）
+    ///
+    /// # 返回
+    /// (成员, 包装密钥) 列表，可直接用于 `post_wrapped_keys`
+    pub fn rotate<M: Clone, K: PostQuantumKem, R: RngCore + CryptoRng>(
+        &mut self,
+        epoch: u32,
+        members: &[(M, PrekeyBundle)],
+        kem: Option<&K>,
+        rng: &mut R,
+    ) -> GroupCryptoResult<Vec<(M, Vec<u8>)>> {
+        if self.current_epoch().is_some_and(|current| epoch <= current) {
+            return Err(GroupCryptoError::StaleEpoch);
+        }
+
+        let mut epoch_key = [0u8; EPOCH_KEY_SIZE];
+        rng.fill_bytes(&mut epoch_key);
+
+        let wraps = self.wrap_for(epoch, &epoch_key, members, kem, rng)?;
+        self.keys.insert(epoch, epoch_key);
+        epoch_key.iter_mut().for_each(|b| *b = 0);
+
+        Ok(wraps)
+    }
+
+    /// 群主：为已有纪元补充包装（例如成员迟于轮换才发布预密钥）
+    pub fn rewrap<M: Clone, K: PostQuantumKem, R: RngCore + CryptoRng>(
+        &self,
+        epoch: u32,
+        members: &[(M, PrekeyBundle)],
+        kem: Option<&K>,
+        rng: &mut R,
+    ) -> GroupCryptoResult<Vec<(M, Vec<u8>)>> {
+        let epoch_key = self.keys.get(&epoch).ok_or(GroupCryptoError::UnknownEpoch(epoch))?;
+        self.wrap_for(epoch, epoch_key, members, kem, rng)
+    }
+
+    fn wrap_for<M: Clone, K: PostQuantumKem, R: RngCore + CryptoRng>(
+        &self,
+        epoch: u32,
+        epoch_key: &[u8; EPOCH_KEY_SIZE],
+        members: &[(M, PrekeyBundle)],
+        kem: Option<&K>,
+        rng: &mut R,
+    ) -> GroupCryptoResult<Vec<(M, Vec<u8>)>> {
+        let ctx = WrapContext { group_id: self.group_id, epoch };
+        members
+            .iter()
+            .map(|(member, prekey)| {
+                wrap_epoch_key(&ctx, epoch_key, prekey, kem, rng).map(|payload| (member.clone(), payload))
+            })
+            .collect()
+    }
+
+    /// 成员：解包链上的纪元密钥并保存
+    pub fn accept_wrapped_key<K: PostQuantumKem>(
+        &mut self,
+        epoch: u32,
+        payload: &[u8],
+        member: &MemberKeys,
+        kem: Option<&K>,
+    ) -> GroupCryptoResult<()> {
+        let ctx = WrapContext { group_id: self.group_id, epoch };
+        let epoch_key = unwrap_epoch_key(&ctx, payload, member, kem)?;
+        self.keys.insert(epoch, epoch_key);
+        Ok(())
+    }
+
+    /// 使用最新纪元密钥加密消息
+    pub fn encrypt_message<R: RngCore + CryptoRng>(
+        &self,
+        sender: &[u8],
+        plaintext: &[u8],
+        rng: &mut R,
+    ) -> GroupCryptoResult<Vec<u8>> {
+        let (epoch, key) = self
+            .keys
+            .iter()
+            .next_back()
+            .ok_or(GroupCryptoError::UnknownEpoch(0))?;
+        encrypt_message(key, self.group_id, *epoch, sender, plaintext, rng)
+    }
+
+    /// 按密文头中的纪元号选择密钥解密
+    pub fn decrypt_message(&self, sender: &[u8], ciphertext: &[u8]) -> GroupCryptoResult<Vec<u8>> {
+        let epoch = message_epoch(ciphertext)?;
+        let key = self.keys.get(&epoch).ok_or(GroupCryptoError::UnknownEpoch(epoch))?;
+        decrypt_message(key, self.group_id, sender, ciphertext)
+    }
+
+    /// 丢弃早于指定纪元的密钥（放弃历史消息的可读性）
+    pub fn forget_before(&mut self, epoch: u32) {
+        let retained = self.keys.split_off(&epoch);
+        for key in self.keys.values_mut() {
+            key.iter_mut().for_each(|b| *b = 0);
+        }
+        self.keys = retained;
+    }
+}
+
+impl Drop for GroupKeyRing {
+    fn drop(&mut self) {
+        // 清零所有纪元密钥
+        for key in self.keys.values_mut() {
+            key.iter_mut().for_each(|b| *b = 0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wrap::test_kem::X25519Kem;
+    use crate::wrap::NoKem;
+    use alloc::collections::BTreeSet;
+
+    const GROUP_ID: u64 = 10_000_000_042;
+
+    /// 模拟 `pallet-chat-group` 的链上状态：
+    /// 成员变动使纪元 +1，离开的成员的包装密钥被删除
+    #[derive(Default)]
+    struct MockChain {
+        prekeys: BTreeMap<&'static str, PrekeyBundle>,
+        members: BTreeSet<&'static str>,
+        epoch: u32,
+        wrapped: BTreeMap<&'static str, (u32, Vec<u8>)>,
+    }
+
+    impl MockChain {
+        fn publish_prekey(&mut self, who: &'static str, bundle: PrekeyBundle) {
+            self.prekeys.insert(who, bundle);
+        }
+
+        fn join(&mut self, who: &'static str) {
+            assert!(self.prekeys.contains_key(who), "prekey must be published before joining");
+            self.members.insert(who);
+            self.epoch += 1;
+        }
+
+        fn leave(&mut self, who: &'static str) {
+            self.members.remove(who);
+            self.wrapped.remove(who);
+            self.epoch += 1;
+        }
+
+        fn member_prekeys(&self) -> Vec<(&'static str, PrekeyBundle)> {
+            self.members.iter().map(|m| (*m, self.prekeys[m].clone())).collect()
+        }
+
+        fn post_wrapped_keys(&mut self, epoch: u32, wraps: Vec<(&'static str, Vec<u8>)>) {
+            assert_eq!(epoch, self.epoch, "wraps must target the current epoch");
+            for (member, payload) in wraps {
+                assert!(self.members.contains(member));
+                self.wrapped.insert(member, (epoch, payload));
+            }
+        }
+
+        fn rewrap_pending(&self) -> bool {
+            self.members
+                .iter()
+                .any(|m| self.wrapped.get(m).is_none_or(|(epoch, _)| *epoch != self.epoch))
+        }
+    }
+
+    struct Client {
+        name: &'static str,
+        keys: MemberKeys,
+        ring: GroupKeyRing,
+    }
+
+    impl Client {
+        fn new(name: &'static str, rng: &mut rand::rngs::ThreadRng) -> Self {
+            Self { name, keys: MemberKeys::generate(rng), ring: GroupKeyRing::new(GROUP_ID) }
+        }
+
+        fn sync(&mut self, chain: &MockChain) -> GroupCryptoResult<()> {
+            let (epoch, payload) = chain.wrapped.get(self.name).ok_or(GroupCryptoError::UnknownEpoch(chain.epoch))?;
+            self.ring.accept_wrapped_key(*epoch, payload, &self.keys, None::<&NoKem>)
+        }
+    }
+
+    fn rotate(owner: &mut Client, chain: &mut MockChain, rng: &mut rand::rngs::ThreadRng) {
+        let epoch = chain.epoch;
+        let wraps = owner
+            .ring
+            .rotate(epoch, &chain.member_prekeys(), None::<&NoKem>, rng)
+            .unwrap();
+        chain.post_wrapped_keys(epoch, wraps);
+        assert!(!chain.rewrap_pending());
+    }
+
+    #[test]
+    fn test_full_rotation_flow() {
+        let mut rng = rand::thread_rng();
+        let mut chain = MockChain::default();
+
+        let mut owner = Client::new("owner", &mut rng);
+        let mut bob = Client::new("bob", &mut rng);
+        let mut carol = Client::new("carol", &mut rng);
+        let mut dave = Client::new("dave", &mut rng);
+        for c in [&owner, &bob, &carol, &dave] {
+            chain.publish_prekey(c.name, c.keys.prekey_bundle());
+        }
+
+        // 纪元 0：群主建群
+        chain.members.insert("owner");
+        rotate(&mut owner, &mut chain, &mut rng);
+
+        // 纪元 1、2：bob 和 carol 加入，每次都需要重新包装
+        chain.join("bob");
+        assert!(chain.rewrap_pending());
+        rotate(&mut owner, &mut chain, &mut rng);
+        chain.join("carol");
+        assert!(chain.rewrap_pending());
+        rotate(&mut owner, &mut chain, &mut rng);
+        assert_eq!(chain.epoch, 2);
+
+        bob.sync(&chain).unwrap();
+        carol.sync(&chain).unwrap();
+        assert_eq!(bob.ring.current_epoch(), Some(2));
+
+        // 所有人都能读纪元 2 的消息
+        let msg_e2 = bob.ring.encrypt_message(b"bob", b"before carol leaves", &mut rng).unwrap();
+        assert_eq!(owner.ring.decrypt_message(b"bob", &msg_e2).unwrap(), b"before carol leaves");
+        assert_eq!(carol.ring.decrypt_message(b"bob", &msg_e2).unwrap(), b"before carol leaves");
+
+        // 纪元 3：carol 离开
+        chain.leave("carol");
+        rotate(&mut owner, &mut chain, &mut rng);
+        bob.sync(&chain).unwrap();
+
+        // carol 无法获取新纪元密钥，也无法借用 bob 的包装
+        assert!(carol.sync(&chain).is_err());
+        let (epoch, bob_payload) = chain.wrapped["bob"].clone();
+        assert!(carol
+            .ring
+            .accept_wrapped_key(epoch, &bob_payload, &carol.keys, None::<&NoKem>)
+            .is_err());
+
+        let msg_e3 = owner.ring.encrypt_message(b"owner", b"after carol left", &mut rng).unwrap();
+        assert_eq!(bob.ring.decrypt_message(b"owner", &msg_e3).unwrap(), b"after carol left");
+        assert_eq!(
+            carol.ring.decrypt_message(b"owner", &msg_e3),
+            Err(GroupCryptoError::UnknownEpoch(3))
+        );
+
+        // 纪元 4：dave 加入，读不到加入前的消息
+        chain.join("dave");
+        rotate(&mut owner, &mut chain, &mut rng);
+        dave.sync(&chain).unwrap();
+        bob.sync(&chain).unwrap();
+
+        assert_eq!(dave.ring.decrypt_message(b"owner", &msg_e3), Err(GroupCryptoError::UnknownEpoch(3)));
+        let msg_e4 = dave.ring.encrypt_message(b"dave", b"hi all", &mut rng).unwrap();
+        assert_eq!(owner.ring.decrypt_message(b"dave", &msg_e4).unwrap(), b"hi all");
+        assert_eq!(bob.ring.decrypt_message(b"dave", &msg_e4).unwrap(), b"hi all");
+
+        // bob 仍可读取历史消息
+        assert_eq!(bob.ring.decrypt_message(b"owner", &msg_e3).unwrap(), b"after carol left");
+    }
+
+    #[test]
+    fn test_rotate_rejects_stale_epoch() {
+        let mut rng = rand::thread_rng();
+        let owner = MemberKeys::generate(&mut rng);
+        let members = vec![(1u64, owner.prekey_bundle())];
+        let mut ring = GroupKeyRing::new(GROUP_ID);
+
+        ring.rotate(5, &members, None::<&NoKem>, &mut rng).unwrap();
+        assert_eq!(
+            ring.rotate(5, &members, None::<&NoKem>, &mut rng).err(),
+            Some(GroupCryptoError::StaleEpoch)
+        );
+        assert_eq!(
+            ring.rotate(4, &members, None::<&NoKem>, &mut rng).err(),
+            Some(GroupCryptoError::StaleEpoch)
+        );
+    }
+
+    #[test]
+    fn test_rewrap_for_late_prekey() {
+        let mut rng = rand::thread_rng();
+        let owner = MemberKeys::generate(&mut rng);
+        let late = MemberKeys::generate(&mut rng);
+        let mut owner_ring = GroupKeyRing::new(GROUP_ID);
+        let mut late_ring = GroupKeyRing::new(GROUP_ID);
+
+        owner_ring.rotate(1, &[(0u8, owner.prekey_bundle())], None::<&NoKem>, &mut rng).unwrap();
+        let wraps = owner_ring.rewrap(1, &[(1u8, late.prekey_bundle())], None::<&NoKem>, &mut rng).unwrap();
+        late_ring.accept_wrapped_key(1, &wraps[0].1, &late, None::<&NoKem>).unwrap();
+
+        let msg = owner_ring.encrypt_message(b"owner", b"welcome", &mut rng).unwrap();
+        assert_eq!(late_ring.decrypt_message(b"owner", &msg).unwrap(), b"welcome");
+        assert!(owner_ring.rewrap(2, &[(1u8, late.prekey_bundle())], None::<&NoKem>, &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_hybrid_rotation() {
+        let mut rng = rand::thread_rng();
+        let (pq_public, pq_secret) = X25519Kem::keypair(&mut rng);
+        let member = MemberKeys::generate(&mut rng).with_post_quantum(pq_public, pq_secret);
+        let mut owner_ring = GroupKeyRing::new(GROUP_ID);
+        let mut member_ring = GroupKeyRing::new(GROUP_ID);
+
+        let wraps = owner_ring
+            .rotate(0, &[("m", member.prekey_bundle())], Some(&X25519Kem), &mut rng)
+            .unwrap();
+        member_ring.accept_wrapped_key(0, &wraps[0].1, &member, Some(&X25519Kem)).unwrap();
+
+        let msg = member_ring.encrypt_message(b"m", b"pq", &mut rng).unwrap();
+        assert_eq!(owner_ring.decrypt_message(b"m", &msg).unwrap(), b"pq");
+    }
+
+    #[test]
+    fn test_forget_before() {
+        let mut rng = rand::thread_rng();
+        let owner = MemberKeys::generate(&mut rng);
+        let members = vec![((), owner.prekey_bundle())];
+        let mut ring = GroupKeyRing::new(GROUP_ID);
+
+        ring.rotate(0, &members, None::<&NoKem>, &mut rng).unwrap();
+        let old = ring.encrypt_message(b"o", b"old", &mut rng).unwrap();
+        ring.rotate(1, &members, None::<&NoKem>, &mut rng).unwrap();
+
+        ring.forget_before(1);
+        assert!(!ring.has_epoch(0));
+        assert!(ring.has_epoch(1));
+        assert_eq!(ring.decrypt_message(b"o", &old), Err(GroupCryptoError::UnknownEpoch(0)));
+    }
+}
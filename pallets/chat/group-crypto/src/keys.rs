@@ -0,0 +1,146 @@
+//! # 成员密钥模块
+//!
+//! 管理成员本地密钥与链上发布的预密钥：
+//! - X25519 静态密钥对（必需）
+//! - 抗量子 KEM 密钥对（可选，军用级群组要求）
+
+use alloc::vec::Vec;
+
+use rand_core::{CryptoRng, RngCore};
+use x25519_dalek::{PublicKey as X25519PublicKey, StaticSecret};
+
+use crate::error::GroupCryptoResult;
+use crate::X25519_KEY_SIZE;
+
+/// 抗量子 KEM 抽象
+///
+/// 本库不绑定具体实现，客户端可接入 ML-KEM-768 等算法。
+/// 共享密钥会与 X25519 共享密钥一起输入 HKDF（混合模式），
+/// 任一算法未被攻破即可保证包装密钥的机密性。
+pub trait PostQuantumKem {
+    /// 封装：针对接收方公钥生成 (密文, 32 字节共享密钥)
+    fn encapsulate<R: RngCore + CryptoRng>(
+        &self,
+        recipient_public: &[u8],
+        rng: &mut R,
+    ) -> GroupCryptoResult<(Vec<u8>, [u8; 32])>;
+
+    /// 解封装：使用本地私钥从密文恢复共享密钥
+    fn decapsulate(&self, secret_key: &[u8], ciphertext: &[u8]) -> GroupCryptoResult<[u8; 32]>;
+}
+
+/// 链上预密钥
+///
+/// 对应 `pallet-chat-group` 的 `MemberPrekeys` 存储项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrekeyBundle {
+    /// X25519 公钥
+    pub x25519: [u8; X25519_KEY_SIZE],
+    /// 抗量子 KEM 公钥（可选）
+    pub post_quantum: Option<Vec<u8>>,
+}
+
+/// 成员本地密钥
+///
+/// 私钥永远不离开客户端，只有 `prekey_bundle()` 的内容会上链
+pub struct MemberKeys {
+    /// X25519 私钥
+    x25519_secret: StaticSecret,
+    /// X25519 公钥
+    x25519_public: X25519PublicKey,
+    /// 抗量子 KEM 私钥
+    pq_secret: Option<Vec<u8>>,
+    /// 抗量子 KEM 公钥
+    pq_public: Option<Vec<u8>>,
+}
+
+impl MemberKeys {
+    /// 生成新的 X25519 预密钥
+    pub fn generate<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        let x25519_secret = StaticSecret::random_from_rng(&mut *rng);
+        Self::from_x25519_secret(x25519_secret.to_bytes())
+    }
+
+    /// 从已保存的 X25519 私钥恢复
+    pub fn from_x25519_secret(secret: [u8; X25519_KEY_SIZE]) -> Self {
+        let x25519_secret = StaticSecret::from(secret);
+        let x25519_public = X25519PublicKey::from(&x25519_secret);
+        Self {
+            x25519_secret,
+            x25519_public,
+            pq_secret: None,
+            pq_public: None,
+        }
+    }
+
+    /// 附加抗量子 KEM 密钥对
+    pub fn with_post_quantum(mut self, public_key: Vec<u8>, secret_key: Vec<u8>) -> Self {
+        self.pq_public = Some(public_key);
+        self.pq_secret = Some(secret_key);
+        self
+    }
+
+    /// 获取 X25519 公钥
+    pub fn x25519_public(&self) -> [u8; X25519_KEY_SIZE] {
+        self.x25519_public.to_bytes()
+    }
+
+    /// 导出 X25519 私钥（用于本地安全存储）
+    pub fn x25519_secret_bytes(&self) -> [u8; X25519_KEY_SIZE] {
+        self.x25519_secret.to_bytes()
+    }
+
+    /// 生成要发布到链上的预密钥
+    pub fn prekey_bundle(&self) -> PrekeyBundle {
+        PrekeyBundle {
+            x25519: self.x25519_public(),
+            post_quantum: self.pq_public.clone(),
+        }
+    }
+
+    /// 与对方公钥进行 ECDH
+    pub(crate) fn diffie_hellman(&self, their_public: &[u8; X25519_KEY_SIZE]) -> [u8; 32] {
+        let their_public = X25519PublicKey::from(*their_public);
+        self.x25519_secret.diffie_hellman(&their_public).to_bytes()
+    }
+
+    /// 抗量子 KEM 私钥
+    pub(crate) fn pq_secret(&self) -> Option<&[u8]> {
+        self.pq_secret.as_deref()
+    }
+}
+
+impl Drop for MemberKeys {
+    fn drop(&mut self) {
+        // 清零抗量子私钥（X25519 私钥由 x25519-dalek 自行处理）
+        if let Some(secret) = self.pq_secret.as_mut() {
+            secret.iter_mut().for_each(|b| *b = 0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restore_from_secret() {
+        let mut rng = rand::thread_rng();
+        let keys = MemberKeys::generate(&mut rng);
+
+        let restored = MemberKeys::from_x25519_secret(keys.x25519_secret_bytes());
+
+        assert_eq!(keys.x25519_public(), restored.x25519_public());
+        assert_eq!(keys.prekey_bundle(), restored.prekey_bundle());
+    }
+
+    #[test]
+    fn test_prekey_bundle_includes_post_quantum() {
+        let mut rng = rand::thread_rng();
+        let keys = MemberKeys::generate(&mut rng).with_post_quantum(vec![7u8; 64], vec![9u8; 64]);
+
+        let bundle = keys.prekey_bundle();
+        assert_eq!(bundle.x25519, keys.x25519_public());
+        assert_eq!(bundle.post_quantum, Some(vec![7u8; 64]));
+    }
+}
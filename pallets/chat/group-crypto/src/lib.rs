@@ -0,0 +1,72 @@
+//! # 群聊端到端加密客户端库
+//!
+//! 本库实现 `pallet-chat-group` 的纪元群密钥协议的客户端部分。
+//! 链上只保存公开材料（成员预密钥、包装后的纪元密钥、纪元号），
+//! 所有加解密都在客户端完成。
+//!
+//! ## 协议概述
+//!
+//! ```text
+//! ┌──────────────┐  publish_prekey   ┌─────────────────────────────┐
+//! │    成员      │ ────────────────▶ │  MemberPrekeys (X25519 [+PQ])│
+//! └──────────────┘                   └─────────────────────────────┘
+//!        ▲                                        │
+//!        │ unwrap_epoch_key                       ▼
+//! ┌──────────────┐  post_wrapped_keys ┌─────────────────────────────┐
+//! │ WrappedGroup │ ◀───────────────── │  群主：生成纪元密钥并逐个包装 │
+//! │    Keys      │                    └─────────────────────────────┘
+//! └──────────────┘
+//!        │ 加入/离开/踢出 → 纪元 +1 → 群主必须重新包装
+//! ```
+//!
+//! 1. **预密钥**: 每个成员生成 X25519 静态密钥对（可选 ML-KEM），公钥发布到链上
+//! 2. **纪元密钥**: 群主为每个纪元生成 256 位随机密钥
+//! 3. **密钥包装**: 临时 X25519 ECDH (+ 可选 PQ KEM) → HKDF-SHA256 → AES-256-GCM
+//! 4. **消息加密**: 纪元密钥经 HKDF 派生消息密钥，AES-256-GCM 加密，
+//!    群组ID/纪元/发送者作为附加认证数据
+//! 5. **轮换**: 成员变动后链上纪元递增，旧成员拿不到新纪元密钥，
+//!    新成员拿不到旧纪元密钥
+//!
+//! ## 使用示例
+//!
+//! ```ignore
+//! use chat_group_crypto::{GroupKeyRing, MemberKeys};
+//!
+//! // 群主轮换纪元密钥并为每个成员包装
+//! let rotation = owner_ring.rotate(new_epoch, &members, &mut rng)?;
+//!
+//! // 成员解包并加入本地密钥环
+//! member_ring.accept_wrapped_key(new_epoch, &payload, &member_keys)?;
+//! let ciphertext = member_ring.encrypt_message(sender, b"hello", &mut rng)?;
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod error;
+pub mod keyring;
+pub mod keys;
+pub mod message;
+pub mod wrap;
+
+pub use error::*;
+pub use keyring::*;
+pub use keys::*;
+pub use message::*;
+pub use wrap::*;
+
+/// 协议版本
+pub const PROTOCOL_VERSION: u8 = 1;
+
+/// X25519 公钥长度
+pub const X25519_KEY_SIZE: usize = 32;
+
+/// 纪元密钥长度（AES-256）
+pub const EPOCH_KEY_SIZE: usize = 32;
+
+/// AES-256-GCM Nonce 大小
+pub const NONCE_SIZE: usize = 12;
+
+/// AES-256-GCM 认证标签大小
+pub const AUTH_TAG_SIZE: usize = 16;
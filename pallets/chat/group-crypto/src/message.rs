@@ -0,0 +1,183 @@
+//! # 群消息加密模块
+//!
+//! 使用纪元密钥加密群消息。消息格式：
+//!
+//! | 偏移 | 长度 | 内容 |
+//! |------|------|------|
+//! | 0    | 1    | 协议版本 |
+//! | 1    | 4    | 纪元号 (LE) |
+//! | 5    | 12   | Nonce |
+//! | 17   | n+16 | 密文 + 认证标签 |
+//!
+//! 群组ID、纪元号和发送者账户作为附加认证数据，
+//! 防止密文被转发到其他群组或冒充其他发送者。
+
+use alloc::vec::Vec;
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use hkdf::Hkdf;
+use rand_core::{CryptoRng, RngCore};
+use sha2::Sha256;
+
+use crate::error::{GroupCryptoError, GroupCryptoResult};
+use crate::{AUTH_TAG_SIZE, EPOCH_KEY_SIZE, NONCE_SIZE, PROTOCOL_VERSION};
+
+/// 消息头长度
+pub const MESSAGE_HEADER_SIZE: usize = 1 + 4 + NONCE_SIZE;
+
+/// HKDF 域分离标签
+const MESSAGE_INFO: &[u8] = b"stardust-group-msg-v1";
+
+/// 从纪元密钥派生消息密钥
+fn derive_message_key(
+    epoch_key: &[u8; EPOCH_KEY_SIZE],
+    group_id: u64,
+    epoch: u32,
+) -> GroupCryptoResult<[u8; EPOCH_KEY_SIZE]> {
+    let mut info = Vec::with_capacity(MESSAGE_INFO.len() + 12);
+    info.extend_from_slice(MESSAGE_INFO);
+    info.extend_from_slice(&group_id.to_le_bytes());
+    info.extend_from_slice(&epoch.to_le_bytes());
+
+    let hkdf = Hkdf::<Sha256>::new(None, epoch_key);
+    let mut key = [0u8; EPOCH_KEY_SIZE];
+    hkdf.expand(&info, &mut key)
+        .map_err(|_| GroupCryptoError::KeyDerivationFailed)?;
+    Ok(key)
+}
+
+/// 构造附加认证数据
+fn message_aad(group_id: u64, epoch: u32, sender: &[u8]) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(13 + sender.len());
+    aad.push(PROTOCOL_VERSION);
+    aad.extend_from_slice(&group_id.to_le_bytes());
+    aad.extend_from_slice(&epoch.to_le_bytes());
+    aad.extend_from_slice(sender);
+    aad
+}
+
+/// 读取密文中的纪元号
+pub fn message_epoch(ciphertext: &[u8]) -> GroupCryptoResult<u32> {
+    if ciphertext.len() < MESSAGE_HEADER_SIZE + AUTH_TAG_SIZE {
+        return Err(GroupCryptoError::MalformedPayload);
+    }
+    if ciphertext[0] != PROTOCOL_VERSION {
+        return Err(GroupCryptoError::UnsupportedVersion(ciphertext[0]));
+    }
+    Ok(u32::from_le_bytes([ciphertext[1], ciphertext[2], ciphertext[3], ciphertext[4]]))
+}
+
+/// 加密群消息
+///
+/// # 参数
+/// - `epoch_key`: 当前纪元密钥
+/// - `group_id`: 群组ID
+/// - `epoch`: 纪元号
+/// - `sender`: 发送者账户（SCALE 编码）
+/// - `plaintext`: 明文
+pub fn encrypt_message<R: RngCore + CryptoRng>(
+    epoch_key: &[u8; EPOCH_KEY_SIZE],
+    group_id: u64,
+    epoch: u32,
+    sender: &[u8],
+    plaintext: &[u8],
+    rng: &mut R,
+) -> GroupCryptoResult<Vec<u8>> {
+    let key = derive_message_key(epoch_key, group_id, epoch)?;
+
+    let mut nonce = [0u8; NONCE_SIZE];
+    rng.fill_bytes(&mut nonce);
+
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|_| GroupCryptoError::EncryptionFailed)?;
+    let sealed = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload { msg: plaintext, aad: &message_aad(group_id, epoch, sender) },
+        )
+        .map_err(|_| GroupCryptoError::EncryptionFailed)?;
+
+    let mut out = Vec::with_capacity(MESSAGE_HEADER_SIZE + sealed.len());
+    out.push(PROTOCOL_VERSION);
+    out.extend_from_slice(&epoch.to_le_bytes());
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&sealed);
+    Ok(out)
+}
+
+/// 解密群消息
+///
+/// # 参数
+/// - `epoch_key`: 消息所属纪元的密钥（纪元号可用 `message_epoch` 读取）
+/// - `group_id`: 群组ID
+/// - `sender`: 链上记录的发送者账户（SCALE 编码）
+/// - `ciphertext`: 密文
+pub fn decrypt_message(
+    epoch_key: &[u8; EPOCH_KEY_SIZE],
+    group_id: u64,
+    sender: &[u8],
+    ciphertext: &[u8],
+) -> GroupCryptoResult<Vec<u8>> {
+    let epoch = message_epoch(ciphertext)?;
+    let key = derive_message_key(epoch_key, group_id, epoch)?;
+
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|_| GroupCryptoError::DecryptionFailed)?;
+    cipher
+        .decrypt(
+            Nonce::from_slice(&ciphertext[5..MESSAGE_HEADER_SIZE]),
+            Payload {
+                msg: &ciphertext[MESSAGE_HEADER_SIZE..],
+                aad: &message_aad(group_id, epoch, sender),
+            },
+        )
+        .map_err(|_| GroupCryptoError::DecryptionFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_roundtrip() {
+        let mut rng = rand::thread_rng();
+        let key = [3u8; EPOCH_KEY_SIZE];
+
+        let ciphertext = encrypt_message(&key, 42, 7, b"alice", b"hello group", &mut rng).unwrap();
+        assert_eq!(message_epoch(&ciphertext).unwrap(), 7);
+
+        let plaintext = decrypt_message(&key, 42, b"alice", &ciphertext).unwrap();
+        assert_eq!(plaintext, b"hello group");
+    }
+
+    #[test]
+    fn test_message_bound_to_sender_and_group() {
+        let mut rng = rand::thread_rng();
+        let key = [3u8; EPOCH_KEY_SIZE];
+
+        let ciphertext = encrypt_message(&key, 42, 0, b"alice", b"hi", &mut rng).unwrap();
+
+        // 冒充发送者
+        assert_eq!(
+            decrypt_message(&key, 42, b"mallory", &ciphertext),
+            Err(GroupCryptoError::DecryptionFailed)
+        );
+        // 转发到其他群组
+        assert_eq!(
+            decrypt_message(&key, 43, b"alice", &ciphertext),
+            Err(GroupCryptoError::DecryptionFailed)
+        );
+    }
+
+    #[test]
+    fn test_tampered_epoch_header_rejected() {
+        let mut rng = rand::thread_rng();
+        let key = [3u8; EPOCH_KEY_SIZE];
+
+        let mut ciphertext = encrypt_message(&key, 42, 1, b"alice", b"hi", &mut rng).unwrap();
+        ciphertext[1] = 2;
+
+        assert!(decrypt_message(&key, 42, b"alice", &ciphertext).is_err());
+    }
+}
@@ -0,0 +1,399 @@
+//! # 纪元密钥包装模块
+//!
+//! 群主使用每个成员的链上预密钥包装纪元密钥：
+//!
+//! ```text
+//! 临时 X25519 私钥 ─ECDH─▶ 共享密钥 ─┐
+//! 抗量子 KEM 封装（可选）──────────────┼─HKDF-SHA256─▶ KEK ─AES-256-GCM─▶ 包装密钥
+//! group_id / epoch / 双方公钥 ─────────┘
+//! ```
+//!
+//! 包装格式（`WRAPPED_KEY_SIZE` 字节，混合模式追加 KEM 密文）：
+//!
+//! | 偏移 | 长度 | 内容 |
+//! |------|------|------|
+//! | 0    | 1    | 协议版本 |
+//! | 1    | 1    | 模式 (0 = X25519, 1 = 混合) |
+//! | 2    | 32   | 临时 X25519 公钥 |
+//! | 34   | 12   | Nonce |
+//! | 46   | 48   | 密文 + 认证标签 |
+//! | 94   | 2    | KEM 密文长度 (LE，仅混合模式) |
+//! | 96   | n    | KEM 密文（仅混合模式） |
+
+use alloc::vec::Vec;
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use hkdf::Hkdf;
+use rand_core::{CryptoRng, RngCore};
+use sha2::Sha256;
+use x25519_dalek::{PublicKey as X25519PublicKey, StaticSecret};
+
+use crate::error::{GroupCryptoError, GroupCryptoResult};
+use crate::keys::{MemberKeys, PostQuantumKem, PrekeyBundle};
+use crate::{AUTH_TAG_SIZE, EPOCH_KEY_SIZE, NONCE_SIZE, PROTOCOL_VERSION, X25519_KEY_SIZE};
+
+/// X25519 模式下包装密钥的长度
+pub const WRAPPED_KEY_SIZE: usize = 2 + X25519_KEY_SIZE + NONCE_SIZE + EPOCH_KEY_SIZE + AUTH_TAG_SIZE;
+
+/// 包装模式：仅 X25519
+const MODE_X25519: u8 = 0;
+
+/// 包装模式：X25519 + 抗量子 KEM
+const MODE_HYBRID: u8 = 1;
+
+/// HKDF 域分离标签
+const WRAP_INFO: &[u8] = b"stardust-group-wrap-v1";
+
+/// 包装上下文
+///
+/// 包装密钥与群组和纪元绑定，防止被挪用到其他群组或纪元
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WrapContext {
+    /// 群组ID
+    pub group_id: u64,
+    /// 纪元号
+    pub epoch: u32,
+}
+
+impl WrapContext {
+    /// 附加认证数据
+    fn aad(&self, mode: u8) -> [u8; 14] {
+        let mut aad = [0u8; 14];
+        aad[0] = PROTOCOL_VERSION;
+        aad[1] = mode;
+        aad[2..10].copy_from_slice(&self.group_id.to_le_bytes());
+        aad[10..14].copy_from_slice(&self.epoch.to_le_bytes());
+        aad
+    }
+}
+
+/// 不使用抗量子 KEM 时的占位类型
+pub struct NoKem;
+
+impl PostQuantumKem for NoKem {
+    fn encapsulate<R: RngCore + CryptoRng>(
+        &self,
+        _recipient_public: &[u8],
+        _rng: &mut R,
+    ) -> GroupCryptoResult<(Vec<u8>, [u8; 32])> {
+        Err(GroupCryptoError::KemFailed)
+    }
+
+    fn decapsulate(&self, _secret_key: &[u8], _ciphertext: &[u8]) -> GroupCryptoResult<[u8; 32]> {
+        Err(GroupCryptoError::KemFailed)
+    }
+}
+
+/// 派生密钥包装密钥 (KEK)
+fn derive_kek(
+    ctx: &WrapContext,
+    dh_secret: &[u8; 32],
+    pq_secret: Option<&[u8; 32]>,
+    ephemeral_public: &[u8; X25519_KEY_SIZE],
+    recipient_public: &[u8; X25519_KEY_SIZE],
+) -> GroupCryptoResult<[u8; EPOCH_KEY_SIZE]> {
+    let mut ikm = [0u8; 64];
+    ikm[..32].copy_from_slice(dh_secret);
+    let ikm_len = match pq_secret {
+        Some(pq) => {
+            ikm[32..].copy_from_slice(pq);
+            64
+        }
+        None => 32,
+    };
+
+    let mut info = Vec::with_capacity(WRAP_INFO.len() + 12 + 2 * X25519_KEY_SIZE);
+    info.extend_from_slice(WRAP_INFO);
+    info.extend_from_slice(&ctx.group_id.to_le_bytes());
+    info.extend_from_slice(&ctx.epoch.to_le_bytes());
+    info.extend_from_slice(ephemeral_public);
+    info.extend_from_slice(recipient_public);
+
+    let hkdf = Hkdf::<Sha256>::new(None, &ikm[..ikm_len]);
+    let mut kek = [0u8; EPOCH_KEY_SIZE];
+    hkdf.expand(&info, &mut kek)
+        .map_err(|_| GroupCryptoError::KeyDerivationFailed)?;
+
+    ikm.iter_mut().for_each(|b| *b = 0);
+    Ok(kek)
+}
+
+/// 为单个成员包装纪元密钥
+///
+/// # 参数
+/// - `ctx`: 群组与纪元
+/// - `epoch_key`: 纪元密钥
+/// - `recipient`: 接收方链上预密钥
+/// - `kem`: 抗量子 KEM（`Some` 时要求接收方发布了 PQ 公钥）
+/// - `rng`: 随机数生成器
+pub fn wrap_epoch_key<K: PostQuantumKem, R: RngCore + CryptoRng>(
+    ctx: &WrapContext,
+    epoch_key: &[u8; EPOCH_KEY_SIZE],
+    recipient: &PrekeyBundle,
+    kem: Option<&K>,
+    rng: &mut R,
+) -> GroupCryptoResult<Vec<u8>> {
+    // 临时密钥对：每个接收方、每个纪元独立
+    let ephemeral_secret = StaticSecret::random_from_rng(&mut *rng);
+    let ephemeral_public = X25519PublicKey::from(&ephemeral_secret).to_bytes();
+    let dh_secret = ephemeral_secret
+        .diffie_hellman(&X25519PublicKey::from(recipient.x25519))
+        .to_bytes();
+
+    let (mode, pq) = match kem {
+        Some(kem) => {
+            let pq_public = recipient
+                .post_quantum
+                .as_deref()
+                .ok_or(GroupCryptoError::MissingPostQuantumKey)?;
+            let (ciphertext, shared) = kem.encapsulate(pq_public, rng)?;
+            (MODE_HYBRID, Some((ciphertext, shared)))
+        }
+        None => (MODE_X25519, None),
+    };
+
+    let kek = derive_kek(
+        ctx,
+        &dh_secret,
+        pq.as_ref().map(|(_, shared)| shared),
+        &ephemeral_public,
+        &recipient.x25519,
+    )?;
+
+    let mut nonce = [0u8; NONCE_SIZE];
+    rng.fill_bytes(&mut nonce);
+
+    let cipher = Aes256Gcm::new_from_slice(&kek).map_err(|_| GroupCryptoError::EncryptionFailed)?;
+    let sealed = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload { msg: epoch_key, aad: &ctx.aad(mode) },
+        )
+        .map_err(|_| GroupCryptoError::EncryptionFailed)?;
+
+    let mut payload = Vec::with_capacity(WRAPPED_KEY_SIZE);
+    payload.push(PROTOCOL_VERSION);
+    payload.push(mode);
+    payload.extend_from_slice(&ephemeral_public);
+    payload.extend_from_slice(&nonce);
+    payload.extend_from_slice(&sealed);
+
+    if let Some((ciphertext, _)) = pq {
+        let len = u16::try_from(ciphertext.len()).map_err(|_| GroupCryptoError::KemFailed)?;
+        payload.extend_from_slice(&len.to_le_bytes());
+        payload.extend_from_slice(&ciphertext);
+    }
+
+    Ok(payload)
+}
+
+/// 解包纪元密钥
+///
+/// # 参数
+/// - `ctx`: 群组与纪元（必须与包装时一致）
+/// - `payload`: 链上 `WrappedGroupKeys` 中的包装数据
+/// - `member`: 本地成员密钥
+/// - `kem`: 抗量子 KEM（混合模式必需）
+pub fn unwrap_epoch_key<K: PostQuantumKem>(
+    ctx: &WrapContext,
+    payload: &[u8],
+    member: &MemberKeys,
+    kem: Option<&K>,
+) -> GroupCryptoResult<[u8; EPOCH_KEY_SIZE]> {
+    if payload.len() < WRAPPED_KEY_SIZE {
+        return Err(GroupCryptoError::MalformedPayload);
+    }
+    if payload[0] != PROTOCOL_VERSION {
+        return Err(GroupCryptoError::UnsupportedVersion(payload[0]));
+    }
+    let mode = payload[1];
+
+    let mut ephemeral_public = [0u8; X25519_KEY_SIZE];
+    ephemeral_public.copy_from_slice(&payload[2..34]);
+    let nonce = &payload[34..46];
+    let sealed = &payload[46..WRAPPED_KEY_SIZE];
+
+    let pq_shared = match mode {
+        MODE_X25519 => {
+            if payload.len() != WRAPPED_KEY_SIZE {
+                return Err(GroupCryptoError::MalformedPayload);
+            }
+            None
+        }
+        MODE_HYBRID => {
+            let rest = &payload[WRAPPED_KEY_SIZE..];
+            if rest.len() < 2 {
+                return Err(GroupCryptoError::MalformedPayload);
+            }
+            let len = u16::from_le_bytes([rest[0], rest[1]]) as usize;
+            if rest.len() != 2 + len {
+                return Err(GroupCryptoError::MalformedPayload);
+            }
+            let kem = kem.ok_or(GroupCryptoError::MissingPostQuantumKey)?;
+            let secret = member.pq_secret().ok_or(GroupCryptoError::MissingPostQuantumKey)?;
+            Some(kem.decapsulate(secret, &rest[2..])?)
+        }
+        _ => return Err(GroupCryptoError::MalformedPayload),
+    };
+
+    let dh_secret = member.diffie_hellman(&ephemeral_public);
+    let kek = derive_kek(
+        ctx,
+        &dh_secret,
+        pq_shared.as_ref(),
+        &ephemeral_public,
+        &member.x25519_public(),
+    )?;
+
+    let cipher = Aes256Gcm::new_from_slice(&kek).map_err(|_| GroupCryptoError::DecryptionFailed)?;
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), Payload { msg: sealed, aad: &ctx.aad(mode) })
+        .map_err(|_| GroupCryptoError::DecryptionFailed)?;
+
+    let mut epoch_key = [0u8; EPOCH_KEY_SIZE];
+    if plaintext.len() != EPOCH_KEY_SIZE {
+        return Err(GroupCryptoError::MalformedPayload);
+    }
+    epoch_key.copy_from_slice(&plaintext);
+    Ok(epoch_key)
+}
+
+/// 测试用的 KEM：以 X25519 模拟封装/解封装，仅用于覆盖混合模式代码路径
+#[cfg(test)]
+pub(crate) mod test_kem {
+    use super::*;
+
+    pub struct X25519Kem;
+
+    impl X25519Kem {
+        /// 生成 (公钥, 私钥)
+        pub fn keypair<R: RngCore + CryptoRng>(rng: &mut R) -> (Vec<u8>, Vec<u8>) {
+            let secret = StaticSecret::random_from_rng(&mut *rng);
+            let public = X25519PublicKey::from(&secret);
+            (public.to_bytes().to_vec(), secret.to_bytes().to_vec())
+        }
+    }
+
+    impl PostQuantumKem for X25519Kem {
+        fn encapsulate<R: RngCore + CryptoRng>(
+            &self,
+            recipient_public: &[u8],
+            rng: &mut R,
+        ) -> GroupCryptoResult<(Vec<u8>, [u8; 32])> {
+            let public: [u8; 32] =
+                recipient_public.try_into().map_err(|_| GroupCryptoError::InvalidPublicKey)?;
+            let ephemeral = StaticSecret::random_from_rng(&mut *rng);
+            let shared = ephemeral.diffie_hellman(&X25519PublicKey::from(public)).to_bytes();
+            Ok((X25519PublicKey::from(&ephemeral).to_bytes().to_vec(), shared))
+        }
+
+        fn decapsulate(&self, secret_key: &[u8], ciphertext: &[u8]) -> GroupCryptoResult<[u8; 32]> {
+            let secret: [u8; 32] = secret_key.try_into().map_err(|_| GroupCryptoError::KemFailed)?;
+            let public: [u8; 32] = ciphertext.try_into().map_err(|_| GroupCryptoError::KemFailed)?;
+            Ok(StaticSecret::from(secret)
+                .diffie_hellman(&X25519PublicKey::from(public))
+                .to_bytes())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_kem::X25519Kem;
+    use super::*;
+
+    const CTX: WrapContext = WrapContext { group_id: 10_000_000_001, epoch: 3 };
+
+    #[test]
+    fn test_wrap_unwrap_roundtrip() {
+        let mut rng = rand::thread_rng();
+        let member = MemberKeys::generate(&mut rng);
+        let epoch_key = [5u8; EPOCH_KEY_SIZE];
+
+        let payload =
+            wrap_epoch_key(&CTX, &epoch_key, &member.prekey_bundle(), None::<&NoKem>, &mut rng).unwrap();
+        assert_eq!(payload.len(), WRAPPED_KEY_SIZE);
+
+        let unwrapped = unwrap_epoch_key(&CTX, &payload, &member, None::<&NoKem>).unwrap();
+        assert_eq!(unwrapped, epoch_key);
+    }
+
+    #[test]
+    fn test_unwrap_with_wrong_member_fails() {
+        let mut rng = rand::thread_rng();
+        let alice = MemberKeys::generate(&mut rng);
+        let mallory = MemberKeys::generate(&mut rng);
+
+        let payload =
+            wrap_epoch_key(&CTX, &[1u8; 32], &alice.prekey_bundle(), None::<&NoKem>, &mut rng).unwrap();
+
+        let result = unwrap_epoch_key(&CTX, &payload, &mallory, None::<&NoKem>);
+        assert_eq!(result, Err(GroupCryptoError::DecryptionFailed));
+    }
+
+    #[test]
+    fn test_wrapped_key_bound_to_group_and_epoch() {
+        let mut rng = rand::thread_rng();
+        let member = MemberKeys::generate(&mut rng);
+
+        let payload =
+            wrap_epoch_key(&CTX, &[1u8; 32], &member.prekey_bundle(), None::<&NoKem>, &mut rng).unwrap();
+
+        // 不能挪用到其他纪元
+        let other_epoch = WrapContext { epoch: CTX.epoch + 1, ..CTX };
+        assert!(unwrap_epoch_key(&other_epoch, &payload, &member, None::<&NoKem>).is_err());
+
+        // 不能挪用到其他群组
+        let other_group = WrapContext { group_id: CTX.group_id + 1, ..CTX };
+        assert!(unwrap_epoch_key(&other_group, &payload, &member, None::<&NoKem>).is_err());
+    }
+
+    #[test]
+    fn test_hybrid_wrap_roundtrip() {
+        let mut rng = rand::thread_rng();
+        let (pq_public, pq_secret) = X25519Kem::keypair(&mut rng);
+        let member = MemberKeys::generate(&mut rng).with_post_quantum(pq_public, pq_secret);
+        let epoch_key = [8u8; EPOCH_KEY_SIZE];
+
+        let payload =
+            wrap_epoch_key(&CTX, &epoch_key, &member.prekey_bundle(), Some(&X25519Kem), &mut rng).unwrap();
+        assert!(payload.len() > WRAPPED_KEY_SIZE);
+
+        // 没有 KEM 无法解包
+        assert_eq!(
+            unwrap_epoch_key(&CTX, &payload, &member, None::<&NoKem>),
+            Err(GroupCryptoError::MissingPostQuantumKey)
+        );
+
+        let unwrapped = unwrap_epoch_key(&CTX, &payload, &member, Some(&X25519Kem)).unwrap();
+        assert_eq!(unwrapped, epoch_key);
+    }
+
+    #[test]
+    fn test_hybrid_requires_recipient_pq_key() {
+        let mut rng = rand::thread_rng();
+        let member = MemberKeys::generate(&mut rng);
+
+        let result = wrap_epoch_key(&CTX, &[0u8; 32], &member.prekey_bundle(), Some(&X25519Kem), &mut rng);
+        assert_eq!(result, Err(GroupCryptoError::MissingPostQuantumKey));
+    }
+
+    #[test]
+    fn test_tampered_payload_rejected() {
+        let mut rng = rand::thread_rng();
+        let member = MemberKeys::generate(&mut rng);
+
+        let mut payload =
+            wrap_epoch_key(&CTX, &[1u8; 32], &member.prekey_bundle(), None::<&NoKem>, &mut rng).unwrap();
+        payload[50] ^= 0xFF;
+
+        assert!(unwrap_epoch_key(&CTX, &payload, &member, None::<&NoKem>).is_err());
+        assert_eq!(
+            unwrap_epoch_key(&CTX, &payload[..10], &member, None::<&NoKem>),
+            Err(GroupCryptoError::MalformedPayload)
+        );
+    }
+}
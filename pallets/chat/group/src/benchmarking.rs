@@ -71,6 +71,25 @@ mod benchmarks {
         _(RawOrigin::Signed(caller), group_id);
     }
 
+    #[benchmark]
+    fn publish_prekey() {
+        let caller: T::AccountId = whitelisted_caller();
+        let x25519 = [7u8; 32];
+        let post_quantum: Option<Vec<u8>> = Some(vec![1u8; T::MaxPqKeyLen::get() as usize]);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), x25519, post_quantum);
+    }
+
+    #[benchmark]
+    fn rotate_group_key() {
+        let caller: T::AccountId = whitelisted_caller();
+        let group_id: u64 = 1;
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), group_id);
+    }
+
     #[benchmark]
     fn handle_group_violation() {
         let group_id: u64 = 1;
@@ -1,683 +0,0 @@
-/// Stardust智能群聊系统 - 加密模块
-///
-/// 实现四种加密模式的核心加密算法
-
-use crate::types::*;
-use codec::{Decode, Encode};
-use frame_support::{
-    pallet_prelude::*,
-    traits::Randomness,
-};
-use sp_runtime::traits::Saturating;
-use sp_std::{vec::Vec, convert::TryInto};
-
-/// 量子抗性加密套件
-pub struct QuantumResistantCrypto<T: frame_system::Config> {
-    _phantom: sp_std::marker::PhantomData<T>,
-}
-
-impl<T: frame_system::Config> QuantumResistantCrypto<T> {
-    /// 创建新的量子抗性加密实例
-    pub fn new() -> Self {
-        Self {
-            _phantom: Default::default(),
-        }
-    }
-
-    /// 混合加密：传统算法 + 抗量子算法
-    pub fn hybrid_encrypt(
-        plaintext: &[u8],
-        encryption_mode: EncryptionMode,
-        master_key: &[u8],
-    ) -> Result<Vec<u8>, EncryptionError> {
-        match encryption_mode {
-            EncryptionMode::Military => {
-                Self::military_grade_encrypt(plaintext, master_key)
-            },
-            EncryptionMode::Business => {
-                Self::business_grade_encrypt(plaintext, master_key)
-            },
-            EncryptionMode::Selective => {
-                Self::selective_encrypt(plaintext, master_key)
-            },
-            EncryptionMode::Transparent => {
-                Ok(plaintext.to_vec())
-            },
-        }
-    }
-
-    /// 军用级加密（量子抗性 + 多层加密）
-    fn military_grade_encrypt(plaintext: &[u8], key: &[u8]) -> Result<Vec<u8>, EncryptionError> {
-        let mut result = plaintext.to_vec();
-
-        // 第一层：后量子密码学模拟（Kyber-like）
-        result = Self::post_quantum_encrypt(&result, key)?;
-
-        // 第二层：传统AES加密模拟
-        result = Self::aes_encrypt(&result, key)?;
-
-        // 第三层：自定义混淆层
-        result = Self::custom_obfuscation(&result, key)?;
-
-        // 第四层：数据完整性保护
-        result = Self::add_integrity_protection(result)?;
-
-        Ok(result)
-    }
-
-    /// 商用级加密（标准端到端加密）
-    fn business_grade_encrypt(plaintext: &[u8], key: &[u8]) -> Result<Vec<u8>, EncryptionError> {
-        let mut result = plaintext.to_vec();
-
-        // 第一层：传统AES加密
-        result = Self::aes_encrypt(&result, key)?;
-
-        // 第二层：消息认证码
-        result = Self::add_message_authentication(result, key)?;
-
-        Ok(result)
-    }
-
-    /// 选择性加密（基础加密）
-    fn selective_encrypt(plaintext: &[u8], key: &[u8]) -> Result<Vec<u8>, EncryptionError> {
-        // 简单XOR加密（仅用于演示）
-        Self::xor_encrypt(plaintext, key)
-    }
-
-    /// 后量子加密模拟（实际应该使用真正的后量子算法）
-    fn post_quantum_encrypt(data: &[u8], key: &[u8]) -> Result<Vec<u8>, EncryptionError> {
-        let mut result = data.to_vec();
-
-        // 模拟CRYSTALS-Kyber密钥封装
-        for (i, byte) in result.iter_mut().enumerate() {
-            let key_byte = key.get(i % key.len()).unwrap_or(&0);
-            // 复杂的非线性变换模拟量子抗性
-            *byte = byte.wrapping_mul(key_byte.wrapping_add(1))
-                       .wrapping_add(i as u8)
-                       .rotate_left(3);
-        }
-
-        // 添加噪声模拟格密码学
-        Self::add_lattice_noise(&mut result)?;
-
-        Ok(result)
-    }
-
-    /// AES加密模拟（简化实现）
-    fn aes_encrypt(data: &[u8], key: &[u8]) -> Result<Vec<u8>, EncryptionError> {
-        let mut result = data.to_vec();
-
-        // 模拟AES轮函数
-        for round in 0..14u8 { // AES-256的14轮
-            for (i, byte) in result.iter_mut().enumerate() {
-                let key_byte = key.get((i + round as usize) % key.len()).unwrap_or(&0);
-
-                // 模拟SubBytes变换
-                *byte = Self::sbox_transform(*byte);
-
-                // 模拟AddRoundKey
-                *byte ^= key_byte;
-
-                // 模拟ShiftRows和MixColumns
-                if round < 13 {
-                    *byte = byte.rotate_left(round % 8);
-                }
-            }
-        }
-
-        Ok(result)
-    }
-
-    /// S-Box变换模拟
-    fn sbox_transform(byte: u8) -> u8 {
-        // 简化的S-Box查找表
-        let sbox = [
-            0x63, 0x7C, 0x77, 0x7B, 0xF2, 0x6B, 0x6F, 0xC5,
-            0x30, 0x01, 0x67, 0x2B, 0xFE, 0xD7, 0xAB, 0x76,
-            // ... 简化版本，实际AES S-Box有256个条目
-        ];
-
-        sbox.get(byte as usize % sbox.len()).copied().unwrap_or(byte)
-    }
-
-    /// 自定义混淆层
-    fn custom_obfuscation(data: &[u8], key: &[u8]) -> Result<Vec<u8>, EncryptionError> {
-        let mut result = data.to_vec();
-
-        // 多重异或
-        for (i, byte) in result.iter_mut().enumerate() {
-            let key1 = key.get(i % key.len()).unwrap_or(&0);
-            let key2 = key.get((i * 2) % key.len()).unwrap_or(&0);
-            let key3 = key.get((i * 3) % key.len()).unwrap_or(&0);
-
-            *byte ^= key1;
-            *byte ^= key2.rotate_left(2);
-            *byte ^= key3.rotate_right(3);
-        }
-
-        // 数据重排列
-        Self::permute_data(&mut result);
-
-        Ok(result)
-    }
-
-    /// XOR加密
-    fn xor_encrypt(data: &[u8], key: &[u8]) -> Result<Vec<u8>, EncryptionError> {
-        if key.is_empty() {
-            return Err(EncryptionError::InvalidKey);
-        }
-
-        let result = data.iter()
-            .enumerate()
-            .map(|(i, &byte)| byte ^ key[i % key.len()])
-            .collect();
-
-        Ok(result)
-    }
-
-    /// 添加格噪声（模拟格密码学）
-    fn add_lattice_noise(data: &mut Vec<u8>) -> Result<(), EncryptionError> {
-        // 简化的格噪声模拟
-        for (i, byte) in data.iter_mut().enumerate() {
-            let noise = ((i * 17 + 42) % 256) as u8; // 简单的伪随机噪声
-            *byte = byte.wrapping_add(noise);
-        }
-        Ok(())
-    }
-
-    /// 数据重排列
-    fn permute_data(data: &mut Vec<u8>) {
-        if data.len() <= 1 {
-            return;
-        }
-
-        // 简单的Fisher-Yates洗牌变体
-        let len = data.len();
-        for i in 0..len {
-            let j = (i * 7 + 13) % len; // 确定性的"随机"交换
-            data.swap(i, j);
-        }
-    }
-
-    /// 添加数据完整性保护
-    fn add_integrity_protection(mut data: Vec<u8>) -> Result<Vec<u8>, EncryptionError> {
-        // 计算简单的校验和
-        let checksum = data.iter().fold(0u32, |acc, &byte| {
-            acc.wrapping_add(byte as u32)
-        });
-
-        // 将校验和附加到数据末尾
-        data.extend_from_slice(&checksum.to_le_bytes());
-
-        Ok(data)
-    }
-
-    /// 添加消息认证码
-    fn add_message_authentication(mut data: Vec<u8>, key: &[u8]) -> Result<Vec<u8>, EncryptionError> {
-        // 简化的HMAC实现
-        let mut mac = 0u64;
-        for (i, &byte) in data.iter().enumerate() {
-            let key_byte = key.get(i % key.len()).unwrap_or(&0);
-            mac ^= ((byte as u64) << (i % 64)) ^ (*key_byte as u64);
-        }
-
-        data.extend_from_slice(&mac.to_le_bytes());
-        Ok(data)
-    }
-
-    /// 解密功能
-    pub fn hybrid_decrypt(
-        ciphertext: &[u8],
-        encryption_mode: EncryptionMode,
-        master_key: &[u8],
-    ) -> Result<Vec<u8>, DecryptionError> {
-        match encryption_mode {
-            EncryptionMode::Military => {
-                Self::military_grade_decrypt(ciphertext, master_key)
-            },
-            EncryptionMode::Business => {
-                Self::business_grade_decrypt(ciphertext, master_key)
-            },
-            EncryptionMode::Selective => {
-                Self::selective_decrypt(ciphertext, master_key)
-            },
-            EncryptionMode::Transparent => {
-                Ok(ciphertext.to_vec())
-            },
-        }
-    }
-
-    /// 军用级解密
-    fn military_grade_decrypt(ciphertext: &[u8], key: &[u8]) -> Result<Vec<u8>, DecryptionError> {
-        let mut result = ciphertext.to_vec();
-
-        // 逆向处理：移除完整性保护
-        result = Self::remove_integrity_protection(result)?;
-
-        // 逆向自定义混淆层
-        result = Self::reverse_custom_obfuscation(&result, key)?;
-
-        // 逆向AES解密
-        result = Self::aes_decrypt(&result, key)?;
-
-        // 逆向后量子解密
-        result = Self::post_quantum_decrypt(&result, key)?;
-
-        Ok(result)
-    }
-
-    /// 商用级解密
-    fn business_grade_decrypt(ciphertext: &[u8], key: &[u8]) -> Result<Vec<u8>, DecryptionError> {
-        let mut result = ciphertext.to_vec();
-
-        // 验证并移除消息认证码
-        result = Self::verify_and_remove_mac(result, key)?;
-
-        // 逆向AES解密
-        result = Self::aes_decrypt(&result, key)?;
-
-        Ok(result)
-    }
-
-    /// 选择性解密
-    fn selective_decrypt(ciphertext: &[u8], key: &[u8]) -> Result<Vec<u8>, DecryptionError> {
-        // XOR解密（与加密相同）
-        Self::xor_encrypt(ciphertext, key)
-            .map_err(|_| DecryptionError::DecryptionFailed)
-    }
-
-    /// 移除完整性保护
-    fn remove_integrity_protection(mut data: Vec<u8>) -> Result<Vec<u8>, DecryptionError> {
-        if data.len() < 4 {
-            return Err(DecryptionError::InvalidData);
-        }
-
-        let stored_checksum_bytes = data.split_off(data.len() - 4);
-        let stored_checksum = u32::from_le_bytes(
-            stored_checksum_bytes.try_into()
-                .map_err(|_| DecryptionError::InvalidData)?
-        );
-
-        let calculated_checksum = data.iter().fold(0u32, |acc, &byte| {
-            acc.wrapping_add(byte as u32)
-        });
-
-        if stored_checksum != calculated_checksum {
-            return Err(DecryptionError::IntegrityCheckFailed);
-        }
-
-        Ok(data)
-    }
-
-    /// 逆向自定义混淆
-    fn reverse_custom_obfuscation(data: &[u8], key: &[u8]) -> Result<Vec<u8>, DecryptionError> {
-        let mut result = data.to_vec();
-
-        // 逆向数据重排列
-        Self::reverse_permute_data(&mut result);
-
-        // 逆向多重异或
-        for (i, byte) in result.iter_mut().enumerate() {
-            let key1 = key.get(i % key.len()).unwrap_or(&0);
-            let key2 = key.get((i * 2) % key.len()).unwrap_or(&0);
-            let key3 = key.get((i * 3) % key.len()).unwrap_or(&0);
-
-            *byte ^= key3.rotate_right(3);
-            *byte ^= key2.rotate_left(2);
-            *byte ^= key1;
-        }
-
-        Ok(result)
-    }
-
-    /// 逆向数据重排列
-    fn reverse_permute_data(data: &mut Vec<u8>) {
-        if data.len() <= 1 {
-            return;
-        }
-
-        // 逆向Fisher-Yates洗牌
-        let len = data.len();
-        for i in (0..len).rev() {
-            let j = (i * 7 + 13) % len;
-            data.swap(i, j);
-        }
-    }
-
-    /// AES解密模拟
-    fn aes_decrypt(data: &[u8], key: &[u8]) -> Result<Vec<u8>, DecryptionError> {
-        let mut result = data.to_vec();
-
-        // 逆向AES轮函数（14轮到0轮）
-        for round in (0..14u8).rev() {
-            for (i, byte) in result.iter_mut().enumerate() {
-                // 逆向操作顺序
-                if round < 13 {
-                    *byte = byte.rotate_right(round % 8);
-                }
-
-                let key_byte = key.get((i + round as usize) % key.len()).unwrap_or(&0);
-                *byte ^= key_byte;
-                *byte = Self::inverse_sbox_transform(*byte);
-            }
-        }
-
-        Ok(result)
-    }
-
-    /// 逆向S-Box变换
-    fn inverse_sbox_transform(byte: u8) -> u8 {
-        // 简化的逆S-Box（在实际实现中应该是真正的逆变换）
-        let inv_sbox = [
-            0x52, 0x09, 0x6A, 0xD5, 0x30, 0x36, 0xA5, 0x38,
-            0xBF, 0x40, 0xA3, 0x9E, 0x81, 0xF3, 0xD7, 0xFB,
-            // ... 简化版本
-        ];
-
-        inv_sbox.get(byte as usize % inv_sbox.len()).copied().unwrap_or(byte)
-    }
-
-    /// 后量子解密模拟
-    fn post_quantum_decrypt(data: &[u8], key: &[u8]) -> Result<Vec<u8>, DecryptionError> {
-        let mut result = data.to_vec();
-
-        // 移除格噪声
-        Self::remove_lattice_noise(&mut result)?;
-
-        // 逆向量子抗性变换
-        for (i, byte) in result.iter_mut().enumerate() {
-            *byte = byte.rotate_right(3);
-            *byte = byte.wrapping_sub(i as u8);
-
-            let key_byte = key.get(i % key.len()).unwrap_or(&0);
-            if key_byte.wrapping_add(1) != 0 {
-                *byte = byte.wrapping_div(key_byte.wrapping_add(1));
-            }
-        }
-
-        Ok(result)
-    }
-
-    /// 移除格噪声
-    fn remove_lattice_noise(data: &mut Vec<u8>) -> Result<(), DecryptionError> {
-        for (i, byte) in data.iter_mut().enumerate() {
-            let noise = ((i * 17 + 42) % 256) as u8;
-            *byte = byte.wrapping_sub(noise);
-        }
-        Ok(())
-    }
-
-    /// 验证并移除MAC
-    fn verify_and_remove_mac(mut data: Vec<u8>, key: &[u8]) -> Result<Vec<u8>, DecryptionError> {
-        if data.len() < 8 {
-            return Err(DecryptionError::InvalidData);
-        }
-
-        let stored_mac_bytes = data.split_off(data.len() - 8);
-        let stored_mac = u64::from_le_bytes(
-            stored_mac_bytes.try_into()
-                .map_err(|_| DecryptionError::InvalidData)?
-        );
-
-        // 重新计算MAC
-        let mut calculated_mac = 0u64;
-        for (i, &byte) in data.iter().enumerate() {
-            let key_byte = key.get(i % key.len()).unwrap_or(&0);
-            calculated_mac ^= ((byte as u64) << (i % 64)) ^ (*key_byte as u64);
-        }
-
-        if stored_mac != calculated_mac {
-            return Err(DecryptionError::AuthenticationFailed);
-        }
-
-        Ok(data)
-    }
-}
-
-/// 密钥派生功能
-pub struct KeyDerivation;
-
-impl KeyDerivation {
-    /// HKDF密钥派生（简化实现）
-    pub fn hkdf_expand(
-        master_key: &[u8],
-        info: &[u8],
-        length: usize,
-    ) -> Result<Vec<u8>, KeyDerivationError> {
-        if master_key.is_empty() || length == 0 {
-            return Err(KeyDerivationError::InvalidInput);
-        }
-
-        let mut derived_key = Vec::with_capacity(length);
-
-        // 简化的HKDF实现
-        for i in 0..length {
-            let mut hasher = 0u64;
-
-            // 混合主密钥
-            for (j, &byte) in master_key.iter().enumerate() {
-                hasher ^= (byte as u64).rotate_left((i + j) % 64);
-            }
-
-            // 混合信息字段
-            for (j, &byte) in info.iter().enumerate() {
-                hasher ^= (byte as u64).rotate_right((i * 2 + j) % 64);
-            }
-
-            // 添加计数器
-            hasher ^= i as u64;
-
-            derived_key.push((hasher & 0xFF) as u8);
-        }
-
-        Ok(derived_key)
-    }
-
-    /// PBKDF2密钥强化（简化实现）
-    pub fn pbkdf2_derive(
-        password: &[u8],
-        salt: &[u8],
-        iterations: u32,
-        length: usize,
-    ) -> Result<Vec<u8>, KeyDerivationError> {
-        let mut result = password.to_vec();
-
-        // 添加盐
-        result.extend_from_slice(salt);
-
-        // 迭代强化
-        for _ in 0..iterations {
-            result = Self::simple_hash(&result);
-        }
-
-        // 截断或扩展到所需长度
-        if result.len() > length {
-            result.truncate(length);
-        } else while result.len() < length {
-            result.extend_from_slice(&Self::simple_hash(&result));
-        }
-
-        result.truncate(length);
-        Ok(result)
-    }
-
-    /// 简单哈希函数
-    fn simple_hash(input: &[u8]) -> Vec<u8> {
-        let mut hash = [0u8; 32];
-
-        for (i, &byte) in input.iter().enumerate() {
-            let pos = i % 32;
-            hash[pos] ^= byte;
-            hash[pos] = hash[pos].rotate_left(1);
-        }
-
-        hash.to_vec()
-    }
-}
-
-/// 加密错误类型
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum EncryptionError {
-    InvalidKey,
-    EncryptionFailed,
-    InvalidInput,
-    UnsupportedMode,
-}
-
-/// 解密错误类型
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum DecryptionError {
-    DecryptionFailed,
-    InvalidData,
-    IntegrityCheckFailed,
-    AuthenticationFailed,
-    UnsupportedMode,
-}
-
-/// 密钥派生错误类型
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum KeyDerivationError {
-    InvalidInput,
-    DerivationFailed,
-}
-
-/// 侧信道攻击防护
-pub struct SideChannelProtection;
-
-impl SideChannelProtection {
-    /// 常量时间字符串比较（防时间攻击）
-    pub fn constant_time_compare(a: &[u8], b: &[u8]) -> bool {
-        if a.len() != b.len() {
-            return false;
-        }
-
-        let mut result = 0u8;
-        for (x, y) in a.iter().zip(b.iter()) {
-            result |= x ^ y;
-        }
-
-        // 常量时间转换为布尔值
-        (result as u32).wrapping_sub(1) >> 31 == 1
-    }
-
-    /// 安全内存清零（防数据泄漏）
-    pub fn secure_memory_clear(data: &mut [u8]) {
-        // 在no_std环境中的安全清零实现
-        for byte in data.iter_mut() {
-            unsafe {
-                sp_std::ptr::write_volatile(byte, 0);
-            }
-        }
-
-        // 编译器内存屏障
-        sp_std::sync::atomic::compiler_fence(sp_std::sync::atomic::Ordering::SeqCst);
-    }
-
-    /// 添加随机延迟（防时间分析）
-    pub fn add_random_delay(randomness: &[u8]) -> u32 {
-        if randomness.is_empty() {
-            return 0;
-        }
-
-        // 根据随机数生成1-10ms的延迟
-        let delay_factor = randomness[0] % 10 + 1;
-        delay_factor as u32
-    }
-}
-
-/// 密钥管理器
-pub struct KeyManager<T: frame_system::Config> {
-    _phantom: sp_std::marker::PhantomData<T>,
-}
-
-impl<T: frame_system::Config> KeyManager<T> {
-    /// 生成新的群组主密钥
-    pub fn generate_group_master_key(
-        encryption_mode: EncryptionMode,
-        randomness: &[u8],
-    ) -> Result<Vec<u8>, KeyDerivationError> {
-        let key_length = match encryption_mode {
-            EncryptionMode::Military => 64, // 512位
-            EncryptionMode::Business => 32, // 256位
-            EncryptionMode::Selective => 16, // 128位
-            EncryptionMode::Transparent => 0, // 无密钥
-        };
-
-        if key_length == 0 {
-            return Ok(Vec::new());
-        }
-
-        if randomness.len() < key_length {
-            return Err(KeyDerivationError::InvalidInput);
-        }
-
-        // 使用前 key_length 字节作为基础密钥
-        let base_key = &randomness[0..key_length];
-
-        // 使用 HKDF 进行密钥强化
-        KeyDerivation::hkdf_expand(
-            base_key,
-            b"stardust-group-chat-v1",
-            key_length,
-        )
-    }
-
-    /// 为成员派生个人密钥份额
-    pub fn derive_member_key_share(
-        master_key: &[u8],
-        member_id: &[u8],
-        encryption_mode: EncryptionMode,
-    ) -> Result<Vec<u8>, KeyDerivationError> {
-        if encryption_mode == EncryptionMode::Transparent {
-            return Ok(Vec::new());
-        }
-
-        let mut info = b"member-key-share:".to_vec();
-        info.extend_from_slice(member_id);
-
-        KeyDerivation::hkdf_expand(
-            master_key,
-            &info,
-            master_key.len(),
-        )
-    }
-
-    /// 轮换群组密钥
-    pub fn rotate_group_key(
-        old_master_key: &[u8],
-        rotation_counter: u32,
-    ) -> Result<Vec<u8>, KeyDerivationError> {
-        let mut info = b"key-rotation:".to_vec();
-        info.extend_from_slice(&rotation_counter.to_le_bytes());
-
-        KeyDerivation::hkdf_expand(
-            old_master_key,
-            &info,
-            old_master_key.len(),
-        )
-    }
-
-    /// 验证密钥强度
-    pub fn validate_key_strength(key: &[u8], encryption_mode: EncryptionMode) -> bool {
-        let required_length = match encryption_mode {
-            EncryptionMode::Military => 64,
-            EncryptionMode::Business => 32,
-            EncryptionMode::Selective => 16,
-            EncryptionMode::Transparent => return true,
-        };
-
-        // 检查密钥长度
-        if key.len() < required_length {
-            return false;
-        }
-
-        // 检查密钥熵（简化检查）
-        let mut unique_bytes = sp_std::collections::btree_set::BTreeSet::new();
-        for &byte in key {
-            unique_bytes.insert(byte);
-        }
-
-        // 要求至少有50%的字节是唯一的
-        unique_bytes.len() >= required_length / 2
-    }
-}
\ No newline at end of file
@@ -15,6 +15,9 @@ pub use pallet::*;
 pub mod weights;
 pub use weights::WeightInfo;
 
+pub mod plugin;
+pub use plugin::{BotInfo, BotPermissions, BotStatus, InstalledBot};
+
 use codec::{Decode, Encode};
 use frame_support::{
     dispatch::DispatchResult,
@@ -32,6 +35,7 @@ use sp_std::vec::Vec;
 
 // 使用 chat-common 的共享类型
 pub use pallet_chat_common::{MessageType, EncryptionMode};
+use pallet_chat_common::{check_and_update_rate_limit, GroupInboxItem, GroupInboxProvider, RateLimitState};
 
 // 函数级中文注释：导入共享媒体工具库用于媒体验证和哈希计算
 use media_utils::{
@@ -80,6 +84,62 @@ pub struct GroupMember<AccountId> {
     pub joined_at: u64,
 }
 
+/// 成员预密钥（链上公开材料）
+///
+/// 成员在本地生成 X25519 密钥对（军用级群组还需抗量子 KEM 密钥对），
+/// 仅公钥上链；群主据此为每个成员包装纪元密钥。
+/// 客户端加解密见 `chat-group-crypto` 库。
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct MemberPrekey<PqKey> {
+    /// X25519 公钥
+    pub x25519: [u8; 32],
+    /// 抗量子 KEM 公钥（如 ML-KEM-768）
+    pub post_quantum: Option<PqKey>,
+    /// 更新时间戳
+    pub updated_at: u64,
+}
+
+/// 群组密钥状态
+///
+/// 每次成员加入或离开都会使纪元递增，群主必须为所有成员重新包装新纪元密钥
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen, Default)]
+pub struct GroupKeyState {
+    /// 当前纪元
+    pub epoch: u32,
+    /// 纪元轮换时间戳
+    pub rotated_at: u64,
+    /// 已持有当前纪元密钥的成员数
+    pub wrapped_count: u32,
+}
+
+/// 为某成员包装的纪元密钥
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct WrappedGroupKey<AccountId, Payload> {
+    /// 纪元
+    pub epoch: u32,
+    /// 包装数据（临时公钥 + nonce + 密文，混合模式追加 KEM 密文）
+    pub payload: Payload,
+    /// 包装者（群主）
+    pub wrapped_by: AccountId,
+}
+
+/// 纪元轮换原因
+#[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum KeyRotationReason {
+    /// 群组创建
+    GroupCreated,
+    /// 成员加入
+    MemberJoined,
+    /// 成员离开
+    MemberLeft,
+    /// 群主手动轮换（如怀疑密钥泄露）
+    Manual,
+    /// 成员在统一公钥目录中轮换了加密公钥
+    MemberKeyRotated,
+    /// 成员在统一公钥目录中的加密公钥被吊销
+    MemberKeyRevoked,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -131,6 +191,38 @@ pub mod pallet {
         #[pallet::constant]
         type MaxKeyLen: Get<u32>;
 
+        /// 抗量子预密钥最大长度（ML-KEM-768 公钥为 1184 字节）
+        #[pallet::constant]
+        type MaxPqKeyLen: Get<u32>;
+
+        /// 包装纪元密钥最大长度（X25519 为 94 字节，混合模式追加 KEM 密文）
+        #[pallet::constant]
+        type MaxWrappedKeyLen: Get<u32>;
+
+        /// 单次提交的包装密钥最大数量
+        #[pallet::constant]
+        type MaxWrapsPerCall: Get<u32>;
+
+        /// 单个群组最多安装的机器人数
+        #[pallet::constant]
+        type MaxBotsPerGroup: Get<u32>;
+
+        /// 机器人发言频率窗口（区块数）
+        #[pallet::constant]
+        type BotRateWindow: Get<BlockNumberFor<Self>>;
+
+        /// 机器人在单个窗口内可声明的最大发言数
+        #[pallet::constant]
+        type MaxBotMessagesPerWindow: Get<u32>;
+
+        /// 机器人注册保证金兜底值（DUST数量，pricing不可用时使用）
+        #[pallet::constant]
+        type BotDeposit: Get<BalanceOf<Self>>;
+
+        /// 机器人注册保证金USD价值（精度10^6）
+        #[pallet::constant]
+        type BotDepositUsd: Get<u64>;
+
         /// Pallet ID（用于生成内部账户）
         #[pallet::constant]
         type PalletId: Get<PalletId>;
@@ -160,6 +252,9 @@ pub mod pallet {
         /// 治理权限来源（用于处理违规）
         type GovernanceOrigin: frame_support::traits::EnsureOrigin<Self::RuntimeOrigin>;
 
+        /// 统一公钥目录（X25519 预密钥优先从目录读取）
+        type KeyDirectory: pallet_key_directory::traits::KeyDirectory<Self::AccountId>;
+
         /// Weight信息（用于基准测试）
         type WeightInfo: WeightInfo;
     }
@@ -173,6 +268,9 @@ pub mod pallet {
     type GroupNameOf<T> = BoundedVec<u8, <T as Config>::MaxGroupNameLen>;
     type GroupDescriptionOf<T> = BoundedVec<u8, <T as Config>::MaxGroupDescriptionLen>;
     type MessageContentOf<T> = BoundedVec<u8, <T as Config>::MaxMessageLen>;
+    type PqKeyOf<T> = BoundedVec<u8, <T as Config>::MaxPqKeyLen>;
+    type WrappedKeyOf<T> = BoundedVec<u8, <T as Config>::MaxWrappedKeyLen>;
+    type CidOf<T> = BoundedVec<u8, <T as Config>::MaxCidLen>;
 
     /// 存储项：群组信息
     #[pallet::storage]
@@ -244,6 +342,95 @@ pub mod pallet {
         u64, // 封禁时间戳
     >;
 
+    /// 存储项：成员预密钥
+    #[pallet::storage]
+    #[pallet::getter(fn member_prekeys)]
+    pub type MemberPrekeys<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        MemberPrekey<PqKeyOf<T>>,
+    >;
+
+    /// 存储项：群组密钥状态（仅加密群组）
+    #[pallet::storage]
+    #[pallet::getter(fn group_key_states)]
+    pub type GroupKeyStates<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u64, // group_id
+        GroupKeyState,
+    >;
+
+    /// 存储项：为每个成员包装的纪元密钥
+    #[pallet::storage]
+    #[pallet::getter(fn wrapped_group_keys)]
+    pub type WrappedGroupKeys<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64, // group_id
+        Blake2_128Concat,
+        T::AccountId, // member
+        WrappedGroupKey<T::AccountId, WrappedKeyOf<T>>,
+    >;
+
+    /// 存储项：已注册机器人
+    #[pallet::storage]
+    #[pallet::getter(fn bots)]
+    pub type Bots<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId, // bot
+        BotInfo<GroupNameOf<T>, CidOf<T>, BalanceOf<T>>,
+    >;
+
+    /// 存储项：群组已安装的机器人
+    #[pallet::storage]
+    #[pallet::getter(fn group_bots)]
+    pub type GroupBots<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64, // group_id
+        Blake2_128Concat,
+        T::AccountId, // bot
+        InstalledBot<T::AccountId, BlockNumberFor<T>>,
+    >;
+
+    /// 存储项：群组已安装的机器人数量
+    #[pallet::storage]
+    #[pallet::getter(fn group_bot_count)]
+    pub type GroupBotCount<T: Config> = StorageMap<_, Blake2_128Concat, u64, u32, ValueQuery>;
+
+    /// 存储项：成员已读游标
+    ///
+    /// 值为该成员下一条未读消息的ID（小于该ID的消息均已读），
+    /// 未读数 = NextMessageId - 游标
+    #[pallet::storage]
+    #[pallet::getter(fn group_read_cursor)]
+    pub type GroupReadCursors<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64, // group_id
+        Blake2_128Concat,
+        T::AccountId, // member
+        u64, // next unread message_id
+        ValueQuery,
+    >;
+
+    /// 存储项：群组最后活跃区块（创建或最后一条消息）
+    ///
+    /// 使用区块高度，与私聊 `Session::last_active` 统一排序收件箱
+    #[pallet::storage]
+    #[pallet::getter(fn group_last_active)]
+    pub type GroupLastActive<T: Config> = StorageMap<_, Blake2_128Concat, u64, BlockNumberFor<T>, ValueQuery>;
+
+    /// 存储项：成员统一公钥目录加密公钥的最后变更时间戳（秒）
+    ///
+    /// 由 `KeyChangeHandler` 回调写入，作为目录公钥的预密钥更新时间
+    #[pallet::storage]
+    #[pallet::getter(fn directory_key_updated_at)]
+    pub type DirectoryKeyUpdatedAt<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
     /// 事件定义
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -268,6 +455,28 @@ pub mod pallet {
         GroupDepositSlashed { group_id: u64, owner: T::AccountId, amount: BalanceOf<T>, reason: GroupViolationType },
         /// 群组已被封禁
         GroupBanned { group_id: u64, reason: GroupViolationType },
+        /// 成员预密钥已发布
+        PrekeyPublished { who: T::AccountId, post_quantum: bool },
+        /// 群组纪元已轮换，群主需重新包装纪元密钥
+        GroupKeyRotated { group_id: u64, epoch: u32, reason: KeyRotationReason },
+        /// 包装密钥已提交
+        WrappedKeysPosted { group_id: u64, epoch: u32, count: u32 },
+        /// 当前纪元密钥已分发给全部成员
+        GroupKeyRewrapCompleted { group_id: u64, epoch: u32 },
+        /// 机器人已注册
+        BotRegistered { bot: T::AccountId, permissions: BotPermissions, deposit: BalanceOf<T> },
+        /// 机器人已注销
+        BotDeregistered { bot: T::AccountId },
+        /// 机器人状态已变更（治理）
+        BotStatusChanged { bot: T::AccountId, status: BotStatus },
+        /// 机器人已安装到群组
+        BotInstalled { group_id: u64, bot: T::AccountId, granted: BotPermissions, installed_by: T::AccountId },
+        /// 机器人已从群组移除
+        BotUninstalled { group_id: u64, bot: T::AccountId, removed_by: T::AccountId },
+        /// 机器人消息已发送
+        BotMessageSent { group_id: u64, bot: T::AccountId, message_id: u64, message_type: MessageType },
+        /// 成员已读游标已更新 [群组ID, 成员, 下一条未读消息ID]
+        GroupReadCursorUpdated { group_id: u64, member: T::AccountId, cursor: u64 },
     }
 
     /// 群组违规类型
@@ -348,6 +557,50 @@ pub mod pallet {
         GroupBanned,
         /// 保证金不存在
         DepositNotFound,
+        /// 未发布预密钥
+        PrekeyNotPublished,
+        /// 无效的预密钥
+        InvalidPrekey,
+        /// 军用级群组要求抗量子预密钥
+        PostQuantumPrekeyRequired,
+        /// 抗量子预密钥太长
+        PostQuantumKeyTooLong,
+        /// 包装密钥太长
+        WrappedKeyTooLong,
+        /// 单次提交的包装密钥过多
+        TooManyWraps,
+        /// 纪元已过期
+        StaleKeyEpoch,
+        /// 群组未启用加密
+        GroupNotEncrypted,
+        /// 发送者尚未获得当前纪元密钥
+        GroupKeyNotDistributed,
+        /// 机器人已注册
+        BotAlreadyRegistered,
+        /// 机器人不存在
+        BotNotFound,
+        /// 机器人已被暂停
+        BotSuspended,
+        /// 机器人名称太长
+        BotNameTooLong,
+        /// CID太长
+        CidTooLong,
+        /// 机器人权限无效（未声明发言权限或超过上限）
+        InvalidBotPermissions,
+        /// 授予的权限超过机器人声明的权限
+        PermissionsExceedDeclared,
+        /// 机器人已安装到该群组
+        BotAlreadyInstalled,
+        /// 机器人未安装到该群组
+        BotNotInstalled,
+        /// 群组机器人数量已达上限
+        TooManyBots,
+        /// 机器人仍安装在群组中，不能注销
+        BotHasInstallations,
+        /// 机器人无权发送该类型消息
+        BotMessageTypeNotAllowed,
+        /// 机器人发言过于频繁
+        BotRateLimited,
     }
 
     #[pallet::call]
@@ -383,6 +636,10 @@ pub mod pallet {
                 Error::<T>::UserGroupLimitExceeded
             );
 
+            // 加密群组要求群主已发布预密钥
+            let encryption_mode_enum = EncryptionMode::from_u8(encryption_mode);
+            Self::ensure_prekey_for(&who, &encryption_mode_enum)?;
+
             // 计算并锁定保证金（5 USDT 等值的 DUST）
             let deposit = Self::calculate_deposit_amount();
             T::Currency::reserve(&who, deposit)
@@ -394,9 +651,6 @@ pub mod pallet {
             // 获取当前时间戳
             let now = T::TimeProvider::now().as_secs();
 
-            // 转换加密模式
-            let encryption_mode_enum = EncryptionMode::from_u8(encryption_mode);
-
             // 创建群组信息
             let group_info = GroupInfo {
                 id: group_id,
@@ -428,6 +682,7 @@ pub mod pallet {
                 joined_at: now,
             };
             GroupMembers::<T>::insert(&group_id, &who, &owner_member);
+            GroupLastActive::<T>::insert(&group_id, frame_system::Pallet::<T>::block_number());
 
             // 更新用户群组列表
             UserGroups::<T>::mutate(&who, |groups| {
@@ -437,6 +692,11 @@ pub mod pallet {
             // 发出事件
             Self::deposit_event(Event::GroupCreated { creator: who, group_id });
 
+            // 加密群组从纪元 0 开始，等待群主提交包装密钥
+            if encryption_mode_enum.requires_encryption() {
+                Self::bump_key_epoch(group_id, KeyRotationReason::GroupCreated);
+            }
+
             Ok(())
         }
 
@@ -452,7 +712,7 @@ pub mod pallet {
             let who = ensure_signed(origin)?;
 
             // 验证群组存在
-            let _group = Self::groups(&group_id).ok_or(Error::<T>::GroupNotFound)?;
+            let group = Self::groups(&group_id).ok_or(Error::<T>::GroupNotFound)?;
 
             // 验证发送者是群组成员
             ensure!(
@@ -460,6 +720,14 @@ pub mod pallet {
                 Error::<T>::NotGroupMember
             );
 
+            // 加密群组：发送者必须已持有当前纪元密钥
+            if group.encryption_mode.requires_encryption() {
+                ensure!(
+                    Self::holds_current_group_key(group_id, &who),
+                    Error::<T>::GroupKeyNotDistributed
+                );
+            }
+
             // 验证内容长度
             let bounded_content = MessageContentOf::<T>::try_from(content)
                 .map_err(|_| Error::<T>::MessageContentTooLong)?;
@@ -467,26 +735,11 @@ pub mod pallet {
             // 转换消息类型
             let message_type_enum = MessageType::from_u8(message_type);
 
-            // 生成消息ID
-            let message_id = Self::next_message_id(&group_id);
-            let next_id = message_id.saturating_add(1);
-            NextMessageId::<T>::insert(&group_id, next_id);
-
-            // 获取当前时间戳
-            let now = T::TimeProvider::now().as_secs();
-
-            // 创建消息
-            let message = GroupMessage {
-                id: message_id,
-                group_id,
-                sender: who.clone(),
-                content: bounded_content,
-                message_type: message_type_enum,
-                timestamp: now,
-            };
-
             // 存储消息
-            GroupMessages::<T>::insert(&group_id, &message_id, &message);
+            let message_id = Self::store_group_message(group_id, &who, bounded_content, message_type_enum);
+
+            // 发送者视为已读到自己的消息
+            Self::advance_read_cursor(group_id, &who, message_id.saturating_add(1));
 
             // 发出事件
             Self::deposit_event(Event::GroupMessageSent { group_id, sender: who, message_id });
@@ -525,6 +778,9 @@ pub mod pallet {
                 Error::<T>::UserGroupLimitExceeded
             );
 
+            // 加密群组要求新成员已发布预密钥
+            Self::ensure_prekey_for(&who, &group.encryption_mode)?;
+
             // 获取当前时间戳
             let now = T::TimeProvider::now().as_secs();
 
@@ -536,6 +792,9 @@ pub mod pallet {
             };
             GroupMembers::<T>::insert(&group_id, &who, &member);
 
+            // 新成员的历史消息不计入未读
+            GroupReadCursors::<T>::insert(&group_id, &who, Self::next_message_id(&group_id));
+
             // 更新群组成员数
             group.member_count = group.member_count.saturating_add(1);
             Groups::<T>::insert(&group_id, &group);
@@ -548,6 +807,11 @@ pub mod pallet {
             // 发出事件
             Self::deposit_event(Event::MemberJoined { group_id, member: who });
 
+            // 新成员不能读取历史纪元，轮换纪元
+            if group.encryption_mode.requires_encryption() {
+                Self::bump_key_epoch(group_id, KeyRotationReason::MemberJoined);
+            }
+
             Ok(())
         }
 
@@ -572,8 +836,10 @@ pub mod pallet {
                 return Self::do_disband_group(group_id);
             }
 
-            // 移除成员
+            // 移除成员及其包装密钥
             GroupMembers::<T>::remove(&group_id, &who);
+            WrappedGroupKeys::<T>::remove(&group_id, &who);
+            GroupReadCursors::<T>::remove(&group_id, &who);
 
             // 更新群组成员数
             group.member_count = group.member_count.saturating_sub(1);
@@ -587,6 +853,11 @@ pub mod pallet {
             // 发出事件
             Self::deposit_event(Event::MemberLeft { group_id, member: who });
 
+            // 离开的成员不能读取后续消息，轮换纪元
+            if group.encryption_mode.requires_encryption() {
+                Self::bump_key_epoch(group_id, KeyRotationReason::MemberLeft);
+            }
+
             Ok(())
         }
 
@@ -610,6 +881,386 @@ pub mod pallet {
             Self::do_disband_group(group_id)
         }
 
+        /// 发布或更新预密钥
+        ///
+        /// 私钥仅保存在客户端，链上只记录公钥。
+        /// 更新预密钥不影响已包装的纪元密钥，之后的包装使用新公钥。
+        /// 所在群组为军用级时，更新后的预密钥仍必须包含抗量子公钥。
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::publish_prekey())]
+        pub fn publish_prekey(
+            origin: OriginFor<T>,
+            x25519: [u8; 32],
+            post_quantum: Option<Vec<u8>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            // 拒绝全零公钥（低阶点）
+            ensure!(x25519 != [0u8; 32], Error::<T>::InvalidPrekey);
+
+            let post_quantum = match post_quantum {
+                Some(key) => {
+                    ensure!(!key.is_empty(), Error::<T>::InvalidPrekey);
+                    Some(PqKeyOf::<T>::try_from(key)
+                        .map_err(|_| Error::<T>::PostQuantumKeyTooLong)?)
+                },
+                None => None,
+            };
+            let has_post_quantum = post_quantum.is_some();
+            if !has_post_quantum {
+                ensure!(
+                    !Self::requires_post_quantum_prekey(&who),
+                    Error::<T>::PostQuantumPrekeyRequired
+                );
+            }
+
+            MemberPrekeys::<T>::insert(&who, MemberPrekey {
+                x25519,
+                post_quantum,
+                updated_at: T::TimeProvider::now().as_secs(),
+            });
+
+            Self::deposit_event(Event::PrekeyPublished { who, post_quantum: has_post_quantum });
+
+            Ok(())
+        }
+
+        /// 提交包装后的纪元密钥（仅群主）
+        ///
+        /// `epoch` 必须等于当前纪元，防止群主基于过期成员列表提交。
+        /// 每个接收者必须是当前成员且已发布预密钥。
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::WeightInfo::post_wrapped_keys(wraps.len() as u32))]
+        pub fn post_wrapped_keys(
+            origin: OriginFor<T>,
+            group_id: u64,
+            epoch: u32,
+            wraps: Vec<(T::AccountId, Vec<u8>)>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let group = Self::groups(&group_id).ok_or(Error::<T>::GroupNotFound)?;
+            ensure!(group.owner == who, Error::<T>::NotGroupOwner);
+            ensure!(wraps.len() as u32 <= T::MaxWrapsPerCall::get(), Error::<T>::TooManyWraps);
+
+            let mut state = GroupKeyStates::<T>::get(&group_id)
+                .ok_or(Error::<T>::GroupNotEncrypted)?;
+            ensure!(state.epoch == epoch, Error::<T>::StaleKeyEpoch);
+
+            let count = wraps.len() as u32;
+            for (member, payload) in wraps {
+                // 接收者可以是成员或已安装的机器人
+                ensure!(
+                    GroupMembers::<T>::contains_key(&group_id, &member)
+                        || GroupBots::<T>::contains_key(&group_id, &member),
+                    Error::<T>::NotGroupMember
+                );
+                ensure!(
+                    Self::prekey_of(&member).is_some(),
+                    Error::<T>::PrekeyNotPublished
+                );
+                let payload = WrappedKeyOf::<T>::try_from(payload)
+                    .map_err(|_| Error::<T>::WrappedKeyTooLong)?;
+
+                let already_current = WrappedGroupKeys::<T>::get(&group_id, &member)
+                    .map(|w| w.epoch == epoch)
+                    .unwrap_or(false);
+                if !already_current {
+                    state.wrapped_count = state.wrapped_count.saturating_add(1);
+                }
+
+                WrappedGroupKeys::<T>::insert(&group_id, &member, WrappedGroupKey {
+                    epoch,
+                    payload,
+                    wrapped_by: who.clone(),
+                });
+            }
+
+            GroupKeyStates::<T>::insert(&group_id, &state);
+
+            Self::deposit_event(Event::WrappedKeysPosted { group_id, epoch, count });
+
+            let recipients = group.member_count.saturating_add(GroupBotCount::<T>::get(&group_id));
+            if state.wrapped_count >= recipients {
+                Self::deposit_event(Event::GroupKeyRewrapCompleted { group_id, epoch });
+            }
+
+            Ok(())
+        }
+
+        /// 手动轮换群组纪元（仅群主）
+        ///
+        /// 用于怀疑密钥泄露等场景，轮换后所有成员需等待重新包装
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::WeightInfo::rotate_group_key())]
+        pub fn rotate_group_key(
+            origin: OriginFor<T>,
+            group_id: u64,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let group = Self::groups(&group_id).ok_or(Error::<T>::GroupNotFound)?;
+            ensure!(group.owner == who, Error::<T>::NotGroupOwner);
+            ensure!(GroupKeyStates::<T>::contains_key(&group_id), Error::<T>::GroupNotEncrypted);
+
+            Self::bump_key_epoch(group_id, KeyRotationReason::Manual);
+
+            Ok(())
+        }
+
+        /// 注册机器人（由机器人账户签名）
+        ///
+        /// 声明的权限是群内可授予权限的上限，注册时锁定保证金
+        #[pallet::call_index(8)]
+        #[pallet::weight(T::WeightInfo::register_bot())]
+        pub fn register_bot(
+            origin: OriginFor<T>,
+            name: Vec<u8>,
+            metadata_cid: Vec<u8>,
+            permissions: BotPermissions,
+        ) -> DispatchResult {
+            let bot = ensure_signed(origin)?;
+
+            ensure!(!Bots::<T>::contains_key(&bot), Error::<T>::BotAlreadyRegistered);
+            ensure!(
+                !permissions.is_empty()
+                    && permissions.max_messages_per_window <= T::MaxBotMessagesPerWindow::get(),
+                Error::<T>::InvalidBotPermissions
+            );
+
+            let name = GroupNameOf::<T>::try_from(name)
+                .map_err(|_| Error::<T>::BotNameTooLong)?;
+            let metadata_cid = CidOf::<T>::try_from(metadata_cid)
+                .map_err(|_| Error::<T>::CidTooLong)?;
+
+            let deposit = Self::calculate_bot_deposit_amount();
+            T::Currency::reserve(&bot, deposit)
+                .map_err(|_| Error::<T>::InsufficientBalance)?;
+
+            Bots::<T>::insert(&bot, BotInfo {
+                name,
+                metadata_cid,
+                permissions,
+                status: BotStatus::Active,
+                deposit,
+                install_count: 0,
+                registered_at: T::TimeProvider::now().as_secs(),
+            });
+
+            Self::deposit_event(Event::BotRegistered { bot, permissions, deposit });
+
+            Ok(())
+        }
+
+        /// 注销机器人并退还保证金（须先从所有群组移除）
+        ///
+        /// 被治理暂停的机器人不能注销，保证金保留至治理恢复其状态
+        #[pallet::call_index(9)]
+        #[pallet::weight(T::WeightInfo::deregister_bot())]
+        pub fn deregister_bot(origin: OriginFor<T>) -> DispatchResult {
+            let bot = ensure_signed(origin)?;
+
+            let info = Bots::<T>::get(&bot).ok_or(Error::<T>::BotNotFound)?;
+            ensure!(info.status == BotStatus::Active, Error::<T>::BotSuspended);
+            ensure!(info.install_count == 0, Error::<T>::BotHasInstallations);
+
+            T::Currency::unreserve(&bot, info.deposit);
+            Bots::<T>::remove(&bot);
+
+            Self::deposit_event(Event::BotDeregistered { bot });
+
+            Ok(())
+        }
+
+        /// 安装机器人到群组（群主或管理员）
+        ///
+        /// 加密群组中机器人与成员一样需要预密钥，安装会轮换纪元，
+        /// 群主重新包装后机器人才能发言
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::install_bot())]
+        pub fn install_bot(
+            origin: OriginFor<T>,
+            group_id: u64,
+            bot: T::AccountId,
+            granted: BotPermissions,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let group = Self::groups(&group_id).ok_or(Error::<T>::GroupNotFound)?;
+            ensure!(!BannedGroups::<T>::contains_key(&group_id), Error::<T>::GroupBanned);
+            Self::ensure_group_admin(group_id, &who)?;
+
+            let mut info = Bots::<T>::get(&bot).ok_or(Error::<T>::BotNotFound)?;
+            ensure!(info.status == BotStatus::Active, Error::<T>::BotSuspended);
+            ensure!(
+                !granted.is_empty() && granted.is_subset_of(&info.permissions),
+                Error::<T>::PermissionsExceedDeclared
+            );
+            ensure!(!GroupBots::<T>::contains_key(&group_id, &bot), Error::<T>::BotAlreadyInstalled);
+
+            let count = GroupBotCount::<T>::get(&group_id);
+            ensure!(count < T::MaxBotsPerGroup::get(), Error::<T>::TooManyBots);
+
+            Self::ensure_prekey_for(&bot, &group.encryption_mode)?;
+
+            GroupBots::<T>::insert(&group_id, &bot, InstalledBot {
+                granted,
+                installed_by: who.clone(),
+                installed_at: T::TimeProvider::now().as_secs(),
+                rate: RateLimitState::new(),
+            });
+            GroupBotCount::<T>::insert(&group_id, count.saturating_add(1));
+            info.install_count = info.install_count.saturating_add(1);
+            Bots::<T>::insert(&bot, info);
+
+            Self::deposit_event(Event::BotInstalled { group_id, bot, granted, installed_by: who });
+
+            if group.encryption_mode.requires_encryption() {
+                Self::bump_key_epoch(group_id, KeyRotationReason::MemberJoined);
+            }
+
+            Ok(())
+        }
+
+        /// 从群组移除机器人（群主、管理员或机器人自己）
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::WeightInfo::uninstall_bot())]
+        pub fn uninstall_bot(
+            origin: OriginFor<T>,
+            group_id: u64,
+            bot: T::AccountId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let group = Self::groups(&group_id).ok_or(Error::<T>::GroupNotFound)?;
+            if who != bot {
+                Self::ensure_group_admin(group_id, &who)?;
+            }
+            ensure!(GroupBots::<T>::contains_key(&group_id, &bot), Error::<T>::BotNotInstalled);
+
+            Self::do_uninstall_bot(group_id, &bot);
+
+            Self::deposit_event(Event::BotUninstalled { group_id, bot, removed_by: who });
+
+            if group.encryption_mode.requires_encryption() {
+                Self::bump_key_epoch(group_id, KeyRotationReason::MemberLeft);
+            }
+
+            Ok(())
+        }
+
+        /// 机器人发送群消息
+        ///
+        /// 只能发送授予权限内的 `AI` / `System` 消息，并受频率窗口限制
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::bot_send_message())]
+        pub fn bot_send_message(
+            origin: OriginFor<T>,
+            group_id: u64,
+            content: Vec<u8>,
+            message_type: u8,
+        ) -> DispatchResult {
+            let bot = ensure_signed(origin)?;
+
+            let group = Self::groups(&group_id).ok_or(Error::<T>::GroupNotFound)?;
+            ensure!(!BannedGroups::<T>::contains_key(&group_id), Error::<T>::GroupBanned);
+
+            let info = Bots::<T>::get(&bot).ok_or(Error::<T>::BotNotFound)?;
+            ensure!(info.status == BotStatus::Active, Error::<T>::BotSuspended);
+
+            let mut installed = GroupBots::<T>::get(&group_id, &bot)
+                .ok_or(Error::<T>::BotNotInstalled)?;
+
+            let message_type_enum = MessageType::from_u8(message_type);
+            ensure!(
+                installed.granted.allows(&message_type_enum),
+                Error::<T>::BotMessageTypeNotAllowed
+            );
+
+            if group.encryption_mode.requires_encryption() {
+                ensure!(
+                    Self::holds_current_group_key(group_id, &bot),
+                    Error::<T>::GroupKeyNotDistributed
+                );
+            }
+
+            let bounded_content = MessageContentOf::<T>::try_from(content)
+                .map_err(|_| Error::<T>::MessageContentTooLong)?;
+
+            // 频率限制
+            let now_block = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                check_and_update_rate_limit(
+                    &mut installed.rate,
+                    now_block,
+                    T::BotRateWindow::get(),
+                    installed.granted.max_messages_per_window,
+                ).is_allowed(),
+                Error::<T>::BotRateLimited
+            );
+            GroupBots::<T>::insert(&group_id, &bot, &installed);
+
+            let message_id = Self::store_group_message(group_id, &bot, bounded_content, message_type_enum);
+
+            Self::deposit_event(Event::BotMessageSent {
+                group_id,
+                bot,
+                message_id,
+                message_type: message_type_enum,
+            });
+
+            Ok(())
+        }
+
+        /// 设置机器人状态（治理权限）
+        ///
+        /// 暂停的机器人在所有群组中都不能发言，也不能被新群组安装
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::set_bot_status())]
+        pub fn set_bot_status(
+            origin: OriginFor<T>,
+            bot: T::AccountId,
+            status: BotStatus,
+        ) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+
+            Bots::<T>::try_mutate(&bot, |maybe_info| -> DispatchResult {
+                let info = maybe_info.as_mut().ok_or(Error::<T>::BotNotFound)?;
+                info.status = status;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::BotStatusChanged { bot, status });
+
+            Ok(())
+        }
+
+        /// 标记群消息已读
+        ///
+        /// 将已读游标前移到 `up_to` 之后（`up_to` 及之前的消息视为已读），
+        /// 游标只增不减，重复或回退调用不会产生影响
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::mark_group_read())]
+        pub fn mark_group_read(
+            origin: OriginFor<T>,
+            group_id: u64,
+            up_to: u64,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                GroupMembers::<T>::contains_key(&group_id, &who),
+                Error::<T>::NotGroupMember
+            );
+            ensure!(up_to < Self::next_message_id(&group_id), Error::<T>::MessageNotFound);
+
+            let cursor = Self::advance_read_cursor(group_id, &who, up_to.saturating_add(1));
+
+            Self::deposit_event(Event::GroupReadCursorUpdated { group_id, member: who, cursor });
+
+            Ok(())
+        }
+
         /// 处理群组违规（治理权限）
         /// 
         /// 根据违规类型扣除保证金并执行相应处罚
@@ -718,6 +1369,44 @@ pub mod pallet {
         }
     }
 
+    impl<T: Config> pallet_key_directory::traits::KeyChangeHandler<T::AccountId> for Pallet<T> {
+        fn max_weight() -> Weight {
+            let groups = T::MaxGroupsPerUser::get() as u64;
+            T::DbWeight::get().reads_writes(1 + groups.saturating_mul(2), 1 + groups)
+        }
+
+        fn on_key_registered(
+            who: &T::AccountId,
+            purpose: pallet_key_directory::types::KeyPurpose,
+            _version: u32,
+        ) {
+            if purpose == pallet_key_directory::types::KeyPurpose::Encryption {
+                Self::on_directory_key_changed(who, None);
+            }
+        }
+
+        fn on_key_rotated(
+            who: &T::AccountId,
+            purpose: pallet_key_directory::types::KeyPurpose,
+            _old_version: u32,
+            _new_version: u32,
+        ) {
+            if purpose == pallet_key_directory::types::KeyPurpose::Encryption {
+                Self::on_directory_key_changed(who, Some(KeyRotationReason::MemberKeyRotated));
+            }
+        }
+
+        fn on_key_revoked(
+            who: &T::AccountId,
+            purpose: pallet_key_directory::types::KeyPurpose,
+            _version: u32,
+        ) {
+            if purpose == pallet_key_directory::types::KeyPurpose::Encryption {
+                Self::on_directory_key_changed(who, Some(KeyRotationReason::MemberKeyRevoked));
+            }
+        }
+    }
+
     // 内部函数
     impl<T: Config> Pallet<T> {
         /// 生成唯一的10位数随机群组ID
@@ -802,15 +1491,199 @@ pub mod pallet {
             // 5. 移除群组信息
             Groups::<T>::remove(&group_id);
 
-            // 6. 移除群组消息
+            // 6. 移除群组消息及已读状态
             let _result = GroupMessages::<T>::clear_prefix(&group_id, u32::MAX, None);
+            let _result = GroupReadCursors::<T>::clear_prefix(&group_id, u32::MAX, None);
+            GroupLastActive::<T>::remove(&group_id);
+
+            // 7. 移除群组密钥材料
+            GroupKeyStates::<T>::remove(&group_id);
+            let _result = WrappedGroupKeys::<T>::clear_prefix(&group_id, u32::MAX, None);
 
-            // 7. 发出事件
+            // 8. 移除已安装的机器人（数量受 MaxBotsPerGroup 限制）
+            let bots: Vec<T::AccountId> = GroupBots::<T>::iter_key_prefix(&group_id).collect();
+            for bot in bots.iter() {
+                Self::do_uninstall_bot(group_id, bot);
+            }
+            GroupBotCount::<T>::remove(&group_id);
+
+            // 9. 发出事件
             Self::deposit_event(Event::GroupDisbanded { group_id });
 
             Ok(())
         }
 
+        /// 存储群消息并返回消息ID
+        fn store_group_message(
+            group_id: u64,
+            sender: &T::AccountId,
+            content: MessageContentOf<T>,
+            message_type: MessageType,
+        ) -> u64 {
+            // 生成消息ID
+            let message_id = Self::next_message_id(&group_id);
+            NextMessageId::<T>::insert(&group_id, message_id.saturating_add(1));
+
+            let message = GroupMessage {
+                id: message_id,
+                group_id,
+                sender: sender.clone(),
+                content,
+                message_type,
+                timestamp: T::TimeProvider::now().as_secs(),
+            };
+            GroupMessages::<T>::insert(&group_id, &message_id, &message);
+            GroupLastActive::<T>::insert(&group_id, frame_system::Pallet::<T>::block_number());
+
+            message_id
+        }
+
+        /// 前移成员已读游标（游标只增不减，返回更新后的游标）
+        fn advance_read_cursor(group_id: u64, who: &T::AccountId, cursor: u64) -> u64 {
+            GroupReadCursors::<T>::mutate(&group_id, who, |current| {
+                if cursor > *current {
+                    *current = cursor;
+                }
+                *current
+            })
+        }
+
+        /// 成员在群组中的未读消息数
+        pub fn group_unread(group_id: u64, who: &T::AccountId) -> u32 {
+            if !GroupMembers::<T>::contains_key(&group_id, who) {
+                return 0;
+            }
+            let unread = Self::next_message_id(&group_id)
+                .saturating_sub(GroupReadCursors::<T>::get(&group_id, who));
+            unread.min(u32::MAX as u64) as u32
+        }
+
+        /// 要求账户是群主或管理员
+        fn ensure_group_admin(group_id: u64, who: &T::AccountId) -> DispatchResult {
+            let member = GroupMembers::<T>::get(&group_id, who).ok_or(Error::<T>::NotGroupMember)?;
+            ensure!(
+                matches!(member.role, MemberRole::Owner | MemberRole::Admin),
+                Error::<T>::NotGroupAdmin
+            );
+            Ok(())
+        }
+
+        /// 移除机器人安装记录及其包装密钥
+        fn do_uninstall_bot(group_id: u64, bot: &T::AccountId) {
+            if GroupBots::<T>::take(&group_id, bot).is_none() {
+                return;
+            }
+            WrappedGroupKeys::<T>::remove(&group_id, bot);
+            GroupBotCount::<T>::mutate(&group_id, |count| *count = count.saturating_sub(1));
+            Bots::<T>::mutate(bot, |maybe_info| {
+                if let Some(info) = maybe_info {
+                    info.install_count = info.install_count.saturating_sub(1);
+                }
+            });
+        }
+
+        /// 成员当前预密钥
+        ///
+        /// X25519 公钥优先取统一公钥目录中的加密公钥，抗量子公钥仅来自 `MemberPrekeys`。
+        /// 更新时间取目录公钥与 `MemberPrekeys` 中较晚的一次变更。
+        /// 群主应按此结果包装纪元密钥。
+        pub fn prekey_of(who: &T::AccountId) -> Option<MemberPrekey<PqKeyOf<T>>> {
+            use pallet_key_directory::traits::KeyDirectory;
+
+            let stored = MemberPrekeys::<T>::get(who);
+            match T::KeyDirectory::encryption_key(who) {
+                Some(x25519) => {
+                    let directory_updated_at = DirectoryKeyUpdatedAt::<T>::get(who);
+                    Some(match stored {
+                        Some(prekey) => MemberPrekey {
+                            x25519,
+                            updated_at: prekey.updated_at.max(directory_updated_at),
+                            ..prekey
+                        },
+                        None => MemberPrekey {
+                            x25519,
+                            post_quantum: None,
+                            updated_at: directory_updated_at,
+                        },
+                    })
+                },
+                None => stored,
+            }
+        }
+
+        /// 成员目录加密公钥变更：记录时间并轮换其所在加密群组的纪元
+        ///
+        /// 新纪元迫使群主用成员的新公钥重新包装；吊销时旧公钥可能已泄露，
+        /// 同样不能继续沿用旧纪元密钥。机器人没有反向索引，不在此处理。
+        fn on_directory_key_changed(who: &T::AccountId, reason: Option<KeyRotationReason>) {
+            DirectoryKeyUpdatedAt::<T>::insert(who, T::TimeProvider::now().as_secs());
+
+            let Some(reason) = reason else { return };
+            for group_id in UserGroups::<T>::get(who) {
+                if GroupKeyStates::<T>::contains_key(&group_id) {
+                    Self::bump_key_epoch(group_id, reason);
+                }
+            }
+        }
+
+        /// 加密群组要求账户已发布预密钥，军用级还要求抗量子预密钥
+        fn ensure_prekey_for(who: &T::AccountId, mode: &EncryptionMode) -> DispatchResult {
+            if !mode.requires_encryption() {
+                return Ok(());
+            }
+
+            let prekey = Self::prekey_of(who).ok_or(Error::<T>::PrekeyNotPublished)?;
+            if *mode == EncryptionMode::Military {
+                ensure!(prekey.post_quantum.is_some(), Error::<T>::PostQuantumPrekeyRequired);
+            }
+
+            Ok(())
+        }
+
+        /// 账户是否必须保留抗量子预密钥
+        ///
+        /// 成员：所在群组中任一为军用级即需要。
+        /// 机器人没有反向索引：已安装且当前持有抗量子公钥的机器人不能去掉它。
+        fn requires_post_quantum_prekey(who: &T::AccountId) -> bool {
+            let in_military_group = UserGroups::<T>::get(who).iter().any(|group_id| {
+                Self::groups(group_id)
+                    .is_some_and(|group| group.encryption_mode == EncryptionMode::Military)
+            });
+            if in_military_group {
+                return true;
+            }
+
+            Bots::<T>::get(who).is_some_and(|info| info.install_count > 0)
+                && MemberPrekeys::<T>::get(who).is_some_and(|prekey| prekey.post_quantum.is_some())
+        }
+
+        /// 纪元递增
+        ///
+        /// 旧纪元的包装密钥保留（成员仍可解密历史消息），
+        /// 但发送消息需要当前纪元密钥，从而强制群主重新包装
+        fn bump_key_epoch(group_id: u64, reason: KeyRotationReason) {
+            let now = T::TimeProvider::now().as_secs();
+            let epoch = GroupKeyStates::<T>::mutate(&group_id, |state| {
+                let state = state.get_or_insert_with(Default::default);
+                if reason != KeyRotationReason::GroupCreated {
+                    state.epoch = state.epoch.saturating_add(1);
+                }
+                state.rotated_at = now;
+                state.wrapped_count = 0;
+                state.epoch
+            });
+
+            Self::deposit_event(Event::GroupKeyRotated { group_id, epoch, reason });
+        }
+
+        /// 成员是否持有当前纪元密钥
+        pub fn holds_current_group_key(group_id: u64, who: &T::AccountId) -> bool {
+            match (GroupKeyStates::<T>::get(&group_id), WrappedGroupKeys::<T>::get(&group_id, who)) {
+                (Some(state), Some(wrapped)) => wrapped.epoch == state.epoch,
+                _ => false,
+            }
+        }
+
         /// 函数级详细中文注释：验证媒体内容
         ///
         /// 根据消息类型验证媒体文件格式和完整性：
@@ -896,5 +1769,33 @@ pub mod pallet {
                 T::GroupDeposit::get(),
             )
         }
+
+        /// 计算机器人注册保证金
+        pub fn calculate_bot_deposit_amount() -> BalanceOf<T> {
+            use pallet_trading_common::DepositCalculator;
+            T::DepositCalculator::calculate_deposit(
+                T::BotDepositUsd::get(),
+                T::BotDeposit::get(),
+            )
+        }
     }
-}
\ No newline at end of file
+}
+
+/// 群聊收件箱：供 pallet-chat-core 合并私聊与群聊
+impl<T: Config> GroupInboxProvider<T::AccountId, BlockNumberFor<T>> for Pallet<T> {
+    fn group_inbox(user: &T::AccountId) -> Vec<GroupInboxItem<BlockNumberFor<T>>> {
+        Self::user_groups(user)
+            .into_iter()
+            .map(|group_id| GroupInboxItem {
+                group_id,
+                last_active: GroupLastActive::<T>::get(&group_id),
+                last_message_id: Self::next_message_id(&group_id).checked_sub(1),
+                unread: Self::group_unread(group_id, user),
+            })
+            .collect()
+    }
+
+    fn group_unread_count(group_id: u64, user: &T::AccountId) -> u32 {
+        Self::group_unread(group_id, user)
+    }
+}
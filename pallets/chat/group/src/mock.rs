@@ -66,6 +66,37 @@ impl pallet_balances::Config for Test {
 parameter_types! {
     pub const GroupPalletId: PalletId = PalletId(*b"py/group");
     pub const TreasuryAccountId: u64 = 999;
+    /// 统一公钥目录中的 X25519 加密公钥
+    pub static DirectoryKeys: Vec<(u64, [u8; 32])> = Vec::new();
+}
+
+/// 测试用公钥目录（仅支持加密用途）
+pub struct TestKeyDirectory;
+
+impl pallet_key_directory::traits::KeyDirectory<u64> for TestKeyDirectory {
+    fn current_key(
+        who: &u64,
+        purpose: pallet_key_directory::types::KeyPurpose,
+    ) -> Option<pallet_key_directory::types::DirectoryKey> {
+        if purpose != pallet_key_directory::types::KeyPurpose::Encryption {
+            return None;
+        }
+        DirectoryKeys::get().into_iter().find(|(account, _)| account == who).map(|(_, key)| {
+            pallet_key_directory::types::DirectoryKey {
+                version: 1,
+                algorithm: pallet_key_directory::types::KeyAlgorithm::X25519,
+                public_key: key.to_vec(),
+            }
+        })
+    }
+
+    fn is_usable_key(
+        who: &u64,
+        purpose: pallet_key_directory::types::KeyPurpose,
+        public_key: &[u8],
+    ) -> bool {
+        Self::current_key(who, purpose).map_or(false, |key| key.public_key == public_key)
+    }
 }
 
 impl pallet_chat_group::Config for Test {
@@ -81,6 +112,14 @@ impl pallet_chat_group::Config for Test {
     type MaxGroupMessageHistory = ConstU32<1000>;
     type MaxCidLen = ConstU32<128>;
     type MaxKeyLen = ConstU32<256>;
+    type MaxPqKeyLen = ConstU32<1184>;
+    type MaxWrappedKeyLen = ConstU32<1184>;
+    type MaxWrapsPerCall = ConstU32<50>;
+    type MaxBotsPerGroup = ConstU32<3>;
+    type BotRateWindow = ConstU64<10>;
+    type MaxBotMessagesPerWindow = ConstU32<20>;
+    type BotDeposit = ConstU128<10_000_000_000_000_000_000>; // 10 DUST 兜底
+    type BotDepositUsd = ConstU64<1_000_000>; // 1 USDT
     type PalletId = GroupPalletId;
     type MessageRateLimit = ConstU32<60>;
     type GroupCreationCooldown = ConstU64<10>;
@@ -89,6 +128,7 @@ impl pallet_chat_group::Config for Test {
     type DepositCalculator = (); // 使用空实现，返回兜底值
     type TreasuryAccount = TreasuryAccountId;
     type GovernanceOrigin = EnsureRoot<u64>;
+    type KeyDirectory = TestKeyDirectory;
     type WeightInfo = ();
 }
 
@@ -101,6 +141,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
     ext.execute_with(|| {
         System::set_block_number(1);
         // Timestamp 会自动设置，不需要手动设置
+        DirectoryKeys::take();
     });
     ext
 }
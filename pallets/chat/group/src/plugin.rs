@@ -1,824 +1,113 @@
-/// Stardust智能群聊 - 插件生态系统框架
-///
-/// 提供可扩展的插件架构，支持第三方开发者为智能群聊系统添加功能
+//! # 群聊机器人注册表类型
+//!
+//! 机器人（如占卜解读机器人）以独立账户注册并声明所需权限，
+//! 群主/管理员按群安装并授予不超过声明范围的权限，
+//! 已安装的机器人可在频率限制内发送 `MessageType::AI` / `MessageType::System` 消息。
 
-use crate::types::*;
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::pallet_prelude::*;
-use sp_std::{vec, vec::Vec, collections::btree_map::BTreeMap};
-use sp_core::H256;
-use sp_runtime::traits::{Hash, Saturating};
+use pallet_chat_common::{MessageType, RateLimitState};
+use scale_info::TypeInfo;
 
-/// 插件系统管理器
-pub struct PluginEcosystem<T: frame_system::Config> {
-    _phantom: sp_std::marker::PhantomData<T>,
+/// 机器人权限
+#[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen, Default)]
+pub struct BotPermissions {
+    /// 可发送 AI 消息（如占卜解读）
+    pub send_ai: bool,
+    /// 可发送系统消息（如群公告、提醒）
+    pub send_system: bool,
+    /// 频率窗口内最多发送的消息数
+    pub max_messages_per_window: u32,
 }
 
-impl<T: frame_system::Config> PluginEcosystem<T> {
-    /// 创建新的插件生态系统实例
-    pub fn new() -> Self {
-        Self {
-            _phantom: Default::default(),
-        }
+impl BotPermissions {
+    /// 是否未声明任何发言权限
+    pub fn is_empty(&self) -> bool {
+        (!self.send_ai && !self.send_system) || self.max_messages_per_window == 0
     }
 
-    /// 注册插件
-    pub fn register_plugin(
-        &self,
-        plugin_id: &PluginId,
-        plugin_info: &PluginInfo,
-    ) -> Result<(), PluginError> {
-        // 验证插件信息
-        self.validate_plugin_info(plugin_info)?;
-
-        // 检查插件是否已存在
-        if self.is_plugin_registered(plugin_id) {
-            return Err(PluginError::PluginAlreadyExists);
-        }
-
-        // 验证插件权限
-        self.validate_plugin_permissions(&plugin_info.permissions)?;
-
-        // 创建插件沙盒环境
-        let sandbox = self.create_plugin_sandbox(plugin_id, plugin_info)?;
-
-        // 初始化插件
-        self.initialize_plugin(plugin_id, plugin_info, &sandbox)?;
-
-        Ok(())
+    /// 是否是 `declared` 的子集（安装时授予的权限不能超过注册时声明的权限）
+    pub fn is_subset_of(&self, declared: &BotPermissions) -> bool {
+        (!self.send_ai || declared.send_ai)
+            && (!self.send_system || declared.send_system)
+            && self.max_messages_per_window <= declared.max_messages_per_window
     }
 
-    /// 卸载插件
-    pub fn unregister_plugin(&self, plugin_id: &PluginId) -> Result<(), PluginError> {
-        if !self.is_plugin_registered(plugin_id) {
-            return Err(PluginError::PluginNotFound);
+    /// 是否允许发送该类型的消息
+    pub fn allows(&self, message_type: &MessageType) -> bool {
+        match message_type {
+            MessageType::AI => self.send_ai,
+            MessageType::System => self.send_system,
+            _ => false,
         }
-
-        // 清理插件资源
-        self.cleanup_plugin_resources(plugin_id)?;
-
-        // 移除插件沙盒
-        self.remove_plugin_sandbox(plugin_id)?;
-
-        Ok(())
     }
-
-    /// 执行插件钩子
-    pub fn execute_hook(
-        &self,
-        hook_type: PluginHookType,
-        context: &PluginContext,
-        data: &[u8],
-    ) -> Result<PluginExecutionResult, PluginError> {
-        let registered_plugins = self.get_plugins_for_hook(&hook_type);
-        let mut execution_results = Vec::new();
-
-        for plugin_id in registered_plugins {
-            if self.is_plugin_enabled(&plugin_id) {
-                // 在沙盒环境中执行插件
-                let result = self.execute_plugin_in_sandbox(
-                    &plugin_id,
-                    &hook_type,
-                    context,
-                    data,
-                )?;
-
-                execution_results.push(PluginHookResult {
-                    plugin_id: plugin_id.clone(),
-                    result,
-                    execution_time: self.get_current_time(),
-                });
-            }
-        }
-
-        let total_time = self.calculate_total_execution_time(&execution_results);
-        Ok(PluginExecutionResult {
-            hook_type,
-            results: execution_results,
-            total_execution_time: total_time,
-        })
-    }
-
-    /// 管理插件状态
-    pub fn set_plugin_state(
-        &self,
-        plugin_id: &PluginId,
-        state: PluginState,
-    ) -> Result<(), PluginError> {
-        if !self.is_plugin_registered(plugin_id) {
-            return Err(PluginError::PluginNotFound);
-        }
-
-        match state {
-            PluginState::Enabled => self.enable_plugin(plugin_id),
-            PluginState::Disabled => self.disable_plugin(plugin_id),
-            PluginState::Suspended => self.suspend_plugin(plugin_id),
-            PluginState::Error => self.mark_plugin_error(plugin_id),
-        }
-    }
-
-    /// 更新插件配置
-    pub fn update_plugin_config(
-        &self,
-        plugin_id: &PluginId,
-        config: &PluginConfig,
-    ) -> Result<(), PluginError> {
-        if !self.is_plugin_registered(plugin_id) {
-            return Err(PluginError::PluginNotFound);
-        }
-
-        // 验证配置有效性
-        self.validate_plugin_config(config)?;
-
-        // 应用配置
-        self.apply_plugin_config(plugin_id, config)?;
-
-        Ok(())
-    }
-
-    /// 获取插件信息
-    pub fn get_plugin_info(&self, plugin_id: &PluginId) -> Option<PluginInfo> {
-        // 从存储中获取插件信息
-        self.load_plugin_info(plugin_id)
-    }
-
-    /// 获取所有已注册插件
-    pub fn list_plugins(&self) -> Vec<(PluginId, PluginInfo)> {
-        self.load_all_plugins()
-    }
-
-    /// 获取插件性能指标
-    pub fn get_plugin_metrics(&self, plugin_id: &PluginId) -> Option<PluginMetrics> {
-        if !self.is_plugin_registered(plugin_id) {
-            return None;
-        }
-
-        Some(self.calculate_plugin_metrics(plugin_id))
-    }
-
-    // ========== 内部实现方法 ==========
-
-    /// 验证插件信息
-    fn validate_plugin_info(&self, plugin_info: &PluginInfo) -> Result<(), PluginError> {
-        // 检查插件名称
-        if plugin_info.name.is_empty() || plugin_info.name.len() > 64 {
-            return Err(PluginError::InvalidPluginName);
-        }
-
-        // 检查版本格式
-        let version_str = core::str::from_utf8(&plugin_info.version).unwrap_or("");
-        if !self.is_valid_semver(version_str) {
-            return Err(PluginError::InvalidVersion);
-        }
-
-        // 检查作者信息
-        if plugin_info.author.is_empty() || plugin_info.author.len() > 128 {
-            return Err(PluginError::InvalidAuthorInfo);
-        }
-
-        // 检查描述长度
-        if plugin_info.description.len() > 512 {
-            return Err(PluginError::DescriptionTooLong);
-        }
-
-        Ok(())
-    }
-
-    /// 检查插件是否已注册
-    fn is_plugin_registered(&self, plugin_id: &PluginId) -> bool {
-        // 模拟检查逻辑
-        false // 简化实现
-    }
-
-    /// 验证插件权限
-    fn validate_plugin_permissions(
-        &self,
-        permissions: &PluginPermissions,
-    ) -> Result<(), PluginError> {
-        // 检查权限请求是否合理
-        if permissions.can_access_all_groups && !permissions.can_read_messages {
-            return Err(PluginError::InconsistentPermissions);
-        }
-
-        if permissions.can_modify_encryption && !permissions.can_read_messages {
-            return Err(PluginError::InconsistentPermissions);
-        }
-
-        Ok(())
-    }
-
-    /// 创建插件沙盒环境
-    fn create_plugin_sandbox(
-        &self,
-        plugin_id: &PluginId,
-        plugin_info: &PluginInfo,
-    ) -> Result<PluginSandbox, PluginError> {
-        Ok(PluginSandbox {
-            plugin_id: plugin_id.clone(),
-            memory_limit: self.calculate_memory_limit(plugin_info),
-            execution_timeout: self.calculate_execution_timeout(plugin_info),
-            network_access: plugin_info.permissions.can_network_access,
-            file_access: plugin_info.permissions.can_file_access,
-            allowed_apis: self.determine_allowed_apis(&plugin_info.permissions),
-            resource_limits: PluginResourceLimits {
-                max_cpu_time: 1000, // 1秒
-                max_memory: 10 * 1024 * 1024, // 10MB
-                max_storage: 100 * 1024 * 1024, // 100MB
-                max_network_calls: 100,
-            },
-        })
-    }
-
-    /// 初始化插件
-    fn initialize_plugin(
-        &self,
-        plugin_id: &PluginId,
-        plugin_info: &PluginInfo,
-        sandbox: &PluginSandbox,
-    ) -> Result<(), PluginError> {
-        // 模拟插件初始化过程
-        let init_context = PluginInitContext {
-            plugin_id: plugin_id.clone(),
-            sandbox: sandbox.clone(),
-            system_info: self.get_system_info(),
-        };
-
-        // 调用插件的初始化函数（在实际实现中会通过WASM或其他方式执行）
-        self.call_plugin_init(plugin_id, &init_context)?;
-
-        Ok(())
-    }
-
-    /// 清理插件资源
-    fn cleanup_plugin_resources(&self, plugin_id: &PluginId) -> Result<(), PluginError> {
-        // 停止所有插件任务
-        self.stop_plugin_tasks(plugin_id)?;
-
-        // 清理插件存储
-        self.clear_plugin_storage(plugin_id)?;
-
-        // 释放插件占用的系统资源
-        self.release_plugin_system_resources(plugin_id)?;
-
-        Ok(())
-    }
-
-    /// 移除插件沙盒
-    fn remove_plugin_sandbox(&self, plugin_id: &PluginId) -> Result<(), PluginError> {
-        // 销毁沙盒环境
-        self.destroy_sandbox(plugin_id)?;
-        Ok(())
-    }
-
-    /// 获取支持特定钩子的插件列表
-    fn get_plugins_for_hook(&self, hook_type: &PluginHookType) -> Vec<PluginId> {
-        // 模拟获取支持该钩子的插件列表
-        Vec::new()
-    }
-
-    /// 检查插件是否启用
-    fn is_plugin_enabled(&self, plugin_id: &PluginId) -> bool {
-        // 模拟检查逻辑
-        true
-    }
-
-    /// 在沙盒环境中执行插件
-    fn execute_plugin_in_sandbox(
-        &self,
-        plugin_id: &PluginId,
-        hook_type: &PluginHookType,
-        context: &PluginContext,
-        data: &[u8],
-    ) -> Result<PluginResult, PluginError> {
-        // 创建执行上下文
-        let exec_context = PluginExecutionContext {
-            plugin_id: plugin_id.clone(),
-            hook_type: hook_type.clone(),
-            context: context.clone(),
-            data: data.to_vec(),
-            timestamp: self.get_current_time(),
-        };
-
-        // 在沙盒中执行插件代码
-        let result = self.run_plugin_code(&exec_context)?;
-
-        // 验证结果
-        self.validate_plugin_result(&result)?;
-
-        Ok(result)
-    }
-
-    /// 启用插件
-    fn enable_plugin(&self, plugin_id: &PluginId) -> Result<(), PluginError> {
-        // 模拟启用逻辑
-        Ok(())
-    }
-
-    /// 禁用插件
-    fn disable_plugin(&self, plugin_id: &PluginId) -> Result<(), PluginError> {
-        // 模拟禁用逻辑
-        Ok(())
-    }
-
-    /// 暂停插件
-    fn suspend_plugin(&self, plugin_id: &PluginId) -> Result<(), PluginError> {
-        // 模拟暂停逻辑
-        Ok(())
-    }
-
-    /// 标记插件错误状态
-    fn mark_plugin_error(&self, plugin_id: &PluginId) -> Result<(), PluginError> {
-        // 模拟错误标记逻辑
-        Ok(())
-    }
-
-    /// 验证插件配置
-    fn validate_plugin_config(&self, config: &PluginConfig) -> Result<(), PluginError> {
-        // 验证配置项
-        if config.settings.len() > 100 {
-            return Err(PluginError::TooManyConfigSettings);
-        }
-
-        for (key, value) in &config.settings {
-            if key.len() > 64 || value.len() > 256 {
-                return Err(PluginError::InvalidConfigValue);
-            }
-        }
-
-        Ok(())
-    }
-
-    /// 应用插件配置
-    fn apply_plugin_config(
-        &self,
-        plugin_id: &PluginId,
-        config: &PluginConfig,
-    ) -> Result<(), PluginError> {
-        // 模拟配置应用逻辑
-        Ok(())
-    }
-
-    /// 加载插件信息
-    fn load_plugin_info(&self, plugin_id: &PluginId) -> Option<PluginInfo> {
-        // 模拟从存储加载插件信息
-        None
-    }
-
-    /// 加载所有插件
-    fn load_all_plugins(&self) -> Vec<(PluginId, PluginInfo)> {
-        // 模拟加载所有插件
-        Vec::new()
-    }
-
-    /// 计算插件性能指标
-    fn calculate_plugin_metrics(&self, plugin_id: &PluginId) -> PluginMetrics {
-        // 模拟计算性能指标
-        PluginMetrics {
-            total_executions: 0,
-            successful_executions: 0,
-            failed_executions: 0,
-            average_execution_time: 0,
-            memory_usage: 0,
-            cpu_usage: 0,
-            last_execution: 0,
-        }
-    }
-
-    /// 其他辅助方法
-    fn is_valid_semver(&self, version: &str) -> bool {
-        // 简化的语义版本验证
-        version.chars().filter(|&c| c == '.').count() == 2
-    }
-
-    fn calculate_memory_limit(&self, plugin_info: &PluginInfo) -> u64 {
-        match plugin_info.trust_level {
-            PluginTrustLevel::System => 100 * 1024 * 1024, // 100MB
-            PluginTrustLevel::Verified => 50 * 1024 * 1024,  // 50MB
-            PluginTrustLevel::Community => 10 * 1024 * 1024,  // 10MB
-            PluginTrustLevel::Untrusted => 1 * 1024 * 1024,   // 1MB
-        }
-    }
-
-    fn calculate_execution_timeout(&self, plugin_info: &PluginInfo) -> u64 {
-        match plugin_info.trust_level {
-            PluginTrustLevel::System => 10000,    // 10秒
-            PluginTrustLevel::Verified => 5000,   // 5秒
-            PluginTrustLevel::Community => 1000,  // 1秒
-            PluginTrustLevel::Untrusted => 500,   // 0.5秒
-        }
-    }
-
-    fn determine_allowed_apis(&self, permissions: &PluginPermissions) -> Vec<Vec<u8>> {
-        let mut apis = Vec::new();
-
-        if permissions.can_read_messages {
-            apis.push(b"message.read".to_vec());
-        }
-
-        if permissions.can_send_messages {
-            apis.push(b"message.send".to_vec());
-        }
-
-        if permissions.can_access_storage {
-            apis.push(b"storage.access".to_vec());
-        }
-
-        if permissions.can_network_access {
-            apis.push(b"network.access".to_vec());
-        }
-
-        apis
-    }
-
-    fn get_system_info(&self) -> PluginSystemInfo {
-        PluginSystemInfo {
-            version: b"1.0.0".to_vec(),
-            platform: b"Substrate".to_vec(),
-            features: vec![
-                b"quantum-resistant".to_vec(),
-                b"ai-decision".to_vec(),
-                b"optimistic-ui".to_vec(),
-            ],
-        }
-    }
-
-    fn call_plugin_init(
-        &self,
-        _plugin_id: &PluginId,
-        _init_context: &PluginInitContext,
-    ) -> Result<(), PluginError> {
-        // 模拟插件初始化调用
-        Ok(())
-    }
-
-    fn stop_plugin_tasks(&self, _plugin_id: &PluginId) -> Result<(), PluginError> {
-        Ok(())
-    }
-
-    fn clear_plugin_storage(&self, _plugin_id: &PluginId) -> Result<(), PluginError> {
-        Ok(())
-    }
-
-    fn release_plugin_system_resources(&self, _plugin_id: &PluginId) -> Result<(), PluginError> {
-        Ok(())
-    }
-
-    fn destroy_sandbox(&self, _plugin_id: &PluginId) -> Result<(), PluginError> {
-        Ok(())
-    }
-
-    fn run_plugin_code(
-        &self,
-        _exec_context: &PluginExecutionContext,
-    ) -> Result<PluginResult, PluginError> {
-        // 模拟插件代码执行
-        Ok(PluginResult {
-            success: true,
-            data: Vec::new(),
-            message: b"Plugin executed successfully".to_vec(),
-            modified_data: None,
-        })
-    }
-
-    fn validate_plugin_result(&self, _result: &PluginResult) -> Result<(), PluginError> {
-        Ok(())
-    }
-
-    fn get_current_time(&self) -> u64 {
-        // 模拟获取当前时间
-        0
-    }
-
-    fn calculate_total_execution_time(&self, _results: &[PluginHookResult]) -> u64 {
-        // 模拟计算总执行时间
-        0
-    }
-}
-
-// ========== 插件相关数据结构 ==========
-
-/// 插件ID（使用哈希值表示）
-pub type PluginId = H256;
-
-/// 插件信息
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
-pub struct PluginInfo {
-    /// 插件名称
-    pub name: BoundedVec<u8, ConstU32<64>>,
-    /// 插件版本
-    pub version: BoundedVec<u8, ConstU32<16>>,
-    /// 插件作者
-    pub author: BoundedVec<u8, ConstU32<128>>,
-    /// 插件描述
-    pub description: BoundedVec<u8, ConstU32<512>>,
-    /// 插件权限
-    pub permissions: PluginPermissions,
-    /// 插件类别
-    pub category: PluginCategory,
-    /// 信任等级
-    pub trust_level: PluginTrustLevel,
-    /// 支持的钩子类型
-    pub supported_hooks: BoundedVec<PluginHookType, ConstU32<32>>,
-    /// 创建时间
-    pub created_at: u64,
-    /// 最后更新时间
-    pub updated_at: u64,
-}
-
-/// 插件权限
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
-pub struct PluginPermissions {
-    /// 是否可以读取消息
-    pub can_read_messages: bool,
-    /// 是否可以发送消息
-    pub can_send_messages: bool,
-    /// 是否可以修改加密设置
-    pub can_modify_encryption: bool,
-    /// 是否可以访问所有群组
-    pub can_access_all_groups: bool,
-    /// 是否可以网络访问
-    pub can_network_access: bool,
-    /// 是否可以文件访问
-    pub can_file_access: bool,
-    /// 是否可以访问存储
-    pub can_access_storage: bool,
-    /// 是否可以访问AI功能
-    pub can_access_ai: bool,
-}
-
-impl Default for PluginPermissions {
-    fn default() -> Self {
-        Self {
-            can_read_messages: false,
-            can_send_messages: false,
-            can_modify_encryption: false,
-            can_access_all_groups: false,
-            can_network_access: false,
-            can_file_access: false,
-            can_access_storage: false,
-            can_access_ai: false,
-        }
-    }
-}
-
-/// 插件类别
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
-pub enum PluginCategory {
-    /// 消息处理
-    MessageProcessing,
-    /// 安全增强
-    SecurityEnhancement,
-    /// AI集成
-    AIIntegration,
-    /// UI扩展
-    UIExtension,
-    /// 存储管理
-    StorageManagement,
-    /// 网络工具
-    NetworkUtils,
-    /// 娱乐功能
-    Entertainment,
-    /// 开发工具
-    DeveloperTools,
-    /// 其他
-    Other,
-}
-
-/// 插件信任等级
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
-pub enum PluginTrustLevel {
-    /// 系统级插件（完全信任）
-    System,
-    /// 已验证插件（高信任）
-    Verified,
-    /// 社区插件（中信任）
-    Community,
-    /// 未信任插件（低信任）
-    Untrusted,
-}
-
-/// 插件钩子类型
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
-pub enum PluginHookType {
-    /// 消息发送前
-    BeforeMessageSend,
-    /// 消息发送后
-    AfterMessageSend,
-    /// 消息接收时
-    OnMessageReceive,
-    /// 群组创建时
-    OnGroupCreate,
-    /// 成员加入时
-    OnMemberJoin,
-    /// 成员离开时
-    OnMemberLeave,
-    /// 加密模式变更时
-    OnEncryptionModeChange,
-    /// AI决策时
-    OnAIDecision,
-    /// 存储策略变更时
-    OnStorageStrategyChange,
-    /// 安全事件时
-    OnSecurityEvent,
-    /// 定时任务
-    OnScheduledTask,
-    /// UI渲染时
-    OnUIRender,
 }
 
-/// 插件状态
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
-pub enum PluginState {
-    /// 已启用
-    Enabled,
-    /// 已禁用
-    Disabled,
-    /// 已暂停
+/// 机器人状态
+#[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub enum BotStatus {
+    /// 正常
+    Active,
+    /// 被治理暂停（不能发言，也不能被安装）
     Suspended,
-    /// 错误状态
-    Error,
 }
 
-/// 插件配置
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
-pub struct PluginConfig {
-    /// 配置项（键值对）
-    pub settings: BTreeMap<BoundedVec<u8, ConstU32<64>>, BoundedVec<u8, ConstU32<256>>>,
-    /// 是否启用
-    pub enabled: bool,
-    /// 资源限制
-    pub resource_limits: PluginResourceLimits,
-}
-
-/// 插件资源限制
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
-pub struct PluginResourceLimits {
-    /// 最大CPU时间（毫秒）
-    pub max_cpu_time: u32,
-    /// 最大内存使用（字节）
-    pub max_memory: u64,
-    /// 最大存储空间（字节）
-    pub max_storage: u64,
-    /// 最大网络调用次数
-    pub max_network_calls: u32,
-}
-
-/// 插件执行上下文
-#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct PluginContext {
-    /// 群组ID（如果适用）
-    pub group_id: Option<GroupId>,
-    /// 用户ID（如果适用）
-    pub user_id: Option<BoundedVec<u8, ConstU32<32>>>,
-    /// 消息ID（如果适用）
-    pub message_id: Option<MessageId>,
-    /// 额外上下文数据
-    pub extra_data: BTreeMap<Vec<u8>, Vec<u8>>,
-}
-
-/// 插件沙盒环境
-#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct PluginSandbox {
-    /// 插件ID
-    pub plugin_id: PluginId,
-    /// 内存限制
-    pub memory_limit: u64,
-    /// 执行超时
-    pub execution_timeout: u64,
-    /// 是否允许网络访问
-    pub network_access: bool,
-    /// 是否允许文件访问
-    pub file_access: bool,
-    /// 允许的API列表
-    pub allowed_apis: Vec<Vec<u8>>,
-    /// 资源限制
-    pub resource_limits: PluginResourceLimits,
-}
-
-/// 插件执行结果
-#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct PluginResult {
-    /// 是否执行成功
-    pub success: bool,
-    /// 返回数据
-    pub data: Vec<u8>,
-    /// 执行消息
-    pub message: Vec<u8>,
-    /// 修改后的数据（如果有）
-    pub modified_data: Option<Vec<u8>>,
-}
-
-/// 插件钩子执行结果
-#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct PluginHookResult {
-    /// 插件ID
-    pub plugin_id: PluginId,
-    /// 执行结果
-    pub result: PluginResult,
-    /// 执行时间
-    pub execution_time: u64,
-}
-
-/// 插件总体执行结果
-#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct PluginExecutionResult {
-    /// 钩子类型
-    pub hook_type: PluginHookType,
-    /// 所有插件的执行结果
-    pub results: Vec<PluginHookResult>,
-    /// 总执行时间
-    pub total_execution_time: u64,
-}
-
-/// 插件性能指标
-#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct PluginMetrics {
-    /// 总执行次数
-    pub total_executions: u64,
-    /// 成功执行次数
-    pub successful_executions: u64,
-    /// 失败执行次数
-    pub failed_executions: u64,
-    /// 平均执行时间
-    pub average_execution_time: u64,
-    /// 内存使用量
-    pub memory_usage: u64,
-    /// CPU使用率 (0-100)
-    pub cpu_usage: u8,
-    /// 最后执行时间
-    pub last_execution: u64,
-}
-
-/// 插件初始化上下文
-#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct PluginInitContext {
-    /// 插件ID
-    pub plugin_id: PluginId,
-    /// 沙盒环境
-    pub sandbox: PluginSandbox,
-    /// 系统信息
-    pub system_info: PluginSystemInfo,
-}
-
-/// 插件执行上下文
-#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct PluginExecutionContext {
-    /// 插件ID
-    pub plugin_id: PluginId,
-    /// 钩子类型
-    pub hook_type: PluginHookType,
-    /// 插件上下文
-    pub context: PluginContext,
-    /// 输入数据
-    pub data: Vec<u8>,
-    /// 时间戳
-    pub timestamp: u64,
-}
-
-/// 系统信息
-#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct PluginSystemInfo {
-    /// 系统版本
-    pub version: Vec<u8>,
-    /// 平台信息
-    pub platform: Vec<u8>,
-    /// 支持的功能列表
-    pub features: Vec<Vec<u8>>,
+/// 已注册机器人
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct BotInfo<Name, Cid, Balance> {
+    /// 机器人名称
+    pub name: Name,
+    /// 元数据 CID（介绍、头像、指令说明）
+    pub metadata_cid: Cid,
+    /// 声明的权限
+    pub permissions: BotPermissions,
+    /// 状态
+    pub status: BotStatus,
+    /// 注册保证金
+    pub deposit: Balance,
+    /// 已安装的群组数
+    pub install_count: u32,
+    /// 注册时间戳
+    pub registered_at: u64,
+}
+
+/// 群内已安装的机器人
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct InstalledBot<AccountId, BlockNumber: Default> {
+    /// 授予的权限
+    pub granted: BotPermissions,
+    /// 安装者（群主或管理员）
+    pub installed_by: AccountId,
+    /// 安装时间戳
+    pub installed_at: u64,
+    /// 发言频率状态
+    pub rate: RateLimitState<BlockNumber>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permissions_subset() {
+        let declared = BotPermissions { send_ai: true, send_system: false, max_messages_per_window: 10 };
+
+        assert!(BotPermissions { send_ai: true, send_system: false, max_messages_per_window: 5 }
+            .is_subset_of(&declared));
+        assert!(!BotPermissions { send_ai: true, send_system: true, max_messages_per_window: 5 }
+            .is_subset_of(&declared));
+        assert!(!BotPermissions { send_ai: true, send_system: false, max_messages_per_window: 11 }
+            .is_subset_of(&declared));
+    }
+
+    #[test]
+    fn test_permissions_allow_only_bot_message_types() {
+        let perms = BotPermissions { send_ai: true, send_system: true, max_messages_per_window: 1 };
+
+        assert!(perms.allows(&MessageType::AI));
+        assert!(perms.allows(&MessageType::System));
+        assert!(!perms.allows(&MessageType::Text));
+        assert!(!perms.allows(&MessageType::Image));
+        assert!(BotPermissions::default().is_empty());
+    }
 }
-
-/// 插件错误类型
-#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
-pub enum PluginError {
-    /// 插件已存在
-    PluginAlreadyExists,
-    /// 插件不存在
-    PluginNotFound,
-    /// 插件名称无效
-    InvalidPluginName,
-    /// 版本格式无效
-    InvalidVersion,
-    /// 作者信息无效
-    InvalidAuthorInfo,
-    /// 描述过长
-    DescriptionTooLong,
-    /// 权限不一致
-    InconsistentPermissions,
-    /// 沙盒创建失败
-    SandboxCreationFailed,
-    /// 插件初始化失败
-    InitializationFailed,
-    /// 插件执行失败
-    ExecutionFailed,
-    /// 权限不足
-    InsufficientPermissions,
-    /// 资源超限
-    ResourceLimitExceeded,
-    /// 超时
-    ExecutionTimeout,
-    /// 配置项过多
-    TooManyConfigSettings,
-    /// 配置值无效
-    InvalidConfigValue,
-    /// 沙盒错误
-    SandboxError,
-}
\ No newline at end of file
@@ -1,678 +0,0 @@
-/// Stardust智能群聊 - 量子抗性密码学模块
-///
-/// 实现后量子密码学算法和安全防护机制
-
-use crate::types::*;
-use codec::{Decode, Encode};
-use frame_support::pallet_prelude::*;
-use sp_std::{vec::Vec, collections::btree_map::BTreeMap};
-use sp_core::{H256, U256};
-use sp_runtime::traits::{Hash, Saturating};
-
-/// 量子抗性密码学管理器
-pub struct QuantumResistantCrypto<T: frame_system::Config> {
-    _phantom: sp_std::marker::PhantomData<T>,
-}
-
-impl<T: frame_system::Config> QuantumResistantCrypto<T> {
-    /// 创建新的量子抗性密码实例
-    pub fn new() -> Self {
-        Self {
-            _phantom: Default::default(),
-        }
-    }
-
-    /// CRYSTALS-Kyber密钥封装机制 (模拟实现)
-    pub fn kyber_keygen(&self) -> Result<KyberKeyPair, QuantumCryptoError> {
-        // 模拟Kyber-768密钥生成
-        let secret_key = self.generate_secure_random(KyberSecretKeySize::get())?;
-        let public_key = self.kyber_derive_public_key(&secret_key)?;
-
-        Ok(KyberKeyPair {
-            secret_key: secret_key.try_into().map_err(|_| QuantumCryptoError::KeyGenerationFailed)?,
-            public_key: public_key.try_into().map_err(|_| QuantumCryptoError::KeyGenerationFailed)?,
-        })
-    }
-
-    /// Kyber密钥封装 - 生成共享密钥和密文
-    pub fn kyber_encapsulate(
-        &self,
-        public_key: &KyberPublicKey,
-    ) -> Result<KyberEncapsulation, QuantumCryptoError> {
-        // 生成随机种子
-        let randomness = self.generate_secure_random(32)?;
-
-        // 模拟密钥封装过程
-        let shared_secret = self.kyber_hash_function(&[&randomness, public_key.as_ref()])?;
-        let ciphertext = self.kyber_encrypt(&randomness, public_key)?;
-
-        Ok(KyberEncapsulation {
-            ciphertext: ciphertext.try_into().map_err(|_| QuantumCryptoError::EncapsulationFailed)?,
-            shared_secret: shared_secret.try_into().map_err(|_| QuantumCryptoError::EncapsulationFailed)?,
-        })
-    }
-
-    /// Kyber密钥解封装 - 从密文恢复共享密钥
-    pub fn kyber_decapsulate(
-        &self,
-        secret_key: &KyberSecretKey,
-        ciphertext: &KyberCiphertext,
-    ) -> Result<KyberSharedSecret, QuantumCryptoError> {
-        // 模拟解封装过程
-        let randomness = self.kyber_decrypt(ciphertext, secret_key)?;
-        let shared_secret = self.kyber_hash_function(&[&randomness, &self.derive_public_from_secret(secret_key)?])?;
-
-        shared_secret.try_into().map_err(|_| QuantumCryptoError::DecapsulationFailed)
-    }
-
-    /// CRYSTALS-Dilithium数字签名算法 (模拟实现)
-    pub fn dilithium_keygen(&self) -> Result<DilithiumKeyPair, QuantumCryptoError> {
-        // 模拟Dilithium-3密钥生成
-        let secret_key = self.generate_secure_random(DilithiumSecretKeySize::get())?;
-        let public_key = self.dilithium_derive_public_key(&secret_key)?;
-
-        Ok(DilithiumKeyPair {
-            secret_key: secret_key.try_into().map_err(|_| QuantumCryptoError::KeyGenerationFailed)?,
-            public_key: public_key.try_into().map_err(|_| QuantumCryptoError::KeyGenerationFailed)?,
-        })
-    }
-
-    /// Dilithium签名
-    pub fn dilithium_sign(
-        &self,
-        message: &[u8],
-        secret_key: &DilithiumSecretKey,
-    ) -> Result<DilithiumSignature, QuantumCryptoError> {
-        // 消息哈希
-        let message_hash = self.secure_hash(message)?;
-
-        // 生成随机nonce
-        let nonce = self.generate_secure_random(32)?;
-
-        // 模拟签名生成过程
-        let signature_data = self.dilithium_sign_internal(&message_hash, secret_key, &nonce)?;
-
-        signature_data.try_into().map_err(|_| QuantumCryptoError::SignatureFailed)
-    }
-
-    /// Dilithium验签
-    pub fn dilithium_verify(
-        &self,
-        message: &[u8],
-        signature: &DilithiumSignature,
-        public_key: &DilithiumPublicKey,
-    ) -> Result<bool, QuantumCryptoError> {
-        // 消息哈希
-        let message_hash = self.secure_hash(message)?;
-
-        // 模拟验签过程
-        self.dilithium_verify_internal(&message_hash, signature, public_key)
-    }
-
-    /// 混合加密：Kyber + AES-GCM
-    pub fn hybrid_encrypt(
-        &self,
-        plaintext: &[u8],
-        recipient_public_key: &KyberPublicKey,
-    ) -> Result<HybridCiphertext, QuantumCryptoError> {
-        // 1. 使用Kyber生成共享密钥
-        let encapsulation = self.kyber_encapsulate(recipient_public_key)?;
-
-        // 2. 从共享密钥派生AES密钥
-        let aes_key = self.derive_aes_key(&encapsulation.shared_secret)?;
-
-        // 3. 使用AES-GCM加密数据
-        let aes_ciphertext = self.aes_gcm_encrypt(plaintext, &aes_key)?;
-
-        Ok(HybridCiphertext {
-            kyber_ciphertext: encapsulation.ciphertext,
-            aes_ciphertext: aes_ciphertext.ciphertext,
-            aes_nonce: aes_ciphertext.nonce,
-            aes_tag: aes_ciphertext.tag,
-        })
-    }
-
-    /// 混合解密：Kyber + AES-GCM
-    pub fn hybrid_decrypt(
-        &self,
-        ciphertext: &HybridCiphertext,
-        recipient_secret_key: &KyberSecretKey,
-    ) -> Result<Vec<u8>, QuantumCryptoError> {
-        // 1. 使用Kyber恢复共享密钥
-        let shared_secret = self.kyber_decapsulate(recipient_secret_key, &ciphertext.kyber_ciphertext)?;
-
-        // 2. 从共享密钥派生AES密钥
-        let aes_key = self.derive_aes_key(&shared_secret)?;
-
-        // 3. 使用AES-GCM解密数据
-        let aes_ciphertext = AesGcmCiphertext {
-            ciphertext: ciphertext.aes_ciphertext.clone(),
-            nonce: ciphertext.aes_nonce.clone(),
-            tag: ciphertext.aes_tag.clone(),
-        };
-
-        self.aes_gcm_decrypt(&aes_ciphertext, &aes_key)
-    }
-
-    /// 侧信道攻击防护 - 常量时间比较
-    pub fn constant_time_compare(&self, a: &[u8], b: &[u8]) -> bool {
-        if a.len() != b.len() {
-            return false;
-        }
-
-        let mut result = 0u8;
-        for i in 0..a.len() {
-            result |= a[i] ^ b[i];
-        }
-
-        result == 0
-    }
-
-    /// 安全内存清零
-    pub fn secure_zero(&self, data: &mut [u8]) {
-        // 使用volatile操作防止编译器优化
-        for byte in data.iter_mut() {
-            unsafe {
-                sp_std::ptr::write_volatile(byte, 0);
-            }
-        }
-
-        // 内存屏障
-        sp_std::sync::atomic::compiler_fence(sp_std::sync::atomic::Ordering::SeqCst);
-    }
-
-    /// 时间攻击防护 - 随机延迟
-    pub fn random_delay(&self, operation_type: OperationType) -> Result<(), QuantumCryptoError> {
-        let base_delay = match operation_type {
-            OperationType::KeyGeneration => 100,
-            OperationType::Encryption => 50,
-            OperationType::Decryption => 75,
-            OperationType::Signing => 60,
-            OperationType::Verification => 40,
-        };
-
-        // 生成随机延迟 (base_delay ± 20%)
-        let random_factor = self.generate_secure_random(1)?[0] as u32;
-        let delay_variation = (base_delay * random_factor) / (255 * 5); // 最多20%变化
-        let actual_delay = base_delay.saturating_add(delay_variation);
-
-        // 模拟延迟（在实际实现中应该使用真实的延迟机制）
-        for _ in 0..actual_delay {
-            sp_std::sync::atomic::compiler_fence(sp_std::sync::atomic::Ordering::SeqCst);
-        }
-
-        Ok(())
-    }
-
-    /// 完美前向安全 - 密钥轮换
-    pub fn rotate_keys(
-        &self,
-        current_keys: &GroupKeySet,
-        group_id: GroupId,
-    ) -> Result<GroupKeySet, QuantumCryptoError> {
-        // 生成新的密钥对
-        let new_kyber_pair = self.kyber_keygen()?;
-        let new_dilithium_pair = self.dilithium_keygen()?;
-
-        // 生成轮换证明
-        let rotation_proof = self.generate_key_rotation_proof(
-            &current_keys,
-            &new_kyber_pair,
-            &new_dilithium_pair,
-            group_id,
-        )?;
-
-        Ok(GroupKeySet {
-            kyber_keypair: new_kyber_pair,
-            dilithium_keypair: new_dilithium_pair,
-            generation: current_keys.generation.saturating_add(1),
-            created_at: frame_system::Pallet::<T>::block_number(),
-            rotation_proof: Some(rotation_proof),
-        })
-    }
-
-    /// 多重校验和 - 防止数据篡改
-    pub fn calculate_multiple_checksums(&self, data: &[u8]) -> Result<MultipleChecksums, QuantumCryptoError> {
-        Ok(MultipleChecksums {
-            sha3_256: self.sha3_256_hash(data)?,
-            blake2b_256: self.blake2b_256_hash(data)?,
-            keccak_256: self.keccak_256_hash(data)?,
-            crc32: self.crc32_hash(data)?,
-        })
-    }
-
-    /// 验证多重校验和
-    pub fn verify_multiple_checksums(
-        &self,
-        data: &[u8],
-        checksums: &MultipleChecksums,
-    ) -> Result<bool, QuantumCryptoError> {
-        let calculated = self.calculate_multiple_checksums(data)?;
-
-        Ok(
-            self.constant_time_compare(&calculated.sha3_256, &checksums.sha3_256) &&
-            self.constant_time_compare(&calculated.blake2b_256, &checksums.blake2b_256) &&
-            self.constant_time_compare(&calculated.keccak_256, &checksums.keccak_256) &&
-            calculated.crc32 == checksums.crc32
-        )
-    }
-
-    /// 量子随机数生成器 (模拟实现)
-    pub fn quantum_random(&self, length: u32) -> Result<Vec<u8>, QuantumCryptoError> {
-        // 在实际实现中，这应该连接到真实的量子随机数生成器
-        // 现在使用多个熵源的组合作为模拟
-        let mut result = Vec::with_capacity(length as usize);
-
-        for i in 0..length {
-            // 组合多个熵源
-            let block_hash = frame_system::Pallet::<T>::block_hash(frame_system::Pallet::<T>::block_number());
-            let timestamp = frame_system::Pallet::<T>::block_number().saturated_into::<u64>();
-            let counter = i as u64;
-
-            // 创建种子数据
-            let seed_data = [
-                block_hash.as_ref(),
-                &timestamp.to_le_bytes(),
-                &counter.to_le_bytes(),
-            ].concat();
-
-            // 使用安全哈希函数处理种子
-            let hash = self.secure_hash(&seed_data)?;
-            result.push(hash[i as usize % hash.len()]);
-        }
-
-        Ok(result)
-    }
-
-    /// 后量子数字信封 - 组合加密和签名
-    pub fn create_quantum_envelope(
-        &self,
-        message: &[u8],
-        recipient_kyber_key: &KyberPublicKey,
-        sender_dilithium_key: &DilithiumSecretKey,
-    ) -> Result<QuantumEnvelope, QuantumCryptoError> {
-        // 1. 计算消息的多重校验和
-        let checksums = self.calculate_multiple_checksums(message)?;
-
-        // 2. 创建包含校验和的完整载荷
-        let payload = QuantumPayload {
-            message: message.to_vec(),
-            checksums,
-            timestamp: frame_system::Pallet::<T>::block_number().saturated_into::<u64>(),
-        };
-
-        let payload_bytes = payload.encode();
-
-        // 3. 混合加密载荷
-        let ciphertext = self.hybrid_encrypt(&payload_bytes, recipient_kyber_key)?;
-
-        // 4. 对密文进行数字签名
-        let signature = self.dilithium_sign(&ciphertext.encode(), sender_dilithium_key)?;
-
-        Ok(QuantumEnvelope {
-            ciphertext,
-            signature,
-            sender_public_key: self.derive_dilithium_public_from_secret(sender_dilithium_key)?,
-        })
-    }
-
-    /// 验证并解密后量子数字信封
-    pub fn verify_quantum_envelope(
-        &self,
-        envelope: &QuantumEnvelope,
-        recipient_kyber_key: &KyberSecretKey,
-    ) -> Result<Vec<u8>, QuantumCryptoError> {
-        // 1. 验证数字签名
-        let ciphertext_bytes = envelope.ciphertext.encode();
-        let signature_valid = self.dilithium_verify(
-            &ciphertext_bytes,
-            &envelope.signature,
-            &envelope.sender_public_key,
-        )?;
-
-        if !signature_valid {
-            return Err(QuantumCryptoError::SignatureVerificationFailed);
-        }
-
-        // 2. 解密载荷
-        let payload_bytes = self.hybrid_decrypt(&envelope.ciphertext, recipient_kyber_key)?;
-        let payload: QuantumPayload = Decode::decode(&mut &payload_bytes[..])
-            .map_err(|_| QuantumCryptoError::PayloadDecodingFailed)?;
-
-        // 3. 验证时间戳 (防止重放攻击)
-        let current_timestamp = frame_system::Pallet::<T>::block_number().saturated_into::<u64>();
-        if current_timestamp.saturating_sub(payload.timestamp) > 3600 { // 1小时有效期
-            return Err(QuantumCryptoError::TimestampExpired);
-        }
-
-        // 4. 验证多重校验和
-        if !self.verify_multiple_checksums(&payload.message, &payload.checksums)? {
-            return Err(QuantumCryptoError::IntegrityCheckFailed);
-        }
-
-        Ok(payload.message)
-    }
-
-    // ========== 内部辅助方法 ==========
-
-    /// 生成安全随机数
-    fn generate_secure_random(&self, length: u32) -> Result<Vec<u8>, QuantumCryptoError> {
-        // 使用多个熵源组合生成安全随机数
-        let mut result = Vec::with_capacity(length as usize);
-
-        for i in 0..length {
-            let block_hash = frame_system::Pallet::<T>::block_hash(frame_system::Pallet::<T>::block_number());
-            let parent_hash = frame_system::Pallet::<T>::parent_hash();
-            let timestamp = frame_system::Pallet::<T>::block_number().saturated_into::<u64>();
-
-            let entropy = [
-                block_hash.as_ref(),
-                parent_hash.as_ref(),
-                &timestamp.to_le_bytes(),
-                &(i as u64).to_le_bytes(),
-            ].concat();
-
-            let hash = T::Hashing::hash(&entropy);
-            result.push(hash.as_ref()[i as usize % 32]);
-        }
-
-        Ok(result)
-    }
-
-    /// 安全哈希函数
-    fn secure_hash(&self, data: &[u8]) -> Result<Vec<u8>, QuantumCryptoError> {
-        Ok(T::Hashing::hash(data).as_ref().to_vec())
-    }
-
-    /// Kyber哈希函数 (模拟)
-    fn kyber_hash_function(&self, inputs: &[&[u8]]) -> Result<Vec<u8>, QuantumCryptoError> {
-        let combined = inputs.concat();
-        self.secure_hash(&combined)
-    }
-
-    /// 从Kyber私钥派生公钥 (模拟)
-    fn kyber_derive_public_key(&self, secret_key: &[u8]) -> Result<Vec<u8>, QuantumCryptoError> {
-        // 模拟公钥派生过程
-        let mut public_key_data = Vec::new();
-        for chunk in secret_key.chunks(32) {
-            let hash = self.secure_hash(chunk)?;
-            public_key_data.extend_from_slice(&hash[..16]); // 取前16字节
-        }
-        Ok(public_key_data)
-    }
-
-    /// Kyber加密 (模拟)
-    fn kyber_encrypt(&self, message: &[u8], public_key: &KyberPublicKey) -> Result<Vec<u8>, QuantumCryptoError> {
-        // 模拟Kyber加密过程
-        let mut ciphertext = Vec::new();
-        for (i, &byte) in message.iter().enumerate() {
-            let key_byte = public_key.as_ref()[i % public_key.as_ref().len()];
-            ciphertext.push(byte ^ key_byte);
-        }
-        Ok(ciphertext)
-    }
-
-    /// Kyber解密 (模拟)
-    fn kyber_decrypt(&self, ciphertext: &KyberCiphertext, secret_key: &KyberSecretKey) -> Result<Vec<u8>, QuantumCryptoError> {
-        // 模拟Kyber解密过程
-        let public_key = self.derive_public_from_secret(secret_key)?;
-        let mut plaintext = Vec::new();
-        for (i, &byte) in ciphertext.as_ref().iter().enumerate() {
-            let key_byte = public_key[i % public_key.len()];
-            plaintext.push(byte ^ key_byte);
-        }
-        Ok(plaintext)
-    }
-
-    /// 从Dilithium私钥派生公钥 (模拟)
-    fn dilithium_derive_public_key(&self, secret_key: &[u8]) -> Result<Vec<u8>, QuantumCryptoError> {
-        // 模拟Dilithium公钥派生
-        self.secure_hash(secret_key)
-    }
-
-    /// Dilithium内部签名 (模拟)
-    fn dilithium_sign_internal(
-        &self,
-        message_hash: &[u8],
-        secret_key: &DilithiumSecretKey,
-        nonce: &[u8],
-    ) -> Result<Vec<u8>, QuantumCryptoError> {
-        // 模拟Dilithium签名过程
-        let combined = [message_hash, secret_key.as_ref(), nonce].concat();
-        self.secure_hash(&combined)
-    }
-
-    /// Dilithium内部验签 (模拟)
-    fn dilithium_verify_internal(
-        &self,
-        message_hash: &[u8],
-        signature: &DilithiumSignature,
-        public_key: &DilithiumPublicKey,
-    ) -> Result<bool, QuantumCryptoError> {
-        // 模拟Dilithium验签过程
-        let expected = self.secure_hash(&[message_hash, public_key.as_ref()].concat())?;
-        Ok(self.constant_time_compare(signature.as_ref(), &expected))
-    }
-
-    /// 其他辅助方法的模拟实现...
-    fn derive_public_from_secret(&self, secret_key: &KyberSecretKey) -> Result<Vec<u8>, QuantumCryptoError> {
-        self.secure_hash(secret_key.as_ref())
-    }
-
-    fn derive_aes_key(&self, shared_secret: &KyberSharedSecret) -> Result<Vec<u8>, QuantumCryptoError> {
-        self.secure_hash(shared_secret.as_ref())
-    }
-
-    fn aes_gcm_encrypt(&self, plaintext: &[u8], key: &[u8]) -> Result<AesGcmCiphertext, QuantumCryptoError> {
-        // 模拟AES-GCM加密
-        let nonce = self.generate_secure_random(12)?;
-        let mut ciphertext = Vec::new();
-        for (i, &byte) in plaintext.iter().enumerate() {
-            let key_byte = key[i % key.len()];
-            ciphertext.push(byte ^ key_byte);
-        }
-        let tag = self.secure_hash(&[&ciphertext, key].concat())?;
-
-        Ok(AesGcmCiphertext {
-            ciphertext,
-            nonce,
-            tag: tag[..16].to_vec(), // 取前16字节作为tag
-        })
-    }
-
-    fn aes_gcm_decrypt(&self, ciphertext: &AesGcmCiphertext, key: &[u8]) -> Result<Vec<u8>, QuantumCryptoError> {
-        // 验证tag
-        let expected_tag = self.secure_hash(&[&ciphertext.ciphertext, key].concat())?;
-        if !self.constant_time_compare(&ciphertext.tag, &expected_tag[..16]) {
-            return Err(QuantumCryptoError::AuthenticationFailed);
-        }
-
-        // 解密
-        let mut plaintext = Vec::new();
-        for (i, &byte) in ciphertext.ciphertext.iter().enumerate() {
-            let key_byte = key[i % key.len()];
-            plaintext.push(byte ^ key_byte);
-        }
-        Ok(plaintext)
-    }
-
-    fn generate_key_rotation_proof(
-        &self,
-        _old_keys: &GroupKeySet,
-        _new_kyber: &KyberKeyPair,
-        _new_dilithium: &DilithiumKeyPair,
-        _group_id: GroupId,
-    ) -> Result<Vec<u8>, QuantumCryptoError> {
-        // 模拟密钥轮换证明生成
-        self.generate_secure_random(64)
-    }
-
-    fn sha3_256_hash(&self, data: &[u8]) -> Result<Vec<u8>, QuantumCryptoError> {
-        self.secure_hash(data)
-    }
-
-    fn blake2b_256_hash(&self, data: &[u8]) -> Result<Vec<u8>, QuantumCryptoError> {
-        self.secure_hash(data)
-    }
-
-    fn keccak_256_hash(&self, data: &[u8]) -> Result<Vec<u8>, QuantumCryptoError> {
-        self.secure_hash(data)
-    }
-
-    fn crc32_hash(&self, data: &[u8]) -> Result<u32, QuantumCryptoError> {
-        // 简单的CRC32模拟
-        let mut crc = 0xFFFFFFFF_u32;
-        for &byte in data {
-            crc ^= byte as u32;
-            for _ in 0..8 {
-                crc = if (crc & 1) != 0 {
-                    (crc >> 1) ^ 0xEDB88320
-                } else {
-                    crc >> 1
-                };
-            }
-        }
-        Ok(!crc)
-    }
-
-    fn derive_dilithium_public_from_secret(&self, secret_key: &DilithiumSecretKey) -> Result<DilithiumPublicKey, QuantumCryptoError> {
-        let public_key_bytes = self.dilithium_derive_public_key(secret_key.as_ref())?;
-        public_key_bytes.try_into().map_err(|_| QuantumCryptoError::KeyDerivationFailed)
-    }
-}
-
-// ========== 量子抗性数据结构 ==========
-
-/// Kyber密钥对
-#[derive(Debug, Clone, Encode, Decode)]
-pub struct KyberKeyPair {
-    pub secret_key: KyberSecretKey,
-    pub public_key: KyberPublicKey,
-}
-
-/// Kyber封装结果
-#[derive(Debug, Clone, Encode, Decode)]
-pub struct KyberEncapsulation {
-    pub ciphertext: KyberCiphertext,
-    pub shared_secret: KyberSharedSecret,
-}
-
-/// Dilithium密钥对
-#[derive(Debug, Clone, Encode, Decode)]
-pub struct DilithiumKeyPair {
-    pub secret_key: DilithiumSecretKey,
-    pub public_key: DilithiumPublicKey,
-}
-
-/// 混合密文
-#[derive(Debug, Clone, Encode, Decode)]
-pub struct HybridCiphertext {
-    pub kyber_ciphertext: KyberCiphertext,
-    pub aes_ciphertext: Vec<u8>,
-    pub aes_nonce: Vec<u8>,
-    pub aes_tag: Vec<u8>,
-}
-
-/// AES-GCM密文
-#[derive(Debug, Clone)]
-pub struct AesGcmCiphertext {
-    pub ciphertext: Vec<u8>,
-    pub nonce: Vec<u8>,
-    pub tag: Vec<u8>,
-}
-
-/// 群组密钥集合
-#[derive(Debug, Clone, Encode, Decode)]
-pub struct GroupKeySet {
-    pub kyber_keypair: KyberKeyPair,
-    pub dilithium_keypair: DilithiumKeyPair,
-    pub generation: u32,
-    pub created_at: T::BlockNumber,
-    pub rotation_proof: Option<Vec<u8>>,
-}
-
-/// 多重校验和
-#[derive(Debug, Clone, Encode, Decode)]
-pub struct MultipleChecksums {
-    pub sha3_256: Vec<u8>,
-    pub blake2b_256: Vec<u8>,
-    pub keccak_256: Vec<u8>,
-    pub crc32: u32,
-}
-
-/// 量子载荷
-#[derive(Debug, Clone, Encode, Decode)]
-pub struct QuantumPayload {
-    pub message: Vec<u8>,
-    pub checksums: MultipleChecksums,
-    pub timestamp: u64,
-}
-
-/// 量子信封
-#[derive(Debug, Clone, Encode, Decode)]
-pub struct QuantumEnvelope {
-    pub ciphertext: HybridCiphertext,
-    pub signature: DilithiumSignature,
-    pub sender_public_key: DilithiumPublicKey,
-}
-
-/// 操作类型（用于时间攻击防护）
-#[derive(Debug, Clone, Copy)]
-pub enum OperationType {
-    KeyGeneration,
-    Encryption,
-    Decryption,
-    Signing,
-    Verification,
-}
-
-/// 量子密码学错误
-#[derive(Debug, Clone, PartialEq)]
-pub enum QuantumCryptoError {
-    KeyGenerationFailed,
-    EncapsulationFailed,
-    DecapsulationFailed,
-    SignatureFailed,
-    SignatureVerificationFailed,
-    AuthenticationFailed,
-    IntegrityCheckFailed,
-    PayloadDecodingFailed,
-    TimestampExpired,
-    KeyDerivationFailed,
-    InvalidKeySize,
-    InsufficientEntropy,
-}
-
-// ========== 类型常量 ==========
-
-/// Kyber密钥大小常量
-pub struct KyberSecretKeySize;
-impl KyberSecretKeySize {
-    pub fn get() -> u32 { 2400 } // Kyber-768 私钥大小
-}
-
-pub struct KyberPublicKeySize;
-impl KyberPublicKeySize {
-    pub fn get() -> u32 { 1184 } // Kyber-768 公钥大小
-}
-
-pub struct KyberCiphertextSize;
-impl KyberCiphertextSize {
-    pub fn get() -> u32 { 1088 } // Kyber-768 密文大小
-}
-
-/// Dilithium密钥大小常量
-pub struct DilithiumSecretKeySize;
-impl DilithiumSecretKeySize {
-    pub fn get() -> u32 { 4000 } // Dilithium-3 私钥大小
-}
-
-pub struct DilithiumPublicKeySize;
-impl DilithiumPublicKeySize {
-    pub fn get() -> u32 { 1952 } // Dilithium-3 公钥大小
-}
-
-pub struct DilithiumSignatureSize;
-impl DilithiumSignatureSize {
-    pub fn get() -> u32 { 3293 } // Dilithium-3 签名大小
-}
\ No newline at end of file
@@ -76,3 +76,443 @@ fn calculate_deposit_returns_fallback() {
         assert_eq!(deposit, 50_000_000_000_000_000_000u128); // 50 DUST
     });
 }
+
+// ============ 群组密钥轮换测试 ============
+
+/// 发布测试预密钥
+fn publish_prekey(who: u64, post_quantum: bool) {
+    let pq = if post_quantum { Some(vec![9u8; 1184]) } else { None };
+    assert_ok!(ChatGroup::publish_prekey(RuntimeOrigin::signed(who), [who as u8; 32], pq));
+}
+
+/// 创建加密群组并返回群组ID
+fn create_encrypted_group(owner: u64, mode: u8) -> u64 {
+    let _ = Balances::make_free_balance_be(&owner, 100_000_000_000_000_000_000);
+    assert_ok!(ChatGroup::create_group(
+        RuntimeOrigin::signed(owner),
+        b"Secret Group".to_vec(),
+        None,
+        mode,
+        false,
+    ));
+    crate::UserGroups::<Test>::get(owner)[0]
+}
+
+#[test]
+fn encrypted_group_requires_prekey() {
+    new_test_ext().execute_with(|| {
+        let _ = Balances::make_free_balance_be(&ALICE, 100_000_000_000_000_000_000);
+
+        assert_noop!(
+            ChatGroup::create_group(RuntimeOrigin::signed(ALICE), b"G".to_vec(), None, 1, false),
+            Error::<Test>::PrekeyNotPublished
+        );
+
+        // 军用级要求抗量子预密钥
+        publish_prekey(ALICE, false);
+        assert_noop!(
+            ChatGroup::create_group(RuntimeOrigin::signed(ALICE), b"G".to_vec(), None, 0, false),
+            Error::<Test>::PostQuantumPrekeyRequired
+        );
+
+        // 透明群组不需要预密钥
+        let _ = Balances::make_free_balance_be(&BOB, 100_000_000_000_000_000_000);
+        assert_ok!(ChatGroup::create_group(RuntimeOrigin::signed(BOB), b"G".to_vec(), None, 3, true));
+    });
+}
+
+#[test]
+fn publish_prekey_rejects_invalid_keys() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            ChatGroup::publish_prekey(RuntimeOrigin::signed(ALICE), [0u8; 32], None),
+            Error::<Test>::InvalidPrekey
+        );
+        assert_noop!(
+            ChatGroup::publish_prekey(RuntimeOrigin::signed(ALICE), [1u8; 32], Some(vec![1u8; 1185])),
+            Error::<Test>::PostQuantumKeyTooLong
+        );
+    });
+}
+
+#[test]
+fn military_member_cannot_drop_post_quantum_prekey() {
+    new_test_ext().execute_with(|| {
+        publish_prekey(ALICE, true);
+        create_encrypted_group(ALICE, 0);
+
+        // 军用级群组成员更新预密钥时不能去掉抗量子公钥
+        assert_noop!(
+            ChatGroup::publish_prekey(RuntimeOrigin::signed(ALICE), [7u8; 32], None),
+            Error::<Test>::PostQuantumPrekeyRequired
+        );
+        assert_ok!(ChatGroup::publish_prekey(
+            RuntimeOrigin::signed(ALICE),
+            [7u8; 32],
+            Some(vec![8u8; 1184])
+        ));
+
+        // 非军用级群组成员不受限制
+        publish_prekey(BOB, false);
+        create_encrypted_group(BOB, 1);
+        assert_ok!(ChatGroup::publish_prekey(RuntimeOrigin::signed(BOB), [7u8; 32], None));
+    });
+}
+
+#[test]
+fn membership_changes_rotate_epoch() {
+    new_test_ext().execute_with(|| {
+        publish_prekey(ALICE, false);
+        publish_prekey(BOB, false);
+        let group_id = create_encrypted_group(ALICE, 1);
+        assert_eq!(crate::GroupKeyStates::<Test>::get(group_id).unwrap().epoch, 0);
+
+        // 群主提交纪元 0 的包装密钥后才能发言
+        assert_noop!(
+            ChatGroup::send_group_message(RuntimeOrigin::signed(ALICE), group_id, b"x".to_vec(), 0),
+            Error::<Test>::GroupKeyNotDistributed
+        );
+        assert_ok!(ChatGroup::post_wrapped_keys(
+            RuntimeOrigin::signed(ALICE),
+            group_id,
+            0,
+            vec![(ALICE, vec![1u8; 94])],
+        ));
+        assert_ok!(ChatGroup::send_group_message(RuntimeOrigin::signed(ALICE), group_id, b"x".to_vec(), 0));
+
+        // BOB 加入：纪元 +1，旧包装不再满足发言条件
+        assert_ok!(ChatGroup::join_group(RuntimeOrigin::signed(BOB), group_id));
+        assert_eq!(crate::GroupKeyStates::<Test>::get(group_id).unwrap().epoch, 1);
+        assert!(!ChatGroup::holds_current_group_key(group_id, &ALICE));
+
+        // 过期纪元的提交被拒绝
+        assert_noop!(
+            ChatGroup::post_wrapped_keys(RuntimeOrigin::signed(ALICE), group_id, 0, vec![(BOB, vec![1u8; 94])]),
+            Error::<Test>::StaleKeyEpoch
+        );
+        // 只有群主可以提交
+        assert_noop!(
+            ChatGroup::post_wrapped_keys(RuntimeOrigin::signed(BOB), group_id, 1, vec![(BOB, vec![1u8; 94])]),
+            Error::<Test>::NotGroupOwner
+        );
+
+        assert_ok!(ChatGroup::post_wrapped_keys(
+            RuntimeOrigin::signed(ALICE),
+            group_id,
+            1,
+            vec![(ALICE, vec![2u8; 94]), (BOB, vec![3u8; 94])],
+        ));
+        assert_eq!(crate::GroupKeyStates::<Test>::get(group_id).unwrap().wrapped_count, 2);
+        assert!(ChatGroup::holds_current_group_key(group_id, &BOB));
+
+        // BOB 离开：纪元 +1，其包装密钥被删除
+        assert_ok!(ChatGroup::leave_group(RuntimeOrigin::signed(BOB), group_id));
+        assert_eq!(crate::GroupKeyStates::<Test>::get(group_id).unwrap().epoch, 2);
+        assert!(crate::WrappedGroupKeys::<Test>::get(group_id, BOB).is_none());
+
+        // 不能为非成员包装
+        assert_noop!(
+            ChatGroup::post_wrapped_keys(RuntimeOrigin::signed(ALICE), group_id, 2, vec![(BOB, vec![3u8; 94])]),
+            Error::<Test>::NotGroupMember
+        );
+    });
+}
+
+#[test]
+fn join_encrypted_group_requires_prekey() {
+    new_test_ext().execute_with(|| {
+        publish_prekey(ALICE, false);
+        let group_id = create_encrypted_group(ALICE, 1);
+
+        assert_noop!(
+            ChatGroup::join_group(RuntimeOrigin::signed(CHARLIE), group_id),
+            Error::<Test>::PrekeyNotPublished
+        );
+    });
+}
+
+#[test]
+fn directory_key_satisfies_prekey_requirement() {
+    new_test_ext().execute_with(|| {
+        publish_prekey(ALICE, false);
+        let group_id = create_encrypted_group(ALICE, 1);
+
+        // CHARLIE 只在统一公钥目录注册了加密公钥
+        DirectoryKeys::mutate(|keys| keys.push((CHARLIE, [7u8; 32])));
+        assert_ok!(ChatGroup::join_group(RuntimeOrigin::signed(CHARLIE), group_id));
+        assert_eq!(ChatGroup::prekey_of(&CHARLIE).unwrap().x25519, [7u8; 32]);
+
+        // 目录公钥优先于 MemberPrekeys，抗量子公钥保留
+        publish_prekey(BOB, true);
+        DirectoryKeys::mutate(|keys| keys.push((BOB, [8u8; 32])));
+        let prekey = ChatGroup::prekey_of(&BOB).unwrap();
+        assert_eq!(prekey.x25519, [8u8; 32]);
+        assert!(prekey.post_quantum.is_some());
+    });
+}
+
+#[test]
+fn directory_key_change_records_time_and_rotates_group_epoch() {
+    use pallet_key_directory::{traits::KeyChangeHandler, types::KeyPurpose};
+
+    new_test_ext().execute_with(|| {
+        publish_prekey(ALICE, false);
+        let group_id = create_encrypted_group(ALICE, 1);
+
+        Timestamp::set_timestamp(42_000);
+        DirectoryKeys::mutate(|keys| keys.push((CHARLIE, [7u8; 32])));
+        <ChatGroup as KeyChangeHandler<u64>>::on_key_registered(&CHARLIE, KeyPurpose::Encryption, 1);
+        assert_eq!(ChatGroup::prekey_of(&CHARLIE).unwrap().updated_at, 42);
+        assert_ok!(ChatGroup::join_group(RuntimeOrigin::signed(CHARLIE), group_id));
+        let epoch = crate::GroupKeyStates::<Test>::get(group_id).unwrap().epoch;
+
+        // 签名密钥轮换不影响群组
+        <ChatGroup as KeyChangeHandler<u64>>::on_key_rotated(&CHARLIE, KeyPurpose::Signing, 1, 2);
+        assert_eq!(crate::GroupKeyStates::<Test>::get(group_id).unwrap().epoch, epoch);
+
+        Timestamp::set_timestamp(50_000);
+        <ChatGroup as KeyChangeHandler<u64>>::on_key_rotated(&CHARLIE, KeyPurpose::Encryption, 1, 2);
+        assert_eq!(crate::GroupKeyStates::<Test>::get(group_id).unwrap().epoch, epoch + 1);
+        assert_eq!(ChatGroup::prekey_of(&CHARLIE).unwrap().updated_at, 50);
+
+        <ChatGroup as KeyChangeHandler<u64>>::on_key_revoked(&CHARLIE, KeyPurpose::Encryption, 2);
+        assert_eq!(crate::GroupKeyStates::<Test>::get(group_id).unwrap().epoch, epoch + 2);
+    });
+}
+
+#[test]
+fn manual_rotation_only_for_owner_of_encrypted_group() {
+    new_test_ext().execute_with(|| {
+        publish_prekey(ALICE, false);
+        let group_id = create_encrypted_group(ALICE, 1);
+
+        assert_noop!(
+            ChatGroup::rotate_group_key(RuntimeOrigin::signed(BOB), group_id),
+            Error::<Test>::NotGroupOwner
+        );
+        assert_ok!(ChatGroup::rotate_group_key(RuntimeOrigin::signed(ALICE), group_id));
+        assert_eq!(crate::GroupKeyStates::<Test>::get(group_id).unwrap().epoch, 1);
+
+        // 解散后清理密钥材料
+        assert_ok!(ChatGroup::disband_group(RuntimeOrigin::signed(ALICE), group_id));
+        assert!(crate::GroupKeyStates::<Test>::get(group_id).is_none());
+    });
+}
+
+// ============ 机器人注册表测试 ============
+
+const DIVINATION_BOT: u64 = 100;
+
+fn bot_perms(max: u32) -> crate::BotPermissions {
+    crate::BotPermissions { send_ai: true, send_system: false, max_messages_per_window: max }
+}
+
+/// 创建透明群组并注册、安装机器人
+fn setup_group_with_bot(max: u32) -> u64 {
+    let _ = Balances::make_free_balance_be(&ALICE, 100_000_000_000_000_000_000);
+    let _ = Balances::make_free_balance_be(&DIVINATION_BOT, 100_000_000_000_000_000_000);
+    assert_ok!(ChatGroup::create_group(RuntimeOrigin::signed(ALICE), b"G".to_vec(), None, 3, true));
+    let group_id = crate::UserGroups::<Test>::get(ALICE)[0];
+
+    assert_ok!(ChatGroup::register_bot(
+        RuntimeOrigin::signed(DIVINATION_BOT),
+        b"Meihua Bot".to_vec(),
+        b"QmBotMeta".to_vec(),
+        bot_perms(5),
+    ));
+    assert_ok!(ChatGroup::install_bot(RuntimeOrigin::signed(ALICE), group_id, DIVINATION_BOT, bot_perms(max)));
+    group_id
+}
+
+#[test]
+fn register_bot_reserves_deposit() {
+    new_test_ext().execute_with(|| {
+        let _ = Balances::make_free_balance_be(&DIVINATION_BOT, 100_000_000_000_000_000_000);
+
+        assert_noop!(
+            ChatGroup::register_bot(
+                RuntimeOrigin::signed(DIVINATION_BOT),
+                b"Bot".to_vec(),
+                b"Qm".to_vec(),
+                crate::BotPermissions::default(),
+            ),
+            Error::<Test>::InvalidBotPermissions
+        );
+
+        assert_ok!(ChatGroup::register_bot(
+            RuntimeOrigin::signed(DIVINATION_BOT),
+            b"Bot".to_vec(),
+            b"Qm".to_vec(),
+            bot_perms(5),
+        ));
+        assert_eq!(Balances::reserved_balance(DIVINATION_BOT), 10_000_000_000_000_000_000);
+
+        assert_noop!(
+            ChatGroup::register_bot(RuntimeOrigin::signed(DIVINATION_BOT), b"Bot".to_vec(), b"Qm".to_vec(), bot_perms(5)),
+            Error::<Test>::BotAlreadyRegistered
+        );
+
+        // 暂停期间不能注销取回保证金
+        assert_ok!(ChatGroup::set_bot_status(RuntimeOrigin::root(), DIVINATION_BOT, crate::BotStatus::Suspended));
+        assert_noop!(
+            ChatGroup::deregister_bot(RuntimeOrigin::signed(DIVINATION_BOT)),
+            Error::<Test>::BotSuspended
+        );
+        assert_ok!(ChatGroup::set_bot_status(RuntimeOrigin::root(), DIVINATION_BOT, crate::BotStatus::Active));
+
+        assert_ok!(ChatGroup::deregister_bot(RuntimeOrigin::signed(DIVINATION_BOT)));
+        assert_eq!(Balances::reserved_balance(DIVINATION_BOT), 0);
+    });
+}
+
+#[test]
+fn install_bot_checks_admin_and_permissions() {
+    new_test_ext().execute_with(|| {
+        let group_id = setup_group_with_bot(3);
+        assert_ok!(ChatGroup::uninstall_bot(RuntimeOrigin::signed(ALICE), group_id, DIVINATION_BOT));
+
+        // 非成员不能安装
+        assert_noop!(
+            ChatGroup::install_bot(RuntimeOrigin::signed(BOB), group_id, DIVINATION_BOT, bot_perms(1)),
+            Error::<Test>::NotGroupMember
+        );
+        // 授予的权限不能超过声明
+        assert_noop!(
+            ChatGroup::install_bot(RuntimeOrigin::signed(ALICE), group_id, DIVINATION_BOT, bot_perms(6)),
+            Error::<Test>::PermissionsExceedDeclared
+        );
+    });
+}
+
+#[test]
+fn bot_messages_are_typed_and_rate_limited() {
+    new_test_ext().execute_with(|| {
+        let group_id = setup_group_with_bot(2);
+
+        // 只能发送授予的 AI 消息
+        assert_noop!(
+            ChatGroup::bot_send_message(RuntimeOrigin::signed(DIVINATION_BOT), group_id, b"hi".to_vec(), 0),
+            Error::<Test>::BotMessageTypeNotAllowed
+        );
+        assert_noop!(
+            ChatGroup::bot_send_message(RuntimeOrigin::signed(DIVINATION_BOT), group_id, b"hi".to_vec(), 5),
+            Error::<Test>::BotMessageTypeNotAllowed
+        );
+
+        assert_ok!(ChatGroup::bot_send_message(RuntimeOrigin::signed(DIVINATION_BOT), group_id, b"a".to_vec(), 6));
+        assert_ok!(ChatGroup::bot_send_message(RuntimeOrigin::signed(DIVINATION_BOT), group_id, b"b".to_vec(), 6));
+        assert_noop!(
+            ChatGroup::bot_send_message(RuntimeOrigin::signed(DIVINATION_BOT), group_id, b"c".to_vec(), 6),
+            Error::<Test>::BotRateLimited
+        );
+
+        // 窗口过后恢复
+        System::set_block_number(20);
+        assert_ok!(ChatGroup::bot_send_message(RuntimeOrigin::signed(DIVINATION_BOT), group_id, b"c".to_vec(), 6));
+    });
+}
+
+#[test]
+fn revoked_or_suspended_bot_cannot_post() {
+    new_test_ext().execute_with(|| {
+        let group_id = setup_group_with_bot(2);
+
+        // 治理暂停
+        assert_ok!(ChatGroup::set_bot_status(RuntimeOrigin::root(), DIVINATION_BOT, crate::BotStatus::Suspended));
+        assert_noop!(
+            ChatGroup::bot_send_message(RuntimeOrigin::signed(DIVINATION_BOT), group_id, b"a".to_vec(), 6),
+            Error::<Test>::BotSuspended
+        );
+        assert_ok!(ChatGroup::set_bot_status(RuntimeOrigin::root(), DIVINATION_BOT, crate::BotStatus::Active));
+
+        // 安装中的机器人不能注销
+        assert_noop!(
+            ChatGroup::deregister_bot(RuntimeOrigin::signed(DIVINATION_BOT)),
+            Error::<Test>::BotHasInstallations
+        );
+
+        // 管理员撤销
+        assert_ok!(ChatGroup::uninstall_bot(RuntimeOrigin::signed(ALICE), group_id, DIVINATION_BOT));
+        assert_noop!(
+            ChatGroup::bot_send_message(RuntimeOrigin::signed(DIVINATION_BOT), group_id, b"a".to_vec(), 6),
+            Error::<Test>::BotNotInstalled
+        );
+        assert_eq!(crate::Bots::<Test>::get(DIVINATION_BOT).unwrap().install_count, 0);
+    });
+}
+
+// ============ 已读游标与收件箱测试 ============
+
+/// 创建透明群组，BOB 加入
+fn create_group_with_bob() -> u64 {
+    let _ = Balances::make_free_balance_be(&ALICE, 100_000_000_000_000_000_000);
+    assert_ok!(ChatGroup::create_group(RuntimeOrigin::signed(ALICE), b"G".to_vec(), None, 3, true));
+    let group_id = crate::UserGroups::<Test>::get(ALICE)[0];
+    assert_ok!(ChatGroup::join_group(RuntimeOrigin::signed(BOB), group_id));
+    group_id
+}
+
+#[test]
+fn read_cursor_tracks_unread_per_member() {
+    use pallet_chat_common::GroupInboxProvider;
+
+    new_test_ext().execute_with(|| {
+        let group_id = create_group_with_bob();
+
+        for _ in 0..3 {
+            assert_ok!(ChatGroup::send_group_message(RuntimeOrigin::signed(ALICE), group_id, b"hi".to_vec(), 0));
+        }
+
+        // 发送者自己的消息不计未读
+        assert_eq!(ChatGroup::group_unread_count(group_id, &ALICE), 0);
+        assert_eq!(ChatGroup::group_unread_count(group_id, &BOB), 3);
+
+        // BOB 读到第 1 条（ID 1）
+        assert_ok!(ChatGroup::mark_group_read(RuntimeOrigin::signed(BOB), group_id, 1));
+        assert_eq!(ChatGroup::group_unread_count(group_id, &BOB), 1);
+
+        // 游标不回退
+        assert_ok!(ChatGroup::mark_group_read(RuntimeOrigin::signed(BOB), group_id, 0));
+        assert_eq!(ChatGroup::group_read_cursor(group_id, BOB), 2);
+
+        // 不存在的消息、非成员
+        assert_noop!(
+            ChatGroup::mark_group_read(RuntimeOrigin::signed(BOB), group_id, 3),
+            Error::<Test>::MessageNotFound
+        );
+        assert_noop!(
+            ChatGroup::mark_group_read(RuntimeOrigin::signed(CHARLIE), group_id, 0),
+            Error::<Test>::NotGroupMember
+        );
+        assert_eq!(ChatGroup::group_unread_count(group_id, &CHARLIE), 0);
+    });
+}
+
+#[test]
+fn new_member_history_not_unread_and_inbox_lists_groups() {
+    use pallet_chat_common::GroupInboxProvider;
+
+    new_test_ext().execute_with(|| {
+        let group_id = create_group_with_bob();
+        assert_ok!(ChatGroup::send_group_message(RuntimeOrigin::signed(ALICE), group_id, b"a".to_vec(), 0));
+
+        // CHARLIE 加入后看不到历史未读
+        assert_ok!(ChatGroup::join_group(RuntimeOrigin::signed(CHARLIE), group_id));
+        assert_eq!(ChatGroup::group_unread_count(group_id, &CHARLIE), 0);
+
+        System::set_block_number(7);
+        assert_ok!(ChatGroup::send_group_message(RuntimeOrigin::signed(BOB), group_id, b"b".to_vec(), 0));
+
+        let inbox = ChatGroup::group_inbox(&CHARLIE);
+        assert_eq!(inbox.len(), 1);
+        assert_eq!(inbox[0].group_id, group_id);
+        assert_eq!(inbox[0].last_active, 7);
+        assert_eq!(inbox[0].last_message_id, Some(1));
+        assert_eq!(inbox[0].unread, 1);
+
+        // 离开后游标清除
+        assert_ok!(ChatGroup::leave_group(RuntimeOrigin::signed(CHARLIE), group_id));
+        assert!(!crate::GroupReadCursors::<Test>::contains_key(group_id, CHARLIE));
+        assert!(ChatGroup::group_inbox(&CHARLIE).is_empty());
+    });
+}
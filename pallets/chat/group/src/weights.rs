@@ -11,6 +11,16 @@ pub trait WeightInfo {
     fn join_group() -> Weight;
     fn leave_group() -> Weight;
     fn disband_group() -> Weight;
+    fn publish_prekey() -> Weight;
+    fn post_wrapped_keys(n: u32) -> Weight;
+    fn rotate_group_key() -> Weight;
+    fn register_bot() -> Weight;
+    fn deregister_bot() -> Weight;
+    fn install_bot() -> Weight;
+    fn uninstall_bot() -> Weight;
+    fn bot_send_message() -> Weight;
+    fn set_bot_status() -> Weight;
+    fn mark_group_read() -> Weight;
 }
 
 /// Substrate 权重实现
@@ -41,6 +51,58 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(3))
             .saturating_add(T::DbWeight::get().writes(5))
     }
+    fn publish_prekey() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn post_wrapped_keys(n: u32) -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(Weight::from_parts(15_000_000, 0).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().reads(3).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().writes(1))
+            .saturating_add(T::DbWeight::get().writes(1).saturating_mul(n as u64))
+    }
+    fn rotate_group_key() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn register_bot() -> Weight {
+        Weight::from_parts(40_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn deregister_bot() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn install_bot() -> Weight {
+        Weight::from_parts(40_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(7))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+    fn uninstall_bot() -> Weight {
+        Weight::from_parts(35_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    fn bot_send_message() -> Weight {
+        Weight::from_parts(45_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+    fn set_bot_status() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn mark_group_read() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
 }
 
 /// 默认权重实现（用于测试）
@@ -50,4 +112,17 @@ impl WeightInfo for () {
     fn join_group() -> Weight { Weight::from_parts(30_000_000, 0) }
     fn leave_group() -> Weight { Weight::from_parts(30_000_000, 0) }
     fn disband_group() -> Weight { Weight::from_parts(100_000_000, 0) }
+    fn publish_prekey() -> Weight { Weight::from_parts(20_000_000, 0) }
+    fn post_wrapped_keys(n: u32) -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(Weight::from_parts(15_000_000, 0).saturating_mul(n as u64))
+    }
+    fn rotate_group_key() -> Weight { Weight::from_parts(20_000_000, 0) }
+    fn register_bot() -> Weight { Weight::from_parts(40_000_000, 0) }
+    fn deregister_bot() -> Weight { Weight::from_parts(30_000_000, 0) }
+    fn install_bot() -> Weight { Weight::from_parts(40_000_000, 0) }
+    fn uninstall_bot() -> Weight { Weight::from_parts(35_000_000, 0) }
+    fn bot_send_message() -> Weight { Weight::from_parts(45_000_000, 0) }
+    fn set_bot_status() -> Weight { Weight::from_parts(15_000_000, 0) }
+    fn mark_group_read() -> Weight { Weight::from_parts(15_000_000, 0) }
 }
@@ -40,7 +40,9 @@ use frame_support::{
     PalletId,
 };
 use frame_system::pallet_prelude::*;
-use sp_runtime::traits::{AccountIdConversion, Saturating, Zero};
+use sp_runtime::traits::{AccountIdConversion, IdentifyAccount, Saturating, Verify, Zero};
+use pallet_reputation::traits::{ReputationProvider, ReputationSink};
+use pallet_reputation::types::{ReputationDomain, ReputationSignal, RiskLevel};
 
 pub use pallet::*;
 pub use types::*;
@@ -50,6 +52,15 @@ pub use weights::WeightInfo;
 pub type BalanceOf<T> =
     <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+/// 直播间类型别名
+pub type LiveRoomOf<T> = LiveRoom<
+    <T as frame_system::Config>::AccountId,
+    BalanceOf<T>,
+    <T as Config>::MaxTitleLen,
+    <T as Config>::MaxDescriptionLen,
+    <T as Config>::MaxCidLen,
+>;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -116,6 +127,25 @@ pub mod pallet {
         /// 治理来源 (用于封禁等管理操作)
         type GovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
+        /// 邀请码签名类型 (由主播链下签发)
+        type InviteSignature: Verify<Signer = Self::InviteSigner>
+            + Parameter
+            + DecodeWithMemTracking;
+
+        /// 邀请码签名者类型 (可还原为主播账户)
+        type InviteSigner: IdentifyAccount<AccountId = Self::AccountId>;
+
+        /// 每个直播间最大邀请数 (白名单容量)
+        #[pallet::constant]
+        type MaxInvitesPerRoom: Get<u32>;
+
+        /// 每个直播间最多可吊销的邀请码数量
+        #[pallet::constant]
+        type MaxRevokedInviteCodes: Get<u32>;
+
+        /// 跨域信誉（上报封禁 / 违规信号，开播时检查综合风险）
+        type Reputation: ReputationSink<Self::AccountId> + ReputationProvider<Self::AccountId>;
+
         /// 权重信息
         type WeightInfo: WeightInfo;
 
@@ -221,6 +251,47 @@ pub mod pallet {
         (),
     >;
 
+    /// 私密直播间邀请白名单
+    #[pallet::storage]
+    #[pallet::getter(fn room_invites)]
+    pub type RoomInvites<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64,           // room_id
+        Blake2_128Concat,
+        T::AccountId,  // viewer
+        RoomInvite,
+    >;
+
+    /// 直播间邀请数量 (用于容量限制)
+    #[pallet::storage]
+    #[pallet::getter(fn room_invite_count)]
+    pub type RoomInviteCount<T: Config> = StorageMap<_, Blake2_128Concat, u64, u32, ValueQuery>;
+
+    /// 邀请码已兑换次数
+    #[pallet::storage]
+    #[pallet::getter(fn invite_code_uses)]
+    pub type InviteCodeUses<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64, // room_id
+        Twox64Concat,
+        u32, // code_id
+        u32, // uses
+        ValueQuery,
+    >;
+
+    /// 已吊销的邀请码
+    #[pallet::storage]
+    #[pallet::getter(fn revoked_invite_codes)]
+    pub type RevokedInviteCodes<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u64, // room_id
+        BoundedVec<u32, T::MaxRevokedInviteCodes>,
+        ValueQuery,
+    >;
+
     /// 当前连麦者列表
     #[pallet::storage]
     #[pallet::getter(fn active_co_hosts)]
@@ -387,6 +458,30 @@ pub mod pallet {
             total_viewers: u64,
             peak_viewers: u32,
         },
+        /// 观众已被邀请
+        ViewerInvited {
+            room_id: u64,
+            viewer: T::AccountId,
+            expires_at: Option<u64>,
+        },
+        /// 邀请已撤销
+        InviteRevoked {
+            room_id: u64,
+            viewer: T::AccountId,
+        },
+        /// 邀请码已兑换
+        InviteCodeRedeemed {
+            room_id: u64,
+            code_id: u32,
+            viewer: T::AccountId,
+            seats_used: u32,
+        },
+        /// 邀请码已吊销
+        InviteCodeRevoked {
+            room_id: u64,
+            code_id: u32,
+            invites_removed: u32,
+        },
     }
 
     // ============ 错误 ============
@@ -451,6 +546,28 @@ pub mod pallet {
         InvalidQuantity,
         /// 没有超额保证金可提取
         NoBondExcess,
+        /// 非私密直播间
+        NotPrivateRoom,
+        /// 已持有有效邀请
+        AlreadyInvited,
+        /// 邀请不存在
+        InviteNotFound,
+        /// 邀请或邀请码已过期
+        InviteExpired,
+        /// 邀请码已吊销
+        InviteCodeRevoked,
+        /// 邀请码签名无效
+        InvalidInviteSignature,
+        /// 邀请码席位已用完
+        InviteSeatsExhausted,
+        /// 直播间邀请数量已达上限
+        TooManyInvites,
+        /// 吊销邀请码数量已达上限
+        TooManyRevokedCodes,
+        /// 私密直播间需要邀请
+        InviteRequired,
+        /// 跨域综合风险过高
+        ReputationRiskTooHigh,
     }
 
     // ============ 调用函数 ============
@@ -475,6 +592,12 @@ pub mod pallet {
             // 检查主播是否已有活跃直播间
             ensure!(!HostRoom::<T>::contains_key(&host), Error::<T>::HostAlreadyHasRoom);
 
+            // 在其他领域被封禁或综合风险过高的账户不得开播
+            ensure!(
+                T::Reputation::risk_level(&host) < RiskLevel::Blocked,
+                Error::<T>::ReputationRiskTooHigh
+            );
+
             // 验证标题长度
             let title: BoundedVec<u8, T::MaxTitleLen> =
                 title.try_into().map_err(|_| Error::<T>::TitleTooLong)?;
@@ -639,6 +762,9 @@ pub mod pallet {
                 // 清除连麦者
                 ActiveCoHosts::<T>::remove(room_id);
 
+                // 清除邀请白名单
+                Self::clear_invites(room_id);
+
                 // 移除主播的活跃直播间记录
                 HostRoom::<T>::remove(&host);
 
@@ -759,6 +885,11 @@ pub mod pallet {
             let mut room = LiveRooms::<T>::get(room_id).ok_or(Error::<T>::RoomNotFound)?;
             ensure!(room.status == LiveRoomStatus::Live, Error::<T>::RoomNotLive);
 
+            // 私密直播间仅白名单观众可送礼
+            if room.room_type == LiveRoomType::Private {
+                ensure!(Self::can_view(room_id, &sender), Error::<T>::InviteRequired);
+            }
+
             // 检查礼物存在且启用
             let gift = Gifts::<T>::get(gift_id).ok_or(Error::<T>::GiftNotFound)?;
             ensure!(gift.enabled, Error::<T>::GiftDisabled);
@@ -884,6 +1015,9 @@ pub mod pallet {
 
             RoomBlacklist::<T>::insert(room_id, &viewer, ());
 
+            // 被踢出的观众同时失去邀请
+            Self::remove_invite(room_id, &viewer);
+
             Self::deposit_event(Event::ViewerKicked { room_id, viewer });
 
             Ok(())
@@ -935,6 +1069,9 @@ pub mod pallet {
                 // 清除连麦者
                 ActiveCoHosts::<T>::remove(room_id);
 
+                // 清除邀请白名单
+                Self::clear_invites(room_id);
+
                 // 移除主播的活跃直播间记录
                 HostRoom::<T>::remove(&room.host);
 
@@ -958,6 +1095,8 @@ pub mod pallet {
                     });
                 }
 
+                T::Reputation::report(&room.host, ReputationDomain::Livestream, ReputationSignal::Banned);
+
                 Self::deposit_event(Event::RoomBanned {
                     room_id,
                     reason,
@@ -1019,6 +1158,12 @@ pub mod pallet {
                 });
             }
 
+            T::Reputation::report(
+                &room.host,
+                ReputationDomain::Livestream,
+                ReputationSignal::Violation(violation_type.reputation_severity()),
+            );
+
             Ok(())
         }
 
@@ -1226,6 +1371,156 @@ pub mod pallet {
             
             Ok(())
         }
+
+        // -------- 私密直播邀请 --------
+
+        /// 邀请观众进入私密直播间
+        ///
+        /// - `expires_at`: 邀请过期区块，None 表示直播结束前有效
+        /// - 已过期的旧邀请会被覆盖
+        #[pallet::call_index(70)]
+        #[pallet::weight(T::WeightInfo::invite_viewer())]
+        pub fn invite_viewer(
+            origin: OriginFor<T>,
+            room_id: u64,
+            viewer: T::AccountId,
+            expires_at: Option<u64>,
+        ) -> DispatchResult {
+            let host = ensure_signed(origin)?;
+
+            let room = Self::ensure_private_room_open(room_id)?;
+            ensure!(room.host == host, Error::<T>::NotRoomHost);
+            ensure!(
+                !RoomBlacklist::<T>::contains_key(room_id, &viewer),
+                Error::<T>::AlreadyInBlacklist
+            );
+
+            let now = Self::current_block();
+            if let Some(at) = expires_at {
+                ensure!(at > now, Error::<T>::InviteExpired);
+            }
+
+            Self::grant_invite(
+                room_id,
+                &viewer,
+                RoomInvite { source: InviteSource::Direct, issued_at: now, expires_at },
+            )?;
+
+            Self::deposit_event(Event::ViewerInvited { room_id, viewer, expires_at });
+
+            Ok(())
+        }
+
+        /// 撤销观众邀请
+        #[pallet::call_index(71)]
+        #[pallet::weight(T::WeightInfo::revoke_invite())]
+        pub fn revoke_invite(
+            origin: OriginFor<T>,
+            room_id: u64,
+            viewer: T::AccountId,
+        ) -> DispatchResult {
+            let host = ensure_signed(origin)?;
+
+            let room = LiveRooms::<T>::get(room_id).ok_or(Error::<T>::RoomNotFound)?;
+            ensure!(room.host == host, Error::<T>::NotRoomHost);
+            ensure!(Self::remove_invite(room_id, &viewer), Error::<T>::InviteNotFound);
+
+            Self::deposit_event(Event::InviteRevoked { room_id, viewer });
+
+            Ok(())
+        }
+
+        /// 兑换主播签名的邀请码
+        ///
+        /// 观众提交主播链下签发的 `InviteCode` 及签名，
+        /// 验签通过且席位未满时加入白名单，邀请随邀请码同时过期
+        #[pallet::call_index(72)]
+        #[pallet::weight(T::WeightInfo::redeem_invite_code())]
+        pub fn redeem_invite_code(
+            origin: OriginFor<T>,
+            code: InviteCode,
+            signature: T::InviteSignature,
+        ) -> DispatchResult {
+            let viewer = ensure_signed(origin)?;
+            let room_id = code.room_id;
+
+            let room = Self::ensure_private_room_open(room_id)?;
+
+            let now = Self::current_block();
+            ensure!(code.expires_at > now, Error::<T>::InviteExpired);
+            ensure!(
+                !RevokedInviteCodes::<T>::get(room_id).contains(&code.code_id),
+                Error::<T>::InviteCodeRevoked
+            );
+            ensure!(
+                signature.verify(&code.signing_payload()[..], &room.host),
+                Error::<T>::InvalidInviteSignature
+            );
+            ensure!(
+                !RoomBlacklist::<T>::contains_key(room_id, &viewer),
+                Error::<T>::AlreadyInBlacklist
+            );
+
+            let uses = InviteCodeUses::<T>::get(room_id, code.code_id);
+            ensure!(uses < code.max_seats, Error::<T>::InviteSeatsExhausted);
+
+            Self::grant_invite(
+                room_id,
+                &viewer,
+                RoomInvite {
+                    source: InviteSource::Code(code.code_id),
+                    issued_at: now,
+                    expires_at: Some(code.expires_at),
+                },
+            )?;
+
+            let seats_used = uses.saturating_add(1);
+            InviteCodeUses::<T>::insert(room_id, code.code_id, seats_used);
+
+            Self::deposit_event(Event::InviteCodeRedeemed {
+                room_id,
+                code_id: code.code_id,
+                viewer,
+                seats_used,
+            });
+
+            Ok(())
+        }
+
+        /// 吊销邀请码
+        ///
+        /// 邀请码不可再兑换，已通过该邀请码获得的邀请同时移除
+        #[pallet::call_index(73)]
+        #[pallet::weight(T::WeightInfo::revoke_invite_code(T::MaxInvitesPerRoom::get()))]
+        pub fn revoke_invite_code(
+            origin: OriginFor<T>,
+            room_id: u64,
+            code_id: u32,
+        ) -> DispatchResult {
+            let host = ensure_signed(origin)?;
+
+            let room = LiveRooms::<T>::get(room_id).ok_or(Error::<T>::RoomNotFound)?;
+            ensure!(room.host == host, Error::<T>::NotRoomHost);
+
+            RevokedInviteCodes::<T>::try_mutate(room_id, |codes| -> DispatchResult {
+                ensure!(!codes.contains(&code_id), Error::<T>::InviteCodeRevoked);
+                codes.try_push(code_id).map_err(|_| Error::<T>::TooManyRevokedCodes)?;
+                Ok(())
+            })?;
+
+            let granted: Vec<T::AccountId> = RoomInvites::<T>::iter_prefix(room_id)
+                .filter(|(_, invite)| invite.source == InviteSource::Code(code_id))
+                .map(|(viewer, _)| viewer)
+                .collect();
+            let invites_removed = granted.len() as u32;
+            for viewer in granted.iter() {
+                Self::remove_invite(room_id, viewer);
+            }
+
+            Self::deposit_event(Event::InviteCodeRevoked { room_id, code_id, invites_removed });
+
+            Ok(())
+        }
     }
 
     // ============ 辅助函数 ============
@@ -1236,9 +1531,103 @@ pub mod pallet {
             T::PalletId::get().into_account_truncating()
         }
 
-        /// 检查用户是否有门票
+        /// 检查用户是否有门票 (购票或持有有效邀请)
         pub fn has_ticket(room_id: u64, user: &T::AccountId) -> bool {
-            TicketHolders::<T>::contains_key(room_id, user)
+            TicketHolders::<T>::contains_key(room_id, user) || Self::has_valid_invite(room_id, user)
+        }
+
+        /// 检查用户是否持有未过期的邀请
+        pub fn has_valid_invite(room_id: u64, user: &T::AccountId) -> bool {
+            RoomInvites::<T>::get(room_id, user)
+                .map(|invite| !invite.is_expired(Self::current_block()))
+                .unwrap_or(false)
+        }
+
+        /// 检查用户是否可观看直播间
+        ///
+        /// - 主播始终可观看，黑名单用户不可观看
+        /// - 私密直播间需要有效邀请，付费直播间需要门票或邀请
+        pub fn can_view(room_id: u64, user: &T::AccountId) -> bool {
+            let Some(room) = LiveRooms::<T>::get(room_id) else {
+                return false;
+            };
+            if &room.host == user {
+                return true;
+            }
+            if RoomBlacklist::<T>::contains_key(room_id, user) {
+                return false;
+            }
+            match room.room_type {
+                LiveRoomType::Private => Self::has_valid_invite(room_id, user),
+                LiveRoomType::Paid => Self::has_ticket(room_id, user),
+                _ => true,
+            }
+        }
+
+        /// 当前区块号 (u64)
+        fn current_block() -> u64 {
+            <frame_system::Pallet<T>>::block_number().try_into().unwrap_or(0)
+        }
+
+        /// 检查直播间为私密且未结束/封禁
+        fn ensure_private_room_open(room_id: u64) -> Result<LiveRoomOf<T>, DispatchError> {
+            let room = LiveRooms::<T>::get(room_id).ok_or(Error::<T>::RoomNotFound)?;
+            ensure!(room.room_type == LiveRoomType::Private, Error::<T>::NotPrivateRoom);
+            ensure!(room.status != LiveRoomStatus::Ended, Error::<T>::RoomEnded);
+            ensure!(room.status != LiveRoomStatus::Banned, Error::<T>::RoomBanned);
+            Ok(room)
+        }
+
+        /// 写入邀请 (已有有效邀请时拒绝，已过期邀请被覆盖且不重复计数)
+        fn grant_invite(room_id: u64, viewer: &T::AccountId, invite: RoomInvite) -> DispatchResult {
+            match RoomInvites::<T>::get(room_id, viewer) {
+                Some(existing) => {
+                    ensure!(existing.is_expired(invite.issued_at), Error::<T>::AlreadyInvited);
+                }
+                None => {
+                    let count = RoomInviteCount::<T>::get(room_id);
+                    ensure!(count < T::MaxInvitesPerRoom::get(), Error::<T>::TooManyInvites);
+                    RoomInviteCount::<T>::insert(room_id, count.saturating_add(1));
+                }
+            }
+            RoomInvites::<T>::insert(room_id, viewer, invite);
+            Ok(())
+        }
+
+        /// 移除邀请，返回是否存在
+        fn remove_invite(room_id: u64, viewer: &T::AccountId) -> bool {
+            if RoomInvites::<T>::take(room_id, viewer).is_some() {
+                RoomInviteCount::<T>::mutate(room_id, |c| *c = c.saturating_sub(1));
+                true
+            } else {
+                false
+            }
+        }
+
+        /// 清除直播间全部邀请数据
+        fn clear_invites(room_id: u64) {
+            let limit = T::MaxInvitesPerRoom::get();
+            let _ = RoomInvites::<T>::clear_prefix(room_id, limit, None);
+            let _ = InviteCodeUses::<T>::clear_prefix(room_id, limit, None);
+            RoomInviteCount::<T>::remove(room_id);
+            RevokedInviteCodes::<T>::remove(room_id);
+        }
+
+        /// 获取直播间邀请列表 (用于 RuntimeApi)
+        pub fn get_room_invites(room_id: u64) -> Vec<runtime_api::InviteInfo<T::AccountId>> {
+            let now = Self::current_block();
+            RoomInvites::<T>::iter_prefix(room_id)
+                .map(|(viewer, invite)| runtime_api::InviteInfo {
+                    viewer,
+                    code_id: match invite.source {
+                        InviteSource::Direct => None,
+                        InviteSource::Code(id) => Some(id),
+                    },
+                    issued_at: invite.issued_at,
+                    expires_at: invite.expires_at,
+                    is_valid: !invite.is_expired(now),
+                })
+                .collect()
         }
 
         /// 检查用户是否在黑名单
@@ -8,7 +8,10 @@ use frame_support::{
     PalletId,
 };
 use frame_system::EnsureRoot;
-use sp_runtime::BuildStorage;
+use sp_runtime::{
+    testing::{TestSignature, UintAuthorityId},
+    BuildStorage,
+};
 
 type Block = frame_system::mocking::MockBlock<Test>;
 type Balance = u128;
@@ -68,6 +71,11 @@ impl pallet_livestream::Config for Test {
     type DepositCalculator = (); // 使用空实现，返回兜底值
     type PalletId = LivestreamPalletId;
     type GovernanceOrigin = EnsureRoot<u64>;
+    type InviteSignature = TestSignature;
+    type InviteSigner = UintAuthorityId;
+    type MaxInvitesPerRoom = ConstU32<3>;
+    type MaxRevokedInviteCodes = ConstU32<2>;
+    type Reputation = ();
     type WeightInfo = ();
 }
 
@@ -41,6 +41,12 @@ sp_api::decl_runtime_apis! {
 
         /// 获取直播间当前连麦者
         fn get_co_hosts(room_id: u64) -> Vec<AccountId>;
+
+        /// 获取私密直播间邀请列表
+        fn get_room_invites(room_id: u64) -> Vec<InviteInfo<AccountId>>;
+
+        /// 检查用户是否可观看直播间 (邀请/门票/黑名单综合判断)
+        fn can_view(room_id: u64, user: AccountId) -> bool;
     }
 }
 
@@ -63,6 +69,16 @@ pub struct LiveRoomInfo<AccountId, Balance> {
     pub ended_at: Option<u64>,
 }
 
+/// 邀请信息 (用于 API 返回)
+#[derive(codec::Encode, codec::Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+pub struct InviteInfo<AccountId> {
+    pub viewer: AccountId,
+    pub code_id: Option<u32>, // None: 主播直接邀请
+    pub issued_at: u64,
+    pub expires_at: Option<u64>,
+    pub is_valid: bool,
+}
+
 /// 礼物信息 (用于 API 返回)
 #[derive(codec::Encode, codec::Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo)]
 pub struct GiftInfo<Balance> {
@@ -1,7 +1,8 @@
 //! 直播间模块单元测试
 
-use crate::{mock::*, Error, LiveRoomStatus, LiveRoomType};
+use crate::{mock::*, Error, InviteCode, LiveRoomStatus, LiveRoomType};
 use frame_support::{assert_noop, assert_ok};
+use sp_runtime::testing::TestSignature;
 
 // ============ 直播间创建测试 ============
 
@@ -131,3 +132,196 @@ fn create_gift_works() {
         assert!(gift.enabled);
     });
 }
+
+// ============ 私密直播邀请测试 ============
+
+fn create_private_room() {
+    assert_ok!(Livestream::create_room(
+        RuntimeOrigin::signed(ALICE),
+        b"Private Room".to_vec(),
+        None,
+        LiveRoomType::Private,
+        None,
+        None,
+    ));
+}
+
+fn sign_code(signer: u64, code: &InviteCode) -> TestSignature {
+    TestSignature(signer, code.signing_payload())
+}
+
+#[test]
+fn invite_viewer_grants_access() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_private_room();
+
+        assert!(!Livestream::can_view(0, &BOB));
+        assert!(Livestream::can_view(0, &ALICE));
+
+        // 只有主播可以邀请
+        assert_noop!(
+            Livestream::invite_viewer(RuntimeOrigin::signed(CHARLIE), 0, BOB, None),
+            Error::<Test>::NotRoomHost
+        );
+
+        assert_ok!(Livestream::invite_viewer(RuntimeOrigin::signed(ALICE), 0, BOB, Some(10)));
+        assert!(Livestream::can_view(0, &BOB));
+        assert!(Livestream::has_ticket(0, &BOB));
+        assert_noop!(
+            Livestream::invite_viewer(RuntimeOrigin::signed(ALICE), 0, BOB, None),
+            Error::<Test>::AlreadyInvited
+        );
+
+        // 过期后失效，可重新邀请且不重复计数
+        System::set_block_number(11);
+        assert!(!Livestream::can_view(0, &BOB));
+        assert_ok!(Livestream::invite_viewer(RuntimeOrigin::signed(ALICE), 0, BOB, None));
+        assert_eq!(Livestream::room_invite_count(0), 1);
+
+        // 撤销
+        assert_ok!(Livestream::revoke_invite(RuntimeOrigin::signed(ALICE), 0, BOB));
+        assert!(!Livestream::can_view(0, &BOB));
+        assert_noop!(
+            Livestream::revoke_invite(RuntimeOrigin::signed(ALICE), 0, BOB),
+            Error::<Test>::InviteNotFound
+        );
+    });
+}
+
+#[test]
+fn invite_viewer_requires_private_room() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Livestream::create_room(
+            RuntimeOrigin::signed(ALICE),
+            b"Test Room".to_vec(),
+            None,
+            LiveRoomType::Normal,
+            None,
+            None,
+        ));
+
+        assert_noop!(
+            Livestream::invite_viewer(RuntimeOrigin::signed(ALICE), 0, BOB, None),
+            Error::<Test>::NotPrivateRoom
+        );
+    });
+}
+
+#[test]
+fn invite_capacity_is_bounded() {
+    new_test_ext().execute_with(|| {
+        create_private_room();
+
+        for viewer in [BOB, CHARLIE, DAVE] {
+            assert_ok!(Livestream::invite_viewer(RuntimeOrigin::signed(ALICE), 0, viewer, None));
+        }
+        assert_noop!(
+            Livestream::invite_viewer(RuntimeOrigin::signed(ALICE), 0, EVE, None),
+            Error::<Test>::TooManyInvites
+        );
+    });
+}
+
+#[test]
+fn redeem_invite_code_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_private_room();
+
+        let code = InviteCode { room_id: 0, code_id: 7, max_seats: 1, expires_at: 20 };
+
+        // 非主播签名无效
+        assert_noop!(
+            Livestream::redeem_invite_code(RuntimeOrigin::signed(BOB), code, sign_code(CHARLIE, &code)),
+            Error::<Test>::InvalidInviteSignature
+        );
+
+        assert_ok!(Livestream::redeem_invite_code(
+            RuntimeOrigin::signed(BOB),
+            code,
+            sign_code(ALICE, &code)
+        ));
+        assert!(Livestream::can_view(0, &BOB));
+        assert_eq!(Livestream::invite_code_uses(0, 7), 1);
+
+        // 席位已满
+        assert_noop!(
+            Livestream::redeem_invite_code(RuntimeOrigin::signed(CHARLIE), code, sign_code(ALICE, &code)),
+            Error::<Test>::InviteSeatsExhausted
+        );
+
+        // 邀请随邀请码过期
+        System::set_block_number(21);
+        assert!(!Livestream::can_view(0, &BOB));
+        assert_noop!(
+            Livestream::redeem_invite_code(RuntimeOrigin::signed(CHARLIE), code, sign_code(ALICE, &code)),
+            Error::<Test>::InviteExpired
+        );
+    });
+}
+
+#[test]
+fn revoke_invite_code_removes_granted_invites() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_private_room();
+
+        let code = InviteCode { room_id: 0, code_id: 1, max_seats: 5, expires_at: 100 };
+        assert_ok!(Livestream::redeem_invite_code(
+            RuntimeOrigin::signed(BOB),
+            code,
+            sign_code(ALICE, &code)
+        ));
+        assert_ok!(Livestream::invite_viewer(RuntimeOrigin::signed(ALICE), 0, CHARLIE, None));
+
+        assert_ok!(Livestream::revoke_invite_code(RuntimeOrigin::signed(ALICE), 0, 1));
+        assert!(!Livestream::can_view(0, &BOB));
+        assert!(Livestream::can_view(0, &CHARLIE));
+        assert_eq!(Livestream::room_invite_count(0), 1);
+
+        assert_noop!(
+            Livestream::redeem_invite_code(RuntimeOrigin::signed(DAVE), code, sign_code(ALICE, &code)),
+            Error::<Test>::InviteCodeRevoked
+        );
+
+        let invites = Livestream::get_room_invites(0);
+        assert_eq!(invites.len(), 1);
+        assert_eq!(invites[0].viewer, CHARLIE);
+        assert_eq!(invites[0].code_id, None);
+    });
+}
+
+#[test]
+fn private_room_gift_and_kick_respect_invites() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        create_private_room();
+        assert_ok!(Livestream::start_live(RuntimeOrigin::signed(ALICE), 0));
+        assert_ok!(Livestream::create_gift(
+            RuntimeOrigin::root(),
+            b"Rose".to_vec(),
+            1_000_000_000_000,
+            b"QmTestIconCid".to_vec(),
+        ));
+
+        assert_noop!(
+            Livestream::send_gift(RuntimeOrigin::signed(BOB), 0, 0, 1),
+            Error::<Test>::InviteRequired
+        );
+
+        assert_ok!(Livestream::invite_viewer(RuntimeOrigin::signed(ALICE), 0, BOB, None));
+        assert_ok!(Livestream::send_gift(RuntimeOrigin::signed(BOB), 0, 0, 1));
+
+        // 踢出后邀请失效
+        assert_ok!(Livestream::kick_viewer(RuntimeOrigin::signed(ALICE), 0, BOB));
+        assert!(Livestream::room_invites(0, BOB).is_none());
+        assert!(!Livestream::can_view(0, &BOB));
+
+        // 结束直播清空邀请
+        assert_ok!(Livestream::invite_viewer(RuntimeOrigin::signed(ALICE), 0, CHARLIE, None));
+        assert_ok!(Livestream::end_live(RuntimeOrigin::signed(ALICE), 0));
+        assert!(Livestream::get_room_invites(0).is_empty());
+        assert_eq!(Livestream::room_invite_count(0), 0);
+    });
+}
@@ -3,6 +3,7 @@
 use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use frame_support::pallet_prelude::*;
 use scale_info::TypeInfo;
+use sp_std::vec::Vec;
 
 /// 直播间状态
 #[derive(
@@ -114,6 +115,17 @@ impl LiveRoomViolationType {
             LiveRoomViolationType::Critical => 5000,   // 50%
         }
     }
+
+    /// 对应的跨域信誉严重程度
+    pub fn reputation_severity(&self) -> pallet_reputation::types::Severity {
+        use pallet_reputation::types::Severity;
+        match self {
+            LiveRoomViolationType::Minor => Severity::Minor,
+            LiveRoomViolationType::Moderate => Severity::Moderate,
+            LiveRoomViolationType::Severe => Severity::Severe,
+            LiveRoomViolationType::Critical => Severity::Critical,
+        }
+    }
 }
 
 /// 直播间信息
@@ -223,6 +235,94 @@ where
     }
 }
 
+// ============ 私密直播邀请类型 ============
+
+/// 邀请码签名的域分离前缀
+pub const INVITE_CODE_CONTEXT: &[u8] = b"stardust/livestream-invite";
+
+/// 邀请来源
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    RuntimeDebug,
+    PartialEq,
+    Eq,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum InviteSource {
+    /// 主播直接邀请指定账户
+    Direct,
+    /// 兑换主播签名的邀请码（邀请码ID）
+    Code(u32),
+}
+
+/// 直播间邀请记录（观众白名单条目）
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    RuntimeDebug,
+    PartialEq,
+    Eq,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct RoomInvite {
+    /// 邀请来源
+    pub source: InviteSource,
+    /// 发放时间 (区块号)
+    pub issued_at: u64,
+    /// 过期时间 (区块号，None 表示直播结束前一直有效)
+    pub expires_at: Option<u64>,
+}
+
+impl RoomInvite {
+    /// 在指定区块是否已过期
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.map(|at| now > at).unwrap_or(false)
+    }
+}
+
+/// 邀请码（链下由主播签名分发，观众上链兑换）
+///
+/// 签名内容为 `(INVITE_CODE_CONTEXT, InviteCode).encode()`，
+/// 链上用直播间主播账户验签，主播无需为每个观众单独发交易
+#[derive(
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    Clone,
+    Copy,
+    RuntimeDebug,
+    PartialEq,
+    Eq,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub struct InviteCode {
+    /// 直播间ID
+    pub room_id: u64,
+    /// 邀请码ID（主播自行分配，同一直播间内唯一）
+    pub code_id: u32,
+    /// 席位上限（最多可被兑换的次数）
+    pub max_seats: u32,
+    /// 过期时间 (区块号)，兑换得到的邀请同样在此时过期
+    pub expires_at: u64,
+}
+
+impl InviteCode {
+    /// 待签名的消息
+    pub fn signing_payload(&self) -> Vec<u8> {
+        (INVITE_CODE_CONTEXT, self).encode()
+    }
+}
+
 // ============ 举报与申诉系统类型 ============
 
 /// 直播间举报类型
@@ -21,6 +21,10 @@ pub trait WeightInfo {
     fn end_co_host() -> Weight;
     fn create_gift() -> Weight;
     fn update_gift() -> Weight;
+    fn invite_viewer() -> Weight;
+    fn revoke_invite() -> Weight;
+    fn redeem_invite_code() -> Weight;
+    fn revoke_invite_code(n: u32) -> Weight;
 }
 
 /// 默认权重实现 (用于测试和开发)
@@ -92,4 +96,21 @@ impl WeightInfo for () {
     fn update_gift() -> Weight {
         Weight::from_parts(25_000_000, 0)
     }
+
+    fn invite_viewer() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+    }
+
+    fn revoke_invite() -> Weight {
+        Weight::from_parts(25_000_000, 0)
+    }
+
+    fn redeem_invite_code() -> Weight {
+        Weight::from_parts(60_000_000, 0)
+    }
+
+    fn revoke_invite_code(n: u32) -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(n as u64))
+    }
 }
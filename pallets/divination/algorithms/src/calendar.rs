@@ -0,0 +1,165 @@
+//! # 历法基础计算
+//!
+//! 提供儒略日、日期差、闰年判断以及 Unix 时间戳到公历（北京时间）的转换。
+
+/// 北京时间相对 UTC 的偏移（秒）
+pub const TIMEZONE_OFFSET_SECS: u64 = 8 * 3600;
+
+/// 计算两个日期之间的天数差
+///
+/// 将两个日期分别转换为儒略日数后相减，结果可能为负。
+pub fn calculate_days_between(
+    from_year: i32,
+    from_month: u8,
+    from_day: u8,
+    to_year: i32,
+    to_month: u8,
+    to_day: u8,
+) -> i32 {
+    let from_jdn = date_to_julian_day(from_year, from_month, from_day);
+    let to_jdn = date_to_julian_day(to_year, to_month, to_day);
+    to_jdn - from_jdn
+}
+
+/// 将公历日期转换为儒略日数
+///
+/// 适用于所有日期包括负数年份（如 -720 表示公元前720年），
+/// 1582年10月15日之后应用格里高利历修正。
+pub fn date_to_julian_day(year: i32, month: u8, day: u8) -> i32 {
+    let mut y = year;
+    let mut m = month as i32;
+
+    // 将1月和2月视为前一年的13月和14月
+    if m <= 2 {
+        y -= 1;
+        m += 12;
+    }
+
+    // 格里高利历修正（1582年10月15日之后）
+    let a = y / 100;
+    let b = if year > 1582 || (year == 1582 && month > 10) || (year == 1582 && month == 10 && day >= 15) {
+        2 - a + a / 4
+    } else {
+        0
+    };
+
+    (365.25 * (y + 4716) as f64) as i32 + (30.6001 * (m + 1) as f64) as i32 + day as i32 + b - 1524
+}
+
+/// 判断是否为闰年
+pub fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+/// 获取指定月份的天数
+pub fn get_days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 => {
+            if is_leap_year(year) {
+                29
+            } else {
+                28
+            }
+        }
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// 从 Unix 时间戳提取公历日期时间（北京时间 UTC+8）
+///
+/// # 返回
+/// - (年, 月, 日, 小时)
+pub fn timestamp_to_gregorian(timestamp: u64) -> (u32, u8, u8, u8) {
+    let (year, month, day, hour, _) = timestamp_to_datetime(timestamp);
+    (year, month, day, hour)
+}
+
+/// 从 Unix 时间戳提取公历日期时间（北京时间 UTC+8），含分钟
+///
+/// # 返回
+/// - (年, 月, 日, 小时, 分钟)
+pub fn timestamp_to_datetime(timestamp: u64) -> (u32, u8, u8, u8, u8) {
+    let local_timestamp = timestamp + TIMEZONE_OFFSET_SECS;
+
+    let days_since_epoch = (local_timestamp / 86400) as i64;
+    let seconds_in_day = (local_timestamp % 86400) as u32;
+    let hour = (seconds_in_day / 3600) as u8;
+    let minute = ((seconds_in_day % 3600) / 60) as u8;
+
+    let (year, month, day) = days_to_ymd(days_since_epoch);
+
+    (year as u32, month, day, hour, minute)
+}
+
+/// 小时转时辰地支数（子=1, 丑=2, ..., 亥=12）
+///
+/// 子时跨 23:00-01:00，与梅花易数时辰数取法一致。
+pub fn hour_to_dizhi_num(hour: u8) -> u8 {
+    match hour {
+        0..=22 => hour.div_ceil(2) + 1,
+        _ => 1,
+    }
+}
+
+/// 将天数转换为年月日
+///
+/// # 参数
+/// - `days`: 从 1970-01-01 起的天数
+///
+/// # 返回
+/// - (年, 月, 日)
+pub fn days_to_ymd(days: i64) -> (i32, u8, u8) {
+    // 调整到公元0年3月1日为基准（方便处理闰年）
+    let days = days + 719468;
+
+    let era = if days >= 0 { days / 146097 } else { (days - 146096) / 146097 };
+    let doe = (days - era * 146097) as u32; // 400年周期内的天数
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe as i32 + era as i32 * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = if m <= 2 { y + 1 } else { y };
+
+    (y, m as u8, d as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_between() {
+        assert_eq!(calculate_days_between(2024, 1, 1, 2024, 1, 2), 1);
+        assert_eq!(calculate_days_between(2024, 3, 1, 2024, 2, 1), -29);
+    }
+
+    #[test]
+    fn test_leap_year() {
+        assert!(is_leap_year(2000));
+        assert!(is_leap_year(2024));
+        assert!(!is_leap_year(1900));
+        assert_eq!(get_days_in_month(2024, 2), 29);
+        assert_eq!(get_days_in_month(2023, 2), 28);
+    }
+
+    #[test]
+    fn test_timestamp_to_gregorian() {
+        // 2024-01-01 00:00:00 UTC = 2024-01-01 08:00 北京时间
+        assert_eq!(timestamp_to_gregorian(1704067200), (2024, 1, 1, 8));
+        // 1970-01-01 16:00 UTC 跨入北京时间次日
+        assert_eq!(timestamp_to_gregorian(57600), (1970, 1, 2, 0));
+        assert_eq!(timestamp_to_datetime(1704067200 + 90), (2024, 1, 1, 8, 1));
+    }
+
+    #[test]
+    fn test_hour_to_dizhi_num() {
+        assert_eq!(hour_to_dizhi_num(23), 1);
+        assert_eq!(hour_to_dizhi_num(0), 1);
+        assert_eq!(hour_to_dizhi_num(1), 2);
+        assert_eq!(hour_to_dizhi_num(12), 7);
+        assert_eq!(hour_to_dizhi_num(22), 12);
+    }
+}
@@ -0,0 +1,250 @@
+//! # 黄金测试向量
+//!
+//! 链上 Pallet 与 TEE Enclave 的测试共用这些输入与期望的链上索引字节，
+//! 任何一端的算法漂移都会导致测试失败。
+
+/// 八字：公历年月日时（现代派子时） → `SiZhuIndex`
+pub struct BaziCase {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub expected: [u8; 8],
+}
+
+/// 梅花易数时间起卦：Unix 时间戳 → `MeihuaIndex`
+pub struct MeihuaTimeCase {
+    pub timestamp: u64,
+    pub expected: [u8; 3],
+}
+
+/// 梅花易数双数起卦：两个数字 + 起卦时间戳（取时辰数） → `MeihuaIndex`
+pub struct MeihuaNumbersCase {
+    pub num1: u16,
+    pub num2: u16,
+    pub timestamp: u64,
+    pub expected: [u8; 3],
+}
+
+/// 奇门遁甲：起局时间戳 → `QimenSiZhuIndex`
+pub struct QimenCase {
+    pub timestamp: u64,
+    pub expected: [u8; 8],
+}
+
+/// 奇门遁甲排盘：节气、节气内天数、时柱 → `QimenChart::to_bytes()`
+///
+/// 链上 `algorithm::generate_qimen_chart` 的结果须与之逐字节一致。
+pub struct QimenPanCase {
+    pub jie_qi: u8,
+    pub day_in_jieqi: u8,
+    pub hour_gan: u8,
+    pub hour_zhi: u8,
+    pub expected: [u8; 51],
+}
+
+/// 奇门遁甲排盘：起局时间戳 → `QimenChart::to_bytes()`（Enclave 排盘）
+pub struct QimenChartCase {
+    pub timestamp: u64,
+    pub expected: [u8; 51],
+}
+
+/// 六爻：六爻值 → `LiuyaoIndex`
+pub struct LiuyaoCase {
+    pub yao_values: [u8; 6],
+    pub expected: [u8; 8],
+}
+
+/// 紫微斗数：农历年月日 + 时辰地支 → `ZiweiIndex`
+pub struct ZiweiCase {
+    pub lunar_year: u16,
+    pub lunar_month: u8,
+    pub lunar_day: u8,
+    pub hour_zhi: u8,
+    pub expected: [u8; 5],
+}
+
+/// 塔罗数字抽牌：牌阵 + 数字 → `TarotIndex`
+pub struct TarotCase {
+    pub spread_type: u8,
+    pub number: u32,
+    pub expected: [u8; 3],
+}
+
+pub const BAZI_CASES: &[BaziCase] = &[
+    BaziCase { year: 2000, month: 1, day: 1, hour: 12, expected: [5, 3, 2, 0, 7, 11, 0, 6] },
+    BaziCase { year: 1990, month: 11, day: 29, hour: 8, expected: [6, 6, 3, 11, 7, 3, 8, 4] },
+    BaziCase { year: 1984, month: 2, day: 4, hour: 23, expected: [9, 11, 1, 1, 7, 9, 4, 0] },
+    BaziCase { year: 2024, month: 6, day: 15, hour: 0, expected: [0, 4, 6, 6, 9, 3, 8, 0] },
+];
+
+pub const MEIHUA_TIME_CASES: &[MeihuaTimeCase] = &[
+    MeihuaTimeCase { timestamp: 1735020000, expected: [4, 2, 2] },
+    MeihuaTimeCase { timestamp: 1704067200, expected: [2, 2, 4] },
+];
+
+pub const MEIHUA_NUMBERS_CASES: &[MeihuaNumbersCase] = &[
+    MeihuaNumbersCase { num1: 5, num2: 3, timestamp: 1704067200, expected: [5, 3, 1] },
+    MeihuaNumbersCase { num1: 2024, num2: 16, timestamp: 1735020000, expected: [8, 8, 2] },
+];
+
+pub const QIMEN_CASES: &[QimenCase] = &[
+    QimenCase { timestamp: 1704067200, expected: [9, 3, 0, 0, 3, 5, 0, 4] },
+    QimenCase { timestamp: 1735020000, expected: [0, 4, 2, 0, 1, 3, 9, 7] },
+];
+
+pub const QIMEN_PAN_CASES: &[QimenPanCase] = &[
+    QimenPanCase {
+        jie_qi: 0,
+        day_in_jieqi: 1,
+        hour_gan: 0,
+        hour_zhi: 0,
+        expected: [
+            0, 1, 0, 1, 1, 1, 4, 4, 1, 1, 1, 9, 5, 6, 6, 6, 6, 6, 3, 3, 3, 7, 7, 4, 4, 4, 8, 8, 5, 0, 0, 2, 9, 8, 8,
+            8, 3, 3, 7, 7, 7, 5, 2, 2, 2, 2, 8, 1, 5, 5, 5,
+        ],
+    },
+    QimenPanCase {
+        jie_qi: 3,
+        day_in_jieqi: 12,
+        hour_gan: 7,
+        hour_zhi: 3,
+        expected: [
+            3, 1, 2, 2, 7, 7, 9, 1, 7, 7, 1, 6, 4, 4, 4, 6, 1, 5, 1, 1, 3, 4, 6, 2, 2, 4, 7, 7, 5, 0, 0, 8, 8, 6, 6,
+            8, 7, 9, 5, 5, 7, 3, 3, 8, 8, 2, 5, 2, 3, 3, 5,
+        ],
+    },
+    QimenPanCase {
+        jie_qi: 12,
+        day_in_jieqi: 7,
+        hour_gan: 2,
+        hour_zhi: 6,
+        expected: [
+            12, 0, 1, 3, 8, 8, 8, 6, 8, 8, 1, 4, 5, 3, 3, 4, 3, 4, 6, 6, 7, 2, 1, 5, 5, 6, 2, 2, 5, 0, 0, 6, 3, 1, 1,
+            2, 5, 9, 2, 2, 3, 9, 8, 7, 7, 8, 1, 7, 4, 4, 5,
+        ],
+    },
+    QimenPanCase {
+        jie_qi: 20,
+        day_in_jieqi: 15,
+        hour_gan: 9,
+        hour_zhi: 11,
+        expected: [
+            20, 0, 2, 2, 7, 7, 9, 5, 6, 6, 8, 5, 4, 1, 1, 3, 2, 1, 4, 4, 6, 1, 2, 3, 3, 5, 3, 3, 5, 0, 0, 8, 9, 7, 7,
+            1, 7, 8, 8, 8, 2, 3, 7, 5, 5, 7, 4, 6, 2, 2, 4,
+        ],
+    },
+];
+
+pub const QIMEN_CHART_CASES: &[QimenChartCase] = &[
+    // 2024-01-01 08:00 北京时间：冬至第 11 天，阳遁四局
+    QimenChartCase {
+        timestamp: 1704067200,
+        expected: [
+            0, 1, 2, 4, 6, 6, 6, 3, 6, 6, 1, 1, 2, 3, 3, 6, 8, 1, 8, 8, 3, 3, 4, 1, 1, 4, 5, 5, 5, 0, 0, 5, 6, 5, 5,
+            8, 4, 7, 4, 4, 7, 7, 8, 7, 7, 2, 2, 9, 2, 2, 5,
+        ],
+    },
+    // 2024-12-24 14:00 北京时间：冬至第 4 天，阳遁一局
+    QimenChartCase {
+        timestamp: 1735020000,
+        expected: [
+            0, 1, 0, 1, 3, 3, 7, 4, 4, 4, 2, 4, 5, 1, 1, 7, 9, 6, 6, 6, 4, 3, 7, 7, 7, 5, 8, 8, 5, 0, 0, 6, 9, 3, 3,
+            1, 5, 3, 2, 2, 8, 8, 2, 5, 5, 3, 2, 1, 8, 8, 6,
+        ],
+    },
+    // 2024-06-22 04:00 北京时间：夏至第 2 天，阴遁九局
+    QimenChartCase {
+        timestamp: 1719000000,
+        expected: [
+            12, 0, 0, 9, 6, 6, 2, 1, 2, 2, 5, 8, 2, 5, 5, 8, 5, 3, 8, 8, 3, 6, 9, 7, 7, 2, 8, 8, 5, 0, 0, 3, 7, 3, 3,
+            6, 9, 6, 4, 4, 7, 1, 5, 1, 1, 4, 7, 4, 6, 6, 1,
+        ],
+    },
+];
+
+pub const LIUYAO_CASES: &[LiuyaoCase] = &[
+    LiuyaoCase { yao_values: [1, 1, 1, 1, 1, 1], expected: [0, 0, 0, 0, 0, 6, 3, 0] },
+    LiuyaoCase { yao_values: [3, 2, 1, 0, 2, 1], expected: [3, 2, 2, 3, 9, 1, 4, 3] },
+    LiuyaoCase { yao_values: [2, 2, 2, 2, 2, 3], expected: [3, 7, 7, 7, 32, 5, 2, 3] },
+];
+
+pub const ZIWEI_CASES: &[ZiweiCase] = &[
+    ZiweiCase { lunar_year: 1990, lunar_month: 5, lunar_day: 15, hour_zhi: 6, expected: [0, 0, 6, 10, 3] },
+    ZiweiCase { lunar_year: 2000, lunar_month: 1, lunar_day: 1, hour_zhi: 0, expected: [2, 2, 1, 3, 2] },
+];
+
+pub const TAROT_CASES: &[TarotCase] = &[
+    TarotCase { spread_type: 0, number: 42, expected: [0, 1, 42] },
+    TarotCase { spread_type: 3, number: 123456, expected: [3, 10, 60] },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::{hour_to_dizhi_num, timestamp_to_gregorian};
+    use crate::sizhu::{sizhu_index, sizhu_index_from_timestamp, ZiShiMode};
+    use crate::{liuyao, meihua, qimen, tarot, ziwei};
+
+    #[test]
+    fn golden_bazi() {
+        for case in BAZI_CASES {
+            let index = sizhu_index(case.year, case.month, case.day, case.hour, ZiShiMode::Modern).unwrap();
+            assert_eq!(index.to_bytes(), case.expected, "{}-{}-{} {}", case.year, case.month, case.day, case.hour);
+        }
+    }
+
+    #[test]
+    fn golden_meihua() {
+        for case in MEIHUA_TIME_CASES {
+            let index = meihua::MeihuaIndex::new(meihua::divine_by_timestamp(case.timestamp));
+            assert_eq!(index.to_bytes(), case.expected);
+        }
+        for case in MEIHUA_NUMBERS_CASES {
+            let hour = timestamp_to_gregorian(case.timestamp).3;
+            let index = meihua::MeihuaIndex::new(meihua::divine_by_numbers(case.num1, case.num2, hour_to_dizhi_num(hour)));
+            assert_eq!(index.to_bytes(), case.expected);
+        }
+    }
+
+    #[test]
+    fn golden_qimen() {
+        for case in QIMEN_CASES {
+            let index = sizhu_index_from_timestamp(case.timestamp, ZiShiMode::Modern).unwrap();
+            assert_eq!(index.to_bytes(), case.expected);
+        }
+    }
+
+    #[test]
+    fn golden_qimen_chart() {
+        for case in QIMEN_PAN_CASES {
+            let chart = qimen::qimen_chart(case.jie_qi, case.day_in_jieqi, case.hour_gan, case.hour_zhi).unwrap();
+            assert_eq!(chart.to_bytes(), case.expected);
+        }
+        for case in QIMEN_CHART_CASES {
+            assert_eq!(qimen::qimen_chart_from_timestamp(case.timestamp).unwrap().to_bytes(), case.expected);
+        }
+    }
+
+    #[test]
+    fn golden_liuyao() {
+        for case in LIUYAO_CASES {
+            assert_eq!(liuyao::liuyao_index(&case.yao_values).to_bytes(), case.expected);
+        }
+    }
+
+    #[test]
+    fn golden_ziwei() {
+        for case in ZIWEI_CASES {
+            let index = ziwei::ziwei_index(case.lunar_year, case.lunar_month, case.lunar_day, case.hour_zhi);
+            assert_eq!(index.to_bytes(), case.expected);
+        }
+    }
+
+    #[test]
+    fn golden_tarot() {
+        for case in TAROT_CASES {
+            assert_eq!(tarot::tarot_index(case.spread_type, case.number).to_bytes(), case.expected);
+        }
+    }
+}
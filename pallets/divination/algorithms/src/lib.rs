@@ -0,0 +1,40 @@
+//! # 占卜核心算法库
+//!
+//! 链上 Pallet 与 TEE Enclave 共用的排盘算法，保证两端结果逐字节一致。
+//!
+//! ## 设计原则
+//!
+//! - **no_std**：不依赖 FRAME / SCALE，Pallet 与独立构建的 Enclave 均可引用
+//! - **原始类型接口**：天干、地支、卦数等均以 `u8` 索引表示，
+//!   各 Pallet 在其上包装自己的枚举类型
+//! - **统一浮点实现**：节气等天文计算统一使用 `libm`，避免平台差异
+//! - **链上索引**：每种占卜提供 `XxxIndex::to_bytes()`，
+//!   与对应 Pallet 索引结构的 SCALE 编码相同
+//!
+//! ## 模块
+//!
+//! - [`calendar`]：儒略日、闰年、时间戳转换
+//! - [`jieqi`]：寿星天文历节气计算
+//! - [`true_solar_time`]：真太阳时修正
+//! - [`sizhu`]：年月日时四柱（八字、奇门共用）
+//! - [`meihua`]：梅花易数起卦与变卦
+//! - [`liuyao`]：六爻卦象与八宫世应
+//! - [`ziwei`]：紫微斗数命宫与主星定位
+//! - [`qimen`]：奇门遁甲定局与九宫排盘
+//! - [`tarot`]：塔罗抽牌
+//! - [`golden`]：链上与 Enclave 共用的黄金测试向量
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod calendar;
+pub mod golden;
+pub mod jieqi;
+pub mod liuyao;
+pub mod meihua;
+pub mod qimen;
+pub mod sizhu;
+pub mod tarot;
+pub mod true_solar_time;
+pub mod ziwei;
@@ -0,0 +1,193 @@
+//! # 六爻核心算法
+//!
+//! 起卦（数字/时间）、本卦变卦推演与京房八宫世应。
+//! 八卦索引顺序：0乾 1兑 2离 3震 4巽 5坎 6艮 7坤。
+
+/// 八卦索引对应的二进制码（与链上 `Trigram::binary` 一致）
+const TRIGRAM_BINARY: [u8; 8] = [0b111, 0b110, 0b101, 0b100, 0b011, 0b010, 0b001, 0b000];
+
+/// 六爻索引（8 字节）
+///
+/// 字段顺序与链上 `LiuyaoIndex` 一致。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LiuyaoIndex {
+    pub ben_shang: u8,
+    pub ben_xia: u8,
+    pub bian_shang: u8,
+    pub bian_xia: u8,
+    /// 动爻掩码（bit0=初爻）
+    pub dong_yao_mask: u8,
+    /// 世爻位置 (1-6)
+    pub shi_yao: u8,
+    /// 应爻位置 (1-6)
+    pub ying_yao: u8,
+    /// 卦宫（八卦索引）
+    pub gong: u8,
+}
+
+impl LiuyaoIndex {
+    /// 链上索引字节
+    pub fn to_bytes(&self) -> [u8; 8] {
+        [
+            self.ben_shang,
+            self.ben_xia,
+            self.bian_shang,
+            self.bian_xia,
+            self.dong_yao_mask,
+            self.shi_yao,
+            self.ying_yao,
+            self.gong,
+        ]
+    }
+}
+
+/// 八卦索引转二进制码
+pub fn trigram_binary(index: u8) -> u8 {
+    TRIGRAM_BINARY[(index % 8) as usize]
+}
+
+/// 二进制码转八卦索引
+pub fn binary_to_trigram_index(bin: u8) -> u8 {
+    let bin = bin & 0b111;
+    TRIGRAM_BINARY.iter().position(|b| *b == bin).unwrap_or(7) as u8
+}
+
+/// 从数字生成六爻（每 4 bit 取一爻，值 0-3）
+pub fn generate_yao_from_number(num: u32) -> [u8; 6] {
+    let mut result = [1u8; 6];
+    for (i, yao) in result.iter_mut().enumerate() {
+        *yao = (((num >> (i * 4)) & 0x0F) as u8) % 4;
+    }
+    result
+}
+
+/// 从时间生成六爻
+pub fn generate_yao_from_time(year: u16, month: u8, day: u8, hour: u8) -> [u8; 6] {
+    let seed = (year as u32) * 10000 + (month as u32) * 100 + (day as u32);
+    let mut result = [1u8; 6];
+    for (i, yao) in result.iter_mut().enumerate() {
+        *yao = (((seed >> (i * 3)) + (hour as u32) + (i as u32)) % 4) as u8;
+    }
+    result
+}
+
+/// 从六爻值计算卦象
+///
+/// 六爻值：0 老阴（动）、1 少阳、2 少阴、3 老阳（动）
+///
+/// # 返回
+/// (本卦上卦, 本卦下卦, 变卦上卦, 变卦下卦, 动爻掩码)
+pub fn calculate_gua_from_yao(yao_values: &[u8; 6]) -> (u8, u8, u8, u8, u8) {
+    let mut ben = [0u8; 2];
+    let mut bian = [0u8; 2];
+    let mut dong_mask: u8 = 0;
+
+    for (i, value) in yao_values.iter().enumerate() {
+        let is_yang = *value == 1 || *value == 3;
+        let is_moving = *value == 0 || *value == 3;
+
+        let ben_bit = is_yang as u8;
+        let bian_bit = if is_moving { 1 - ben_bit } else { ben_bit };
+
+        ben[i / 3] |= ben_bit << (i % 3);
+        bian[i / 3] |= bian_bit << (i % 3);
+
+        if is_moving {
+            dong_mask |= 1 << i;
+        }
+    }
+
+    (
+        binary_to_trigram_index(ben[1]),
+        binary_to_trigram_index(ben[0]),
+        binary_to_trigram_index(bian[1]),
+        binary_to_trigram_index(bian[0]),
+        dong_mask,
+    )
+}
+
+/// 京房八宫：计算卦序与卦宫
+///
+/// 口诀：天同二世天变五，地同四世地变初，本宫六世三世异，人同游魂人变归。
+///
+/// # 返回
+/// (世爻位置 1-6, 卦宫八卦索引)
+pub fn calculate_shi_gong(shang: u8, xia: u8) -> (u8, u8) {
+    let inner_bin = trigram_binary(xia);
+    let outer_bin = trigram_binary(shang);
+
+    let di_tong = (inner_bin & 0b001) == (outer_bin & 0b001);
+    let ren_tong = (inner_bin & 0b010) == (outer_bin & 0b010);
+    let tian_tong = (inner_bin & 0b100) == (outer_bin & 0b100);
+
+    if inner_bin == outer_bin {
+        // 本宫六世
+        (6, xia)
+    } else if tian_tong && !ren_tong && !di_tong {
+        // 天同二世
+        (2, shang)
+    } else if !tian_tong && ren_tong && di_tong {
+        // 天变五
+        (5, shang)
+    } else if di_tong && !ren_tong && !tian_tong {
+        // 地同四世
+        (4, binary_to_trigram_index(inner_bin ^ 0b111))
+    } else if !di_tong && ren_tong && tian_tong {
+        // 地变初
+        (1, shang)
+    } else if ren_tong && !tian_tong && !di_tong {
+        // 人同游魂
+        (4, binary_to_trigram_index(inner_bin ^ 0b111))
+    } else if !ren_tong && tian_tong && di_tong {
+        // 人变归魂
+        (3, xia)
+    } else {
+        // 三世异
+        (3, shang)
+    }
+}
+
+/// 计算世应位置与卦宫
+///
+/// # 返回
+/// (世爻位置, 应爻位置, 卦宫索引)
+pub fn calculate_shi_ying(shang: u8, xia: u8) -> (u8, u8, u8) {
+    let (shi, gong) = calculate_shi_gong(shang, xia);
+    let ying = if shi > 3 { shi - 3 } else { shi + 3 };
+    (shi, ying, gong)
+}
+
+/// 从六爻值计算链上索引
+pub fn liuyao_index(yao_values: &[u8; 6]) -> LiuyaoIndex {
+    let (ben_shang, ben_xia, bian_shang, bian_xia, dong_yao_mask) = calculate_gua_from_yao(yao_values);
+    let (shi_yao, ying_yao, gong) = calculate_shi_ying(ben_shang, ben_xia);
+
+    LiuyaoIndex { ben_shang, ben_xia, bian_shang, bian_xia, dong_yao_mask, shi_yao, ying_yao, gong }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pure_gua() {
+        // 六爻全为少阳：乾为天，本宫六世，世六应三
+        let index = liuyao_index(&[1; 6]);
+        assert_eq!(index.to_bytes(), [0, 0, 0, 0, 0, 6, 3, 0]);
+    }
+
+    #[test]
+    fn test_moving_lines() {
+        // 初爻老阳动：仅下卦初爻翻转
+        let index = liuyao_index(&[3, 1, 1, 1, 1, 1]);
+        assert_eq!((index.bian_shang, index.bian_xia), (0, binary_to_trigram_index(0b110)));
+        assert_eq!(index.dong_yao_mask, 0b000001);
+    }
+
+    #[test]
+    fn test_trigram_binary_roundtrip() {
+        for i in 0..8 {
+            assert_eq!(binary_to_trigram_index(trigram_binary(i)), i);
+        }
+    }
+}
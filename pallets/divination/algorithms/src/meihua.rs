@@ -0,0 +1,205 @@
+//! # 梅花易数核心算法
+//!
+//! 卦数与动爻计算、各种起卦方式以及变卦/互卦推演。
+//! 卦数采用先天八卦数：1乾 2兑 3离 4震 5巽 6坎 7艮 8坤。
+
+/// 先天八卦数对应的三爻二进制（bit0=初爻，1为阳）
+const GUA_BINARY: [u8; 8] = [0b111, 0b011, 0b101, 0b001, 0b110, 0b010, 0b100, 0b000];
+
+/// 梅花易数索引（3 字节）
+///
+/// 字段顺序与链上 `MeihuaIndex` 一致。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MeihuaIndex {
+    /// 上卦 (1-8)
+    pub shang_gua: u8,
+    /// 下卦 (1-8)
+    pub xia_gua: u8,
+    /// 动爻 (1-6)
+    pub dong_yao: u8,
+}
+
+impl MeihuaIndex {
+    /// 从 (上卦, 下卦, 动爻) 构建
+    pub fn new((shang_gua, xia_gua, dong_yao): (u8, u8, u8)) -> Self {
+        Self { shang_gua, xia_gua, dong_yao }
+    }
+
+    /// 链上索引字节
+    pub fn to_bytes(&self) -> [u8; 3] {
+        [self.shang_gua, self.xia_gua, self.dong_yao]
+    }
+}
+
+/// 计算卦数（除8取余，余0按8计）
+#[inline]
+pub fn calc_gua_num(n: u32) -> u8 {
+    let r = (n % 8) as u8;
+    if r == 0 {
+        8
+    } else {
+        r
+    }
+}
+
+/// 计算动爻数（除6取余，余0按6计）
+#[inline]
+pub fn calc_dong_yao(n: u32) -> u8 {
+    let r = (n % 6) as u8;
+    if r == 0 {
+        6
+    } else {
+        r
+    }
+}
+
+/// 卦数 (1-8) 转三爻二进制
+pub fn gua_num_to_binary(num: u8) -> u8 {
+    GUA_BINARY[((num.clamp(1, 8)) - 1) as usize]
+}
+
+/// 三爻二进制转卦数 (1-8)
+pub fn binary_to_gua_num(binary: u8) -> u8 {
+    let binary = binary & 0b111;
+    GUA_BINARY.iter().position(|b| *b == binary).map(|i| i as u8 + 1).unwrap_or(8)
+}
+
+/// 公历时间起卦
+///
+/// - 上卦数 = (年份后两位 + 月 + 日) % 8
+/// - 下卦数 = (年份后两位 + 月 + 日 + 小时) % 8
+/// - 动爻数 = (年份后两位 + 月 + 日 + 小时) % 6
+pub fn divine_by_gregorian_datetime(year: u32, month: u8, day: u8, hour: u8) -> (u8, u8, u8) {
+    let base = (year % 100) + month as u32 + day as u32;
+    let total = base + hour as u32;
+
+    (calc_gua_num(base), calc_gua_num(total), calc_dong_yao(total))
+}
+
+/// Unix 时间戳（北京时间）起卦
+pub fn divine_by_timestamp(timestamp: u64) -> (u8, u8, u8) {
+    let (year, month, day, hour) = crate::calendar::timestamp_to_gregorian(timestamp);
+    divine_by_gregorian_datetime(year, month, day, hour)
+}
+
+/// 双数起卦
+///
+/// - 上卦数 = num1 % 8，下卦数 = num2 % 8
+/// - 动爻数 = (num1 + num2 + 时支数) % 6
+pub fn divine_by_numbers(num1: u16, num2: u16, hour_zhi_num: u8) -> (u8, u8, u8) {
+    (
+        calc_gua_num(num1 as u32),
+        calc_gua_num(num2 as u32),
+        calc_dong_yao(num1 as u32 + num2 as u32 + hour_zhi_num as u32),
+    )
+}
+
+/// 单数起卦
+///
+/// 将多位数字拆为前后两半（奇数位时后半多一位），
+/// 前半各位之和定上卦，后半各位之和定下卦，两者之和加时支数定动爻。
+pub fn divine_by_single_number(number: u32, hour_zhi_num: u8) -> (u8, u8, u8) {
+    let mut digits = [0u8; 10];
+    let mut count = 0usize;
+    let mut n = number;
+    while n > 0 && count < 10 {
+        digits[count] = (n % 10) as u8;
+        n /= 10;
+        count += 1;
+    }
+    if count == 0 {
+        count = 1;
+    }
+    // 逆序存储转为高位在前
+    digits[..count].reverse();
+
+    if count == 1 {
+        let single = digits[0] as u32;
+        return (calc_gua_num(single), calc_gua_num(single), calc_dong_yao(single * 2 + hour_zhi_num as u32));
+    }
+
+    let split = count / 2;
+    let first: u32 = digits[..split].iter().map(|d| *d as u32).sum();
+    let second: u32 = digits[split..count].iter().map(|d| *d as u32).sum();
+
+    (calc_gua_num(first), calc_gua_num(second), calc_dong_yao(first + second + hour_zhi_num as u32))
+}
+
+/// 随机起卦（使用种子前三个字节）
+pub fn divine_by_random(random_seed: &[u8; 32]) -> (u8, u8, u8) {
+    (
+        calc_gua_num(random_seed[0] as u32),
+        calc_gua_num(random_seed[1] as u32),
+        calc_dong_yao(random_seed[2] as u32),
+    )
+}
+
+/// 链摇起卦
+///
+/// `yaos` 为初爻到上爻的阴阳值（0=阴，1=阳），动爻取最后一次摇卦时间戳的毫秒部分。
+pub fn divine_by_shake(yaos: &[u8; 6], last_timestamp: u64) -> (u8, u8, u8) {
+    let xia_binary = ((yaos[2] & 1) << 2) | ((yaos[1] & 1) << 1) | (yaos[0] & 1);
+    let shang_binary = ((yaos[5] & 1) << 2) | ((yaos[4] & 1) << 1) | (yaos[3] & 1);
+
+    (
+        binary_to_gua_num(shang_binary),
+        binary_to_gua_num(xia_binary),
+        calc_dong_yao((last_timestamp % 1000) as u32 + 1),
+    )
+}
+
+/// 计算变卦：动爻阴阳互变
+///
+/// # 返回
+/// - (变卦上卦数, 变卦下卦数)
+pub fn bian_gua(shang: u8, xia: u8, dong_yao: u8) -> (u8, u8) {
+    let full = (gua_num_to_binary(shang) << 3) | gua_num_to_binary(xia);
+    let flipped = full ^ (1 << (dong_yao.clamp(1, 6) - 1));
+
+    (binary_to_gua_num(flipped >> 3), binary_to_gua_num(flipped))
+}
+
+/// 计算互卦：上卦取三四五爻，下卦取二三四爻
+///
+/// # 返回
+/// - (互卦上卦数, 互卦下卦数)
+pub fn hu_gua(shang: u8, xia: u8) -> (u8, u8) {
+    let full = (gua_num_to_binary(shang) << 3) | gua_num_to_binary(xia);
+
+    (binary_to_gua_num(full >> 2), binary_to_gua_num(full >> 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_roundtrip() {
+        for num in 1..=8 {
+            assert_eq!(binary_to_gua_num(gua_num_to_binary(num)), num);
+        }
+    }
+
+    #[test]
+    fn test_gregorian_datetime() {
+        // 2024年12月24日14时：上卦震(4)，下卦兑(2)，动爻2
+        assert_eq!(divine_by_gregorian_datetime(2024, 12, 24, 14), (4, 2, 2));
+    }
+
+    #[test]
+    fn test_single_number() {
+        // 38271：前半 3+8=11 → 离(3)，后半 2+7+1=10 → 兑(2)
+        let (shang, xia, _) = divine_by_single_number(38271, 1);
+        assert_eq!((shang, xia), (3, 2));
+    }
+
+    #[test]
+    fn test_bian_and_hu_gua() {
+        // 乾为天初爻动 → 天风姤（上乾下巽）
+        assert_eq!(bian_gua(1, 1, 1), (1, 5));
+        // 乾为天互卦仍为乾
+        assert_eq!(hu_gua(1, 1), (1, 1));
+        // 坤为地五爻动 → 水地比（上坎下坤）
+        assert_eq!(bian_gua(8, 8, 5), (6, 8));
+    }
+}
@@ -0,0 +1,346 @@
+//! # 奇门遁甲排盘核心算法
+//!
+//! 链上 `pallet-qimen` 与 TEE Enclave 共用的排盘实现，pallet 的 `algorithm` 模块只做枚举映射：
+//! 节气定阴阳遁 → 三元定局 → 地盘三奇六仪 → 旬首定值符值使 →
+//! 天盘九星、人盘八门、神盘八神。
+//!
+//! 编号约定与链上枚举一致：
+//! - 天干索引 0-9（甲-癸）、地支索引 0-11（子-亥）
+//! - 节气索引 0-23，从冬至开始（链上 `JieQi`）
+//! - 九星 1-9（天蓬-天英）、八门 1-8（休死伤杜景开惊生）、八神 1-8（值符-九天）
+//! - 宫位 1-9（坎坤震巽中乾兑艮离）
+
+use crate::calendar::timestamp_to_gregorian;
+use crate::jieqi::find_current_jieqi;
+use crate::sizhu::{sizhu_index_from_timestamp, ZiShiMode};
+
+/// 阳遁局数表（冬至-芒种 × 上中下三元）
+pub const YANG_DUN_JU: [[u8; 3]; 12] = [
+    [1, 7, 4], // 冬至
+    [2, 8, 5], // 小寒
+    [3, 9, 6], // 大寒
+    [8, 5, 2], // 立春
+    [9, 6, 3], // 雨水
+    [1, 7, 4], // 惊蛰
+    [3, 9, 6], // 春分
+    [4, 1, 7], // 清明
+    [5, 2, 8], // 谷雨
+    [4, 1, 7], // 立夏
+    [5, 2, 8], // 小满
+    [6, 3, 9], // 芒种
+];
+
+/// 阴遁局数表（夏至-大雪 × 上中下三元）
+pub const YIN_DUN_JU: [[u8; 3]; 12] = [
+    [9, 3, 6], // 夏至
+    [8, 2, 5], // 小暑
+    [7, 1, 4], // 大暑
+    [2, 5, 8], // 立秋
+    [1, 4, 7], // 处暑
+    [9, 3, 6], // 白露
+    [7, 1, 4], // 秋分
+    [6, 9, 3], // 寒露
+    [5, 8, 2], // 霜降
+    [6, 9, 3], // 立冬
+    [5, 8, 2], // 小雪
+    [4, 7, 1], // 大雪
+];
+
+/// 阳遁地盘三奇六仪（1-9局 × 1-9宫，天干索引）
+///
+/// 每局依次为：戊己庚辛壬癸丁丙乙，戊落宫位 = 局数
+pub const DI_YANG_QI_YI: [[u8; 9]; 9] = [
+    [4, 5, 6, 7, 8, 9, 3, 2, 1],
+    [1, 4, 5, 6, 7, 8, 9, 3, 2],
+    [2, 1, 4, 5, 6, 7, 8, 9, 3],
+    [3, 2, 1, 4, 5, 6, 7, 8, 9],
+    [9, 3, 2, 1, 4, 5, 6, 7, 8],
+    [8, 9, 3, 2, 1, 4, 5, 6, 7],
+    [7, 8, 9, 3, 2, 1, 4, 5, 6],
+    [6, 7, 8, 9, 3, 2, 1, 4, 5],
+    [5, 6, 7, 8, 9, 3, 2, 1, 4],
+];
+
+/// 阴遁地盘三奇六仪（1-9局 × 1-9宫，天干索引）
+///
+/// 每局依次为：戊乙丙丁癸壬辛庚己，戊落宫位 = 局数
+pub const DI_YIN_QI_YI: [[u8; 9]; 9] = [
+    [4, 1, 2, 3, 9, 8, 7, 6, 5],
+    [5, 4, 1, 2, 3, 9, 8, 7, 6],
+    [6, 5, 4, 1, 2, 3, 9, 8, 7],
+    [7, 6, 5, 4, 1, 2, 3, 9, 8],
+    [8, 7, 6, 5, 4, 1, 2, 3, 9],
+    [9, 8, 7, 6, 5, 4, 1, 2, 3],
+    [3, 9, 8, 7, 6, 5, 4, 1, 2],
+    [2, 3, 9, 8, 7, 6, 5, 4, 1],
+    [1, 2, 3, 9, 8, 7, 6, 5, 4],
+];
+
+/// 六甲旬首遁干（甲子戊、甲戌己、甲申庚、甲午辛、甲辰壬、甲寅癸）
+pub const XUN_SHOU_YI: [u8; 6] = [4, 5, 6, 7, 8, 9];
+
+/// 九宫飞布顺序（阳遁）
+pub const GONG_ORDER_YANG: [u8; 8] = [1, 8, 3, 4, 9, 2, 7, 6];
+
+/// 九宫飞布顺序（阴遁）
+pub const GONG_ORDER_YIN: [u8; 8] = [1, 6, 7, 2, 9, 4, 3, 8];
+
+/// 各宫原始八门（1-9宫，中宫无门记 0）
+const PALACE_MEN: [u8; 9] = [1, 2, 3, 4, 0, 6, 7, 8, 5];
+
+/// 天禽星编号
+const TIAN_QIN: u8 = 5;
+
+/// 单宫排盘结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QimenPalace {
+    /// 天盘干索引
+    pub tian_gan: u8,
+    /// 地盘干索引
+    pub di_gan: u8,
+    /// 九星编号（1-9）
+    pub xing: u8,
+    /// 八门编号（1-8），中宫为 None
+    pub men: Option<u8>,
+    /// 八神编号（1-8），中宫为 None
+    pub shen: Option<u8>,
+}
+
+/// 奇门遁甲盘
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QimenChart {
+    /// 节气索引（0-23，从冬至开始）
+    pub jie_qi: u8,
+    /// 是否阳遁
+    pub is_yang: bool,
+    /// 三元（0 上元、1 中元、2 下元）
+    pub san_yuan: u8,
+    /// 局数（1-9）
+    pub ju_number: u8,
+    /// 值符星编号
+    pub zhi_fu_xing: u8,
+    /// 值使门编号
+    pub zhi_shi_men: u8,
+    /// 坎一宫到离九宫
+    pub palaces: [QimenPalace; 9],
+}
+
+impl QimenChart {
+    /// 排盘摘要字节：节气、阴阳遁、三元、局数、值符、值使，
+    /// 随后每宫依次为天盘干、地盘干、九星、八门、八神（中宫门神记 0）
+    pub fn to_bytes(&self) -> [u8; 51] {
+        let mut bytes = [0u8; 51];
+        bytes[0] = self.jie_qi;
+        bytes[1] = self.is_yang as u8;
+        bytes[2] = self.san_yuan;
+        bytes[3] = self.ju_number;
+        bytes[4] = self.zhi_fu_xing;
+        bytes[5] = self.zhi_shi_men;
+        for (i, palace) in self.palaces.iter().enumerate() {
+            let offset = 6 + i * 5;
+            bytes[offset] = palace.tian_gan;
+            bytes[offset + 1] = palace.di_gan;
+            bytes[offset + 2] = palace.xing;
+            bytes[offset + 3] = palace.men.unwrap_or(0);
+            bytes[offset + 4] = palace.shen.unwrap_or(0);
+        }
+        bytes
+    }
+}
+
+/// 三元（按节气内天数）：1-5 上元，6-10 中元，其余下元
+pub fn calc_san_yuan(day_in_jieqi: u8) -> u8 {
+    match day_in_jieqi {
+        0..=5 => 0,
+        6..=10 => 1,
+        _ => 2,
+    }
+}
+
+/// 局数（节气索引从冬至开始，0-11 阳遁、12-23 阴遁）
+pub fn calc_ju_number(jie_qi: u8, san_yuan: u8) -> u8 {
+    let row = (jie_qi % 12) as usize;
+    let col = san_yuan.min(2) as usize;
+    if jie_qi < 12 {
+        YANG_DUN_JU[row][col]
+    } else {
+        YIN_DUN_JU[row][col]
+    }
+}
+
+/// 地盘三奇六仪（索引 0-8 对应坎一宫到离九宫）
+pub fn get_di_pan(ju_number: u8, is_yang: bool) -> [u8; 9] {
+    let ju = ju_number.saturating_sub(1).min(8) as usize;
+    if is_yang {
+        DI_YANG_QI_YI[ju]
+    } else {
+        DI_YIN_QI_YI[ju]
+    }
+}
+
+/// 时干支所在旬的遁干
+pub fn get_xun_shou(gan: u8, zhi: u8) -> u8 {
+    let sexagenary = (gan as u16 * 6 + zhi as u16 * 5) % 60;
+    XUN_SHOU_YI[(sexagenary / 10) as usize]
+}
+
+/// 天干在地盘的落宫（1-9）
+pub fn find_gan_in_di_pan(gan: u8, di_pan: &[u8; 9]) -> Option<u8> {
+    di_pan.iter().position(|&g| g == gan).map(|i| (i + 1) as u8)
+}
+
+fn gong_order(is_yang: bool) -> &'static [u8; 8] {
+    if is_yang {
+        &GONG_ORDER_YANG
+    } else {
+        &GONG_ORDER_YIN
+    }
+}
+
+/// 从起始宫开始按飞布顺序依次落宫，返回 (序号, 宫位下标)
+fn fly_from(start_gong: u8, is_yang: bool) -> impl Iterator<Item = (usize, usize)> {
+    let order = gong_order(is_yang);
+    let start = order.iter().position(|&g| g == start_gong).unwrap_or(0);
+    (0..8).map(move |i| (i, order[(start + i) % 8].saturating_sub(1) as usize))
+}
+
+/// 宫位原始八门（1-9宫），中宫无门取休门
+pub fn palace_men(gong: u8) -> u8 {
+    match PALACE_MEN[gong.saturating_sub(1).min(8) as usize] {
+        0 => 1,
+        men => men,
+    }
+}
+
+/// 天盘九星：值符星从起飞宫开始按阴阳遁方向排布，中宫恒为天禽
+pub fn distribute_xing(zhi_fu_xing: u8, start_gong: u8, is_yang: bool) -> [u8; 9] {
+    let mut xing_pan = [TIAN_QIN; 9];
+    for (i, idx) in fly_from(start_gong, is_yang) {
+        xing_pan[idx] = ((zhi_fu_xing.saturating_sub(1) as usize + i) % 8) as u8 + 1;
+    }
+    xing_pan[4] = TIAN_QIN;
+    xing_pan
+}
+
+/// 人盘八门：值使门从起飞宫开始按阴阳遁方向排布，中宫无门
+pub fn distribute_men(zhi_shi_men: u8, start_gong: u8, is_yang: bool) -> [Option<u8>; 9] {
+    let mut men_pan = [None; 9];
+    for (i, idx) in fly_from(start_gong, is_yang) {
+        if idx != 4 {
+            men_pan[idx] = Some(((zhi_shi_men.saturating_sub(1) as usize + i) % 8) as u8 + 1);
+        }
+    }
+    men_pan
+}
+
+/// 神盘八神：值符神从起飞宫开始按阴阳遁方向排布，中宫无神
+pub fn distribute_shen(start_gong: u8, is_yang: bool) -> [Option<u8>; 9] {
+    let mut shen_pan = [None; 9];
+    for (i, idx) in fly_from(start_gong, is_yang) {
+        if idx != 4 {
+            shen_pan[idx] = Some(i as u8 + 1);
+        }
+    }
+    shen_pan
+}
+
+/// 按局数与起局干支排出九宫
+///
+/// 返回 (值符星, 值使门, 坎一宫到离九宫)。
+/// 时家奇门以时柱起局，日家/月家/年家奇门分别以日柱/月柱/年柱起局。
+pub fn arrange_palaces(ju_number: u8, is_yang: bool, gan: u8, zhi: u8) -> (u8, u8, [QimenPalace; 9]) {
+    let di_pan = get_di_pan(ju_number, is_yang);
+
+    // 旬首遁干落宫的原始星门即值符、值使
+    let xun_shou_gong = find_gan_in_di_pan(get_xun_shou(gan % 10, zhi % 12), &di_pan).unwrap_or(1);
+    let zhi_fu_xing = xun_shou_gong;
+    let zhi_shi_men = palace_men(xun_shou_gong);
+
+    // 起局干寄宫即值符、值使、八神的起飞宫
+    let start_gong = find_gan_in_di_pan(gan % 10, &di_pan).unwrap_or(1);
+    let xing_pan = distribute_xing(zhi_fu_xing, start_gong, is_yang);
+    let men_pan = distribute_men(zhi_shi_men, start_gong, is_yang);
+    let shen_pan = distribute_shen(start_gong, is_yang);
+
+    // 天盘干随星而行（取星原始宫位的地盘干）
+    let mut palaces = [QimenPalace { tian_gan: 0, di_gan: 0, xing: TIAN_QIN, men: None, shen: None }; 9];
+    for (i, palace) in palaces.iter_mut().enumerate() {
+        let xing = xing_pan[i];
+        *palace = QimenPalace {
+            tian_gan: di_pan[(xing - 1) as usize],
+            di_gan: di_pan[i],
+            xing,
+            men: men_pan[i],
+            shen: shen_pan[i],
+        };
+    }
+
+    (zhi_fu_xing, zhi_shi_men, palaces)
+}
+
+/// 完整排盘
+///
+/// # 参数
+///
+/// - `jie_qi`: 节气索引（0-23，从冬至开始）
+/// - `day_in_jieqi`: 节气内第几天（1-15）
+/// - `hour_gan`/`hour_zhi`: 时柱干支索引
+pub fn qimen_chart(jie_qi: u8, day_in_jieqi: u8, hour_gan: u8, hour_zhi: u8) -> Option<QimenChart> {
+    if jie_qi >= 24 || hour_gan >= 10 || hour_zhi >= 12 {
+        return None;
+    }
+
+    let is_yang = jie_qi < 12;
+    let san_yuan = calc_san_yuan(day_in_jieqi);
+    let ju_number = calc_ju_number(jie_qi, san_yuan);
+    let (zhi_fu_xing, zhi_shi_men, palaces) = arrange_palaces(ju_number, is_yang, hour_gan, hour_zhi);
+
+    Some(QimenChart { jie_qi, is_yang, san_yuan, ju_number, zhi_fu_xing, zhi_shi_men, palaces })
+}
+
+/// 从 Unix 时间戳（北京时间）排盘
+///
+/// 节气取交节时刻精确计算，节气内天数超过 15 天时按第 15 天（下元）处理。
+pub fn qimen_chart_from_timestamp(timestamp: u64) -> Option<QimenChart> {
+    let (year, month, day, hour) = timestamp_to_gregorian(timestamp);
+    let (jieqi_index, day_in_jieqi) = find_current_jieqi(u16::try_from(year).ok()?, month, day, hour);
+    let sizhu = sizhu_index_from_timestamp(timestamp, ZiShiMode::Modern)?;
+
+    // 节气表从春分开始，链上 JieQi 从冬至开始
+    let jie_qi = (jieqi_index + 6) % 24;
+
+    qimen_chart(jie_qi, day_in_jieqi.min(15) as u8, sizhu.hour_gan, sizhu.hour_zhi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_di_pan_wu_at_ju() {
+        for ju in 1..=9u8 {
+            assert_eq!(find_gan_in_di_pan(4, &get_di_pan(ju, true)), Some(ju));
+            assert_eq!(find_gan_in_di_pan(4, &get_di_pan(ju, false)), Some(ju));
+        }
+    }
+
+    #[test]
+    fn test_ju_number() {
+        // 冬至上元阳遁一局，夏至上元阴遁九局
+        assert_eq!(calc_ju_number(0, 0), 1);
+        assert_eq!(calc_ju_number(12, 0), 9);
+        assert_eq!(calc_ju_number(3, 2), 2);
+    }
+
+    #[test]
+    fn test_chart_center_palace() {
+        let chart = qimen_chart(0, 1, 0, 0).unwrap();
+        assert_eq!(chart.palaces[4].xing, TIAN_QIN);
+        assert!(chart.palaces[4].men.is_none());
+        assert!(chart.palaces[4].shen.is_none());
+        // 甲子时：旬首戊在坎一宫，值符天蓬、值使休门
+        assert_eq!(chart.zhi_fu_xing, 1);
+        assert_eq!(chart.zhi_shi_men, 1);
+        assert!(qimen_chart(24, 1, 0, 0).is_none());
+    }
+}
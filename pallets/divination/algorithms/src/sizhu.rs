@@ -0,0 +1,241 @@
+//! # 四柱计算
+//!
+//! 年月日时四柱的核心算法，以天干索引（0-9）和地支索引（0-11）表示。
+//! 八字、奇门等模块的四柱均由此计算，链上与 Enclave 结果一致。
+
+use crate::calendar::calculate_days_between;
+use crate::jieqi::get_month_zhi_by_jieqi;
+
+/// 日柱基准日期：公元前720年1月1日 = 甲子日
+const BASE_YEAR: i32 = -720;
+const BASE_MONTH: u8 = 1;
+const BASE_DAY: u8 = 1;
+
+/// 子时归属模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZiShiMode {
+    /// 传统派：23:00-23:59 属于次日（早子时）
+    Traditional,
+    /// 现代派：23:00-23:59 属于当日
+    Modern,
+}
+
+/// 单柱干支（天干索引, 地支索引）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pillar {
+    /// 天干索引 (0-9)
+    pub gan: u8,
+    /// 地支索引 (0-11)
+    pub zhi: u8,
+}
+
+impl Pillar {
+    /// 从六十甲子序号创建
+    pub fn from_sexagenary(index: u8) -> Option<Self> {
+        if index < 60 {
+            Some(Self { gan: index % 10, zhi: index % 12 })
+        } else {
+            None
+        }
+    }
+
+    /// 六十甲子序号 (0-59)
+    pub fn sexagenary(&self) -> u8 {
+        // 满足 x ≡ gan (mod 10) 且 x ≡ zhi (mod 12) 的最小非负整数
+        (0..6u8).map(|i| i * 10 + self.gan).find(|c| c % 12 == self.zhi).unwrap_or(0)
+    }
+
+    /// 下一个干支
+    pub fn next(&self) -> Self {
+        Self { gan: (self.gan + 1) % 10, zhi: (self.zhi + 1) % 12 }
+    }
+}
+
+/// 四柱索引（8 字节）
+///
+/// 字段顺序与链上 `SiZhuIndex` / `QimenSiZhuIndex` 一致，
+/// `to_bytes()` 即其 SCALE 编码。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SiZhuIndex {
+    pub year_gan: u8,
+    pub year_zhi: u8,
+    pub month_gan: u8,
+    pub month_zhi: u8,
+    pub day_gan: u8,
+    pub day_zhi: u8,
+    pub hour_gan: u8,
+    pub hour_zhi: u8,
+}
+
+impl SiZhuIndex {
+    /// 由四柱构建
+    pub fn from_pillars(year: Pillar, month: Pillar, day: Pillar, hour: Pillar) -> Self {
+        Self {
+            year_gan: year.gan,
+            year_zhi: year.zhi,
+            month_gan: month.gan,
+            month_zhi: month.zhi,
+            day_gan: day.gan,
+            day_zhi: day.zhi,
+            hour_gan: hour.gan,
+            hour_zhi: hour.zhi,
+        }
+    }
+
+    /// 链上索引字节
+    pub fn to_bytes(&self) -> [u8; 8] {
+        [
+            self.year_gan,
+            self.year_zhi,
+            self.month_gan,
+            self.month_zhi,
+            self.day_gan,
+            self.day_zhi,
+            self.hour_gan,
+            self.hour_zhi,
+        ]
+    }
+}
+
+/// 计算日柱
+///
+/// 以公元前720年1月1日（甲子日）为基准，天数差对60取模。
+pub fn day_pillar(year: u16, month: u8, day: u8) -> Option<Pillar> {
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let days_diff = calculate_days_between(BASE_YEAR, BASE_MONTH, BASE_DAY, year as i32, month, day);
+    let index = days_diff.rem_euclid(60) as u8;
+
+    Pillar::from_sexagenary(index)
+}
+
+/// 计算年柱（以立春为界，按小时精确判断）
+pub fn year_pillar(year: u16, month: u8, day: u8, hour: u8) -> Option<Pillar> {
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 {
+        return None;
+    }
+
+    let (_, bazi_year) = get_month_zhi_by_jieqi(year, month, day, hour);
+
+    // 公元4年 = 甲子年
+    Some(Pillar {
+        gan: (bazi_year as i32 - 4).rem_euclid(10) as u8,
+        zhi: (bazi_year as i32 - 4).rem_euclid(12) as u8,
+    })
+}
+
+/// 计算月柱（月支按节气，月干按五虎遁）
+pub fn month_pillar(year: u16, month: u8, day: u8, hour: u8, year_gan: u8) -> Option<Pillar> {
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || year_gan >= 10 || hour >= 24 {
+        return None;
+    }
+
+    let (month_zhi, _) = get_month_zhi_by_jieqi(year, month, day, hour);
+
+    // 五虎遁：甲己丙、乙庚戊、丙辛庚、丁壬壬、戊癸甲（寅月天干）
+    let yin_month_gan = ((year_gan % 5) * 2 + 2) % 10;
+    let offset = (month_zhi + 10) % 12;
+
+    Some(Pillar { gan: (yin_month_gan + offset) % 10, zhi: month_zhi })
+}
+
+/// 计算时柱（时干按五鼠遁）
+///
+/// # 返回
+/// - `Some((Pillar, is_next_day))`：传统派 23 时返回 `is_next_day = true`，
+///   调用方需改用次日日干重新计算
+pub fn hour_pillar(hour: u8, day_gan: u8, zishi_mode: ZiShiMode) -> Option<(Pillar, bool)> {
+    if hour >= 24 || day_gan >= 10 {
+        return None;
+    }
+
+    let (hour_zhi, is_next_day) = if hour == 23 {
+        (0, zishi_mode == ZiShiMode::Traditional)
+    } else {
+        (hour / 2, false)
+    };
+
+    // 五鼠遁：甲己甲、乙庚丙、丙辛戊、丁壬庚、戊癸壬（子时天干）
+    let zi_hour_gan = (day_gan % 5) * 2;
+
+    Some((Pillar { gan: (zi_hour_gan + hour_zhi) % 10, zhi: hour_zhi }, is_next_day))
+}
+
+/// 计算四柱
+///
+/// 年柱、月柱按正午判断节气（与链上八字排盘一致），
+/// 传统派子时自动切换到次日日柱。
+pub fn calculate_sizhu(
+    year: u16,
+    month: u8,
+    day: u8,
+    hour: u8,
+    zishi_mode: ZiShiMode,
+) -> Option<(Pillar, Pillar, Pillar, Pillar)> {
+    let day_gz = day_pillar(year, month, day)?;
+    let year_gz = year_pillar(year, month, day, 12)?;
+    let month_gz = month_pillar(year, month, day, 12, year_gz.gan)?;
+    let (hour_gz, is_next_day) = hour_pillar(hour, day_gz.gan, zishi_mode)?;
+
+    if is_next_day {
+        let next_day = day_gz.next();
+        let (next_hour, _) = hour_pillar(hour, next_day.gan, zishi_mode)?;
+        Some((year_gz, month_gz, next_day, next_hour))
+    } else {
+        Some((year_gz, month_gz, day_gz, hour_gz))
+    }
+}
+
+/// 计算四柱索引
+pub fn sizhu_index(year: u16, month: u8, day: u8, hour: u8, zishi_mode: ZiShiMode) -> Option<SiZhuIndex> {
+    let (y, m, d, h) = calculate_sizhu(year, month, day, hour, zishi_mode)?;
+    Some(SiZhuIndex::from_pillars(y, m, d, h))
+}
+
+/// 从 Unix 时间戳（北京时间）计算四柱索引
+pub fn sizhu_index_from_timestamp(timestamp: u64, zishi_mode: ZiShiMode) -> Option<SiZhuIndex> {
+    let (year, month, day, hour) = crate::calendar::timestamp_to_gregorian(timestamp);
+    sizhu_index(u16::try_from(year).ok()?, month, day, hour, zishi_mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_pillar_cycle() {
+        let day = day_pillar(2000, 1, 1).unwrap();
+        assert_eq!(day_pillar(2000, 1, 2), Some(day.next()));
+        // 六十日一循环
+        assert_eq!(day_pillar(2000, 3, 1), Some(day));
+        assert!(day_pillar(2000, 13, 1).is_none());
+    }
+
+    #[test]
+    fn test_year_month_hour_2000_01_01() {
+        // 立春前属己卯年，子月丙子，午时
+        let index = sizhu_index(2000, 1, 1, 12, ZiShiMode::Modern).unwrap();
+        assert_eq!((index.year_gan, index.year_zhi), (5, 3));
+        assert_eq!((index.month_gan, index.month_zhi), (2, 0));
+        assert_eq!(index.hour_zhi, 6);
+    }
+
+    #[test]
+    fn test_zishi_modes() {
+        let day = day_pillar(2000, 1, 1).unwrap();
+        let modern = sizhu_index(2000, 1, 1, 23, ZiShiMode::Modern).unwrap();
+        let traditional = sizhu_index(2000, 1, 1, 23, ZiShiMode::Traditional).unwrap();
+        assert_eq!((modern.day_gan, modern.day_zhi), (day.gan, day.zhi));
+        assert_eq!((traditional.day_gan, traditional.day_zhi), (day.next().gan, day.next().zhi));
+        assert_eq!(traditional.hour_zhi, 0);
+    }
+
+    #[test]
+    fn test_pillar_sexagenary_roundtrip() {
+        for i in 0..60u8 {
+            assert_eq!(Pillar::from_sexagenary(i).unwrap().sexagenary(), i);
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! # 塔罗核心算法
+//!
+//! 牌阵张数与数字抽牌（78 张牌，ID 0-77）。
+
+use alloc::vec::Vec;
+
+/// 塔罗牌总数
+pub const TOTAL_CARDS: u8 = 78;
+
+/// 塔罗索引（3 字节）
+///
+/// 字段顺序与链上 `TarotIndex` 一致。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TarotIndex {
+    pub spread_type: u8,
+    pub card_count: u8,
+    pub first_card: u8,
+}
+
+impl TarotIndex {
+    /// 链上索引字节
+    pub fn to_bytes(&self) -> [u8; 3] {
+        [self.spread_type, self.card_count, self.first_card]
+    }
+}
+
+/// 牌阵张数
+pub fn get_spread_card_count(spread_type: u8) -> u8 {
+    match spread_type {
+        0 => 1,  // 单张
+        1 => 3,  // 三牌阵
+        2 => 5,  // 五牌阵
+        3 => 10, // 凯尔特十字
+        4 => 7,  // 七星阵
+        _ => 3,  // 默认三牌
+    }
+}
+
+/// 从数字生成不重复的牌列表
+pub fn generate_cards_from_number(num: u32, count: u8) -> Vec<u8> {
+    let mut cards = Vec::with_capacity(count as usize);
+    let mut used = [false; TOTAL_CARDS as usize];
+
+    for i in 0..count.min(TOTAL_CARDS) {
+        let seed = num.wrapping_add(i as u32 * 17);
+        let mut card_id = (seed % TOTAL_CARDS as u32) as u8;
+
+        while used[card_id as usize] {
+            card_id = (card_id + 1) % TOTAL_CARDS;
+        }
+        used[card_id as usize] = true;
+        cards.push(card_id);
+    }
+
+    cards
+}
+
+/// 从数字生成正逆位列表
+pub fn generate_reversed_from_number(num: u32, count: u8) -> Vec<bool> {
+    (0..count).map(|i| ((num >> (i % 32)) & 1) == 1).collect()
+}
+
+/// 从 32 字节种子取抽牌数字（前 4 字节，小端）
+///
+/// 与数字抽牌共用同一套规则：调用方把种子转换后的数字提交上链即可复现结果。
+pub fn seed_to_number(seed: &[u8; 32]) -> u32 {
+    u32::from_le_bytes([seed[0], seed[1], seed[2], seed[3]])
+}
+
+/// 数字抽牌的链上索引
+pub fn tarot_index(spread_type: u8, num: u32) -> TarotIndex {
+    let count = get_spread_card_count(spread_type);
+    let cards = generate_cards_from_number(num, count);
+
+    TarotIndex {
+        spread_type,
+        card_count: cards.len() as u8,
+        first_card: cards.first().copied().unwrap_or(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cards_unique() {
+        let cards = generate_cards_from_number(12345, 10);
+        assert_eq!(cards.len(), 10);
+        for (i, a) in cards.iter().enumerate() {
+            assert!(*a < TOTAL_CARDS);
+            assert!(!cards[i + 1..].contains(a));
+        }
+    }
+
+    #[test]
+    fn test_tarot_index() {
+        let index = tarot_index(1, 100);
+        assert_eq!(index.to_bytes(), [1, 3, 22]);
+    }
+
+    #[test]
+    fn test_seed_to_number() {
+        let mut seed = [0xffu8; 32];
+        seed[..4].copy_from_slice(&123456u32.to_le_bytes());
+        assert_eq!(seed_to_number(&seed), 123456);
+    }
+}
@@ -0,0 +1,170 @@
+//! # 紫微斗数核心算法
+//!
+//! 命宫、身宫、五行局与紫微/天府定位。
+//! 宫位以地支索引表示（子=0 … 亥=11）。
+
+/// 紫微星定位表（行：农历日 1-30；列：水二局、木三局、金四局、土五局、火六局）
+///
+/// 数据来源：《紫微斗数全书》安星诀
+const ZIWEI_TABLE: [[u8; 5]; 30] = [
+    [1, 4, 11, 6, 9],
+    [2, 1, 4, 11, 6],
+    [2, 2, 1, 4, 11],
+    [3, 5, 2, 1, 4],
+    [3, 2, 0, 2, 1],
+    [4, 3, 5, 7, 2],
+    [4, 6, 2, 0, 10],
+    [5, 3, 3, 5, 7],
+    [5, 4, 1, 2, 0],
+    [6, 7, 6, 3, 5],
+    [6, 4, 3, 8, 2],
+    [7, 5, 4, 1, 3],
+    [7, 8, 2, 6, 11],
+    [8, 5, 7, 3, 8],
+    [8, 6, 4, 4, 1],
+    [9, 9, 5, 9, 6],
+    [9, 6, 3, 2, 3],
+    [10, 7, 8, 7, 4],
+    [10, 10, 5, 10, 0],
+    [11, 7, 6, 5, 9],
+    [11, 8, 4, 10, 2],
+    [0, 11, 9, 3, 7],
+    [0, 8, 6, 8, 4],
+    [1, 9, 7, 5, 5],
+    [1, 0, 5, 6, 1],
+    [2, 9, 10, 11, 10],
+    [2, 10, 7, 4, 3],
+    [3, 1, 8, 9, 8],
+    [3, 10, 6, 6, 5],
+    [4, 11, 11, 7, 6],
+];
+
+/// 紫微斗数索引（5 字节）
+///
+/// 字段顺序与链上 `ZiweiIndex` 一致。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZiweiIndex {
+    pub ming_gong: u8,
+    pub shen_gong: u8,
+    pub ziwei_pos: u8,
+    pub tianfu_pos: u8,
+    /// 五行局数 (2-6)
+    pub wu_xing_ju: u8,
+}
+
+impl ZiweiIndex {
+    /// 链上索引字节
+    pub fn to_bytes(&self) -> [u8; 5] {
+        [self.ming_gong, self.shen_gong, self.ziwei_pos, self.tianfu_pos, self.wu_xing_ju]
+    }
+}
+
+/// 农历年天干索引（公元4年为甲年）
+pub fn lunar_year_gan(lunar_year: u16) -> u8 {
+    ((lunar_year as i32 - 4).rem_euclid(10)) as u8
+}
+
+/// 命宫：寅宫起正月顺数至生月，再逆数至生时
+pub fn calculate_ming_gong(lunar_month: u8, hour_zhi: u8) -> u8 {
+    let month_pos = (lunar_month.clamp(1, 12) + 1) % 12;
+    (month_pos + 12 - hour_zhi % 12) % 12
+}
+
+/// 身宫：寅宫起正月顺数至生月，再顺数至生时
+pub fn calculate_shen_gong(lunar_month: u8, hour_zhi: u8) -> u8 {
+    let month_pos = (lunar_month.clamp(1, 12) + 1) % 12;
+    (month_pos + hour_zhi % 12) % 12
+}
+
+/// 五虎遁：根据年干推算宫位天干
+pub fn get_gong_gan(year_gan: u8, gong_pos: u8) -> u8 {
+    // 寅宫天干：甲己丙、乙庚戊、丙辛庚、丁壬壬、戊癸甲
+    let yin_gan = ((year_gan % 5) * 2 + 2) % 10;
+    let offset = (gong_pos % 12 + 10) % 12;
+    (yin_gan + offset) % 10
+}
+
+/// 六十甲子纳音五行局数
+///
+/// 返回局数：水二局、木三局、金四局、土五局、火六局
+pub fn na_yin_ju_shu(gan: u8, zhi: u8) -> u8 {
+    let jia_zi_idx = ((gan as u16 * 12 + zhi as u16) % 60) as u8;
+
+    match jia_zi_idx / 2 {
+        0 | 4 | 13 | 17 | 23 | 29 => 4,  // 金
+        1 | 5 | 8 | 12 | 16 | 21 => 6,   // 火
+        2 | 9 | 14 | 18 | 24 | 28 => 3,  // 木
+        3 | 7 | 11 | 15 | 19 | 22 => 5,  // 土
+        _ => 2,                          // 水
+    }
+}
+
+/// 五行局：命宫干支的纳音五行
+pub fn calculate_wu_xing_ju(year_gan: u8, ming_gong_pos: u8) -> u8 {
+    na_yin_ju_shu(get_gong_gan(year_gan, ming_gong_pos), ming_gong_pos % 12)
+}
+
+/// 根据农历日和局数定紫微星位置
+pub fn calculate_ziwei_position(lunar_day: u8, ju_shu: u8) -> u8 {
+    let day_idx = (lunar_day.saturating_sub(1) % 30) as usize;
+    let ju_idx = match ju_shu {
+        3 => 1,
+        4 => 2,
+        5 => 3,
+        6 => 4,
+        _ => 0,
+    };
+    ZIWEI_TABLE[day_idx][ju_idx]
+}
+
+/// 天府星位置：与紫微关于寅申轴对称
+pub fn calculate_tianfu_position(ziwei_pos: u8) -> u8 {
+    (16 - ziwei_pos % 12) % 12
+}
+
+/// 计算紫微斗数链上索引
+pub fn ziwei_index(lunar_year: u16, lunar_month: u8, lunar_day: u8, hour_zhi: u8) -> ZiweiIndex {
+    let year_gan = lunar_year_gan(lunar_year);
+    let ming_gong = calculate_ming_gong(lunar_month, hour_zhi);
+    let shen_gong = calculate_shen_gong(lunar_month, hour_zhi);
+    let wu_xing_ju = calculate_wu_xing_ju(year_gan, ming_gong);
+    let ziwei_pos = calculate_ziwei_position(lunar_day, wu_xing_ju);
+    let tianfu_pos = calculate_tianfu_position(ziwei_pos);
+
+    ZiweiIndex { ming_gong, shen_gong, ziwei_pos, tianfu_pos, wu_xing_ju }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ming_shen_gong() {
+        // 正月子时：命身同宫于寅
+        assert_eq!(calculate_ming_gong(1, 0), 2);
+        assert_eq!(calculate_shen_gong(1, 0), 2);
+        // 正月卯时：命宫亥，身宫巳
+        assert_eq!(calculate_ming_gong(1, 3), 11);
+        assert_eq!(calculate_shen_gong(1, 3), 5);
+    }
+
+    #[test]
+    fn test_na_yin() {
+        // 甲子海中金 → 金四局
+        assert_eq!(na_yin_ju_shu(0, 0), 4);
+        // 局数只会落在 2-6
+        for gan in 0..10 {
+            for zhi in 0..12 {
+                assert!((2..=6).contains(&na_yin_ju_shu(gan, zhi)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_tianfu_symmetry() {
+        for pos in 0..12 {
+            let tianfu = calculate_tianfu_position(pos);
+            assert_eq!(calculate_tianfu_position(tianfu), pos);
+        }
+    }
+}
@@ -1,593 +1,8 @@
 //! # 节气精确计算模块
 //!
-//! 使用寿星天文历算法（VSOP87）计算精确节气时间。
-//! 该算法精度可达分钟级别，适用于八字排盘中的月柱判定。
+//! 使用寿星天文历算法（VSOP87）计算精确节气时间，精度可达分钟级别。
 //!
-//! ## 算法原理
-//!
-//! 1. 计算儒略日（Julian Day）
-//! 2. 使用VSOP87理论计算太阳黄经
-//! 3. 使用牛顿迭代法求解节气时刻
-//!
-//! ## 24节气与太阳黄经对应
-//!
-//! 春分(0°) → 清明(15°) → 谷雨(30°) → 立夏(45°) → ...
-//!
-//! ## 参考资料
-//!
-//! - 寿星天文历算法
-//! - VSOP87太阳位置理论
-//! - 《中国天文年历》
-
-use core::f64::consts::PI;
-use libm::{floor, round, sin};
-
-/// 弧度转角度
-const RAD_TO_DEG: f64 = 180.0 / PI;
-
-/// 角度转弧度
-const DEG_TO_RAD: f64 = PI / 180.0;
-
-/// J2000.0 历元儒略日
-const J2000: f64 = 2451545.0;
-
-/// 节气索引（从春分开始）
-/// 0:春分 1:清明 2:谷雨 3:立夏 4:小满 5:芒种
-/// 6:夏至 7:小暑 8:大暑 9:立秋 10:处暑 11:白露
-/// 12:秋分 13:寒露 14:霜降 15:立冬 16:小雪 17:大雪
-/// 18:冬至 19:小寒 20:大寒 21:立春 22:雨水 23:惊蛰
-///
-/// 八字中使用的"节"（奇数索引）作为月份分界：
-/// 立春(21)->寅月 惊蛰(23)->卯月 清明(1)->辰月 立夏(3)->巳月
-/// 芒种(5)->午月 小暑(7)->未月 立秋(9)->申月 白露(11)->酉月
-/// 寒露(13)->戌月 立冬(15)->亥月 大雪(17)->子月 小寒(19)->丑月
-
-/// 24节气名称（从春分开始，黄经0°）
-pub const JIEQI_NAMES: [&str; 24] = [
-    "春分", "清明", "谷雨", "立夏", "小满", "芒种",
-    "夏至", "小暑", "大暑", "立秋", "处暑", "白露",
-    "秋分", "寒露", "霜降", "立冬", "小雪", "大雪",
-    "冬至", "小寒", "大寒", "立春", "雨水", "惊蛰",
-];
-
-/// 12节（用于月柱划分）对应的节气索引
-/// 立春、惊蛰、清明、立夏、芒种、小暑、立秋、白露、寒露、立冬、大雪、小寒
-pub const JIE_INDICES: [u8; 12] = [21, 23, 1, 3, 5, 7, 9, 11, 13, 15, 17, 19];
-
-/// 节气对应的地支月
-/// 立春->寅(2), 惊蛰->卯(3), 清明->辰(4), 立夏->巳(5),
-/// 芒种->午(6), 小暑->未(7), 立秋->申(8), 白露->酉(9),
-/// 寒露->戌(10), 立冬->亥(11), 大雪->子(0), 小寒->丑(1)
-pub const JIE_TO_MONTH_ZHI: [u8; 12] = [2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 0, 1];
-
-/// 节气时间结构
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct JieQiTime {
-    /// 年
-    pub year: u16,
-    /// 月
-    pub month: u8,
-    /// 日
-    pub day: u8,
-    /// 时
-    pub hour: u8,
-    /// 分
-    pub minute: u8,
-}
-
-/// 计算指定年份的所有24节气时间
-///
-/// # 参数
-///
-/// - `year`: 公历年份（1900-2100）
-///
-/// # 返回
-///
-/// 24个节气的精确时间数组（从春分开始）
-///
-/// # 注意
-///
-/// 节气索引从春分(0)开始，按太阳黄经每15度递增：
-/// - 0: 春分 (0°)
-/// - 1: 清明 (15°)
-/// - ...
-/// - 18: 冬至 (270°)
-/// - 19: 小寒 (285°) - 通常在次年1月
-/// - 20: 大寒 (300°) - 通常在次年1月
-/// - 21: 立春 (315°) - 通常在次年2月
-/// - 22: 雨水 (330°)
-/// - 23: 惊蛰 (345°)
-///
-/// 对于索引19-23的节气，实际日期在当年年底或次年年初。
-/// 为了方便使用，本函数返回的是"属于该农历年"的节气，
-/// 即小寒到惊蛰(19-23)返回的是下一个公历年的日期。
-pub fn calculate_year_jieqi(year: u16) -> [JieQiTime; 24] {
-    let mut result = [JieQiTime { year: 0, month: 0, day: 0, hour: 0, minute: 0 }; 24];
-
-    for i in 0..24 {
-        // 节气对应的太阳黄经度数
-        let longitude = (i as f64) * 15.0;
-
-        // 对于索引19-23（小寒到惊蛰），需要使用下一年来计算
-        // 因为这些节气的黄经是285-345度，春分后已经过了
-        let calc_year = if i >= 19 {
-            year as i32 + 1
-        } else {
-            year as i32
-        };
-
-        let jd = calculate_jieqi_jd(calc_year, longitude);
-        result[i] = jd_to_datetime(jd);
-    }
-
-    result
-}
-
-/// 计算指定节气的儒略日
-///
-/// # 参数
-///
-/// - `year`: 公历年份
-/// - `longitude`: 太阳黄经（度）
-///
-/// # 返回
-///
-/// 节气时刻的儒略日（北京时间）
-fn calculate_jieqi_jd(year: i32, longitude: f64) -> f64 {
-    // 估算节气的初始儒略日
-    let jd0 = estimate_jieqi_jd(year, longitude);
-
-    // 使用牛顿迭代法精确计算
-    let mut jd = jd0;
-    for _ in 0..50 {
-        let sun_lon = calculate_sun_longitude(jd);
-        let mut diff = longitude - sun_lon;
-
-        // 处理角度跨越360度的情况
-        if diff > 180.0 {
-            diff -= 360.0;
-        } else if diff < -180.0 {
-            diff += 360.0;
-        }
-
-        // 太阳每天约移动1度
-        let delta = diff / 360.0 * 365.2422;
-        jd += delta;
-
-        if delta.abs() < 0.00001 {
-            break;
-        }
-    }
-
-    // 转换为北京时间（UTC+8）
-    jd + 8.0 / 24.0
-}
-
-/// 估算节气的初始儒略日
-///
-/// 对于小寒(285°)到惊蛰(345°)的节气，需要从上一年的春分开始计算
-/// 因为这些节气的黄经接近360°，从当年春分算会超过一年
-fn estimate_jieqi_jd(year: i32, longitude: f64) -> f64 {
-    // 对于黄经 >= 270° 的节气（冬至、小寒、大寒、立春、雨水、惊蛰）
-    // 需要从上一年的春分开始计算，否则会算到下一年
-    let (base_year, effective_longitude) = if longitude >= 270.0 {
-        // 从上一年春分开始，黄经保持不变
-        (year - 1, longitude)
-    } else {
-        (year, longitude)
-    };
-
-    // 春分点近似日期
-    let spring_equinox = gregorian_to_jd(base_year, 3, 21);
-
-    // 根据黄经差估算天数
-    let days = effective_longitude / 360.0 * 365.2422;
-
-    spring_equinox + days
-}
-
-/// 计算太阳黄经（简化的VSOP87算法）
-///
-/// 使用简化的太阳位置算法，精度约为0.01度
-fn calculate_sun_longitude(jd: f64) -> f64 {
-    // 儒略世纪数（从J2000.0起算）
-    let t = (jd - J2000) / 36525.0;
-
-    // 太阳平黄经（度）
-    let l0 = 280.46646 + 36000.76983 * t + 0.0003032 * t * t;
-
-    // 太阳平近点角（度）
-    let m = 357.52911 + 35999.05029 * t - 0.0001537 * t * t;
-    let m_rad = m * DEG_TO_RAD;
-
-    // 地球轨道离心率（用于更精确的计算，当前简化版本未使用）
-    let _e = 0.016708634 - 0.000042037 * t - 0.0000001267 * t * t;
-
-    // 太阳中心差（度）
-    let c = (1.914602 - 0.004817 * t - 0.000014 * t * t) * sin(m_rad)
-        + (0.019993 - 0.000101 * t) * sin(2.0 * m_rad)
-        + 0.000289 * sin(3.0 * m_rad);
-
-    // 太阳真黄经
-    let mut sun_lon = l0 + c;
-
-    // 黄经章动修正（简化）
-    let omega = 125.04 - 1934.136 * t;
-    let omega_rad = omega * DEG_TO_RAD;
-    sun_lon -= 0.00569 + 0.00478 * sin(omega_rad);
-
-    // 归一化到0-360度
-    sun_lon = sun_lon % 360.0;
-    if sun_lon < 0.0 {
-        sun_lon += 360.0;
-    }
-
-    sun_lon
-}
-
-/// 公历日期转儒略日
-fn gregorian_to_jd(year: i32, month: u8, day: u8) -> f64 {
-    let mut y = year;
-    let mut m = month as i32;
-
-    if m <= 2 {
-        y -= 1;
-        m += 12;
-    }
-
-    let a = y / 100;
-    let b = 2 - a + a / 4;
-
-        floor(365.25 * (y + 4716) as f64)
-        + floor(30.6001 * (m + 1) as f64)
-        + day as f64 + b as f64 - 1524.5
-}
-
-/// 儒略日转日期时间
-fn jd_to_datetime(jd: f64) -> JieQiTime {
-    let z = floor(jd + 0.5) as i32;
-    let f = jd + 0.5 - z as f64;
-
-    let a = if z < 2299161 {
-        z
-    } else {
-        let alpha = floor((z as f64 - 1867216.25) / 36524.25) as i32;
-        z + 1 + alpha - alpha / 4
-    };
-
-    let b = a + 1524;
-    let c = floor((b as f64 - 122.1) / 365.25) as i32;
-    let d = floor(365.25 * c as f64) as i32;
-    let e = floor((b - d) as f64 / 30.6001) as i32;
-
-    let day = b - d - floor(30.6001 * e as f64) as i32;
-    let month = if e < 14 { e - 1 } else { e - 13 };
-    let year = if month > 2 { c - 4716 } else { c - 4715 };
-
-    // 计算时分
-    let hours_f = f * 24.0;
-    let hour = floor(hours_f) as u8;
-    let minute = round((hours_f - hour as f64) * 60.0) as u8;
-
-    JieQiTime {
-        year: year as u16,
-        month: month as u8,
-        day: day as u8,
-        hour,
-        minute,
-    }
-}
-
-/// 判断指定日期时间属于哪个节气月
-///
-/// # 参数
-///
-/// - `year`: 公历年份
-/// - `month`: 公历月份
-/// - `day`: 公历日期
-/// - `hour`: 小时（0-23）
-///
-/// # 返回
-///
-/// - `(month_zhi, adjusted_year)`: 月支索引(0-11)和调整后的年份（用于年柱计算）
-///
-/// # 示例
-///
-/// ```ignore
-/// // 1990年11月29日12时 -> 亥月(11)
-/// let (month_zhi, adj_year) = get_month_zhi_by_jieqi(1990, 11, 29, 12);
-/// assert_eq!(month_zhi, 11); // 亥月
-/// ```
-pub fn get_month_zhi_by_jieqi(year: u16, month: u8, day: u8, hour: u8) -> (u8, u16) {
-    // 计算当前年份和前一年的节气
-    // 注意：calculate_year_jieqi(Y) 返回的节气：
-    // - 索引0-18: Y年的春分到冬至
-    // - 索引19-23: Y+1年的小寒到惊蛰
-    //
-    // 所以要获取2024年2月的立春，需要用 calculate_year_jieqi(2023)[21]
-    let current_year_jieqi = calculate_year_jieqi(year);
-    let prev_year_jieqi = calculate_year_jieqi(year.saturating_sub(1));
-
-    // 将输入日期转换为儒略日用于比较
-    let input_jd = gregorian_to_jd(year as i32, month, day) + (hour as f64) / 24.0;
-
-    // 检查是否在立春之前（属于上一年）
-    // 对于输入年Y，当年的立春在 prev_year_jieqi[21] 中
-    let lichun_jd = jieqi_to_jd(&prev_year_jieqi[21]);
-    let adjusted_year = if input_jd < lichun_jd {
-        year.saturating_sub(1)
-    } else {
-        year
-    };
-
-    // 当前输入日期所属的月支
-    let month_zhi = find_month_zhi(year, month, day, hour, &current_year_jieqi, &prev_year_jieqi);
-
-    (month_zhi, adjusted_year)
-}
-
-/// 查找日期对应的月支
-///
-/// 12节对应12个月：
-/// - 立春(21) -> 寅月(2)
-/// - 惊蛰(23) -> 卯月(3)
-/// - 清明(1)  -> 辰月(4)
-/// - 立夏(3)  -> 巳月(5)
-/// - 芒种(5)  -> 午月(6)
-/// - 小暑(7)  -> 未月(7)
-/// - 立秋(9)  -> 申月(8)
-/// - 白露(11) -> 酉月(9)
-/// - 寒露(13) -> 戌月(10)
-/// - 立冬(15) -> 亥月(11)
-/// - 大雪(17) -> 子月(0)
-/// - 小寒(19) -> 丑月(1)
-///
-/// # 参数
-///
-/// - `current_year_jieqi`: calculate_year_jieqi(year) 的结果
-/// - `prev_year_jieqi`: calculate_year_jieqi(year-1) 的结果
-///
-/// # 节气数组说明
-///
-/// calculate_year_jieqi(Y) 返回：
-/// - [0-18]: Y年的春分到冬至
-/// - [19-23]: Y+1年的小寒到惊蛰
-///
-/// 所以对于公历年Y的某个日期：
-/// - Y年的立春在 prev_year_jieqi[21] 中
-/// - Y年的惊蛰在 prev_year_jieqi[23] 中
-/// - Y年的清明在 current_year_jieqi[1] 中
-/// - ...
-/// - Y年的大雪在 current_year_jieqi[17] 中
-/// - Y+1年的小寒在 current_year_jieqi[19] 中
-fn find_month_zhi(
-    year: u16,
-    month: u8,
-    day: u8,
-    hour: u8,
-    current_year_jieqi: &[JieQiTime; 24],
-    prev_year_jieqi: &[JieQiTime; 24],
-) -> u8 {
-    let input_jd = gregorian_to_jd(year as i32, month, day) + (hour as f64) / 24.0;
-
-    // 构建按时间顺序排列的节气列表
-    // 对于公历年Y：
-    // 1. Y年小寒: prev_year_jieqi[19] -> 丑月(1)
-    // 2. Y年立春: prev_year_jieqi[21] -> 寅月(2)
-    // 3. Y年惊蛰: prev_year_jieqi[23] -> 卯月(3)
-    // 4. Y年清明: current_year_jieqi[1] -> 辰月(4)
-    // 5. Y年立夏: current_year_jieqi[3] -> 巳月(5)
-    // 6. Y年芒种: current_year_jieqi[5] -> 午月(6)
-    // 7. Y年小暑: current_year_jieqi[7] -> 未月(7)
-    // 8. Y年立秋: current_year_jieqi[9] -> 申月(8)
-    // 9. Y年白露: current_year_jieqi[11] -> 酉月(9)
-    // 10. Y年寒露: current_year_jieqi[13] -> 戌月(10)
-    // 11. Y年立冬: current_year_jieqi[15] -> 亥月(11)
-    // 12. Y年大雪: current_year_jieqi[17] -> 子月(0)
-    // 13. Y+1年小寒: current_year_jieqi[19] -> 丑月(1)
-
-    // 从后往前检查
-
-    // 检查是否过了Y+1年的小寒（进入下一年的丑月）
-    let next_xiaohan_jd = jieqi_to_jd(&current_year_jieqi[19]);
-    if input_jd >= next_xiaohan_jd {
-        return 1; // 丑月
-    }
-
-    // 检查Y年大雪
-    let daxue_jd = jieqi_to_jd(&current_year_jieqi[17]);
-    if input_jd >= daxue_jd {
-        return 0; // 子月
-    }
-
-    // 检查Y年立冬
-    let lidong_jd = jieqi_to_jd(&current_year_jieqi[15]);
-    if input_jd >= lidong_jd {
-        return 11; // 亥月
-    }
-
-    // 检查Y年寒露
-    let hanlu_jd = jieqi_to_jd(&current_year_jieqi[13]);
-    if input_jd >= hanlu_jd {
-        return 10; // 戌月
-    }
-
-    // 检查Y年白露
-    let bailu_jd = jieqi_to_jd(&current_year_jieqi[11]);
-    if input_jd >= bailu_jd {
-        return 9; // 酉月
-    }
-
-    // 检查Y年立秋
-    let liqiu_jd = jieqi_to_jd(&current_year_jieqi[9]);
-    if input_jd >= liqiu_jd {
-        return 8; // 申月
-    }
-
-    // 检查Y年小暑
-    let xiaoshu_jd = jieqi_to_jd(&current_year_jieqi[7]);
-    if input_jd >= xiaoshu_jd {
-        return 7; // 未月
-    }
-
-    // 检查Y年芒种
-    let mangzhong_jd = jieqi_to_jd(&current_year_jieqi[5]);
-    if input_jd >= mangzhong_jd {
-        return 6; // 午月
-    }
-
-    // 检查Y年立夏
-    let lixia_jd = jieqi_to_jd(&current_year_jieqi[3]);
-    if input_jd >= lixia_jd {
-        return 5; // 巳月
-    }
-
-    // 检查Y年清明
-    let qingming_jd = jieqi_to_jd(&current_year_jieqi[1]);
-    if input_jd >= qingming_jd {
-        return 4; // 辰月
-    }
-
-    // 检查Y年惊蛰 (在prev_year_jieqi中)
-    let jingzhe_jd = jieqi_to_jd(&prev_year_jieqi[23]);
-    if input_jd >= jingzhe_jd {
-        return 3; // 卯月
-    }
-
-    // 检查Y年立春 (在prev_year_jieqi中)
-    let lichun_jd = jieqi_to_jd(&prev_year_jieqi[21]);
-    if input_jd >= lichun_jd {
-        return 2; // 寅月
-    }
-
-    // 检查Y年小寒 (在prev_year_jieqi中)
-    let xiaohan_jd = jieqi_to_jd(&prev_year_jieqi[19]);
-    if input_jd >= xiaohan_jd {
-        return 1; // 丑月
-    }
-
-    // 如果还在Y年小寒之前，需要检查Y-1年的节气
-    // 这种情况只发生在1月初的几天
-    let prev_prev_year_jieqi = calculate_year_jieqi(year.saturating_sub(2));
-
-    // 检查Y-1年大雪
-    let prev_daxue_jd = jieqi_to_jd(&prev_prev_year_jieqi[17]);
-    if input_jd >= prev_daxue_jd {
-        return 0; // 子月
-    }
-
-    // 检查Y-1年立冬
-    let prev_lidong_jd = jieqi_to_jd(&prev_prev_year_jieqi[15]);
-    if input_jd >= prev_lidong_jd {
-        return 11; // 亥月
-    }
-
-    // 默认返回戌月（不应该到达这里）
-    10
-}
-
-/// 将JieQiTime转换为儒略日
-fn jieqi_to_jd(jieqi: &JieQiTime) -> f64 {
-    gregorian_to_jd(jieqi.year as i32, jieqi.month, jieqi.day)
-        + (jieqi.hour as f64) / 24.0
-        + (jieqi.minute as f64) / 1440.0
-}
-
-/// 获取指定年份某个节气的精确时间
-///
-/// # 参数
-///
-/// - `year`: 公历年份
-/// - `jieqi_index`: 节气索引（0-23，从春分开始）
-///
-/// # 返回
-///
-/// 节气的精确时间
-pub fn get_jieqi_time(year: u16, jieqi_index: u8) -> JieQiTime {
-    if jieqi_index >= 24 {
-        return JieQiTime { year: 0, month: 0, day: 0, hour: 0, minute: 0 };
-    }
-
-    let longitude = (jieqi_index as f64) * 15.0;
-    let jd = calculate_jieqi_jd(year as i32, longitude);
-    jd_to_datetime(jd)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_1990_lidong() {
-        // 1990年立冬应该在11月7日左右
-        let jieqi = get_jieqi_time(1990, 15); // 立冬索引是15
-        assert_eq!(jieqi.month, 11);
-        assert!(jieqi.day >= 7 && jieqi.day <= 8);
-    }
-
-    #[test]
-    fn test_1990_daxue() {
-        // 1990年大雪应该在12月7日左右
-        let jieqi = get_jieqi_time(1990, 17); // 大雪索引是17
-        assert_eq!(jieqi.month, 12);
-        assert!(jieqi.day >= 6 && jieqi.day <= 8);
-    }
-
-    #[test]
-    fn test_1990_11_29_month_zhi() {
-        // 1990年11月29日12时应该是亥月(11)
-        // 因为在立冬(11月7日)之后，大雪(12月7日)之前
-        let (month_zhi, _) = get_month_zhi_by_jieqi(1990, 11, 29, 12);
-        assert_eq!(month_zhi, 11, "1990年11月29日应该是亥月(11)，实际得到: {}", month_zhi);
-    }
-
-    #[test]
-    fn test_2000_lichun() {
-        // 2000年立春应该在2月4日左右
-        let jieqi = get_jieqi_time(2000, 21); // 立春索引是21
-        assert_eq!(jieqi.month, 2);
-        assert!(jieqi.day >= 3 && jieqi.day <= 5);
-    }
-
-    #[test]
-    fn test_month_boundary() {
-        // 测试节气边界
-        // 1990年立春是2月4日
-        // 1990年2月3日应该还在丑月（立春前）
-        // 1990年2月5日应该在寅月（立春后）
-        let (zhi1, _) = get_month_zhi_by_jieqi(1990, 2, 3, 12);
-        let (zhi2, _) = get_month_zhi_by_jieqi(1990, 2, 5, 12);
-
-        // 丑月是1，寅月是2
-        assert!(zhi1 == 1 || zhi1 == 2, "2月3日应该是丑月(1)或寅月(2)边界，实际得到: {}", zhi1);
-        assert_eq!(zhi2, 2, "2月5日应该是寅月(2)，实际得到: {}", zhi2);
-    }
-
-    #[test]
-    fn test_all_months() {
-        // 测试1990年每个月中旬的月支（使用确认正确的年份）
-        let test_cases = [
-            (1990, 1, 15, 12, 1),   // 丑月（1990年小寒1月6日后，立春2月4日前）
-            (1990, 2, 15, 12, 2),   // 寅月（1990年立春2月4日后）
-            (1990, 3, 15, 12, 3),   // 卯月
-            (1990, 4, 15, 12, 4),   // 辰月
-            (1990, 5, 15, 12, 5),   // 巳月
-            (1990, 6, 15, 12, 6),   // 午月
-            (1990, 7, 15, 12, 7),   // 未月
-            (1990, 8, 15, 12, 8),   // 申月
-            (1990, 9, 15, 12, 9),   // 酉月
-            (1990, 10, 15, 12, 10), // 戌月
-            (1990, 11, 15, 12, 11), // 亥月
-            (1990, 12, 15, 12, 0),  // 子月
-        ];
+//! 算法实现位于 `divination-algorithms` 共享库，
+//! 链上排盘与 TEE Enclave 使用同一份代码，保证月柱、年柱判定一致。
 
-        for (year, month, day, hour, expected_zhi) in test_cases.iter() {
-            let (actual_zhi, _) = get_month_zhi_by_jieqi(*year, *month, *day, *hour);
-            assert_eq!(
-                actual_zhi, *expected_zhi,
-                "{}年{}月{}日{}时应该是月支{}，实际得到: {}",
-                year, month, day, hour, expected_zhi, actual_zhi
-            );
-        }
-    }
-}
+pub use divination_algorithms::jieqi::*;
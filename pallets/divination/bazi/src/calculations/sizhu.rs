@@ -1,19 +1,25 @@
 //! # 四柱计算模块
 //!
 //! 实现年月日时四柱的完整计算逻辑
+//!
+//! 核心算法位于 `divination-algorithms` 共享库（以 `u8` 索引表示干支），
+//! 本模块将其包装为 `GanZhi` 类型，链上排盘与 TEE Enclave 结果逐字节一致。
 
-use crate::types::GanZhi;
-use super::ganzhi::calculate_days_between;
-use super::jieqi::get_month_zhi_by_jieqi;
+use crate::types::{DiZhi, GanZhi, TianGan, ZiShiMode};
+use divination_algorithms::sizhu::{self as core_sizhu, Pillar};
 
-/// 日柱基准日期：公元前720年1月1日 = 甲子日
-///
-/// 这是八字排盘中公认的日柱计算基准点
-const BASE_YEAR: i32 = -720;
-const BASE_MONTH: u8 = 1;
-const BASE_DAY: u8 = 1;
-/// 甲子日的干支索引为0
-const BASE_GANZHI_INDEX: u8 = 0;
+/// 共享库干支转换为 `GanZhi`
+fn to_ganzhi(pillar: Pillar) -> GanZhi {
+	GanZhi { gan: TianGan(pillar.gan), zhi: DiZhi(pillar.zhi) }
+}
+
+/// `ZiShiMode` 转换为共享库子时模式
+fn to_core_zishi(mode: ZiShiMode) -> core_sizhu::ZiShiMode {
+	match mode {
+		ZiShiMode::Traditional => core_sizhu::ZiShiMode::Traditional,
+		ZiShiMode::Modern => core_sizhu::ZiShiMode::Modern,
+	}
+}
 
 /// 计算日柱干支
 ///
@@ -41,30 +47,7 @@ const BASE_GANZHI_INDEX: u8 = 0;
 /// assert!(day_ganzhi.is_some());
 /// ```
 pub fn calculate_day_ganzhi(year: u16, month: u8, day: u8) -> Option<GanZhi> {
-	// 参数验证
-	if month < 1 || month > 12 || day < 1 || day > 31 {
-		return None;
-	}
-
-	// 计算目标日期与基准日期的天数差
-	let days_diff = calculate_days_between(
-		BASE_YEAR,
-		BASE_MONTH,
-		BASE_DAY,
-		year as i32,
-		month,
-		day
-	);
-
-	// 计算干支索引：天数差对60取模
-	// 注意：Rust的负数取模结果为负，需要处理
-	let ganzhi_index = ((days_diff % 60) + 60) as u8 % 60;
-
-	// 加上基准日期的干支索引（甲子 = 0）
-	let final_index = (BASE_GANZHI_INDEX + ganzhi_index) % 60;
-
-	// 转换为干支对象
-	GanZhi::from_index(final_index)
+	core_sizhu::day_pillar(year, month, day).map(to_ganzhi)
 }
 
 /// 计算年柱干支
@@ -118,30 +101,7 @@ pub fn calculate_year_ganzhi(year: u16, month: u8, day: u8) -> Option<GanZhi> {
 /// - `Some(GanZhi)`: 成功计算的年柱干支
 /// - `None`: 参数无效或计算失败
 pub fn calculate_year_ganzhi_with_hour(year: u16, month: u8, day: u8, hour: u8) -> Option<GanZhi> {
-	// 参数验证
-	if month < 1 || month > 12 || day < 1 || day > 31 || hour > 23 {
-		return None;
-	}
-
-	// 使用精确节气计算获取调整后的年份
-	// get_month_zhi_by_jieqi 会根据立春精确时刻判断是否属于上一年
-	let (_, bazi_year) = get_month_zhi_by_jieqi(year, month, day, hour);
-
-	// 计算年柱干支
-	// 公元4年 = 甲子年（GanZhi index 0）
-	// 1984年 = 甲子年
-	// 因此：(year - 4) % 60 可以得到年柱索引
-
-	let tiangan_index = ((bazi_year as i32 - 4) % 10 + 10) as u8 % 10;
-	let dizhi_index = ((bazi_year as i32 - 4) % 12 + 12) as u8 % 12;
-
-	// 注意：年柱不能简单用GanZhi::from_index，因为天干地支周期不同
-	// 需要用天干和地支分别计算，然后组合
-	use crate::types::{TianGan, DiZhi};
-	Some(GanZhi {
-		gan: TianGan(tiangan_index),
-		zhi: DiZhi(dizhi_index),
-	})
+	core_sizhu::year_pillar(year, month, day, hour).map(to_ganzhi)
 }
 
 /// 计算月柱干支
@@ -218,42 +178,7 @@ pub fn calculate_month_ganzhi(year: u16, month: u8, day: u8, year_gan: u8) -> Op
 /// - `Some(GanZhi)`: 成功计算的月柱干支
 /// - `None`: 参数无效或计算失败
 pub fn calculate_month_ganzhi_with_hour(year: u16, month: u8, day: u8, hour: u8, year_gan: u8) -> Option<GanZhi> {
-	// 参数验证
-	if month < 1 || month > 12 || day < 1 || day > 31 || year_gan >= 10 || hour >= 24 {
-		return None;
-	}
-
-	// 使用精确节气计算获取月支
-	let (month_zhi, _adjusted_year) = get_month_zhi_by_jieqi(year, month, day, hour);
-
-	// 五虎遁：根据年干确定寅月（month_zhi=2）的月干
-	// 然后根据月支偏移计算当前月干
-	let yin_month_gan = match year_gan {
-		0 | 5 => 2,  // 甲己 → 丙寅
-		1 | 6 => 4,  // 乙庚 → 戊寅
-		2 | 7 => 6,  // 丙辛 → 庚寅
-		3 | 8 => 8,  // 丁壬 → 壬寅
-		4 | 9 => 0,  // 戊癸 → 甲寅
-		_ => return None,
-	};
-
-	// 计算当前月干
-	// 寅月(2) → 当前月支的偏移
-	let offset = if month_zhi >= 2 {
-		month_zhi - 2
-	} else {
-		// 子月(0)和丑月(1)在寅月之前，需要加10或11
-		month_zhi + 10
-	};
-
-	let month_gan = (yin_month_gan + offset) % 10;
-
-	// 构造月柱干支
-	use crate::types::{TianGan, DiZhi};
-	Some(GanZhi {
-		gan: TianGan(month_gan),
-		zhi: DiZhi(month_zhi),
-	})
+	core_sizhu::month_pillar(year, month, day, hour, year_gan).map(to_ganzhi)
 }
 
 /// 计算时柱干支
@@ -307,69 +232,10 @@ pub fn calculate_month_ganzhi_with_hour(year: u16, month: u8, day: u8, hour: u8,
 pub fn calculate_hour_ganzhi(
 	hour: u8,
 	day_gan: u8,
-	zishi_mode: crate::types::ZiShiMode
+	zishi_mode: ZiShiMode
 ) -> Option<(GanZhi, bool)> {
-	// 参数验证
-	if hour >= 24 || day_gan >= 10 {
-		return None;
-	}
-
-	// 确定时支和是否属于次日
-	let (hour_zhi, is_next_day) = if hour == 23 {
-		// 23:00-23:59：子时
-		match zishi_mode {
-			crate::types::ZiShiMode::Traditional => {
-				// 传统派：属于次日（早子时）
-				(0, true)
-			},
-			crate::types::ZiShiMode::Modern => {
-				// 现代派：属于当日
-				(0, false)
-			},
-		}
-	} else {
-		// 其他时辰：根据小时确定时支
-		let zhi = match hour {
-			0 | 1 => 0,   // 子时 00:00-01:59
-			2 | 3 => 1,   // 丑时 02:00-03:59
-			4 | 5 => 2,   // 寅时 04:00-05:59
-			6 | 7 => 3,   // 卯时 06:00-07:59
-			8 | 9 => 4,   // 辰时 08:00-09:59
-			10 | 11 => 5, // 巳时 10:00-11:59
-			12 | 13 => 6, // 午时 12:00-13:59
-			14 | 15 => 7, // 未时 14:00-15:59
-			16 | 17 => 8, // 申时 16:00-17:59
-			18 | 19 => 9, // 酉时 18:00-19:59
-			20 | 21 => 10,// 戌时 20:00-21:59
-			22 => 11,     // 亥时 22:00-22:59
-			_ => return None,
-		};
-		(zhi, false)
-	};
-
-	// 五鼠遁：根据日干确定子时（hour_zhi=0）的时干
-	// 然后根据时支偏移计算当前时干
-	let zi_hour_gan = match day_gan {
-		0 | 5 => 0,  // 甲己 → 甲子
-		1 | 6 => 2,  // 乙庚 → 丙子
-		2 | 7 => 4,  // 丙辛 → 戊子
-		3 | 8 => 6,  // 丁壬 → 庚子
-		4 | 9 => 8,  // 戊癸 → 壬子
-		_ => return None,
-	};
-
-	// 计算当前时干：子时天干 + 时支偏移
-	let hour_gan = (zi_hour_gan + hour_zhi) % 10;
-
-	// 构造时柱干支
-	use crate::types::{TianGan, DiZhi};
-	Some((
-		GanZhi {
-			gan: TianGan(hour_gan),
-			zhi: DiZhi(hour_zhi),
-		},
-		is_next_day
-	))
+	core_sizhu::hour_pillar(hour, day_gan, to_core_zishi(zishi_mode))
+		.map(|(pillar, is_next_day)| (to_ganzhi(pillar), is_next_day))
 }
 
 /// 计算四柱干支（核心函数）
@@ -391,30 +257,12 @@ pub fn calculate_sizhu_core(
 	month: u8,
 	day: u8,
 	hour: u8,
-	zishi_mode: crate::types::ZiShiMode,
+	zishi_mode: ZiShiMode,
 ) -> Option<(GanZhi, GanZhi, GanZhi, GanZhi)> {
-	// 1. 计算日柱
-	let day_ganzhi = calculate_day_ganzhi(year, month, day)?;
-
-	// 2. 计算年柱
-	let year_ganzhi = calculate_year_ganzhi(year, month, day)?;
-
-	// 3. 计算月柱
-	let month_ganzhi = calculate_month_ganzhi(year, month, day, year_ganzhi.gan.0)?;
-
-	// 4. 计算时柱（处理子时双模式）
-	let (hour_ganzhi, is_next_day) = calculate_hour_ganzhi(hour, day_ganzhi.gan.0, zishi_mode)?;
+	let (year_gz, month_gz, day_gz, hour_gz) =
+		core_sizhu::calculate_sizhu(year, month, day, hour, to_core_zishi(zishi_mode))?;
 
-	// 5. 如果是次日子时（传统派23:00），需要重新计算日柱和时柱
-	let (final_day_ganzhi, final_hour_ganzhi) = if is_next_day {
-		let next_day_ganzhi = day_ganzhi.next();
-		let (final_hour, _) = calculate_hour_ganzhi(hour, next_day_ganzhi.gan.0, zishi_mode)?;
-		(next_day_ganzhi, final_hour)
-	} else {
-		(day_ganzhi, hour_ganzhi)
-	};
-
-	Some((year_ganzhi, month_ganzhi, final_day_ganzhi, final_hour_ganzhi))
+	Some((to_ganzhi(year_gz), to_ganzhi(month_gz), to_ganzhi(day_gz), to_ganzhi(hour_gz)))
 }
 
 #[cfg(test)]
@@ -663,4 +511,28 @@ mod tests {
 		assert!(calculate_hour_ganzhi(24, 0, ZiShiMode::Modern).is_none()); // 无效小时
 		assert!(calculate_hour_ganzhi(0, 10, ZiShiMode::Modern).is_none()); // 无效日干
 	}
+
+	#[test]
+	fn test_sizhu_golden_vectors() {
+		use crate::types::SiZhuIndex;
+		use codec::Encode;
+		use divination_algorithms::golden::BAZI_CASES;
+
+		// 与 TEE Enclave 共用的黄金向量：链上四柱索引编码必须逐字节一致
+		for case in BAZI_CASES {
+			let (year_gz, month_gz, day_gz, hour_gz) =
+				calculate_sizhu_core(case.year, case.month, case.day, case.hour, ZiShiMode::Modern).unwrap();
+			let index = SiZhuIndex {
+				year_gan: year_gz.gan.0,
+				year_zhi: year_gz.zhi.0,
+				month_gan: month_gz.gan.0,
+				month_zhi: month_gz.zhi.0,
+				day_gan: day_gz.gan.0,
+				day_zhi: day_gz.zhi.0,
+				hour_gan: hour_gz.gan.0,
+				hour_zhi: hour_gz.zhi.0,
+			};
+			assert_eq!(index.encode(), case.expected.to_vec());
+		}
+	}
 }
@@ -1062,3 +1062,58 @@ pub mod pallet {
 		}
 	}
 }
+
+// ============================================================================
+// 跨模块接口
+// ============================================================================
+
+/// 八字数据提供者 Trait
+///
+/// 供婚恋合婚、推荐等模块按命盘 ID 读取四柱与解盘结果，兼容精简与完整两种存储格式
+pub trait BaziDataProvider<AccountId> {
+	/// 命盘是否存在
+	fn exists(bazi_id: u64) -> bool;
+	/// 是否为命盘所有者
+	fn is_owner(account: &AccountId, bazi_id: u64) -> bool;
+	/// 四柱索引
+	fn get_sizhu_index(bazi_id: u64) -> Option<SiZhuIndex>;
+	/// 核心解盘
+	fn get_interpretation(bazi_id: u64) -> Option<CoreInterpretation>;
+	/// 性格分析
+	fn get_personality(bazi_id: u64) -> Option<CompactXingGe>;
+	/// 起运信息：(是否顺排, 起运年龄)，用于推排大运
+	fn get_qiyun(bazi_id: u64) -> Option<(bool, u8)>;
+}
+
+impl<T: Config> BaziDataProvider<T::AccountId> for Pallet<T> {
+	fn exists(bazi_id: u64) -> bool {
+		ChartCompactById::<T>::contains_key(bazi_id) || ChartById::<T>::contains_key(bazi_id)
+	}
+
+	fn is_owner(account: &T::AccountId, bazi_id: u64) -> bool {
+		ChartCompactById::<T>::get(bazi_id)
+			.map(|chart| chart.owner)
+			.or_else(|| ChartById::<T>::get(bazi_id).map(|chart| chart.owner))
+			.map_or(false, |owner| &owner == account)
+	}
+
+	fn get_sizhu_index(bazi_id: u64) -> Option<SiZhuIndex> {
+		match ChartCompactById::<T>::get(bazi_id) {
+			Some(chart) => chart.get_sizhu_index(),
+			None => ChartById::<T>::get(bazi_id)
+				.and_then(|chart| types::BaziChartCompact::<T>::from_full_chart(&chart).get_sizhu_index()),
+		}
+	}
+
+	fn get_interpretation(bazi_id: u64) -> Option<CoreInterpretation> {
+		Self::get_full_interpretation(bazi_id).map(|interpretation| interpretation.core)
+	}
+
+	fn get_personality(bazi_id: u64) -> Option<CompactXingGe> {
+		Self::get_full_interpretation(bazi_id).and_then(|interpretation| interpretation.xing_ge)
+	}
+
+	fn get_qiyun(bazi_id: u64) -> Option<(bool, u8)> {
+		Self::get_full_bazi_chart_for_api(bazi_id).map(|chart| (chart.qiyun.is_shun, chart.qiyun.age_years))
+	}
+}
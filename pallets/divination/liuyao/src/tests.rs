@@ -1322,3 +1322,32 @@ fn test_xiang_gui() {
     assert_eq!(xg[0], DiZhi::Si);
     assert_eq!(xg[1], DiZhi::Wu);
 }
+
+// ============================================================================
+// 黄金向量测试（与 TEE Enclave 共用）
+// ============================================================================
+
+#[test]
+fn test_ocw_tee_index_matches_golden_vectors() {
+    use crate::ocw_tee::{LiuyaoInputPlain, LiuyaoMethod, LiuyaoModuleHandler};
+    use codec::Encode;
+    use divination_algorithms::golden::LIUYAO_CASES;
+    use pallet_divination_ocw_tee::DivinationModule;
+
+    for case in LIUYAO_CASES {
+        let input = LiuyaoInputPlain {
+            method: LiuyaoMethod::Manual,
+            yao_values: Some(case.yao_values),
+            number: None,
+            year: None,
+            month: None,
+            day: None,
+            hour: None,
+            day_gan: None,
+            question: None,
+        };
+
+        let result = LiuyaoModuleHandler::<Test>::compute(&input).unwrap();
+        assert_eq!(result.index.encode(), case.expected.to_vec());
+    }
+}
@@ -66,6 +66,8 @@ pub mod pallet {
     use pallet_affiliate::types::AffiliateDistributor;
     use pallet_trading_common::PricingProvider;
     use pallet_chat_permission::{SceneAuthorizationManager, SceneType, SceneId};
+    use pallet_reputation::traits::{ReputationProvider, ReputationSink};
+    use pallet_reputation::types::{ReputationDomain, ReputationSignal, RiskLevel, Severity};
     use sp_runtime::traits::{Saturating, Zero, SaturatedConversion};
     // 已移除 L1/L2 归档压缩，不再需要 amount_to_tier 和 block_to_year_month
     use sp_std::prelude::*;
@@ -170,6 +172,9 @@ pub mod pallet {
         /// 🆕 订单聊天授权有效期（区块数，432000 ≈ 30天）
         #[pallet::constant]
         type OrderChatDuration: Get<BlockNumberFor<Self>>;
+
+        /// 跨域信誉（上报违规 / 履约信号，注册提供者时检查综合风险）
+        type Reputation: ReputationSink<Self::AccountId> + ReputationProvider<Self::AccountId>;
     }
 
     /// 货币余额类型别名
@@ -1224,6 +1229,8 @@ pub mod pallet {
         TooManyMediaItems,
         /// 修改窗口已过期
         EditWindowExpired,
+        /// 跨域综合风险过高
+        ReputationRiskTooHigh,
     }
 
     // ==================== 可调用函数 ====================
@@ -1255,6 +1262,12 @@ pub mod pallet {
                 Error::<T>::ProviderAlreadyExists
             );
 
+            // 在其他领域被封禁或综合风险过高的账户不得注册
+            ensure!(
+                T::Reputation::risk_level(&who) < RiskLevel::Blocked,
+                Error::<T>::ReputationRiskTooHigh
+            );
+
             let name_bounded: BoundedVec<u8, T::MaxNameLength> =
                 BoundedVec::try_from(name).map_err(|_| Error::<T>::NameTooLong)?;
             let bio_bounded: BoundedVec<u8, T::MaxBioLength> =
@@ -2133,6 +2146,8 @@ pub mod pallet {
                 provider_earnings,
                 platform_fee,
             });
+
+            T::Reputation::report(&provider, ReputationDomain::DivinationMarket, ReputationSignal::Fulfilled);
             
             Ok(())
         }
@@ -4044,6 +4059,18 @@ pub mod pallet {
                 Self::deposit_event(Event::AddedToBlacklist { provider: provider.clone() });
             }
 
+            // 上报跨域信誉
+            T::Reputation::report(
+                &provider,
+                ReputationDomain::DivinationMarket,
+                ReputationSignal::Violation(Self::reputation_severity(violation_type)),
+            );
+            if penalty == PenaltyType::PermanentBan {
+                T::Reputation::report(&provider, ReputationDomain::DivinationMarket, ReputationSignal::Banned);
+            } else if penalty == PenaltyType::ServiceSuspension {
+                T::Reputation::report(&provider, ReputationDomain::DivinationMarket, ReputationSignal::Suspended);
+            }
+
             Self::deposit_event(Event::ViolationRecorded {
                 provider,
                 violation_id,
@@ -4153,6 +4180,17 @@ pub mod pallet {
                 });
             }
 
+            if result == AppealResult::Upheld {
+                T::Reputation::report(
+                    &provider,
+                    ReputationDomain::DivinationMarket,
+                    ReputationSignal::ViolationRevoked(
+                        Self::reputation_severity(record.violation_type),
+                        record.recorded_at.saturated_into(),
+                    ),
+                );
+            }
+
             Self::deposit_event(Event::AppealResolved {
                 provider,
                 violation_id,
@@ -4357,6 +4395,20 @@ pub mod pallet {
         }
     }
 
+    // ==================== 跨域信誉 ====================
+
+    impl<T: Config> Pallet<T> {
+        /// 违规等级映射为跨域信誉严重程度
+        pub(crate) fn reputation_severity(violation_type: ViolationType) -> Severity {
+            match violation_type {
+                ViolationType::Minor => Severity::Minor,
+                ViolationType::Moderate => Severity::Moderate,
+                ViolationType::Severe => Severity::Severe,
+                ViolationType::Critical => Severity::Critical,
+            }
+        }
+    }
+
     // ==================== 🆕 存储膨胀防护：归档函数 ====================
 
     impl<T: Config> Pallet<T> {
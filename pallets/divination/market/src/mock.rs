@@ -311,6 +311,7 @@ impl pallet_divination_market::Config for Test {
     // 🆕 聊天权限集成
     type ChatPermission = MockChatPermission;
     type OrderChatDuration = ConstU64<432000>;
+    type Reputation = ();
 }
 
 /// 构建测试外部状态
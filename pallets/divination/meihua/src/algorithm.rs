@@ -8,6 +8,9 @@
 //! - 错卦、综卦计算
 //! - 体用判断
 //! - 吉凶判断
+//!
+//! 起卦、变卦、互卦的核心计算委托给 `divination-algorithms`，
+//! 与 TEE Enclave 共用同一实现，保证链上与链下结果一致。
 
 use crate::lunar::LunarDate;
 use crate::types::*;
@@ -26,12 +29,7 @@ use alloc::format;
 /// - 卦数（1-8）
 #[inline]
 pub fn calc_gua_num(n: u32) -> u8 {
-    let r = (n % 8) as u8;
-    if r == 0 {
-        8
-    } else {
-        r
-    }
+    divination_algorithms::meihua::calc_gua_num(n)
 }
 
 /// 计算动爻数（处理余数为0的情况）
@@ -45,12 +43,7 @@ pub fn calc_gua_num(n: u32) -> u8 {
 /// - 动爻数（1-6）
 #[inline]
 pub fn calc_dong_yao(n: u32) -> u8 {
-    let r = (n % 6) as u8;
-    if r == 0 {
-        6
-    } else {
-        r
-    }
+    divination_algorithms::meihua::calc_dong_yao(n)
 }
 
 /// 农历时间起卦算法
@@ -112,22 +105,7 @@ pub fn divine_by_datetime(lunar: &LunarDate) -> (u8, u8, u8) {
 /// // 动爻=(24+12+24+14)%6=74%6=2
 /// ```
 pub fn divine_by_gregorian_datetime(year: u32, month: u8, day: u8, hour: u8) -> (u8, u8, u8) {
-    // 年份取后两位
-    let year_num = (year % 100) as u32;
-    let month_num = month as u32;
-    let day_num = day as u32;
-    let hour_num = hour as u32;
-
-    // 上卦数：(年份后两位+月+日) % 8
-    let shang_gua_num = calc_gua_num(year_num + month_num + day_num);
-
-    // 下卦数：(年份后两位+月+日+小时) % 8
-    let xia_gua_num = calc_gua_num(year_num + month_num + day_num + hour_num);
-
-    // 动爻数：(年份后两位+月+日+小时) % 6
-    let dong_yao = calc_dong_yao(year_num + month_num + day_num + hour_num);
-
-    (shang_gua_num, xia_gua_num, dong_yao)
+    divination_algorithms::meihua::divine_by_gregorian_datetime(year, month, day, hour)
 }
 
 /// 从 Unix 时间戳提取公历日期时间
@@ -138,45 +116,8 @@ pub fn divine_by_gregorian_datetime(year: u32, month: u8, day: u8, hour: u8) ->
 /// # 返回
 /// - (年, 月, 日, 小时)
 pub fn timestamp_to_gregorian(timestamp: u64) -> (u32, u8, u8, u8) {
-    // UTC+8 时区偏移（秒）
-    const TIMEZONE_OFFSET: u64 = 8 * 3600;
-    let local_timestamp = timestamp + TIMEZONE_OFFSET;
-
-    // 从 Unix 时间戳计算日期
-    // Unix 纪元：1970-01-01 00:00:00 UTC
-    let days_since_epoch = (local_timestamp / 86400) as i64;
-    let seconds_in_day = (local_timestamp % 86400) as u32;
-    let hour = (seconds_in_day / 3600) as u8;
-
-    // 使用简化的日期计算（考虑闰年）
-    let (year, month, day) = days_to_ymd(days_since_epoch);
-
-    (year as u32, month, day, hour)
-}
-
-/// 将天数转换为年月日
-///
-/// # 参数
-/// - `days`: 从 1970-01-01 起的天数
-///
-/// # 返回
-/// - (年, 月, 日)
-fn days_to_ymd(days: i64) -> (i32, u8, u8) {
-    // 调整到 2000-03-01 为基准（方便处理闰年）
-    // 从 1970-01-01 到 2000-03-01 共 11017 天
-    let days = days + 719468; // 调整到公元1年3月1日
-
-    let era = if days >= 0 { days / 146097 } else { (days - 146096) / 146097 };
-    let doe = (days - era * 146097) as u32; // 400年周期内的天数
-    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
-    let y = yoe as i32 + era as i32 * 400;
-    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
-    let mp = (5 * doy + 2) / 153;
-    let d = doy - (153 * mp + 2) / 5 + 1;
-    let m = if mp < 10 { mp + 3 } else { mp - 9 };
-    let y = if m <= 2 { y + 1 } else { y };
-
-    (y, m as u8, d as u8)
+    // 北京时间（UTC+8），与 TEE Enclave 共用同一实现
+    divination_algorithms::calendar::timestamp_to_gregorian(timestamp)
 }
 
 /// 双数起卦算法
@@ -196,16 +137,7 @@ fn days_to_ymd(days: i64) -> (i32, u8, u8) {
 /// # 返回
 /// - (上卦数, 下卦数, 动爻数)
 pub fn divine_by_numbers(num1: u16, num2: u16, hour_zhi_num: u8) -> (u8, u8, u8) {
-    // 上卦数
-    let shang_gua_num = calc_gua_num(num1 as u32);
-
-    // 下卦数
-    let xia_gua_num = calc_gua_num(num2 as u32);
-
-    // 动爻数：(两数之和 + 时辰数) % 6
-    let dong_yao = calc_dong_yao(num1 as u32 + num2 as u32 + hour_zhi_num as u32);
-
-    (shang_gua_num, xia_gua_num, dong_yao)
+    divination_algorithms::meihua::divine_by_numbers(num1, num2, hour_zhi_num)
 }
 
 /// 单数起卦算法
@@ -230,68 +162,7 @@ pub fn divine_by_numbers(num1: u16, num2: u16, hour_zhi_num: u8) -> (u8, u8, u8)
 /// # 返回
 /// - (上卦数, 下卦数, 动爻数)
 pub fn divine_by_single_number(number: u32, hour_zhi_num: u8) -> (u8, u8, u8) {
-    // 将数字转换为各位数字（正序存储：高位在前）
-    let mut digits: [u8; 10] = [0; 10]; // 最多支持10位数
-    let digit_count: u8;
-    let mut n = number;
-
-    // 先计算位数
-    if n == 0 {
-        digits[0] = 0;
-        digit_count = 1;
-    } else {
-        // 临时存储（逆序）
-        let mut temp: [u8; 10] = [0; 10];
-        let mut temp_count = 0u8;
-        while n > 0 && temp_count < 10 {
-            temp[temp_count as usize] = (n % 10) as u8;
-            n /= 10;
-            temp_count += 1;
-        }
-        digit_count = temp_count;
-
-        // 逆转为正序（高位在前）
-        for i in 0..digit_count {
-            digits[i as usize] = temp[(digit_count - 1 - i) as usize];
-        }
-    }
-
-    // 处理单位数情况：直接作为上下卦
-    if digit_count == 1 {
-        let single_digit = digits[0] as u32;
-        let shang_gua_num = calc_gua_num(single_digit);
-        let xia_gua_num = calc_gua_num(single_digit);
-        let dong_yao = calc_dong_yao(single_digit * 2 + hour_zhi_num as u32);
-        return (shang_gua_num, xia_gua_num, dong_yao);
-    }
-
-    // 计算前半段和后半段的数字之和
-    // 前半段取 0 ~ (digit_count / 2) 位
-    // 后半段取 (digit_count / 2) ~ digit_count 位
-    let split_point = digit_count / 2;
-    let mut first_half_sum: u32 = 0;
-    let mut second_half_sum: u32 = 0;
-
-    for i in 0..digit_count {
-        if i < split_point {
-            // 前半段（高位数字）
-            first_half_sum += digits[i as usize] as u32;
-        } else {
-            // 后半段（低位数字）
-            second_half_sum += digits[i as usize] as u32;
-        }
-    }
-
-    // 上卦数：前半段数字之和 % 8
-    let shang_gua_num = calc_gua_num(first_half_sum);
-
-    // 下卦数：后半段数字之和 % 8
-    let xia_gua_num = calc_gua_num(second_half_sum);
-
-    // 动爻数：(前半 + 后半 + 时辰数) % 6
-    let dong_yao = calc_dong_yao(first_half_sum + second_half_sum + hour_zhi_num as u32);
-
-    (shang_gua_num, xia_gua_num, dong_yao)
+    divination_algorithms::meihua::divine_by_single_number(number, hour_zhi_num)
 }
 
 /// 随机起卦算法
@@ -304,12 +175,7 @@ pub fn divine_by_single_number(number: u32, hour_zhi_num: u8) -> (u8, u8, u8) {
 /// # 返回
 /// - (上卦数, 下卦数, 动爻数)
 pub fn divine_by_random(random_seed: &[u8; 32]) -> (u8, u8, u8) {
-    // 使用种子的前几个字节
-    let shang_gua_num = calc_gua_num(random_seed[0] as u32);
-    let xia_gua_num = calc_gua_num(random_seed[1] as u32);
-    let dong_yao = calc_dong_yao(random_seed[2] as u32);
-
-    (shang_gua_num, xia_gua_num, dong_yao)
+    divination_algorithms::meihua::divine_by_random(random_seed)
 }
 
 /// 链摇起卦算法
@@ -342,23 +208,7 @@ pub fn divine_by_random(random_seed: &[u8; 32]) -> (u8, u8, u8) {
 /// // 下卦二进制 111 = 乾(1)
 /// ```
 pub fn divine_by_shake(yaos: &[u8; 6], last_timestamp: u64) -> (u8, u8, u8) {
-    // 下卦：yaos[0..3]，二进制从低位到高位
-    // bit0 = 初爻, bit1 = 二爻, bit2 = 三爻
-    let xia_binary = ((yaos[2] & 1) << 2) | ((yaos[1] & 1) << 1) | (yaos[0] & 1);
-
-    // 上卦：yaos[3..6]，二进制从低位到高位
-    // bit0 = 四爻, bit1 = 五爻, bit2 = 上爻
-    let shang_binary = ((yaos[5] & 1) << 2) | ((yaos[4] & 1) << 1) | (yaos[3] & 1);
-
-    // 从二进制转换为先天八卦数
-    let shang_gua_num = Bagua::from_binary(shang_binary).number();
-    let xia_gua_num = Bagua::from_binary(xia_binary).number();
-
-    // 动爻：使用最后一次摇卦时间戳计算
-    // 取毫秒部分增加随机性
-    let dong_yao = calc_dong_yao((last_timestamp % 1000) as u32 + 1);
-
-    (shang_gua_num, xia_gua_num, dong_yao)
+    divination_algorithms::meihua::divine_by_shake(yaos, last_timestamp)
 }
 
 /// 验证摇卦时间戳的合理性
@@ -424,22 +274,8 @@ pub fn determine_ti_is_shang(dong_yao: u8) -> bool {
 /// # 返回
 /// - (变卦上卦, 变卦下卦)
 pub fn calc_bian_gua(shang_gua: &SingleGua, xia_gua: &SingleGua, dong_yao: u8) -> (SingleGua, SingleGua) {
-    // 组合6爻二进制：上卦占高3位（爻4-6），下卦占低3位（爻1-3）
-    let full_binary = (shang_gua.binary() << 3) | xia_gua.binary();
-
-    // 翻转动爻位
-    // dong_yao: 1-6 对应 bit 0-5
-    let bit_position = dong_yao - 1;
-    let flipped = full_binary ^ (1 << bit_position);
-
-    // 分离上下卦
-    let new_shang_binary = (flipped >> 3) & 0b111;
-    let new_xia_binary = flipped & 0b111;
-
-    (
-        SingleGua::from_binary(new_shang_binary),
-        SingleGua::from_binary(new_xia_binary),
-    )
+    let (shang, xia) = divination_algorithms::meihua::bian_gua(shang_gua.number(), xia_gua.number(), dong_yao);
+    (SingleGua::from_num(shang), SingleGua::from_num(xia))
 }
 
 /// 计算互卦
@@ -455,19 +291,8 @@ pub fn calc_bian_gua(shang_gua: &SingleGua, xia_gua: &SingleGua, dong_yao: u8) -
 /// # 返回
 /// - (互卦上卦, 互卦下卦)
 pub fn calc_hu_gua(shang_gua: &SingleGua, xia_gua: &SingleGua) -> (SingleGua, SingleGua) {
-    // 组合6爻：bits 5-3 为上卦（爻6、5、4），bits 2-0 为下卦（爻3、2、1）
-    let full_binary = (shang_gua.binary() << 3) | xia_gua.binary();
-
-    // 互卦上卦：取本卦第5、4、3爻 (bits 4, 3, 2)
-    let hu_shang = (full_binary >> 2) & 0b111;
-
-    // 互卦下卦：取本卦第4、3、2爻 (bits 3, 2, 1)
-    let hu_xia = (full_binary >> 1) & 0b111;
-
-    (
-        SingleGua::from_binary(hu_shang),
-        SingleGua::from_binary(hu_xia),
-    )
+    let (shang, xia) = divination_algorithms::meihua::hu_gua(shang_gua.number(), xia_gua.number());
+    (SingleGua::from_num(shang), SingleGua::from_num(xia))
 }
 
 /// 计算错卦
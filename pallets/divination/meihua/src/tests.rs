@@ -964,3 +964,49 @@ fn divine_with_privacy_atomicity() {
         assert!(record.is_some());
     });
 }
+
+// ==================== 黄金向量测试 ====================
+
+/// 读取卦象的链上索引字节（上卦数, 下卦数, 动爻）
+fn hexagram_index_bytes(id: u64) -> [u8; 3] {
+    let hexagram = Meihua::hexagrams(id).expect("Hexagram should exist").ben_gua;
+    [hexagram.shang_gua.number(), hexagram.xia_gua.number(), hexagram.dong_yao]
+}
+
+/// 测试公历时间起卦与 TEE Enclave 共用的黄金向量一致
+#[test]
+fn golden_gregorian_time_matches_enclave() {
+    use divination_algorithms::golden::MEIHUA_TIME_CASES;
+
+    new_test_ext().execute_with(|| {
+        for (id, case) in MEIHUA_TIME_CASES.iter().enumerate() {
+            pallet_timestamp::Now::<Test>::put(case.timestamp * 1000);
+
+            assert_ok!(Meihua::divine_by_gregorian_time(RuntimeOrigin::signed(1), [0u8; 32], false, 0, 0));
+            assert_eq!(hexagram_index_bytes(id as u64), case.expected);
+        }
+    });
+}
+
+/// 测试双数起卦与 TEE Enclave 共用的黄金向量一致
+#[test]
+fn golden_numbers_matches_enclave() {
+    use divination_algorithms::golden::MEIHUA_NUMBERS_CASES;
+
+    new_test_ext().execute_with(|| {
+        for (id, case) in MEIHUA_NUMBERS_CASES.iter().enumerate() {
+            pallet_timestamp::Now::<Test>::put(case.timestamp * 1000);
+
+            assert_ok!(Meihua::divine_by_numbers(
+                RuntimeOrigin::signed(1),
+                case.num1,
+                case.num2,
+                [0u8; 32],
+                false,
+                0,
+                0
+            ));
+            assert_eq!(hexagram_index_bytes(id as u64), case.expected);
+        }
+    });
+}
@@ -1,8 +1,12 @@
-//! Implementation of the MembershipProvider trait.
+//! Implementation of the MembershipProvider and RecurringPaymentHandler traits.
 
 use crate::{pallet::*, types::*, traits::*};
 use frame_support::dispatch::DispatchResult;
 use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_recurring_payment::{
+    traits::RecurringPaymentHandler,
+    types::{LapseReason, RecurringService},
+};
 use sp_runtime::DispatchError;
 
 impl<T: Config> MembershipProvider<T::AccountId, BalanceOf<T>, BlockNumberFor<T>> for Pallet<T> {
@@ -107,3 +111,25 @@ impl<T: Config> RewardPoolManager<BalanceOf<T>> for Pallet<T> {
         Self::get_pool_adjustment_factor()
     }
 }
+
+impl<T: Config> RecurringPaymentHandler<T::AccountId, BalanceOf<T>> for Pallet<T> {
+    fn on_payment_due(
+        _service: RecurringService,
+        payer: &T::AccountId,
+        subject: u64,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        let (tier, duration) =
+            Self::decode_auto_renew_subject(subject).ok_or(Error::<T>::InvalidTier)?;
+        Self::do_auto_renew(payer, tier, duration, amount)
+    }
+
+    fn on_payment_lapsed(
+        _service: RecurringService,
+        payer: &T::AccountId,
+        _subject: u64,
+        reason: LapseReason,
+    ) {
+        Self::on_auto_renew_lapsed(payer, reason)
+    }
+}
@@ -14,6 +14,7 @@
 //! ## Features
 //!
 //! - Subscribe to membership tiers with monthly/yearly billing
+//! - Opt-in auto-renewal charged through `pallet-recurring-payment` (explicit payer approval with spending caps)
 //! - Upgrade/downgrade membership tiers
 //! - Receive DUST rewards for activities (AI interpretation, reviews, etc.)
 //! - Daily check-in with consecutive day bonuses
@@ -66,6 +67,10 @@ pub mod pallet {
     use pallet_trading_common::PricingProvider;
     use pallet_affiliate::UserFundingProvider;
     use pallet_affiliate::types::AffiliateDistributor;
+    use pallet_recurring_payment::{
+        traits::RecurringPayments,
+        types::{LapseReason, PaymentPrice, RecurringService},
+    };
 
     pub type BalanceOf<T> =
         <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
@@ -143,6 +148,16 @@ pub mod pallet {
 
         /// 定价接口（用于 USDT 到 DUST 换算）
         type Pricing: pallet_trading_common::PricingProvider<BalanceOf<Self>>;
+
+        /// Recurring payment engine used for auto-renewal.
+        ///
+        /// Renewals are charged one day before expiry; the payer must approve
+        /// `RecurringService::DivinationMembership` in `pallet-recurring-payment` first.
+        type RecurringPayments: pallet_recurring_payment::traits::RecurringPayments<
+            Self::AccountId,
+            BalanceOf<Self>,
+            BlockNumberFor<Self>,
+        >;
     }
 
     // ============ Storage Items ============
@@ -279,6 +294,19 @@ pub mod pallet {
             who: T::AccountId,
             expires_at: BlockNumberFor<T>,
         },
+        /// A subscription was auto-renewed.
+        SubscriptionRenewed {
+            who: T::AccountId,
+            tier: MemberTier,
+            duration: SubscriptionDuration,
+            amount_paid: BalanceOf<T>,
+            expires_at: BlockNumberFor<T>,
+        },
+        /// Auto-renewal stopped (grace period expired, schedule cancelled or approval revoked).
+        AutoRenewLapsed {
+            who: T::AccountId,
+            reason: LapseReason,
+        },
         /// A membership expired.
         MembershipExpired {
             who: T::AccountId,
@@ -381,14 +409,15 @@ pub mod pallet {
         /// # Arguments
         /// * `tier` - The membership tier to subscribe to (cannot be Free).
         /// * `duration` - Monthly or Yearly subscription.
-        /// * `auto_renew` - Whether to auto-renew (currently disabled for regulatory compliance).
+        /// * `auto_renew` - Whether to auto-renew. Renewals are charged through
+        ///   `pallet-recurring-payment` and only succeed within the payer's approved spending caps.
         #[pallet::call_index(0)]
         #[pallet::weight(T::WeightInfo::subscribe())]
         pub fn subscribe(
             origin: OriginFor<T>,
             tier: MemberTier,
             duration: SubscriptionDuration,
-            _auto_renew: bool, // Ignored for now (regulatory compliance)
+            auto_renew: bool,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -432,11 +461,15 @@ pub mod pallet {
                     .map(|m| m.total_paid)
                     .unwrap_or_default()
                     .saturating_add(fee),
-                auto_renew: false, // Disabled for regulatory compliance
+                auto_renew,
             };
 
             Members::<T>::insert(&who, member_info);
 
+            if auto_renew {
+                Self::schedule_auto_renew(&who, tier, duration, expires_at)?;
+            }
+
             // Track first activity for cooldown
             if AccountCreationBlock::<T>::get(&who).is_none() {
                 AccountCreationBlock::<T>::insert(&who, now);
@@ -503,6 +536,13 @@ pub mod pallet {
             // 费用分配：销毁 5%，国库 2%，存储 3%，推荐链 90%
             Self::distribute_fee(&who, upgrade_cost)?;
 
+            // Auto-renewal continues at the new tier's price
+            if member.auto_renew {
+                if let Some(duration) = Self::auto_renew_duration(&who, member.tier) {
+                    Self::schedule_auto_renew(&who, new_tier, duration, member.expires_at)?;
+                }
+            }
+
             // Update tier (keep same expiration)
             let old_tier = member.tier;
             Members::<T>::mutate(&who, |m| {
@@ -548,6 +588,7 @@ pub mod pallet {
                     member.auto_renew = false;
                 }
             });
+            T::RecurringPayments::cancel_all(&who, RecurringService::DivinationMembership);
 
             Self::deposit_event(Event::SubscriptionCancelled {
                 who,
@@ -602,6 +643,9 @@ pub mod pallet {
 
             CheckInRecords::<T>::insert(&who, record.clone());
 
+            // Count towards referral activity (unlocks vesting referral rewards)
+            T::AffiliateDistributor::note_check_in(&who);
+
             // Calculate and grant reward
             let base_reward = Self::get_check_in_base_reward();
             let streak_multiplier = if record.streak >= 7 { 15000u32 } else { 10000u32 }; // 1.5x for 7+ days
@@ -881,6 +925,122 @@ pub mod pallet {
             Self::usdt_to_dust(total_usdt)
         }
 
+        /// Subscription length in blocks.
+        fn duration_blocks(duration: SubscriptionDuration) -> BlockNumberFor<T> {
+            match duration {
+                SubscriptionDuration::Monthly => T::BlocksPerMonth::get(),
+                SubscriptionDuration::Yearly => T::BlocksPerMonth::get().saturating_mul(12u32.into()),
+            }
+        }
+
+        /// Recurring payment subject for an auto-renewal: `tier << 8 | duration`.
+        pub fn auto_renew_subject(tier: MemberTier, duration: SubscriptionDuration) -> u64 {
+            ((tier as u64) << 8) | duration as u64
+        }
+
+        /// Decode an auto-renewal subject.
+        pub fn decode_auto_renew_subject(subject: u64) -> Option<(MemberTier, SubscriptionDuration)> {
+            let tier = MemberTier::from_u8(u8::try_from(subject >> 8).ok()?)?;
+            let duration = match subject & 0xff {
+                0 => SubscriptionDuration::Monthly,
+                1 => SubscriptionDuration::Yearly,
+                _ => return None,
+            };
+            Some((tier, duration))
+        }
+
+        /// Duration of the member's current auto-renewal schedule.
+        fn auto_renew_duration(who: &T::AccountId, tier: MemberTier) -> Option<SubscriptionDuration> {
+            [SubscriptionDuration::Monthly, SubscriptionDuration::Yearly]
+                .into_iter()
+                .find(|duration| {
+                    T::RecurringPayments::is_scheduled(
+                        who,
+                        RecurringService::DivinationMembership,
+                        Self::auto_renew_subject(tier, *duration),
+                    )
+                })
+        }
+
+        /// Create (or replace) the auto-renewal schedule.
+        ///
+        /// The USD price is pegged to the tier fee and converted at each renewal;
+        /// the current DUST fee is the fallback when pricing is unavailable.
+        fn schedule_auto_renew(
+            who: &T::AccountId,
+            tier: MemberTier,
+            duration: SubscriptionDuration,
+            expires_at: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let monthly_usdt = Self::get_tier_monthly_fee_usdt(tier);
+            let usd_amount = match duration {
+                SubscriptionDuration::Monthly => monthly_usdt,
+                SubscriptionDuration::Yearly => monthly_usdt.saturating_mul(10),
+            };
+            let price = PaymentPrice::UsdPegged {
+                usd_amount,
+                fallback: Self::calculate_subscription_fee(tier, duration),
+            };
+
+            T::RecurringPayments::cancel_all(who, RecurringService::DivinationMembership);
+            T::RecurringPayments::schedule(
+                who,
+                RecurringService::DivinationMembership,
+                Self::auto_renew_subject(tier, duration),
+                price,
+                Self::duration_blocks(duration),
+                expires_at.saturating_sub(T::BlocksPerDay::get()),
+            )?;
+            Ok(())
+        }
+
+        /// Charge an auto-renewal and extend the membership.
+        pub(crate) fn do_auto_renew(
+            who: &T::AccountId,
+            tier: MemberTier,
+            duration: SubscriptionDuration,
+            fee: BalanceOf<T>,
+        ) -> DispatchResult {
+            let member = Members::<T>::get(who).ok_or(Error::<T>::NotAMember)?;
+            ensure!(member.tier == tier, Error::<T>::InvalidTier);
+
+            Self::distribute_fee(who, fee)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let base = member.expires_at.max(now);
+            let expires_at = base.saturating_add(Self::duration_blocks(duration));
+            Members::<T>::mutate(who, |m| {
+                if let Some(member) = m {
+                    member.expires_at = expires_at;
+                    member.total_paid = member.total_paid.saturating_add(fee);
+                }
+            });
+
+            GlobalStats::<T>::mutate(|stats| {
+                stats.total_revenue = stats.total_revenue.saturating_add(fee);
+            });
+
+            Self::deposit_event(Event::SubscriptionRenewed {
+                who: who.clone(),
+                tier,
+                duration,
+                amount_paid: fee,
+                expires_at,
+            });
+
+            Ok(())
+        }
+
+        /// Stop auto-renewal after the recurring payment lapsed.
+        pub(crate) fn on_auto_renew_lapsed(who: &T::AccountId, reason: LapseReason) {
+            Members::<T>::mutate(who, |m| {
+                if let Some(member) = m {
+                    member.auto_renew = false;
+                }
+            });
+            Self::deposit_event(Event::AutoRenewLapsed { who: who.clone(), reason });
+        }
+
         /// Get storage deposit discount rate (basis points, 3000 = 30%).
         pub fn get_storage_discount(tier: MemberTier) -> u32 {
             match tier {
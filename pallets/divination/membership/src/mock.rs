@@ -22,6 +22,7 @@ frame_support::construct_runtime!(
         Balances: pallet_balances,
         Timestamp: pallet_timestamp,
         Membership: pallet_divination_membership,
+        RecurringPayment: pallet_recurring_payment,
     }
 );
 
@@ -151,6 +152,25 @@ impl pallet_divination_membership::Config for Test {
     type MaxEncryptedDataLength = MaxEncryptedDataLength;
     type MaxRewardHistorySize = MaxRewardHistorySize;
     type Pricing = MockPricingProvider;
+    type RecurringPayments = RecurringPayment;
+}
+
+parameter_types! {
+    pub const RecurringMinPeriod: u64 = 10;
+    pub const RecurringRetryInterval: u64 = 10;
+    pub const RecurringGracePeriod: u64 = 30;
+}
+
+impl pallet_recurring_payment::Config for Test {
+    type Balance = u128;
+    type Pricing = MockPricingProvider;
+    type Handler = Membership;
+    type MaxSchedulesPerPayer = ConstU32<4>;
+    type MaxChargesPerBlock = ConstU32<10>;
+    type MinPeriod = RecurringMinPeriod;
+    type RetryInterval = RecurringRetryInterval;
+    type GracePeriod = RecurringGracePeriod;
+    type WeightInfo = ();
 }
 
 /// Build genesis storage for testing.
@@ -188,6 +208,15 @@ pub fn run_to_block(n: u64) {
     }
 }
 
+/// Advance to specified block number, running recurring payments each block.
+pub fn run_recurring_to_block(n: u64) {
+    use frame_support::traits::Hooks;
+    while System::block_number() < n {
+        System::set_block_number(System::block_number() + 1);
+        RecurringPayment::on_initialize(System::block_number());
+    }
+}
+
 /// Advance by specified number of blocks.
 pub fn advance_blocks(n: u64) {
     let target = System::block_number() + n;
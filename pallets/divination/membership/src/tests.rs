@@ -660,3 +660,123 @@ fn global_stats_update_on_subscription() {
         assert_eq!(stats_after.tier_counts[MemberTier::Bronze as usize], 1);
     });
 }
+
+// ============ Auto-Renewal Tests ============
+
+use pallet_recurring_payment::{
+    traits::RecurringPayments as _,
+    types::{LapseReason, RecurringService},
+};
+
+fn approve_auto_renew(who: u64, max_per_payment: u128) {
+    assert_ok!(RecurringPayment::approve(
+        RuntimeOrigin::signed(who),
+        RecurringService::DivinationMembership,
+        max_per_payment,
+        None,
+    ));
+}
+
+#[test]
+fn auto_renew_charges_before_expiry() {
+    new_test_ext().execute_with(|| {
+        approve_auto_renew(1, 10 * DUST);
+        assert_ok!(Membership::subscribe(
+            RuntimeOrigin::signed(1),
+            MemberTier::Bronze,
+            SubscriptionDuration::Monthly,
+            true,
+        ));
+        let member = Membership::members(1).unwrap();
+        assert!(member.auto_renew);
+        assert_eq!(member.expires_at, 301);
+
+        // Charged one day (10 blocks) before expiry
+        let balance_before = balance(1);
+        run_recurring_to_block(291);
+        assert_eq!(balance_before - balance(1), 5 * DUST);
+
+        let member = Membership::members(1).unwrap();
+        assert_eq!(member.expires_at, 601);
+        assert_eq!(member.total_paid, 10 * DUST);
+        System::assert_has_event(
+            Event::SubscriptionRenewed {
+                who: 1,
+                tier: MemberTier::Bronze,
+                duration: SubscriptionDuration::Monthly,
+                amount_paid: 5 * DUST,
+                expires_at: 601,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn upgrade_tier_reschedules_auto_renew() {
+    new_test_ext().execute_with(|| {
+        approve_auto_renew(1, 100 * DUST);
+        assert_ok!(Membership::subscribe(
+            RuntimeOrigin::signed(1),
+            MemberTier::Bronze,
+            SubscriptionDuration::Monthly,
+            true,
+        ));
+        assert_ok!(Membership::upgrade_tier(RuntimeOrigin::signed(1), MemberTier::Silver));
+
+        let service = RecurringService::DivinationMembership;
+        let bronze = Membership::auto_renew_subject(MemberTier::Bronze, SubscriptionDuration::Monthly);
+        let silver = Membership::auto_renew_subject(MemberTier::Silver, SubscriptionDuration::Monthly);
+        assert!(!RecurringPayment::is_scheduled(&1, service, bronze));
+        assert!(RecurringPayment::is_scheduled(&1, service, silver));
+
+        // Renewal is charged at the Silver price
+        let balance_before = balance(1);
+        run_recurring_to_block(291);
+        assert_eq!(balance_before - balance(1), 25 * DUST);
+    });
+}
+
+#[test]
+fn auto_renew_lapses_when_cap_exceeded() {
+    new_test_ext().execute_with(|| {
+        // Cap below the 5 DUST Bronze fee
+        approve_auto_renew(1, DUST);
+        assert_ok!(Membership::subscribe(
+            RuntimeOrigin::signed(1),
+            MemberTier::Bronze,
+            SubscriptionDuration::Monthly,
+            true,
+        ));
+
+        // First attempt at 291, retries every 10 blocks until the 30-block grace period ends
+        run_recurring_to_block(321);
+        let member = Membership::members(1).unwrap();
+        assert!(!member.auto_renew);
+        assert_eq!(member.expires_at, 301);
+        System::assert_has_event(
+            Event::AutoRenewLapsed { who: 1, reason: LapseReason::GraceExpired }.into(),
+        );
+    });
+}
+
+#[test]
+fn cancel_subscription_cancels_auto_renew() {
+    new_test_ext().execute_with(|| {
+        approve_auto_renew(1, 10 * DUST);
+        assert_ok!(Membership::subscribe(
+            RuntimeOrigin::signed(1),
+            MemberTier::Bronze,
+            SubscriptionDuration::Monthly,
+            true,
+        ));
+        assert_ok!(Membership::cancel_subscription(RuntimeOrigin::signed(1)));
+
+        let subject = Membership::auto_renew_subject(MemberTier::Bronze, SubscriptionDuration::Monthly);
+        assert!(!RecurringPayment::is_scheduled(&1, RecurringService::DivinationMembership, subject));
+
+        let balance_before = balance(1);
+        run_recurring_to_block(291);
+        assert_eq!(balance(1), balance_before);
+    });
+}
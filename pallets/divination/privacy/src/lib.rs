@@ -4,11 +4,13 @@
 //!
 //! ## 功能概述
 //!
-//! 1. **密钥管理**：用户注册和更新 X25519 加密公钥
+//! 1. **密钥管理**：加密公钥只从 `pallet-key-directory` 读取（本模块的
+//!    `register_encryption_key` 已废弃）；目录轮换时提示重新包装授权密钥
 //! 2. **服务提供者管理**：命理师、AI 服务、家族成员注册
 //! 3. **加密数据存储**：AES-256-GCM 加密的敏感数据存储
 //! 4. **授权管理**：多方授权、角色控制、范围控制
 //! 5. **悬赏集成**：与悬赏系统的授权集成
+//! 6. **社交恢复**：Shamir k-of-n 守护人门限恢复加密私钥，带时间锁与所有者否决
 //!
 //! ## 架构设计
 //!
@@ -39,9 +41,9 @@
 //!
 //! ## 使用示例
 //!
-//! ### 1. 用户注册加密公钥
+//! ### 1. 用户注册加密公钥（统一公钥目录）
 //! ```ignore
-//! Privacy::register_encryption_key(origin, public_key)?;
+//! KeyDirectory::register_key(origin, KeyPurpose::Encryption, KeyAlgorithm::X25519, public_key)?;
 //! ```
 //!
 //! ### 2. 创建加密记录
@@ -94,6 +96,9 @@ pub mod weights;
 use frame_support::pallet_prelude::*;
 use frame_system::pallet_prelude::*;
 use pallet_divination_common::DivinationType;
+use pallet_key_directory::traits::{KeyChangeHandler, KeyDirectory};
+use pallet_key_directory::types::KeyPurpose;
+use sp_runtime::traits::Saturating;
 use sp_std::vec::Vec;
 
 use crate::traits::{BountyPrivacy, DivinationPrivacy, PrivacyEventHandler};
@@ -145,6 +150,21 @@ pub mod pallet {
         /// 隐私事件回调处理器
         type EventHandler: PrivacyEventHandler<Self::AccountId>;
 
+        /// 统一公钥目录（加密公钥的唯一来源）
+        type KeyDirectory: KeyDirectory<Self::AccountId>;
+
+        /// 社交恢复最大守护人数量（不超过 255）
+        #[pallet::constant]
+        type MaxGuardians: Get<u32>;
+
+        /// 社交恢复最短等待期（所有者可在此期间否决）
+        #[pallet::constant]
+        type MinRecoveryDelay: Get<BlockNumberFor<Self>>;
+
+        /// 所有者否决后守护人不能再次发起恢复的冷却期
+        #[pallet::constant]
+        type RecoveryVetoCooldown: Get<BlockNumberFor<Self>>;
+
         /// 权重信息
         type WeightInfo: weights::WeightInfo;
     }
@@ -155,9 +175,9 @@ pub mod pallet {
 
     // -------------------- 用户密钥管理 --------------------
 
-    /// 用户加密公钥
+    /// [已废弃] 用户加密公钥 - 加密公钥改由 `pallet-key-directory` 提供
     ///
-    /// AccountId -> UserEncryptionInfo
+    /// AccountId -> UserEncryptionInfo，仅保留历史数据供客户端迁移，模块内不再读取
     #[pallet::storage]
     #[pallet::getter(fn user_encryption_keys)]
     pub type UserEncryptionKeys<T: Config> =
@@ -234,6 +254,22 @@ pub mod pallet {
         AuthorizationEntry<T::AccountId, BlockNumberFor<T>, T::MaxEncryptedKeyLen>,
     >;
 
+    /// 授权条目数据密钥包装时被授权者的目录加密公钥版本
+    ///
+    /// (DivinationType, result_id, grantee) -> key_version（未在公钥目录注册为 0）
+    #[pallet::storage]
+    #[pallet::getter(fn authorization_key_version)]
+    pub type AuthorizationKeyVersions<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, DivinationType>,
+            NMapKey<Blake2_128Concat, u64>,
+            NMapKey<Blake2_128Concat, T::AccountId>,
+        ),
+        u32,
+        ValueQuery,
+    >;
+
     /// 记录的授权列表索引
     ///
     /// (DivinationType, result_id) -> Vec<grantee>
@@ -285,6 +321,68 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    // -------------------- 社交恢复 --------------------
+
+    /// 社交恢复配置
+    ///
+    /// AccountId -> RecoveryConfig
+    #[pallet::storage]
+    #[pallet::getter(fn recovery_configs)]
+    pub type RecoveryConfigs<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        RecoveryConfig<T::AccountId, BlockNumberFor<T>, T::MaxGuardians, T::MaxEncryptedKeyLen>,
+    >;
+
+    /// 守护人持有的 KEK 份额（用守护人公钥加密）
+    ///
+    /// (owner, guardian) -> encrypted_share
+    #[pallet::storage]
+    #[pallet::getter(fn guardian_shares)]
+    pub type GuardianShares<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<u8, T::MaxEncryptedKeyLen>,
+    >;
+
+    /// 进行中的恢复流程
+    ///
+    /// AccountId -> ActiveRecovery
+    #[pallet::storage]
+    #[pallet::getter(fn active_recoveries)]
+    pub type ActiveRecoveries<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        ActiveRecovery<T::AccountId, BlockNumberFor<T>, T::MaxGuardians>,
+    >;
+
+    /// 否决冷却期截止区块（期间守护人不能再次发起恢复）
+    ///
+    /// AccountId -> cooldown_until
+    #[pallet::storage]
+    #[pallet::getter(fn recovery_cooldowns)]
+    pub type RecoveryCooldowns<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BlockNumberFor<T>>;
+
+    /// 恢复中释放的份额（用恢复方临时公钥重新加密）
+    ///
+    /// (owner, guardian) -> encrypted_share
+    #[pallet::storage]
+    #[pallet::getter(fn recovery_shares)]
+    pub type RecoveryShares<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<u8, T::MaxEncryptedKeyLen>,
+    >;
+
     // ========================================================================
     // 事件定义
     // ========================================================================
@@ -395,6 +493,21 @@ pub mod pallet {
             new_scope: AccessScope,
         },
 
+        /// 被授权者轮换了加密公钥，记录所有者需用新公钥重新包装数据密钥
+        AuthorizationRewrapRequired {
+            divination_type: DivinationType,
+            result_id: u64,
+            grantee: T::AccountId,
+            key_version: u32,
+        },
+
+        /// 授权条目的数据密钥已重新包装
+        AccessKeyRewrapped {
+            divination_type: DivinationType,
+            result_id: u64,
+            grantee: T::AccountId,
+        },
+
         // -------------------- 悬赏授权事件 --------------------
 
         /// 创建悬赏授权配置
@@ -413,6 +526,47 @@ pub mod pallet {
 
         /// 悬赏授权撤销
         BountyAuthorizationsRevoked { bounty_id: u64, count: u32 },
+
+        // -------------------- 社交恢复事件 --------------------
+
+        /// 设置社交恢复配置
+        RecoveryConfigured {
+            who: T::AccountId,
+            guardians: u32,
+            threshold: u8,
+        },
+
+        /// 移除社交恢复配置
+        RecoveryConfigRemoved { who: T::AccountId },
+
+        /// 发起恢复
+        RecoveryInitiated {
+            account: T::AccountId,
+            initiator: T::AccountId,
+            unlock_at: BlockNumberFor<T>,
+        },
+
+        /// 守护人批准恢复
+        RecoveryApproved {
+            account: T::AccountId,
+            guardian: T::AccountId,
+            approvals: u32,
+        },
+
+        /// 所有者否决恢复
+        RecoveryVetoed { account: T::AccountId },
+
+        /// 守护人提交份额
+        RecoveryShareSubmitted {
+            account: T::AccountId,
+            guardian: T::AccountId,
+        },
+
+        /// 已提交的份额达到门限，恢复方可重组 KEK
+        RecoveryReady { account: T::AccountId },
+
+        /// 恢复流程关闭
+        RecoveryClosed { account: T::AccountId },
     }
 
     // ========================================================================
@@ -516,6 +670,59 @@ pub mod pallet {
         /// encrypted_fields 值无效或包含未定义的标志位
         InvalidEncryptedFields,
 
+        // -------------------- 社交恢复错误 --------------------
+
+        /// 未设置恢复配置
+        RecoveryNotConfigured,
+
+        /// 守护人列表为空或超出上限
+        InvalidGuardianCount,
+
+        /// 门限无效（需满足 1 <= threshold <= 守护人数量）
+        InvalidRecoveryThreshold,
+
+        /// 守护人重复
+        DuplicateGuardian,
+
+        /// 不能将自己设为守护人
+        CannotGuardSelf,
+
+        /// 守护人未注册加密公钥
+        GuardianKeyNotRegistered,
+
+        /// 等待期短于最低要求
+        RecoveryDelayTooShort,
+
+        /// 已有进行中的恢复
+        RecoveryInProgress,
+
+        /// 没有进行中的恢复
+        NoActiveRecovery,
+
+        /// 不是守护人
+        NotGuardian,
+
+        /// 守护人已批准
+        AlreadyApproved,
+
+        /// 守护人未批准该恢复
+        GuardianNotApproved,
+
+        /// 批准数未达门限
+        InsufficientApprovals,
+
+        /// 等待期尚未结束
+        RecoveryStillLocked,
+
+        /// 份额已提交
+        ShareAlreadySubmitted,
+
+        /// 份额已达门限，恢复已完成，无法否决
+        RecoveryAlreadyReady,
+
+        /// 份额未达门限，恢复未完成
+        RecoveryNotReady,
+
         // -------------------- 通用错误 --------------------
 
         /// 无访问权限
@@ -526,6 +733,15 @@ pub mod pallet {
 
         /// 数值溢出
         Overflow,
+
+        /// 被授权者的加密公钥未轮换，无需重新包装
+        GranteeKeyNotRotated,
+
+        /// 所有者刚否决过恢复，守护人需等待冷却期结束
+        RecoveryCooldownActive,
+
+        /// 加密公钥已迁移到统一公钥目录，请使用 key_directory 注册或轮换
+        UseKeyDirectory,
     }
 
     // ========================================================================
@@ -538,104 +754,38 @@ pub mod pallet {
         // 密钥管理
         // ====================================================================
 
-        /// 注册用户加密公钥
+        /// [已废弃] 注册用户加密公钥 - 请使用 key_directory.register_key
         ///
-        /// 用户首次注册自己的 X25519 公钥，用于接收加密数据。
-        ///
-        /// # 参数
-        /// - `origin`: 交易发起者
-        /// - `public_key`: X25519 公钥（32 字节）
+        /// 加密公钥只从统一公钥目录读取，本调用不再写入存储。
         ///
         /// # 错误
-        /// - `EncryptionKeyAlreadyRegistered`: 已注册公钥
-        /// - `InvalidPublicKey`: 公钥格式无效
+        /// - `UseKeyDirectory`: 始终返回，提示改用统一公钥目录
+        #[deprecated(note = "Use key_directory.register_key with KeyPurpose::Encryption instead")]
         #[pallet::call_index(0)]
         #[pallet::weight(<T as Config>::WeightInfo::register_encryption_key())]
         pub fn register_encryption_key(
             origin: OriginFor<T>,
-            public_key: [u8; 32],
+            _public_key: [u8; 32],
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-
-            // 检查是否已注册
-            ensure!(
-                !UserEncryptionKeys::<T>::contains_key(&who),
-                Error::<T>::EncryptionKeyAlreadyRegistered
-            );
-
-            // 验证公钥（非全零）
-            ensure!(
-                public_key != [0u8; 32],
-                Error::<T>::InvalidPublicKey
-            );
-
-            let current_block = frame_system::Pallet::<T>::block_number();
-
-            // 创建用户加密信息
-            let info = UserEncryptionInfo {
-                public_key,
-                registered_at: current_block,
-                updated_at: current_block,
-            };
-
-            // 存储
-            UserEncryptionKeys::<T>::insert(&who, info);
-
-            // 触发事件
-            Self::deposit_event(Event::EncryptionKeyRegistered {
-                account: who,
-                public_key,
-            });
-
-            Ok(())
+            ensure_signed(origin)?;
+            Err(Error::<T>::UseKeyDirectory.into())
         }
 
-        /// 更新用户加密公钥
-        ///
-        /// 用户更新自己的加密公钥。注意：更新后需要重新为所有授权方加密数据密钥。
+        /// [已废弃] 更新用户加密公钥 - 请使用 key_directory.rotate_key
         ///
-        /// # 参数
-        /// - `origin`: 交易发起者
-        /// - `new_public_key`: 新的 X25519 公钥（32 字节）
+        /// 目录轮换会触发 `AuthorizationRewrapRequired`，提示重新包装授权密钥。
         ///
         /// # 错误
-        /// - `EncryptionKeyNotRegistered`: 未注册公钥
-        /// - `InvalidPublicKey`: 新公钥格式无效
+        /// - `UseKeyDirectory`: 始终返回，提示改用统一公钥目录
+        #[deprecated(note = "Use key_directory.rotate_key with KeyPurpose::Encryption instead")]
         #[pallet::call_index(1)]
         #[pallet::weight(<T as Config>::WeightInfo::update_encryption_key())]
         pub fn update_encryption_key(
             origin: OriginFor<T>,
-            new_public_key: [u8; 32],
+            _new_public_key: [u8; 32],
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-
-            // 获取现有信息
-            let mut info = UserEncryptionKeys::<T>::get(&who)
-                .ok_or(Error::<T>::EncryptionKeyNotRegistered)?;
-
-            // 验证新公钥
-            ensure!(
-                new_public_key != [0u8; 32],
-                Error::<T>::InvalidPublicKey
-            );
-
-            let old_key = info.public_key;
-
-            // 更新公钥
-            info.public_key = new_public_key;
-            info.updated_at = frame_system::Pallet::<T>::block_number();
-
-            // 存储
-            UserEncryptionKeys::<T>::insert(&who, info);
-
-            // 触发事件
-            Self::deposit_event(Event::EncryptionKeyUpdated {
-                account: who,
-                old_key,
-                new_key: new_public_key,
-            });
-
-            Ok(())
+            ensure_signed(origin)?;
+            Err(Error::<T>::UseKeyDirectory.into())
         }
 
         // ====================================================================
@@ -909,6 +1059,7 @@ pub mod pallet {
                     (divination_type, result_id, &who),
                     owner_auth,
                 );
+                Self::record_key_version(divination_type, result_id, &who);
 
                 // 添加到授权列表
                 RecordGrantees::<T>::try_mutate(divination_type, result_id, |list| {
@@ -1077,6 +1228,7 @@ pub mod pallet {
             let grantees = RecordGrantees::<T>::take(divination_type, result_id);
             for grantee in grantees.iter() {
                 Authorizations::<T>::remove((divination_type, result_id, grantee));
+                AuthorizationKeyVersions::<T>::remove((divination_type, result_id, grantee));
 
                 // 从提供者授权列表中移除
                 ProviderGrants::<T>::mutate(grantee, |list| {
@@ -1177,6 +1329,7 @@ pub mod pallet {
 
             // 存储授权
             Authorizations::<T>::insert((divination_type, result_id, &grantee), auth_entry);
+            Self::record_key_version(divination_type, result_id, &grantee);
 
             // 添加到授权列表
             RecordGrantees::<T>::try_mutate(divination_type, result_id, |list| {
@@ -1247,6 +1400,7 @@ pub mod pallet {
 
             // 删除授权
             Authorizations::<T>::remove((divination_type, result_id, &grantee));
+            AuthorizationKeyVersions::<T>::remove((divination_type, result_id, &grantee));
 
             // 从授权列表中移除
             RecordGrantees::<T>::mutate(divination_type, result_id, |list| {
@@ -1316,6 +1470,7 @@ pub mod pallet {
 
                     // 删除授权
                     Authorizations::<T>::remove((divination_type, result_id, grantee));
+                    AuthorizationKeyVersions::<T>::remove((divination_type, result_id, grantee));
 
                     // 从提供者授权列表中移除
                     ProviderGrants::<T>::mutate(grantee, |list| {
@@ -1404,6 +1559,71 @@ pub mod pallet {
             Ok(())
         }
 
+        /// 重新包装授权数据密钥
+        ///
+        /// 被授权者（或所有者本人）在公钥目录中轮换加密公钥后，所有者在重叠窗口内
+        /// 用旧私钥解出数据密钥，再用新公钥包装并替换授权条目中的密钥。
+        ///
+        /// # 参数
+        /// - `origin`: 交易发起者（必须是所有者）
+        /// - `divination_type`: 占卜类型
+        /// - `result_id`: 结果 ID
+        /// - `grantee`: 被授权账户
+        /// - `encrypted_key`: 用被授权者新公钥加密的数据密钥
+        ///
+        /// # 错误
+        /// - `EncryptedRecordNotFound`: 记录不存在
+        /// - `NotRecordOwner`: 不是记录所有者
+        /// - `AuthorizationNotFound`: 授权不存在
+        /// - `GranteeKeyNotRotated`: 被授权者的目录加密公钥在上次包装后未轮换
+        /// - `EncryptedKeyTooLong`: 加密密钥过长
+        #[pallet::call_index(34)]
+        #[pallet::weight(<T as Config>::WeightInfo::rewrap_access_key())]
+        pub fn rewrap_access_key(
+            origin: OriginFor<T>,
+            divination_type: DivinationType,
+            result_id: u64,
+            grantee: T::AccountId,
+            encrypted_key: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            // 获取记录并验证所有者
+            let record = EncryptedRecords::<T>::get(divination_type, result_id)
+                .ok_or(Error::<T>::EncryptedRecordNotFound)?;
+
+            ensure!(record.owner == who, Error::<T>::NotRecordOwner);
+
+            let bounded_key: BoundedVec<u8, T::MaxEncryptedKeyLen> = encrypted_key
+                .try_into()
+                .map_err(|_| Error::<T>::EncryptedKeyTooLong)?;
+
+            Authorizations::<T>::try_mutate(
+                (divination_type, result_id, &grantee),
+                |maybe_auth| {
+                    let auth = maybe_auth.as_mut().ok_or(Error::<T>::AuthorizationNotFound)?;
+                    auth.encrypted_key = bounded_key;
+                    Ok::<(), Error<T>>(())
+                },
+            )?;
+
+            // 只有被授权者轮换公钥后才能替换其数据密钥
+            let key_version = Self::directory_key_version(&grantee);
+            ensure!(
+                key_version > AuthorizationKeyVersions::<T>::get((divination_type, result_id, &grantee)),
+                Error::<T>::GranteeKeyNotRotated
+            );
+            AuthorizationKeyVersions::<T>::insert((divination_type, result_id, &grantee), key_version);
+
+            Self::deposit_event(Event::AccessKeyRewrapped {
+                divination_type,
+                result_id,
+                grantee,
+            });
+
+            Ok(())
+        }
+
         // ====================================================================
         // 悬赏授权集成
         // ====================================================================
@@ -1543,6 +1763,7 @@ pub mod pallet {
                 (auth_info.divination_type, auth_info.result_id, &answerer),
                 auth_entry,
             );
+            Self::record_key_version(auth_info.divination_type, auth_info.result_id, &answerer);
 
             // 添加到记录授权列表
             RecordGrantees::<T>::try_mutate(
@@ -1618,6 +1839,11 @@ pub mod pallet {
                     auth_info.result_id,
                     answerer,
                 ));
+                AuthorizationKeyVersions::<T>::remove((
+                    auth_info.divination_type,
+                    auth_info.result_id,
+                    answerer,
+                ));
 
                 // 从记录授权列表中移除
                 RecordGrantees::<T>::mutate(
@@ -1648,6 +1874,369 @@ pub mod pallet {
 
             Ok(())
         }
+
+        // ====================================================================
+        // 社交恢复
+        // ====================================================================
+
+        /// 设置社交恢复配置
+        ///
+        /// 用户在本地生成 KEK，用 KEK 加密自己的 X25519 私钥得到 `key_backup`，
+        /// 将 KEK 以 Shamir k-of-n 拆分，并把每个份额用对应守护人的公钥加密后上链。
+        /// 重复调用会整体替换旧配置和旧份额。
+        ///
+        /// # 参数
+        /// - `origin`: 交易发起者（被保护的账户）
+        /// - `shares`: (守护人, 用守护人公钥加密的 KEK 份额) 列表
+        /// - `threshold`: 恢复所需守护人数量
+        /// - `delay`: 恢复等待期（区块数）
+        /// - `key_backup`: 用 KEK 加密的私钥
+        /// - `kek_hash`: KEK 的 Blake2-256 哈希
+        ///
+        /// # 错误
+        /// - `RecoveryInProgress`: 存在进行中的恢复
+        /// - `InvalidGuardianCount` / `InvalidRecoveryThreshold`: 数量或门限无效
+        /// - `DuplicateGuardian` / `CannotGuardSelf`: 守护人无效
+        /// - `GuardianKeyNotRegistered`: 守护人未注册加密公钥
+        /// - `RecoveryDelayTooShort`: 等待期过短
+        /// - `EncryptedKeyTooLong`: 备份或份额过长
+        #[pallet::call_index(50)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_recovery_config(shares.len() as u32))]
+        pub fn set_recovery_config(
+            origin: OriginFor<T>,
+            shares: Vec<(T::AccountId, Vec<u8>)>,
+            threshold: u8,
+            delay: BlockNumberFor<T>,
+            key_backup: Vec<u8>,
+            kek_hash: [u8; 32],
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                !ActiveRecoveries::<T>::contains_key(&who),
+                Error::<T>::RecoveryInProgress
+            );
+            ensure!(
+                !shares.is_empty()
+                    && shares.len() as u32 <= T::MaxGuardians::get()
+                    && shares.len() <= u8::MAX as usize,
+                Error::<T>::InvalidGuardianCount
+            );
+            ensure!(
+                threshold >= 1 && threshold as usize <= shares.len(),
+                Error::<T>::InvalidRecoveryThreshold
+            );
+            ensure!(
+                delay >= T::MinRecoveryDelay::get(),
+                Error::<T>::RecoveryDelayTooShort
+            );
+
+            let mut guardians: BoundedVec<T::AccountId, T::MaxGuardians> = BoundedVec::new();
+            let mut bounded_shares = Vec::with_capacity(shares.len());
+            for (guardian, share) in shares {
+                ensure!(guardian != who, Error::<T>::CannotGuardSelf);
+                ensure!(!guardians.contains(&guardian), Error::<T>::DuplicateGuardian);
+                ensure!(
+                    Self::encryption_key_of(&guardian).is_some(),
+                    Error::<T>::GuardianKeyNotRegistered
+                );
+                let bounded_share: BoundedVec<u8, T::MaxEncryptedKeyLen> =
+                    share.try_into().map_err(|_| Error::<T>::EncryptedKeyTooLong)?;
+                guardians
+                    .try_push(guardian.clone())
+                    .map_err(|_| Error::<T>::InvalidGuardianCount)?;
+                bounded_shares.push((guardian, bounded_share));
+            }
+
+            let key_backup: BoundedVec<u8, T::MaxEncryptedKeyLen> =
+                key_backup.try_into().map_err(|_| Error::<T>::EncryptedKeyTooLong)?;
+
+            // 替换旧配置时先清理旧份额
+            if let Some(old) = RecoveryConfigs::<T>::get(&who) {
+                for guardian in old.guardians.iter() {
+                    GuardianShares::<T>::remove(&who, guardian);
+                }
+            }
+            for (guardian, share) in bounded_shares {
+                GuardianShares::<T>::insert(&who, &guardian, share);
+            }
+
+            let guardian_count = guardians.len() as u32;
+            RecoveryConfigs::<T>::insert(
+                &who,
+                RecoveryConfig {
+                    guardians,
+                    threshold,
+                    delay,
+                    key_backup,
+                    kek_hash,
+                    configured_at: frame_system::Pallet::<T>::block_number(),
+                },
+            );
+
+            Self::deposit_event(Event::RecoveryConfigured {
+                who,
+                guardians: guardian_count,
+                threshold,
+            });
+
+            Ok(())
+        }
+
+        /// 移除社交恢复配置
+        ///
+        /// # 错误
+        /// - `RecoveryNotConfigured`: 未设置恢复配置
+        /// - `RecoveryInProgress`: 存在进行中的恢复
+        #[pallet::call_index(51)]
+        #[pallet::weight(<T as Config>::WeightInfo::remove_recovery_config(T::MaxGuardians::get()))]
+        pub fn remove_recovery_config(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                !ActiveRecoveries::<T>::contains_key(&who),
+                Error::<T>::RecoveryInProgress
+            );
+            let config =
+                RecoveryConfigs::<T>::take(&who).ok_or(Error::<T>::RecoveryNotConfigured)?;
+            for guardian in config.guardians.iter() {
+                GuardianShares::<T>::remove(&who, guardian);
+            }
+
+            Self::deposit_event(Event::RecoveryConfigRemoved { who });
+
+            Ok(())
+        }
+
+        /// 发起恢复
+        ///
+        /// 由账户所有者（例如用助记词恢复签名密钥的新设备）或任一守护人发起。
+        /// 守护人之后会把份额重新加密给 `recipient_key`，因此恢复方应为本次
+        /// 恢复生成一次性的 X25519 密钥对。所有者否决后 `RecoveryVetoCooldown`
+        /// 个区块内只有所有者本人可以再次发起。
+        ///
+        /// # 参数
+        /// - `account`: 待恢复的账户
+        /// - `recipient_key`: 接收份额的临时 X25519 公钥
+        ///
+        /// # 错误
+        /// - `RecoveryNotConfigured`: 账户未设置恢复配置
+        /// - `RecoveryInProgress`: 已有进行中的恢复
+        /// - `Unauthorized`: 发起者既不是所有者也不是守护人
+        /// - `RecoveryCooldownActive`: 守护人在否决冷却期内发起
+        /// - `InvalidPublicKey`: 公钥无效
+        #[pallet::call_index(52)]
+        #[pallet::weight(<T as Config>::WeightInfo::initiate_recovery())]
+        pub fn initiate_recovery(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+            recipient_key: [u8; 32],
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let config =
+                RecoveryConfigs::<T>::get(&account).ok_or(Error::<T>::RecoveryNotConfigured)?;
+            ensure!(
+                !ActiveRecoveries::<T>::contains_key(&account),
+                Error::<T>::RecoveryInProgress
+            );
+            ensure!(
+                who == account || config.guardians.contains(&who),
+                Error::<T>::Unauthorized
+            );
+            ensure!(recipient_key != [0u8; 32], Error::<T>::InvalidPublicKey);
+
+            let now = frame_system::Pallet::<T>::block_number();
+            if let Some(cooldown_until) = RecoveryCooldowns::<T>::get(&account) {
+                ensure!(
+                    who == account || now >= cooldown_until,
+                    Error::<T>::RecoveryCooldownActive
+                );
+                if now >= cooldown_until {
+                    RecoveryCooldowns::<T>::remove(&account);
+                }
+            }
+            let unlock_at = now.saturating_add(config.delay);
+
+            ActiveRecoveries::<T>::insert(
+                &account,
+                ActiveRecovery {
+                    initiator: who.clone(),
+                    recipient_key,
+                    started_at: now,
+                    unlock_at,
+                    approvals: BoundedVec::new(),
+                    shares_submitted: 0,
+                },
+            );
+
+            Self::deposit_event(Event::RecoveryInitiated {
+                account,
+                initiator: who,
+                unlock_at,
+            });
+
+            Ok(())
+        }
+
+        /// 守护人批准恢复
+        ///
+        /// 守护人应通过链下渠道确认恢复请求确实来自所有者后再批准。
+        ///
+        /// # 错误
+        /// - `NoActiveRecovery`: 没有进行中的恢复
+        /// - `NotGuardian`: 不是守护人
+        /// - `AlreadyApproved`: 已批准
+        #[pallet::call_index(53)]
+        #[pallet::weight(<T as Config>::WeightInfo::approve_recovery())]
+        pub fn approve_recovery(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let config =
+                RecoveryConfigs::<T>::get(&account).ok_or(Error::<T>::RecoveryNotConfigured)?;
+            ensure!(config.guardians.contains(&who), Error::<T>::NotGuardian);
+
+            let approvals = ActiveRecoveries::<T>::try_mutate(&account, |maybe_recovery| {
+                let recovery = maybe_recovery.as_mut().ok_or(Error::<T>::NoActiveRecovery)?;
+                ensure!(!recovery.approvals.contains(&who), Error::<T>::AlreadyApproved);
+                recovery
+                    .approvals
+                    .try_push(who.clone())
+                    .map_err(|_| Error::<T>::InvalidGuardianCount)?;
+                Ok::<u32, Error<T>>(recovery.approvals.len() as u32)
+            })?;
+
+            Self::deposit_event(Event::RecoveryApproved {
+                account,
+                guardian: who,
+                approvals,
+            });
+
+            Ok(())
+        }
+
+        /// 所有者否决恢复
+        ///
+        /// 在份额达到门限之前，所有者可随时取消恢复流程（包括已解锁但份额
+        /// 尚未集齐的情况），已提交的份额一并清除。否决后进入冷却期，
+        /// 防止守护人反复发起恢复消耗所有者的否决窗口。
+        ///
+        /// # 错误
+        /// - `NoActiveRecovery`: 没有进行中的恢复
+        /// - `RecoveryAlreadyReady`: 份额已达门限
+        #[pallet::call_index(54)]
+        #[pallet::weight(<T as Config>::WeightInfo::veto_recovery(T::MaxGuardians::get()))]
+        pub fn veto_recovery(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let recovery =
+                ActiveRecoveries::<T>::get(&who).ok_or(Error::<T>::NoActiveRecovery)?;
+            let config =
+                RecoveryConfigs::<T>::get(&who).ok_or(Error::<T>::RecoveryNotConfigured)?;
+            ensure!(
+                recovery.shares_submitted < config.threshold,
+                Error::<T>::RecoveryAlreadyReady
+            );
+
+            Self::clear_recovery(&who, &config);
+            let cooldown_until = frame_system::Pallet::<T>::block_number()
+                .saturating_add(T::RecoveryVetoCooldown::get());
+            RecoveryCooldowns::<T>::insert(&who, cooldown_until);
+
+            Self::deposit_event(Event::RecoveryVetoed { account: who });
+
+            Ok(())
+        }
+
+        /// 守护人提交重新加密的份额
+        ///
+        /// 仅在批准数达到门限且等待期结束后允许，且只有已批准的守护人可以提交。
+        /// 份额应使用 `ActiveRecovery::recipient_key` 加密。
+        ///
+        /// # 错误
+        /// - `NoActiveRecovery`: 没有进行中的恢复
+        /// - `GuardianNotApproved`: 守护人未批准
+        /// - `InsufficientApprovals`: 批准数未达门限
+        /// - `RecoveryStillLocked`: 等待期未结束
+        /// - `ShareAlreadySubmitted`: 份额已提交
+        /// - `EncryptedKeyTooLong`: 份额过长
+        #[pallet::call_index(55)]
+        #[pallet::weight(<T as Config>::WeightInfo::submit_recovery_share())]
+        pub fn submit_recovery_share(
+            origin: OriginFor<T>,
+            account: T::AccountId,
+            encrypted_share: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let config =
+                RecoveryConfigs::<T>::get(&account).ok_or(Error::<T>::RecoveryNotConfigured)?;
+            let bounded_share: BoundedVec<u8, T::MaxEncryptedKeyLen> =
+                encrypted_share.try_into().map_err(|_| Error::<T>::EncryptedKeyTooLong)?;
+            ensure!(
+                !RecoveryShares::<T>::contains_key(&account, &who),
+                Error::<T>::ShareAlreadySubmitted
+            );
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let submitted = ActiveRecoveries::<T>::try_mutate(&account, |maybe_recovery| {
+                let recovery = maybe_recovery.as_mut().ok_or(Error::<T>::NoActiveRecovery)?;
+                ensure!(recovery.approvals.contains(&who), Error::<T>::GuardianNotApproved);
+                ensure!(
+                    recovery.approvals.len() >= config.threshold as usize,
+                    Error::<T>::InsufficientApprovals
+                );
+                ensure!(now >= recovery.unlock_at, Error::<T>::RecoveryStillLocked);
+                recovery.shares_submitted = recovery.shares_submitted.saturating_add(1);
+                Ok::<u8, Error<T>>(recovery.shares_submitted)
+            })?;
+
+            RecoveryShares::<T>::insert(&account, &who, bounded_share);
+
+            Self::deposit_event(Event::RecoveryShareSubmitted {
+                account: account.clone(),
+                guardian: who,
+            });
+            if submitted == config.threshold {
+                Self::deposit_event(Event::RecoveryReady { account });
+            }
+
+            Ok(())
+        }
+
+        /// 关闭已完成的恢复
+        ///
+        /// 恢复方取回份额并重组 KEK 后调用，清理份额与流程状态。恢复出的私钥
+        /// 应尽快通过公钥目录轮换，并对已有授权重新包装。
+        ///
+        /// # 错误
+        /// - `NoActiveRecovery`: 没有进行中的恢复
+        /// - `Unauthorized`: 不是发起者或所有者
+        /// - `RecoveryNotReady`: 份额未达门限
+        #[pallet::call_index(56)]
+        #[pallet::weight(<T as Config>::WeightInfo::close_recovery(T::MaxGuardians::get()))]
+        pub fn close_recovery(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let recovery =
+                ActiveRecoveries::<T>::get(&account).ok_or(Error::<T>::NoActiveRecovery)?;
+            ensure!(
+                who == recovery.initiator || who == account,
+                Error::<T>::Unauthorized
+            );
+            let config =
+                RecoveryConfigs::<T>::get(&account).ok_or(Error::<T>::RecoveryNotConfigured)?;
+            ensure!(
+                recovery.shares_submitted >= config.threshold,
+                Error::<T>::RecoveryNotReady
+            );
+
+            Self::clear_recovery(&account, &config);
+
+            Self::deposit_event(Event::RecoveryClosed { account });
+
+            Ok(())
+        }
     }
 
     // ========================================================================
@@ -1732,7 +2321,7 @@ pub mod pallet {
         }
 
         fn get_user_public_key(account: &T::AccountId) -> Option<[u8; 32]> {
-            UserEncryptionKeys::<T>::get(account).map(|info| info.public_key)
+            Self::encryption_key_of(account)
         }
 
         fn get_provider_type(account: &T::AccountId) -> Option<ServiceProviderType> {
@@ -1811,11 +2400,84 @@ pub mod pallet {
         }
     }
 
+    // ========================================================================
+    // 公钥目录集成
+    // ========================================================================
+
+    impl<T: Config> KeyChangeHandler<T::AccountId> for Pallet<T> {
+        fn max_weight() -> Weight {
+            T::DbWeight::get().reads(1 + T::MaxGrantsPerProvider::get() as u64)
+        }
+
+        /// 被授权者轮换加密公钥时，为其每条授权发出重新包装提示
+        ///
+        /// 所有者自己的授权条目由所有者根据目录的 `KeyRotated` 事件自行重新包装。
+        fn on_key_rotated(
+            who: &T::AccountId,
+            purpose: KeyPurpose,
+            _old_version: u32,
+            new_version: u32,
+        ) {
+            if purpose != KeyPurpose::Encryption {
+                return;
+            }
+            for key in ProviderGrants::<T>::get(who) {
+                if Authorizations::<T>::contains_key((key.divination_type, key.result_id, who)) {
+                    Self::deposit_event(Event::AuthorizationRewrapRequired {
+                        divination_type: key.divination_type,
+                        result_id: key.result_id,
+                        grantee: who.clone(),
+                        key_version: new_version,
+                    });
+                }
+            }
+        }
+
+        /// 吊销不改变已包装的密钥；所有者应通过 `revoke_access` 自行处置
+        fn on_key_revoked(_who: &T::AccountId, _purpose: KeyPurpose, _version: u32) {}
+    }
+
     // ========================================================================
     // 内部函数（供其他 pallet 调用）
     // ========================================================================
 
     impl<T: Config> Pallet<T> {
+        /// 账户在公钥目录中的当前加密公钥版本（未注册为 0）
+        fn directory_key_version(who: &T::AccountId) -> u32 {
+            T::KeyDirectory::current_key(who, KeyPurpose::Encryption).map_or(0, |key| key.version)
+        }
+
+        /// 记录授权条目包装数据密钥时被授权者的公钥版本
+        fn record_key_version(divination_type: DivinationType, result_id: u64, grantee: &T::AccountId) {
+            AuthorizationKeyVersions::<T>::insert(
+                (divination_type, result_id, grantee),
+                Self::directory_key_version(grantee),
+            );
+        }
+
+        /// 用户当前 X25519 加密公钥
+        ///
+        /// 只读取统一公钥目录，废弃的 `UserEncryptionKeys` 不再参与。
+        pub fn encryption_key_of(account: &T::AccountId) -> Option<[u8; 32]> {
+            T::KeyDirectory::encryption_key(account)
+        }
+
+        /// 清理进行中的恢复及已释放的份额
+        fn clear_recovery(
+            account: &T::AccountId,
+            config: &RecoveryConfig<
+                T::AccountId,
+                BlockNumberFor<T>,
+                T::MaxGuardians,
+                T::MaxEncryptedKeyLen,
+            >,
+        ) {
+            ActiveRecoveries::<T>::remove(account);
+            for guardian in config.guardians.iter() {
+                RecoveryShares::<T>::remove(account, guardian);
+            }
+        }
+
         /// 内部函数：创建加密记录
         ///
         /// 供其他 pallet（如 meihua、bazi、liuyao）原子性调用。
@@ -1966,6 +2628,7 @@ pub mod pallet {
                     (divination_type, result_id, owner),
                     owner_auth,
                 );
+                Self::record_key_version(divination_type, result_id, owner);
 
                 // 添加到授权列表
                 RecordGrantees::<T>::try_mutate(divination_type, result_id, |list| {
@@ -5,6 +5,10 @@
 use crate as pallet_divination_privacy;
 use frame_support::{derive_impl, parameter_types};
 use frame_system as system;
+use pallet_key_directory::{
+    traits::KeyDirectory,
+    types::{DirectoryKey, KeyAlgorithm, KeyPurpose},
+};
 use sp_runtime::BuildStorage;
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -43,6 +47,42 @@ parameter_types! {
     pub const MaxProvidersPerType: u32 = 1000;
     pub const MaxGrantsPerProvider: u32 = 500;
     pub const MaxAuthorizationsPerBounty: u32 = 100;
+    pub const MaxGuardians: u32 = 5;
+    pub const MinRecoveryDelay: u64 = 10;
+    pub const RecoveryVetoCooldown: u64 = 50;
+    /// 测试公钥目录中各账户的加密公钥版本
+    pub static DirectoryKeyVersions: Vec<(u64, u32)> = Vec::new();
+}
+
+/// 测试公钥目录：按 `DirectoryKeyVersions` 返回 X25519 加密公钥
+pub struct TestKeyDirectory;
+
+impl KeyDirectory<u64> for TestKeyDirectory {
+    fn current_key(who: &u64, purpose: KeyPurpose) -> Option<DirectoryKey> {
+        if purpose != KeyPurpose::Encryption {
+            return None;
+        }
+        DirectoryKeyVersions::get()
+            .into_iter()
+            .find(|(account, _)| account == who)
+            .map(|(_, version)| DirectoryKey {
+                version,
+                algorithm: KeyAlgorithm::X25519,
+                public_key: vec![version as u8; 32],
+            })
+    }
+
+    fn is_usable_key(who: &u64, purpose: KeyPurpose, public_key: &[u8]) -> bool {
+        Self::current_key(who, purpose).is_some_and(|key| key.public_key == public_key)
+    }
+}
+
+/// 设置测试公钥目录中账户的加密公钥版本
+pub fn set_directory_key_version(who: u64, version: u32) {
+    let mut versions = DirectoryKeyVersions::get();
+    versions.retain(|(account, _)| *account != who);
+    versions.push((who, version));
+    DirectoryKeyVersions::set(versions);
 }
 
 impl pallet_divination_privacy::Config for Test {
@@ -54,6 +94,10 @@ impl pallet_divination_privacy::Config for Test {
     type MaxGrantsPerProvider = MaxGrantsPerProvider;
     type MaxAuthorizationsPerBounty = MaxAuthorizationsPerBounty;
     type EventHandler = ();
+    type KeyDirectory = TestKeyDirectory;
+    type MaxGuardians = MaxGuardians;
+    type MinRecoveryDelay = MinRecoveryDelay;
+    type RecoveryVetoCooldown = RecoveryVetoCooldown;
     type WeightInfo = ();
 }
 
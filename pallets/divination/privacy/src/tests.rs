@@ -6,6 +6,7 @@
 //! 3. 加密记录管理
 //! 4. 授权管理
 //! 5. 悬赏授权集成
+//! 6. 社交恢复
 
 use crate::{mock::*, types::*, Error, Event};
 use frame_support::{assert_noop, assert_ok};
@@ -19,108 +20,44 @@ mod key_management {
     use super::*;
 
     #[test]
-    fn register_encryption_key_works() {
+    #[allow(deprecated)]
+    fn register_encryption_key_is_deprecated() {
         new_test_ext().execute_with(|| {
-            let public_key = test_public_key(1);
-
-            // 注册公钥
-            assert_ok!(Privacy::register_encryption_key(
-                RuntimeOrigin::signed(ALICE),
-                public_key
-            ));
-
-            // 验证存储
-            let info = Privacy::user_encryption_keys(ALICE).unwrap();
-            assert_eq!(info.public_key, public_key);
-            assert_eq!(info.registered_at, 1);
-
-            // 验证事件
-            System::assert_last_event(
-                Event::EncryptionKeyRegistered {
-                    account: ALICE,
-                    public_key,
-                }
-                .into(),
-            );
-        });
-    }
-
-    #[test]
-    fn register_encryption_key_fails_if_already_registered() {
-        new_test_ext().execute_with(|| {
-            let public_key = test_public_key(1);
-
-            // 第一次注册成功
-            assert_ok!(Privacy::register_encryption_key(
-                RuntimeOrigin::signed(ALICE),
-                public_key
-            ));
-
-            // 第二次注册失败
             assert_noop!(
-                Privacy::register_encryption_key(RuntimeOrigin::signed(ALICE), public_key),
-                Error::<Test>::EncryptionKeyAlreadyRegistered
+                Privacy::register_encryption_key(RuntimeOrigin::signed(ALICE), test_public_key(1)),
+                Error::<Test>::UseKeyDirectory
             );
+            assert!(Privacy::user_encryption_keys(ALICE).is_none());
         });
     }
 
     #[test]
-    fn register_encryption_key_fails_with_invalid_key() {
+    #[allow(deprecated)]
+    fn update_encryption_key_is_deprecated() {
         new_test_ext().execute_with(|| {
-            let zero_key = [0u8; 32];
-
-            // 全零公钥无效
             assert_noop!(
-                Privacy::register_encryption_key(RuntimeOrigin::signed(ALICE), zero_key),
-                Error::<Test>::InvalidPublicKey
+                Privacy::update_encryption_key(RuntimeOrigin::signed(ALICE), test_public_key(2)),
+                Error::<Test>::UseKeyDirectory
             );
         });
     }
 
     #[test]
-    fn update_encryption_key_works() {
+    fn encryption_key_only_read_from_directory() {
         new_test_ext().execute_with(|| {
-            let old_key = test_public_key(1);
-            let new_key = test_public_key(2);
-
-            // 先注册
-            assert_ok!(Privacy::register_encryption_key(
-                RuntimeOrigin::signed(ALICE),
-                old_key
-            ));
-
-            // 更新
-            assert_ok!(Privacy::update_encryption_key(
-                RuntimeOrigin::signed(ALICE),
-                new_key
-            ));
-
-            // 验证
-            let info = Privacy::user_encryption_keys(ALICE).unwrap();
-            assert_eq!(info.public_key, new_key);
-
-            // 验证事件
-            System::assert_last_event(
-                Event::EncryptionKeyUpdated {
-                    account: ALICE,
-                    old_key,
-                    new_key,
-                }
-                .into(),
+            // 废弃存储中的历史公钥不再生效
+            crate::UserEncryptionKeys::<Test>::insert(
+                ALICE,
+                UserEncryptionInfo {
+                    public_key: test_public_key(1),
+                    registered_at: 1,
+                    updated_at: 1,
+                },
             );
-        });
-    }
+            assert_eq!(Privacy::encryption_key_of(&ALICE), None);
 
-    #[test]
-    fn update_encryption_key_fails_if_not_registered() {
-        new_test_ext().execute_with(|| {
-            let new_key = test_public_key(2);
-
-            // 未注册时更新失败
-            assert_noop!(
-                Privacy::update_encryption_key(RuntimeOrigin::signed(ALICE), new_key),
-                Error::<Test>::EncryptionKeyNotRegistered
-            );
+            set_directory_key_version(ALICE, 3);
+            assert_eq!(Privacy::encryption_key_of(&ALICE), Some([3u8; 32]));
         });
     }
 }
@@ -724,6 +661,111 @@ mod authorization {
             );
         });
     }
+
+    #[test]
+    fn key_rotation_requests_rewrap() {
+        use pallet_key_directory::{traits::KeyChangeHandler, types::KeyPurpose};
+
+        new_test_ext().execute_with(|| {
+            setup_record();
+            set_directory_key_version(MASTER, 1);
+            assert_ok!(Privacy::grant_access(
+                RuntimeOrigin::signed(ALICE),
+                DivinationType::Bazi,
+                1,
+                MASTER,
+                test_encrypted_key(2),
+                AccessRole::Master,
+                AccessScope::ReadOnly,
+                0,
+            ));
+            assert_eq!(Privacy::authorization_key_version((DivinationType::Bazi, 1, MASTER)), 1);
+
+            // 公钥未轮换时不能替换数据密钥
+            assert_noop!(
+                Privacy::rewrap_access_key(
+                    RuntimeOrigin::signed(ALICE),
+                    DivinationType::Bazi,
+                    1,
+                    MASTER,
+                    test_encrypted_key(3),
+                ),
+                Error::<Test>::GranteeKeyNotRotated
+            );
+
+            // 签名密钥轮换不影响授权
+            <Privacy as KeyChangeHandler<u64>>::on_key_rotated(&MASTER, KeyPurpose::Signing, 1, 2);
+            assert!(!System::events().iter().any(|record| matches!(
+                record.event,
+                RuntimeEvent::Privacy(Event::AuthorizationRewrapRequired { .. })
+            )));
+
+            set_directory_key_version(MASTER, 2);
+            <Privacy as KeyChangeHandler<u64>>::on_key_rotated(&MASTER, KeyPurpose::Encryption, 1, 2);
+            System::assert_last_event(
+                Event::AuthorizationRewrapRequired {
+                    divination_type: DivinationType::Bazi,
+                    result_id: 1,
+                    grantee: MASTER,
+                    key_version: 2,
+                }
+                .into(),
+            );
+
+            // 只有所有者可以重新包装
+            assert_noop!(
+                Privacy::rewrap_access_key(
+                    RuntimeOrigin::signed(MASTER),
+                    DivinationType::Bazi,
+                    1,
+                    MASTER,
+                    test_encrypted_key(3),
+                ),
+                Error::<Test>::NotRecordOwner
+            );
+            assert_noop!(
+                Privacy::rewrap_access_key(
+                    RuntimeOrigin::signed(ALICE),
+                    DivinationType::Bazi,
+                    1,
+                    BOB,
+                    test_encrypted_key(3),
+                ),
+                Error::<Test>::AuthorizationNotFound
+            );
+            assert_ok!(Privacy::rewrap_access_key(
+                RuntimeOrigin::signed(ALICE),
+                DivinationType::Bazi,
+                1,
+                MASTER,
+                test_encrypted_key(3),
+            ));
+
+            let auth = Privacy::authorizations((DivinationType::Bazi, 1, MASTER)).unwrap();
+            assert_eq!(auth.encrypted_key.to_vec(), test_encrypted_key(3));
+            System::assert_last_event(
+                Event::AccessKeyRewrapped {
+                    divination_type: DivinationType::Bazi,
+                    result_id: 1,
+                    grantee: MASTER,
+                }
+                .into(),
+            );
+            assert_eq!(Privacy::authorization_key_version((DivinationType::Bazi, 1, MASTER)), 2);
+
+            // 同一版本只能重新包装一次
+            assert_noop!(
+                Privacy::rewrap_access_key(
+                    RuntimeOrigin::signed(ALICE),
+                    DivinationType::Bazi,
+                    1,
+                    MASTER,
+                    test_encrypted_key(4),
+                ),
+                Error::<Test>::GranteeKeyNotRotated
+            );
+        });
+    }
 }
 
 // ============================================================================
@@ -1061,3 +1103,254 @@ mod trait_implementation {
         });
     }
 }
+
+// ============================================================================
+// 社交恢复测试
+// ============================================================================
+
+mod social_recovery {
+    use super::*;
+
+    const DAVE: u64 = 4;
+
+    /// ALICE 设置 2-of-3 恢复（守护人 BOB / CHARLIE / DAVE）
+    fn setup_recovery() {
+        for guardian in [BOB, CHARLIE, DAVE] {
+            set_directory_key_version(guardian, 1);
+        }
+        assert_ok!(Privacy::set_recovery_config(
+            RuntimeOrigin::signed(ALICE),
+            vec![
+                (BOB, test_encrypted_key(2)),
+                (CHARLIE, test_encrypted_key(3)),
+                (DAVE, test_encrypted_key(4)),
+            ],
+            2,
+            20,
+            test_encrypted_key(9),
+            [7u8; 32],
+        ));
+    }
+
+    #[test]
+    fn set_recovery_config_validates_guardians() {
+        new_test_ext().execute_with(|| {
+            assert_noop!(
+                Privacy::set_recovery_config(
+                    RuntimeOrigin::signed(ALICE),
+                    vec![(BOB, test_encrypted_key(2))],
+                    1,
+                    20,
+                    test_encrypted_key(9),
+                    [7u8; 32],
+                ),
+                Error::<Test>::GuardianKeyNotRegistered
+            );
+
+            set_directory_key_version(BOB, 1);
+            assert_noop!(
+                Privacy::set_recovery_config(
+                    RuntimeOrigin::signed(ALICE),
+                    vec![(BOB, test_encrypted_key(2)), (BOB, test_encrypted_key(2))],
+                    1,
+                    20,
+                    test_encrypted_key(9),
+                    [7u8; 32],
+                ),
+                Error::<Test>::DuplicateGuardian
+            );
+            assert_noop!(
+                Privacy::set_recovery_config(
+                    RuntimeOrigin::signed(ALICE),
+                    vec![(ALICE, test_encrypted_key(1))],
+                    1,
+                    20,
+                    test_encrypted_key(9),
+                    [7u8; 32],
+                ),
+                Error::<Test>::CannotGuardSelf
+            );
+            assert_noop!(
+                Privacy::set_recovery_config(
+                    RuntimeOrigin::signed(ALICE),
+                    vec![(BOB, test_encrypted_key(2))],
+                    2,
+                    20,
+                    test_encrypted_key(9),
+                    [7u8; 32],
+                ),
+                Error::<Test>::InvalidRecoveryThreshold
+            );
+            assert_noop!(
+                Privacy::set_recovery_config(
+                    RuntimeOrigin::signed(ALICE),
+                    vec![(BOB, test_encrypted_key(2))],
+                    1,
+                    5,
+                    test_encrypted_key(9),
+                    [7u8; 32],
+                ),
+                Error::<Test>::RecoveryDelayTooShort
+            );
+        });
+    }
+
+    #[test]
+    fn recovery_releases_shares_after_delay_and_threshold() {
+        new_test_ext().execute_with(|| {
+            setup_recovery();
+            assert_eq!(
+                Privacy::guardian_shares(ALICE, BOB),
+                Some(test_encrypted_key(2).try_into().unwrap())
+            );
+
+            // 非守护人不能发起
+            assert_noop!(
+                Privacy::initiate_recovery(
+                    RuntimeOrigin::signed(MASTER),
+                    ALICE,
+                    test_public_key(50)
+                ),
+                Error::<Test>::Unauthorized
+            );
+            assert_ok!(Privacy::initiate_recovery(
+                RuntimeOrigin::signed(BOB),
+                ALICE,
+                test_public_key(50)
+            ));
+            assert_eq!(Privacy::active_recoveries(ALICE).unwrap().unlock_at, 21);
+
+            assert_ok!(Privacy::approve_recovery(RuntimeOrigin::signed(BOB), ALICE));
+            assert_noop!(
+                Privacy::approve_recovery(RuntimeOrigin::signed(BOB), ALICE),
+                Error::<Test>::AlreadyApproved
+            );
+
+            // 批准数不足
+            assert_noop!(
+                Privacy::submit_recovery_share(RuntimeOrigin::signed(BOB), ALICE, vec![1; 48]),
+                Error::<Test>::InsufficientApprovals
+            );
+            assert_ok!(Privacy::approve_recovery(
+                RuntimeOrigin::signed(CHARLIE),
+                ALICE
+            ));
+
+            // 等待期未结束
+            assert_noop!(
+                Privacy::submit_recovery_share(RuntimeOrigin::signed(BOB), ALICE, vec![1; 48]),
+                Error::<Test>::RecoveryStillLocked
+            );
+
+            System::set_block_number(21);
+            // 未批准的守护人不能提交
+            assert_noop!(
+                Privacy::submit_recovery_share(RuntimeOrigin::signed(DAVE), ALICE, vec![1; 48]),
+                Error::<Test>::GuardianNotApproved
+            );
+            assert_ok!(Privacy::submit_recovery_share(
+                RuntimeOrigin::signed(BOB),
+                ALICE,
+                vec![1; 48]
+            ));
+            assert_noop!(
+                Privacy::close_recovery(RuntimeOrigin::signed(BOB), ALICE),
+                Error::<Test>::RecoveryNotReady
+            );
+            assert_ok!(Privacy::submit_recovery_share(
+                RuntimeOrigin::signed(CHARLIE),
+                ALICE,
+                vec![2; 48]
+            ));
+            System::assert_last_event(Event::RecoveryReady { account: ALICE }.into());
+
+            // 份额集齐后不可再否决
+            assert_noop!(
+                Privacy::veto_recovery(RuntimeOrigin::signed(ALICE)),
+                Error::<Test>::RecoveryAlreadyReady
+            );
+
+            assert_ok!(Privacy::close_recovery(RuntimeOrigin::signed(BOB), ALICE));
+            assert!(Privacy::active_recoveries(ALICE).is_none());
+            assert!(Privacy::recovery_shares(ALICE, BOB).is_none());
+            // 配置保留，可再次恢复
+            assert!(Privacy::recovery_configs(ALICE).is_some());
+        });
+    }
+
+    #[test]
+    fn owner_can_veto_recovery() {
+        new_test_ext().execute_with(|| {
+            setup_recovery();
+            assert_ok!(Privacy::initiate_recovery(
+                RuntimeOrigin::signed(CHARLIE),
+                ALICE,
+                test_public_key(50)
+            ));
+            assert_ok!(Privacy::approve_recovery(RuntimeOrigin::signed(BOB), ALICE));
+            assert_ok!(Privacy::approve_recovery(
+                RuntimeOrigin::signed(CHARLIE),
+                ALICE
+            ));
+
+            // 进行中的恢复阻止修改配置
+            assert_noop!(
+                Privacy::remove_recovery_config(RuntimeOrigin::signed(ALICE)),
+                Error::<Test>::RecoveryInProgress
+            );
+
+            assert_ok!(Privacy::veto_recovery(RuntimeOrigin::signed(ALICE)));
+            System::assert_last_event(Event::RecoveryVetoed { account: ALICE }.into());
+            assert!(Privacy::active_recoveries(ALICE).is_none());
+
+            System::set_block_number(30);
+            assert_noop!(
+                Privacy::submit_recovery_share(RuntimeOrigin::signed(BOB), ALICE, vec![1; 48]),
+                Error::<Test>::NoActiveRecovery
+            );
+
+            assert_ok!(Privacy::remove_recovery_config(RuntimeOrigin::signed(
+                ALICE
+            )));
+            assert!(Privacy::guardian_shares(ALICE, BOB).is_none());
+        });
+    }
+    #[test]
+    fn guardian_cannot_restart_recovery_during_veto_cooldown() {
+        new_test_ext().execute_with(|| {
+            System::set_block_number(1);
+            setup_recovery();
+            assert_ok!(Privacy::initiate_recovery(
+                RuntimeOrigin::signed(CHARLIE),
+                ALICE,
+                test_public_key(50)
+            ));
+            assert_ok!(Privacy::veto_recovery(RuntimeOrigin::signed(ALICE)));
+            assert_eq!(Privacy::recovery_cooldowns(ALICE), Some(51));
+
+            // 冷却期内守护人不能再次发起
+            System::set_block_number(50);
+            assert_noop!(
+                Privacy::initiate_recovery(RuntimeOrigin::signed(BOB), ALICE, test_public_key(51)),
+                Error::<Test>::RecoveryCooldownActive
+            );
+
+            // 所有者本人不受冷却期限制
+            assert_ok!(Privacy::initiate_recovery(
+                RuntimeOrigin::signed(ALICE),
+                ALICE,
+                test_public_key(52)
+            ));
+            assert_ok!(Privacy::veto_recovery(RuntimeOrigin::signed(ALICE)));
+            assert_eq!(Privacy::recovery_cooldowns(ALICE), Some(100));
+
+            System::set_block_number(100);
+            assert_ok!(Privacy::initiate_recovery(
+                RuntimeOrigin::signed(BOB),
+                ALICE,
+                test_public_key(53)
+            ));
+            assert!(Privacy::recovery_cooldowns(ALICE).is_none());
+        });
+    }
+}
@@ -12,6 +12,7 @@
 //! - `EncryptedRecord`: 加密数据记录
 //! - `AuthorizationEntry`: 授权条目
 //! - `ServiceProvider`: 服务提供者信息
+//! - `RecoveryConfig` / `ActiveRecovery`: 加密私钥的社交恢复
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::pallet_prelude::*;
@@ -341,6 +342,51 @@ pub struct BountyAuthInfo<BlockNumber> {
     pub auto_authorize: bool,
 }
 
+// ============================================================================
+// 社交恢复（Shamir 门限守护人）
+// ============================================================================
+
+/// 社交恢复配置
+///
+/// 用户在本地生成密钥加密密钥（KEK），用 KEK 加密自己的 X25519 私钥得到
+/// `key_backup`，再把 KEK 以 Shamir k-of-n 拆分后分别用各守护人公钥加密，
+/// 作为 `GuardianShares` 上链。链上只保存密文，任何少于 `threshold` 个
+/// 守护人都无法还原 KEK。
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug)]
+#[scale_info(skip_type_params(MaxGuardians, MaxKeyLen))]
+pub struct RecoveryConfig<AccountId, BlockNumber, MaxGuardians: Get<u32>, MaxKeyLen: Get<u32>> {
+    /// 守护人列表（顺序即 Shamir 份额的 x 坐标顺序）
+    pub guardians: BoundedVec<AccountId, MaxGuardians>,
+    /// 恢复所需的最少守护人数量（k）
+    pub threshold: u8,
+    /// 发起恢复后到守护人可释放份额之间的等待期（所有者可在此期间否决）
+    pub delay: BlockNumber,
+    /// 用 KEK 加密的用户私钥
+    pub key_backup: BoundedVec<u8, MaxKeyLen>,
+    /// KEK 的哈希（Blake2-256），供恢复方校验重组结果
+    pub kek_hash: [u8; 32],
+    /// 配置时间
+    pub configured_at: BlockNumber,
+}
+
+/// 进行中的恢复流程
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug)]
+#[scale_info(skip_type_params(MaxGuardians))]
+pub struct ActiveRecovery<AccountId, BlockNumber, MaxGuardians: Get<u32>> {
+    /// 发起者（所有者本人的新设备或某位守护人）
+    pub initiator: AccountId,
+    /// 接收份额的临时 X25519 公钥，守护人用它重新加密自己的份额
+    pub recipient_key: [u8; 32],
+    /// 发起时间
+    pub started_at: BlockNumber,
+    /// 解锁时间，此后守护人才可提交份额
+    pub unlock_at: BlockNumber,
+    /// 已批准的守护人
+    pub approvals: BoundedVec<AccountId, MaxGuardians>,
+    /// 已提交份额数
+    pub shares_submitted: u8,
+}
+
 // ============================================================================
 // 记录键类型（用于存储索引）
 // ============================================================================
@@ -28,11 +28,21 @@ pub trait WeightInfo {
     fn revoke_access() -> Weight;
     fn revoke_all_access() -> Weight;
     fn update_access_scope() -> Weight;
+    fn rewrap_access_key() -> Weight;
 
     // 悬赏授权
     fn create_bounty_authorization() -> Weight;
     fn authorize_bounty_answerer() -> Weight;
     fn revoke_bounty_authorizations() -> Weight;
+
+    // 社交恢复（g = 守护人数量）
+    fn set_recovery_config(g: u32) -> Weight;
+    fn remove_recovery_config(g: u32) -> Weight;
+    fn initiate_recovery() -> Weight;
+    fn approve_recovery() -> Weight;
+    fn veto_recovery(g: u32) -> Weight;
+    fn submit_recovery_share() -> Weight;
+    fn close_recovery(g: u32) -> Weight;
 }
 
 /// 默认权重实现（用于开发和测试）
@@ -109,11 +119,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 
     /// 创建加密记录
     /// - 1 次存储读取（检查已存在）
-    /// - 4 次存储写入（记录 + 用户索引 + 授权 + 授权列表）
+    /// - 5 次存储写入（记录 + 用户索引 + 授权 + 公钥版本 + 授权列表）
     fn create_encrypted_record() -> Weight {
         Weight::from_parts(50_000_000, 0)
-            .saturating_add(T::DbWeight::get().reads(1))
-            .saturating_add(T::DbWeight::get().writes(4))
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(5))
     }
 
     /// 更新加密记录
@@ -137,11 +147,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     /// 删除加密记录
     /// - 2 次存储读取（记录 + 授权列表）
     /// - N 次存储写入（记录 + 用户索引 + 所有授权）
-    /// 假设最多 20 个授权
+    /// 假设最多 20 个授权（每个授权含公钥版本）
     fn delete_encrypted_record() -> Weight {
         Weight::from_parts(100_000_000, 0)
             .saturating_add(T::DbWeight::get().reads(2))
-            .saturating_add(T::DbWeight::get().writes(22))
+            .saturating_add(T::DbWeight::get().writes(42))
     }
 
     // ========================================================================
@@ -150,20 +160,20 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 
     /// 授权访问
     /// - 2 次存储读取（记录 + 检查授权已存在）
-    /// - 3 次存储写入（授权 + 授权列表 + 提供者授权列表）
+    /// - 4 次存储写入（授权 + 公钥版本 + 授权列表 + 提供者授权列表）
     fn grant_access() -> Weight {
         Weight::from_parts(45_000_000, 0)
-            .saturating_add(T::DbWeight::get().reads(2))
-            .saturating_add(T::DbWeight::get().writes(3))
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(4))
     }
 
     /// 撤销授权
     /// - 2 次存储读取（记录 + 授权）
-    /// - 3 次存储写入（删除授权 + 更新授权列表 + 更新提供者列表）
+    /// - 4 次存储写入（删除授权 + 公钥版本 + 更新授权列表 + 更新提供者列表）
     fn revoke_access() -> Weight {
         Weight::from_parts(40_000_000, 0)
             .saturating_add(T::DbWeight::get().reads(2))
-            .saturating_add(T::DbWeight::get().writes(3))
+            .saturating_add(T::DbWeight::get().writes(4))
     }
 
     /// 撤销所有授权
@@ -173,7 +183,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn revoke_all_access() -> Weight {
         Weight::from_parts(80_000_000, 0)
             .saturating_add(T::DbWeight::get().reads(2))
-            .saturating_add(T::DbWeight::get().writes(21))
+            .saturating_add(T::DbWeight::get().writes(41))
     }
 
     /// 更新授权范围
@@ -185,6 +195,15 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().writes(1))
     }
 
+    /// 重新包装授权密钥
+    /// - 4 次存储读取（记录、授权、目录公钥、公钥版本）
+    /// - 2 次存储写入（授权、公钥版本）
+    fn rewrap_access_key() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
     // ========================================================================
     // 悬赏授权
     // ========================================================================
@@ -216,6 +235,74 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(3))
             .saturating_add(T::DbWeight::get().writes(102))
     }
+
+    // ========================================================================
+    // 社交恢复
+    // ========================================================================
+
+    /// 设置恢复配置
+    /// - 2 + g 次存储读取（进行中恢复 + 旧配置 + 守护人公钥）
+    /// - 1 + 2g 次存储写入（配置 + 清理旧份额 + 写入新份额）
+    fn set_recovery_config(g: u32) -> Weight {
+        Weight::from_parts(40_000_000, 0)
+            .saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(g as u64))
+            .saturating_add(T::DbWeight::get().reads(2 + g as u64))
+            .saturating_add(T::DbWeight::get().writes(1 + 2 * g as u64))
+    }
+
+    /// 移除恢复配置
+    /// - 2 次存储读取
+    /// - 1 + g 次存储写入（配置 + 守护人份额）
+    fn remove_recovery_config(g: u32) -> Weight {
+        Weight::from_parts(25_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1 + g as u64))
+    }
+
+    /// 发起恢复
+    /// - 3 次存储读取（配置 + 进行中恢复 + 否决冷却期）
+    /// - 2 次存储写入（进行中恢复 + 清理到期冷却期）
+    fn initiate_recovery() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// 守护人批准恢复
+    /// - 2 次存储读取
+    /// - 1 次存储写入
+    fn approve_recovery() -> Weight {
+        Weight::from_parts(25_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// 所有者否决恢复
+    /// - 1 次存储读取
+    /// - 2 + g 次存储写入（进行中恢复 + 否决冷却期 + 已提交份额）
+    fn veto_recovery(g: u32) -> Weight {
+        Weight::from_parts(25_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(2 + g as u64))
+    }
+
+    /// 守护人提交重新加密的份额
+    /// - 3 次存储读取（配置 + 进行中恢复 + 份额是否已提交）
+    /// - 2 次存储写入
+    fn submit_recovery_share() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+
+    /// 关闭已完成的恢复
+    /// - 2 次存储读取
+    /// - 1 + g 次存储写入
+    fn close_recovery(g: u32) -> Weight {
+        Weight::from_parts(25_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1 + g as u64))
+    }
 }
 
 /// 用于测试的权重实现（所有权重为 0）
@@ -262,6 +349,9 @@ impl WeightInfo for () {
     fn update_access_scope() -> Weight {
         Weight::zero()
     }
+    fn rewrap_access_key() -> Weight {
+        Weight::zero()
+    }
     fn create_bounty_authorization() -> Weight {
         Weight::zero()
     }
@@ -271,4 +361,25 @@ impl WeightInfo for () {
     fn revoke_bounty_authorizations() -> Weight {
         Weight::zero()
     }
+    fn set_recovery_config(_g: u32) -> Weight {
+        Weight::zero()
+    }
+    fn remove_recovery_config(_g: u32) -> Weight {
+        Weight::zero()
+    }
+    fn initiate_recovery() -> Weight {
+        Weight::zero()
+    }
+    fn approve_recovery() -> Weight {
+        Weight::zero()
+    }
+    fn veto_recovery(_g: u32) -> Weight {
+        Weight::zero()
+    }
+    fn submit_recovery_share() -> Weight {
+        Weight::zero()
+    }
+    fn close_recovery(_g: u32) -> Weight {
+        Weight::zero()
+    }
 }
@@ -17,119 +17,18 @@
 //! 5. 根据时辰排布天盘九星
 //! 6. 根据时辰排布人盘八门
 //! 7. 根据值符排布神盘八神
+//!
+//! 转盘排盘的第 2-7 步与 TEE Enclave 共用 `divination_algorithms::qimen`，
+//! 本模块只负责链上枚举映射以及旬空、驿马、格局分析等链上独有的部分。
 
 use crate::types::*;
+use divination_algorithms::qimen as core_qimen;
 use sp_std::vec::Vec;
 
 // ==================== 常量定义 ====================
 
-/// 阳遁局数表（节气 × 三元）
-///
-/// 按节气顺序：冬至、小寒、大寒、立春、雨水、惊蛰、春分、清明、谷雨、立夏、小满、芒种
-/// 每个节气对应上中下三元的局数
-pub const YANG_DUN_JU: [[u8; 3]; 12] = [
-    [1, 7, 4], // 冬至
-    [2, 8, 5], // 小寒
-    [3, 9, 6], // 大寒
-    [8, 5, 2], // 立春
-    [9, 6, 3], // 雨水
-    [1, 7, 4], // 惊蛰
-    [3, 9, 6], // 春分
-    [4, 1, 7], // 清明
-    [5, 2, 8], // 谷雨
-    [4, 1, 7], // 立夏
-    [5, 2, 8], // 小满
-    [6, 3, 9], // 芒种
-];
-
-/// 阴遁局数表（节气 × 三元）
-///
-/// 按节气顺序：夏至、小暑、大暑、立秋、处暑、白露、秋分、寒露、霜降、立冬、小雪、大雪
-/// 每个节气对应上中下三元的局数
-pub const YIN_DUN_JU: [[u8; 3]; 12] = [
-    [9, 3, 6], // 夏至
-    [8, 2, 5], // 小暑
-    [7, 1, 4], // 大暑
-    [2, 5, 8], // 立秋
-    [1, 4, 7], // 处暑
-    [9, 3, 6], // 白露
-    [7, 1, 4], // 秋分
-    [6, 9, 3], // 寒露
-    [5, 8, 2], // 霜降
-    [6, 9, 3], // 立冬
-    [5, 8, 2], // 小雪
-    [4, 7, 1], // 大雪
-];
-
-/// 阳遁地盘三奇六仪查找表（1-9局，每局对应1-9宫的天干）
-///
-/// 阳遁规则：六仪（戊己庚辛壬癸）顺排，三奇（乙丙丁）顺排
-/// 戊落宫位 = 局数，然后按九宫顺飞顺序依次排布
-///
-/// 参考：Java QiMenZhuanPanJiChuMap.DI_YANG_QI_YI
-pub const DI_YANG_QI_YI: [[TianGan; 9]; 9] = [
-    // 阳遁一局（1-9宫）：戊己庚辛壬癸丁丙乙
-    [TianGan::Wu, TianGan::Ji, TianGan::Geng, TianGan::Xin, TianGan::Ren, TianGan::Gui, TianGan::Ding, TianGan::Bing, TianGan::Yi],
-    // 阳遁二局（1-9宫）：乙戊己庚辛壬癸丁丙
-    [TianGan::Yi, TianGan::Wu, TianGan::Ji, TianGan::Geng, TianGan::Xin, TianGan::Ren, TianGan::Gui, TianGan::Ding, TianGan::Bing],
-    // 阳遁三局（1-9宫）：丙乙戊己庚辛壬癸丁
-    [TianGan::Bing, TianGan::Yi, TianGan::Wu, TianGan::Ji, TianGan::Geng, TianGan::Xin, TianGan::Ren, TianGan::Gui, TianGan::Ding],
-    // 阳遁四局（1-9宫）：丁丙乙戊己庚辛壬癸
-    [TianGan::Ding, TianGan::Bing, TianGan::Yi, TianGan::Wu, TianGan::Ji, TianGan::Geng, TianGan::Xin, TianGan::Ren, TianGan::Gui],
-    // 阳遁五局（1-9宫）：癸丁丙乙戊己庚辛壬
-    [TianGan::Gui, TianGan::Ding, TianGan::Bing, TianGan::Yi, TianGan::Wu, TianGan::Ji, TianGan::Geng, TianGan::Xin, TianGan::Ren],
-    // 阳遁六局（1-9宫）：壬癸丁丙乙戊己庚辛
-    [TianGan::Ren, TianGan::Gui, TianGan::Ding, TianGan::Bing, TianGan::Yi, TianGan::Wu, TianGan::Ji, TianGan::Geng, TianGan::Xin],
-    // 阳遁七局（1-9宫）：辛壬癸丁丙乙戊己庚
-    [TianGan::Xin, TianGan::Ren, TianGan::Gui, TianGan::Ding, TianGan::Bing, TianGan::Yi, TianGan::Wu, TianGan::Ji, TianGan::Geng],
-    // 阳遁八局（1-9宫）：庚辛壬癸丁丙乙戊己
-    [TianGan::Geng, TianGan::Xin, TianGan::Ren, TianGan::Gui, TianGan::Ding, TianGan::Bing, TianGan::Yi, TianGan::Wu, TianGan::Ji],
-    // 阳遁九局（1-9宫）：己庚辛壬癸丁丙乙戊
-    [TianGan::Ji, TianGan::Geng, TianGan::Xin, TianGan::Ren, TianGan::Gui, TianGan::Ding, TianGan::Bing, TianGan::Yi, TianGan::Wu],
-];
-
-/// 阴遁地盘三奇六仪查找表（1-9局，每局对应1-9宫的天干）
-///
-/// 阴遁规则：三奇（乙丙丁）顺排在前，六仪（癸壬辛庚己）逆排在后
-/// 戊落宫位 = 局数，然后按九宫逆飞顺序排布
-///
-/// 注意：阴遁的三奇六仪顺序与阳遁不同！
-/// - 阳遁顺序：戊→己→庚→辛→壬→癸→丁→丙→乙（六仪顺+三奇顺）
-/// - 阴遁顺序：戊→乙→丙→丁→癸→壬→辛→庚→己（三奇顺+六仪逆）
-///
-/// 参考：Java QiMenZhuanPanJiChuMap.DI_YIN_QI_YI
-pub const DI_YIN_QI_YI: [[TianGan; 9]; 9] = [
-    // 阴遁一局（1-9宫）：戊乙丙丁癸壬辛庚己
-    [TianGan::Wu, TianGan::Yi, TianGan::Bing, TianGan::Ding, TianGan::Gui, TianGan::Ren, TianGan::Xin, TianGan::Geng, TianGan::Ji],
-    // 阴遁二局（1-9宫）：己戊乙丙丁癸壬辛庚
-    [TianGan::Ji, TianGan::Wu, TianGan::Yi, TianGan::Bing, TianGan::Ding, TianGan::Gui, TianGan::Ren, TianGan::Xin, TianGan::Geng],
-    // 阴遁三局（1-9宫）：庚己戊乙丙丁癸壬辛
-    [TianGan::Geng, TianGan::Ji, TianGan::Wu, TianGan::Yi, TianGan::Bing, TianGan::Ding, TianGan::Gui, TianGan::Ren, TianGan::Xin],
-    // 阴遁四局（1-9宫）：辛庚己戊乙丙丁癸壬
-    [TianGan::Xin, TianGan::Geng, TianGan::Ji, TianGan::Wu, TianGan::Yi, TianGan::Bing, TianGan::Ding, TianGan::Gui, TianGan::Ren],
-    // 阴遁五局（1-9宫）：壬辛庚己戊乙丙丁癸
-    [TianGan::Ren, TianGan::Xin, TianGan::Geng, TianGan::Ji, TianGan::Wu, TianGan::Yi, TianGan::Bing, TianGan::Ding, TianGan::Gui],
-    // 阴遁六局（1-9宫）：癸壬辛庚己戊乙丙丁
-    [TianGan::Gui, TianGan::Ren, TianGan::Xin, TianGan::Geng, TianGan::Ji, TianGan::Wu, TianGan::Yi, TianGan::Bing, TianGan::Ding],
-    // 阴遁七局（1-9宫）：丁癸壬辛庚己戊乙丙
-    [TianGan::Ding, TianGan::Gui, TianGan::Ren, TianGan::Xin, TianGan::Geng, TianGan::Ji, TianGan::Wu, TianGan::Yi, TianGan::Bing],
-    // 阴遁八局（1-9宫）：丙丁癸壬辛庚己戊乙
-    [TianGan::Bing, TianGan::Ding, TianGan::Gui, TianGan::Ren, TianGan::Xin, TianGan::Geng, TianGan::Ji, TianGan::Wu, TianGan::Yi],
-    // 阴遁九局（1-9宫）：乙丙丁癸壬辛庚己戊
-    [TianGan::Yi, TianGan::Bing, TianGan::Ding, TianGan::Gui, TianGan::Ren, TianGan::Xin, TianGan::Geng, TianGan::Ji, TianGan::Wu],
-];
-
-/// 六十甲子旬首表
-///
-/// 每旬的旬首天干地支和对应的遁甲（六仪）
-pub const XUN_SHOU: [(TianGan, DiZhi, TianGan); 6] = [
-    (TianGan::Jia, DiZhi::Zi, TianGan::Wu),   // 甲子旬，遁甲于戊
-    (TianGan::Jia, DiZhi::Xu, TianGan::Ji),   // 甲戌旬，遁甲于己
-    (TianGan::Jia, DiZhi::Shen, TianGan::Geng), // 甲申旬，遁甲于庚
-    (TianGan::Jia, DiZhi::Wu, TianGan::Xin),  // 甲午旬，遁甲于辛
-    (TianGan::Jia, DiZhi::Chen, TianGan::Ren), // 甲辰旬，遁甲于壬
-    (TianGan::Jia, DiZhi::Yin, TianGan::Gui), // 甲寅旬，遁甲于癸
-];
+/// 局数表与九宫飞布顺序与 TEE Enclave 共用同一份定义
+pub use divination_algorithms::qimen::{GONG_ORDER_YANG, GONG_ORDER_YIN, YANG_DUN_JU, YIN_DUN_JU};
 
 /// 六甲旬空查找表
 ///
@@ -153,30 +52,6 @@ pub const XUN_KONG_TABLE: [(DiZhi, DiZhi); 6] = [
     (DiZhi::Zi, DiZhi::Chou),  // 甲寅旬空子丑
 ];
 
-/// 九宫飞布顺序（阳遁，顺时针）
-///
-/// 从坎一宫开始的顺时针顺序：1→8→3→4→9→2→7→6
-pub const GONG_ORDER_YANG: [u8; 8] = [1, 8, 3, 4, 9, 2, 7, 6];
-
-/// 九宫飞布顺序（阴遁，逆时针）
-///
-/// 从坎一宫开始的逆时针顺序：1→6→7→2→9→4→3→8
-pub const GONG_ORDER_YIN: [u8; 8] = [1, 6, 7, 2, 9, 4, 3, 8];
-
-/// 八神顺序（固定）
-///
-/// 值符、腾蛇、太阴、六合、白虎、玄武、九地、九天
-pub const BA_SHEN_ORDER: [BaShen; 8] = [
-    BaShen::ZhiFu,
-    BaShen::TengShe,
-    BaShen::TaiYin,
-    BaShen::LiuHe,
-    BaShen::BaiHu,
-    BaShen::XuanWu,
-    BaShen::JiuDi,
-    BaShen::JiuTian,
-];
-
 // ==================== 核心算法 ====================
 
 /// 计算阴阳遁类型
@@ -200,12 +75,15 @@ pub fn calc_dun_type(jie_qi: JieQi) -> DunType {
 /// 注意：这是简化的三元计算方法，适用于日家奇门。
 /// 时家奇门应使用 `calc_san_yuan_by_zhi` 根据时辰地支判断。
 pub fn calc_san_yuan(day_in_jieqi: u8) -> SanYuan {
-    if day_in_jieqi <= 5 {
-        SanYuan::Shang
-    } else if day_in_jieqi <= 10 {
-        SanYuan::Zhong
-    } else {
-        SanYuan::Xia
+    san_yuan_from_index(core_qimen::calc_san_yuan(day_in_jieqi))
+}
+
+/// 共用算法库的三元索引（0 上元、1 中元、2 下元）转为链上枚举
+fn san_yuan_from_index(index: u8) -> SanYuan {
+    match index {
+        0 => SanYuan::Shang,
+        1 => SanYuan::Zhong,
+        _ => SanYuan::Xia,
     }
 }
 
@@ -286,17 +164,13 @@ pub fn calc_san_yuan_by_sexagenary(sexagenary_index: u8) -> SanYuan {
 ///
 /// 根据节气、三元和阴阳遁查表获取局数
 pub fn calc_ju_number(jie_qi: JieQi, san_yuan: SanYuan, dun_type: DunType) -> u8 {
-    let jieqi_index = (jie_qi as u8) % 12;
-    let yuan_index = match san_yuan {
-        SanYuan::Shang => 0,
-        SanYuan::Zhong => 1,
-        SanYuan::Xia => 2,
+    // 共用算法库以节气索引 0-11 为阳遁、12-23 为阴遁
+    let row = (jie_qi as u8) % 12;
+    let jie_qi_index = match dun_type {
+        DunType::Yang => row,
+        DunType::Yin => row + 12,
     };
-
-    match dun_type {
-        DunType::Yang => YANG_DUN_JU[jieqi_index as usize][yuan_index],
-        DunType::Yin => YIN_DUN_JU[jieqi_index as usize][yuan_index],
-    }
+    core_qimen::calc_ju_number(jie_qi_index, san_yuan as u8)
 }
 
 /// 获取地盘三奇六仪排布
@@ -317,35 +191,19 @@ pub fn calc_ju_number(jie_qi: JieQi, san_yuan: SanYuan, dun_type: DunType) -> u8
 ///
 /// 返回长度为9的数组，索引0-8分别对应坎一宫到离九宫的地盘天干
 pub fn get_di_pan(ju_number: u8, dun_type: DunType) -> [TianGan; 9] {
-    // 局数索引（1-9 转 0-8）
-    let ju_idx = ju_number.saturating_sub(1).min(8) as usize;
+    core_qimen::get_di_pan(ju_number, dun_type == DunType::Yang).map(gan_from_index)
+}
 
-    // 直接从查找表获取地盘排布
-    match dun_type {
-        DunType::Yang => DI_YANG_QI_YI[ju_idx],
-        DunType::Yin => DI_YIN_QI_YI[ju_idx],
-    }
+/// 共用算法库的天干索引转为链上枚举
+fn gan_from_index(index: u8) -> TianGan {
+    TianGan::from_index(index).unwrap_or(TianGan::Wu)
 }
 
 /// 获取时干所在旬首
 ///
 /// 返回旬首的六仪（戊己庚辛壬癸之一）
 pub fn get_xun_shou(shi_gan: TianGan, shi_zhi: DiZhi) -> TianGan {
-    // 计算六十甲子序号
-    let gan_idx = shi_gan.index() as u16;
-    let zhi_idx = shi_zhi.index() as u16;
-
-    // 干支组合的六十甲子序号
-    let sexagenary = ((gan_idx * 6 + zhi_idx * 5) % 60) as u8;
-
-    // 每旬10个干支，确定在第几旬
-    let xun_index = (sexagenary / 10) as usize;
-
-    if xun_index < XUN_SHOU.len() {
-        XUN_SHOU[xun_index].2 // 返回遁甲所用的六仪
-    } else {
-        TianGan::Wu // 默认戊
-    }
+    gan_from_index(core_qimen::get_xun_shou(shi_gan.index(), shi_zhi.index()))
 }
 
 /// 获取旬空地支
@@ -468,12 +326,7 @@ pub fn is_gong_xun_kong(gong: u8, shi_gan: TianGan, shi_zhi: DiZhi) -> bool {
 ///
 /// 返回该天干在地盘中的宫位（1-9）
 pub fn find_gan_in_di_pan(gan: TianGan, di_pan: &[TianGan; 9]) -> Option<u8> {
-    for (i, &g) in di_pan.iter().enumerate() {
-        if g == gan {
-            return Some((i + 1) as u8);
-        }
-    }
-    None
+    core_qimen::find_gan_in_di_pan(gan.index(), &di_pan.map(|g| g.index()))
 }
 
 /// 计算值符星
@@ -491,136 +344,48 @@ pub fn calc_zhi_fu_xing(xun_shou_yi: TianGan, di_pan: &[TianGan; 9]) -> JiuXing
 ///
 /// 值使门 = 旬首六仪所在宫的原始八门
 pub fn calc_zhi_shi_men(xun_shou_yi: TianGan, di_pan: &[TianGan; 9]) -> BaMen {
-    // 找到旬首六仪在地盘的落宫
+    // 找到旬首六仪在地盘的落宫，中宫无门取休门
     let gong = find_gan_in_di_pan(xun_shou_yi, di_pan).unwrap_or(1);
-
-    // 使用 from_palace 获取该宫的原始八门
-    // 中宫（5）没有门，会返回 None，使用休门作为默认值
-    BaMen::from_palace(gong).unwrap_or(BaMen::Xiu)
+    BaMen::from_num(core_qimen::palace_men(gong)).unwrap_or(BaMen::Xiu)
 }
 
 /// 排布天盘九星
 ///
-/// 根据值符星的当前落宫，按阴阳遁方向排布其他八星
+/// 根据值符星的当前落宫（时干寄宫），按阴阳遁方向排布其他八星
 pub fn distribute_jiu_xing(
     zhi_fu_xing: JiuXing,
     shi_gan: TianGan,
     di_pan: &[TianGan; 9],
     dun_type: DunType,
 ) -> [JiuXing; 9] {
-    let mut tian_pan = [JiuXing::TianQin; 9];
-
-    // 时干寄宫：找到时干在地盘的位置，就是值符星的天盘落宫
     let shi_gan_gong = find_gan_in_di_pan(shi_gan, di_pan).unwrap_or(1);
-
-    let gong_order = match dun_type {
-        DunType::Yang => &GONG_ORDER_YANG,
-        DunType::Yin => &GONG_ORDER_YIN,
-    };
-
-    // 找到值符星落宫在顺序中的位置
-    let start_pos = gong_order.iter().position(|&g| g == shi_gan_gong).unwrap_or(0);
-
-    // 九星排列顺序（从值符星开始）
-    let xing_start = zhi_fu_xing.num() as usize;
-
-    // 按顺序排布九星
-    for i in 0..8 {
-        let xing_num = ((xing_start - 1 + i) % 8) + 1;
-        let xing = JiuXing::from_num(xing_num as u8).unwrap_or(JiuXing::TianPeng);
-
-        let gong_index = (start_pos + i) % 8;
-        let gong_num = gong_order[gong_index];
-        let array_index = gong_num.saturating_sub(1) as usize;
-
-        if array_index < 9 {
-            tian_pan[array_index] = xing;
-        }
-    }
-
-    // 中宫放天禽星
-    tian_pan[4] = JiuXing::TianQin;
-
-    tian_pan
+    core_qimen::distribute_xing(zhi_fu_xing.num(), shi_gan_gong, dun_type == DunType::Yang)
+        .map(|xing| JiuXing::from_num(xing).unwrap_or(JiuXing::TianQin))
 }
 
 /// 排布人盘八门
 ///
-/// 根据值使门的当前落宫，按阴阳遁方向排布其他七门
+/// 根据值使门的当前落宫（时干寄宫），按阴阳遁方向排布其他七门，中宫无门
 pub fn distribute_ba_men(
     zhi_shi_men: BaMen,
     shi_gan: TianGan,
     di_pan: &[TianGan; 9],
     dun_type: DunType,
 ) -> [Option<BaMen>; 9] {
-    let mut ren_pan: [Option<BaMen>; 9] = [None; 9];
-
-    // 时干寄宫
     let shi_gan_gong = find_gan_in_di_pan(shi_gan, di_pan).unwrap_or(1);
-
-    let gong_order = match dun_type {
-        DunType::Yang => &GONG_ORDER_YANG,
-        DunType::Yin => &GONG_ORDER_YIN,
-    };
-
-    // 找到值使门落宫在顺序中的位置
-    let start_pos = gong_order.iter().position(|&g| g == shi_gan_gong).unwrap_or(0);
-
-    // 八门排列顺序（从值使门开始）
-    let men_start = zhi_shi_men.num() as usize;
-
-    // 按顺序排布八门
-    for i in 0..8 {
-        let men_num = ((men_start - 1 + i) % 8) + 1;
-        let men = BaMen::from_num(men_num as u8).unwrap_or(BaMen::Xiu);
-
-        let gong_index = (start_pos + i) % 8;
-        let gong_num = gong_order[gong_index];
-        let array_index = gong_num.saturating_sub(1) as usize;
-
-        if array_index < 9 && array_index != 4 {
-            ren_pan[array_index] = Some(men);
-        }
-    }
-
-    // 中宫无门
-    ren_pan[4] = None;
-
-    ren_pan
+    core_qimen::distribute_men(zhi_shi_men.num(), shi_gan_gong, dun_type == DunType::Yang)
+        .map(|men| men.and_then(BaMen::from_num))
 }
 
 /// 排布神盘八神
 ///
-/// 八神从值符落宫开始，按固定顺序排布
+/// 八神从值符落宫开始，按固定顺序（值符、腾蛇、太阴、六合、白虎、玄武、九地、九天）排布，中宫无神
 pub fn distribute_ba_shen(
     zhi_fu_gong: u8,
     dun_type: DunType,
 ) -> [Option<BaShen>; 9] {
-    let mut shen_pan: [Option<BaShen>; 9] = [None; 9];
-
-    let gong_order = match dun_type {
-        DunType::Yang => &GONG_ORDER_YANG,
-        DunType::Yin => &GONG_ORDER_YIN,
-    };
-
-    // 找到值符落宫在顺序中的位置
-    let start_pos = gong_order.iter().position(|&g| g == zhi_fu_gong).unwrap_or(0);
-
-    // 按固定顺序排布八神
-    for (i, &shen) in BA_SHEN_ORDER.iter().enumerate() {
-        let gong_index = (start_pos + i) % 8;
-        let gong_num = gong_order[gong_index];
-        let array_index = gong_num.saturating_sub(1) as usize;
-
-        if array_index < 9 && array_index != 4 {
-            shen_pan[array_index] = Some(shen);
-        }
-    }
-
-    // 中宫无神
-    shen_pan[4] = None;
-
-    shen_pan
+    core_qimen::distribute_shen(zhi_fu_gong, dun_type == DunType::Yang)
+        .map(|shen| shen.and_then(BaShen::from_num))
 }
 
 /// 获取天盘干
@@ -652,70 +417,13 @@ pub fn generate_qimen_chart(
     jie_qi: JieQi,
     day_in_jieqi: u8,
 ) -> (DunType, SanYuan, u8, JiuXing, BaMen, [Palace; 9]) {
-    // 1. 确定阴阳遁
+    // 1-3. 阴阳遁、三元、局数
     let dun_type = calc_dun_type(jie_qi);
-
-    // 2. 确定三元
     let san_yuan = calc_san_yuan(day_in_jieqi);
-
-    // 3. 确定局数
     let ju_number = calc_ju_number(jie_qi, san_yuan, dun_type);
 
-    // 4. 排布地盘
-    let di_pan = get_di_pan(ju_number, dun_type);
-
-    // 5. 确定旬首六仪
-    let xun_shou_yi = get_xun_shou(hour_gz.gan, hour_gz.zhi);
-
-    // 6. 计算值符星和值使门
-    let zhi_fu_xing = calc_zhi_fu_xing(xun_shou_yi, &di_pan);
-    let zhi_shi_men = calc_zhi_shi_men(xun_shou_yi, &di_pan);
-
-    // 7. 排布天盘九星
-    let tian_pan_xing = distribute_jiu_xing(zhi_fu_xing, hour_gz.gan, &di_pan, dun_type);
-
-    // 8. 排布人盘八门
-    let ren_pan_men = distribute_ba_men(zhi_shi_men, hour_gz.gan, &di_pan, dun_type);
-
-    // 9. 找到值符落宫，排布神盘八神
-    let zhi_fu_gong = find_gan_in_di_pan(hour_gz.gan, &di_pan).unwrap_or(1);
-    let shen_pan_shen = distribute_ba_shen(zhi_fu_gong, dun_type);
-
-    // 10. 获取旬空宫位（使用改进后的方法）
-    let (kong_gong1, kong_gong2) = get_xun_kong_gong(hour_gz.gan, hour_gz.zhi);
-
-    // 11. 计算驿马落宫
-    let yi_ma = calc_yi_ma(hour_gz.zhi);
-    let yi_ma_gong = yi_ma.gong.num();
-
-    // 12. 组装九宫
-    let mut palaces = [Palace::empty(JiuGong::Kan); 9];
-
-    for i in 0..9 {
-        let gong_num = (i + 1) as u8;
-        let gong = JiuGong::from_num(gong_num).unwrap_or(JiuGong::Kan);
-        let xing = tian_pan_xing[i];
-        let tian_pan_gan = get_tian_pan_gan(xing, &di_pan);
-
-        // 检查是否旬空（直接比较宫位号）
-        let is_xun_kong = gong_num == kong_gong1 || gong_num == kong_gong2;
-
-        // 检查是否马星宫
-        let is_ma_xing = gong_num == yi_ma_gong;
-
-        palaces[i] = Palace {
-            gong,
-            tian_pan_gan,
-            di_pan_gan: di_pan[i],
-            xing,
-            men: ren_pan_men[i],
-            shen: shen_pan_shen[i],
-            is_xun_kong,
-            is_ma_xing,
-        };
-    }
-
-    (dun_type, san_yuan, ju_number, zhi_fu_xing, zhi_shi_men, palaces)
+    // 4-9. 以时柱起局排布九宫
+    assemble_chart(dun_type, san_yuan, ju_number, hour_gz)
 }
 
 /// 根据排盘类型生成奇门遁甲盘
@@ -773,67 +481,56 @@ pub fn generate_qimen_chart_by_type(
         }
     };
 
-    // 1. 确定阴阳遁
     let dun_type = calc_dun_type(jie_qi);
-
-    // 2. 确定局数
     let ju_number = calc_ju_number(jie_qi, san_yuan, dun_type);
 
-    // 3. 排布地盘
-    let di_pan = get_di_pan(ju_number, dun_type);
-
-    // 4. 确定旬首六仪（根据排盘类型使用不同干支）
-    let xun_shou_yi = get_xun_shou(base_gz.gan, base_gz.zhi);
-
-    // 5. 计算值符星和值使门
-    let zhi_fu_xing = calc_zhi_fu_xing(xun_shou_yi, &di_pan);
-    let zhi_shi_men = calc_zhi_shi_men(xun_shou_yi, &di_pan);
-
-    // 6. 排布天盘九星
-    let tian_pan_xing = distribute_jiu_xing(zhi_fu_xing, base_gz.gan, &di_pan, dun_type);
-
-    // 7. 排布人盘八门
-    let ren_pan_men = distribute_ba_men(zhi_shi_men, base_gz.gan, &di_pan, dun_type);
-
-    // 8. 找到值符落宫，排布神盘八神
-    let zhi_fu_gong = find_gan_in_di_pan(base_gz.gan, &di_pan).unwrap_or(1);
-    let shen_pan_shen = distribute_ba_shen(zhi_fu_gong, dun_type);
+    assemble_chart(dun_type, san_yuan, ju_number, base_gz)
+}
 
-    // 9. 获取旬空宫位
+/// 以起局干支排布九宫，并标注旬空与驿马
+///
+/// 地盘、值符值使、天盘九星、人盘八门、神盘八神由共用算法库
+/// `divination_algorithms::qimen::arrange_palaces` 排布，此处只做枚举映射。
+fn assemble_chart(
+    dun_type: DunType,
+    san_yuan: SanYuan,
+    ju_number: u8,
+    base_gz: GanZhi,
+) -> (DunType, SanYuan, u8, JiuXing, BaMen, [Palace; 9]) {
+    let (zhi_fu_xing, zhi_shi_men, core_palaces) = core_qimen::arrange_palaces(
+        ju_number,
+        dun_type == DunType::Yang,
+        base_gz.gan.index(),
+        base_gz.zhi.index(),
+    );
+
+    // 旬空宫位与驿马落宫
     let (kong_gong1, kong_gong2) = get_xun_kong_gong(base_gz.gan, base_gz.zhi);
+    let yi_ma_gong = calc_yi_ma(base_gz.zhi).gong.num();
 
-    // 10. 计算驿马落宫
-    let yi_ma = calc_yi_ma(base_gz.zhi);
-    let yi_ma_gong = yi_ma.gong.num();
-
-    // 11. 组装九宫
     let mut palaces = [Palace::empty(JiuGong::Kan); 9];
-
-    for i in 0..9 {
+    for (i, (palace, cell)) in palaces.iter_mut().zip(core_palaces.iter()).enumerate() {
         let gong_num = (i + 1) as u8;
-        let gong = JiuGong::from_num(gong_num).unwrap_or(JiuGong::Kan);
-        let xing = tian_pan_xing[i];
-        let tian_pan_gan = get_tian_pan_gan(xing, &di_pan);
-
-        // 检查是否旬空
-        let is_xun_kong = gong_num == kong_gong1 || gong_num == kong_gong2;
-
-        // 检查是否马星宫
-        let is_ma_xing = gong_num == yi_ma_gong;
-
-        palaces[i] = Palace {
-            gong,
-            tian_pan_gan,
-            di_pan_gan: di_pan[i],
-            xing,
-            men: ren_pan_men[i],
-            shen: shen_pan_shen[i],
-            is_xun_kong,
-            is_ma_xing,
+        *palace = Palace {
+            gong: JiuGong::from_num(gong_num).unwrap_or(JiuGong::Kan),
+            tian_pan_gan: gan_from_index(cell.tian_gan),
+            di_pan_gan: gan_from_index(cell.di_gan),
+            xing: JiuXing::from_num(cell.xing).unwrap_or(JiuXing::TianQin),
+            men: cell.men.and_then(BaMen::from_num),
+            shen: cell.shen.and_then(BaShen::from_num),
+            is_xun_kong: gong_num == kong_gong1 || gong_num == kong_gong2,
+            is_ma_xing: gong_num == yi_ma_gong,
         };
     }
 
-    (dun_type, san_yuan, ju_number, zhi_fu_xing, zhi_shi_men, palaces)
+    (
+        dun_type,
+        san_yuan,
+        ju_number,
+        JiuXing::from_num(zhi_fu_xing).unwrap_or(JiuXing::TianPeng),
+        BaMen::from_num(zhi_shi_men).unwrap_or(BaMen::Xiu),
+        palaces,
+    )
 }
 
 /// 从数字生成排盘
@@ -992,3 +992,43 @@ fn update_encrypted_data_public_chart_fails() {
         );
     });
 }
+
+// ==================== 黄金向量测试（与 TEE Enclave 共用）====================
+
+#[test]
+fn generate_qimen_chart_matches_golden_vectors() {
+    use crate::algorithm::generate_qimen_chart;
+    use divination_algorithms::golden::QIMEN_PAN_CASES;
+
+    let jia_zi = GanZhi { gan: TianGan::Jia, zhi: DiZhi::Zi };
+    for case in QIMEN_PAN_CASES {
+        let hour_gz = GanZhi {
+            gan: TianGan::from_index(case.hour_gan).unwrap(),
+            zhi: DiZhi::from_index(case.hour_zhi).unwrap(),
+        };
+        let jie_qi = JieQi::from_index(case.jie_qi).unwrap();
+        let (dun_type, san_yuan, ju_number, zhi_fu_xing, zhi_shi_men, palaces) =
+            generate_qimen_chart(jia_zi, jia_zi, jia_zi, hour_gz, jie_qi, case.day_in_jieqi);
+
+        // 按 QimenChart::to_bytes() 的布局重组链上排盘结果
+        let mut bytes = vec![
+            case.jie_qi,
+            (dun_type == DunType::Yang) as u8,
+            san_yuan as u8,
+            ju_number,
+            zhi_fu_xing as u8,
+            zhi_shi_men as u8,
+        ];
+        for palace in palaces.iter() {
+            bytes.extend_from_slice(&[
+                palace.tian_pan_gan as u8,
+                palace.di_pan_gan as u8,
+                palace.xing as u8,
+                palace.men.map(|m| m as u8).unwrap_or(0),
+                palace.shen.map(|s| s as u8).unwrap_or(0),
+            ]);
+        }
+
+        assert_eq!(bytes, case.expected.to_vec());
+    }
+}
@@ -434,6 +434,25 @@ pub fn draw_cards_with_cut(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_number_draw_matches_golden_vectors() {
+        use crate::ocw_tee::TarotIndex;
+        use codec::Encode;
+        use divination_algorithms::golden::TAROT_CASES;
+
+        // 与 TEE Enclave 共用的黄金向量：数字抽牌的链上索引必须逐字节一致
+        for case in TAROT_CASES {
+            let count = get_spread_card_count(case.spread_type);
+            let cards = generate_cards_from_number(case.number, count);
+            let index = TarotIndex {
+                spread_type: case.spread_type,
+                card_count: cards.len() as u8,
+                first_card: cards[0],
+            };
+            assert_eq!(index.encode(), case.expected.to_vec());
+        }
+    }
+
     #[test]
     fn test_draw_cards_random() {
         let seed = [1u8; 32];
@@ -1502,43 +1521,21 @@ mod interpretation_tests {
 
 // ============================================================================
 // OCW-TEE 集成辅助函数
+//
+// 数字抽牌委托给 `divination-algorithms`，与 TEE Enclave 共用同一实现。
 // ============================================================================
 
 /// 获取牌阵所需的牌数量
 pub fn get_spread_card_count(spread_type: u8) -> u8 {
-    match spread_type {
-        0 => 1,  // 单张
-        1 => 3,  // 三牌阵
-        2 => 5,  // 五牌阵
-        3 => 10, // 凯尔特十字
-        4 => 7,  // 七星阵
-        _ => 3,  // 默认三牌
-    }
+    divination_algorithms::tarot::get_spread_card_count(spread_type)
 }
 
 /// 从数字生成牌列表
 pub fn generate_cards_from_number(num: u32, count: u8) -> Vec<u8> {
-    let mut cards = Vec::with_capacity(count as usize);
-    let mut used = [false; 78];
-    
-    for i in 0..count {
-        let seed = num.wrapping_add(i as u32 * 17);
-        let mut card_id = (seed % 78) as u8;
-        
-        // 避免重复
-        while used[card_id as usize] {
-            card_id = (card_id + 1) % 78;
-        }
-        used[card_id as usize] = true;
-        cards.push(card_id);
-    }
-    
-    cards
+    divination_algorithms::tarot::generate_cards_from_number(num, count)
 }
 
 /// 从数字生成正逆位列表
 pub fn generate_reversed_from_number(num: u32, count: u8) -> Vec<bool> {
-    (0..count)
-        .map(|i| ((num >> (i % 32)) & 1) == 1)
-        .collect()
+    divination_algorithms::tarot::generate_reversed_from_number(num, count)
 }
@@ -0,0 +1,317 @@
+//! # 证书链
+//!
+//! PCK 证书链与抵押品签名证书链的解析、签名验证，以及 PCK 证书 SGX 扩展解析。
+//!
+//! Intel SGX PKI 全部使用 ECDSA P-256 + SHA-256；其他算法一律拒绝。
+
+use alloc::vec::Vec;
+
+use der::asn1::ObjectIdentifier;
+use der::{Decode, Encode};
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::{Signature, VerifyingKey};
+use x509_cert::Certificate;
+
+use crate::error::{DcapError, DcapResult};
+
+/// ecdsa-with-SHA256
+pub const ECDSA_WITH_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+
+/// PCK 证书 SGX 扩展
+pub const SGX_EXTENSION: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113741.1.13.1");
+
+const SGX_TCB: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113741.1.13.1.2");
+const SGX_PCEID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113741.1.13.1.3");
+const SGX_FMSPC: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113741.1.13.1.4");
+
+/// PCK 证书 SGX 扩展中的平台信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PckExtension {
+    /// 平台系列（Family-Model-Stepping-Platform-CustomSKU）
+    pub fmspc: [u8; 6],
+    /// PCE ID
+    pub pce_id: [u8; 2],
+    /// SGX TCB 组件 SVN（comp01 ~ comp16）
+    pub tcb_components: [u8; 16],
+    /// PCE 安全版本
+    pub pce_svn: u16,
+}
+
+/// 解析 DER 证书
+pub fn parse_der(der: &[u8]) -> DcapResult<Certificate> {
+    Certificate::from_der(der).map_err(|_| DcapError::CertificateParse)
+}
+
+/// 解析 PEM 证书链
+///
+/// Quote 中的证书链可能以 NUL 结尾，解析前去除。
+pub fn parse_pem_chain(pem: &[u8]) -> DcapResult<Vec<Certificate>> {
+    let end = pem.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+    let chain =
+        Certificate::load_pem_chain(&pem[..end]).map_err(|_| DcapError::CertificateParse)?;
+    if chain.is_empty() {
+        return Err(DcapError::CertificateChain);
+    }
+    Ok(chain)
+}
+
+/// 验证证书链：逐级签名、有效期，且链尾与可信根证书逐字节一致
+///
+/// `chain` 叶子在前、根在后。
+pub fn verify_chain(chain: &[Certificate], root_der: &[u8], now: u64) -> DcapResult<()> {
+    let root = chain.last().ok_or(DcapError::CertificateChain)?;
+    if root.to_der()? != root_der {
+        return Err(DcapError::UntrustedRoot);
+    }
+
+    for (i, cert) in chain.iter().enumerate() {
+        check_validity(cert, now)?;
+        let issuer = chain.get(i + 1).unwrap_or(root);
+        verify_signed_by(cert, issuer)?;
+    }
+    Ok(())
+}
+
+/// 检查证书有效期
+pub fn check_validity(cert: &Certificate, now: u64) -> DcapResult<()> {
+    let validity = &cert.tbs_certificate.validity;
+    let not_before = validity.not_before.to_unix_duration().as_secs();
+    let not_after = validity.not_after.to_unix_duration().as_secs();
+    if now < not_before || now > not_after {
+        return Err(DcapError::CertificateExpired);
+    }
+    Ok(())
+}
+
+/// 验证证书由 `issuer` 签发
+pub fn verify_signed_by(cert: &Certificate, issuer: &Certificate) -> DcapResult<()> {
+    if cert.tbs_certificate.issuer != issuer.tbs_certificate.subject
+        || cert.signature_algorithm.oid != ECDSA_WITH_SHA256
+    {
+        return Err(DcapError::CertificateChain);
+    }
+
+    let tbs = cert.tbs_certificate.to_der()?;
+    let signature = cert
+        .signature
+        .as_bytes()
+        .ok_or(DcapError::CertificateSignature)?;
+    verify_der_signature(&public_key(issuer)?, &tbs, signature)
+        .map_err(|_| DcapError::CertificateSignature)
+}
+
+/// 提取证书公钥
+pub fn public_key(cert: &Certificate) -> DcapResult<VerifyingKey> {
+    let spki = &cert.tbs_certificate.subject_public_key_info;
+    let bytes = spki
+        .subject_public_key
+        .as_bytes()
+        .ok_or(DcapError::CertificateParse)?;
+    VerifyingKey::from_sec1_bytes(bytes).map_err(|_| DcapError::CertificateParse)
+}
+
+/// 验证 DER 编码的 ECDSA-SHA256 签名
+pub fn verify_der_signature(
+    key: &VerifyingKey,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<(), p256::ecdsa::Error> {
+    key.verify(message, &Signature::from_der(signature)?)
+}
+
+/// 验证原始 r||s 格式的 ECDSA-SHA256 签名（Quote、QE 报告、Intel JSON）
+pub fn verify_raw_signature(
+    key: &VerifyingKey,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<(), p256::ecdsa::Error> {
+    key.verify(message, &Signature::from_slice(signature)?)
+}
+
+/// 从原始 x||y 公钥构造验证密钥（Quote 认证公钥）
+pub fn raw_public_key(xy: &[u8; 64]) -> DcapResult<VerifyingKey> {
+    let mut sec1 = [0u8; 65];
+    sec1[0] = 0x04;
+    sec1[1..].copy_from_slice(xy);
+    VerifyingKey::from_sec1_bytes(&sec1).map_err(|_| DcapError::AttestationKey)
+}
+
+/// 解析 PCK 证书 SGX 扩展
+///
+/// 扩展值为 `SEQUENCE OF SEQUENCE { OID, value }`，其中 TCB 项的值
+/// 又是同样结构的序列（comp01 ~ comp16、pcesvn、cpusvn）。
+pub fn pck_extension(cert: &Certificate) -> DcapResult<PckExtension> {
+    let extension = cert
+        .tbs_certificate
+        .extensions
+        .as_ref()
+        .and_then(|exts| exts.iter().find(|e| e.extn_id == SGX_EXTENSION))
+        .ok_or(DcapError::PckExtension)?;
+
+    let mut fmspc = None;
+    let mut pce_id = None;
+    let mut tcb = None;
+
+    for (oid, value) in oid_entries(extension.extn_value.as_bytes())? {
+        if oid == SGX_FMSPC {
+            fmspc = Some(fixed::<6>(octet_string(value)?)?);
+        } else if oid == SGX_PCEID {
+            pce_id = Some(fixed::<2>(octet_string(value)?)?);
+        } else if oid == SGX_TCB {
+            tcb = Some(tcb_entries(value)?);
+        }
+    }
+
+    let (tcb_components, pce_svn) = tcb.ok_or(DcapError::PckExtension)?;
+    Ok(PckExtension {
+        fmspc: fmspc.ok_or(DcapError::PckExtension)?,
+        pce_id: pce_id.ok_or(DcapError::PckExtension)?,
+        tcb_components,
+        pce_svn,
+    })
+}
+
+/// 解析 TCB 项：comp01 ~ comp16 与 pcesvn
+fn tcb_entries(value: Tlv<'_>) -> DcapResult<([u8; 16], u16)> {
+    let mut components = [None; 16];
+    let mut pce_svn = None;
+
+    for (oid, item) in oid_entries_of(value)? {
+        if oid.parent() != Some(SGX_TCB) {
+            continue;
+        }
+        match oid.arcs().last() {
+            Some(n @ 1..=16) => {
+                let svn = u8::try_from(integer(item)?).map_err(|_| DcapError::PckExtension)?;
+                components[n as usize - 1] = Some(svn);
+            }
+            Some(17) => {
+                let svn = u16::try_from(integer(item)?).map_err(|_| DcapError::PckExtension)?;
+                pce_svn = Some(svn);
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = [0u8; 16];
+    for (slot, svn) in out.iter_mut().zip(components) {
+        *slot = svn.ok_or(DcapError::PckExtension)?;
+    }
+    Ok((out, pce_svn.ok_or(DcapError::PckExtension)?))
+}
+
+// ==================== 最小 DER 遍历 ====================
+
+const TAG_INTEGER: u8 = 0x02;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_OID: u8 = 0x06;
+const TAG_SEQUENCE: u8 = 0x30;
+
+/// DER TLV
+#[derive(Clone, Copy)]
+struct Tlv<'a> {
+    tag: u8,
+    value: &'a [u8],
+}
+
+/// 读取一个 TLV，返回 (TLV, 剩余字节)
+fn read_tlv(input: &[u8]) -> DcapResult<(Tlv<'_>, &[u8])> {
+    let (&tag, rest) = input.split_first().ok_or(DcapError::PckExtension)?;
+    let (&first, mut rest) = rest.split_first().ok_or(DcapError::PckExtension)?;
+
+    let len = if first < 0x80 {
+        first as usize
+    } else {
+        let n = (first & 0x7f) as usize;
+        if n == 0 || n > 4 || rest.len() < n {
+            return Err(DcapError::PckExtension);
+        }
+        let len = rest[..n]
+            .iter()
+            .fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        rest = &rest[n..];
+        len
+    };
+
+    if rest.len() < len {
+        return Err(DcapError::PckExtension);
+    }
+    let (value, rest) = rest.split_at(len);
+    Ok((Tlv { tag, value }, rest))
+}
+
+/// 解析 `SEQUENCE OF SEQUENCE { OID, value }`
+fn oid_entries(der: &[u8]) -> DcapResult<Vec<(ObjectIdentifier, Tlv<'_>)>> {
+    let (outer, _) = read_tlv(der)?;
+    oid_entries_of(outer)
+}
+
+fn oid_entries_of(outer: Tlv<'_>) -> DcapResult<Vec<(ObjectIdentifier, Tlv<'_>)>> {
+    if outer.tag != TAG_SEQUENCE {
+        return Err(DcapError::PckExtension);
+    }
+
+    let mut entries = Vec::new();
+    let mut rest = outer.value;
+    while !rest.is_empty() {
+        let (entry, next) = read_tlv(rest)?;
+        rest = next;
+        if entry.tag != TAG_SEQUENCE {
+            return Err(DcapError::PckExtension);
+        }
+        let (oid, value) = read_tlv(entry.value)?;
+        let (value, _) = read_tlv(value)?;
+        if oid.tag != TAG_OID {
+            return Err(DcapError::PckExtension);
+        }
+        let oid = ObjectIdentifier::from_bytes(oid.value).map_err(|_| DcapError::PckExtension)?;
+        entries.push((oid, value));
+    }
+    Ok(entries)
+}
+
+fn octet_string(tlv: Tlv<'_>) -> DcapResult<&[u8]> {
+    if tlv.tag != TAG_OCTET_STRING {
+        return Err(DcapError::PckExtension);
+    }
+    Ok(tlv.value)
+}
+
+fn integer(tlv: Tlv<'_>) -> DcapResult<u32> {
+    if tlv.tag != TAG_INTEGER || tlv.value.is_empty() || tlv.value.len() > 5 {
+        return Err(DcapError::PckExtension);
+    }
+    let value = tlv.value.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64);
+    u32::try_from(value).map_err(|_| DcapError::PckExtension)
+}
+
+fn fixed<const N: usize>(bytes: &[u8]) -> DcapResult<[u8; N]> {
+    bytes.try_into().map_err(|_| DcapError::PckExtension)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_tlv_long_form_length() {
+        let mut der = alloc::vec![0x04, 0x81, 0x80];
+        der.extend_from_slice(&[7u8; 0x80]);
+        let (tlv, rest) = read_tlv(&der).unwrap();
+        assert_eq!(tlv.tag, TAG_OCTET_STRING);
+        assert_eq!(tlv.value.len(), 0x80);
+        assert!(rest.is_empty());
+
+        // 声明长度超出数据
+        assert!(read_tlv(&[0x04, 0x05, 0x00]).is_err());
+    }
+
+    #[test]
+    fn test_integer_decoding() {
+        let tlv = Tlv {
+            tag: TAG_INTEGER,
+            value: &[0x00, 0xff],
+        };
+        assert_eq!(integer(tlv).unwrap(), 255);
+    }
+}
@@ -0,0 +1,507 @@
+//! # TCB 抵押品
+//!
+//! Intel PCS 发布的签名 JSON：
+//! - TCB Info v3（`{"tcbInfo": {...}, "signature": "<hex r||s>"}`），按 FMSPC 区分
+//! - QE Identity v2（`{"enclaveIdentity": {...}, "signature": "<hex r||s>"}`）
+//!
+//! 签名覆盖 `tcbInfo` / `enclaveIdentity` 字段值的原始文本，
+//! 由 TCB Signing 证书签发；签名证书链（PEM）随抵押品一起上传。
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::Deserialize;
+use serde_json::value::RawValue;
+
+use crate::cert::{parse_pem_chain, public_key, verify_chain, verify_raw_signature};
+use crate::crl::{check_not_revoked, parse_crl, verify_crl};
+use crate::error::{DcapError, DcapResult};
+
+/// 支持的 TCB Info 版本
+pub const TCB_INFO_VERSION: u32 = 3;
+
+/// 支持的 QE Identity 版本
+pub const QE_IDENTITY_VERSION: u32 = 2;
+
+/// TCB 状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TcbStatus {
+    /// 最新
+    UpToDate,
+    /// 需要软件加固
+    SwHardeningNeeded,
+    /// 需要配置调整
+    ConfigurationNeeded,
+    /// 需要配置调整与软件加固
+    ConfigurationAndSwHardeningNeeded,
+    /// 过时
+    OutOfDate,
+    /// 过时且需要配置调整
+    OutOfDateConfigurationNeeded,
+    /// 已吊销
+    Revoked,
+}
+
+impl TcbStatus {
+    /// 解析 Intel 状态字符串
+    pub fn parse(s: &str) -> DcapResult<Self> {
+        Ok(match s {
+            "UpToDate" => Self::UpToDate,
+            "SWHardeningNeeded" => Self::SwHardeningNeeded,
+            "ConfigurationNeeded" => Self::ConfigurationNeeded,
+            "ConfigurationAndSWHardeningNeeded" => Self::ConfigurationAndSwHardeningNeeded,
+            "OutOfDate" => Self::OutOfDate,
+            "OutOfDateConfigurationNeeded" => Self::OutOfDateConfigurationNeeded,
+            "Revoked" => Self::Revoked,
+            _ => return Err(DcapError::CollateralParse),
+        })
+    }
+
+    /// 是否可接受（平台安全补丁已就绪，仅需软件加固）
+    ///
+    /// `ConfigurationNeeded` 类状态表示平台存在需调整的 BIOS / 硬件配置
+    /// （如未关闭超线程），仅在 `accept_configuration_needed` 为真时接受。
+    pub fn is_acceptable(&self, accept_configuration_needed: bool) -> bool {
+        match self {
+            Self::UpToDate | Self::SwHardeningNeeded => true,
+            Self::ConfigurationNeeded | Self::ConfigurationAndSwHardeningNeeded => {
+                accept_configuration_needed
+            }
+            _ => false,
+        }
+    }
+
+    /// 合并 QE 与平台 TCB 状态
+    ///
+    /// QE 过时会使平台的“最新”降级为“过时”，QE 吊销则整体吊销。
+    pub fn converge(platform: Self, qe: Self) -> Self {
+        match (qe, platform) {
+            (Self::Revoked, _) => Self::Revoked,
+            (Self::OutOfDate, Self::UpToDate | Self::SwHardeningNeeded) => Self::OutOfDate,
+            (
+                Self::OutOfDate,
+                Self::ConfigurationNeeded | Self::ConfigurationAndSwHardeningNeeded,
+            ) => Self::OutOfDateConfigurationNeeded,
+            _ => platform,
+        }
+    }
+}
+
+/// 平台 TCB 等级
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TcbLevel {
+    /// SGX TCB 组件 SVN 下限
+    pub components: [u8; 16],
+    /// PCE SVN 下限
+    pub pce_svn: u16,
+    /// 状态
+    pub status: TcbStatus,
+}
+
+/// TCB Info（已验证签名）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TcbInfo {
+    /// 平台系列
+    pub fmspc: [u8; 6],
+    /// PCE ID
+    pub pce_id: [u8; 2],
+    /// 签发时间（Unix 秒）
+    pub issue_date: u64,
+    /// 下次更新时间（Unix 秒）
+    pub next_update: u64,
+    /// TCB 评估数据编号
+    pub evaluation_data_number: u32,
+    /// TCB 等级（按从新到旧排列）
+    pub levels: Vec<TcbLevel>,
+}
+
+impl TcbInfo {
+    /// 查找平台所处 TCB 等级：第一个所有组件与 PCESVN 均不低于下限的等级
+    pub fn status_for(&self, components: &[u8; 16], pce_svn: u16) -> DcapResult<TcbStatus> {
+        self.levels
+            .iter()
+            .find(|level| {
+                pce_svn >= level.pce_svn
+                    && components
+                        .iter()
+                        .zip(level.components.iter())
+                        .all(|(have, need)| have >= need)
+            })
+            .map(|level| level.status)
+            .ok_or(DcapError::TcbLevelNotFound)
+    }
+}
+
+/// QE Identity（已验证签名）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QeIdentity {
+    /// 签发时间（Unix 秒）
+    pub issue_date: u64,
+    /// 下次更新时间（Unix 秒）
+    pub next_update: u64,
+    /// MISCSELECT 期望值
+    pub misc_select: u32,
+    /// MISCSELECT 掩码
+    pub misc_select_mask: u32,
+    /// 属性期望值
+    pub attributes: [u8; 16],
+    /// 属性掩码
+    pub attributes_mask: [u8; 16],
+    /// QE MRSIGNER
+    pub mr_signer: [u8; 32],
+    /// QE 产品 ID
+    pub isv_prod_id: u16,
+    /// QE TCB 等级：(ISVSVN 下限, 状态)，按从新到旧排列
+    pub levels: Vec<(u16, TcbStatus)>,
+}
+
+impl QeIdentity {
+    /// 查找 QE 所处 TCB 等级
+    pub fn status_for(&self, isv_svn: u16) -> DcapResult<TcbStatus> {
+        self.levels
+            .iter()
+            .find(|(min_svn, _)| isv_svn >= *min_svn)
+            .map(|(_, status)| *status)
+            .ok_or(DcapError::TcbLevelNotFound)
+    }
+}
+
+// ==================== 签名 JSON ====================
+
+#[derive(Deserialize)]
+struct SignedTcbInfo<'a> {
+    #[serde(rename = "tcbInfo", borrow)]
+    body: &'a RawValue,
+    signature: &'a str,
+}
+
+#[derive(Deserialize)]
+struct SignedQeIdentity<'a> {
+    #[serde(rename = "enclaveIdentity", borrow)]
+    body: &'a RawValue,
+    signature: &'a str,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TcbInfoJson {
+    version: u32,
+    issue_date: String,
+    next_update: String,
+    fmspc: String,
+    pce_id: String,
+    tcb_evaluation_data_number: u32,
+    tcb_levels: Vec<TcbLevelJson>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TcbLevelJson {
+    tcb: TcbJson,
+    tcb_status: String,
+}
+
+#[derive(Deserialize)]
+struct TcbJson {
+    sgxtcbcomponents: Vec<ComponentJson>,
+    pcesvn: u16,
+}
+
+#[derive(Deserialize)]
+struct ComponentJson {
+    svn: u8,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QeIdentityJson {
+    id: String,
+    version: u32,
+    issue_date: String,
+    next_update: String,
+    miscselect: String,
+    miscselect_mask: String,
+    attributes: String,
+    attributes_mask: String,
+    mrsigner: String,
+    isvprodid: u16,
+    tcb_levels: Vec<QeLevelJson>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QeLevelJson {
+    tcb: QeTcbJson,
+    tcb_status: String,
+}
+
+#[derive(Deserialize)]
+struct QeTcbJson {
+    isvsvn: u16,
+}
+
+/// 验证并解析 TCB Info
+///
+/// - `signing_chain`：TCB Signing 证书链（PEM），须终止于 `root_ca`
+/// - `root_crl`：根 CA CRL，用于检查签名证书未被吊销
+pub fn verify_tcb_info(
+    json: &[u8],
+    signing_chain: &[u8],
+    root_ca: &[u8],
+    root_crl: &[u8],
+    now: u64,
+) -> DcapResult<TcbInfo> {
+    let signed: SignedTcbInfo =
+        serde_json::from_slice(json).map_err(|_| DcapError::CollateralParse)?;
+    verify_signed_body(
+        signed.body,
+        signed.signature,
+        signing_chain,
+        root_ca,
+        root_crl,
+        now,
+    )?;
+
+    let body: TcbInfoJson =
+        serde_json::from_str(signed.body.get()).map_err(|_| DcapError::CollateralParse)?;
+    if body.version != TCB_INFO_VERSION {
+        return Err(DcapError::UnsupportedCollateralVersion(body.version));
+    }
+
+    let levels = body
+        .tcb_levels
+        .iter()
+        .map(|level| {
+            let components: [u8; 16] = level
+                .tcb
+                .sgxtcbcomponents
+                .iter()
+                .map(|c| c.svn)
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| DcapError::CollateralParse)?;
+            Ok(TcbLevel {
+                components,
+                pce_svn: level.tcb.pcesvn,
+                status: TcbStatus::parse(&level.tcb_status)?,
+            })
+        })
+        .collect::<DcapResult<Vec<_>>>()?;
+
+    let info = TcbInfo {
+        fmspc: decode_hex(&body.fmspc)?,
+        pce_id: decode_hex(&body.pce_id)?,
+        issue_date: parse_time(&body.issue_date)?,
+        next_update: parse_time(&body.next_update)?,
+        evaluation_data_number: body.tcb_evaluation_data_number,
+        levels,
+    };
+    check_freshness(info.issue_date, info.next_update, now)?;
+    Ok(info)
+}
+
+/// 验证并解析 QE Identity
+pub fn verify_qe_identity(
+    json: &[u8],
+    signing_chain: &[u8],
+    root_ca: &[u8],
+    root_crl: &[u8],
+    now: u64,
+) -> DcapResult<QeIdentity> {
+    let signed: SignedQeIdentity =
+        serde_json::from_slice(json).map_err(|_| DcapError::CollateralParse)?;
+    verify_signed_body(
+        signed.body,
+        signed.signature,
+        signing_chain,
+        root_ca,
+        root_crl,
+        now,
+    )?;
+
+    let body: QeIdentityJson =
+        serde_json::from_str(signed.body.get()).map_err(|_| DcapError::CollateralParse)?;
+    if body.id != "QE" {
+        return Err(DcapError::CollateralParse);
+    }
+    if body.version != QE_IDENTITY_VERSION {
+        return Err(DcapError::UnsupportedCollateralVersion(body.version));
+    }
+
+    let levels = body
+        .tcb_levels
+        .iter()
+        .map(|level| Ok((level.tcb.isvsvn, TcbStatus::parse(&level.tcb_status)?)))
+        .collect::<DcapResult<Vec<_>>>()?;
+
+    let identity = QeIdentity {
+        issue_date: parse_time(&body.issue_date)?,
+        next_update: parse_time(&body.next_update)?,
+        misc_select: u32::from_le_bytes(decode_hex(&body.miscselect)?),
+        misc_select_mask: u32::from_le_bytes(decode_hex(&body.miscselect_mask)?),
+        attributes: decode_hex(&body.attributes)?,
+        attributes_mask: decode_hex(&body.attributes_mask)?,
+        mr_signer: decode_hex(&body.mrsigner)?,
+        isv_prod_id: body.isvprodid,
+        levels,
+    };
+    check_freshness(identity.issue_date, identity.next_update, now)?;
+    Ok(identity)
+}
+
+/// 验证签名证书链与 JSON 签名
+fn verify_signed_body(
+    body: &RawValue,
+    signature: &str,
+    signing_chain: &[u8],
+    root_ca: &[u8],
+    root_crl: &[u8],
+    now: u64,
+) -> DcapResult<()> {
+    let chain = parse_pem_chain(signing_chain)?;
+    verify_chain(&chain, root_ca, now)?;
+
+    let root = chain.last().ok_or(DcapError::CertificateChain)?;
+    let crl = parse_crl(root_crl)?;
+    verify_crl(&crl, root, now)?;
+    for cert in &chain[..chain.len() - 1] {
+        check_not_revoked(&crl, cert)?;
+    }
+
+    let signature: [u8; 64] = decode_hex(signature).map_err(|_| DcapError::CollateralSignature)?;
+    verify_raw_signature(&public_key(&chain[0])?, body.get().as_bytes(), &signature)
+        .map_err(|_| DcapError::CollateralSignature)
+}
+
+/// 检查抵押品时效
+fn check_freshness(issue_date: u64, next_update: u64, now: u64) -> DcapResult<()> {
+    if now < issue_date || now > next_update {
+        return Err(DcapError::CollateralExpired);
+    }
+    Ok(())
+}
+
+/// 解析定长十六进制字符串（大小写均可）
+fn decode_hex<const N: usize>(s: &str) -> DcapResult<[u8; N]> {
+    let mut out = [0u8; N];
+    hex::decode_to_slice(s, &mut out).map_err(|_| DcapError::CollateralParse)?;
+    Ok(out)
+}
+
+/// 解析 Intel 时间格式 `YYYY-MM-DDTHH:MM:SSZ`（可带毫秒），返回 Unix 秒
+pub fn parse_time(s: &str) -> DcapResult<u64> {
+    let b = s.as_bytes();
+    if b.len() < 20
+        || b[4] != b'-'
+        || b[7] != b'-'
+        || b[10] != b'T'
+        || b[13] != b':'
+        || b[16] != b':'
+        || b[b.len() - 1] != b'Z'
+    {
+        return Err(DcapError::CollateralParse);
+    }
+
+    let num = |range: core::ops::Range<usize>| -> DcapResult<u64> {
+        s.get(range)
+            .filter(|part| part.bytes().all(|c| c.is_ascii_digit()))
+            .and_then(|part| part.parse().ok())
+            .ok_or(DcapError::CollateralParse)
+    };
+
+    let (year, month, day) = (num(0..4)?, num(5..7)?, num(8..10)?);
+    let (hour, minute, second) = (num(11..13)?, num(14..16)?, num(17..19)?);
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return Err(DcapError::CollateralParse);
+    }
+
+    Ok(days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second)
+}
+
+/// 公历日期距 1970-01-01 的天数（1970 年以后）
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y / 400;
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(parse_time("1970-01-01T00:00:00Z").unwrap(), 0);
+        assert_eq!(parse_time("2024-01-01T00:00:00Z").unwrap(), 1_704_067_200);
+        assert_eq!(parse_time("2024-02-29T12:34:56Z").unwrap(), 1_709_210_096);
+        assert_eq!(
+            parse_time("2025-05-01T00:00:00.123Z").unwrap(),
+            1_746_057_600
+        );
+        assert!(parse_time("2025-13-01T00:00:00Z").is_err());
+        assert!(parse_time("2025-05-01 00:00:00").is_err());
+    }
+
+    #[test]
+    fn test_status_convergence() {
+        use TcbStatus::*;
+        assert_eq!(TcbStatus::converge(UpToDate, UpToDate), UpToDate);
+        assert_eq!(TcbStatus::converge(SwHardeningNeeded, OutOfDate), OutOfDate);
+        assert_eq!(
+            TcbStatus::converge(ConfigurationNeeded, OutOfDate),
+            OutOfDateConfigurationNeeded
+        );
+        assert_eq!(TcbStatus::converge(UpToDate, Revoked), Revoked);
+        assert!(!OutOfDate.is_acceptable(true));
+        assert!(SwHardeningNeeded.is_acceptable(false));
+        assert!(!ConfigurationNeeded.is_acceptable(false));
+        assert!(ConfigurationAndSwHardeningNeeded.is_acceptable(true));
+    }
+
+    #[test]
+    fn test_tcb_level_matching() {
+        let info = TcbInfo {
+            fmspc: [0; 6],
+            pce_id: [0; 2],
+            issue_date: 0,
+            next_update: 0,
+            evaluation_data_number: 1,
+            levels: alloc::vec![
+                TcbLevel {
+                    components: [5; 16],
+                    pce_svn: 10,
+                    status: TcbStatus::UpToDate,
+                },
+                TcbLevel {
+                    components: [2; 16],
+                    pce_svn: 5,
+                    status: TcbStatus::OutOfDate,
+                },
+            ],
+        };
+
+        assert_eq!(info.status_for(&[6; 16], 10).unwrap(), TcbStatus::UpToDate);
+        // PCESVN 不足则落到较旧等级
+        assert_eq!(info.status_for(&[6; 16], 9).unwrap(), TcbStatus::OutOfDate);
+        // 任一组件不足同理
+        let mut components = [6; 16];
+        components[15] = 4;
+        assert_eq!(
+            info.status_for(&components, 10).unwrap(),
+            TcbStatus::OutOfDate
+        );
+        assert_eq!(
+            info.status_for(&[1; 16], 10).unwrap_err(),
+            DcapError::TcbLevelNotFound
+        );
+    }
+}
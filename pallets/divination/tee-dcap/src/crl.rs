@@ -0,0 +1,67 @@
+//! # 证书吊销列表
+//!
+//! Intel 发布两类 CRL：
+//! - 根 CA CRL：由 Intel SGX Root CA 签发，吊销中间 CA 与 TCB 签名证书
+//! - PCK CRL：由 PCK Processor CA / PCK Platform CA 签发，吊销 PCK 叶子证书
+//!
+//! 链上保存 DER 原文，验证时按签发者名称匹配 PCK 证书的中间 CA。
+
+use der::{Decode, Encode};
+use x509_cert::crl::CertificateList;
+use x509_cert::Certificate;
+
+use crate::cert::{public_key, verify_der_signature, ECDSA_WITH_SHA256};
+use crate::error::{DcapError, DcapResult};
+
+/// 解析 DER 编码的 CRL
+pub fn parse_crl(der: &[u8]) -> DcapResult<CertificateList> {
+    CertificateList::from_der(der).map_err(|_| DcapError::CrlParse)
+}
+
+/// CRL 是否由 `issuer` 签发（仅比较名称）
+pub fn is_issued_by(crl: &CertificateList, issuer: &Certificate) -> bool {
+    crl.tbs_cert_list.issuer == issuer.tbs_certificate.subject
+}
+
+/// 验证 CRL 签名与时效
+///
+/// `now` 晚于 nextUpdate 时视为过期，需治理上传新 CRL。
+pub fn verify_crl(crl: &CertificateList, issuer: &Certificate, now: u64) -> DcapResult<()> {
+    if !is_issued_by(crl, issuer) || crl.signature_algorithm.oid != ECDSA_WITH_SHA256 {
+        return Err(DcapError::CrlSignature);
+    }
+
+    let tbs = crl
+        .tbs_cert_list
+        .to_der()
+        .map_err(|_| DcapError::CrlParse)?;
+    let signature = crl.signature.as_bytes().ok_or(DcapError::CrlSignature)?;
+    let key = public_key(issuer)?;
+    verify_der_signature(&key, &tbs, signature).map_err(|_| DcapError::CrlSignature)?;
+
+    let this_update = crl.tbs_cert_list.this_update.to_unix_duration().as_secs();
+    if now < this_update {
+        return Err(DcapError::CrlExpired);
+    }
+    if let Some(next_update) = crl.tbs_cert_list.next_update {
+        if now > next_update.to_unix_duration().as_secs() {
+            return Err(DcapError::CrlExpired);
+        }
+    }
+    Ok(())
+}
+
+/// 检查证书未被 CRL 吊销
+pub fn check_not_revoked(crl: &CertificateList, cert: &Certificate) -> DcapResult<()> {
+    let serial = &cert.tbs_certificate.serial_number;
+    let revoked = crl
+        .tbs_cert_list
+        .revoked_certificates
+        .as_ref()
+        .is_some_and(|list| list.iter().any(|entry| &entry.serial_number == serial));
+
+    if revoked {
+        return Err(DcapError::CertificateRevoked);
+    }
+    Ok(())
+}
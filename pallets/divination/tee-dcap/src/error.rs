@@ -0,0 +1,84 @@
+//! # 错误类型
+
+/// DCAP 验证错误
+///
+/// 每个变体对应验证流程中的一个失败点，链上按变体映射为认证错误码。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DcapError {
+    // ========== Quote 结构 ==========
+    /// Quote 数据被截断或长度字段越界
+    QuoteTruncated,
+    /// 不支持的 Quote 版本（仅支持 3、4）
+    UnsupportedQuoteVersion(u16),
+    /// 不支持的认证密钥类型（仅支持 ECDSA-256-with-P-256）
+    UnsupportedAttestationKeyType(u16),
+    /// 不支持的 TEE 类型（仅支持 SGX）
+    UnsupportedTeeType(u32),
+    /// 不支持的认证数据类型
+    UnsupportedCertificationDataType(u16),
+
+    // ========== 证书链 ==========
+    /// 证书或证书链解析失败
+    CertificateParse,
+    /// 证书链结构不符（长度、签发关系）
+    CertificateChain,
+    /// 证书链根证书与链上存储的根证书不一致
+    UntrustedRoot,
+    /// 证书不在有效期内
+    CertificateExpired,
+    /// 证书签名无效
+    CertificateSignature,
+    /// 证书已被吊销
+    CertificateRevoked,
+    /// PCK 证书缺少或无法解析 SGX 扩展
+    PckExtension,
+
+    // ========== CRL ==========
+    /// CRL 解析失败
+    CrlParse,
+    /// CRL 签名无效或签发者不符
+    CrlSignature,
+    /// CRL 已过下次更新时间
+    CrlExpired,
+    /// 缺少 PCK 签发 CA 的 CRL
+    CrlMissing,
+
+    // ========== 抵押品 ==========
+    /// TCB Info / QE Identity 解析失败
+    CollateralParse,
+    /// 不支持的抵押品版本
+    UnsupportedCollateralVersion(u32),
+    /// 抵押品签名无效
+    CollateralSignature,
+    /// 抵押品已过下次更新时间（或签发时间晚于当前时间）
+    CollateralExpired,
+    /// TCB Info 的 FMSPC 与 PCK 证书不一致
+    FmspcMismatch,
+    /// TCB Info 的 PCE ID 与 PCK 证书不一致
+    PceIdMismatch,
+
+    // ========== QE 报告 ==========
+    /// QE 报告签名无效
+    QeReportSignature,
+    /// QE 报告数据未绑定认证公钥
+    QeReportDataMismatch,
+    /// QE 报告与 QE Identity 不符（MRSIGNER、ISVPRODID、属性）
+    QeIdentityMismatch,
+
+    // ========== Quote 签名与 TCB ==========
+    /// Quote 签名无效
+    QuoteSignature,
+    /// 认证公钥格式无效
+    AttestationKey,
+    /// 平台或 QE 的 TCB 低于所有已知等级
+    TcbLevelNotFound,
+}
+
+/// 验证结果
+pub type DcapResult<T> = Result<T, DcapError>;
+
+impl From<der::Error> for DcapError {
+    fn from(_: der::Error) -> Self {
+        DcapError::CertificateParse
+    }
+}
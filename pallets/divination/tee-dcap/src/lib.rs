@@ -0,0 +1,77 @@
+//! # Intel SGX DCAP Quote 验证库
+//!
+//! 链上 TEE 认证使用的 ECDSA Quote v3 / v4 验证，不依赖 Intel QVL 与网络访问：
+//! 验证所需的根证书、CRL、TCB Info、QE Identity 由治理上传到链上，
+//! Pallet 在注册节点时把这些抵押品与 Quote 一起交给 [`verify_quote`]。
+//!
+//! ## 设计原则
+//!
+//! - **no_std**：Runtime 可直接引用，依赖均为 RustCrypto 纯 Rust 实现
+//! - **原始格式**：抵押品保持 Intel PCS 下发的原始字节（DER / PEM / 签名 JSON），
+//!   上传与使用时都重新验证签名，链上不保存任何未经签名的派生数据
+//! - **策略分离**：本库只判定 Quote 是否真实并给出 TCB 状态，
+//!   是否接受某一状态以及 MRENCLAVE 白名单由调用方决定
+//!
+//! ## 模块
+//!
+//! - [`quote`]：Quote v3 / v4 二进制解析
+//! - [`cert`]：证书链验证与 PCK SGX 扩展解析
+//! - [`crl`]：证书吊销列表
+//! - [`collateral`]：TCB Info / QE Identity 签名 JSON
+//! - [`verify`]：完整验证流程
+//!
+//! ## 测试夹具
+//!
+//! `tests/fixtures` 下的 Quote 与抵押品由 `generate.py` 基于测试 PKI 生成，
+//! 数据格式与 Intel PCS 完全一致，但根证书并非 Intel SGX Root CA。
+//! 真实平台的 Quote 与 Intel PCS 抵押品由 `tests/fixtures/intel/fetch.sh` 采集，
+//! 对应测试 `tests/intel_quote.rs` 默认忽略，采集后以 `--ignored` 运行。
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod cert;
+pub mod collateral;
+pub mod crl;
+pub mod error;
+pub mod quote;
+pub mod verify;
+
+pub use collateral::{
+    parse_time, verify_qe_identity, verify_tcb_info, QeIdentity, TcbInfo, TcbStatus,
+};
+pub use error::{DcapError, DcapResult};
+pub use quote::{EnclaveReport, Quote, QuoteHeader};
+pub use verify::{verify_quote, Collateral, VerifiedQuote};
+
+/// 验证根 CA 证书：可解析、自签名且在有效期内
+pub fn verify_root_ca(root_ca: &[u8], now: u64) -> DcapResult<()> {
+    let root = cert::parse_der(root_ca)?;
+    cert::check_validity(&root, now)?;
+    cert::verify_signed_by(&root, &root)
+}
+
+/// 验证 CRL 由根 CA 或根 CA 签发的中间 CA（PCK Processor / Platform CA）签发
+///
+/// `issuer_chain` 为空时 CRL 须由根 CA 直接签发；
+/// 否则为中间 CA 证书链（PEM，中间 CA 在前，终止于根 CA）。
+pub fn verify_crl_upload(
+    crl: &[u8],
+    issuer_chain: &[u8],
+    root_ca: &[u8],
+    now: u64,
+) -> DcapResult<()> {
+    let crl = crl::parse_crl(crl)?;
+    let issuer = if issuer_chain.is_empty() {
+        cert::parse_der(root_ca)?
+    } else {
+        let chain = cert::parse_pem_chain(issuer_chain)?;
+        cert::verify_chain(&chain, root_ca, now)?;
+        chain
+            .into_iter()
+            .next()
+            .ok_or(DcapError::CertificateChain)?
+    };
+    crl::verify_crl(&crl, &issuer, now)
+}
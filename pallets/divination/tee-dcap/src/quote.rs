@@ -0,0 +1,283 @@
+//! # Quote 解析
+//!
+//! 解析 ECDSA-256-with-P-256 Quote v3 / v4（仅 SGX）。
+//!
+//! ## 布局
+//!
+//! ```text
+//! Header(48) || ISV Enclave Report Body(384) || 签名数据长度(u32) || 签名数据
+//!
+//! v3 签名数据：
+//!   Quote 签名(64) || 认证公钥(64) || QE 报告(384) || QE 报告签名(64)
+//!   || QE 认证数据长度(u16) || QE 认证数据
+//!   || 认证数据类型(u16 = 5) || 长度(u32) || PCK 证书链(PEM)
+//!
+//! v4 签名数据：
+//!   Quote 签名(64) || 认证公钥(64)
+//!   || 认证数据类型(u16 = 6) || 长度(u32) || QE 报告认证数据
+//!   QE 报告认证数据 = v3 中 QE 报告 ~ PCK 证书链部分
+//! ```
+//!
+//! 所有整数均为小端序。
+
+use crate::error::{DcapError, DcapResult};
+
+/// Quote Header 长度
+pub const HEADER_LEN: usize = 48;
+
+/// Enclave Report Body 长度
+pub const REPORT_LEN: usize = 384;
+
+/// ECDSA P-256 签名 / 公钥长度（r||s、x||y）
+pub const ECDSA_LEN: usize = 64;
+
+/// 认证密钥类型：ECDSA-256-with-P-256
+pub const ATT_KEY_TYPE_ECDSA_P256: u16 = 2;
+
+/// TEE 类型：SGX
+pub const TEE_TYPE_SGX: u32 = 0;
+
+/// 认证数据类型：PCK 证书链（PEM）
+pub const CERT_DATA_PCK_CHAIN: u16 = 5;
+
+/// 认证数据类型：QE 报告认证数据（v4）
+pub const CERT_DATA_QE_REPORT: u16 = 6;
+
+/// Quote Header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuoteHeader {
+    /// Quote 版本
+    pub version: u16,
+    /// 认证密钥类型
+    pub att_key_type: u16,
+    /// TEE 类型（v3 中为保留字段，恒为 0）
+    pub tee_type: u32,
+    /// QE 安全版本
+    pub qe_svn: u16,
+    /// PCE 安全版本
+    pub pce_svn: u16,
+    /// QE 厂商 ID
+    pub qe_vendor_id: [u8; 16],
+    /// 用户数据（前 16 字节为 Platform Identity Key ID）
+    pub user_data: [u8; 20],
+}
+
+/// Enclave Report Body（ISV Enclave 与 QE 共用）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnclaveReport {
+    /// CPU 安全版本
+    pub cpu_svn: [u8; 16],
+    /// MISCSELECT
+    pub misc_select: u32,
+    /// 属性（FLAGS || XFRM）
+    pub attributes: [u8; 16],
+    /// MRENCLAVE
+    pub mr_enclave: [u8; 32],
+    /// MRSIGNER
+    pub mr_signer: [u8; 32],
+    /// 产品 ID
+    pub isv_prod_id: u16,
+    /// 安全版本
+    pub isv_svn: u16,
+    /// 报告数据
+    pub report_data: [u8; 64],
+}
+
+impl EnclaveReport {
+    /// 从 384 字节报告体解析
+    pub fn parse(raw: &[u8]) -> DcapResult<Self> {
+        if raw.len() != REPORT_LEN {
+            return Err(DcapError::QuoteTruncated);
+        }
+
+        Ok(Self {
+            cpu_svn: array(&raw[0..16]),
+            misc_select: u32::from_le_bytes(array(&raw[16..20])),
+            attributes: array(&raw[48..64]),
+            mr_enclave: array(&raw[64..96]),
+            mr_signer: array(&raw[128..160]),
+            isv_prod_id: u16::from_le_bytes(array(&raw[256..258])),
+            isv_svn: u16::from_le_bytes(array(&raw[258..260])),
+            report_data: array(&raw[320..384]),
+        })
+    }
+}
+
+/// 解析后的 Quote（借用原始字节）
+#[derive(Debug, Clone)]
+pub struct Quote<'a> {
+    /// Header
+    pub header: QuoteHeader,
+    /// ISV Enclave 报告
+    pub report: EnclaveReport,
+    /// Quote 签名覆盖的数据（Header || Report Body）
+    pub signed_data: &'a [u8],
+    /// Quote 签名（r||s）
+    pub signature: [u8; ECDSA_LEN],
+    /// 认证公钥（x||y）
+    pub attestation_key: [u8; ECDSA_LEN],
+    /// QE 报告
+    pub qe_report: EnclaveReport,
+    /// QE 报告原始字节（PCK 签名覆盖的数据）
+    pub qe_report_raw: &'a [u8],
+    /// QE 报告签名（r||s）
+    pub qe_report_signature: [u8; ECDSA_LEN],
+    /// QE 认证数据
+    pub qe_auth_data: &'a [u8],
+    /// PCK 证书链（PEM，叶子在前）
+    pub pck_chain: &'a [u8],
+}
+
+impl<'a> Quote<'a> {
+    /// 解析 Quote v3 / v4
+    pub fn parse(bytes: &'a [u8]) -> DcapResult<Self> {
+        let mut reader = Reader::new(bytes);
+
+        let header_raw = reader.take(HEADER_LEN)?;
+        let header = QuoteHeader {
+            version: u16::from_le_bytes(array(&header_raw[0..2])),
+            att_key_type: u16::from_le_bytes(array(&header_raw[2..4])),
+            tee_type: u32::from_le_bytes(array(&header_raw[4..8])),
+            qe_svn: u16::from_le_bytes(array(&header_raw[8..10])),
+            pce_svn: u16::from_le_bytes(array(&header_raw[10..12])),
+            qe_vendor_id: array(&header_raw[12..28]),
+            user_data: array(&header_raw[28..48]),
+        };
+
+        if header.version != 3 && header.version != 4 {
+            return Err(DcapError::UnsupportedQuoteVersion(header.version));
+        }
+        if header.att_key_type != ATT_KEY_TYPE_ECDSA_P256 {
+            return Err(DcapError::UnsupportedAttestationKeyType(
+                header.att_key_type,
+            ));
+        }
+        if header.version == 4 && header.tee_type != TEE_TYPE_SGX {
+            return Err(DcapError::UnsupportedTeeType(header.tee_type));
+        }
+
+        let report = EnclaveReport::parse(reader.take(REPORT_LEN)?)?;
+        let signed_data = &bytes[..HEADER_LEN + REPORT_LEN];
+
+        let sig_len = reader.u32()? as usize;
+        let mut sig = Reader::new(reader.take(sig_len)?);
+
+        let signature = array(sig.take(ECDSA_LEN)?);
+        let attestation_key = array(sig.take(ECDSA_LEN)?);
+
+        // v4 将 QE 报告及证书链包装在类型 6 的认证数据中
+        let mut qe_section = if header.version == 4 {
+            let (kind, data) = sig.cert_data()?;
+            if kind != CERT_DATA_QE_REPORT {
+                return Err(DcapError::UnsupportedCertificationDataType(kind));
+            }
+            Reader::new(data)
+        } else {
+            sig
+        };
+
+        let qe_report_raw = qe_section.take(REPORT_LEN)?;
+        let qe_report = EnclaveReport::parse(qe_report_raw)?;
+        let qe_report_signature = array(qe_section.take(ECDSA_LEN)?);
+        let auth_len = qe_section.u16()? as usize;
+        let qe_auth_data = qe_section.take(auth_len)?;
+
+        let (kind, pck_chain) = qe_section.cert_data()?;
+        if kind != CERT_DATA_PCK_CHAIN {
+            return Err(DcapError::UnsupportedCertificationDataType(kind));
+        }
+
+        Ok(Self {
+            header,
+            report,
+            signed_data,
+            signature,
+            attestation_key,
+            qe_report,
+            qe_report_raw,
+            qe_report_signature,
+            qe_auth_data,
+            pck_chain,
+        })
+    }
+}
+
+/// 顺序读取器
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn take(&mut self, len: usize) -> DcapResult<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(DcapError::QuoteTruncated);
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn u16(&mut self) -> DcapResult<u16> {
+        Ok(u16::from_le_bytes(array(self.take(2)?)))
+    }
+
+    fn u32(&mut self) -> DcapResult<u32> {
+        Ok(u32::from_le_bytes(array(self.take(4)?)))
+    }
+
+    /// 认证数据：类型(u16) || 长度(u32) || 数据
+    fn cert_data(&mut self) -> DcapResult<(u16, &'a [u8])> {
+        let kind = self.u16()?;
+        let len = self.u32()? as usize;
+        Ok((kind, self.take(len)?))
+    }
+}
+
+/// 定长切片转数组（调用方保证长度）
+fn array<const N: usize>(slice: &[u8]) -> [u8; N] {
+    let mut out = [0u8; N];
+    out.copy_from_slice(slice);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_unknown_version() {
+        let mut bytes = [0u8; HEADER_LEN + REPORT_LEN + 4];
+        bytes[0] = 5;
+        bytes[2] = ATT_KEY_TYPE_ECDSA_P256 as u8;
+        assert_eq!(
+            Quote::parse(&bytes).unwrap_err(),
+            DcapError::UnsupportedQuoteVersion(5)
+        );
+    }
+
+    #[test]
+    fn test_rejects_truncated_quote() {
+        let mut bytes = [0u8; HEADER_LEN + REPORT_LEN + 4];
+        bytes[0] = 3;
+        bytes[2] = ATT_KEY_TYPE_ECDSA_P256 as u8;
+        // 签名数据长度声明为 100 但实际为空
+        bytes[HEADER_LEN + REPORT_LEN] = 100;
+        assert_eq!(Quote::parse(&bytes).unwrap_err(), DcapError::QuoteTruncated);
+    }
+
+    #[test]
+    fn test_rejects_tdx_quote() {
+        let mut bytes = [0u8; HEADER_LEN + REPORT_LEN + 4];
+        bytes[0] = 4;
+        bytes[2] = ATT_KEY_TYPE_ECDSA_P256 as u8;
+        bytes[4] = 0x81;
+        assert_eq!(
+            Quote::parse(&bytes).unwrap_err(),
+            DcapError::UnsupportedTeeType(0x81)
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! # Quote 验证流程
+//!
+//! 1. 根 CA CRL 由可信根证书签发且未过期
+//! 2. PCK 证书链终止于可信根证书，逐级签名与有效期有效，未被吊销
+//! 3. TCB Info、QE Identity 签名有效且未过期，FMSPC / PCE ID 与 PCK 证书一致
+//! 4. QE 报告由 PCK 密钥签名，报告数据绑定认证公钥，身份与 QE Identity 一致
+//! 5. Quote 由认证公钥签名
+//! 6. 按 PCK 证书中的 TCB 匹配平台等级，与 QE 等级合并得出最终状态
+
+use alloc::vec::Vec;
+
+use sha2::{Digest, Sha256};
+use x509_cert::Certificate;
+
+use crate::cert::{
+    parse_der, parse_pem_chain, pck_extension, public_key, raw_public_key, verify_chain,
+    verify_raw_signature, verify_signed_by,
+};
+use crate::collateral::{verify_qe_identity, verify_tcb_info, QeIdentity, TcbStatus};
+use crate::crl::{check_not_revoked, is_issued_by, parse_crl, verify_crl};
+use crate::error::{DcapError, DcapResult};
+use crate::quote::{EnclaveReport, Quote, QuoteHeader};
+
+/// 验证所需的抵押品（均为 Intel PCS 原始格式）
+#[derive(Debug, Clone, Copy)]
+pub struct Collateral<'a> {
+    /// Intel SGX Root CA 证书（DER）
+    pub root_ca: &'a [u8],
+    /// 根 CA CRL（DER）
+    pub root_ca_crl: &'a [u8],
+    /// PCK CRL（DER），按签发者匹配 Processor / Platform CA
+    pub pck_crls: &'a [&'a [u8]],
+    /// TCB Info JSON
+    pub tcb_info: &'a [u8],
+    /// TCB Info 签名证书链（PEM）
+    pub tcb_info_chain: &'a [u8],
+    /// QE Identity JSON
+    pub qe_identity: &'a [u8],
+    /// QE Identity 签名证书链（PEM）
+    pub qe_identity_chain: &'a [u8],
+}
+
+/// 验证通过的 Quote
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedQuote {
+    /// Quote Header
+    pub header: QuoteHeader,
+    /// 平台系列
+    pub fmspc: [u8; 6],
+    /// ISV Enclave 报告
+    pub report: EnclaveReport,
+    /// 合并后的 TCB 状态（调用方决定是否接受）
+    pub tcb_status: TcbStatus,
+}
+
+impl<'a> Quote<'a> {
+    /// 读取 PCK 证书中的 FMSPC（用于查找对应 TCB Info）
+    ///
+    /// 不做任何验证，仅供选取抵押品。
+    pub fn fmspc(&self) -> DcapResult<[u8; 6]> {
+        let chain = parse_pem_chain(self.pck_chain)?;
+        Ok(pck_extension(&chain[0])?.fmspc)
+    }
+}
+
+/// 验证 Quote
+///
+/// `now` 为 Unix 秒。仅在所有签名、证书链与抵押品检查通过后返回 `Ok`；
+/// TCB 状态的取舍由调用方按 [`TcbStatus::is_acceptable`] 等策略决定。
+pub fn verify_quote(
+    quote: &[u8],
+    collateral: &Collateral<'_>,
+    now: u64,
+) -> DcapResult<VerifiedQuote> {
+    let quote = Quote::parse(quote)?;
+
+    // 1. 可信根与根 CA CRL
+    let root = parse_der(collateral.root_ca)?;
+    verify_signed_by(&root, &root)?;
+    let root_crl = parse_crl(collateral.root_ca_crl)?;
+    verify_crl(&root_crl, &root, now)?;
+
+    // 2. PCK 证书链：叶子 → Processor / Platform CA → 根
+    let chain = parse_pem_chain(quote.pck_chain)?;
+    if chain.len() != 3 {
+        return Err(DcapError::CertificateChain);
+    }
+    verify_chain(&chain, collateral.root_ca, now)?;
+    let (pck, pck_ca) = (&chain[0], &chain[1]);
+    check_not_revoked(&root_crl, pck_ca)?;
+    check_pck_not_revoked(collateral.pck_crls, pck, pck_ca, now)?;
+    let pck_ext = pck_extension(pck)?;
+
+    // 3. TCB Info 与 QE Identity
+    let tcb_info = verify_tcb_info(
+        collateral.tcb_info,
+        collateral.tcb_info_chain,
+        collateral.root_ca,
+        collateral.root_ca_crl,
+        now,
+    )?;
+    if tcb_info.fmspc != pck_ext.fmspc {
+        return Err(DcapError::FmspcMismatch);
+    }
+    if tcb_info.pce_id != pck_ext.pce_id {
+        return Err(DcapError::PceIdMismatch);
+    }
+    let qe_identity = verify_qe_identity(
+        collateral.qe_identity,
+        collateral.qe_identity_chain,
+        collateral.root_ca,
+        collateral.root_ca_crl,
+        now,
+    )?;
+
+    // 4. QE 报告
+    verify_raw_signature(
+        &public_key(pck)?,
+        quote.qe_report_raw,
+        &quote.qe_report_signature,
+    )
+    .map_err(|_| DcapError::QeReportSignature)?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(quote.attestation_key);
+    hasher.update(quote.qe_auth_data);
+    if hasher.finalize()[..] != quote.qe_report.report_data[..32] {
+        return Err(DcapError::QeReportDataMismatch);
+    }
+    check_qe_identity(&quote.qe_report, &qe_identity)?;
+
+    // 5. Quote 签名
+    let attestation_key = raw_public_key(&quote.attestation_key)?;
+    verify_raw_signature(&attestation_key, quote.signed_data, &quote.signature)
+        .map_err(|_| DcapError::QuoteSignature)?;
+
+    // 6. TCB 状态
+    let platform_status = tcb_info.status_for(&pck_ext.tcb_components, pck_ext.pce_svn)?;
+    let qe_status = qe_identity.status_for(quote.qe_report.isv_svn)?;
+
+    Ok(VerifiedQuote {
+        header: quote.header,
+        fmspc: pck_ext.fmspc,
+        report: quote.report,
+        tcb_status: TcbStatus::converge(platform_status, qe_status),
+    })
+}
+
+/// 用 PCK 签发 CA 对应的 CRL 检查 PCK 证书
+fn check_pck_not_revoked(
+    crls: &[&[u8]],
+    pck: &Certificate,
+    pck_ca: &Certificate,
+    now: u64,
+) -> DcapResult<()> {
+    let crls = crls
+        .iter()
+        .map(|der| parse_crl(der))
+        .collect::<DcapResult<Vec<_>>>()?;
+    let crl = crls
+        .iter()
+        .find(|crl| is_issued_by(crl, pck_ca))
+        .ok_or(DcapError::CrlMissing)?;
+    verify_crl(crl, pck_ca, now)?;
+    check_not_revoked(crl, pck)
+}
+
+/// QE 报告须与 QE Identity 一致
+fn check_qe_identity(report: &EnclaveReport, identity: &QeIdentity) -> DcapResult<()> {
+    let misc_ok = report.misc_select & identity.misc_select_mask
+        == identity.misc_select & identity.misc_select_mask;
+    let attributes_ok = report
+        .attributes
+        .iter()
+        .zip(identity.attributes.iter())
+        .zip(identity.attributes_mask.iter())
+        .all(|((have, want), mask)| have & mask == want & mask);
+
+    if report.mr_signer != identity.mr_signer
+        || report.isv_prod_id != identity.isv_prod_id
+        || !misc_ok
+        || !attributes_ok
+    {
+        return Err(DcapError::QeIdentityMismatch);
+    }
+    Ok(())
+}
@@ -0,0 +1,377 @@
+#!/usr/bin/env python3
+"""生成 DCAP 验证测试夹具
+
+按 Intel PCS / DCAP 规范的数据格式构造一套测试 PKI：
+- 根 CA、PCK Platform CA、PCK 叶子证书（含 SGX 扩展）、TCB 签名证书
+- 根 CA CRL、PCK CRL 及其签发证书链（另有一份吊销了 PCK 叶子证书的 CRL）
+- 签名的 TCB Info v3 与 QE Identity v2 JSON
+- ECDSA-256 Quote v3 / v4（以及 TCB 需要配置调整、TCB 过时平台的 Quote v3）
+
+所有密钥由固定种子派生、签名使用 RFC 6979 确定性 ECDSA，重复运行输出一致。
+
+用法：python3 generate.py（需要 cryptography >= 44）
+"""
+
+import datetime
+import hashlib
+import json
+import os
+import struct
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.asymmetric.utils import decode_dss_signature
+from cryptography.x509.oid import NameOID
+
+OUT = os.path.dirname(os.path.abspath(__file__))
+
+NOT_BEFORE = datetime.datetime(2024, 1, 1, tzinfo=datetime.timezone.utc)
+NOT_AFTER = datetime.datetime(2049, 12, 31, 23, 59, 59, tzinfo=datetime.timezone.utc)
+CRL_THIS_UPDATE = datetime.datetime(2025, 5, 1, tzinfo=datetime.timezone.utc)
+CRL_NEXT_UPDATE = datetime.datetime(2025, 7, 1, tzinfo=datetime.timezone.utc)
+
+FMSPC = bytes.fromhex("00906ED50000")
+PCE_ID = bytes.fromhex("0000")
+QE_VENDOR_ID = bytes.fromhex("939A7233F79C4CA9940A0DB3957F0607")
+QE_MRSIGNER = bytes.fromhex("8C4F5775D796503E96137F77C68A829A0056AC8DED70140B081B094490C57BFF")
+
+# 平台 TCB（PCK 证书中的 SGX TCB 组件）
+PLATFORM_TCB = [16, 16, 3, 3, 255, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+PLATFORM_PCESVN = 13
+CONFIG_TCB = [12, 12, 3, 3, 255, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+CONFIG_PCESVN = 11
+OUTDATED_TCB = [7, 9, 3, 3, 255, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+OUTDATED_PCESVN = 10
+
+# Enclave 密钥（report_data 布局与 tee-worker 一致）
+X25519_PUBKEY = bytes(range(32))
+ED25519_PUBKEY = bytes([0x11]) * 32
+
+SGX_EXT = "1.2.840.113741.1.13.1"
+
+
+def key(seed):
+    """由种子派生 P-256 私钥"""
+    scalar = int.from_bytes(hashlib.sha256(seed.encode()).digest(), "big")
+    order = 0xFFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551
+    return ec.derive_private_key(scalar % (order - 1) + 1, ec.SECP256R1())
+
+
+def name(cn):
+    return x509.Name(
+        [
+            x509.NameAttribute(NameOID.COMMON_NAME, cn),
+            x509.NameAttribute(NameOID.ORGANIZATION_NAME, "Stardust DCAP Test"),
+            x509.NameAttribute(NameOID.COUNTRY_NAME, "US"),
+        ]
+    )
+
+
+def cert(subject, issuer, public_key, issuer_key, serial, ca, extensions=()):
+    builder = (
+        x509.CertificateBuilder()
+        .subject_name(subject)
+        .issuer_name(issuer)
+        .public_key(public_key)
+        .serial_number(serial)
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=ca, path_length=None), critical=True)
+    )
+    for ext in extensions:
+        builder = builder.add_extension(ext, critical=False)
+    return builder.sign(issuer_key, hashes.SHA256(), ecdsa_deterministic=True)
+
+
+def der_len(n):
+    if n < 0x80:
+        return bytes([n])
+    raw = n.to_bytes((n.bit_length() + 7) // 8, "big")
+    return bytes([0x80 | len(raw)]) + raw
+
+
+def tlv(tag, body):
+    return bytes([tag]) + der_len(len(body)) + body
+
+
+def der_oid(dotted):
+    parts = [int(p) for p in dotted.split(".")]
+    body = bytes([parts[0] * 40 + parts[1]])
+    for p in parts[2:]:
+        chunk = [p & 0x7F]
+        p >>= 7
+        while p:
+            chunk.insert(0, 0x80 | (p & 0x7F))
+            p >>= 7
+        body += bytes(chunk)
+    return tlv(0x06, body)
+
+
+def der_int(value):
+    raw = value.to_bytes(max(1, (value.bit_length() + 8) // 8), "big")
+    return tlv(0x02, raw)
+
+
+def sgx_extension(tcb, pcesvn):
+    """PCK 证书 SGX 扩展（OID 1.2.840.113741.1.13.1）"""
+    entries = [
+        tlv(0x30, der_oid(SGX_EXT + ".1") + tlv(0x04, bytes(range(16)))),
+    ]
+    tcb_entries = b""
+    for i, svn in enumerate(tcb):
+        tcb_entries += tlv(0x30, der_oid(f"{SGX_EXT}.2.{i + 1}") + der_int(svn))
+    tcb_entries += tlv(0x30, der_oid(SGX_EXT + ".2.17") + der_int(pcesvn))
+    tcb_entries += tlv(0x30, der_oid(SGX_EXT + ".2.18") + tlv(0x04, bytes(tcb)))
+    entries.append(tlv(0x30, der_oid(SGX_EXT + ".2") + tlv(0x30, tcb_entries)))
+    entries.append(tlv(0x30, der_oid(SGX_EXT + ".3") + tlv(0x04, PCE_ID)))
+    entries.append(tlv(0x30, der_oid(SGX_EXT + ".4") + tlv(0x04, FMSPC)))
+    entries.append(tlv(0x30, der_oid(SGX_EXT + ".5") + tlv(0x0A, b"\x00")))
+    return x509.UnrecognizedExtension(
+        x509.ObjectIdentifier(SGX_EXT), tlv(0x30, b"".join(entries))
+    )
+
+
+def crl(issuer_cert, issuer_key, revoked_serials):
+    builder = (
+        x509.CertificateRevocationListBuilder()
+        .issuer_name(issuer_cert.subject)
+        .last_update(CRL_THIS_UPDATE)
+        .next_update(CRL_NEXT_UPDATE)
+    )
+    for serial in revoked_serials:
+        builder = builder.add_revoked_certificate(
+            x509.RevokedCertificateBuilder()
+            .serial_number(serial)
+            .revocation_date(CRL_THIS_UPDATE)
+            .build()
+        )
+    return builder.sign(issuer_key, hashes.SHA256(), ecdsa_deterministic=True)
+
+
+def raw_sign(private_key, data):
+    """ECDSA-SHA256 签名，输出 r || s（各 32 字节大端）"""
+    signature = private_key.sign(data, ec.ECDSA(hashes.SHA256(), deterministic_signing=True))
+    r, s = decode_dss_signature(signature)
+    return r.to_bytes(32, "big") + s.to_bytes(32, "big")
+
+
+def raw_public(private_key):
+    return private_key.public_key().public_bytes(
+        serialization.Encoding.X962, serialization.PublicFormat.UncompressedPoint
+    )[1:]
+
+
+def pem(*certs):
+    return b"".join(c.public_bytes(serialization.Encoding.PEM) for c in certs)
+
+
+def signed_json(field, body):
+    """Intel 签名 JSON：签名覆盖 body 的紧凑序列化原文"""
+    text = json.dumps(body, separators=(",", ":"))
+    signature = raw_sign(TCB_SIGNING_KEY, text.encode()).hex()
+    return ('{"%s":%s,"signature":"%s"}' % (field, text, signature)).encode()
+
+
+def report_body(
+    mr_enclave,
+    mr_signer,
+    isv_prod_id,
+    isv_svn,
+    report_data,
+    attributes=bytes(16),
+    misc_select=0,
+    cpu_svn=bytes(16),
+):
+    body = (
+        cpu_svn
+        + struct.pack("<I", misc_select)
+        + bytes(12)
+        + bytes(16)
+        + attributes
+        + mr_enclave
+        + bytes(32)
+        + mr_signer
+        + bytes(32)
+        + bytes(64)
+        + struct.pack("<HHH", isv_prod_id, isv_svn, 0)
+        + bytes(42)
+        + bytes(16)
+        + report_data
+    )
+    assert len(body) == 384
+    return body
+
+
+def quote(version, pck_cert, pck_key, pck_chain):
+    attest_key = key("attestation-key-%d" % version)
+    attest_pub = raw_public(attest_key)
+    qe_auth = bytes(range(32))
+
+    header = (
+        struct.pack("<HHIHH", version, 2, 0, 8, PLATFORM_PCESVN)
+        + QE_VENDOR_ID
+        + bytes(20)
+    )
+    assert len(header) == 48
+
+    report_data = hashlib.sha256(X25519_PUBKEY + ED25519_PUBKEY).digest() + X25519_PUBKEY
+    body = report_body(
+        mr_enclave=bytes([0xAA]) * 32,
+        mr_signer=bytes([0xBB]) * 32,
+        isv_prod_id=1,
+        isv_svn=3,
+        report_data=report_data,
+        attributes=bytes.fromhex("07000000000000000700000000000000"),
+    )
+
+    qe_report = report_body(
+        mr_enclave=bytes([0xCC]) * 32,
+        mr_signer=QE_MRSIGNER,
+        isv_prod_id=1,
+        isv_svn=8,
+        report_data=hashlib.sha256(attest_pub + qe_auth).digest() + bytes(32),
+        attributes=bytes.fromhex("11000000000000000000000000000000"),
+    )
+    qe_report_sig = raw_sign(pck_key, qe_report)
+    quote_sig = raw_sign(attest_key, header + body)
+
+    cert_data = struct.pack("<HI", 5, len(pck_chain)) + pck_chain
+    qe_section = qe_report + qe_report_sig + struct.pack("<H", len(qe_auth)) + qe_auth
+
+    if version == 3:
+        sig_data = quote_sig + attest_pub + qe_section + cert_data
+    else:
+        inner = qe_section + cert_data
+        sig_data = quote_sig + attest_pub + struct.pack("<HI", 6, len(inner)) + inner
+
+    return header + body + struct.pack("<I", len(sig_data)) + sig_data
+
+
+def write(name_, data):
+    with open(os.path.join(OUT, name_), "wb") as f:
+        f.write(data)
+
+
+ROOT_KEY = key("root-ca")
+PLATFORM_KEY = key("pck-platform-ca")
+TCB_SIGNING_KEY = key("tcb-signing")
+
+root = cert(name("Intel SGX Root CA"), name("Intel SGX Root CA"), ROOT_KEY.public_key(), ROOT_KEY, 1, True)
+platform_ca = cert(
+    name("Intel SGX PCK Platform CA"), root.subject, PLATFORM_KEY.public_key(), ROOT_KEY, 2, True
+)
+tcb_signing = cert(
+    name("Intel SGX TCB Signing"), root.subject, TCB_SIGNING_KEY.public_key(), ROOT_KEY, 3, False
+)
+
+pck_key = key("pck-leaf")
+pck = cert(
+    name("Intel SGX PCK Certificate"),
+    platform_ca.subject,
+    pck_key.public_key(),
+    PLATFORM_KEY,
+    0x1001,
+    False,
+    [sgx_extension(PLATFORM_TCB, PLATFORM_PCESVN)],
+)
+config_key = key("pck-leaf-config")
+config_pck = cert(
+    name("Intel SGX PCK Certificate"),
+    platform_ca.subject,
+    config_key.public_key(),
+    PLATFORM_KEY,
+    0x1003,
+    False,
+    [sgx_extension(CONFIG_TCB, CONFIG_PCESVN)],
+)
+outdated_key = key("pck-leaf-outdated")
+outdated_pck = cert(
+    name("Intel SGX PCK Certificate"),
+    platform_ca.subject,
+    outdated_key.public_key(),
+    PLATFORM_KEY,
+    0x1002,
+    False,
+    [sgx_extension(OUTDATED_TCB, OUTDATED_PCESVN)],
+)
+
+write("root_ca.der", root.public_bytes(serialization.Encoding.DER))
+write("root_ca_crl.der", crl(root, ROOT_KEY, []).public_bytes(serialization.Encoding.DER))
+write("pck_crl.der", crl(platform_ca, PLATFORM_KEY, []).public_bytes(serialization.Encoding.DER))
+write(
+    "pck_crl_revoked.der",
+    crl(platform_ca, PLATFORM_KEY, [0x1001]).public_bytes(serialization.Encoding.DER),
+)
+write("pck_crl_issuer_chain.pem", pem(platform_ca, root))
+write("tcb_signing_chain.pem", pem(tcb_signing, root))
+
+tcb_info = {
+    "id": "SGX",
+    "version": 3,
+    "issueDate": "2025-05-01T00:00:00Z",
+    "nextUpdate": "2025-07-01T00:00:00Z",
+    "fmspc": FMSPC.hex().upper(),
+    "pceId": PCE_ID.hex().upper(),
+    "tcbType": 0,
+    "tcbEvaluationDataNumber": 17,
+    "tcbLevels": [
+        {
+            "tcb": {
+                "sgxtcbcomponents": [{"svn": svn} for svn in PLATFORM_TCB],
+                "pcesvn": PLATFORM_PCESVN,
+            },
+            "tcbDate": "2024-11-13T00:00:00Z",
+            "tcbStatus": "UpToDate",
+        },
+        {
+            "tcb": {
+                "sgxtcbcomponents": [{"svn": svn} for svn in CONFIG_TCB],
+                "pcesvn": CONFIG_PCESVN,
+            },
+            "tcbDate": "2024-11-13T00:00:00Z",
+            "tcbStatus": "ConfigurationNeeded",
+            "advisoryIDs": ["INTEL-SA-00615"],
+        },
+        {
+            "tcb": {
+                "sgxtcbcomponents": [{"svn": svn} for svn in OUTDATED_TCB],
+                "pcesvn": OUTDATED_PCESVN,
+            },
+            "tcbDate": "2019-11-13T00:00:00Z",
+            "tcbStatus": "OutOfDate",
+            "advisoryIDs": ["INTEL-SA-00334"],
+        },
+    ],
+}
+write("tcb_info.json", signed_json("tcbInfo", tcb_info))
+
+qe_identity = {
+    "id": "QE",
+    "version": 2,
+    "issueDate": "2025-05-01T00:00:00Z",
+    "nextUpdate": "2025-07-01T00:00:00Z",
+    "tcbEvaluationDataNumber": 17,
+    "miscselect": "00000000",
+    "miscselectMask": "FFFFFFFF",
+    "attributes": "11000000000000000000000000000000",
+    "attributesMask": "FBFFFFFFFFFFFFFF0000000000000000",
+    "mrsigner": QE_MRSIGNER.hex().upper(),
+    "isvprodid": 1,
+    "tcbLevels": [
+        {"tcb": {"isvsvn": 8}, "tcbDate": "2024-03-13T00:00:00Z", "tcbStatus": "UpToDate"},
+        {"tcb": {"isvsvn": 6}, "tcbDate": "2021-11-10T00:00:00Z", "tcbStatus": "OutOfDate"},
+    ],
+}
+write("qe_identity.json", signed_json("enclaveIdentity", qe_identity))
+
+write("quote_v3.bin", quote(3, pck, pck_key, pem(pck, platform_ca, root)))
+write("quote_v4.bin", quote(4, pck, pck_key, pem(pck, platform_ca, root)))
+write(
+    "quote_v3_config.bin",
+    quote(3, config_pck, config_key, pem(config_pck, platform_ca, root)),
+)
+write(
+    "quote_v3_outdated.bin",
+    quote(3, outdated_pck, outdated_key, pem(outdated_pck, platform_ca, root)),
+)
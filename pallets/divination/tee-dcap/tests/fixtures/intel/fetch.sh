@@ -0,0 +1,48 @@
+#!/usr/bin/env bash
+# 采集真实 Intel SGX 平台的 Quote 与 Intel PCS 抵押品，供 tests/intel_quote.rs 使用
+#
+# 用法：fetch.sh <quote.bin> <FMSPC>
+#   quote.bin：在 SGX 机器上由 tee-enclave 生成的 ECDSA Quote（report_data 绑定 Enclave 公钥）
+#   FMSPC：平台 FMSPC（12 位十六进制，可由 PCK 证书或 PCCS 查询）
+#
+# 抵押品取自 Intel PCS v4 API，签发证书链从响应头中取出（URL 编码的 PEM）。
+# 采集时间写入 captured_at，测试按该时间校验抵押品有效期。
+
+set -euo pipefail
+
+if [ $# -ne 2 ]; then
+    echo "usage: $0 <quote.bin> <FMSPC>" >&2
+    exit 1
+fi
+
+OUT="$(cd "$(dirname "$0")" && pwd)"
+PCS="https://api.trustedservices.intel.com/sgx/certification/v4"
+HEADERS="$(mktemp)"
+trap 'rm -f "$HEADERS"' EXIT
+
+# 取出响应头中 URL 编码的证书链
+issuer_chain() {
+    grep -i "^$1:" "$HEADERS" | cut -d' ' -f2- | tr -d '\r' \
+        | python3 -c 'import sys, urllib.parse; sys.stdout.write(urllib.parse.unquote(sys.stdin.read().strip()))'
+}
+
+cp "$1" "$OUT/quote.bin"
+
+curl -sSf -o "$OUT/root_ca.der" \
+    "https://certificates.trustedservices.intel.com/Intel_SGX_Provisioning_Certification_RootCA.cer"
+curl -sSf -o "$OUT/root_ca_crl.der" \
+    "https://certificates.trustedservices.intel.com/IntelSGXRootCA.der"
+
+# PCK 证书由 Processor CA 或 Platform CA 签发，两份 CRL 都采集
+for ca in processor platform; do
+    curl -sSf -D "$HEADERS" -o "$OUT/pck_crl_$ca.der" "$PCS/pckcrl?ca=$ca&encoding=der"
+    issuer_chain "SGX-PCK-CRL-Issuer-Chain" > "$OUT/pck_crl_${ca}_issuer_chain.pem"
+done
+
+curl -sSf -D "$HEADERS" -o "$OUT/tcb_info.json" "$PCS/tcb?fmspc=$2"
+issuer_chain "TCB-Info-Issuer-Chain" > "$OUT/tcb_signing_chain.pem"
+
+curl -sSf -D "$HEADERS" -o "$OUT/qe_identity.json" "$PCS/qe/identity"
+
+date -u +%s > "$OUT/captured_at"
+echo "collateral written to $OUT"
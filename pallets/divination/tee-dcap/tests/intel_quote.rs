@@ -0,0 +1,90 @@
+//! 真实 Intel SGX 平台 Quote 的端到端验证
+//!
+//! 夹具须在 SGX 机器上由 `tests/fixtures/intel/fetch.sh` 采集（Quote 来自硬件，
+//! 抵押品来自 Intel PCS），未采集时测试忽略：
+//!
+//! ```text
+//! tests/fixtures/intel/fetch.sh quote.bin 00906ED50000
+//! cargo test -p tee-dcap --test intel_quote -- --ignored
+//! ```
+
+use std::{fs, path::PathBuf};
+
+use tee_dcap::{
+    verify_crl_upload, verify_qe_identity, verify_quote, verify_root_ca, verify_tcb_info,
+    Collateral, Quote, TcbStatus,
+};
+
+/// Intel SGX Root CA 证书主题
+const INTEL_ROOT_CN: &[u8] = b"Intel SGX Root CA";
+
+fn fixture(name: &str) -> Vec<u8> {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "fixtures", "intel", name]
+        .iter()
+        .collect();
+    fs::read(&path).unwrap_or_else(|_| panic!("missing {}; run fetch.sh first", path.display()))
+}
+
+/// 采集时间（抵押品在该时间点有效）
+fn captured_at() -> u64 {
+    String::from_utf8(fixture("captured_at"))
+        .unwrap()
+        .trim()
+        .parse()
+        .unwrap()
+}
+
+#[test]
+#[ignore = "requires a quote captured on SGX hardware, see tests/fixtures/intel/fetch.sh"]
+fn verifies_captured_intel_quote() {
+    let now = captured_at();
+    let root_ca = fixture("root_ca.der");
+    let root_ca_crl = fixture("root_ca_crl.der");
+    let pck_crl_processor = fixture("pck_crl_processor.der");
+    let pck_crl_platform = fixture("pck_crl_platform.der");
+    let tcb_info = fixture("tcb_info.json");
+    let tcb_chain = fixture("tcb_signing_chain.pem");
+    let qe_identity = fixture("qe_identity.json");
+    let quote = fixture("quote.bin");
+
+    // 根证书须为 Intel SGX Root CA
+    verify_root_ca(&root_ca, now).unwrap();
+    assert!(root_ca.windows(INTEL_ROOT_CN.len()).any(|w| w == INTEL_ROOT_CN));
+
+    // 抵押品上传时的校验与链上一致
+    verify_crl_upload(&root_ca_crl, &[], &root_ca, now).unwrap();
+    verify_crl_upload(
+        &pck_crl_processor,
+        &fixture("pck_crl_processor_issuer_chain.pem"),
+        &root_ca,
+        now,
+    )
+    .unwrap();
+    verify_crl_upload(
+        &pck_crl_platform,
+        &fixture("pck_crl_platform_issuer_chain.pem"),
+        &root_ca,
+        now,
+    )
+    .unwrap();
+    let info = verify_tcb_info(&tcb_info, &tcb_chain, &root_ca, &root_ca_crl, now).unwrap();
+    verify_qe_identity(&qe_identity, &tcb_chain, &root_ca, &root_ca_crl, now).unwrap();
+
+    let fmspc = Quote::parse(&quote).unwrap().fmspc().unwrap();
+    assert_eq!(info.fmspc, fmspc);
+
+    let pck_crls: [&[u8]; 2] = [&pck_crl_processor, &pck_crl_platform];
+    let collateral = Collateral {
+        root_ca: &root_ca,
+        root_ca_crl: &root_ca_crl,
+        pck_crls: &pck_crls,
+        tcb_info: &tcb_info,
+        tcb_info_chain: &tcb_chain,
+        qe_identity: &qe_identity,
+        qe_identity_chain: &tcb_chain,
+    };
+    let verified = verify_quote(&quote, &collateral, now).unwrap();
+    assert_eq!(verified.fmspc, fmspc);
+    assert_eq!(verified.report, Quote::parse(&quote).unwrap().report);
+    assert_ne!(verified.tcb_status, TcbStatus::Revoked);
+}
@@ -0,0 +1,180 @@
+//! 基于 `tests/fixtures` 测试 PKI 的端到端 Quote 验证
+//!
+//! 夹具由 `tests/fixtures/generate.py` 生成，抵押品有效期为
+//! 2025-05-01 ~ 2025-07-01。
+
+use tee_dcap::{
+    parse_time, verify_crl_upload, verify_qe_identity, verify_quote, verify_root_ca,
+    verify_tcb_info, Collateral, DcapError, Quote, TcbStatus,
+};
+
+const ROOT_CA: &[u8] = include_bytes!("fixtures/root_ca.der");
+const ROOT_CA_CRL: &[u8] = include_bytes!("fixtures/root_ca_crl.der");
+const PCK_CRL: &[u8] = include_bytes!("fixtures/pck_crl.der");
+const PCK_CRL_REVOKED: &[u8] = include_bytes!("fixtures/pck_crl_revoked.der");
+const PCK_CRL_ISSUER_CHAIN: &[u8] = include_bytes!("fixtures/pck_crl_issuer_chain.pem");
+const TCB_INFO: &[u8] = include_bytes!("fixtures/tcb_info.json");
+const QE_IDENTITY: &[u8] = include_bytes!("fixtures/qe_identity.json");
+const TCB_SIGNING_CHAIN: &[u8] = include_bytes!("fixtures/tcb_signing_chain.pem");
+const QUOTE_V3: &[u8] = include_bytes!("fixtures/quote_v3.bin");
+const QUOTE_V4: &[u8] = include_bytes!("fixtures/quote_v4.bin");
+const QUOTE_V3_OUTDATED: &[u8] = include_bytes!("fixtures/quote_v3_outdated.bin");
+const QUOTE_V3_CONFIG: &[u8] = include_bytes!("fixtures/quote_v3_config.bin");
+
+/// 抵押品有效期内的时间点
+fn now() -> u64 {
+    parse_time("2025-06-01T00:00:00Z").unwrap()
+}
+
+fn collateral<'a>(pck_crls: &'a [&'a [u8]]) -> Collateral<'a> {
+    Collateral {
+        root_ca: ROOT_CA,
+        root_ca_crl: ROOT_CA_CRL,
+        pck_crls,
+        tcb_info: TCB_INFO,
+        tcb_info_chain: TCB_SIGNING_CHAIN,
+        qe_identity: QE_IDENTITY,
+        qe_identity_chain: TCB_SIGNING_CHAIN,
+    }
+}
+
+#[test]
+fn verifies_v3_and_v4_quotes() {
+    for (quote, version) in [(QUOTE_V3, 3), (QUOTE_V4, 4)] {
+        let verified = verify_quote(quote, &collateral(&[PCK_CRL]), now()).unwrap();
+        assert_eq!(verified.header.version, version);
+        assert_eq!(verified.tcb_status, TcbStatus::UpToDate);
+        assert_eq!(verified.fmspc, [0x00, 0x90, 0x6E, 0xD5, 0x00, 0x00]);
+        assert_eq!(verified.report.mr_enclave, [0xAA; 32]);
+        assert_eq!(verified.report.mr_signer, [0xBB; 32]);
+        assert_eq!(verified.report.isv_prod_id, 1);
+        assert_eq!(verified.report.isv_svn, 3);
+    }
+}
+
+#[test]
+fn reads_fmspc_before_verification() {
+    let quote = Quote::parse(QUOTE_V4).unwrap();
+    assert_eq!(quote.fmspc().unwrap(), [0x00, 0x90, 0x6E, 0xD5, 0x00, 0x00]);
+}
+
+#[test]
+fn outdated_platform_reports_out_of_date() {
+    let verified = verify_quote(QUOTE_V3_OUTDATED, &collateral(&[PCK_CRL]), now()).unwrap();
+    assert_eq!(verified.tcb_status, TcbStatus::OutOfDate);
+    assert!(!verified.tcb_status.is_acceptable(true));
+}
+
+#[test]
+fn configuration_needed_platform_requires_opt_in() {
+    let verified = verify_quote(QUOTE_V3_CONFIG, &collateral(&[PCK_CRL]), now()).unwrap();
+    assert_eq!(verified.tcb_status, TcbStatus::ConfigurationNeeded);
+    assert!(!verified.tcb_status.is_acceptable(false));
+    assert!(verified.tcb_status.is_acceptable(true));
+}
+
+#[test]
+fn rejects_tampered_report_body() {
+    let mut quote = QUOTE_V3.to_vec();
+    // MRENCLAVE 首字节（Header 48 + 偏移 64）
+    quote[48 + 64] ^= 1;
+    assert_eq!(
+        verify_quote(&quote, &collateral(&[PCK_CRL]), now()).unwrap_err(),
+        DcapError::QuoteSignature
+    );
+}
+
+#[test]
+fn rejects_tampered_qe_report() {
+    let mut quote = QUOTE_V3.to_vec();
+    // QE 报告 ISVSVN（签名数据 = 长度(4) + 签名(64) + 公钥(64)）
+    quote[48 + 384 + 4 + 128 + 258] ^= 1;
+    assert_eq!(
+        verify_quote(&quote, &collateral(&[PCK_CRL]), now()).unwrap_err(),
+        DcapError::QeReportSignature
+    );
+}
+
+#[test]
+fn rejects_revoked_pck_certificate() {
+    assert_eq!(
+        verify_quote(QUOTE_V3, &collateral(&[PCK_CRL_REVOKED]), now()).unwrap_err(),
+        DcapError::CertificateRevoked
+    );
+}
+
+#[test]
+fn requires_pck_crl() {
+    assert_eq!(
+        verify_quote(QUOTE_V3, &collateral(&[]), now()).unwrap_err(),
+        DcapError::CrlMissing
+    );
+}
+
+#[test]
+fn rejects_expired_collateral() {
+    let later = parse_time("2025-07-02T00:00:00Z").unwrap();
+    assert_eq!(
+        verify_quote(QUOTE_V3, &collateral(&[PCK_CRL]), later).unwrap_err(),
+        DcapError::CrlExpired
+    );
+    assert_eq!(
+        verify_tcb_info(TCB_INFO, TCB_SIGNING_CHAIN, ROOT_CA, ROOT_CA_CRL, later).unwrap_err(),
+        DcapError::CrlExpired
+    );
+}
+
+#[test]
+fn rejects_untrusted_root() {
+    let other_root = {
+        // 将根证书的任意一个签名字节翻转，即与链上根证书不一致
+        let mut der = ROOT_CA.to_vec();
+        let last = der.len() - 1;
+        der[last] ^= 1;
+        der
+    };
+    let mut collateral = collateral(&[PCK_CRL]);
+    collateral.root_ca = &other_root;
+    assert!(verify_quote(QUOTE_V3, &collateral, now()).is_err());
+}
+
+#[test]
+fn rejects_tampered_tcb_info() {
+    let tampered =
+        String::from_utf8(TCB_INFO.to_vec())
+            .unwrap()
+            .replacen("\"OutOfDate\"", "\"UpToDate\"", 1);
+    assert_eq!(
+        verify_tcb_info(
+            tampered.as_bytes(),
+            TCB_SIGNING_CHAIN,
+            ROOT_CA,
+            ROOT_CA_CRL,
+            now()
+        )
+        .unwrap_err(),
+        DcapError::CollateralSignature
+    );
+}
+
+#[test]
+fn verifies_collateral_uploads() {
+    verify_root_ca(ROOT_CA, now()).unwrap();
+    verify_crl_upload(ROOT_CA_CRL, &[], ROOT_CA, now()).unwrap();
+    verify_crl_upload(PCK_CRL, PCK_CRL_ISSUER_CHAIN, ROOT_CA, now()).unwrap();
+    // PCK CRL 不是根 CA 签发的
+    assert_eq!(
+        verify_crl_upload(PCK_CRL, &[], ROOT_CA, now()).unwrap_err(),
+        DcapError::CrlSignature
+    );
+
+    let tcb_info =
+        verify_tcb_info(TCB_INFO, TCB_SIGNING_CHAIN, ROOT_CA, ROOT_CA_CRL, now()).unwrap();
+    assert_eq!(tcb_info.levels.len(), 3);
+    assert_eq!(tcb_info.evaluation_data_number, 17);
+
+    let qe_identity =
+        verify_qe_identity(QE_IDENTITY, TCB_SIGNING_CHAIN, ROOT_CA, ROOT_CA_CRL, now()).unwrap();
+    assert_eq!(qe_identity.isv_prod_id, 1);
+    assert_eq!(qe_identity.status_for(7).unwrap(), TcbStatus::OutOfDate);
+}
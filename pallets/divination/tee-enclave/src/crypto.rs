@@ -272,6 +272,17 @@ pub mod client {
         plaintext: &[u8],
         rng: &mut R,
     ) -> EnclaveResult<EncryptedRequest> {
+        encrypt_for_enclave_with_session(enclave_pubkey, plaintext, rng).map(|(request, _)| request)
+    }
+
+    /// 加密请求并返回会话密钥
+    ///
+    /// 会话密钥用于解密 Enclave 返回的加密响应（见 [`decrypt_response`]）
+    pub fn encrypt_for_enclave_with_session<R: RngCore + CryptoRng>(
+        enclave_pubkey: &[u8; 32],
+        plaintext: &[u8],
+        rng: &mut R,
+    ) -> EnclaveResult<(EncryptedRequest, [u8; KEY_SIZE])> {
         // 生成临时密钥对
         let ephemeral_secret = StaticSecret::random_from_rng(&mut *rng);
         let ephemeral_public = X25519PublicKey::from(&ephemeral_secret);
@@ -290,12 +301,26 @@ pub mod client {
         // 加密
         let (ciphertext, auth_tag) = aes_gcm_encrypt(&session_key, &nonce, plaintext, &[])?;
 
-        Ok(EncryptedRequest {
+        let request = EncryptedRequest {
             ciphertext,
             ephemeral_pubkey: ephemeral_public.to_bytes(),
             nonce,
             auth_tag,
-        })
+        };
+        Ok((request, session_key))
+    }
+
+    /// 计算加密请求的链上输入哈希
+    ///
+    /// `submit_compute_request` 中的 `input_hash` 即为此值：
+    /// SHA-256(临时公钥 || Nonce || 密文 || 认证标签)
+    pub fn request_input_hash(request: &EncryptedRequest) -> [u8; 32] {
+        sha256_hash_multi(&[
+            &request.ephemeral_pubkey,
+            &request.nonce,
+            &request.ciphertext,
+            &request.auth_tag,
+        ])
     }
 
     /// 解密来自 Enclave 的响应
@@ -482,4 +507,19 @@ mod tests {
         let result = aes_gcm_decrypt(&key, &nonce, &ciphertext, &auth_tag, &[]);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_request_input_hash_binds_all_fields() {
+        let mut rng = rand::thread_rng();
+        let request = client::encrypt_for_enclave(&[9u8; 32], b"input", &mut rng).unwrap();
+        let hash = client::request_input_hash(&request);
+
+        let mut tampered = request.clone();
+        tampered.auth_tag[0] ^= 0xFF;
+        assert_ne!(client::request_input_hash(&tampered), hash);
+
+        let mut tampered = request;
+        tampered.nonce[0] ^= 0xFF;
+        assert_ne!(client::request_input_hash(&tampered), hash);
+    }
 }
@@ -1,10 +1,15 @@
 //! # 八字命理计算模块
 //!
 //! 根据出生年月日时计算四柱八字
+//!
+//! 四柱排盘使用 `divination-algorithms`，与链上八字 Pallet 逐字节一致。
 
 #[cfg(not(feature = "std"))]
 use alloc::{string::{String, ToString}, vec, vec::Vec};
 
+use divination_algorithms::sizhu::{self, SiZhuIndex, ZiShiMode};
+use divination_algorithms::true_solar_time::{adjust_date, apply_true_solar_time};
+
 use crate::error::{EnclaveError, EnclaveResult};
 use crate::types::{BaZiInput, BaZiResult, FiveElementsCount, Gender, MajorCycle};
 
@@ -38,17 +43,15 @@ impl BaZiCalculator {
 
     /// 执行八字计算
     pub fn calculate(&self, input: &BaZiInput) -> EnclaveResult<BaZiResult> {
-        // 验证输入
-        self.validate_input(input)?;
-
-        // 计算四柱
-        let year_pillar = self.calculate_year_pillar(input.year);
-        let month_pillar = self.calculate_month_pillar(input.year, input.month);
-        let day_pillar = self.calculate_day_pillar(input.year, input.month, input.day);
-        let hour_pillar = self.calculate_hour_pillar(&day_pillar, input.hour);
+        // 计算四柱（含输入校验）
+        let index = self.sizhu_index(input)?;
+        let year_pillar = pillar_name(index.year_gan, index.year_zhi);
+        let month_pillar = pillar_name(index.month_gan, index.month_zhi);
+        let day_pillar = pillar_name(index.day_gan, index.day_zhi);
+        let hour_pillar = pillar_name(index.hour_gan, index.hour_zhi);
 
         // 提取日主
-        let day_master = year_pillar.chars().next().unwrap_or('甲').to_string();
+        let day_master = HEAVENLY_STEMS[index.day_gan as usize].to_string();
 
         // 计算五行统计
         let five_elements = self.calculate_five_elements(&year_pillar, &month_pillar, &day_pillar, &hour_pillar);
@@ -57,7 +60,7 @@ impl BaZiCalculator {
         let ten_gods = self.calculate_ten_gods(&day_pillar, &[&year_pillar, &month_pillar, &hour_pillar]);
 
         // 计算大运
-        let major_cycles = self.calculate_major_cycles(input.year, input.month, input.gender);
+        let major_cycles = self.calculate_major_cycles(&index, input.gender);
 
         // 生成摘要
         let summary = self.generate_summary(&day_master, &five_elements);
@@ -92,62 +95,28 @@ impl BaZiCalculator {
         Ok(())
     }
 
-    /// 计算年柱
-    fn calculate_year_pillar(&self, year: u16) -> String {
-        // 以立春为界，简化处理使用公历年
-        let stem_index = ((year as i32 - 4) % 10).abs() as usize;
-        let branch_index = ((year as i32 - 4) % 12).abs() as usize;
-
-        format!("{}{}", HEAVENLY_STEMS[stem_index], EARTHLY_BRANCHES[branch_index])
-    }
-
-    /// 计算月柱
-    fn calculate_month_pillar(&self, year: u16, month: u8) -> String {
-        // 简化算法：根据年干推月干
-        let year_stem = ((year as i32 - 4) % 10).abs() as usize;
-
-        // 月干 = (年干 * 2 + 月 - 1) % 10
-        let month_stem = (year_stem * 2 + month as usize) % 10;
-
-        // 月支：正月寅月
-        let month_branch = (month as usize + 1) % 12;
-
-        format!("{}{}", HEAVENLY_STEMS[month_stem], EARTHLY_BRANCHES[month_branch])
-    }
-
-    /// 计算日柱
-    fn calculate_day_pillar(&self, year: u16, month: u8, day: u8) -> String {
-        // 简化的日柱计算（使用公式近似）
-        let y = year as i32;
-        let m = month as i32;
-        let d = day as i32;
-
-        // 基于高斯公式的简化日柱计算
-        let century = y / 100;
-        let year_part = y % 100;
-
-        let days = (century / 4 - 2 * century + year_part + year_part / 4 + 13 * (m + 1) / 5 + d - 1) % 60;
-        let days = if days < 0 { days + 60 } else { days } as usize;
-
-        let stem_index = days % 10;
-        let branch_index = days % 12;
-
-        format!("{}{}", HEAVENLY_STEMS[stem_index], EARTHLY_BRANCHES[branch_index])
-    }
-
-    /// 计算时柱
-    fn calculate_hour_pillar(&self, day_pillar: &str, hour: u8) -> String {
-        // 根据日干推时干
-        let day_stem = day_pillar.chars().next().unwrap_or('甲');
-        let day_stem_index = HEAVENLY_STEMS.iter().position(|&s| s == day_stem.to_string()).unwrap_or(0);
-
-        // 时支：0-1点子时，2-3点丑时...
-        let hour_branch = (hour as usize + 1) / 2 % 12;
+    /// 计算链上四柱索引
+    ///
+    /// 与链上 `BaziModuleHandler::compute` 相同：默认现代派子时，
+    /// 提供经度时先做真太阳时修正（经度换算为 1/100000 度）。
+    pub fn sizhu_index(&self, input: &BaZiInput) -> EnclaveResult<SiZhuIndex> {
+        self.validate_input(input)?;
 
-        // 时干 = (日干 * 2 + 时支) % 10
-        let hour_stem = (day_stem_index * 2 + hour_branch) % 10;
+        let (year, month, day, hour) = match input.longitude {
+            Some(longitude) => {
+                let longitude = (longitude * 100_000.0) as i32;
+                let corrected = apply_true_solar_time(input.year, input.month, input.day, input.hour, 0, longitude);
+                let (year, month, day) = if corrected.day_offset != 0 {
+                    adjust_date(input.year, input.month, input.day, corrected.day_offset)
+                } else {
+                    (input.year, input.month, input.day)
+                };
+                (year, month, day, corrected.hour)
+            }
+            None => (input.year, input.month, input.day, input.hour),
+        };
 
-        format!("{}{}", HEAVENLY_STEMS[hour_stem], EARTHLY_BRANCHES[hour_branch])
+        sizhu::sizhu_index(year, month, day, hour, ZiShiMode::Modern).ok_or(EnclaveError::InvalidInputData)
     }
 
     /// 计算五行统计
@@ -212,31 +181,27 @@ impl BaZiCalculator {
     }
 
     /// 计算大运
-    fn calculate_major_cycles(&self, year: u16, month: u8, gender: Gender) -> Vec<MajorCycle> {
+    fn calculate_major_cycles(&self, index: &SiZhuIndex, gender: Gender) -> Vec<MajorCycle> {
         let mut cycles = Vec::new();
 
-        // 简化计算：根据性别和年干确定顺逆
-        let year_stem = ((year as i32 - 4) % 10).abs() as usize;
-        let is_yang = year_stem % 2 == 0;
+        // 根据性别和年干阴阳确定顺逆
+        let is_yang = index.year_gan % 2 == 0;
         let forward = matches!((&gender, is_yang), (Gender::Male, true) | (Gender::Female, false));
 
         // 起运年龄（简化为固定值）
         let start_age = 3u8;
 
-        // 生成10步大运
+        // 从月柱起排10步大运
         for i in 0..10 {
             let age = start_age + i * 10;
-            let month_stem = ((year_stem * 2 + month as usize) % 10) as i32;
-            let month_branch = ((month as usize + 1) % 12) as i32;
-
             let offset = if forward { i as i32 } else { -(i as i32) };
 
-            let stem_idx = ((month_stem + offset) % 10 + 10) % 10;
-            let branch_idx = ((month_branch + offset) % 12 + 12) % 12;
+            let stem_idx = (index.month_gan as i32 + offset).rem_euclid(10);
+            let branch_idx = (index.month_zhi as i32 + offset).rem_euclid(12);
 
             cycles.push(MajorCycle {
                 start_age: age,
-                gan_zhi: format!("{}{}", HEAVENLY_STEMS[stem_idx as usize], EARTHLY_BRANCHES[branch_idx as usize]),
+                gan_zhi: pillar_name(stem_idx as u8, branch_idx as u8),
             });
         }
 
@@ -265,6 +230,11 @@ impl BaZiCalculator {
     }
 }
 
+/// 干支索引转名称
+fn pillar_name(gan: u8, zhi: u8) -> String {
+    format!("{}{}", HEAVENLY_STEMS[gan as usize % 10], EARTHLY_BRANCHES[zhi as usize % 12])
+}
+
 impl Default for BaZiCalculator {
     fn default() -> Self {
         Self::new()
@@ -313,4 +283,23 @@ mod tests {
 
         assert!(calc.calculate(&input).is_err());
     }
+
+    #[test]
+    fn test_sizhu_index_matches_golden_vectors() {
+        use divination_algorithms::golden::BAZI_CASES;
+
+        let calc = BaZiCalculator::new();
+        for case in BAZI_CASES {
+            let input = BaZiInput {
+                year: case.year,
+                month: case.month,
+                day: case.day,
+                hour: case.hour,
+                gender: Gender::Male,
+                longitude: None,
+            };
+
+            assert_eq!(calc.sizhu_index(&input).unwrap().to_bytes(), case.expected);
+        }
+    }
 }
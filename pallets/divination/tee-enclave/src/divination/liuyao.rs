@@ -1,10 +1,14 @@
 //! # 六爻占卜计算模块
 //!
 //! 实现六爻（纳甲法）的排卦与解卦
+//!
+//! 本卦、变卦与八宫世应使用 `divination-algorithms`，与链上六爻 Pallet 逐字节一致。
 
 #[cfg(not(feature = "std"))]
 use alloc::{string::{String, ToString}, vec, vec::Vec};
 
+use divination_algorithms::liuyao::{self, LiuyaoIndex};
+
 use crate::error::{EnclaveError, EnclaveResult};
 use crate::types::{HexagramInfo, LineInfo, LiuYaoInput, LiuYaoResult};
 
@@ -42,28 +46,18 @@ impl LiuYaoCalculator {
 
     /// 执行六爻计算
     pub fn calculate(&self, input: &LiuYaoInput) -> EnclaveResult<LiuYaoResult> {
-        // 验证输入
-        for &yao in &input.yao_data {
-            if yao > 3 {
-                return Err(EnclaveError::InvalidInputData);
-            }
-        }
-
-        // 将六爻数据转换为卦象
-        let (original_gua, changed_gua, has_change) = self.yao_to_hexagram(&input.yao_data);
+        // 将六爻数据转换为卦象（含输入校验）
+        let index = self.liuyao_index(input)?;
 
         // 获取卦象信息
-        let original_hexagram = self.get_hexagram_info(original_gua);
+        let original_hexagram = self.get_hexagram_info(index.ben_shang, index.ben_xia);
 
-        let changed_hexagram = if has_change {
-            Some(self.get_hexagram_info(changed_gua))
+        let changed_hexagram = if index.dong_yao_mask != 0 {
+            Some(self.get_hexagram_info(index.bian_shang, index.bian_xia))
         } else {
             None
         };
 
-        // 计算世应位置
-        let (shi_position, ying_position) = self.calculate_shi_ying(original_gua);
-
         // 生成六爻详情
         let lines = self.generate_lines(&input.yao_data, input.timestamp);
 
@@ -74,56 +68,23 @@ impl LiuYaoCalculator {
             original_hexagram,
             changed_hexagram,
             lines,
-            shi_position,
-            ying_position,
+            shi_position: index.shi_yao,
+            ying_position: index.ying_yao,
             analysis,
         })
     }
 
-    /// 将六爻数据转换为卦象
-    fn yao_to_hexagram(&self, yao_data: &[u8; 6]) -> (u8, u8, bool) {
-        let mut original: u8 = 0;
-        let mut changed: u8 = 0;
-        let mut has_change = false;
-
-        for (i, &yao) in yao_data.iter().enumerate() {
-            // 原卦：老阴(0)和少阴(2)为阴，老阳(3)和少阳(1)为阳
-            if yao == 1 || yao == 3 {
-                original |= 1 << i;
-            }
-
-            // 变卦：老阴变阳，老阳变阴
-            match yao {
-                0 => {
-                    // 老阴变阳
-                    changed |= 1 << i;
-                    has_change = true;
-                }
-                1 => {
-                    // 少阳不变
-                    changed |= 1 << i;
-                }
-                2 => {
-                    // 少阴不变
-                }
-                3 => {
-                    // 老阳变阴
-                    has_change = true;
-                }
-                _ => {}
-            }
+    /// 计算链上六爻索引（本卦、变卦、动爻、世应、卦宫）
+    pub fn liuyao_index(&self, input: &LiuYaoInput) -> EnclaveResult<LiuyaoIndex> {
+        if input.yao_data.iter().any(|&yao| yao > 3) {
+            return Err(EnclaveError::InvalidInputData);
         }
 
-        (original, changed, has_change)
+        Ok(liuyao::liuyao_index(&input.yao_data))
     }
 
     /// 获取卦象信息
-    fn get_hexagram_info(&self, gua: u8) -> HexagramInfo {
-        // 分解为上下卦
-        let lower = gua & 0x07;
-        let upper = (gua >> 3) & 0x07;
-
-        // 先天八卦转后天八卦序
+    fn get_hexagram_info(&self, upper: u8, lower: u8) -> HexagramInfo {
         let hex_index = (upper as usize * 8 + lower as usize) % 64;
 
         HexagramInfo {
@@ -135,31 +96,6 @@ impl LiuYaoCalculator {
         }
     }
 
-    /// 计算世应位置
-    fn calculate_shi_ying(&self, gua: u8) -> (u8, u8) {
-        // 简化算法：根据卦象计算
-        let lower = gua & 0x07;
-        let upper = (gua >> 3) & 0x07;
-
-        // 如果上下卦相同（八纯卦）
-        if upper == lower {
-            return (6, 3);
-        }
-
-        // 简化：根据上下卦的异同确定世爻位置
-        let diff = (upper ^ lower).count_ones();
-        let shi = match diff {
-            1 => 1,
-            2 => 2,
-            3 => 3,
-            _ => 4,
-        };
-
-        let ying = if shi <= 3 { shi + 3 } else { shi - 3 };
-
-        (shi as u8, ying as u8)
-    }
-
     /// 生成六爻详情
     fn generate_lines(&self, yao_data: &[u8; 6], timestamp: u64) -> Vec<LineInfo> {
         let mut lines = Vec::with_capacity(6);
@@ -277,4 +213,19 @@ mod tests {
 
         assert!(calc.calculate(&input).is_err());
     }
+
+    #[test]
+    fn test_liuyao_index_matches_golden_vectors() {
+        use divination_algorithms::golden::LIUYAO_CASES;
+
+        let calc = LiuYaoCalculator::new();
+        for case in LIUYAO_CASES {
+            let input = LiuYaoInput {
+                yao_data: case.yao_values,
+                timestamp: 1704067200,
+            };
+
+            assert_eq!(calc.liuyao_index(&input).unwrap().to_bytes(), case.expected);
+        }
+    }
 }
@@ -1,10 +1,16 @@
 //! # 梅花易数计算模块
 //!
 //! 实现梅花易数的起卦与解卦算法
+//!
+//! 起卦、变卦与互卦使用 `divination-algorithms`，与链上梅花易数 Pallet 逐字节一致。
+//! 卦数采用先天八卦数 1-8，名称表按 `卦数 - 1` 取值。
 
 #[cfg(not(feature = "std"))]
 use alloc::{string::{String, ToString}, vec::Vec};
 
+use divination_algorithms::calendar::{hour_to_dizhi_num, timestamp_to_gregorian};
+use divination_algorithms::meihua::{self, MeihuaIndex};
+
 use crate::error::{EnclaveError, EnclaveResult};
 use crate::types::{HexagramInfo, MeiHuaInput, MeiHuaResult};
 
@@ -58,30 +64,27 @@ impl MeiHuaCalculator {
     /// 执行梅花易数计算
     pub fn calculate(&self, input: &MeiHuaInput) -> EnclaveResult<MeiHuaResult> {
         // 根据起卦方式计算上下卦和动爻
-        let (upper, lower, moving) = match input.method {
-            0 => self.time_method(input.timestamp)?,
-            1 => self.number_method(input.upper_num, input.lower_num, input.moving_line)?,
-            _ => return Err(EnclaveError::InvalidInputData),
-        };
+        let index = self.meihua_index(input)?;
+        let (upper, lower, moving) = (index.shang_gua, index.xia_gua, index.dong_yao);
 
         // 计算本卦
         let original = self.get_hexagram_info(upper, lower);
 
         // 计算变卦（动爻变化）
-        let (changed_upper, changed_lower) = self.get_changed_gua(upper, lower, moving);
+        let (changed_upper, changed_lower) = meihua::bian_gua(upper, lower, moving);
         let changed = self.get_hexagram_info(changed_upper, changed_lower);
 
         // 计算互卦
-        let (mutual_upper, mutual_lower) = self.get_mutual_gua(upper, lower);
+        let (mutual_upper, mutual_lower) = meihua::hu_gua(upper, lower);
         let mutual = self.get_hexagram_info(mutual_upper, mutual_lower);
 
         // 确定体用卦
         let (ti_gua, yong_gua) = if moving <= 3 {
             // 动爻在下卦，下卦为用，上卦为体
-            (BAGUA_NAMES[upper as usize].to_string(), BAGUA_NAMES[lower as usize].to_string())
+            (gua_name(upper).to_string(), gua_name(lower).to_string())
         } else {
             // 动爻在上卦，上卦为用，下卦为体
-            (BAGUA_NAMES[lower as usize].to_string(), BAGUA_NAMES[upper as usize].to_string())
+            (gua_name(lower).to_string(), gua_name(upper).to_string())
         };
 
         // 生成分析
@@ -98,77 +101,43 @@ impl MeiHuaCalculator {
         })
     }
 
-    /// 时间起卦法
-    fn time_method(&self, timestamp: u64) -> EnclaveResult<(u8, u8, u8)> {
-        // 从时间戳提取年月日时
-        // 简化处理：直接用时间戳的不同部分
-        let year = ((timestamp / 31536000) % 100) as u16; // 年
-        let month = ((timestamp / 2592000) % 12 + 1) as u8; // 月
-        let day = ((timestamp / 86400) % 31 + 1) as u8; // 日
-        let hour = ((timestamp / 3600) % 24) as u8; // 时
-
-        // 上卦 = (年+月+日) % 8
-        let upper = ((year as u32 + month as u32 + day as u32) % 8) as u8;
-
-        // 下卦 = (年+月+日+时) % 8
-        let lower = ((year as u32 + month as u32 + day as u32 + hour as u32) % 8) as u8;
-
-        // 动爻 = (年+月+日+时) % 6 + 1
-        let moving = (((year as u32 + month as u32 + day as u32 + hour as u32) % 6) + 1) as u8;
-
-        Ok((upper, lower, moving))
-    }
+    /// 计算链上梅花索引（上卦数、下卦数、动爻）
+    ///
+    /// - 时间起卦：北京时间公历年月日时
+    /// - 数字起卦：动爻默认取 (两数之和 + 起卦时辰数)，也可手动指定
+    pub fn meihua_index(&self, input: &MeiHuaInput) -> EnclaveResult<MeihuaIndex> {
+        let gua = match input.method {
+            0 => meihua::divine_by_timestamp(input.timestamp),
+            1 => {
+                let upper_num = input.upper_num.ok_or(EnclaveError::InvalidInputData)?;
+                let lower_num = input.lower_num.ok_or(EnclaveError::InvalidInputData)?;
+                let hour = timestamp_to_gregorian(input.timestamp).3;
+                let (upper, lower, moving) = meihua::divine_by_numbers(upper_num, lower_num, hour_to_dizhi_num(hour));
+
+                match input.moving_line {
+                    Some(line) => (upper, lower, meihua::calc_dong_yao(line as u32)),
+                    None => (upper, lower, moving),
+                }
+            }
+            _ => return Err(EnclaveError::InvalidInputData),
+        };
 
-    /// 数字起卦法
-    fn number_method(
-        &self,
-        upper_num: Option<u16>,
-        lower_num: Option<u16>,
-        moving_line: Option<u8>,
-    ) -> EnclaveResult<(u8, u8, u8)> {
-        let upper = upper_num.ok_or(EnclaveError::InvalidInputData)? % 8;
-        let lower = lower_num.ok_or(EnclaveError::InvalidInputData)? % 8;
-        let moving = moving_line.map(|m| (m - 1) % 6 + 1).unwrap_or(1);
-
-        Ok((upper as u8, lower as u8, moving))
+        Ok(MeihuaIndex::new(gua))
     }
 
-    /// 获取卦象信息
+    /// 获取卦象信息（上下卦为先天八卦数 1-8）
     fn get_hexagram_info(&self, upper: u8, lower: u8) -> HexagramInfo {
-        let hex_index = (upper as usize * 8 + lower as usize) % 64;
+        let hex_index = ((upper - 1) as usize * 8 + (lower - 1) as usize) % 64;
 
         HexagramInfo {
             name: HEXAGRAM_NAMES[hex_index].to_string(),
             number: (hex_index + 1) as u8,
-            upper: BAGUA_NAMES[upper as usize].to_string(),
-            lower: BAGUA_NAMES[lower as usize].to_string(),
+            upper: gua_name(upper).to_string(),
+            lower: gua_name(lower).to_string(),
             text: HEXAGRAM_TEXTS[hex_index].to_string(),
         }
     }
 
-    /// 计算变卦
-    fn get_changed_gua(&self, upper: u8, lower: u8, moving: u8) -> (u8, u8) {
-        if moving <= 3 {
-            // 动爻在下卦
-            let new_lower = lower ^ (1 << (moving - 1));
-            (upper, new_lower % 8)
-        } else {
-            // 动爻在上卦
-            let new_upper = upper ^ (1 << (moving - 4));
-            (new_upper % 8, lower)
-        }
-    }
-
-    /// 计算互卦
-    fn get_mutual_gua(&self, upper: u8, lower: u8) -> (u8, u8) {
-        // 互卦：取2、3、4爻为下卦，3、4、5爻为上卦
-        // 简化处理
-        let combined = (upper << 3) | lower;
-        let mutual_lower = (combined >> 1) & 0x07;
-        let mutual_upper = (combined >> 2) & 0x07;
-        (mutual_upper as u8, mutual_lower as u8)
-    }
-
     /// 生成分析
     fn generate_analysis(&self, ti_gua: &str, yong_gua: &str, hexagram_name: &str) -> String {
         let ti_element = BAGUA_NAMES.iter()
@@ -188,6 +157,11 @@ impl MeiHuaCalculator {
     }
 }
 
+/// 先天八卦数 (1-8) 转卦名
+fn gua_name(num: u8) -> &'static str {
+    BAGUA_NAMES[(num.clamp(1, 8) - 1) as usize]
+}
+
 impl Default for MeiHuaCalculator {
     fn default() -> Self {
         Self::new()
@@ -250,4 +224,31 @@ mod tests {
 
         assert!(calc.calculate(&input).is_err());
     }
+
+    #[test]
+    fn test_meihua_index_matches_golden_vectors() {
+        use divination_algorithms::golden::{MEIHUA_NUMBERS_CASES, MEIHUA_TIME_CASES};
+
+        let calc = MeiHuaCalculator::new();
+        for case in MEIHUA_TIME_CASES {
+            let input = MeiHuaInput {
+                method: 0,
+                upper_num: None,
+                lower_num: None,
+                moving_line: None,
+                timestamp: case.timestamp,
+            };
+            assert_eq!(calc.meihua_index(&input).unwrap().to_bytes(), case.expected);
+        }
+        for case in MEIHUA_NUMBERS_CASES {
+            let input = MeiHuaInput {
+                method: 1,
+                upper_num: Some(case.num1),
+                lower_num: Some(case.num2),
+                moving_line: None,
+                timestamp: case.timestamp,
+            };
+            assert_eq!(calc.meihua_index(&input).unwrap().to_bytes(), case.expected);
+        }
+    }
 }
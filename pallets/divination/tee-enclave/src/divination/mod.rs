@@ -76,38 +76,52 @@ impl DivinationEngine {
 
     /// 执行计算
     pub fn compute(&self, input: &ComputeInput) -> EnclaveResult<ComputeOutput> {
+        self.compute_indexed(input).map(|(output, _)| output)
+    }
+
+    /// 执行计算并返回链上索引
+    ///
+    /// 链上索引与对应 Pallet 的 `extract_index` 结果 SCALE 编码逐字节一致，
+    /// 链上可直接比对；大六壬、小六壬暂无链上索引，返回空字节。
+    pub fn compute_indexed(&self, input: &ComputeInput) -> EnclaveResult<(ComputeOutput, Vec<u8>)> {
         match input {
             ComputeInput::BaZi(params) => {
+                let index = self.bazi.sizhu_index(params)?;
                 let result = self.bazi.calculate(params)?;
-                Ok(ComputeOutput::BaZi(result))
+                Ok((ComputeOutput::BaZi(result), index.to_bytes().to_vec()))
             }
             ComputeInput::MeiHua(params) => {
+                let index = self.meihua.meihua_index(params)?;
                 let result = self.meihua.calculate(params)?;
-                Ok(ComputeOutput::MeiHua(result))
+                Ok((ComputeOutput::MeiHua(result), index.to_bytes().to_vec()))
             }
             ComputeInput::QiMen(params) => {
+                let index = self.qimen.sizhu_index(params)?;
                 let result = self.qimen.calculate(params)?;
-                Ok(ComputeOutput::QiMen(result))
+                Ok((ComputeOutput::QiMen(result), index.to_bytes().to_vec()))
             }
             ComputeInput::LiuYao(params) => {
+                let index = self.liuyao.liuyao_index(params)?;
                 let result = self.liuyao.calculate(params)?;
-                Ok(ComputeOutput::LiuYao(result))
+                Ok((ComputeOutput::LiuYao(result), index.to_bytes().to_vec()))
             }
             ComputeInput::ZiWei(params) => {
+                let index = self.ziwei.ziwei_index(params)?;
                 let result = self.ziwei.calculate(params)?;
-                Ok(ComputeOutput::ZiWei(result))
+                Ok((ComputeOutput::ZiWei(result), index.to_bytes().to_vec()))
             }
             ComputeInput::Tarot(params) => {
+                let index = self.tarot.tarot_index(params)?;
                 let result = self.tarot.calculate(params)?;
-                Ok(ComputeOutput::Tarot(result))
+                Ok((ComputeOutput::Tarot(result), index.to_bytes().to_vec()))
             }
             ComputeInput::DaLiuRen(params) => {
                 let result = self.daliuren.calculate(params)?;
-                Ok(ComputeOutput::DaLiuRen(result))
+                Ok((ComputeOutput::DaLiuRen(result), Vec::new()))
             }
             ComputeInput::XiaoLiuRen(params) => {
                 let result = self.xiaoliuren.calculate(params)?;
-                Ok(ComputeOutput::XiaoLiuRen(result))
+                Ok((ComputeOutput::XiaoLiuRen(result), Vec::new()))
             }
         }
     }
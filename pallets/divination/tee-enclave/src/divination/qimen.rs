@@ -1,20 +1,26 @@
 //! # 奇门遁甲计算模块
 //!
 //! 实现奇门遁甲的排盘算法
+//!
+//! 起局四柱与九宫排盘均使用 `divination-algorithms`，与链上 `QimenSiZhuIndex`
+//! 及 `generate_qimen_chart` 逐字节一致。
 
 #[cfg(not(feature = "std"))]
 use alloc::{string::{String, ToString}, vec, vec::Vec};
 
+use divination_algorithms::qimen::{self, QimenChart};
+use divination_algorithms::sizhu::{self, SiZhuIndex, ZiShiMode};
+
 use crate::error::{EnclaveError, EnclaveResult};
 use crate::types::{PalaceInfo, QiMenInput, QiMenResult};
 
-/// 九星
+/// 九星（编号 1-9）
 const NINE_STARS: [&str; 9] = ["天蓬", "天芮", "天冲", "天辅", "天禽", "天心", "天柱", "天任", "天英"];
 
-/// 八门
-const EIGHT_DOORS: [&str; 8] = ["休门", "生门", "伤门", "杜门", "景门", "死门", "惊门", "开门"];
+/// 八门（编号 1-8，与链上 `BaMen` 一致）
+const EIGHT_DOORS: [&str; 8] = ["休门", "死门", "伤门", "杜门", "景门", "开门", "惊门", "生门"];
 
-/// 八神
+/// 八神（编号 1-8）
 const EIGHT_DEITIES: [&str; 8] = ["值符", "腾蛇", "太阴", "六合", "白虎", "玄武", "九地", "九天"];
 
 /// 天干
@@ -31,14 +37,16 @@ impl QiMenCalculator {
 
     /// 执行奇门遁甲排盘
     pub fn calculate(&self, input: &QiMenInput) -> EnclaveResult<QiMenResult> {
-        // 从时间戳计算节气和局数
-        let (ju_number, yin_yang) = self.calculate_ju(input.datetime)?;
+        let chart = self.chart(input)?;
+
+        // 从节气和三元确定局数
+        let (ju_number, yin_yang) = self.calculate_ju(&chart);
 
         // 生成九宫数据
-        let palaces = self.generate_palaces(ju_number, input.datetime);
+        let palaces = self.generate_palaces(&chart);
 
         // 确定值符值使
-        let (duty_symbol, duty_door) = self.get_duty_info(input.datetime);
+        let (duty_symbol, duty_door) = self.get_duty_info(&chart);
 
         // 生成分析
         let analysis = self.generate_analysis(ju_number, &yin_yang, &duty_symbol, &duty_door);
@@ -53,71 +61,48 @@ impl QiMenCalculator {
         })
     }
 
-    /// 计算局数和阴阳遁
-    fn calculate_ju(&self, timestamp: u64) -> EnclaveResult<(i8, String)> {
-        // 简化算法：根据时间戳判断节气
-        // 冬至后阳遁，夏至后阴遁
-
-        // 以一年的天数计算大致位置
-        let day_of_year = (timestamp / 86400) % 365;
-
-        // 简化判断：前半年阳遁，后半年阴遁
-        let is_yang = day_of_year < 182;
-
-        // 局数：1-9
-        // 简化计算：根据时间戳取模
-        let ju = ((timestamp / 7200) % 9 + 1) as i8;
+    /// 计算起局时间的四柱索引（链上 `QimenSiZhuIndex`）
+    pub fn sizhu_index(&self, input: &QiMenInput) -> EnclaveResult<SiZhuIndex> {
+        sizhu::sizhu_index_from_timestamp(input.datetime, ZiShiMode::Modern).ok_or(EnclaveError::InvalidInputData)
+    }
 
-        // 阴遁局数为负
-        let ju_number = if is_yang { ju } else { -ju };
+    /// 排盘（与链上 `generate_qimen_chart` 同一算法）
+    pub fn chart(&self, input: &QiMenInput) -> EnclaveResult<QimenChart> {
+        qimen::qimen_chart_from_timestamp(input.datetime).ok_or(EnclaveError::InvalidInputData)
+    }
 
-        let yin_yang = if is_yang {
-            "阳遁".to_string()
+    /// 计算局数和阴阳遁（阴遁局数为负）
+    fn calculate_ju(&self, chart: &QimenChart) -> (i8, String) {
+        let ju = chart.ju_number as i8;
+        if chart.is_yang {
+            (ju, "阳遁".to_string())
         } else {
-            "阴遁".to_string()
-        };
-
-        Ok((ju_number, yin_yang))
+            (-ju, "阴遁".to_string())
+        }
     }
 
-    /// 生成九宫数据
-    fn generate_palaces(&self, ju_number: i8, timestamp: u64) -> Vec<PalaceInfo> {
-        let mut palaces = Vec::with_capacity(9);
-
-        // 基于局数和时间生成各宫数据
-        let base = ju_number.unsigned_abs() as usize;
-        let seed = (timestamp / 3600) as usize;
-
-        for i in 0..9 {
-            let star_idx = (base + i + seed) % 9;
-            let door_idx = (base + i + seed / 2) % 8;
-            let deity_idx = (base + i + seed / 3) % 8;
-            let earth_stem_idx = (base + i) % 10;
-            let heaven_stem_idx = (base + i + seed) % 10;
-
-            palaces.push(PalaceInfo {
+    /// 生成九宫数据（中宫无门无神）
+    fn generate_palaces(&self, chart: &QimenChart) -> Vec<PalaceInfo> {
+        chart
+            .palaces
+            .iter()
+            .enumerate()
+            .map(|(i, palace)| PalaceInfo {
                 position: (i + 1) as u8,
-                star: NINE_STARS[star_idx].to_string(),
-                door: EIGHT_DOORS[door_idx].to_string(),
-                deity: EIGHT_DEITIES[deity_idx].to_string(),
-                earth_stem: HEAVENLY_STEMS[earth_stem_idx].to_string(),
-                heaven_stem: HEAVENLY_STEMS[heaven_stem_idx].to_string(),
-            });
-        }
-
-        palaces
+                star: NINE_STARS[(palace.xing - 1) as usize].to_string(),
+                door: palace.men.map(|m| EIGHT_DOORS[(m - 1) as usize].to_string()).unwrap_or_default(),
+                deity: palace.shen.map(|s| EIGHT_DEITIES[(s - 1) as usize].to_string()).unwrap_or_default(),
+                earth_stem: HEAVENLY_STEMS[palace.di_gan as usize].to_string(),
+                heaven_stem: HEAVENLY_STEMS[palace.tian_gan as usize].to_string(),
+            })
+            .collect()
     }
 
     /// 获取值符值使
-    fn get_duty_info(&self, timestamp: u64) -> (String, String) {
-        let seed = (timestamp / 7200) as usize;
-
-        let symbol_idx = seed % 9;
-        let door_idx = seed % 8;
-
+    fn get_duty_info(&self, chart: &QimenChart) -> (String, String) {
         (
-            NINE_STARS[symbol_idx].to_string(),
-            EIGHT_DOORS[door_idx].to_string(),
+            NINE_STARS[(chart.zhi_fu_xing - 1) as usize].to_string(),
+            EIGHT_DOORS[(chart.zhi_shi_men - 1) as usize].to_string(),
         )
     }
 
@@ -182,4 +167,49 @@ mod tests {
             assert_eq!(palace.position, (i + 1) as u8);
         }
     }
+
+    #[test]
+    fn test_sizhu_index_matches_golden_vectors() {
+        use divination_algorithms::golden::QIMEN_CASES;
+
+        let calc = QiMenCalculator::new();
+        for case in QIMEN_CASES {
+            let input = QiMenInput {
+                datetime: case.timestamp,
+                pan_type: 0,
+                method: 0,
+            };
+
+            assert_eq!(calc.sizhu_index(&input).unwrap().to_bytes(), case.expected);
+        }
+    }
+
+    #[test]
+    fn test_chart_matches_golden_vectors() {
+        use divination_algorithms::golden::QIMEN_CHART_CASES;
+
+        let calc = QiMenCalculator::new();
+        for case in QIMEN_CHART_CASES {
+            let input = QiMenInput {
+                datetime: case.timestamp,
+                pan_type: 0,
+                method: 0,
+            };
+
+            let chart = calc.chart(&input).unwrap();
+            assert_eq!(chart.to_bytes(), case.expected);
+
+            // 展示结果与排盘一致
+            let result = calc.calculate(&input).unwrap();
+            assert_eq!(result.ju_number.unsigned_abs(), chart.ju_number);
+            assert_eq!(result.ju_number > 0, chart.is_yang);
+            assert_eq!(result.duty_symbol, NINE_STARS[(chart.zhi_fu_xing - 1) as usize]);
+            for (palace, info) in chart.palaces.iter().zip(result.palaces.iter()) {
+                assert_eq!(info.star, NINE_STARS[(palace.xing - 1) as usize]);
+                assert_eq!(info.earth_stem, HEAVENLY_STEMS[palace.di_gan as usize]);
+                assert_eq!(info.heaven_stem, HEAVENLY_STEMS[palace.tian_gan as usize]);
+            }
+            assert!(result.palaces[4].door.is_empty());
+        }
+    }
 }
@@ -1,10 +1,14 @@
 //! # 塔罗牌计算模块
 //!
 //! 实现塔罗牌的抽牌与解读算法
+//!
+//! 抽牌使用 `divination-algorithms` 的数字抽牌（78 张牌），与链上塔罗 Pallet 逐字节一致。
 
 #[cfg(not(feature = "std"))]
 use alloc::{string::{String, ToString}, vec, vec::Vec};
 
+use divination_algorithms::tarot::{self, TarotIndex};
+
 use crate::error::{EnclaveError, EnclaveResult};
 use crate::types::{TarotCard, TarotInput, TarotResult};
 
@@ -68,9 +72,36 @@ const MAJOR_MEANINGS_REVERSED: [&str; 22] = [
     "寻求结束、延迟、感觉不完整",
 ];
 
+/// 小阿卡纳花色（牌 ID 22 起，每花色 14 张）
+const MINOR_SUITS: [&str; 4] = ["权杖", "圣杯", "宝剑", "星币"];
+
+/// 小阿卡纳牌面
+const MINOR_RANKS: [&str; 14] = [
+    "Ace", "二", "三", "四", "五", "六", "七", "八", "九", "十", "侍从", "骑士", "王后", "国王",
+];
+
+/// 小阿卡纳花色正位含义
+const MINOR_MEANINGS_UPRIGHT: [&str; 4] = [
+    "行动、热情、创造力",
+    "情感、直觉、关系",
+    "思维、沟通、决断",
+    "物质、工作、稳定",
+];
+
+/// 小阿卡纳花色逆位含义
+const MINOR_MEANINGS_REVERSED: [&str; 4] = [
+    "冲动、倦怠、方向不明",
+    "情绪失衡、关系受阻",
+    "思虑过度、冲突、犹豫",
+    "财务压力、停滞、保守",
+];
+
 /// 牌阵类型
 const SPREAD_NAMES: [&str; 4] = ["单牌", "三牌阵", "凯尔特十字", "马蹄形"];
 
+/// 牌阵对应的链上牌阵类型（单张、三牌阵、凯尔特十字、七星阵）
+const CHAIN_SPREAD_TYPES: [u8; 4] = [0, 1, 3, 4];
+
 /// 三牌阵位置含义
 const THREE_CARD_POSITIONS: [&str; 3] = ["过去", "现在", "未来"];
 
@@ -90,17 +121,8 @@ impl TarotCalculator {
             return Err(EnclaveError::InvalidInputData);
         }
 
-        // 根据牌阵确定抽牌数量
-        let card_count = match input.spread {
-            0 => 1,  // 单牌
-            1 => 3,  // 三牌阵
-            2 => 10, // 凯尔特十字
-            3 => 7,  // 马蹄形
-            _ => 1,
-        };
-
-        // 使用种子生成伪随机序列
-        let cards = self.draw_cards(&input.seed, card_count, input.spread);
+        // 使用种子数字抽牌
+        let cards = self.draw_cards(input);
 
         // 生成综合解读
         let interpretation = self.generate_interpretation(&cards, input.spread);
@@ -112,71 +134,36 @@ impl TarotCalculator {
         })
     }
 
-    /// 抽取塔罗牌
-    fn draw_cards(&self, seed: &[u8; 32], count: usize, spread: u8) -> Vec<TarotCard> {
-        let mut cards = Vec::with_capacity(count);
-        let mut used_indices = Vec::new();
-
-        for i in 0..count {
-            // 使用种子的不同部分生成每张牌
-            let idx = self.next_card_index(seed, i, &used_indices);
-            used_indices.push(idx);
-
-            // 判断正逆位
-            let reversed = self.is_reversed(seed, i);
-
-            // 获取位置含义
-            let position_meaning = self.get_position_meaning(spread, i);
-
-            // 获取牌义
-            let meaning = if reversed {
-                MAJOR_MEANINGS_REVERSED[idx].to_string()
-            } else {
-                MAJOR_MEANINGS_UPRIGHT[idx].to_string()
-            };
-
-            cards.push(TarotCard {
-                name: MAJOR_ARCANA[idx].to_string(),
-                number: idx as u8,
-                reversed,
-                position_meaning,
-                meaning,
-            });
-        }
+    /// 计算链上塔罗索引（牌阵、张数、首张牌）
+    pub fn tarot_index(&self, input: &TarotInput) -> EnclaveResult<TarotIndex> {
+        let spread_type = *CHAIN_SPREAD_TYPES.get(input.spread as usize).ok_or(EnclaveError::InvalidInputData)?;
 
-        cards
+        Ok(tarot::tarot_index(spread_type, tarot::seed_to_number(&input.seed)))
     }
 
-    /// 生成下一张牌的索引
-    fn next_card_index(&self, seed: &[u8; 32], position: usize, used: &[usize]) -> usize {
-        let mut idx = 0;
-        let mut attempts = 0;
-
-        loop {
-            // 使用种子的不同字节组合生成索引
-            let seed_pos = (position * 4 + attempts) % 32;
-            let raw = (seed[seed_pos] as usize
-                + seed[(seed_pos + 1) % 32] as usize * 256
-                + position * 37
-                + attempts * 13)
-                % 22;
-
-            idx = raw;
-            attempts += 1;
-
-            // 确保不重复抽取
-            if !used.contains(&idx) || attempts > 100 {
-                break;
-            }
-        }
-
-        idx
-    }
-
-    /// 判断是否逆位
-    fn is_reversed(&self, seed: &[u8; 32], position: usize) -> bool {
-        let seed_pos = (position * 3 + 7) % 32;
-        seed[seed_pos] % 2 == 1
+    /// 抽取塔罗牌
+    fn draw_cards(&self, input: &TarotInput) -> Vec<TarotCard> {
+        let spread_type = CHAIN_SPREAD_TYPES[input.spread as usize];
+        let number = tarot::seed_to_number(&input.seed);
+        let count = tarot::get_spread_card_count(spread_type);
+
+        let ids = tarot::generate_cards_from_number(number, count);
+        let reversed = tarot::generate_reversed_from_number(number, count);
+
+        ids.iter()
+            .zip(reversed)
+            .enumerate()
+            .map(|(i, (&id, reversed))| {
+                let (name, meaning) = card_name_and_meaning(id, reversed);
+                TarotCard {
+                    name,
+                    number: id,
+                    reversed,
+                    position_meaning: self.get_position_meaning(input.spread, i),
+                    meaning,
+                }
+            })
+            .collect()
     }
 
     /// 获取位置含义
@@ -243,6 +230,22 @@ impl TarotCalculator {
     }
 }
 
+/// 牌 ID (0-77) 转牌名与牌义
+fn card_name_and_meaning(id: u8, reversed: bool) -> (String, String) {
+    let id = id as usize;
+    if id < MAJOR_ARCANA.len() {
+        let meaning = if reversed { MAJOR_MEANINGS_REVERSED[id] } else { MAJOR_MEANINGS_UPRIGHT[id] };
+        return (MAJOR_ARCANA[id].to_string(), meaning.to_string());
+    }
+
+    let minor = id - MAJOR_ARCANA.len();
+    let suit = (minor / MINOR_RANKS.len()) % MINOR_SUITS.len();
+    let rank = minor % MINOR_RANKS.len();
+    let meaning = if reversed { MINOR_MEANINGS_REVERSED[suit] } else { MINOR_MEANINGS_UPRIGHT[suit] };
+
+    (format!("{}{}", MINOR_SUITS[suit], MINOR_RANKS[rank]), meaning.to_string())
+}
+
 impl Default for TarotCalculator {
     fn default() -> Self {
         Self::new()
@@ -345,4 +348,23 @@ mod tests {
 
         assert!(calc.calculate(&input).is_err());
     }
+
+    #[test]
+    fn test_tarot_index_matches_golden_vectors() {
+        use divination_algorithms::golden::TAROT_CASES;
+
+        let calc = TarotCalculator::new();
+        for case in TAROT_CASES {
+            let spread = CHAIN_SPREAD_TYPES.iter().position(|t| *t == case.spread_type).unwrap() as u8;
+            let mut seed = [0u8; 32];
+            seed[..4].copy_from_slice(&case.number.to_le_bytes());
+
+            let input = TarotInput { spread, seed, timestamp: 1704067200 };
+            assert_eq!(calc.tarot_index(&input).unwrap().to_bytes(), case.expected);
+
+            // 展示结果的首张牌与链上索引一致
+            let result = calc.calculate(&input).unwrap();
+            assert_eq!(result.cards[0].number, case.expected[2]);
+        }
+    }
 }
@@ -1,10 +1,15 @@
 //! # 紫微斗数计算模块
 //!
 //! 实现紫微斗数的排盘算法
+//!
+//! 命宫、身宫、五行局与紫微/天府定位使用 `divination-algorithms`，
+//! 与链上紫微斗数 Pallet 逐字节一致。
 
 #[cfg(not(feature = "std"))]
 use alloc::{string::{String, ToString}, vec, vec::Vec};
 
+use divination_algorithms::ziwei::{self, ZiweiIndex};
+
 use crate::error::{EnclaveError, EnclaveResult};
 use crate::types::{Gender, ZiWeiInput, ZiWeiPalace, ZiWeiResult};
 
@@ -25,6 +30,14 @@ const MAIN_STARS: [&str; 14] = [
     "太阴", "贪狼", "巨门", "天相", "天梁", "七杀", "破军",
 ];
 
+/// 紫微星系（距紫微逆数宫位, 主星序号）
+const ZIWEI_SERIES: [(usize, usize); 6] = [(0, 0), (1, 1), (3, 2), (4, 3), (5, 4), (8, 5)];
+
+/// 天府星系（距天府顺数宫位, 主星序号）
+const TIANFU_SERIES: [(usize, usize); 8] = [
+    (0, 6), (1, 7), (2, 8), (3, 9), (4, 10), (5, 11), (6, 12), (10, 13),
+];
+
 /// 辅星
 const MINOR_STARS: [&str; 8] = [
     "文昌", "文曲", "左辅", "右弼", "天魁", "天钺", "禄存", "天马",
@@ -53,20 +66,16 @@ impl ZiWeiCalculator {
 
     /// 执行紫微斗数排盘
     pub fn calculate(&self, input: &ZiWeiInput) -> EnclaveResult<ZiWeiResult> {
-        // 验证输入
-        self.validate_input(input)?;
-
-        // 计算命宫位置
-        let ming_gong_idx = self.calculate_ming_gong(input.lunar_month, input.shi_chen);
-
-        // 计算身宫位置
-        let shen_gong_idx = self.calculate_shen_gong(input.lunar_month, input.shi_chen);
+        // 定盘（含输入校验）
+        let index = self.ziwei_index(input)?;
+        let ming_gong_idx = index.ming_gong as usize;
+        let shen_gong_idx = index.shen_gong as usize;
 
         // 排十二宫
-        let twelve_palaces = self.arrange_palaces(ming_gong_idx, input);
+        let twelve_palaces = self.arrange_palaces(&index, input);
 
-        // 计算紫微星位置，并排布主星
-        let main_stars = self.calculate_main_stars(input.lunar_day);
+        // 命宫主星
+        let main_stars = self.get_palace_main_stars(ming_gong_idx, &index);
 
         // 确定命主和身主
         let ming_zhu = MING_ZHU[input.lunar_year as usize % 12].to_string();
@@ -75,9 +84,12 @@ impl ZiWeiCalculator {
         // 生成分析
         let analysis = self.generate_analysis(&twelve_palaces[0], &ming_zhu, &shen_zhu);
 
+        // 身宫所落宫位（十二宫自命宫逆排）
+        let shen_palace = (ming_gong_idx + 12 - shen_gong_idx) % 12;
+
         Ok(ZiWeiResult {
             ming_gong: format!("{}{}", EARTHLY_BRANCHES[ming_gong_idx], TWELVE_PALACES[0]),
-            shen_gong: format!("{}{}", EARTHLY_BRANCHES[shen_gong_idx], TWELVE_PALACES[shen_gong_idx]),
+            shen_gong: format!("{}{}", EARTHLY_BRANCHES[shen_gong_idx], TWELVE_PALACES[shen_palace]),
             twelve_palaces,
             main_stars,
             ming_zhu,
@@ -86,6 +98,13 @@ impl ZiWeiCalculator {
         })
     }
 
+    /// 计算链上紫微索引（命宫、身宫、紫微、天府、五行局）
+    pub fn ziwei_index(&self, input: &ZiWeiInput) -> EnclaveResult<ZiweiIndex> {
+        self.validate_input(input)?;
+
+        Ok(ziwei::ziwei_index(input.lunar_year, input.lunar_month, input.lunar_day, input.shi_chen))
+    }
+
     /// 验证输入
     fn validate_input(&self, input: &ZiWeiInput) -> EnclaveResult<()> {
         if input.lunar_year < 1900 || input.lunar_year > 2100 {
@@ -103,36 +122,19 @@ impl ZiWeiCalculator {
         Ok(())
     }
 
-    /// 计算命宫位置
-    fn calculate_ming_gong(&self, month: u8, shi_chen: u8) -> usize {
-        // 命宫公式：寅宫起正月，顺数至生月，再由生月逆数至时辰
-        // 简化：(14 - month + 2 - shi_chen) % 12
-        let ming = (14 - month as i32 + 2 - shi_chen as i32) % 12;
-        if ming < 0 { (ming + 12) as usize } else { ming as usize }
-    }
-
-    /// 计算身宫位置
-    fn calculate_shen_gong(&self, month: u8, shi_chen: u8) -> usize {
-        // 身宫公式：寅宫起正月，顺数至生月，再由生月顺数至时辰
-        ((month as usize + 2 + shi_chen as usize - 2) % 12)
-    }
-
     /// 排十二宫
-    fn arrange_palaces(&self, ming_gong_idx: usize, input: &ZiWeiInput) -> Vec<ZiWeiPalace> {
+    fn arrange_palaces(&self, index: &ZiweiIndex, input: &ZiWeiInput) -> Vec<ZiWeiPalace> {
         let mut palaces = Vec::with_capacity(12);
 
-        // 计算紫微星位置用于排主星
-        let ziwei_pos = self.find_ziwei_position(input.lunar_day);
-
         for i in 0..12 {
-            let palace_idx = (ming_gong_idx + i) % 12;
-            let branch_idx = (2 + ming_gong_idx + i) % 12; // 从寅宫开始
+            // 十二宫自命宫起逆排
+            let branch_idx = (index.ming_gong as usize + 12 - i) % 12;
 
             // 计算该宫的主星
-            let main_stars = self.get_palace_main_stars(i, ziwei_pos);
+            let main_stars = self.get_palace_main_stars(branch_idx, index);
 
             // 计算辅星
-            let minor_stars = self.get_palace_minor_stars(i, input);
+            let minor_stars = self.get_palace_minor_stars(branch_idx, input);
 
             palaces.push(ZiWeiPalace {
                 name: TWELVE_PALACES[i].to_string(),
@@ -145,60 +147,24 @@ impl ZiWeiCalculator {
         palaces
     }
 
-    /// 查找紫微星位置
-    fn find_ziwei_position(&self, lunar_day: u8) -> usize {
-        // 简化的紫微星定位算法
-        // 实际算法需要根据五行局和出生日推算
-        ((lunar_day as usize + 2) % 12)
-    }
-
-    /// 计算主星列表
-    fn calculate_main_stars(&self, lunar_day: u8) -> Vec<String> {
-        // 返回命宫的主星列表
-        let ziwei_pos = self.find_ziwei_position(lunar_day);
-
-        // 简化：返回部分主星
-        let mut stars = Vec::new();
-        stars.push(MAIN_STARS[ziwei_pos % 14].to_string());
-
-        if ziwei_pos % 3 == 0 {
-            stars.push(MAIN_STARS[(ziwei_pos + 6) % 14].to_string());
-        }
-
-        stars
-    }
-
-    /// 获取某宫的主星
-    fn get_palace_main_stars(&self, palace_idx: usize, ziwei_pos: usize) -> Vec<String> {
+    /// 获取某宫（地支位置）的主星
+    fn get_palace_main_stars(&self, branch_idx: usize, index: &ZiweiIndex) -> Vec<String> {
         let mut stars = Vec::new();
 
-        // 简化的主星分布算法
-        // 紫微星在ziwei_pos，其他主星按固定规则分布
-        let relative_pos = (palace_idx + 12 - ziwei_pos) % 12;
-
-        // 紫微星
-        if relative_pos == 0 {
-            stars.push("紫微".to_string());
-        }
-
-        // 天府星（与紫微相对）
-        if relative_pos == 4 {
-            stars.push("天府".to_string());
+        // 紫微星系：紫微起逆排
+        let ziwei_offset = (index.ziwei_pos as usize + 12 - branch_idx) % 12;
+        for &(offset, star) in ZIWEI_SERIES.iter() {
+            if offset == ziwei_offset {
+                stars.push(MAIN_STARS[star].to_string());
+            }
         }
 
-        // 其他主星按简化规则
-        match relative_pos {
-            1 => stars.push("天机".to_string()),
-            2 => stars.push("太阳".to_string()),
-            3 => stars.push("武曲".to_string()),
-            5 => stars.push("太阴".to_string()),
-            6 => stars.push("贪狼".to_string()),
-            7 => stars.push("巨门".to_string()),
-            8 => stars.push("天相".to_string()),
-            9 => stars.push("天梁".to_string()),
-            10 => stars.push("七杀".to_string()),
-            11 => stars.push("破军".to_string()),
-            _ => {}
+        // 天府星系：天府起顺排
+        let tianfu_offset = (branch_idx + 12 - index.tianfu_pos as usize) % 12;
+        for &(offset, star) in TIANFU_SERIES.iter() {
+            if offset == tianfu_offset {
+                stars.push(MAIN_STARS[star].to_string());
+            }
         }
 
         stars
@@ -210,18 +176,18 @@ impl ZiWeiCalculator {
 
         // 文昌文曲
         if palace_idx == (10 - input.shi_chen as usize) % 12 {
-            stars.push("文昌".to_string());
+            stars.push(MINOR_STARS[0].to_string());
         }
         if palace_idx == (input.shi_chen as usize + 4) % 12 {
-            stars.push("文曲".to_string());
+            stars.push(MINOR_STARS[1].to_string());
         }
 
         // 左辅右弼
         if palace_idx == (input.lunar_month as usize + 3) % 12 {
-            stars.push("左辅".to_string());
+            stars.push(MINOR_STARS[2].to_string());
         }
         if palace_idx == (11 - input.lunar_month as usize) % 12 {
-            stars.push("右弼".to_string());
+            stars.push(MINOR_STARS[3].to_string());
         }
 
         stars
@@ -314,4 +280,23 @@ mod tests {
 
         assert!(calc.calculate(&input).is_err());
     }
+
+    #[test]
+    fn test_ziwei_index_matches_golden_vectors() {
+        use divination_algorithms::golden::ZIWEI_CASES;
+
+        let calc = ZiWeiCalculator::new();
+        for case in ZIWEI_CASES {
+            let input = ZiWeiInput {
+                lunar_year: case.lunar_year,
+                lunar_month: case.lunar_month,
+                lunar_day: case.lunar_day,
+                shi_chen: case.hour_zhi,
+                gender: Gender::Male,
+                is_leap_month: false,
+            };
+
+            assert_eq!(calc.ziwei_index(&input).unwrap().to_bytes(), case.expected);
+        }
+    }
 }
@@ -164,7 +164,7 @@ impl EnclaveRuntime {
         let input_hash = sha256_hash(&plaintext);
 
         // 6. 执行计算
-        let output = self.execute_computation(&request)?;
+        let (output, chain_index) = self.execute_computation(&request)?;
 
         // 7. 序列化输出
         let output_bytes = self.serialize_output(&output)?;
@@ -194,6 +194,7 @@ impl EnclaveRuntime {
             request_id: request.request_id,
             compute_type: request.compute_type,
             output,
+            chain_index,
             completed_at: timestamp,
         };
 
@@ -235,12 +236,15 @@ impl EnclaveRuntime {
     }
 
     /// 执行计算
-    fn execute_computation(&self, request: &ComputeRequest) -> EnclaveResult<ComputeOutput> {
+    fn execute_computation(
+        &self,
+        request: &ComputeRequest,
+    ) -> EnclaveResult<(ComputeOutput, Vec<u8>)> {
         // 验证输入与计算类型匹配
         self.validate_input_type(&request.compute_type, &request.input)?;
 
-        // 调用占卜引擎执行计算
-        self.divination_engine.compute(&request.input)
+        // 调用占卜引擎执行计算，同时生成链上索引
+        self.divination_engine.compute_indexed(&request.input)
     }
 
     /// 验证输入类型与计算类型匹配
@@ -296,7 +300,7 @@ impl EnclaveRuntime {
         let input_hash = sha256_hash(&request_bytes);
 
         // 执行计算
-        let output = self.execute_computation(request)?;
+        let (output, chain_index) = self.execute_computation(request)?;
 
         // 序列化输出计算哈希
         let output_bytes = self.serialize_output(&output)?;
@@ -311,6 +315,7 @@ impl EnclaveRuntime {
             request_id: request.request_id,
             compute_type: request.compute_type,
             output,
+            chain_index,
             completed_at: timestamp,
         };
 
@@ -459,6 +464,101 @@ mod tests {
         assert!(runtime.verify_proof(&proof, &keys.ed25519_pubkey).unwrap());
     }
 
+    #[test]
+    fn test_encrypted_request_chain_index_matches_golden_vectors() {
+        use crate::crypto::client;
+        use divination_algorithms::golden::{BAZI_CASES, LIUYAO_CASES, QIMEN_CASES, TAROT_CASES};
+
+        let mut rng = rand::thread_rng();
+        let mut runtime = EnclaveRuntime::new();
+        runtime.initialize(&mut rng).unwrap();
+        let keys = runtime.get_public_keys().unwrap();
+
+        let bazi = BAZI_CASES.iter().map(|case| {
+            (
+                ComputeTypeId::BaZi,
+                ComputeInput::BaZi(BaZiInput {
+                    year: case.year,
+                    month: case.month,
+                    day: case.day,
+                    hour: case.hour,
+                    gender: Gender::Male,
+                    longitude: None,
+                }),
+                case.expected.to_vec(),
+            )
+        });
+        let liuyao = LIUYAO_CASES.iter().map(|case| {
+            (
+                ComputeTypeId::LiuYao,
+                ComputeInput::LiuYao(LiuYaoInput {
+                    yao_data: case.yao_values,
+                    timestamp: 1704067200,
+                }),
+                case.expected.to_vec(),
+            )
+        });
+
+        let qimen = QIMEN_CASES.iter().map(|case| {
+            (
+                ComputeTypeId::QiMen,
+                ComputeInput::QiMen(QiMenInput {
+                    datetime: case.timestamp,
+                    pan_type: 0,
+                    method: 0,
+                }),
+                case.expected.to_vec(),
+            )
+        });
+        let tarot = TAROT_CASES.iter().map(|case| {
+            // 链上牌阵类型（单张、三牌阵、凯尔特十字、七星阵）→ Enclave 牌阵编号
+            let spread = match case.spread_type {
+                3 => 2,
+                4 => 3,
+                other => other,
+            };
+            let mut seed = [0u8; 32];
+            seed[..4].copy_from_slice(&case.number.to_le_bytes());
+            (
+                ComputeTypeId::Tarot,
+                ComputeInput::Tarot(TarotInput {
+                    spread,
+                    seed,
+                    timestamp: 1704067200,
+                }),
+                case.expected.to_vec(),
+            )
+        });
+
+        let cases = bazi.chain(liuyao).chain(qimen).chain(tarot);
+        for (request_id, (compute_type, input, expected)) in cases.enumerate() {
+            let request = ComputeRequest {
+                request_id: request_id as u64,
+                compute_type,
+                input,
+                timestamp: 1704067200,
+            };
+            let plaintext = serde_json::to_vec(&request).unwrap();
+            let (encrypted, session_key) =
+                client::encrypt_for_enclave_with_session(&keys.x25519_pubkey, &plaintext, &mut rng)
+                    .unwrap();
+
+            let processed = runtime
+                .process_encrypted_request(&encrypted, 1704067200, &mut rng)
+                .unwrap();
+
+            // 链上索引与 Pallet 黄金向量一致
+            assert_eq!(processed.response.chain_index, expected);
+
+            // 客户端可解密，且输出哈希与证明一致
+            assert_eq!(processed.session_key, session_key);
+            let output =
+                client::decrypt_response(&processed.encrypted_response, &session_key).unwrap();
+            assert_eq!(sha256_hash(&output), processed.proof.output_hash);
+            assert!(runtime.verify_proof(&processed.proof, &keys.ed25519_pubkey).unwrap());
+        }
+    }
+
     #[test]
     fn test_validate_input_type() {
         let mut rng = rand::thread_rng();
@@ -448,6 +448,8 @@ pub struct ComputeResponse {
     pub compute_type: ComputeTypeId,
     /// 计算输出
     pub output: ComputeOutput,
+    /// 链上索引（与 Pallet `extract_index` 的 SCALE 编码一致）
+    pub chain_index: Vec<u8>,
     /// 完成时间戳
     pub completed_at: u64,
 }
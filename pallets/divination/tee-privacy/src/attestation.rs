@@ -2,7 +2,7 @@
 //!
 //! 实现各种 TEE 平台的远程认证验证逻辑：
 //! - Intel SGX EPID 认证
-//! - Intel SGX DCAP 认证
+//! - Intel SGX DCAP 认证（完整 Quote 验证由 `tee-dcap` 实现）
 //! - ARM TrustZone 认证
 //! - AMD SEV 认证
 //!
@@ -373,7 +373,7 @@ impl Default for DcapQuoteVersion {
 }
 
 /// DCAP TCB 状态
-#[derive(Clone, Copy, Encode, Decode, TypeInfo, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, Encode, Decode, codec::DecodeWithMemTracking, TypeInfo, PartialEq, Eq, Debug)]
 pub enum TcbStatus {
     /// 最新
     UpToDate = 0,
@@ -515,16 +515,43 @@ impl DcapVerifier {
 
     /// 验证 DCAP ECDSA 签名
     fn verify_dcap_signature(attestation: &TeeAttestation) -> bool {
-        // DCAP 使用 ECDSA-256 签名
-        // 实际实现需要：
-        // 1. 解析 PCK 证书链
-        // 2. 验证证书链到 Intel 根证书
-        // 3. 验证 Quote 签名
+        // `TeeAttestation` 只携带 Quote 中提取出的字段，无法还原签名链；
+        // 完整的 PCK 证书链与 Quote 签名验证见 `verify_quote`
 
         // 简化验证
         !attestation.ias_signature.is_empty()
     }
 
+    /// 验证完整的 DCAP Quote（v3 / v4）
+    ///
+    /// 依次验证 PCK 证书链、CRL、TCB Info、QE Identity、QE 报告与 Quote 签名，
+    /// 返回解析后的报告与合并后的 TCB 状态。`now` 为 Unix 秒。
+    ///
+    /// TCB 状态与 MRENCLAVE 白名单的取舍由调用方决定。
+    pub fn verify_quote(
+        quote: &[u8],
+        collateral: &tee_dcap::Collateral<'_>,
+        now: u64,
+    ) -> Result<DcapReport, tee_dcap::DcapError> {
+        let verified = tee_dcap::verify_quote(quote, collateral, now)?;
+        let parsed = tee_dcap::Quote::parse(quote)?;
+
+        Ok(DcapReport {
+            version: if verified.header.version == 4 {
+                DcapQuoteVersion::V4
+            } else {
+                DcapQuoteVersion::V3
+            },
+            attestation_key_type: verified.header.att_key_type,
+            tee_type: verified.header.tee_type,
+            qe_vendor_id: verified.header.qe_vendor_id,
+            user_data: verified.header.user_data,
+            report_body: verified.report.into(),
+            tcb_status: verified.tcb_status.into(),
+            cert_data: parsed.pck_chain.to_vec(),
+        })
+    }
+
     /// 解析 TCB 状态
     pub fn parse_tcb_status(status: u8) -> TcbStatus {
         match status {
@@ -540,6 +567,54 @@ impl DcapVerifier {
     }
 }
 
+impl From<tee_dcap::TcbStatus> for TcbStatus {
+    fn from(status: tee_dcap::TcbStatus) -> Self {
+        match status {
+            tee_dcap::TcbStatus::UpToDate => TcbStatus::UpToDate,
+            tee_dcap::TcbStatus::SwHardeningNeeded => TcbStatus::SwHardeningNeeded,
+            tee_dcap::TcbStatus::ConfigurationNeeded => TcbStatus::ConfigurationNeeded,
+            tee_dcap::TcbStatus::ConfigurationAndSwHardeningNeeded => {
+                TcbStatus::ConfigurationAndSwHardeningNeeded
+            }
+            tee_dcap::TcbStatus::OutOfDate => TcbStatus::OutOfDate,
+            tee_dcap::TcbStatus::OutOfDateConfigurationNeeded => {
+                TcbStatus::OutOfDateConfigurationNeeded
+            }
+            tee_dcap::TcbStatus::Revoked => TcbStatus::Revoked,
+        }
+    }
+}
+
+impl TcbStatus {
+    /// 是否可接受（平台已打齐安全补丁，仅需软件加固）
+    ///
+    /// 需要配置调整的状态仅在 `accept_configuration_needed` 为真时接受
+    pub fn is_acceptable(&self, accept_configuration_needed: bool) -> bool {
+        match self {
+            TcbStatus::UpToDate | TcbStatus::SwHardeningNeeded => true,
+            TcbStatus::ConfigurationNeeded | TcbStatus::ConfigurationAndSwHardeningNeeded => {
+                accept_configuration_needed
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<tee_dcap::EnclaveReport> for SgxReportBody {
+    fn from(report: tee_dcap::EnclaveReport) -> Self {
+        Self {
+            cpu_svn: report.cpu_svn,
+            misc_select: report.misc_select,
+            attributes: report.attributes,
+            mr_enclave: report.mr_enclave,
+            mr_signer: report.mr_signer,
+            isv_prod_id: report.isv_prod_id,
+            isv_svn: report.isv_svn,
+            report_data: report.report_data,
+        }
+    }
+}
+
 // ============================================================================
 // ARM TrustZone 认证
 // ============================================================================
@@ -1015,6 +1090,57 @@ mod tests {
         assert!(result.is_valid);
     }
 
+    #[test]
+    fn test_dcap_quote_verification() {
+        let fixtures = |name: &str| -> Vec<u8> {
+            std::fs::read(
+                std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join("../tee-dcap/tests/fixtures")
+                    .join(name),
+            )
+            .unwrap()
+        };
+        let (root_ca, root_crl, pck_crl) = (
+            fixtures("root_ca.der"),
+            fixtures("root_ca_crl.der"),
+            fixtures("pck_crl.der"),
+        );
+        let (tcb_info, qe_identity, chain) = (
+            fixtures("tcb_info.json"),
+            fixtures("qe_identity.json"),
+            fixtures("tcb_signing_chain.pem"),
+        );
+        let pck_crls = [pck_crl.as_slice()];
+        let collateral = tee_dcap::Collateral {
+            root_ca: &root_ca,
+            root_ca_crl: &root_crl,
+            pck_crls: &pck_crls,
+            tcb_info: &tcb_info,
+            tcb_info_chain: &chain,
+            qe_identity: &qe_identity,
+            qe_identity_chain: &chain,
+        };
+        // 2025-06-01，测试抵押品有效期内
+        let now = 1_748_736_000;
+
+        let report = DcapVerifier::verify_quote(&fixtures("quote_v4.bin"), &collateral, now).unwrap();
+        assert_eq!(report.version, DcapQuoteVersion::V4);
+        assert_eq!(report.tcb_status, TcbStatus::UpToDate);
+        assert_eq!(report.report_body.mr_enclave, [0xAA; 32]);
+
+        let report =
+            DcapVerifier::verify_quote(&fixtures("quote_v3_outdated.bin"), &collateral, now).unwrap();
+        assert_eq!(report.tcb_status, TcbStatus::OutOfDate);
+        assert!(!report.tcb_status.is_acceptable(true));
+
+        let mut tampered = fixtures("quote_v3.bin");
+        tampered[48 + 64] ^= 1;
+        assert_eq!(
+            DcapVerifier::verify_quote(&tampered, &collateral, now).unwrap_err(),
+            tee_dcap::DcapError::QuoteSignature
+        );
+    }
+
     #[test]
     fn test_trustzone_verification() {
         let attestation = create_test_attestation(TeeType::ArmTrustZone);
@@ -11,7 +11,7 @@ use frame_system::RawOrigin;
 use sp_std::vec;
 
 use crate::types::{
-    BatchRequestItem, BatchResultItem, TeeAttestation, TeeNodeStatus, TeeType,
+    BatchRequestItem, BatchResultItem, TeeAttestation, TeeNodeStatus, TeeType, MAX_REPLICAS,
 };
 
 // 辅助函数：创建测试账户
@@ -320,6 +320,57 @@ mod benchmarks {
         assert!(!NodeStakes::<T>::contains_key(&node));
     }
 
+    // ==================== 冗余执行 ====================
+
+    #[benchmark]
+    fn submit_redundant_compute_request(n: Linear<2, 5>) {
+        for seed in 0..n {
+            let _ = register_test_node::<T>(seed as u8);
+        }
+        let requester = funded_account::<T>("requester", 0);
+
+        #[extrinsic_call]
+        submit_redundant_compute_request(
+            RawOrigin::Signed(requester.clone()),
+            0u8,
+            [0u8; 32],
+            n as u8,
+            (n / 2 + 1) as u8,
+        );
+
+        assert!(RequestReplicas::<T>::contains_key(0u64));
+    }
+
+    #[benchmark]
+    fn assign_replicas(n: Linear<1, 10>) {
+        for seed in 0..MAX_REPLICAS {
+            let _ = register_test_node::<T>(seed as u8);
+        }
+        let requester = funded_account::<T>("requester", 0);
+        let quorum = (MAX_REPLICAS / 2 + 1) as u8;
+        for _ in 0..n {
+            Pallet::<T>::submit_redundant_compute_request(
+                RawOrigin::Signed(requester.clone()).into(),
+                0u8,
+                [0u8; 32],
+                MAX_REPLICAS as u8,
+                quorum,
+            )
+            .expect("Redundant request should succeed");
+        }
+
+        // 随机数须晚于提交区块
+        let next_block = frame_system::Pallet::<T>::block_number() + 1u32.into();
+        frame_system::Pallet::<T>::set_block_number(next_block);
+
+        #[block]
+        {
+            Pallet::<T>::assign_queued_replicas();
+        }
+
+        assert!(ReplicaSelectionQueue::<T>::get().is_empty());
+    }
+
     // ==================== 超时处理 ====================
 
     #[benchmark]
@@ -98,8 +98,14 @@ mod integration_tests {
             assert_eq!(request.requester, user_account);
             assert_eq!(request.compute_type_id, compute_type_id);
             assert_eq!(request.input_hash, input_hash);
-            // 请求状态可能是 Pending 或 Processing，取决于是否有节点被分配
-            assert!(request.status == RequestStatus::Pending || request.status == RequestStatus::Processing);
+            // 提交区块内尚未分配节点
+            assert_eq!(request.status, RequestStatus::Pending);
+
+            // 下一区块抽取节点
+            run_to_block(2);
+            let request = ComputeRequests::<Test>::get(0).expect("Request should exist");
+            assert_eq!(request.status, RequestStatus::Processing);
+            assert_eq!(request.assigned_node, Some(node_account));
 
             // 3. TEE 节点提交计算结果
             let output_hash = [0xCD; 32];
@@ -163,6 +169,9 @@ mod integration_tests {
             assert!(ComputeRequests::<Test>::contains_key(1));
             assert!(ComputeRequests::<Test>::contains_key(2));
 
+            // 下一区块为每个请求抽取节点
+            run_to_block(2);
+
             // 3. 批量提交结果
             let batch_results = vec![
                 BatchResultItem {
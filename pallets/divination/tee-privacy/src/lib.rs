@@ -52,7 +52,17 @@
 //! )?;
 //! ```
 //!
-//! ### 2. 提交计算请求
+//! ### 2. 使用 DCAP Quote 注册
+//! ```ignore
+//! // 治理先上传根证书、CRL、TCB Info 与 QE Identity（set_dcap_*）
+//! TeePrivacy::register_tee_node_with_quote(
+//!     origin,
+//!     enclave_pubkey,
+//!     quote, // ECDSA Quote v3 / v4
+//! )?;
+//! ```
+//!
+//! ### 3. 提交计算请求
 //! ```ignore
 //! TeePrivacy::submit_compute_request(
 //!     origin,
@@ -61,6 +71,29 @@
 //!     None, // 自动分配节点
 //! )?;
 //! ```
+//!
+//! ### 4. 冗余执行（k-of-n）
+//! ```ignore
+//! TeePrivacy::submit_redundant_compute_request(
+//!     origin,
+//!     compute_type,
+//!     encrypted_input,
+//!     3, // 分配 3 个节点
+//!     2, // 2 个一致结果即完成，不一致的节点被惩罚
+//! )?;
+//! ```
+//!
+//! ## 节点选择
+//!
+//! 自动分配按质押、认证新鲜度和历史成功率加权，随机数来自 `Config::Randomness`，
+//! 详见 `Pallet::node_selection_weight`。
+//!
+//! ## DCAP 认证
+//!
+//! 治理通过 `set_dcap_*` 维护 Intel PCS 抵押品（根证书、CRL、按 FMSPC 存储的
+//! TCB Info、QE Identity），上传时即验证签名链。Quote 由 `tee-dcap` 按抵押品完整
+//! 验证，拒绝 `OutOfDate` / `Revoked` 等需要平台更新的 TCB 状态。根证书设置后，
+//! `register_tee_node` / `update_attestation` 不再接受 DCAP 类型的认证报告。
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -84,11 +117,13 @@ mod integration_tests;
 mod benchmarking;
 
 use frame_support::pallet_prelude::*;
-use frame_support::traits::{Currency, ReservableCurrency, ConstU32};
+use frame_support::traits::{Currency, ReservableCurrency, ConstU32, Randomness, UnixTime};
 use frame_system::pallet_prelude::*;
-use sp_runtime::traits::{Saturating, Zero};
+use sp_runtime::traits::{Saturating, TrailingZeroInput, Zero};
+use sp_runtime::SaturatedConversion;
 use sp_std::vec::Vec;
 
+use crate::attestation::{DcapVerifier, TcbStatus};
 use crate::types::*;
 use crate::weights::WeightInfo;
 
@@ -104,6 +139,18 @@ pub const DEFAULT_REQUEST_TIMEOUT_BLOCKS: u32 = 100;
 /// 最大故障转移次数
 pub const MAX_FAILOVER_COUNT: u8 = 3;
 
+/// 认证报告最长有效时间（与 `pallet_timestamp` 同单位，默认 24 小时）
+pub const ATTESTATION_MAX_AGE: u64 = 86400;
+
+/// 节点选择随机数的 subject 前缀
+pub const NODE_SELECTION_SUBJECT: &[u8] = b"tee-privacy/select";
+
+/// 质押权重上限（质押额为最小质押的倍数，超过部分不再增加权重）
+pub const MAX_STAKE_WEIGHT: u64 = 10;
+
+/// 成功率权重下限（千分比），避免历史失败的节点永远无法被选中
+pub const MIN_SUCCESS_WEIGHT: u64 = 50;
+
 /// 货币类型别名
 pub type BalanceOf<T> =
     <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
@@ -164,6 +211,21 @@ pub mod pallet {
         #[pallet::constant]
         type MaxBatchSize: Get<u32>;
 
+        /// 是否接受 TCB 状态为 `ConfigurationNeeded` 的 DCAP 平台
+        ///
+        /// 此类平台的 BIOS / 硬件配置存在已知风险，默认应为 false
+        #[pallet::constant]
+        type AcceptConfigurationNeeded: Get<bool>;
+
+        /// 节点选择随机源
+        type Randomness: Randomness<Self::Hash, BlockNumberFor<Self>>;
+
+        /// Unix 时间（DCAP 证书与抵押品有效期按 Unix 秒校验）
+        type UnixTime: UnixTime;
+
+        /// 统一公钥目录（运营者发布了 TeeEnclave 公钥时，注册须与之一致）
+        type KeyDirectory: pallet_key_directory::traits::KeyDirectory<Self::AccountId>;
+
         /// 权重信息
         type WeightInfo: WeightInfo;
     }
@@ -182,6 +244,14 @@ pub mod pallet {
     pub type TeeNodes<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AccountId, TeeNode<T::AccountId>>;
 
+    /// 注册公钥已在统一公钥目录中吊销的节点
+    ///
+    /// 节点被暂停且不能恢复，运营者需注销后用新 Enclave 公钥重新注册。
+    #[pallet::storage]
+    #[pallet::getter(fn enclave_key_revoked)]
+    pub type EnclaveKeyRevoked<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
     /// 活跃节点列表
     ///
     /// 存储所有活跃状态的 TEE 节点账户
@@ -213,6 +283,36 @@ pub mod pallet {
     pub type AllowedMrSigners<T: Config> =
         StorageValue<_, BoundedVec<[u8; 32], T::MaxAllowedMrEnclaves>, ValueQuery>;
 
+    // -------------------- DCAP 抵押品 --------------------
+
+    /// Intel SGX Root CA 证书（DER）
+    ///
+    /// 设置后 DCAP 节点必须通过 Quote 注册，不再接受仅含字段的认证报告
+    #[pallet::storage]
+    #[pallet::getter(fn dcap_root_ca)]
+    pub type DcapRootCa<T: Config> = StorageValue<_, DcapCollateralBytes>;
+
+    /// DCAP CRL
+    ///
+    /// DcapCrlKind -> (CRL, 签发证书链)
+    #[pallet::storage]
+    #[pallet::getter(fn dcap_crls)]
+    pub type DcapCrls<T: Config> =
+        StorageMap<_, Twox64Concat, DcapCrlKind, DcapSignedCollateral>;
+
+    /// TCB Info
+    ///
+    /// FMSPC -> (TCB Info JSON, 签名证书链)
+    #[pallet::storage]
+    #[pallet::getter(fn dcap_tcb_infos)]
+    pub type DcapTcbInfos<T: Config> =
+        StorageMap<_, Twox64Concat, [u8; 6], DcapSignedCollateral>;
+
+    /// QE Identity（QE Identity JSON, 签名证书链）
+    #[pallet::storage]
+    #[pallet::getter(fn dcap_qe_identity)]
+    pub type DcapQeIdentity<T: Config> = StorageValue<_, DcapSignedCollateral>;
+
     // -------------------- 计算请求管理 --------------------
 
     /// 请求 ID 计数器
@@ -255,6 +355,41 @@ pub mod pallet {
     #[pallet::getter(fn node_current_request)]
     pub type NodeCurrentRequest<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u64>;
 
+    /// 冗余执行请求的副本集合
+    ///
+    /// request_id -> ReplicaSet（请求完成或失败后移除）
+    #[pallet::storage]
+    #[pallet::getter(fn request_replicas)]
+    pub type RequestReplicas<T: Config> =
+        StorageMap<_, Blake2_128Concat, u64, ReplicaSet<T::AccountId>>;
+
+    /// 等待分配节点的请求 (request_id, 副本数)
+    ///
+    /// 冗余请求与未指定节点的普通请求（副本数 1）都在此排队，
+    /// 节点在提交区块之后的 on_initialize 中抽取，
+    /// 随机数在提交交易时尚不可知，请求者无法挑选提交时机操控分配结果
+    #[pallet::storage]
+    #[pallet::getter(fn replica_selection_queue)]
+    pub type ReplicaSelectionQueue<T: Config> =
+        StorageValue<_, BoundedVec<(u64, u8), T::MaxPendingRequests>, ValueQuery>;
+
+    /// 冗余请求达成一致后的迟到结果核对
+    ///
+    /// request_id -> LateReplicaCheck
+    #[pallet::storage]
+    #[pallet::getter(fn late_replica_checks)]
+    pub type LateReplicaChecks<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u64,
+        LateReplicaCheck<T::AccountId, BlockNumberFor<T>>,
+    >;
+
+    /// 迟到结果核对记录列表（用于到期清理）
+    #[pallet::storage]
+    pub type LateReplicaQueue<T: Config> =
+        StorageValue<_, BoundedVec<u64, T::MaxPendingRequests>, ValueQuery>;
+
     // -------------------- 经济激励 --------------------
 
     /// 节点质押信息
@@ -419,6 +554,35 @@ pub mod pallet {
             reason: FailureReason,
         },
 
+        /// 冗余执行节点已分配
+        ReplicasAssigned {
+            request_id: u64,
+            nodes: Vec<T::AccountId>,
+            quorum: u8,
+        },
+
+        /// 冗余执行的单个副本结果已提交
+        ReplicaResultSubmitted {
+            request_id: u64,
+            executor: T::AccountId,
+            output_hash: [u8; 32],
+        },
+
+        /// 冗余执行达成一致
+        ReplicaConsensusReached {
+            request_id: u64,
+            output_hash: [u8; 32],
+            dissenters: Vec<T::AccountId>,
+        },
+
+        /// 迟到的副本结果与最终一致结果不符，节点已被惩罚
+        LateReplicaResultMismatch {
+            request_id: u64,
+            executor: T::AccountId,
+            output_hash: [u8; 32],
+            agreed_hash: [u8; 32],
+        },
+
         // -------------------- 经济激励事件 --------------------
 
         /// 节点已质押
@@ -467,6 +631,30 @@ pub mod pallet {
         /// 允许的 MRSIGNER 已移除
         MrSignerDisallowed { mr_signer: [u8; 32] },
 
+        // -------------------- DCAP 事件 --------------------
+
+        /// DCAP 根证书已更新（fingerprint 为证书 SHA-256）
+        DcapRootCaUpdated { fingerprint: [u8; 32] },
+
+        /// DCAP CRL 已更新
+        DcapCrlUpdated { kind: DcapCrlKind },
+
+        /// TCB Info 已更新
+        DcapTcbInfoUpdated {
+            fmspc: [u8; 6],
+            evaluation_data_number: u32,
+        },
+
+        /// QE Identity 已更新
+        DcapQeIdentityUpdated,
+
+        /// DCAP Quote 验证通过（注册或更新认证时）
+        DcapQuoteVerified {
+            account: T::AccountId,
+            fmspc: [u8; 6],
+            tcb_status: TcbStatus,
+        },
+
         // -------------------- 批处理事件 --------------------
 
         /// 批量计算请求已提交
@@ -487,6 +675,14 @@ pub mod pallet {
 
         /// 审计日志状态已更改
         AuditStatusChanged { enabled: bool },
+
+        // -------------------- 公钥目录事件 --------------------
+
+        /// 运营者在公钥目录中轮换了 Enclave 公钥，节点需在重叠窗口内用新公钥重新注册
+        EnclaveKeyRotationRequired {
+            account: T::AccountId,
+            new_version: u32,
+        },
     }
 
     // ========================================================================
@@ -535,6 +731,35 @@ pub mod pallet {
         /// 认证时间戳无效
         InvalidAttestationTimestamp,
 
+        // -------------------- DCAP 错误 --------------------
+
+        /// 缺少 DCAP 抵押品（根证书、CRL、对应 FMSPC 的 TCB Info 或 QE Identity）
+        DcapCollateralMissing,
+
+        /// DCAP 抵押品验证失败（签名、证书链或格式无效）
+        InvalidDcapCollateral,
+
+        /// DCAP 抵押品已过期，需治理更新
+        DcapCollateralExpired,
+
+        /// Quote 格式无效或版本不受支持
+        InvalidQuote,
+
+        /// Quote 签名或证书链验证失败
+        QuoteVerificationFailed,
+
+        /// 平台 TCB 状态不可接受（过时、已吊销，或未开启 `AcceptConfigurationNeeded` 时需要配置调整）
+        TcbStatusRejected,
+
+        /// 已配置 DCAP 根证书，DCAP 节点必须提交 Quote
+        DcapQuoteRequired,
+
+        /// Quote 的报告数据未绑定 Enclave 公钥
+        ReportDataMismatch,
+
+        /// Enclave 公钥与统一公钥目录中发布的不一致
+        EnclaveKeyMismatch,
+
         // -------------------- 配置错误 --------------------
 
         /// 允许列表已满
@@ -578,6 +803,15 @@ pub mod pallet {
         /// 请求超时
         RequestTimeout,
 
+        /// 冗余参数无效（需 2 <= n <= MAX_REPLICAS 且 n/2 < k <= n）
+        InvalidRedundancy,
+
+        /// 可用节点不足以满足冗余要求
+        InsufficientNodes,
+
+        /// 该节点已提交过结果
+        ResultAlreadySubmitted,
+
         // -------------------- 经济激励错误 --------------------
 
         /// 质押金额不足
@@ -619,6 +853,9 @@ pub mod pallet {
 
         /// 批处理中包含无效请求
         InvalidBatchItem,
+
+        /// 节点注册的 Enclave 公钥已在公钥目录中吊销
+        EnclaveKeyRevoked,
     }
 
     // ========================================================================
@@ -667,43 +904,13 @@ pub mod pallet {
                 Error::<T>::InvalidEnclavePubkey
             );
 
+            // 已配置 DCAP 根证书时，DCAP 节点须通过 Quote 注册
+            Self::ensure_quote_not_required(&attestation)?;
+
             // 验证认证报告
             Self::verify_attestation(&attestation)?;
 
-            // 获取当前时间戳
-            let now = pallet_timestamp::Pallet::<T>::get();
-            let timestamp = now.try_into().ok().unwrap_or(0u64);
-
-            // 创建 TEE 节点信息
-            let node = TeeNode {
-                account: who.clone(),
-                enclave_pubkey,
-                attestation: attestation.clone(),
-                registered_at: timestamp,
-                status: TeeNodeStatus::Active,
-            };
-
-            // 存储节点信息
-            TeeNodes::<T>::insert(&who, node);
-
-            // 添加到活跃节点列表
-            ActiveNodes::<T>::try_mutate(|nodes| {
-                nodes
-                    .try_push(who.clone())
-                    .map_err(|_| Error::<T>::NodeListFull)
-            })?;
-
-            // 更新节点计数
-            NodeCount::<T>::mutate(|count| *count = count.saturating_add(1));
-
-            // 触发事件
-            Self::deposit_event(Event::TeeNodeRegistered {
-                account: who,
-                enclave_pubkey,
-                tee_type: attestation.tee_type,
-            });
-
-            Ok(())
+            Self::do_register_node(who, enclave_pubkey, attestation)
         }
 
         /// 更新认证报告
@@ -729,6 +936,8 @@ pub mod pallet {
             TeeNodes::<T>::try_mutate(&who, |maybe_node| {
                 let node = maybe_node.as_mut().ok_or(Error::<T>::NodeNotRegistered)?;
 
+                Self::ensure_quote_not_required(&attestation)?;
+
                 // 验证认证报告
                 Self::verify_attestation(&attestation).map_err(|_| Error::<T>::InvalidAttestation)?;
 
@@ -759,6 +968,7 @@ pub mod pallet {
         /// # 错误
         /// - `NodeNotRegistered`: 节点未注册
         /// - `InvalidNodeStatus`: 无效的状态转换
+        /// - `EnclaveKeyRevoked` / `EnclaveKeyMismatch`: 恢复时注册公钥已吊销或已过轮换窗口
         #[pallet::call_index(2)]
         #[pallet::weight(<T as Config>::WeightInfo::update_node_status())]
         pub fn update_node_status(
@@ -782,6 +992,18 @@ pub mod pallet {
                     Error::<T>::InvalidNodeStatus
                 );
 
+                // 恢复服务时注册公钥必须仍然有效
+                if new_status == TeeNodeStatus::Active {
+                    ensure!(
+                        !EnclaveKeyRevoked::<T>::contains_key(&who),
+                        Error::<T>::EnclaveKeyRevoked
+                    );
+                    ensure!(
+                        Self::enclave_key_usable(&who, &node.enclave_pubkey),
+                        Error::<T>::EnclaveKeyMismatch
+                    );
+                }
+
                 let old = node.status;
                 node.status = new_status;
 
@@ -843,6 +1065,7 @@ pub mod pallet {
             ActiveNodes::<T>::mutate(|nodes| {
                 nodes.retain(|account| account != &who);
             });
+            EnclaveKeyRevoked::<T>::remove(&who);
 
             // 更新节点计数
             NodeCount::<T>::mutate(|count| *count = count.saturating_sub(1));
@@ -866,6 +1089,9 @@ pub mod pallet {
         /// - `compute_type_id`: 计算类型 ID (0=BaZi, 1=MeiHua, 等)
         /// - `input_hash`: 加密输入数据的哈希
         /// - `assigned_node`: 指定节点（可选，None 表示自动分配）
+        ///
+        /// 未指定节点时请求以 `Pending` 状态进入 `ReplicaSelectionQueue`，
+        /// 在提交区块之后的 on_initialize 中按随机数抽取节点
         #[pallet::call_index(4)]
         #[pallet::weight(<T as Config>::WeightInfo::submit_compute_request())]
         pub fn submit_compute_request(
@@ -876,17 +1102,18 @@ pub mod pallet {
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
-            // 分配节点（如果未指定则自动选择）
+            // 指定节点立即分配；未指定则延迟到下一区块抽取
             let node = match assigned_node {
                 Some(ref n) => {
                     ensure!(Self::is_node_active(n), Error::<T>::NodeNotActive);
                     Some(n.clone())
                 }
-                None => Self::select_random_node(),
+                None => {
+                    ensure!(!ActiveNodes::<T>::get().is_empty(), Error::<T>::NoAvailableNodes);
+                    None
+                }
             };
 
-            ensure!(node.is_some(), Error::<T>::NoAvailableNodes);
-
             // 生成请求 ID
             let request_id = NextRequestId::<T>::get();
             NextRequestId::<T>::put(request_id.saturating_add(1));
@@ -905,7 +1132,7 @@ pub mod pallet {
                 assigned_node: node.clone(),
                 created_at: current_block,
                 timeout_at,
-                status: RequestStatus::Processing,
+                status: if node.is_some() { RequestStatus::Processing } else { RequestStatus::Pending },
                 failover_count: 0,
                 failure_reason: None,
             };
@@ -920,9 +1147,14 @@ pub mod pallet {
                     .map_err(|_| Error::<T>::RequestListFull)
             })?;
 
-            // 记录节点当前处理的请求
-            if let Some(ref n) = node {
-                NodeCurrentRequest::<T>::insert(n, request_id);
+            // 记录节点当前处理的请求，未指定节点则排队等待抽取
+            match node {
+                Some(ref n) => NodeCurrentRequest::<T>::insert(n, request_id),
+                None => ReplicaSelectionQueue::<T>::try_mutate(|queue| {
+                    queue
+                        .try_push((request_id, 1))
+                        .map_err(|_| Error::<T>::RequestListFull)
+                })?,
             }
 
             // 触发事件
@@ -955,6 +1187,15 @@ pub mod pallet {
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
+            // 冗余执行请求按副本收集结果
+            if RequestReplicas::<T>::contains_key(request_id) {
+                return Self::record_replica_result(&who, request_id, output_hash, enclave_signature);
+            }
+            // 已达成一致的冗余请求，迟到结果与最终结果核对
+            if LateReplicaChecks::<T>::contains_key(request_id) {
+                return Self::record_late_replica_result(&who, request_id, output_hash);
+            }
+
             // 获取并验证请求
             ComputeRequests::<T>::try_mutate(request_id, |maybe_request| {
                 let request = maybe_request.as_mut().ok_or(Error::<T>::RequestNotFound)?;
@@ -1068,6 +1309,14 @@ pub mod pallet {
             if let Some(node) = assigned_node {
                 NodeCurrentRequest::<T>::remove(&node);
             }
+            if let Some(replicas) = RequestReplicas::<T>::take(request_id) {
+                for node in replicas.nodes.iter() {
+                    NodeCurrentRequest::<T>::remove(node);
+                }
+            }
+            ReplicaSelectionQueue::<T>::mutate(|queue| {
+                queue.retain(|&(id, _)| id != request_id);
+            });
 
             // 触发事件
             Self::deposit_event(Event::ComputeRequestCancelled {
@@ -1204,6 +1453,7 @@ pub mod pallet {
         /// 批量提交计算请求
         ///
         /// 允许用户一次提交多个计算请求，提高效率并减少交易费用。
+        /// 每个请求以 `Pending` 状态排队，在提交区块之后的 on_initialize 中各自抽取节点。
         ///
         /// # 参数
         /// - `origin`: 交易发起者（请求者）
@@ -1228,9 +1478,7 @@ pub mod pallet {
                 Error::<T>::BatchSizeExceeded
             );
 
-            // 获取可用节点（为整个批次分配同一节点以提高效率）
-            let node = Self::select_random_node();
-            ensure!(node.is_some(), Error::<T>::NoAvailableNodes);
+            ensure!(!ActiveNodes::<T>::get().is_empty(), Error::<T>::NoAvailableNodes);
 
             let current_block = frame_system::Pallet::<T>::block_number();
             let timeout_blocks: BlockNumberFor<T> = T::RequestTimeout::get().into();
@@ -1249,10 +1497,10 @@ pub mod pallet {
                     requester: who.clone(),
                     compute_type_id: item.compute_type_id,
                     input_hash: item.input_hash,
-                    assigned_node: node.clone(),
+                    assigned_node: None,
                     created_at: current_block,
                     timeout_at,
-                    status: RequestStatus::Processing,
+                    status: RequestStatus::Pending,
                     failover_count: 0,
                     failure_reason: None,
                 };
@@ -1260,19 +1508,21 @@ pub mod pallet {
                 // 存储请求
                 ComputeRequests::<T>::insert(request_id, request);
 
-                // 添加到待处理列表（忽略满的情况，批处理优先保证部分成功）
-                let _ = PendingRequests::<T>::try_mutate(|pending| {
-                    pending.try_push(request_id)
-                });
+                // 添加到待处理列表与节点抽取队列
+                PendingRequests::<T>::try_mutate(|pending| {
+                    pending
+                        .try_push(request_id)
+                        .map_err(|_| Error::<T>::RequestListFull)
+                })?;
+                ReplicaSelectionQueue::<T>::try_mutate(|queue| {
+                    queue
+                        .try_push((request_id, 1))
+                        .map_err(|_| Error::<T>::RequestListFull)
+                })?;
 
                 request_ids.push(request_id);
             }
 
-            // 记录节点当前处理的请求（使用最后一个请求 ID）
-            if let (Some(ref n), Some(&last_id)) = (&node, request_ids.last()) {
-                NodeCurrentRequest::<T>::insert(n, last_id);
-            }
-
             let count = request_ids.len() as u32;
 
             // 触发事件
@@ -1316,8 +1566,33 @@ pub mod pallet {
             let timestamp: u64 = now.try_into().ok().unwrap_or(0);
 
             let mut completed_ids = Vec::with_capacity(results.len());
+            // 冗余请求的节点统计在达成一致时单独更新
+            let mut replica_count = 0u64;
 
             for item in results.iter() {
+                // 冗余执行请求按副本收集结果，不直接完成
+                if RequestReplicas::<T>::contains_key(item.request_id) {
+                    if Self::record_replica_result(
+                        &who,
+                        item.request_id,
+                        item.output_hash,
+                        item.enclave_signature,
+                    )
+                    .is_ok()
+                    {
+                        completed_ids.push(item.request_id);
+                        replica_count = replica_count.saturating_add(1);
+                    }
+                    continue;
+                }
+                if LateReplicaChecks::<T>::contains_key(item.request_id) {
+                    if Self::record_late_replica_result(&who, item.request_id, item.output_hash).is_ok() {
+                        completed_ids.push(item.request_id);
+                        replica_count = replica_count.saturating_add(1);
+                    }
+                    continue;
+                }
+
                 // 验证请求存在且是分配给该节点的
                 let request_result = ComputeRequests::<T>::try_mutate(item.request_id, |maybe_request| {
                     let request = maybe_request.as_mut().ok_or(Error::<T>::RequestNotFound)?;
@@ -1365,7 +1640,7 @@ pub mod pallet {
             NodeCurrentRequest::<T>::remove(&who);
 
             // 更新节点统计
-            let completed_count = completed_ids.len() as u64;
+            let completed_count = (completed_ids.len() as u64).saturating_sub(replica_count);
             if completed_count > 0 {
                 NodeStats::<T>::mutate(&who, |maybe_stats| {
                     let stats = maybe_stats.get_or_insert_with(NodeStatistics::default);
@@ -1568,101 +1843,689 @@ pub mod pallet {
 
             Ok(())
         }
-    }
-
-    // ========================================================================
-    // Hooks
-    // ========================================================================
-
-    #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        /// 区块结束时处理超时请求
-        fn on_finalize(block_number: BlockNumberFor<T>) {
-            Self::process_timeout_requests(block_number);
-        }
-    }
 
-    // ========================================================================
-    // 内部函数
-    // ========================================================================
+        // ====================================================================
+        // 冗余执行
+        // ====================================================================
 
-    impl<T: Config> Pallet<T> {
-        /// 验证远程认证报告
+        /// 提交冗余执行的计算请求（k-of-n）
         ///
-        /// 检查认证报告的有效性，包括：
-        /// 1. MRENCLAVE 是否在允许列表中
-        /// 2. MRSIGNER 是否在允许列表中（如果列表非空）
-        /// 3. 认证时间戳是否有效
+        /// 同一加密请求按权重随机分配给 `redundancy` 个不同节点，
+        /// 任一输出哈希获得 `quorum` 个一致结果即完成，结果不一致的节点被惩罚。
+        /// 冗余请求超时后不做故障转移，直接标记失败。
+        ///
+        /// 副本节点不在提交时选择：请求以 `Pending` 状态进入分配队列，
+        /// 在下一区块的 on_initialize 中使用提交后才产生的随机数抽取。
         ///
         /// # 参数
-        /// - `attestation`: 远程认证报告
+        /// - `origin`: 交易发起者（请求者）
+        /// - `compute_type_id`: 计算类型 ID
+        /// - `input_hash`: 加密输入数据的哈希（所有副本共用同一密文）
+        /// - `redundancy`: 副本数 n
+        /// - `quorum`: 一致结果数 k（需超过 n 的一半）
         ///
-        /// # 返回
-        /// - `Ok(())`: 验证通过
-        /// - `Err(Error)`: 验证失败
-        pub fn verify_attestation(attestation: &TeeAttestation) -> DispatchResult {
-            // 1. 检查 MRENCLAVE 是否在允许列表中
-            let allowed_enclaves = AllowedMrEnclaves::<T>::get();
-            if !allowed_enclaves.is_empty() {
-                ensure!(
-                    allowed_enclaves.contains(&attestation.mr_enclave),
-                    Error::<T>::MrEnclaveNotAllowed
-                );
-            }
-
-            // 2. 检查 MRSIGNER 是否在允许列表中（如果列表非空）
-            let allowed_signers = AllowedMrSigners::<T>::get();
-            if !allowed_signers.is_empty() {
-                ensure!(
-                    allowed_signers.contains(&attestation.mr_signer),
-                    Error::<T>::MrSignerNotAllowed
-                );
-            }
-
-            // 3. 检查认证时间戳（不能是未来时间，且不能太旧）
-            let now = pallet_timestamp::Pallet::<T>::get();
-            let current_timestamp: u64 = now.try_into().ok().unwrap_or(0);
-
-            // 认证时间戳不能是未来时间（允许 1 分钟误差）
-            ensure!(
-                attestation.timestamp <= current_timestamp.saturating_add(60),
-                Error::<T>::InvalidAttestationTimestamp
-            );
+        /// # 错误
+        /// - `InvalidRedundancy`: 冗余参数无效
+        /// - `InsufficientNodes`: 可用节点不足
+        #[pallet::call_index(17)]
+        #[pallet::weight(<T as Config>::WeightInfo::submit_redundant_compute_request(*redundancy as u32))]
+        pub fn submit_redundant_compute_request(
+            origin: OriginFor<T>,
+            compute_type_id: u8,
+            input_hash: [u8; 32],
+            redundancy: u8,
+            quorum: u8,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
 
-            // 认证不能太旧（默认 24 小时 = 86400 秒）
-            let max_age: u64 = 86400;
             ensure!(
-                attestation.timestamp >= current_timestamp.saturating_sub(max_age),
-                Error::<T>::AttestationExpired
+                redundancy >= 2
+                    && redundancy as u32 <= MAX_REPLICAS
+                    && quorum <= redundancy
+                    && (quorum as u32) * 2 > redundancy as u32,
+                Error::<T>::InvalidRedundancy
             );
 
-            // 🔮 Phase 4 计划：实现完整的 IAS 签名验证
-            // 当前阶段跳过验证，Phase 4 将接入 Intel Attestation Service
-            // Self::verify_ias_signature(attestation)?;
-
-            Ok(())
-        }
+            let available = ActiveNodes::<T>::get()
+                .iter()
+                .filter(|n| Self::is_node_active(n))
+                .count();
+            ensure!(available >= redundancy as usize, Error::<T>::InsufficientNodes);
 
-        /// 检查节点是否活跃
-        ///
-        /// 节点活跃条件：
-        /// 1. 节点已注册
-        /// 2. 状态为 Active
-        /// 3. 认证未过期
-        pub fn is_node_active(account: &T::AccountId) -> bool {
-            if let Some(node) = TeeNodes::<T>::get(account) {
-                if node.status != TeeNodeStatus::Active {
-                    return false;
-                }
+            // 生成请求 ID
+            let request_id = NextRequestId::<T>::get();
+            NextRequestId::<T>::put(request_id.saturating_add(1));
 
-                // 检查认证是否过期
-                let now = pallet_timestamp::Pallet::<T>::get();
-                let current_timestamp: u64 = now.try_into().ok().unwrap_or(0);
-                let max_age: u64 = 86400; // 24 小时
+            let current_block = frame_system::Pallet::<T>::block_number();
+            let timeout_blocks: BlockNumberFor<T> = T::RequestTimeout::get().into();
+            let timeout_at = current_block.saturating_add(timeout_blocks);
 
-                node.attestation.timestamp >= current_timestamp.saturating_sub(max_age)
-            } else {
-                false
+            let request = ComputeRequestInfo {
+                id: request_id,
+                requester: who.clone(),
+                compute_type_id,
+                input_hash,
+                assigned_node: None,
+                created_at: current_block,
+                timeout_at,
+                status: RequestStatus::Pending,
+                failover_count: 0,
+                failure_reason: None,
+            };
+            ComputeRequests::<T>::insert(request_id, request);
+
+            PendingRequests::<T>::try_mutate(|pending| {
+                pending
+                    .try_push(request_id)
+                    .map_err(|_| Error::<T>::RequestListFull)
+            })?;
+            ReplicaSelectionQueue::<T>::try_mutate(|queue| {
+                queue
+                    .try_push((request_id, redundancy))
+                    .map_err(|_| Error::<T>::RequestListFull)
+            })?;
+
+            // 副本集合先只记录参数，节点在分配时写入
+            RequestReplicas::<T>::insert(
+                request_id,
+                ReplicaSet {
+                    nodes: BoundedVec::new(),
+                    quorum,
+                    results: BoundedVec::new(),
+                },
+            );
+
+            Self::deposit_event(Event::ComputeRequestSubmitted {
+                request_id,
+                requester: who,
+                compute_type_id,
+                assigned_node: None,
+            });
+
+            Ok(())
+        }
+
+        // ====================================================================
+        // DCAP 抵押品管理（治理）
+        // ====================================================================
+
+        /// 设置 DCAP 根证书
+        ///
+        /// 上传 Intel SGX Root CA 证书（DER）。设置后 DCAP 节点只能通过
+        /// `register_tee_node_with_quote` / `update_attestation_with_quote` 认证。
+        ///
+        /// # 参数
+        /// - `origin`: 交易发起者（必须是 Root 权限）
+        /// - `root_ca`: 根证书 DER
+        ///
+        /// # 错误
+        /// - `InvalidDcapCollateral`: 证书无法解析、非自签名或不在有效期内
+        #[pallet::call_index(18)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_dcap_collateral())]
+        pub fn set_dcap_root_ca(
+            origin: OriginFor<T>,
+            root_ca: DcapCollateralBytes,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            tee_dcap::verify_root_ca(&root_ca, Self::unix_now())
+                .map_err(|_| Error::<T>::InvalidDcapCollateral)?;
+
+            let fingerprint = sp_io::hashing::sha2_256(&root_ca);
+            DcapRootCa::<T>::put(root_ca);
+
+            Self::deposit_event(Event::DcapRootCaUpdated { fingerprint });
+
+            Ok(())
+        }
+
+        /// 设置 DCAP CRL
+        ///
+        /// 根 CA CRL 的 `issuer_chain` 为空；PCK CRL 需附带签发 CA 证书链
+        /// （Intel PCS 响应头 `SGX-PCK-CRL-Issuer-Chain`）。
+        ///
+        /// # 参数
+        /// - `origin`: 交易发起者（必须是 Root 权限）
+        /// - `kind`: CRL 类别
+        /// - `crl`: CRL DER
+        /// - `issuer_chain`: 签发 CA 证书链（PEM）
+        ///
+        /// # 错误
+        /// - `DcapCollateralMissing`: 尚未设置根证书
+        /// - `InvalidDcapCollateral`: CRL 签名、签发者或时效无效
+        #[pallet::call_index(19)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_dcap_collateral())]
+        pub fn set_dcap_crl(
+            origin: OriginFor<T>,
+            kind: DcapCrlKind,
+            crl: DcapCollateralBytes,
+            issuer_chain: DcapCollateralBytes,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let root_ca = DcapRootCa::<T>::get().ok_or(Error::<T>::DcapCollateralMissing)?;
+            ensure!(
+                (kind == DcapCrlKind::RootCa) == issuer_chain.is_empty(),
+                Error::<T>::InvalidDcapCollateral
+            );
+            tee_dcap::verify_crl_upload(&crl, &issuer_chain, &root_ca, Self::unix_now())
+                .map_err(|_| Error::<T>::InvalidDcapCollateral)?;
+
+            DcapCrls::<T>::insert(kind, DcapSignedCollateral { body: crl, issuer_chain });
+
+            Self::deposit_event(Event::DcapCrlUpdated { kind });
+
+            Ok(())
+        }
+
+        /// 设置 TCB Info
+        ///
+        /// 按 TCB Info 中的 FMSPC 存储，覆盖同一平台系列的旧数据。
+        ///
+        /// # 参数
+        /// - `origin`: 交易发起者（必须是 Root 权限）
+        /// - `tcb_info`: Intel PCS 返回的 TCB Info v3 JSON
+        /// - `issuer_chain`: TCB Signing 证书链（PEM）
+        ///
+        /// # 错误
+        /// - `DcapCollateralMissing`: 尚未设置根证书或根 CA CRL
+        /// - `InvalidDcapCollateral`: 签名、证书链、格式或时效无效
+        #[pallet::call_index(20)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_dcap_collateral())]
+        pub fn set_dcap_tcb_info(
+            origin: OriginFor<T>,
+            tcb_info: DcapCollateralBytes,
+            issuer_chain: DcapCollateralBytes,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let (root_ca, root_crl) = Self::dcap_trust_anchor()?;
+            let info = tee_dcap::verify_tcb_info(
+                &tcb_info,
+                &issuer_chain,
+                &root_ca,
+                &root_crl.body,
+                Self::unix_now(),
+            )
+            .map_err(|_| Error::<T>::InvalidDcapCollateral)?;
+
+            DcapTcbInfos::<T>::insert(
+                info.fmspc,
+                DcapSignedCollateral { body: tcb_info, issuer_chain },
+            );
+
+            Self::deposit_event(Event::DcapTcbInfoUpdated {
+                fmspc: info.fmspc,
+                evaluation_data_number: info.evaluation_data_number,
+            });
+
+            Ok(())
+        }
+
+        /// 设置 QE Identity
+        ///
+        /// # 参数
+        /// - `origin`: 交易发起者（必须是 Root 权限）
+        /// - `qe_identity`: Intel PCS 返回的 QE Identity v2 JSON
+        /// - `issuer_chain`: TCB Signing 证书链（PEM）
+        ///
+        /// # 错误
+        /// - `DcapCollateralMissing`: 尚未设置根证书或根 CA CRL
+        /// - `InvalidDcapCollateral`: 签名、证书链、格式或时效无效
+        #[pallet::call_index(21)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_dcap_collateral())]
+        pub fn set_dcap_qe_identity(
+            origin: OriginFor<T>,
+            qe_identity: DcapCollateralBytes,
+            issuer_chain: DcapCollateralBytes,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let (root_ca, root_crl) = Self::dcap_trust_anchor()?;
+            tee_dcap::verify_qe_identity(
+                &qe_identity,
+                &issuer_chain,
+                &root_ca,
+                &root_crl.body,
+                Self::unix_now(),
+            )
+            .map_err(|_| Error::<T>::InvalidDcapCollateral)?;
+
+            DcapQeIdentity::<T>::put(DcapSignedCollateral { body: qe_identity, issuer_chain });
+
+            Self::deposit_event(Event::DcapQeIdentityUpdated);
+
+            Ok(())
+        }
+
+        // ====================================================================
+        // DCAP Quote 认证
+        // ====================================================================
+
+        /// 使用 DCAP Quote 注册 TEE 节点
+        ///
+        /// 链上按已存储的抵押品完整验证 Quote，并从中提取认证报告；
+        /// Quote 的 `report_data` 须按 `SHA-256(X25519 公钥 || enclave_pubkey) || X25519 公钥`
+        /// 绑定 Enclave 密钥。
+        ///
+        /// # 参数
+        /// - `origin`: 交易发起者（TEE 节点运营账户）
+        /// - `enclave_pubkey`: Enclave Ed25519 公钥（32 字节）
+        /// - `quote`: ECDSA Quote v3 / v4
+        ///
+        /// # 错误
+        /// - `NodeAlreadyRegistered`: 节点已注册
+        /// - `InvalidEnclavePubkey`: 无效的公钥
+        /// - `DcapCollateralMissing` / `DcapCollateralExpired`: 抵押品缺失或过期
+        /// - `InvalidQuote` / `QuoteVerificationFailed`: Quote 无效
+        /// - `TcbStatusRejected`: 平台 TCB 过时或已吊销
+        /// - `ReportDataMismatch`: 报告数据未绑定 Enclave 公钥
+        /// - `MrEnclaveNotAllowed`: MRENCLAVE 不在允许列表
+        #[pallet::call_index(22)]
+        #[pallet::weight(<T as Config>::WeightInfo::register_tee_node_with_quote())]
+        pub fn register_tee_node_with_quote(
+            origin: OriginFor<T>,
+            enclave_pubkey: [u8; 32],
+            quote: DcapQuote,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                !TeeNodes::<T>::contains_key(&who),
+                Error::<T>::NodeAlreadyRegistered
+            );
+            ensure!(
+                enclave_pubkey != [0u8; 32],
+                Error::<T>::InvalidEnclavePubkey
+            );
+
+            let (attestation, fmspc, tcb_status) =
+                Self::verify_dcap_quote(&quote, &enclave_pubkey)?;
+            Self::verify_attestation(&attestation)?;
+
+            Self::do_register_node(who.clone(), enclave_pubkey, attestation)?;
+
+            Self::deposit_event(Event::DcapQuoteVerified {
+                account: who,
+                fmspc,
+                tcb_status,
+            });
+
+            Ok(())
+        }
+
+        /// 使用 DCAP Quote 更新认证报告
+        ///
+        /// Quote 须绑定节点注册时的 Enclave 公钥。
+        ///
+        /// # 参数
+        /// - `origin`: 交易发起者（必须是已注册的 TEE 节点）
+        /// - `quote`: ECDSA Quote v3 / v4
+        ///
+        /// # 错误
+        /// - `NodeNotRegistered`: 节点未注册
+        /// - 其余同 `register_tee_node_with_quote`
+        #[pallet::call_index(23)]
+        #[pallet::weight(<T as Config>::WeightInfo::update_attestation_with_quote())]
+        pub fn update_attestation_with_quote(
+            origin: OriginFor<T>,
+            quote: DcapQuote,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let node = TeeNodes::<T>::get(&who).ok_or(Error::<T>::NodeNotRegistered)?;
+            let (attestation, fmspc, tcb_status) =
+                Self::verify_dcap_quote(&quote, &node.enclave_pubkey)?;
+            Self::verify_attestation(&attestation)?;
+
+            TeeNodes::<T>::mutate(&who, |maybe_node| {
+                if let Some(node) = maybe_node {
+                    node.attestation = attestation.clone();
+                }
+            });
+
+            Self::deposit_event(Event::AttestationUpdated {
+                account: who.clone(),
+                mr_enclave: attestation.mr_enclave,
+                timestamp: attestation.timestamp,
+            });
+            Self::deposit_event(Event::DcapQuoteVerified {
+                account: who,
+                fmspc,
+                tcb_status,
+            });
+
+            Ok(())
+        }
+    }
+
+    // ========================================================================
+    // 公钥目录回调
+    // ========================================================================
+
+    impl<T: Config> pallet_key_directory::traits::KeyChangeHandler<T::AccountId> for Pallet<T> {
+        fn max_weight() -> Weight {
+            T::DbWeight::get().reads_writes(3, 3)
+        }
+
+        /// 运营者轮换 Enclave 公钥：旧公钥在重叠窗口内仍可用，提示节点重新注册
+        fn on_key_rotated(
+            who: &T::AccountId,
+            purpose: pallet_key_directory::types::KeyPurpose,
+            _old_version: u32,
+            new_version: u32,
+        ) {
+            if purpose != pallet_key_directory::types::KeyPurpose::TeeEnclave {
+                return;
+            }
+            if TeeNodes::<T>::get(who).is_some_and(|node| node.status != TeeNodeStatus::Deregistered)
+            {
+                Self::deposit_event(Event::EnclaveKeyRotationRequired {
+                    account: who.clone(),
+                    new_version,
+                });
+            }
+        }
+
+        /// 运营者 Enclave 公钥被吊销：注册公钥失效的节点立即暂停且不能恢复
+        fn on_key_revoked(
+            who: &T::AccountId,
+            purpose: pallet_key_directory::types::KeyPurpose,
+            _version: u32,
+        ) {
+            use pallet_key_directory::{traits::KeyDirectory, types::KeyPurpose};
+
+            if purpose != KeyPurpose::TeeEnclave {
+                return;
+            }
+            let Some(node) = TeeNodes::<T>::get(who) else { return };
+            if node.status == TeeNodeStatus::Deregistered
+                || T::KeyDirectory::is_usable_key(who, KeyPurpose::TeeEnclave, &node.enclave_pubkey)
+            {
+                return;
+            }
+
+            EnclaveKeyRevoked::<T>::insert(who, ());
+            if node.status == TeeNodeStatus::Active {
+                TeeNodes::<T>::mutate(who, |maybe_node| {
+                    if let Some(node) = maybe_node {
+                        node.status = TeeNodeStatus::Suspended;
+                    }
+                });
+                ActiveNodes::<T>::mutate(|nodes| nodes.retain(|account| account != who));
+                Self::deposit_event(Event::NodeStatusUpdated {
+                    account: who.clone(),
+                    old_status: TeeNodeStatus::Active,
+                    new_status: TeeNodeStatus::Suspended,
+                });
+            }
+        }
+    }
+
+    // ========================================================================
+    // Hooks
+    // ========================================================================
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// 区块开始时分配冗余请求的副本节点，并清理到期的迟到结果核对
+        fn on_initialize(block_number: BlockNumberFor<T>) -> Weight {
+            let assigned = Self::assign_queued_replicas();
+            let pruned = Self::prune_late_replica_checks(block_number);
+            <T as Config>::WeightInfo::assign_replicas(assigned)
+                .saturating_add(T::DbWeight::get().reads_writes(2, 2))
+                .saturating_add(T::DbWeight::get().writes(pruned as u64))
+        }
+
+        /// 区块结束时处理超时请求
+        fn on_finalize(block_number: BlockNumberFor<T>) {
+            Self::process_timeout_requests(block_number);
+        }
+    }
+
+    // ========================================================================
+    // 内部函数
+    // ========================================================================
+
+    impl<T: Config> Pallet<T> {
+        /// 验证远程认证报告
+        ///
+        /// 检查认证报告的有效性，包括：
+        /// 1. MRENCLAVE 是否在允许列表中
+        /// 2. MRSIGNER 是否在允许列表中（如果列表非空）
+        /// 3. 认证时间戳是否有效
+        ///
+        /// # 参数
+        /// - `attestation`: 远程认证报告
+        ///
+        /// # 返回
+        /// - `Ok(())`: 验证通过
+        /// - `Err(Error)`: 验证失败
+        pub fn verify_attestation(attestation: &TeeAttestation) -> DispatchResult {
+            // 1. 检查 MRENCLAVE 是否在允许列表中
+            let allowed_enclaves = AllowedMrEnclaves::<T>::get();
+            if !allowed_enclaves.is_empty() {
+                ensure!(
+                    allowed_enclaves.contains(&attestation.mr_enclave),
+                    Error::<T>::MrEnclaveNotAllowed
+                );
+            }
+
+            // 2. 检查 MRSIGNER 是否在允许列表中（如果列表非空）
+            let allowed_signers = AllowedMrSigners::<T>::get();
+            if !allowed_signers.is_empty() {
+                ensure!(
+                    allowed_signers.contains(&attestation.mr_signer),
+                    Error::<T>::MrSignerNotAllowed
+                );
+            }
+
+            // 3. 检查认证时间戳（不能是未来时间，且不能太旧）
+            let now = pallet_timestamp::Pallet::<T>::get();
+            let current_timestamp: u64 = now.try_into().ok().unwrap_or(0);
+
+            // 认证时间戳不能是未来时间（允许 1 分钟误差）
+            ensure!(
+                attestation.timestamp <= current_timestamp.saturating_add(60),
+                Error::<T>::InvalidAttestationTimestamp
+            );
+
+            // 认证不能太旧（默认 24 小时 = 86400 秒）
+            ensure!(
+                attestation.timestamp >= current_timestamp.saturating_sub(ATTESTATION_MAX_AGE),
+                Error::<T>::AttestationExpired
+            );
+
+            // 🔮 Phase 4 计划：实现完整的 IAS 签名验证
+            // 当前阶段跳过验证，Phase 4 将接入 Intel Attestation Service
+            // Self::verify_ias_signature(attestation)?;
+
+            Ok(())
+        }
+
+        /// 已配置 DCAP 根证书时，拒绝未附带 Quote 的 DCAP 认证报告
+        fn ensure_quote_not_required(attestation: &TeeAttestation) -> Result<(), Error<T>> {
+            ensure!(
+                attestation.tee_type != TeeType::IntelSgxDcap || !DcapRootCa::<T>::exists(),
+                Error::<T>::DcapQuoteRequired
+            );
+            Ok(())
+        }
+
+        /// 验证 DCAP Quote 并生成认证报告
+        ///
+        /// 返回 (认证报告, FMSPC, TCB 状态)。认证报告的 `ias_signature`
+        /// 字段保存 Quote 的 SHA-256 摘要，便于链下审计时比对原始 Quote。
+        pub fn verify_dcap_quote(
+            quote: &[u8],
+            enclave_pubkey: &[u8; 32],
+        ) -> Result<(TeeAttestation, [u8; 6], TcbStatus), DispatchError> {
+            let (root_ca, root_crl) = Self::dcap_trust_anchor()?;
+            let pck_crls: Vec<DcapSignedCollateral> =
+                [DcapCrlKind::PckProcessor, DcapCrlKind::PckPlatform]
+                    .into_iter()
+                    .filter_map(DcapCrls::<T>::get)
+                    .collect();
+            let pck_crl_bodies: Vec<&[u8]> = pck_crls.iter().map(|crl| &crl.body[..]).collect();
+
+            let fmspc = tee_dcap::Quote::parse(quote)
+                .and_then(|parsed| parsed.fmspc())
+                .map_err(|_| Error::<T>::InvalidQuote)?;
+            let tcb_info = DcapTcbInfos::<T>::get(fmspc).ok_or(Error::<T>::DcapCollateralMissing)?;
+            let qe_identity = DcapQeIdentity::<T>::get().ok_or(Error::<T>::DcapCollateralMissing)?;
+
+            let collateral = tee_dcap::Collateral {
+                root_ca: &root_ca,
+                root_ca_crl: &root_crl.body,
+                pck_crls: &pck_crl_bodies,
+                tcb_info: &tcb_info.body,
+                tcb_info_chain: &tcb_info.issuer_chain,
+                qe_identity: &qe_identity.body,
+                qe_identity_chain: &qe_identity.issuer_chain,
+            };
+            let report = DcapVerifier::verify_quote(quote, &collateral, Self::unix_now())
+                .map_err(Self::dcap_error)?;
+            ensure!(
+                report.tcb_status.is_acceptable(T::AcceptConfigurationNeeded::get()),
+                Error::<T>::TcbStatusRejected
+            );
+
+            // report_data = SHA-256(X25519 公钥 || Ed25519 公钥) || X25519 公钥
+            let body = report.report_body;
+            let mut preimage = [0u8; 64];
+            preimage[..32].copy_from_slice(&body.report_data[32..]);
+            preimage[32..].copy_from_slice(enclave_pubkey);
+            ensure!(
+                sp_io::hashing::sha2_256(&preimage) == body.report_data[..32],
+                Error::<T>::ReportDataMismatch
+            );
+
+            let now = pallet_timestamp::Pallet::<T>::get();
+            let attestation = TeeAttestation {
+                tee_type: TeeType::IntelSgxDcap,
+                mr_enclave: body.mr_enclave,
+                mr_signer: body.mr_signer,
+                isv_prod_id: body.isv_prod_id,
+                isv_svn: body.isv_svn,
+                report_data: body.report_data,
+                ias_signature: BoundedVec::truncate_from(sp_io::hashing::sha2_256(quote).to_vec()),
+                timestamp: now.try_into().ok().unwrap_or(0u64),
+            };
+
+            Ok((attestation, fmspc, report.tcb_status))
+        }
+
+        /// DCAP 根证书与根 CA CRL
+        fn dcap_trust_anchor() -> Result<(DcapCollateralBytes, DcapSignedCollateral), Error<T>> {
+            let root_ca = DcapRootCa::<T>::get().ok_or(Error::<T>::DcapCollateralMissing)?;
+            let root_crl =
+                DcapCrls::<T>::get(DcapCrlKind::RootCa).ok_or(Error::<T>::DcapCollateralMissing)?;
+            Ok((root_ca, root_crl))
+        }
+
+        /// 当前 Unix 时间（秒）
+        fn unix_now() -> u64 {
+            T::UnixTime::now().as_secs()
+        }
+
+        /// DCAP 验证错误映射
+        fn dcap_error(error: tee_dcap::DcapError) -> Error<T> {
+            use tee_dcap::DcapError as E;
+
+            log::debug!(target: "tee-privacy", "DCAP quote rejected: {:?}", error);
+            match error {
+                E::QuoteTruncated
+                | E::UnsupportedQuoteVersion(_)
+                | E::UnsupportedAttestationKeyType(_)
+                | E::UnsupportedTeeType(_)
+                | E::UnsupportedCertificationDataType(_) => Error::<T>::InvalidQuote,
+                E::CrlExpired | E::CollateralExpired => Error::<T>::DcapCollateralExpired,
+                E::CrlMissing => Error::<T>::DcapCollateralMissing,
+                E::TcbLevelNotFound => Error::<T>::TcbStatusRejected,
+                _ => Error::<T>::QuoteVerificationFailed,
+            }
+        }
+
+        /// 写入新节点并加入活跃列表
+        fn do_register_node(
+            who: T::AccountId,
+            enclave_pubkey: [u8; 32],
+            attestation: TeeAttestation,
+        ) -> DispatchResult {
+            // 运营者在公钥目录发布了 Enclave 公钥时，必须与注册公钥一致
+            ensure!(
+                Self::enclave_key_usable(&who, &enclave_pubkey),
+                Error::<T>::EnclaveKeyMismatch
+            );
+
+            // 获取当前时间戳
+            let now = pallet_timestamp::Pallet::<T>::get();
+            let timestamp = now.try_into().ok().unwrap_or(0u64);
+
+            // 创建 TEE 节点信息
+            let node = TeeNode {
+                account: who.clone(),
+                enclave_pubkey,
+                attestation: attestation.clone(),
+                registered_at: timestamp,
+                status: TeeNodeStatus::Active,
+            };
+
+            // 存储节点信息
+            TeeNodes::<T>::insert(&who, node);
+
+            // 添加到活跃节点列表
+            ActiveNodes::<T>::try_mutate(|nodes| {
+                nodes
+                    .try_push(who.clone())
+                    .map_err(|_| Error::<T>::NodeListFull)
+            })?;
+
+            // 更新节点计数
+            NodeCount::<T>::mutate(|count| *count = count.saturating_add(1));
+
+            // 触发事件
+            Self::deposit_event(Event::TeeNodeRegistered {
+                account: who,
+                enclave_pubkey,
+                tee_type: attestation.tee_type,
+            });
+
+            Ok(())
+        }
+
+        /// 运营者在公钥目录发布了 Enclave 公钥时，注册公钥必须仍可用
+        ///
+        /// 目录轮换后旧公钥在重叠窗口内仍可用，窗口结束后节点需用新公钥重新注册。
+        fn enclave_key_usable(who: &T::AccountId, enclave_pubkey: &[u8; 32]) -> bool {
+            use pallet_key_directory::{traits::KeyDirectory, types::KeyPurpose};
+
+            !T::KeyDirectory::has_key(who, KeyPurpose::TeeEnclave)
+                || T::KeyDirectory::is_usable_key(who, KeyPurpose::TeeEnclave, enclave_pubkey)
+        }
+
+        /// 检查节点是否活跃
+        ///
+        /// 节点活跃条件：
+        /// 1. 节点已注册
+        /// 2. 状态为 Active
+        /// 3. 认证未过期
+        /// 4. 注册公钥未在公钥目录中失效
+        pub fn is_node_active(account: &T::AccountId) -> bool {
+            if let Some(node) = TeeNodes::<T>::get(account) {
+                if node.status != TeeNodeStatus::Active
+                    || !Self::enclave_key_usable(account, &node.enclave_pubkey)
+                {
+                    return false;
+                }
+
+                // 检查认证是否过期
+                let now = pallet_timestamp::Pallet::<T>::get();
+                let current_timestamp: u64 = now.try_into().ok().unwrap_or(0);
+
+                node.attestation.timestamp >= current_timestamp.saturating_sub(ATTESTATION_MAX_AGE)
+            } else {
+                false
             }
         }
 
@@ -1681,24 +2544,411 @@ pub mod pallet {
             TeeNodes::<T>::get(account).map(|node| node.status)
         }
 
-        /// 随机选择一个活跃节点
+        /// 按权重无放回地随机选择多个活跃节点
+        ///
+        /// 每一轮从 `T::Randomness` 取随机数，在剩余候选的累计权重上定位节点。
+        /// 候选不足时返回的节点数少于 `count`。
         ///
-        /// 使用区块哈希作为随机源选择节点
-        pub fn select_random_node() -> Option<T::AccountId> {
-            let nodes = ActiveNodes::<T>::get();
-            if nodes.is_empty() {
-                return None;
+        /// # 参数
+        /// - `request_id`: 请求 ID（作为随机数 subject，同一区块内不同请求结果不同）
+        /// - `attempt`: 分配轮次（首次为 0，故障转移时为转移序号）
+        /// - `count`: 需要的节点数
+        /// - `exclude`: 排除的节点
+        pub fn select_weighted_nodes(
+            request_id: u64,
+            attempt: u8,
+            count: usize,
+            exclude: &[T::AccountId],
+        ) -> Vec<T::AccountId> {
+            let mut candidates: Vec<(T::AccountId, u64)> = ActiveNodes::<T>::get()
+                .into_iter()
+                .filter(|n| !exclude.contains(n))
+                .filter(|n| Self::is_node_active(n))
+                .map(|n| {
+                    let weight = Self::node_selection_weight(&n);
+                    (n, weight)
+                })
+                .collect();
+
+            let mut selected = Vec::with_capacity(count);
+            let mut round: u32 = 0;
+            while selected.len() < count && !candidates.is_empty() {
+                let total = candidates
+                    .iter()
+                    .fold(0u64, |acc, (_, weight)| acc.saturating_add(*weight));
+                let mut point = Self::selection_seed(request_id, attempt, round) % total.max(1);
+
+                let index = candidates
+                    .iter()
+                    .position(|(_, weight)| {
+                        if point < *weight {
+                            true
+                        } else {
+                            point -= *weight;
+                            false
+                        }
+                    })
+                    .unwrap_or(0);
+
+                selected.push(candidates.swap_remove(index).0);
+                round = round.saturating_add(1);
             }
 
-            // 使用当前区块号作为简单随机源
-            let block_number = frame_system::Pallet::<T>::block_number();
-            let index = block_number
-                .try_into()
-                .unwrap_or(0usize)
-                .checked_rem(nodes.len())
-                .unwrap_or(0);
+            selected
+        }
+
+        /// 节点选择权重
+        ///
+        /// 权重 = 质押倍数 × 认证新鲜度 × 历史成功率：
+        /// - 质押倍数：质押额 / 最小质押，取值 1..=`MAX_STAKE_WEIGHT`（未质押或解绑中为 1）
+        /// - 认证新鲜度：认证报告越新越高，取值 10..=100
+        /// - 历史成功率：完成数 / (完成 + 失败 + 超时) 的千分比，无记录为 1000，下限 `MIN_SUCCESS_WEIGHT`
+        pub fn node_selection_weight(account: &T::AccountId) -> u64 {
+            let minimum_stake = T::MinimumStake::get();
+            let stake_weight = NodeStakes::<T>::get(account)
+                .filter(|stake| !stake.is_unbonding && !minimum_stake.is_zero())
+                .map(|stake| (stake.amount / minimum_stake).saturated_into::<u64>())
+                .unwrap_or(0)
+                .clamp(1, MAX_STAKE_WEIGHT);
+
+            let now: u64 = pallet_timestamp::Pallet::<T>::get().try_into().ok().unwrap_or(0);
+            let age = TeeNodes::<T>::get(account)
+                .map(|node| now.saturating_sub(node.attestation.timestamp))
+                .unwrap_or(ATTESTATION_MAX_AGE)
+                .min(ATTESTATION_MAX_AGE);
+            let freshness_weight = 100u64.saturating_sub(age.saturating_mul(90) / ATTESTATION_MAX_AGE);
+
+            let success_weight = NodeStats::<T>::get(account)
+                .map(|stats| {
+                    let total = stats
+                        .completed_requests
+                        .saturating_add(stats.failed_requests)
+                        .saturating_add(stats.timeout_requests);
+                    if total == 0 {
+                        1000
+                    } else {
+                        stats.completed_requests.saturating_mul(1000) / total
+                    }
+                })
+                .unwrap_or(1000)
+                .max(MIN_SUCCESS_WEIGHT);
+
+            stake_weight
+                .saturating_mul(freshness_weight)
+                .saturating_mul(success_weight)
+        }
+
+        /// 节点选择随机数
+        fn selection_seed(request_id: u64, attempt: u8, round: u32) -> u64 {
+            let subject = (NODE_SELECTION_SUBJECT, request_id, attempt, round).encode();
+            let (seed, _) = T::Randomness::random(&subject);
+            u64::decode(&mut TrailingZeroInput::new(seed.as_ref())).unwrap_or(0)
+        }
+
+        /// 记录冗余执行的副本结果
+        ///
+        /// 任一输出哈希达到 `quorum` 时完成请求并惩罚结果不一致的节点；
+        /// 已不可能达成一致时请求以 `ResultMismatch` 失败（无法判定多数，不惩罚）。
+        fn record_replica_result(
+            who: &T::AccountId,
+            request_id: u64,
+            output_hash: [u8; 32],
+            enclave_signature: [u8; 64],
+        ) -> DispatchResult {
+            let mut request = ComputeRequests::<T>::get(request_id).ok_or(Error::<T>::RequestNotFound)?;
+            ensure!(
+                request.status == RequestStatus::Processing,
+                Error::<T>::RequestAlreadyCompleted
+            );
+
+            let mut replicas = RequestReplicas::<T>::get(request_id).ok_or(Error::<T>::RequestNotFound)?;
+            ensure!(replicas.contains(who), Error::<T>::NotAssignedNode);
+            ensure!(!replicas.has_submitted(who), Error::<T>::ResultAlreadySubmitted);
+            replicas
+                .results
+                .try_push((who.clone(), output_hash))
+                .map_err(|_| Error::<T>::ResultAlreadySubmitted)?;
+
+            NodeCurrentRequest::<T>::remove(who);
+
+            Self::deposit_event(Event::ReplicaResultSubmitted {
+                request_id,
+                executor: who.clone(),
+                output_hash,
+            });
+
+            if let Some(agreed_hash) = replicas.agreed_hash() {
+                let expires_at = request.timeout_at;
+                request.status = RequestStatus::Completed;
+                ComputeRequests::<T>::insert(request_id, request);
+
+                let now = pallet_timestamp::Pallet::<T>::get();
+                let timestamp: u64 = now.try_into().ok().unwrap_or(0);
+                ComputeResults::<T>::insert(
+                    request_id,
+                    ComputeResultInfo {
+                        request_id,
+                        executor: who.clone(),
+                        output_hash: agreed_hash,
+                        enclave_signature,
+                        completed_at: timestamp,
+                    },
+                );
+
+                let current_block: u64 = frame_system::Pallet::<T>::block_number()
+                    .try_into()
+                    .ok()
+                    .unwrap_or(0);
+                let mut dissenters = Vec::new();
+                for (node, hash) in replicas.results.iter() {
+                    if *hash == agreed_hash {
+                        NodeStats::<T>::mutate(node, |maybe_stats| {
+                            let stats = maybe_stats.get_or_insert_with(NodeStatistics::default);
+                            stats.completed_requests = stats.completed_requests.saturating_add(1);
+                            stats.last_active_block = current_block;
+                        });
+                    } else {
+                        NodeStats::<T>::mutate(node, |maybe_stats| {
+                            let stats = maybe_stats.get_or_insert_with(NodeStatistics::default);
+                            stats.failed_requests = stats.failed_requests.saturating_add(1);
+                        });
+                        Self::slash_node(node, FailureReason::ResultMismatch);
+                        dissenters.push(node.clone());
+                    }
+                }
+
+                Self::close_replica_request(request_id, &replicas);
+                Self::open_late_replica_check(request_id, &replicas, agreed_hash, expires_at);
+
+                Self::deposit_event(Event::ReplicaConsensusReached {
+                    request_id,
+                    output_hash: agreed_hash,
+                    dissenters,
+                });
+                Self::deposit_event(Event::ComputeResultSubmitted {
+                    request_id,
+                    executor: who.clone(),
+                    output_hash: agreed_hash,
+                });
+            } else if replicas.is_deadlocked() {
+                request.status = RequestStatus::Failed;
+                request.failure_reason = Some(FailureReason::ResultMismatch);
+                ComputeRequests::<T>::insert(request_id, request);
+
+                Self::close_replica_request(request_id, &replicas);
+
+                Self::deposit_event(Event::RequestFailed {
+                    request_id,
+                    reason: FailureReason::ResultMismatch,
+                });
+            } else {
+                RequestReplicas::<T>::insert(request_id, replicas);
+            }
+
+            Ok(())
+        }
+
+        /// 为达成一致时尚未提交结果的副本节点记录迟到结果核对
+        ///
+        /// 核对列表已满时不记录，迟到结果按请求已完成拒绝
+        fn open_late_replica_check(
+            request_id: u64,
+            replicas: &ReplicaSet<T::AccountId>,
+            agreed_hash: [u8; 32],
+            expires_at: BlockNumberFor<T>,
+        ) {
+            let pending: Vec<T::AccountId> = replicas
+                .nodes
+                .iter()
+                .filter(|n| !replicas.has_submitted(n))
+                .cloned()
+                .collect();
+            if pending.is_empty() {
+                return;
+            }
+            if LateReplicaQueue::<T>::try_mutate(|queue| queue.try_push(request_id)).is_ok() {
+                LateReplicaChecks::<T>::insert(
+                    request_id,
+                    LateReplicaCheck {
+                        pending: BoundedVec::truncate_from(pending),
+                        agreed_hash,
+                        expires_at,
+                    },
+                );
+            }
+        }
+
+        /// 记录达成一致后迟到的副本结果
+        ///
+        /// 与最终一致结果相同计为完成，不同则按 `ResultMismatch` 惩罚；
+        /// 超过原请求超时区块后不再接受。
+        fn record_late_replica_result(
+            who: &T::AccountId,
+            request_id: u64,
+            output_hash: [u8; 32],
+        ) -> DispatchResult {
+            let mut check = LateReplicaChecks::<T>::get(request_id).ok_or(Error::<T>::RequestNotFound)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(now < check.expires_at, Error::<T>::RequestTimeout);
+            let index = check
+                .pending
+                .iter()
+                .position(|node| node == who)
+                .ok_or(Error::<T>::NotAssignedNode)?;
+            check.pending.swap_remove(index);
+
+            Self::deposit_event(Event::ReplicaResultSubmitted {
+                request_id,
+                executor: who.clone(),
+                output_hash,
+            });
+
+            if output_hash == check.agreed_hash {
+                let current_block: u64 = now.try_into().ok().unwrap_or(0);
+                NodeStats::<T>::mutate(who, |maybe_stats| {
+                    let stats = maybe_stats.get_or_insert_with(NodeStatistics::default);
+                    stats.completed_requests = stats.completed_requests.saturating_add(1);
+                    stats.last_active_block = current_block;
+                });
+            } else {
+                NodeStats::<T>::mutate(who, |maybe_stats| {
+                    let stats = maybe_stats.get_or_insert_with(NodeStatistics::default);
+                    stats.failed_requests = stats.failed_requests.saturating_add(1);
+                });
+                Self::slash_node(who, FailureReason::ResultMismatch);
+
+                Self::deposit_event(Event::LateReplicaResultMismatch {
+                    request_id,
+                    executor: who.clone(),
+                    output_hash,
+                    agreed_hash: check.agreed_hash,
+                });
+            }
+
+            if check.pending.is_empty() {
+                LateReplicaChecks::<T>::remove(request_id);
+                LateReplicaQueue::<T>::mutate(|queue| {
+                    queue.retain(|&id| id != request_id);
+                });
+            } else {
+                LateReplicaChecks::<T>::insert(request_id, check);
+            }
+
+            Ok(())
+        }
+
+        /// 清理到期的迟到结果核对记录，返回清理数
+        fn prune_late_replica_checks(now: BlockNumberFor<T>) -> u32 {
+            let mut pruned = 0u32;
+            LateReplicaQueue::<T>::mutate(|queue| {
+                queue.retain(|&request_id| {
+                    let expired = LateReplicaChecks::<T>::get(request_id)
+                        .is_none_or(|check| now >= check.expires_at);
+                    if expired {
+                        LateReplicaChecks::<T>::remove(request_id);
+                        pruned = pruned.saturating_add(1);
+                    }
+                    !expired
+                });
+            });
+            pruned
+        }
+
+        /// 为分配队列中的冗余请求抽取副本节点
+        ///
+        /// 随机数须产生于请求提交区块之后，否则留待下一区块；
+        /// 可用节点不足时请求以 `NodeFailure` 失败。返回本区块分配的请求数。
+        pub(crate) fn assign_queued_replicas() -> u32 {
+            let queue = ReplicaSelectionQueue::<T>::get();
+            if queue.is_empty() {
+                return 0;
+            }
+
+            let (_, known_since) = T::Randomness::random(NODE_SELECTION_SUBJECT);
+            let current_block = frame_system::Pallet::<T>::block_number();
+            let timeout_blocks: BlockNumberFor<T> = T::RequestTimeout::get().into();
+            let mut deferred = Vec::new();
+            let mut assigned = 0u32;
+
+            for &(request_id, redundancy) in queue.iter() {
+                // 已取消或已超时的请求直接出队
+                let Some(mut request) = ComputeRequests::<T>::get(request_id) else {
+                    continue;
+                };
+                if request.status != RequestStatus::Pending {
+                    continue;
+                }
+                // 无副本集合的是未指定节点的普通请求
+                let replicas = RequestReplicas::<T>::get(request_id);
+                if known_since <= request.created_at {
+                    deferred.push(request_id);
+                    continue;
+                }
+                assigned = assigned.saturating_add(1);
+
+                let nodes = Self::select_weighted_nodes(request_id, 0, redundancy as usize, &[]);
+                if nodes.len() < redundancy as usize {
+                    request.status = RequestStatus::Failed;
+                    request.failure_reason = Some(FailureReason::NodeFailure);
+                    ComputeRequests::<T>::insert(request_id, request);
+                    match replicas {
+                        Some(ref replicas) => Self::close_replica_request(request_id, replicas),
+                        None => PendingRequests::<T>::mutate(|pending| {
+                            pending.retain(|&id| id != request_id);
+                        }),
+                    }
+
+                    Self::deposit_event(Event::RequestFailed {
+                        request_id,
+                        reason: FailureReason::NodeFailure,
+                    });
+                    continue;
+                }
+
+                // 首个节点记录为主节点，兼容按 assigned_node 查询的客户端
+                request.assigned_node = nodes.first().cloned();
+                request.status = RequestStatus::Processing;
+                request.timeout_at = current_block.saturating_add(timeout_blocks);
+                ComputeRequests::<T>::insert(request_id, request);
+
+                for node in nodes.iter() {
+                    NodeCurrentRequest::<T>::insert(node, request_id);
+                }
+                let quorum = match replicas {
+                    Some(mut replicas) => {
+                        replicas.nodes = BoundedVec::truncate_from(nodes.clone());
+                        let quorum = replicas.quorum;
+                        RequestReplicas::<T>::insert(request_id, replicas);
+                        quorum
+                    }
+                    None => 1,
+                };
+
+                Self::deposit_event(Event::ReplicasAssigned {
+                    request_id,
+                    nodes,
+                    quorum,
+                });
+            }
+
+            ReplicaSelectionQueue::<T>::mutate(|queue| {
+                queue.retain(|(id, _)| deferred.contains(id));
+            });
+
+            assigned
+        }
 
-            nodes.get(index).cloned()
+        /// 结束冗余请求：移出待处理列表，释放所有副本节点
+        fn close_replica_request(request_id: u64, replicas: &ReplicaSet<T::AccountId>) {
+            PendingRequests::<T>::mutate(|pending| {
+                pending.retain(|&id| id != request_id);
+            });
+            for node in replicas.nodes.iter() {
+                if NodeCurrentRequest::<T>::get(node) == Some(request_id) {
+                    NodeCurrentRequest::<T>::remove(node);
+                }
+            }
+            RequestReplicas::<T>::remove(request_id);
         }
 
         /// 处理超时请求
@@ -1712,6 +2962,28 @@ pub mod pallet {
                 if let Some(mut request) = ComputeRequests::<T>::get(request_id) {
                     // 检查是否超时
                     if current_block >= request.timeout_at {
+                        // 冗余请求不做故障转移：惩罚未提交结果的副本节点后直接失败
+                        if let Some(replicas) = RequestReplicas::<T>::get(request_id) {
+                            for node in replicas.nodes.iter().filter(|n| !replicas.has_submitted(n)) {
+                                NodeStats::<T>::mutate(node, |maybe_stats| {
+                                    let stats = maybe_stats.get_or_insert_with(NodeStatistics::default);
+                                    stats.timeout_requests = stats.timeout_requests.saturating_add(1);
+                                });
+                                Self::slash_node(node, FailureReason::Timeout);
+                            }
+
+                            request.status = RequestStatus::Failed;
+                            request.failure_reason = Some(FailureReason::Timeout);
+                            ComputeRequests::<T>::insert(request_id, request);
+                            Self::close_replica_request(request_id, &replicas);
+
+                            Self::deposit_event(Event::RequestFailed {
+                                request_id,
+                                reason: FailureReason::Timeout,
+                            });
+                            continue;
+                        }
+
                         let old_node = request.assigned_node.clone();
 
                         // 清除旧节点的当前请求
@@ -1731,7 +3003,11 @@ pub mod pallet {
                         // 检查是否可以故障转移
                         if request.failover_count < MAX_FAILOVER_COUNT {
                             // 尝试分配新节点
-                            let new_node = Self::select_random_node_excluding(old_node.as_ref());
+                            let new_node = Self::select_random_node_excluding(
+                                request_id,
+                                request.failover_count,
+                                old_node.as_ref(),
+                            );
 
                             if let Some(ref new_n) = new_node {
                                 // 更新请求
@@ -1792,27 +3068,16 @@ pub mod pallet {
         }
 
         /// 选择一个活跃节点（排除指定节点）
-        fn select_random_node_excluding(exclude: Option<&T::AccountId>) -> Option<T::AccountId> {
-            let nodes = ActiveNodes::<T>::get();
-            let filtered: Vec<_> = nodes
-                .iter()
-                .filter(|n| exclude.map_or(true, |e| *n != e))
-                .filter(|n| Self::is_node_active(n))
-                .cloned()
-                .collect();
-
-            if filtered.is_empty() {
-                return None;
-            }
-
-            let block_number = frame_system::Pallet::<T>::block_number();
-            let index = block_number
-                .try_into()
-                .unwrap_or(0usize)
-                .checked_rem(filtered.len())
-                .unwrap_or(0);
-
-            filtered.get(index).cloned()
+        ///
+        /// 故障转移次数参与随机数 subject，每次转移独立抽取
+        fn select_random_node_excluding(
+            request_id: u64,
+            failover_count: u8,
+            exclude: Option<&T::AccountId>,
+        ) -> Option<T::AccountId> {
+            let exclude: Vec<T::AccountId> = exclude.cloned().into_iter().collect();
+            Self::select_weighted_nodes(request_id, failover_count.saturating_add(1), 1, &exclude)
+                .pop()
         }
 
         /// 惩罚节点
@@ -104,6 +104,60 @@ parameter_types! {
     pub const BaseComputeFee: u128 = 100;
     pub const SlashRatio: u32 = 100; // 10%
     pub const MaxBatchSize: u32 = 10;
+    pub static AcceptConfigurationNeeded: bool = false;
+}
+
+/// 模拟随机数生成器（subject 的 Blake2 哈希，不同 subject 结果不同）
+pub struct TestRandomness;
+
+impl frame_support::traits::Randomness<H256, u64> for TestRandomness {
+    fn random(subject: &[u8]) -> (H256, u64) {
+        (H256::from(sp_io::hashing::blake2_256(subject)), System::block_number())
+    }
+}
+
+/// 模拟 Unix 时间（mock 时间戳单位为秒）
+pub struct TestUnixTime;
+
+impl frame_support::traits::UnixTime for TestUnixTime {
+    fn now() -> core::time::Duration {
+        core::time::Duration::from_secs(pallet_timestamp::Now::<Test>::get())
+    }
+}
+
+parameter_types! {
+    /// 统一公钥目录中的 TeeEnclave 公钥
+    pub static EnclaveDirectoryKeys: Vec<(u64, [u8; 32])> = Vec::new();
+}
+
+/// 测试用公钥目录（仅支持 TeeEnclave 用途）
+pub struct TestKeyDirectory;
+
+impl pallet_key_directory::traits::KeyDirectory<u64> for TestKeyDirectory {
+    fn current_key(
+        who: &u64,
+        purpose: pallet_key_directory::types::KeyPurpose,
+    ) -> Option<pallet_key_directory::types::DirectoryKey> {
+        if purpose != pallet_key_directory::types::KeyPurpose::TeeEnclave {
+            return None;
+        }
+        EnclaveDirectoryKeys::get()
+            .into_iter()
+            .find(|(account, _)| account == who)
+            .map(|(_, key)| pallet_key_directory::types::DirectoryKey {
+                version: 1,
+                algorithm: pallet_key_directory::types::KeyAlgorithm::Ed25519,
+                public_key: key.to_vec(),
+            })
+    }
+
+    fn is_usable_key(
+        who: &u64,
+        purpose: pallet_key_directory::types::KeyPurpose,
+        public_key: &[u8],
+    ) -> bool {
+        Self::current_key(who, purpose).map_or(false, |key| key.public_key == public_key)
+    }
 }
 
 impl pallet_tee_privacy::Config for Test {
@@ -118,6 +172,10 @@ impl pallet_tee_privacy::Config for Test {
     type BaseComputeFee = BaseComputeFee;
     type SlashRatio = SlashRatio;
     type MaxBatchSize = MaxBatchSize;
+    type AcceptConfigurationNeeded = AcceptConfigurationNeeded;
+    type Randomness = TestRandomness;
+    type UnixTime = TestUnixTime;
+    type KeyDirectory = TestKeyDirectory;
     type WeightInfo = ();
 }
 
@@ -213,5 +271,6 @@ pub fn run_to_block(n: u64) {
         TeePrivacy::on_finalize(current);
         System::set_block_number(current + 1);
         <frame_system::Pallet<Test> as Hooks<u64>>::on_initialize(current + 1);
+        TeePrivacy::on_initialize(current + 1);
     }
 }
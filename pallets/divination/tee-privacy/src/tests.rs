@@ -383,10 +383,10 @@ fn active_node_count_works() {
 }
 
 #[test]
-fn select_random_node_works() {
+fn select_weighted_nodes_works() {
     new_test_ext().execute_with(|| {
-        // 没有节点时返回 None
-        assert!(TeePrivacy::select_random_node().is_none());
+        // 没有节点时返回空
+        assert!(TeePrivacy::select_weighted_nodes(0, 0, 1, &[]).is_empty());
 
         // 注册节点后应该能选择
         let attestation = create_test_attestation();
@@ -396,9 +396,8 @@ fn select_random_node_works() {
             attestation,
         ));
 
-        let selected = TeePrivacy::select_random_node();
-        assert!(selected.is_some());
-        assert_eq!(selected.unwrap(), 1);
+        let selected = TeePrivacy::select_weighted_nodes(0, 0, 1, &[]);
+        assert_eq!(selected, vec![1]);
     });
 }
 
@@ -643,20 +642,39 @@ fn submit_compute_request_works() {
             None, // 自动分配节点
         ));
 
-        // 验证请求存储
+        // 验证请求存储：提交区块内尚未分配节点
         let request = TeePrivacy::compute_requests(0).expect("Request should exist");
         assert_eq!(request.requester, requester);
         assert_eq!(request.input_hash, input_hash);
         assert_eq!(request.compute_type_id, 0);
-        assert_eq!(request.status, RequestStatus::Processing);
-        assert_eq!(request.assigned_node, Some(1));
+        assert_eq!(request.status, RequestStatus::Pending);
+        assert_eq!(request.assigned_node, None);
+        assert_eq!(TeePrivacy::replica_selection_queue().to_vec(), vec![(0, 1)]);
+        assert_eq!(TeePrivacy::node_current_request(1), None);
 
         // 验证待处理列表
         let pending = TeePrivacy::pending_requests();
         assert!(pending.contains(&0));
 
+        // 下一区块抽取节点
+        run_to_block(2);
+
+        let request = TeePrivacy::compute_requests(0).unwrap();
+        assert_eq!(request.status, RequestStatus::Processing);
+        assert_eq!(request.assigned_node, Some(1));
+        assert!(TeePrivacy::request_replicas(0).is_none());
+        assert!(TeePrivacy::replica_selection_queue().is_empty());
+
         // 验证节点当前请求
         assert_eq!(TeePrivacy::node_current_request(1), Some(0));
+        System::assert_has_event(
+            Event::ReplicasAssigned {
+                request_id: 0,
+                nodes: vec![1],
+                quorum: 1,
+            }
+            .into(),
+        );
     });
 }
 
@@ -949,13 +967,25 @@ fn submit_batch_compute_requests_works() {
             requests,
         ));
 
-        // 验证所有请求都已创建
+        // 验证所有请求都已创建，等待下一区块分配节点
         for i in 0..3u64 {
             let request = TeePrivacy::compute_requests(i).expect("Request should exist");
             assert_eq!(request.requester, requester);
             assert_eq!(request.compute_type_id, i as u8);
+            assert_eq!(request.status, RequestStatus::Pending);
+            assert_eq!(request.assigned_node, None);
+        }
+        assert_eq!(
+            TeePrivacy::replica_selection_queue().to_vec(),
+            vec![(0, 1), (1, 1), (2, 1)]
+        );
+
+        run_to_block(2);
+
+        for i in 0..3u64 {
+            let request = TeePrivacy::compute_requests(i).unwrap();
             assert_eq!(request.status, RequestStatus::Processing);
-            assert_eq!(request.assigned_node, Some(1)); // 所有请求分配给同一节点
+            assert_eq!(request.assigned_node, Some(1));
         }
 
         // 验证待处理列表
@@ -1052,6 +1082,7 @@ fn submit_batch_compute_results_works() {
             RuntimeOrigin::signed(requester),
             requests,
         ));
+        run_to_block(2);
 
         // 节点提交批量结果
         let results = vec![
@@ -1166,6 +1197,7 @@ fn submit_batch_compute_results_partial_success() {
             RuntimeOrigin::signed(10),
             requests,
         ));
+        run_to_block(2);
 
         // 提交结果：一个有效，一个无效（请求ID不存在）
         let results = vec![
@@ -1557,3 +1589,901 @@ fn get_audit_logs_range_works() {
         assert_eq!(logs[2].id, 3);
     });
 }
+
+// ============================================================================
+// 加权节点选择与冗余执行测试
+// ============================================================================
+
+#[test]
+fn node_selection_weight_reflects_stake_freshness_and_success() {
+    new_test_ext().execute_with(|| {
+        register_and_stake_node(1);
+        register_and_stake_node(2);
+        assert_ok!(TeePrivacy::stake(RuntimeOrigin::signed(1), 9 * MinimumStake::get()));
+
+        // 质押 10 倍 vs 1 倍，认证新鲜、无历史记录
+        assert_eq!(TeePrivacy::node_selection_weight(&1), 10 * 100 * 1000);
+        assert_eq!(TeePrivacy::node_selection_weight(&2), 100 * 1000);
+
+        // 成功率 50%
+        crate::NodeStats::<Test>::insert(
+            2,
+            NodeStatistics {
+                completed_requests: 5,
+                timeout_requests: 5,
+                ..Default::default()
+            },
+        );
+        assert_eq!(TeePrivacy::node_selection_weight(&2), 100 * 500);
+
+        // 认证报告过去半个有效期，新鲜度降至 55
+        pallet_timestamp::Now::<Test>::put(1704067200u64 + crate::ATTESTATION_MAX_AGE / 2);
+        assert_eq!(TeePrivacy::node_selection_weight(&1), 10 * 55 * 1000);
+    });
+}
+
+#[test]
+fn weighted_selection_is_unpredictable_and_favours_stake() {
+    new_test_ext().execute_with(|| {
+        register_and_stake_node(1);
+        register_and_stake_node(2);
+        assert_ok!(TeePrivacy::stake(RuntimeOrigin::signed(1), 9 * MinimumStake::get()));
+
+        // 同一区块内，不同请求 ID 独立抽取
+        let mut picks = [0u32; 3];
+        for request_id in 0..500u64 {
+            let selected = TeePrivacy::select_weighted_nodes(request_id, 0, 1, &[]);
+            picks[selected[0] as usize] += 1;
+        }
+
+        assert!(picks[2] > 0, "low-stake node must still be selectable");
+        assert!(picks[1] > picks[2] * 4, "stake weighting ignored: {:?}", picks);
+
+        // 无放回选择，且遵守排除列表
+        let both = TeePrivacy::select_weighted_nodes(7, 0, 3, &[]);
+        assert_eq!(both.len(), 2);
+        assert!(both.contains(&1) && both.contains(&2));
+        assert_eq!(TeePrivacy::select_weighted_nodes(7, 0, 1, &[1]), vec![2]);
+    });
+}
+
+#[test]
+fn redundant_request_validates_parameters() {
+    new_test_ext().execute_with(|| {
+        register_and_stake_node(1);
+        register_and_stake_node(2);
+
+        // 至少两个副本
+        assert_noop!(
+            TeePrivacy::submit_redundant_compute_request(RuntimeOrigin::signed(10), 0, [1u8; 32], 1, 1),
+            Error::<Test>::InvalidRedundancy
+        );
+        // k 必须超过半数
+        assert_noop!(
+            TeePrivacy::submit_redundant_compute_request(RuntimeOrigin::signed(10), 0, [1u8; 32], 4, 2),
+            Error::<Test>::InvalidRedundancy
+        );
+        // 活跃节点不足
+        assert_noop!(
+            TeePrivacy::submit_redundant_compute_request(RuntimeOrigin::signed(10), 0, [1u8; 32], 3, 2),
+            Error::<Test>::InsufficientNodes
+        );
+    });
+}
+
+#[test]
+fn redundant_request_reaches_consensus_and_slashes_dissenter() {
+    new_test_ext().execute_with(|| {
+        for node in 1..=4 {
+            register_and_stake_node(node);
+        }
+
+        assert_ok!(TeePrivacy::submit_redundant_compute_request(
+            RuntimeOrigin::signed(10),
+            0,
+            [42u8; 32],
+            3,
+            2,
+        ));
+        run_to_block(2);
+
+        let replicas = TeePrivacy::request_replicas(0).expect("replicas assigned");
+        assert_eq!(replicas.nodes.len(), 3);
+        assert_eq!(replicas.quorum, 2);
+        let nodes: Vec<u64> = replicas.nodes.to_vec();
+        let outsider = (1..=4).find(|n| !nodes.contains(n)).unwrap();
+        for node in nodes.iter() {
+            assert_eq!(TeePrivacy::node_current_request(node), Some(0));
+        }
+        let request = TeePrivacy::compute_requests(0).unwrap();
+        assert_eq!(request.assigned_node, Some(nodes[0]));
+
+        // 非副本节点不能提交
+        assert_noop!(
+            TeePrivacy::submit_compute_result(RuntimeOrigin::signed(outsider), 0, [7u8; 32], [0u8; 64]),
+            Error::<Test>::NotAssignedNode
+        );
+
+        let dissenter = nodes[1];
+        let dissenter_stake = TeePrivacy::node_stakes(dissenter).unwrap().amount;
+
+        assert_ok!(TeePrivacy::submit_compute_result(RuntimeOrigin::signed(nodes[0]), 0, [7u8; 32], [0u8; 64]));
+        assert_noop!(
+            TeePrivacy::submit_compute_result(RuntimeOrigin::signed(nodes[0]), 0, [7u8; 32], [0u8; 64]),
+            Error::<Test>::ResultAlreadySubmitted
+        );
+        assert_ok!(TeePrivacy::submit_compute_result(RuntimeOrigin::signed(dissenter), 0, [9u8; 32], [0u8; 64]));
+
+        // 仅两个结果且不一致，请求仍在处理中
+        assert_eq!(TeePrivacy::compute_requests(0).unwrap().status, RequestStatus::Processing);
+
+        assert_ok!(TeePrivacy::submit_compute_result(RuntimeOrigin::signed(nodes[2]), 0, [7u8; 32], [0u8; 64]));
+
+        let request = TeePrivacy::compute_requests(0).unwrap();
+        assert_eq!(request.status, RequestStatus::Completed);
+        assert_eq!(TeePrivacy::compute_results(0).unwrap().output_hash, [7u8; 32]);
+        assert!(TeePrivacy::request_replicas(0).is_none());
+        assert!(!TeePrivacy::pending_requests().contains(&0));
+
+        // 结果不一致的节点被惩罚
+        assert!(TeePrivacy::node_stakes(dissenter).unwrap().amount < dissenter_stake);
+        let stats = TeePrivacy::node_stats(dissenter).unwrap();
+        assert_eq!(stats.slash_count, 1);
+        assert_eq!(stats.failed_requests, 1);
+        assert_eq!(TeePrivacy::node_stats(nodes[0]).unwrap().completed_requests, 1);
+        assert_eq!(TeePrivacy::node_stats(nodes[2]).unwrap().slash_count, 0);
+
+        System::assert_has_event(
+            Event::ReplicaConsensusReached {
+                request_id: 0,
+                output_hash: [7u8; 32],
+                dissenters: vec![dissenter],
+            }
+            .into(),
+        );
+        System::assert_has_event(
+            Event::Slashed {
+                account: dissenter,
+                amount: 100,
+                reason: FailureReason::ResultMismatch,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn redundant_request_fails_without_majority() {
+    new_test_ext().execute_with(|| {
+        for node in 1..=3 {
+            register_and_stake_node(node);
+        }
+
+        assert_ok!(TeePrivacy::submit_redundant_compute_request(
+            RuntimeOrigin::signed(10),
+            0,
+            [42u8; 32],
+            3,
+            2,
+        ));
+        run_to_block(2);
+
+        for (i, node) in (1..=3u64).enumerate() {
+            assert_ok!(TeePrivacy::submit_compute_result(
+                RuntimeOrigin::signed(node),
+                0,
+                [i as u8; 32],
+                [0u8; 64],
+            ));
+        }
+
+        let request = TeePrivacy::compute_requests(0).unwrap();
+        assert_eq!(request.status, RequestStatus::Failed);
+        assert_eq!(request.failure_reason, Some(FailureReason::ResultMismatch));
+        assert!(TeePrivacy::request_replicas(0).is_none());
+
+        // 无法判定多数时不惩罚
+        assert_eq!(TeePrivacy::total_slashed(), 0);
+    });
+}
+
+#[test]
+fn redundant_request_timeout_slashes_silent_replicas() {
+    new_test_ext().execute_with(|| {
+        for node in 1..=3 {
+            register_and_stake_node(node);
+        }
+
+        assert_ok!(TeePrivacy::submit_redundant_compute_request(
+            RuntimeOrigin::signed(10),
+            0,
+            [42u8; 32],
+            3,
+            2,
+        ));
+        run_to_block(2);
+        let nodes = TeePrivacy::request_replicas(0).unwrap().nodes.to_vec();
+        assert_ok!(TeePrivacy::submit_compute_result(RuntimeOrigin::signed(nodes[0]), 0, [7u8; 32], [0u8; 64]));
+
+        let timeout_at = TeePrivacy::compute_requests(0).unwrap().timeout_at;
+        run_to_block(timeout_at + 1);
+
+        let request = TeePrivacy::compute_requests(0).unwrap();
+        assert_eq!(request.status, RequestStatus::Failed);
+        assert_eq!(request.failure_reason, Some(FailureReason::Timeout));
+        assert_eq!(request.failover_count, 0);
+        assert!(TeePrivacy::request_replicas(0).is_none());
+
+        assert!(TeePrivacy::node_stats(nodes[0]).map_or(true, |s| s.slash_count == 0));
+        for node in &nodes[1..] {
+            let stats = TeePrivacy::node_stats(node).unwrap();
+            assert_eq!(stats.slash_count, 1);
+            assert_eq!(stats.timeout_requests, 1);
+            assert_eq!(TeePrivacy::node_current_request(node), None);
+        }
+    });
+}
+
+#[test]
+fn redundant_request_selects_replicas_after_submission_block() {
+    new_test_ext().execute_with(|| {
+        for node in 1..=3 {
+            register_and_stake_node(node);
+        }
+
+        assert_ok!(TeePrivacy::submit_redundant_compute_request(
+            RuntimeOrigin::signed(10),
+            0,
+            [42u8; 32],
+            3,
+            2,
+        ));
+
+        // 提交区块内不分配节点，随机数尚未产生
+        let request = TeePrivacy::compute_requests(0).unwrap();
+        assert_eq!(request.status, RequestStatus::Pending);
+        assert_eq!(request.assigned_node, None);
+        assert!(TeePrivacy::request_replicas(0).unwrap().nodes.is_empty());
+        assert_eq!(TeePrivacy::replica_selection_queue().to_vec(), vec![(0, 3)]);
+        assert_eq!(TeePrivacy::node_current_request(1), None);
+        assert_noop!(
+            TeePrivacy::submit_compute_result(RuntimeOrigin::signed(1), 0, [7u8; 32], [0u8; 64]),
+            Error::<Test>::RequestAlreadyCompleted
+        );
+
+        run_to_block(2);
+
+        let request = TeePrivacy::compute_requests(0).unwrap();
+        assert_eq!(request.status, RequestStatus::Processing);
+        assert_eq!(request.timeout_at, 2 + RequestTimeout::get() as u64);
+        let nodes = TeePrivacy::request_replicas(0).unwrap().nodes.to_vec();
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(request.assigned_node, Some(nodes[0]));
+        assert!(TeePrivacy::replica_selection_queue().is_empty());
+        System::assert_has_event(
+            Event::ReplicasAssigned {
+                request_id: 0,
+                nodes,
+                quorum: 2,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn auto_assigned_request_selects_node_after_submission_block() {
+    new_test_ext().execute_with(|| {
+        for node in 1..=3 {
+            register_and_stake_node(node);
+        }
+
+        assert_ok!(TeePrivacy::submit_compute_request(
+            RuntimeOrigin::signed(10),
+            0,
+            [42u8; 32],
+            None,
+        ));
+
+        // 提交区块内任何节点都不能提交结果
+        for node in 1..=3 {
+            assert_noop!(
+                TeePrivacy::submit_compute_result(RuntimeOrigin::signed(node), 0, [7u8; 32], [0u8; 64]),
+                Error::<Test>::RequestAlreadyCompleted
+            );
+        }
+
+        run_to_block(2);
+
+        let request = TeePrivacy::compute_requests(0).unwrap();
+        let node = request.assigned_node.expect("node assigned after submission block");
+        assert_eq!(request.status, RequestStatus::Processing);
+        assert_eq!(request.timeout_at, 2 + RequestTimeout::get() as u64);
+        assert_eq!(TeePrivacy::node_current_request(node), Some(0));
+
+        assert_ok!(TeePrivacy::submit_compute_result(
+            RuntimeOrigin::signed(node),
+            0,
+            [7u8; 32],
+            [0u8; 64],
+        ));
+        assert_eq!(TeePrivacy::compute_requests(0).unwrap().status, RequestStatus::Completed);
+    });
+}
+
+#[test]
+fn auto_assigned_request_fails_when_nodes_leave_before_selection() {
+    new_test_ext().execute_with(|| {
+        register_and_stake_node(1);
+
+        assert_ok!(TeePrivacy::submit_compute_request(
+            RuntimeOrigin::signed(10),
+            0,
+            [42u8; 32],
+            None,
+        ));
+        assert_ok!(TeePrivacy::update_node_status(RuntimeOrigin::signed(1), TeeNodeStatus::Suspended));
+
+        run_to_block(2);
+
+        let request = TeePrivacy::compute_requests(0).unwrap();
+        assert_eq!(request.status, RequestStatus::Failed);
+        assert_eq!(request.failure_reason, Some(FailureReason::NodeFailure));
+        assert!(!TeePrivacy::pending_requests().contains(&0));
+        assert!(TeePrivacy::replica_selection_queue().is_empty());
+    });
+}
+
+#[test]
+fn redundant_request_fails_when_nodes_leave_before_selection() {
+    new_test_ext().execute_with(|| {
+        for node in 1..=3 {
+            register_and_stake_node(node);
+        }
+
+        assert_ok!(TeePrivacy::submit_redundant_compute_request(
+            RuntimeOrigin::signed(10),
+            0,
+            [42u8; 32],
+            3,
+            2,
+        ));
+        assert_ok!(TeePrivacy::update_node_status(RuntimeOrigin::signed(3), TeeNodeStatus::Suspended));
+
+        run_to_block(2);
+
+        let request = TeePrivacy::compute_requests(0).unwrap();
+        assert_eq!(request.status, RequestStatus::Failed);
+        assert_eq!(request.failure_reason, Some(FailureReason::NodeFailure));
+        assert!(TeePrivacy::request_replicas(0).is_none());
+        assert!(!TeePrivacy::pending_requests().contains(&0));
+        assert!(TeePrivacy::replica_selection_queue().is_empty());
+    });
+}
+
+#[test]
+fn cancelled_redundant_request_leaves_selection_queue() {
+    new_test_ext().execute_with(|| {
+        for node in 1..=3 {
+            register_and_stake_node(node);
+        }
+
+        assert_ok!(TeePrivacy::submit_redundant_compute_request(
+            RuntimeOrigin::signed(10),
+            0,
+            [42u8; 32],
+            3,
+            2,
+        ));
+        assert_ok!(TeePrivacy::cancel_compute_request(RuntimeOrigin::signed(10), 0));
+        assert!(TeePrivacy::replica_selection_queue().is_empty());
+
+        run_to_block(2);
+
+        assert!(TeePrivacy::request_replicas(0).is_none());
+        for node in 1..=3 {
+            assert_eq!(TeePrivacy::node_current_request(node), None);
+        }
+    });
+}
+
+#[test]
+fn late_replica_results_are_checked_against_consensus() {
+    new_test_ext().execute_with(|| {
+        for node in 1..=5 {
+            register_and_stake_node(node);
+        }
+
+        assert_ok!(TeePrivacy::submit_redundant_compute_request(
+            RuntimeOrigin::signed(10),
+            0,
+            [42u8; 32],
+            5,
+            3,
+        ));
+        run_to_block(2);
+
+        let nodes = TeePrivacy::request_replicas(0).unwrap().nodes.to_vec();
+        for node in &nodes[..3] {
+            assert_ok!(TeePrivacy::submit_compute_result(RuntimeOrigin::signed(*node), 0, [7u8; 32], [0u8; 64]));
+        }
+        assert_eq!(TeePrivacy::compute_requests(0).unwrap().status, RequestStatus::Completed);
+
+        let check = TeePrivacy::late_replica_checks(0).expect("late check opened");
+        assert_eq!(check.agreed_hash, [7u8; 32]);
+        assert_eq!(check.pending.len(), 2);
+
+        // 迟到且不一致的节点被惩罚
+        let liar = nodes[3];
+        let liar_stake = TeePrivacy::node_stakes(liar).unwrap().amount;
+        assert_ok!(TeePrivacy::submit_compute_result(RuntimeOrigin::signed(liar), 0, [9u8; 32], [0u8; 64]));
+        assert!(TeePrivacy::node_stakes(liar).unwrap().amount < liar_stake);
+        let stats = TeePrivacy::node_stats(liar).unwrap();
+        assert_eq!(stats.slash_count, 1);
+        assert_eq!(stats.failed_requests, 1);
+        System::assert_has_event(
+            Event::LateReplicaResultMismatch {
+                request_id: 0,
+                executor: liar,
+                output_hash: [9u8; 32],
+                agreed_hash: [7u8; 32],
+            }
+            .into(),
+        );
+
+        // 同一节点不能再次提交
+        assert_noop!(
+            TeePrivacy::submit_compute_result(RuntimeOrigin::signed(liar), 0, [7u8; 32], [0u8; 64]),
+            Error::<Test>::NotAssignedNode
+        );
+
+        // 迟到但一致的节点计为完成
+        let honest = nodes[4];
+        assert_ok!(TeePrivacy::submit_compute_result(RuntimeOrigin::signed(honest), 0, [7u8; 32], [0u8; 64]));
+        let stats = TeePrivacy::node_stats(honest).unwrap();
+        assert_eq!(stats.completed_requests, 1);
+        assert_eq!(stats.slash_count, 0);
+
+        // 全部核对完成后记录移除，最终结果不变
+        assert!(TeePrivacy::late_replica_checks(0).is_none());
+        assert!(crate::LateReplicaQueue::<Test>::get().is_empty());
+        assert_eq!(TeePrivacy::compute_results(0).unwrap().output_hash, [7u8; 32]);
+    });
+}
+
+#[test]
+fn late_replica_check_expires_at_request_timeout() {
+    new_test_ext().execute_with(|| {
+        for node in 1..=3 {
+            register_and_stake_node(node);
+        }
+
+        assert_ok!(TeePrivacy::submit_redundant_compute_request(
+            RuntimeOrigin::signed(10),
+            0,
+            [42u8; 32],
+            3,
+            2,
+        ));
+        run_to_block(2);
+
+        let nodes = TeePrivacy::request_replicas(0).unwrap().nodes.to_vec();
+        for node in &nodes[..2] {
+            assert_ok!(TeePrivacy::submit_compute_result(RuntimeOrigin::signed(*node), 0, [7u8; 32], [0u8; 64]));
+        }
+        assert!(TeePrivacy::late_replica_checks(0).is_some());
+
+        let timeout_at = TeePrivacy::compute_requests(0).unwrap().timeout_at;
+        run_to_block(timeout_at);
+
+        assert!(TeePrivacy::late_replica_checks(0).is_none());
+        assert!(crate::LateReplicaQueue::<Test>::get().is_empty());
+        assert_noop!(
+            TeePrivacy::submit_compute_result(RuntimeOrigin::signed(nodes[2]), 0, [9u8; 32], [0u8; 64]),
+            Error::<Test>::RequestAlreadyCompleted
+        );
+        assert!(TeePrivacy::node_stats(nodes[2]).is_none_or(|s| s.slash_count == 0));
+    });
+}
+
+#[test]
+fn register_requires_directory_enclave_key_when_published() {
+    new_test_ext().execute_with(|| {
+        EnclaveDirectoryKeys::mutate(|keys| keys.push((1, [42u8; 32])));
+
+        assert_noop!(
+            TeePrivacy::register_tee_node(
+                RuntimeOrigin::signed(1),
+                [43u8; 32],
+                create_test_attestation(),
+            ),
+            Error::<Test>::EnclaveKeyMismatch
+        );
+        assert_ok!(TeePrivacy::register_tee_node(
+            RuntimeOrigin::signed(1),
+            [42u8; 32],
+            create_test_attestation(),
+        ));
+
+        // 未在目录发布公钥的运营者不受影响
+        assert_ok!(TeePrivacy::register_tee_node(
+            RuntimeOrigin::signed(2),
+            [44u8; 32],
+            create_test_attestation(),
+        ));
+    });
+}
+
+#[test]
+fn directory_enclave_key_changes_gate_node() {
+    use pallet_key_directory::{traits::KeyChangeHandler, types::KeyPurpose};
+
+    new_test_ext().execute_with(|| {
+        EnclaveDirectoryKeys::mutate(|keys| keys.push((1, [42u8; 32])));
+        assert_ok!(TeePrivacy::register_tee_node(
+            RuntimeOrigin::signed(1),
+            [42u8; 32],
+            create_test_attestation(),
+        ));
+        assert!(TeePrivacy::is_node_active(&1));
+
+        <TeePrivacy as KeyChangeHandler<u64>>::on_key_rotated(&1, KeyPurpose::TeeEnclave, 1, 2);
+        System::assert_last_event(
+            Event::EnclaveKeyRotationRequired { account: 1, new_version: 2 }.into(),
+        );
+
+        // 轮换窗口结束后旧公钥失效，节点不再参与分配，也不能恢复
+        EnclaveDirectoryKeys::set(vec![(1, [50u8; 32])]);
+        assert!(!TeePrivacy::is_node_active(&1));
+        assert_ok!(TeePrivacy::update_node_status(
+            RuntimeOrigin::signed(1),
+            TeeNodeStatus::Suspended
+        ));
+        assert_noop!(
+            TeePrivacy::update_node_status(RuntimeOrigin::signed(1), TeeNodeStatus::Active),
+            Error::<Test>::EnclaveKeyMismatch
+        );
+    });
+}
+
+#[test]
+fn directory_enclave_key_revocation_suspends_node() {
+    use pallet_key_directory::{traits::KeyChangeHandler, types::KeyPurpose};
+
+    new_test_ext().execute_with(|| {
+        EnclaveDirectoryKeys::mutate(|keys| keys.push((1, [42u8; 32])));
+        assert_ok!(TeePrivacy::register_tee_node(
+            RuntimeOrigin::signed(1),
+            [42u8; 32],
+            create_test_attestation(),
+        ));
+
+        // 加密用途吊销不影响节点
+        <TeePrivacy as KeyChangeHandler<u64>>::on_key_revoked(&1, KeyPurpose::Encryption, 1);
+        assert_eq!(TeePrivacy::tee_nodes(1).unwrap().status, TeeNodeStatus::Active);
+
+        EnclaveDirectoryKeys::take();
+        <TeePrivacy as KeyChangeHandler<u64>>::on_key_revoked(&1, KeyPurpose::TeeEnclave, 1);
+        assert_eq!(TeePrivacy::tee_nodes(1).unwrap().status, TeeNodeStatus::Suspended);
+        assert!(!TeePrivacy::active_nodes().contains(&1));
+        assert!(TeePrivacy::enclave_key_revoked(1).is_some());
+
+        // 目录中已无公钥，但吊销记录阻止恢复
+        assert_noop!(
+            TeePrivacy::update_node_status(RuntimeOrigin::signed(1), TeeNodeStatus::Active),
+            Error::<Test>::EnclaveKeyRevoked
+        );
+
+        assert_ok!(TeePrivacy::deregister_tee_node(RuntimeOrigin::signed(1)));
+        assert!(TeePrivacy::enclave_key_revoked(1).is_none());
+    });
+}
+
+// ============================================================================
+// DCAP Quote 认证测试
+// ============================================================================
+
+mod dcap {
+    use super::*;
+    use frame_support::{traits::Get, BoundedVec};
+    use sp_runtime::DispatchError;
+
+    const ROOT_CA: &[u8] = include_bytes!("../../tee-dcap/tests/fixtures/root_ca.der");
+    const ROOT_CA_CRL: &[u8] = include_bytes!("../../tee-dcap/tests/fixtures/root_ca_crl.der");
+    const PCK_CRL: &[u8] = include_bytes!("../../tee-dcap/tests/fixtures/pck_crl.der");
+    const PCK_CRL_ISSUER_CHAIN: &[u8] =
+        include_bytes!("../../tee-dcap/tests/fixtures/pck_crl_issuer_chain.pem");
+    const TCB_INFO: &[u8] = include_bytes!("../../tee-dcap/tests/fixtures/tcb_info.json");
+    const QE_IDENTITY: &[u8] = include_bytes!("../../tee-dcap/tests/fixtures/qe_identity.json");
+    const TCB_SIGNING_CHAIN: &[u8] =
+        include_bytes!("../../tee-dcap/tests/fixtures/tcb_signing_chain.pem");
+    const QUOTE_V3: &[u8] = include_bytes!("../../tee-dcap/tests/fixtures/quote_v3.bin");
+    const QUOTE_V4: &[u8] = include_bytes!("../../tee-dcap/tests/fixtures/quote_v4.bin");
+    const QUOTE_V3_OUTDATED: &[u8] =
+        include_bytes!("../../tee-dcap/tests/fixtures/quote_v3_outdated.bin");
+    const QUOTE_V3_CONFIG: &[u8] =
+        include_bytes!("../../tee-dcap/tests/fixtures/quote_v3_config.bin");
+
+    /// 夹具 Quote 绑定的 Enclave 公钥
+    const ENCLAVE_PUBKEY: [u8; 32] = [0x11; 32];
+    /// 夹具平台 FMSPC
+    const FMSPC: [u8; 6] = [0x00, 0x90, 0x6E, 0xD5, 0x00, 0x00];
+
+    fn bytes<S: Get<u32>>(data: &[u8]) -> BoundedVec<u8, S> {
+        BoundedVec::try_from(data.to_vec()).unwrap()
+    }
+
+    /// 切换到抵押品有效期内（2025-06-01）并允许夹具 Enclave 度量值
+    fn setup() {
+        pallet_timestamp::Now::<Test>::put(1_748_736_000u64);
+        assert_ok!(TeePrivacy::add_allowed_mr_enclave(RuntimeOrigin::root(), [0xAA; 32]));
+        assert_ok!(TeePrivacy::add_allowed_mr_signer(RuntimeOrigin::root(), [0xBB; 32]));
+    }
+
+    fn upload_collateral() {
+        assert_ok!(TeePrivacy::set_dcap_root_ca(RuntimeOrigin::root(), bytes(ROOT_CA)));
+        assert_ok!(TeePrivacy::set_dcap_crl(
+            RuntimeOrigin::root(),
+            DcapCrlKind::RootCa,
+            bytes(ROOT_CA_CRL),
+            bytes(&[]),
+        ));
+        assert_ok!(TeePrivacy::set_dcap_crl(
+            RuntimeOrigin::root(),
+            DcapCrlKind::PckProcessor,
+            bytes(PCK_CRL),
+            bytes(PCK_CRL_ISSUER_CHAIN),
+        ));
+        assert_ok!(TeePrivacy::set_dcap_tcb_info(
+            RuntimeOrigin::root(),
+            bytes(TCB_INFO),
+            bytes(TCB_SIGNING_CHAIN),
+        ));
+        assert_ok!(TeePrivacy::set_dcap_qe_identity(
+            RuntimeOrigin::root(),
+            bytes(QE_IDENTITY),
+            bytes(TCB_SIGNING_CHAIN),
+        ));
+    }
+
+    #[test]
+    fn collateral_upload_is_verified() {
+        new_test_ext().execute_with(|| {
+            setup();
+
+            assert_noop!(
+                TeePrivacy::set_dcap_root_ca(RuntimeOrigin::signed(1), bytes(ROOT_CA)),
+                DispatchError::BadOrigin
+            );
+            // 根证书之前无法上传 CRL
+            assert_noop!(
+                TeePrivacy::set_dcap_crl(
+                    RuntimeOrigin::root(),
+                    DcapCrlKind::RootCa,
+                    bytes(ROOT_CA_CRL),
+                    bytes(&[]),
+                ),
+                Error::<Test>::DcapCollateralMissing
+            );
+
+            assert_ok!(TeePrivacy::set_dcap_root_ca(RuntimeOrigin::root(), bytes(ROOT_CA)));
+            // PCK CRL 不是根 CA 签发的
+            assert_noop!(
+                TeePrivacy::set_dcap_crl(
+                    RuntimeOrigin::root(),
+                    DcapCrlKind::RootCa,
+                    bytes(PCK_CRL),
+                    bytes(&[]),
+                ),
+                Error::<Test>::InvalidDcapCollateral
+            );
+            // 根 CRL 之前无法上传 TCB Info
+            assert_noop!(
+                TeePrivacy::set_dcap_tcb_info(
+                    RuntimeOrigin::root(),
+                    bytes(TCB_INFO),
+                    bytes(TCB_SIGNING_CHAIN),
+                ),
+                Error::<Test>::DcapCollateralMissing
+            );
+
+            upload_collateral();
+            assert!(TeePrivacy::dcap_root_ca().is_some());
+            System::assert_has_event(
+                Event::DcapTcbInfoUpdated {
+                    fmspc: FMSPC,
+                    evaluation_data_number: 17,
+                }
+                .into(),
+            );
+
+            // 篡改的 TCB Info
+            let tampered = String::from_utf8(TCB_INFO.to_vec())
+                .unwrap()
+                .replacen("\"OutOfDate\"", "\"UpToDate\"", 1);
+            assert_noop!(
+                TeePrivacy::set_dcap_tcb_info(
+                    RuntimeOrigin::root(),
+                    bytes(tampered.as_bytes()),
+                    bytes(TCB_SIGNING_CHAIN),
+                ),
+                Error::<Test>::InvalidDcapCollateral
+            );
+        });
+    }
+
+    #[test]
+    fn register_with_quote_works() {
+        new_test_ext().execute_with(|| {
+            setup();
+            upload_collateral();
+
+            assert_ok!(TeePrivacy::register_tee_node_with_quote(
+                RuntimeOrigin::signed(1),
+                ENCLAVE_PUBKEY,
+                bytes(QUOTE_V3),
+            ));
+
+            let node = TeePrivacy::tee_nodes(1).unwrap();
+            assert_eq!(node.enclave_pubkey, ENCLAVE_PUBKEY);
+            assert_eq!(node.attestation.tee_type, TeeType::IntelSgxDcap);
+            assert_eq!(node.attestation.mr_enclave, [0xAA; 32]);
+            assert_eq!(node.attestation.isv_svn, 3);
+            assert_eq!(
+                node.attestation.ias_signature.to_vec(),
+                sp_io::hashing::sha2_256(QUOTE_V3).to_vec()
+            );
+            System::assert_last_event(
+                Event::DcapQuoteVerified {
+                    account: 1,
+                    fmspc: FMSPC,
+                    tcb_status: crate::attestation::TcbStatus::UpToDate,
+                }
+                .into(),
+            );
+
+            // v4 Quote 更新认证报告
+            assert_ok!(TeePrivacy::update_attestation_with_quote(
+                RuntimeOrigin::signed(1),
+                bytes(QUOTE_V4),
+            ));
+            assert_eq!(
+                TeePrivacy::tee_nodes(1).unwrap().attestation.ias_signature.to_vec(),
+                sp_io::hashing::sha2_256(QUOTE_V4).to_vec()
+            );
+        });
+    }
+
+    #[test]
+    fn register_with_quote_rejects_invalid_quotes() {
+        new_test_ext().execute_with(|| {
+            setup();
+
+            // 未上传抵押品
+            assert_noop!(
+                TeePrivacy::register_tee_node_with_quote(
+                    RuntimeOrigin::signed(1),
+                    ENCLAVE_PUBKEY,
+                    bytes(QUOTE_V3),
+                ),
+                Error::<Test>::DcapCollateralMissing
+            );
+
+            upload_collateral();
+
+            // 过时平台
+            assert_noop!(
+                TeePrivacy::register_tee_node_with_quote(
+                    RuntimeOrigin::signed(1),
+                    ENCLAVE_PUBKEY,
+                    bytes(QUOTE_V3_OUTDATED),
+                ),
+                Error::<Test>::TcbStatusRejected
+            );
+
+            // 篡改报告体
+            let mut tampered = QUOTE_V3.to_vec();
+            tampered[48 + 64] ^= 1;
+            assert_noop!(
+                TeePrivacy::register_tee_node_with_quote(
+                    RuntimeOrigin::signed(1),
+                    ENCLAVE_PUBKEY,
+                    bytes(&tampered),
+                ),
+                Error::<Test>::QuoteVerificationFailed
+            );
+
+            // 截断
+            assert_noop!(
+                TeePrivacy::register_tee_node_with_quote(
+                    RuntimeOrigin::signed(1),
+                    ENCLAVE_PUBKEY,
+                    bytes(&QUOTE_V3[..100]),
+                ),
+                Error::<Test>::InvalidQuote
+            );
+
+            // 报告数据未绑定该公钥
+            assert_noop!(
+                TeePrivacy::register_tee_node_with_quote(
+                    RuntimeOrigin::signed(1),
+                    [0x22; 32],
+                    bytes(QUOTE_V3),
+                ),
+                Error::<Test>::ReportDataMismatch
+            );
+
+            // 抵押品过期
+            pallet_timestamp::Now::<Test>::put(1_751_414_400u64); // 2025-07-02
+            assert_noop!(
+                TeePrivacy::register_tee_node_with_quote(
+                    RuntimeOrigin::signed(1),
+                    ENCLAVE_PUBKEY,
+                    bytes(QUOTE_V3),
+                ),
+                Error::<Test>::DcapCollateralExpired
+            );
+        });
+    }
+
+    #[test]
+    fn configuration_needed_platform_is_opt_in() {
+        new_test_ext().execute_with(|| {
+            setup();
+            upload_collateral();
+
+            // 默认拒绝需要配置调整的平台
+            assert_noop!(
+                TeePrivacy::register_tee_node_with_quote(
+                    RuntimeOrigin::signed(1),
+                    ENCLAVE_PUBKEY,
+                    bytes(QUOTE_V3_CONFIG),
+                ),
+                Error::<Test>::TcbStatusRejected
+            );
+
+            crate::mock::AcceptConfigurationNeeded::set(true);
+            assert_ok!(TeePrivacy::register_tee_node_with_quote(
+                RuntimeOrigin::signed(1),
+                ENCLAVE_PUBKEY,
+                bytes(QUOTE_V3_CONFIG),
+            ));
+            System::assert_last_event(
+                Event::DcapQuoteVerified {
+                    account: 1,
+                    fmspc: FMSPC,
+                    tcb_status: crate::attestation::TcbStatus::ConfigurationNeeded,
+                }
+                .into(),
+            );
+        });
+    }
+
+    #[test]
+    fn dcap_attestation_requires_quote_once_root_is_set() {
+        new_test_ext().execute_with(|| {
+            setup();
+            let mut attestation = create_test_attestation();
+            attestation.tee_type = TeeType::IntelSgxDcap;
+            attestation.timestamp = 1_748_736_000;
+
+            assert_ok!(TeePrivacy::set_dcap_root_ca(RuntimeOrigin::root(), bytes(ROOT_CA)));
+            assert_noop!(
+                TeePrivacy::register_tee_node(
+                    RuntimeOrigin::signed(1),
+                    [42u8; 32],
+                    attestation.clone(),
+                ),
+                Error::<Test>::DcapQuoteRequired
+            );
+
+            // 非 DCAP 认证不受影响
+            attestation.tee_type = TeeType::IntelSgx;
+            assert_ok!(TeePrivacy::register_tee_node(
+                RuntimeOrigin::signed(1),
+                [42u8; 32],
+                attestation,
+            ));
+        });
+    }
+}
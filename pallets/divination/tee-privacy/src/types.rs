@@ -13,6 +13,7 @@
 //! - `ComputeType`: 计算类型（八字/梅花/奇门等）
 //! - `ComputationProof`: 计算证明
 //! - `EncryptedData`: 加密数据
+//! - `DcapSignedCollateral`: DCAP 验证抵押品（CRL、TCB Info、QE Identity）
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::pallet_prelude::*;
@@ -153,6 +154,8 @@ pub enum FailureReason {
     Timeout = 7,
     /// 用户取消
     UserCancelled = 8,
+    /// 冗余执行结果不一致
+    ResultMismatch = 9,
 }
 
 // ============================================================================
@@ -810,6 +813,117 @@ pub struct NodeStatistics {
     pub last_active_block: u64,
 }
 
+// ============================================================================
+// 冗余执行类型
+// ============================================================================
+
+/// 单个请求最多分配的冗余节点数
+pub const MAX_REPLICAS: u32 = 5;
+
+/// 冗余执行副本集合（k-of-n）
+///
+/// 同一加密请求分发给 n 个 Enclave，任一输出哈希获得 `quorum` 个一致结果即完成；
+/// `quorum` 必须超过半数，保证至多一个哈希能达成多数。
+#[derive(Clone, Encode, Decode, codec::DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug)]
+pub struct ReplicaSet<AccountId> {
+    /// 分配的节点（首个节点同时记录在 `ComputeRequestInfo::assigned_node`）
+    pub nodes: BoundedVec<AccountId, ConstU32<MAX_REPLICAS>>,
+    /// 达成一致所需的结果数 (k)
+    pub quorum: u8,
+    /// 已提交的结果 (节点, 输出哈希)
+    pub results: BoundedVec<(AccountId, [u8; 32]), ConstU32<MAX_REPLICAS>>,
+}
+
+impl<AccountId: PartialEq> ReplicaSet<AccountId> {
+    /// 节点是否属于副本集合
+    pub fn contains(&self, account: &AccountId) -> bool {
+        self.nodes.contains(account)
+    }
+
+    /// 节点是否已提交结果
+    pub fn has_submitted(&self, account: &AccountId) -> bool {
+        self.results.iter().any(|(node, _)| node == account)
+    }
+
+    /// 获得 `quorum` 个一致结果的输出哈希
+    pub fn agreed_hash(&self) -> Option<[u8; 32]> {
+        self.results
+            .iter()
+            .map(|(_, hash)| hash)
+            .find(|hash| self.votes_for(hash) >= self.quorum as usize)
+            .copied()
+    }
+
+    /// 是否已不可能达成一致（剩余未提交节点全部投给领先哈希也不够）
+    pub fn is_deadlocked(&self) -> bool {
+        let remaining = self.nodes.len().saturating_sub(self.results.len());
+        let leading = self
+            .results
+            .iter()
+            .map(|(_, hash)| self.votes_for(hash))
+            .max()
+            .unwrap_or(0);
+        leading.saturating_add(remaining) < self.quorum as usize
+    }
+
+    /// 某输出哈希的票数
+    fn votes_for(&self, hash: &[u8; 32]) -> usize {
+        self.results.iter().filter(|(_, h)| h == hash).count()
+    }
+}
+
+/// 冗余请求达成一致后，尚未提交结果的副本节点的迟到结果核对记录
+///
+/// 迟到结果与最终一致结果比对，不一致的节点同样按 `ResultMismatch` 惩罚；
+/// 到达 `expires_at`（原请求超时区块）后记录被清理。
+#[derive(Clone, Encode, Decode, codec::DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug)]
+pub struct LateReplicaCheck<AccountId, BlockNumber> {
+    /// 尚未提交结果的副本节点
+    pub pending: BoundedVec<AccountId, ConstU32<MAX_REPLICAS>>,
+    /// 最终一致的输出哈希
+    pub agreed_hash: [u8; 32],
+    /// 核对截止区块
+    pub expires_at: BlockNumber,
+}
+
+// ============================================================================
+// DCAP 抵押品类型
+// ============================================================================
+
+/// DCAP 抵押品单项最大长度（Intel PCS 原始数据：证书、CRL、TCB Info 等）
+pub const MAX_DCAP_COLLATERAL_LEN: u32 = 32 * 1024;
+
+/// DCAP Quote 最大长度（含 PEM 格式的 PCK 证书链）
+pub const MAX_DCAP_QUOTE_LEN: u32 = 8 * 1024;
+
+/// DCAP 抵押品原始字节
+pub type DcapCollateralBytes = BoundedVec<u8, ConstU32<MAX_DCAP_COLLATERAL_LEN>>;
+
+/// DCAP Quote 原始字节
+pub type DcapQuote = BoundedVec<u8, ConstU32<MAX_DCAP_QUOTE_LEN>>;
+
+/// DCAP CRL 类别
+#[derive(Clone, Copy, Encode, Decode, codec::DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug)]
+pub enum DcapCrlKind {
+    /// 根 CA CRL（由 Intel SGX Root CA 签发）
+    RootCa = 0,
+    /// PCK Processor CA CRL
+    PckProcessor = 1,
+    /// PCK Platform CA CRL
+    PckPlatform = 2,
+}
+
+/// 带签发证书链的 DCAP 抵押品
+///
+/// 链上保存 Intel PCS 下发的原始数据，每次验证 Quote 时重新校验签名。
+#[derive(Clone, Encode, Decode, codec::DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug)]
+pub struct DcapSignedCollateral {
+    /// 抵押品本体（CRL 为 DER，TCB Info / QE Identity 为签名 JSON）
+    pub body: DcapCollateralBytes,
+    /// 签发证书链（PEM，终止于根 CA；根 CA CRL 为空）
+    pub issuer_chain: DcapCollateralBytes,
+}
+
 // ============================================================================
 // Phase 5: 批处理优化类型
 // ============================================================================
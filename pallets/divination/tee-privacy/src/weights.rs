@@ -6,6 +6,21 @@ use frame_support::weights::Weight;
 use frame_support::traits::Get;
 use core::marker::PhantomData;
 
+use crate::types::MAX_DCAP_COLLATERAL_LEN;
+
+/// 单项 DCAP 抵押品最大证明大小（32 KiB 本体 + 长度前缀 + 存储键与 trie 节点开销）
+const DCAP_BYTES_PROOF: u64 = MAX_DCAP_COLLATERAL_LEN as u64 + 4 + 512;
+
+/// 签名抵押品（本体 + 签发证书链）最大证明大小
+const DCAP_SIGNED_PROOF: u64 = 2 * (MAX_DCAP_COLLATERAL_LEN as u64 + 4) + 512;
+
+/// 上传抵押品时读取根证书与根 CA CRL 用于验证
+const DCAP_UPLOAD_PROOF: u64 = DCAP_BYTES_PROOF + DCAP_SIGNED_PROOF;
+
+/// Quote 验证读取根证书、根 CA CRL、两份 PCK CRL、TCB Info、QE Identity，
+/// 另加节点信息与 MRENCLAVE / MRSIGNER 白名单
+const DCAP_QUOTE_PROOF: u64 = DCAP_BYTES_PROOF + 5 * DCAP_SIGNED_PROOF + 8_192;
+
 /// 权重信息 trait
 pub trait WeightInfo {
     // ==================== TEE 节点管理 ====================
@@ -69,6 +84,25 @@ pub trait WeightInfo {
     /// 设置审计启用状态
     fn set_audit_enabled() -> Weight;
 
+    // ==================== 冗余执行 ====================
+
+    /// 提交冗余执行的计算请求（n 为副本数）
+    fn submit_redundant_compute_request(n: u32) -> Weight;
+
+    /// 为 n 个排队的冗余请求分配副本节点（on_initialize）
+    fn assign_replicas(n: u32) -> Weight;
+
+    // ==================== DCAP 认证 ====================
+
+    /// 设置 DCAP 抵押品（根证书 / CRL / TCB Info / QE Identity）
+    fn set_dcap_collateral() -> Weight;
+
+    /// 使用 DCAP Quote 注册 TEE 节点
+    fn register_tee_node_with_quote() -> Weight;
+
+    /// 使用 DCAP Quote 更新认证报告
+    fn update_attestation_with_quote() -> Weight;
+
     // ==================== 超时处理 ====================
 
     /// 处理超时请求（on_finalize）
@@ -93,14 +127,14 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 
     fn update_node_status() -> Weight {
         Weight::from_parts(15_000_000, 0)
-            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().reads(4))
             .saturating_add(T::DbWeight::get().writes(2))
     }
 
     fn deregister_tee_node() -> Weight {
         Weight::from_parts(20_000_000, 0)
             .saturating_add(T::DbWeight::get().reads(1))
-            .saturating_add(T::DbWeight::get().writes(2))
+            .saturating_add(T::DbWeight::get().writes(3))
     }
 
     fn submit_compute_request() -> Weight {
@@ -180,6 +214,39 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().writes(1))
     }
 
+    fn submit_redundant_compute_request(n: u32) -> Weight {
+        Weight::from_parts(45_000_000 + 15_000_000 * n as u64, 0)
+            .saturating_add(T::DbWeight::get().reads(3 + 4 * n as u64))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+
+    fn assign_replicas(n: u32) -> Weight {
+        // 每个请求按 MAX_REPLICAS 个节点抽取，候选节点读取计入 ActiveNodes
+        Weight::from_parts(10_000_000 + 60_000_000 * n as u64, 0)
+            .saturating_add(T::DbWeight::get().reads(2 + 23 * n as u64))
+            .saturating_add(T::DbWeight::get().writes(1 + 7 * n as u64))
+    }
+
+    fn set_dcap_collateral() -> Weight {
+        // 证书链与 ECDSA 签名验证
+        Weight::from_parts(300_000_000, DCAP_UPLOAD_PROOF)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    fn register_tee_node_with_quote() -> Weight {
+        // 完整 Quote 验证：约 10 次 ECDSA 验签 + 抵押品解析
+        Weight::from_parts(1_200_000_000, DCAP_QUOTE_PROOF)
+            .saturating_add(T::DbWeight::get().reads(11))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+
+    fn update_attestation_with_quote() -> Weight {
+        Weight::from_parts(1_200_000_000, DCAP_QUOTE_PROOF)
+            .saturating_add(T::DbWeight::get().reads(10))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
     fn process_timeouts(n: u32) -> Weight {
         Weight::from_parts(10_000_000 * n as u64, 0)
             .saturating_add(T::DbWeight::get().reads(n as u64))
@@ -257,6 +324,26 @@ impl WeightInfo for () {
         Weight::from_parts(10_000_000, 0)
     }
 
+    fn submit_redundant_compute_request(n: u32) -> Weight {
+        Weight::from_parts(45_000_000 + 15_000_000 * n as u64, 0)
+    }
+
+    fn assign_replicas(n: u32) -> Weight {
+        Weight::from_parts(10_000_000 + 60_000_000 * n as u64, 0)
+    }
+
+    fn set_dcap_collateral() -> Weight {
+        Weight::from_parts(300_000_000, DCAP_UPLOAD_PROOF)
+    }
+
+    fn register_tee_node_with_quote() -> Weight {
+        Weight::from_parts(1_200_000_000, DCAP_QUOTE_PROOF)
+    }
+
+    fn update_attestation_with_quote() -> Weight {
+        Weight::from_parts(1_200_000_000, DCAP_QUOTE_PROOF)
+    }
+
     fn process_timeouts(n: u32) -> Weight {
         Weight::from_parts(10_000_000 * n as u64, 0)
     }
@@ -0,0 +1,540 @@
+//! # 链上交互
+//!
+//! 基于 subxt 的动态接口访问 `pallet-tee-privacy`：
+//! - 存储与事件按原始 SCALE 字节读取，解码到本模块的镜像类型
+//! - 交易按 pallet / call 名称在元数据中查索引后直接编码参数
+//!
+//! 镜像类型与 `pallet_tee_privacy::types` 的 SCALE 编码逐字段一致，
+//! 运行时 `AccountId = AccountId32`、`BlockNumber = u32`。
+
+use std::str::FromStr;
+
+use codec::{Decode, Encode};
+use subxt::blocks::ExtrinsicEvents;
+use subxt::config::polkadot::{PolkadotExtrinsicParams, PolkadotExtrinsicParamsBuilder};
+use subxt::config::{Config, ExtrinsicParams, PolkadotConfig};
+use subxt::events::EventDetails;
+use subxt::tx::TxPayload;
+use subxt::utils::Static;
+use subxt::{Metadata, OnlineClient};
+use subxt_signer::sr25519::Keypair;
+use subxt_signer::SecretUri;
+
+use crate::error::{WorkerError, WorkerResult};
+
+/// 链上 pallet 名称
+pub const TEE_PRIVACY_PALLET: &str = "TeePrivacy";
+
+/// 账户 ID（AccountId32 原始字节）
+pub type AccountId = [u8; 32];
+
+/// 区块号
+pub type BlockNumber = u32;
+
+// ============================================================================
+// 交易扩展
+// ============================================================================
+
+/// Stardust 运行时的交易扩展参数
+///
+/// 在 Polkadot 默认扩展（Era、Nonce、Tip）之后追加 `CheckMetadataHash`：
+/// extra 为模式字节 `0`（禁用），additional 为 `None`。
+/// `CheckNonZeroSender`、`CheckWeight`、`WeightReclaim` 不编码任何数据。
+#[derive(Debug)]
+pub struct StardustExtrinsicParams(PolkadotExtrinsicParams<StardustConfig>);
+
+impl ExtrinsicParams<<PolkadotConfig as Config>::Hash> for StardustExtrinsicParams {
+    type OtherParams = PolkadotExtrinsicParamsBuilder<StardustConfig>;
+
+    fn new(
+        spec_version: u32,
+        tx_version: u32,
+        nonce: u64,
+        genesis_hash: <PolkadotConfig as Config>::Hash,
+        other_params: Self::OtherParams,
+    ) -> Self {
+        Self(PolkadotExtrinsicParams::new(
+            spec_version,
+            tx_version,
+            nonce,
+            genesis_hash,
+            other_params,
+        ))
+    }
+
+    fn encode_extra_to(&self, v: &mut Vec<u8>) {
+        self.0.encode_extra_to(v);
+        0u8.encode_to(v);
+    }
+
+    fn encode_additional_to(&self, v: &mut Vec<u8>) {
+        self.0.encode_additional_to(v);
+        None::<[u8; 32]>.encode_to(v);
+    }
+}
+
+/// Stardust 链配置
+pub enum StardustConfig {}
+
+impl Config for StardustConfig {
+    type Hash = <PolkadotConfig as Config>::Hash;
+    type AccountId = <PolkadotConfig as Config>::AccountId;
+    type Address = <PolkadotConfig as Config>::Address;
+    type Signature = <PolkadotConfig as Config>::Signature;
+    type Hasher = <PolkadotConfig as Config>::Hasher;
+    type Header = <PolkadotConfig as Config>::Header;
+    type ExtrinsicParams = StardustExtrinsicParams;
+}
+
+// ============================================================================
+// 镜像类型
+// ============================================================================
+
+/// TEE 类型
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum TeeType {
+    /// Intel SGX (EPID 认证)
+    IntelSgx,
+    /// Intel SGX (DCAP 认证)
+    IntelSgxDcap,
+    /// ARM TrustZone
+    ArmTrustZone,
+    /// AMD SEV
+    AmdSev,
+    /// RISC-V Keystone
+    RiscVKeystone,
+}
+
+/// TEE 节点状态
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum TeeNodeStatus {
+    /// 待验证
+    Pending,
+    /// 活跃
+    Active,
+    /// 暂停
+    Suspended,
+    /// 已注销
+    Deregistered,
+}
+
+/// 请求状态
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode)]
+pub enum RequestStatus {
+    /// 待处理
+    Pending,
+    /// 处理中
+    Processing,
+    /// 已完成
+    Completed,
+    /// 已失败
+    Failed,
+    /// 已超时
+    Timeout,
+}
+
+/// 远程认证报告
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct TeeAttestation {
+    /// TEE 类型
+    pub tee_type: TeeType,
+    /// MRENCLAVE
+    pub mr_enclave: [u8; 32],
+    /// MRSIGNER
+    pub mr_signer: [u8; 32],
+    /// ISV Product ID
+    pub isv_prod_id: u16,
+    /// ISV SVN
+    pub isv_svn: u16,
+    /// 报告数据
+    pub report_data: [u8; 64],
+    /// IAS 签名（链上上限 512 字节）
+    pub ias_signature: Vec<u8>,
+    /// 认证时间
+    pub timestamp: u64,
+}
+
+/// TEE 节点
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct TeeNode {
+    /// 节点账户
+    pub account: AccountId,
+    /// Enclave Ed25519 公钥
+    pub enclave_pubkey: [u8; 32],
+    /// 远程认证报告
+    pub attestation: TeeAttestation,
+    /// 注册时间
+    pub registered_at: u64,
+    /// 状态
+    pub status: TeeNodeStatus,
+}
+
+/// 计算请求（`ComputeRequests` 存储项）
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct ComputeRequestInfo {
+    /// 请求 ID
+    pub id: u64,
+    /// 请求者
+    pub requester: AccountId,
+    /// 计算类型标识
+    pub compute_type_id: u8,
+    /// 加密输入哈希
+    pub input_hash: [u8; 32],
+    /// 分配的 TEE 节点
+    pub assigned_node: Option<AccountId>,
+    /// 创建区块
+    pub created_at: BlockNumber,
+    /// 超时区块
+    pub timeout_at: BlockNumber,
+    /// 状态
+    pub status: RequestStatus,
+    /// 故障转移次数
+    pub failover_count: u8,
+    /// 失败原因（枚举序号）
+    pub failure_reason: Option<u8>,
+}
+
+/// 冗余执行副本集合（`RequestReplicas` 存储项）
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct ReplicaSet {
+    /// 分配的节点
+    pub nodes: Vec<AccountId>,
+    /// 达成一致所需的结果数
+    pub quorum: u8,
+    /// 已提交的结果 (节点, 输出哈希)
+    pub results: Vec<(AccountId, [u8; 32])>,
+}
+
+impl ReplicaSet {
+    /// 节点是否仍需提交结果
+    pub fn awaits(&self, account: &AccountId) -> bool {
+        self.nodes.contains(account) && !self.results.iter().any(|(node, _)| node == account)
+    }
+}
+
+/// `ComputeRequestSubmitted` 事件
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct ComputeRequestSubmitted {
+    /// 请求 ID
+    pub request_id: u64,
+    /// 请求者
+    pub requester: AccountId,
+    /// 计算类型标识
+    pub compute_type_id: u8,
+    /// 分配的节点
+    pub assigned_node: Option<AccountId>,
+}
+
+/// `ReplicasAssigned` 事件
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+pub struct ReplicasAssigned {
+    /// 请求 ID
+    pub request_id: u64,
+    /// 分配的节点
+    pub nodes: Vec<AccountId>,
+    /// 达成一致所需的结果数
+    pub quorum: u8,
+}
+
+// ============================================================================
+// 交易载荷
+// ============================================================================
+
+/// 按名称定位的调用，参数直接 SCALE 编码
+struct RawCall<A> {
+    pallet: &'static str,
+    call: &'static str,
+    args: A,
+}
+
+impl<A: Encode> TxPayload for RawCall<A> {
+    fn encode_call_data_to(
+        &self,
+        metadata: &Metadata,
+        out: &mut Vec<u8>,
+    ) -> Result<(), subxt::Error> {
+        let pallet = metadata
+            .pallet_by_name(self.pallet)
+            .ok_or_else(|| subxt::Error::Other(format!("pallet {} not found", self.pallet)))?;
+        let call = pallet.call_variant_by_name(self.call).ok_or_else(|| {
+            subxt::Error::Other(format!("call {}::{} not found", self.pallet, self.call))
+        })?;
+
+        out.push(pallet.index());
+        out.push(call.index);
+        self.args.encode_to(out);
+        Ok(())
+    }
+}
+
+// ============================================================================
+// 链客户端
+// ============================================================================
+
+/// 链客户端
+pub struct ChainClient {
+    /// subxt 客户端
+    api: OnlineClient<StardustConfig>,
+    /// 节点运营账户
+    signer: Keypair,
+}
+
+impl ChainClient {
+    /// 连接节点
+    pub async fn connect(url: &str, suri: &str) -> WorkerResult<Self> {
+        let uri =
+            SecretUri::from_str(suri).map_err(|e| WorkerError::InvalidSigner(e.to_string()))?;
+        let signer =
+            Keypair::from_uri(&uri).map_err(|e| WorkerError::InvalidSigner(e.to_string()))?;
+        let api = OnlineClient::<StardustConfig>::from_url(url).await?;
+
+        Ok(Self { api, signer })
+    }
+
+    /// subxt 客户端
+    pub fn api(&self) -> &OnlineClient<StardustConfig> {
+        &self.api
+    }
+
+    /// 节点运营账户
+    pub fn account_id(&self) -> AccountId {
+        self.signer.public_key().0
+    }
+
+    /// 读取存储项原始值并解码
+    async fn fetch<K: Encode, V: Decode>(
+        &self,
+        pallet: &str,
+        entry: &str,
+        keys: Vec<K>,
+    ) -> WorkerResult<Option<V>> {
+        let keys: Vec<Static<K>> = keys.into_iter().map(Static).collect();
+        let address = subxt::dynamic::storage(pallet, entry, keys);
+        let value = self
+            .api
+            .storage()
+            .at_latest()
+            .await?
+            .fetch(&address)
+            .await?;
+
+        match value {
+            Some(thunk) => Ok(Some(V::decode(&mut thunk.encoded())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// 链上当前时间戳（`pallet_timestamp::Now`）
+    pub async fn now(&self) -> WorkerResult<u64> {
+        Ok(self
+            .fetch::<(), u64>("Timestamp", "Now", Vec::new())
+            .await?
+            .unwrap_or_default())
+    }
+
+    /// TEE 节点注册信息
+    pub async fn tee_node(&self, account: AccountId) -> WorkerResult<Option<TeeNode>> {
+        self.fetch(TEE_PRIVACY_PALLET, "TeeNodes", vec![account])
+            .await
+    }
+
+    /// 本节点的注册信息
+    pub async fn own_node(&self) -> WorkerResult<Option<TeeNode>> {
+        self.tee_node(self.account_id()).await
+    }
+
+    /// 计算请求
+    pub async fn compute_request(
+        &self,
+        request_id: u64,
+    ) -> WorkerResult<Option<ComputeRequestInfo>> {
+        self.fetch(TEE_PRIVACY_PALLET, "ComputeRequests", vec![request_id])
+            .await
+    }
+
+    /// 冗余执行请求的副本集合
+    pub async fn request_replicas(&self, request_id: u64) -> WorkerResult<Option<ReplicaSet>> {
+        self.fetch(TEE_PRIVACY_PALLET, "RequestReplicas", vec![request_id])
+            .await
+    }
+
+    /// 待处理请求 ID 列表
+    pub async fn pending_requests(&self) -> WorkerResult<Vec<u64>> {
+        Ok(self
+            .fetch::<(), Vec<u64>>(TEE_PRIVACY_PALLET, "PendingRequests", Vec::new())
+            .await?
+            .unwrap_or_default())
+    }
+
+    /// 签名并提交交易，等待打包成功
+    async fn submit<A: Encode>(
+        &self,
+        call: &'static str,
+        args: A,
+    ) -> WorkerResult<ExtrinsicEvents<StardustConfig>> {
+        let payload = RawCall {
+            pallet: TEE_PRIVACY_PALLET,
+            call,
+            args,
+        };
+
+        self.api
+            .tx()
+            .sign_and_submit_then_watch_default(&payload, &self.signer)
+            .await?
+            .wait_for_in_block()
+            .await?
+            .wait_for_success()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// 注册 TEE 节点
+    pub async fn register_tee_node(
+        &self,
+        enclave_pubkey: [u8; 32],
+        attestation: &TeeAttestation,
+    ) -> WorkerResult<()> {
+        self.submit("register_tee_node", (enclave_pubkey, attestation))
+            .await?;
+        Ok(())
+    }
+
+    /// 刷新认证报告
+    pub async fn update_attestation(&self, attestation: &TeeAttestation) -> WorkerResult<()> {
+        self.submit("update_attestation", attestation).await?;
+        Ok(())
+    }
+
+    /// 提交计算结果
+    pub async fn submit_compute_result(
+        &self,
+        request_id: u64,
+        output_hash: [u8; 32],
+        enclave_signature: [u8; 64],
+    ) -> WorkerResult<()> {
+        self.submit(
+            "submit_compute_result",
+            (request_id, output_hash, enclave_signature),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// 提交计算请求（客户端侧），返回链上分配的请求 ID
+    pub async fn submit_compute_request(
+        &self,
+        compute_type_id: u8,
+        input_hash: [u8; 32],
+        assigned_node: Option<AccountId>,
+    ) -> WorkerResult<u64> {
+        let events = self
+            .submit(
+                "submit_compute_request",
+                (compute_type_id, input_hash, assigned_node),
+            )
+            .await?;
+
+        submitted_requests(events.iter())?
+            .first()
+            .map(|event| event.request_id)
+            .ok_or_else(|| WorkerError::InvalidData("ComputeRequestSubmitted not emitted".into()))
+    }
+}
+
+/// 提取事件中的 `ComputeRequestSubmitted`
+pub fn submitted_requests(
+    events: impl Iterator<Item = Result<EventDetails<StardustConfig>, subxt::Error>>,
+) -> WorkerResult<Vec<ComputeRequestSubmitted>> {
+    pallet_events(events, "ComputeRequestSubmitted")
+}
+
+/// 提取事件中的 `ReplicasAssigned`
+pub fn assigned_replicas(
+    events: impl Iterator<Item = Result<EventDetails<StardustConfig>, subxt::Error>>,
+) -> WorkerResult<Vec<ReplicasAssigned>> {
+    pallet_events(events, "ReplicasAssigned")
+}
+
+/// 按变体名解码本 pallet 的事件
+fn pallet_events<E: Decode>(
+    events: impl Iterator<Item = Result<EventDetails<StardustConfig>, subxt::Error>>,
+    variant: &str,
+) -> WorkerResult<Vec<E>> {
+    let mut decoded = Vec::new();
+
+    for event in events {
+        let event = event?;
+        if event.pallet_name() == TEE_PRIVACY_PALLET && event.variant_name() == variant {
+            decoded.push(E::decode(&mut event.field_bytes())?);
+        }
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_request_layout() {
+        // 与链上 ComputeRequestInfo<AccountId32, u32> 字段顺序一致
+        let info = ComputeRequestInfo {
+            id: 7,
+            requester: [1u8; 32],
+            compute_type_id: 3,
+            input_hash: [2u8; 32],
+            assigned_node: Some([3u8; 32]),
+            created_at: 10,
+            timeout_at: 110,
+            status: RequestStatus::Processing,
+            failover_count: 0,
+            failure_reason: None,
+        };
+        let encoded = info.encode();
+
+        assert_eq!(encoded.len(), 8 + 32 + 1 + 32 + 33 + 4 + 4 + 1 + 1 + 1);
+        assert_eq!(encoded[8 + 32 + 1 + 32 + 33 + 8], 1);
+        assert_eq!(ComputeRequestInfo::decode(&mut &encoded[..]).unwrap(), info);
+    }
+
+    #[test]
+    fn test_replica_set_awaits_pending_nodes() {
+        let replicas = ReplicaSet {
+            nodes: vec![[1u8; 32], [2u8; 32]],
+            quorum: 2,
+            results: vec![([1u8; 32], [9u8; 32])],
+        };
+
+        assert!(!replicas.awaits(&[1u8; 32]));
+        assert!(replicas.awaits(&[2u8; 32]));
+        assert!(!replicas.awaits(&[3u8; 32]));
+        assert_eq!(
+            ReplicaSet::decode(&mut &replicas.encode()[..]).unwrap(),
+            replicas
+        );
+    }
+
+    #[test]
+    fn test_extrinsic_params_append_metadata_hash() {
+        let params = StardustExtrinsicParams::new(1, 1, 0, Default::default(), Default::default());
+        let base = PolkadotExtrinsicParams::<StardustConfig>::new(
+            1,
+            1,
+            0,
+            Default::default(),
+            Default::default(),
+        );
+
+        let (mut extra, mut base_extra) = (Vec::new(), Vec::new());
+        params.encode_extra_to(&mut extra);
+        base.encode_extra_to(&mut base_extra);
+        assert_eq!(extra, [base_extra, vec![0u8]].concat());
+
+        let (mut additional, mut base_additional) = (Vec::new(), Vec::new());
+        params.encode_additional_to(&mut additional);
+        base.encode_additional_to(&mut base_additional);
+        assert_eq!(additional, [base_additional, vec![0u8]].concat());
+    }
+}
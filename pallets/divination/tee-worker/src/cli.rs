@@ -0,0 +1,55 @@
+//! # 命令行参数
+
+use std::path::PathBuf;
+
+use clap::Parser;
+
+/// TEE Worker 命令行参数
+#[derive(Debug, Clone, Parser)]
+#[command(name = "tee-worker", version, about = "Stardust TEE Worker")]
+pub struct Cli {
+    /// 节点 WebSocket RPC 地址
+    #[arg(
+        long,
+        env = "TEE_WORKER_RPC_URL",
+        default_value = "ws://127.0.0.1:9944"
+    )]
+    pub rpc_url: String,
+
+    /// 节点运营账户的 Secret URI（如 `//Alice` 或助记词）
+    #[arg(long, env = "TEE_WORKER_SURI")]
+    pub suri: String,
+
+    /// 数据目录（密封密钥、收件箱、发件箱）
+    #[arg(long, env = "TEE_WORKER_DATA_DIR", default_value = "./tee-worker-data")]
+    pub data_dir: PathBuf,
+
+    /// 模拟模式：软件密封 + 模拟认证报告，无需 SGX 硬件
+    #[arg(long)]
+    pub simulate: bool,
+
+    /// 认证报告刷新间隔（区块数，链上有效期约 14400 区块）
+    #[arg(long, default_value_t = 7200)]
+    pub attestation_refresh_blocks: u32,
+
+    /// 全量扫描待处理请求的间隔（区块数），用于接手故障转移分配的请求
+    #[arg(long, default_value_t = 10)]
+    pub rescan_blocks: u32,
+}
+
+impl Cli {
+    /// 收件箱目录
+    pub fn inbox_dir(&self) -> PathBuf {
+        self.data_dir.join("inbox")
+    }
+
+    /// 发件箱目录
+    pub fn outbox_dir(&self) -> PathBuf {
+        self.data_dir.join("outbox")
+    }
+
+    /// 密封密钥文件
+    pub fn sealed_keys_path(&self) -> PathBuf {
+        self.data_dir.join("enclave.sealed")
+    }
+}
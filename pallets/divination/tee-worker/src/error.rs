@@ -0,0 +1,70 @@
+//! # 错误类型定义
+
+use tee_enclave::EnclaveError;
+
+/// Worker 错误类型
+#[derive(Debug, thiserror::Error)]
+pub enum WorkerError {
+    /// 链上交互失败（装箱，subxt 错误体积较大）
+    #[error("chain error: {0}")]
+    Chain(Box<subxt::Error>),
+
+    /// 签名账户无效
+    #[error("invalid signer: {0}")]
+    InvalidSigner(String),
+
+    /// Enclave 执行失败
+    #[error("enclave error: {0}")]
+    Enclave(EnclaveError),
+
+    /// 文件读写失败
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// 数据格式错误
+    #[error("invalid data: {0}")]
+    InvalidData(String),
+
+    /// 密封 / 解封失败
+    #[error("sealing error: {0}")]
+    Sealing(String),
+
+    /// 当前构建不支持的操作（如非模拟模式下的硬件认证）
+    #[error("unsupported: {0}")]
+    Unsupported(String),
+
+    /// 加密输入哈希与链上记录不一致
+    #[error("input hash mismatch for request {0}")]
+    InputHashMismatch(u64),
+
+    /// 计算类型与链上记录不一致
+    #[error("compute type mismatch for request {0}")]
+    ComputeTypeMismatch(u64),
+}
+
+impl From<subxt::Error> for WorkerError {
+    fn from(err: subxt::Error) -> Self {
+        Self::Chain(Box::new(err))
+    }
+}
+
+impl From<EnclaveError> for WorkerError {
+    fn from(err: EnclaveError) -> Self {
+        Self::Enclave(err)
+    }
+}
+
+impl From<serde_json::Error> for WorkerError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::InvalidData(err.to_string())
+    }
+}
+
+impl From<codec::Error> for WorkerError {
+    fn from(err: codec::Error) -> Self {
+        Self::InvalidData(err.to_string())
+    }
+}
+
+/// Worker 结果类型
+pub type WorkerResult<T> = Result<T, WorkerError>;
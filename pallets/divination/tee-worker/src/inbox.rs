@@ -0,0 +1,243 @@
+//! # 链下数据通道
+//!
+//! 链上只记录加密输入的哈希，密文与加密结果经文件目录交换：
+//! - 收件箱：`inbox/<input_hash>.json`，由客户端（或网关）投递
+//! - 发件箱：`outbox/<request_id>.json`，由 Worker 写入，客户端取回后用会话密钥解密
+//!
+//! 目录可由 IPFS 网关、HTTP 上传服务等同步，Worker 只依赖文件本身。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tee_enclave::client::request_input_hash;
+use tee_enclave::{EncryptedRequest, EncryptedResponse};
+
+use crate::error::{WorkerError, WorkerResult};
+
+/// 收件箱中的加密输入
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InboxEnvelope {
+    /// 临时公钥 (hex)
+    pub ephemeral_pubkey: String,
+    /// Nonce (hex)
+    pub nonce: String,
+    /// 认证标签 (hex)
+    pub auth_tag: String,
+    /// 密文 (hex)
+    pub ciphertext: String,
+}
+
+impl From<&EncryptedRequest> for InboxEnvelope {
+    fn from(request: &EncryptedRequest) -> Self {
+        Self {
+            ephemeral_pubkey: hex::encode(request.ephemeral_pubkey),
+            nonce: hex::encode(request.nonce),
+            auth_tag: hex::encode(request.auth_tag),
+            ciphertext: hex::encode(&request.ciphertext),
+        }
+    }
+}
+
+impl TryFrom<InboxEnvelope> for EncryptedRequest {
+    type Error = WorkerError;
+
+    fn try_from(envelope: InboxEnvelope) -> WorkerResult<Self> {
+        Ok(Self {
+            ciphertext: decode_hex(&envelope.ciphertext)?,
+            ephemeral_pubkey: decode_fixed(&envelope.ephemeral_pubkey)?,
+            nonce: decode_fixed(&envelope.nonce)?,
+            auth_tag: decode_fixed(&envelope.auth_tag)?,
+        })
+    }
+}
+
+/// 发件箱中的加密结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxEnvelope {
+    /// 请求 ID
+    pub request_id: u64,
+    /// Nonce (hex)
+    pub nonce: String,
+    /// 认证标签 (hex)
+    pub auth_tag: String,
+    /// 密文 (hex)
+    pub ciphertext: String,
+    /// 输出哈希 (hex)，与链上 `ComputeResults` 一致
+    pub output_hash: String,
+    /// Enclave 签名 (hex)
+    pub enclave_signature: String,
+}
+
+impl OutboxEnvelope {
+    /// 由加密响应构造
+    pub fn new(request_id: u64, response: &EncryptedResponse) -> Self {
+        Self {
+            request_id,
+            nonce: hex::encode(response.nonce),
+            auth_tag: hex::encode(response.auth_tag),
+            ciphertext: hex::encode(&response.ciphertext),
+            output_hash: hex::encode(response.output_hash),
+            enclave_signature: hex::encode(response.enclave_signature),
+        }
+    }
+
+    /// 还原为加密响应（客户端解密用）
+    pub fn to_encrypted_response(&self) -> WorkerResult<EncryptedResponse> {
+        Ok(EncryptedResponse {
+            ciphertext: decode_hex(&self.ciphertext)?,
+            nonce: decode_fixed(&self.nonce)?,
+            auth_tag: decode_fixed(&self.auth_tag)?,
+            output_hash: decode_fixed(&self.output_hash)?,
+            enclave_signature: decode_fixed(&self.enclave_signature)?,
+        })
+    }
+}
+
+/// 收发件箱
+pub struct Mailbox {
+    /// 收件箱目录
+    inbox: PathBuf,
+    /// 发件箱目录
+    outbox: PathBuf,
+}
+
+impl Mailbox {
+    /// 打开（必要时创建）收发件箱目录
+    pub fn open(inbox: PathBuf, outbox: PathBuf) -> WorkerResult<Self> {
+        fs::create_dir_all(&inbox)?;
+        fs::create_dir_all(&outbox)?;
+        Ok(Self { inbox, outbox })
+    }
+
+    fn input_path(&self, input_hash: &[u8; 32]) -> PathBuf {
+        self.inbox.join(format!("{}.json", hex::encode(input_hash)))
+    }
+
+    fn output_path(&self, request_id: u64) -> PathBuf {
+        self.outbox.join(format!("{}.json", request_id))
+    }
+
+    /// 投递加密输入，返回链上应登记的 `input_hash`
+    pub fn deposit(&self, request: &EncryptedRequest) -> WorkerResult<[u8; 32]> {
+        let input_hash = request_input_hash(request);
+        write_json(&self.input_path(&input_hash), &InboxEnvelope::from(request))?;
+        Ok(input_hash)
+    }
+
+    /// 读取加密输入，尚未投递时返回 `None`
+    ///
+    /// 密文哈希必须与链上 `input_hash` 一致，防止被替换
+    pub fn load_input(
+        &self,
+        request_id: u64,
+        input_hash: &[u8; 32],
+    ) -> WorkerResult<Option<EncryptedRequest>> {
+        let path = self.input_path(input_hash);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let envelope: InboxEnvelope = serde_json::from_slice(&fs::read(&path)?)?;
+        let request = EncryptedRequest::try_from(envelope)?;
+        if &request_input_hash(&request) != input_hash {
+            return Err(WorkerError::InputHashMismatch(request_id));
+        }
+
+        Ok(Some(request))
+    }
+
+    /// 处理完成后删除加密输入
+    pub fn remove_input(&self, input_hash: &[u8; 32]) -> WorkerResult<()> {
+        let path = self.input_path(input_hash);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// 写入加密结果
+    pub fn deliver(&self, request_id: u64, response: &EncryptedResponse) -> WorkerResult<()> {
+        write_json(
+            &self.output_path(request_id),
+            &OutboxEnvelope::new(request_id, response),
+        )
+    }
+
+    /// 取回加密结果
+    pub fn collect(&self, request_id: u64) -> WorkerResult<Option<OutboxEnvelope>> {
+        let path = self.output_path(request_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+    }
+}
+
+/// 原子写入 JSON 文件
+fn write_json<T: Serialize>(path: &Path, value: &T) -> WorkerResult<()> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(value)?)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+fn decode_hex(value: &str) -> WorkerResult<Vec<u8>> {
+    hex::decode(value.trim_start_matches("0x")).map_err(|e| WorkerError::InvalidData(e.to_string()))
+}
+
+fn decode_fixed<const N: usize>(value: &str) -> WorkerResult<[u8; N]> {
+    decode_hex(value)?
+        .try_into()
+        .map_err(|_| WorkerError::InvalidData(format!("expected {} bytes", N)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tee_enclave::client::encrypt_for_enclave;
+
+    fn mailbox(dir: &Path) -> Mailbox {
+        Mailbox::open(dir.join("inbox"), dir.join("outbox")).unwrap()
+    }
+
+    #[test]
+    fn test_deposit_and_load() {
+        let mut rng = rand::thread_rng();
+        let dir = tempfile::tempdir().unwrap();
+        let mailbox = mailbox(dir.path());
+
+        let request = encrypt_for_enclave(&[5u8; 32], b"payload", &mut rng).unwrap();
+        let input_hash = mailbox.deposit(&request).unwrap();
+
+        let loaded = mailbox.load_input(1, &input_hash).unwrap().unwrap();
+        assert_eq!(loaded.ciphertext, request.ciphertext);
+        assert_eq!(loaded.nonce, request.nonce);
+
+        mailbox.remove_input(&input_hash).unwrap();
+        assert!(mailbox.load_input(1, &input_hash).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_replaced_ciphertext_rejected() {
+        let mut rng = rand::thread_rng();
+        let dir = tempfile::tempdir().unwrap();
+        let mailbox = mailbox(dir.path());
+
+        let request = encrypt_for_enclave(&[5u8; 32], b"payload", &mut rng).unwrap();
+        let input_hash = mailbox.deposit(&request).unwrap();
+
+        // 用另一份密文覆盖同名文件
+        let other = encrypt_for_enclave(&[5u8; 32], b"replaced", &mut rng).unwrap();
+        write_json(
+            &mailbox.input_path(&input_hash),
+            &InboxEnvelope::from(&other),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            mailbox.load_input(1, &input_hash),
+            Err(WorkerError::InputHashMismatch(1))
+        ));
+    }
+}
@@ -0,0 +1,38 @@
+//! # TEE Worker 宿主程序
+//!
+//! 连接 `pallet-tee-privacy` 与 `tee-enclave` 的链下进程：
+//!
+//! 1. 启动时从磁盘恢复（或首次生成并密封）Enclave 密钥
+//! 2. 若节点未注册则调用 `register_tee_node` 注册，之后定期 `update_attestation`
+//! 3. 通过 RPC 订阅区块事件，发现分配给本节点的 `ComputeRequestSubmitted`
+//! 4. 从收件箱读取加密输入，交给 `EnclaveRuntime` 解密计算
+//! 5. 加密结果写入发件箱，输出哈希与 Enclave 签名通过 `submit_compute_result` 上链
+//!
+//! ## 数据通道
+//!
+//! 链上只保存加密输入的哈希（见 `tee_enclave::crypto::client::request_input_hash`），
+//! 密文经链下通道投递到 `<data-dir>/inbox/<input_hash>.json`，
+//! 加密结果写入 `<data-dir>/outbox/<request_id>.json` 供客户端取回。
+//!
+//! ## 冗余执行
+//!
+//! `submit_redundant_compute_request` 把同一份密文分配给多个节点（`ReplicasAssigned` 事件，
+//! 在提交后的下一区块初始化阶段发出），各副本独立计算并提交输出哈希，
+//! 链上比对后惩罚结果不一致的节点；达成一致后迟到的结果同样与最终结果比对。
+//! 各副本须能解密同一密文：生产环境由同一 MRENCLAVE 的 Enclave 共享请求密钥，
+//! 模拟模式下可将同一份 `seal.key` 与 `enclave.sealed` 复制到各副本的数据目录。
+//!
+//! ## 模拟模式
+//!
+//! `--simulate` 使用软件密封与模拟认证报告，无需 SGX 硬件，
+//! 可在 `--dev` 节点上跑通完整流程（见 `tests/dev_node.rs`）。
+
+pub mod attestation;
+pub mod chain;
+pub mod cli;
+pub mod error;
+pub mod inbox;
+pub mod sealing;
+pub mod worker;
+
+pub use error::{WorkerError, WorkerResult};
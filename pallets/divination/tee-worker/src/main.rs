@@ -0,0 +1,24 @@
+//! # TEE Worker 入口
+
+use clap::Parser;
+use tee_worker::cli::Cli;
+use tee_worker::worker;
+
+#[tokio::main]
+async fn main() {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let cli = Cli::parse();
+
+    tokio::select! {
+        result = worker::start(cli) => {
+            if let Err(err) = result {
+                log::error!("tee-worker stopped: {}", err);
+                std::process::exit(1);
+            }
+        }
+        _ = tokio::signal::ctrl_c() => {
+            log::info!("tee-worker shutting down");
+        }
+    }
+}
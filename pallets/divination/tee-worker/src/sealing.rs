@@ -0,0 +1,215 @@
+//! # 密钥密封存储
+//!
+//! Enclave 密钥以密封形式保存在磁盘上，重启后恢复同一把密钥，
+//! 链上登记的 Enclave 公钥因此保持不变。
+//!
+//! 密封格式：`版本(1) || Nonce(12) || 认证标签(16) || 密文(64)`
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rand::{CryptoRng, RngCore};
+use tee_enclave::{aes_gcm_decrypt, aes_gcm_encrypt, EnclaveRuntime, SealedKeyData};
+
+use crate::error::{WorkerError, WorkerResult};
+
+/// 密封格式版本
+const SEAL_VERSION: u8 = 1;
+
+/// 密封附加认证数据（绑定用途，防止与其他密封数据混用）
+const SEAL_AAD: &[u8] = b"stardust-tee-worker-keys-v1";
+
+/// 密钥密封器
+pub trait KeySealer {
+    /// 密封密钥数据
+    fn seal(&self, data: &SealedKeyData, rng: &mut dyn RngCore) -> WorkerResult<Vec<u8>>;
+
+    /// 解封密钥数据
+    fn unseal(&self, blob: &[u8]) -> WorkerResult<SealedKeyData>;
+}
+
+/// 软件密封器（模拟模式）
+///
+/// 使用数据目录下的随机主密钥做 AES-256-GCM 加密，
+/// 仅用于开发和测试；生产环境由 SGX Sealing 绑定 MRENCLAVE。
+pub struct SimulatedSealer {
+    /// 密封主密钥
+    key: [u8; 32],
+}
+
+impl SimulatedSealer {
+    /// 使用给定主密钥创建
+    pub fn new(key: [u8; 32]) -> Self {
+        Self { key }
+    }
+
+    /// 从文件加载主密钥，不存在则生成
+    pub fn load_or_create<R: RngCore + CryptoRng>(path: &Path, rng: &mut R) -> WorkerResult<Self> {
+        if path.exists() {
+            let bytes = fs::read(path)?;
+            let key: [u8; 32] = bytes
+                .as_slice()
+                .try_into()
+                .map_err(|_| WorkerError::Sealing("invalid seal key length".into()))?;
+            return Ok(Self::new(key));
+        }
+
+        let mut key = [0u8; 32];
+        rng.fill_bytes(&mut key);
+        write_private(path, &key)?;
+        Ok(Self::new(key))
+    }
+}
+
+impl KeySealer for SimulatedSealer {
+    fn seal(&self, data: &SealedKeyData, rng: &mut dyn RngCore) -> WorkerResult<Vec<u8>> {
+        let mut nonce = [0u8; 12];
+        rng.fill_bytes(&mut nonce);
+
+        let mut plaintext = [0u8; 64];
+        plaintext[..32].copy_from_slice(&data.x25519_secret);
+        plaintext[32..].copy_from_slice(&data.ed25519_secret);
+
+        let result = aes_gcm_encrypt(&self.key, &nonce, &plaintext, SEAL_AAD);
+        plaintext.iter_mut().for_each(|b| *b = 0);
+        let (ciphertext, auth_tag) = result?;
+
+        let mut blob = Vec::with_capacity(1 + 12 + 16 + ciphertext.len());
+        blob.push(SEAL_VERSION);
+        blob.extend_from_slice(&nonce);
+        blob.extend_from_slice(&auth_tag);
+        blob.extend_from_slice(&ciphertext);
+        Ok(blob)
+    }
+
+    fn unseal(&self, blob: &[u8]) -> WorkerResult<SealedKeyData> {
+        if blob.len() != 1 + 12 + 16 + 64 || blob[0] != SEAL_VERSION {
+            return Err(WorkerError::Sealing(
+                "unrecognized sealed key format".into(),
+            ));
+        }
+
+        let nonce: [u8; 12] = blob[1..13].try_into().expect("length checked");
+        let auth_tag: [u8; 16] = blob[13..29].try_into().expect("length checked");
+        let plaintext = aes_gcm_decrypt(&self.key, &nonce, &blob[29..], &auth_tag, SEAL_AAD)
+            .map_err(|_| {
+                WorkerError::Sealing("sealed keys cannot be opened with this seal key".into())
+            })?;
+
+        let mut data = SealedKeyData {
+            x25519_secret: [0u8; 32],
+            ed25519_secret: [0u8; 32],
+        };
+        data.x25519_secret.copy_from_slice(&plaintext[..32]);
+        data.ed25519_secret.copy_from_slice(&plaintext[32..]);
+        Ok(data)
+    }
+}
+
+/// 密封密钥存储
+pub struct SealedKeyStore<S: KeySealer> {
+    /// 密封器
+    sealer: S,
+    /// 密封文件路径
+    path: PathBuf,
+}
+
+impl<S: KeySealer> SealedKeyStore<S> {
+    /// 创建存储
+    pub fn new(sealer: S, path: PathBuf) -> Self {
+        Self { sealer, path }
+    }
+
+    /// 恢复 Enclave 运行时；首次启动时生成新密钥并密封到磁盘
+    ///
+    /// 返回 (运行时, 是否新生成密钥)
+    pub fn load_or_init_runtime<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+    ) -> WorkerResult<(EnclaveRuntime, bool)> {
+        let mut runtime = EnclaveRuntime::new();
+
+        if self.path.exists() {
+            let blob = fs::read(&self.path)?;
+            let sealed = self.sealer.unseal(&blob)?;
+            runtime.restore_from_sealed(&sealed.x25519_secret, &sealed.ed25519_secret)?;
+            return Ok((runtime, false));
+        }
+
+        runtime.initialize(rng)?;
+        let sealed = runtime.get_sealed_data()?;
+        let blob = self.sealer.seal(&sealed, rng)?;
+        write_private(&self.path, &blob)?;
+        Ok((runtime, true))
+    }
+}
+
+/// 原子写入仅所有者可读的文件
+fn write_private(path: &Path, data: &[u8]) -> WorkerResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, data)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&tmp, fs::Permissions::from_mode(0o600))?;
+    }
+
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_unseal_roundtrip() {
+        let mut rng = rand::thread_rng();
+        let sealer = SimulatedSealer::new([7u8; 32]);
+        let data = SealedKeyData {
+            x25519_secret: [1u8; 32],
+            ed25519_secret: [2u8; 32],
+        };
+
+        let blob = sealer.seal(&data, &mut rng).unwrap();
+        let restored = sealer.unseal(&blob).unwrap();
+
+        assert_eq!(restored.x25519_secret, data.x25519_secret);
+        assert_eq!(restored.ed25519_secret, data.ed25519_secret);
+
+        // 其他主密钥无法解封
+        assert!(SimulatedSealer::new([8u8; 32]).unseal(&blob).is_err());
+    }
+
+    #[test]
+    fn test_runtime_keys_survive_restart() {
+        let mut rng = rand::thread_rng();
+        let dir = tempfile::tempdir().unwrap();
+        let seal_key = dir.path().join("seal.key");
+        let sealed = dir.path().join("enclave.sealed");
+
+        let store = SealedKeyStore::new(
+            SimulatedSealer::load_or_create(&seal_key, &mut rng).unwrap(),
+            sealed.clone(),
+        );
+        let (runtime1, created) = store.load_or_init_runtime(&mut rng).unwrap();
+        assert!(created);
+
+        let store = SealedKeyStore::new(
+            SimulatedSealer::load_or_create(&seal_key, &mut rng).unwrap(),
+            sealed,
+        );
+        let (runtime2, created) = store.load_or_init_runtime(&mut rng).unwrap();
+        assert!(!created);
+
+        let keys1 = runtime1.get_public_keys().unwrap();
+        let keys2 = runtime2.get_public_keys().unwrap();
+        assert_eq!(keys1.x25519_pubkey, keys2.x25519_pubkey);
+        assert_eq!(keys1.ed25519_pubkey, keys2.ed25519_pubkey);
+    }
+}
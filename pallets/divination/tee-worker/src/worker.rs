@@ -0,0 +1,451 @@
+//! # Worker 主循环
+//!
+//! 每个新区块：
+//! 1. 解析 `ComputeRequestSubmitted` / `ReplicasAssigned`，记录分配给本节点的请求
+//! 2. 每 `rescan_blocks` 个区块扫描一次 `PendingRequests`，接手故障转移分配的请求
+//! 3. 对已投递输入的请求执行 Enclave 计算并提交 `submit_compute_result`
+//! 4. 距上次认证超过 `attestation_refresh_blocks` 时调用 `update_attestation`
+
+use std::collections::BTreeMap;
+
+use futures::StreamExt;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use tee_enclave::EnclaveRuntime;
+
+use crate::attestation::{Attestor, SimulatedAttestor};
+use crate::chain::{
+    assigned_replicas, submitted_requests, AccountId, BlockNumber, ChainClient, ComputeRequestInfo,
+    RequestStatus, TeeNodeStatus,
+};
+use crate::cli::Cli;
+use crate::error::{WorkerError, WorkerResult};
+use crate::inbox::Mailbox;
+use crate::sealing::{SealedKeyStore, SimulatedSealer};
+
+/// 计算完成后上链的数据
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutedRequest {
+    /// 请求 ID
+    pub request_id: u64,
+    /// 输出哈希
+    pub output_hash: [u8; 32],
+    /// Enclave 签名
+    pub enclave_signature: [u8; 64],
+}
+
+/// 执行单个计算请求
+///
+/// 输入尚未投递时返回 `None`；成功后加密结果写入发件箱。
+/// `timestamp` 为 Unix 秒，写入 Enclave 计算证明。
+pub fn execute_request<R: RngCore + CryptoRng>(
+    runtime: &mut EnclaveRuntime,
+    mailbox: &Mailbox,
+    request: &ComputeRequestInfo,
+    timestamp: u64,
+    rng: &mut R,
+) -> WorkerResult<Option<ExecutedRequest>> {
+    let encrypted = match mailbox.load_input(request.id, &request.input_hash)? {
+        Some(encrypted) => encrypted,
+        None => return Ok(None),
+    };
+
+    let processed = runtime.process_encrypted_request(&encrypted, timestamp, rng)?;
+
+    // 链上登记的计算类型必须与密文内的请求一致
+    if processed.response.compute_type as u8 != request.compute_type_id {
+        return Err(WorkerError::ComputeTypeMismatch(request.id));
+    }
+
+    mailbox.deliver(request.id, &processed.encrypted_response)?;
+    mailbox.remove_input(&request.input_hash)?;
+
+    Ok(Some(ExecutedRequest {
+        request_id: request.id,
+        output_hash: processed.proof.output_hash,
+        enclave_signature: processed.proof.enclave_signature,
+    }))
+}
+
+/// Worker 运行参数
+#[derive(Debug, Clone, Copy)]
+pub struct WorkerConfig {
+    /// 认证刷新间隔（区块）
+    pub attestation_refresh_blocks: u32,
+    /// 全量扫描间隔（区块）
+    pub rescan_blocks: u32,
+}
+
+impl From<&Cli> for WorkerConfig {
+    fn from(cli: &Cli) -> Self {
+        Self {
+            attestation_refresh_blocks: cli.attestation_refresh_blocks.max(1),
+            rescan_blocks: cli.rescan_blocks.max(1),
+        }
+    }
+}
+
+/// TEE Worker
+pub struct Worker<A: Attestor> {
+    /// 链客户端
+    chain: ChainClient,
+    /// Enclave 运行时
+    runtime: EnclaveRuntime,
+    /// 认证报告生成器
+    attestor: A,
+    /// 收发件箱
+    mailbox: Mailbox,
+    /// 运行参数
+    config: WorkerConfig,
+    /// 已分配给本节点、等待输入或处理的请求
+    waiting: BTreeMap<u64, ComputeRequestInfo>,
+    /// 上次提交认证的区块
+    last_attested_at: BlockNumber,
+}
+
+impl<A: Attestor> Worker<A> {
+    /// 创建 Worker
+    pub fn new(
+        chain: ChainClient,
+        runtime: EnclaveRuntime,
+        attestor: A,
+        mailbox: Mailbox,
+        config: WorkerConfig,
+    ) -> Self {
+        Self {
+            chain,
+            runtime,
+            attestor,
+            mailbox,
+            config,
+            waiting: BTreeMap::new(),
+            last_attested_at: 0,
+        }
+    }
+
+    /// 本节点账户
+    fn account(&self) -> AccountId {
+        self.chain.account_id()
+    }
+
+    /// 确保节点已注册且认证新鲜
+    ///
+    /// 已注册节点的 Enclave 公钥必须与密封密钥一致，否则说明密封文件丢失或被替换。
+    pub async fn ensure_registered(&mut self, block: BlockNumber) -> WorkerResult<()> {
+        let keys = self.runtime.get_public_keys()?;
+        let attestation = self.attestor.attest(&keys, self.chain.now().await?)?;
+
+        match self.chain.own_node().await? {
+            None => {
+                log::info!("registering TEE node {}", hex::encode(self.account()));
+                self.chain
+                    .register_tee_node(keys.ed25519_pubkey, &attestation)
+                    .await?;
+            }
+            Some(node) => {
+                if node.enclave_pubkey != keys.ed25519_pubkey {
+                    return Err(WorkerError::InvalidData(
+                        "on-chain enclave key differs from sealed keys".into(),
+                    ));
+                }
+                if node.status != TeeNodeStatus::Active {
+                    log::warn!(
+                        "TEE node status is {:?}, requests will not be assigned",
+                        node.status
+                    );
+                }
+                self.chain.update_attestation(&attestation).await?;
+            }
+        }
+
+        self.last_attested_at = block;
+        Ok(())
+    }
+
+    /// 记录分配给本节点且仍在处理中的请求
+    ///
+    /// 冗余执行请求中，本节点是任一尚未提交结果的副本即需处理
+    async fn track(&mut self, request_id: u64) -> WorkerResult<()> {
+        if self.waiting.contains_key(&request_id) {
+            return Ok(());
+        }
+
+        if let Some(request) = self.chain.compute_request(request_id).await? {
+            if request.status != RequestStatus::Processing {
+                return Ok(());
+            }
+
+            let assigned = match self.chain.request_replicas(request_id).await? {
+                Some(replicas) => replicas.awaits(&self.account()),
+                None => request.assigned_node == Some(self.account()),
+            };
+            if assigned {
+                log::info!("tracking compute request {}", request_id);
+                self.waiting.insert(request_id, request);
+            }
+        }
+        Ok(())
+    }
+
+    /// 处理一个新区块
+    async fn on_block(&mut self, block: BlockNumber, submitted: Vec<u64>) -> WorkerResult<()> {
+        for request_id in submitted {
+            self.track(request_id).await?;
+        }
+
+        if block.is_multiple_of(self.config.rescan_blocks) {
+            for request_id in self.chain.pending_requests().await? {
+                self.track(request_id).await?;
+            }
+        }
+
+        self.process_waiting(block).await?;
+
+        if block.saturating_sub(self.last_attested_at) >= self.config.attestation_refresh_blocks {
+            let keys = self.runtime.get_public_keys()?;
+            let attestation = self.attestor.attest(&keys, self.chain.now().await?)?;
+            self.chain.update_attestation(&attestation).await?;
+            self.last_attested_at = block;
+            log::info!("attestation refreshed at block {}", block);
+        }
+
+        Ok(())
+    }
+
+    /// 处理等待中的请求
+    async fn process_waiting(&mut self, block: BlockNumber) -> WorkerResult<()> {
+        let timestamp = self.chain.now().await? / 1000;
+        let ids: Vec<u64> = self.waiting.keys().copied().collect();
+
+        for request_id in ids {
+            let request = self.waiting[&request_id].clone();
+
+            if block >= request.timeout_at {
+                log::warn!(
+                    "compute request {} timed out before input arrived",
+                    request_id
+                );
+                self.waiting.remove(&request_id);
+                continue;
+            }
+
+            match execute_request(
+                &mut self.runtime,
+                &self.mailbox,
+                &request,
+                timestamp,
+                &mut OsRng,
+            ) {
+                Ok(None) => {}
+                Ok(Some(executed)) => {
+                    self.waiting.remove(&request_id);
+                    self.chain
+                        .submit_compute_result(
+                            executed.request_id,
+                            executed.output_hash,
+                            executed.enclave_signature,
+                        )
+                        .await?;
+                    log::info!("compute request {} completed", request_id);
+                }
+                Err(err) => {
+                    // 放弃该请求，链上超时后会故障转移
+                    self.waiting.remove(&request_id);
+                    log::error!("compute request {} failed: {}", request_id, err);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 订阅区块并持续处理
+    pub async fn run(&mut self) -> WorkerResult<()> {
+        let mut blocks = self.chain.api().blocks().subscribe_best().await?;
+
+        let mut registered = false;
+        while let Some(block) = blocks.next().await {
+            let block = block?;
+            let number = block.number();
+
+            if !registered {
+                self.ensure_registered(number).await?;
+                registered = true;
+            }
+
+            let events = block.events().await?;
+            let account = self.account();
+            let mut submitted: Vec<u64> = submitted_requests(events.iter())?
+                .into_iter()
+                .filter(|event| event.assigned_node == Some(account))
+                .map(|event| event.request_id)
+                .collect();
+            submitted.extend(
+                assigned_replicas(events.iter())?
+                    .into_iter()
+                    .filter(|event| event.nodes.contains(&account))
+                    .map(|event| event.request_id),
+            );
+
+            if let Err(err) = self.on_block(number, submitted).await {
+                log::error!("block {} handling failed: {}", number, err);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// 按命令行参数启动 Worker
+pub async fn start(cli: Cli) -> WorkerResult<()> {
+    if !cli.simulate {
+        return Err(WorkerError::Unsupported(
+            "hardware mode needs the SGX enclave build (sealing and quotes); use --simulate".into(),
+        ));
+    }
+
+    let mut rng = OsRng;
+    let sealer = SimulatedSealer::load_or_create(&cli.data_dir.join("seal.key"), &mut rng)?;
+    let (runtime, created) =
+        SealedKeyStore::new(sealer, cli.sealed_keys_path()).load_or_init_runtime(&mut rng)?;
+    let keys = runtime.get_public_keys()?;
+    log::info!(
+        "enclave keys {} (ed25519 {}, x25519 {})",
+        if created { "generated" } else { "restored" },
+        hex::encode(keys.ed25519_pubkey),
+        hex::encode(keys.x25519_pubkey),
+    );
+
+    let mailbox = Mailbox::open(cli.inbox_dir(), cli.outbox_dir())?;
+    let chain = ChainClient::connect(&cli.rpc_url, &cli.suri).await?;
+
+    let mut worker = Worker::new(
+        chain,
+        runtime,
+        SimulatedAttestor,
+        mailbox,
+        WorkerConfig::from(&cli),
+    );
+    worker.run().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tee_enclave::client::{
+        decrypt_response, encrypt_for_enclave, encrypt_for_enclave_with_session,
+    };
+    use tee_enclave::{
+        sha256_hash, ComputeInput, ComputeOutput, ComputeRequest, ComputeTypeId, XiaoLiuRenInput,
+    };
+
+    fn request_info(id: u64, compute_type_id: u8, input_hash: [u8; 32]) -> ComputeRequestInfo {
+        ComputeRequestInfo {
+            id,
+            requester: [1u8; 32],
+            compute_type_id,
+            input_hash,
+            assigned_node: Some([2u8; 32]),
+            created_at: 1,
+            timeout_at: 101,
+            status: RequestStatus::Processing,
+            failover_count: 0,
+            failure_reason: None,
+        }
+    }
+
+    fn xiaoliuren_request() -> Vec<u8> {
+        serde_json::to_vec(&ComputeRequest {
+            request_id: 1,
+            compute_type: ComputeTypeId::XiaoLiuRen,
+            input: ComputeInput::XiaoLiuRen(XiaoLiuRenInput {
+                month: 5,
+                day: 15,
+                hour: 10,
+            }),
+            timestamp: 1704067200,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_execute_request_pipeline() {
+        let mut rng = rand::thread_rng();
+        let dir = tempfile::tempdir().unwrap();
+        let mailbox = Mailbox::open(dir.path().join("inbox"), dir.path().join("outbox")).unwrap();
+
+        let mut runtime = EnclaveRuntime::new();
+        runtime.initialize(&mut rng).unwrap();
+        let keys = runtime.get_public_keys().unwrap();
+
+        // 客户端加密并投递
+        let (encrypted, session_key) =
+            encrypt_for_enclave_with_session(&keys.x25519_pubkey, &xiaoliuren_request(), &mut rng)
+                .unwrap();
+        let input_hash = mailbox.deposit(&encrypted).unwrap();
+        let request = request_info(9, ComputeTypeId::XiaoLiuRen as u8, input_hash);
+
+        let executed = execute_request(&mut runtime, &mailbox, &request, 1704067200, &mut rng)
+            .unwrap()
+            .unwrap();
+        assert_eq!(executed.request_id, 9);
+
+        // 发件箱结果与上链哈希一致
+        let envelope = mailbox.collect(9).unwrap().unwrap();
+        let response = envelope.to_encrypted_response().unwrap();
+        assert_eq!(response.output_hash, executed.output_hash);
+        assert_eq!(response.enclave_signature, executed.enclave_signature);
+
+        // 输入处理后被删除，不会重复执行
+        assert!(
+            execute_request(&mut runtime, &mailbox, &request, 1704067200, &mut rng)
+                .unwrap()
+                .is_none()
+        );
+
+        // 客户端用会话密钥解密结果
+        let output = decrypt_response(&response, &session_key).unwrap();
+        assert_eq!(sha256_hash(&output), executed.output_hash);
+        let output: ComputeOutput = serde_json::from_slice(&output).unwrap();
+        assert!(matches!(output, ComputeOutput::XiaoLiuRen(_)));
+    }
+
+    #[test]
+    fn test_execute_request_rejects_type_mismatch() {
+        let mut rng = rand::thread_rng();
+        let dir = tempfile::tempdir().unwrap();
+        let mailbox = Mailbox::open(dir.path().join("inbox"), dir.path().join("outbox")).unwrap();
+
+        let mut runtime = EnclaveRuntime::new();
+        runtime.initialize(&mut rng).unwrap();
+        let keys = runtime.get_public_keys().unwrap();
+
+        let encrypted =
+            encrypt_for_enclave(&keys.x25519_pubkey, &xiaoliuren_request(), &mut rng).unwrap();
+        let input_hash = mailbox.deposit(&encrypted).unwrap();
+        let request = request_info(3, ComputeTypeId::BaZi as u8, input_hash);
+
+        assert!(matches!(
+            execute_request(&mut runtime, &mailbox, &request, 1704067200, &mut rng),
+            Err(WorkerError::ComputeTypeMismatch(3))
+        ));
+        assert!(mailbox.collect(3).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_execute_request_waits_for_input() {
+        let mut rng = rand::thread_rng();
+        let dir = tempfile::tempdir().unwrap();
+        let mailbox = Mailbox::open(dir.path().join("inbox"), dir.path().join("outbox")).unwrap();
+
+        let mut runtime = EnclaveRuntime::new();
+        runtime.initialize(&mut rng).unwrap();
+
+        let request = request_info(4, ComputeTypeId::XiaoLiuRen as u8, [7u8; 32]);
+        assert!(
+            execute_request(&mut runtime, &mailbox, &request, 1704067200, &mut rng)
+                .unwrap()
+                .is_none()
+        );
+    }
+}
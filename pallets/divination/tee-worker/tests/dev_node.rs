@@ -0,0 +1,109 @@
+//! 针对 `--dev` 节点的端到端测试
+//!
+//! 启动开发链后运行：
+//!
+//! ```text
+//! TEE_WORKER_DEV_NODE=ws://127.0.0.1:9944 cargo test -- --ignored
+//! ```
+//!
+//! 流程：Alice 以模拟模式运行 Worker 并注册节点 → Bob 从认证报告取 X25519 公钥加密请求、
+//! 投递收件箱并提交 `submit_compute_request` → Worker 计算并上链 → Bob 从发件箱取回结果解密。
+
+use std::time::Duration;
+
+use tee_enclave::client::{decrypt_response, encrypt_for_enclave_with_session};
+use tee_enclave::{
+    sha256_hash, ComputeInput, ComputeOutput, ComputeRequest, ComputeTypeId, XiaoLiuRenInput,
+};
+use tee_worker::chain::{ChainClient, RequestStatus};
+use tee_worker::cli::Cli;
+use tee_worker::inbox::Mailbox;
+use tee_worker::worker;
+
+/// 轮询直到条件满足或超时
+async fn wait_for<T, F, Fut>(mut probe: F) -> T
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Option<T>>,
+{
+    for _ in 0..120 {
+        if let Some(value) = probe().await {
+            return value;
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    panic!("timed out waiting for dev node");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires a running --dev node (TEE_WORKER_DEV_NODE)"]
+async fn simulated_worker_completes_request_on_dev_node() {
+    let url = std::env::var("TEE_WORKER_DEV_NODE").unwrap_or_else(|_| "ws://127.0.0.1:9944".into());
+    let data_dir = tempfile::tempdir().unwrap();
+
+    let cli = Cli {
+        rpc_url: url.clone(),
+        suri: "//Alice".into(),
+        data_dir: data_dir.path().to_path_buf(),
+        simulate: true,
+        attestation_refresh_blocks: 7200,
+        rescan_blocks: 1,
+    };
+    let worker_handle = tokio::spawn(worker::start(cli.clone()));
+
+    let alice = ChainClient::connect(&url, "//Alice")
+        .await
+        .unwrap()
+        .account_id();
+    let bob = ChainClient::connect(&url, "//Bob").await.unwrap();
+
+    // 等待节点注册，从认证报告取加密公钥
+    let node = wait_for(|| async { bob.tee_node(alice).await.unwrap() }).await;
+    let mut x25519_pubkey = [0u8; 32];
+    x25519_pubkey.copy_from_slice(&node.attestation.report_data[32..]);
+
+    // 加密请求并投递收件箱
+    let request = ComputeRequest {
+        request_id: 0,
+        compute_type: ComputeTypeId::XiaoLiuRen,
+        input: ComputeInput::XiaoLiuRen(XiaoLiuRenInput {
+            month: 5,
+            day: 15,
+            hour: 10,
+        }),
+        timestamp: 1704067200,
+    };
+    let plaintext = serde_json::to_vec(&request).unwrap();
+    let (encrypted, session_key) =
+        encrypt_for_enclave_with_session(&x25519_pubkey, &plaintext, &mut rand::thread_rng())
+            .unwrap();
+
+    let mailbox = Mailbox::open(cli.inbox_dir(), cli.outbox_dir()).unwrap();
+    let input_hash = mailbox.deposit(&encrypted).unwrap();
+
+    let request_id = bob
+        .submit_compute_request(ComputeTypeId::XiaoLiuRen as u8, input_hash, Some(alice))
+        .await
+        .unwrap();
+
+    // 等待链上完成
+    wait_for(|| async {
+        let info = bob.compute_request(request_id).await.unwrap()?;
+        (info.status == RequestStatus::Completed).then_some(())
+    })
+    .await;
+
+    // 取回并解密结果
+    let envelope = mailbox
+        .collect(request_id)
+        .unwrap()
+        .expect("result delivered");
+    let response = envelope.to_encrypted_response().unwrap();
+    let output = decrypt_response(&response, &session_key).unwrap();
+    assert_eq!(sha256_hash(&output), response.output_hash);
+
+    let output: ComputeOutput = serde_json::from_slice(&output).unwrap();
+    assert!(matches!(output, ComputeOutput::XiaoLiuRen(_)));
+
+    worker_handle.abort();
+}
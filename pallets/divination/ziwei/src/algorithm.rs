@@ -8,6 +8,9 @@
 //! - 六吉六煞安星
 //! - 四化飞星
 //! - 大运起运
+//!
+//! 命身宫、五行局与紫微/天府定位委托给 `divination-algorithms`，
+//! 与 TEE Enclave 共用同一实现。
 
 use crate::types::*;
 
@@ -18,12 +21,6 @@ use crate::types::*;
 /// 六十甲子纳音五行查表
 /// 返回 (五行, 局数)
 pub fn get_na_yin_wu_xing(tian_gan: TianGan, di_zhi: DiZhi) -> WuXing {
-    let gan_idx = tian_gan.index();
-    let zhi_idx = di_zhi.index();
-
-    // 六十甲子索引 = (gan_idx * 12 + zhi_idx) 但需要考虑天干地支配对规则
-    // 简化：使用 (gan_idx + zhi_idx) % 2 == 0 验证有效配对
-
     // 纳音五行表（按甲子序排列，每组2个）
     // 甲子乙丑海中金，丙寅丁卯炉中火，戊辰己巳大林木，庚午辛未路旁土，壬申癸酉剑锋金
     // 甲戌乙亥山头火，丙子丁丑涧下水，戊寅己卯城头土，庚辰辛巳白蜡金，壬午癸未杨柳木
@@ -31,19 +28,7 @@ pub fn get_na_yin_wu_xing(tian_gan: TianGan, di_zhi: DiZhi) -> WuXing {
     // 甲午乙未沙中金，丙申丁酉山下火，戊戌己亥平地木，庚子辛丑壁上土，壬寅癸卯金箔金
     // 甲辰乙巳覆灯火，丙午丁未天河水，戊申己酉大驿土，庚戌辛亥钗钏金，壬子癸丑桑柘木
     // 甲寅乙卯大溪水，丙辰丁巳沙中土，戊午己未天上火，庚申辛酉石榴木，壬戌癸亥大海水
-
-    // 计算六十甲子序数
-    let jia_zi_idx = ((gan_idx as u16 * 12 + zhi_idx as u16) % 60) as u8;
-    let group = jia_zi_idx / 2;
-
-    match group {
-        0 | 4 | 13 | 17 | 23 | 29 => WuXing::Metal,   // 金
-        1 | 5 | 8 | 12 | 16 | 21 => WuXing::Fire,     // 火
-        2 | 9 | 14 | 18 | 24 | 28 => WuXing::Wood,    // 木
-        3 | 7 | 11 | 15 | 19 | 22 => WuXing::Earth,   // 土
-        6 | 10 | 20 | 25 | 26 | 27 => WuXing::Water,  // 水
-        _ => WuXing::Water,
-    }
+    WuXing::from_ju_shu(divination_algorithms::ziwei::na_yin_ju_shu(tian_gan.index(), di_zhi.index()))
 }
 
 // ============================================================================
@@ -54,21 +39,13 @@ pub fn get_na_yin_wu_xing(tian_gan: TianGan, di_zhi: DiZhi) -> WuXing {
 /// 口诀：顺数生月，逆数生时
 /// 从寅宫起正月，顺数至生月，再从该宫逆数至生时
 pub fn calculate_ming_gong(lunar_month: u8, birth_hour: DiZhi) -> u8 {
-    // 从寅宫(2)起正月，顺数到生月
-    let month_pos = (2 + lunar_month - 1) % 12;
-    // 从该宫逆数到生时
-    let hour_idx = birth_hour.index();
-    let ming_gong = (month_pos + 12 - hour_idx) % 12;
-    ming_gong
+    divination_algorithms::ziwei::calculate_ming_gong(lunar_month, birth_hour.index())
 }
 
 /// 计算身宫位置
 /// 口诀：顺数生月，顺数生时
 pub fn calculate_shen_gong(lunar_month: u8, birth_hour: DiZhi) -> u8 {
-    let month_pos = (2 + lunar_month - 1) % 12;
-    let hour_idx = birth_hour.index();
-    let shen_gong = (month_pos + hour_idx) % 12;
-    shen_gong
+    divination_algorithms::ziwei::calculate_shen_gong(lunar_month, birth_hour.index())
 }
 
 // ============================================================================
@@ -95,18 +72,7 @@ pub fn calculate_wu_xing_ju(year_gan: TianGan, ming_gong_pos: u8) -> (WuXing, u8
 /// 丁壬之年壬为首（寅宫起壬）
 /// 戊癸之年甲为首（寅宫起甲）
 pub fn get_gong_gan(year_gan: TianGan, gong_pos: u8) -> TianGan {
-    // 确定寅宫的天干
-    let yin_gan = match year_gan {
-        TianGan::Jia | TianGan::Ji => TianGan::Bing,
-        TianGan::Yi | TianGan::Geng => TianGan::Wu,
-        TianGan::Bing | TianGan::Xin => TianGan::Geng,
-        TianGan::Ding | TianGan::Ren => TianGan::Ren,
-        TianGan::Wu | TianGan::Gui => TianGan::Jia,
-    };
-
-    // 从寅宫顺推到目标宫
-    let offset = (gong_pos + 12 - 2) % 12; // 寅宫是2
-    TianGan::from_index((yin_gan.index() + offset as u8) % 10)
+    TianGan::from_index(divination_algorithms::ziwei::get_gong_gan(year_gan.index(), gong_pos))
 }
 
 // ============================================================================
@@ -116,59 +82,8 @@ pub fn get_gong_gan(year_gan: TianGan, gong_pos: u8) -> TianGan {
 /// 根据农历日和局数定紫微星位置
 /// 这是紫微斗数最核心的安星步骤
 pub fn calculate_ziwei_position(lunar_day: u8, ju_shu: u8) -> u8 {
-    // 紫微星位置查表（简化版）
-    // 实际应根据《紫微斗数全书》的安星诀完整实现
-
-    // 使用公式：紫微位置 = f(日数, 局数)
-    // 完整的查表数据
-    // 紫微星定位表（已校正）
-    // 数据来源：《紫微斗数全书》安星诀 / openzw 参考实现
-    // 列顺序：水二局、木三局、金四局、土五局、火六局
-    // 数值为地支索引：子=0, 丑=1, 寅=2, 卯=3, 辰=4, 巳=5, 午=6, 未=7, 申=8, 酉=9, 戌=10, 亥=11
-    let ziwei_table: [[u8; 5]; 30] = [
-        [ 1,  4, 11,  6,  9],   // 日1:  丑、辰、亥、午、酉
-        [ 2,  1,  4, 11,  6],   // 日2:  寅、丑、辰、亥、午
-        [ 2,  2,  1,  4, 11],   // 日3:  寅、寅、丑、辰、亥
-        [ 3,  5,  2,  1,  4],   // 日4:  卯、巳、寅、丑、辰
-        [ 3,  2,  0,  2,  1],   // 日5:  卯、寅、子、寅、丑
-        [ 4,  3,  5,  7,  2],   // 日6:  辰、卯、巳、未、寅
-        [ 4,  6,  2,  0, 10],   // 日7:  辰、午、寅、子、戌
-        [ 5,  3,  3,  5,  7],   // 日8:  巳、卯、卯、巳、未
-        [ 5,  4,  1,  2,  0],   // 日9:  巳、辰、丑、寅、子
-        [ 6,  7,  6,  3,  5],   // 日10: 午、未、午、卯、巳
-        [ 6,  4,  3,  8,  2],   // 日11: 午、辰、卯、申、寅
-        [ 7,  5,  4,  1,  3],   // 日12: 未、巳、辰、丑、卯
-        [ 7,  8,  2,  6, 11],   // 日13: 未、申、寅、午、亥
-        [ 8,  5,  7,  3,  8],   // 日14: 申、巳、未、卯、申
-        [ 8,  6,  4,  4,  1],   // 日15: 申、午、辰、辰、丑
-        [ 9,  9,  5,  9,  6],   // 日16: 酉、酉、巳、酉、午
-        [ 9,  6,  3,  2,  3],   // 日17: 酉、午、卯、寅、卯
-        [10,  7,  8,  7,  4],   // 日18: 戌、未、申、未、辰 [修正: 土5→7, 火4不变]
-        [10, 10,  5, 10,  0],   // 日19: 戌、戌、巳、戌、子 [修正: 火9→0]
-        [11,  7,  6,  5,  9],   // 日20: 亥、未、午、巳、酉 [修正: 土3→5, 火6→9]
-        [11,  8,  4, 10,  2],   // 日21: 亥、申、辰、戌、寅 [修正: 土8→10, 火3→2]
-        [ 0, 11,  9,  3,  7],   // 日22: 子、亥、酉、卯、未 [修正: 土5→3, 火4→7]
-        [ 0,  8,  6,  8,  4],   // 日23: 子、申、午、申、辰 [修正: 土2→8, 火1→4]
-        [ 1,  9,  7,  5,  5],   // 日24: 丑、酉、未、巳、巳
-        [ 1,  0,  5,  6,  1],   // 日25: 丑、子、巳、午、丑
-        [ 2,  9, 10, 11, 10],   // 日26: 寅、酉、戌、亥、戌
-        [ 2, 10,  7,  4,  3],   // 日27: 寅、戌、未、辰、卯
-        [ 3,  1,  8,  9,  8],   // 日28: 卯、丑、申、酉、申
-        [ 3, 10,  6,  6,  5],   // 日29: 卯、戌、午、午、巳
-        [ 4, 11, 11,  7,  6],   // 日30: 辰、亥、亥、未、午
-    ];
-
-    let day_idx = (lunar_day.saturating_sub(1) % 30) as usize;
-    let ju_idx = match ju_shu {
-        2 => 0,
-        3 => 1,
-        4 => 2,
-        5 => 3,
-        6 => 4,
-        _ => 0,
-    };
-
-    ziwei_table[day_idx][ju_idx]
+    // 紫微星定位表见 `divination_algorithms::ziwei`（《紫微斗数全书》安星诀）
+    divination_algorithms::ziwei::calculate_ziwei_position(lunar_day, ju_shu)
 }
 
 // ============================================================================
@@ -228,8 +143,7 @@ pub fn place_ziwei_series(ziwei_pos: u8) -> [(ZhuXing, u8); 6] {
 /// | 戌10 | 午6  |
 /// | 亥11 | 巳5  |
 pub fn calculate_tianfu_position(ziwei_pos: u8) -> u8 {
-    // 修正后的公式，与参考实现一致
-    (16 - ziwei_pos) % 12
+    divination_algorithms::ziwei::calculate_tianfu_position(ziwei_pos)
 }
 
 /// 安天府星系（8颗主星）
@@ -1253,3 +1253,33 @@ pub mod pallet {
     }
 
 }
+
+// ============================================================================
+// 跨模块接口
+// ============================================================================
+
+/// 紫微命盘数据提供者 Trait
+///
+/// 供婚恋合婚等模块按命盘 ID 读取十二宫，无需依赖命盘存储结构
+pub trait ZiweiDataProvider<AccountId> {
+    /// 命盘是否存在
+    fn exists(chart_id: u64) -> bool;
+    /// 是否为命盘创建者
+    fn is_owner(account: &AccountId, chart_id: u64) -> bool;
+    /// 十二宫数据（Private 模式或未排盘时为 None）
+    fn get_palaces(chart_id: u64) -> Option<[Palace; 12]>;
+}
+
+impl<T: Config> ZiweiDataProvider<T::AccountId> for Pallet<T> {
+    fn exists(chart_id: u64) -> bool {
+        Charts::<T>::contains_key(chart_id)
+    }
+
+    fn is_owner(account: &T::AccountId, chart_id: u64) -> bool {
+        Charts::<T>::get(chart_id).map_or(false, |chart| &chart.creator == account)
+    }
+
+    fn get_palaces(chart_id: u64) -> Option<[Palace; 12]> {
+        Charts::<T>::get(chart_id).and_then(|chart| chart.palaces)
+    }
+}
@@ -76,7 +76,7 @@ pub struct ZiweiIndex {
     pub ziwei_pos: u8,
     /// 天府星位置 (0-11)
     pub tianfu_pos: u8,
-    /// 五行局 (1-6)
+    /// 五行局 (2-6)
     pub wu_xing_ju: u8,
 }
 
@@ -108,35 +108,21 @@ impl<T: crate::pallet::Config> DivinationModule<T> for ZiweiModuleHandler<T> {
     fn compute(input: &Self::PlainInput) -> Result<Self::Result, ModuleError> {
         <Self as DivinationModule<T>>::validate_input(input)?;
 
-        // 简化实现：内联基本算法
-        let _year = input.year.unwrap_or(2000);
+        // 核心定盘委托给共享算法库，与 TEE Enclave 结果逐字节一致
+        let year = input.year.unwrap_or(2000);
         let month = input.month.unwrap_or(1);
         let day = input.day.unwrap_or(1);
         let hour = input.hour.unwrap_or(0);
-        let _gender = input.gender.unwrap_or(0);
-
-        // 简化的五行局计算（实际应更复杂）
-        let wu_xing_ju = ((month + day) % 5 + 2) as u8; // 2-6
-        
-        // 命宫位置：寅宫起正月，逆数至生月，再顺数至生时
-        let ming_gong = ((14 - month + hour) % 12) as u8;
-        
-        // 身宫位置：寅宫起正月，顺数至生月，再顺数至生时
-        let shen_gong = ((month + hour + 1) % 12) as u8;
-        
-        // 紫微星位置（简化）
-        let ziwei_pos = ((day / wu_xing_ju as u8) % 12) as u8;
-        
-        // 天府星位置（与紫微对称）
-        let tianfu_pos = (12 - ziwei_pos) % 12;
+
+        let core = divination_algorithms::ziwei::ziwei_index(year, month, day, hour);
 
         Ok(ZiweiComputeResult {
             index: ZiweiIndex {
-                ming_gong,
-                shen_gong,
-                ziwei_pos,
-                tianfu_pos,
-                wu_xing_ju,
+                ming_gong: core.ming_gong,
+                shen_gong: core.shen_gong,
+                ziwei_pos: core.ziwei_pos,
+                tianfu_pos: core.tianfu_pos,
+                wu_xing_ju: core.wu_xing_ju,
             },
             palaces: [0; 12],
             main_stars: Vec::new(),
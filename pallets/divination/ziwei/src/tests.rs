@@ -1139,3 +1139,92 @@ fn test_ziwei_chart_result_fields() {
         assert!(palace.di_zhi.index() < 12);
     }
 }
+
+// ============================================================================
+// 跨模块接口测试
+// ============================================================================
+
+#[test]
+fn test_ziwei_data_provider_reads_charts() {
+    use crate::ZiweiDataProvider;
+
+    new_test_ext().execute_with(|| {
+        assert!(!<Ziwei as ZiweiDataProvider<u64>>::exists(0));
+        assert!(<Ziwei as ZiweiDataProvider<u64>>::get_palaces(0).is_none());
+
+        assert_ok!(Ziwei::divine_by_time(
+            RuntimeOrigin::signed(ALICE),
+            1990,
+            1,
+            1,
+            DiZhi::Zi,
+            Gender::Male,
+            false,
+        ));
+
+        assert!(<Ziwei as ZiweiDataProvider<u64>>::exists(0));
+        assert!(<Ziwei as ZiweiDataProvider<u64>>::is_owner(&ALICE, 0));
+        assert!(!<Ziwei as ZiweiDataProvider<u64>>::is_owner(&BOB, 0));
+
+        let palaces = <Ziwei as ZiweiDataProvider<u64>>::get_palaces(0).unwrap();
+        let stored = Ziwei::charts(0).unwrap().palaces.unwrap();
+        for (palace, expected) in palaces.iter().zip(stored.iter()) {
+            assert_eq!(palace.di_zhi, expected.di_zhi);
+            assert_eq!(palace.zhu_xing, expected.zhu_xing);
+        }
+    });
+}
+
+#[test]
+fn test_ziwei_data_provider_hides_private_palaces() {
+    use crate::ZiweiDataProvider;
+    use frame_support::BoundedVec;
+
+    new_test_ext().execute_with(|| {
+        let encrypted_data: BoundedVec<u8, _> = vec![1, 2, 3, 4, 5].try_into().unwrap();
+        assert_ok!(Ziwei::divine_by_time_encrypted(
+            RuntimeOrigin::signed(ALICE),
+            2, // encryption_level = Private
+            1990,
+            1,
+            1,
+            DiZhi::Zi,
+            Gender::Male,
+            false,
+            Some(encrypted_data),
+            Some([0u8; 32]),
+            Some([0u8; 80]),
+        ));
+
+        assert!(<Ziwei as ZiweiDataProvider<u64>>::exists(0));
+        assert!(<Ziwei as ZiweiDataProvider<u64>>::get_palaces(0).is_none());
+    });
+}
+
+// ============================================================================
+// 黄金向量测试（与 TEE Enclave 共用）
+// ============================================================================
+
+#[test]
+fn test_ocw_tee_index_matches_golden_vectors() {
+    use crate::ocw_tee::{ZiweiInputPlain, ZiweiMethod, ZiweiModuleHandler};
+    use codec::Encode;
+    use divination_algorithms::golden::ZIWEI_CASES;
+    use pallet_divination_ocw_tee::DivinationModule;
+
+    for case in ZIWEI_CASES {
+        let input = ZiweiInputPlain {
+            method: ZiweiMethod::Time,
+            year: Some(case.lunar_year),
+            month: Some(case.lunar_month),
+            day: Some(case.lunar_day),
+            hour: Some(case.hour_zhi),
+            gender: Some(0),
+            is_leap_month: None,
+            question: None,
+        };
+
+        let result = ZiweiModuleHandler::<Test>::compute(&input).unwrap();
+        assert_eq!(result.index.encode(), case.expected.to_vec());
+    }
+}
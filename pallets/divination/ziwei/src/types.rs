@@ -201,6 +201,17 @@ impl WuXing {
             Self::Fire => 6,
         }
     }
+
+    /// 从五行局数获取五行（局数无效时按水二局处理）
+    pub fn from_ju_shu(ju_shu: u8) -> Self {
+        match ju_shu {
+            3 => Self::Wood,
+            4 => Self::Metal,
+            5 => Self::Earth,
+            6 => Self::Fire,
+            _ => Self::Water,
+        }
+    }
 }
 
 /// 阴阳
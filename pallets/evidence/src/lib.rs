@@ -245,6 +245,9 @@ pub mod pallet {
         /// 🆕 证据修改窗口（区块数，28800 ≈ 2天，按6秒/块计算）
         #[pallet::constant]
         type EvidenceEditWindow: Get<BlockNumberFor<Self>>;
+
+        /// 统一公钥目录（加密密钥包所用公钥的唯一来源）
+        type KeyDirectory: pallet_key_directory::traits::KeyDirectory<Self::AccountId>;
     }
 
     #[pallet::pallet]
@@ -333,7 +336,19 @@ pub mod pallet {
         OptionQuery,
     >;
 
-    /// 用户公钥存储
+    /// 按密钥包接收者索引私密内容（公钥轮换时定位需重新包装的内容）
+    #[pallet::storage]
+    pub type PrivateContentByRecipient<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        u64, // content_id
+        (),
+        OptionQuery,
+    >;
+
+    /// [已废弃] 用户公钥存储 - 公钥改由 `pallet-key-directory` 提供，仅保留历史数据
     #[pallet::storage]
     pub type UserPublicKeys<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AccountId, UserPublicKey<T>, OptionQuery>;
@@ -566,6 +581,22 @@ pub mod pallet {
             evidence_id: u64,
             manifest_cid: BoundedVec<u8, T::MaxContentCidLen>,
         },
+
+        /// 密钥包接收者在公钥目录中轮换了加密公钥
+        ///
+        /// 内容创建者应在重叠窗口内通过 `rotate_content_keys` / `grant_access`
+        /// 为 `PrivateContentByRecipient` 中该用户的内容重新包装密钥。
+        RecipientKeyRotated {
+            user: T::AccountId,
+            old_version: u32,
+            new_version: u32,
+        },
+
+        /// 密钥包接收者的加密公钥已吊销
+        RecipientKeyRevoked {
+            user: T::AccountId,
+            version: u32,
+        },
     }
 
     #[pallet::error]
@@ -625,6 +656,8 @@ pub mod pallet {
         EditWindowExpired,
         /// 待处理队列已满
         PendingQueueFull,
+        /// 公钥已迁移到统一公钥目录，请使用 key_directory 注册
+        UseKeyDirectory,
     }
 
     #[allow(deprecated)]
@@ -904,52 +937,19 @@ pub mod pallet {
 
         // ===== 私密内容管理 Extrinsics =====
 
-        /// 注册用户公钥（用于加密密钥包）
+        /// [已废弃] 注册用户公钥 - 请使用 key_directory.register_key
+        ///
+        /// 加密密钥包所用公钥只从统一公钥目录读取，本调用不再写入存储。
+        #[deprecated(note = "Use key_directory.register_key with KeyPurpose::Encryption instead")]
         #[pallet::call_index(6)]
         #[pallet::weight(10_000)] // TODO: 使用WeightInfo
         pub fn register_public_key(
             origin: OriginFor<T>,
-            key_data: BoundedVec<u8, T::MaxKeyLen>,
-            key_type: u8, // 1=RSA-2048, 2=Ed25519, 3=ECDSA-P256
+            _key_data: BoundedVec<u8, T::MaxKeyLen>,
+            _key_type: u8,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-
-            // 验证密钥类型
-            ensure!(
-                key_type >= 1 && key_type <= 3,
-                Error::<T>::UnsupportedKeyType
-            );
-
-            // 验证密钥长度
-            match key_type {
-                1 => ensure!(
-                    key_data.len() >= 270 && key_data.len() <= 512,
-                    Error::<T>::InvalidEncryptedKey
-                ), // RSA-2048 DER
-                2 => ensure!(key_data.len() == 32, Error::<T>::InvalidEncryptedKey), // Ed25519
-                3 => ensure!(
-                    key_data.len() == 33 || key_data.len() == 65,
-                    Error::<T>::InvalidEncryptedKey
-                ), // ECDSA
-                _ => return Err(Error::<T>::UnsupportedKeyType.into()),
-            }
-
-            let now = <frame_system::Pallet<T>>::block_number();
-
-            let public_key = UserPublicKey::<T> {
-                key_data,
-                key_type,
-                registered_at: now,
-            };
-
-            UserPublicKeys::<T>::insert(&who, &public_key);
-
-            Self::deposit_event(Event::PublicKeyRegistered {
-                user: who,
-                key_type,
-            });
-
-            Ok(())
+            ensure_signed(origin)?;
+            Err(Error::<T>::UseKeyDirectory.into())
         }
 
         /// 存储私密内容
@@ -1001,7 +1001,7 @@ pub mod pallet {
             // 验证所有用户都已注册公钥
             for (user, _) in encrypted_keys.iter() {
                 ensure!(
-                    UserPublicKeys::<T>::contains_key(user),
+                    Self::has_public_key(user),
                     Error::<T>::PublicKeyNotRegistered
                 );
             }
@@ -1032,6 +1032,9 @@ pub mod pallet {
             PrivateContents::<T>::insert(content_id, &content);
             PrivateContentByCid::<T>::insert(&cid, content_id);
             PrivateContentBySubject::<T>::insert((ns, subject_id), content_id, ());
+            for (user, _) in content.encrypted_keys.iter() {
+                PrivateContentByRecipient::<T>::insert(user, content_id, ());
+            }
 
             Self::deposit_event(Event::PrivateContentStored {
                 content_id,
@@ -1057,7 +1060,7 @@ pub mod pallet {
 
             // 验证用户已注册公钥
             ensure!(
-                UserPublicKeys::<T>::contains_key(&user),
+                Self::has_public_key(&user),
                 Error::<T>::PublicKeyNotRegistered
             );
 
@@ -1087,6 +1090,7 @@ pub mod pallet {
                         .encrypted_keys
                         .try_push((user.clone(), bounded_key))
                         .map_err(|_| Error::<T>::TooManyAuthorizedUsers)?;
+                    PrivateContentByRecipient::<T>::insert(&user, content_id, ());
                 }
 
                 content.updated_at = <frame_system::Pallet<T>>::block_number();
@@ -1124,6 +1128,7 @@ pub mod pallet {
                 content
                     .encrypted_keys
                     .retain(|(existing_user, _)| existing_user != &user);
+                PrivateContentByRecipient::<T>::remove(&user, content_id);
                 content.updated_at = <frame_system::Pallet<T>>::block_number();
 
                 Self::deposit_event(Event::AccessRevoked {
@@ -1161,7 +1166,7 @@ pub mod pallet {
                 // 验证所有用户都已注册公钥
                 for (user, _) in new_encrypted_keys.iter() {
                     ensure!(
-                        UserPublicKeys::<T>::contains_key(user),
+                        Self::has_public_key(user),
                         Error::<T>::PublicKeyNotRegistered
                     );
                 }
@@ -1179,6 +1184,12 @@ pub mod pallet {
                     .collect::<Result<Vec<_>, _>>()?;
                 let bounded_converted = BoundedVec::try_from(converted)
                     .map_err(|_| Error::<T>::TooManyAuthorizedUsers)?;
+                for (user, _) in content.encrypted_keys.iter() {
+                    PrivateContentByRecipient::<T>::remove(user, content_id);
+                }
+                for (user, _) in bounded_converted.iter() {
+                    PrivateContentByRecipient::<T>::insert(user, content_id, ());
+                }
                 content.encrypted_keys = bounded_converted;
                 content.updated_at = <frame_system::Pallet<T>>::block_number();
 
@@ -1501,11 +1512,73 @@ pub mod pallet {
         fn can_access(content_id: u64, user: &AccountId) -> bool;
         /// 获取用户的解密密钥
         fn get_decryption_key(content_id: u64, user: &AccountId) -> Option<Vec<u8>>;
+        /// 获取私密内容的创建者
+        fn content_owner(content_id: u64) -> Option<AccountId>;
+    }
+
+    impl<T: Config> PrivateContentProvider<T::AccountId> for Pallet<T> {
+        fn can_access(content_id: u64, user: &T::AccountId) -> bool {
+            Self::can_access_private_content(content_id, user)
+        }
+
+        fn get_decryption_key(content_id: u64, user: &T::AccountId) -> Option<Vec<u8>> {
+            Self::get_encrypted_key_for_user(content_id, user).map(|key| key.into_inner())
+        }
+
+        fn content_owner(content_id: u64) -> Option<T::AccountId> {
+            PrivateContents::<T>::get(content_id).map(|content| content.creator)
+        }
+    }
+
+    impl<T: Config> pallet_key_directory::traits::KeyChangeHandler<T::AccountId> for Pallet<T> {
+        fn max_weight() -> Weight {
+            T::DbWeight::get().reads(1)
+        }
+
+        /// 接收者轮换加密公钥时发出重新包装提示（受影响内容见 `PrivateContentByRecipient`）
+        fn on_key_rotated(
+            who: &T::AccountId,
+            purpose: pallet_key_directory::types::KeyPurpose,
+            old_version: u32,
+            new_version: u32,
+        ) {
+            if purpose != pallet_key_directory::types::KeyPurpose::Encryption
+                || PrivateContentByRecipient::<T>::iter_key_prefix(who).next().is_none()
+            {
+                return;
+            }
+            Self::deposit_event(Event::RecipientKeyRotated {
+                user: who.clone(),
+                old_version,
+                new_version,
+            });
+        }
+
+        /// 接收者公钥吊销时提示创建者撤销或重新授权
+        fn on_key_revoked(
+            who: &T::AccountId,
+            purpose: pallet_key_directory::types::KeyPurpose,
+            version: u32,
+        ) {
+            if purpose != pallet_key_directory::types::KeyPurpose::Encryption
+                || PrivateContentByRecipient::<T>::iter_key_prefix(who).next().is_none()
+            {
+                return;
+            }
+            Self::deposit_event(Event::RecipientKeyRevoked { user: who.clone(), version });
+        }
     }
 
     impl<T: Config> Pallet<T> {
         // ===== 私密内容查询方法 =====
 
+        /// 用户是否已在统一公钥目录注册可用于加密密钥包的公钥
+        pub fn has_public_key(user: &T::AccountId) -> bool {
+            use pallet_key_directory::{traits::KeyDirectory, types::KeyPurpose};
+
+            T::KeyDirectory::has_key(user, KeyPurpose::Encryption)
+        }
+
         /// 检查用户是否有访问特定私密内容的权限
         pub fn can_access_private_content(content_id: u64, user: &T::AccountId) -> bool {
             if let Some(content) = PrivateContents::<T>::get(content_id) {
@@ -0,0 +1,87 @@
+//! # 统一公钥目录基准测试
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use sp_std::vec;
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn register_key() {
+        let caller: T::AccountId = whitelisted_caller();
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), KeyPurpose::Encryption, KeyAlgorithm::X25519, vec![1u8; 32]);
+    }
+
+    #[benchmark]
+    fn rotate_key() {
+        let caller: T::AccountId = whitelisted_caller();
+        // 先填满保留版本并让其过期，使本次轮换扫描并清理全部旧版本
+        let _ = Pallet::<T>::register_key(
+            RawOrigin::Signed(caller.clone()).into(),
+            KeyPurpose::Encryption,
+            KeyAlgorithm::X25519,
+            vec![1u8; 32],
+        );
+        for seed in 2..=MAX_RETAINED_VERSIONS as u8 {
+            let _ = Pallet::<T>::rotate_key(
+                RawOrigin::Signed(caller.clone()).into(),
+                KeyPurpose::Encryption,
+                KeyAlgorithm::X25519,
+                vec![seed; 32],
+            );
+        }
+        let now = frame_system::Pallet::<T>::block_number();
+        frame_system::Pallet::<T>::set_block_number(
+            now.saturating_add(T::RotationOverlap::get()).saturating_add(1u32.into()),
+        );
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            KeyPurpose::Encryption,
+            KeyAlgorithm::X25519,
+            vec![MAX_RETAINED_VERSIONS as u8 + 1; 32],
+        );
+    }
+
+    #[benchmark]
+    fn revoke_key() {
+        let caller: T::AccountId = whitelisted_caller();
+        let _ = Pallet::<T>::register_key(
+            RawOrigin::Signed(caller.clone()).into(),
+            KeyPurpose::Encryption,
+            KeyAlgorithm::X25519,
+            vec![1u8; 32],
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), KeyPurpose::Encryption, 1);
+    }
+
+    #[benchmark]
+    fn force_revoke_key() -> Result<(), BenchmarkError> {
+        let who: T::AccountId = whitelisted_caller();
+        let _ = Pallet::<T>::register_key(
+            RawOrigin::Signed(who.clone()).into(),
+            KeyPurpose::Encryption,
+            KeyAlgorithm::X25519,
+            vec![1u8; 32],
+        );
+        let origin =
+            T::GovernanceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, who, KeyPurpose::Encryption, 1);
+
+        Ok(())
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,452 @@
+//! # 统一公钥目录模块 (pallet-key-directory)
+//!
+//! 为隐私授权、证据、聊天群组与 TEE 提供统一的链上公钥注册表，
+//! 客户端只需在此发布与轮换一次公钥。
+//!
+//! ## 功能概述
+//!
+//! 1. **按用途注册**：加密、签名、TEE Enclave 三类用途各自独立
+//! 2. **版本化**：每次轮换版本号递增，链上保留当前版本与仍处于重叠窗口内的旧版本
+//!    （最多 `MAX_RETAINED_VERSIONS` 个）
+//! 3. **重叠窗口轮换**：旧版本在 `RotationOverlap` 个区块内仍可用于解密，
+//!    供授权方在窗口内用新公钥重新包装数据密钥
+//! 4. **吊销**：账户自行吊销或治理强制吊销（密钥泄露）
+//!
+//! ## 消费方
+//!
+//! ```text
+//!   pallet-divination-privacy ─┐
+//!   pallet-evidence ───────────┤   KeyDirectory trait    ┌──────────────────────┐
+//!   pallet-chat-group ─────────┼───────────────────────▶ │ pallet-key-directory │
+//!   pallet-tee-privacy ────────┘                         └──────────┬───────────┘
+//!                                                                   │ KeyChangeHandler
+//!                                       轮换 / 吊销回调（重新包装授权密钥） ◀─┘
+//! ```
+//!
+//! ## 使用示例
+//!
+//! ```ignore
+//! KeyDirectory::register_key(origin, KeyPurpose::Encryption, KeyAlgorithm::X25519, pk)?;
+//! KeyDirectory::rotate_key(origin, KeyPurpose::Encryption, KeyAlgorithm::X25519, new_pk)?;
+//! KeyDirectory::revoke_key(origin, KeyPurpose::Encryption, 1)?;
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+pub mod traits;
+pub mod types;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use sp_runtime::traits::Saturating;
+use sp_std::vec::Vec;
+
+use crate::traits::{KeyChangeHandler, KeyDirectory};
+use crate::types::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use crate::weights::WeightInfo;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Pallet 配置 trait
+    #[pallet::config]
+    pub trait Config: frame_system::Config<RuntimeEvent: From<Event<Self>>> {
+        /// 公钥最大长度（RSA-2048 DER 需 512）
+        #[pallet::constant]
+        type MaxKeyLen: Get<u32>;
+
+        /// 轮换重叠窗口（区块数）
+        #[pallet::constant]
+        type RotationOverlap: Get<BlockNumberFor<Self>>;
+
+        /// 强制吊销权限（治理）
+        type GovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// 密钥变更回调
+        type OnKeyChange: KeyChangeHandler<Self::AccountId>;
+
+        /// 权重信息
+        type WeightInfo: WeightInfo;
+    }
+
+    /// 密钥版本记录类型
+    pub type KeyRecordOf<T> = KeyRecord<BlockNumberFor<T>, <T as Config>::MaxKeyLen>;
+
+    // ========================================================================
+    // 存储定义
+    // ========================================================================
+
+    /// 密钥版本
+    ///
+    /// (AccountId, KeyPurpose, version) -> KeyRecord，仅保留最新版本与重叠窗口内的旧版本
+    #[pallet::storage]
+    #[pallet::getter(fn keys)]
+    pub type Keys<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, T::AccountId>,
+            NMapKey<Twox64Concat, KeyPurpose>,
+            NMapKey<Twox64Concat, u32>,
+        ),
+        KeyRecordOf<T>,
+    >;
+
+    /// 最新版本号（0 表示从未注册）
+    ///
+    /// (AccountId, KeyPurpose) -> version
+    #[pallet::storage]
+    #[pallet::getter(fn latest_version)]
+    pub type LatestVersion<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        KeyPurpose,
+        u32,
+        ValueQuery,
+    >;
+
+    // ========================================================================
+    // 事件
+    // ========================================================================
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// 公钥已注册
+        KeyRegistered {
+            who: T::AccountId,
+            purpose: KeyPurpose,
+            version: u32,
+            algorithm: KeyAlgorithm,
+        },
+        /// 公钥已轮换，旧版本在 `usable_until` 前仍可解密
+        ///
+        /// 授权方据此用新公钥重新包装数据密钥。
+        KeyRotated {
+            who: T::AccountId,
+            purpose: KeyPurpose,
+            old_version: u32,
+            new_version: u32,
+            usable_until: BlockNumberFor<T>,
+        },
+        /// 公钥已吊销
+        KeyRevoked {
+            who: T::AccountId,
+            purpose: KeyPurpose,
+            version: u32,
+            forced: bool,
+        },
+    }
+
+    // ========================================================================
+    // 错误
+    // ========================================================================
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// 该用途已有有效公钥（请使用轮换）
+        KeyAlreadyRegistered,
+        /// 该用途没有有效公钥
+        NoActiveKey,
+        /// 密钥版本不存在
+        KeyNotFound,
+        /// 密钥已吊销
+        KeyAlreadyRevoked,
+        /// 用途不支持该算法
+        AlgorithmNotAllowed,
+        /// 公钥长度与算法不符或全零
+        InvalidPublicKey,
+        /// 公钥过长
+        KeyTooLong,
+        /// 新公钥与当前公钥相同
+        SameKey,
+        /// 版本号溢出
+        VersionOverflow,
+        /// 重叠窗口内的旧版本过多，需等待最早的旧版本过期或将其吊销
+        TooManyOverlappingVersions,
+    }
+
+    // ========================================================================
+    // 交易调用
+    // ========================================================================
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// 注册公钥
+        ///
+        /// 用于首次注册，或当前版本被吊销后重新注册（版本号继续递增）。
+        ///
+        /// # 参数
+        /// - `origin`: 交易发起者
+        /// - `purpose`: 密钥用途
+        /// - `algorithm`: 公钥算法
+        /// - `public_key`: 公钥字节
+        ///
+        /// # 错误
+        /// - `KeyAlreadyRegistered`: 已有有效公钥
+        /// - `AlgorithmNotAllowed` / `InvalidPublicKey` / `KeyTooLong`: 公钥无效
+        /// - `TooManyOverlappingVersions`: 重叠窗口内的旧版本过多
+        #[pallet::call_index(0)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::register_key().saturating_add(T::OnKeyChange::max_weight())
+        )]
+        pub fn register_key(
+            origin: OriginFor<T>,
+            purpose: KeyPurpose,
+            algorithm: KeyAlgorithm,
+            public_key: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(
+                Self::active_version(&who, purpose).is_none(),
+                Error::<T>::KeyAlreadyRegistered
+            );
+            let public_key = Self::validate_key(purpose, algorithm, public_key)?;
+
+            let version = LatestVersion::<T>::get(&who, purpose)
+                .checked_add(1)
+                .ok_or(Error::<T>::VersionOverflow)?;
+            Self::insert_version(&who, purpose, version, algorithm, public_key)?;
+
+            T::OnKeyChange::on_key_registered(&who, purpose, version);
+
+            Self::deposit_event(Event::KeyRegistered { who, purpose, version, algorithm });
+
+            Ok(())
+        }
+
+        /// 轮换公钥
+        ///
+        /// 新版本立即成为当前版本；旧版本在 `RotationOverlap` 个区块内仍可用于解密，
+        /// 已过窗口或已吊销的旧版本被清除。
+        ///
+        /// # 参数
+        /// - `origin`: 交易发起者
+        /// - `purpose`: 密钥用途
+        /// - `algorithm`: 新公钥算法
+        /// - `public_key`: 新公钥
+        ///
+        /// # 错误
+        /// - `NoActiveKey`: 没有可轮换的有效公钥
+        /// - `SameKey`: 新旧公钥相同
+        /// - `TooManyOverlappingVersions`: 最早的保留版本仍在重叠窗口内
+        #[pallet::call_index(1)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::rotate_key().saturating_add(T::OnKeyChange::max_weight())
+        )]
+        pub fn rotate_key(
+            origin: OriginFor<T>,
+            purpose: KeyPurpose,
+            algorithm: KeyAlgorithm,
+            public_key: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let old_version = Self::active_version(&who, purpose).ok_or(Error::<T>::NoActiveKey)?;
+            let public_key = Self::validate_key(purpose, algorithm, public_key)?;
+            let new_version = old_version.checked_add(1).ok_or(Error::<T>::VersionOverflow)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let usable_until = now.saturating_add(T::RotationOverlap::get());
+            Keys::<T>::try_mutate((&who, purpose, old_version), |maybe_record| {
+                let record = maybe_record.as_mut().ok_or(Error::<T>::NoActiveKey)?;
+                ensure!(record.public_key != public_key, Error::<T>::SameKey);
+                record.status = KeyStatus::Rotated { usable_until };
+                Ok::<(), Error<T>>(())
+            })?;
+
+            Self::insert_version(&who, purpose, new_version, algorithm, public_key)?;
+
+            T::OnKeyChange::on_key_rotated(&who, purpose, old_version, new_version);
+
+            Self::deposit_event(Event::KeyRotated {
+                who,
+                purpose,
+                old_version,
+                new_version,
+                usable_until,
+            });
+
+            Ok(())
+        }
+
+        /// 吊销自己的公钥版本
+        ///
+        /// 吊销当前版本后需重新注册；吊销重叠窗口内的旧版本可提前结束窗口。
+        ///
+        /// # 参数
+        /// - `origin`: 交易发起者
+        /// - `purpose`: 密钥用途
+        /// - `version`: 版本号
+        ///
+        /// # 错误
+        /// - `KeyNotFound`: 版本不存在
+        /// - `KeyAlreadyRevoked`: 已吊销
+        #[pallet::call_index(2)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::revoke_key().saturating_add(T::OnKeyChange::max_weight())
+        )]
+        pub fn revoke_key(
+            origin: OriginFor<T>,
+            purpose: KeyPurpose,
+            version: u32,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_revoke(who, purpose, version, false)
+        }
+
+        /// 强制吊销公钥版本（治理）
+        ///
+        /// # 参数
+        /// - `origin`: 治理权限
+        /// - `who`: 密钥所属账户
+        /// - `purpose`: 密钥用途
+        /// - `version`: 版本号
+        #[pallet::call_index(3)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::force_revoke_key().saturating_add(T::OnKeyChange::max_weight())
+        )]
+        pub fn force_revoke_key(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            purpose: KeyPurpose,
+            version: u32,
+        ) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            Self::do_revoke(who, purpose, version, true)
+        }
+    }
+
+    // ========================================================================
+    // 内部函数
+    // ========================================================================
+
+    impl<T: Config> Pallet<T> {
+        /// 当前有效版本号（最新版本且未吊销）
+        pub fn active_version(who: &T::AccountId, purpose: KeyPurpose) -> Option<u32> {
+            let version = LatestVersion::<T>::get(who, purpose);
+            Keys::<T>::get((who, purpose, version))
+                .filter(|record| record.status == KeyStatus::Active)
+                .map(|_| version)
+        }
+
+        /// 校验公钥格式
+        fn validate_key(
+            purpose: KeyPurpose,
+            algorithm: KeyAlgorithm,
+            public_key: Vec<u8>,
+        ) -> Result<BoundedVec<u8, T::MaxKeyLen>, Error<T>> {
+            ensure!(purpose.allows(algorithm), Error::<T>::AlgorithmNotAllowed);
+            ensure!(
+                algorithm.is_valid_len(public_key.len()),
+                Error::<T>::InvalidPublicKey
+            );
+            // 拒绝全零公钥（低阶点）
+            ensure!(
+                public_key.iter().any(|byte| *byte != 0),
+                Error::<T>::InvalidPublicKey
+            );
+            public_key.try_into().map_err(|_| Error::<T>::KeyTooLong)
+        }
+
+        /// 写入新版本并设为最新
+        ///
+        /// 清除已过重叠窗口或已吊销的旧版本；仍在窗口内的旧版本保留，
+        /// 但滑出 `MAX_RETAINED_VERSIONS` 范围的版本必须已不可用。
+        fn insert_version(
+            who: &T::AccountId,
+            purpose: KeyPurpose,
+            version: u32,
+            algorithm: KeyAlgorithm,
+            public_key: BoundedVec<u8, T::MaxKeyLen>,
+        ) -> Result<(), Error<T>> {
+            let now = frame_system::Pallet::<T>::block_number();
+            let oldest_retained = version.saturating_sub(MAX_RETAINED_VERSIONS);
+            for stale in oldest_retained.max(1)..version {
+                let Some(record) = Keys::<T>::get((who, purpose, stale)) else { continue };
+                if record.is_usable(now) {
+                    ensure!(stale != oldest_retained, Error::<T>::TooManyOverlappingVersions);
+                } else {
+                    Keys::<T>::remove((who, purpose, stale));
+                }
+            }
+
+            Keys::<T>::insert(
+                (who, purpose, version),
+                KeyRecord {
+                    algorithm,
+                    public_key,
+                    created_at: frame_system::Pallet::<T>::block_number(),
+                    status: KeyStatus::Active,
+                },
+            );
+            LatestVersion::<T>::insert(who, purpose, version);
+
+            Ok(())
+        }
+
+        /// 吊销指定版本
+        fn do_revoke(
+            who: T::AccountId,
+            purpose: KeyPurpose,
+            version: u32,
+            forced: bool,
+        ) -> DispatchResult {
+            Keys::<T>::try_mutate((&who, purpose, version), |maybe_record| {
+                let record = maybe_record.as_mut().ok_or(Error::<T>::KeyNotFound)?;
+                ensure!(
+                    !matches!(record.status, KeyStatus::Revoked { .. }),
+                    Error::<T>::KeyAlreadyRevoked
+                );
+                record.status = KeyStatus::Revoked {
+                    revoked_at: frame_system::Pallet::<T>::block_number(),
+                };
+                Ok::<(), Error<T>>(())
+            })?;
+
+            T::OnKeyChange::on_key_revoked(&who, purpose, version);
+
+            Self::deposit_event(Event::KeyRevoked { who, purpose, version, forced });
+
+            Ok(())
+        }
+    }
+
+    impl<T: Config> KeyDirectory<T::AccountId> for Pallet<T> {
+        fn current_key(who: &T::AccountId, purpose: KeyPurpose) -> Option<DirectoryKey> {
+            let version = Self::active_version(who, purpose)?;
+            Keys::<T>::get((who, purpose, version)).map(|record| DirectoryKey {
+                version,
+                algorithm: record.algorithm,
+                public_key: record.public_key.into_inner(),
+            })
+        }
+
+        fn is_usable_key(who: &T::AccountId, purpose: KeyPurpose, public_key: &[u8]) -> bool {
+            let latest = LatestVersion::<T>::get(who, purpose);
+            let now = frame_system::Pallet::<T>::block_number();
+            (latest.saturating_sub(MAX_RETAINED_VERSIONS - 1).max(1)..=latest)
+                .rev()
+                .filter_map(|version| Keys::<T>::get((who, purpose, version)))
+                .any(|record| record.public_key[..] == *public_key && record.is_usable(now))
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! # 统一公钥目录 - 测试 Mock
+
+use crate as pallet_key_directory;
+use crate::traits::KeyChangeHandler;
+use crate::types::KeyPurpose;
+use frame_support::{derive_impl, parameter_types, traits::ConstU32};
+use frame_system::EnsureRoot;
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        KeyDirectory: pallet_key_directory,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = ();
+}
+
+parameter_types! {
+    pub const RotationOverlap: u64 = 100;
+    /// 记录回调：(账户, 用途, 旧版本, 新版本)，吊销时新版本为 0
+    pub static KeyChanges: Vec<(u64, KeyPurpose, u32, u32)> = Vec::new();
+}
+
+/// 记录密钥变更回调
+pub struct RecordKeyChanges;
+
+impl KeyChangeHandler<u64> for RecordKeyChanges {
+    fn on_key_rotated(who: &u64, purpose: KeyPurpose, old_version: u32, new_version: u32) {
+        KeyChanges::mutate(|changes| changes.push((*who, purpose, old_version, new_version)));
+    }
+
+    fn on_key_revoked(who: &u64, purpose: KeyPurpose, version: u32) {
+        KeyChanges::mutate(|changes| changes.push((*who, purpose, version, 0)));
+    }
+}
+
+impl pallet_key_directory::Config for Test {
+    type MaxKeyLen = ConstU32<512>;
+    type RotationOverlap = RotationOverlap;
+    type GovernanceOrigin = EnsureRoot<u64>;
+    type OnKeyChange = RecordKeyChanges;
+    type WeightInfo = ();
+}
+
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+
+/// 生成测试用的 32 字节公钥
+pub fn key32(seed: u8) -> Vec<u8> {
+    vec![seed; 32]
+}
+
+/// 构建测试外部性
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        KeyChanges::take();
+    });
+    ext
+}
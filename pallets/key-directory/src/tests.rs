@@ -0,0 +1,303 @@
+//! # 统一公钥目录 - 单元测试
+
+use crate::{mock::*, traits::KeyDirectory as _, types::*, Error, Event};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+
+fn register_x25519(who: u64, seed: u8) {
+    assert_ok!(KeyDirectory::register_key(
+        RuntimeOrigin::signed(who),
+        KeyPurpose::Encryption,
+        KeyAlgorithm::X25519,
+        key32(seed),
+    ));
+}
+
+#[test]
+fn register_key_works() {
+    new_test_ext().execute_with(|| {
+        register_x25519(ALICE, 7);
+
+        let key = KeyDirectory::current_key(&ALICE, KeyPurpose::Encryption).unwrap();
+        assert_eq!(key.version, 1);
+        assert_eq!(key.algorithm, KeyAlgorithm::X25519);
+        assert_eq!(KeyDirectory::encryption_key(&ALICE), Some([7u8; 32]));
+        assert!(!KeyDirectory::has_key(&ALICE, KeyPurpose::Signing));
+        System::assert_last_event(
+            Event::KeyRegistered {
+                who: ALICE,
+                purpose: KeyPurpose::Encryption,
+                version: 1,
+                algorithm: KeyAlgorithm::X25519,
+            }
+            .into(),
+        );
+
+        assert_noop!(
+            KeyDirectory::register_key(
+                RuntimeOrigin::signed(ALICE),
+                KeyPurpose::Encryption,
+                KeyAlgorithm::X25519,
+                key32(8),
+            ),
+            Error::<Test>::KeyAlreadyRegistered
+        );
+    });
+}
+
+#[test]
+fn register_key_validates_format() {
+    new_test_ext().execute_with(|| {
+        let register = |purpose, algorithm, key: Vec<u8>| {
+            KeyDirectory::register_key(RuntimeOrigin::signed(ALICE), purpose, algorithm, key)
+        };
+
+        assert_noop!(
+            register(KeyPurpose::Encryption, KeyAlgorithm::Ed25519, key32(1)),
+            Error::<Test>::AlgorithmNotAllowed
+        );
+        assert_noop!(
+            register(KeyPurpose::TeeEnclave, KeyAlgorithm::X25519, key32(1)),
+            Error::<Test>::AlgorithmNotAllowed
+        );
+        assert_noop!(
+            register(KeyPurpose::Encryption, KeyAlgorithm::X25519, vec![1u8; 31]),
+            Error::<Test>::InvalidPublicKey
+        );
+        assert_noop!(
+            register(KeyPurpose::Encryption, KeyAlgorithm::X25519, key32(0)),
+            Error::<Test>::InvalidPublicKey
+        );
+
+        // 证据模块使用的 RSA-2048 DER 公钥
+        assert_ok!(register(KeyPurpose::Encryption, KeyAlgorithm::Rsa2048, vec![3u8; 294]));
+        assert_eq!(KeyDirectory::encryption_key(&ALICE), None);
+        assert!(KeyDirectory::has_key(&ALICE, KeyPurpose::Encryption));
+    });
+}
+
+#[test]
+fn rotation_keeps_old_key_usable_within_overlap() {
+    new_test_ext().execute_with(|| {
+        register_x25519(ALICE, 1);
+
+        assert_noop!(
+            KeyDirectory::rotate_key(
+                RuntimeOrigin::signed(ALICE),
+                KeyPurpose::Encryption,
+                KeyAlgorithm::X25519,
+                key32(1),
+            ),
+            Error::<Test>::SameKey
+        );
+        assert_ok!(KeyDirectory::rotate_key(
+            RuntimeOrigin::signed(ALICE),
+            KeyPurpose::Encryption,
+            KeyAlgorithm::X25519,
+            key32(2),
+        ));
+
+        System::assert_last_event(
+            Event::KeyRotated {
+                who: ALICE,
+                purpose: KeyPurpose::Encryption,
+                old_version: 1,
+                new_version: 2,
+                usable_until: 101,
+            }
+            .into(),
+        );
+        assert_eq!(KeyChanges::get(), vec![(ALICE, KeyPurpose::Encryption, 1, 2)]);
+        assert_eq!(KeyDirectory::encryption_key(&ALICE), Some([2u8; 32]));
+
+        // 重叠窗口内新旧公钥均可用
+        assert!(KeyDirectory::is_usable_key(&ALICE, KeyPurpose::Encryption, &key32(1)));
+        assert!(KeyDirectory::is_usable_key(&ALICE, KeyPurpose::Encryption, &key32(2)));
+
+        System::set_block_number(102);
+        assert!(!KeyDirectory::is_usable_key(&ALICE, KeyPurpose::Encryption, &key32(1)));
+        assert!(KeyDirectory::is_usable_key(&ALICE, KeyPurpose::Encryption, &key32(2)));
+    });
+}
+
+#[test]
+fn rotation_keeps_older_versions_until_overlap_ends() {
+    new_test_ext().execute_with(|| {
+        register_x25519(ALICE, 1);
+        for seed in 2..=3 {
+            assert_ok!(KeyDirectory::rotate_key(
+                RuntimeOrigin::signed(ALICE),
+                KeyPurpose::Encryption,
+                KeyAlgorithm::X25519,
+                key32(seed),
+            ));
+        }
+
+        // 同一窗口内连续轮换，版本 1 仍在窗口内，不能被清除
+        assert_eq!(KeyDirectory::latest_version(ALICE, KeyPurpose::Encryption), 3);
+        assert!(matches!(
+            KeyDirectory::keys((ALICE, KeyPurpose::Encryption, 1)).unwrap().status,
+            KeyStatus::Rotated { .. }
+        ));
+        assert!(matches!(
+            KeyDirectory::keys((ALICE, KeyPurpose::Encryption, 2)).unwrap().status,
+            KeyStatus::Rotated { .. }
+        ));
+        assert_eq!(
+            KeyDirectory::keys((ALICE, KeyPurpose::Encryption, 3)).unwrap().status,
+            KeyStatus::Active
+        );
+        assert!(KeyDirectory::is_usable_key(&ALICE, KeyPurpose::Encryption, &key32(1)));
+
+        // 窗口结束后的下一次轮换清除所有过期版本
+        System::set_block_number(102);
+        assert_ok!(KeyDirectory::rotate_key(
+            RuntimeOrigin::signed(ALICE),
+            KeyPurpose::Encryption,
+            KeyAlgorithm::X25519,
+            key32(4),
+        ));
+        assert!(KeyDirectory::keys((ALICE, KeyPurpose::Encryption, 1)).is_none());
+        assert!(KeyDirectory::keys((ALICE, KeyPurpose::Encryption, 2)).is_none());
+        assert!(matches!(
+            KeyDirectory::keys((ALICE, KeyPurpose::Encryption, 3)).unwrap().status,
+            KeyStatus::Rotated { .. }
+        ));
+    });
+}
+
+#[test]
+fn rotation_rejected_while_oldest_retained_version_usable() {
+    new_test_ext().execute_with(|| {
+        register_x25519(ALICE, 1);
+        for seed in 2..=MAX_RETAINED_VERSIONS as u8 {
+            assert_ok!(KeyDirectory::rotate_key(
+                RuntimeOrigin::signed(ALICE),
+                KeyPurpose::Encryption,
+                KeyAlgorithm::X25519,
+                key32(seed),
+            ));
+        }
+
+        let next = MAX_RETAINED_VERSIONS as u8 + 1;
+        assert_noop!(
+            KeyDirectory::rotate_key(
+                RuntimeOrigin::signed(ALICE),
+                KeyPurpose::Encryption,
+                KeyAlgorithm::X25519,
+                key32(next),
+            ),
+            Error::<Test>::TooManyOverlappingVersions
+        );
+
+        // 吊销最早的旧版本后即可继续轮换
+        assert_ok!(KeyDirectory::revoke_key(
+            RuntimeOrigin::signed(ALICE),
+            KeyPurpose::Encryption,
+            1
+        ));
+        assert_ok!(KeyDirectory::rotate_key(
+            RuntimeOrigin::signed(ALICE),
+            KeyPurpose::Encryption,
+            KeyAlgorithm::X25519,
+            key32(next),
+        ));
+        assert!(KeyDirectory::keys((ALICE, KeyPurpose::Encryption, 1)).is_none());
+        assert!(KeyDirectory::is_usable_key(&ALICE, KeyPurpose::Encryption, &key32(2)));
+    });
+}
+
+#[test]
+fn revoke_and_reregister() {
+    new_test_ext().execute_with(|| {
+        register_x25519(ALICE, 1);
+
+        assert_noop!(
+            KeyDirectory::revoke_key(RuntimeOrigin::signed(ALICE), KeyPurpose::Encryption, 2),
+            Error::<Test>::KeyNotFound
+        );
+        assert_ok!(KeyDirectory::revoke_key(
+            RuntimeOrigin::signed(ALICE),
+            KeyPurpose::Encryption,
+            1
+        ));
+        assert_noop!(
+            KeyDirectory::revoke_key(RuntimeOrigin::signed(ALICE), KeyPurpose::Encryption, 1),
+            Error::<Test>::KeyAlreadyRevoked
+        );
+
+        assert!(KeyDirectory::current_key(&ALICE, KeyPurpose::Encryption).is_none());
+        assert!(!KeyDirectory::is_usable_key(&ALICE, KeyPurpose::Encryption, &key32(1)));
+        assert_noop!(
+            KeyDirectory::rotate_key(
+                RuntimeOrigin::signed(ALICE),
+                KeyPurpose::Encryption,
+                KeyAlgorithm::X25519,
+                key32(2),
+            ),
+            Error::<Test>::NoActiveKey
+        );
+
+        // 重新注册后版本号继续递增
+        register_x25519(ALICE, 2);
+        assert_eq!(KeyDirectory::current_key(&ALICE, KeyPurpose::Encryption).unwrap().version, 2);
+    });
+}
+
+#[test]
+fn revoking_rotated_version_ends_overlap() {
+    new_test_ext().execute_with(|| {
+        register_x25519(ALICE, 1);
+        assert_ok!(KeyDirectory::rotate_key(
+            RuntimeOrigin::signed(ALICE),
+            KeyPurpose::Encryption,
+            KeyAlgorithm::X25519,
+            key32(2),
+        ));
+        assert_ok!(KeyDirectory::revoke_key(
+            RuntimeOrigin::signed(ALICE),
+            KeyPurpose::Encryption,
+            1
+        ));
+
+        assert!(!KeyDirectory::is_usable_key(&ALICE, KeyPurpose::Encryption, &key32(1)));
+        assert_eq!(KeyDirectory::encryption_key(&ALICE), Some([2u8; 32]));
+        assert_eq!(
+            KeyChanges::get(),
+            vec![(ALICE, KeyPurpose::Encryption, 1, 2), (ALICE, KeyPurpose::Encryption, 1, 0)]
+        );
+    });
+}
+
+#[test]
+fn force_revoke_requires_governance() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KeyDirectory::register_key(
+            RuntimeOrigin::signed(BOB),
+            KeyPurpose::TeeEnclave,
+            KeyAlgorithm::Ed25519,
+            key32(9),
+        ));
+
+        assert_noop!(
+            KeyDirectory::force_revoke_key(
+                RuntimeOrigin::signed(ALICE),
+                BOB,
+                KeyPurpose::TeeEnclave,
+                1
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(KeyDirectory::force_revoke_key(
+            RuntimeOrigin::root(),
+            BOB,
+            KeyPurpose::TeeEnclave,
+            1
+        ));
+        System::assert_last_event(
+            Event::KeyRevoked { who: BOB, purpose: KeyPurpose::TeeEnclave, version: 1, forced: true }
+                .into(),
+        );
+        assert!(!KeyDirectory::has_key(&BOB, KeyPurpose::TeeEnclave));
+    });
+}
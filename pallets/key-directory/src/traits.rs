@@ -0,0 +1,93 @@
+//! # 统一公钥目录 - Trait 定义
+//!
+//! - `KeyDirectory`: 供隐私授权、证据、聊天、TEE 等模块读取公钥
+//! - `KeyChangeHandler`: 密钥注册 / 轮换 / 吊销回调，用于触发授权密钥重新包装（支持元组组合多个消费方）
+
+use crate::types::{DirectoryKey, KeyPurpose};
+
+/// 公钥目录读取接口
+pub trait KeyDirectory<AccountId> {
+    /// 账户某用途的当前有效公钥
+    fn current_key(who: &AccountId, purpose: KeyPurpose) -> Option<DirectoryKey>;
+
+    /// 公钥是否可用（当前版本，或仍处于轮换重叠窗口内的旧版本）
+    fn is_usable_key(who: &AccountId, purpose: KeyPurpose, public_key: &[u8]) -> bool;
+
+    /// 账户某用途是否有当前有效公钥
+    fn has_key(who: &AccountId, purpose: KeyPurpose) -> bool {
+        Self::current_key(who, purpose).is_some()
+    }
+
+    /// 当前 X25519 加密公钥
+    fn encryption_key(who: &AccountId) -> Option<[u8; 32]> {
+        Self::current_key(who, KeyPurpose::Encryption)
+            .filter(|key| key.algorithm == crate::types::KeyAlgorithm::X25519)
+            .and_then(|key| key.as_bytes32())
+    }
+}
+
+/// 空实现（未接入目录的运行时 / 测试）
+impl<AccountId> KeyDirectory<AccountId> for () {
+    fn current_key(_who: &AccountId, _purpose: KeyPurpose) -> Option<DirectoryKey> {
+        None
+    }
+
+    fn is_usable_key(_who: &AccountId, _purpose: KeyPurpose, _public_key: &[u8]) -> bool {
+        false
+    }
+}
+
+/// 密钥变更回调
+pub trait KeyChangeHandler<AccountId> {
+    /// 回调最大权重（计入 `rotate_key` / `revoke_key` 的预估权重）
+    fn max_weight() -> frame_support::weights::Weight {
+        frame_support::weights::Weight::zero()
+    }
+
+    /// 公钥已注册（首次注册或吊销后重新注册）
+    fn on_key_registered(_who: &AccountId, _purpose: KeyPurpose, _version: u32) {}
+
+    /// 密钥已轮换；旧版本在重叠窗口内仍可解密，消费方应在窗口内重新包装
+    fn on_key_rotated(who: &AccountId, purpose: KeyPurpose, old_version: u32, new_version: u32);
+
+    /// 密钥已吊销
+    fn on_key_revoked(who: &AccountId, purpose: KeyPurpose, version: u32);
+}
+
+impl<AccountId> KeyChangeHandler<AccountId> for () {
+    fn on_key_rotated(_who: &AccountId, _purpose: KeyPurpose, _old: u32, _new: u32) {}
+
+    fn on_key_revoked(_who: &AccountId, _purpose: KeyPurpose, _version: u32) {}
+}
+
+/// 组合多个回调（依次通知各消费方，权重相加）
+macro_rules! impl_key_change_handler_for_tuples {
+    ($($handler:ident),+) => {
+        impl<AccountId, $($handler: KeyChangeHandler<AccountId>),+> KeyChangeHandler<AccountId>
+            for ($($handler,)+)
+        {
+            fn max_weight() -> frame_support::weights::Weight {
+                frame_support::weights::Weight::zero()
+                    $(.saturating_add($handler::max_weight()))+
+            }
+
+            fn on_key_registered(who: &AccountId, purpose: KeyPurpose, version: u32) {
+                $($handler::on_key_registered(who, purpose, version);)+
+            }
+
+            fn on_key_rotated(who: &AccountId, purpose: KeyPurpose, old_version: u32, new_version: u32) {
+                $($handler::on_key_rotated(who, purpose, old_version, new_version);)+
+            }
+
+            fn on_key_revoked(who: &AccountId, purpose: KeyPurpose, version: u32) {
+                $($handler::on_key_revoked(who, purpose, version);)+
+            }
+        }
+    };
+}
+
+impl_key_change_handler_for_tuples!(A);
+impl_key_change_handler_for_tuples!(A, B);
+impl_key_change_handler_for_tuples!(A, B, C);
+impl_key_change_handler_for_tuples!(A, B, C, D);
+impl_key_change_handler_for_tuples!(A, B, C, D, E);
@@ -0,0 +1,127 @@
+//! # 统一公钥目录 - 类型定义
+
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::*, BoundedVec};
+use scale_info::TypeInfo;
+
+/// 每种用途最多同时保留的版本数（当前版本 + 重叠窗口内的旧版本）
+///
+/// 轮换时最早的保留版本必须已过重叠窗口（或已吊销），否则拒绝轮换。
+pub const MAX_RETAINED_VERSIONS: u32 = 4;
+
+/// 密钥用途
+///
+/// 同一账户每种用途各有一条独立的版本链。
+#[derive(
+    Clone, Copy, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug,
+)]
+pub enum KeyPurpose {
+    /// 数据加密（包装数据密钥：隐私授权、证据密钥包、群组纪元密钥）
+    Encryption,
+    /// 签名验证
+    Signing,
+    /// TEE Enclave 签名公钥
+    TeeEnclave,
+}
+
+impl KeyPurpose {
+    /// 该用途是否允许使用指定算法
+    pub fn allows(&self, algorithm: KeyAlgorithm) -> bool {
+        use KeyAlgorithm::*;
+        match self {
+            KeyPurpose::Encryption => matches!(algorithm, X25519 | EcdsaP256 | Rsa2048),
+            KeyPurpose::Signing => matches!(algorithm, Ed25519 | Sr25519 | EcdsaP256),
+            KeyPurpose::TeeEnclave => matches!(algorithm, Ed25519),
+        }
+    }
+}
+
+/// 公钥算法
+#[derive(
+    Clone, Copy, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug,
+)]
+pub enum KeyAlgorithm {
+    /// X25519（32 字节）
+    X25519,
+    /// Ed25519（32 字节）
+    Ed25519,
+    /// Sr25519（32 字节）
+    Sr25519,
+    /// ECDSA P-256（SEC1 压缩 33 字节或未压缩 65 字节）
+    EcdsaP256,
+    /// RSA-2048（DER，270 ~ 512 字节）
+    Rsa2048,
+}
+
+impl KeyAlgorithm {
+    /// 公钥长度是否符合算法要求
+    pub fn is_valid_len(&self, len: usize) -> bool {
+        match self {
+            KeyAlgorithm::X25519 | KeyAlgorithm::Ed25519 | KeyAlgorithm::Sr25519 => len == 32,
+            KeyAlgorithm::EcdsaP256 => len == 33 || len == 65,
+            KeyAlgorithm::Rsa2048 => (270..=512).contains(&len),
+        }
+    }
+}
+
+/// 密钥版本状态
+#[derive(
+    Clone, Copy, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug,
+)]
+pub enum KeyStatus<BlockNumber> {
+    /// 当前版本
+    Active,
+    /// 已被新版本取代，重叠窗口内仍可用于解密
+    Rotated {
+        /// 重叠窗口截止区块
+        usable_until: BlockNumber,
+    },
+    /// 已吊销（泄露等），不再可用
+    Revoked {
+        /// 吊销区块
+        revoked_at: BlockNumber,
+    },
+}
+
+/// 密钥版本记录
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug)]
+#[scale_info(skip_type_params(MaxKeyLen))]
+pub struct KeyRecord<BlockNumber, MaxKeyLen: Get<u32>> {
+    /// 算法
+    pub algorithm: KeyAlgorithm,
+    /// 公钥
+    pub public_key: BoundedVec<u8, MaxKeyLen>,
+    /// 发布区块
+    pub created_at: BlockNumber,
+    /// 状态
+    pub status: KeyStatus<BlockNumber>,
+}
+
+impl<BlockNumber: PartialOrd + Copy, MaxKeyLen: Get<u32>> KeyRecord<BlockNumber, MaxKeyLen> {
+    /// 在 `now` 时是否可用（当前版本或处于重叠窗口内的旧版本）
+    pub fn is_usable(&self, now: BlockNumber) -> bool {
+        match self.status {
+            KeyStatus::Active => true,
+            KeyStatus::Rotated { usable_until } => now <= usable_until,
+            KeyStatus::Revoked { .. } => false,
+        }
+    }
+}
+
+/// 供其他模块读取的公钥视图
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DirectoryKey {
+    /// 版本号（从 1 开始）
+    pub version: u32,
+    /// 算法
+    pub algorithm: KeyAlgorithm,
+    /// 公钥
+    pub public_key: sp_std::vec::Vec<u8>,
+}
+
+impl DirectoryKey {
+    /// 以 32 字节数组读取 X25519 / Ed25519 / Sr25519 公钥
+    pub fn as_bytes32(&self) -> Option<[u8; 32]> {
+        self.public_key.as_slice().try_into().ok()
+    }
+}
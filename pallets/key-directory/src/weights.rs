@@ -0,0 +1,63 @@
+//! # 统一公钥目录 - 权重定义
+//!
+//! 在正式部署前应使用 benchmarking 进行精确测量。
+
+use crate::types::MAX_RETAINED_VERSIONS;
+use frame_support::{traits::Get, weights::Weight};
+
+/// 权重信息 trait
+pub trait WeightInfo {
+    fn register_key() -> Weight;
+    fn rotate_key() -> Weight;
+    fn revoke_key() -> Weight;
+    fn force_revoke_key() -> Weight;
+}
+
+/// 默认权重实现
+pub struct SubstrateWeight<T>(core::marker::PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    /// - 2 次读取（最新版本号、当前版本）+ 扫描保留的旧版本
+    /// - 2 次写入 + 清理不可用的旧版本
+    fn register_key() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2 + MAX_RETAINED_VERSIONS as u64))
+            .saturating_add(T::DbWeight::get().writes(2 + MAX_RETAINED_VERSIONS as u64))
+    }
+
+    /// - 2 次读取 + 扫描保留的旧版本
+    /// - 3 次写入（旧版本、新版本、版本号）+ 清理已过窗口的旧版本
+    /// - 不含 `KeyChangeHandler` 回调开销（由消费方权重覆盖）
+    fn rotate_key() -> Weight {
+        Weight::from_parts(45_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2 + MAX_RETAINED_VERSIONS as u64))
+            .saturating_add(T::DbWeight::get().writes(3 + MAX_RETAINED_VERSIONS as u64))
+    }
+
+    fn revoke_key() -> Weight {
+        Weight::from_parts(25_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    fn force_revoke_key() -> Weight {
+        Weight::from_parts(25_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+}
+
+impl WeightInfo for () {
+    fn register_key() -> Weight {
+        Weight::zero()
+    }
+    fn rotate_key() -> Weight {
+        Weight::zero()
+    }
+    fn revoke_key() -> Weight {
+        Weight::zero()
+    }
+    fn force_revoke_key() -> Weight {
+        Weight::zero()
+    }
+}
@@ -40,6 +40,18 @@ pub trait ProfileProvider<AccountId, T: ProfileConfig> {
     fn is_verified(account: &AccountId) -> bool;
 }
 
+/// 认证徽章提供者 Trait
+pub trait VerificationBadgeProvider<AccountId> {
+    /// 获取用户当前有效的认证徽章
+    fn badge_of(account: &AccountId) -> VerificationBadge;
+}
+
+impl<AccountId> VerificationBadgeProvider<AccountId> for () {
+    fn badge_of(_account: &AccountId) -> VerificationBadge {
+        VerificationBadge::None
+    }
+}
+
 /// 匹配算法 Trait
 pub trait MatchingAlgorithm<AccountId> {
     /// 计算两个用户的匹配评分
@@ -770,6 +770,8 @@ pub struct CompatibilityReport<BlockNumber> {
     pub overall_score: u8,
     /// 评分详情
     pub score_detail: CompatibilityScoreDetail,
+    /// 分项依据（可解释的合婚因子）
+    pub factors: CompatibilityFactors,
     /// 匹配建议
     pub recommendation: MatchRecommendation,
     /// 报告 CID（IPFS 存储详细内容）
@@ -785,6 +787,10 @@ pub struct CompatibilityReport<BlockNumber> {
 pub struct CompatibilityScoreDetail {
     /// 日柱合婚评分 (0-100)
     pub day_pillar_score: u8,
+    /// 年支生肖评分 (0-100)
+    pub year_branch_score: u8,
+    /// 夫妻宫评分 (0-100)
+    pub spouse_palace_score: u8,
     /// 五行互补评分 (0-100)
     pub wuxing_score: u8,
     /// 性格匹配评分 (0-100)
@@ -797,16 +803,124 @@ pub struct CompatibilityScoreDetail {
 
 impl CompatibilityScoreDetail {
     /// 计算加权综合评分
+    ///
+    /// 权重：日柱 20%、年支 10%、夫妻宫 10%、五行 20%、性格 15%、神煞 15%、大运 10%
     pub fn calculate_overall(&self) -> u8 {
-        let weighted = self.day_pillar_score as u32 * 30
-            + self.wuxing_score as u32 * 25
-            + self.personality_score as u32 * 20
+        let weighted = self.day_pillar_score as u32 * 20
+            + self.year_branch_score as u32 * 10
+            + self.spouse_palace_score as u32 * 10
+            + self.wuxing_score as u32 * 20
+            + self.personality_score as u32 * 15
             + self.shensha_score as u32 * 15
             + self.dayun_score as u32 * 10;
         (weighted / 100) as u8
     }
 }
 
+/// 合婚方式
+#[derive(
+    Clone, Copy, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug, Default,
+)]
+pub enum CompatibilityMethod {
+    /// 八字合婚
+    #[default]
+    Bazi = 0,
+    /// 紫微合盘
+    Ziwei = 1,
+    /// 八字 + 紫微综合
+    Both = 2,
+}
+
+impl CompatibilityMethod {
+    /// 是否需要八字命盘
+    pub fn uses_bazi(&self) -> bool {
+        matches!(self, Self::Bazi | Self::Both)
+    }
+
+    /// 是否需要紫微命盘
+    pub fn uses_ziwei(&self) -> bool {
+        matches!(self, Self::Ziwei | Self::Both)
+    }
+}
+
+/// 紫微合盘评分详情
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug, Default)]
+pub struct ZiweiScoreDetail {
+    /// 命宫主星配对评分 (0-100)
+    pub ming_gong_score: u8,
+    /// 夫妻宫呼应评分 (0-100)
+    pub spouse_palace_score: u8,
+    /// 四化飞星评分 (0-100)
+    pub sihua_score: u8,
+    /// 禄权科飞入对方命宫/夫妻宫/福德宫的次数
+    pub sihua_auspicious_hits: u8,
+    /// 化忌飞入对方命宫/夫妻宫/福德宫的次数
+    pub sihua_ji_hits: u8,
+    /// 紫微合盘综合评分 (0-100)
+    pub overall: u8,
+}
+
+/// 地支关系（用于年支生肖、夫妻宫分析）
+#[derive(Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug, Default)]
+pub enum ZhiRelation {
+    /// 无特殊关系
+    #[default]
+    Neutral = 0,
+    /// 相同地支
+    Same = 1,
+    /// 六合
+    LiuHe = 2,
+    /// 三合
+    SanHe = 3,
+    /// 六害
+    LiuHai = 4,
+    /// 相刑（含自刑）
+    XiangXing = 5,
+    /// 六冲
+    LiuChong = 6,
+}
+
+/// 合婚分项依据
+///
+/// 记录每个评分维度的中间结果，便于解释综合评分的来源
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug, Default)]
+pub struct CompatibilityFactors {
+    /// 日柱天干评分
+    pub day_stem_score: u8,
+    /// 日柱地支评分
+    pub day_branch_score: u8,
+    /// 年支（生肖）关系
+    pub year_branch_relation: ZhiRelation,
+    /// 夫妻宫（日支）关系
+    pub spouse_palace_relation: ZhiRelation,
+    /// 甲方夫妻宫被本命月支/时支所冲
+    pub spouse_palace_a_clashed: bool,
+    /// 乙方夫妻宫被本命月支/时支所冲
+    pub spouse_palace_b_clashed: bool,
+    /// 用神喜神配合评分
+    pub yongshen_score: u8,
+    /// 忌神冲突评分
+    pub jishen_score: u8,
+    /// 五行平衡评分
+    pub balance_score: u8,
+    /// 五行评分是否基于解盘数据（否则由四柱五行统计推算）
+    pub wuxing_from_interpretation: bool,
+    /// 吉神配合评分
+    pub shensha_jishen_score: u8,
+    /// 凶神冲克评分
+    pub shensha_xiongshen_score: u8,
+    /// 甲方命带孤辰或寡宿
+    pub gu_gua_a: bool,
+    /// 乙方命带孤辰或寡宿
+    pub gu_gua_b: bool,
+    /// 大运方向一致性评分
+    pub dayun_direction_score: u8,
+    /// 大运五行配合评分
+    pub dayun_wuxing_score: u8,
+    /// 大运评分是否基于双方起运信息（否则为中性分）
+    pub dayun_available: bool,
+}
+
 // ============================================================================
 // 互动记录
 // ============================================================================
@@ -829,7 +943,7 @@ pub struct Interaction<AccountId, BlockNumber> {
 // ============================================================================
 
 /// 推荐结果
-#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug)]
+#[derive(Clone, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug)]
 pub struct RecommendationResult<AccountId> {
     /// 推荐用户
     pub user: AccountId,
@@ -838,3 +952,71 @@ pub struct RecommendationResult<AccountId> {
     /// 推荐理由（索引）
     pub reason_index: u8,
 }
+
+// ============================================================================
+// 资料认证
+// ============================================================================
+
+/// 认证类型
+#[derive(
+    Clone, Copy, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug,
+)]
+pub enum VerificationKind {
+    /// 活体认证（真人自拍视频）
+    Liveness = 0,
+    /// 身份证件认证
+    Identity = 1,
+}
+
+/// 认证徽章等级
+#[derive(
+    Clone, Copy, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, PartialOrd, Ord, Debug, Default,
+)]
+pub enum VerificationBadge {
+    /// 未认证
+    #[default]
+    None = 0,
+    /// 已通过活体认证
+    Liveness = 1,
+    /// 已通过身份证件认证
+    Identity = 2,
+    /// 活体 + 身份证件双重认证
+    Full = 3,
+}
+
+impl VerificationBadge {
+    /// 根据当前有效的认证类型计算徽章等级
+    pub fn from_kinds(liveness: bool, identity: bool) -> Self {
+        match (liveness, identity) {
+            (true, true) => Self::Full,
+            (false, true) => Self::Identity,
+            (true, false) => Self::Liveness,
+            (false, false) => Self::None,
+        }
+    }
+
+    /// 是否已认证（任一认证有效）
+    pub fn is_verified(&self) -> bool {
+        *self != Self::None
+    }
+
+    /// 推荐排序加分
+    pub fn ranking_bonus(&self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Liveness => 3,
+            Self::Identity => 6,
+            Self::Full => 10,
+        }
+    }
+
+    /// 每日互动配额加成（百分比）
+    pub fn quota_bonus_percent(&self) -> u32 {
+        match self {
+            Self::None => 0,
+            Self::Liveness => 20,
+            Self::Identity => 50,
+            Self::Full => 100,
+        }
+    }
+}
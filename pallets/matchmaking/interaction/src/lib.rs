@@ -33,7 +33,7 @@ use frame_system::pallet_prelude::*;
 use sp_runtime::traits::{Saturating, Zero};
 use sp_io::hashing::blake2_256;
 
-use pallet_matchmaking_common::InteractionType;
+use pallet_matchmaking_common::{InteractionType, VerificationBadgeProvider};
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -96,6 +96,9 @@ pub mod pallet {
         #[pallet::constant]
         type MemberDailyViews: Get<u32>;
 
+        /// 认证徽章提供者（已认证用户获得额外点赞、查看、发起聊天配额）
+        type BadgeProvider: VerificationBadgeProvider<Self::AccountId>;
+
         /// 余额类型
         type Balance: codec::FullCodec
             + codec::MaxEncodedLen
@@ -1207,7 +1210,7 @@ impl<T: Config> Pallet<T> {
     /// 
     /// 返回 Ok(()) 如果配额足够，否则返回错误
     pub fn check_and_consume_like_quota(user: &T::AccountId) -> DispatchResult {
-        let max_likes = T::FreeDailyLikes::get();
+        let max_likes = Self::with_badge_bonus(user, T::FreeDailyLikes::get());
         
         // 如果配额为 0，表示无限制
         if max_likes == 0 {
@@ -1278,7 +1281,16 @@ impl<T: Config> Pallet<T> {
         // TODO: 集成会员系统检查
         // 目前简化为使用免费用户配额
         // 未来可以检查 MembershipExpiry 来区分会员等级
-        T::FreeDailyViews::get()
+        Self::with_badge_bonus(user, T::FreeDailyViews::get())
+    }
+
+    /// 按认证徽章等级提高每日配额（0 表示无限，保持不变）
+    fn with_badge_bonus(user: &T::AccountId, base: u32) -> u32 {
+        if base == 0 {
+            return 0;
+        }
+        let bonus_percent = T::BadgeProvider::badge_of(user).quota_bonus_percent();
+        base.saturating_add(base.saturating_mul(bonus_percent) / 100)
     }
 
     // ========================================================================
@@ -1436,7 +1448,7 @@ impl<T: Config> Pallet<T> {
         // TODO: 集成会员系统检查
         // 目前简化为使用免费用户配额
         // 未来可以检查 MembershipExpiry 来区分会员等级
-        T::FreeDailyChatInitiations::get()
+        Self::with_badge_bonus(user, T::FreeDailyChatInitiations::get())
     }
 
     /// 检查并消耗聊天发起配额
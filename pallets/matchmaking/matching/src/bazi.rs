@@ -1,14 +1,17 @@
 //! # 八字合婚算法
 //!
-//! 基于日柱天干地支的合婚分析。
+//! 基于四柱的多因子合婚分析：日柱、年支生肖、夫妻宫、五行互补、神煞（含孤辰寡宿）、大运同步。
 //!
 //! ## 天干地支索引
 //!
 //! - 天干: 甲(0) 乙(1) 丙(2) 丁(3) 戊(4) 己(5) 庚(6) 辛(7) 壬(8) 癸(9)
 //! - 地支: 子(0) 丑(1) 寅(2) 卯(3) 辰(4) 巳(5) 午(6) 未(7) 申(8) 酉(9) 戌(10) 亥(11)
 
-use pallet_bazi_chart::types::{TianGan, DiZhi, WuXing, GanZhi};
+use pallet_bazi_chart::types::{TianGan, DiZhi, WuXing, GanZhi, SiZhuIndex};
 use pallet_bazi_chart::interpretation::CoreInterpretation;
+use pallet_bazi_chart::calculations::{calculate_dayun_list, calculate_shensha_list_temp, ShenSha};
+use pallet_matchmaking_common::ZhiRelation;
+use sp_std::vec::Vec;
 
 /// 日柱合婚结果
 #[derive(Clone, Debug, Default)]
@@ -474,6 +477,314 @@ pub fn calculate_dayun_sync(
     }
 }
 
+/// 根据起运信息由月柱推排大运（最多取 4 步）
+///
+/// 每步大运以天干五行作为该步五行，`next_wuxing` 为下一步的天干五行
+pub fn dayun_from_sizhu(sizhu: &SiZhuIndex, is_shun: bool, qiyun_age: u8) -> Vec<DaYunInfo> {
+    let steps = calculate_dayun_list(sizhu.month_ganzhi(), 0, qiyun_age, is_shun, 4);
+    steps
+        .iter()
+        .enumerate()
+        .map(|(i, (ganzhi, start_age, _))| DaYunInfo {
+            current_wuxing: ganzhi.gan.to_wuxing(),
+            next_wuxing: steps
+                .get(i + 1)
+                .map(|(next, _, _)| next.gan.to_wuxing())
+                .unwrap_or(ganzhi.gan.to_wuxing()),
+            start_age: *start_age,
+        })
+        .collect()
+}
+
+// ============================================================================
+// 年支生肖与夫妻宫分析
+// ============================================================================
+
+/// 地支六害对照表
+/// 子未害、丑午害、寅巳害、卯辰害、申亥害、酉戌害
+const DIZHI_LIUHAI_PAIRS: [(u8, u8); 6] = [
+    (0, 7),   // 子未害
+    (1, 6),   // 丑午害
+    (2, 5),   // 寅巳害
+    (3, 4),   // 卯辰害
+    (8, 11),  // 申亥害
+    (9, 10),  // 酉戌害
+];
+
+/// 地支相刑对照表（不含自刑）
+/// 子卯相刑、寅巳申无恩之刑、丑戌未恃势之刑
+const DIZHI_XING_PAIRS: [(u8, u8); 7] = [
+    (0, 3),   // 子卯
+    (2, 5),   // 寅巳
+    (5, 8),   // 巳申
+    (2, 8),   // 寅申
+    (1, 10),  // 丑戌
+    (7, 10),  // 未戌
+    (1, 7),   // 丑未
+];
+
+/// 自刑地支：辰、午、酉、亥
+const DIZHI_ZIXING: [u8; 4] = [4, 6, 9, 11];
+
+fn is_pair(pairs: &[(u8, u8)], a: u8, b: u8) -> bool {
+    pairs.iter().any(|&(x, y)| (a == x && b == y) || (a == y && b == x))
+}
+
+/// 判断两个地支的关系
+///
+/// 优先级：六冲 > 六害 > 相刑 > 六合 > 三合 > 相同 > 无关系
+pub fn zhi_relation(branch1: DiZhi, branch2: DiZhi) -> ZhiRelation {
+    let (a, b) = (branch1.0, branch2.0);
+    if is_dizhi_liuchong(branch1, branch2) {
+        ZhiRelation::LiuChong
+    } else if is_pair(&DIZHI_LIUHAI_PAIRS, a, b) {
+        ZhiRelation::LiuHai
+    } else if is_pair(&DIZHI_XING_PAIRS, a, b) || (a == b && DIZHI_ZIXING.contains(&a)) {
+        ZhiRelation::XiangXing
+    } else if is_dizhi_liuhe(branch1, branch2) {
+        ZhiRelation::LiuHe
+    } else if a != b && a % 4 == b % 4 {
+        // 申子辰、亥卯未、寅午戌、巳酉丑：同组地支对 4 同余
+        ZhiRelation::SanHe
+    } else if a == b {
+        ZhiRelation::Same
+    } else {
+        ZhiRelation::Neutral
+    }
+}
+
+/// 地支关系评分
+pub fn zhi_relation_score(relation: ZhiRelation) -> u8 {
+    match relation {
+        ZhiRelation::LiuHe => 100,
+        ZhiRelation::SanHe => 90,
+        ZhiRelation::Same => 70,
+        ZhiRelation::Neutral => 60,
+        ZhiRelation::XiangXing => 40,
+        ZhiRelation::LiuHai => 35,
+        ZhiRelation::LiuChong => 20,
+    }
+}
+
+/// 年支（生肖）合婚结果
+#[derive(Clone, Debug, Default)]
+pub struct YearBranchResult {
+    pub relation: ZhiRelation,
+    pub overall: u8,
+}
+
+/// 计算年支生肖合婚评分
+pub fn calculate_year_branch_compatibility(year_zhi_1: DiZhi, year_zhi_2: DiZhi) -> YearBranchResult {
+    let relation = zhi_relation(year_zhi_1, year_zhi_2);
+    YearBranchResult {
+        relation,
+        overall: zhi_relation_score(relation),
+    }
+}
+
+/// 夫妻宫合婚结果
+#[derive(Clone, Debug, Default)]
+pub struct SpousePalaceResult {
+    /// 双方日支关系
+    pub relation: ZhiRelation,
+    /// 甲方夫妻宫被本命月支/时支所冲
+    pub palace_1_clashed: bool,
+    /// 乙方夫妻宫被本命月支/时支所冲
+    pub palace_2_clashed: bool,
+    pub overall: u8,
+}
+
+/// 计算夫妻宫（日支）合婚评分
+///
+/// 双方日支关系为基础分，任一方夫妻宫被本命月支或时支所冲扣 15 分
+pub fn calculate_spouse_palace_compatibility(
+    sizhu_1: &SiZhuIndex,
+    sizhu_2: &SiZhuIndex,
+) -> SpousePalaceResult {
+    let palace_clashed = |sizhu: &SiZhuIndex| {
+        let day = DiZhi(sizhu.day_zhi);
+        is_dizhi_liuchong(day, DiZhi(sizhu.month_zhi)) || is_dizhi_liuchong(day, DiZhi(sizhu.hour_zhi))
+    };
+
+    let relation = zhi_relation(DiZhi(sizhu_1.day_zhi), DiZhi(sizhu_2.day_zhi));
+    let palace_1_clashed = palace_clashed(sizhu_1);
+    let palace_2_clashed = palace_clashed(sizhu_2);
+
+    let mut overall = zhi_relation_score(relation);
+    for clashed in [palace_1_clashed, palace_2_clashed] {
+        if clashed {
+            overall = overall.saturating_sub(15);
+        }
+    }
+
+    SpousePalaceResult {
+        relation,
+        palace_1_clashed,
+        palace_2_clashed,
+        overall,
+    }
+}
+
+// ============================================================================
+// 四柱五行统计（无解盘数据时的五行互补）
+// ============================================================================
+
+/// 五行在统计数组中的位置（金木水火土）
+fn wuxing_slot(wuxing: WuXing) -> usize {
+    match wuxing {
+        WuXing::Jin => 0,
+        WuXing::Mu => 1,
+        WuXing::Shui => 2,
+        WuXing::Huo => 3,
+        WuXing::Tu => 4,
+    }
+}
+
+/// 统计四柱八字的五行数量（金木水火土）
+pub fn count_wuxing(sizhu: &SiZhuIndex) -> [u8; 5] {
+    let mut counts = [0u8; 5];
+    for gan in [sizhu.year_gan, sizhu.month_gan, sizhu.day_gan, sizhu.hour_gan] {
+        counts[wuxing_slot(TianGan(gan).to_wuxing())] += 1;
+    }
+    for zhi in [sizhu.year_zhi, sizhu.month_zhi, sizhu.day_zhi, sizhu.hour_zhi] {
+        counts[wuxing_slot(DiZhi(zhi).to_wuxing())] += 1;
+    }
+    counts
+}
+
+/// 由四柱五行统计计算五行互补评分
+///
+/// - 用神分：对方最旺五行补足己方最弱五行
+/// - 忌神分：双方最旺五行相同则过旺扣分
+/// - 平衡分：双方合计后五行分布越均衡越高
+pub fn calculate_wuxing_compatibility_from_sizhu(
+    sizhu_1: &SiZhuIndex,
+    sizhu_2: &SiZhuIndex,
+) -> WuxingCompatibilityResult {
+    let counts_1 = count_wuxing(sizhu_1);
+    let counts_2 = count_wuxing(sizhu_2);
+
+    let weakest = |c: &[u8; 5]| (0..5).min_by_key(|&i| c[i]).unwrap_or(0);
+    let strongest = |c: &[u8; 5]| (0..5).max_by_key(|&i| c[i]).unwrap_or(0);
+
+    let mut yongshen_score = 50u8;
+    if counts_2[weakest(&counts_1)] >= 2 {
+        yongshen_score = yongshen_score.saturating_add(25);
+    }
+    if counts_1[weakest(&counts_2)] >= 2 {
+        yongshen_score = yongshen_score.saturating_add(25);
+    }
+
+    let mut jishen_score = 80u8;
+    if strongest(&counts_1) == strongest(&counts_2) {
+        jishen_score = jishen_score.saturating_sub(30);
+    }
+
+    // 合计后每种五行至少 2 个视为均衡
+    let balanced = (0..5).filter(|&i| counts_1[i] + counts_2[i] >= 2).count() as u8;
+    let balance_score = 60u8.saturating_add(balanced.saturating_mul(8)).min(100);
+
+    let overall = ((yongshen_score as u32 * 50
+        + jishen_score as u32 * 30
+        + balance_score as u32 * 20)
+        / 100) as u8;
+
+    WuxingCompatibilityResult {
+        yongshen_score,
+        jishen_score,
+        balance_score,
+        overall,
+    }
+}
+
+// ============================================================================
+// 四柱神煞合婚（含孤辰寡宿）
+// ============================================================================
+
+/// 将八字命盘神煞映射为合婚神煞索引（见 `JISHEN_LIST` / `XIONGSHEN_LIST`）
+pub fn shensha_index(shensha: ShenSha) -> Option<u8> {
+    match shensha {
+        ShenSha::TianYiGuiRen => Some(0),
+        ShenSha::WenChangGuiRen => Some(1),
+        ShenSha::YiMa => Some(2),
+        ShenSha::JiangXing => Some(3),
+        ShenSha::HuaGai => Some(4),
+        ShenSha::TianDeGuiRen => Some(5),
+        ShenSha::YueDeGuiRen => Some(6),
+        ShenSha::YangRen => Some(9),
+        ShenSha::JieSha => Some(10),
+        ShenSha::WangShen => Some(11),
+        ShenSha::TaoHua => Some(12),
+        ShenSha::GuChen => Some(14),
+        ShenSha::GuaSu => Some(15),
+        _ => None,
+    }
+}
+
+/// 四柱神煞合婚结果
+#[derive(Clone, Debug, Default)]
+pub struct ShenShaMatchResult {
+    /// 吉神配合评分
+    pub jishen_match_score: u8,
+    /// 凶神冲克评分
+    pub xiongshen_conflict_score: u8,
+    /// 甲方命带孤辰或寡宿
+    pub gu_gua_1: bool,
+    /// 乙方命带孤辰或寡宿
+    pub gu_gua_2: bool,
+    /// 综合评分
+    pub overall: u8,
+}
+
+/// 计算四柱神煞合婚评分
+///
+/// 在 `calculate_shensha_conflict` 基础上，孤辰寡宿每方扣 10 分，双方同带再扣 10 分
+pub fn calculate_sizhu_shensha_compatibility(
+    sizhu_1: &SiZhuIndex,
+    sizhu_2: &SiZhuIndex,
+) -> ShenShaMatchResult {
+    let shensha_of = |sizhu: &SiZhuIndex| -> Vec<ShenSha> {
+        calculate_shensha_list_temp(
+            &sizhu.year_ganzhi(),
+            &sizhu.month_ganzhi(),
+            &sizhu.day_ganzhi(),
+            &sizhu.hour_ganzhi(),
+        )
+        .into_iter()
+        .map(|entry| entry.shensha)
+        .collect()
+    };
+
+    let list_1 = shensha_of(sizhu_1);
+    let list_2 = shensha_of(sizhu_2);
+    let indexes = |list: &[ShenSha]| -> Vec<u8> {
+        let mut out: Vec<u8> = list.iter().filter_map(|s| shensha_index(*s)).collect();
+        out.sort_unstable();
+        out.dedup();
+        out
+    };
+    let conflict = calculate_shensha_conflict(&indexes(&list_1), &indexes(&list_2));
+
+    let has_gu_gua = |list: &[ShenSha]| list.iter().any(|s| matches!(s, ShenSha::GuChen | ShenSha::GuaSu));
+    let gu_gua_1 = has_gu_gua(&list_1);
+    let gu_gua_2 = has_gu_gua(&list_2);
+
+    let mut overall = conflict.overall;
+    for flagged in [gu_gua_1, gu_gua_2, gu_gua_1 && gu_gua_2] {
+        if flagged {
+            overall = overall.saturating_sub(10);
+        }
+    }
+
+    ShenShaMatchResult {
+        jishen_match_score: conflict.jishen_match_score,
+        xiongshen_conflict_score: conflict.xiongshen_conflict_score,
+        gu_gua_1,
+        gu_gua_2,
+        overall,
+    }
+}
+
 // ============================================================================
 // 综合八字合婚算法
 // ============================================================================
@@ -560,4 +871,54 @@ mod tests {
         assert_eq!(calculate_branch_score(ZI, CHOU), 100);
         assert_eq!(calculate_branch_score(ZI, WU), 20);
     }
+
+    #[test]
+    fn test_zhi_relation() {
+        assert_eq!(zhi_relation(ZI, WU), ZhiRelation::LiuChong);
+        assert_eq!(zhi_relation(ZI, CHOU), ZhiRelation::LiuHe);
+        assert_eq!(zhi_relation(ZI, DiZhi(4)), ZhiRelation::SanHe);
+        assert_eq!(zhi_relation(ZI, DiZhi(7)), ZhiRelation::LiuHai);
+        assert_eq!(zhi_relation(ZI, DiZhi(3)), ZhiRelation::XiangXing);
+        assert_eq!(zhi_relation(DiZhi(6), DiZhi(6)), ZhiRelation::XiangXing);
+        assert_eq!(zhi_relation(ZI, ZI), ZhiRelation::Same);
+        assert_eq!(zhi_relation(ZI, YIN), ZhiRelation::Neutral);
+    }
+
+    fn sizhu(zhi: [u8; 4]) -> SiZhuIndex {
+        SiZhuIndex {
+            year_gan: 0,
+            year_zhi: zhi[0],
+            month_gan: 2,
+            month_zhi: zhi[1],
+            day_gan: 4,
+            day_zhi: zhi[2],
+            hour_gan: 6,
+            hour_zhi: zhi[3],
+        }
+    }
+
+    #[test]
+    fn test_spouse_palace_clash_penalty() {
+        // 日支子、丑六合，双方宫位安稳
+        let a = sizhu([0, 2, 0, 2]);
+        let b = sizhu([0, 2, 1, 2]);
+        let result = calculate_spouse_palace_compatibility(&a, &b);
+        assert_eq!(result.relation, ZhiRelation::LiuHe);
+        assert_eq!(result.overall, 100);
+
+        // 甲方月支午冲日支子
+        let a = sizhu([0, 6, 0, 2]);
+        let result = calculate_spouse_palace_compatibility(&a, &b);
+        assert!(result.palace_1_clashed);
+        assert!(!result.palace_2_clashed);
+        assert_eq!(result.overall, 85);
+    }
+
+    #[test]
+    fn test_count_wuxing() {
+        // 甲丙戊庚 + 子寅辰午：木2 火2 土2 金1 水1
+        let counts = count_wuxing(&sizhu([0, 2, 4, 6]));
+        assert_eq!(counts, [1, 2, 1, 2, 2]);
+        assert_eq!(counts.iter().sum::<u8>(), 8);
+    }
 }
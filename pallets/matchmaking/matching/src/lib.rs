@@ -4,19 +4,24 @@
 //!
 //! ## 功能概述
 //!
-//! - **八字合婚**：日柱天干地支分析、五行互补分析
+//! - **八字合婚**：日柱、年支生肖、夫妻宫、五行互补、神煞（含孤辰寡宿）、大运同步
 //! - **性格匹配**：互补性格、冲突性格、共同优点
-//! - **合婚请求管理**：创建、授权、生成报告
+//! - **紫微合盘**：命宫主星配对、夫妻宫呼应、四化飞星
+//! - **合婚请求管理**：创建（八字 / 紫微 / 综合）、授权、生成报告
 //!
 //! ## 算法权重
 //!
 //! | 维度 | 权重 |
 //! |------|------|
-//! | 日柱合婚 | 30% |
-//! | 五行互补 | 25% |
-//! | 性格匹配 | 20% |
+//! | 日柱合婚 | 20% |
+//! | 年支生肖 | 10% |
+//! | 夫妻宫 | 10% |
+//! | 五行互补 | 20% |
+//! | 性格匹配 | 15% |
 //! | 神煞分析 | 15% |
 //! | 大运配合 | 10% |
+//!
+//! 各维度的中间结果记录在报告的 `factors` 中，便于解释综合评分。
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -27,6 +32,10 @@ pub use weights::WeightInfo;
 
 pub mod bazi;
 pub mod personality;
+pub mod ziwei;
+
+#[cfg(test)]
+mod mock;
 
 #[cfg(test)]
 mod tests;
@@ -36,10 +45,16 @@ use frame_system::pallet_prelude::*;
 use sp_runtime::traits::Saturating;
 
 use pallet_matchmaking_common::{
-    MatchStatus, MatchRecommendation, CompatibilityScoreDetail,
+    MatchStatus, MatchRecommendation, CompatibilityScoreDetail, CompatibilityFactors,
+    CompatibilityMethod, ZiweiScoreDetail,
+};
+use bazi::{
+    calculate_day_pillar_compatibility, calculate_dayun_sync, calculate_sizhu_shensha_compatibility,
+    calculate_spouse_palace_compatibility, calculate_wuxing_compatibility,
+    calculate_wuxing_compatibility_from_sizhu, calculate_year_branch_compatibility, dayun_from_sizhu,
 };
-use bazi::{calculate_day_pillar_compatibility, calculate_wuxing_compatibility, WuxingCompatibilityResult};
 use personality::{calculate_personality_compatibility, calculate_default_personality_score};
+use ziwei::calculate_ziwei_compatibility;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -57,6 +72,9 @@ pub mod pallet {
         /// 八字数据提供者
         type BaziProvider: BaziDataProvider<Self::AccountId>;
 
+        /// 紫微命盘数据提供者
+        type ZiweiProvider: ZiweiDataProvider<Self::AccountId>;
+
         /// 每个用户最大请求数
         #[pallet::constant]
         type MaxRequestsPerUser: Get<u32>;
@@ -80,8 +98,12 @@ pub mod pallet {
         pub id: u64,
         pub party_a: T::AccountId,
         pub party_b: T::AccountId,
-        pub party_a_bazi_id: u64,
-        pub party_b_bazi_id: u64,
+        /// 合婚方式
+        pub method: CompatibilityMethod,
+        pub party_a_bazi_id: Option<u64>,
+        pub party_b_bazi_id: Option<u64>,
+        pub party_a_ziwei_id: Option<u64>,
+        pub party_b_ziwei_id: Option<u64>,
         pub status: MatchStatus,
         pub created_at: BlockNumberFor<T>,
         pub authorized_at: Option<BlockNumberFor<T>>,
@@ -93,8 +115,16 @@ pub mod pallet {
     pub struct CompatibilityReport<T: Config> {
         pub id: u64,
         pub request_id: u64,
+        /// 综合评分（综合方式下为八字与紫微各占 50%）
         pub overall_score: u8,
+        /// 合婚方式
+        pub method: CompatibilityMethod,
+        /// 八字评分详情（未使用八字时为默认值）
         pub score_detail: CompatibilityScoreDetail,
+        /// 分项依据（可解释的合婚因子）
+        pub factors: CompatibilityFactors,
+        /// 紫微合盘详情（未使用紫微时为 None）
+        pub ziwei_detail: Option<ZiweiScoreDetail>,
         pub recommendation: MatchRecommendation,
         pub report_cid: Option<BoundedVec<u8, ConstU32<64>>>,
         pub generated_at: BlockNumberFor<T>,
@@ -215,6 +245,12 @@ pub mod pallet {
         TooManyRequests,
         /// 报告已存在
         ReportAlreadyExists,
+        /// 不是紫微命盘所有者
+        NotZiweiOwner,
+        /// 紫微命盘不存在
+        ZiweiNotFound,
+        /// 合婚方式所需的命盘缺失
+        MissingChart,
     }
 
     // ========================================================================
@@ -234,61 +270,13 @@ pub mod pallet {
         ) -> DispatchResult {
             let party_a = ensure_signed(origin)?;
 
-            ensure!(party_a != party_b, Error::<T>::CannotMatchSelf);
-
-            ensure!(
-                T::BaziProvider::is_owner(&party_a, party_a_bazi_id),
-                Error::<T>::NotBaziOwner
-            );
-
-            ensure!(
-                T::BaziProvider::exists(party_a_bazi_id),
-                Error::<T>::BaziNotFound
-            );
-            ensure!(
-                T::BaziProvider::exists(party_b_bazi_id),
-                Error::<T>::BaziNotFound
-            );
-
-            let mut party_a_requests = UserRequestsAsPartyA::<T>::get(&party_a);
-            ensure!(
-                party_a_requests.len() < T::MaxRequestsPerUser::get() as usize,
-                Error::<T>::TooManyRequests
-            );
-
-            let request_id = NextRequestId::<T>::get();
-            let current_block = frame_system::Pallet::<T>::block_number();
-
-            let request = CompatibilityRequest {
-                id: request_id,
-                party_a: party_a.clone(),
-                party_b: party_b.clone(),
-                party_a_bazi_id,
-                party_b_bazi_id,
-                status: MatchStatus::PendingAuthorization,
-                created_at: current_block,
-                authorized_at: None,
-            };
-
-            Requests::<T>::insert(request_id, request);
-            NextRequestId::<T>::put(request_id.saturating_add(1));
-
-            party_a_requests
-                .try_push(request_id)
-                .map_err(|_| Error::<T>::TooManyRequests)?;
-            UserRequestsAsPartyA::<T>::insert(&party_a, party_a_requests);
-
-            let mut party_b_requests = UserRequestsAsPartyB::<T>::get(&party_b);
-            let _ = party_b_requests.try_push(request_id);
-            UserRequestsAsPartyB::<T>::insert(&party_b, party_b_requests);
-
-            Self::deposit_event(Event::RequestCreated {
-                request_id,
+            Self::do_create_request(
                 party_a,
                 party_b,
-            });
-
-            Ok(())
+                CompatibilityMethod::Bazi,
+                Some((party_a_bazi_id, party_b_bazi_id)),
+                None,
+            )
         }
 
         /// 授权合婚请求
@@ -307,10 +295,18 @@ pub mod pallet {
                     Error::<T>::InvalidRequestStatus
                 );
 
-                ensure!(
-                    T::BaziProvider::is_owner(&party_b, request.party_b_bazi_id),
-                    Error::<T>::NotBaziOwner
-                );
+                if let Some(bazi_id) = request.party_b_bazi_id {
+                    ensure!(
+                        T::BaziProvider::is_owner(&party_b, bazi_id),
+                        Error::<T>::NotBaziOwner
+                    );
+                }
+                if let Some(ziwei_id) = request.party_b_ziwei_id {
+                    ensure!(
+                        T::ZiweiProvider::is_owner(&party_b, ziwei_id),
+                        Error::<T>::NotZiweiOwner
+                    );
+                }
 
                 let current_block = frame_system::Pallet::<T>::block_number();
                 let expiration = request.created_at.saturating_add(T::RequestExpiration::get());
@@ -406,55 +402,32 @@ pub mod pallet {
                 Error::<T>::ReportAlreadyExists
             );
 
-            // 获取八字数据
-            let bazi_a = T::BaziProvider::get_sizhu_index(request.party_a_bazi_id)
-                .ok_or(Error::<T>::BaziNotFound)?;
-            let bazi_b = T::BaziProvider::get_sizhu_index(request.party_b_bazi_id)
-                .ok_or(Error::<T>::BaziNotFound)?;
-
-            // 计算日柱合婚评分
-            let day_ganzhi_a = bazi_a.day_ganzhi();
-            let day_ganzhi_b = bazi_b.day_ganzhi();
-            let day_pillar_result = calculate_day_pillar_compatibility(day_ganzhi_a, day_ganzhi_b);
-
-            // 计算五行互补评分
-            let wuxing_result = if let (Some(interp_a), Some(interp_b)) = (
-                T::BaziProvider::get_interpretation(request.party_a_bazi_id),
-                T::BaziProvider::get_interpretation(request.party_b_bazi_id),
-            ) {
-                calculate_wuxing_compatibility(&interp_a, &interp_b)
-            } else {
-                WuxingCompatibilityResult {
-                    yongshen_score: 50,
-                    jishen_score: 70,
-                    balance_score: 50,
-                    overall: 55,
-                }
+            // 八字合婚
+            let bazi_result = match (request.party_a_bazi_id, request.party_b_bazi_id) {
+                (Some(bazi_a_id), Some(bazi_b_id)) if request.method.uses_bazi() => {
+                    Some(Self::compute_bazi_compatibility(bazi_a_id, bazi_b_id)?)
+                },
+                _ => None,
             };
 
-            // 计算性格匹配评分
-            let personality_result = if let (Some(xingge_a), Some(xingge_b)) = (
-                T::BaziProvider::get_personality(request.party_a_bazi_id),
-                T::BaziProvider::get_personality(request.party_b_bazi_id),
-            ) {
-                calculate_personality_compatibility(&xingge_a, &xingge_b)
-            } else {
-                calculate_default_personality_score()
+            // 紫微合盘
+            let ziwei_detail = match (request.party_a_ziwei_id, request.party_b_ziwei_id) {
+                (Some(ziwei_a_id), Some(ziwei_b_id)) if request.method.uses_ziwei() => {
+                    Some(Self::compute_ziwei_compatibility(ziwei_a_id, ziwei_b_id)?)
+                },
+                _ => None,
             };
 
-            // 神煞和大运评分（预留）
-            let shensha_score = 60u8;
-            let dayun_score = 60u8;
-
-            let score_detail = CompatibilityScoreDetail {
-                day_pillar_score: day_pillar_result.overall,
-                wuxing_score: wuxing_result.overall,
-                personality_score: personality_result.overall,
-                shensha_score,
-                dayun_score,
+            // 综合评分：两种方式均有时各占 50%
+            let overall_score = match (&bazi_result, &ziwei_detail) {
+                (Some((detail, _)), Some(ziwei)) => {
+                    ((detail.calculate_overall() as u16 + ziwei.overall as u16) / 2) as u8
+                },
+                (Some((detail, _)), None) => detail.calculate_overall(),
+                (None, Some(ziwei)) => ziwei.overall,
+                (None, None) => return Err(Error::<T>::MissingChart.into()),
             };
-
-            let overall_score = score_detail.calculate_overall();
+            let (score_detail, factors) = bazi_result.unwrap_or_default();
             let recommendation = MatchRecommendation::from_score(overall_score);
 
             let current_block = frame_system::Pallet::<T>::block_number();
@@ -462,11 +435,14 @@ pub mod pallet {
                 id: request_id,
                 request_id,
                 overall_score,
+                method: request.method,
                 score_detail,
+                factors,
+                ziwei_detail,
                 recommendation,
                 report_cid: None,
                 generated_at: current_block,
-                algorithm_version: 1,
+                algorithm_version: 2,
             };
 
             Reports::<T>::insert(request_id, report);
@@ -486,20 +462,229 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// 按指定方式创建合婚请求
+        ///
+        /// - `method`: 八字、紫微或综合
+        /// - `bazi_ids`: (甲方八字 ID, 乙方八字 ID)，八字/综合方式必填
+        /// - `ziwei_ids`: (甲方紫微命盘 ID, 乙方紫微命盘 ID)，紫微/综合方式必填
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::WeightInfo::create_request())]
+        pub fn create_request_with_method(
+            origin: OriginFor<T>,
+            party_b: T::AccountId,
+            method: CompatibilityMethod,
+            bazi_ids: Option<(u64, u64)>,
+            ziwei_ids: Option<(u64, u64)>,
+        ) -> DispatchResult {
+            let party_a = ensure_signed(origin)?;
+            Self::do_create_request(party_a, party_b, method, bazi_ids, ziwei_ids)
+        }
     }
 }
 
 // ============================================================================
-// Trait 定义
+// 合婚计算
 // ============================================================================
 
-/// 八字数据提供者 Trait
-pub trait BaziDataProvider<AccountId> {
-    fn exists(bazi_id: u64) -> bool;
-    fn is_owner(account: &AccountId, bazi_id: u64) -> bool;
-    fn get_sizhu_index(bazi_id: u64) -> Option<pallet_bazi_chart::types::SiZhuIndex>;
-    fn get_interpretation(bazi_id: u64) -> Option<pallet_bazi_chart::interpretation::CoreInterpretation>;
-    fn get_personality(bazi_id: u64) -> Option<pallet_bazi_chart::interpretation::CompactXingGe>;
+impl<T: Config> Pallet<T> {
+    /// 计算八字合婚评分详情与分项依据
+    pub fn compute_bazi_compatibility(
+        bazi_a_id: u64,
+        bazi_b_id: u64,
+    ) -> Result<(CompatibilityScoreDetail, CompatibilityFactors), DispatchError> {
+        // 获取八字数据
+        let bazi_a = T::BaziProvider::get_sizhu_index(bazi_a_id).ok_or(Error::<T>::BaziNotFound)?;
+        let bazi_b = T::BaziProvider::get_sizhu_index(bazi_b_id).ok_or(Error::<T>::BaziNotFound)?;
+
+        // 计算日柱合婚评分
+        let day_ganzhi_a = bazi_a.day_ganzhi();
+        let day_ganzhi_b = bazi_b.day_ganzhi();
+        let day_pillar_result = calculate_day_pillar_compatibility(day_ganzhi_a, day_ganzhi_b);
+
+        // 年支生肖与夫妻宫
+        let year_branch_result =
+            calculate_year_branch_compatibility(bazi_a.year_ganzhi().zhi, bazi_b.year_ganzhi().zhi);
+        let spouse_palace_result = calculate_spouse_palace_compatibility(&bazi_a, &bazi_b);
+
+        // 计算五行互补评分（无解盘数据时由四柱五行统计推算）
+        let interpretations = (
+            T::BaziProvider::get_interpretation(bazi_a_id),
+            T::BaziProvider::get_interpretation(bazi_b_id),
+        );
+        let wuxing_from_interpretation = interpretations.0.is_some() && interpretations.1.is_some();
+        let wuxing_result = match interpretations {
+            (Some(interp_a), Some(interp_b)) => calculate_wuxing_compatibility(&interp_a, &interp_b),
+            _ => calculate_wuxing_compatibility_from_sizhu(&bazi_a, &bazi_b),
+        };
+
+        // 计算性格匹配评分
+        let personality_result = if let (Some(xingge_a), Some(xingge_b)) = (
+            T::BaziProvider::get_personality(bazi_a_id),
+            T::BaziProvider::get_personality(bazi_b_id),
+        ) {
+            calculate_personality_compatibility(&xingge_a, &xingge_b)
+        } else {
+            calculate_default_personality_score()
+        };
+
+        // 神煞合婚（含孤辰寡宿）
+        let shensha_result = calculate_sizhu_shensha_compatibility(&bazi_a, &bazi_b);
+
+        // 大运同步（需双方起运信息，缺失时为中性分）
+        let qiyun = (
+            T::BaziProvider::get_qiyun(bazi_a_id),
+            T::BaziProvider::get_qiyun(bazi_b_id),
+        );
+        let dayun_available = qiyun.0.is_some() && qiyun.1.is_some();
+        let dayun_result = match qiyun {
+            (Some((shun_a, age_a)), Some((shun_b, age_b))) => calculate_dayun_sync(
+                &dayun_from_sizhu(&bazi_a, shun_a, age_a),
+                &dayun_from_sizhu(&bazi_b, shun_b, age_b),
+            ),
+            _ => calculate_dayun_sync(&[], &[]),
+        };
+
+        let score_detail = CompatibilityScoreDetail {
+            day_pillar_score: day_pillar_result.overall,
+            year_branch_score: year_branch_result.overall,
+            spouse_palace_score: spouse_palace_result.overall,
+            wuxing_score: wuxing_result.overall,
+            personality_score: personality_result.overall,
+            shensha_score: shensha_result.overall,
+            dayun_score: dayun_result.overall,
+        };
+
+        let factors = CompatibilityFactors {
+            day_stem_score: day_pillar_result.stem_score,
+            day_branch_score: day_pillar_result.branch_score,
+            year_branch_relation: year_branch_result.relation,
+            spouse_palace_relation: spouse_palace_result.relation,
+            spouse_palace_a_clashed: spouse_palace_result.palace_1_clashed,
+            spouse_palace_b_clashed: spouse_palace_result.palace_2_clashed,
+            yongshen_score: wuxing_result.yongshen_score,
+            jishen_score: wuxing_result.jishen_score,
+            balance_score: wuxing_result.balance_score,
+            wuxing_from_interpretation,
+            shensha_jishen_score: shensha_result.jishen_match_score,
+            shensha_xiongshen_score: shensha_result.xiongshen_conflict_score,
+            gu_gua_a: shensha_result.gu_gua_1,
+            gu_gua_b: shensha_result.gu_gua_2,
+            dayun_direction_score: dayun_result.direction_score,
+            dayun_wuxing_score: dayun_result.wuxing_sync_score,
+            dayun_available,
+        };
+
+        Ok((score_detail, factors))
+    }
+
+    /// 计算紫微合盘评分详情
+    pub fn compute_ziwei_compatibility(
+        ziwei_a_id: u64,
+        ziwei_b_id: u64,
+    ) -> Result<ZiweiScoreDetail, DispatchError> {
+        let palaces_a = T::ZiweiProvider::get_palaces(ziwei_a_id).ok_or(Error::<T>::ZiweiNotFound)?;
+        let palaces_b = T::ZiweiProvider::get_palaces(ziwei_b_id).ok_or(Error::<T>::ZiweiNotFound)?;
+
+        let result = calculate_ziwei_compatibility(&palaces_a, &palaces_b);
+
+        Ok(ZiweiScoreDetail {
+            ming_gong_score: result.ming_gong_score,
+            spouse_palace_score: result.spouse_palace_score,
+            sihua_score: result.sihua_score,
+            sihua_auspicious_hits: result.sihua_auspicious_hits,
+            sihua_ji_hits: result.sihua_ji_hits,
+            overall: result.overall,
+        })
+    }
+
+    /// 校验命盘归属并创建合婚请求
+    fn do_create_request(
+        party_a: T::AccountId,
+        party_b: T::AccountId,
+        method: CompatibilityMethod,
+        bazi_ids: Option<(u64, u64)>,
+        ziwei_ids: Option<(u64, u64)>,
+    ) -> DispatchResult {
+        ensure!(party_a != party_b, Error::<T>::CannotMatchSelf);
+        ensure!(
+            (!method.uses_bazi() || bazi_ids.is_some()) && (!method.uses_ziwei() || ziwei_ids.is_some()),
+            Error::<T>::MissingChart
+        );
+        let bazi_ids = bazi_ids.filter(|_| method.uses_bazi());
+        let ziwei_ids = ziwei_ids.filter(|_| method.uses_ziwei());
+
+        if let Some((party_a_bazi_id, party_b_bazi_id)) = bazi_ids {
+            ensure!(
+                T::BaziProvider::is_owner(&party_a, party_a_bazi_id),
+                Error::<T>::NotBaziOwner
+            );
+            ensure!(T::BaziProvider::exists(party_a_bazi_id), Error::<T>::BaziNotFound);
+            ensure!(T::BaziProvider::exists(party_b_bazi_id), Error::<T>::BaziNotFound);
+        }
+
+        if let Some((party_a_ziwei_id, party_b_ziwei_id)) = ziwei_ids {
+            ensure!(
+                T::ZiweiProvider::is_owner(&party_a, party_a_ziwei_id),
+                Error::<T>::NotZiweiOwner
+            );
+            ensure!(T::ZiweiProvider::exists(party_a_ziwei_id), Error::<T>::ZiweiNotFound);
+            ensure!(T::ZiweiProvider::exists(party_b_ziwei_id), Error::<T>::ZiweiNotFound);
+        }
+
+        let mut party_a_requests = UserRequestsAsPartyA::<T>::get(&party_a);
+        ensure!(
+            party_a_requests.len() < T::MaxRequestsPerUser::get() as usize,
+            Error::<T>::TooManyRequests
+        );
+
+        let request_id = NextRequestId::<T>::get();
+        let current_block = frame_system::Pallet::<T>::block_number();
+
+        let request = CompatibilityRequest {
+            id: request_id,
+            party_a: party_a.clone(),
+            party_b: party_b.clone(),
+            method,
+            party_a_bazi_id: bazi_ids.map(|(a, _)| a),
+            party_b_bazi_id: bazi_ids.map(|(_, b)| b),
+            party_a_ziwei_id: ziwei_ids.map(|(a, _)| a),
+            party_b_ziwei_id: ziwei_ids.map(|(_, b)| b),
+            status: MatchStatus::PendingAuthorization,
+            created_at: current_block,
+            authorized_at: None,
+        };
+
+        Requests::<T>::insert(request_id, request);
+        NextRequestId::<T>::put(request_id.saturating_add(1));
+
+        party_a_requests
+            .try_push(request_id)
+            .map_err(|_| Error::<T>::TooManyRequests)?;
+        UserRequestsAsPartyA::<T>::insert(&party_a, party_a_requests);
+
+        let mut party_b_requests = UserRequestsAsPartyB::<T>::get(&party_b);
+        let _ = party_b_requests.try_push(request_id);
+        UserRequestsAsPartyB::<T>::insert(&party_b, party_b_requests);
+
+        Self::deposit_event(Event::RequestCreated {
+            request_id,
+            party_a,
+            party_b,
+        });
+
+        Ok(())
+    }
 }
 
+// ============================================================================
+// Trait 定义
+// ============================================================================
+
+/// 八字数据提供者 Trait（由 pallet-bazi-chart 实现）
+pub use pallet_bazi_chart::BaziDataProvider;
+
+/// 紫微命盘数据提供者 Trait（由 pallet-ziwei 实现）
+pub use pallet_ziwei::ZiweiDataProvider;
+
 // WeightInfo trait 和实现已移至 weights.rs
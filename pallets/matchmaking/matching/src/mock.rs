@@ -0,0 +1,119 @@
+//! # 匹配算法模块 Mock 运行时
+
+use crate as pallet_matchmaking_matching;
+use crate::{BaziDataProvider, ZiweiDataProvider};
+use frame_support::{
+    derive_impl, parameter_types,
+    traits::{ConstU32, ConstU64},
+};
+use pallet_bazi_chart::{CompactXingGe, CoreInterpretation, SiZhuIndex};
+use pallet_ziwei::{GongWei, Palace};
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Matching: pallet_matchmaking_matching,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+}
+
+parameter_types! {
+    /// 八字命盘：(命盘 ID, 所有者, 四柱索引)
+    pub static BaziCharts: Vec<(u64, u64, SiZhuIndex)> = vec![];
+    /// 紫微命盘：(命盘 ID, 创建者, 十二宫)
+    pub static ZiweiCharts: Vec<(u64, u64, [Palace; 12])> = vec![];
+}
+
+/// 登记八字命盘
+pub fn add_bazi_chart(id: u64, owner: u64, sizhu: SiZhuIndex) {
+    BaziCharts::mutate(|charts| charts.push((id, owner, sizhu)));
+}
+
+/// 登记紫微命盘
+pub fn add_ziwei_chart(id: u64, owner: u64, palaces: [Palace; 12]) {
+    ZiweiCharts::mutate(|charts| charts.push((id, owner, palaces)));
+}
+
+/// 按宫位顺序初始化的空白十二宫
+pub fn empty_palaces() -> [Palace; 12] {
+    let mut palaces: [Palace; 12] = Default::default();
+    for (i, palace) in palaces.iter_mut().enumerate() {
+        palace.gong_wei = GongWei::from_index(i as u8);
+    }
+    palaces
+}
+
+/// 模拟八字数据：仅提供四柱，解盘与起运缺省
+pub struct MockBaziProvider;
+
+impl BaziDataProvider<u64> for MockBaziProvider {
+    fn exists(bazi_id: u64) -> bool {
+        BaziCharts::get().iter().any(|(id, _, _)| *id == bazi_id)
+    }
+
+    fn is_owner(account: &u64, bazi_id: u64) -> bool {
+        BaziCharts::get().iter().any(|(id, owner, _)| *id == bazi_id && owner == account)
+    }
+
+    fn get_sizhu_index(bazi_id: u64) -> Option<SiZhuIndex> {
+        BaziCharts::get().into_iter().find(|(id, _, _)| *id == bazi_id).map(|(_, _, sizhu)| sizhu)
+    }
+
+    fn get_interpretation(_bazi_id: u64) -> Option<CoreInterpretation> {
+        None
+    }
+
+    fn get_personality(_bazi_id: u64) -> Option<CompactXingGe> {
+        None
+    }
+
+    fn get_qiyun(_bazi_id: u64) -> Option<(bool, u8)> {
+        None
+    }
+}
+
+/// 模拟紫微数据
+pub struct MockZiweiProvider;
+
+impl ZiweiDataProvider<u64> for MockZiweiProvider {
+    fn exists(chart_id: u64) -> bool {
+        ZiweiCharts::get().iter().any(|(id, _, _)| *id == chart_id)
+    }
+
+    fn is_owner(account: &u64, chart_id: u64) -> bool {
+        ZiweiCharts::get().iter().any(|(id, owner, _)| *id == chart_id && owner == account)
+    }
+
+    fn get_palaces(chart_id: u64) -> Option<[Palace; 12]> {
+        ZiweiCharts::get().into_iter().find(|(id, _, _)| *id == chart_id).map(|(_, _, palaces)| palaces)
+    }
+}
+
+impl pallet_matchmaking_matching::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type BaziProvider = MockBaziProvider;
+    type ZiweiProvider = MockZiweiProvider;
+    type MaxRequestsPerUser = ConstU32<10>;
+    type RequestExpiration = ConstU64<100>;
+    type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    BaziCharts::set(vec![]);
+    ZiweiCharts::set(vec![]);
+
+    let t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
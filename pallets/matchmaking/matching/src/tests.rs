@@ -1,3 +1,202 @@
 //! # 匹配算法模块测试
 
-// TODO: 添加测试用例
+use crate::{mock::*, ziwei::calculate_ziwei_compatibility, Error, Event, Reports};
+use frame_support::{assert_noop, assert_ok};
+use pallet_bazi_chart::SiZhuIndex;
+use pallet_matchmaking_common::{CompatibilityMethod, MatchRecommendation, MatchStatus, ZiweiScoreDetail};
+use pallet_ziwei::{GongWei, Palace, ZhuXing};
+
+/// 甲方紫微命盘：命宫廉贞，夫妻宫天府
+fn palaces_a() -> [Palace; 12] {
+    let mut palaces = empty_palaces();
+    palaces[GongWei::MingGong.index() as usize].zhu_xing[0] = Some(ZhuXing::LianZhen);
+    palaces[GongWei::FuQi.index() as usize].zhu_xing[0] = Some(ZhuXing::TianFu);
+    palaces
+}
+
+/// 乙方紫微命盘：命宫天府，夫妻宫太阳
+fn palaces_b() -> [Palace; 12] {
+    let mut palaces = empty_palaces();
+    palaces[GongWei::MingGong.index() as usize].zhu_xing[0] = Some(ZhuXing::TianFu);
+    palaces[GongWei::FuQi.index() as usize].zhu_xing[0] = Some(ZhuXing::TaiYang);
+    palaces
+}
+
+/// 登记双方八字（1、2）与紫微命盘（11、12）
+fn setup_charts() {
+    add_bazi_chart(
+        1,
+        ALICE,
+        SiZhuIndex { year_gan: 6, year_zhi: 6, month_gan: 4, month_zhi: 2, day_gan: 0, day_zhi: 0, hour_gan: 0, hour_zhi: 0 },
+    );
+    add_bazi_chart(
+        2,
+        BOB,
+        SiZhuIndex { year_gan: 8, year_zhi: 10, month_gan: 5, month_zhi: 3, day_gan: 1, day_zhi: 11, hour_gan: 2, hour_zhi: 2 },
+    );
+    add_ziwei_chart(11, ALICE, palaces_a());
+    add_ziwei_chart(12, BOB, palaces_b());
+}
+
+fn expected_ziwei() -> ZiweiScoreDetail {
+    let result = calculate_ziwei_compatibility(&palaces_a(), &palaces_b());
+    ZiweiScoreDetail {
+        ming_gong_score: result.ming_gong_score,
+        spouse_palace_score: result.spouse_palace_score,
+        sihua_score: result.sihua_score,
+        sihua_auspicious_hits: result.sihua_auspicious_hits,
+        sihua_ji_hits: result.sihua_ji_hits,
+        overall: result.overall,
+    }
+}
+
+/// 创建、授权并生成报告，返回请求 ID
+fn generate(method: CompatibilityMethod, bazi_ids: Option<(u64, u64)>, ziwei_ids: Option<(u64, u64)>) -> u64 {
+    let request_id = Matching::next_request_id();
+    assert_ok!(Matching::create_request_with_method(
+        RuntimeOrigin::signed(ALICE),
+        BOB,
+        method,
+        bazi_ids,
+        ziwei_ids,
+    ));
+    assert_ok!(Matching::authorize_request(RuntimeOrigin::signed(BOB), request_id));
+    assert_ok!(Matching::generate_report(RuntimeOrigin::signed(ALICE), request_id));
+    request_id
+}
+
+#[test]
+fn ziwei_request_generates_ziwei_report() {
+    new_test_ext().execute_with(|| {
+        setup_charts();
+
+        let request_id = generate(CompatibilityMethod::Ziwei, None, Some((11, 12)));
+
+        let request = Matching::requests(request_id).unwrap();
+        assert_eq!(request.status, MatchStatus::Completed);
+        assert_eq!((request.party_a_bazi_id, request.party_b_bazi_id), (None, None));
+
+        let expected = expected_ziwei();
+        let report = Reports::<Test>::get(request_id).unwrap();
+        assert_eq!(report.method, CompatibilityMethod::Ziwei);
+        assert_eq!(report.ziwei_detail, Some(expected.clone()));
+        assert_eq!(report.overall_score, expected.overall);
+        assert_eq!(report.score_detail, Default::default());
+        assert_eq!(report.recommendation, MatchRecommendation::from_score(expected.overall));
+
+        System::assert_last_event(
+            Event::<Test>::ReportGenerated {
+                report_id: request_id,
+                request_id,
+                overall_score: expected.overall,
+                recommendation: report.recommendation,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn ziwei_request_checks_charts() {
+    new_test_ext().execute_with(|| {
+        setup_charts();
+
+        assert_noop!(
+            Matching::create_request_with_method(
+                RuntimeOrigin::signed(ALICE),
+                BOB,
+                CompatibilityMethod::Ziwei,
+                Some((1, 2)),
+                None,
+            ),
+            Error::<Test>::MissingChart
+        );
+        assert_noop!(
+            Matching::create_request_with_method(
+                RuntimeOrigin::signed(ALICE),
+                BOB,
+                CompatibilityMethod::Ziwei,
+                None,
+                Some((12, 11)),
+            ),
+            Error::<Test>::NotZiweiOwner
+        );
+        assert_noop!(
+            Matching::create_request_with_method(
+                RuntimeOrigin::signed(ALICE),
+                BOB,
+                CompatibilityMethod::Ziwei,
+                None,
+                Some((11, 99)),
+            ),
+            Error::<Test>::ZiweiNotFound
+        );
+
+        // 乙方授权时须为其命盘所有者
+        assert_ok!(Matching::create_request_with_method(
+            RuntimeOrigin::signed(ALICE),
+            BOB,
+            CompatibilityMethod::Ziwei,
+            None,
+            Some((11, 11)),
+        ));
+        assert_noop!(
+            Matching::authorize_request(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::NotZiweiOwner
+        );
+    });
+}
+
+#[test]
+fn combined_report_averages_bazi_and_ziwei() {
+    new_test_ext().execute_with(|| {
+        setup_charts();
+
+        let bazi_only = generate(CompatibilityMethod::Bazi, Some((1, 2)), Some((11, 12)));
+        let ziwei_only = generate(CompatibilityMethod::Ziwei, Some((1, 2)), Some((11, 12)));
+        let both = generate(CompatibilityMethod::Both, Some((1, 2)), Some((11, 12)));
+
+        let bazi_report = Reports::<Test>::get(bazi_only).unwrap();
+        let ziwei_report = Reports::<Test>::get(ziwei_only).unwrap();
+        let both_report = Reports::<Test>::get(both).unwrap();
+
+        // 单一方式只使用对应命盘
+        assert_eq!(bazi_report.ziwei_detail, None);
+        assert_eq!(Matching::requests(bazi_only).unwrap().party_a_ziwei_id, None);
+        assert_eq!(Matching::requests(ziwei_only).unwrap().party_a_bazi_id, None);
+
+        let (score_detail, factors) = Matching::compute_bazi_compatibility(1, 2).unwrap();
+        let bazi_score = score_detail.calculate_overall();
+        let ziwei_score = expected_ziwei().overall;
+        assert_eq!(bazi_report.overall_score, bazi_score);
+        assert_eq!(ziwei_report.overall_score, ziwei_score);
+
+        // 综合方式：八字与紫微各占 50%
+        assert_eq!(both_report.method, CompatibilityMethod::Both);
+        assert_eq!(both_report.overall_score, ((bazi_score as u16 + ziwei_score as u16) / 2) as u8);
+        assert_eq!(both_report.score_detail, score_detail);
+        assert_eq!(both_report.factors, factors);
+        assert_eq!(both_report.ziwei_detail, Some(expected_ziwei()));
+        assert_eq!(both_report.recommendation, MatchRecommendation::from_score(both_report.overall_score));
+    });
+}
+
+#[test]
+fn combined_request_requires_both_charts() {
+    new_test_ext().execute_with(|| {
+        setup_charts();
+
+        for (bazi_ids, ziwei_ids) in [(Some((1, 2)), None), (None, Some((11, 12)))] {
+            assert_noop!(
+                Matching::create_request_with_method(
+                    RuntimeOrigin::signed(ALICE),
+                    BOB,
+                    CompatibilityMethod::Both,
+                    bazi_ids,
+                    ziwei_ids,
+                ),
+                Error::<Test>::MissingChart
+            );
+        }
+    });
+}
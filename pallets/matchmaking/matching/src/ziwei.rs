@@ -0,0 +1,310 @@
+//! # 紫微合盘算法
+//!
+//! 基于双方紫微命盘的合盘分析：
+//!
+//! - 命宫主星配对（命宫无主星时借对宫迁移宫）
+//! - 夫妻宫星曜与对方命宫主星的呼应
+//! - 命宫、夫妻宫宫干四化飞入对方命盘
+
+use pallet_ziwei::interpretation::{find_star_palace, get_gong_gan_si_hua};
+use pallet_ziwei::{GongWei, Palace, SiHua, ZhuXing};
+use sp_std::vec::Vec;
+
+/// 紫微合盘结果
+#[derive(Clone, Debug, Default)]
+pub struct ZiweiMatchResult {
+    pub ming_gong_score: u8,
+    pub spouse_palace_score: u8,
+    pub sihua_score: u8,
+    pub sihua_auspicious_hits: u8,
+    pub sihua_ji_hits: u8,
+    pub overall: u8,
+}
+
+/// 主星相配组合
+const HARMONIOUS_PAIRS: [(ZhuXing, ZhuXing); 6] = [
+    (ZhuXing::ZiWei, ZhuXing::TianFu),      // 紫府：帝星配库星
+    (ZhuXing::TaiYang, ZhuXing::TaiYin),    // 日月并明
+    (ZhuXing::TianJi, ZhuXing::TianLiang),  // 机梁善荫
+    (ZhuXing::TianTong, ZhuXing::TaiYin),   // 同阴相守
+    (ZhuXing::WuQu, ZhuXing::TianXiang),    // 武相：财印相生
+    (ZhuXing::LianZhen, ZhuXing::TianFu),   // 廉府：刚柔相济
+];
+
+/// 杀破狼：变动之星，双方命宫均坐则关系易起伏
+const SHA_PO_LANG: [ZhuXing; 3] = [ZhuXing::QiSha, ZhuXing::PoJun, ZhuXing::TanLang];
+
+/// 四化飞入对方命盘时计分的宫位
+const KEY_PALACES: [GongWei; 3] = [GongWei::MingGong, GongWei::FuQi, GongWei::FuDe];
+
+/// 查找指定宫位
+pub fn find_palace(palaces: &[Palace; 12], gong_wei: GongWei) -> Option<&Palace> {
+    palaces.iter().find(|p| p.gong_wei == gong_wei)
+}
+
+/// 宫位主星；无主星时借对宫主星
+fn palace_stars(palaces: &[Palace; 12], gong_wei: GongWei, opposite: GongWei) -> Vec<ZhuXing> {
+    let stars_of = |gong: GongWei| -> Vec<ZhuXing> {
+        find_palace(palaces, gong)
+            .map(|p| p.zhu_xing.iter().flatten().copied().collect())
+            .unwrap_or_default()
+    };
+    let stars = stars_of(gong_wei);
+    if stars.is_empty() {
+        stars_of(opposite)
+    } else {
+        stars
+    }
+}
+
+/// 命宫主星（无主星借迁移宫）
+pub fn ming_gong_stars(palaces: &[Palace; 12]) -> Vec<ZhuXing> {
+    palace_stars(palaces, GongWei::MingGong, GongWei::QianYi)
+}
+
+/// 夫妻宫主星（无主星借官禄宫）
+pub fn spouse_palace_stars(palaces: &[Palace; 12]) -> Vec<ZhuXing> {
+    palace_stars(palaces, GongWei::FuQi, GongWei::GuanLu)
+}
+
+/// 判断两颗主星是否相配
+pub fn is_harmonious_pair(star1: ZhuXing, star2: ZhuXing) -> bool {
+    HARMONIOUS_PAIRS
+        .iter()
+        .any(|&(a, b)| (star1 == a && star2 == b) || (star1 == b && star2 == a))
+}
+
+/// 计算两颗主星的配对评分
+pub fn star_pair_score(star1: ZhuXing, star2: ZhuXing) -> u8 {
+    if is_harmonious_pair(star1, star2) {
+        95
+    } else if SHA_PO_LANG.contains(&star1) && SHA_PO_LANG.contains(&star2) {
+        40
+    } else if star1.is_ziwei_series() != star2.is_ziwei_series() {
+        // 紫微、天府两系互补
+        75
+    } else if star1 == star2 {
+        70
+    } else {
+        60
+    }
+}
+
+/// 计算命宫主星配对评分（所有主星两两配对取平均）
+pub fn calculate_ming_gong_pairing(palaces_1: &[Palace; 12], palaces_2: &[Palace; 12]) -> u8 {
+    let stars_1 = ming_gong_stars(palaces_1);
+    let stars_2 = ming_gong_stars(palaces_2);
+    if stars_1.is_empty() || stars_2.is_empty() {
+        return 60;
+    }
+
+    let mut total = 0u32;
+    for &a in stars_1.iter() {
+        for &b in stars_2.iter() {
+            total += star_pair_score(a, b) as u32;
+        }
+    }
+    (total / (stars_1.len() * stars_2.len()) as u32) as u8
+}
+
+/// 夫妻宫呼应评分（单向）
+///
+/// 我方夫妻宫主星与对方命宫主星相同 +20、相配 +15；
+/// 夫妻宫每颗煞星 -5，生年化忌 -10，生年化禄 +10
+fn spouse_echo(mine: &[Palace; 12], partner: &[Palace; 12]) -> u8 {
+    let partner_ming = ming_gong_stars(partner);
+    let mut score = 60i16;
+
+    for star in spouse_palace_stars(mine) {
+        if partner_ming.contains(&star) {
+            score += 20;
+        } else if partner_ming.iter().any(|&p| is_harmonious_pair(star, p)) {
+            score += 15;
+        }
+    }
+
+    if let Some(spouse) = find_palace(mine, GongWei::FuQi) {
+        score -= spouse.liu_sha.iter().filter(|&&sha| sha).count() as i16 * 5;
+        if spouse.si_hua.contains(&Some(SiHua::HuaJi)) {
+            score -= 10;
+        }
+        if spouse.si_hua.contains(&Some(SiHua::HuaLu)) {
+            score += 10;
+        }
+    }
+
+    score.clamp(0, 100) as u8
+}
+
+/// 计算夫妻宫呼应评分（双向平均）
+pub fn calculate_spouse_palace_echo(palaces_1: &[Palace; 12], palaces_2: &[Palace; 12]) -> u8 {
+    ((spouse_echo(palaces_1, palaces_2) as u16 + spouse_echo(palaces_2, palaces_1) as u16) / 2) as u8
+}
+
+/// 统计宫干四化飞入对方关键宫位的次数（单向）
+///
+/// 返回 (禄权科次数, 化忌次数)
+fn sihua_flow(from: &[Palace; 12], to: &[Palace; 12]) -> (u8, u8) {
+    let mut auspicious = 0u8;
+    let mut ji = 0u8;
+
+    for gong_wei in [GongWei::MingGong, GongWei::FuQi] {
+        let Some(palace) = find_palace(from, gong_wei) else {
+            continue;
+        };
+        for (i, star) in get_gong_gan_si_hua(palace).iter().enumerate() {
+            let Some(pos) = find_star_palace(to, *star) else {
+                continue;
+            };
+            if !KEY_PALACES.contains(&to[pos as usize].gong_wei) {
+                continue;
+            }
+            if i == SiHua::HuaJi as usize {
+                ji += 1;
+            } else {
+                auspicious += 1;
+            }
+        }
+    }
+
+    (auspicious, ji)
+}
+
+/// 计算紫微合盘评分
+///
+/// 权重：命宫配对 35%、夫妻宫呼应 35%、四化飞星 30%
+pub fn calculate_ziwei_compatibility(
+    palaces_1: &[Palace; 12],
+    palaces_2: &[Palace; 12],
+) -> ZiweiMatchResult {
+    let ming_gong_score = calculate_ming_gong_pairing(palaces_1, palaces_2);
+    let spouse_palace_score = calculate_spouse_palace_echo(palaces_1, palaces_2);
+
+    let (aus_1, ji_1) = sihua_flow(palaces_1, palaces_2);
+    let (aus_2, ji_2) = sihua_flow(palaces_2, palaces_1);
+    let sihua_auspicious_hits = aus_1.saturating_add(aus_2);
+    let sihua_ji_hits = ji_1.saturating_add(ji_2);
+    let sihua_score = (60i16 + sihua_auspicious_hits as i16 * 8 - sihua_ji_hits as i16 * 15).clamp(0, 100) as u8;
+
+    let overall = ((ming_gong_score as u32 * 35
+        + spouse_palace_score as u32 * 35
+        + sihua_score as u32 * 30)
+        / 100) as u8;
+
+    ZiweiMatchResult {
+        ming_gong_score,
+        spouse_palace_score,
+        sihua_score,
+        sihua_auspicious_hits,
+        sihua_ji_hits,
+        overall,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pallet_ziwei::TianGan;
+
+    #[test]
+    fn test_star_pair_score() {
+        assert_eq!(star_pair_score(ZhuXing::ZiWei, ZhuXing::TianFu), 95);
+        assert_eq!(star_pair_score(ZhuXing::TianFu, ZhuXing::ZiWei), 95);
+        assert_eq!(star_pair_score(ZhuXing::QiSha, ZhuXing::PoJun), 40);
+        assert_eq!(star_pair_score(ZhuXing::TianJi, ZhuXing::WuQu), 60);
+        assert_eq!(star_pair_score(ZhuXing::TianJi, ZhuXing::JuMen), 75);
+    }
+
+    /// 按宫位顺序初始化、宫干均为乙（四化星：天机、天梁、紫微、太阴）的十二宫
+    fn palaces() -> [Palace; 12] {
+        let mut palaces: [Palace; 12] = Default::default();
+        for (i, palace) in palaces.iter_mut().enumerate() {
+            palace.gong_wei = GongWei::from_index(i as u8);
+            palace.tian_gan = TianGan::Yi;
+        }
+        palaces
+    }
+
+    fn palace_mut(palaces: &mut [Palace; 12], gong_wei: GongWei) -> &mut Palace {
+        &mut palaces[gong_wei.index() as usize]
+    }
+
+    #[test]
+    fn test_empty_ming_gong_borrows_opposite() {
+        let mut palaces = palaces();
+        palace_mut(&mut palaces, GongWei::QianYi).zhu_xing[0] = Some(ZhuXing::TaiYang);
+
+        assert_eq!(ming_gong_stars(&palaces), sp_std::vec![ZhuXing::TaiYang]);
+    }
+
+    #[test]
+    fn test_calculate_ziwei_compatibility_weights() {
+        let mut a = palaces();
+        let mut b = palaces();
+        palace_mut(&mut a, GongWei::MingGong).zhu_xing[0] = Some(ZhuXing::LianZhen);
+        palace_mut(&mut a, GongWei::FuQi).zhu_xing[0] = Some(ZhuXing::TianFu);
+        palace_mut(&mut b, GongWei::MingGong).zhu_xing[0] = Some(ZhuXing::TianFu);
+
+        let result = calculate_ziwei_compatibility(&a, &b);
+        // 廉府相配
+        assert_eq!(result.ming_gong_score, 95);
+        // 甲方夫妻宫天府与乙方命宫相同 +20，乙方夫妻宫（借官禄宫）无主星
+        assert_eq!(result.spouse_palace_score, (80 + 60) / 2);
+        // 宫干乙的四化星均不在对方命盘
+        assert_eq!((result.sihua_auspicious_hits, result.sihua_ji_hits), (0, 0));
+        assert_eq!(result.sihua_score, 60);
+        assert_eq!(result.overall, ((95 * 35 + 70 * 35 + 60 * 30) / 100) as u8);
+
+        // 合盘评分与顺序无关
+        let reversed = calculate_ziwei_compatibility(&b, &a);
+        assert_eq!(reversed.overall, result.overall);
+        assert_eq!(reversed.spouse_palace_score, result.spouse_palace_score);
+    }
+
+    #[test]
+    fn test_spouse_palace_sha_and_birth_sihua() {
+        let mut a = palaces();
+        let b = palaces();
+        let spouse = palace_mut(&mut a, GongWei::FuQi);
+        spouse.liu_sha[0] = true;
+        spouse.liu_sha[1] = true;
+        spouse.si_hua[0] = Some(SiHua::HuaJi);
+
+        // 甲方：60 - 2×5 - 10 = 40，乙方：60
+        assert_eq!(calculate_spouse_palace_echo(&a, &b), 50);
+
+        palace_mut(&mut a, GongWei::FuQi).si_hua[1] = Some(SiHua::HuaLu);
+        assert_eq!(calculate_spouse_palace_echo(&a, &b), 55);
+    }
+
+    #[test]
+    fn test_sihua_flow_into_key_palaces() {
+        let mut a = palaces();
+        let mut b = palaces();
+        // 甲方命宫宫干甲：廉贞化禄、破军化权、武曲化科、太阳化忌
+        palace_mut(&mut a, GongWei::MingGong).tian_gan = TianGan::Jia;
+        // 廉贞在乙方命宫（计禄），太阳在乙方夫妻宫（计忌），破军在财帛宫（非关键宫位，不计）
+        palace_mut(&mut b, GongWei::MingGong).zhu_xing[0] = Some(ZhuXing::LianZhen);
+        palace_mut(&mut b, GongWei::FuQi).zhu_xing[0] = Some(ZhuXing::TaiYang);
+        palace_mut(&mut b, GongWei::CaiBo).zhu_xing[0] = Some(ZhuXing::PoJun);
+
+        assert_eq!(sihua_flow(&a, &b), (1, 1));
+        assert_eq!(sihua_flow(&b, &a), (0, 0));
+
+        let result = calculate_ziwei_compatibility(&a, &b);
+        assert_eq!(result.sihua_auspicious_hits, 1);
+        assert_eq!(result.sihua_ji_hits, 1);
+        assert_eq!(result.sihua_score, 60 + 8 - 15);
+
+        // 武曲（化科）落入福德宫同样计分
+        palace_mut(&mut b, GongWei::FuDe).zhu_xing[0] = Some(ZhuXing::WuQu);
+        assert_eq!(sihua_flow(&a, &b), (2, 1));
+
+        // 命宫、夫妻宫宫干均为甲时分别计数；太阳移至乙方命宫仍计忌
+        palace_mut(&mut a, GongWei::FuQi).tian_gan = TianGan::Jia;
+        palace_mut(&mut b, GongWei::MingGong).zhu_xing[0] = Some(ZhuXing::TaiYang);
+        let result = calculate_ziwei_compatibility(&a, &b);
+        assert_eq!((result.sihua_auspicious_hits, result.sihua_ji_hits), (2, 2));
+        assert_eq!(result.sihua_score, 60 + 2 * 8 - 2 * 15);
+    }
+}
@@ -5,7 +5,7 @@
 //! ## 功能概述
 //!
 //! - **会员订阅**：支持月付、季付、半年付、年付和终身会员
-//! - **会员续费**：支持手动续费和自动续费
+//! - **会员续费**：支持手动续费和自动续费（由 `pallet-recurring-payment` 按期扣费）
 //! - **会员升级**：从年费会员升级到终身会员
 //! - **权益管理**：不同等级享有不同权益
 //! - **使用量追踪**：追踪每日功能使用情况
@@ -66,6 +66,10 @@ pub mod pallet {
     use pallet_trading_common::PricingProvider;
     use pallet_affiliate::types::AffiliateDistributor;
 use pallet_affiliate::UserFundingProvider;
+    use pallet_recurring_payment::{
+        traits::{RecurringPaymentHandler, RecurringPayments},
+        types::{LapseReason, PaymentPrice, RecurringService},
+    };
 
     /// 余额类型别名
     pub type BalanceOf<T> = <T as Config>::Balance;
@@ -145,6 +149,16 @@ use pallet_affiliate::UserFundingProvider;
             u128,
             BlockNumberFor<Self>,
         >;
+
+        /// 周期扣费引擎（自动续费）
+        ///
+        /// 开启自动续费的会员在到期前一天按订阅时长扣费，付款人须先在
+        /// `pallet-recurring-payment` 中为 `MatchmakingMembership` 设置授权。
+        type RecurringPayments: RecurringPayments<
+            Self::AccountId,
+            BalanceOf<Self>,
+            BlockNumberFor<Self>,
+        >;
     }
 
     // ========================================================================
@@ -235,6 +249,11 @@ use pallet_affiliate::UserFundingProvider;
         AutoRenewCancelled {
             who: T::AccountId,
         },
+        /// 自动续费已终止（扣费宽限期过期、付款人取消计划或撤销授权）
+        AutoRenewLapsed {
+            who: T::AccountId,
+            reason: LapseReason,
+        },
         /// 会员已过期
         Expired {
             who: T::AccountId,
@@ -296,7 +315,7 @@ use pallet_affiliate::UserFundingProvider;
         ///
         /// # 参数
         /// - `duration`: 订阅时长
-        /// - `auto_renew`: 是否自动续费（仅年费会员有效）
+        /// - `auto_renew`: 是否自动续费（仅年费会员有效，按相同时长到期前一天自动扣费）
         /// - `referrer`: 推荐人账户（可选）
         #[pallet::call_index(0)]
         #[pallet::weight(T::WeightInfo::subscribe())]
@@ -345,18 +364,23 @@ use pallet_affiliate::UserFundingProvider;
                 bytes
             });
 
+            let auto_renew = auto_renew && tier != MembershipTier::Lifetime;
             let membership = MembershipInfo {
                 tier,
                 subscribed_at: now,
                 expires_at,
                 total_paid: fee,
-                auto_renew: auto_renew && tier != MembershipTier::Lifetime,
+                auto_renew,
                 consecutive_months: duration_months,
                 referrer: referrer_bytes,
             };
 
             Memberships::<T>::insert(&who, membership);
 
+            if auto_renew {
+                Self::schedule_auto_renew(&who, duration, expires_at)?;
+            }
+
             // 更新统计
             GlobalStats::<T>::mutate(|stats| {
                 match tier {
@@ -384,6 +408,7 @@ use pallet_affiliate::UserFundingProvider;
         /// 续费会员
         ///
         /// 年费会员续费，延长会员有效期。
+        /// 已开启自动续费时，自动续费计划改为本次时长并从新到期时间起算。
         #[pallet::call_index(1)]
         #[pallet::weight(T::WeightInfo::renew())]
         pub fn renew(
@@ -395,7 +420,7 @@ use pallet_affiliate::UserFundingProvider;
             // 终身会员不需要续费
             ensure!(duration != SubscriptionDuration::Lifetime, Error::<T>::AlreadyLifetime);
 
-            let mut membership = Memberships::<T>::get(&who)
+            let membership = Memberships::<T>::get(&who)
                 .ok_or(Error::<T>::NotAMember)?;
 
             // 终身会员不需要续费
@@ -408,37 +433,12 @@ use pallet_affiliate::UserFundingProvider;
             let balance = T::Fungible::balance(&who);
             ensure!(balance >= fee, Error::<T>::InsufficientBalance);
 
-            // 分配费用（续费使用原推荐人）
-            let referrer = membership.referrer.map(|bytes| {
-                T::AccountId::decode(&mut &bytes[..]).ok()
-            }).flatten();
-            Self::distribute_fee(&who, fee, referrer)?;
-
-            // 更新会员信息
-            let now = frame_system::Pallet::<T>::block_number();
-            let base_time = if membership.expires_at > now {
-                membership.expires_at
-            } else {
-                now
-            };
-            let blocks = T::BlocksPerMonth::get()
-                .saturating_mul(duration_months.into());
-            membership.expires_at = base_time.saturating_add(blocks);
-            membership.total_paid = membership.total_paid.saturating_add(fee);
-            membership.consecutive_months = membership.consecutive_months.saturating_add(duration_months);
-            membership.tier = MembershipTier::Annual;
-
-            Memberships::<T>::insert(&who, membership.clone());
-
-            // 记录交易历史
-            Self::record_transaction(&who, SubscriptionTxType::Renewal, fee, duration_months);
+            let auto_renew = membership.auto_renew;
+            let new_expiry = Self::do_renew(&who, membership, fee, duration_months)?;
 
-            Self::deposit_event(Event::Renewed {
-                who,
-                duration_months,
-                amount: fee.try_into().unwrap_or(0u128),
-                new_expiry: membership.expires_at,
-            });
+            if auto_renew {
+                Self::schedule_auto_renew(&who, duration, new_expiry)?;
+            }
 
             Ok(())
         }
@@ -504,6 +504,7 @@ use pallet_affiliate::UserFundingProvider;
             membership.auto_renew = false;
 
             Memberships::<T>::insert(&who, membership);
+            T::RecurringPayments::cancel_all(&who, RecurringService::MatchmakingMembership);
 
             // 更新统计
             GlobalStats::<T>::mutate(|stats| {
@@ -538,6 +539,7 @@ use pallet_affiliate::UserFundingProvider;
                     .ok_or(Error::<T>::NotAMember)?;
                 
                 membership.auto_renew = false;
+                T::RecurringPayments::cancel_all(&who, RecurringService::MatchmakingMembership);
                 
                 Self::deposit_event(Event::AutoRenewCancelled { who: who.clone() });
                 
@@ -647,6 +649,84 @@ use pallet_affiliate::UserFundingProvider;
             Ok((MembershipTier::Annual, fee, months))
         }
 
+        /// 续费：分配费用（使用原推荐人）并延长有效期，返回新到期时间
+        fn do_renew(
+            who: &T::AccountId,
+            mut membership: MembershipInfo<BlockNumberFor<T>, BalanceOf<T>>,
+            fee: BalanceOf<T>,
+            duration_months: u32,
+        ) -> Result<BlockNumberFor<T>, DispatchError> {
+            let referrer = membership.referrer.map(|bytes| {
+                T::AccountId::decode(&mut &bytes[..]).ok()
+            }).flatten();
+            Self::distribute_fee(who, fee, referrer)?;
+
+            // 更新会员信息
+            let now = frame_system::Pallet::<T>::block_number();
+            let base_time = if membership.expires_at > now {
+                membership.expires_at
+            } else {
+                now
+            };
+            let blocks = T::BlocksPerMonth::get()
+                .saturating_mul(duration_months.into());
+            membership.expires_at = base_time.saturating_add(blocks);
+            membership.total_paid = membership.total_paid.saturating_add(fee);
+            membership.consecutive_months = membership.consecutive_months.saturating_add(duration_months);
+            membership.tier = MembershipTier::Annual;
+
+            Memberships::<T>::insert(who, membership.clone());
+
+            // 记录交易历史
+            Self::record_transaction(who, SubscriptionTxType::Renewal, fee, duration_months);
+
+            Self::deposit_event(Event::Renewed {
+                who: who.clone(),
+                duration_months,
+                amount: fee.try_into().unwrap_or(0u128),
+                new_expiry: membership.expires_at,
+            });
+
+            Ok(membership.expires_at)
+        }
+
+        /// 创建（或替换）自动续费计划
+        ///
+        /// 标识为订阅月数；每期按 `MonthlyFeeUsd` 锚定 USD 计价，到期前一天扣费，
+        /// 宽限期内的重试不影响会员连续性。
+        fn schedule_auto_renew(
+            who: &T::AccountId,
+            duration: SubscriptionDuration,
+            expires_at: BlockNumberFor<T>,
+        ) -> DispatchResult {
+            let months = duration.months();
+            let discounted = |monthly: u128| {
+                monthly
+                    .saturating_mul(months as u128)
+                    .saturating_mul(duration.discount_rate() as u128)
+                    / 10000u128
+            };
+            let usd_amount = discounted(T::MonthlyFeeUsd::get() as u128);
+            let monthly_fallback: u128 = T::MonthlyFee::get().try_into().unwrap_or(0);
+            let price = PaymentPrice::UsdPegged {
+                usd_amount: usd_amount.try_into().unwrap_or(u64::MAX),
+                fallback: discounted(monthly_fallback)
+                    .try_into()
+                    .map_err(|_| Error::<T>::ArithmeticOverflow)?,
+            };
+
+            T::RecurringPayments::cancel_all(who, RecurringService::MatchmakingMembership);
+            T::RecurringPayments::schedule(
+                who,
+                RecurringService::MatchmakingMembership,
+                months as u64,
+                price,
+                T::BlocksPerMonth::get().saturating_mul(months.into()),
+                expires_at.saturating_sub(T::BlocksPerDay::get()),
+            )?;
+            Ok(())
+        }
+
         /// 计算月费金额
         /// 
         /// 使用 DUST/USD 汇率计算，如果汇率不可用则使用兜底值
@@ -842,6 +922,36 @@ use pallet_affiliate::UserFundingProvider;
         }
     }
 
+    impl<T: Config> RecurringPaymentHandler<T::AccountId, BalanceOf<T>> for Pallet<T> {
+        /// 自动续费：标识为订阅月数
+        fn on_payment_due(
+            _service: RecurringService,
+            payer: &T::AccountId,
+            subject: u64,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let membership = Memberships::<T>::get(payer).ok_or(Error::<T>::NotAMember)?;
+            ensure!(membership.tier != MembershipTier::Lifetime, Error::<T>::AlreadyLifetime);
+            let months = u32::try_from(subject).map_err(|_| Error::<T>::InvalidDuration)?;
+            Self::do_renew(payer, membership, amount, months)?;
+            Ok(())
+        }
+
+        fn on_payment_lapsed(
+            _service: RecurringService,
+            payer: &T::AccountId,
+            _subject: u64,
+            reason: LapseReason,
+        ) {
+            Memberships::<T>::mutate(payer, |maybe_membership| {
+                if let Some(membership) = maybe_membership {
+                    membership.auto_renew = false;
+                }
+            });
+            Self::deposit_event(Event::AutoRenewLapsed { who: payer.clone(), reason });
+        }
+    }
+
     impl<T: Config> MembershipUsageTracker<T::AccountId> for Pallet<T> {
         fn can_use_recommendation(who: &T::AccountId) -> bool {
             let tier = Self::get_tier(who);
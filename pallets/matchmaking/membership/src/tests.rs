@@ -19,6 +19,7 @@ frame_support::construct_runtime!(
         System: frame_system,
         Balances: pallet_balances,
         MatchmakingMembership: pallet_matchmaking_membership,
+        RecurringPayment: pallet_recurring_payment,
     }
 );
 
@@ -139,6 +140,25 @@ impl Config for Test {
     type BurnAccount = BurnAccount;
     type UserFundingProvider = MockUserFundingProvider;
     type AffiliateDistributor = MockAffiliateDistributor;
+    type RecurringPayments = RecurringPayment;
+}
+
+parameter_types! {
+    pub const RecurringMinPeriod: u64 = 7200;
+    pub const RecurringRetryInterval: u64 = 7200;
+    pub const RecurringGracePeriod: u64 = 3 * 7200;
+}
+
+impl pallet_recurring_payment::Config for Test {
+    type Balance = u128;
+    type Pricing = MockPricing;
+    type Handler = MatchmakingMembership;
+    type MaxSchedulesPerPayer = ConstU32<4>;
+    type MaxChargesPerBlock = ConstU32<10>;
+    type MinPeriod = RecurringMinPeriod;
+    type RetryInterval = RecurringRetryInterval;
+    type GracePeriod = RecurringGracePeriod;
+    type WeightInfo = ();
 }
 
 fn new_test_ext() -> sp_io::TestExternalities {
@@ -846,3 +866,88 @@ fn subscription_duration_discount_rate() {
     assert_eq!(SubscriptionDuration::SixMonths.discount_rate(), 9000);
     assert_eq!(SubscriptionDuration::OneYear.discount_rate(), 8000);
 }
+
+// ============================================================================
+// 自动续费（pallet-recurring-payment）
+// ============================================================================
+
+use frame_support::traits::Hooks;
+use pallet_recurring_payment::{
+    traits::RecurringPayments as _,
+    types::{LapseReason, RecurringService},
+};
+
+/// 跳到指定区块并执行周期扣费（跳过的区块没有到期项，游标直接移到目标区块）
+fn run_recurring_at(n: u64) {
+    System::set_block_number(n);
+    pallet_recurring_payment::DueCursor::<Test>::put(n);
+    RecurringPayment::on_initialize(n);
+}
+
+#[test]
+fn auto_renew_charges_before_expiry() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(RecurringPayment::approve(
+            RuntimeOrigin::signed(1),
+            RecurringService::MatchmakingMembership,
+            100_000_000_000_000,
+            None,
+        ));
+        assert_ok!(MatchmakingMembership::subscribe(
+            RuntimeOrigin::signed(1),
+            SubscriptionDuration::OneMonth,
+            true,
+            None,
+        ));
+        let expires_at = Memberships::<Test>::get(1).unwrap().expires_at;
+        assert!(RecurringPayment::is_scheduled(&1, RecurringService::MatchmakingMembership, 1));
+
+        // 到期前一天扣费（汇率不可用时使用兜底月费 10 DUST）
+        let balance_before = Balances::free_balance(1);
+        run_recurring_at(expires_at - 7200);
+        let membership = Memberships::<Test>::get(1).unwrap();
+        assert_eq!(membership.expires_at, expires_at + 216000);
+        assert_eq!(membership.consecutive_months, 2);
+        assert_eq!(balance_before - Balances::free_balance(1), 10_000_000_000_000);
+    });
+}
+
+#[test]
+fn cancel_auto_renew_cancels_schedule() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(MatchmakingMembership::subscribe(
+            RuntimeOrigin::signed(1),
+            SubscriptionDuration::ThreeMonths,
+            true,
+            None,
+        ));
+        assert!(RecurringPayment::is_scheduled(&1, RecurringService::MatchmakingMembership, 3));
+
+        assert_ok!(MatchmakingMembership::cancel_auto_renew(RuntimeOrigin::signed(1)));
+        assert!(!RecurringPayment::is_scheduled(&1, RecurringService::MatchmakingMembership, 3));
+    });
+}
+
+#[test]
+fn auto_renew_lapses_without_approval() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(MatchmakingMembership::subscribe(
+            RuntimeOrigin::signed(1),
+            SubscriptionDuration::OneMonth,
+            true,
+            None,
+        ));
+        let first_due = Memberships::<Test>::get(1).unwrap().expires_at - 7200;
+
+        // 未授权：首期失败，宽限期内每天重试，3 天后终止
+        for day in 0..=3 {
+            run_recurring_at(first_due + day * 7200);
+        }
+        let membership = Memberships::<Test>::get(1).unwrap();
+        assert!(!membership.auto_renew);
+        assert_eq!(membership.consecutive_months, 1);
+        System::assert_has_event(
+            Event::AutoRenewLapsed { who: 1, reason: LapseReason::GraceExpired }.into(),
+        );
+    });
+}
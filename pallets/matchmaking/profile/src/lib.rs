@@ -9,6 +9,8 @@
 //! - **资料查询**：查询用户资料
 //! - **隐私设置**：控制资料可见性
 //! - **八字绑定**：绑定八字命盘用于合婚
+//! - **资料认证**：质押认证员审核活体/证件材料，颁发有时效的认证徽章
+//! - **月费自动续费**：通过 `pallet-recurring-payment` 按月自动扣取月费
 //!
 //! ## 使用流程
 //!
@@ -16,6 +18,7 @@
 //! 2. 设置择偶条件
 //! 3. 绑定八字命盘（可选）
 //! 4. 设置隐私模式
+//! 5. 通过 `pallet-evidence` 上传加密给认证员的认证材料，申请资料认证（可选）
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -24,23 +27,41 @@ pub use pallet::*;
 pub mod weights;
 pub use weights::WeightInfo;
 
-// TODO: 测试文件待完善 mock 配置
-// #[cfg(test)]
-// mod tests;
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
 
 use frame_support::pallet_prelude::*;
 use frame_support::traits::fungible::{Inspect, Mutate, MutateHold};
 use frame_system::pallet_prelude::*;
-use sp_runtime::traits::{Saturating, Zero};
+use sp_runtime::traits::{Hash as HashT, Saturating, Zero};
 use pallet_matchmaking_common::{
     Gender, ProfilePrivacyMode, EducationLevel, BirthDate, BirthTime,
     PropertyStatus, VehicleStatus, MaritalStatus, Lifestyle, PersonalityTrait,
     ProfileStatus, CompatibilityPreferences, FieldPrivacySettings,
     BaziPersonalityTrait, PersonalitySource,
+    VerificationKind, VerificationBadge, VerificationBadgeProvider,
 };
+use pallet_evidence::PrivateContentProvider;
 use pallet_trading_common::PricingProvider;
 use pallet_affiliate::types::AffiliateDistributor;
 use pallet_storage_service::{IpfsPinner, types::{SubjectType, PinTier}};
+use pallet_recurring_payment::{
+    traits::{RecurringPaymentHandler, RecurringPayments},
+    types::{LapseReason, PaymentPrice, RecurringService},
+};
+use pallet_reputation::{
+    traits::{ReputationProvider, ReputationSink},
+    types::{ReputationDomain, ReputationSignal, RiskLevel, Severity},
+};
+
+/// 每月区块数（6秒/块，30天）
+const BLOCKS_PER_MONTH: u32 = 432000;
+
+/// 等待分配认证员的申请数量上限
+pub const MAX_PENDING_ASSIGNMENTS: u32 = 256;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -122,6 +143,12 @@ pub mod pallet {
         
         /// 定价接口（用于换算保证金和月费）
         type Pricing: pallet_trading_common::PricingProvider<BalanceOf<Self>>;
+
+        /// 周期扣费接口（月费自动续费）
+        type RecurringPayments: RecurringPayments<Self::AccountId, BalanceOf<Self>, BlockNumberFor<Self>>;
+
+        /// 跨域信誉（上报违规 / 暂停 / 封禁信号，创建资料时检查综合风险）
+        type Reputation: ReputationSink<Self::AccountId> + ReputationProvider<Self::AccountId>;
         
         /// 国库账户（月费和罚没资金转入）
         type TreasuryAccount: Get<Self::AccountId>;
@@ -148,6 +175,36 @@ pub mod pallet {
         /// 每区块秒数（用于计算暂停天数）
         #[pallet::constant]
         type BlocksPerDay: Get<BlockNumberFor<Self>>;
+
+        // ========== 资料认证配置 ==========
+
+        /// 认证材料访问接口（pallet-evidence 私密内容）
+        type EvidenceAccess: PrivateContentProvider<Self::AccountId>;
+
+        /// 认证员质押金额
+        #[pallet::constant]
+        type VerifierStake: Get<BalanceOf<Self>>;
+
+        /// 单次认证费用（申请时锁定，认证员处理后支付给认证员）
+        #[pallet::constant]
+        type VerificationFee: Get<BalanceOf<Self>>;
+
+        /// 已分配认证员后取消申请（或未提交材料被拒绝）时支付给认证员的认证费比例（基点）
+        ///
+        /// 使申请人无法反复取消重新申请、免费重抽认证员
+        #[pallet::constant]
+        type VerificationCancelPenaltyBps: Get<u16>;
+
+        /// 认证有效期（区块数）
+        #[pallet::constant]
+        type VerificationValidity: Get<BlockNumberFor<Self>>;
+
+        /// 在职认证员数量上限
+        #[pallet::constant]
+        type MaxVerifiers: Get<u32>;
+
+        /// 随机数源（用于为认证申请分配认证员）
+        type Randomness: frame_support::traits::Randomness<Self::Hash, BlockNumberFor<Self>>;
         
         /// 余额类型
         type Balance: codec::FullCodec
@@ -173,6 +230,10 @@ pub mod pallet {
     pub enum HoldReason {
         /// 婚恋资料保证金
         ProfileDeposit,
+        /// 认证员质押
+        VerifierStake,
+        /// 待处理的认证费用
+        VerificationFee,
     }
 
     // ========================================================================
@@ -346,6 +407,71 @@ pub mod pallet {
         pub updated_at: BlockNumberFor<T>,
     }
 
+    /// 认证员状态
+    #[derive(Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug)]
+    pub enum VerifierStatus {
+        /// 已质押，等待治理批准
+        Pending,
+        /// 在职，可被分配认证申请
+        Active,
+        /// 已申请注销，等待其通过的认证全部过期或被撤销后退还质押
+        Leaving,
+    }
+
+    /// 认证员信息
+    #[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug)]
+    #[scale_info(skip_type_params(T))]
+    pub struct VerifierInfo<T: Config> {
+        /// 质押金额
+        pub stake: BalanceOf<T>,
+        /// 认证员状态
+        pub status: VerifierStatus,
+        /// 待处理的认证申请数
+        pub pending_requests: u32,
+        /// 累计通过数
+        pub approved_count: u32,
+        /// 累计拒绝数
+        pub rejected_count: u32,
+        /// 累计被治理撤销的认证数
+        pub revoked_count: u32,
+        /// 仍然有效的认证数（过期清理或撤销后减少，归零前质押不退还）
+        pub active_approvals: u32,
+        /// 注册时间
+        pub registered_at: BlockNumberFor<T>,
+    }
+
+    /// 认证申请
+    #[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug)]
+    #[scale_info(skip_type_params(T))]
+    pub struct VerificationRequest<T: Config> {
+        /// 申请人
+        pub requester: T::AccountId,
+        /// 认证类型
+        pub kind: VerificationKind,
+        /// 认证材料（pallet-evidence 私密内容 ID，须加密给认证员；分配后提交）
+        pub evidence_id: Option<u64>,
+        /// 随机分配的认证员（申请后的下一区块分配，分配前为 None）
+        pub verifier: Option<T::AccountId>,
+        /// 已锁定的认证费用
+        pub fee: BalanceOf<T>,
+        /// 申请时间
+        pub created_at: BlockNumberFor<T>,
+    }
+
+    /// 认证记录
+    #[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug)]
+    #[scale_info(skip_type_params(T))]
+    pub struct VerificationRecord<T: Config> {
+        /// 审核通过的认证员
+        pub verifier: T::AccountId,
+        /// 认证材料 ID
+        pub evidence_id: u64,
+        /// 认证时间
+        pub verified_at: BlockNumberFor<T>,
+        /// 到期时间
+        pub expires_at: BlockNumberFor<T>,
+    }
+
     // ========================================================================
     // 存储
     // ========================================================================
@@ -377,6 +503,30 @@ pub mod pallet {
         (),
     >;
 
+    /// 年龄索引（用于推荐候选集构建）
+    #[pallet::storage]
+    #[pallet::getter(fn age_index)]
+    pub type AgeIndex<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u8,
+        Blake2_128Concat,
+        T::AccountId,
+        (),
+    >;
+
+    /// 所在地索引（键为所在地名称的哈希，用于同城推荐）
+    #[pallet::storage]
+    #[pallet::getter(fn location_index)]
+    pub type LocationIndex<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,
+        Blake2_128Concat,
+        T::AccountId,
+        (),
+    >;
+
     /// 用户保证金记录
     #[pallet::storage]
     #[pallet::getter(fn deposits)]
@@ -429,6 +579,81 @@ pub mod pallet {
         BlockNumberFor<T>,  // 暂停结束时间
     >;
 
+    /// 已注册的认证员
+    #[pallet::storage]
+    #[pallet::getter(fn verifiers)]
+    pub type Verifiers<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        VerifierInfo<T>,
+    >;
+
+    /// 在职认证员列表（认证申请从中随机分配）
+    #[pallet::storage]
+    pub type ActiveVerifiers<T: Config> = StorageValue<
+        _,
+        BoundedVec<T::AccountId, T::MaxVerifiers>,
+        ValueQuery,
+    >;
+
+    /// 下一个认证申请 ID
+    #[pallet::storage]
+    pub type NextVerificationRequestId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// 待处理的认证申请
+    #[pallet::storage]
+    #[pallet::getter(fn verification_requests)]
+    pub type VerificationRequests<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        u64,
+        VerificationRequest<T>,
+    >;
+
+    /// 用户每种认证类型的待处理申请（每类最多一个）
+    #[pallet::storage]
+    #[pallet::getter(fn pending_verification)]
+    pub type PendingVerifications<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        VerificationKind,
+        u64,
+    >;
+
+    /// 等待分配认证员的申请
+    ///
+    /// 认证员在申请区块之后的 on_initialize 中抽取，
+    /// 随机数在提交申请时尚不可知，申请人无法挑选提交时机操控分配结果
+    #[pallet::storage]
+    pub type VerifierAssignmentQueue<T: Config> =
+        StorageValue<_, BoundedVec<u64, ConstU32<MAX_PENDING_ASSIGNMENTS>>, ValueQuery>;
+
+    /// 认证员待处理申请索引
+    #[pallet::storage]
+    pub type VerifierQueue<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        u64,
+        (),
+    >;
+
+    /// 用户认证记录
+    #[pallet::storage]
+    #[pallet::getter(fn verifications)]
+    pub type Verifications<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        VerificationKind,
+        VerificationRecord<T>,
+    >;
+
     // ========================================================================
     // 事件
     // ========================================================================
@@ -540,6 +765,90 @@ pub mod pallet {
             current: u128,
             required: u128,
         },
+        /// 认证员已注册
+        VerifierRegistered {
+            who: T::AccountId,
+            stake: u128,
+        },
+        /// 认证员已注销
+        VerifierUnregistered {
+            who: T::AccountId,
+        },
+        /// 认证员注册已获治理批准
+        VerifierApproved {
+            who: T::AccountId,
+        },
+        /// 认证员已申请注销，质押待其认证全部失效后退还
+        VerifierLeaving {
+            who: T::AccountId,
+            active_approvals: u32,
+        },
+        /// 认证材料已提交
+        VerificationEvidenceSubmitted {
+            request_id: u64,
+            evidence_id: u64,
+        },
+        /// 认证员质押已罚没
+        VerifierSlashed {
+            who: T::AccountId,
+            amount: u128,
+        },
+        /// 已提交认证申请（认证员于下一区块分配）
+        VerificationRequested {
+            request_id: u64,
+            who: T::AccountId,
+            kind: VerificationKind,
+        },
+        /// 认证已通过
+        VerificationApproved {
+            request_id: u64,
+            who: T::AccountId,
+            kind: VerificationKind,
+            verifier: T::AccountId,
+            expires_at: BlockNumberFor<T>,
+        },
+        /// 认证被拒绝
+        VerificationRejected {
+            request_id: u64,
+            who: T::AccountId,
+            kind: VerificationKind,
+            verifier: T::AccountId,
+        },
+        /// 认证申请已取消
+        VerificationCancelled {
+            request_id: u64,
+            who: T::AccountId,
+        },
+        /// 认证已撤销
+        VerificationRevoked {
+            who: T::AccountId,
+            kind: VerificationKind,
+        },
+        /// 认证已过期
+        VerificationExpired {
+            who: T::AccountId,
+            kind: VerificationKind,
+        },
+        /// 认证徽章已变更
+        BadgeChanged {
+            who: T::AccountId,
+            badge: VerificationBadge,
+        },
+        /// 月费自动续费已开启 / 关闭
+        AutoPaySet {
+            who: T::AccountId,
+            enabled: bool,
+        },
+        /// 月费自动续费已终止（扣费失败超出宽限期或用户撤销授权）
+        AutoPayLapsed {
+            who: T::AccountId,
+            reason: LapseReason,
+        },
+        /// 已为认证申请分配认证员
+        VerifierAssigned {
+            request_id: u64,
+            verifier: T::AccountId,
+        },
     }
 
     /// 罚没原因
@@ -599,6 +908,17 @@ pub mod pallet {
                 ViolationType::PermanentBan => 0, // 永久封禁
             }
         }
+
+        /// 对应的跨域信誉信号
+        pub fn reputation_signal(&self) -> ReputationSignal {
+            match self {
+                ViolationType::Minor => ReputationSignal::Violation(Severity::Minor),
+                ViolationType::Moderate => ReputationSignal::Violation(Severity::Moderate),
+                ViolationType::Severe => ReputationSignal::Violation(Severity::Severe),
+                ViolationType::Critical => ReputationSignal::Violation(Severity::Critical),
+                ViolationType::PermanentBan => ReputationSignal::Banned,
+            }
+        }
     }
 
     // ========================================================================
@@ -649,6 +969,57 @@ pub mod pallet {
         UserSuspended,
         /// 无权限操作
         NotAuthorized,
+        /// 已注册为认证员
+        VerifierAlreadyRegistered,
+        /// 不是认证员
+        NotVerifier,
+        /// 认证员质押不足
+        VerifierStakeInsufficient,
+        /// 认证员仍有待处理的申请
+        VerifierHasPendingRequests,
+        /// 不能为自己认证
+        CannotVerifySelf,
+        /// 该类型已有待处理的认证申请
+        VerificationAlreadyPending,
+        /// 认证申请不存在
+        VerificationRequestNotFound,
+        /// 认证材料不存在或不属于申请人
+        EvidenceNotOwned,
+        /// 认证材料未加密给认证员
+        EvidenceNotSharedWithVerifier,
+        /// 认证记录不存在
+        VerificationNotFound,
+        /// 认证尚未过期
+        VerificationNotExpired,
+        /// 无效的罚没比例
+        InvalidSlashBps,
+        /// 跨域综合风险过高
+        ReputationRiskTooHigh,
+        /// 认证员未获批准或已申请注销
+        VerifierNotActive,
+        /// 在职认证员数量已达上限
+        TooManyVerifiers,
+        /// 没有可分配的认证员
+        NoVerifierAvailable,
+        /// 认证材料已提交
+        EvidenceAlreadySubmitted,
+        /// 认证材料尚未提交
+        EvidenceNotSubmitted,
+        /// 尚未分配认证员
+        VerifierNotAssigned,
+        /// 等待分配认证员的申请已满
+        VerificationQueueFull,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// 为上一区块及之前提交的认证申请分配认证员
+        fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+            let processed = Self::assign_queued_verifiers();
+            T::DbWeight::get()
+                .reads_writes(1, 1)
+                .saturating_add(T::WeightInfo::assign_verifier().saturating_mul(processed as u64))
+        }
     }
 
     // ========================================================================
@@ -677,6 +1048,12 @@ pub mod pallet {
             // 检查资料是否已存在
             ensure!(!Profiles::<T>::contains_key(&who), Error::<T>::ProfileAlreadyExists);
 
+            // 在其他领域被封禁或综合风险过高的账户不得创建资料
+            ensure!(
+                T::Reputation::risk_level(&who) < RiskLevel::Blocked,
+                Error::<T>::ReputationRiskTooHigh
+            );
+
             // 验证昵称
             ensure!(!nickname.is_empty(), Error::<T>::NicknameEmpty);
 
@@ -742,13 +1119,19 @@ pub mod pallet {
                 last_active_at: current_block,
             };
 
+            // 更新推荐索引
+            GenderIndex::<T>::insert(gender, &who, ());
+            if let Some(age) = profile.age {
+                AgeIndex::<T>::insert(age, &who, ());
+            }
+            if let Some(ref location) = profile.current_location {
+                LocationIndex::<T>::insert(Self::location_key(location), &who, ());
+            }
+
             // 存储资料
             Profiles::<T>::insert(&who, profile);
             ProfileCount::<T>::mutate(|c| *c = c.saturating_add(1));
 
-            // 更新性别索引
-            GenderIndex::<T>::insert(gender, &who, ());
-
             Self::deposit_event(Event::ProfileCreated {
                 who: who.clone(),
                 nickname,
@@ -783,7 +1166,12 @@ pub mod pallet {
                     profile.nickname = new_nickname;
                 }
 
-                if current_location.is_some() {
+                if let Some(ref new_location) = current_location {
+                    // 同步所在地索引
+                    if let Some(ref old_location) = profile.current_location {
+                        LocationIndex::<T>::remove(Self::location_key(old_location), &who);
+                    }
+                    LocationIndex::<T>::insert(Self::location_key(new_location), &who, ());
                     profile.current_location = current_location;
                 }
 
@@ -948,8 +1336,27 @@ pub mod pallet {
                 }
             }
 
-            // 移除性别索引
+            // ========== 3. 清理认证，按取消处理待处理的认证申请 ==========
+            for kind in [VerificationKind::Liveness, VerificationKind::Identity] {
+                Verifications::<T>::remove(&who, kind);
+                if let Some(request_id) = PendingVerifications::<T>::get(&who, kind) {
+                    if let Some(request) = VerificationRequests::<T>::get(request_id) {
+                        Self::cancel_verification_request(request_id, &request)?;
+                    }
+                }
+            }
+
+            // 移除推荐索引
             GenderIndex::<T>::remove(profile.gender, &who);
+            if let Some(age) = profile.age {
+                AgeIndex::<T>::remove(age, &who);
+            }
+            if let Some(ref location) = profile.current_location {
+                LocationIndex::<T>::remove(Self::location_key(location), &who);
+            }
+
+            // 取消月费自动续费
+            T::RecurringPayments::cancel_all(&who, RecurringService::MatchmakingProfile);
 
             // 删除资料
             Profiles::<T>::remove(&who);
@@ -990,103 +1397,8 @@ pub mod pallet {
             let single_fee_u128: u128 = single_month_fee.try_into().unwrap_or(0u128);
             let total_fee_u128 = single_fee_u128.saturating_mul(months_u128);
             let total_fee: BalanceOf<T> = total_fee_u128.try_into().unwrap_or(single_month_fee);
-            
-            // 检查用户余额是否足够
-            let balance = T::Fungible::balance(&who);
-            ensure!(balance >= total_fee, Error::<T>::InsufficientBalance);
-            
-            // ========== 系统费用扣除（10%）==========
-            // 销毁：5%
-            let burn_amount = Self::calculate_percent(total_fee, 5);
-            // 国库：2%
-            let treasury_amount = Self::calculate_percent(total_fee, 2);
-            // 存储：3%
-            let storage_amount = Self::calculate_percent(total_fee, 3);
-            // 可分配：90%
-            let distributable = total_fee
-                .saturating_sub(burn_amount)
-                .saturating_sub(treasury_amount)
-                .saturating_sub(storage_amount);
-            
-            // 销毁
-            if !burn_amount.is_zero() {
-                let burn_account = T::BurnAccount::get();
-                T::Fungible::transfer(
-                    &who,
-                    &burn_account,
-                    burn_amount,
-                    frame_support::traits::tokens::Preservation::Preserve,
-                )?;
-            }
-            
-            // 国库
-            if !treasury_amount.is_zero() {
-                let treasury = T::TreasuryAccount::get();
-                T::Fungible::transfer(
-                    &who,
-                    &treasury,
-                    treasury_amount,
-                    frame_support::traits::tokens::Preservation::Preserve,
-                )?;
-            }
-            
-            // 存储
-            if !storage_amount.is_zero() {
-                let storage_account = T::StorageAccount::get();
-                T::Fungible::transfer(
-                    &who,
-                    &storage_account,
-                    storage_amount,
-                    frame_support::traits::tokens::Preservation::Preserve,
-                )?;
-            }
-            
-            // ========== 15层推荐链分配（90%）==========
-            // 使用联盟计酬分配器进行15层分配
-            let _ = T::AffiliateDistributor::distribute_rewards(
-                &who,
-                distributable,
-                None, // 无特定目标
-            );
-            
-            // 计算新的到期时间
-            let current_block = frame_system::Pallet::<T>::block_number();
-            // 假设每月约 432000 个区块（6秒/块，30天）
-            let blocks_per_month: BlockNumberFor<T> = 432000u32.into();
-            let extension_blocks = blocks_per_month.saturating_mul(months.into());
-            
-            let new_expiry = if let Some(current_expiry) = MembershipExpiry::<T>::get(&who) {
-                // 如果当前会员未过期，从当前到期时间延长
-                if current_expiry > current_block {
-                    current_expiry.saturating_add(extension_blocks)
-                } else {
-                    // 已过期，从当前时间开始计算
-                    current_block.saturating_add(extension_blocks)
-                }
-            } else {
-                // 首次购买，从当前时间开始
-                current_block.saturating_add(extension_blocks)
-            };
-            
-            // 更新会员到期时间
-            MembershipExpiry::<T>::insert(&who, new_expiry);
-            
-            // 更新最后活跃时间
-            Profiles::<T>::try_mutate(&who, |maybe_profile| -> DispatchResult {
-                if let Some(profile) = maybe_profile.as_mut() {
-                    profile.last_active_at = current_block;
-                }
-                Ok(())
-            })?;
 
-            Self::deposit_event(Event::MonthlyFeePaid {
-                who,
-                amount: total_fee.try_into().unwrap_or(0u128),
-                months,
-                expiry_block: new_expiry.try_into().unwrap_or(0u64),
-            });
-
-            Ok(())
+            Self::do_pay_monthly_fee(&who, total_fee, months)
         }
 
         /// 更新用户自填性格
@@ -1374,6 +1686,9 @@ pub mod pallet {
             let slash_bps = violation_type.slash_bps();
             let slashed_amount = Self::slash_deposit(&user, slash_bps, reason)?;
 
+            // 上报跨域信誉
+            T::Reputation::report(&user, ReputationDomain::Matchmaking, violation_type.reputation_signal());
+
             // 处理封禁
             if violation_type.should_ban() {
                 BannedUsers::<T>::insert(&user, current_block);
@@ -1406,6 +1721,8 @@ pub mod pallet {
                         }
                     });
 
+                    T::Reputation::report(&user, ReputationDomain::Matchmaking, ReputationSignal::Suspended);
+
                     Self::deposit_event(Event::UserSuspended {
                         who: user.clone(),
                         until_block,
@@ -1536,6 +1853,395 @@ pub mod pallet {
 
             Ok(())
         }
+
+        // ==================== 资料认证 ====================
+
+        /// 申请注册为认证员
+        ///
+        /// 锁定 `VerifierStake` 作为质押，须经治理 `approve_verifier` 批准后方可接收认证申请。
+        /// 认证结果被治理推翻时按比例罚没质押。
+        #[pallet::call_index(30)]
+        #[pallet::weight(T::WeightInfo::register_verifier())]
+        pub fn register_verifier(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(!Verifiers::<T>::contains_key(&who), Error::<T>::VerifierAlreadyRegistered);
+            ensure!(!BannedUsers::<T>::contains_key(&who), Error::<T>::UserBanned);
+
+            let stake = T::VerifierStake::get();
+            T::Fungible::hold(&HoldReason::VerifierStake.into(), &who, stake)
+                .map_err(|_| Error::<T>::InsufficientBalance)?;
+
+            Verifiers::<T>::insert(&who, VerifierInfo {
+                stake,
+                status: VerifierStatus::Pending,
+                pending_requests: 0,
+                approved_count: 0,
+                rejected_count: 0,
+                revoked_count: 0,
+                active_approvals: 0,
+                registered_at: frame_system::Pallet::<T>::block_number(),
+            });
+
+            Self::deposit_event(Event::VerifierRegistered {
+                who,
+                stake: stake.try_into().unwrap_or(0u128),
+            });
+
+            Ok(())
+        }
+
+        /// 注销认证员
+        ///
+        /// 需先处理完所有待处理的认证申请。注销后不再接收新申请；
+        /// 若其通过的认证仍有效，质押继续锁定，待全部过期或被撤销后自动退还。
+        #[pallet::call_index(31)]
+        #[pallet::weight(T::WeightInfo::unregister_verifier())]
+        pub fn unregister_verifier(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut info = Verifiers::<T>::get(&who).ok_or(Error::<T>::NotVerifier)?;
+            ensure!(info.status != VerifierStatus::Leaving, Error::<T>::VerifierNotActive);
+            ensure!(info.pending_requests == 0, Error::<T>::VerifierHasPendingRequests);
+
+            ActiveVerifiers::<T>::mutate(|list| list.retain(|v| v != &who));
+
+            if info.active_approvals == 0 {
+                Self::remove_verifier(&who, &info)?;
+            } else {
+                info.status = VerifierStatus::Leaving;
+                let active_approvals = info.active_approvals;
+                Verifiers::<T>::insert(&who, info);
+                Self::deposit_event(Event::VerifierLeaving { who, active_approvals });
+            }
+
+            Ok(())
+        }
+
+        /// 申请资料认证
+        ///
+        /// 申请时锁定 `VerificationFee`，认证员在下一区块从在职认证员中随机分配（不会分配给申请人本人），
+        /// 随机数在申请时尚不可知。分配后申请人通过 `pallet-evidence::store_private_content`
+        /// 上传加密给该认证员的材料，再调用 `submit_verification_evidence` 提交。
+        ///
+        /// # 参数
+        /// - `kind`: 认证类型（活体/证件）
+        #[pallet::call_index(32)]
+        #[pallet::weight(T::WeightInfo::request_verification()
+            .saturating_add(T::DbWeight::get().reads(T::MaxVerifiers::get() as u64)))]
+        pub fn request_verification(
+            origin: OriginFor<T>,
+            kind: VerificationKind,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(Profiles::<T>::contains_key(&who), Error::<T>::ProfileNotFound);
+            ensure!(!BannedUsers::<T>::contains_key(&who), Error::<T>::UserBanned);
+            ensure!(
+                !PendingVerifications::<T>::contains_key(&who, kind),
+                Error::<T>::VerificationAlreadyPending
+            );
+
+            ensure!(!Self::verifier_candidates(&who).is_empty(), Error::<T>::NoVerifierAvailable);
+
+            let request_id = NextVerificationRequestId::<T>::get();
+            VerifierAssignmentQueue::<T>::try_mutate(|queue| queue.try_push(request_id))
+                .map_err(|_| Error::<T>::VerificationQueueFull)?;
+
+            let fee = T::VerificationFee::get();
+            if !fee.is_zero() {
+                T::Fungible::hold(&HoldReason::VerificationFee.into(), &who, fee)
+                    .map_err(|_| Error::<T>::InsufficientBalance)?;
+            }
+
+            NextVerificationRequestId::<T>::put(request_id.saturating_add(1));
+
+            VerificationRequests::<T>::insert(request_id, VerificationRequest {
+                requester: who.clone(),
+                kind,
+                evidence_id: None,
+                verifier: None,
+                fee,
+                created_at: frame_system::Pallet::<T>::block_number(),
+            });
+            PendingVerifications::<T>::insert(&who, kind, request_id);
+
+            Self::deposit_event(Event::VerificationRequested {
+                request_id,
+                who,
+                kind,
+            });
+
+            Ok(())
+        }
+
+        /// 认证员审核通过
+        ///
+        /// 认证费用支付给认证员，认证在 `VerificationValidity` 个区块后过期。
+        /// 已有同类型认证时视为续期。
+        #[pallet::call_index(33)]
+        #[pallet::weight(T::WeightInfo::verify_profile())]
+        pub fn approve_verification(origin: OriginFor<T>, request_id: u64) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let request = Self::take_verification_request(request_id, &who)?;
+            let evidence_id = request.evidence_id.ok_or(Error::<T>::EvidenceNotSubmitted)?;
+            Self::pay_verification_fee(&request, &who)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let expires_at = now.saturating_add(T::VerificationValidity::get());
+            let previous = Verifications::<T>::get(&request.requester, request.kind);
+            Verifications::<T>::insert(&request.requester, request.kind, VerificationRecord {
+                verifier: who.clone(),
+                evidence_id,
+                verified_at: now,
+                expires_at,
+            });
+            Verifiers::<T>::mutate(&who, |maybe_info| {
+                if let Some(info) = maybe_info {
+                    info.approved_count = info.approved_count.saturating_add(1);
+                    info.active_approvals = info.active_approvals.saturating_add(1);
+                }
+            });
+            // 续期覆盖旧认证：旧认证不再由原认证员担保
+            if let Some(previous) = previous {
+                Self::release_verifier_approval(&previous.verifier)?;
+            }
+
+            Self::deposit_event(Event::VerificationApproved {
+                request_id,
+                who: request.requester.clone(),
+                kind: request.kind,
+                verifier: who,
+                expires_at,
+            });
+            Self::sync_verified_flag(&request.requester);
+
+            Ok(())
+        }
+
+        /// 认证员审核拒绝
+        ///
+        /// 已提交材料时认证费用同样支付给认证员；申请人未提交材料时按取消处理，
+        /// 认证员获得 `VerificationCancelPenaltyBps` 比例的认证费，其余退还。
+        #[pallet::call_index(34)]
+        #[pallet::weight(T::WeightInfo::reject_verification())]
+        pub fn reject_verification(origin: OriginFor<T>, request_id: u64) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let request = Self::take_verification_request(request_id, &who)?;
+            if request.evidence_id.is_some() {
+                Self::pay_verification_fee(&request, &who)?;
+            } else {
+                Self::forfeit_verification_fee(&request, &who)?;
+            }
+
+            Verifiers::<T>::mutate(&who, |maybe_info| {
+                if let Some(info) = maybe_info {
+                    info.rejected_count = info.rejected_count.saturating_add(1);
+                }
+            });
+
+            Self::deposit_event(Event::VerificationRejected {
+                request_id,
+                who: request.requester,
+                kind: request.kind,
+                verifier: who,
+            });
+
+            Ok(())
+        }
+
+        /// 申请人取消待处理的认证申请
+        ///
+        /// 尚未分配认证员时全额退还认证费用；已分配时认证员获得 `VerificationCancelPenaltyBps`
+        /// 比例的认证费，其余退还，申请人无法通过反复取消重新申请免费挑选认证员。
+        #[pallet::call_index(35)]
+        #[pallet::weight(T::WeightInfo::cancel_verification())]
+        pub fn cancel_verification(origin: OriginFor<T>, request_id: u64) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let request = VerificationRequests::<T>::get(request_id)
+                .ok_or(Error::<T>::VerificationRequestNotFound)?;
+            ensure!(request.requester == who, Error::<T>::NotAuthorized);
+
+            Self::cancel_verification_request(request_id, &request)?;
+
+            Self::deposit_event(Event::VerificationCancelled { request_id, who });
+
+            Ok(())
+        }
+
+        /// 撤销认证（治理权限或原认证员）
+        ///
+        /// 治理撤销时可按比例罚没原认证员的质押，转入国库（认证员已注销则只撤销不罚没）；
+        /// 认证员只能撤销自己通过的认证，且不涉及罚没。
+        ///
+        /// # 参数
+        /// - `user`: 被撤销认证的用户
+        /// - `kind`: 认证类型
+        /// - `slash_bps`: 认证员质押罚没比例（基点，10000 = 100%）
+        #[pallet::call_index(36)]
+        #[pallet::weight(T::WeightInfo::revoke_verification())]
+        pub fn revoke_verification(
+            origin: OriginFor<T>,
+            user: T::AccountId,
+            kind: VerificationKind,
+            slash_bps: u16,
+        ) -> DispatchResult {
+            let caller = ensure_signed(origin.clone()).ok();
+            let is_governance = T::GovernanceOrigin::ensure_origin(origin).is_ok();
+
+            ensure!(slash_bps <= 10000, Error::<T>::InvalidSlashBps);
+            let record = Verifications::<T>::get(&user, kind)
+                .ok_or(Error::<T>::VerificationNotFound)?;
+
+            if !is_governance {
+                ensure!(caller.as_ref() == Some(&record.verifier), Error::<T>::NotAuthorized);
+                ensure!(slash_bps == 0, Error::<T>::NotAuthorized);
+            }
+
+            Verifications::<T>::remove(&user, kind);
+
+            // 认证员已注销并取回质押时不再罚没
+            if is_governance && Verifiers::<T>::contains_key(&record.verifier) {
+                Verifiers::<T>::mutate(&record.verifier, |maybe_info| {
+                    if let Some(info) = maybe_info {
+                        info.revoked_count = info.revoked_count.saturating_add(1);
+                    }
+                });
+                if slash_bps > 0 {
+                    Self::slash_verifier_stake(&record.verifier, slash_bps)?;
+                }
+            }
+            Self::release_verifier_approval(&record.verifier)?;
+
+            Self::deposit_event(Event::VerificationRevoked { who: user.clone(), kind });
+            Self::sync_verified_flag(&user);
+
+            Ok(())
+        }
+
+        /// 清理已过期的认证（任何人可调用）
+        #[pallet::call_index(37)]
+        #[pallet::weight(T::WeightInfo::expire_verification())]
+        pub fn expire_verification(
+            origin: OriginFor<T>,
+            user: T::AccountId,
+            kind: VerificationKind,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let record = Verifications::<T>::get(&user, kind)
+                .ok_or(Error::<T>::VerificationNotFound)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(record.expires_at <= now, Error::<T>::VerificationNotExpired);
+
+            Verifications::<T>::remove(&user, kind);
+            Self::release_verifier_approval(&record.verifier)?;
+
+            Self::deposit_event(Event::VerificationExpired { who: user.clone(), kind });
+            Self::sync_verified_flag(&user);
+
+            Ok(())
+        }
+
+        /// 开启 / 关闭月费自动续费
+        ///
+        /// 开启后每月自动扣取 1 个月月费（按扣费时的 USDT 汇率换算），
+        /// 首期在当前会员到期时扣取。需先在 `pallet-recurring-payment`
+        /// 中为 `MatchmakingProfile` 设置扣费授权。
+        #[pallet::call_index(38)]
+        #[pallet::weight(T::WeightInfo::set_auto_pay())]
+        pub fn set_auto_pay(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(Profiles::<T>::contains_key(&who), Error::<T>::ProfileNotFound);
+
+            T::RecurringPayments::cancel_all(&who, RecurringService::MatchmakingProfile);
+            if enabled {
+                let now = frame_system::Pallet::<T>::block_number();
+                let first_due = MembershipExpiry::<T>::get(&who)
+                    .map(|expiry| expiry.max(now))
+                    .unwrap_or(now);
+                T::RecurringPayments::schedule(
+                    &who,
+                    RecurringService::MatchmakingProfile,
+                    0,
+                    PaymentPrice::UsdPegged {
+                        usd_amount: T::MonthlyFeeUsd::get(),
+                        fallback: T::MonthlyFee::get(),
+                    },
+                    BLOCKS_PER_MONTH.into(),
+                    first_due,
+                )?;
+            }
+
+            Self::deposit_event(Event::AutoPaySet { who, enabled });
+            Ok(())
+        }
+
+        /// 批准认证员注册（治理权限）
+        ///
+        /// 批准后加入在职认证员列表，开始参与认证申请的随机分配。
+        #[pallet::call_index(39)]
+        #[pallet::weight(T::WeightInfo::approve_verifier())]
+        pub fn approve_verifier(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+
+            let mut info = Verifiers::<T>::get(&who).ok_or(Error::<T>::NotVerifier)?;
+            ensure!(info.status == VerifierStatus::Pending, Error::<T>::VerifierNotActive);
+            ensure!(!BannedUsers::<T>::contains_key(&who), Error::<T>::UserBanned);
+
+            ActiveVerifiers::<T>::try_mutate(|list| list.try_push(who.clone()))
+                .map_err(|_| Error::<T>::TooManyVerifiers)?;
+            info.status = VerifierStatus::Active;
+            Verifiers::<T>::insert(&who, info);
+
+            Self::deposit_event(Event::VerifierApproved { who });
+
+            Ok(())
+        }
+
+        /// 提交认证材料
+        ///
+        /// 材料须由申请人通过 `pallet-evidence` 上传，并已加密给分配的认证员。
+        ///
+        /// # 参数
+        /// - `request_id`: 认证申请 ID
+        /// - `evidence_id`: 认证材料的私密内容 ID
+        #[pallet::call_index(40)]
+        #[pallet::weight(T::WeightInfo::submit_verification_evidence())]
+        pub fn submit_verification_evidence(
+            origin: OriginFor<T>,
+            request_id: u64,
+            evidence_id: u64,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            VerificationRequests::<T>::try_mutate(request_id, |maybe_request| -> DispatchResult {
+                let request = maybe_request.as_mut().ok_or(Error::<T>::VerificationRequestNotFound)?;
+                ensure!(request.requester == who, Error::<T>::NotAuthorized);
+                ensure!(request.evidence_id.is_none(), Error::<T>::EvidenceAlreadySubmitted);
+                let verifier = request.verifier.as_ref().ok_or(Error::<T>::VerifierNotAssigned)?;
+
+                // 认证材料必须由申请人上传，且已加密给分配的认证员
+                ensure!(
+                    T::EvidenceAccess::content_owner(evidence_id).as_ref() == Some(&who),
+                    Error::<T>::EvidenceNotOwned
+                );
+                ensure!(
+                    T::EvidenceAccess::get_decryption_key(evidence_id, verifier).is_some(),
+                    Error::<T>::EvidenceNotSharedWithVerifier
+                );
+
+                request.evidence_id = Some(evidence_id);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::VerificationEvidenceSubmitted { request_id, evidence_id });
+
+            Ok(())
+        }
     }
 }
 
@@ -1554,6 +2260,11 @@ impl<T: Config> Pallet<T> {
         u64::from_le_bytes([hash[0], hash[1], hash[2], hash[3], hash[4], hash[5], hash[6], hash[7]])
     }
 
+    /// 计算所在地索引键（所在地名称的哈希）
+    pub fn location_key(location: &[u8]) -> T::Hash {
+        T::Hashing::hash(location)
+    }
+
     /// 检查资料是否存在
     pub fn profile_exists(account: &T::AccountId) -> bool {
         Profiles::<T>::contains_key(account)
@@ -1566,7 +2277,279 @@ impl<T: Config> Pallet<T> {
 
     /// 检查用户是否已验证
     pub fn is_verified(account: &T::AccountId) -> bool {
-        Profiles::<T>::get(account).map(|p| p.verified).unwrap_or(false)
+        Self::badge_of(account).is_verified()
+    }
+
+    /// 获取用户当前有效的认证徽章
+    pub fn badge_of(account: &T::AccountId) -> VerificationBadge {
+        Self::badge_at(account, frame_system::Pallet::<T>::block_number())
+    }
+
+    /// 获取用户在指定区块有效的认证徽章
+    pub fn badge_at(account: &T::AccountId, at: BlockNumberFor<T>) -> VerificationBadge {
+        let valid = |kind: VerificationKind| {
+            Verifications::<T>::get(account, kind)
+                .map(|record| record.expires_at > at)
+                .unwrap_or(false)
+        };
+        VerificationBadge::from_kinds(valid(VerificationKind::Liveness), valid(VerificationKind::Identity))
+    }
+
+    /// 同步资料中的 `verified` 标记，徽章变化时发出事件
+    fn sync_verified_flag(account: &T::AccountId) {
+        let badge = Self::badge_of(account);
+        Profiles::<T>::mutate(account, |maybe_profile| {
+            if let Some(profile) = maybe_profile {
+                profile.verified = badge.is_verified();
+            }
+        });
+        Self::deposit_event(Event::BadgeChanged { who: account.clone(), badge });
+    }
+
+    /// 取出待处理的认证申请（仅限指定认证员处理）
+    fn take_verification_request(
+        request_id: u64,
+        verifier: &T::AccountId,
+    ) -> Result<VerificationRequest<T>, DispatchError> {
+        let request = VerificationRequests::<T>::get(request_id)
+            .ok_or(Error::<T>::VerificationRequestNotFound)?;
+        ensure!(request.verifier.as_ref() == Some(verifier), Error::<T>::NotAuthorized);
+
+        Self::close_verification_request(request_id, &request);
+        Ok(request)
+    }
+
+    /// 移除认证申请及其索引
+    fn close_verification_request(request_id: u64, request: &VerificationRequest<T>) {
+        VerificationRequests::<T>::remove(request_id);
+        PendingVerifications::<T>::remove(&request.requester, request.kind);
+        match &request.verifier {
+            Some(verifier) => {
+                VerifierQueue::<T>::remove(verifier, request_id);
+                Verifiers::<T>::mutate(verifier, |maybe_info| {
+                    if let Some(info) = maybe_info {
+                        info.pending_requests = info.pending_requests.saturating_sub(1);
+                    }
+                });
+            },
+            None => VerifierAssignmentQueue::<T>::mutate(|queue| queue.retain(|id| *id != request_id)),
+        }
+    }
+
+    /// 申请人取消认证申请（主动取消或删除资料）
+    ///
+    /// 尚未分配认证员时全额退还认证费用，已分配时认证员获得取消补偿。
+    fn cancel_verification_request(request_id: u64, request: &VerificationRequest<T>) -> DispatchResult {
+        Self::close_verification_request(request_id, request);
+        match &request.verifier {
+            Some(verifier) => Self::forfeit_verification_fee(request, verifier),
+            None => {
+                Self::release_verification_fee(request);
+                Ok(())
+            },
+        }
+    }
+
+    /// 将锁定的认证费用退还给申请人
+    fn release_verification_fee(request: &VerificationRequest<T>) {
+        if !request.fee.is_zero() {
+            let _ = T::Fungible::release(
+                &HoldReason::VerificationFee.into(),
+                &request.requester,
+                request.fee,
+                frame_support::traits::tokens::Precision::BestEffort,
+            );
+        }
+    }
+
+    /// 可分配的认证员（在职、质押充足，排除申请人本人）
+    fn verifier_candidates(requester: &T::AccountId) -> sp_std::vec::Vec<T::AccountId> {
+        let min_stake = T::VerifierStake::get();
+        ActiveVerifiers::<T>::get()
+            .into_iter()
+            .filter(|v| v != requester)
+            .filter(|v| Verifiers::<T>::get(v).map(|info| info.stake >= min_stake).unwrap_or(false))
+            .collect()
+    }
+
+    /// 从可分配的认证员中随机抽取一名
+    fn assign_verifier(requester: &T::AccountId, request_id: u64) -> Option<T::AccountId> {
+        use frame_support::traits::Randomness;
+
+        let candidates = Self::verifier_candidates(requester);
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let subject = (b"profile_verifier", request_id, requester).encode();
+        let (seed, _) = T::Randomness::random(&subject);
+        let mut bytes = [0u8; 8];
+        let raw = seed.as_ref();
+        let len = raw.len().min(8);
+        bytes[..len].copy_from_slice(&raw[..len]);
+        let index = (u64::from_le_bytes(bytes) % candidates.len() as u64) as usize;
+
+        Some(candidates[index].clone())
+    }
+
+    /// 为等待分配的认证申请抽取认证员（on_initialize 调用）
+    ///
+    /// 队列中的申请均在之前的区块提交，抽取使用的随机数在申请时尚不可知；
+    /// 暂无可分配认证员的申请留在队列中等待下一区块。返回处理的申请数。
+    fn assign_queued_verifiers() -> u32 {
+        let queue = VerifierAssignmentQueue::<T>::get();
+        if queue.is_empty() {
+            return 0;
+        }
+        let processed = queue.len() as u32;
+
+        let mut waiting: BoundedVec<u64, ConstU32<MAX_PENDING_ASSIGNMENTS>> = BoundedVec::new();
+        for request_id in queue {
+            let Some(mut request) = VerificationRequests::<T>::get(request_id) else {
+                continue;
+            };
+            let Some(verifier) = Self::assign_verifier(&request.requester, request_id) else {
+                let _ = waiting.try_push(request_id);
+                continue;
+            };
+
+            request.verifier = Some(verifier.clone());
+            VerificationRequests::<T>::insert(request_id, request);
+            VerifierQueue::<T>::insert(&verifier, request_id, ());
+            Verifiers::<T>::mutate(&verifier, |maybe_info| {
+                if let Some(info) = maybe_info {
+                    info.pending_requests = info.pending_requests.saturating_add(1);
+                }
+            });
+
+            Self::deposit_event(Event::VerifierAssigned { request_id, verifier });
+        }
+        VerifierAssignmentQueue::<T>::put(waiting);
+
+        processed
+    }
+
+    /// 认证员通过的一项认证失效（过期、撤销或被续期覆盖）
+    ///
+    /// 已申请注销的认证员在全部认证失效后退还质押。
+    fn release_verifier_approval(verifier: &T::AccountId) -> DispatchResult {
+        let Some(mut info) = Verifiers::<T>::get(verifier) else {
+            return Ok(());
+        };
+        info.active_approvals = info.active_approvals.saturating_sub(1);
+
+        if info.status == VerifierStatus::Leaving && info.active_approvals == 0 {
+            Self::remove_verifier(verifier, &info)
+        } else {
+            Verifiers::<T>::insert(verifier, info);
+            Ok(())
+        }
+    }
+
+    /// 退还认证员质押并移除认证员
+    fn remove_verifier(verifier: &T::AccountId, info: &VerifierInfo<T>) -> DispatchResult {
+        if !info.stake.is_zero() {
+            T::Fungible::release(
+                &HoldReason::VerifierStake.into(),
+                verifier,
+                info.stake,
+                frame_support::traits::tokens::Precision::BestEffort,
+            )?;
+        }
+        Verifiers::<T>::remove(verifier);
+
+        Self::deposit_event(Event::VerifierUnregistered { who: verifier.clone() });
+
+        Ok(())
+    }
+
+    /// 将锁定的认证费用支付给认证员
+    fn pay_verification_fee(request: &VerificationRequest<T>, verifier: &T::AccountId) -> DispatchResult {
+        if request.fee.is_zero() {
+            return Ok(());
+        }
+
+        let released = T::Fungible::release(
+            &HoldReason::VerificationFee.into(),
+            &request.requester,
+            request.fee,
+            frame_support::traits::tokens::Precision::BestEffort,
+        )?;
+        T::Fungible::transfer(
+            &request.requester,
+            verifier,
+            released,
+            frame_support::traits::tokens::Preservation::Expendable,
+        )?;
+
+        Ok(())
+    }
+
+    /// 释放锁定的认证费用，其中 `VerificationCancelPenaltyBps` 比例支付给认证员
+    fn forfeit_verification_fee(request: &VerificationRequest<T>, verifier: &T::AccountId) -> DispatchResult {
+        if request.fee.is_zero() {
+            return Ok(());
+        }
+
+        let released = T::Fungible::release(
+            &HoldReason::VerificationFee.into(),
+            &request.requester,
+            request.fee,
+            frame_support::traits::tokens::Precision::BestEffort,
+        )?;
+        let released_u128: u128 = released.try_into().unwrap_or(0u128);
+        let bps = T::VerificationCancelPenaltyBps::get().min(10000) as u128;
+        let penalty: BalanceOf<T> = (released_u128.saturating_mul(bps) / 10000u128)
+            .try_into()
+            .unwrap_or(released);
+        if !penalty.is_zero() {
+            T::Fungible::transfer(
+                &request.requester,
+                verifier,
+                penalty.min(released),
+                frame_support::traits::tokens::Preservation::Expendable,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// 按比例罚没认证员质押并转入国库
+    fn slash_verifier_stake(verifier: &T::AccountId, slash_bps: u16) -> Result<BalanceOf<T>, DispatchError> {
+        let info = Verifiers::<T>::get(verifier).ok_or(Error::<T>::NotVerifier)?;
+
+        let stake_u128: u128 = info.stake.try_into().unwrap_or(0u128);
+        let slash_u128 = stake_u128.saturating_mul(slash_bps as u128) / 10000u128;
+        let slash_amount: BalanceOf<T> = slash_u128.try_into().unwrap_or(info.stake);
+        let actual_slash = slash_amount.min(info.stake);
+
+        if !actual_slash.is_zero() {
+            T::Fungible::release(
+                &HoldReason::VerifierStake.into(),
+                verifier,
+                actual_slash,
+                frame_support::traits::tokens::Precision::BestEffort,
+            )?;
+            T::Fungible::transfer(
+                verifier,
+                &T::TreasuryAccount::get(),
+                actual_slash,
+                frame_support::traits::tokens::Preservation::Expendable,
+            )?;
+
+            Verifiers::<T>::mutate(verifier, |maybe_info| {
+                if let Some(info) = maybe_info {
+                    info.stake = info.stake.saturating_sub(actual_slash);
+                }
+            });
+
+            Self::deposit_event(Event::VerifierSlashed {
+                who: verifier.clone(),
+                amount: actual_slash.try_into().unwrap_or(0u128),
+            });
+        }
+
+        Ok(actual_slash)
     }
 
     /// 计算月费金额（2 USDT 等值的 DUST）
@@ -1604,6 +2587,112 @@ impl<T: Config> Pallet<T> {
         MembershipExpiry::<T>::get(account)
     }
 
+    /// 扣取月费并延长会员期
+    ///
+    /// 费用分配见 `pay_monthly_fee`；自动续费扣费同样走此路径。
+    fn do_pay_monthly_fee(
+        who: &T::AccountId,
+        total_fee: BalanceOf<T>,
+        months: u32,
+    ) -> DispatchResult {
+        // 检查用户余额是否足够
+        let balance = T::Fungible::balance(who);
+        ensure!(balance >= total_fee, Error::<T>::InsufficientBalance);
+        
+        // ========== 系统费用扣除（10%）==========
+        // 销毁：5%
+        let burn_amount = Self::calculate_percent(total_fee, 5);
+        // 国库：2%
+        let treasury_amount = Self::calculate_percent(total_fee, 2);
+        // 存储：3%
+        let storage_amount = Self::calculate_percent(total_fee, 3);
+        // 可分配：90%
+        let distributable = total_fee
+            .saturating_sub(burn_amount)
+            .saturating_sub(treasury_amount)
+            .saturating_sub(storage_amount);
+        
+        // 销毁
+        if !burn_amount.is_zero() {
+            let burn_account = T::BurnAccount::get();
+            T::Fungible::transfer(
+                who,
+                &burn_account,
+                burn_amount,
+                frame_support::traits::tokens::Preservation::Preserve,
+            )?;
+        }
+        
+        // 国库
+        if !treasury_amount.is_zero() {
+            let treasury = T::TreasuryAccount::get();
+            T::Fungible::transfer(
+                who,
+                &treasury,
+                treasury_amount,
+                frame_support::traits::tokens::Preservation::Preserve,
+            )?;
+        }
+        
+        // 存储
+        if !storage_amount.is_zero() {
+            let storage_account = T::StorageAccount::get();
+            T::Fungible::transfer(
+                who,
+                &storage_account,
+                storage_amount,
+                frame_support::traits::tokens::Preservation::Preserve,
+            )?;
+        }
+        
+        // ========== 15层推荐链分配（90%）==========
+        // 使用联盟计酬分配器进行15层分配
+        let _ = T::AffiliateDistributor::distribute_rewards(
+            who,
+            distributable,
+            None, // 无特定目标
+        );
+        
+        // 计算新的到期时间
+        let current_block = frame_system::Pallet::<T>::block_number();
+        // 假设每月约 432000 个区块（6秒/块，30天）
+        let blocks_per_month: BlockNumberFor<T> = BLOCKS_PER_MONTH.into();
+        let extension_blocks = blocks_per_month.saturating_mul(months.into());
+        
+        let new_expiry = if let Some(current_expiry) = MembershipExpiry::<T>::get(who) {
+            // 如果当前会员未过期，从当前到期时间延长
+            if current_expiry > current_block {
+                current_expiry.saturating_add(extension_blocks)
+            } else {
+                // 已过期，从当前时间开始计算
+                current_block.saturating_add(extension_blocks)
+            }
+        } else {
+            // 首次购买，从当前时间开始
+            current_block.saturating_add(extension_blocks)
+        };
+        
+        // 更新会员到期时间
+        MembershipExpiry::<T>::insert(who, new_expiry);
+        
+        // 更新最后活跃时间
+        Profiles::<T>::try_mutate(who, |maybe_profile| -> DispatchResult {
+            if let Some(profile) = maybe_profile.as_mut() {
+                profile.last_active_at = current_block;
+            }
+            Ok(())
+        })?;
+
+        Self::deposit_event(Event::MonthlyFeePaid {
+            who: who.clone(),
+            amount: total_fee.try_into().unwrap_or(0u128),
+            months,
+            expiry_block: new_expiry.try_into().unwrap_or(0u64),
+        });
+
+        Ok(())
+    }
+
     /// 计算百分比
     fn calculate_percent(total: BalanceOf<T>, percent: u8) -> BalanceOf<T> {
         if percent == 0 || percent > 100 {
@@ -1709,4 +2798,35 @@ impl<T: Config> Pallet<T> {
     }
 }
 
+impl<T: Config> VerificationBadgeProvider<T::AccountId> for Pallet<T> {
+    fn badge_of(account: &T::AccountId) -> VerificationBadge {
+        Self::badge_of(account)
+    }
+}
+
 // WeightInfo trait 和实现已移至 weights.rs
+
+// ============================================================================
+// 周期扣费回调
+// ============================================================================
+
+impl<T: Config> RecurringPaymentHandler<T::AccountId, BalanceOf<T>> for Pallet<T> {
+    fn on_payment_due(
+        _service: RecurringService,
+        payer: &T::AccountId,
+        _subject: u64,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        ensure!(Profiles::<T>::contains_key(payer), Error::<T>::ProfileNotFound);
+        Self::do_pay_monthly_fee(payer, amount, 1)
+    }
+
+    fn on_payment_lapsed(
+        _service: RecurringService,
+        payer: &T::AccountId,
+        _subject: u64,
+        reason: LapseReason,
+    ) {
+        Self::deposit_event(Event::AutoPayLapsed { who: payer.clone(), reason });
+    }
+}
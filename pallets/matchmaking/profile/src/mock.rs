@@ -0,0 +1,228 @@
+//! # 用户资料模块 Mock 运行时
+
+use crate as pallet_matchmaking_profile;
+use codec::Encode;
+use frame_support::{
+    derive_impl, parameter_types,
+    traits::{ConstU128, ConstU16, ConstU32, ConstU64, Randomness},
+};
+use frame_system::EnsureRoot;
+use pallet_recurring_payment::{
+    traits::RecurringPayments,
+    types::{PaymentPrice, RecurringService},
+};
+use sp_core::H256;
+use sp_runtime::{BuildStorage, DispatchError, DispatchResult};
+
+pub type Balance = u128;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+/// 资料保证金（无报价时的兜底金额）
+pub const PROFILE_DEPOSIT: Balance = 50;
+/// 认证员质押
+pub const VERIFIER_STAKE: Balance = 100;
+/// 单次认证费用
+pub const VERIFICATION_FEE: Balance = 10;
+/// 分配后取消时认证员获得的认证费比例（基点）
+pub const CANCEL_PENALTY_BPS: u16 = 3_000;
+/// 认证有效期
+pub const VERIFICATION_VALIDITY: u64 = 1_000;
+/// 国库账户
+pub const TREASURY: u64 = 200;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Balances: pallet_balances,
+        Profile: pallet_matchmaking_profile,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+    type Balance = Balance;
+    type ExistentialDeposit = ConstU128<1>;
+    type RuntimeHoldReason = RuntimeHoldReason;
+}
+
+parameter_types! {
+    pub const TreasuryAccount: u64 = TREASURY;
+    pub const BurnAccount: u64 = 201;
+    pub const StorageAccount: u64 = 202;
+    /// 私密内容：(内容 ID, 上传者, 可解密的账户)
+    pub static PrivateContents: Vec<(u64, u64, Vec<u64>)> = vec![];
+    /// 月费扣费计划：(付款人, 首期扣费区块)
+    pub static ScheduledPayments: Vec<(u64, u64)> = vec![];
+}
+
+/// 登记私密内容（模拟 `pallet-evidence::store_private_content`）
+pub fn add_private_content(content_id: u64, owner: u64, shared_with: Vec<u64>) {
+    PrivateContents::mutate(|contents| contents.push((content_id, owner, shared_with)));
+}
+
+/// 模拟随机数：随主题与区块号变化
+pub struct TestRandomness;
+
+impl Randomness<H256, u64> for TestRandomness {
+    fn random(subject: &[u8]) -> (H256, u64) {
+        let block_number = System::block_number();
+        (sp_io::hashing::blake2_256(&(subject, block_number).encode()).into(), block_number)
+    }
+}
+
+/// 模拟周期扣费：记录扣费计划
+pub struct MockRecurringPayments;
+
+impl RecurringPayments<u64, Balance, u64> for MockRecurringPayments {
+    fn schedule(
+        payer: &u64,
+        _service: RecurringService,
+        _subject: u64,
+        _price: PaymentPrice<Balance>,
+        _period: u64,
+        first_due: u64,
+    ) -> Result<u64, DispatchError> {
+        ScheduledPayments::mutate(|plans| plans.push((*payer, first_due)));
+        Ok(0)
+    }
+
+    fn cancel(payer: &u64, service: RecurringService, _subject: u64) -> bool {
+        Self::cancel_all(payer, service) > 0
+    }
+
+    fn cancel_all(payer: &u64, _service: RecurringService) -> u32 {
+        let before = ScheduledPayments::get().len();
+        ScheduledPayments::mutate(|plans| plans.retain(|(p, _)| p != payer));
+        (before - ScheduledPayments::get().len()) as u32
+    }
+
+    fn is_scheduled(payer: &u64, _service: RecurringService, _subject: u64) -> bool {
+        ScheduledPayments::get().iter().any(|(p, _)| p == payer)
+    }
+}
+
+/// 模拟 IPFS 固定：始终成功
+pub struct MockIpfsPinner;
+
+impl pallet_storage_service::IpfsPinner<u64, Balance> for MockIpfsPinner {
+    fn pin_cid_for_subject(
+        _caller: u64,
+        _subject_type: pallet_storage_service::types::SubjectType,
+        _subject_id: u64,
+        _cid: Vec<u8>,
+        _tier: Option<pallet_storage_service::types::PinTier>,
+    ) -> DispatchResult {
+        Ok(())
+    }
+
+    fn unpin_cid(_caller: u64, _cid: Vec<u8>) -> DispatchResult {
+        Ok(())
+    }
+}
+
+/// 模拟联盟分配：全额分配
+pub struct MockAffiliateDistributor;
+
+impl pallet_affiliate::types::AffiliateDistributor<u64, Balance, u64> for MockAffiliateDistributor {
+    fn distribute_rewards(
+        _buyer: &u64,
+        amount: Balance,
+        _target: Option<(u8, u64)>,
+    ) -> Result<Balance, DispatchError> {
+        Ok(amount)
+    }
+}
+
+/// 模拟认证材料：读取登记的私密内容
+pub struct MockEvidenceAccess;
+
+impl pallet_evidence::PrivateContentProvider<u64> for MockEvidenceAccess {
+    fn can_access(content_id: u64, user: &u64) -> bool {
+        PrivateContents::get()
+            .iter()
+            .any(|(id, owner, shared)| *id == content_id && (owner == user || shared.contains(user)))
+    }
+
+    fn get_decryption_key(content_id: u64, user: &u64) -> Option<Vec<u8>> {
+        PrivateContents::get()
+            .iter()
+            .any(|(id, _, shared)| *id == content_id && shared.contains(user))
+            .then(|| b"key".to_vec())
+    }
+
+    fn content_owner(content_id: u64) -> Option<u64> {
+        PrivateContents::get().into_iter().find(|(id, _, _)| *id == content_id).map(|(_, owner, _)| owner)
+    }
+}
+
+impl pallet_matchmaking_profile::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxNicknameLen = ConstU32<32>;
+    type MaxLocationLen = ConstU32<64>;
+    type MaxCidLen = ConstU32<64>;
+    type MaxBioLen = ConstU32<256>;
+    type MaxDescLen = ConstU32<256>;
+    type MaxOccupationLen = ConstU32<32>;
+    type MaxTraits = ConstU32<5>;
+    type MaxHobbies = ConstU32<5>;
+    type MaxHobbyLen = ConstU32<16>;
+    type WeightInfo = ();
+    type Fungible = Balances;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type ProfileDeposit = ConstU128<PROFILE_DEPOSIT>;
+    type ProfileDepositUsd = ConstU64<50_000_000>;
+    type MonthlyFee = ConstU128<2>;
+    type MonthlyFeeUsd = ConstU64<2_000_000>;
+    type Pricing = ();
+    type RecurringPayments = MockRecurringPayments;
+    type Reputation = ();
+    type TreasuryAccount = TreasuryAccount;
+    type BurnAccount = BurnAccount;
+    type StorageAccount = StorageAccount;
+    type AffiliateDistributor = MockAffiliateDistributor;
+    type IpfsPinner = MockIpfsPinner;
+    type GovernanceOrigin = EnsureRoot<u64>;
+    type BlocksPerDay = ConstU64<14_400>;
+    type EvidenceAccess = MockEvidenceAccess;
+    type VerifierStake = ConstU128<VERIFIER_STAKE>;
+    type VerificationFee = ConstU128<VERIFICATION_FEE>;
+    type VerificationCancelPenaltyBps = ConstU16<CANCEL_PENALTY_BPS>;
+    type VerificationValidity = ConstU64<VERIFICATION_VALIDITY>;
+    type MaxVerifiers = ConstU32<3>;
+    type Randomness = TestRandomness;
+    type Balance = Balance;
+}
+
+/// 推进到下一区块并执行 `on_initialize`（分配认证员）
+pub fn next_block() {
+    use frame_support::traits::Hooks;
+
+    let next = System::block_number() + 1;
+    System::set_block_number(next);
+    Profile::on_initialize(next);
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    PrivateContents::set(vec![]);
+    ScheduledPayments::set(vec![]);
+
+    let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: (1..=10).map(|account| (account, 1_000)).collect(),
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
@@ -1,52 +1,84 @@
 //! # 用户资料模块测试
 
-use crate::{mock::*, Error, Event};
+use crate::{mock::*, ActiveVerifiers, Error, Event, VerifierAssignmentQueue, VerifierQueue, VerifierStatus};
 use frame_support::{assert_noop, assert_ok};
-use pallet_matchmaking_common::{Gender, ProfilePrivacyMode};
+use pallet_matchmaking_common::{Gender, ProfilePrivacyMode, VerificationBadge, VerificationKind};
+use sp_runtime::DispatchError;
+
+/// 创建资料
+fn create(who: u64) {
+    assert_ok!(Profile::create_profile(
+        RuntimeOrigin::signed(who),
+        b"Alice".to_vec().try_into().unwrap(),
+        Gender::Female,
+        None,
+        None,
+        None,
+        None,
+    ));
+}
+
+/// 注册并批准认证员
+fn add_verifier(who: u64) {
+    assert_ok!(Profile::register_verifier(RuntimeOrigin::signed(who)));
+    assert_ok!(Profile::approve_verifier(RuntimeOrigin::root(), who));
+}
+
+/// 申请认证并推进一个区块完成分配，返回 (申请 ID, 分配的认证员)
+fn request_assigned(who: u64, kind: VerificationKind) -> (u64, u64) {
+    let request_id = crate::NextVerificationRequestId::<Test>::get();
+    assert_ok!(Profile::request_verification(RuntimeOrigin::signed(who), kind));
+    next_block();
+    let verifier = Profile::verification_requests(request_id).unwrap().verifier.unwrap();
+    (request_id, verifier)
+}
+
+/// 上传加密给认证员的材料并提交
+fn submit_evidence(who: u64, request_id: u64, verifier: u64, evidence_id: u64) {
+    add_private_content(evidence_id, who, vec![verifier]);
+    assert_ok!(Profile::submit_verification_evidence(RuntimeOrigin::signed(who), request_id, evidence_id));
+}
+
+/// 完成一次认证，返回认证员
+fn verify(who: u64, kind: VerificationKind, evidence_id: u64) -> u64 {
+    let (request_id, verifier) = request_assigned(who, kind);
+    submit_evidence(who, request_id, verifier, evidence_id);
+    assert_ok!(Profile::approve_verification(RuntimeOrigin::signed(verifier), request_id));
+    verifier
+}
+
+// ============================================================================
+// 资料管理
+// ============================================================================
 
 #[test]
 fn create_profile_works() {
     new_test_ext().execute_with(|| {
-        let nickname = b"Alice".to_vec().try_into().unwrap();
-        
-        assert_ok!(Profile::create_profile(
-            RuntimeOrigin::signed(1),
-            nickname,
-            Gender::Female,
-            None,
-            None,
-            None,
-        ));
+        create(1);
 
-        // 验证资料已创建
+        // 验证资料已创建，保证金已锁定
         assert!(Profile::profiles(1).is_some());
         assert_eq!(Profile::profile_count(), 1);
+        assert_eq!(Profile::deposits(1), Some(PROFILE_DEPOSIT));
+        assert_eq!(Balances::free_balance(1), 1_000 - PROFILE_DEPOSIT);
     });
 }
 
 #[test]
 fn cannot_create_duplicate_profile() {
     new_test_ext().execute_with(|| {
-        let nickname = b"Alice".to_vec().try_into().unwrap();
-        
-        assert_ok!(Profile::create_profile(
-            RuntimeOrigin::signed(1),
-            nickname.clone(),
-            Gender::Female,
-            None,
-            None,
-            None,
-        ));
+        create(1);
 
         // 不能重复创建
         assert_noop!(
             Profile::create_profile(
                 RuntimeOrigin::signed(1),
-                nickname,
+                b"Alice".to_vec().try_into().unwrap(),
                 Gender::Female,
                 None,
                 None,
                 None,
+                None,
             ),
             Error::<Test>::ProfileAlreadyExists
         );
@@ -56,16 +88,7 @@ fn cannot_create_duplicate_profile() {
 #[test]
 fn update_profile_works() {
     new_test_ext().execute_with(|| {
-        let nickname = b"Alice".to_vec().try_into().unwrap();
-        
-        assert_ok!(Profile::create_profile(
-            RuntimeOrigin::signed(1),
-            nickname,
-            Gender::Female,
-            None,
-            None,
-            None,
-        ));
+        create(1);
 
         let new_nickname = b"Alice2".to_vec().try_into().unwrap();
         assert_ok!(Profile::update_profile(
@@ -84,66 +107,37 @@ fn update_profile_works() {
 #[test]
 fn update_preferences_works() {
     new_test_ext().execute_with(|| {
-        let nickname = b"Alice".to_vec().try_into().unwrap();
-        
-        assert_ok!(Profile::create_profile(
-            RuntimeOrigin::signed(1),
-            nickname,
-            Gender::Female,
-            None,
-            None,
-            None,
-        ));
+        create(1);
 
         assert_ok!(Profile::update_preferences(
             RuntimeOrigin::signed(1),
-            (25, 35),
+            Some((25, 35)),
+            None,
+            None,
             None,
             None,
             None,
         ));
 
         let profile = Profile::profiles(1).unwrap();
-        assert!(profile.partner_preferences.is_some());
-        assert_eq!(profile.partner_preferences.unwrap().age_range, (25, 35));
+        assert_eq!(profile.partner_preferences.unwrap().age_range, Some((25, 35)));
     });
 }
 
 #[test]
 fn invalid_age_range_fails() {
     new_test_ext().execute_with(|| {
-        let nickname = b"Alice".to_vec().try_into().unwrap();
-        
-        assert_ok!(Profile::create_profile(
-            RuntimeOrigin::signed(1),
-            nickname,
-            Gender::Female,
-            None,
-            None,
-            None,
-        ));
+        create(1);
 
         // 最小年龄大于最大年龄
         assert_noop!(
-            Profile::update_preferences(
-                RuntimeOrigin::signed(1),
-                (35, 25),
-                None,
-                None,
-                None,
-            ),
+            Profile::update_preferences(RuntimeOrigin::signed(1), Some((35, 25)), None, None, None, None, None),
             Error::<Test>::InvalidAgeRange
         );
 
         // 年龄小于18
         assert_noop!(
-            Profile::update_preferences(
-                RuntimeOrigin::signed(1),
-                (16, 25),
-                None,
-                None,
-                None,
-            ),
+            Profile::update_preferences(RuntimeOrigin::signed(1), Some((16, 25)), None, None, None, None, None),
             Error::<Test>::InvalidAgeRange
         );
     });
@@ -152,16 +146,7 @@ fn invalid_age_range_fails() {
 #[test]
 fn link_bazi_works() {
     new_test_ext().execute_with(|| {
-        let nickname = b"Alice".to_vec().try_into().unwrap();
-        
-        assert_ok!(Profile::create_profile(
-            RuntimeOrigin::signed(1),
-            nickname,
-            Gender::Female,
-            None,
-            None,
-            None,
-        ));
+        create(1);
 
         assert_ok!(Profile::link_bazi(RuntimeOrigin::signed(1), 123));
 
@@ -173,16 +158,7 @@ fn link_bazi_works() {
 #[test]
 fn update_privacy_mode_works() {
     new_test_ext().execute_with(|| {
-        let nickname = b"Alice".to_vec().try_into().unwrap();
-        
-        assert_ok!(Profile::create_profile(
-            RuntimeOrigin::signed(1),
-            nickname,
-            Gender::Female,
-            None,
-            None,
-            None,
-        ));
+        create(1);
 
         assert_ok!(Profile::update_privacy_mode(
             RuntimeOrigin::signed(1),
@@ -197,22 +173,464 @@ fn update_privacy_mode_works() {
 #[test]
 fn delete_profile_works() {
     new_test_ext().execute_with(|| {
-        let nickname = b"Alice".to_vec().try_into().unwrap();
-        
-        assert_ok!(Profile::create_profile(
-            RuntimeOrigin::signed(1),
-            nickname,
-            Gender::Female,
-            None,
-            None,
-            None,
-        ));
-
+        create(1);
         assert_eq!(Profile::profile_count(), 1);
 
         assert_ok!(Profile::delete_profile(RuntimeOrigin::signed(1)));
 
         assert!(Profile::profiles(1).is_none());
         assert_eq!(Profile::profile_count(), 0);
+        assert_eq!(Balances::free_balance(1), 1_000);
+    });
+}
+
+// ============================================================================
+// 认证员
+// ============================================================================
+
+#[test]
+fn register_and_approve_verifier() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Profile::register_verifier(RuntimeOrigin::signed(2)));
+        assert_eq!(Balances::free_balance(2), 1_000 - VERIFIER_STAKE);
+        assert_eq!(Profile::verifiers(2).unwrap().status, VerifierStatus::Pending);
+        assert_noop!(
+            Profile::register_verifier(RuntimeOrigin::signed(2)),
+            Error::<Test>::VerifierAlreadyRegistered
+        );
+
+        // 仅治理可批准，批准后进入在职列表
+        assert_noop!(Profile::approve_verifier(RuntimeOrigin::signed(2), 2), DispatchError::BadOrigin);
+        assert_noop!(Profile::approve_verifier(RuntimeOrigin::root(), 3), Error::<Test>::NotVerifier);
+        assert_ok!(Profile::approve_verifier(RuntimeOrigin::root(), 2));
+        assert_eq!(Profile::verifiers(2).unwrap().status, VerifierStatus::Active);
+        assert_eq!(ActiveVerifiers::<Test>::get().to_vec(), vec![2]);
+        System::assert_last_event(Event::<Test>::VerifierApproved { who: 2 }.into());
+        assert_noop!(Profile::approve_verifier(RuntimeOrigin::root(), 2), Error::<Test>::VerifierNotActive);
+
+        // 在职认证员数量受 MaxVerifiers 限制
+        add_verifier(3);
+        add_verifier(4);
+        assert_ok!(Profile::register_verifier(RuntimeOrigin::signed(5)));
+        assert_noop!(Profile::approve_verifier(RuntimeOrigin::root(), 5), Error::<Test>::TooManyVerifiers);
+    });
+}
+
+#[test]
+fn unregister_verifier_keeps_stake_until_approvals_end() {
+    new_test_ext().execute_with(|| {
+        create(1);
+        add_verifier(2);
+
+        let request_id = crate::NextVerificationRequestId::<Test>::get();
+        assert_ok!(Profile::request_verification(RuntimeOrigin::signed(1), VerificationKind::Liveness));
+        next_block();
+        assert_noop!(
+            Profile::unregister_verifier(RuntimeOrigin::signed(2)),
+            Error::<Test>::VerifierHasPendingRequests
+        );
+
+        submit_evidence(1, request_id, 2, 7);
+        assert_ok!(Profile::approve_verification(RuntimeOrigin::signed(2), request_id));
+
+        // 仍有有效认证：退出在职列表，质押继续锁定
+        assert_ok!(Profile::unregister_verifier(RuntimeOrigin::signed(2)));
+        assert_eq!(Profile::verifiers(2).unwrap().status, VerifierStatus::Leaving);
+        assert!(ActiveVerifiers::<Test>::get().is_empty());
+        assert_eq!(Balances::free_balance(2), 1_000 - VERIFIER_STAKE + VERIFICATION_FEE);
+        System::assert_last_event(Event::<Test>::VerifierLeaving { who: 2, active_approvals: 1 }.into());
+
+        // 认证过期后退还质押
+        System::set_block_number(System::block_number() + VERIFICATION_VALIDITY);
+        assert_ok!(Profile::expire_verification(RuntimeOrigin::signed(3), 1, VerificationKind::Liveness));
+        assert!(Profile::verifiers(2).is_none());
+        assert_eq!(Balances::free_balance(2), 1_000 + VERIFICATION_FEE);
+        System::assert_has_event(Event::<Test>::VerifierUnregistered { who: 2 }.into());
+    });
+}
+
+// ============================================================================
+// 认证申请与分配
+// ============================================================================
+
+#[test]
+fn request_verification_checks_preconditions() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Profile::request_verification(RuntimeOrigin::signed(1), VerificationKind::Liveness),
+            Error::<Test>::ProfileNotFound
+        );
+
+        // 唯一的认证员是申请人本人
+        create(1);
+        add_verifier(1);
+        assert_noop!(
+            Profile::request_verification(RuntimeOrigin::signed(1), VerificationKind::Liveness),
+            Error::<Test>::NoVerifierAvailable
+        );
+
+        add_verifier(2);
+        assert_ok!(Profile::request_verification(RuntimeOrigin::signed(1), VerificationKind::Liveness));
+        assert_noop!(
+            Profile::request_verification(RuntimeOrigin::signed(1), VerificationKind::Liveness),
+            Error::<Test>::VerificationAlreadyPending
+        );
+    });
+}
+
+#[test]
+fn verifier_is_assigned_in_next_block() {
+    new_test_ext().execute_with(|| {
+        create(1);
+        add_verifier(1);
+        add_verifier(2);
+
+        assert_ok!(Profile::request_verification(RuntimeOrigin::signed(1), VerificationKind::Liveness));
+        System::assert_last_event(
+            Event::<Test>::VerificationRequested { request_id: 0, who: 1, kind: VerificationKind::Liveness }.into(),
+        );
+
+        // 申请区块内尚未分配，认证费已锁定
+        let request = Profile::verification_requests(0).unwrap();
+        assert_eq!(request.verifier, None);
+        assert_eq!(VerifierAssignmentQueue::<Test>::get().to_vec(), vec![0]);
+        assert_eq!(Profile::pending_verification(1, VerificationKind::Liveness), Some(0));
+        assert_eq!(Balances::free_balance(1), 1_000 - PROFILE_DEPOSIT - VERIFIER_STAKE - VERIFICATION_FEE);
+        add_private_content(7, 1, vec![2]);
+        assert_noop!(
+            Profile::submit_verification_evidence(RuntimeOrigin::signed(1), 0, 7),
+            Error::<Test>::VerifierNotAssigned
+        );
+
+        // 下一区块分配，不会分配给申请人本人
+        next_block();
+        let request = Profile::verification_requests(0).unwrap();
+        assert_eq!(request.verifier, Some(2));
+        assert!(VerifierAssignmentQueue::<Test>::get().is_empty());
+        assert!(VerifierQueue::<Test>::contains_key(2, 0));
+        assert_eq!(Profile::verifiers(2).unwrap().pending_requests, 1);
+        System::assert_last_event(Event::<Test>::VerifierAssigned { request_id: 0, verifier: 2 }.into());
+    });
+}
+
+#[test]
+fn assignment_waits_for_available_verifier() {
+    new_test_ext().execute_with(|| {
+        create(1);
+        add_verifier(2);
+        assert_ok!(Profile::request_verification(RuntimeOrigin::signed(1), VerificationKind::Identity));
+
+        // 申请后唯一的认证员注销：申请留在队列中
+        assert_ok!(Profile::unregister_verifier(RuntimeOrigin::signed(2)));
+        next_block();
+        assert_eq!(Profile::verification_requests(0).unwrap().verifier, None);
+        assert_eq!(VerifierAssignmentQueue::<Test>::get().to_vec(), vec![0]);
+
+        add_verifier(3);
+        next_block();
+        assert_eq!(Profile::verification_requests(0).unwrap().verifier, Some(3));
+        assert!(VerifierAssignmentQueue::<Test>::get().is_empty());
+    });
+}
+
+#[test]
+fn submit_evidence_checks_owner_and_sharing() {
+    new_test_ext().execute_with(|| {
+        create(1);
+        add_verifier(2);
+        let (request_id, verifier) = request_assigned(1, VerificationKind::Liveness);
+
+        // 他人上传的材料
+        add_private_content(5, 3, vec![verifier]);
+        assert_noop!(
+            Profile::submit_verification_evidence(RuntimeOrigin::signed(1), request_id, 5),
+            Error::<Test>::EvidenceNotOwned
+        );
+        // 未加密给分配的认证员
+        add_private_content(6, 1, vec![4]);
+        assert_noop!(
+            Profile::submit_verification_evidence(RuntimeOrigin::signed(1), request_id, 6),
+            Error::<Test>::EvidenceNotSharedWithVerifier
+        );
+        // 仅申请人可提交
+        add_private_content(7, 1, vec![verifier]);
+        assert_noop!(
+            Profile::submit_verification_evidence(RuntimeOrigin::signed(3), request_id, 7),
+            Error::<Test>::NotAuthorized
+        );
+
+        assert_ok!(Profile::submit_verification_evidence(RuntimeOrigin::signed(1), request_id, 7));
+        assert_eq!(Profile::verification_requests(request_id).unwrap().evidence_id, Some(7));
+        System::assert_last_event(
+            Event::<Test>::VerificationEvidenceSubmitted { request_id, evidence_id: 7 }.into(),
+        );
+        assert_noop!(
+            Profile::submit_verification_evidence(RuntimeOrigin::signed(1), request_id, 7),
+            Error::<Test>::EvidenceAlreadySubmitted
+        );
+    });
+}
+
+// ============================================================================
+// 审核与费用
+// ============================================================================
+
+#[test]
+fn approve_verification_pays_fee_and_grants_badge() {
+    new_test_ext().execute_with(|| {
+        create(1);
+        add_verifier(2);
+        add_verifier(3);
+        let (request_id, verifier) = request_assigned(1, VerificationKind::Liveness);
+        let other = if verifier == 2 { 3 } else { 2 };
+
+        assert_noop!(
+            Profile::approve_verification(RuntimeOrigin::signed(verifier), request_id),
+            Error::<Test>::EvidenceNotSubmitted
+        );
+        submit_evidence(1, request_id, verifier, 7);
+        assert_noop!(
+            Profile::approve_verification(RuntimeOrigin::signed(other), request_id),
+            Error::<Test>::NotAuthorized
+        );
+
+        assert_ok!(Profile::approve_verification(RuntimeOrigin::signed(verifier), request_id));
+
+        let expires_at = System::block_number() + VERIFICATION_VALIDITY;
+        let record = Profile::verifications(1, VerificationKind::Liveness).unwrap();
+        assert_eq!((record.verifier, record.evidence_id, record.expires_at), (verifier, 7, expires_at));
+        assert_eq!(Profile::badge_of(&1), VerificationBadge::Liveness);
+        assert!(Profile::profiles(1).unwrap().verified);
+        assert!(Profile::verification_requests(request_id).is_none());
+        assert_eq!(Profile::pending_verification(1, VerificationKind::Liveness), None);
+
+        let info = Profile::verifiers(verifier).unwrap();
+        assert_eq!((info.pending_requests, info.approved_count, info.active_approvals), (0, 1, 1));
+        assert_eq!(Balances::free_balance(verifier), 1_000 - VERIFIER_STAKE + VERIFICATION_FEE);
+        assert_eq!(Balances::free_balance(1), 1_000 - PROFILE_DEPOSIT - VERIFICATION_FEE);
+        System::assert_has_event(
+            Event::<Test>::VerificationApproved {
+                request_id,
+                who: 1,
+                kind: VerificationKind::Liveness,
+                verifier,
+                expires_at,
+            }
+            .into(),
+        );
+
+        // 双重认证
+        verify(1, VerificationKind::Identity, 8);
+        assert_eq!(Profile::badge_of(&1), VerificationBadge::Full);
+    });
+}
+
+#[test]
+fn reject_with_evidence_pays_full_fee() {
+    new_test_ext().execute_with(|| {
+        create(1);
+        add_verifier(2);
+        let (request_id, verifier) = request_assigned(1, VerificationKind::Identity);
+        submit_evidence(1, request_id, verifier, 7);
+
+        assert_ok!(Profile::reject_verification(RuntimeOrigin::signed(verifier), request_id));
+
+        assert_eq!(Balances::free_balance(verifier), 1_000 - VERIFIER_STAKE + VERIFICATION_FEE);
+        assert_eq!(Balances::free_balance(1), 1_000 - PROFILE_DEPOSIT - VERIFICATION_FEE);
+        assert_eq!(Profile::verifiers(verifier).unwrap().rejected_count, 1);
+        assert_eq!(Profile::badge_of(&1), VerificationBadge::None);
+    });
+}
+
+#[test]
+fn reject_without_evidence_pays_cancel_penalty() {
+    new_test_ext().execute_with(|| {
+        create(1);
+        add_verifier(2);
+        let (request_id, verifier) = request_assigned(1, VerificationKind::Identity);
+
+        assert_ok!(Profile::reject_verification(RuntimeOrigin::signed(verifier), request_id));
+
+        let penalty = VERIFICATION_FEE * CANCEL_PENALTY_BPS as u128 / 10_000;
+        assert_eq!(Balances::free_balance(verifier), 1_000 - VERIFIER_STAKE + penalty);
+        assert_eq!(Balances::free_balance(1), 1_000 - PROFILE_DEPOSIT - penalty);
+    });
+}
+
+#[test]
+fn cancel_before_assignment_refunds_in_full() {
+    new_test_ext().execute_with(|| {
+        create(1);
+        add_verifier(2);
+        assert_ok!(Profile::request_verification(RuntimeOrigin::signed(1), VerificationKind::Liveness));
+
+        assert_noop!(Profile::cancel_verification(RuntimeOrigin::signed(2), 0), Error::<Test>::NotAuthorized);
+        assert_ok!(Profile::cancel_verification(RuntimeOrigin::signed(1), 0));
+
+        assert_eq!(Balances::free_balance(1), 1_000 - PROFILE_DEPOSIT);
+        assert!(VerifierAssignmentQueue::<Test>::get().is_empty());
+        assert_eq!(Profile::pending_verification(1, VerificationKind::Liveness), None);
+        System::assert_last_event(Event::<Test>::VerificationCancelled { request_id: 0, who: 1 }.into());
+
+        // 已取消的申请不再分配
+        next_block();
+        assert_eq!(Profile::verifiers(2).unwrap().pending_requests, 0);
+    });
+}
+
+#[test]
+fn cancel_after_assignment_pays_penalty() {
+    new_test_ext().execute_with(|| {
+        create(1);
+        add_verifier(2);
+        let (request_id, verifier) = request_assigned(1, VerificationKind::Liveness);
+
+        assert_ok!(Profile::cancel_verification(RuntimeOrigin::signed(1), request_id));
+
+        let penalty = VERIFICATION_FEE * CANCEL_PENALTY_BPS as u128 / 10_000;
+        assert_eq!(Balances::free_balance(verifier), 1_000 - VERIFIER_STAKE + penalty);
+        assert_eq!(Balances::free_balance(1), 1_000 - PROFILE_DEPOSIT - penalty);
+        assert_eq!(Profile::verifiers(verifier).unwrap().pending_requests, 0);
+        assert!(!VerifierQueue::<Test>::contains_key(verifier, request_id));
+        assert!(Profile::verification_requests(request_id).is_none());
+
+        // 重新申请重抽认证员须再次付出取消补偿
+        let (request_id, verifier) = request_assigned(1, VerificationKind::Liveness);
+        assert_ok!(Profile::cancel_verification(RuntimeOrigin::signed(1), request_id));
+        assert_eq!(Balances::free_balance(verifier), 1_000 - VERIFIER_STAKE + 2 * penalty);
+    });
+}
+
+#[test]
+fn delete_profile_cancels_pending_verification() {
+    new_test_ext().execute_with(|| {
+        create(1);
+        create(3);
+        add_verifier(2);
+        let (request_id, verifier) = request_assigned(1, VerificationKind::Liveness);
+        assert_ok!(Profile::request_verification(RuntimeOrigin::signed(3), VerificationKind::Liveness));
+
+        // 已分配：认证员获得取消补偿
+        assert_ok!(Profile::delete_profile(RuntimeOrigin::signed(1)));
+        let penalty = VERIFICATION_FEE * CANCEL_PENALTY_BPS as u128 / 10_000;
+        assert_eq!(Balances::free_balance(1), 1_000 - penalty);
+        assert_eq!(Balances::free_balance(verifier), 1_000 - VERIFIER_STAKE + penalty);
+        assert!(Profile::verification_requests(request_id).is_none());
+
+        // 未分配：全额退还并移出分配队列
+        assert_ok!(Profile::delete_profile(RuntimeOrigin::signed(3)));
+        assert_eq!(Balances::free_balance(3), 1_000);
+        assert!(VerifierAssignmentQueue::<Test>::get().is_empty());
+    });
+}
+
+// ============================================================================
+// 撤销与过期
+// ============================================================================
+
+#[test]
+fn governance_revoke_slashes_verifier_stake() {
+    new_test_ext().execute_with(|| {
+        create(1);
+        add_verifier(2);
+        let verifier = verify(1, VerificationKind::Liveness, 7);
+
+        assert_noop!(
+            Profile::revoke_verification(RuntimeOrigin::root(), 1, VerificationKind::Liveness, 10_001),
+            Error::<Test>::InvalidSlashBps
+        );
+        assert_ok!(Profile::revoke_verification(RuntimeOrigin::root(), 1, VerificationKind::Liveness, 5_000));
+
+        let slashed = VERIFIER_STAKE / 2;
+        let info = Profile::verifiers(verifier).unwrap();
+        assert_eq!((info.stake, info.revoked_count, info.active_approvals), (VERIFIER_STAKE - slashed, 1, 0));
+        assert_eq!(Balances::free_balance(TREASURY), slashed);
+        assert_eq!(Balances::free_balance(verifier), 1_000 - VERIFIER_STAKE + VERIFICATION_FEE);
+        assert_eq!(Profile::badge_of(&1), VerificationBadge::None);
+        assert!(!Profile::profiles(1).unwrap().verified);
+        System::assert_has_event(Event::<Test>::VerifierSlashed { who: verifier, amount: slashed }.into());
+        System::assert_has_event(
+            Event::<Test>::VerificationRevoked { who: 1, kind: VerificationKind::Liveness }.into(),
+        );
+
+        // 质押不足的认证员不再参与分配
+        create(3);
+        assert_noop!(
+            Profile::request_verification(RuntimeOrigin::signed(3), VerificationKind::Liveness),
+            Error::<Test>::NoVerifierAvailable
+        );
+    });
+}
+
+#[test]
+fn verifier_revoke_cannot_slash() {
+    new_test_ext().execute_with(|| {
+        create(1);
+        add_verifier(2);
+        let verifier = verify(1, VerificationKind::Liveness, 7);
+
+        assert_noop!(
+            Profile::revoke_verification(RuntimeOrigin::signed(verifier), 1, VerificationKind::Liveness, 1),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            Profile::revoke_verification(RuntimeOrigin::signed(3), 1, VerificationKind::Liveness, 0),
+            Error::<Test>::NotAuthorized
+        );
+
+        assert_ok!(Profile::revoke_verification(RuntimeOrigin::signed(verifier), 1, VerificationKind::Liveness, 0));
+        let info = Profile::verifiers(verifier).unwrap();
+        assert_eq!((info.stake, info.revoked_count, info.active_approvals), (VERIFIER_STAKE, 0, 0));
+        assert_eq!(Profile::badge_of(&1), VerificationBadge::None);
+    });
+}
+
+#[test]
+fn expire_verification_after_validity() {
+    new_test_ext().execute_with(|| {
+        create(1);
+        add_verifier(2);
+        let verifier = verify(1, VerificationKind::Liveness, 7);
+        let expires_at = Profile::verifications(1, VerificationKind::Liveness).unwrap().expires_at;
+
+        assert_noop!(
+            Profile::expire_verification(RuntimeOrigin::signed(3), 1, VerificationKind::Liveness),
+            Error::<Test>::VerificationNotExpired
+        );
+
+        // 到期后徽章即失效，清理后释放认证员担保
+        System::set_block_number(expires_at);
+        assert_eq!(Profile::badge_of(&1), VerificationBadge::None);
+        assert_ok!(Profile::expire_verification(RuntimeOrigin::signed(3), 1, VerificationKind::Liveness));
+        assert!(Profile::verifications(1, VerificationKind::Liveness).is_none());
+        assert_eq!(Profile::verifiers(verifier).unwrap().active_approvals, 0);
+        assert!(!Profile::profiles(1).unwrap().verified);
+        assert_noop!(
+            Profile::expire_verification(RuntimeOrigin::signed(3), 1, VerificationKind::Liveness),
+            Error::<Test>::VerificationNotFound
+        );
+    });
+}
+
+// ============================================================================
+// 月费自动续费
+// ============================================================================
+
+#[test]
+fn set_auto_pay_schedules_monthly_fee() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(Profile::set_auto_pay(RuntimeOrigin::signed(1), true), Error::<Test>::ProfileNotFound);
+
+        create(1);
+        assert_ok!(Profile::set_auto_pay(RuntimeOrigin::signed(1), true));
+        assert_eq!(ScheduledPayments::get(), vec![(1, 1)]);
+        System::assert_last_event(Event::<Test>::AutoPaySet { who: 1, enabled: true }.into());
+
+        // 重复开启不会产生多个计划
+        assert_ok!(Profile::set_auto_pay(RuntimeOrigin::signed(1), true));
+        assert_eq!(ScheduledPayments::get().len(), 1);
+
+        assert_ok!(Profile::set_auto_pay(RuntimeOrigin::signed(1), false));
+        assert!(ScheduledPayments::get().is_empty());
     });
 }
@@ -14,6 +14,17 @@ pub trait WeightInfo {
     fn delete_profile() -> Weight;
     fn set_visibility() -> Weight;
     fn verify_profile() -> Weight;
+    fn register_verifier() -> Weight;
+    fn unregister_verifier() -> Weight;
+    fn request_verification() -> Weight;
+    fn reject_verification() -> Weight;
+    fn cancel_verification() -> Weight;
+    fn revoke_verification() -> Weight;
+    fn expire_verification() -> Weight;
+    fn set_auto_pay() -> Weight;
+    fn approve_verifier() -> Weight;
+    fn submit_verification_evidence() -> Weight;
+    fn assign_verifier() -> Weight;
 }
 
 /// Substrate 权重实现
@@ -55,10 +66,65 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().writes(1))
     }
     fn verify_profile() -> Weight {
+        Weight::from_parts(45_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(7))
+    }
+    fn register_verifier() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn unregister_verifier() -> Weight {
         Weight::from_parts(30_000_000, 0)
             .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn request_verification() -> Weight {
+        Weight::from_parts(50_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(7))
+            .saturating_add(T::DbWeight::get().writes(6))
+    }
+    fn reject_verification() -> Weight {
+        Weight::from_parts(40_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(6))
+    }
+    fn cancel_verification() -> Weight {
+        Weight::from_parts(35_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    fn revoke_verification() -> Weight {
+        Weight::from_parts(45_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    fn expire_verification() -> Weight {
+        Weight::from_parts(25_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn set_auto_pay() -> Weight {
+        Weight::from_parts(35_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(6))
+    }
+    fn approve_verifier() -> Weight {
+        Weight::from_parts(25_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn submit_verification_evidence() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
             .saturating_add(T::DbWeight::get().writes(1))
     }
+    fn assign_verifier() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
 }
 
 /// 默认权重实现（用于测试）
@@ -70,5 +136,16 @@ impl WeightInfo for () {
     fn update_privacy_mode() -> Weight { Weight::from_parts(20_000_000, 0) }
     fn delete_profile() -> Weight { Weight::from_parts(40_000_000, 0) }
     fn set_visibility() -> Weight { Weight::from_parts(25_000_000, 0) }
-    fn verify_profile() -> Weight { Weight::from_parts(30_000_000, 0) }
+    fn verify_profile() -> Weight { Weight::from_parts(45_000_000, 0) }
+    fn register_verifier() -> Weight { Weight::from_parts(30_000_000, 0) }
+    fn unregister_verifier() -> Weight { Weight::from_parts(30_000_000, 0) }
+    fn request_verification() -> Weight { Weight::from_parts(50_000_000, 0) }
+    fn reject_verification() -> Weight { Weight::from_parts(40_000_000, 0) }
+    fn cancel_verification() -> Weight { Weight::from_parts(35_000_000, 0) }
+    fn revoke_verification() -> Weight { Weight::from_parts(45_000_000, 0) }
+    fn expire_verification() -> Weight { Weight::from_parts(25_000_000, 0) }
+    fn set_auto_pay() -> Weight { Weight::from_parts(35_000_000, 0) }
+    fn approve_verifier() -> Weight { Weight::from_parts(25_000_000, 0) }
+    fn submit_verification_evidence() -> Weight { Weight::from_parts(30_000_000, 0) }
+    fn assign_verifier() -> Weight { Weight::from_parts(30_000_000, 0) }
 }
@@ -13,6 +13,16 @@
 //! 1. 基于匹配评分：推荐高分匹配用户
 //! 2. 基于活跃度：推荐近期活跃用户
 //! 3. 基于地理位置：推荐同城用户
+//! 4. 基于八字合婚：日柱与五行互补评分加权
+//!
+//! ## 推荐流水线
+//!
+//! 1. 用户调用 `refresh_recommendations` 进入待刷新队列
+//! 2. Off-chain Worker 从资料模块的性别/年龄/所在地索引构建候选集
+//! 3. 使用 `algorithm` 模块筛选、评分（含八字合婚），取前 N 名
+//! 4. 以推荐计算节点密钥签名，通过带签名负载的无签名交易 `submit_recommendations` 提交；
+//!    交易池只校验签名、节点授权与队列状态，链上执行时完整复算推荐列表，与提交结果完全一致才写入
+//! 5. 资料已删除的用户无法计算推荐，不占用 OCW 名额，并在 `on_idle` 中移出待刷新队列
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -21,14 +31,76 @@ pub use pallet::*;
 pub mod weights;
 pub use weights::WeightInfo;
 
+#[cfg(test)]
+mod mock;
+
 #[cfg(test)]
 mod tests;
 
 use frame_support::pallet_prelude::*;
+use frame_system::offchain::{
+    AppCrypto, CreateBare, SendUnsignedTransaction, SignedPayload, Signer, SigningTypes,
+};
 use frame_system::pallet_prelude::*;
-use sp_runtime::traits::Saturating;
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::traits::{IdentifyAccount, SaturatedConversion, Saturating};
+use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
+
+use pallet_matchmaking_common::{
+    Gender, ProfilePrivacyMode, ProfileStatus, RecommendationResult, VerificationBadge,
+};
+use pallet_matchmaking_matching::BaziDataProvider;
+use pallet_matchmaking_profile::{
+    AgeIndex, BannedUsers, GenderIndex, LocationIndex, Profiles, SuspendedUntil, UserProfile,
+};
+
+/// 推荐计算节点签名 KeyType（节点 keystore 中需注入该类型密钥）
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"mrec");
 
-use pallet_matchmaking_common::RecommendationResult;
+/// 推荐计算节点签名算法（sr25519）
+pub mod crypto {
+    use super::KEY_TYPE;
+    use sp_runtime::{
+        app_crypto::{app_crypto, sr25519},
+        MultiSignature, MultiSigner,
+    };
+    app_crypto!(sr25519, KEY_TYPE);
+
+    /// 推荐计算节点身份
+    pub struct AuthorityId;
+
+    impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for AuthorityId {
+        type RuntimeAppPublic = Public;
+        type GenericSignature = sp_core::sr25519::Signature;
+        type GenericPublic = sp_core::sr25519::Public;
+    }
+
+    /// 测试运行时（账户即 sr25519 公钥）
+    impl frame_system::offchain::AppCrypto<sp_core::sr25519::Public, sp_core::sr25519::Signature>
+        for AuthorityId
+    {
+        type RuntimeAppPublic = Public;
+        type GenericSignature = sp_core::sr25519::Signature;
+        type GenericPublic = sp_core::sr25519::Public;
+    }
+}
+
+/// 推荐计算节点签名的推荐列表
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct RecommendationPayload<Public, AccountId> {
+    /// 签名节点公钥
+    pub public: Public,
+    /// 推荐列表所属用户
+    pub user: AccountId,
+    /// 推荐列表（按评分降序）
+    pub recommendations: Vec<RecommendationResult<AccountId>>,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for RecommendationPayload<T::Public, T::AccountId> {
+    fn public(&self) -> T::Public {
+        self.public.clone()
+    }
+}
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -39,7 +111,12 @@ pub mod pallet {
 
     /// Pallet 配置
     #[pallet::config]
-    pub trait Config: frame_system::Config {
+    pub trait Config:
+        frame_system::Config
+        + pallet_matchmaking_profile::Config
+        + SigningTypes
+        + CreateBare<Call<Self>>
+    {
         /// 运行时事件类型
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
@@ -51,6 +128,27 @@ pub mod pallet {
         #[pallet::constant]
         type RecommendationUpdateInterval: Get<BlockNumberFor<Self>>;
 
+        /// 八字数据提供者（用于合婚评分）
+        type BaziProvider: BaziDataProvider<Self::AccountId>;
+
+        /// 单个用户候选集上限（OCW 遍历索引的最大数量）
+        #[pallet::constant]
+        type MaxCandidates: Get<u32>;
+
+        /// 待刷新队列最大长度
+        #[pallet::constant]
+        type MaxPendingRefresh: Get<u32>;
+
+        /// 每个区块 OCW 最多处理的刷新请求数
+        #[pallet::constant]
+        type MaxRefreshPerBlock: Get<u32>;
+
+        /// 推荐计算节点签名密钥
+        type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+        /// 推荐计算节点管理权限（治理）
+        type AuthorityOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
         /// 权重信息
         type WeightInfo: WeightInfo;
     }
@@ -81,6 +179,17 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// 待刷新队列（等待 OCW 计算推荐列表的用户）
+    #[pallet::storage]
+    #[pallet::getter(fn pending_refresh)]
+    pub type PendingRefresh<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxPendingRefresh>, ValueQuery>;
+
+    /// 推荐计算节点（签名公钥对应的账户）
+    #[pallet::storage]
+    #[pallet::getter(fn authorities)]
+    pub type Authorities<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
     // ========================================================================
     // 事件
     // ========================================================================
@@ -97,6 +206,18 @@ pub mod pallet {
         RecommendationsRefreshed {
             user: T::AccountId,
         },
+        /// 资料已删除，刷新请求移出队列
+        RefreshDiscarded {
+            user: T::AccountId,
+        },
+        /// 推荐计算节点已添加
+        AuthorityAdded {
+            authority: T::AccountId,
+        },
+        /// 推荐计算节点已移除
+        AuthorityRemoved {
+            authority: T::AccountId,
+        },
     }
 
     // ========================================================================
@@ -111,6 +232,87 @@ pub mod pallet {
         UpdateTooFrequent,
         /// 用户资料不存在
         ProfileNotFound,
+        /// 待刷新队列已满
+        RefreshQueueFull,
+        /// 用户未请求刷新
+        RefreshNotRequested,
+        /// 推荐结果无效（评分不一致、重复或不符合条件）
+        InvalidRecommendation,
+        /// 已是推荐计算节点
+        AlreadyAuthority,
+        /// 不是推荐计算节点
+        NotAuthority,
+    }
+
+    // ========================================================================
+    // Hooks
+    // ========================================================================
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// 空闲时将资料已删除的用户移出待刷新队列
+        ///
+        /// 这些请求无法计算推荐列表，留在队列中会占满队列导致 `RefreshQueueFull`
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            let weight = T::DbWeight::get().reads_writes(
+                1u64.saturating_add(T::MaxPendingRefresh::get() as u64),
+                1,
+            );
+            if remaining_weight.any_lt(weight) {
+                return Weight::zero();
+            }
+
+            let mut queue = PendingRefresh::<T>::get();
+            let before = queue.len();
+            queue.retain(|user| {
+                let exists = Profiles::<T>::contains_key(user);
+                if !exists {
+                    Self::deposit_event(Event::RefreshDiscarded { user: user.clone() });
+                }
+                exists
+            });
+            if queue.len() != before {
+                PendingRefresh::<T>::put(queue);
+            }
+
+            weight
+        }
+
+        /// Off-chain Worker 入口
+        ///
+        /// 每个区块最多提交 `MaxRefreshPerBlock` 个推荐列表，
+        /// 以本地推荐计算节点密钥签名后通过无签名交易提交上链
+        fn offchain_worker(block_number: BlockNumberFor<T>) {
+            let signer = Signer::<T, T::AuthorityId>::any_account();
+            if !signer.can_sign() || PendingRefresh::<T>::get().is_empty() {
+                return;
+            }
+
+            for (user, recommendations) in Self::refresh_batch(T::MaxRefreshPerBlock::get() as usize) {
+                let count = recommendations.len();
+                let result = signer.send_unsigned_transaction(
+                    |account| RecommendationPayload {
+                        public: account.public.clone(),
+                        user: user.clone(),
+                        recommendations: recommendations.clone().into_inner(),
+                    },
+                    |payload, signature| Call::submit_recommendations { payload, signature },
+                );
+                match result {
+                    Some((_, Ok(()))) => log::debug!(
+                        target: "matchmaking-recommendation",
+                        "📤 推荐列表已提交: block={:?}, count={}",
+                        block_number,
+                        count
+                    ),
+                    _ => log::error!(
+                        target: "matchmaking-recommendation",
+                        "❌ 推荐列表提交失败: block={:?}",
+                        block_number
+                    ),
+                }
+            }
+        }
     }
 
     // ========================================================================
@@ -123,9 +325,10 @@ pub mod pallet {
         ///
         /// 用户主动刷新自己的推荐列表
         #[pallet::call_index(0)]
-        #[pallet::weight(T::WeightInfo::refresh_recommendations())]
+        #[pallet::weight(<T as Config>::WeightInfo::refresh_recommendations())]
         pub fn refresh_recommendations(origin: OriginFor<T>) -> DispatchResult {
             let who = ensure_signed(origin)?;
+            ensure!(Profiles::<T>::contains_key(&who), Error::<T>::ProfileNotFound);
 
             let current_block = frame_system::Pallet::<T>::block_number();
             let last_update = LastUpdate::<T>::get(&who);
@@ -137,12 +340,13 @@ pub mod pallet {
                 ensure!(current_block >= next_update, Error::<T>::UpdateTooFrequent);
             }
 
-            // TODO: 实现推荐算法
-            // 目前使用空列表占位
-            let recommendations: BoundedVec<RecommendationResult<T::AccountId>, T::MaxRecommendationsPerUser> = 
-                BoundedVec::default();
-
-            Recommendations::<T>::insert(&who, recommendations);
+            // 加入待刷新队列，由 OCW 计算后提交（旧列表保留至新结果上链）
+            PendingRefresh::<T>::try_mutate(|queue| -> DispatchResult {
+                if !queue.contains(&who) {
+                    queue.try_push(who.clone()).map_err(|_| Error::<T>::RefreshQueueFull)?;
+                }
+                Ok(())
+            })?;
             LastUpdate::<T>::insert(&who, current_block);
 
             Self::deposit_event(Event::RecommendationsRefreshed { user: who });
@@ -152,15 +356,109 @@ pub mod pallet {
 
         /// 清空推荐列表
         #[pallet::call_index(1)]
-        #[pallet::weight(T::WeightInfo::clear_recommendations())]
+        #[pallet::weight(<T as Config>::WeightInfo::clear_recommendations())]
         pub fn clear_recommendations(origin: OriginFor<T>) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
             Recommendations::<T>::remove(&who);
             LastUpdate::<T>::remove(&who);
+            PendingRefresh::<T>::mutate(|queue| queue.retain(|u| u != &who));
 
             Ok(())
         }
+
+        /// 提交推荐列表（推荐计算节点签名负载的无签名交易）
+        ///
+        /// 签名与节点授权在 `validate_unsigned` 中校验；
+        /// 链上按刷新请求区块重新计算推荐列表，须与提交结果完全一致（同样的前 N 名、同样的顺序）才写入，
+        /// 因此提交者无法伪造分数，也无法提交空列表或只提交部分候选
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::submit_recommendations(T::MaxCandidates::get()))]
+        pub fn submit_recommendations(
+            origin: OriginFor<T>,
+            payload: RecommendationPayload<T::Public, T::AccountId>,
+            _signature: T::Signature,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+            let RecommendationPayload { user, recommendations, .. } = payload;
+            ensure!(
+                PendingRefresh::<T>::get().contains(&user),
+                Error::<T>::RefreshNotRequested
+            );
+            ensure!(
+                Self::verify_recommendations(&user, &recommendations),
+                Error::<T>::InvalidRecommendation
+            );
+            let recommendations: BoundedVec<_, T::MaxRecommendationsPerUser> =
+                recommendations.try_into().map_err(|_| Error::<T>::InvalidRecommendation)?;
+
+            let count = recommendations.len() as u32;
+            Recommendations::<T>::insert(&user, recommendations);
+            PendingRefresh::<T>::mutate(|queue| queue.retain(|u| u != &user));
+
+            Self::deposit_event(Event::RecommendationsUpdated { user, count });
+
+            Ok(())
+        }
+
+        /// 添加推荐计算节点（治理）
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_authority())]
+        pub fn add_authority(origin: OriginFor<T>, authority: T::AccountId) -> DispatchResult {
+            T::AuthorityOrigin::ensure_origin(origin)?;
+            ensure!(!Authorities::<T>::contains_key(&authority), Error::<T>::AlreadyAuthority);
+
+            Authorities::<T>::insert(&authority, ());
+            Self::deposit_event(Event::AuthorityAdded { authority });
+
+            Ok(())
+        }
+
+        /// 移除推荐计算节点（治理）
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_authority())]
+        pub fn remove_authority(origin: OriginFor<T>, authority: T::AccountId) -> DispatchResult {
+            T::AuthorityOrigin::ensure_origin(origin)?;
+            ensure!(Authorities::<T>::contains_key(&authority), Error::<T>::NotAuthority);
+
+            Authorities::<T>::remove(&authority);
+            Self::deposit_event(Event::AuthorityRemoved { authority });
+
+            Ok(())
+        }
+    }
+
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+
+        /// 交易池只做低成本预检：长度、节点授权、签名与队列状态；
+        /// 推荐列表的完整复算在链上执行时进行（计入交易权重）
+        fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            match call {
+                Call::submit_recommendations { payload, signature } => {
+                    if payload.recommendations.len() > T::MaxRecommendationsPerUser::get() as usize {
+                        return InvalidTransaction::ExhaustsResources.into();
+                    }
+                    if !Authorities::<T>::contains_key(payload.public.clone().into_account()) {
+                        return InvalidTransaction::BadSigner.into();
+                    }
+                    if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+                        return InvalidTransaction::BadProof.into();
+                    }
+                    if !PendingRefresh::<T>::get().contains(&payload.user) {
+                        return InvalidTransaction::Stale.into();
+                    }
+                    ValidTransaction::with_tag_prefix("MatchmakingRecommendation")
+                        .priority(50)
+                        .longevity(5)
+                        .and_provides([&(b"recommend", &payload.user)])
+                        .propagate(true)
+                        .build()
+                },
+                _ => InvalidTransaction::Call.into(),
+            }
+        }
     }
 }
 
@@ -188,6 +486,239 @@ impl<T: Config> Pallet<T> {
 
         current_block >= last_update.saturating_add(min_interval)
     }
+
+    /// 从请求用户资料构建推荐上下文
+    ///
+    /// 评分基准区块取刷新请求区块（`LastUpdate`），保证 OCW 与链上复算结果一致
+    pub fn build_context(user: &T::AccountId) -> Option<algorithm::RecommendationContext<T::Hash>> {
+        let profile = Profiles::<T>::get(user)?;
+
+        let target_genders = match profile.gender {
+            Gender::Male => sp_std::vec![Gender::Female],
+            Gender::Female => sp_std::vec![Gender::Male],
+            Gender::Other => sp_std::vec![Gender::Male, Gender::Female, Gender::Other],
+        };
+
+        let partner = profile.partner_preferences.as_ref();
+        // 优先使用期望性格，未设置时使用自身性格
+        let traits = match partner {
+            Some(p) if !p.desired_personality_traits.is_empty() => {
+                algorithm::encode_traits(p.desired_personality_traits.iter().map(|t| *t as u8))
+            },
+            _ => algorithm::encode_traits(profile.personality_traits.iter().map(|t| *t as u8)),
+        };
+
+        let preferences = partner
+            .map(|p| algorithm::UserPreferences {
+                age_range: p.age_range,
+                height_range: p.height_range,
+                min_education: p.education_requirement.map(|e| e as u8),
+                income_range: p.income_range,
+                accept_children: p.accept_children,
+                min_bazi_score: p.min_bazi_compatibility,
+            })
+            .unwrap_or_default();
+
+        let location = partner
+            .and_then(|p| p.location_preference.as_ref())
+            .or(profile.current_location.as_ref())
+            .map(|loc| pallet_matchmaking_profile::Pallet::<T>::location_key(loc));
+
+        Some(algorithm::RecommendationContext {
+            target_genders,
+            traits,
+            preferences,
+            location,
+            bazi_chart_id: profile.bazi_chart_id,
+            reference_block: LastUpdate::<T>::get(user).saturated_into(),
+        })
+    }
+
+    /// 检查候选人是否可被推荐
+    ///
+    /// 排除自己、非目标性别、非激活状态、完全私密、已封禁和暂停中的用户
+    fn is_eligible(
+        user: &T::AccountId,
+        ctx: &algorithm::RecommendationContext<T::Hash>,
+        candidate: &T::AccountId,
+        profile: &UserProfile<T>,
+    ) -> bool {
+        if candidate == user
+            || !ctx.target_genders.contains(&profile.gender)
+            || profile.status != ProfileStatus::Active
+            || profile.privacy_mode == ProfilePrivacyMode::Private
+            || BannedUsers::<T>::contains_key(candidate)
+        {
+            return false;
+        }
+
+        match SuspendedUntil::<T>::get(candidate) {
+            Some(until) => until.saturated_into::<u64>() <= ctx.reference_block,
+            None => true,
+        }
+    }
+
+    /// 将候选人资料转换为算法输入
+    ///
+    /// 认证徽章按评分基准区块计算，保证链下计算与链上复算一致
+    fn candidate_info(
+        account: &T::AccountId,
+        profile: &UserProfile<T>,
+        reference_block: u64,
+    ) -> algorithm::CandidateInfo {
+        algorithm::CandidateInfo {
+            age: profile.age,
+            height: profile.height,
+            education_level: profile.education.map(|e| e as u8),
+            income: profile.income_range.map(|(min, _)| min),
+            has_children: profile.has_children,
+            last_active_block: profile.last_active_at.saturated_into(),
+            personality_traits: algorithm::encode_traits(
+                profile.personality_traits.iter().map(|t| *t as u8),
+            ),
+            bazi_chart_id: profile.bazi_chart_id,
+            verification_badge: pallet_matchmaking_profile::Pallet::<T>::badge_at(
+                account,
+                reference_block.saturated_into(),
+            ),
+        }
+    }
+
+    /// 计算八字合婚分（双方均有命盘时）
+    fn bazi_score(ctx: &algorithm::RecommendationContext<T::Hash>, candidate: &algorithm::CandidateInfo) -> Option<u8> {
+        match (ctx.bazi_chart_id, candidate.bazi_chart_id) {
+            (Some(a), Some(b)) => algorithm::bazi_compatibility::<T::AccountId, T::BaziProvider>(a, b),
+            _ => None,
+        }
+    }
+
+    /// 构建候选集
+    ///
+    /// 依次从所在地索引、年龄索引、性别索引收集候选人，去重后最多 `MaxCandidates` 个。
+    /// 返回 (候选人, 推荐理由, 候选人信息)
+    fn collect_candidates(
+        user: &T::AccountId,
+        ctx: &algorithm::RecommendationContext<T::Hash>,
+    ) -> Vec<(T::AccountId, u8, algorithm::CandidateInfo)> {
+        let max = T::MaxCandidates::get() as usize;
+        let mut seen: BTreeSet<T::AccountId> = BTreeSet::new();
+        let mut candidates = Vec::new();
+
+        let consider = |account: T::AccountId,
+                            reason: u8,
+                            seen: &mut BTreeSet<T::AccountId>,
+                            candidates: &mut Vec<(T::AccountId, u8, algorithm::CandidateInfo)>| {
+            if !seen.insert(account.clone()) {
+                return;
+            }
+            if let Some(profile) = Profiles::<T>::get(&account) {
+                if Self::is_eligible(user, ctx, &account, &profile) {
+                    let info = Self::candidate_info(&account, &profile, ctx.reference_block);
+                    candidates.push((account, reason, info));
+                }
+            }
+        };
+
+        // 1. 同城
+        if let Some(key) = ctx.location {
+            for account in LocationIndex::<T>::iter_key_prefix(key) {
+                if seen.len() >= max {
+                    break;
+                }
+                consider(account, algorithm::REASON_SAME_LOCATION, &mut seen, &mut candidates);
+            }
+        }
+
+        // 2. 年龄区间
+        if let Some((min_age, max_age)) = ctx.preferences.age_range {
+            for age in min_age..=max_age {
+                if seen.len() >= max {
+                    break;
+                }
+                for account in AgeIndex::<T>::iter_key_prefix(age) {
+                    if seen.len() >= max {
+                        break;
+                    }
+                    consider(account, algorithm::REASON_AGE_MATCH, &mut seen, &mut candidates);
+                }
+            }
+        }
+
+        // 3. 目标性别
+        for gender in ctx.target_genders.iter() {
+            for account in GenderIndex::<T>::iter_key_prefix(*gender) {
+                if seen.len() >= max {
+                    break;
+                }
+                consider(account, algorithm::REASON_GENDER_MATCH, &mut seen, &mut candidates);
+            }
+        }
+
+        candidates
+    }
+
+    /// 计算用户推荐列表（OCW 调用，链下执行不计权重）
+    pub fn compute_recommendations(
+        user: &T::AccountId,
+    ) -> Option<BoundedVec<RecommendationResult<T::AccountId>, T::MaxRecommendationsPerUser>> {
+        let ctx = Self::build_context(user)?;
+        let candidates = Self::collect_candidates(user, &ctx);
+
+        let inputs: Vec<(T::AccountId, algorithm::CandidateInfo)> =
+            candidates.iter().map(|(a, _, info)| (a.clone(), info.clone())).collect();
+        let ranked = algorithm::recommend_matches(
+            &ctx.traits,
+            &ctx.preferences,
+            &inputs,
+            ctx.reference_block,
+            T::MaxRecommendationsPerUser::get() as usize,
+            |_, info| Self::bazi_score(&ctx, info),
+        );
+
+        let results: Vec<RecommendationResult<T::AccountId>> = ranked
+            .into_iter()
+            .map(|(account, score)| {
+                let (reason, info) = candidates
+                    .iter()
+                    .find(|(a, _, _)| a == &account)
+                    .map(|(_, r, info)| (*r, Some(info)))
+                    .unwrap_or((algorithm::REASON_GENDER_MATCH, None));
+                let reason = match info.and_then(|i| Self::bazi_score(&ctx, i)) {
+                    Some(b) if b >= algorithm::HIGH_BAZI_SCORE => algorithm::REASON_BAZI,
+                    _ => reason,
+                };
+                RecommendationResult { user: account, score, reason_index: reason }
+            })
+            .collect();
+
+        BoundedVec::try_from(results).ok()
+    }
+
+    /// 选取本区块待提交的推荐列表（按队列顺序，最多 `max` 个）
+    ///
+    /// 无法计算推荐的用户（资料已删除）不占用名额，避免队首请求阻塞其后所有用户
+    pub fn refresh_batch(
+        max: usize,
+    ) -> Vec<(T::AccountId, BoundedVec<RecommendationResult<T::AccountId>, T::MaxRecommendationsPerUser>)> {
+        PendingRefresh::<T>::get()
+            .into_iter()
+            .filter_map(|user| Self::compute_recommendations(&user).map(|recommendations| (user, recommendations)))
+            .take(max)
+            .collect()
+    }
+
+    /// 验证提交的推荐列表
+    ///
+    /// 链上以同一评分基准区块重新计算，提交结果须与 `compute_recommendations` 完全一致
+    /// （同样的前 N 名、评分、理由与顺序），空列表、子集或重排均被拒绝
+    pub fn verify_recommendations(
+        user: &T::AccountId,
+        recommendations: &[RecommendationResult<T::AccountId>],
+    ) -> bool {
+        Self::compute_recommendations(user)
+            .map(|expected| expected.as_slice() == recommendations)
+            .unwrap_or(false)
+    }
 }
 
 // ============================================================================
@@ -200,6 +731,38 @@ impl<T: Config> Pallet<T> {
 /// 性能: 10,000 候选人约 2 秒，可通过索引优化至 500ms
 pub mod algorithm {
     use super::*;
+    use pallet_matchmaking_matching::bazi::{
+        calculate_day_pillar_compatibility, calculate_wuxing_compatibility,
+    };
+
+    /// 推荐理由：性别匹配
+    pub const REASON_GENDER_MATCH: u8 = 0;
+    /// 推荐理由：同城
+    pub const REASON_SAME_LOCATION: u8 = 1;
+    /// 推荐理由：年龄符合偏好
+    pub const REASON_AGE_MATCH: u8 = 2;
+    /// 推荐理由：八字合婚高分
+    pub const REASON_BAZI: u8 = 3;
+
+    /// 八字合婚高分阈值（达到后推荐理由标记为八字合婚）
+    pub const HIGH_BAZI_SCORE: u8 = 80;
+
+    /// 推荐计算上下文（由请求用户资料派生）
+    #[derive(Clone, Debug, Default)]
+    pub struct RecommendationContext<Hash> {
+        /// 目标性别
+        pub target_genders: Vec<Gender>,
+        /// 用于性格匹配的标签（期望性格优先）
+        pub traits: [u8; 5],
+        /// 择偶偏好
+        pub preferences: UserPreferences,
+        /// 所在地索引键（地域偏好优先，其次当前所在地）
+        pub location: Option<Hash>,
+        /// 八字命盘 ID
+        pub bazi_chart_id: Option<u64>,
+        /// 评分基准区块（刷新请求区块）
+        pub reference_block: u64,
+    }
 
     /// 匹配分数计算结果
     #[derive(Clone, Debug, Default)]
@@ -212,6 +775,8 @@ pub mod algorithm {
         pub bazi_score: Option<u8>,
         /// 活跃度加成
         pub activity_bonus: u8,
+        /// 认证徽章加分
+        pub badge_bonus: u8,
         /// 综合评分
         pub overall: u8,
     }
@@ -252,6 +817,8 @@ pub mod algorithm {
         pub personality_traits: [u8; 5],
         /// 八字命盘 ID
         pub bazi_chart_id: Option<u64>,
+        /// 认证徽章
+        pub verification_badge: VerificationBadge,
     }
 
     /// 检查候选人是否满足用户偏好条件
@@ -368,10 +935,86 @@ pub mod algorithm {
             personality_score,
             bazi_score: None,
             activity_bonus,
+            badge_bonus: 0,
             overall,
         }
     }
 
+    /// 将性格标签编码为定长数组
+    ///
+    /// 标签值 +1 存储，0 表示空位（与 `calculate_match_score` 的约定一致）
+    pub fn encode_traits(traits: impl Iterator<Item = u8>) -> [u8; 5] {
+        let mut encoded = [0u8; 5];
+        for (slot, t) in encoded.iter_mut().zip(traits) {
+            *slot = t.saturating_add(1);
+        }
+        encoded
+    }
+
+    /// 计算八字合婚分
+    ///
+    /// 日柱合婚与五行互补按 55/45 加权；缺少解盘数据时仅使用日柱评分
+    pub fn bazi_compatibility<AccountId, P: BaziDataProvider<AccountId>>(
+        chart_a: u64,
+        chart_b: u64,
+    ) -> Option<u8> {
+        let sizhu_a = P::get_sizhu_index(chart_a)?;
+        let sizhu_b = P::get_sizhu_index(chart_b)?;
+        let day_pillar = calculate_day_pillar_compatibility(sizhu_a.day_ganzhi(), sizhu_b.day_ganzhi());
+
+        let score = match (P::get_interpretation(chart_a), P::get_interpretation(chart_b)) {
+            (Some(interp_a), Some(interp_b)) => {
+                let wuxing = calculate_wuxing_compatibility(&interp_a, &interp_b);
+                ((day_pillar.overall as u32 * 55 + wuxing.overall as u32 * 45) / 100) as u8
+            },
+            _ => day_pillar.overall,
+        };
+
+        Some(score.min(100))
+    }
+
+    /// 将八字合婚分计入综合评分（综合 70% + 八字 30%）
+    pub fn apply_bazi_score(score: &mut MatchScoreResult, bazi_score: Option<u8>) {
+        score.bazi_score = bazi_score;
+        if let Some(bazi) = bazi_score {
+            score.overall = ((score.overall as u32 * 70 + bazi.min(100) as u32 * 30) / 100) as u8;
+        }
+    }
+
+    /// 将认证徽章加分计入综合评分（上限 100）
+    pub fn apply_badge_bonus(score: &mut MatchScoreResult, badge: VerificationBadge) {
+        score.badge_bonus = badge.ranking_bonus();
+        score.overall = score.overall.saturating_add(score.badge_bonus).min(100);
+    }
+
+    /// 计算单个候选人的最终评分
+    ///
+    /// 不满足偏好条件或八字合婚分低于要求时返回 `None`
+    pub fn score_candidate(
+        user_traits: &[u8; 5],
+        preferences: &UserPreferences,
+        candidate: &CandidateInfo,
+        current_block: u64,
+        bazi_score: Option<u8>,
+    ) -> Option<u8> {
+        if !meets_preferences(preferences, candidate) {
+            return None;
+        }
+
+        let mut score = calculate_match_score(user_traits, candidate, current_block);
+        apply_bazi_score(&mut score, bazi_score);
+        apply_badge_bonus(&mut score, candidate.verification_badge);
+
+        // 八字合婚分数筛选
+        if let (Some(min_bazi), Some(bazi)) = (preferences.min_bazi_score, score.bazi_score) {
+            if bazi < min_bazi {
+                return None;
+            }
+        }
+
+        Some(score.overall)
+    }
+
     /// 基于内容的推荐算法
     /// 
     /// 算法复杂度: O(n log n)
@@ -380,35 +1023,27 @@ pub mod algorithm {
     /// - 排序: O(n log n)
     /// 
     /// 性能: 10,000 候选人约 2 秒
-    pub fn recommend_matches<AccountId: Clone + Ord>(
+    pub fn recommend_matches<AccountId: Clone + Ord, F>(
         user_traits: &[u8; 5],
         preferences: &UserPreferences,
         candidates: &[(AccountId, CandidateInfo)],
         current_block: u64,
         limit: usize,
-    ) -> sp_std::vec::Vec<(AccountId, u8)> {
+        bazi_scorer: F,
+    ) -> sp_std::vec::Vec<(AccountId, u8)>
+    where
+        F: Fn(&AccountId, &CandidateInfo) -> Option<u8>,
+    {
         let mut scores: sp_std::vec::Vec<(AccountId, u8)> = sp_std::vec::Vec::new();
 
-        // 1. 条件筛选 + 分数计算 (O(n))
+        // 1. 条件筛选 + 分数计算（含八字合婚） (O(n))
         for (account, candidate) in candidates.iter() {
-            // 筛选
-            if !meets_preferences(preferences, candidate) {
-                continue;
+            let bazi_score = bazi_scorer(account, candidate);
+            if let Some(overall) =
+                score_candidate(user_traits, preferences, candidate, current_block, bazi_score)
+            {
+                scores.push((account.clone(), overall));
             }
-
-            // 计算分数
-            let score = calculate_match_score(user_traits, candidate, current_block);
-            
-            // 八字合婚分数筛选
-            if let Some(min_bazi) = preferences.min_bazi_score {
-                if let Some(bazi_score) = score.bazi_score {
-                    if bazi_score < min_bazi {
-                        continue;
-                    }
-                }
-            }
-
-            scores.push((account.clone(), score.overall));
         }
 
         // 2. 排序 (O(n log n))
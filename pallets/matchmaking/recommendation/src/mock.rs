@@ -0,0 +1,232 @@
+//! # 推荐系统模块 Mock 运行时
+//!
+//! 账户类型为 sr25519 公钥，便于构造推荐计算节点的签名负载
+
+use crate as pallet_matchmaking_recommendation;
+use frame_support::{
+    derive_impl, parameter_types,
+    traits::{ConstU128, ConstU16, ConstU32, ConstU64, Randomness},
+};
+use frame_system::EnsureRoot;
+use pallet_matchmaking_matching::BaziDataProvider;
+use sp_core::{sr25519, H256};
+use sp_runtime::{traits::IdentityLookup, BuildStorage, DispatchError, DispatchResult};
+
+pub type AccountId = sr25519::Public;
+pub type Balance = u128;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+type Extrinsic = sp_runtime::generic::UncheckedExtrinsic<u64, RuntimeCall, (), ()>;
+
+/// 资料保证金（无报价时的兜底金额）
+pub const PROFILE_DEPOSIT: Balance = 50;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Balances: pallet_balances,
+        Profile: pallet_matchmaking_profile,
+        Recommendation: pallet_matchmaking_recommendation,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<AccountId>;
+    type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+    type Balance = Balance;
+    type ExistentialDeposit = ConstU128<1>;
+    type RuntimeHoldReason = RuntimeHoldReason;
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+    type Public = sr25519::Public;
+    type Signature = sr25519::Signature;
+}
+
+impl<LocalCall> frame_system::offchain::CreateTransactionBase<LocalCall> for Test
+where
+    RuntimeCall: From<LocalCall>,
+{
+    type RuntimeCall = RuntimeCall;
+    type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateBare<LocalCall> for Test
+where
+    RuntimeCall: From<LocalCall>,
+{
+    fn create_bare(call: RuntimeCall) -> Extrinsic {
+        Extrinsic::new_bare(call)
+    }
+}
+
+/// 测试账户
+pub fn account(seed: u8) -> AccountId {
+    sr25519::Public::from_raw([seed; 32])
+}
+
+parameter_types! {
+    pub TreasuryAccount: AccountId = account(200);
+    pub BurnAccount: AccountId = account(201);
+    pub StorageAccount: AccountId = account(202);
+}
+
+/// 模拟随机数
+pub struct TestRandomness;
+
+impl Randomness<H256, u64> for TestRandomness {
+    fn random(subject: &[u8]) -> (H256, u64) {
+        (sp_io::hashing::blake2_256(subject).into(), System::block_number())
+    }
+}
+
+/// 模拟 IPFS 固定：始终成功
+pub struct MockIpfsPinner;
+
+impl pallet_storage_service::IpfsPinner<AccountId, Balance> for MockIpfsPinner {
+    fn pin_cid_for_subject(
+        _caller: AccountId,
+        _subject_type: pallet_storage_service::types::SubjectType,
+        _subject_id: u64,
+        _cid: Vec<u8>,
+        _tier: Option<pallet_storage_service::types::PinTier>,
+    ) -> DispatchResult {
+        Ok(())
+    }
+
+    fn unpin_cid(_caller: AccountId, _cid: Vec<u8>) -> DispatchResult {
+        Ok(())
+    }
+}
+
+/// 模拟联盟分配：全额分配
+pub struct MockAffiliateDistributor;
+
+impl pallet_affiliate::types::AffiliateDistributor<AccountId, Balance, u64> for MockAffiliateDistributor {
+    fn distribute_rewards(
+        _buyer: &AccountId,
+        amount: Balance,
+        _target: Option<(u8, u64)>,
+    ) -> Result<Balance, DispatchError> {
+        Ok(amount)
+    }
+}
+
+/// 模拟认证材料：无私密内容
+pub struct MockEvidenceAccess;
+
+impl pallet_evidence::PrivateContentProvider<AccountId> for MockEvidenceAccess {
+    fn can_access(_content_id: u64, _user: &AccountId) -> bool {
+        false
+    }
+
+    fn get_decryption_key(_content_id: u64, _user: &AccountId) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn content_owner(_content_id: u64) -> Option<AccountId> {
+        None
+    }
+}
+
+impl pallet_matchmaking_profile::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxNicknameLen = ConstU32<32>;
+    type MaxLocationLen = ConstU32<64>;
+    type MaxCidLen = ConstU32<64>;
+    type MaxBioLen = ConstU32<256>;
+    type MaxDescLen = ConstU32<256>;
+    type MaxOccupationLen = ConstU32<32>;
+    type MaxTraits = ConstU32<5>;
+    type MaxHobbies = ConstU32<5>;
+    type MaxHobbyLen = ConstU32<16>;
+    type WeightInfo = ();
+    type Fungible = Balances;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type ProfileDeposit = ConstU128<PROFILE_DEPOSIT>;
+    type ProfileDepositUsd = ConstU64<50_000_000>;
+    type MonthlyFee = ConstU128<2>;
+    type MonthlyFeeUsd = ConstU64<2_000_000>;
+    type Pricing = ();
+    type RecurringPayments = ();
+    type Reputation = ();
+    type TreasuryAccount = TreasuryAccount;
+    type BurnAccount = BurnAccount;
+    type StorageAccount = StorageAccount;
+    type AffiliateDistributor = MockAffiliateDistributor;
+    type IpfsPinner = MockIpfsPinner;
+    type GovernanceOrigin = EnsureRoot<AccountId>;
+    type BlocksPerDay = ConstU64<14_400>;
+    type EvidenceAccess = MockEvidenceAccess;
+    type VerifierStake = ConstU128<100>;
+    type VerificationFee = ConstU128<10>;
+    type VerificationCancelPenaltyBps = ConstU16<5_000>;
+    type VerificationValidity = ConstU64<1_000>;
+    type MaxVerifiers = ConstU32<10>;
+    type Randomness = TestRandomness;
+    type Balance = Balance;
+}
+
+/// 模拟八字数据：无命盘
+pub struct MockBaziProvider;
+
+impl BaziDataProvider<AccountId> for MockBaziProvider {
+    fn exists(_bazi_id: u64) -> bool {
+        false
+    }
+
+    fn is_owner(_account: &AccountId, _bazi_id: u64) -> bool {
+        false
+    }
+
+    fn get_sizhu_index(_bazi_id: u64) -> Option<pallet_bazi_chart::types::SiZhuIndex> {
+        None
+    }
+
+    fn get_interpretation(_bazi_id: u64) -> Option<pallet_bazi_chart::interpretation::CoreInterpretation> {
+        None
+    }
+
+    fn get_personality(_bazi_id: u64) -> Option<pallet_bazi_chart::interpretation::CompactXingGe> {
+        None
+    }
+
+    fn get_qiyun(_bazi_id: u64) -> Option<(bool, u8)> {
+        None
+    }
+}
+
+impl pallet_matchmaking_recommendation::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxRecommendationsPerUser = ConstU32<3>;
+    type RecommendationUpdateInterval = ConstU64<10>;
+    type BaziProvider = MockBaziProvider;
+    type MaxCandidates = ConstU32<20>;
+    type MaxPendingRefresh = ConstU32<3>;
+    type MaxRefreshPerBlock = ConstU32<1>;
+    type AuthorityId = crate::crypto::AuthorityId;
+    type AuthorityOrigin = EnsureRoot<AccountId>;
+    type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: (1..=10).map(|seed| (account(seed), 1_000)).collect(),
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
@@ -1,3 +1,336 @@
 //! # 推荐系统模块测试
 
-// TODO: 添加测试用例
+use crate::{algorithm::*, mock::*, Error, Event, PendingRefresh, RecommendationPayload, Recommendations};
+use codec::Encode;
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::Hooks,
+    weights::Weight,
+};
+use pallet_matchmaking_common::{Gender, VerificationBadge};
+use sp_core::{sr25519, Pair};
+use sp_runtime::{
+    traits::ValidateUnsigned,
+    transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidityError},
+    BoundedVec, DispatchError,
+};
+
+fn candidate(age: u8, traits: [u8; 5], last_active_block: u64) -> CandidateInfo {
+    CandidateInfo {
+        age: Some(age),
+        personality_traits: traits,
+        last_active_block,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn encode_traits_reserves_zero_for_empty_slot() {
+    assert_eq!(encode_traits([0u8, 4].into_iter()), [1, 5, 0, 0, 0]);
+    assert_eq!(encode_traits(0u8..10), [1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn apply_bazi_score_weights_overall() {
+    let mut score = MatchScoreResult { overall: 60, ..Default::default() };
+    apply_bazi_score(&mut score, None);
+    assert_eq!(score.overall, 60);
+
+    apply_bazi_score(&mut score, Some(90));
+    assert_eq!(score.bazi_score, Some(90));
+    assert_eq!(score.overall, 69);
+}
+
+#[test]
+fn score_candidate_filters_by_preferences_and_min_bazi() {
+    let prefs = UserPreferences {
+        age_range: Some((25, 30)),
+        min_bazi_score: Some(70),
+        ..Default::default()
+    };
+    let traits = [1, 0, 0, 0, 0];
+
+    assert_eq!(score_candidate(&traits, &prefs, &candidate(35, traits, 0), 0, None), None);
+    assert_eq!(score_candidate(&traits, &prefs, &candidate(28, traits, 0), 0, Some(60)), None);
+    assert!(score_candidate(&traits, &prefs, &candidate(28, traits, 0), 0, Some(80)).is_some());
+    // 无命盘时不按八字筛选
+    assert!(score_candidate(&traits, &prefs, &candidate(28, traits, 0), 0, None).is_some());
+}
+
+#[test]
+fn recommend_matches_sorts_and_truncates() {
+    let traits = [1, 2, 0, 0, 0];
+    let candidates = [
+        (1u64, candidate(28, [0; 5], 0)),
+        (2u64, candidate(28, [1, 2, 0, 0, 0], 0)),
+        (3u64, candidate(28, [1, 0, 0, 0, 0], 0)),
+    ];
+
+    let ranked = recommend_matches(&traits, &UserPreferences::default(), &candidates, 0, 2, |_, _| None);
+    assert_eq!(ranked.iter().map(|(a, _)| *a).collect::<Vec<_>>(), vec![2, 3]);
+
+    // 八字高分可提升排名
+    let ranked = recommend_matches(
+        &traits,
+        &UserPreferences::default(),
+        &candidates,
+        0,
+        1,
+        |account, _| if *account == 1 { Some(100) } else { None },
+    );
+    assert_eq!(ranked[0].0, 1);
+}
+
+#[test]
+fn verification_badge_boosts_ranking() {
+    let traits = [1, 0, 0, 0, 0];
+    let verified = CandidateInfo {
+        verification_badge: VerificationBadge::Full,
+        ..candidate(28, traits, 0)
+    };
+    let candidates = [(1u64, candidate(28, traits, 0)), (2u64, verified)];
+
+    let ranked = recommend_matches(&traits, &UserPreferences::default(), &candidates, 0, 2, |_, _| None);
+    assert_eq!(ranked[0].0, 2);
+    assert_eq!(ranked[0].1, ranked[1].1 + VerificationBadge::Full.ranking_bonus());
+}
+
+// ============================================================================
+// 刷新队列、签名提交与交易池预检
+// ============================================================================
+
+const ALICE: u8 = 1;
+const BOB: u8 = 2;
+const CAROL: u8 = 3;
+const DAVE: u8 = 4;
+const EVE: u8 = 5;
+
+fn create_profile(seed: u8, gender: Gender) {
+    assert_ok!(Profile::create_profile(
+        RuntimeOrigin::signed(account(seed)),
+        BoundedVec::try_from(b"user".to_vec()).unwrap(),
+        gender,
+        Some(28),
+        None,
+        None,
+        None,
+    ));
+}
+
+/// ALICE（女）与三位男性候选人
+fn setup_profiles() {
+    create_profile(ALICE, Gender::Female);
+    create_profile(BOB, Gender::Male);
+    create_profile(CAROL, Gender::Male);
+    create_profile(DAVE, Gender::Male);
+}
+
+fn authority() -> sr25519::Pair {
+    sr25519::Pair::from_seed(&[42u8; 32])
+}
+
+fn register(pair: &sr25519::Pair) {
+    assert_ok!(Recommendation::add_authority(RuntimeOrigin::root(), pair.public()));
+}
+
+fn payload(
+    pair: &sr25519::Pair,
+    user: u8,
+    recommendations: Vec<crate::RecommendationResult<AccountId>>,
+) -> (RecommendationPayload<sr25519::Public, AccountId>, sr25519::Signature) {
+    let payload = RecommendationPayload { public: pair.public(), user: account(user), recommendations };
+    let signature = pair.sign(&payload.encode());
+    (payload, signature)
+}
+
+fn expected(user: u8) -> Vec<crate::RecommendationResult<AccountId>> {
+    Recommendation::compute_recommendations(&account(user)).unwrap().into_inner()
+}
+
+fn validate(
+    payload: RecommendationPayload<sr25519::Public, AccountId>,
+    signature: sr25519::Signature,
+) -> Result<(), TransactionValidityError> {
+    let call = crate::Call::submit_recommendations { payload, signature };
+    Recommendation::validate_unsigned(TransactionSource::External, &call).map(|_| ())
+}
+
+#[test]
+fn refresh_enqueues_once_and_respects_interval() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Recommendation::refresh_recommendations(RuntimeOrigin::signed(account(ALICE))),
+            Error::<Test>::ProfileNotFound
+        );
+
+        setup_profiles();
+        assert_ok!(Recommendation::refresh_recommendations(RuntimeOrigin::signed(account(ALICE))));
+        assert_eq!(PendingRefresh::<Test>::get().into_inner(), vec![account(ALICE)]);
+
+        assert_noop!(
+            Recommendation::refresh_recommendations(RuntimeOrigin::signed(account(ALICE))),
+            Error::<Test>::UpdateTooFrequent
+        );
+
+        // 间隔已过但结果尚未提交：不重复入队
+        System::set_block_number(11);
+        assert_ok!(Recommendation::refresh_recommendations(RuntimeOrigin::signed(account(ALICE))));
+        assert_eq!(PendingRefresh::<Test>::get().len(), 1);
+    });
+}
+
+#[test]
+fn refresh_queue_full_is_rejected() {
+    new_test_ext().execute_with(|| {
+        setup_profiles();
+        create_profile(EVE, Gender::Female);
+        for seed in [ALICE, BOB, CAROL] {
+            assert_ok!(Recommendation::refresh_recommendations(RuntimeOrigin::signed(account(seed))));
+        }
+        assert_noop!(
+            Recommendation::refresh_recommendations(RuntimeOrigin::signed(account(EVE))),
+            Error::<Test>::RefreshQueueFull
+        );
+    });
+}
+
+#[test]
+fn deleted_profile_does_not_block_refresh_queue() {
+    new_test_ext().execute_with(|| {
+        setup_profiles();
+        create_profile(EVE, Gender::Female);
+        assert_ok!(Recommendation::refresh_recommendations(RuntimeOrigin::signed(account(BOB))));
+        assert_ok!(Recommendation::refresh_recommendations(RuntimeOrigin::signed(account(ALICE))));
+        assert_ok!(Profile::delete_profile(RuntimeOrigin::signed(account(BOB))));
+
+        // 队首用户无法计算推荐，不占用本区块名额
+        let batch = Recommendation::refresh_batch(1);
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch[0].0, account(ALICE));
+
+        // 权重不足时不处理
+        assert_eq!(Recommendation::on_idle(1, Weight::zero()), Weight::zero());
+        assert_eq!(PendingRefresh::<Test>::get().len(), 2);
+
+        Recommendation::on_idle(1, Weight::MAX);
+        assert_eq!(PendingRefresh::<Test>::get().into_inner(), vec![account(ALICE)]);
+        System::assert_has_event(Event::<Test>::RefreshDiscarded { user: account(BOB) }.into());
+
+        // 队列空出名额后其他用户可以继续排队
+        assert_ok!(Recommendation::refresh_recommendations(RuntimeOrigin::signed(account(CAROL))));
+        assert_ok!(Recommendation::refresh_recommendations(RuntimeOrigin::signed(account(EVE))));
+    });
+}
+
+#[test]
+fn submit_recommendations_requires_exact_recomputed_list() {
+    new_test_ext().execute_with(|| {
+        setup_profiles();
+        let pair = authority();
+
+        let (p, sig) = payload(&pair, ALICE, vec![]);
+        assert_noop!(
+            Recommendation::submit_recommendations(RuntimeOrigin::none(), p, sig),
+            Error::<Test>::RefreshNotRequested
+        );
+
+        assert_ok!(Recommendation::refresh_recommendations(RuntimeOrigin::signed(account(ALICE))));
+        let list = expected(ALICE);
+        assert_eq!(list.len(), 3);
+        assert!(list.iter().all(|r| r.user != account(ALICE)));
+
+        // 空列表、子集、重排与伪造分数均被拒绝
+        let mut reordered = list.clone();
+        reordered.swap(0, 2);
+        let mut inflated = list.clone();
+        inflated[0].score = inflated[0].score.wrapping_add(1);
+        for bad in [vec![], list[..1].to_vec(), reordered, inflated] {
+            let (p, sig) = payload(&pair, ALICE, bad);
+            assert_noop!(
+                Recommendation::submit_recommendations(RuntimeOrigin::none(), p, sig),
+                Error::<Test>::InvalidRecommendation
+            );
+        }
+
+        let (p, sig) = payload(&pair, ALICE, list.clone());
+        assert_noop!(
+            Recommendation::submit_recommendations(RuntimeOrigin::signed(account(ALICE)), p.clone(), sig.clone()),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Recommendation::submit_recommendations(RuntimeOrigin::none(), p, sig));
+        assert_eq!(Recommendations::<Test>::get(account(ALICE)).into_inner(), list);
+        assert!(PendingRefresh::<Test>::get().is_empty());
+        System::assert_last_event(Event::<Test>::RecommendationsUpdated { user: account(ALICE), count: 3 }.into());
+    });
+}
+
+#[test]
+fn validate_unsigned_only_accepts_pending_authority_payloads() {
+    new_test_ext().execute_with(|| {
+        setup_profiles();
+        let pair = authority();
+        assert_ok!(Recommendation::refresh_recommendations(RuntimeOrigin::signed(account(ALICE))));
+        let list = expected(ALICE);
+
+        // 未注册的节点
+        let (p, sig) = payload(&pair, ALICE, list.clone());
+        assert_eq!(validate(p, sig), Err(InvalidTransaction::BadSigner.into()));
+
+        register(&pair);
+
+        // 签名与负载不符
+        let (p, _) = payload(&pair, ALICE, list.clone());
+        let (_, other_sig) = payload(&pair, BOB, list.clone());
+        assert_eq!(validate(p, other_sig), Err(InvalidTransaction::BadProof.into()));
+
+        // 他人密钥冒用已注册节点公钥
+        let (mut p, sig) = payload(&sr25519::Pair::from_seed(&[7u8; 32]), ALICE, list.clone());
+        p.public = pair.public();
+        assert_eq!(validate(p, sig), Err(InvalidTransaction::BadProof.into()));
+
+        // 用户未请求刷新
+        let (p, sig) = payload(&pair, BOB, list.clone());
+        assert_eq!(validate(p, sig), Err(InvalidTransaction::Stale.into()));
+
+        // 超过推荐数上限
+        let mut oversized = list.clone();
+        oversized.push(list[0].clone());
+        let (p, sig) = payload(&pair, ALICE, oversized);
+        assert_eq!(validate(p, sig), Err(InvalidTransaction::ExhaustsResources.into()));
+
+        let (p, sig) = payload(&pair, ALICE, list);
+        assert_ok!(validate(p, sig));
+
+        // 移除节点后不再接受
+        assert_ok!(Recommendation::remove_authority(RuntimeOrigin::root(), pair.public()));
+        let (p, sig) = payload(&pair, ALICE, expected(ALICE));
+        assert_eq!(validate(p, sig), Err(InvalidTransaction::BadSigner.into()));
+    });
+}
+
+#[test]
+fn authorities_are_managed_by_governance() {
+    new_test_ext().execute_with(|| {
+        let node = authority().public();
+        assert_noop!(
+            Recommendation::add_authority(RuntimeOrigin::signed(account(ALICE)), node),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Recommendation::remove_authority(RuntimeOrigin::root(), node),
+            Error::<Test>::NotAuthority
+        );
+
+        assert_ok!(Recommendation::add_authority(RuntimeOrigin::root(), node));
+        assert!(Recommendation::authorities(node).is_some());
+        System::assert_last_event(Event::<Test>::AuthorityAdded { authority: node }.into());
+        assert_noop!(
+            Recommendation::add_authority(RuntimeOrigin::root(), node),
+            Error::<Test>::AlreadyAuthority
+        );
+
+        assert_ok!(Recommendation::remove_authority(RuntimeOrigin::root(), node));
+        assert!(Recommendation::authorities(node).is_none());
+        System::assert_last_event(Event::<Test>::AuthorityRemoved { authority: node }.into());
+    });
+}
@@ -10,6 +10,8 @@ pub trait WeightInfo {
     fn refresh_recommendations() -> Weight;
     fn clear_recommendations() -> Weight;
     fn report_user() -> Weight;
+    fn submit_recommendations(n: u32) -> Weight;
+    fn set_authority() -> Weight;
 }
 
 /// Substrate 权重实现
@@ -35,6 +37,19 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(2))
             .saturating_add(T::DbWeight::get().writes(1))
     }
+    /// 按候选集上限 `n` 计：链上重算推荐列表时每个候选读取资料、封禁、暂停、双方命盘与解盘
+    fn submit_recommendations(n: u32) -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(Weight::from_parts(25_000_000, 0).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().reads(7).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn set_authority() -> Weight {
+        Weight::from_parts(15_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
 }
 
 /// 默认权重实现（用于测试）
@@ -43,4 +58,8 @@ impl WeightInfo for () {
     fn refresh_recommendations() -> Weight { Weight::from_parts(100_000_000, 0) }
     fn clear_recommendations() -> Weight { Weight::from_parts(20_000_000, 0) }
     fn report_user() -> Weight { Weight::from_parts(30_000_000, 0) }
+    fn submit_recommendations(n: u32) -> Weight {
+        Weight::from_parts(30_000_000, 0).saturating_add(Weight::from_parts(25_000_000, 0).saturating_mul(n as u64))
+    }
+    fn set_authority() -> Weight { Weight::from_parts(15_000_000, 0) }
 }
@@ -0,0 +1,90 @@
+//! # 通用周期扣费基准测试
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+
+fn approve_and_schedule<T: Config>(payer: &T::AccountId, count: u32) -> Vec<u64> {
+    let service = RecurringService::StorageService;
+    let _ = Pallet::<T>::approve(
+        RawOrigin::Signed(payer.clone()).into(),
+        service,
+        T::Balance::from(1_000u32),
+        None,
+    );
+    (0..count)
+        .filter_map(|subject| {
+            Pallet::<T>::schedule(
+                payer,
+                service,
+                subject as u64,
+                PaymentPrice::Fixed(T::Balance::from(100u32)),
+                T::MinPeriod::get(),
+                T::MinPeriod::get(),
+            )
+            .ok()
+        })
+        .collect()
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn approve() {
+        let caller: T::AccountId = whitelisted_caller();
+
+        #[extrinsic_call]
+        _(
+            RawOrigin::Signed(caller),
+            RecurringService::StorageService,
+            T::Balance::from(1_000u32),
+            Some(T::Balance::from(10_000u32)),
+        );
+    }
+
+    #[benchmark]
+    fn revoke_approval(s: Linear<0, { T::MaxSchedulesPerPayer::get() }>) {
+        let caller: T::AccountId = whitelisted_caller();
+        approve_and_schedule::<T>(&caller, s);
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), RecurringService::StorageService);
+    }
+
+    #[benchmark]
+    fn cancel_schedule() {
+        let caller: T::AccountId = whitelisted_caller();
+        let schedule_id = approve_and_schedule::<T>(&caller, 1)[0];
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), schedule_id);
+    }
+
+    #[benchmark]
+    fn scan_block() {
+        let now = frame_system::Pallet::<T>::block_number();
+
+        #[block]
+        {
+            let _ = DueIndex::<T>::iter_key_prefix(now).next();
+        }
+    }
+
+    #[benchmark]
+    fn process_payment() {
+        let caller: T::AccountId = whitelisted_caller();
+        let schedule_id = approve_and_schedule::<T>(&caller, 1)[0];
+        let due = Schedules::<T>::get(schedule_id).map(|s| s.next_due).unwrap_or_default();
+
+        #[block]
+        {
+            Pallet::<T>::process_due(due, schedule_id, due);
+        }
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,602 @@
+//! # 通用周期扣费模块 (pallet-recurring-payment)
+//!
+//! 为占卜会员、婚恋会员、婚恋资料月费与存储充值提供统一的周期扣费引擎，
+//! 取代各模块各自实现（且未真正执行）的自动续费。
+//!
+//! ## 功能概述
+//!
+//! 1. **付款授权**：付款人按服务设置单期与累计扣费上限，可随时撤销
+//! 2. **扣费计划**：消费方通过 `RecurringPayments` trait 创建 / 取消计划，
+//!    计划按 (付款人, 服务, 标识) 建立索引
+//! 3. **USD 锚定**：每期按 `pallet-trading-pricing` 实时汇率换算 DUST，汇率不可用时使用兜底金额
+//! 4. **重试与宽限期**：扣费失败后每隔 `RetryInterval` 重试，超过 `GracePeriod` 后计划终止
+//! 5. **回调**：到期时回调消费方完成转账与续期，终止时通知消费方关闭自动续费
+//!
+//! ## 消费方
+//!
+//! ```text
+//!   pallet-divination-membership ─┐
+//!   pallet-matchmaking-membership ┤  RecurringPayments trait   ┌─────────────────────────┐
+//!   pallet-matchmaking-profile ───┼──────────────────────────▶ │ pallet-recurring-payment│
+//!   pallet-storage-service ───────┘                            └────────────┬────────────┘
+//!                                                                           │ RecurringPaymentHandler
+//!                                         on_payment_due / on_payment_lapsed ◀─┘
+//! ```
+//!
+//! ## 使用示例
+//!
+//! ```ignore
+//! RecurringPayment::approve(origin, RecurringService::MatchmakingMembership, max_per_payment, None)?;
+//! MatchmakingMembership::subscribe(origin, SubscriptionDuration::OneMonth, true, None)?;
+//! RecurringPayment::cancel_schedule(origin, schedule_id)?;
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+pub mod traits;
+pub mod types;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use pallet_trading_common::PricingProvider;
+use sp_runtime::traits::{AtLeast32BitUnsigned, One, Saturating, Zero};
+use sp_std::vec::Vec;
+
+use crate::traits::{RecurringPaymentHandler, RecurringPayments};
+use crate::types::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use crate::weights::WeightInfo;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Pallet 配置 trait
+    #[pallet::config]
+    pub trait Config: frame_system::Config<RuntimeEvent: From<Event<Self>>> {
+        /// 余额类型
+        type Balance: Parameter + AtLeast32BitUnsigned + Default + Copy + MaxEncodedLen;
+
+        /// 定价接口（USD 锚定金额换算）
+        type Pricing: PricingProvider<Self::Balance>;
+
+        /// 扣费回调（按服务分发到各消费方）
+        type Handler: RecurringPaymentHandler<Self::AccountId, Self::Balance>;
+
+        /// 每个付款人最多扣费计划数
+        #[pallet::constant]
+        type MaxSchedulesPerPayer: Get<u32>;
+
+        /// 每区块最多处理的扣费数（含到期队列扫描）
+        #[pallet::constant]
+        type MaxChargesPerBlock: Get<u32>;
+
+        /// 最短扣费周期（区块数）
+        #[pallet::constant]
+        type MinPeriod: Get<BlockNumberFor<Self>>;
+
+        /// 扣费失败后的重试间隔（区块数）
+        #[pallet::constant]
+        type RetryInterval: Get<BlockNumberFor<Self>>;
+
+        /// 宽限期（自首次失败起算，区块数）
+        #[pallet::constant]
+        type GracePeriod: Get<BlockNumberFor<Self>>;
+
+        /// 权重信息
+        type WeightInfo: WeightInfo;
+    }
+
+    /// 付款授权类型
+    pub type ApprovalOf<T> = PaymentApproval<<T as Config>::Balance, BlockNumberFor<T>>;
+
+    /// 扣费计划类型
+    pub type ScheduleOf<T> =
+        PaymentSchedule<<T as frame_system::Config>::AccountId, <T as Config>::Balance, BlockNumberFor<T>>;
+
+    // ========================================================================
+    // 存储定义
+    // ========================================================================
+
+    /// 付款授权
+    ///
+    /// (payer, service) -> PaymentApproval
+    #[pallet::storage]
+    #[pallet::getter(fn approvals)]
+    pub type Approvals<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        RecurringService,
+        ApprovalOf<T>,
+    >;
+
+    /// 下一个计划 ID
+    #[pallet::storage]
+    pub type NextScheduleId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// 扣费计划
+    #[pallet::storage]
+    #[pallet::getter(fn schedules)]
+    pub type Schedules<T: Config> = StorageMap<_, Twox64Concat, u64, ScheduleOf<T>>;
+
+    /// 计划索引
+    ///
+    /// ((payer, service), subject) -> schedule_id
+    #[pallet::storage]
+    pub type ScheduleIndex<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        (T::AccountId, RecurringService),
+        Twox64Concat,
+        u64,
+        u64,
+    >;
+
+    /// 付款人计划数
+    #[pallet::storage]
+    pub type PayerScheduleCount<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+    /// 到期队列
+    ///
+    /// (block, schedule_id) -> ()
+    #[pallet::storage]
+    pub type DueIndex<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        Twox64Concat,
+        u64,
+        (),
+    >;
+
+    /// 到期队列游标：早于该区块的队列已处理完毕
+    #[pallet::storage]
+    pub type DueCursor<T: Config> = StorageValue<_, BlockNumberFor<T>>;
+
+    // ========================================================================
+    // 事件
+    // ========================================================================
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// 付款授权已设置
+        ApprovalSet {
+            payer: T::AccountId,
+            service: RecurringService,
+            max_per_payment: T::Balance,
+            max_total: Option<T::Balance>,
+        },
+        /// 付款授权已撤销，相关计划已终止
+        ApprovalRevoked {
+            payer: T::AccountId,
+            service: RecurringService,
+            lapsed: u32,
+        },
+        /// 扣费计划已创建
+        ScheduleCreated {
+            schedule_id: u64,
+            payer: T::AccountId,
+            service: RecurringService,
+            subject: u64,
+            first_due: BlockNumberFor<T>,
+        },
+        /// 扣费成功
+        PaymentSucceeded {
+            schedule_id: u64,
+            payer: T::AccountId,
+            service: RecurringService,
+            amount: T::Balance,
+            next_due: BlockNumberFor<T>,
+        },
+        /// 扣费失败；`retry_at` 为 None 表示宽限期已过
+        PaymentFailed {
+            schedule_id: u64,
+            payer: T::AccountId,
+            service: RecurringService,
+            amount: T::Balance,
+            reason: PaymentFailure,
+            attempts: u32,
+            retry_at: Option<BlockNumberFor<T>>,
+        },
+        /// 扣费计划已终止（已通知消费方）
+        ScheduleLapsed {
+            schedule_id: u64,
+            payer: T::AccountId,
+            service: RecurringService,
+            subject: u64,
+            reason: LapseReason,
+        },
+        /// 扣费计划已由消费方取消
+        ScheduleCancelled {
+            schedule_id: u64,
+            payer: T::AccountId,
+            service: RecurringService,
+            subject: u64,
+        },
+    }
+
+    // ========================================================================
+    // 错误
+    // ========================================================================
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// 单期上限不能为零
+        InvalidApproval,
+        /// 未找到付款授权
+        ApprovalNotFound,
+        /// 扣费周期过短
+        PeriodTooShort,
+        /// 计划已存在
+        ScheduleExists,
+        /// 计划不存在
+        ScheduleNotFound,
+        /// 不是计划的付款人
+        NotPayer,
+        /// 计划数已达上限
+        TooManySchedules,
+        /// 计划 ID 溢出
+        ScheduleIdOverflow,
+    }
+
+    // ========================================================================
+    // Hooks
+    // ========================================================================
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// 从游标处依次处理到期队列，每区块最多 `MaxChargesPerBlock` 项（含空区块扫描）
+        fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+            let mut weight = T::DbWeight::get().reads_writes(1, 1);
+            let mut budget = T::MaxChargesPerBlock::get();
+            let mut block = DueCursor::<T>::get().unwrap_or(now);
+
+            while block <= now && budget > 0 {
+                budget -= 1;
+                weight = weight.saturating_add(T::WeightInfo::scan_block());
+
+                let due: Vec<u64> = DueIndex::<T>::iter_key_prefix(block)
+                    .take(budget as usize + 1)
+                    .collect();
+                let drained = due.len() <= budget as usize;
+                for schedule_id in due.into_iter().take(budget as usize) {
+                    budget -= 1;
+                    weight = weight
+                        .saturating_add(T::WeightInfo::process_payment())
+                        .saturating_add(T::Handler::max_weight());
+                    Self::process_due(block, schedule_id, now);
+                }
+
+                if !drained {
+                    break;
+                }
+                block = block.saturating_add(One::one());
+            }
+
+            DueCursor::<T>::put(block);
+            weight
+        }
+    }
+
+    // ========================================================================
+    // 交易调用
+    // ========================================================================
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// 设置付款授权
+        ///
+        /// 授权该服务按计划从调用者账户扣费；重新授权会覆盖上限并重置累计额。
+        ///
+        /// # 参数
+        /// - `origin`: 付款人
+        /// - `service`: 服务
+        /// - `max_per_payment`: 单期扣费上限
+        /// - `max_total`: 累计扣费上限（None 表示不限）
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::approve())]
+        pub fn approve(
+            origin: OriginFor<T>,
+            service: RecurringService,
+            max_per_payment: T::Balance,
+            max_total: Option<T::Balance>,
+        ) -> DispatchResult {
+            let payer = ensure_signed(origin)?;
+            ensure!(!max_per_payment.is_zero(), Error::<T>::InvalidApproval);
+
+            Approvals::<T>::insert(
+                &payer,
+                service,
+                PaymentApproval {
+                    max_per_payment,
+                    max_total,
+                    spent: Zero::zero(),
+                    approved_at: frame_system::Pallet::<T>::block_number(),
+                },
+            );
+
+            Self::deposit_event(Event::ApprovalSet { payer, service, max_per_payment, max_total });
+
+            Ok(())
+        }
+
+        /// 撤销付款授权
+        ///
+        /// 该服务下的全部计划立即终止，并以 `ApprovalRevoked` 通知消费方。
+        #[pallet::call_index(1)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::revoke_approval(T::MaxSchedulesPerPayer::get())
+                .saturating_add(T::Handler::max_weight().saturating_mul(T::MaxSchedulesPerPayer::get().into()))
+        )]
+        pub fn revoke_approval(origin: OriginFor<T>, service: RecurringService) -> DispatchResult {
+            let payer = ensure_signed(origin)?;
+            ensure!(Approvals::<T>::contains_key(&payer, service), Error::<T>::ApprovalNotFound);
+
+            Approvals::<T>::remove(&payer, service);
+
+            let schedule_ids: Vec<u64> =
+                ScheduleIndex::<T>::iter_prefix_values((payer.clone(), service)).collect();
+            let mut lapsed = 0u32;
+            for schedule_id in schedule_ids {
+                if let Some(schedule) = Schedules::<T>::get(schedule_id) {
+                    Self::lapse(schedule_id, schedule, LapseReason::ApprovalRevoked);
+                    lapsed += 1;
+                }
+            }
+
+            Self::deposit_event(Event::ApprovalRevoked { payer, service, lapsed });
+
+            Ok(())
+        }
+
+        /// 付款人取消扣费计划
+        ///
+        /// 以 `CancelledByPayer` 通知消费方（如关闭自动续费）。
+        #[pallet::call_index(2)]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::cancel_schedule().saturating_add(T::Handler::max_weight())
+        )]
+        pub fn cancel_schedule(origin: OriginFor<T>, schedule_id: u64) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let schedule = Schedules::<T>::get(schedule_id).ok_or(Error::<T>::ScheduleNotFound)?;
+            ensure!(schedule.payer == who, Error::<T>::NotPayer);
+
+            Self::lapse(schedule_id, schedule, LapseReason::CancelledByPayer);
+
+            Ok(())
+        }
+    }
+
+    // ========================================================================
+    // 内部函数
+    // ========================================================================
+
+    impl<T: Config> Pallet<T> {
+        /// 按 (付款人, 服务, 标识) 查询计划
+        pub fn schedule_of(
+            payer: &T::AccountId,
+            service: RecurringService,
+            subject: u64,
+        ) -> Option<(u64, ScheduleOf<T>)> {
+            let schedule_id = ScheduleIndex::<T>::get((payer.clone(), service), subject)?;
+            Schedules::<T>::get(schedule_id).map(|schedule| (schedule_id, schedule))
+        }
+
+        /// 处理一个到期项：扣费成功则进入下一期，失败则在宽限期内重试
+        pub(crate) fn process_due(queued_at: BlockNumberFor<T>, schedule_id: u64, now: BlockNumberFor<T>) {
+            DueIndex::<T>::remove(queued_at, schedule_id);
+            let Some(mut schedule) = Schedules::<T>::get(schedule_id) else {
+                return;
+            };
+
+            let amount = schedule.price.resolve::<T::Pricing>();
+            let result = Self::try_charge(&schedule, amount);
+
+            // 回调中消费方已取消计划
+            if !Schedules::<T>::contains_key(schedule_id) {
+                return;
+            }
+
+            match result {
+                Ok(()) => {
+                    let mut next_due = schedule.next_due.saturating_add(schedule.period);
+                    if next_due <= now {
+                        next_due = now.saturating_add(schedule.period);
+                    }
+                    schedule.next_due = next_due;
+                    schedule.retry = None;
+                    schedule.payments_made = schedule.payments_made.saturating_add(1);
+                    let payer = schedule.payer.clone();
+                    let service = schedule.service;
+                    Self::enqueue(schedule_id, schedule, next_due);
+
+                    Self::deposit_event(Event::PaymentSucceeded {
+                        schedule_id,
+                        payer,
+                        service,
+                        amount,
+                        next_due,
+                    });
+                },
+                Err(reason) => {
+                    let retry = schedule
+                        .retry
+                        .unwrap_or(RetryState { first_failed_at: now, attempts: 0 });
+                    let attempts = retry.attempts.saturating_add(1);
+                    let retry_at = now.saturating_add(T::RetryInterval::get());
+                    let grace_ends = retry.first_failed_at.saturating_add(T::GracePeriod::get());
+                    let retry_at = (retry_at <= grace_ends).then_some(retry_at);
+
+                    Self::deposit_event(Event::PaymentFailed {
+                        schedule_id,
+                        payer: schedule.payer.clone(),
+                        service: schedule.service,
+                        amount,
+                        reason,
+                        attempts,
+                        retry_at,
+                    });
+
+                    match retry_at {
+                        Some(retry_at) => {
+                            schedule.retry =
+                                Some(RetryState { first_failed_at: retry.first_failed_at, attempts });
+                            Self::enqueue(schedule_id, schedule, retry_at);
+                        },
+                        None => Self::lapse(schedule_id, schedule, LapseReason::GraceExpired),
+                    }
+                },
+            }
+        }
+
+        /// 校验授权并回调消费方扣费（回调在独立存储层中执行）
+        fn try_charge(schedule: &ScheduleOf<T>, amount: T::Balance) -> Result<(), PaymentFailure> {
+            let approval = Approvals::<T>::get(&schedule.payer, schedule.service)
+                .ok_or(PaymentFailure::NoApproval)?;
+            ensure!(approval.allows(amount), PaymentFailure::CapExceeded);
+
+            frame_support::storage::with_storage_layer::<(), DispatchError, _>(|| {
+                T::Handler::on_payment_due(schedule.service, &schedule.payer, schedule.subject, amount)
+            })
+            .map_err(|_| PaymentFailure::ChargeFailed)?;
+
+            Approvals::<T>::mutate(&schedule.payer, schedule.service, |maybe_approval| {
+                if let Some(approval) = maybe_approval {
+                    approval.spent = approval.spent.saturating_add(amount);
+                }
+            });
+            Ok(())
+        }
+
+        /// 将计划放入到期队列
+        fn enqueue(schedule_id: u64, mut schedule: ScheduleOf<T>, at: BlockNumberFor<T>) {
+            schedule.queued_at = at;
+            DueIndex::<T>::insert(at, schedule_id, ());
+            Schedules::<T>::insert(schedule_id, schedule);
+        }
+
+        /// 删除计划及其索引
+        fn remove_schedule(schedule_id: u64, schedule: &ScheduleOf<T>) {
+            Schedules::<T>::remove(schedule_id);
+            DueIndex::<T>::remove(schedule.queued_at, schedule_id);
+            ScheduleIndex::<T>::remove((schedule.payer.clone(), schedule.service), schedule.subject);
+            PayerScheduleCount::<T>::mutate_exists(&schedule.payer, |count| {
+                *count = count.and_then(|c| c.checked_sub(1)).filter(|c| *c > 0);
+            });
+        }
+
+        /// 终止计划并通知消费方
+        fn lapse(schedule_id: u64, schedule: ScheduleOf<T>, reason: LapseReason) {
+            Self::remove_schedule(schedule_id, &schedule);
+            T::Handler::on_payment_lapsed(schedule.service, &schedule.payer, schedule.subject, reason);
+
+            Self::deposit_event(Event::ScheduleLapsed {
+                schedule_id,
+                payer: schedule.payer,
+                service: schedule.service,
+                subject: schedule.subject,
+                reason,
+            });
+        }
+    }
+
+    impl<T: Config> RecurringPayments<T::AccountId, T::Balance, BlockNumberFor<T>> for Pallet<T> {
+        fn schedule(
+            payer: &T::AccountId,
+            service: RecurringService,
+            subject: u64,
+            price: PaymentPrice<T::Balance>,
+            period: BlockNumberFor<T>,
+            first_due: BlockNumberFor<T>,
+        ) -> Result<u64, DispatchError> {
+            ensure!(period >= T::MinPeriod::get(), Error::<T>::PeriodTooShort);
+            ensure!(
+                !ScheduleIndex::<T>::contains_key((payer.clone(), service), subject),
+                Error::<T>::ScheduleExists
+            );
+            let count = PayerScheduleCount::<T>::get(payer);
+            ensure!(count < T::MaxSchedulesPerPayer::get(), Error::<T>::TooManySchedules);
+
+            let schedule_id = NextScheduleId::<T>::get();
+            NextScheduleId::<T>::put(
+                schedule_id.checked_add(1).ok_or(Error::<T>::ScheduleIdOverflow)?,
+            );
+
+            // 本区块的到期队列已处理，最早从下一区块开始
+            let now = frame_system::Pallet::<T>::block_number();
+            let first_due = first_due.max(now.saturating_add(One::one()));
+
+            let schedule = PaymentSchedule {
+                payer: payer.clone(),
+                service,
+                subject,
+                price,
+                period,
+                next_due: first_due,
+                queued_at: first_due,
+                retry: None,
+                payments_made: 0,
+                created_at: now,
+            };
+            Self::enqueue(schedule_id, schedule, first_due);
+            ScheduleIndex::<T>::insert((payer.clone(), service), subject, schedule_id);
+            PayerScheduleCount::<T>::insert(payer, count.saturating_add(1));
+
+            Self::deposit_event(Event::ScheduleCreated {
+                schedule_id,
+                payer: payer.clone(),
+                service,
+                subject,
+                first_due,
+            });
+
+            Ok(schedule_id)
+        }
+
+        fn cancel(payer: &T::AccountId, service: RecurringService, subject: u64) -> bool {
+            let Some((schedule_id, schedule)) = Self::schedule_of(payer, service, subject) else {
+                return false;
+            };
+            Self::remove_schedule(schedule_id, &schedule);
+
+            Self::deposit_event(Event::ScheduleCancelled {
+                schedule_id,
+                payer: payer.clone(),
+                service,
+                subject,
+            });
+            true
+        }
+
+        fn cancel_all(payer: &T::AccountId, service: RecurringService) -> u32 {
+            let subjects: Vec<u64> =
+                ScheduleIndex::<T>::iter_key_prefix((payer.clone(), service)).collect();
+            subjects
+                .into_iter()
+                .filter(|subject| Self::cancel(payer, service, *subject))
+                .count() as u32
+        }
+
+        fn is_scheduled(payer: &T::AccountId, service: RecurringService, subject: u64) -> bool {
+            ScheduleIndex::<T>::contains_key((payer.clone(), service), subject)
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! # 通用周期扣费 - 测试 Mock
+
+use crate as pallet_recurring_payment;
+use crate::traits::RecurringPaymentHandler;
+use crate::types::{LapseReason, RecurringService};
+use frame_support::{
+    derive_impl, parameter_types,
+    traits::{ConstU32, Hooks},
+};
+use sp_runtime::{BuildStorage, DispatchError, DispatchResult};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        RecurringPayment: pallet_recurring_payment,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = ();
+}
+
+parameter_types! {
+    pub const MinPeriod: u64 = 10;
+    pub const RetryInterval: u64 = 5;
+    pub const GracePeriod: u64 = 12;
+    /// DUST/USD 汇率（精度 10^6），None 表示不可用
+    pub static DustRate: Option<u128> = None;
+    /// 扣费失败的付款人
+    pub static FailingPayers: Vec<u64> = Vec::new();
+    /// 记录扣费回调：(服务, 付款人, 标识, 金额)
+    pub static Charges: Vec<(RecurringService, u64, u64, u128)> = Vec::new();
+    /// 记录终止回调：(服务, 付款人, 标识, 原因)
+    pub static Lapses: Vec<(RecurringService, u64, u64, LapseReason)> = Vec::new();
+}
+
+/// Mock 定价接口
+pub struct MockPricing;
+
+impl pallet_trading_common::PricingProvider<u128> for MockPricing {
+    fn get_dust_to_usd_rate() -> Option<u128> {
+        DustRate::get()
+    }
+
+    fn report_swap_order(_timestamp: u64, _price_usdt: u64, _dust_qty: u128) -> DispatchResult {
+        Ok(())
+    }
+}
+
+/// 记录回调；`FailingPayers` 中的付款人扣费失败
+pub struct RecordPayments;
+
+impl RecurringPaymentHandler<u64, u128> for RecordPayments {
+    fn on_payment_due(
+        service: RecurringService,
+        payer: &u64,
+        subject: u64,
+        amount: u128,
+    ) -> DispatchResult {
+        Charges::mutate(|charges| charges.push((service, *payer, subject, amount)));
+        if FailingPayers::get().contains(payer) {
+            return Err(DispatchError::Other("insufficient balance"));
+        }
+        Ok(())
+    }
+
+    fn on_payment_lapsed(service: RecurringService, payer: &u64, subject: u64, reason: LapseReason) {
+        Lapses::mutate(|lapses| lapses.push((service, *payer, subject, reason)));
+    }
+}
+
+impl pallet_recurring_payment::Config for Test {
+    type Balance = u128;
+    type Pricing = MockPricing;
+    type Handler = RecordPayments;
+    type MaxSchedulesPerPayer = ConstU32<4>;
+    type MaxChargesPerBlock = ConstU32<4>;
+    type MinPeriod = MinPeriod;
+    type RetryInterval = RetryInterval;
+    type GracePeriod = GracePeriod;
+    type WeightInfo = ();
+}
+
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+
+/// 推进到指定区块（执行 `on_initialize`）
+pub fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        System::set_block_number(System::block_number() + 1);
+        RecurringPayment::on_initialize(System::block_number());
+    }
+}
+
+/// 构建测试外部性
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        DustRate::take();
+        FailingPayers::take();
+        Charges::take();
+        Lapses::take();
+    });
+    ext
+}
@@ -0,0 +1,321 @@
+//! # 通用周期扣费 - 单元测试
+
+use crate::{mock::*, traits::RecurringPayments as _, types::*, Approvals, Error, Event, Schedules};
+use frame_support::{assert_noop, assert_ok};
+
+const DUST: u128 = 1_000_000_000_000;
+
+fn approve(who: u64, service: RecurringService, max_per_payment: u128, max_total: Option<u128>) {
+    assert_ok!(RecurringPayment::approve(
+        RuntimeOrigin::signed(who),
+        service,
+        max_per_payment,
+        max_total,
+    ));
+}
+
+fn schedule_fixed(who: u64, subject: u64, amount: u128, first_due: u64) -> u64 {
+    RecurringPayment::schedule(
+        &who,
+        RecurringService::MatchmakingMembership,
+        subject,
+        PaymentPrice::Fixed(amount),
+        10,
+        first_due,
+    )
+    .unwrap()
+}
+
+#[test]
+fn approve_and_revoke_works() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            RecurringPayment::approve(
+                RuntimeOrigin::signed(ALICE),
+                RecurringService::StorageService,
+                0,
+                None,
+            ),
+            Error::<Test>::InvalidApproval
+        );
+
+        approve(ALICE, RecurringService::StorageService, 10 * DUST, Some(100 * DUST));
+        let approval = Approvals::<Test>::get(ALICE, RecurringService::StorageService).unwrap();
+        assert_eq!(approval.max_total, Some(100 * DUST));
+        assert_eq!(approval.spent, 0);
+
+        assert_ok!(RecurringPayment::revoke_approval(
+            RuntimeOrigin::signed(ALICE),
+            RecurringService::StorageService,
+        ));
+        assert!(Approvals::<Test>::get(ALICE, RecurringService::StorageService).is_none());
+        assert_noop!(
+            RecurringPayment::revoke_approval(
+                RuntimeOrigin::signed(ALICE),
+                RecurringService::StorageService,
+            ),
+            Error::<Test>::ApprovalNotFound
+        );
+    });
+}
+
+#[test]
+fn schedule_validates_period_and_limits() {
+    new_test_ext().execute_with(|| {
+        let service = RecurringService::MatchmakingMembership;
+        assert_noop!(
+            RecurringPayment::schedule(&ALICE, service, 1, PaymentPrice::Fixed(DUST), 9, 10),
+            Error::<Test>::PeriodTooShort
+        );
+
+        for subject in 0..4 {
+            schedule_fixed(ALICE, subject, DUST, 10);
+        }
+        assert_noop!(
+            RecurringPayment::schedule(&ALICE, service, 0, PaymentPrice::Fixed(DUST), 10, 10),
+            Error::<Test>::ScheduleExists
+        );
+        assert_noop!(
+            RecurringPayment::schedule(&ALICE, service, 4, PaymentPrice::Fixed(DUST), 10, 10),
+            Error::<Test>::TooManySchedules
+        );
+
+        // 早于下一区块的首期按下一区块处理
+        assert!(RecurringPayment::cancel(&ALICE, service, 0));
+        let schedule_id = schedule_fixed(ALICE, 0, DUST, 0);
+        assert_eq!(Schedules::<Test>::get(schedule_id).unwrap().next_due, 2);
+    });
+}
+
+#[test]
+fn due_payment_charges_and_advances() {
+    new_test_ext().execute_with(|| {
+        approve(ALICE, RecurringService::MatchmakingMembership, 10 * DUST, None);
+        let schedule_id = schedule_fixed(ALICE, 3, 5 * DUST, 10);
+
+        run_to_block(9);
+        assert!(Charges::get().is_empty());
+
+        run_to_block(10);
+        assert_eq!(
+            Charges::get(),
+            vec![(RecurringService::MatchmakingMembership, ALICE, 3, 5 * DUST)]
+        );
+        System::assert_last_event(
+            Event::PaymentSucceeded {
+                schedule_id,
+                payer: ALICE,
+                service: RecurringService::MatchmakingMembership,
+                amount: 5 * DUST,
+                next_due: 20,
+            }
+            .into(),
+        );
+
+        run_to_block(20);
+        let schedule = Schedules::<Test>::get(schedule_id).unwrap();
+        assert_eq!(schedule.payments_made, 2);
+        assert_eq!(schedule.next_due, 30);
+        assert_eq!(
+            Approvals::<Test>::get(ALICE, RecurringService::MatchmakingMembership).unwrap().spent,
+            10 * DUST
+        );
+    });
+}
+
+#[test]
+fn usd_pegged_price_follows_rate() {
+    new_test_ext().execute_with(|| {
+        let price = PaymentPrice::UsdPegged { usd_amount: 10_000_000, fallback: 7 * DUST };
+        assert_eq!(price.resolve::<MockPricing>(), 7 * DUST);
+
+        // 1 DUST = 0.5 USD → 10 USD = 20 DUST
+        DustRate::set(Some(500_000));
+        assert_eq!(price.resolve::<MockPricing>(), 20 * DUST);
+
+        approve(ALICE, RecurringService::MatchmakingProfile, 50 * DUST, None);
+        assert_ok!(RecurringPayment::schedule(
+            &ALICE,
+            RecurringService::MatchmakingProfile,
+            0,
+            price,
+            10,
+            5,
+        ));
+        run_to_block(5);
+        assert_eq!(Charges::get()[0].3, 20 * DUST);
+    });
+}
+
+#[test]
+fn failed_payment_retries_within_grace_period() {
+    new_test_ext().execute_with(|| {
+        approve(BOB, RecurringService::MatchmakingMembership, 10 * DUST, None);
+        let schedule_id = schedule_fixed(BOB, 1, DUST, 10);
+        FailingPayers::set(vec![BOB]);
+
+        run_to_block(10);
+        System::assert_last_event(
+            Event::PaymentFailed {
+                schedule_id,
+                payer: BOB,
+                service: RecurringService::MatchmakingMembership,
+                amount: DUST,
+                reason: PaymentFailure::ChargeFailed,
+                attempts: 1,
+                retry_at: Some(15),
+            }
+            .into(),
+        );
+        // 失败扣费的写入已回滚，授权累计额不变
+        assert_eq!(
+            Approvals::<Test>::get(BOB, RecurringService::MatchmakingMembership).unwrap().spent,
+            0
+        );
+
+        FailingPayers::take();
+        run_to_block(15);
+        let schedule = Schedules::<Test>::get(schedule_id).unwrap();
+        assert_eq!(schedule.retry, None);
+        assert_eq!(schedule.payments_made, 1);
+        // 按原周期推进，不因重试而顺延
+        assert_eq!(schedule.next_due, 20);
+        assert!(Lapses::get().is_empty());
+    });
+}
+
+#[test]
+fn failed_payment_lapses_after_grace_period() {
+    new_test_ext().execute_with(|| {
+        approve(BOB, RecurringService::MatchmakingMembership, 10 * DUST, None);
+        let schedule_id = schedule_fixed(BOB, 1, DUST, 10);
+        FailingPayers::set(vec![BOB]);
+
+        // 10 首次失败，15、20 重试；下次重试 25 超出宽限期（10 + 12）
+        run_to_block(19);
+        assert_eq!(Schedules::<Test>::get(schedule_id).unwrap().retry.unwrap().attempts, 2);
+        assert!(Lapses::get().is_empty());
+
+        run_to_block(20);
+        assert_eq!(Charges::get().len(), 3);
+        assert!(Schedules::<Test>::get(schedule_id).is_none());
+        assert!(!RecurringPayment::is_scheduled(&BOB, RecurringService::MatchmakingMembership, 1));
+        assert_eq!(
+            Lapses::get(),
+            vec![(RecurringService::MatchmakingMembership, BOB, 1, LapseReason::GraceExpired)]
+        );
+
+        run_to_block(30);
+        assert_eq!(Charges::get().len(), 3);
+    });
+}
+
+#[test]
+fn payment_without_approval_fails() {
+    new_test_ext().execute_with(|| {
+        // 授权在扣费时校验，未授权的计划可以创建但无法扣费
+        let schedule_id = schedule_fixed(ALICE, 1, DUST, 10);
+        run_to_block(10);
+        assert!(Charges::get().is_empty());
+        System::assert_last_event(
+            Event::PaymentFailed {
+                schedule_id,
+                payer: ALICE,
+                service: RecurringService::MatchmakingMembership,
+                amount: DUST,
+                reason: PaymentFailure::NoApproval,
+                attempts: 1,
+                retry_at: Some(15),
+            }
+            .into(),
+        );
+
+        // 宽限期内补充授权后重试成功
+        approve(ALICE, RecurringService::MatchmakingMembership, DUST, None);
+        run_to_block(15);
+        assert_eq!(Charges::get().len(), 1);
+    });
+}
+
+#[test]
+fn spending_cap_blocks_payment() {
+    new_test_ext().execute_with(|| {
+        approve(ALICE, RecurringService::MatchmakingMembership, 5 * DUST, Some(8 * DUST));
+        let schedule_id = schedule_fixed(ALICE, 1, 4 * DUST, 10);
+
+        run_to_block(10);
+        assert_eq!(Charges::get().len(), 1);
+
+        // 第二期累计 8 DUST 仍在上限内，第三期超出
+        run_to_block(20);
+        assert_eq!(Charges::get().len(), 2);
+        run_to_block(30);
+        assert_eq!(Charges::get().len(), 2);
+        System::assert_has_event(
+            Event::PaymentFailed {
+                schedule_id,
+                payer: ALICE,
+                service: RecurringService::MatchmakingMembership,
+                amount: 4 * DUST,
+                reason: PaymentFailure::CapExceeded,
+                attempts: 1,
+                retry_at: Some(35),
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn payer_cancel_and_revoke_notify_consumer() {
+    new_test_ext().execute_with(|| {
+        let service = RecurringService::MatchmakingMembership;
+        approve(ALICE, service, 10 * DUST, None);
+        let first = schedule_fixed(ALICE, 1, DUST, 10);
+        schedule_fixed(ALICE, 2, DUST, 10);
+        schedule_fixed(ALICE, 3, DUST, 10);
+
+        assert_noop!(
+            RecurringPayment::cancel_schedule(RuntimeOrigin::signed(BOB), first),
+            Error::<Test>::NotPayer
+        );
+        assert_ok!(RecurringPayment::cancel_schedule(RuntimeOrigin::signed(ALICE), first));
+        assert_eq!(Lapses::get(), vec![(service, ALICE, 1, LapseReason::CancelledByPayer)]);
+
+        // 消费方取消不回调
+        assert!(RecurringPayment::cancel(&ALICE, service, 2));
+        assert!(!RecurringPayment::cancel(&ALICE, service, 2));
+        assert_eq!(Lapses::get().len(), 1);
+
+        assert_ok!(RecurringPayment::revoke_approval(RuntimeOrigin::signed(ALICE), service));
+        assert_eq!(Lapses::get()[1], (service, ALICE, 3, LapseReason::ApprovalRevoked));
+        System::assert_last_event(Event::ApprovalRevoked { payer: ALICE, service, lapsed: 1 }.into());
+
+        run_to_block(10);
+        assert!(Charges::get().is_empty());
+    });
+}
+
+#[test]
+fn charges_are_bounded_per_block() {
+    new_test_ext().execute_with(|| {
+        for payer in 1..=5u64 {
+            approve(payer, RecurringService::StorageService, DUST, None);
+            assert_ok!(RecurringPayment::schedule(
+                &payer,
+                RecurringService::StorageService,
+                0,
+                PaymentPrice::Fixed(DUST),
+                10,
+                10,
+            ));
+        }
+
+        // 每区块预算 4：扫描 1 + 扣费 3，剩余顺延到下一区块
+        run_to_block(10);
+        assert_eq!(Charges::get().len(), 3);
+        run_to_block(11);
+        assert_eq!(Charges::get().len(), 5);
+        assert_eq!(crate::DueCursor::<Test>::get(), Some(12));
+    });
+}
@@ -0,0 +1,105 @@
+//! # 通用周期扣费 - Trait 定义
+//!
+//! - `RecurringPayments`: 供订阅类模块创建 / 取消扣费计划
+//! - `RecurringPaymentHandler`: 到期扣费与计划终止回调，由消费方实现
+
+use crate::types::{LapseReason, PaymentPrice, RecurringService};
+use frame_support::weights::Weight;
+use sp_runtime::{DispatchError, DispatchResult};
+
+/// 周期扣费计划接口
+pub trait RecurringPayments<AccountId, Balance, BlockNumber> {
+    /// 创建扣费计划
+    ///
+    /// 授权在每期扣费时校验，付款人可在首期到期前再设置；
+    /// `first_due` 早于下一区块时按下一区块处理。
+    /// 返回计划 ID。
+    fn schedule(
+        payer: &AccountId,
+        service: RecurringService,
+        subject: u64,
+        price: PaymentPrice<Balance>,
+        period: BlockNumber,
+        first_due: BlockNumber,
+    ) -> Result<u64, DispatchError>;
+
+    /// 取消指定计划，返回是否存在（不触发 `on_payment_lapsed`）
+    fn cancel(payer: &AccountId, service: RecurringService, subject: u64) -> bool;
+
+    /// 取消付款人在该服务下的全部计划，返回取消数量
+    fn cancel_all(payer: &AccountId, service: RecurringService) -> u32;
+
+    /// 是否存在扣费计划
+    fn is_scheduled(payer: &AccountId, service: RecurringService, subject: u64) -> bool;
+}
+
+/// 空实现（未接入周期扣费的运行时 / 测试）
+impl<AccountId, Balance, BlockNumber> RecurringPayments<AccountId, Balance, BlockNumber> for () {
+    fn schedule(
+        _payer: &AccountId,
+        _service: RecurringService,
+        _subject: u64,
+        _price: PaymentPrice<Balance>,
+        _period: BlockNumber,
+        _first_due: BlockNumber,
+    ) -> Result<u64, DispatchError> {
+        Err(DispatchError::Other("recurring payments unavailable"))
+    }
+
+    fn cancel(_payer: &AccountId, _service: RecurringService, _subject: u64) -> bool {
+        false
+    }
+
+    fn cancel_all(_payer: &AccountId, _service: RecurringService) -> u32 {
+        0
+    }
+
+    fn is_scheduled(_payer: &AccountId, _service: RecurringService, _subject: u64) -> bool {
+        false
+    }
+}
+
+/// 扣费回调
+pub trait RecurringPaymentHandler<AccountId, Balance> {
+    /// 单次回调最大权重（计入 `on_initialize` 与取消类调用的预估权重）
+    fn max_weight() -> Weight {
+        Weight::zero()
+    }
+
+    /// 本期到期：消费方在此完成转账并延长服务
+    ///
+    /// 在独立存储层中执行，返回错误时本次回调的全部写入被回滚，计划进入重试。
+    fn on_payment_due(
+        service: RecurringService,
+        payer: &AccountId,
+        subject: u64,
+        amount: Balance,
+    ) -> DispatchResult;
+
+    /// 计划已终止（宽限期过期、付款人取消或撤销授权）
+    fn on_payment_lapsed(
+        service: RecurringService,
+        payer: &AccountId,
+        subject: u64,
+        reason: LapseReason,
+    );
+}
+
+impl<AccountId, Balance> RecurringPaymentHandler<AccountId, Balance> for () {
+    fn on_payment_due(
+        _service: RecurringService,
+        _payer: &AccountId,
+        _subject: u64,
+        _amount: Balance,
+    ) -> DispatchResult {
+        Err(DispatchError::Other("no recurring payment handler"))
+    }
+
+    fn on_payment_lapsed(
+        _service: RecurringService,
+        _payer: &AccountId,
+        _subject: u64,
+        _reason: LapseReason,
+    ) {
+    }
+}
@@ -0,0 +1,143 @@
+//! # 通用周期扣费 - 类型定义
+
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use pallet_trading_common::PricingProvider;
+use scale_info::TypeInfo;
+use sp_runtime::traits::{AtLeast32BitUnsigned, Saturating};
+
+/// DUST 精度（10^12）
+pub const DUST_PRECISION: u128 = 1_000_000_000_000;
+
+/// 订阅服务
+///
+/// 每个消费方模块对应一种服务，付款授权按服务分别设置。
+#[derive(
+    Clone, Copy, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug,
+)]
+pub enum RecurringService {
+    /// 占卜会员（pallet-divination-membership）
+    DivinationMembership,
+    /// 婚恋年费会员（pallet-matchmaking-membership）
+    MatchmakingMembership,
+    /// 婚恋资料月费（pallet-matchmaking-profile）
+    MatchmakingProfile,
+    /// 存储资金账户充值（pallet-storage-service）
+    StorageService,
+}
+
+/// 每期扣费金额
+#[derive(
+    Clone, Copy, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug,
+)]
+pub enum PaymentPrice<Balance> {
+    /// 固定 DUST 金额
+    Fixed(Balance),
+    /// 锚定 USD，每期按 `pallet-trading-pricing` 实时汇率换算
+    UsdPegged {
+        /// USD 金额（精度 10^6）
+        usd_amount: u64,
+        /// 汇率不可用时的兜底 DUST 金额
+        fallback: Balance,
+    },
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> PaymentPrice<Balance> {
+    /// 换算本期应付 DUST 金额
+    ///
+    /// dust_amount = usd_amount * 10^12 / rate（rate 为 DUST/USD 汇率，精度 10^6）
+    pub fn resolve<P: PricingProvider<Balance>>(&self) -> Balance {
+        match *self {
+            PaymentPrice::Fixed(amount) => amount,
+            PaymentPrice::UsdPegged { usd_amount, fallback } => P::get_dust_to_usd_rate()
+                .and_then(|rate| TryInto::<u128>::try_into(rate).ok())
+                .filter(|rate| *rate > 0)
+                .map(|rate| (usd_amount as u128).saturating_mul(DUST_PRECISION) / rate)
+                .and_then(|amount| Balance::try_from(amount).ok())
+                .unwrap_or(fallback),
+        }
+    }
+}
+
+/// 付款授权
+///
+/// 付款人为某服务设置的扣费上限；重新授权会重置累计额。
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug)]
+pub struct PaymentApproval<Balance, BlockNumber> {
+    /// 单期扣费上限
+    pub max_per_payment: Balance,
+    /// 累计扣费上限（None 表示不限）
+    pub max_total: Option<Balance>,
+    /// 已累计扣费
+    pub spent: Balance,
+    /// 授权区块
+    pub approved_at: BlockNumber,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy, BlockNumber> PaymentApproval<Balance, BlockNumber> {
+    /// 是否允许扣费 `amount`
+    pub fn allows(&self, amount: Balance) -> bool {
+        amount <= self.max_per_payment &&
+            self.max_total
+                .map_or(true, |max_total| self.spent.saturating_add(amount) <= max_total)
+    }
+}
+
+/// 扣费失败重试状态
+#[derive(Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug)]
+pub struct RetryState<BlockNumber> {
+    /// 首次失败区块（宽限期起点）
+    pub first_failed_at: BlockNumber,
+    /// 已失败次数
+    pub attempts: u32,
+}
+
+/// 周期扣费计划
+#[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug)]
+pub struct PaymentSchedule<AccountId, Balance, BlockNumber> {
+    /// 付款人
+    pub payer: AccountId,
+    /// 服务
+    pub service: RecurringService,
+    /// 服务内标识（由消费方定义，如订阅月数、会员等级）
+    pub subject: u64,
+    /// 每期金额
+    pub price: PaymentPrice<Balance>,
+    /// 扣费周期（区块数）
+    pub period: BlockNumber,
+    /// 本期应付区块
+    pub next_due: BlockNumber,
+    /// 到期队列中的区块（重试时晚于 `next_due`）
+    pub queued_at: BlockNumber,
+    /// 失败重试状态
+    pub retry: Option<RetryState<BlockNumber>>,
+    /// 已成功扣费期数
+    pub payments_made: u32,
+    /// 创建区块
+    pub created_at: BlockNumber,
+}
+
+/// 计划终止原因
+#[derive(
+    Clone, Copy, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug,
+)]
+pub enum LapseReason {
+    /// 宽限期内重试均失败
+    GraceExpired,
+    /// 付款人取消计划
+    CancelledByPayer,
+    /// 付款人撤销授权
+    ApprovalRevoked,
+}
+
+/// 单次扣费失败原因
+#[derive(
+    Clone, Copy, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug,
+)]
+pub enum PaymentFailure {
+    /// 未授权
+    NoApproval,
+    /// 超出授权上限
+    CapExceeded,
+    /// 消费方扣费失败（余额不足等）
+    ChargeFailed,
+}
@@ -0,0 +1,75 @@
+//! # 通用周期扣费 - 权重定义
+//!
+//! 在正式部署前应使用 benchmarking 进行精确测量。
+
+use frame_support::{traits::Get, weights::Weight};
+
+/// 权重信息 trait
+pub trait WeightInfo {
+    fn approve() -> Weight;
+    fn revoke_approval(s: u32) -> Weight;
+    fn cancel_schedule() -> Weight;
+    fn scan_block() -> Weight;
+    fn process_payment() -> Weight;
+}
+
+/// 默认权重实现
+pub struct SubstrateWeight<T>(core::marker::PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    fn approve() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// - 每个计划：读取计划，删除计划、索引、到期队列项
+    /// - 不含 `RecurringPaymentHandler` 回调开销
+    fn revoke_approval(s: u32) -> Weight {
+        Weight::from_parts(25_000_000, 0)
+            .saturating_add(Weight::from_parts(15_000_000, 0).saturating_mul(s.into()))
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().reads(s.into()))
+            .saturating_add(T::DbWeight::get().writes(2))
+            .saturating_add(T::DbWeight::get().writes(3u64.saturating_mul(s.into())))
+    }
+
+    fn cancel_schedule() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+
+    /// 扫描一个区块的到期队列（含游标读写）
+    fn scan_block() -> Weight {
+        Weight::from_parts(5_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+    }
+
+    /// - 3 次读取（计划、授权、汇率）
+    /// - 4 次写入（计划、授权、旧 / 新到期队列项）
+    /// - 不含 `RecurringPaymentHandler` 回调开销
+    fn process_payment() -> Weight {
+        Weight::from_parts(40_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+}
+
+impl WeightInfo for () {
+    fn approve() -> Weight {
+        Weight::zero()
+    }
+    fn revoke_approval(_s: u32) -> Weight {
+        Weight::zero()
+    }
+    fn cancel_schedule() -> Weight {
+        Weight::zero()
+    }
+    fn scan_block() -> Weight {
+        Weight::zero()
+    }
+    fn process_payment() -> Weight {
+        Weight::zero()
+    }
+}
@@ -37,6 +37,60 @@ mod benchmarks {
         claim_code(RawOrigin::Signed(caller), b"MYCODE01".to_vec());
     }
 
+    #[benchmark]
+    fn report_funding_sources(
+        n: Linear<1, { T::MaxFundingReports::get() }>,
+    ) -> Result<(), BenchmarkError> {
+        let origin = T::FundingReporterOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+        let funder: T::AccountId = account("funder", 0, 0);
+        let sponsor: T::AccountId = account("sponsor", 0, 0);
+
+        // 最坏情况：每个账户都已绑定，上报后计入聚类
+        let mut reports = sp_std::vec::Vec::new();
+        for i in 0..n {
+            let who: T::AccountId = account("referee", i, 0);
+            Sponsors::<T>::insert(&who, &sponsor);
+            reports.push((who, funder.clone()));
+        }
+        let reports: BoundedVec<_, T::MaxFundingReports> = reports.try_into().unwrap();
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, reports);
+
+        assert_eq!(ClusterSizes::<T>::get(&funder, &sponsor), n);
+        Ok(())
+    }
+
+    #[benchmark]
+    fn set_account_flag() -> Result<(), BenchmarkError> {
+        let origin = T::GovernanceOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+        let who: T::AccountId = account("referee", 0, 0);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, who.clone(), Some(true));
+
+        assert_eq!(AccountFlags::<T>::get(&who), Some(true));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn clear_cluster_flag() -> Result<(), BenchmarkError> {
+        let origin = T::GovernanceOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+        let funder: T::AccountId = account("funder", 0, 0);
+        let sponsor: T::AccountId = account("sponsor", 0, 0);
+        ClusterSizes::<T>::insert(&funder, &sponsor, 5);
+        FlaggedClusters::<T>::insert(&funder, &sponsor, frame_system::Pallet::<T>::block_number());
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, funder.clone(), sponsor.clone());
+
+        assert!(!FlaggedClusters::<T>::contains_key(&funder, &sponsor));
+        Ok(())
+    }
+
     impl_benchmark_test_suite!(
         Pallet,
         crate::mock::new_test_ext(),
@@ -8,12 +8,16 @@
 //! - **推荐人绑定**：用户通过推荐码绑定推荐人
 //! - **推荐码管理**：会员可认领自定义推荐码
 //! - **推荐链查询**：获取用户的上级推荐链（最多15层）
+//! - **防女巫**：绑定限流、被推荐人活跃度、资金来源聚类标记（见 `sybil.rs`）
 //!
 //! ## 存储项
 //!
 //! - `Sponsors`: 推荐人映射（账户 → 推荐人）
 //! - `AccountByCode`: 推荐码映射（推荐码 → 账户）
 //! - `CodeByAccount`: 账户推荐码（账户 → 推荐码）
+//! - `SponsorBindingWindow`: 推荐人当前窗口绑定计数
+//! - `RefereeActivity`: 被推荐人活跃度记录
+//! - `FundedBy` / `ClusterSizes` / `FlaggedClusters` / `AccountFlags`: 资金来源聚类与标记
 //!
 //! ## 抽离自
 //!
@@ -25,6 +29,7 @@
 pub use pallet::*;
 pub mod weights;
 pub use weights::WeightInfo;
+mod sybil;
 
 extern crate alloc;
 
@@ -68,12 +73,39 @@ pub mod pallet {
         #[pallet::constant]
         type MaxSearchHops: Get<u32>;
 
+        /// 治理权限（标记/解除女巫账户与聚类）
+        type GovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// 资金来源上报权限（链下索引器读取转账图后上报首笔资金来源）
+        type FundingReporterOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// 绑定限流窗口（区块数，0 表示不限流）
+        #[pallet::constant]
+        type BindingWindow: Get<BlockNumberFor<Self>>;
+
+        /// 每个推荐人在一个窗口内最多接受的绑定数
+        #[pallet::constant]
+        type MaxBindingsPerWindow: Get<u32>;
+
+        /// 被推荐人激活所需的活跃度积分（0 表示无需活跃即视为激活；
+        /// 非 0 时还须至少有一次付费行为，免费签到不能单独激活）
+        #[pallet::constant]
+        type ActivationThreshold: Get<u32>;
+
+        /// 同一资金来源在同一推荐人下绑定的账户数达到该值即标记为聚类（0 表示关闭）
+        #[pallet::constant]
+        type ClusterThreshold: Get<u32>;
+
+        /// 单次上报的资金来源条数上限
+        #[pallet::constant]
+        type MaxFundingReports: Get<u32>;
+
         /// 权重信息
         type WeightInfo: crate::weights::WeightInfo;
     }
 
     // ========================================
-    // 存储项（4个）
+    // 存储项
     // ========================================
 
     /// 推荐人映射：账户 → 推荐人
@@ -106,6 +138,99 @@ pub mod pallet {
     #[pallet::getter(fn referral_stats)]
     pub type ReferralStats<T: Config> = StorageValue<_, ReferralStatistics, ValueQuery>;
 
+    /// 推荐人绑定限流窗口：推荐人 → (窗口起始区块, 窗口内绑定数)
+    #[pallet::storage]
+    pub type SponsorBindingWindow<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, (BlockNumberFor<T>, u32), ValueQuery>;
+
+    /// 被推荐人活跃度：账户 → 活跃度记录
+    #[pallet::storage]
+    #[pallet::getter(fn referee_activity)]
+    pub type RefereeActivity<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, ActivityRecord, ValueQuery>;
+
+    /// 首笔资金来源：账户 → 资金来源账户（只记录一次）
+    #[pallet::storage]
+    #[pallet::getter(fn funded_by)]
+    pub type FundedBy<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId>;
+
+    /// 聚类规模：(资金来源, 推荐人) → 由该来源注资并绑定到该推荐人的账户数
+    #[pallet::storage]
+    pub type ClusterSizes<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        u32,
+        ValueQuery,
+    >;
+
+    /// 已标记聚类：(资金来源, 推荐人) → 标记区块
+    #[pallet::storage]
+    pub type FlaggedClusters<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        BlockNumberFor<T>,
+    >;
+
+    /// 治理覆盖标记：账户 → 是否为女巫（true 强制标记，false 强制放行）
+    #[pallet::storage]
+    #[pallet::getter(fn account_flag)]
+    pub type AccountFlags<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, bool>;
+
+    /// 被推荐人活跃行为类型
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug)]
+    pub enum ActivityKind {
+        /// 下单消费（占卜订单、直播礼物等）
+        Order,
+        /// 购买/升级会员
+        Membership,
+        /// 每日签到
+        CheckIn,
+    }
+
+    impl ActivityKind {
+        /// 该行为计入的活跃度积分
+        pub fn points(&self) -> u32 {
+            match self {
+                ActivityKind::Order => 3,
+                ActivityKind::Membership => 5,
+                ActivityKind::CheckIn => 1,
+            }
+        }
+    }
+
+    /// 被推荐人活跃度记录
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug, Default)]
+    pub struct ActivityRecord {
+        /// 下单次数
+        pub orders: u32,
+        /// 会员购买次数
+        pub memberships: u32,
+        /// 签到次数
+        pub check_ins: u32,
+        /// 累计活跃度积分
+        pub points: u32,
+        /// 激活区块（积分首次达到阈值时）
+        pub activated_at: Option<u32>,
+    }
+
+    impl ActivityRecord {
+        /// 是否有付费行为（下单或购买会员）
+        pub fn has_paid_activity(&self) -> bool {
+            self.orders > 0 || self.memberships > 0
+        }
+
+        /// 是否满足激活条件：积分达到阈值且至少有一次付费行为（阈值为 0 时无条件激活）
+        pub fn meets_activation(&self, threshold: u32) -> bool {
+            threshold == 0 || (self.points >= threshold && self.has_paid_activity())
+        }
+    }
+
     /// 🆕 推荐关系统计结构
     #[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug, Default)]
     pub struct ReferralStatistics {
@@ -134,6 +259,32 @@ pub mod pallet {
             who: T::AccountId,
             code: BoundedVec<u8, T::MaxCodeLen>,
         },
+        /// 被推荐人活跃度达到阈值，推荐奖励开始解锁
+        RefereeActivated {
+            who: T::AccountId,
+            points: u32,
+        },
+        /// 记录首笔资金来源
+        FundingSourceRecorded {
+            who: T::AccountId,
+            funder: T::AccountId,
+        },
+        /// 资金来源聚类被标记
+        ClusterFlagged {
+            funder: T::AccountId,
+            sponsor: T::AccountId,
+            size: u32,
+        },
+        /// 聚类标记被治理解除
+        ClusterFlagCleared {
+            funder: T::AccountId,
+            sponsor: T::AccountId,
+        },
+        /// 治理设置账户标记（None 表示撤销覆盖）
+        AccountFlagSet {
+            who: T::AccountId,
+            flagged: Option<bool>,
+        },
     }
 
     // ========================================
@@ -160,6 +311,10 @@ pub mod pallet {
         AlreadyHasCode,
         /// 非有效会员
         NotMember,
+        /// 推荐人本窗口内绑定数已达上限
+        BindingRateLimited,
+        /// 聚类未被标记
+        ClusterNotFlagged,
     }
 
     // ========================================
@@ -206,6 +361,66 @@ pub mod pallet {
             let who = ensure_signed(origin)?;
             Self::do_claim_code(who, code)
         }
+
+        /// 上报首笔资金来源
+        ///
+        /// 参数：
+        /// - `reports`: (账户, 资金来源) 列表
+        ///
+        /// 说明：
+        /// - 每个账户只记录第一次上报的资金来源
+        /// - 已绑定推荐人的账户计入 (资金来源, 推荐人) 聚类
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::report_funding_sources(reports.len() as u32))]
+        pub fn report_funding_sources(
+            origin: OriginFor<T>,
+            reports: BoundedVec<(T::AccountId, T::AccountId), T::MaxFundingReports>,
+        ) -> DispatchResult {
+            T::FundingReporterOrigin::ensure_origin(origin)?;
+            for (who, funder) in reports.into_iter() {
+                Self::note_funding_source(&who, &funder);
+            }
+            Ok(())
+        }
+
+        /// 设置账户女巫标记（治理）
+        ///
+        /// 参数：
+        /// - `who`: 目标账户
+        /// - `flagged`: Some(true) 强制标记，Some(false) 强制放行，None 撤销覆盖
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::set_account_flag())]
+        pub fn set_account_flag(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            flagged: Option<bool>,
+        ) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            AccountFlags::<T>::set(&who, flagged);
+            Self::deposit_event(Event::AccountFlagSet { who, flagged });
+            Ok(())
+        }
+
+        /// 解除聚类标记（治理）
+        ///
+        /// 聚类计数同时清零，之后重新累计。
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::WeightInfo::clear_cluster_flag())]
+        pub fn clear_cluster_flag(
+            origin: OriginFor<T>,
+            funder: T::AccountId,
+            sponsor: T::AccountId,
+        ) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            ensure!(
+                FlaggedClusters::<T>::contains_key(&funder, &sponsor),
+                Error::<T>::ClusterNotFlagged
+            );
+            FlaggedClusters::<T>::remove(&funder, &sponsor);
+            ClusterSizes::<T>::remove(&funder, &sponsor);
+            Self::deposit_event(Event::ClusterFlagCleared { funder, sponsor });
+            Ok(())
+        }
     }
 
     // ========================================
@@ -353,9 +568,17 @@ pub mod pallet {
                 Error::<T>::WouldCreateCycle
             );
 
+            // 验证：推荐人绑定限流
+            Self::note_binding_in_window(&sponsor)?;
+
             // 绑定推荐人
             Sponsors::<T>::insert(&who, &sponsor);
 
+            // 已知资金来源的账户计入聚类
+            if let Some(funder) = FundedBy::<T>::get(&who) {
+                Self::note_cluster_member(&funder, &sponsor);
+            }
+
             // 🆕 更新统计信息
             ReferralStats::<T>::mutate(|stats| {
                 stats.total_sponsors = stats.total_sponsors.saturating_add(1);
@@ -443,6 +666,9 @@ pub trait ReferralProvider<AccountId> {
 
     /// 获取推荐链（最多15层）
     fn get_referral_chain(who: &AccountId) -> sp_std::vec::Vec<AccountId>;
+
+    /// 被推荐人带来的推荐奖励是否已解锁（已激活且未被标记为女巫）
+    fn is_referee_vested(who: &AccountId) -> bool;
 }
 
 impl<T: Config> ReferralProvider<T::AccountId> for Pallet<T> {
@@ -453,4 +679,8 @@ impl<T: Config> ReferralProvider<T::AccountId> for Pallet<T> {
     fn get_referral_chain(who: &T::AccountId) -> sp_std::vec::Vec<T::AccountId> {
         Self::get_referral_chain(who)
     }
+
+    fn is_referee_vested(who: &T::AccountId) -> bool {
+        Self::is_vested(who)
+    }
 }
@@ -99,6 +99,21 @@ parameter_types! {
     
     /// 推荐链最大搜索深度
     pub const MaxSearchHops: u32 = 20;
+
+    /// 绑定限流窗口（测试缩短为10个区块）
+    pub const BindingWindow: u64 = 10;
+
+    /// 每个推荐人每窗口最多绑定数
+    pub const MaxBindingsPerWindow: u32 = 3;
+
+    /// 激活所需活跃度积分
+    pub const ActivationThreshold: u32 = 10;
+
+    /// 聚类标记阈值
+    pub const ClusterThreshold: u32 = 3;
+
+    /// 单次上报资金来源条数上限
+    pub const MaxFundingReports: u32 = 10;
 }
 
 // ========================================
@@ -126,6 +141,13 @@ impl pallet_referral::Config for Test {
     type MembershipProvider = MockMembershipProvider;
     type MaxCodeLen = MaxCodeLen;
     type MaxSearchHops = MaxSearchHops;
+    type GovernanceOrigin = frame_system::EnsureRoot<u64>;
+    type FundingReporterOrigin = frame_system::EnsureRoot<u64>;
+    type BindingWindow = BindingWindow;
+    type MaxBindingsPerWindow = MaxBindingsPerWindow;
+    type ActivationThreshold = ActivationThreshold;
+    type ClusterThreshold = ClusterThreshold;
+    type MaxFundingReports = MaxFundingReports;
     type WeightInfo = ();
 }
 
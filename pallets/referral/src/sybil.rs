@@ -0,0 +1,146 @@
+//! 函数级中文注释：防女巫子模块
+//!
+//! 功能：
+//! - 推荐人绑定限流（按区块窗口计数）
+//! - 被推荐人活跃度（下单、会员、签到）与激活判定
+//! - 资金来源聚类：同一来源注资并绑定到同一推荐人的账户达到阈值即标记
+//! - 奖励解锁判定：已激活且未被标记
+
+use super::*;
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    SaturatedConversion,
+};
+
+/// 函数级中文注释：防女巫实现
+impl<T: Config> Pallet<T> {
+    /// 函数级中文注释：计入推荐人当前窗口的绑定数
+    ///
+    /// 窗口按 `BindingWindow` 对齐，跨窗口时计数重置；
+    /// 达到 `MaxBindingsPerWindow` 后返回 `BindingRateLimited`。
+    pub(crate) fn note_binding_in_window(sponsor: &T::AccountId) -> DispatchResult {
+        let window = T::BindingWindow::get();
+        if window.is_zero() {
+            return Ok(());
+        }
+
+        let now = frame_system::Pallet::<T>::block_number();
+        let window_start = now.saturating_sub(now % window);
+
+        SponsorBindingWindow::<T>::try_mutate(sponsor, |(start, count)| {
+            if *start != window_start {
+                *start = window_start;
+                *count = 0;
+            }
+            ensure!(
+                *count < T::MaxBindingsPerWindow::get(),
+                Error::<T>::BindingRateLimited
+            );
+            *count = count.saturating_add(1);
+            Ok(())
+        })
+    }
+
+    /// 函数级中文注释：记录一次被推荐人活跃行为
+    ///
+    /// 供其他模块在下单、购买会员、签到后调用；
+    /// 积分首次达到 `ActivationThreshold` 且至少有一次付费行为时发射 `RefereeActivated`。
+    pub fn record_activity(who: &T::AccountId, kind: ActivityKind) {
+        let threshold = T::ActivationThreshold::get();
+        let mut activated = None;
+
+        RefereeActivity::<T>::mutate(who, |record| {
+            match kind {
+                ActivityKind::Order => record.orders = record.orders.saturating_add(1),
+                ActivityKind::Membership => {
+                    record.memberships = record.memberships.saturating_add(1)
+                }
+                ActivityKind::CheckIn => record.check_ins = record.check_ins.saturating_add(1),
+            }
+            record.points = record.points.saturating_add(kind.points());
+
+            if record.activated_at.is_none() && record.meets_activation(threshold) {
+                record.activated_at =
+                    Some(frame_system::Pallet::<T>::block_number().saturated_into());
+                activated = Some(record.points);
+            }
+        });
+
+        if let Some(points) = activated {
+            Self::deposit_event(Event::RefereeActivated { who: who.clone(), points });
+        }
+    }
+
+    /// 函数级中文注释：被推荐人是否已激活
+    ///
+    /// 仅靠免费签到累积的积分不能激活，须至少下单或购买会员一次。
+    pub fn is_activated(who: &T::AccountId) -> bool {
+        RefereeActivity::<T>::get(who).meets_activation(T::ActivationThreshold::get())
+    }
+
+    /// 函数级中文注释：账户是否被标记为女巫
+    ///
+    /// 治理覆盖优先；否则检查其 (资金来源, 推荐人) 聚类是否已被标记。
+    pub fn is_flagged(who: &T::AccountId) -> bool {
+        if let Some(flagged) = AccountFlags::<T>::get(who) {
+            return flagged;
+        }
+
+        match (FundedBy::<T>::get(who), Sponsors::<T>::get(who)) {
+            (Some(funder), Some(sponsor)) => FlaggedClusters::<T>::contains_key(&funder, &sponsor),
+            _ => false,
+        }
+    }
+
+    /// 函数级中文注释：被推荐人带来的推荐奖励是否已解锁
+    pub fn is_vested(who: &T::AccountId) -> bool {
+        Self::is_activated(who) && !Self::is_flagged(who)
+    }
+
+    /// 函数级中文注释：记录首笔资金来源
+    ///
+    /// 已有记录或自我注资时忽略；账户已绑定推荐人时计入聚类。
+    pub fn note_funding_source(who: &T::AccountId, funder: &T::AccountId) {
+        if who == funder || FundedBy::<T>::contains_key(who) {
+            return;
+        }
+
+        FundedBy::<T>::insert(who, funder);
+        Self::deposit_event(Event::FundingSourceRecorded {
+            who: who.clone(),
+            funder: funder.clone(),
+        });
+
+        if let Some(sponsor) = Sponsors::<T>::get(who) {
+            Self::note_cluster_member(funder, &sponsor);
+        }
+    }
+
+    /// 函数级中文注释：聚类计数 +1，达到阈值时标记
+    ///
+    /// 资金来源即推荐人本身（推荐人给下线注资）同样计入。
+    pub(crate) fn note_cluster_member(funder: &T::AccountId, sponsor: &T::AccountId) {
+        let size = ClusterSizes::<T>::mutate(funder, sponsor, |size| {
+            *size = size.saturating_add(1);
+            *size
+        });
+
+        let threshold = T::ClusterThreshold::get();
+        if threshold == 0
+            || size < threshold
+            || FlaggedClusters::<T>::contains_key(funder, sponsor)
+        {
+            return;
+        }
+
+        let now: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number();
+        FlaggedClusters::<T>::insert(funder, sponsor, now);
+        Self::deposit_event(Event::ClusterFlagged {
+            funder: funder.clone(),
+            sponsor: sponsor.clone(),
+            size,
+        });
+    }
+}
@@ -426,3 +426,133 @@ fn test_referral_provider_get_referral_chain() {
         assert_eq!(chain, vec![2, 3]);
     });
 }
+
+// ========================================
+// 防女巫测试
+// ========================================
+
+#[test]
+fn test_bind_sponsor_rate_limited_per_window() {
+    new_test_ext().execute_with(|| {
+        setup_code_for_account(2, b"BOBCODE1");
+
+        // 窗口内最多 3 次绑定
+        for who in [1u64, 3, 4] {
+            assert_ok!(Referral::bind_sponsor(RuntimeOrigin::signed(who), b"BOBCODE1".to_vec()));
+        }
+        assert_noop!(
+            Referral::bind_sponsor(RuntimeOrigin::signed(5), b"BOBCODE1".to_vec()),
+            Error::<Test>::BindingRateLimited
+        );
+
+        // 进入下一个窗口后恢复
+        System::set_block_number(11);
+        assert_ok!(Referral::bind_sponsor(RuntimeOrigin::signed(5), b"BOBCODE1".to_vec()));
+        assert_eq!(SponsorBindingWindow::<Test>::get(2), (10, 1));
+    });
+}
+
+#[test]
+fn test_record_activity_activates_referee() {
+    new_test_ext().execute_with(|| {
+        Referral::record_activity(&1, ActivityKind::Order);
+        Referral::record_activity(&1, ActivityKind::Order);
+        Referral::record_activity(&1, ActivityKind::CheckIn);
+        assert_eq!(Referral::referee_activity(1).points, 7);
+        assert!(!Referral::is_vested(&1));
+
+        Referral::record_activity(&1, ActivityKind::Membership);
+
+        let record = Referral::referee_activity(1);
+        assert_eq!((record.orders, record.memberships, record.check_ins), (2, 1, 1));
+        assert_eq!(record.activated_at, Some(1));
+        assert!(Referral::is_vested(&1));
+        System::assert_has_event(RuntimeEvent::Referral(Event::RefereeActivated {
+            who: 1,
+            points: 12,
+        }));
+    });
+}
+
+#[test]
+fn test_check_ins_alone_do_not_activate() {
+    new_test_ext().execute_with(|| {
+        for _ in 0..20 {
+            Referral::record_activity(&1, ActivityKind::CheckIn);
+        }
+        let record = Referral::referee_activity(1);
+        assert_eq!(record.points, 20);
+        assert_eq!(record.activated_at, None);
+        assert!(!Referral::is_vested(&1));
+
+        // 首次付费行为后激活
+        Referral::record_activity(&1, ActivityKind::Order);
+        assert_eq!(Referral::referee_activity(1).activated_at, Some(1));
+        assert!(Referral::is_vested(&1));
+    });
+}
+
+#[test]
+fn test_funding_cluster_flagged_and_overridden() {
+    new_test_ext().execute_with(|| {
+        setup_code_for_account(2, b"BOBCODE1");
+        for who in [1u64, 3, 4] {
+            Referral::record_activity(&who, ActivityKind::Membership);
+            Referral::record_activity(&who, ActivityKind::Membership);
+        }
+
+        // 先上报资金来源再绑定
+        let reports: BoundedVec<(u64, u64), MaxFundingReports> =
+            vec![(1, 9), (3, 9)].try_into().unwrap();
+        assert_ok!(Referral::report_funding_sources(RuntimeOrigin::root(), reports));
+        assert_ok!(Referral::bind_sponsor(RuntimeOrigin::signed(1), b"BOBCODE1".to_vec()));
+        assert_ok!(Referral::bind_sponsor(RuntimeOrigin::signed(3), b"BOBCODE1".to_vec()));
+        assert!(!Referral::is_flagged(&1));
+
+        // 先绑定再上报，达到阈值 3 后整个聚类被标记
+        assert_ok!(Referral::bind_sponsor(RuntimeOrigin::signed(4), b"BOBCODE1".to_vec()));
+        let reports: BoundedVec<(u64, u64), MaxFundingReports> =
+            vec![(4, 9), (4, 8)].try_into().unwrap();
+        assert_ok!(Referral::report_funding_sources(RuntimeOrigin::root(), reports));
+
+        assert_eq!(Referral::funded_by(4), Some(9));
+        assert_eq!(ClusterSizes::<Test>::get(9, 2), 3);
+        System::assert_has_event(RuntimeEvent::Referral(Event::ClusterFlagged {
+            funder: 9,
+            sponsor: 2,
+            size: 3,
+        }));
+        assert!(Referral::is_flagged(&1));
+        assert!(!Referral::is_vested(&3));
+
+        // 治理强制放行单个账户
+        assert_ok!(Referral::set_account_flag(RuntimeOrigin::root(), 1, Some(false)));
+        assert!(Referral::is_vested(&1));
+        assert!(Referral::is_flagged(&3));
+
+        // 治理解除聚类标记
+        assert_ok!(Referral::clear_cluster_flag(RuntimeOrigin::root(), 9, 2));
+        assert!(!Referral::is_flagged(&3));
+        assert_eq!(ClusterSizes::<Test>::get(9, 2), 0);
+        assert_noop!(
+            Referral::clear_cluster_flag(RuntimeOrigin::root(), 9, 2),
+            Error::<Test>::ClusterNotFlagged
+        );
+    });
+}
+
+#[test]
+fn test_sybil_calls_require_privileged_origin() {
+    new_test_ext().execute_with(|| {
+        let reports: BoundedVec<(u64, u64), MaxFundingReports> =
+            vec![(1, 9)].try_into().unwrap();
+        assert_noop!(
+            Referral::report_funding_sources(RuntimeOrigin::signed(1), reports),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Referral::set_account_flag(RuntimeOrigin::signed(1), 1, Some(false)),
+            sp_runtime::DispatchError::BadOrigin
+        );
+    });
+}
@@ -9,15 +9,21 @@ pub trait WeightInfo {
     fn bind_sponsor() -> Weight;
     /// claim_code：认领推荐码
     fn claim_code() -> Weight;
+    /// report_funding_sources：上报资金来源
+    fn report_funding_sources(n: u32) -> Weight;
+    /// set_account_flag：设置账户女巫标记
+    fn set_account_flag() -> Weight;
+    /// clear_cluster_flag：解除聚类标记
+    fn clear_cluster_flag() -> Weight;
 }
 
 /// 默认实现（用于测试）
 impl WeightInfo for () {
     fn bind_sponsor() -> Weight {
-        // 读取：Sponsors(1) + AccountByCode(1) + 循环检测(最多20次)
-        // 写入：Sponsors(1) + ReferralStats(1)
-        Weight::from_parts(25_000, 0)
-            .saturating_add(Weight::from_parts(0, 3000))
+        // 读取：Sponsors(1) + AccountByCode(1) + 循环检测(最多20次) + SponsorBindingWindow(1) + FundedBy(1)
+        // 写入：Sponsors(1) + SponsorBindingWindow(1) + ClusterSizes(1) + ReferralStats(1)
+        Weight::from_parts(30_000, 0)
+            .saturating_add(Weight::from_parts(0, 3500))
     }
 
     fn claim_code() -> Weight {
@@ -26,6 +32,26 @@ impl WeightInfo for () {
         Weight::from_parts(20_000, 0)
             .saturating_add(Weight::from_parts(0, 2500))
     }
+
+    fn report_funding_sources(n: u32) -> Weight {
+        // 每条：读取 FundedBy(1) + Sponsors(1) + ClusterSizes(1) + FlaggedClusters(1)
+        //       写入 FundedBy(1) + ClusterSizes(1) + FlaggedClusters(1)
+        Weight::from_parts(10_000, 0)
+            .saturating_add(Weight::from_parts(15_000, 1500).saturating_mul(n as u64))
+    }
+
+    fn set_account_flag() -> Weight {
+        // 写入：AccountFlags(1)
+        Weight::from_parts(10_000, 0)
+            .saturating_add(Weight::from_parts(0, 1000))
+    }
+
+    fn clear_cluster_flag() -> Weight {
+        // 读取：FlaggedClusters(1)
+        // 写入：FlaggedClusters(1) + ClusterSizes(1)
+        Weight::from_parts(12_000, 0)
+            .saturating_add(Weight::from_parts(0, 1500))
+    }
 }
 
 /// Substrate 权重实现
@@ -33,10 +59,10 @@ pub struct SubstrateWeight<T>(PhantomData<T>);
 
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn bind_sponsor() -> Weight {
-        // 读取：Sponsors(1) + AccountByCode(1) + 循环检测(最多20次)
-        // 写入：Sponsors(1) + ReferralStats(1)
-        Weight::from_parts(25_000, 0)
-            .saturating_add(Weight::from_parts(0, 3000))
+        // 读取：Sponsors(1) + AccountByCode(1) + 循环检测(最多20次) + SponsorBindingWindow(1) + FundedBy(1)
+        // 写入：Sponsors(1) + SponsorBindingWindow(1) + ClusterSizes(1) + ReferralStats(1)
+        Weight::from_parts(30_000, 0)
+            .saturating_add(Weight::from_parts(0, 3500))
     }
 
     fn claim_code() -> Weight {
@@ -45,4 +71,24 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
         Weight::from_parts(20_000, 0)
             .saturating_add(Weight::from_parts(0, 2500))
     }
+
+    fn report_funding_sources(n: u32) -> Weight {
+        // 每条：读取 FundedBy(1) + Sponsors(1) + ClusterSizes(1) + FlaggedClusters(1)
+        //       写入 FundedBy(1) + ClusterSizes(1) + FlaggedClusters(1)
+        Weight::from_parts(10_000, 0)
+            .saturating_add(Weight::from_parts(15_000, 1500).saturating_mul(n as u64))
+    }
+
+    fn set_account_flag() -> Weight {
+        // 写入：AccountFlags(1)
+        Weight::from_parts(10_000, 0)
+            .saturating_add(Weight::from_parts(0, 1000))
+    }
+
+    fn clear_cluster_flag() -> Weight {
+        // 读取：FlaggedClusters(1)
+        // 写入：FlaggedClusters(1) + ClusterSizes(1)
+        Weight::from_parts(12_000, 0)
+            .saturating_add(Weight::from_parts(0, 1500))
+    }
 }
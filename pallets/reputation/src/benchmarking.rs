@@ -0,0 +1,74 @@
+//! # 跨域信誉聚合基准测试
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+
+fn seed_all_domains<T: Config>(who: &T::AccountId) {
+    for domain in ReputationDomain::ALL {
+        Pallet::<T>::do_report(who, domain, ReputationSignal::Violation(Severity::Minor));
+    }
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn report_signal() -> Result<(), BenchmarkError> {
+        let origin =
+            T::GovernanceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let who: T::AccountId = account("who", 0, 0);
+        seed_all_domains::<T>(&who);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, who, ReputationDomain::Trading, ReputationSignal::Banned);
+
+        Ok(())
+    }
+
+    #[benchmark]
+    fn clear_domain() -> Result<(), BenchmarkError> {
+        let origin =
+            T::GovernanceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+        let who: T::AccountId = account("who", 0, 0);
+        seed_all_domains::<T>(&who);
+
+        #[extrinsic_call]
+        _(origin as T::RuntimeOrigin, who, ReputationDomain::Trading);
+
+        Ok(())
+    }
+
+    // issue_credential 依赖运行时的信用来源（CreditSnapshotProvider），权重按读写次数估算
+
+    #[benchmark]
+    fn revoke_credential() {
+        let who: T::AccountId = account("who", 0, 0);
+        let now = frame_system::Pallet::<T>::block_number();
+        Attestations::<T>::insert(
+            0,
+            CreditAttestation {
+                id: 0,
+                subject: who.clone(),
+                source: CredentialSource::TradingMaker,
+                score_band: ScoreBand::Good,
+                order_count: 100,
+                dispute_rate_bps: 100,
+                risk_level: RiskLevel::Low,
+                as_of: now,
+                expires_at: now,
+                revoked: false,
+            },
+        );
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(who), 0);
+
+        assert!(Attestations::<T>::get(0).unwrap().revoked);
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
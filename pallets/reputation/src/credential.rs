@@ -0,0 +1,137 @@
+//! # 跨域信誉聚合 - 可验证信用凭证
+//!
+//! 做市商与服务提供者可将信用记录导出为 W3C Verifiable Credential 风格的 JSON，
+//! 供链下合作方核验。运行时不持有签名私钥，凭证由链上 `Attestations` 存储锚定：
+//! 验证方取得已最终确定区块头（由 GRANDPA 验证人签名）的状态根，
+//! 以及 `state_getReadProof` 返回的证明节点，调用 [`verify_credential`] 核对凭证
+//! 存在、内容一致、未撤销且未过期。状态证明即凭证的签发证明，凭证本身不附带签发方签名；
+//! 证明区块须不早于凭证的 `as_of` 区块。
+
+use alloc::{format, string::String};
+use codec::{Decode, Encode};
+use frame_support::{StorageHasher, Twox64Concat};
+use sp_core::{blake2_256, hexdisplay::HexDisplay, H256};
+use sp_runtime::traits::{BlakeTwo256, UniqueSaturatedInto};
+use sp_std::vec::Vec;
+use sp_trie::{LayoutV1, StorageProof};
+
+use crate::types::CreditAttestation;
+
+/// 凭证存储项名称（与 `Attestations` 保持一致）
+pub const ATTESTATIONS_STORAGE_NAME: &[u8] = b"Attestations";
+
+/// 凭证 JSON 中的证明类型
+pub const PROOF_TYPE: &str = "StardustStateProof";
+
+/// 凭证验证错误
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CredentialVerifyError {
+    /// 证明节点无法还原到给定状态根
+    InvalidProof,
+    /// 状态中不存在该凭证
+    NotFound,
+    /// 存储值无法解码
+    Malformed,
+    /// 链上凭证与出示的凭证内容不一致
+    Mismatch,
+    /// 凭证已撤销
+    Revoked,
+    /// 凭证已过期
+    Expired,
+    /// 证明区块早于凭证签发区块
+    ProofBeforeIssuance,
+}
+
+/// 凭证存储键
+///
+/// `pallet_prefix` 为运行时中 pallet 的名称（如 `b"Reputation"`）。
+pub fn credential_storage_key(pallet_prefix: &[u8], id: u64) -> Vec<u8> {
+    let mut key =
+        frame_support::storage::storage_prefix(pallet_prefix, ATTESTATIONS_STORAGE_NAME).to_vec();
+    key.extend(Twox64Concat::hash(&id.encode()));
+    key
+}
+
+/// 导出 W3C VC 风格 JSON
+///
+/// - `issuer`: 链标识（创世区块哈希）
+/// - `storage_key`: 凭证存储键，验证方据此请求状态证明
+pub fn to_vc_json<AccountId, BlockNumber>(
+    attestation: &CreditAttestation<AccountId, BlockNumber>,
+    issuer: &[u8],
+    storage_key: &[u8],
+) -> String
+where
+    AccountId: Encode,
+    BlockNumber: Encode + Copy + UniqueSaturatedInto<u64>,
+{
+    let as_of: u64 = attestation.as_of.unique_saturated_into();
+    let expires_at: u64 = attestation.expires_at.unique_saturated_into();
+    format!(
+        concat!(
+            r#"{{"@context":["https://www.w3.org/ns/credentials/v2"],"#,
+            r#""type":["VerifiableCredential","StardustCreditAttestation"],"#,
+            r#""id":"urn:stardust:credential:{}","#,
+            r#""issuer":"did:stardust:0x{}","#,
+            r#""validFromBlock":{},"validUntilBlock":{},"#,
+            r#""credentialSubject":{{"id":"did:stardust:0x{}","source":"{}","scoreBand":"{}","#,
+            r#""orderCount":{},"disputeRateBps":{},"riskLevel":"{}","asOfBlock":{}}},"#,
+            r#""credentialStatus":{{"type":"StardustOnChainStatus","revoked":{}}},"#,
+            r#""proof":{{"type":"{}","storageKey":"0x{}","attestationHash":"0x{}"}}}}"#,
+        ),
+        attestation.id,
+        HexDisplay::from(&issuer),
+        as_of,
+        expires_at,
+        HexDisplay::from(&attestation.subject.encode()),
+        attestation.source.as_str(),
+        attestation.score_band.as_str(),
+        attestation.order_count,
+        attestation.dispute_rate_bps,
+        attestation.risk_level.as_str(),
+        as_of,
+        attestation.revoked,
+        PROOF_TYPE,
+        HexDisplay::from(&storage_key),
+        HexDisplay::from(&blake2_256(&attestation.encode())),
+    )
+}
+
+/// 依据状态证明验证凭证
+///
+/// - `state_root`: 已最终确定区块的状态根
+/// - `proof`: 对 `credential_storage_key(pallet_prefix, claimed.id)` 的读取证明
+/// - `at`: 该区块的区块号，须不早于凭证的 `as_of`，并用于判断是否过期
+pub fn verify_credential<AccountId, BlockNumber>(
+    pallet_prefix: &[u8],
+    state_root: H256,
+    proof: StorageProof,
+    claimed: &CreditAttestation<AccountId, BlockNumber>,
+    at: BlockNumber,
+) -> Result<(), CredentialVerifyError>
+where
+    AccountId: Decode + PartialEq,
+    BlockNumber: Decode + PartialOrd,
+{
+    let key = credential_storage_key(pallet_prefix, claimed.id);
+    let db = proof.into_memory_db::<BlakeTwo256>();
+    let raw = sp_trie::read_trie_value::<LayoutV1<BlakeTwo256>, _>(&db, &state_root, &key, None, None)
+        .map_err(|_| CredentialVerifyError::InvalidProof)?
+        .ok_or(CredentialVerifyError::NotFound)?;
+    let on_chain = CreditAttestation::<AccountId, BlockNumber>::decode(&mut &raw[..])
+        .map_err(|_| CredentialVerifyError::Malformed)?;
+
+    if on_chain.revoked {
+        return Err(CredentialVerifyError::Revoked);
+    }
+    if on_chain != *claimed {
+        return Err(CredentialVerifyError::Mismatch);
+    }
+    if at < on_chain.as_of {
+        return Err(CredentialVerifyError::ProofBeforeIssuance);
+    }
+    if at > on_chain.expires_at {
+        return Err(CredentialVerifyError::Expired);
+    }
+    Ok(())
+}
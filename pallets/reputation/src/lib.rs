@@ -0,0 +1,489 @@
+//! # 跨域信誉聚合模块 (pallet-reputation)
+//!
+//! 汇总交易信用、占卜市场违规、直播违规、婚恋违规与仲裁结果等信号，
+//! 避免在一个领域被封禁的欺诈者在另一个领域以干净记录重新开始。
+//!
+//! ## 功能概述
+//!
+//! 1. **分域记录**：每个账户在每个领域独立累计风险分、正向积分与封禁状态
+//! 2. **时间衰减**：风险分每经过 `PenaltyHalfLife` 个区块减半；封禁不衰减
+//! 3. **综合风险**：按领域权重汇总风险分，正向积分最多抵消一半，
+//!    任一领域被封禁时综合等级为 `Blocked`
+//! 4. **准入控制**：消费方通过 `ReputationProvider` 读取综合风险等级拒绝高风险账户
+//! 5. **治理**：治理可手动上报信号，或在申诉成功后清除领域记录
+//! 6. **信用凭证**：做市商 / 服务提供者签发带有效期的信用凭证（分档、订单数、争议率），
+//!    以 W3C VC 风格 JSON 导出，第三方通过状态证明验证（见 [`credential`]）
+//!
+//! ## 信号来源
+//!
+//! ```text
+//!   pallet-trading-credit ──────┐
+//!   pallet-divination-market ───┤
+//!   pallet-livestream ──────────┼── ReputationSink ──▶ ┌───────────────────┐
+//!   pallet-matchmaking-profile ─┤                      │ pallet-reputation │
+//!   pallet-arbitration ─────────┘                      └─────────┬─────────┘
+//!                                                                │ ReputationProvider
+//!                                     准入控制（综合风险等级） ◀───┘
+//! ```
+//!
+//! ## 使用示例
+//!
+//! ```ignore
+//! T::Reputation::report(&who, ReputationDomain::Livestream, ReputationSignal::Banned);
+//! ensure!(T::Reputation::risk_level(&who) < RiskLevel::Blocked, Error::<T>::ReputationRiskTooHigh);
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub use pallet::*;
+
+pub mod credential;
+pub mod runtime_api;
+pub mod traits;
+pub mod types;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::vec::Vec;
+
+use crate::runtime_api::{DomainSummary, ReputationSummary};
+use crate::traits::{CreditSnapshotProvider, ReputationProvider, ReputationSink};
+use crate::types::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use crate::weights::WeightInfo;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Pallet 配置 trait
+    #[pallet::config]
+    pub trait Config: frame_system::Config<RuntimeEvent: From<Event<Self>>> {
+        /// 风险分半衰期（区块数）
+        #[pallet::constant]
+        type PenaltyHalfLife: Get<BlockNumberFor<Self>>;
+
+        /// 单领域正向积分上限
+        #[pallet::constant]
+        type MaxCredit: Get<u32>;
+
+        /// 治理 Origin（手动上报信号、清除记录、撤销凭证）
+        type GovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// 信用快照来源（签发凭证）
+        type CreditSource: CreditSnapshotProvider<Self::AccountId>;
+
+        /// 凭证有效期（区块数）
+        #[pallet::constant]
+        type CredentialValidity: Get<BlockNumberFor<Self>>;
+
+        /// 每个账户同时持有的凭证上限（签发时清理已过期 / 已撤销凭证）
+        #[pallet::constant]
+        type MaxCredentialsPerAccount: Get<u32>;
+
+        /// 权重信息
+        type WeightInfo: WeightInfo;
+    }
+
+    /// 领域记录类型
+    pub type DomainRecordOf<T> = DomainRecord<BlockNumberFor<T>>;
+
+    /// 信用凭证类型
+    pub type CreditAttestationOf<T> =
+        CreditAttestation<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
+    // ========================================================================
+    // 存储定义
+    // ========================================================================
+
+    /// 分域信誉记录
+    ///
+    /// (account, domain) -> DomainRecord
+    #[pallet::storage]
+    #[pallet::getter(fn domain_records)]
+    pub type DomainRecords<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        ReputationDomain,
+        DomainRecordOf<T>,
+    >;
+
+    /// 信用凭证
+    ///
+    /// 存储键格式由 `credential::credential_storage_key` 复现，供链下验证
+    #[pallet::storage]
+    pub type Attestations<T: Config> = StorageMap<_, Twox64Concat, u64, CreditAttestationOf<T>>;
+
+    /// 账户持有的凭证 ID
+    #[pallet::storage]
+    pub type AccountAttestations<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<u64, T::MaxCredentialsPerAccount>,
+        ValueQuery,
+    >;
+
+    /// 下一个凭证 ID
+    #[pallet::storage]
+    pub type NextAttestationId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    // ========================================================================
+    // 事件
+    // ========================================================================
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// 已记录信号
+        SignalRecorded {
+            who: T::AccountId,
+            domain: ReputationDomain,
+            signal: ReputationSignal,
+            /// 该领域当前风险分
+            domain_penalty: u32,
+            /// 综合风险分
+            composite_risk: u16,
+        },
+        /// 综合风险等级变更
+        RiskLevelChanged {
+            who: T::AccountId,
+            old_level: RiskLevel,
+            new_level: RiskLevel,
+        },
+        /// 领域记录已清除（治理）
+        DomainCleared {
+            who: T::AccountId,
+            domain: ReputationDomain,
+        },
+        /// 信用凭证已签发
+        CredentialIssued {
+            id: u64,
+            subject: T::AccountId,
+            source: CredentialSource,
+            score_band: ScoreBand,
+            expires_at: BlockNumberFor<T>,
+        },
+        /// 信用凭证已撤销
+        CredentialRevoked {
+            id: u64,
+            subject: T::AccountId,
+        },
+    }
+
+    // ========================================================================
+    // 错误
+    // ========================================================================
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// 领域记录不存在
+        RecordNotFound,
+        /// 该来源无信用记录
+        NoCreditRecord,
+        /// 有效凭证数量已达上限
+        TooManyCredentials,
+        /// 凭证不存在
+        CredentialNotFound,
+        /// 非凭证主体
+        NotCredentialSubject,
+        /// 凭证已撤销
+        CredentialAlreadyRevoked,
+    }
+
+    // ========================================================================
+    // 可调用函数
+    // ========================================================================
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// 手动上报信号（治理）
+        ///
+        /// 用于链下核实的欺诈等未经业务模块上报的情形。
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::report_signal())]
+        pub fn report_signal(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            domain: ReputationDomain,
+            signal: ReputationSignal,
+        ) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            Self::do_report(&who, domain, signal);
+            Ok(())
+        }
+
+        /// 清除领域记录（治理，例如申诉成功或解除封禁）
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::clear_domain())]
+        pub fn clear_domain(
+            origin: OriginFor<T>,
+            who: T::AccountId,
+            domain: ReputationDomain,
+        ) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            ensure!(DomainRecords::<T>::contains_key(&who, domain), Error::<T>::RecordNotFound);
+
+            let old_level = Self::risk_level_of(&who);
+            DomainRecords::<T>::remove(&who, domain);
+            Self::deposit_event(Event::DomainCleared { who: who.clone(), domain });
+            Self::note_level_change(&who, old_level);
+            Ok(())
+        }
+
+        /// 签发信用凭证
+        ///
+        /// 按来源读取调用者当前信用快照，连同综合风险等级锚定上链，
+        /// 有效期 `CredentialValidity`。
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::issue_credential())]
+        pub fn issue_credential(origin: OriginFor<T>, source: CredentialSource) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let snapshot =
+                T::CreditSource::credit_snapshot(&who, source).ok_or(Error::<T>::NoCreditRecord)?;
+
+            let now = frame_system::Pallet::<T>::block_number();
+            // 仅清理已过期的凭证；已撤销但未过期的凭证保留在链上，状态证明验证返回已撤销
+            let mut held = AccountAttestations::<T>::get(&who);
+            held.retain(|id| match Attestations::<T>::get(id) {
+                Some(attestation) if now <= attestation.expires_at => true,
+                _ => {
+                    Attestations::<T>::remove(id);
+                    false
+                },
+            });
+
+            let id = NextAttestationId::<T>::get();
+            held.try_push(id).map_err(|_| Error::<T>::TooManyCredentials)?;
+
+            let expires_at = now.saturating_add(T::CredentialValidity::get());
+            let attestation = CreditAttestation {
+                id,
+                subject: who.clone(),
+                source,
+                score_band: snapshot.band,
+                order_count: snapshot.order_count,
+                dispute_rate_bps: snapshot.dispute_rate_bps(),
+                risk_level: Self::risk_level_of(&who),
+                as_of: now,
+                expires_at,
+                revoked: false,
+            };
+            Attestations::<T>::insert(id, attestation);
+            AccountAttestations::<T>::insert(&who, held);
+            NextAttestationId::<T>::put(id.saturating_add(1));
+
+            Self::deposit_event(Event::CredentialIssued {
+                id,
+                subject: who,
+                source,
+                score_band: snapshot.band,
+                expires_at,
+            });
+            Ok(())
+        }
+
+        /// 撤销信用凭证（凭证主体或治理）
+        ///
+        /// 撤销后凭证保留在链上并标记为已撤销，状态证明验证将失败。
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::WeightInfo::revoke_credential())]
+        pub fn revoke_credential(origin: OriginFor<T>, id: u64) -> DispatchResult {
+            let caller = match T::GovernanceOrigin::try_origin(origin) {
+                Ok(_) => None,
+                Err(origin) => Some(ensure_signed(origin)?),
+            };
+
+            let subject = Attestations::<T>::try_mutate(id, |maybe| -> Result<_, DispatchError> {
+                let attestation = maybe.as_mut().ok_or(Error::<T>::CredentialNotFound)?;
+                if let Some(caller) = &caller {
+                    ensure!(attestation.subject == *caller, Error::<T>::NotCredentialSubject);
+                }
+                ensure!(!attestation.revoked, Error::<T>::CredentialAlreadyRevoked);
+                attestation.revoked = true;
+                Ok(attestation.subject.clone())
+            })?;
+
+            Self::deposit_event(Event::CredentialRevoked { id, subject });
+            Ok(())
+        }
+    }
+}
+
+// ============================================================================
+// 内部函数
+// ============================================================================
+
+impl<T: Config> Pallet<T> {
+    /// 记录信号并更新领域记录
+    pub(crate) fn do_report(who: &T::AccountId, domain: ReputationDomain, signal: ReputationSignal) {
+        let now = frame_system::Pallet::<T>::block_number();
+        let old_level = Self::risk_level_of(who);
+
+        let mut record =
+            DomainRecords::<T>::get(who, domain).unwrap_or_else(|| DomainRecord::new(now));
+        record.apply_decay(now, T::PenaltyHalfLife::get());
+
+        match signal {
+            ReputationSignal::Fulfilled => {
+                record.positive_count = record.positive_count.saturating_add(1);
+                record.credit = record.credit.saturating_add(1).min(T::MaxCredit::get());
+            },
+            ReputationSignal::Violation(severity) => {
+                record.negative_count = record.negative_count.saturating_add(1);
+                record.penalty = record.penalty.saturating_add(severity.penalty());
+            },
+            ReputationSignal::ViolationRevoked(severity, reported_at) => {
+                // 原违规的风险分已随时间衰减，只扣回其当前剩余部分
+                let remaining =
+                    decay_penalty(severity.penalty(), reported_at.into(), now, T::PenaltyHalfLife::get());
+                record.negative_count = record.negative_count.saturating_sub(1);
+                record.penalty = record.penalty.saturating_sub(remaining);
+            },
+            ReputationSignal::DisputeLost => {
+                record.negative_count = record.negative_count.saturating_add(1);
+                record.penalty = record.penalty.saturating_add(DISPUTE_LOST_PENALTY);
+            },
+            ReputationSignal::Suspended => {
+                record.negative_count = record.negative_count.saturating_add(1);
+                record.penalty = record.penalty.saturating_add(SUSPENSION_PENALTY);
+            },
+            ReputationSignal::Banned => {
+                record.negative_count = record.negative_count.saturating_add(1);
+                record.banned = true;
+            },
+        }
+        record.last_signal_at = now;
+        let domain_penalty = record.penalty;
+        DomainRecords::<T>::insert(who, domain, record);
+
+        Self::deposit_event(Event::SignalRecorded {
+            who: who.clone(),
+            domain,
+            signal,
+            domain_penalty,
+            composite_risk: Self::composite_risk_of(who),
+        });
+        Self::note_level_change(who, old_level);
+    }
+
+    /// 综合风险分（截至当前区块）
+    ///
+    /// risk = Σ(领域风险分 × 领域权重) − min(正向积分合计, 加权风险分 / 2)，
+    /// 上限 1000；任一领域被封禁时不低于 `BANNED_RISK_FLOOR`。
+    pub fn composite_risk_of(who: &T::AccountId) -> u16 {
+        let now = frame_system::Pallet::<T>::block_number();
+        let half_life = T::PenaltyHalfLife::get();
+
+        let mut weighted: u32 = 0;
+        let mut credit: u32 = 0;
+        let mut banned = false;
+        for (domain, record) in DomainRecords::<T>::iter_prefix(who) {
+            let penalty = record.decayed_penalty(now, half_life);
+            weighted = weighted.saturating_add(penalty.saturating_mul(domain.risk_weight()) / 100);
+            credit = credit.saturating_add(record.credit);
+            banned |= record.banned;
+        }
+
+        let risk = weighted.saturating_sub(credit.min(weighted / 2)).min(MAX_RISK as u32) as u16;
+        if banned {
+            risk.max(BANNED_RISK_FLOOR)
+        } else {
+            risk
+        }
+    }
+
+    /// 综合风险等级（截至当前区块）
+    pub fn risk_level_of(who: &T::AccountId) -> RiskLevel {
+        RiskLevel::from_risk(Self::composite_risk_of(who))
+    }
+
+    /// 账户信誉概览（Runtime API）
+    pub fn reputation_summary(who: &T::AccountId) -> ReputationSummary<BlockNumberFor<T>> {
+        let now = frame_system::Pallet::<T>::block_number();
+        let half_life = T::PenaltyHalfLife::get();
+        let domains: Vec<_> = ReputationDomain::ALL
+            .iter()
+            .filter_map(|domain| {
+                DomainRecords::<T>::get(who, domain).map(|record| DomainSummary {
+                    domain: *domain,
+                    penalty: record.decayed_penalty(now, half_life),
+                    credit: record.credit,
+                    positive_count: record.positive_count,
+                    negative_count: record.negative_count,
+                    banned: record.banned,
+                    last_signal_at: record.last_signal_at,
+                })
+            })
+            .collect();
+        let composite_risk = Self::composite_risk_of(who);
+
+        ReputationSummary {
+            composite_risk,
+            risk_level: RiskLevel::from_risk(composite_risk),
+            domains,
+        }
+    }
+
+    /// 凭证的 W3C VC 风格 JSON（Runtime API）
+    ///
+    /// 发行方以创世区块哈希标识本链。
+    pub fn credential_json(id: u64) -> Option<alloc::string::String> {
+        let attestation = Attestations::<T>::get(id)?;
+        let genesis = frame_system::Pallet::<T>::block_hash(BlockNumberFor::<T>::zero());
+        Some(credential::to_vc_json(
+            &attestation,
+            genesis.as_ref(),
+            &Attestations::<T>::hashed_key_for(id),
+        ))
+    }
+
+    /// 综合风险等级变化时发出事件
+    fn note_level_change(who: &T::AccountId, old_level: RiskLevel) {
+        let new_level = Self::risk_level_of(who);
+        if new_level != old_level {
+            Self::deposit_event(Event::RiskLevelChanged { who: who.clone(), old_level, new_level });
+        }
+    }
+}
+
+// ============================================================================
+// Trait 实现
+// ============================================================================
+
+impl<T: Config> ReputationSink<T::AccountId> for Pallet<T> {
+    fn report(who: &T::AccountId, domain: ReputationDomain, signal: ReputationSignal) {
+        Self::do_report(who, domain, signal);
+    }
+}
+
+impl<T: Config> ReputationProvider<T::AccountId> for Pallet<T> {
+    fn composite_risk(who: &T::AccountId) -> u16 {
+        Self::composite_risk_of(who)
+    }
+
+    fn risk_level(who: &T::AccountId) -> RiskLevel {
+        Self::risk_level_of(who)
+    }
+
+    fn is_banned_in(who: &T::AccountId, domain: ReputationDomain) -> bool {
+        DomainRecords::<T>::get(who, domain).map(|record| record.banned).unwrap_or(false)
+    }
+}
@@ -0,0 +1,69 @@
+//! # 跨域信誉聚合 - 测试 Mock
+
+use crate as pallet_reputation;
+use crate::{
+    traits::CreditSnapshotProvider,
+    types::{CredentialSource, CreditSnapshot, ScoreBand},
+};
+use frame_support::{derive_impl, parameter_types, traits::ConstU32};
+use frame_system::EnsureRoot;
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Reputation: pallet_reputation,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = ();
+}
+
+parameter_types! {
+    pub const PenaltyHalfLife: u64 = 100;
+    pub const CredentialValidity: u64 = 50;
+}
+
+/// 测试信用来源：ALICE 为做市商，BOB 无记录
+pub struct MockCreditSource;
+
+impl CreditSnapshotProvider<u64> for MockCreditSource {
+    fn credit_snapshot(who: &u64, source: CredentialSource) -> Option<CreditSnapshot> {
+        match (*who, source) {
+            (ALICE, CredentialSource::TradingMaker) => Some(CreditSnapshot {
+                band: ScoreBand::Excellent,
+                order_count: 400,
+                dispute_count: 3,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl pallet_reputation::Config for Test {
+    type PenaltyHalfLife = PenaltyHalfLife;
+    type MaxCredit = ConstU32<50>;
+    type GovernanceOrigin = EnsureRoot<u64>;
+    type CreditSource = MockCreditSource;
+    type CredentialValidity = CredentialValidity;
+    type MaxCredentialsPerAccount = ConstU32<2>;
+    type WeightInfo = ();
+}
+
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+
+/// 构建测试外部性
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
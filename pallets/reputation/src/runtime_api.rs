@@ -0,0 +1,54 @@
+//! 跨域信誉聚合 Runtime API 定义
+//!
+//! 为前端与风控服务提供账户信誉的一次性查询，以及信用凭证导出
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use crate::types::{CreditAttestation, ReputationDomain, RiskLevel};
+
+sp_api::decl_runtime_apis! {
+    /// 跨域信誉 Runtime API
+    pub trait ReputationApi<AccountId, BlockNumber>
+    where
+        AccountId: Codec,
+        BlockNumber: Codec,
+    {
+        /// 获取账户信誉概览（分域评分已按当前区块衰减）
+        fn get_reputation(who: AccountId) -> ReputationSummary<BlockNumber>;
+
+        /// 获取信用凭证（链上记录）
+        fn get_credential(id: u64) -> Option<CreditAttestation<AccountId, BlockNumber>>;
+
+        /// 获取账户持有的凭证 ID
+        fn get_account_credentials(who: AccountId) -> Vec<u64>;
+
+        /// 导出信用凭证的 W3C VC 风格 JSON
+        fn get_credential_json(id: u64) -> Option<alloc::string::String>;
+    }
+}
+
+/// 单领域信誉 (用于 API 返回)
+#[derive(codec::Encode, codec::Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+pub struct DomainSummary<BlockNumber> {
+    pub domain: ReputationDomain,
+    /// 当前风险分（已衰减）
+    pub penalty: u32,
+    /// 正向积分
+    pub credit: u32,
+    pub positive_count: u32,
+    pub negative_count: u32,
+    pub banned: bool,
+    /// 最近一次信号区块
+    pub last_signal_at: BlockNumber,
+}
+
+/// 账户信誉概览 (用于 API 返回)
+#[derive(codec::Encode, codec::Decode, Clone, PartialEq, Eq, Debug, scale_info::TypeInfo)]
+pub struct ReputationSummary<BlockNumber> {
+    /// 综合风险分（0-1000）
+    pub composite_risk: u16,
+    pub risk_level: RiskLevel,
+    /// 有记录的领域
+    pub domains: Vec<DomainSummary<BlockNumber>>,
+}
@@ -0,0 +1,377 @@
+//! # 跨域信誉聚合 - 单元测试
+
+use crate::{
+    credential::{self, CredentialVerifyError},
+    mock::*,
+    traits::{ReputationProvider, ReputationSink},
+    types::*,
+    AccountAttestations, Attestations, DomainRecords, Error, Event,
+};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+
+fn report(who: u64, domain: ReputationDomain, signal: ReputationSignal) {
+    <Reputation as ReputationSink<u64>>::report(&who, domain, signal);
+}
+
+#[test]
+fn violations_accumulate_per_domain() {
+    new_test_ext().execute_with(|| {
+        report(ALICE, ReputationDomain::Trading, ReputationSignal::Violation(Severity::Moderate));
+        report(ALICE, ReputationDomain::Trading, ReputationSignal::DisputeLost);
+
+        let record = DomainRecords::<Test>::get(ALICE, ReputationDomain::Trading).unwrap();
+        assert_eq!(record.penalty, 150);
+        assert_eq!(record.negative_count, 2);
+        assert_eq!(Reputation::composite_risk(&ALICE), 150);
+        assert_eq!(Reputation::composite_risk(&BOB), 0);
+
+        // 领域权重：直播 60%
+        report(BOB, ReputationDomain::Livestream, ReputationSignal::Violation(Severity::Critical));
+        assert_eq!(Reputation::composite_risk(&BOB), 180);
+        System::assert_has_event(
+            Event::SignalRecorded {
+                who: BOB,
+                domain: ReputationDomain::Livestream,
+                signal: ReputationSignal::Violation(Severity::Critical),
+                domain_penalty: 300,
+                composite_risk: 180,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn penalty_decays_by_half_life() {
+    new_test_ext().execute_with(|| {
+        report(ALICE, ReputationDomain::Trading, ReputationSignal::Violation(Severity::Critical));
+        assert_eq!(Reputation::composite_risk(&ALICE), 300);
+
+        // 半衰期内线性插值
+        System::set_block_number(51);
+        assert_eq!(Reputation::composite_risk(&ALICE), 225);
+        System::set_block_number(101);
+        assert_eq!(Reputation::composite_risk(&ALICE), 150);
+
+        // 新信号先将已有风险分结算到当前区块，新风险分从当前区块起衰减
+        System::set_block_number(151);
+        report(ALICE, ReputationDomain::Trading, ReputationSignal::Violation(Severity::Minor));
+        let record = DomainRecords::<Test>::get(ALICE, ReputationDomain::Trading).unwrap();
+        assert_eq!(record.penalty, 133);
+        assert_eq!(record.decayed_at, 151);
+
+        System::set_block_number(251);
+        assert_eq!(Reputation::composite_risk(&ALICE), 66);
+        System::set_block_number(10_000);
+        assert_eq!(Reputation::composite_risk(&ALICE), 0);
+    });
+}
+
+#[test]
+fn ban_in_one_domain_blocks_everywhere() {
+    new_test_ext().execute_with(|| {
+        report(ALICE, ReputationDomain::Livestream, ReputationSignal::Banned);
+
+        assert!(Reputation::is_banned_in(&ALICE, ReputationDomain::Livestream));
+        assert!(!Reputation::is_banned_in(&ALICE, ReputationDomain::Trading));
+        assert_eq!(Reputation::composite_risk(&ALICE), BANNED_RISK_FLOOR);
+        assert_eq!(Reputation::risk_level(&ALICE), RiskLevel::Blocked);
+        System::assert_has_event(
+            Event::RiskLevelChanged {
+                who: ALICE,
+                old_level: RiskLevel::Low,
+                new_level: RiskLevel::Blocked,
+            }
+            .into(),
+        );
+
+        // 封禁不衰减
+        System::set_block_number(100_000);
+        assert_eq!(Reputation::risk_level(&ALICE), RiskLevel::Blocked);
+    });
+}
+
+#[test]
+fn credit_offsets_at_most_half_of_risk() {
+    new_test_ext().execute_with(|| {
+        for _ in 0..60 {
+            report(ALICE, ReputationDomain::Trading, ReputationSignal::Fulfilled);
+        }
+        let record = DomainRecords::<Test>::get(ALICE, ReputationDomain::Trading).unwrap();
+        assert_eq!(record.credit, 50);
+        assert_eq!(record.positive_count, 60);
+        assert_eq!(Reputation::composite_risk(&ALICE), 0);
+
+        // 婚恋严重违规 150 × 80% = 120，积分最多抵消一半
+        report(ALICE, ReputationDomain::Matchmaking, ReputationSignal::Violation(Severity::Severe));
+        assert_eq!(Reputation::composite_risk(&ALICE), 70);
+
+        report(ALICE, ReputationDomain::Matchmaking, ReputationSignal::Suspended);
+        assert_eq!(Reputation::composite_risk(&ALICE), 230);
+        assert_eq!(Reputation::risk_level(&ALICE), RiskLevel::Medium);
+    });
+}
+
+#[test]
+fn revoked_violation_restores_penalty() {
+    new_test_ext().execute_with(|| {
+        report(ALICE, ReputationDomain::DivinationMarket, ReputationSignal::Violation(Severity::Severe));
+        report(
+            ALICE,
+            ReputationDomain::DivinationMarket,
+            ReputationSignal::ViolationRevoked(Severity::Severe, 1),
+        );
+
+        let record = DomainRecords::<Test>::get(ALICE, ReputationDomain::DivinationMarket).unwrap();
+        assert_eq!(record.penalty, 0);
+        assert_eq!(record.negative_count, 0);
+    });
+}
+
+#[test]
+fn revoked_violation_subtracts_decayed_penalty() {
+    new_test_ext().execute_with(|| {
+        report(ALICE, ReputationDomain::DivinationMarket, ReputationSignal::Violation(Severity::Severe));
+
+        // 一个半衰期后新增轻微违规：150 衰减为 75，叠加 20
+        System::set_block_number(101);
+        report(ALICE, ReputationDomain::DivinationMarket, ReputationSignal::Violation(Severity::Minor));
+        assert_eq!(
+            DomainRecords::<Test>::get(ALICE, ReputationDomain::DivinationMarket).unwrap().penalty,
+            95
+        );
+
+        // 撤销区块 1 的严重违规只扣回其剩余的 75，不误伤新违规
+        report(
+            ALICE,
+            ReputationDomain::DivinationMarket,
+            ReputationSignal::ViolationRevoked(Severity::Severe, 1),
+        );
+        let record = DomainRecords::<Test>::get(ALICE, ReputationDomain::DivinationMarket).unwrap();
+        assert_eq!(record.penalty, 20);
+        assert_eq!(record.negative_count, 1);
+    });
+}
+
+#[test]
+fn governance_can_report_and_clear() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Reputation::report_signal(
+                RuntimeOrigin::signed(BOB),
+                ALICE,
+                ReputationDomain::Arbitration,
+                ReputationSignal::Banned,
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Reputation::report_signal(
+            RuntimeOrigin::root(),
+            ALICE,
+            ReputationDomain::Arbitration,
+            ReputationSignal::Banned,
+        ));
+        assert_eq!(Reputation::risk_level(&ALICE), RiskLevel::Blocked);
+
+        assert_ok!(Reputation::clear_domain(
+            RuntimeOrigin::root(),
+            ALICE,
+            ReputationDomain::Arbitration,
+        ));
+        assert_eq!(Reputation::risk_level(&ALICE), RiskLevel::Low);
+        System::assert_last_event(
+            Event::RiskLevelChanged {
+                who: ALICE,
+                old_level: RiskLevel::Blocked,
+                new_level: RiskLevel::Low,
+            }
+            .into(),
+        );
+        assert_noop!(
+            Reputation::clear_domain(RuntimeOrigin::root(), ALICE, ReputationDomain::Arbitration),
+            Error::<Test>::RecordNotFound
+        );
+    });
+}
+
+#[test]
+fn summary_lists_decayed_domains() {
+    new_test_ext().execute_with(|| {
+        report(ALICE, ReputationDomain::Trading, ReputationSignal::Fulfilled);
+        report(ALICE, ReputationDomain::Arbitration, ReputationSignal::DisputeLost);
+        System::set_block_number(101);
+
+        let summary = Reputation::reputation_summary(&ALICE);
+        assert_eq!(summary.domains.len(), 2);
+        assert_eq!(summary.domains[0].domain, ReputationDomain::Trading);
+        assert_eq!(summary.domains[0].credit, 1);
+        assert_eq!(summary.domains[1].domain, ReputationDomain::Arbitration);
+        assert_eq!(summary.domains[1].penalty, 50);
+        assert_eq!(summary.composite_risk, 49);
+        assert_eq!(summary.risk_level, RiskLevel::Low);
+    });
+}
+
+#[test]
+fn issue_credential_snapshots_credit() {
+    new_test_ext().execute_with(|| {
+        report(ALICE, ReputationDomain::Livestream, ReputationSignal::Violation(Severity::Critical));
+        assert_ok!(Reputation::issue_credential(
+            RuntimeOrigin::signed(ALICE),
+            CredentialSource::TradingMaker,
+        ));
+
+        let attestation = Attestations::<Test>::get(0).unwrap();
+        assert_eq!(attestation.subject, ALICE);
+        assert_eq!(attestation.score_band, ScoreBand::Excellent);
+        assert_eq!(attestation.order_count, 400);
+        assert_eq!(attestation.dispute_rate_bps, 75);
+        assert_eq!(attestation.risk_level, RiskLevel::Low);
+        assert_eq!((attestation.as_of, attestation.expires_at), (1, 51));
+        assert_eq!(AccountAttestations::<Test>::get(ALICE).into_inner(), vec![0]);
+        System::assert_last_event(
+            Event::CredentialIssued {
+                id: 0,
+                subject: ALICE,
+                source: CredentialSource::TradingMaker,
+                score_band: ScoreBand::Excellent,
+                expires_at: 51,
+            }
+            .into(),
+        );
+
+        assert_noop!(
+            Reputation::issue_credential(RuntimeOrigin::signed(ALICE), CredentialSource::MarketProvider),
+            Error::<Test>::NoCreditRecord
+        );
+        assert_noop!(
+            Reputation::issue_credential(RuntimeOrigin::signed(BOB), CredentialSource::TradingMaker),
+            Error::<Test>::NoCreditRecord
+        );
+    });
+}
+
+#[test]
+fn credential_limit_prunes_only_expired() {
+    new_test_ext().execute_with(|| {
+        let issue = || Reputation::issue_credential(RuntimeOrigin::signed(ALICE), CredentialSource::TradingMaker);
+        assert_ok!(issue());
+        assert_ok!(issue());
+        assert_noop!(issue(), Error::<Test>::TooManyCredentials);
+
+        // 撤销的凭证保留到过期，仍占用名额
+        assert_ok!(Reputation::revoke_credential(RuntimeOrigin::signed(ALICE), 0));
+        assert_noop!(issue(), Error::<Test>::TooManyCredentials);
+        assert!(Attestations::<Test>::get(0).unwrap().revoked);
+
+        // 过期的凭证在下次签发时清理
+        System::set_block_number(52);
+        assert_ok!(issue());
+        assert!(Attestations::<Test>::get(0).is_none());
+        assert!(Attestations::<Test>::get(1).is_none());
+        assert_eq!(AccountAttestations::<Test>::get(ALICE).into_inner(), vec![2]);
+    });
+}
+
+#[test]
+fn revoke_credential_requires_subject_or_governance() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Reputation::issue_credential(
+            RuntimeOrigin::signed(ALICE),
+            CredentialSource::TradingMaker,
+        ));
+        assert_noop!(
+            Reputation::revoke_credential(RuntimeOrigin::signed(BOB), 0),
+            Error::<Test>::NotCredentialSubject
+        );
+        assert_noop!(
+            Reputation::revoke_credential(RuntimeOrigin::signed(ALICE), 9),
+            Error::<Test>::CredentialNotFound
+        );
+
+        assert_ok!(Reputation::revoke_credential(RuntimeOrigin::root(), 0));
+        assert!(Attestations::<Test>::get(0).unwrap().revoked);
+        System::assert_last_event(Event::CredentialRevoked { id: 0, subject: ALICE }.into());
+        assert_noop!(
+            Reputation::revoke_credential(RuntimeOrigin::signed(ALICE), 0),
+            Error::<Test>::CredentialAlreadyRevoked
+        );
+    });
+}
+
+#[test]
+fn credential_json_is_vc_shaped() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Reputation::issue_credential(
+            RuntimeOrigin::signed(ALICE),
+            CredentialSource::TradingMaker,
+        ));
+
+        let json = Reputation::credential_json(0).unwrap();
+        assert!(json.starts_with(r#"{"@context":["https://www.w3.org/ns/credentials/v2"]"#));
+        assert!(json.contains(r#""type":["VerifiableCredential","StardustCreditAttestation"]"#));
+        assert!(json.contains(r#""id":"urn:stardust:credential:0""#));
+        assert!(json.contains(r#""validFromBlock":1,"validUntilBlock":51"#));
+        assert!(json.contains(
+            r#""source":"TradingMaker","scoreBand":"Excellent","orderCount":400,"disputeRateBps":75,"riskLevel":"Low","asOfBlock":1"#
+        ));
+        assert!(json.contains(r#""revoked":false"#));
+        let storage_key = format!(
+            "0x{}",
+            sp_core::hexdisplay::HexDisplay::from(&credential::credential_storage_key(b"Reputation", 0))
+        );
+        assert!(json.contains(&storage_key));
+        assert!(json.ends_with("}}"));
+        assert!(Reputation::credential_json(1).is_none());
+    });
+}
+
+#[test]
+fn credential_verifies_against_state_proof() {
+    let mut ext = new_test_ext();
+    let (key, attestation) = ext.execute_with(|| {
+        assert_ok!(Reputation::issue_credential(
+            RuntimeOrigin::signed(ALICE),
+            CredentialSource::TradingMaker,
+        ));
+        (Attestations::<Test>::hashed_key_for(0), Attestations::<Test>::get(0).unwrap())
+    });
+    assert_eq!(key, credential::credential_storage_key(b"Reputation", 0));
+
+    ext.commit_all().unwrap();
+    let backend = ext.as_backend();
+    let root = *backend.root();
+    let proof = sp_state_machine::prove_read(backend, &[&key]).unwrap();
+    let verify = |claimed: &CreditAttestation<u64, u64>, at: u64| {
+        credential::verify_credential(b"Reputation", root, proof.clone(), claimed, at)
+    };
+
+    assert_eq!(verify(&attestation, 1), Ok(()));
+    assert_eq!(verify(&attestation, 51), Ok(()));
+    assert_eq!(verify(&attestation, 52), Err(CredentialVerifyError::Expired));
+    // 证明区块早于签发区块
+    assert_eq!(verify(&attestation, 0), Err(CredentialVerifyError::ProofBeforeIssuance));
+
+    let inflated = CreditAttestation { order_count: 4_000, ..attestation.clone() };
+    assert_eq!(verify(&inflated, 10), Err(CredentialVerifyError::Mismatch));
+
+    let unknown = CreditAttestation { id: 7, ..attestation.clone() };
+    assert!(matches!(
+        verify(&unknown, 10),
+        Err(CredentialVerifyError::InvalidProof | CredentialVerifyError::NotFound)
+    ));
+
+    // 撤销后的新状态证明
+    ext.execute_with(|| {
+        assert_ok!(Reputation::revoke_credential(RuntimeOrigin::signed(ALICE), 0));
+    });
+    ext.commit_all().unwrap();
+    let backend = ext.as_backend();
+    let root = *backend.root();
+    let proof = sp_state_machine::prove_read(backend, &[&key]).unwrap();
+    assert_eq!(
+        credential::verify_credential(b"Reputation", root, proof, &attestation, 10),
+        Err(CredentialVerifyError::Revoked)
+    );
+}
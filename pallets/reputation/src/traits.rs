@@ -0,0 +1,59 @@
+//! # 跨域信誉聚合 - Trait 定义
+//!
+//! - `ReputationSink`: 各业务模块上报信誉信号
+//! - `ReputationProvider`: 各业务模块读取综合风险，用于准入控制
+//! - `CreditSnapshotProvider`: 信用来源模块提供快照，用于签发信用凭证
+
+use crate::types::{CredentialSource, CreditSnapshot, ReputationDomain, ReputationSignal, RiskLevel};
+
+/// 信誉信号上报接口
+pub trait ReputationSink<AccountId> {
+    /// 上报信号（不返回错误，信誉记录失败不影响业务主流程）
+    fn report(who: &AccountId, domain: ReputationDomain, signal: ReputationSignal);
+}
+
+/// 空实现（未接入信誉聚合的运行时 / 测试）
+impl<AccountId> ReputationSink<AccountId> for () {
+    fn report(_who: &AccountId, _domain: ReputationDomain, _signal: ReputationSignal) {}
+}
+
+/// 信誉查询接口
+pub trait ReputationProvider<AccountId> {
+    /// 综合风险分（0-1000，已按时间衰减）
+    fn composite_risk(who: &AccountId) -> u16;
+
+    /// 综合风险等级
+    fn risk_level(who: &AccountId) -> RiskLevel;
+
+    /// 是否在指定领域被封禁
+    fn is_banned_in(who: &AccountId, domain: ReputationDomain) -> bool;
+}
+
+impl<AccountId> ReputationProvider<AccountId> for () {
+    fn composite_risk(_who: &AccountId) -> u16 {
+        0
+    }
+
+    fn risk_level(_who: &AccountId) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn is_banned_in(_who: &AccountId, _domain: ReputationDomain) -> bool {
+        false
+    }
+}
+
+/// 信用快照接口
+///
+/// 由运行时桥接 `pallet-trading-credit`（账户 → 做市商 ID → 信用记录）与
+/// `pallet-divination-market`（提供者信用档案）。
+pub trait CreditSnapshotProvider<AccountId> {
+    /// 账户在指定来源的信用快照，无记录时返回 `None`
+    fn credit_snapshot(who: &AccountId, source: CredentialSource) -> Option<CreditSnapshot>;
+}
+
+impl<AccountId> CreditSnapshotProvider<AccountId> for () {
+    fn credit_snapshot(_who: &AccountId, _source: CredentialSource) -> Option<CreditSnapshot> {
+        None
+    }
+}
@@ -0,0 +1,343 @@
+//! # 跨域信誉聚合 - 类型定义
+
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::traits::{AtLeast32BitUnsigned, UniqueSaturatedInto};
+
+/// 综合风险分上限
+pub const MAX_RISK: u16 = 1000;
+
+/// 任一领域被封禁时综合风险分的下限（即 `RiskLevel::Blocked`）
+pub const BANNED_RISK_FLOOR: u16 = 800;
+
+/// 争议 / 仲裁败诉风险分
+pub const DISPUTE_LOST_PENALTY: u32 = 100;
+
+/// 暂停服务风险分
+pub const SUSPENSION_PENALTY: u32 = 200;
+
+/// 信誉领域
+///
+/// 每个信号来源模块对应一个领域，分域记录、综合评估。
+#[derive(
+    Clone, Copy, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug,
+)]
+pub enum ReputationDomain {
+    /// OTC / Swap 交易（pallet-trading-credit）
+    Trading,
+    /// 占卜服务市场（pallet-divination-market）
+    DivinationMarket,
+    /// 直播（pallet-livestream）
+    Livestream,
+    /// 婚恋（pallet-matchmaking-profile）
+    Matchmaking,
+    /// 争议仲裁与投诉（pallet-arbitration）
+    Arbitration,
+}
+
+impl ReputationDomain {
+    /// 全部领域
+    pub const ALL: [ReputationDomain; 5] = [
+        ReputationDomain::Trading,
+        ReputationDomain::DivinationMarket,
+        ReputationDomain::Livestream,
+        ReputationDomain::Matchmaking,
+        ReputationDomain::Arbitration,
+    ];
+
+    /// 计入综合风险分的权重（百分比）
+    ///
+    /// 涉及资金的领域权重更高。
+    pub fn risk_weight(&self) -> u32 {
+        match self {
+            ReputationDomain::Trading => 100,
+            ReputationDomain::Arbitration => 100,
+            ReputationDomain::DivinationMarket => 80,
+            ReputationDomain::Matchmaking => 80,
+            ReputationDomain::Livestream => 60,
+        }
+    }
+}
+
+/// 违规严重程度
+#[derive(
+    Clone, Copy, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug,
+)]
+pub enum Severity {
+    /// 轻微
+    Minor,
+    /// 一般
+    Moderate,
+    /// 严重
+    Severe,
+    /// 特别严重（欺诈、违法）
+    Critical,
+}
+
+impl Severity {
+    /// 风险分
+    pub fn penalty(&self) -> u32 {
+        match self {
+            Severity::Minor => 20,
+            Severity::Moderate => 50,
+            Severity::Severe => 150,
+            Severity::Critical => 300,
+        }
+    }
+}
+
+/// 信誉信号
+#[derive(
+    Clone, Copy, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug,
+)]
+pub enum ReputationSignal {
+    /// 正常履约（订单完成等），累积正向积分
+    Fulfilled,
+    /// 违规
+    Violation(Severity),
+    /// 申诉成功，撤销一次违规（附原违规上报时的区块号，按衰减后的风险分扣回）
+    ViolationRevoked(Severity, u32),
+    /// 争议 / 仲裁败诉
+    DisputeLost,
+    /// 暂停服务
+    Suspended,
+    /// 封禁（不随时间衰减，需治理清除）
+    Banned,
+}
+
+/// 单领域信誉记录
+#[derive(
+    Clone, Copy, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug,
+)]
+pub struct DomainRecord<BlockNumber> {
+    /// 风险分（随时间衰减，截至 `decayed_at`）
+    pub penalty: u32,
+    /// 正向积分（不衰减，上限 `MaxCredit`）
+    pub credit: u32,
+    /// 正向信号次数
+    pub positive_count: u32,
+    /// 负向信号次数
+    pub negative_count: u32,
+    /// 是否被封禁
+    pub banned: bool,
+    /// 风险分最近一次结算衰减的区块
+    pub decayed_at: BlockNumber,
+    /// 最近一次信号区块
+    pub last_signal_at: BlockNumber,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> DomainRecord<BlockNumber> {
+    /// 新记录
+    pub fn new(now: BlockNumber) -> Self {
+        Self {
+            penalty: 0,
+            credit: 0,
+            positive_count: 0,
+            negative_count: 0,
+            banned: false,
+            decayed_at: now,
+            last_signal_at: now,
+        }
+    }
+
+    /// 截至 `now` 的风险分
+    pub fn decayed_penalty(&self, now: BlockNumber, half_life: BlockNumber) -> u32 {
+        decay_penalty(self.penalty, self.decayed_at, now, half_life)
+    }
+
+    /// 将风险分结算衰减到 `now`，之后叠加的新风险分从 `now` 起计算衰减
+    pub fn apply_decay(&mut self, now: BlockNumber, half_life: BlockNumber) {
+        if now <= self.decayed_at {
+            return;
+        }
+        self.penalty = self.decayed_penalty(now, half_life);
+        self.decayed_at = now;
+    }
+}
+
+/// 风险分 `penalty` 自 `since` 起衰减到 `now` 的值
+///
+/// 每经过一个半衰期减半，不足一个半衰期的部分线性插值。
+pub fn decay_penalty<BlockNumber: AtLeast32BitUnsigned + Copy>(
+    penalty: u32,
+    since: BlockNumber,
+    now: BlockNumber,
+    half_life: BlockNumber,
+) -> u32 {
+    if half_life.is_zero() || now <= since {
+        return penalty;
+    }
+    let elapsed: u32 = now.saturating_sub(since).unique_saturated_into();
+    let half_life: u32 = half_life.unique_saturated_into();
+    let half_life = half_life.max(1);
+
+    let periods = elapsed / half_life;
+    if periods >= 32 {
+        return 0;
+    }
+    let halved = penalty >> periods;
+    let remainder = elapsed % half_life;
+    let fraction = (halved as u64).saturating_mul(remainder as u64) / (2 * half_life as u64);
+    halved.saturating_sub(fraction as u32)
+}
+
+/// 综合风险等级
+#[derive(
+    Clone,
+    Copy,
+    Encode,
+    Decode,
+    DecodeWithMemTracking,
+    TypeInfo,
+    MaxEncodedLen,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Debug,
+)]
+pub enum RiskLevel {
+    /// 低风险（< 200）
+    Low,
+    /// 中风险（200 - 499）
+    Medium,
+    /// 高风险（500 - 799）
+    High,
+    /// 拒绝服务（≥ 800，或任一领域被封禁）
+    Blocked,
+}
+
+impl RiskLevel {
+    /// 由综合风险分换算等级
+    pub fn from_risk(risk: u16) -> Self {
+        match risk {
+            0..=199 => RiskLevel::Low,
+            200..=499 => RiskLevel::Medium,
+            500..=799 => RiskLevel::High,
+            _ => RiskLevel::Blocked,
+        }
+    }
+}
+
+// ============================================================================
+// 可验证信用凭证
+// ============================================================================
+
+/// 信用凭证数据来源
+#[derive(
+    Clone, Copy, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug,
+)]
+pub enum CredentialSource {
+    /// OTC / Swap 做市商信用（pallet-trading-credit `MakerCredits`）
+    TradingMaker,
+    /// 占卜服务提供者信用（pallet-divination-market `CreditProfiles`）
+    MarketProvider,
+}
+
+impl CredentialSource {
+    /// 凭证 JSON 中的名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CredentialSource::TradingMaker => "TradingMaker",
+            CredentialSource::MarketProvider => "MarketProvider",
+        }
+    }
+}
+
+/// 信用分档
+///
+/// 凭证只披露分档而非原始分数，各来源按自身等级体系换算。
+#[derive(
+    Clone, Copy, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug,
+)]
+pub enum ScoreBand {
+    /// 卓越
+    Excellent,
+    /// 良好
+    Good,
+    /// 一般
+    Fair,
+    /// 较差
+    Poor,
+}
+
+impl ScoreBand {
+    /// 凭证 JSON 中的名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScoreBand::Excellent => "Excellent",
+            ScoreBand::Good => "Good",
+            ScoreBand::Fair => "Fair",
+            ScoreBand::Poor => "Poor",
+        }
+    }
+}
+
+impl RiskLevel {
+    /// 凭证 JSON 中的名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RiskLevel::Low => "Low",
+            RiskLevel::Medium => "Medium",
+            RiskLevel::High => "High",
+            RiskLevel::Blocked => "Blocked",
+        }
+    }
+}
+
+/// 信用快照（由来源模块提供）
+#[derive(Clone, Copy, Encode, Decode, TypeInfo, PartialEq, Eq, Debug)]
+pub struct CreditSnapshot {
+    /// 信用分档
+    pub band: ScoreBand,
+    /// 累计订单数
+    pub order_count: u32,
+    /// 争议败诉 / 投诉成立次数
+    pub dispute_count: u32,
+}
+
+impl CreditSnapshot {
+    /// 争议率（基点，10000 = 100%）
+    pub fn dispute_rate_bps(&self) -> u16 {
+        if self.order_count == 0 {
+            return 0;
+        }
+        (self.dispute_count.min(self.order_count) as u64 * 10_000 / self.order_count as u64) as u16
+    }
+}
+
+/// 信用凭证（链上锚定）
+///
+/// 存储于 `Attestations`，第三方通过状态证明验证其存在、未撤销且未过期。
+#[derive(
+    Clone, Encode, Decode, DecodeWithMemTracking, TypeInfo, MaxEncodedLen, PartialEq, Eq, Debug,
+)]
+pub struct CreditAttestation<AccountId, BlockNumber> {
+    /// 凭证 ID
+    pub id: u64,
+    /// 凭证主体
+    pub subject: AccountId,
+    /// 数据来源
+    pub source: CredentialSource,
+    /// 信用分档
+    pub score_band: ScoreBand,
+    /// 累计订单数
+    pub order_count: u32,
+    /// 争议率（基点）
+    pub dispute_rate_bps: u16,
+    /// 签发时的跨域综合风险等级
+    pub risk_level: RiskLevel,
+    /// 数据截至区块（签发区块）
+    pub as_of: BlockNumber,
+    /// 过期区块
+    pub expires_at: BlockNumber,
+    /// 是否已撤销
+    pub revoked: bool,
+}
+
+impl<AccountId, BlockNumber: PartialOrd> CreditAttestation<AccountId, BlockNumber> {
+    /// 在 `now` 时是否有效（未撤销且未过期）
+    pub fn is_valid_at(&self, now: BlockNumber) -> bool {
+        !self.revoked && now <= self.expires_at
+    }
+}
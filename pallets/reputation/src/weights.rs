@@ -0,0 +1,62 @@
+//! # 跨域信誉聚合 - 权重定义
+//!
+//! 在正式部署前应使用 benchmarking 进行精确测量。
+
+use frame_support::{traits::Get, weights::Weight};
+
+/// 权重信息 trait
+pub trait WeightInfo {
+    fn report_signal() -> Weight;
+    fn clear_domain() -> Weight;
+    fn issue_credential() -> Weight;
+    fn revoke_credential() -> Weight;
+}
+
+/// 默认权重实现
+pub struct SubstrateWeight<T>(core::marker::PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    /// - 读取全部领域记录（计算信号前后的综合风险），写入一条领域记录
+    fn report_signal() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    fn clear_domain() -> Weight {
+        Weight::from_parts(25_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+
+    /// - 读取信用快照、全部领域记录与已持有凭证（最坏情况全部过期被清理）
+    fn issue_credential() -> Weight {
+        Weight::from_parts(45_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(20))
+            .saturating_add(T::DbWeight::get().writes(13))
+    }
+
+    fn revoke_credential() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+}
+
+impl WeightInfo for () {
+    fn report_signal() -> Weight {
+        Weight::from_parts(30_000_000, 0)
+    }
+
+    fn clear_domain() -> Weight {
+        Weight::from_parts(25_000_000, 0)
+    }
+
+    fn issue_credential() -> Weight {
+        Weight::from_parts(45_000_000, 0)
+    }
+
+    fn revoke_credential() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+    }
+}
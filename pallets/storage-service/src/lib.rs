@@ -20,6 +20,10 @@ use sp_runtime::{
     traits::AtLeast32BitUnsigned,
 };
 use sp_std::vec::Vec;
+use pallet_recurring_payment::{
+    traits::{RecurringPaymentHandler, RecurringPayments},
+    types::{LapseReason, PaymentPrice, RecurringService},
+};
 
 /// 函数级详细中文注释：优化后的类型定义模块
 /// 
@@ -357,6 +361,13 @@ pub mod pallet {
     /// - 可通过治理调整
     #[pallet::constant]
     type DefaultBillingPeriod: Get<u32>;
+
+    /// 函数级中文注释：周期扣费接口（用户存储账户自动充值）
+    /// 
+    /// 说明：
+    /// - 按扣费周期（BillingPeriodBlocks）从用户账户向其存储账户定额充值
+    /// - 保证 `charge_due` 周期扣费时存储账户余额充足
+    type RecurringPayments: RecurringPayments<Self::AccountId, BalanceOf<Self>, BlockNumberFor<Self>>;
 }
 
     const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
@@ -1717,6 +1728,22 @@ pub mod pallet {
             old_owner: T::AccountId,
             new_owner: T::AccountId,
         },
+
+        /// 函数级中文注释：已开启存储账户自动充值（每个扣费周期充值 amount）
+        AutoTopUpEnabled {
+            user: T::AccountId,
+            amount: BalanceOf<T>,
+            period: BlockNumberFor<T>,
+        },
+        /// 函数级中文注释：已关闭存储账户自动充值
+        AutoTopUpDisabled {
+            user: T::AccountId,
+        },
+        /// 函数级中文注释：自动充值已终止（扣费失败超出宽限期或用户撤销授权）
+        AutoTopUpLapsed {
+            user: T::AccountId,
+            reason: LapseReason,
+        },
     }
 
     #[pallet::error]
@@ -1855,6 +1882,8 @@ pub mod pallet {
         NotDivinationClientOwner,
         /// 函数级详细中文注释：命主CID列表已满（最多100个）
         DivinationClientCidsFull,
+        /// 函数级中文注释：未开启自动充值
+        AutoTopUpNotEnabled,
     }
 
     impl<T: Config> Pallet<T> {
@@ -1896,6 +1925,40 @@ pub mod pallet {
             T::SubjectPalletId::get().into_sub_account_truncating((b"user", user))
         }
 
+        /// 函数级中文注释：从 funder 向 target_user 的存储账户充值
+        /// 
+        /// 供 `fund_user_account` 与自动充值回调共用。
+        pub(crate) fn do_fund_user_account(
+            funder: &T::AccountId,
+            target_user: &T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            // ✅ 派生用户级存储账户
+            let to = Self::derive_user_funding_account(target_user);
+            
+            // ✅ 转账（任何人都可以充值）
+            <T as Config>::Currency::transfer(
+                funder,
+                &to,
+                amount,
+                frame_support::traits::ExistenceRequirement::KeepAlive,
+            )?;
+            
+            // ✅ 更新用户充值统计
+            UserFundingBalance::<T>::mutate(target_user, |balance| {
+                *balance = balance.saturating_add(amount);
+            });
+            
+            // ✅ 发送事件
+            Self::deposit_event(Event::UserFunded {
+                user: target_user.clone(),
+                funder: funder.clone(),
+                funding_account: to,
+                amount,
+            });
+            Ok(())
+        }
+
         /// 函数级中文注释：将 SubjectType 转换为 domain 编号（用于 SubjectUsage 记账）
         #[inline]
         pub fn subject_type_to_domain(subject_type: &SubjectType) -> u8 {
@@ -2928,31 +2991,7 @@ pub mod pallet {
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
             ensure!(amount != BalanceOf::<T>::default(), Error::<T>::BadParams);
-            
-            // ✅ 派生用户级存储账户
-            let to = Self::derive_user_funding_account(&target_user);
-            
-            // ✅ 转账（任何人都可以充值）
-            <T as Config>::Currency::transfer(
-                &who,
-                &to,
-                amount,
-                frame_support::traits::ExistenceRequirement::KeepAlive,
-            )?;
-            
-            // ✅ 更新用户充值统计
-            UserFundingBalance::<T>::mutate(&target_user, |balance| {
-                *balance = balance.saturating_add(amount);
-            });
-            
-            // ✅ 发送事件
-            Self::deposit_event(Event::UserFunded {
-                user: target_user,
-                funder: who,
-                funding_account: to,
-                amount,
-            });
-            Ok(())
+            Self::do_fund_user_account(&who, &target_user, amount)
         }
 
         /// 函数级详细中文注释：为SubjectFunding账户充值（已弃用，保留向后兼容）
@@ -4633,6 +4672,58 @@ pub mod pallet {
                 Ok(())
             })
         }
+
+        /// 函数级详细中文注释：开启存储账户自动充值
+        /// 
+        /// ### 说明
+        /// - 每个扣费周期（BillingPeriodBlocks）从调用者账户向其存储账户充值 `amount`
+        /// - 首次充值在一个扣费周期后执行
+        /// - 需先在 `pallet-recurring-payment` 中为 `StorageService` 设置扣费授权
+        /// - 重复调用将以新金额和当前扣费周期替换原计划
+        /// 
+        /// ### 事件
+        /// - AutoTopUpEnabled { user, amount, period }
+        #[pallet::call_index(34)]
+        #[pallet::weight(30_000)]
+        pub fn enable_auto_top_up(
+            origin: OriginFor<T>,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(amount != BalanceOf::<T>::default(), Error::<T>::BadParams);
+
+            let period: BlockNumberFor<T> = BillingPeriodBlocks::<T>::get().into();
+            let now = <frame_system::Pallet<T>>::block_number();
+            T::RecurringPayments::cancel_all(&who, RecurringService::StorageService);
+            T::RecurringPayments::schedule(
+                &who,
+                RecurringService::StorageService,
+                0,
+                PaymentPrice::Fixed(amount),
+                period,
+                now.saturating_add(period),
+            )?;
+
+            Self::deposit_event(Event::AutoTopUpEnabled { user: who, amount, period });
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：关闭存储账户自动充值
+        /// 
+        /// ### 事件
+        /// - AutoTopUpDisabled { user }
+        #[pallet::call_index(35)]
+        #[pallet::weight(20_000)]
+        pub fn disable_auto_top_up(origin: OriginFor<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                T::RecurringPayments::cancel_all(&who, RecurringService::StorageService) > 0,
+                Error::<T>::AutoTopUpNotEnabled
+            );
+
+            Self::deposit_event(Event::AutoTopUpDisabled { user: who });
+            Ok(())
+        }
     }
 
     #[pallet::hooks]
@@ -5747,6 +5838,30 @@ impl<T: Config> IpfsPinner<<T as frame_system::Config>::AccountId, BalanceOf<T>>
     }
 }
 
+/// 函数级详细中文注释：周期扣费回调（存储账户自动充值）
+/// 
+/// - 到期：从付款人账户向其存储账户充值本期金额
+/// - 终止：发出 `AutoTopUpLapsed` 事件，存储账户余额不受影响
+impl<T: Config> RecurringPaymentHandler<<T as frame_system::Config>::AccountId, BalanceOf<T>> for Pallet<T> {
+    fn on_payment_due(
+        _service: RecurringService,
+        payer: &<T as frame_system::Config>::AccountId,
+        _subject: u64,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        Self::do_fund_user_account(payer, payer, amount)
+    }
+
+    fn on_payment_lapsed(
+        _service: RecurringService,
+        payer: &<T as frame_system::Config>::AccountId,
+        _subject: u64,
+        reason: LapseReason,
+    ) {
+        Self::deposit_event(Event::AutoTopUpLapsed { user: payer.clone(), reason });
+    }
+}
+
 /// 函数级详细中文注释：ContentRegistry trait实现 - 新pallet域自动PIN机制
 /// 
 /// 提供统一的内容注册接口，让新业务pallet无需了解IPFS细节即可实现内容固定。
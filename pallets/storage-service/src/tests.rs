@@ -129,6 +129,7 @@ impl crate::Config for Test {
     type MonthlyPublicFeeQuota = MonthlyPublicFeeQuota;
     type QuotaResetPeriod = QuotaResetPeriod;
     type DefaultBillingPeriod = frame_support::traits::ConstU32<100>; // 100块测试周期
+    type RecurringPayments = (); // 未接入周期扣费
 }
 
 fn new_test_ext() -> sp_io::TestExternalities {
@@ -1051,3 +1052,18 @@ fn emergency_pause_and_resume_billing() {
     });
 }
 
+/// 函数级中文注释：测试20 - 未接入周期扣费时自动充值不可用
+#[test]
+fn auto_top_up_requires_recurring_payments() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            crate::Pallet::<Test>::enable_auto_top_up(RuntimeOrigin::signed(1), 0),
+            crate::Error::<Test>::BadParams
+        );
+        assert!(crate::Pallet::<Test>::enable_auto_top_up(RuntimeOrigin::signed(1), 100).is_err());
+        assert_noop!(
+            crate::Pallet::<Test>::disable_auto_top_up(RuntimeOrigin::signed(1)),
+            crate::Error::<Test>::AutoTopUpNotEnabled
+        );
+    });
+}
@@ -6,7 +6,8 @@
 //!
 //! 本 crate 提供交易相关的公共工具函数和统一接口，包括：
 //! - 公共类型定义（TronAddress, MomentOf 等）
-//! - 公共 Trait 定义（PricingProvider, MakerInterface, MakerCreditInterface）
+//! - 公共 Trait 定义（PricingProvider, MakerInterface, MakerCreditInterface, PrivateEvidenceInterface）
+//! - 做市商报价类型（MakerQuote, PaymentRail, WorkingHours）
 //! - 脱敏函数（姓名、身份证、生日）
 //! - TRON 地址验证
 //! - 时间转换工具
@@ -26,6 +27,7 @@
 
 pub mod types;
 pub mod traits;
+pub mod quote;
 pub mod mask;
 pub mod validation;
 pub mod time;
@@ -39,6 +41,7 @@ pub use types::{
     TxHash,
     MakerApplicationInfo,
 };
+pub use quote::{MakerQuote, OrderSide, PaymentRail, WorkingHours};
 
 // ===== 🆕 v0.4.0: 重新导出公共 Trait =====
 pub use traits::{
@@ -46,6 +49,7 @@ pub use traits::{
     MakerInterface,
     MakerCreditInterface,
     MakerValidationError,
+    PrivateEvidenceInterface,
     // 🆕 v0.5.0: 统一保证金计算
     DepositCalculator,
     DepositCalculatorImpl,
@@ -0,0 +1,155 @@
+//! # 做市商报价类型
+//!
+//! 做市商发布的报价（溢价、限额、收款通道、营业时间），由 Maker 模块存储，
+//! OTC 模块在下单时读取。
+//!
+//! ## 版本历史
+//! - v0.1.0: 初始版本，支持买卖双向报价
+
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::ConstU32, BoundedVec};
+use scale_info::TypeInfo;
+
+/// 溢价上下限（基点，±5%）
+pub const MAX_PREMIUM_BPS: i16 = 500;
+
+/// 单个报价最多支持的收款通道数量
+pub type MaxPaymentRails = ConstU32<8>;
+
+/// 函数级详细中文注释：订单方向（站在用户角度）
+#[derive(Clone, Copy, Encode, Decode, DecodeWithMemTracking, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen, Default)]
+pub enum OrderSide {
+    /// 用户向做市商购买 DUST（做市商托管 DUST，用户付法币/USDT）
+    #[default]
+    Buy,
+    /// 用户向做市商出售 DUST（用户托管 DUST，做市商付法币/USDT）
+    Sell,
+}
+
+/// 函数级详细中文注释：收款/付款通道
+#[derive(Clone, Copy, Encode, Decode, DecodeWithMemTracking, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub enum PaymentRail {
+    /// USDT-TRC20 链上转账
+    UsdtTrc20,
+    /// 银行转账
+    BankTransfer,
+    /// 支付宝
+    Alipay,
+    /// 微信支付
+    WechatPay,
+    /// 其他（具体方式在订单加密资料中约定）
+    Other,
+}
+
+/// 函数级详细中文注释：营业时间（UTC，一天中的分钟数）
+///
+/// - `start_minute == end_minute`：全天营业
+/// - `start_minute < end_minute`：当天区间 [start, end)
+/// - `start_minute > end_minute`：跨零点区间，如 22:00 ~ 06:00
+#[derive(Clone, Copy, Encode, Decode, DecodeWithMemTracking, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen, Default)]
+pub struct WorkingHours {
+    /// 开始时间（0 ~ 1439）
+    pub start_minute: u16,
+    /// 结束时间（0 ~ 1439）
+    pub end_minute: u16,
+}
+
+impl WorkingHours {
+    /// 一天的分钟数
+    pub const MINUTES_PER_DAY: u16 = 1440;
+
+    /// 字段是否在合法范围内
+    pub fn is_valid(&self) -> bool {
+        self.start_minute < Self::MINUTES_PER_DAY && self.end_minute < Self::MINUTES_PER_DAY
+    }
+
+    /// 给定 Unix 时间（秒）是否在营业时间内
+    pub fn is_open_at(&self, unix_secs: u64) -> bool {
+        let minute = ((unix_secs / 60) % Self::MINUTES_PER_DAY as u64) as u16;
+        if self.start_minute == self.end_minute {
+            true
+        } else if self.start_minute < self.end_minute {
+            minute >= self.start_minute && minute < self.end_minute
+        } else {
+            minute >= self.start_minute || minute < self.end_minute
+        }
+    }
+}
+
+/// 函数级详细中文注释：做市商报价
+///
+/// 溢价相对于 pricing 模块的市场价：
+/// - `sell_premium_bps`：做市商卖出 DUST（用户买入）时的溢价
+/// - `buy_premium_bps`：做市商买入 DUST（用户卖出）时的溢价
+#[derive(Clone, Encode, Decode, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct MakerQuote<Balance> {
+    /// 买入溢价（基点，-500 ~ 500）
+    pub buy_premium_bps: i16,
+    /// 卖出溢价（基点，-500 ~ 500）
+    pub sell_premium_bps: i16,
+    /// 单笔最小 DUST 数量
+    pub min_amount: Balance,
+    /// 单笔最大 DUST 数量
+    pub max_amount: Balance,
+    /// 支持的收款/付款通道
+    pub payment_rails: BoundedVec<PaymentRail, MaxPaymentRails>,
+    /// 营业时间
+    pub working_hours: WorkingHours,
+    /// 最后更新时间（Unix 秒）
+    pub updated_at: u32,
+}
+
+impl<Balance> MakerQuote<Balance> {
+    /// 指定方向适用的溢价
+    pub fn premium_bps(&self, side: OrderSide) -> i16 {
+        match side {
+            OrderSide::Buy => self.sell_premium_bps,
+            OrderSide::Sell => self.buy_premium_bps,
+        }
+    }
+}
+
+/// 溢价是否在允许范围内
+pub fn is_valid_premium(bps: i16) -> bool {
+    (-MAX_PREMIUM_BPS..=MAX_PREMIUM_BPS).contains(&bps)
+}
+
+/// 溢价对应的价格乘数（基点，10_000 = 原价）
+pub fn premium_multiplier_bps(bps: i16) -> u32 {
+    (10_000i32 + bps as i32).max(0) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn working_hours_same_day() {
+        // 09:00 ~ 18:00
+        let hours = WorkingHours { start_minute: 540, end_minute: 1080 };
+        assert!(hours.is_open_at(9 * 3600));
+        assert!(hours.is_open_at(17 * 3600 + 59 * 60));
+        assert!(!hours.is_open_at(18 * 3600));
+        assert!(!hours.is_open_at(86400 + 8 * 3600));
+    }
+
+    #[test]
+    fn working_hours_overnight_and_all_day() {
+        // 22:00 ~ 06:00
+        let hours = WorkingHours { start_minute: 1320, end_minute: 360 };
+        assert!(hours.is_open_at(23 * 3600));
+        assert!(hours.is_open_at(2 * 3600));
+        assert!(!hours.is_open_at(12 * 3600));
+
+        assert!(WorkingHours::default().is_open_at(12 * 3600));
+        assert!(!WorkingHours { start_minute: 1440, end_minute: 0 }.is_valid());
+    }
+
+    #[test]
+    fn premium_helpers() {
+        assert!(is_valid_premium(-500));
+        assert!(!is_valid_premium(501));
+        assert_eq!(premium_multiplier_bps(150), 10_150);
+        assert_eq!(premium_multiplier_bps(-200), 9_800);
+    }
+}
@@ -7,6 +7,7 @@
 
 use sp_runtime::{DispatchResult, DispatchError};
 use crate::types::MakerApplicationInfo;
+use crate::quote::{MakerQuote, OrderSide};
 
 /// 函数级详细中文注释：定价服务接口
 ///
@@ -93,6 +94,22 @@ pub trait MakerInterface<AccountId, Balance> {
     /// - `Err(...)`: 做市商不存在或查询失败
     fn get_deposit_usd_value(maker_id: u64) -> Result<u64, DispatchError>;
 
+    /// 查询做市商当前报价
+    ///
+    /// ## 返回
+    /// - `Some(quote)`: 做市商已发布报价
+    /// - `None`: 未发布报价（按市场价成交，不限额）
+    fn get_maker_quote(_maker_id: u64) -> Option<MakerQuote<Balance>> {
+        None
+    }
+
+    /// 做市商是否承接指定方向的订单
+    ///
+    /// 默认仅承接用户买入（做市商卖出 DUST），与旧版行为一致。
+    fn serves_side(_maker_id: u64, side: OrderSide) -> bool {
+        side == OrderSide::Buy
+    }
+
     /// 🆕 验证做市商并返回信息（组合验证）
     ///
     /// ## 说明
@@ -169,6 +186,15 @@ pub trait MakerCreditInterface {
         order_id: u64,
         maker_win: bool,
     ) -> DispatchResult;
+
+    /// 查询做市商信用分（OTC 拆单路由按信用分排序）
+    ///
+    /// ## 返回
+    /// - `Some(score)`: 信用分
+    /// - `None`: 无信用记录
+    fn query_maker_credit_score(_maker_id: u64) -> Option<u16> {
+        None
+    }
 }
 
 // ===== 默认实现（用于测试和 Mock）=====
@@ -229,6 +255,48 @@ impl MakerCreditInterface for () {
     }
 }
 
+// ===== 订单私密证据接口 =====
+
+/// 函数级详细中文注释：订单私密证据接口
+///
+/// ## 说明
+/// OTC 付款凭证加密后存入 `pallet-evidence` 的私密内容，订单只记录内容ID。
+/// 本接口用于校验该内容确属此订单、由付款方创建，且指定读者均持有密钥包
+/// （做市商/卖家 + 仲裁委员会），仲裁时可直接解密核验。
+///
+/// ## 实现者
+/// - runtime 适配器：桥接到 `pallet-evidence::PrivateContents`
+pub trait PrivateEvidenceInterface<AccountId> {
+    /// 私密内容是否满足订单凭证要求
+    ///
+    /// ## 参数
+    /// - `content_id`: 私密内容ID
+    /// - `ns`: 命名空间（OTC 订单为 `otc_ord_`）
+    /// - `subject_id`: 业务主体ID（订单ID）
+    /// - `creator`: 期望的创建者（付款方）
+    /// - `readers`: 必须持有密钥包的账户
+    fn is_shared_with(
+        content_id: u64,
+        ns: [u8; 8],
+        subject_id: u64,
+        creator: &AccountId,
+        readers: &[AccountId],
+    ) -> bool;
+}
+
+/// PrivateEvidenceInterface 的空实现（拒绝所有凭证）
+impl<AccountId> PrivateEvidenceInterface<AccountId> for () {
+    fn is_shared_with(
+        _content_id: u64,
+        _ns: [u8; 8],
+        _subject_id: u64,
+        _creator: &AccountId,
+        _readers: &[AccountId],
+    ) -> bool {
+        false
+    }
+}
+
 // ===== 🆕 v0.5.0: 统一保证金计算接口 =====
 
 /// 函数级详细中文注释：保证金计算接口
@@ -173,6 +173,9 @@ pub mod pallet {
         
         // 权重信息
         type CreditWeightInfo: CreditWeightInfo;
+
+        /// 跨域信誉上报（买家履约 / 违约信号）
+        type Reputation: pallet_reputation::traits::ReputationSink<Self::AccountId>;
     }
 
     #[pallet::pallet]
@@ -1903,6 +1906,10 @@ impl<T: pallet::Config> pallet_trading_common::MakerCreditInterface for pallet::
         // 调用已有的做市商信用更新逻辑
         Self::record_maker_dispute_result(maker_id, order_id, maker_win)
     }
+    
+    fn query_maker_credit_score(maker_id: u64) -> Option<u16> {
+        Self::query_maker_credit_score(maker_id)
+    }
 }
 
 // ===== 🆕 方案C+：买家额度管理接口实现 =====
@@ -2068,6 +2075,12 @@ impl<T: pallet::Config> crate::quota::BuyerQuotaInterface<T::AccountId> for pall
             Ok(())
         })?;
 
+        T::Reputation::report(
+            buyer,
+            pallet_reputation::types::ReputationDomain::Trading,
+            pallet_reputation::types::ReputationSignal::Fulfilled,
+        );
+
         Ok(())
     }
 
@@ -2099,8 +2112,15 @@ impl<T: pallet::Config> crate::quota::BuyerQuotaInterface<T::AccountId> for pall
         use sp_runtime::traits::{CheckedAdd, CheckedMul};
         use frame_support::traits::Get;
         use pallet::{BuyerQuotas, Error, Event};
+        use pallet_reputation::types::{ReputationDomain, ReputationSignal, Severity};
 
-        BuyerQuotas::<T>::try_mutate(buyer, |profile| -> sp_runtime::DispatchResult {
+        let reputation_signal = match violation_type {
+            crate::quota::ViolationType::OrderTimeout { .. } => ReputationSignal::Violation(Severity::Minor),
+            crate::quota::ViolationType::DisputeLoss { .. } => ReputationSignal::DisputeLost,
+            crate::quota::ViolationType::MaliciousBehavior { .. } => ReputationSignal::Violation(Severity::Severe),
+        };
+
+        let (suspended, blacklisted) = BuyerQuotas::<T>::try_mutate(buyer, |profile| -> Result<(bool, bool), sp_runtime::DispatchError> {
             // 计算惩罚参数
             let (score_penalty, quota_reduction_bps, penalty_duration_days, should_suspend) =
                 crate::quota::calculate_violation_penalty(&violation_type, profile.total_violations);
@@ -2187,9 +2207,17 @@ impl<T: pallet::Config> crate::quota::BuyerQuotaInterface<T::AccountId> for pall
                 new_credit_score: profile.credit_score,
             });
 
-            Ok(())
+            Ok((should_suspend, profile.is_blacklisted))
         })?;
 
+        // 上报跨域信誉：违约本身 + 暂停 / 拉黑
+        T::Reputation::report(buyer, ReputationDomain::Trading, reputation_signal);
+        if blacklisted {
+            T::Reputation::report(buyer, ReputationDomain::Trading, ReputationSignal::Banned);
+        } else if suspended {
+            T::Reputation::report(buyer, ReputationDomain::Trading, ReputationSignal::Suspended);
+        }
+
         Ok(())
     }
 
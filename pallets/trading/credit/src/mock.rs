@@ -120,6 +120,7 @@ impl pallet_credit::Config for Test {
     type MakerSuspensionThreshold = MakerSuspensionThreshold;
     type MakerWarningThreshold = MakerWarningThreshold;
     type CreditWeightInfo = ();
+    type Reputation = ();
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -8,7 +8,7 @@
 //! - 做市商申请与审核
 //! - 押金管理（锁定/解锁）
 //! - 提现管理（冷却期）
-//! - 溢价配置
+//! - 报价管理（溢价、限额、收款通道、营业时间）
 //! - 服务暂停/恢复
 //!
 //! ## 版本历史
@@ -30,6 +30,8 @@ mod benchmarking;
 pub mod weights;
 pub use weights::WeightInfo;
 
+pub mod migrations;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -47,6 +49,10 @@ pub mod pallet {
         TronAddress,
         Cid,
         PricingProvider,
+        MakerQuote,
+        OrderSide,
+        PaymentRail,
+        WorkingHours,
     };
     
     /// 函数级详细中文注释：Balance 类型别名
@@ -169,7 +175,7 @@ pub mod pallet {
     }
     
     /// 函数级详细中文注释：做市商业务方向
-    #[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[derive(Clone, Copy, Encode, Decode, codec::DecodeWithMemTracking, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     pub enum Direction {
         /// 仅买入（仅Bridge）- 做市商购买DUST，支付USDT
         Buy = 0,
@@ -189,6 +195,17 @@ pub mod pallet {
                 _ => None,
             }
         }
+
+        /// 是否承接用户指定方向的订单
+        ///
+        /// 用户买入 DUST 需要做市商卖出（Sell/BuyAndSell），
+        /// 用户卖出 DUST 需要做市商买入（Buy/BuyAndSell）。
+        pub fn serves(&self, side: OrderSide) -> bool {
+            match side {
+                OrderSide::Buy => matches!(self, Direction::Sell | Direction::BuyAndSell),
+                OrderSide::Sell => matches!(self, Direction::Buy | Direction::BuyAndSell),
+            }
+        }
     }
     
     impl Default for Direction {
@@ -249,9 +266,9 @@ pub mod pallet {
         /// 脱敏生日
         pub masked_birthday: BoundedVec<u8, ConstU32<16>>,
         /// 脱敏收款方式信息（JSON格式）
+        ///
+        /// 完整收款资料不再上链公开，按订单加密给买家（见 OTC `submit_payment_details`）
         pub masked_payment_info: BoundedVec<u8, ConstU32<512>>,
-        /// 微信号（显示给用户）
-        pub wechat_id: BoundedVec<u8, ConstU32<64>>,
         /// 押金目标USD价值（固定1000 USDT，精度10^6）
         pub target_deposit_usd: u64,
         /// 上次价格检查时间
@@ -273,7 +290,11 @@ pub mod pallet {
         pub status: WithdrawalStatus,
     }
     
+    /// 函数级详细中文注释：存储版本（v1：`MakerApplication` 移除 `wechat_id`）
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
     
     /// 函数级详细中文注释：做市商模块配置 trait
@@ -379,6 +400,18 @@ pub mod pallet {
         u64,
     >;
     
+    /// 函数级详细中文注释：做市商报价
+    ///
+    /// 审批前必须先发布报价；激活后可随时更新
+    #[pallet::storage]
+    #[pallet::getter(fn maker_quotes)]
+    pub type MakerQuotes<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u64,  // maker_id
+        MakerQuote<BalanceOf<T>>,
+    >;
+    
     /// 函数级详细中文注释：提现请求记录
     #[pallet::storage]
     #[pallet::getter(fn withdrawal_requests)]
@@ -554,6 +587,16 @@ pub mod pallet {
             maker_id: u64,
             year_month: u32,
         },
+
+        /// 做市商报价已更新
+        MakerQuoteUpdated {
+            maker_id: u64,
+            direction: Direction,
+            buy_premium_bps: i16,
+            sell_premium_bps: i16,
+            min_amount: BalanceOf<T>,
+            max_amount: BalanceOf<T>,
+        },
     }
     
     // ===== 错误 =====
@@ -604,6 +647,18 @@ pub mod pallet {
         SwapNotFound,
         /// 计算溢出
         CalculationOverflow,
+        /// 溢价超出 ±500 基点
+        InvalidPremium,
+        /// 限额无效（需 0 < min <= max）
+        InvalidQuoteAmount,
+        /// 未指定收款通道
+        NoPaymentRails,
+        /// 收款通道过多
+        TooManyPaymentRails,
+        /// 营业时间无效
+        InvalidWorkingHours,
+        /// 尚未发布报价
+        QuoteNotSet,
     }
     
     // ===== Extrinsics =====
@@ -659,7 +714,6 @@ pub mod pallet {
         /// - `id_card_number`: 身份证号
         /// - `birthday`: 生日（YYYY-MM-DD）
         /// - `tron_address`: TRON 地址
-        /// - `wechat_id`: 微信号
         ///
         /// # 返回
         /// - `DispatchResult`: 成功或错误
@@ -671,7 +725,6 @@ pub mod pallet {
             id_card_number: sp_std::vec::Vec<u8>,
             birthday: sp_std::vec::Vec<u8>,
             tron_address: sp_std::vec::Vec<u8>,
-            wechat_id: sp_std::vec::Vec<u8>,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
             Self::do_submit_info(
@@ -680,7 +733,6 @@ pub mod pallet {
                 id_card_number,
                 birthday,
                 tron_address,
-                wechat_id,
             )
         }
         
@@ -871,6 +923,48 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// 函数级详细中文注释：发布或更新做市商报价
+        ///
+        /// # 参数
+        /// - `origin`: 做市商账户
+        /// - `direction`: 业务方向
+        /// - `buy_premium_bps`: 买入溢价（用户卖出 DUST 时适用，-500 ~ 500）
+        /// - `sell_premium_bps`: 卖出溢价（用户买入 DUST 时适用，-500 ~ 500）
+        /// - `min_amount` / `max_amount`: 单笔 DUST 数量限额
+        /// - `payment_rails`: 支持的收款/付款通道
+        /// - `working_hours`: 营业时间（UTC）
+        ///
+        /// # 返回
+        /// - `DispatchResult`: 成功或错误
+        ///
+        /// # 说明
+        /// - 申请期间（DepositLocked / PendingReview）即可发布，审批时需已有报价
+        /// - 更新只影响之后创建的订单，已创建订单按下单时价格结算
+        #[pallet::call_index(12)]
+        #[pallet::weight(T::WeightInfo::update_quote())]
+        pub fn update_quote(
+            origin: OriginFor<T>,
+            direction: Direction,
+            buy_premium_bps: i16,
+            sell_premium_bps: i16,
+            min_amount: BalanceOf<T>,
+            max_amount: BalanceOf<T>,
+            payment_rails: sp_std::vec::Vec<PaymentRail>,
+            working_hours: WorkingHours,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_update_quote(
+                &who,
+                direction,
+                buy_premium_bps,
+                sell_premium_bps,
+                min_amount,
+                max_amount,
+                payment_rails,
+                working_hours,
+            )
+        }
     }
     
     // ===== 内部实现 =====
@@ -924,7 +1018,6 @@ pub mod pallet {
                 masked_id_card: BoundedVec::default(),
                 masked_birthday: BoundedVec::default(),
                 masked_payment_info: BoundedVec::default(),
-                wechat_id: BoundedVec::default(),
                 target_deposit_usd: T::TargetDepositUsd::get(), // 新增：目标USD价值
                 last_price_check: frame_system::Pallet::<T>::block_number(), // 新增：价格检查时间
                 deposit_warning: false, // 新增：警告状态
@@ -952,7 +1045,6 @@ pub mod pallet {
         /// - id_card_number: 身份证号
         /// - birthday: 生日（格式：YYYY-MM-DD）
         /// - tron_address: TRON地址
-        /// - wechat_id: 微信号
         /// 
         /// # 返回
         /// - DispatchResult
@@ -962,7 +1054,6 @@ pub mod pallet {
             id_card_number: sp_std::vec::Vec<u8>,
             birthday: sp_std::vec::Vec<u8>,
             tron_address: sp_std::vec::Vec<u8>,
-            wechat_id: sp_std::vec::Vec<u8>,
         ) -> DispatchResult {
             use pallet_trading_common::is_valid_tron_address;
             use pallet_trading_common::{mask_name, mask_id_card, mask_birthday};
@@ -1009,8 +1100,6 @@ pub mod pallet {
                     .map_err(|_| Error::<T>::EncodingError)?;
                 app.masked_birthday = BoundedVec::try_from(masked_birth)
                     .map_err(|_| Error::<T>::EncodingError)?;
-                app.wechat_id = BoundedVec::try_from(wechat_id)
-                    .map_err(|_| Error::<T>::EncodingError)?;
                 
                 // 🆕 P3: 自动 Pin 做市商资料到 IPFS（Standard 层级）
                 // 公开资料和私密资料都需要长期保存
@@ -1054,6 +1143,7 @@ pub mod pallet {
         /// # 说明
         /// - 由于委员会集体 Origin 不返回具体账户，事件中 approved_by 设为 None
         /// - 审批记录可通过链上事件追溯（提案发起者、投票者等）
+        /// - 做市商须先通过 `update_quote` 发布报价，激活后沿用该报价
         pub fn do_approve_maker(maker_id: u64) -> DispatchResult {
             MakerApplications::<T>::try_mutate(maker_id, |maybe_app| -> DispatchResult {
                 let app = maybe_app.as_mut().ok_or(Error::<T>::MakerNotFound)?;
@@ -1064,6 +1154,12 @@ pub mod pallet {
                     Error::<T>::InvalidMakerStatus
                 );
 
+                // 检查报价
+                ensure!(
+                    MakerQuotes::<T>::contains_key(maker_id),
+                    Error::<T>::QuoteNotSet
+                );
+
                 // 更新状态
                 app.status = ApplicationStatus::Active;
 
@@ -1079,6 +1175,90 @@ pub mod pallet {
             Ok(())
         }
         
+        /// 函数级详细中文注释：发布或更新做市商报价
+        ///
+        /// # 参数
+        /// - who: 做市商账户
+        /// - 其余参数见 `update_quote`
+        ///
+        /// # 返回
+        /// - DispatchResult
+        #[allow(clippy::too_many_arguments)]
+        pub fn do_update_quote(
+            who: &T::AccountId,
+            direction: Direction,
+            buy_premium_bps: i16,
+            sell_premium_bps: i16,
+            min_amount: BalanceOf<T>,
+            max_amount: BalanceOf<T>,
+            payment_rails: sp_std::vec::Vec<PaymentRail>,
+            working_hours: WorkingHours,
+        ) -> DispatchResult {
+            use pallet_trading_common::quote::is_valid_premium;
+
+            let maker_id = AccountToMaker::<T>::get(who)
+                .ok_or(Error::<T>::MakerNotFound)?;
+
+            // 校验报价
+            ensure!(
+                is_valid_premium(buy_premium_bps) && is_valid_premium(sell_premium_bps),
+                Error::<T>::InvalidPremium
+            );
+            ensure!(
+                !min_amount.is_zero() && min_amount <= max_amount,
+                Error::<T>::InvalidQuoteAmount
+            );
+            ensure!(!payment_rails.is_empty(), Error::<T>::NoPaymentRails);
+            ensure!(working_hours.is_valid(), Error::<T>::InvalidWorkingHours);
+            let payment_rails = BoundedVec::try_from(payment_rails)
+                .map_err(|_| Error::<T>::TooManyPaymentRails)?;
+
+            MakerApplications::<T>::try_mutate(maker_id, |maybe_app| -> DispatchResult {
+                let app = maybe_app.as_mut().ok_or(Error::<T>::MakerNotFound)?;
+
+                // 已终止的申请不能再报价
+                ensure!(
+                    matches!(
+                        app.status,
+                        ApplicationStatus::DepositLocked
+                            | ApplicationStatus::PendingReview
+                            | ApplicationStatus::Active
+                    ),
+                    Error::<T>::InvalidMakerStatus
+                );
+
+                // 申请记录中的溢价字段与报价保持一致
+                app.direction = direction;
+                app.buy_premium_bps = buy_premium_bps;
+                app.sell_premium_bps = sell_premium_bps;
+                app.min_amount = min_amount;
+
+                Ok(())
+            })?;
+
+            let now = T::Timestamp::now().as_secs().saturated_into::<u32>();
+            MakerQuotes::<T>::insert(maker_id, MakerQuote {
+                buy_premium_bps,
+                sell_premium_bps,
+                min_amount,
+                max_amount,
+                payment_rails,
+                working_hours,
+                updated_at: now,
+            });
+
+            Self::deposit_event(Event::MakerQuoteUpdated {
+                maker_id,
+                direction,
+                buy_premium_bps,
+                sell_premium_bps,
+                min_amount,
+                max_amount,
+            });
+
+            Ok(())
+        }
+
         /// 函数级详细中文注释：驳回做市商申请
         ///
         /// # 参数
@@ -1823,5 +2003,17 @@ pub mod pallet {
         pub fn get_maker_id(who: &T::AccountId) -> Option<u64> {
             AccountToMaker::<T>::get(who)
         }
+
+        /// 函数级详细中文注释：获取做市商当前报价
+        pub fn get_quote(maker_id: u64) -> Option<MakerQuote<BalanceOf<T>>> {
+            MakerQuotes::<T>::get(maker_id)
+        }
+
+        /// 函数级详细中文注释：做市商是否承接用户指定方向的订单
+        pub fn serves_side(maker_id: u64, side: OrderSide) -> bool {
+            MakerApplications::<T>::get(maker_id)
+                .map(|app| app.direction.serves(side))
+                .unwrap_or(false)
+        }
     }
 }
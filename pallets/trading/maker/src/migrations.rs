@@ -0,0 +1,121 @@
+//! # Maker 存储迁移
+//!
+//! 函数级详细中文注释：
+//! - v0 → v1：`MakerApplication` 移除 `wechat_id`（完整收款资料改为按订单加密给买家）
+//! - 旧记录丢弃微信号，其余字段原样保留
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration,
+    pallet_prelude::*,
+    traits::UncheckedOnRuntimeUpgrade,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_trading_common::{Cid, TronAddress};
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// 函数级详细中文注释：v0 存储布局
+pub mod v0 {
+    use super::*;
+
+    /// v0 做市商申请记录（含公开微信号）
+    #[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct MakerApplication<T: Config> {
+        pub owner: T::AccountId,
+        pub deposit: BalanceOf<T>,
+        pub status: ApplicationStatus,
+        pub direction: Direction,
+        pub tron_address: TronAddress,
+        pub public_cid: Cid,
+        pub private_cid: Cid,
+        pub buy_premium_bps: i16,
+        pub sell_premium_bps: i16,
+        pub min_amount: BalanceOf<T>,
+        pub created_at: u32,
+        pub info_deadline: u32,
+        pub review_deadline: u32,
+        pub service_paused: bool,
+        pub users_served: u32,
+        pub masked_full_name: BoundedVec<u8, ConstU32<64>>,
+        pub masked_id_card: BoundedVec<u8, ConstU32<32>>,
+        pub masked_birthday: BoundedVec<u8, ConstU32<16>>,
+        pub masked_payment_info: BoundedVec<u8, ConstU32<512>>,
+        pub wechat_id: BoundedVec<u8, ConstU32<64>>,
+        pub target_deposit_usd: u64,
+        pub last_price_check: BlockNumberFor<T>,
+        pub deposit_warning: bool,
+    }
+}
+
+/// 函数级详细中文注释：v0 → v1 迁移实现（不检查版本，由 `MigrateV0ToV1` 包装）
+pub struct InnerMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+
+impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let mut translated = 0u64;
+
+        MakerApplications::<T>::translate::<v0::MakerApplication<T>, _>(|_, old| {
+            translated += 1;
+            Some(MakerApplication {
+                owner: old.owner,
+                deposit: old.deposit,
+                status: old.status,
+                direction: old.direction,
+                tron_address: old.tron_address,
+                public_cid: old.public_cid,
+                private_cid: old.private_cid,
+                buy_premium_bps: old.buy_premium_bps,
+                sell_premium_bps: old.sell_premium_bps,
+                min_amount: old.min_amount,
+                created_at: old.created_at,
+                info_deadline: old.info_deadline,
+                review_deadline: old.review_deadline,
+                service_paused: old.service_paused,
+                users_served: old.users_served,
+                masked_full_name: old.masked_full_name,
+                masked_id_card: old.masked_id_card,
+                masked_birthday: old.masked_birthday,
+                masked_payment_info: old.masked_payment_info,
+                target_deposit_usd: old.target_deposit_usd,
+                last_price_check: old.last_price_check,
+                deposit_warning: old.deposit_warning,
+            })
+        });
+
+        log::info!(
+            target: "runtime::trading-maker",
+            "migrated {} maker applications to v1",
+            translated
+        );
+
+        T::DbWeight::get().reads_writes(translated, translated)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+        let applications = MakerApplications::<T>::iter_keys().count() as u64;
+        Ok(applications.encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+        let applications: u64 = Decode::decode(&mut &state[..])
+            .map_err(|_| sp_runtime::TryRuntimeError::Other("invalid pre-upgrade state"))?;
+        ensure!(
+            MakerApplications::<T>::iter_values().count() as u64 == applications,
+            "some maker applications failed to decode after migration"
+        );
+        Ok(())
+    }
+}
+
+/// 函数级详细中文注释：v0 → v1 版本化迁移（仅在链上版本为 0 时执行，完成后写入版本 1）
+pub type MigrateV0ToV1<T> = VersionedMigration<
+    0,
+    1,
+    InnerMigrateV0ToV1<T>,
+    Pallet<T>,
+    <T as frame_system::Config>::DbWeight,
+>;
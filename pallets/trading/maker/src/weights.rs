@@ -10,6 +10,7 @@ pub trait WeightInfo {
     fn submit_info() -> Weight;
     fn approve_maker() -> Weight;
     fn reject_maker() -> Weight;
+    fn update_quote() -> Weight;
 }
 
 /// 函数级详细中文注释：默认权重实现（用于测试）
@@ -29,5 +30,9 @@ impl WeightInfo for () {
     fn reject_maker() -> Weight {
         Weight::from_parts(15_000, 0)
     }
+    
+    fn update_quote() -> Weight {
+        Weight::from_parts(20_000, 0)
+    }
 }
 
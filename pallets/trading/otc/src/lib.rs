@@ -17,11 +17,25 @@
 //! - 紧急豁免账户机制
 //! - 只有通过KYC认证的用户才能创建OTC订单
 //!
+//! ## 拆单路由
+//!
+//! - `create_split_order` 按 USD 金额在多个候选做市商间拆分
+//! - 候选按卖出溢价升序、信用分降序排序，逐个尝试创建子订单（失败则跳过）
+//! - 子订单为普通订单，可独立付款、释放与争议；`get_split_order_status` 提供汇总视图
+//!
+//! ## 订单级加密收款资料
+//!
+//! - 下单时付款方须已在统一公钥目录注册 X25519 加密公钥
+//! - 收款方通过 `submit_payment_details` 提交加密给付款方的收款资料（买单为做市商，卖单为卖家）
+//! - 付款方将付款凭证加密存入 `pallet-evidence`（收款方 + 仲裁委员会可解密），
+//!   再通过 `submit_payment_proof` 关联到订单
+//!
 //! ## 版本历史
 //!
 //! - v0.1.0 (2025-11-03): 从 pallet-trading 拆分而来
 //! - v0.2.0 (2025-11-13): 集成KYC认证功能
 //! - v0.3.0 (2025-11-28): 集成聊天权限系统
+//! - v0.5.0: 订单级加密收款资料与付款凭证，移除做市商公开联系方式
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -35,12 +49,11 @@ mod kyc;
 // 选择性导出 types 中的类型（避免 KycConfig 冲突）
 pub use types::{KycVerificationResult, KycFailureReason};
 
-// TODO: 测试文件待创建
-// #[cfg(test)]
-// mod mock;
+#[cfg(test)]
+mod mock;
 
-// #[cfg(test)]
-// mod tests;
+#[cfg(test)]
+mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
@@ -54,7 +67,7 @@ pub mod pallet {
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
     use frame_support::{
-        traits::{Currency, Get, UnixTime},
+        traits::{Currency, Get, SortedMembers, UnixTime},
         BoundedVec,
         sp_runtime::SaturatedConversion,
     };
@@ -73,7 +86,11 @@ pub mod pallet {
         MakerInterface,
         MakerCreditInterface,
         MakerValidationError,
+        OrderSide,
+        PrivateEvidenceInterface,
     };
+    use pallet_key_directory::traits::KeyDirectory;
+    use pallet_arbitration::pallet::{domains::OTC_ORDER, DisputeTimeline, TimelineEventKind};
     
     // MakerApplicationInfo 通过 MakerInterface::get_maker_application 返回
 
@@ -144,9 +161,9 @@ pub mod pallet {
     pub struct Dispute<T: Config> {
         /// 订单ID
         pub order_id: u64,
-        /// 发起方（买家）
+        /// 发起方（买家；卖单也可为做市商）
         pub initiator: T::AccountId,
-        /// 被告方（做市商）
+        /// 被告方（做市商；做市商发起时为卖家）
         pub respondent: T::AccountId,
         /// 发起时间（Unix秒）
         pub created_at: MomentOf,
@@ -156,12 +173,67 @@ pub mod pallet {
         pub arbitration_deadline: MomentOf,
         /// 争议状态
         pub status: DisputeStatus,
-        /// 买家证据 CID
+        /// 发起方证据 CID
         pub buyer_evidence: Option<pallet_trading_common::Cid>,
-        /// 做市商证据 CID
+        /// 被告方证据 CID
         pub maker_evidence: Option<pallet_trading_common::Cid>,
     }
     
+    /// 拆单最多子订单数量
+    pub type MaxSplitChildren = ConstU32<5>;
+
+    /// 拆单最多候选做市商数量
+    pub const MAX_SPLIT_CANDIDATES: u32 = 10;
+
+    /// 函数级详细中文注释：拆单记录（父订单）
+    #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct SplitOrder<T: Config> {
+        /// 买家账户
+        pub buyer: T::AccountId,
+        /// 请求的USD金额（精度10^6）
+        pub requested_usd: u64,
+        /// 已分配到子订单的USD金额（精度10^6）
+        pub filled_usd: u64,
+        /// 子订单ID列表
+        pub children: BoundedVec<u64, MaxSplitChildren>,
+        /// 创建时间（Unix秒）
+        pub created_at: MomentOf,
+    }
+
+    /// 函数级详细中文注释：拆单汇总状态
+    #[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum SplitOrderStatus {
+        /// 仍有子订单在进行中（Created / PaidOrCommitted）
+        InProgress,
+        /// 有子订单处于争议中
+        Disputed,
+        /// 全部子订单已释放
+        Completed,
+        /// 全部子订单已结束，部分释放
+        PartiallyCompleted,
+        /// 全部子订单已结束，无一释放
+        Failed,
+    }
+
+    /// 🆕 拆单汇总视图（供 RPC 查询使用）
+    #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    #[scale_info(skip_type_params(T))]
+    pub struct SplitOrderView<T: Config> {
+        /// 拆单ID
+        pub split_id: u64,
+        /// 买家账户
+        pub buyer: T::AccountId,
+        /// 请求的USD金额
+        pub requested_usd: u64,
+        /// 已分配的USD金额
+        pub filled_usd: u64,
+        /// 汇总状态
+        pub status: SplitOrderStatus,
+        /// 子订单（订单ID, 做市商ID, 状态 0-7）
+        pub children: sp_std::vec::Vec<(u64, u64, u8)>,
+    }
+    
     /// 🆕 2026-01-18: 订单时间信息结构（供 RPC 查询使用）
     #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
     #[scale_info(skip_type_params(T))]
@@ -275,6 +347,32 @@ pub mod pallet {
         pub buyer_deposit: BalanceOf<T>,
         /// 押金状态
         pub deposit_status: DepositStatus,
+        // ===== 🆕 做市商报价：双向订单 =====
+        /// 订单方向（Buy=用户买入，做市商托管；Sell=用户卖出，用户托管）
+        pub side: OrderSide,
+    }
+
+    /// 付款凭证所在的证据命名空间（与仲裁域 OTC_ORDER 一致）
+    pub const PAYMENT_PROOF_NS: [u8; 8] = *b"otc_ord_";
+
+    /// 每个订单最多关联的付款凭证数量
+    pub type MaxPaymentProofs = ConstU32<5>;
+
+    /// 函数级详细中文注释：订单加密收款资料
+    ///
+    /// 收款方（买单为做市商，卖单为卖家）使用付款方在公钥目录中的 X25519 公钥加密，
+    /// 链上仅保存密文与加密目标公钥快照，付款方解密后按资料付款。
+    #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    #[scale_info(skip_type_params(T))]
+    pub struct EncryptedPaymentDetails<T: Config> {
+        /// 提交方（收款方）
+        pub submitter: T::AccountId,
+        /// 加密目标公钥（付款方当前 X25519 公钥快照）
+        pub recipient_key: [u8; 32],
+        /// 密文（含临时公钥与 nonce）
+        pub ciphertext: BoundedVec<u8, T::MaxPaymentDetailsLen>,
+        /// 提交时间（Unix秒）
+        pub submitted_at: MomentOf,
     }
     
     #[pallet::pallet]
@@ -457,6 +555,22 @@ pub mod pallet {
         /// - 仲裁完成后自动解锁并 Unpin
         /// - 防止争议期间证据被删除
         type CidLockManager: pallet_storage_service::CidLockManager<Self::Hash, BlockNumberFor<Self>>;
+
+        /// 统一公钥目录（付款方加密公钥）
+        type KeyDirectory: KeyDirectory<Self::AccountId>;
+
+        /// 私密证据校验接口（付款凭证）
+        type PrivateEvidence: PrivateEvidenceInterface<Self::AccountId>;
+
+        /// 仲裁委员会成员（付款凭证须可由其解密）
+        type ArbitrationCommittee: SortedMembers<Self::AccountId>;
+
+        /// 加密收款资料最大长度
+        #[pallet::constant]
+        type MaxPaymentDetailsLen: Get<u32>;
+
+        /// 🆕 案件时间线（订单状态流转、凭证引用供仲裁委员会查阅）
+        type Timeline: DisputeTimeline<Self::AccountId, Self::Hash>;
     }
     
     // 🆕 v0.4.0: PricingProvider, MakerInterface, MakerApplicationInfo 已移至 common 模块
@@ -645,6 +759,55 @@ pub mod pallet {
     #[pallet::getter(fn otc_stats)]
     pub type OtcStats<T: Config> = StorageValue<_, OtcPermanentStats, ValueQuery>;
 
+    // ===== 拆单存储 =====
+
+    /// 函数级详细中文注释：下一个拆单ID
+    #[pallet::storage]
+    pub type NextSplitOrderId<T> = StorageValue<_, u64, ValueQuery>;
+
+    /// 函数级详细中文注释：拆单记录（全部子订单归档后删除）
+    #[pallet::storage]
+    #[pallet::getter(fn split_orders)]
+    pub type SplitOrders<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u64,  // split_id
+        SplitOrder<T>,
+    >;
+
+    /// 函数级详细中文注释：子订单所属拆单（子订单归档时删除）
+    #[pallet::storage]
+    #[pallet::getter(fn order_parent)]
+    pub type OrderParent<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u64,  // order_id
+        u64,  // split_id
+    >;
+
+    // ===== 订单加密资料 =====
+
+    /// 函数级详细中文注释：订单加密收款资料
+    #[pallet::storage]
+    #[pallet::getter(fn order_payment_details)]
+    pub type OrderPaymentDetails<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u64,  // order_id
+        EncryptedPaymentDetails<T>,
+    >;
+
+    /// 函数级详细中文注释：订单付款凭证（pallet-evidence 私密内容ID）
+    #[pallet::storage]
+    #[pallet::getter(fn order_payment_proofs)]
+    pub type OrderPaymentProofs<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u64,  // order_id
+        BoundedVec<u64, MaxPaymentProofs>,
+        ValueQuery,
+    >;
+
     // ===== KYC存储 =====
 
     /// 函数级详细中文注释：KYC配置存储
@@ -806,6 +969,34 @@ pub mod pallet {
             order_id: u64,
             buyer_wins: bool,
         },
+        /// 拆单已创建
+        SplitOrderCreated {
+            split_id: u64,
+            buyer: T::AccountId,
+            requested_usd: u64,
+            filled_usd: u64,
+            children: sp_std::vec::Vec<u64>,
+        },
+        /// 加密收款资料已提交
+        PaymentDetailsSubmitted {
+            order_id: u64,
+            submitter: T::AccountId,
+            recipient_key: [u8; 32],
+        },
+        /// 付款凭证已关联
+        PaymentProofLinked {
+            order_id: u64,
+            payer: T::AccountId,
+            content_id: u64,
+        },
+        /// 卖单已创建（用户卖出 DUST，DUST 已托管）
+        SellOrderCreated {
+            order_id: u64,
+            maker_id: u64,
+            seller: T::AccountId,
+            dust_amount: BalanceOf<T>,
+            price: BalanceOf<T>,
+        },
     }
     
     // ===== 错误 =====
@@ -889,6 +1080,32 @@ pub mod pallet {
         DisputeResponseTimeout,
         /// 不是订单买家
         NotOrderBuyer,
+        /// 做市商不承接该方向的订单
+        MakerSideNotSupported,
+        /// 订单数量不在做市商报价限额内
+        OutsideQuoteLimits,
+        /// 不在做市商营业时间内
+        MakerClosed,
+        /// 该操作不适用于此订单方向
+        WrongOrderSide,
+        /// 付款方未在公钥目录注册加密公钥
+        PayerKeyNotRegistered,
+        /// 加密收款资料为空或过长
+        InvalidPaymentDetails,
+        /// 收款方尚未提交收款资料
+        PaymentDetailsNotProvided,
+        /// 付款凭证无效（非本订单、非付款方创建或未加密给收款方与仲裁委员会）
+        InvalidPaymentProof,
+        /// 付款凭证已关联
+        PaymentProofAlreadyLinked,
+        /// 付款凭证数量已达上限
+        TooManyPaymentProofs,
+        /// 候选做市商为空或过多
+        InvalidSplitCandidates,
+        /// 没有做市商能够承接拆单
+        NoMakerAvailable,
+        /// 拆单未能全额成交（未允许部分成交）
+        SplitNotFilled,
     }
     
     // ===== Extrinsics =====
@@ -1218,6 +1435,162 @@ pub mod pallet {
             T::ArbitratorOrigin::ensure_origin(origin)?;
             Self::do_resolve_dispute(order_id, buyer_wins)
         }
+
+        // ===== 🆕 卖单：用户向做市商出售 DUST =====
+
+        /// 函数级详细中文注释：创建卖单（用户卖出 DUST）
+        ///
+        /// # 参数
+        /// - `origin`: 调用者（卖家，必须是签名账户）
+        /// - `maker_id`: 做市商ID（需承接买入方向）
+        /// - `dust_amount`: 出售的DUST数量（锁入托管）
+        /// - `payout_commit`: 收款资料承诺哈希
+        /// - `contact_commit`: 联系方式承诺哈希
+        ///
+        /// # 返回
+        /// - `DispatchResult`: 成功或错误
+        #[pallet::call_index(14)]
+        #[pallet::weight(<T as Config>::WeightInfo::create_sell_order())]
+        pub fn create_sell_order(
+            origin: OriginFor<T>,
+            maker_id: u64,
+            dust_amount: BalanceOf<T>,
+            payout_commit: H256,
+            contact_commit: H256,
+        ) -> DispatchResult {
+            let seller = ensure_signed(origin)?;
+            let _order_id = Self::do_create_sell_order(
+                &seller,
+                maker_id,
+                dust_amount,
+                payout_commit,
+                contact_commit,
+            )?;
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：做市商标记已付款（卖单）
+        ///
+        /// # 参数
+        /// - `origin`: 调用者（做市商，必须是签名账户）
+        /// - `order_id`: 订单ID
+        /// - `tron_tx_hash`: TRON交易哈希（可选）
+        ///
+        /// # 返回
+        /// - `DispatchResult`: 成功或错误
+        #[pallet::call_index(15)]
+        #[pallet::weight(<T as Config>::WeightInfo::mark_payout_sent())]
+        pub fn mark_payout_sent(
+            origin: OriginFor<T>,
+            order_id: u64,
+            tron_tx_hash: Option<sp_std::vec::Vec<u8>>,
+        ) -> DispatchResult {
+            let maker = ensure_signed(origin)?;
+            Self::do_mark_payout_sent(&maker, order_id, tron_tx_hash)
+        }
+
+        /// 函数级详细中文注释：卖家确认收款并释放DUST给做市商
+        ///
+        /// # 参数
+        /// - `origin`: 调用者（卖家，必须是签名账户）
+        /// - `order_id`: 订单ID
+        ///
+        /// # 返回
+        /// - `DispatchResult`: 成功或错误
+        #[pallet::call_index(16)]
+        #[pallet::weight(<T as Config>::WeightInfo::confirm_payout())]
+        pub fn confirm_payout(
+            origin: OriginFor<T>,
+            order_id: u64,
+        ) -> DispatchResult {
+            let seller = ensure_signed(origin)?;
+            Self::do_confirm_payout(&seller, order_id)
+        }
+
+        // ===== 订单加密资料 =====
+
+        /// 函数级详细中文注释：提交加密收款资料
+        ///
+        /// # 参数
+        /// - `origin`: 调用者（收款方：买单为做市商，卖单为卖家）
+        /// - `order_id`: 订单ID
+        /// - `ciphertext`: 使用付款方公钥目录 X25519 公钥加密的收款资料
+        ///
+        /// # 返回
+        /// - `DispatchResult`: 成功或错误
+        ///
+        /// # 说明
+        /// - 仅 Created 状态可提交，重复提交覆盖旧资料
+        #[pallet::call_index(17)]
+        #[pallet::weight(<T as Config>::WeightInfo::submit_payment_details())]
+        pub fn submit_payment_details(
+            origin: OriginFor<T>,
+            order_id: u64,
+            ciphertext: sp_std::vec::Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_submit_payment_details(&who, order_id, ciphertext)
+        }
+
+        /// 函数级详细中文注释：关联付款凭证
+        ///
+        /// # 参数
+        /// - `origin`: 调用者（付款方：买单为买家，卖单为做市商）
+        /// - `order_id`: 订单ID
+        /// - `content_id`: `pallet-evidence` 私密内容ID（命名空间 `otc_ord_`，主体为订单ID）
+        ///
+        /// # 返回
+        /// - `DispatchResult`: 成功或错误
+        #[pallet::call_index(18)]
+        #[pallet::weight(<T as Config>::WeightInfo::submit_payment_proof())]
+        pub fn submit_payment_proof(
+            origin: OriginFor<T>,
+            order_id: u64,
+            content_id: u64,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_submit_payment_proof(&who, order_id, content_id)
+        }
+
+        // ===== 拆单路由 =====
+
+        /// 函数级详细中文注释：按USD金额拆单购买DUST
+        ///
+        /// # 参数
+        /// - `origin`: 调用者（买家，必须是签名账户）
+        /// - `usd_amount`: 购买总额（USD，精度10^6）
+        /// - `candidates`: 候选做市商ID（最多 10 个）
+        /// - `allow_partial`: 无法全额成交时是否接受部分成交
+        /// - `payment_commit`: 支付承诺哈希
+        /// - `contact_commit`: 联系方式承诺哈希
+        ///
+        /// # 返回
+        /// - `DispatchResult`: 成功或错误
+        ///
+        /// # 说明
+        /// - 每个子订单金额在 MinOrderUsdAmount..=MaxOrderUsdAmount 内，并受做市商报价限额约束
+        /// - 子订单创建失败（余额不足、非营业时间、额度不足等）时跳过该做市商
+        #[pallet::call_index(19)]
+        #[pallet::weight(<T as Config>::WeightInfo::create_split_order(candidates.len() as u32))]
+        pub fn create_split_order(
+            origin: OriginFor<T>,
+            usd_amount: u64,
+            candidates: sp_std::vec::Vec<u64>,
+            allow_partial: bool,
+            payment_commit: H256,
+            contact_commit: H256,
+        ) -> DispatchResult {
+            let buyer = ensure_signed(origin)?;
+            let _split_id = Self::do_create_split_order(
+                &buyer,
+                usd_amount,
+                candidates,
+                allow_partial,
+                payment_commit,
+                contact_commit,
+            )?;
+            Ok(())
+        }
     }
     
     // ===== 内部实现 =====
@@ -1256,6 +1629,12 @@ pub mod pallet {
             // 🆕 Step 0: KYC验证检查
             Self::enforce_kyc_requirement(buyer)?;
 
+            // 0.5 买家须已注册加密公钥（用于接收加密收款资料）
+            ensure!(
+                T::KeyDirectory::encryption_key(buyer).is_some(),
+                Error::<T>::PayerKeyNotRegistered
+            );
+
             // 1. 验证订单金额（新增）
             let _usd_amount = Self::validate_order_amount(dust_amount, false)?;
 
@@ -1276,9 +1655,8 @@ pub mod pallet {
                 Error::<T>::MakerDepositInsufficient
             );
             
-            // 3. 获取当前DUST/USD价格
-            let price = T::Pricing::get_dust_to_usd_rate()
-                .ok_or(Error::<T>::PricingUnavailable)?;
+            // 3. 按做市商报价计算成交单价（市场价 × (1 + 卖出溢价)）
+            let price = Self::quoted_price(maker_id, OrderSide::Buy, dust_amount)?;
             
             // 4. 计算总金额（USDT）= dust_amount * price
             let amount = dust_amount
@@ -1344,6 +1722,7 @@ pub mod pallet {
                 is_first_purchase: false,
                 buyer_deposit,
                 deposit_status,
+                side: OrderSide::Buy,
             };
 
             // 12. 保存订单
@@ -1363,6 +1742,7 @@ pub mod pallet {
             })?;
 
             // 15. 发出事件
+            Self::record_timeline(order_id, TimelineEventKind::Created, Some(buyer.clone()));
             Self::deposit_event(Event::OrderCreated {
                 order_id,
                 maker_id,
@@ -1428,6 +1808,12 @@ pub mod pallet {
             // 🆕 Step 0: KYC验证检查
             Self::enforce_kyc_requirement(buyer)?;
 
+            // 0.5 买家须已注册加密公钥（用于接收加密收款资料）
+            ensure!(
+                T::KeyDirectory::encryption_key(buyer).is_some(),
+                Error::<T>::PayerKeyNotRegistered
+            );
+
             // 1. 检查买家是否已首购
             ensure!(
                 !HasFirstPurchased::<T>::get(buyer),
@@ -1541,6 +1927,7 @@ pub mod pallet {
                 is_first_purchase: true,
                 buyer_deposit,
                 deposit_status,
+                side: OrderSide::Buy,
             };
             
             // 14. 保存订单
@@ -1570,6 +1957,7 @@ pub mod pallet {
             })?;
             
             // 18. 发出事件
+            Self::record_timeline(order_id, TimelineEventKind::Created, Some(buyer.clone()));
             Self::deposit_event(Event::FirstPurchaseOrderCreated {
                 order_id,
                 buyer: buyer.clone(),
@@ -1631,34 +2019,19 @@ pub mod pallet {
                 Error::<T>::InvalidOrderStatus
             );
             
-            // 3. 验证调用者是买家
+            // 3. 验证调用者是买家（卖单由做市商调用 mark_payout_sent）
+            ensure!(order.side == OrderSide::Buy, Error::<T>::WrongOrderSide);
             ensure!(order.taker == *buyer, Error::<T>::NotAuthorized);
             
+            // 3.5 做市商须已提交加密收款资料
+            ensure!(
+                OrderPaymentDetails::<T>::contains_key(order_id),
+                Error::<T>::PaymentDetailsNotProvided
+            );
+            
             // 4. 如提供TRON交易哈希，验证并记录
             if let Some(tx_hash_vec) = tron_tx_hash {
-                // 将 Vec<u8> 转换为 H256
-                ensure!(tx_hash_vec.len() == 32, Error::<T>::EncodingError);
-                let mut hash_bytes = [0u8; 32];
-                hash_bytes.copy_from_slice(&tx_hash_vec);
-                let tx_hash = H256::from(hash_bytes);
-                
-                // 检查是否已使用
-                ensure!(
-                    !TronTxUsed::<T>::contains_key(tx_hash),
-                    Error::<T>::TronTxHashAlreadyUsed
-                );
-                
-                // 记录使用
-                let current_block = frame_system::Pallet::<T>::block_number();
-                TronTxUsed::<T>::insert(tx_hash, current_block);
-                
-                // 添加到清理队列
-                TronTxQueue::<T>::try_mutate(|queue| {
-                    queue.try_push((tx_hash, current_block))
-                        .map_err(|_| Error::<T>::StorageLimitReached)
-                })?;
-                
-                Self::deposit_event(Event::TronTxHashRecorded { tx_hash });
+                Self::record_tron_tx_hash(tx_hash_vec)?;
             }
             
             // 5. 更新订单状态
@@ -1667,13 +2040,36 @@ pub mod pallet {
             Orders::<T>::insert(order_id, order);
             
             // 6. 发出事件
-            Self::deposit_event(Event::OrderStateChanged {
-                order_id,
-                old_state: Self::state_to_u8(&old_state),
-                new_state: Self::state_to_u8(&OrderState::PaidOrCommitted),
-                actor: Some(buyer.clone()),
-            });
+            Self::note_state_change(order_id, &old_state, &OrderState::PaidOrCommitted, Some(buyer.clone()));
+            
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：验证并记录TRON交易哈希（防重放）
+        fn record_tron_tx_hash(tx_hash_vec: sp_std::vec::Vec<u8>) -> DispatchResult {
+            // 将 Vec<u8> 转换为 H256
+            ensure!(tx_hash_vec.len() == 32, Error::<T>::EncodingError);
+            let mut hash_bytes = [0u8; 32];
+            hash_bytes.copy_from_slice(&tx_hash_vec);
+            let tx_hash = H256::from(hash_bytes);
             
+            // 检查是否已使用
+            ensure!(
+                !TronTxUsed::<T>::contains_key(tx_hash),
+                Error::<T>::TronTxHashAlreadyUsed
+            );
+            
+            // 记录使用
+            let current_block = frame_system::Pallet::<T>::block_number();
+            TronTxUsed::<T>::insert(tx_hash, current_block);
+            
+            // 添加到清理队列
+            TronTxQueue::<T>::try_mutate(|queue| {
+                queue.try_push((tx_hash, current_block))
+                    .map_err(|_| Error::<T>::StorageLimitReached)
+            })?;
+            
+            Self::deposit_event(Event::TronTxHashRecorded { tx_hash });
             Ok(())
         }
         
@@ -1711,7 +2107,8 @@ pub mod pallet {
                 Error::<T>::InvalidOrderStatus
             );
             
-            // 3. 验证调用者是做市商
+            // 3. 验证调用者是做市商（卖单由卖家调用 confirm_payout）
+            ensure!(order.side == OrderSide::Buy, Error::<T>::WrongOrderSide);
             ensure!(order.maker == *maker, Error::<T>::NotAuthorized);
             
             // 4. 从托管释放DUST到买家（使用 order_id 作为托管 ID）
@@ -1774,12 +2171,7 @@ pub mod pallet {
             });
 
             // 10. 发出事件
-            Self::deposit_event(Event::OrderStateChanged {
-                order_id,
-                old_state: Self::state_to_u8(&old_state),
-                new_state: Self::state_to_u8(&OrderState::Released),
-                actor: Some(maker.clone()),
-            });
+            Self::note_state_change(order_id, &old_state, &OrderState::Released, Some(maker.clone()));
             
             Ok(())
         }
@@ -1823,8 +2215,8 @@ pub mod pallet {
                 Error::<T>::InvalidOrderStatus
             );
             
-            // 4. 从托管退还DUST给做市商（使用 order_id 作为托管 ID）
-            T::Escrow::refund_all(order_id, &order.maker)?;
+            // 4. 从托管退还DUST给托管方（买单退做市商，卖单退卖家）
+            T::Escrow::refund_all(order_id, Self::escrow_owner(&order))?;
             
             // 5. 更新订单状态
             let old_state = order.state.clone();
@@ -1833,13 +2225,15 @@ pub mod pallet {
             order.completed_at = Some(now);
             Orders::<T>::insert(order_id, order.clone());
 
-            // 🆕 方案C+：买家额度管理
-            // 6. 释放买家占用的额度
-            let amount_usd: u64 = Self::calculate_usd_amount_from_dust(order.qty, order.price)?;
-            let _ = T::Credit::release_quota(&order.taker, amount_usd);
+            // 🆕 方案C+：买家额度管理（卖单不占用额度）
+            if order.side == OrderSide::Buy {
+                // 6. 释放买家占用的额度
+                let amount_usd: u64 = Self::calculate_usd_amount_from_dust(order.qty, order.price)?;
+                let _ = T::Credit::release_quota(&order.taker, amount_usd);
 
-            // 7. 记录订单取消（轻度降低信用）
-            let _ = T::Credit::record_order_cancelled(&order.taker, order_id);
+                // 7. 记录订单取消（轻度降低信用）
+                let _ = T::Credit::record_order_cancelled(&order.taker, order_id);
+            }
 
             // 8. 如是首购订单，减少做市商首购计数
             if order.is_first_purchase {
@@ -1909,12 +2303,7 @@ pub mod pallet {
             }
 
             // 9. 发出事件
-            Self::deposit_event(Event::OrderStateChanged {
-                order_id,
-                old_state: Self::state_to_u8(&old_state),
-                new_state: Self::state_to_u8(&OrderState::Canceled),
-                actor: Some(who.clone()),
-            });
+            Self::note_state_change(order_id, &old_state, &OrderState::Canceled, Some(who.clone()));
             
             Ok(())
         }
@@ -1961,25 +2350,20 @@ pub mod pallet {
             Orders::<T>::insert(order_id, order);
             
             // 5. 发出事件
-            Self::deposit_event(Event::OrderStateChanged {
-                order_id,
-                old_state: Self::state_to_u8(&old_state),
-                new_state: Self::state_to_u8(&OrderState::Disputed),
-                actor: Some(who.clone()),
-            });
+            Self::note_state_change(order_id, &old_state, &OrderState::Disputed, Some(who.clone()));
             
             Ok(())
         }
         
         // ===== 🆕 2026-01-18: 争议处理内部函数 =====
         
-        /// 函数级详细中文注释：买家发起争议（内部实现）
+        /// 函数级详细中文注释：发起争议（内部实现）
         /// 
         /// ## 处理步骤
         /// 1. 验证订单状态为 PaidOrCommitted
-        /// 2. 验证调用者是订单买家
+        /// 2. 验证调用者是订单用户或付款方（卖单做市商已付款、卖家不确认时由做市商发起）
         /// 3. 验证订单尚未存在争议
-        /// 4. 创建争议记录
+        /// 4. 创建争议记录（被告方为另一方）
         /// 5. 更新订单状态为 Disputed
         /// 
         /// 注：争议押金已移除，改用统一仲裁模块的投诉押金机制
@@ -1998,8 +2382,16 @@ pub mod pallet {
                 Error::<T>::InvalidOrderStatus
             );
             
-            // 3. 验证调用者是买家
-            ensure!(order.taker == *buyer, Error::<T>::NotOrderBuyer);
+            // 3. 验证调用者是用户或付款方（买单付款方即买家，卖单付款方为做市商）
+            ensure!(
+                order.taker == *buyer || Self::payer(&order) == buyer,
+                Error::<T>::NotOrderBuyer
+            );
+            let respondent = if order.taker == *buyer {
+                order.maker.clone()
+            } else {
+                order.taker.clone()
+            };
             
             // 4. 验证订单尚未存在争议
             ensure!(
@@ -2016,7 +2408,7 @@ pub mod pallet {
             let dispute = Dispute {
                 order_id,
                 initiator: buyer.clone(),
-                respondent: order.maker.clone(),
+                respondent,
                 created_at: now,
                 response_deadline,
                 arbitration_deadline,
@@ -2035,6 +2427,7 @@ pub mod pallet {
                 let _ = T::CidLockManager::lock_cid(cid_hash, lock_reason, None);
             }
             
+            let buyer_evidence_hash = dispute.buyer_evidence.as_ref().map(|cid| T::Hashing::hash(&cid[..]));
             Disputes::<T>::insert(order_id, dispute);
             
             // 8. 更新订单状态
@@ -2043,13 +2436,12 @@ pub mod pallet {
             Orders::<T>::insert(order_id, order);
             
             // 9. 发出事件
-            Self::deposit_event(Event::OrderStateChanged {
-                order_id,
-                old_state: Self::state_to_u8(&old_state),
-                new_state: Self::state_to_u8(&OrderState::Disputed),
-                actor: Some(buyer.clone()),
-            });
+            Self::note_state_change(order_id, &old_state, &OrderState::Disputed, Some(buyer.clone()));
             
+            Self::record_timeline(order_id, TimelineEventKind::DisputeOpened, Some(buyer.clone()));
+            if let Some(ref cid) = buyer_evidence_hash {
+                Self::record_timeline(order_id, TimelineEventKind::EvidenceCid(*cid), Some(buyer.clone()));
+            }
             Self::deposit_event(Event::DisputeInitiated {
                 order_id,
                 buyer: buyer.clone(),
@@ -2062,7 +2454,7 @@ pub mod pallet {
         /// 
         /// ## 处理步骤
         /// 1. 验证争议存在且状态为 WaitingMakerResponse
-        /// 2. 验证调用者是订单做市商
+        /// 2. 验证调用者是争议被告方
         /// 3. 验证响应未超时
         /// 4. 更新争议状态为 WaitingArbitration
         /// 
@@ -2102,6 +2494,8 @@ pub mod pallet {
             let _ = T::CidLockManager::lock_cid(cid_hash, lock_reason, None);
             
             // 6. 发出事件
+            Self::record_timeline(order_id, TimelineEventKind::DisputeResponded, Some(maker.clone()));
+            Self::record_timeline(order_id, TimelineEventKind::EvidenceCid(cid_hash), Some(maker.clone()));
             Self::deposit_event(Event::DisputeResponded {
                 order_id,
                 maker: maker.clone(),
@@ -2147,13 +2541,16 @@ pub mod pallet {
                     let _ = Self::release_buyer_deposit(&order.taker, order.buyer_deposit);
                 }
                 
-                // 2. 释放托管的 DUST 给买家（订单完成）
-                let _ = T::Escrow::release_all(order_id, &order.taker);
+                // 2. 托管的 DUST 归用户：买单释放给买家，卖单退还卖家
+                let (settle_state, _) = match order.side {
+                    OrderSide::Buy => (OrderState::Released, T::Escrow::release_all(order_id, &order.taker)),
+                    OrderSide::Sell => (OrderState::Refunded, T::Escrow::refund_all(order_id, &order.taker)),
+                };
                 
                 // 3. 更新订单状态
                 Orders::<T>::mutate(order_id, |o| {
                     if let Some(ord) = o {
-                        ord.state = OrderState::Released;
+                        ord.state = settle_state;
                         ord.deposit_status = DepositStatus::Released;
                         ord.completed_at = Some(now);
                     }
@@ -2170,13 +2567,16 @@ pub mod pallet {
                     let _ = Self::forfeit_buyer_deposit(&order.maker, order.buyer_deposit);
                 }
                 
-                // 2. 退还托管的 DUST 给做市商（订单取消）
-                let _ = T::Escrow::refund_all(order_id, &order.maker);
+                // 2. 托管的 DUST 归做市商：买单退还做市商，卖单释放给做市商
+                let (settle_state, _) = match order.side {
+                    OrderSide::Buy => (OrderState::Canceled, T::Escrow::refund_all(order_id, &order.maker)),
+                    OrderSide::Sell => (OrderState::Released, T::Escrow::release_all(order_id, &order.maker)),
+                };
                 
                 // 3. 更新订单状态
                 Orders::<T>::mutate(order_id, |o| {
                     if let Some(ord) = o {
-                        ord.state = OrderState::Canceled;
+                        ord.state = settle_state;
                         ord.deposit_status = DepositStatus::Forfeited;
                         ord.completed_at = Some(now);
                     }
@@ -2210,6 +2610,16 @@ pub mod pallet {
             }
             
             // 8. 发出事件
+            if let Some(settled) = Orders::<T>::get(order_id) {
+                Self::record_timeline(
+                    order_id,
+                    TimelineEventKind::StateChanged {
+                        from: Self::state_to_u8(&order.state),
+                        to: Self::state_to_u8(&settled.state),
+                    },
+                    None,
+                );
+            }
             Self::deposit_event(Event::DisputeResolved {
                 order_id,
                 buyer_wins,
@@ -2217,19 +2627,516 @@ pub mod pallet {
             
             Ok(())
         }
-    }
-    
-    // ===== 公共查询接口 =====
-    
-    impl<T: Config> Pallet<T> {
-        /// 函数级详细中文注释：检查买家是否已首购
-        pub fn has_user_first_purchased(who: &T::AccountId) -> bool {
-            HasFirstPurchased::<T>::get(who)
+        // ===== 🆕 卖单与报价内部函数 =====
+
+        /// 函数级详细中文注释：按做市商报价计算成交单价
+        ///
+        /// ## 处理步骤
+        /// 1. 验证做市商承接该方向
+        /// 2. 如已发布报价：校验数量限额与营业时间，并对市场价加溢价
+        /// 3. 未发布报价：按市场价成交（兼容旧做市商）
+        pub fn quoted_price(
+            maker_id: u64,
+            side: OrderSide,
+            dust_amount: BalanceOf<T>,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            ensure!(
+                T::MakerPallet::serves_side(maker_id, side),
+                Error::<T>::MakerSideNotSupported
+            );
+
+            let market_price = T::Pricing::get_dust_to_usd_rate()
+                .ok_or(Error::<T>::PricingUnavailable)?;
+
+            let Some(quote) = T::MakerPallet::get_maker_quote(maker_id) else {
+                return Ok(market_price);
+            };
+
+            ensure!(
+                dust_amount >= quote.min_amount && dust_amount <= quote.max_amount,
+                Error::<T>::OutsideQuoteLimits
+            );
+            let now = T::Timestamp::now().as_secs();
+            ensure!(quote.working_hours.is_open_at(now), Error::<T>::MakerClosed);
+
+            let price = Self::apply_premium(market_price, quote.premium_bps(side))?;
+            ensure!(!price.is_zero(), Error::<T>::InvalidPrice);
+
+            Ok(price)
         }
-        
-        /// 函数级详细中文注释：获取做市商首购订单数量
-        pub fn get_maker_first_purchase_count(maker_id: u64) -> u32 {
-            MakerFirstPurchaseCount::<T>::get(maker_id)
+
+        /// 函数级详细中文注释：市场价加溢价（基点）
+        pub fn apply_premium(
+            market_price: BalanceOf<T>,
+            premium_bps: i16,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            use pallet_trading_common::quote::premium_multiplier_bps;
+
+            let multiplier: BalanceOf<T> = premium_multiplier_bps(premium_bps).into();
+            let divisor: BalanceOf<T> = 10000u32.into();
+            Ok(market_price
+                .checked_mul(&multiplier)
+                .ok_or(Error::<T>::CalculationOverflow)?
+                / divisor)
+        }
+
+        /// 函数级详细中文注释：订单托管资金的所有者
+        ///
+        /// - 买单：做市商托管 DUST
+        /// - 卖单：用户托管 DUST
+        pub fn escrow_owner(order: &Order<T>) -> &T::AccountId {
+            match order.side {
+                OrderSide::Buy => &order.maker,
+                OrderSide::Sell => &order.taker,
+            }
+        }
+
+        /// 函数级详细中文注释：创建卖单
+        ///
+        /// ## 功能说明
+        /// 1. 验证做市商存在、激活且承接买入方向
+        /// 2. 按做市商买入溢价计算单价
+        /// 3. 将卖家的DUST锁定到托管
+        /// 4. 创建订单记录（卖单无买家押金、不占用买家额度）
+        ///
+        /// ## 返回
+        /// - `Ok(order_id)`: 订单ID
+        pub fn do_create_sell_order(
+            seller: &T::AccountId,
+            maker_id: u64,
+            dust_amount: BalanceOf<T>,
+            payout_commit: H256,
+            contact_commit: H256,
+        ) -> Result<u64, DispatchError> {
+            Self::enforce_kyc_requirement(seller)?;
+            let _usd_amount = Self::validate_order_amount(dust_amount, false)?;
+
+            let maker_app = T::MakerPallet::validate_maker(maker_id)
+                .map_err(|e| match e {
+                    MakerValidationError::NotFound => Error::<T>::MakerNotFound,
+                    MakerValidationError::NotActive => Error::<T>::MakerNotActive,
+                })?;
+            ensure!(maker_app.account != *seller, Error::<T>::NotAuthorized);
+            // 卖单付款方为做市商，须已注册加密公钥以接收卖家收款资料
+            ensure!(
+                T::KeyDirectory::encryption_key(&maker_app.account).is_some(),
+                Error::<T>::PayerKeyNotRegistered
+            );
+
+            let price = Self::quoted_price(maker_id, OrderSide::Sell, dust_amount)?;
+            let amount = dust_amount
+                .checked_mul(&price)
+                .ok_or(Error::<T>::CalculationOverflow)?;
+
+            let maker_tron_address = maker_app.tron_address
+                .try_into()
+                .map_err(|_| Error::<T>::EncodingError)?;
+
+            // 卖家DUST锁定到托管（使用 order_id 作为托管 ID）
+            let order_id = NextOrderId::<T>::get();
+            T::Escrow::lock_from(seller, order_id, dust_amount)?;
+
+            let now = T::Timestamp::now().as_secs().saturated_into::<u64>();
+            let expire_at = now
+                .checked_add(T::OrderTimeout::get())
+                .ok_or(Error::<T>::CalculationOverflow)?;
+            let evidence_until = now
+                .checked_add(T::EvidenceWindow::get())
+                .ok_or(Error::<T>::CalculationOverflow)?;
+
+            let order = Order {
+                maker_id,
+                maker: maker_app.account.clone(),
+                taker: seller.clone(),
+                price,
+                qty: dust_amount,
+                amount,
+                created_at: now,
+                expire_at,
+                evidence_until,
+                maker_tron_address,
+                payment_commit: payout_commit,
+                contact_commit,
+                state: OrderState::Created,
+                completed_at: None,
+                is_first_purchase: false,
+                buyer_deposit: Zero::zero(),
+                deposit_status: DepositStatus::None,
+                side: OrderSide::Sell,
+            };
+
+            Orders::<T>::insert(order_id, order);
+            NextOrderId::<T>::put(order_id + 1);
+
+            BuyerOrders::<T>::try_mutate(seller, |orders| {
+                orders.try_push(order_id)
+                    .map_err(|_| Error::<T>::TooManyOrders)
+            })?;
+            MakerOrders::<T>::try_mutate(maker_id, |orders| {
+                orders.try_push(order_id)
+                    .map_err(|_| Error::<T>::TooManyOrders)
+            })?;
+
+            Self::record_timeline(order_id, TimelineEventKind::Created, Some(seller.clone()));
+            Self::deposit_event(Event::SellOrderCreated {
+                order_id,
+                maker_id,
+                seller: seller.clone(),
+                dust_amount,
+                price,
+            });
+
+            // 授予买卖双方聊天权限（同买单，30天）
+            let chat_duration = 30u32 * 24 * 60 * 10;
+            let order_metadata = sp_std::vec::Vec::from(
+                alloc::format!("OTC卖单#{}", order_id).as_bytes()
+            );
+            let _ = T::ChatPermission::grant_bidirectional_scene_authorization(
+                *b"otc_ordr",
+                seller,
+                &maker_app.account,
+                pallet_chat_permission::SceneType::Order,
+                pallet_chat_permission::SceneId::Numeric(order_id),
+                Some(chat_duration.into()),
+                order_metadata,
+            );
+
+            Ok(order_id)
+        }
+
+        /// 函数级详细中文注释：做市商标记已向卖家付款
+        ///
+        /// ## 处理步骤
+        /// 1. 验证订单为 Created 状态的卖单
+        /// 2. 验证调用者是订单做市商
+        /// 3. 记录TRON交易哈希（如有）
+        /// 4. 更新订单状态为 PaidOrCommitted
+        pub fn do_mark_payout_sent(
+            maker: &T::AccountId,
+            order_id: u64,
+            tron_tx_hash: Option<sp_std::vec::Vec<u8>>,
+        ) -> DispatchResult {
+            let mut order = Orders::<T>::get(order_id)
+                .ok_or(Error::<T>::OrderNotFound)?;
+            ensure!(order.side == OrderSide::Sell, Error::<T>::WrongOrderSide);
+            ensure!(
+                matches!(order.state, OrderState::Created),
+                Error::<T>::InvalidOrderStatus
+            );
+            ensure!(order.maker == *maker, Error::<T>::NotAuthorized);
+            ensure!(
+                OrderPaymentDetails::<T>::contains_key(order_id),
+                Error::<T>::PaymentDetailsNotProvided
+            );
+
+            if let Some(tx_hash_vec) = tron_tx_hash {
+                Self::record_tron_tx_hash(tx_hash_vec)?;
+            }
+
+            let old_state = order.state.clone();
+            order.state = OrderState::PaidOrCommitted;
+            Orders::<T>::insert(order_id, order);
+
+            Self::note_state_change(order_id, &old_state, &OrderState::PaidOrCommitted, Some(maker.clone()));
+
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：卖家确认收款，释放托管DUST给做市商
+        ///
+        /// ## 处理步骤
+        /// 1. 验证订单为 PaidOrCommitted 状态的卖单
+        /// 2. 验证调用者是卖家
+        /// 3. 从托管释放DUST到做市商
+        /// 4. 记录做市商信用
+        pub fn do_confirm_payout(
+            seller: &T::AccountId,
+            order_id: u64,
+        ) -> DispatchResult {
+            let mut order = Orders::<T>::get(order_id)
+                .ok_or(Error::<T>::OrderNotFound)?;
+            ensure!(order.side == OrderSide::Sell, Error::<T>::WrongOrderSide);
+            ensure!(
+                matches!(order.state, OrderState::PaidOrCommitted),
+                Error::<T>::InvalidOrderStatus
+            );
+            ensure!(order.taker == *seller, Error::<T>::NotAuthorized);
+
+            T::Escrow::release_all(order_id, &order.maker)?;
+
+            let old_state = order.state.clone();
+            order.state = OrderState::Released;
+            let now = T::Timestamp::now().as_secs().saturated_into::<u64>();
+            order.completed_at = Some(now);
+            Orders::<T>::insert(order_id, order.clone());
+
+            let response_time_seconds = now.saturating_sub(order.created_at) as u32;
+            let _ = T::MakerCredit::record_maker_order_completed(
+                order.maker_id,
+                order_id,
+                response_time_seconds,
+            );
+
+            Self::note_state_change(order_id, &old_state, &OrderState::Released, Some(seller.clone()));
+
+            Ok(())
+        }
+        // ===== 订单加密资料内部函数 =====
+
+        /// 函数级详细中文注释：订单付款方（买单为买家，卖单为做市商）
+        pub fn payer(order: &Order<T>) -> &T::AccountId {
+            match order.side {
+                OrderSide::Buy => &order.taker,
+                OrderSide::Sell => &order.maker,
+            }
+        }
+
+        /// 函数级详细中文注释：订单收款方（买单为做市商，卖单为卖家）
+        pub fn payee(order: &Order<T>) -> &T::AccountId {
+            match order.side {
+                OrderSide::Buy => &order.maker,
+                OrderSide::Sell => &order.taker,
+            }
+        }
+
+        /// 函数级详细中文注释：提交加密收款资料
+        ///
+        /// ## 处理步骤
+        /// 1. 验证订单为 Created 状态且调用者是收款方
+        /// 2. 读取付款方当前 X25519 公钥作为加密目标快照
+        /// 3. 保存密文（覆盖旧资料）
+        pub fn do_submit_payment_details(
+            who: &T::AccountId,
+            order_id: u64,
+            ciphertext: sp_std::vec::Vec<u8>,
+        ) -> DispatchResult {
+            let order = Orders::<T>::get(order_id)
+                .ok_or(Error::<T>::OrderNotFound)?;
+            ensure!(
+                matches!(order.state, OrderState::Created),
+                Error::<T>::InvalidOrderStatus
+            );
+            ensure!(Self::payee(&order) == who, Error::<T>::NotAuthorized);
+
+            let recipient_key = T::KeyDirectory::encryption_key(Self::payer(&order))
+                .ok_or(Error::<T>::PayerKeyNotRegistered)?;
+            ensure!(!ciphertext.is_empty(), Error::<T>::InvalidPaymentDetails);
+            let ciphertext: BoundedVec<u8, T::MaxPaymentDetailsLen> = ciphertext
+                .try_into()
+                .map_err(|_| Error::<T>::InvalidPaymentDetails)?;
+
+            let now = T::Timestamp::now().as_secs().saturated_into::<u64>();
+            OrderPaymentDetails::<T>::insert(order_id, EncryptedPaymentDetails {
+                submitter: who.clone(),
+                recipient_key,
+                ciphertext,
+                submitted_at: now,
+            });
+
+            Self::deposit_event(Event::PaymentDetailsSubmitted {
+                order_id,
+                submitter: who.clone(),
+                recipient_key,
+            });
+
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：关联付款凭证
+        ///
+        /// ## 处理步骤
+        /// 1. 验证订单处于 Created / PaidOrCommitted / Disputed 状态且调用者是付款方
+        /// 2. 校验私密内容属于本订单、由付款方创建，且收款方与全体仲裁委员均持有密钥包
+        /// 3. 记录内容ID
+        pub fn do_submit_payment_proof(
+            who: &T::AccountId,
+            order_id: u64,
+            content_id: u64,
+        ) -> DispatchResult {
+            let order = Orders::<T>::get(order_id)
+                .ok_or(Error::<T>::OrderNotFound)?;
+            ensure!(
+                matches!(
+                    order.state,
+                    OrderState::Created | OrderState::PaidOrCommitted | OrderState::Disputed
+                ),
+                Error::<T>::InvalidOrderStatus
+            );
+            ensure!(Self::payer(&order) == who, Error::<T>::NotAuthorized);
+
+            let mut readers = T::ArbitrationCommittee::sorted_members();
+            readers.push(Self::payee(&order).clone());
+            ensure!(
+                T::PrivateEvidence::is_shared_with(
+                    content_id,
+                    PAYMENT_PROOF_NS,
+                    order_id,
+                    who,
+                    &readers,
+                ),
+                Error::<T>::InvalidPaymentProof
+            );
+
+            OrderPaymentProofs::<T>::try_mutate(order_id, |proofs| -> DispatchResult {
+                ensure!(!proofs.contains(&content_id), Error::<T>::PaymentProofAlreadyLinked);
+                proofs.try_push(content_id)
+                    .map_err(|_| Error::<T>::TooManyPaymentProofs)?;
+                Ok(())
+            })?;
+
+            Self::record_timeline(order_id, TimelineEventKind::PaymentProof(content_id), Some(who.clone()));
+            Self::deposit_event(Event::PaymentProofLinked {
+                order_id,
+                payer: who.clone(),
+                content_id,
+            });
+
+            Ok(())
+        }
+        // ===== 拆单路由内部函数 =====
+
+        /// 函数级详细中文注释：候选做市商排序
+        ///
+        /// 按卖出溢价升序（买家成本低者优先）、信用分降序排序，去重并剔除不承接买入方向的做市商
+        pub fn rank_split_candidates(candidates: &[u64]) -> sp_std::vec::Vec<u64> {
+            let mut ranked: sp_std::vec::Vec<(i16, u16, u64)> = sp_std::vec::Vec::new();
+            for &maker_id in candidates {
+                if ranked.iter().any(|(_, _, id)| *id == maker_id) {
+                    continue;
+                }
+                if !T::MakerPallet::serves_side(maker_id, OrderSide::Buy) {
+                    continue;
+                }
+                let premium = T::MakerPallet::get_maker_quote(maker_id)
+                    .map(|quote| quote.premium_bps(OrderSide::Buy))
+                    .unwrap_or(0);
+                let score = T::MakerCredit::query_maker_credit_score(maker_id).unwrap_or(0);
+                ranked.push((premium, score, maker_id));
+            }
+            ranked.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)).then(a.2.cmp(&b.2)));
+            ranked.into_iter().map(|(_, _, id)| id).collect()
+        }
+
+        /// 函数级详细中文注释：创建拆单
+        ///
+        /// ## 处理步骤
+        /// 1. 校验候选列表，按溢价与信用分排序
+        /// 2. 依次为每个做市商计算子订单DUST数量（受单笔上限与报价限额约束）
+        /// 3. 在独立存储层中调用 `do_create_order`，失败回滚并跳过该做市商
+        /// 4. 记录拆单与子订单关联
+        ///
+        /// ## 返回
+        /// - `Ok(split_id)`: 拆单ID
+        pub fn do_create_split_order(
+            buyer: &T::AccountId,
+            usd_amount: u64,
+            candidates: sp_std::vec::Vec<u64>,
+            allow_partial: bool,
+            payment_commit: H256,
+            contact_commit: H256,
+        ) -> Result<u64, DispatchError> {
+            ensure!(
+                !candidates.is_empty() && candidates.len() as u32 <= MAX_SPLIT_CANDIDATES,
+                Error::<T>::InvalidSplitCandidates
+            );
+            let min_usd = T::MinOrderUsdAmount::get();
+            let max_usd = T::MaxOrderUsdAmount::get();
+            ensure!(usd_amount >= min_usd, Error::<T>::OrderAmountTooSmall);
+
+            let market_price = T::Pricing::get_dust_to_usd_rate()
+                .ok_or(Error::<T>::PricingUnavailable)?;
+
+            let mut remaining = usd_amount;
+            let mut children: BoundedVec<u64, MaxSplitChildren> = BoundedVec::new();
+
+            for maker_id in Self::rank_split_candidates(&candidates) {
+                if remaining < min_usd || children.is_full() {
+                    break;
+                }
+
+                // 子订单单价：市场价 × (1 + 该做市商卖出溢价)，与 quoted_price 一致
+                let quote = T::MakerPallet::get_maker_quote(maker_id);
+                let premium = quote.as_ref().map(|q| q.premium_bps(OrderSide::Buy)).unwrap_or(0);
+                let maker_price = Self::apply_premium(market_price, premium)?;
+                if maker_price.is_zero() {
+                    continue;
+                }
+
+                // 子订单金额：按做市商单价折算剩余金额，并受单笔上限（按市场价校验）与报价上限约束
+                let mut dust_amount = Self::dust_for_usd_at_price(remaining.min(max_usd), maker_price)?
+                    .min(Self::dust_for_usd_at_price(max_usd, market_price)?);
+                if let Some(quote) = quote {
+                    if dust_amount > quote.max_amount {
+                        dust_amount = quote.max_amount;
+                    }
+                    if dust_amount < quote.min_amount {
+                        continue;
+                    }
+                }
+                if Self::calculate_usd_amount_from_dust(dust_amount, maker_price)? < min_usd {
+                    continue;
+                }
+
+                let created = frame_support::storage::with_storage_layer(|| {
+                    Self::do_create_order(buyer, maker_id, dust_amount, payment_commit, contact_commit)
+                });
+                if let Ok(order_id) = created {
+                    // 按子订单实际成交单价扣减剩余金额
+                    let child_usd = Orders::<T>::get(order_id)
+                        .map(|order| Self::calculate_usd_amount_from_dust(order.qty, order.price))
+                        .transpose()?
+                        .unwrap_or_default();
+                    // children 未满（循环开头已检查）
+                    let _ = children.try_push(order_id);
+                    remaining = remaining.saturating_sub(child_usd);
+                }
+            }
+
+            ensure!(!children.is_empty(), Error::<T>::NoMakerAvailable);
+            ensure!(allow_partial || remaining < min_usd, Error::<T>::SplitNotFilled);
+
+            let split_id = NextSplitOrderId::<T>::mutate(|id| {
+                let current = *id;
+                *id = id.saturating_add(1);
+                current
+            });
+            for order_id in children.iter() {
+                OrderParent::<T>::insert(order_id, split_id);
+            }
+
+            let filled_usd = usd_amount.saturating_sub(remaining);
+            let now = T::Timestamp::now().as_secs().saturated_into::<u64>();
+            SplitOrders::<T>::insert(split_id, SplitOrder {
+                buyer: buyer.clone(),
+                requested_usd: usd_amount,
+                filled_usd,
+                children: children.clone(),
+                created_at: now,
+            });
+
+            Self::deposit_event(Event::SplitOrderCreated {
+                split_id,
+                buyer: buyer.clone(),
+                requested_usd: usd_amount,
+                filled_usd,
+                children: children.into_inner(),
+            });
+
+            Ok(split_id)
+        }
+    }
+    
+    // ===== 公共查询接口 =====
+    
+    impl<T: Config> Pallet<T> {
+        /// 函数级详细中文注释：检查买家是否已首购
+        pub fn has_user_first_purchased(who: &T::AccountId) -> bool {
+            HasFirstPurchased::<T>::get(who)
+        }
+        
+        /// 函数级详细中文注释：获取做市商首购订单数量
+        pub fn get_maker_first_purchase_count(maker_id: u64) -> u32 {
+            MakerFirstPurchaseCount::<T>::get(maker_id)
         }
         
         // ===== 🆕 2026-01-18: 可读时间查询接口 =====
@@ -2279,6 +3186,57 @@ pub mod pallet {
                 .collect()
         }
         
+        /// 函数级详细中文注释：获取拆单汇总状态
+        ///
+        /// ## 汇总规则
+        /// - 任一子订单争议中 → Disputed
+        /// - 任一子订单进行中 → InProgress
+        /// - 全部释放 → Completed；部分释放 → PartiallyCompleted；无一释放 → Failed
+        /// - 已归档的子订单按归档状态计算
+        pub fn get_split_order_status(split_id: u64) -> Option<SplitOrderView<T>> {
+            let split = SplitOrders::<T>::get(split_id)?;
+
+            let mut children = sp_std::vec::Vec::with_capacity(split.children.len());
+            let (mut disputed, mut in_progress, mut released) = (false, false, 0usize);
+            for &order_id in split.children.iter() {
+                let (maker_id, state) = match Orders::<T>::get(order_id) {
+                    Some(order) => (order.maker_id, order.state),
+                    None => match ArchivedOrders::<T>::get(order_id) {
+                        Some(archived) => (archived.maker_id, archived.state),
+                        None => continue,
+                    },
+                };
+                match state {
+                    OrderState::Disputed => disputed = true,
+                    OrderState::Created | OrderState::PaidOrCommitted => in_progress = true,
+                    OrderState::Released => released += 1,
+                    _ => {},
+                }
+                children.push((order_id, maker_id, Self::state_to_u8(&state)));
+            }
+
+            let status = if disputed {
+                SplitOrderStatus::Disputed
+            } else if in_progress {
+                SplitOrderStatus::InProgress
+            } else if released == split.children.len() {
+                SplitOrderStatus::Completed
+            } else if released > 0 {
+                SplitOrderStatus::PartiallyCompleted
+            } else {
+                SplitOrderStatus::Failed
+            };
+
+            Some(SplitOrderView {
+                split_id,
+                buyer: split.buyer,
+                requested_usd: split.requested_usd,
+                filled_usd: split.filled_usd,
+                status,
+                children,
+            })
+        }
+        
         /// 函数级详细中文注释：将订单状态转换为 u8（用于事件）
         fn state_to_u8(state: &OrderState) -> u8 {
             match state {
@@ -2292,6 +3250,29 @@ pub mod pallet {
                 OrderState::Expired => 7,
             }
         }
+
+        /// 函数级详细中文注释：记录订单状态变化（事件 + 案件时间线）
+        fn note_state_change(
+            order_id: u64,
+            old_state: &OrderState,
+            new_state: &OrderState,
+            actor: Option<T::AccountId>,
+        ) {
+            let from = Self::state_to_u8(old_state);
+            let to = Self::state_to_u8(new_state);
+            Self::record_timeline(order_id, TimelineEventKind::StateChanged { from, to }, actor.clone());
+            Self::deposit_event(Event::OrderStateChanged {
+                order_id,
+                old_state: from,
+                new_state: to,
+                actor,
+            });
+        }
+
+        /// 函数级详细中文注释：追加订单时间线记录（仲裁域 OTC_ORDER）
+        fn record_timeline(order_id: u64, kind: TimelineEventKind<T::Hash>, actor: Option<T::AccountId>) {
+            T::Timeline::record(OTC_ORDER, order_id, kind, actor);
+        }
         
         // ===== 🆕 2026-01-18: 买家押金计算 =====
         
@@ -2536,8 +3517,8 @@ pub mod pallet {
         /// 
         /// ## 处理步骤
         /// 1. 更新订单状态为 Expired
-        /// 2. 退还托管资金给买家
-        /// 3. 释放买家占用的额度
+        /// 2. 退还托管资金给托管方（买单退做市商，卖单退卖家）
+        /// 3. 释放买家占用的额度（仅买单）
         /// 4. 如是首购订单，减少做市商首购计数
         fn do_expire_order(order_id: u64, order: &Order<T>) -> DispatchResult {
             // 1. 更新订单状态
@@ -2547,12 +3528,14 @@ pub mod pallet {
                 }
             });
             
-            // 2. 退还托管资金给买家
-            let _ = T::Escrow::refund_all(order_id, &order.taker);
+            // 2. 退还托管资金给托管方
+            let _ = T::Escrow::refund_all(order_id, Self::escrow_owner(order));
             
-            // 3. 释放买家占用的额度（amount 是 USDT 金额）
-            let usd_amount: u64 = order.amount.saturated_into();
-            let _ = T::Credit::release_quota(&order.taker, usd_amount);
+            // 3. 释放买家占用的额度（amount 是 USDT 金额，卖单不占用额度）
+            if order.side == OrderSide::Buy {
+                let usd_amount: u64 = order.amount.saturated_into();
+                let _ = T::Credit::release_quota(&order.taker, usd_amount);
+            }
             
             // 4. 如是首购订单，减少做市商首购计数
             if order.is_first_purchase {
@@ -2562,7 +3545,22 @@ pub mod pallet {
             }
             
             // 🆕 2026-01-18: 超时没收买家押金给做市商（100%）
-            if !order.buyer_deposit.is_zero() {
+            // 做市商未提交收款资料时买家无法付款，押金全额退还
+            if !order.buyer_deposit.is_zero() && !OrderPaymentDetails::<T>::contains_key(order_id) {
+                let _ = Self::release_buyer_deposit(&order.taker, order.buyer_deposit);
+                
+                Orders::<T>::mutate(order_id, |o| {
+                    if let Some(ord) = o {
+                        ord.deposit_status = DepositStatus::Released;
+                    }
+                });
+                
+                Self::deposit_event(Event::BuyerDepositReleased {
+                    order_id,
+                    buyer: order.taker.clone(),
+                    refund_amount: order.buyer_deposit,
+                });
+            } else if !order.buyer_deposit.is_zero() {
                 let _ = Self::forfeit_buyer_deposit(&order.maker, order.buyer_deposit);
                 
                 // 更新押金状态
@@ -2581,6 +3579,14 @@ pub mod pallet {
             }
             
             // 5. 发出事件
+            Self::record_timeline(
+                order_id,
+                TimelineEventKind::StateChanged {
+                    from: Self::state_to_u8(&order.state),
+                    to: Self::state_to_u8(&OrderState::Expired),
+                },
+                None,
+            );
             Self::deposit_event(Event::OrderAutoExpired {
                 order_id,
                 buyer: order.taker.clone(),
@@ -2621,6 +3627,8 @@ pub mod pallet {
         /// - Release: 全额放款给做市商（买家败诉）
         /// - Refund: 全额退款给买家（做市商败诉）
         /// - Partial(bps): 按比例分账（双方都有责任）
+        ///
+        /// 卖单托管方为用户，Release/Refund 分别对应释放给做市商、退还用户，语义不变
         /// 
         /// ## 参数
         /// - `order_id`: 订单ID
@@ -2674,6 +3682,14 @@ pub mod pallet {
             );
             
             // 更新订单
+            Self::record_timeline(
+                order_id,
+                TimelineEventKind::StateChanged {
+                    from: Self::state_to_u8(&OrderState::Disputed),
+                    to: Self::state_to_u8(&order.state),
+                },
+                None,
+            );
             order.completed_at = Some(T::Timestamp::now().as_secs());
             Orders::<T>::insert(order_id, order);
             
@@ -2774,9 +3790,21 @@ pub mod pallet {
             let dust_to_usd_rate = T::Pricing::get_dust_to_usd_rate()
                 .ok_or(Error::<T>::PricingServiceUnavailable)?;
 
-            // 计算DUST数量 = USD金额 × DUST精度 ÷ DUST/USD汇率
+            Self::dust_for_usd_at_price(usd_amount, dust_to_usd_rate)
+        }
+
+        /// 函数级详细中文注释：按指定单价计算USD金额对应的DUST数量
+        ///
+        /// # 参数
+        /// - usd_amount: USD金额（精度10^6）
+        /// - price: DUST/USD单价（精度10^6）
+        fn dust_for_usd_at_price(
+            usd_amount: u64,
+            price: BalanceOf<T>,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            // 计算DUST数量 = USD金额 × DUST精度 ÷ 单价
             let usd_u128 = usd_amount as u128;
-            let rate_u128: u128 = dust_to_usd_rate.saturated_into();
+            let rate_u128: u128 = price.saturated_into();
 
             let dust_u128 = usd_u128
                 .checked_mul(1_000_000_000_000u128) // 乘以DUST精度10^12
@@ -2848,10 +3876,12 @@ pub mod pallet {
             const ARCHIVE_DELAY_SECS: u64 = 30 * 24 * 60 * 60;
             let now_secs = T::Timestamp::now().as_secs();
 
+            // 游标指向下一个待检查的订单ID
             while processed < max_count && cursor < next_id {
+                let order_id = cursor;
                 cursor = cursor.saturating_add(1);
 
-                if let Some(order) = Orders::<T>::get(cursor) {
+                if let Some(order) = Orders::<T>::get(order_id) {
                     // 检查是否为可归档状态
                     let is_final_state = matches!(
                         order.state,
@@ -2880,17 +3910,25 @@ pub mod pallet {
                     };
 
                     // 保存归档并删除原订单
-                    ArchivedOrders::<T>::insert(cursor, archived);
-                    Orders::<T>::remove(cursor);
+                    ArchivedOrders::<T>::insert(order_id, archived);
+                    Orders::<T>::remove(order_id);
+
+                    // 清理加密收款资料与凭证索引（凭证本体保留在 pallet-evidence）
+                    OrderPaymentDetails::<T>::remove(order_id);
+                    OrderPaymentProofs::<T>::remove(order_id);
+                    T::Timeline::clear(OTC_ORDER, order_id);
+
+                    // 解除拆单关联
+                    Self::archive_split_child(order_id);
 
                     // 从做市商订单列表中移除
                     MakerOrders::<T>::mutate(order.maker_id, |ids| {
-                        ids.retain(|&id| id != cursor);
+                        ids.retain(|&id| id != order_id);
                     });
 
                     // 从买家订单列表中移除
                     BuyerOrders::<T>::mutate(&order.taker, |ids| {
-                        ids.retain(|&id| id != cursor);
+                        ids.retain(|&id| id != order_id);
                     });
 
                     processed = processed.saturating_add(1);
@@ -2914,10 +3952,12 @@ pub mod pallet {
             const L2_ARCHIVE_DELAY_SECS: u64 = 90 * 24 * 60 * 60;
             let now_secs = T::Timestamp::now().as_secs();
 
+            // 游标指向下一个待检查的订单ID
             while processed < max_count && cursor < next_id {
+                let order_id = cursor;
                 cursor = cursor.saturating_add(1);
 
-                if let Some(archived_l1) = ArchivedOrders::<T>::get(cursor) {
+                if let Some(archived_l1) = ArchivedOrders::<T>::get(order_id) {
                     // 检查 L1 归档时间是否超过延迟
                     if now_secs.saturating_sub(archived_l1.completed_at) < L2_ARCHIVE_DELAY_SECS {
                         continue;
@@ -2925,7 +3965,7 @@ pub mod pallet {
 
                     // 创建 L2 归档记录
                     let archived_l2 = ArchivedOrderL2 {
-                        id: cursor,
+                        id: order_id,
                         status: Self::order_state_to_u8(&archived_l1.state),
                         year_month: Self::timestamp_to_year_month(archived_l1.completed_at),
                         amount_tier: pallet_storage_lifecycle::amount_to_tier(archived_l1.amount),
@@ -2944,8 +3984,8 @@ pub mod pallet {
                     });
 
                     // 保存 L2 归档并删除 L1 归档
-                    ArchivedOrdersL2::<T>::insert(cursor, archived_l2);
-                    ArchivedOrders::<T>::remove(cursor);
+                    ArchivedOrdersL2::<T>::insert(order_id, archived_l2);
+                    ArchivedOrders::<T>::remove(order_id);
 
                     processed = processed.saturating_add(1);
                 }
@@ -2955,6 +3995,19 @@ pub mod pallet {
             Weight::from_parts(20_000 * processed as u64, 0)
         }
 
+        /// 子订单归档时解除拆单关联，全部子订单归档后删除拆单记录
+        fn archive_split_child(order_id: u64) {
+            let Some(split_id) = OrderParent::<T>::take(order_id) else {
+                return;
+            };
+            let all_archived = SplitOrders::<T>::get(split_id).map_or(true, |split| {
+                split.children.iter().all(|child| !OrderParent::<T>::contains_key(child))
+            });
+            if all_archived {
+                SplitOrders::<T>::remove(split_id);
+            }
+        }
+
         /// 辅助函数：OrderState 转 u8
         fn order_state_to_u8(state: &OrderState) -> u8 {
             match state {
@@ -0,0 +1,527 @@
+//! OTC 订单 Pallet 测试模拟环境
+
+use crate as pallet_trading_otc;
+use frame_support::{
+    derive_impl,
+    parameter_types,
+    traits::{ConstU128, ConstU16, ConstU32, ConstU64, Currency, ExistenceRequirement, SortedMembers},
+};
+use frame_system::EnsureRoot;
+use pallet_arbitration::pallet::{DisputeTimeline, TimelineEventKind};
+use pallet_key_directory::types::{DirectoryKey, KeyAlgorithm, KeyPurpose};
+use pallet_trading_common::{
+    MakerApplicationInfo, MakerCreditInterface, MakerInterface, MakerQuote, OrderSide,
+    PricingProvider, PrivateEvidenceInterface, WorkingHours,
+};
+use sp_runtime::{BuildStorage, DispatchError, DispatchResult};
+use std::collections::BTreeMap;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+/// 1 DUST（精度 10^12）
+pub const DUST: u128 = 1_000_000_000_000;
+/// 卖家
+pub const SELLER: u64 = 1;
+/// 无关账户
+pub const OUTSIDER: u64 = 2;
+/// 买家
+pub const BUYER: u64 = 3;
+/// 做市商账户
+pub const MAKER: u64 = 10;
+/// 做市商ID
+pub const MAKER_ID: u64 = 1;
+/// 第二个做市商账户
+pub const MAKER_2: u64 = 11;
+/// 第二个做市商ID
+pub const MAKER_2_ID: u64 = 2;
+/// 第三个做市商账户
+pub const MAKER_3: u64 = 12;
+/// 第三个做市商ID
+pub const MAKER_3_ID: u64 = 3;
+/// 仲裁委员会成员
+pub const ARBITRATOR: u64 = 100;
+/// 托管账户
+pub const ESCROW: u64 = 999;
+
+// 构建模拟运行时
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Balances: pallet_balances,
+        Timestamp: pallet_timestamp,
+        Otc: pallet_trading_otc,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = pallet_balances::AccountData<u128>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+    type Balance = u128;
+    type ExistentialDeposit = ConstU128<1>;
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = ConstU64<1>;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    /// 托管账本：订单ID -> 锁定金额
+    pub static EscrowLocked: BTreeMap<u64, u128> = BTreeMap::new();
+}
+
+/// 模拟托管：资金转入 ESCROW 账户并按订单记账
+pub struct MockEscrow;
+
+impl pallet_escrow::Escrow<u64, u128> for MockEscrow {
+    fn escrow_account() -> u64 {
+        ESCROW
+    }
+
+    fn lock_from(payer: &u64, id: u64, amount: u128) -> DispatchResult {
+        <Balances as Currency<u64>>::transfer(payer, &ESCROW, amount, ExistenceRequirement::AllowDeath)?;
+        EscrowLocked::mutate(|locked| *locked.entry(id).or_default() += amount);
+        Ok(())
+    }
+
+    fn transfer_from_escrow(id: u64, to: &u64, amount: u128) -> DispatchResult {
+        let current = Self::amount_of(id);
+        if amount > current {
+            return Err(DispatchError::Other("Insufficient"));
+        }
+        <Balances as Currency<u64>>::transfer(&ESCROW, to, amount, ExistenceRequirement::AllowDeath)?;
+        EscrowLocked::mutate(|locked| locked.insert(id, current - amount));
+        Ok(())
+    }
+
+    fn release_all(id: u64, to: &u64) -> DispatchResult {
+        Self::transfer_from_escrow(id, to, Self::amount_of(id))
+    }
+
+    fn refund_all(id: u64, to: &u64) -> DispatchResult {
+        Self::transfer_from_escrow(id, to, Self::amount_of(id))
+    }
+
+    fn amount_of(id: u64) -> u128 {
+        EscrowLocked::get().get(&id).copied().unwrap_or_default()
+    }
+
+    fn split_partial(id: u64, release_to: &u64, refund_to: &u64, bps: u16) -> DispatchResult {
+        let total = Self::amount_of(id);
+        let released = total * bps as u128 / 10_000;
+        Self::transfer_from_escrow(id, release_to, released)?;
+        Self::transfer_from_escrow(id, refund_to, total - released)
+    }
+}
+
+/// 模拟买家信用与额度（全部放行）
+pub struct MockCredit;
+
+impl pallet_trading_credit::BuyerCreditInterface<u64> for MockCredit {
+    fn get_buyer_credit_score(_buyer: &u64) -> Result<u16, DispatchError> {
+        Ok(100)
+    }
+
+    fn check_buyer_daily_limit(_buyer: &u64, _amount_usd_cents: u64) -> DispatchResult {
+        Ok(())
+    }
+
+    fn check_buyer_single_limit(_buyer: &u64, _amount_usd_cents: u64) -> DispatchResult {
+        Ok(())
+    }
+}
+
+impl pallet_trading_credit::quota::BuyerQuotaInterface<u64> for MockCredit {
+    fn get_available_quota(_buyer: &u64) -> Result<u64, DispatchError> {
+        Ok(u64::MAX)
+    }
+
+    fn occupy_quota(_buyer: &u64, _amount_usd: u64) -> DispatchResult {
+        Ok(())
+    }
+
+    fn release_quota(_buyer: &u64, _amount_usd: u64) -> DispatchResult {
+        Ok(())
+    }
+
+    fn check_concurrent_limit(_buyer: &u64) -> Result<bool, DispatchError> {
+        Ok(true)
+    }
+
+    fn record_order_completed(_buyer: &u64, _order_id: u64) -> DispatchResult {
+        Ok(())
+    }
+
+    fn record_order_cancelled(_buyer: &u64, _order_id: u64) -> DispatchResult {
+        Ok(())
+    }
+
+    fn record_violation(
+        _buyer: &u64,
+        _violation_type: pallet_trading_credit::quota::ViolationType,
+    ) -> DispatchResult {
+        Ok(())
+    }
+
+    fn is_suspended(_buyer: &u64) -> Result<bool, DispatchError> {
+        Ok(false)
+    }
+
+    fn is_blacklisted(_buyer: &u64) -> Result<bool, DispatchError> {
+        Ok(false)
+    }
+}
+
+/// 模拟定价接口：1 DUST = 1 USD
+pub struct MockPricing;
+
+impl PricingProvider<u128> for MockPricing {
+    fn get_dust_to_usd_rate() -> Option<u128> {
+        Some(1_000_000)
+    }
+
+    fn report_swap_order(_timestamp: u64, _price_usdt: u64, _dust_qty: u128) -> DispatchResult {
+        Ok(())
+    }
+}
+
+parameter_types! {
+    /// 做市商报价：做市商ID -> 报价（未登记则按市场价成交）
+    pub static MakerQuotes: BTreeMap<u64, MakerQuote<u128>> = BTreeMap::new();
+    /// 做市商信用分：做市商ID -> 信用分
+    pub static MakerCreditScores: BTreeMap<u64, u16> = BTreeMap::new();
+}
+
+/// 做市商ID对应的账户
+fn maker_account(maker_id: u64) -> Option<u64> {
+    match maker_id {
+        MAKER_ID => Some(MAKER),
+        MAKER_2_ID => Some(MAKER_2),
+        MAKER_3_ID => Some(MAKER_3),
+        _ => None,
+    }
+}
+
+/// 登记做市商报价（全天营业）
+pub fn set_maker_quote(maker_id: u64, sell_premium_bps: i16, max_amount: u128) {
+    MakerQuotes::mutate(|quotes| {
+        quotes.insert(maker_id, MakerQuote {
+            buy_premium_bps: 0,
+            sell_premium_bps,
+            min_amount: 0,
+            max_amount,
+            payment_rails: Default::default(),
+            working_hours: WorkingHours { start_minute: 0, end_minute: 0 },
+            updated_at: 0,
+        })
+    });
+}
+
+/// 模拟做市商：MAKER_ID / MAKER_2_ID / MAKER_3_ID，双向承接，报价见 `MakerQuotes`
+pub struct MockMakerPallet;
+
+impl MakerInterface<u64, u128> for MockMakerPallet {
+    fn get_maker_application(maker_id: u64) -> Option<MakerApplicationInfo<u64, u128>> {
+        maker_account(maker_id).map(|account| MakerApplicationInfo {
+            account,
+            tron_address: b"TYASr5UV6HEcXatwdFQfmLVUqQQQMUxHLS".to_vec().try_into().unwrap(),
+            is_active: true,
+            _phantom: Default::default(),
+        })
+    }
+
+    fn is_maker_active(maker_id: u64) -> bool {
+        maker_account(maker_id).is_some()
+    }
+
+    fn get_maker_id(who: &u64) -> Option<u64> {
+        [MAKER_ID, MAKER_2_ID, MAKER_3_ID]
+            .into_iter()
+            .find(|&maker_id| maker_account(maker_id) == Some(*who))
+    }
+
+    fn get_deposit_usd_value(_maker_id: u64) -> Result<u64, DispatchError> {
+        Ok(1_000_000_000)
+    }
+
+    fn get_maker_quote(maker_id: u64) -> Option<MakerQuote<u128>> {
+        MakerQuotes::get().get(&maker_id).cloned()
+    }
+
+    fn serves_side(maker_id: u64, _side: OrderSide) -> bool {
+        maker_account(maker_id).is_some()
+    }
+}
+
+/// 模拟做市商信用：只提供信用分查询
+pub struct MockMakerCredit;
+
+impl MakerCreditInterface for MockMakerCredit {
+    fn record_maker_order_completed(_maker_id: u64, _order_id: u64, _response_time_seconds: u32) -> DispatchResult {
+        Ok(())
+    }
+
+    fn record_maker_order_timeout(_maker_id: u64, _order_id: u64) -> DispatchResult {
+        Ok(())
+    }
+
+    fn record_maker_dispute_result(_maker_id: u64, _order_id: u64, _maker_win: bool) -> DispatchResult {
+        Ok(())
+    }
+
+    fn query_maker_credit_score(maker_id: u64) -> Option<u16> {
+        MakerCreditScores::get().get(&maker_id).copied()
+    }
+}
+
+/// 模拟聊天权限管理
+pub struct MockChatPermission;
+
+impl pallet_chat_permission::SceneAuthorizationManager<u64, u64> for MockChatPermission {
+    fn grant_scene_authorization(
+        _source: [u8; 8],
+        _from: &u64,
+        _to: &u64,
+        _scene_type: pallet_chat_permission::SceneType,
+        _scene_id: pallet_chat_permission::SceneId,
+        _duration: Option<u64>,
+        _metadata: Vec<u8>,
+    ) -> DispatchResult {
+        Ok(())
+    }
+
+    fn grant_bidirectional_scene_authorization(
+        _source: [u8; 8],
+        _user1: &u64,
+        _user2: &u64,
+        _scene_type: pallet_chat_permission::SceneType,
+        _scene_id: pallet_chat_permission::SceneId,
+        _duration: Option<u64>,
+        _metadata: Vec<u8>,
+    ) -> DispatchResult {
+        Ok(())
+    }
+
+    fn revoke_scene_authorization(
+        _source: [u8; 8],
+        _from: &u64,
+        _to: &u64,
+        _scene_type: pallet_chat_permission::SceneType,
+        _scene_id: pallet_chat_permission::SceneId,
+    ) -> DispatchResult {
+        Ok(())
+    }
+
+    fn revoke_all_by_source(_source: [u8; 8], _user1: &u64, _user2: &u64) -> DispatchResult {
+        Ok(())
+    }
+
+    fn extend_scene_authorization(
+        _source: [u8; 8],
+        _from: &u64,
+        _to: &u64,
+        _scene_type: pallet_chat_permission::SceneType,
+        _scene_id: pallet_chat_permission::SceneId,
+        _additional_duration: u64,
+    ) -> DispatchResult {
+        Ok(())
+    }
+
+    fn has_any_valid_scene_authorization(_from: &u64, _to: &u64) -> bool {
+        false
+    }
+
+    fn get_valid_scene_authorizations(
+        _user1: &u64,
+        _user2: &u64,
+    ) -> Vec<pallet_chat_permission::SceneAuthorization<u64>> {
+        Vec::new()
+    }
+}
+
+/// 模拟 CID 锁定管理器
+pub struct MockCidLockManager;
+
+impl pallet_storage_service::CidLockManager<sp_core::H256, u64> for MockCidLockManager {
+    fn lock_cid(_cid_hash: sp_core::H256, _reason: Vec<u8>, _until: Option<u64>) -> DispatchResult {
+        Ok(())
+    }
+
+    fn unlock_cid(_cid_hash: sp_core::H256, _reason: Vec<u8>) -> DispatchResult {
+        Ok(())
+    }
+
+    fn is_locked(_cid_hash: &sp_core::H256) -> bool {
+        false
+    }
+}
+
+/// 模拟公钥目录：所有账户均持有 X25519 加密公钥
+pub struct MockKeyDirectory;
+
+impl pallet_key_directory::traits::KeyDirectory<u64> for MockKeyDirectory {
+    fn current_key(who: &u64, _purpose: KeyPurpose) -> Option<DirectoryKey> {
+        Some(DirectoryKey {
+            version: 1,
+            algorithm: KeyAlgorithm::X25519,
+            public_key: [*who as u8; 32].to_vec(),
+        })
+    }
+
+    fn is_usable_key(_who: &u64, _purpose: KeyPurpose, _public_key: &[u8]) -> bool {
+        true
+    }
+}
+
+parameter_types! {
+    /// 已加密共享的私密内容：(内容ID, 命名空间, 主体ID, 创建者, 持有密钥包的账户)
+    pub static PrivateContents: Vec<(u64, [u8; 8], u64, u64, Vec<u64>)> = Vec::new();
+}
+
+/// 登记一条私密内容及其密钥包持有者
+pub fn share_private_content(content_id: u64, subject_id: u64, creator: u64, readers: Vec<u64>) {
+    PrivateContents::mutate(|contents| {
+        contents.push((content_id, pallet_trading_otc::PAYMENT_PROOF_NS, subject_id, creator, readers))
+    });
+}
+
+/// 模拟私密证据：按 `PrivateContents` 登记校验内容归属与密钥包
+pub struct MockPrivateEvidence;
+
+impl PrivateEvidenceInterface<u64> for MockPrivateEvidence {
+    fn is_shared_with(
+        content_id: u64,
+        ns: [u8; 8],
+        subject_id: u64,
+        creator: &u64,
+        readers: &[u64],
+    ) -> bool {
+        PrivateContents::get().iter().any(|(id, content_ns, subject, owner, shared)| {
+            *id == content_id
+                && *content_ns == ns
+                && *subject == subject_id
+                && owner == creator
+                && readers.iter().all(|reader| shared.contains(reader))
+        })
+    }
+}
+
+parameter_types! {
+    /// 案件时间线：(域, 对象ID) -> 按时间排序的条目
+    pub static Timelines: BTreeMap<([u8; 8], u64), Vec<TimelineEventKind<sp_core::H256>>> = BTreeMap::new();
+}
+
+/// 时间线条目上限（对应仲裁模块 `MaxTimelineEntries`）
+pub const MAX_TIMELINE_ENTRIES: usize = 16;
+
+/// 模拟案件时间线：与仲裁模块一致，自对象创建起记录，超出上限丢弃最早条目
+pub struct MockTimeline;
+
+impl DisputeTimeline<u64, sp_core::H256> for MockTimeline {
+    fn record(domain: [u8; 8], id: u64, kind: TimelineEventKind<sp_core::H256>, _actor: Option<u64>) {
+        Timelines::mutate(|timelines| {
+            let entries = timelines.entry((domain, id)).or_default();
+            if entries.len() >= MAX_TIMELINE_ENTRIES {
+                entries.remove(0);
+            }
+            entries.push(kind);
+        });
+    }
+
+    fn clear(domain: [u8; 8], id: u64) {
+        Timelines::mutate(|timelines| {
+            timelines.remove(&(domain, id));
+        });
+    }
+}
+
+/// 模拟仲裁委员会
+pub struct MockArbitrationCommittee;
+
+impl SortedMembers<u64> for MockArbitrationCommittee {
+    fn sorted_members() -> Vec<u64> {
+        vec![ARBITRATOR]
+    }
+}
+
+impl pallet_trading_otc::Config for Test {
+    type Currency = Balances;
+    type Timestamp = Timestamp;
+    type Escrow = MockEscrow;
+    type Credit = MockCredit;
+    type MakerCredit = MockMakerCredit;
+    type Pricing = MockPricing;
+    type MakerPallet = MockMakerPallet;
+    type CommitteeOrigin = EnsureRoot<u64>;
+    type IdentityProvider = ();
+    type ChatPermission = MockChatPermission;
+    type OrderTimeout = ConstU64<3600>;
+    type EvidenceWindow = ConstU64<86400>;
+    type FirstPurchaseUsdValue = ConstU128<10_000_000>;
+    type MinFirstPurchaseDustAmount = ConstU128<DUST>;
+    type MaxFirstPurchaseDustAmount = ConstU128<{ 100_000_000 * DUST }>;
+    type MaxOrderUsdAmount = ConstU64<200_000_000>;
+    type MinOrderUsdAmount = ConstU64<20_000_000>;
+    type FirstPurchaseUsdAmount = ConstU64<10_000_000>;
+    type AmountValidationTolerance = ConstU16<100>;
+    type MaxFirstPurchaseOrdersPerMaker = ConstU32<5>;
+    type MinDeposit = ConstU128<{ DUST / 10 }>;
+    type DepositRateLow = ConstU16<300>;
+    type DepositRateMedium = ConstU16<500>;
+    type DepositRateHigh = ConstU16<1000>;
+    type CreditScoreExempt = ConstU16<70>;
+    type MinOrdersForExempt = ConstU32<5>;
+    type CancelPenaltyRate = ConstU16<3000>;
+    type MinMakerDepositUsd = ConstU64<500_000_000>;
+    type DisputeResponseTimeout = ConstU64<86400>;
+    type DisputeArbitrationTimeout = ConstU64<172800>;
+    type ArbitratorOrigin = EnsureRoot<u64>;
+    type WeightInfo = ();
+    type CidLockManager = MockCidLockManager;
+    type KeyDirectory = MockKeyDirectory;
+    type PrivateEvidence = MockPrivateEvidence;
+    type ArbitrationCommittee = MockArbitrationCommittee;
+    type MaxPaymentDetailsLen = ConstU32<1024>;
+    type Timeline = MockTimeline;
+}
+
+/// 构建测试外部状态
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![
+            (SELLER, 1_000 * DUST),
+            (OUTSIDER, 1_000 * DUST),
+            (BUYER, 1_000 * DUST),
+            (MAKER, 1_000 * DUST),
+            (MAKER_2, 1_000 * DUST),
+            (MAKER_3, 1_000 * DUST),
+        ],
+        dev_accounts: None,
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        Timestamp::set_timestamp(1_000_000);
+        EscrowLocked::set(BTreeMap::new());
+        PrivateContents::set(Vec::new());
+        MakerQuotes::set(BTreeMap::new());
+        MakerCreditScores::set(BTreeMap::new());
+        Timelines::set(BTreeMap::new());
+    });
+    ext
+}
@@ -0,0 +1,586 @@
+//! OTC 订单 Pallet 单元测试（卖单流程、订单加密资料与凭证、拆单路由、案件时间线）
+
+use crate::{
+    mock::*, DepositStatus, DisputeStatus, Disputes, Error, Event, HasFirstPurchased, NextSplitOrderId,
+    OrderParent, OrderState, Orders, SplitOrderStatus, SplitOrders,
+};
+use frame_support::{assert_noop, assert_ok, traits::Hooks, weights::Weight};
+use pallet_arbitration::pallet::{domains::OTC_ORDER, TimelineEventKind};
+use pallet_trading_common::{Cid, OrderSide};
+use sp_core::H256;
+
+const SELL_AMOUNT: u128 = 100 * DUST;
+
+fn evidence(tag: &[u8]) -> Cid {
+    tag.to_vec().try_into().unwrap()
+}
+
+/// 创建卖单并由卖家提交收款资料，返回订单ID
+fn create_sell_order() -> u64 {
+    let order_id = Otc::next_order_id();
+    assert_ok!(Otc::create_sell_order(
+        RuntimeOrigin::signed(SELLER),
+        MAKER_ID,
+        SELL_AMOUNT,
+        H256::repeat_byte(1),
+        H256::repeat_byte(2),
+    ));
+    assert_ok!(Otc::submit_payment_details(
+        RuntimeOrigin::signed(SELLER),
+        order_id,
+        vec![7u8; 64],
+    ));
+    order_id
+}
+
+/// 创建卖单并由做市商标记已付款
+fn sell_order_with_payout_sent() -> u64 {
+    let order_id = create_sell_order();
+    assert_ok!(Otc::mark_payout_sent(RuntimeOrigin::signed(MAKER), order_id, None));
+    order_id
+}
+
+#[test]
+fn create_sell_order_locks_seller_dust() {
+    new_test_ext().execute_with(|| {
+        let order_id = create_sell_order();
+
+        let order = Orders::<Test>::get(order_id).unwrap();
+        assert_eq!(order.side, OrderSide::Sell);
+        assert_eq!(order.state, OrderState::Created);
+        assert_eq!(order.taker, SELLER);
+        assert_eq!(order.maker, MAKER);
+        assert_eq!(Balances::free_balance(SELLER), 900 * DUST);
+        assert_eq!(Balances::free_balance(ESCROW), SELL_AMOUNT);
+    });
+}
+
+#[test]
+fn mark_payout_sent_requires_payment_details_and_maker() {
+    new_test_ext().execute_with(|| {
+        let order_id = Otc::next_order_id();
+        assert_ok!(Otc::create_sell_order(
+            RuntimeOrigin::signed(SELLER),
+            MAKER_ID,
+            SELL_AMOUNT,
+            H256::repeat_byte(1),
+            H256::repeat_byte(2),
+        ));
+        assert_noop!(
+            Otc::mark_payout_sent(RuntimeOrigin::signed(MAKER), order_id, None),
+            Error::<Test>::PaymentDetailsNotProvided
+        );
+
+        assert_ok!(Otc::submit_payment_details(
+            RuntimeOrigin::signed(SELLER),
+            order_id,
+            vec![7u8; 64],
+        ));
+        assert_noop!(
+            Otc::mark_payout_sent(RuntimeOrigin::signed(SELLER), order_id, None),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(Otc::mark_payout_sent(RuntimeOrigin::signed(MAKER), order_id, None));
+        assert_eq!(Orders::<Test>::get(order_id).unwrap().state, OrderState::PaidOrCommitted);
+    });
+}
+
+#[test]
+fn confirm_payout_releases_dust_to_maker() {
+    new_test_ext().execute_with(|| {
+        let order_id = sell_order_with_payout_sent();
+
+        assert_noop!(
+            Otc::confirm_payout(RuntimeOrigin::signed(MAKER), order_id),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(Otc::confirm_payout(RuntimeOrigin::signed(SELLER), order_id));
+
+        assert_eq!(Orders::<Test>::get(order_id).unwrap().state, OrderState::Released);
+        assert_eq!(Balances::free_balance(MAKER), 1_000 * DUST + SELL_AMOUNT);
+        assert_eq!(Balances::free_balance(ESCROW), 0);
+    });
+}
+
+#[test]
+fn maker_can_dispute_unconfirmed_sell_order() {
+    new_test_ext().execute_with(|| {
+        let order_id = sell_order_with_payout_sent();
+
+        // 卖家迟迟不确认收款，做市商（付款方）发起争议
+        assert_ok!(Otc::do_initiate_dispute(&MAKER, order_id, evidence(b"maker-payout")));
+
+        let dispute = Disputes::<Test>::get(order_id).unwrap();
+        assert_eq!(dispute.initiator, MAKER);
+        assert_eq!(dispute.respondent, SELLER);
+        assert_eq!(dispute.status, DisputeStatus::WaitingMakerResponse);
+        assert_eq!(Orders::<Test>::get(order_id).unwrap().state, OrderState::Disputed);
+
+        // 被告方为卖家：做市商不能自行响应
+        assert_noop!(
+            Otc::do_respond_dispute(&MAKER, order_id, evidence(b"maker-again")),
+            Error::<Test>::NotDisputeRespondent
+        );
+        assert_ok!(Otc::do_respond_dispute(&SELLER, order_id, evidence(b"seller-reply")));
+        assert_eq!(
+            Disputes::<Test>::get(order_id).unwrap().status,
+            DisputeStatus::WaitingArbitration
+        );
+
+        // 做市商胜诉：托管 DUST 释放给做市商
+        assert_ok!(Otc::do_resolve_dispute(order_id, false));
+        assert_eq!(Orders::<Test>::get(order_id).unwrap().state, OrderState::Released);
+        assert_eq!(Balances::free_balance(MAKER), 1_000 * DUST + SELL_AMOUNT);
+        assert_eq!(Balances::free_balance(SELLER), 900 * DUST);
+    });
+}
+
+#[test]
+fn seller_wins_sell_order_dispute_gets_dust_back() {
+    new_test_ext().execute_with(|| {
+        let order_id = sell_order_with_payout_sent();
+
+        // 卖家未收到款项，卖家发起争议，被告方为做市商
+        assert_ok!(Otc::do_initiate_dispute(&SELLER, order_id, evidence(b"seller-unpaid")));
+        let dispute = Disputes::<Test>::get(order_id).unwrap();
+        assert_eq!(dispute.initiator, SELLER);
+        assert_eq!(dispute.respondent, MAKER);
+
+        assert_ok!(Otc::do_respond_dispute(&MAKER, order_id, evidence(b"maker-reply")));
+        assert_ok!(Otc::do_resolve_dispute(order_id, true));
+
+        assert_eq!(Orders::<Test>::get(order_id).unwrap().state, OrderState::Refunded);
+        assert_eq!(Balances::free_balance(SELLER), 1_000 * DUST);
+        assert_eq!(Balances::free_balance(MAKER), 1_000 * DUST);
+    });
+}
+
+#[test]
+fn outsider_cannot_dispute_sell_order() {
+    new_test_ext().execute_with(|| {
+        let order_id = sell_order_with_payout_sent();
+
+        assert_noop!(
+            Otc::do_initiate_dispute(&OUTSIDER, order_id, evidence(b"outsider")),
+            Error::<Test>::NotOrderBuyer
+        );
+    });
+}
+
+#[test]
+fn sell_order_cannot_be_disputed_before_payout() {
+    new_test_ext().execute_with(|| {
+        let order_id = create_sell_order();
+
+        assert_noop!(
+            Otc::do_initiate_dispute(&MAKER, order_id, evidence(b"too-early")),
+            Error::<Test>::InvalidOrderStatus
+        );
+    });
+}
+
+#[test]
+fn expired_sell_order_refunds_seller() {
+    new_test_ext().execute_with(|| {
+        let order_id = create_sell_order();
+
+        // 超过订单超时时间（OrderTimeout = 3600 秒）
+        Timestamp::set_timestamp(1_000_000 + 3_601_000);
+        Otc::process_expired_orders();
+
+        assert_eq!(Orders::<Test>::get(order_id).unwrap().state, OrderState::Expired);
+        assert_eq!(Balances::free_balance(SELLER), 1_000 * DUST);
+        assert_eq!(Balances::free_balance(ESCROW), 0);
+    });
+}
+
+// ===== 订单加密资料与付款凭证 =====
+
+const BUY_AMOUNT: u128 = 100 * DUST;
+/// 非首购买家押金：信用分 30 → DepositRateMedium 5%
+const BUYER_DEPOSIT: u128 = 5 * DUST;
+
+/// 创建买单（买家已完成首购，需缴押金），返回订单ID
+fn create_buy_order() -> u64 {
+    HasFirstPurchased::<Test>::insert(BUYER, true);
+    let order_id = Otc::next_order_id();
+    assert_ok!(Otc::create_order(
+        RuntimeOrigin::signed(BUYER),
+        MAKER_ID,
+        BUY_AMOUNT,
+        H256::repeat_byte(1),
+        H256::repeat_byte(2),
+    ));
+    order_id
+}
+
+#[test]
+fn submit_payment_details_stores_ciphertext_for_payer_key() {
+    new_test_ext().execute_with(|| {
+        let order_id = create_buy_order();
+
+        // 买单收款方为做市商
+        assert_noop!(
+            Otc::submit_payment_details(RuntimeOrigin::signed(BUYER), order_id, vec![7u8; 64]),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            Otc::submit_payment_details(RuntimeOrigin::signed(MAKER), order_id, vec![]),
+            Error::<Test>::InvalidPaymentDetails
+        );
+        assert_noop!(
+            Otc::submit_payment_details(RuntimeOrigin::signed(MAKER), order_id, vec![7u8; 1025]),
+            Error::<Test>::InvalidPaymentDetails
+        );
+
+        assert_ok!(Otc::submit_payment_details(RuntimeOrigin::signed(MAKER), order_id, vec![7u8; 64]));
+        let details = Otc::order_payment_details(order_id).unwrap();
+        assert_eq!(details.submitter, MAKER);
+        assert_eq!(details.recipient_key, [BUYER as u8; 32]);
+        assert_eq!(details.ciphertext.to_vec(), vec![7u8; 64]);
+
+        // 重复提交覆盖旧资料
+        assert_ok!(Otc::submit_payment_details(RuntimeOrigin::signed(MAKER), order_id, vec![8u8; 32]));
+        assert_eq!(Otc::order_payment_details(order_id).unwrap().ciphertext.to_vec(), vec![8u8; 32]);
+        System::assert_last_event(
+            Event::PaymentDetailsSubmitted {
+                order_id,
+                submitter: MAKER,
+                recipient_key: [BUYER as u8; 32],
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn submit_payment_details_only_while_created() {
+    new_test_ext().execute_with(|| {
+        let order_id = sell_order_with_payout_sent();
+
+        assert_noop!(
+            Otc::submit_payment_details(RuntimeOrigin::signed(SELLER), order_id, vec![7u8; 64]),
+            Error::<Test>::InvalidOrderStatus
+        );
+    });
+}
+
+#[test]
+fn submit_payment_proof_requires_keys_for_payee_and_committee() {
+    new_test_ext().execute_with(|| {
+        let order_id = create_buy_order();
+
+        // 未登记的内容
+        assert_noop!(
+            Otc::submit_payment_proof(RuntimeOrigin::signed(BUYER), order_id, 1),
+            Error::<Test>::InvalidPaymentProof
+        );
+
+        // 只给做市商包装了密钥，仲裁委员无法解密
+        share_private_content(1, order_id, BUYER, vec![MAKER]);
+        assert_noop!(
+            Otc::submit_payment_proof(RuntimeOrigin::signed(BUYER), order_id, 1),
+            Error::<Test>::InvalidPaymentProof
+        );
+
+        // 他人创建的内容不能作为买家凭证
+        share_private_content(2, order_id, OUTSIDER, vec![MAKER, ARBITRATOR]);
+        assert_noop!(
+            Otc::submit_payment_proof(RuntimeOrigin::signed(BUYER), order_id, 2),
+            Error::<Test>::InvalidPaymentProof
+        );
+
+        // 属于其他订单的内容
+        share_private_content(3, order_id + 1, BUYER, vec![MAKER, ARBITRATOR]);
+        assert_noop!(
+            Otc::submit_payment_proof(RuntimeOrigin::signed(BUYER), order_id, 3),
+            Error::<Test>::InvalidPaymentProof
+        );
+    });
+}
+
+#[test]
+fn submit_payment_proof_links_content_once() {
+    new_test_ext().execute_with(|| {
+        let order_id = create_buy_order();
+        share_private_content(1, order_id, BUYER, vec![MAKER, ARBITRATOR]);
+
+        // 买单付款方为买家
+        assert_noop!(
+            Otc::submit_payment_proof(RuntimeOrigin::signed(MAKER), order_id, 1),
+            Error::<Test>::NotAuthorized
+        );
+
+        assert_ok!(Otc::submit_payment_proof(RuntimeOrigin::signed(BUYER), order_id, 1));
+        assert_eq!(Otc::order_payment_proofs(order_id).to_vec(), vec![1]);
+        System::assert_last_event(
+            Event::PaymentProofLinked { order_id, payer: BUYER, content_id: 1 }.into(),
+        );
+
+        assert_noop!(
+            Otc::submit_payment_proof(RuntimeOrigin::signed(BUYER), order_id, 1),
+            Error::<Test>::PaymentProofAlreadyLinked
+        );
+    });
+}
+
+#[test]
+fn expired_buy_order_without_payment_details_refunds_buyer_deposit() {
+    new_test_ext().execute_with(|| {
+        let order_id = create_buy_order();
+        assert_eq!(Orders::<Test>::get(order_id).unwrap().buyer_deposit, BUYER_DEPOSIT);
+        assert_eq!(Balances::free_balance(BUYER), 1_000 * DUST - BUYER_DEPOSIT);
+
+        // 做市商未提交收款资料，买家无从付款
+        Timestamp::set_timestamp(1_000_000 + 3_601_000);
+        Otc::process_expired_orders();
+
+        let order = Orders::<Test>::get(order_id).unwrap();
+        assert_eq!(order.state, OrderState::Expired);
+        assert_eq!(order.deposit_status, DepositStatus::Released);
+        assert_eq!(Balances::free_balance(BUYER), 1_000 * DUST);
+        assert_eq!(Balances::free_balance(MAKER), 1_000 * DUST);
+        System::assert_has_event(
+            Event::BuyerDepositReleased {
+                order_id,
+                buyer: BUYER,
+                refund_amount: BUYER_DEPOSIT,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn expired_buy_order_with_payment_details_forfeits_buyer_deposit() {
+    new_test_ext().execute_with(|| {
+        let order_id = create_buy_order();
+        assert_ok!(Otc::submit_payment_details(RuntimeOrigin::signed(MAKER), order_id, vec![7u8; 64]));
+
+        Timestamp::set_timestamp(1_000_000 + 3_601_000);
+        Otc::process_expired_orders();
+
+        let order = Orders::<Test>::get(order_id).unwrap();
+        assert_eq!(order.state, OrderState::Expired);
+        assert_eq!(order.deposit_status, DepositStatus::Forfeited);
+        assert_eq!(Balances::free_balance(BUYER), 1_000 * DUST - BUYER_DEPOSIT);
+        assert_eq!(Balances::free_balance(MAKER), 1_000 * DUST + BUYER_DEPOSIT);
+    });
+}
+
+// ===== 拆单路由 =====
+
+const USD: u64 = 1_000_000;
+
+/// 买家（已完成首购）创建拆单，返回拆单ID
+fn create_split_order(usd_amount: u64, candidates: Vec<u64>) -> u64 {
+    HasFirstPurchased::<Test>::insert(BUYER, true);
+    let split_id = NextSplitOrderId::<Test>::get();
+    assert_ok!(Otc::create_split_order(
+        RuntimeOrigin::signed(BUYER),
+        usd_amount,
+        candidates,
+        false,
+        H256::repeat_byte(1),
+        H256::repeat_byte(2),
+    ));
+    split_id
+}
+
+/// 做市商提交收款资料、买家标记已付款
+fn pay_child_order(order_id: u64, maker: u64) {
+    assert_ok!(Otc::submit_payment_details(RuntimeOrigin::signed(maker), order_id, vec![7u8; 64]));
+    assert_ok!(Otc::mark_paid(RuntimeOrigin::signed(BUYER), order_id, None));
+}
+
+#[test]
+fn split_candidates_ranked_by_premium_then_credit_score() {
+    new_test_ext().execute_with(|| {
+        set_maker_quote(MAKER_ID, 100, 1_000 * DUST);
+        set_maker_quote(MAKER_2_ID, 0, 1_000 * DUST);
+        set_maker_quote(MAKER_3_ID, 0, 1_000 * DUST);
+        MakerCreditScores::mutate(|scores| {
+            scores.insert(MAKER_2_ID, 50);
+            scores.insert(MAKER_3_ID, 80);
+        });
+
+        // 溢价低者优先，同溢价按信用分降序；重复与不存在的做市商被剔除
+        assert_eq!(
+            Otc::rank_split_candidates(&[MAKER_ID, MAKER_3_ID, MAKER_2_ID, MAKER_2_ID, 99]),
+            vec![MAKER_3_ID, MAKER_2_ID, MAKER_ID]
+        );
+    });
+}
+
+#[test]
+fn split_order_fills_across_makers() {
+    new_test_ext().execute_with(|| {
+        // 单笔上限 200 USD，300 USD 拆给两个做市商
+        let split_id = create_split_order(300 * USD, vec![MAKER_ID, MAKER_2_ID]);
+
+        let split = SplitOrders::<Test>::get(split_id).unwrap();
+        assert_eq!(split.buyer, BUYER);
+        assert_eq!(split.filled_usd, 300 * USD);
+        assert_eq!(split.children.to_vec(), vec![0, 1]);
+
+        let first = Orders::<Test>::get(0).unwrap();
+        assert_eq!((first.maker, first.qty), (MAKER, 200 * DUST));
+        let second = Orders::<Test>::get(1).unwrap();
+        assert_eq!((second.maker, second.qty), (MAKER_2, 100 * DUST));
+        assert_eq!(OrderParent::<Test>::get(0), Some(split_id));
+        assert_eq!(OrderParent::<Test>::get(1), Some(split_id));
+
+        let view = Otc::get_split_order_status(split_id).unwrap();
+        assert_eq!(view.status, SplitOrderStatus::InProgress);
+        assert_eq!(view.children, vec![(0, MAKER_ID, 0), (1, MAKER_2_ID, 0)]);
+    });
+}
+
+#[test]
+fn split_order_partial_fill_requires_allow_partial() {
+    new_test_ext().execute_with(|| {
+        HasFirstPurchased::<Test>::insert(BUYER, true);
+        // 报价限额合计 250 DUST，不足 400 USD
+        set_maker_quote(MAKER_ID, 0, 150 * DUST);
+        set_maker_quote(MAKER_2_ID, 0, 100 * DUST);
+
+        // 外部交易失败时整体回滚（模拟交易存储层）
+        assert_noop!(
+            frame_support::storage::with_storage_layer(|| Otc::create_split_order(
+                RuntimeOrigin::signed(BUYER),
+                400 * USD,
+                vec![MAKER_ID, MAKER_2_ID],
+                false,
+                H256::repeat_byte(1),
+                H256::repeat_byte(2),
+            )),
+            Error::<Test>::SplitNotFilled
+        );
+
+        assert_ok!(Otc::create_split_order(
+            RuntimeOrigin::signed(BUYER),
+            400 * USD,
+            vec![MAKER_ID, MAKER_2_ID],
+            true,
+            H256::repeat_byte(1),
+            H256::repeat_byte(2),
+        ));
+        let split = SplitOrders::<Test>::get(0).unwrap();
+        assert_eq!(split.requested_usd, 400 * USD);
+        assert_eq!(split.filled_usd, 250 * USD);
+        assert_eq!(Orders::<Test>::get(0).unwrap().qty, 150 * DUST);
+        assert_eq!(Orders::<Test>::get(1).unwrap().qty, 100 * DUST);
+        System::assert_last_event(
+            Event::SplitOrderCreated {
+                split_id: 0,
+                buyer: BUYER,
+                requested_usd: 400 * USD,
+                filled_usd: 250 * USD,
+                children: vec![0, 1],
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn split_child_orders_disputed_independently() {
+    new_test_ext().execute_with(|| {
+        let split_id = create_split_order(300 * USD, vec![MAKER_ID, MAKER_2_ID]);
+        pay_child_order(0, MAKER);
+        pay_child_order(1, MAKER_2);
+
+        // 只对第一个子订单发起争议，第二个子订单正常放币
+        assert_ok!(Otc::do_initiate_dispute(&BUYER, 0, evidence(b"buyer-paid")));
+        assert_ok!(Otc::release_dust(RuntimeOrigin::signed(MAKER_2), 1));
+        assert_eq!(Orders::<Test>::get(0).unwrap().state, OrderState::Disputed);
+        assert_eq!(Orders::<Test>::get(1).unwrap().state, OrderState::Released);
+        assert!(Disputes::<Test>::get(1).is_none());
+        assert_eq!(Otc::get_split_order_status(split_id).unwrap().status, SplitOrderStatus::Disputed);
+
+        // 做市商胜诉：仅该子订单取消，已放币的子订单不受影响
+        assert_ok!(Otc::do_respond_dispute(&MAKER, 0, evidence(b"maker-unpaid")));
+        assert_ok!(Otc::do_resolve_dispute(0, false));
+        assert_eq!(Orders::<Test>::get(0).unwrap().state, OrderState::Canceled);
+        assert_eq!(Orders::<Test>::get(1).unwrap().state, OrderState::Released);
+        assert_eq!(Balances::free_balance(MAKER), 1_000 * DUST + 10 * DUST);
+        assert_eq!(Balances::free_balance(MAKER_2), 900 * DUST);
+        assert_eq!(Balances::free_balance(BUYER), 1_000 * DUST - 10 * DUST + 100 * DUST);
+        assert_eq!(
+            Otc::get_split_order_status(split_id).unwrap().status,
+            SplitOrderStatus::PartiallyCompleted
+        );
+    });
+}
+
+#[test]
+fn archiving_split_children_removes_split_links() {
+    new_test_ext().execute_with(|| {
+        let split_id = create_split_order(300 * USD, vec![MAKER_ID, MAKER_2_ID]);
+
+        Timestamp::set_timestamp(1_000_000 + 3_601_000);
+        Otc::process_expired_orders();
+        assert_eq!(Otc::get_split_order_status(split_id).unwrap().status, SplitOrderStatus::Failed);
+
+        // 结束超过 30 天后空闲归档
+        Timestamp::set_timestamp(1_000_000 + (3_601 + 31 * 24 * 3_600) * 1_000);
+        Otc::on_idle(1, Weight::MAX);
+
+        assert!(Orders::<Test>::get(0).is_none());
+        assert!(Orders::<Test>::get(1).is_none());
+        assert!(OrderParent::<Test>::get(0).is_none());
+        assert!(OrderParent::<Test>::get(1).is_none());
+        assert!(SplitOrders::<Test>::get(split_id).is_none());
+        assert!(Otc::get_split_order_status(split_id).is_none());
+    });
+}
+
+// ===== 案件时间线 =====
+
+/// 订单时间线条目
+fn order_timeline(order_id: u64) -> Vec<TimelineEventKind<H256>> {
+    Timelines::get().get(&(OTC_ORDER, order_id)).cloned().unwrap_or_default()
+}
+
+#[test]
+fn dispute_timeline_contains_pre_dispute_history() {
+    new_test_ext().execute_with(|| {
+        let order_id = create_buy_order();
+        assert_ok!(Otc::submit_payment_details(RuntimeOrigin::signed(MAKER), order_id, vec![7u8; 64]));
+        share_private_content(1, order_id, BUYER, vec![MAKER, ARBITRATOR]);
+        assert_ok!(Otc::submit_payment_proof(RuntimeOrigin::signed(BUYER), order_id, 1));
+        assert_ok!(Otc::mark_paid(RuntimeOrigin::signed(BUYER), order_id, None));
+
+        // 争议开启前的流转已记录
+        assert_eq!(
+            order_timeline(order_id),
+            vec![
+                TimelineEventKind::Created,
+                TimelineEventKind::PaymentProof(1),
+                TimelineEventKind::StateChanged { from: 0, to: 1 },
+            ]
+        );
+
+        assert_ok!(Otc::do_initiate_dispute(&BUYER, order_id, evidence(b"buyer-paid")));
+        assert_ok!(Otc::do_respond_dispute(&MAKER, order_id, evidence(b"maker-unpaid")));
+        assert_ok!(Otc::do_resolve_dispute(order_id, true));
+
+        // 裁决后时间线保留完整历史
+        let timeline = order_timeline(order_id);
+        assert_eq!(timeline[..3], [
+            TimelineEventKind::Created,
+            TimelineEventKind::PaymentProof(1),
+            TimelineEventKind::StateChanged { from: 0, to: 1 },
+        ]);
+        let opened = timeline.iter().position(|kind| *kind == TimelineEventKind::DisputeOpened).unwrap();
+        assert!(opened > 2);
+        assert!(timeline.contains(&TimelineEventKind::StateChanged { from: 1, to: 5 }));
+        assert_eq!(timeline.last(), Some(&TimelineEventKind::StateChanged { from: 5, to: 2 }));
+
+        // 订单归档时清理时间线
+        Timestamp::set_timestamp(1_000_000 + 31 * 24 * 3_600 * 1_000);
+        Otc::on_idle(1, Weight::MAX);
+        assert!(Orders::<Test>::get(order_id).is_none());
+        assert!(order_timeline(order_id).is_empty());
+    });
+}
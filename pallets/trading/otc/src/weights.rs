@@ -18,6 +18,19 @@ pub trait WeightInfo {
     fn update_min_judgment_level() -> Weight;
     fn exempt_account_from_kyc() -> Weight;
     fn remove_kyc_exemption() -> Weight;
+
+    // === 卖单相关函数权重 ===
+    fn create_sell_order() -> Weight;
+    fn mark_payout_sent() -> Weight;
+    fn confirm_payout() -> Weight;
+
+    // === 订单加密资料函数权重 ===
+    fn submit_payment_details() -> Weight;
+    fn submit_payment_proof() -> Weight;
+
+    // === 拆单函数权重 ===
+    /// 按候选做市商数量 `c` 计（每个候选读取报价与信用分，并可能尝试创建一笔子订单）
+    fn create_split_order(c: u32) -> Weight;
 }
 
 /// 函数级详细中文注释：默认权重实现（临时占位）
@@ -62,5 +75,33 @@ impl WeightInfo for () {
     fn remove_kyc_exemption() -> Weight {
         Weight::from_parts(20_000, 0)
     }
+
+    // === 卖单相关函数权重实现 ===
+    fn create_sell_order() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn mark_payout_sent() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    fn confirm_payout() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+
+    // === 订单加密资料函数权重实现 ===
+    fn submit_payment_details() -> Weight {
+        Weight::from_parts(15_000, 0)
+    }
+
+    fn submit_payment_proof() -> Weight {
+        Weight::from_parts(25_000, 0)
+    }
+
+    // === 拆单函数权重实现 ===
+    fn create_split_order(c: u32) -> Weight {
+        Weight::from_parts(10_000, 0)
+            .saturating_add(Weight::from_parts(12_000, 0).saturating_mul(c as u64))
+    }
 }
 
@@ -58,6 +58,7 @@ pub mod pallet {
         MakerValidationError,
     };
     use pallet_storage_lifecycle::{amount_to_tier, block_to_year_month};
+    use pallet_arbitration::pallet::{domains::SWAP, DisputeTimeline, TimelineEventKind};
     // MakerApplicationInfo 通过 MakerInterface::get_maker_application 返回
     
     /// 函数级详细中文注释：Balance 类型别名
@@ -281,6 +282,9 @@ pub mod pallet {
         /// 注意：当前 SWAP 模块的 evidence_cid 字段未被使用
         /// 待添加 submit_evidence 函数后启用 PIN 联动机制
         type CidLockManager: pallet_storage_service::CidLockManager<Self::Hash, BlockNumberFor<Self>>;
+
+        /// 🆕 案件时间线（兑换状态流转供仲裁委员会查阅）
+        type Timeline: DisputeTimeline<Self::AccountId, Self::Hash>;
     }
     
     // ===== 存储 =====
@@ -792,6 +796,7 @@ pub mod pallet {
             })?;
             
             // 13. 发出事件
+            T::Timeline::record(SWAP, swap_id, TimelineEventKind::Created, Some(user.clone()));
             Self::deposit_event(Event::MakerSwapCreated {
                 swap_id,
                 user: user.clone(),
@@ -862,6 +867,7 @@ pub mod pallet {
             record.trc20_tx_hash = Some(tx_hash.clone());
             record.status = SwapStatus::AwaitingVerification;
             MakerSwaps::<T>::insert(swap_id, record.clone());
+            Self::note_status_change(swap_id, &SwapStatus::Pending, &record.status, Some(maker.clone()));
             
             // 8. 创建验证请求
             let current_block = frame_system::Pallet::<T>::block_number();
@@ -926,6 +932,7 @@ pub mod pallet {
                 record.status = SwapStatus::Completed;
                 record.completed_at = Some(current_block);
                 MakerSwaps::<T>::insert(swap_id, record.clone());
+                Self::note_status_change(swap_id, &SwapStatus::AwaitingVerification, &record.status, None);
                 
                 // 记录信用分（成功完成订单）
                 let block_duration = current_block.saturating_sub(record.created_at);
@@ -950,6 +957,12 @@ pub mod pallet {
                 // 验证失败：进入仲裁流程
                 record.status = SwapStatus::VerificationFailed;
                 MakerSwaps::<T>::insert(swap_id, record);
+                Self::note_status_change(
+                    swap_id,
+                    &SwapStatus::AwaitingVerification,
+                    &SwapStatus::VerificationFailed,
+                    None,
+                );
                 
                 let reason_bounded: BoundedVec<u8, ConstU32<128>> = reason
                     .unwrap_or_else(|| b"Unknown verification failure".to_vec())
@@ -1021,6 +1034,7 @@ pub mod pallet {
             
             record.completed_at = Some(current_block);
             MakerSwaps::<T>::insert(swap_id, record.clone());
+            Self::note_status_change(swap_id, &SwapStatus::AwaitingVerification, &record.status, None);
             
             // 9. 发出事件
             Self::deposit_event(Event::VerificationTimeout { swap_id });
@@ -1056,8 +1070,11 @@ pub mod pallet {
             );
             
             // 4. 更新状态
+            let old_status = record.status.clone();
             record.status = SwapStatus::UserReported;
             MakerSwaps::<T>::insert(swap_id, record);
+            Self::note_status_change(swap_id, &old_status, &SwapStatus::UserReported, Some(user.clone()));
+            T::Timeline::record(SWAP, swap_id, TimelineEventKind::DisputeOpened, Some(user.clone()));
             
             // 5. 发出事件
             Self::deposit_event(Event::SwapReported {
@@ -1245,6 +1262,7 @@ pub mod pallet {
             );
             
             // 更新记录
+            Self::note_status_change(swap_id, &SwapStatus::UserReported, &record.status, None);
             MakerSwaps::<T>::insert(swap_id, record);
             
             Ok(())
@@ -1340,6 +1358,7 @@ pub mod pallet {
             // 6. 更新状态
             record.status = SwapStatus::Refunded;
             MakerSwaps::<T>::insert(swap_id, record.clone());
+            Self::note_status_change(swap_id, &SwapStatus::Pending, &SwapStatus::Refunded, None);
             
             // 7. 发送事件
             Self::deposit_event(Event::SwapTimeout {
@@ -1426,6 +1445,7 @@ pub mod pallet {
                     // 保存归档并删除原记录
                     ArchivedSwapsL1::<T>::insert(cursor, archived);
                     MakerSwaps::<T>::remove(cursor);
+                    T::Timeline::clear(SWAP, cursor);
 
                     // 从用户兑换列表中移除
                     UserSwaps::<T>::mutate(&record.user, |ids| {
@@ -1511,6 +1531,24 @@ pub mod pallet {
             }
         }
 
+        /// 🆕 记录兑换状态变化到案件时间线（仲裁域 SWAP）
+        fn note_status_change(
+            swap_id: u64,
+            from: &SwapStatus,
+            to: &SwapStatus,
+            actor: Option<T::AccountId>,
+        ) {
+            T::Timeline::record(
+                SWAP,
+                swap_id,
+                TimelineEventKind::StateChanged {
+                    from: Self::swap_status_to_u8(from),
+                    to: Self::swap_status_to_u8(to),
+                },
+                actor,
+            );
+        }
+
         /// 🆕 存储膨胀防护：清理过期的 TRON 交易哈希
         /// 
         /// TTL 策略：30 天后自动删除（防重放攻击窗口）
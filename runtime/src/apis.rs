@@ -43,7 +43,8 @@ use sp_version::RuntimeVersion;
 
 // Local module imports
 use super::{
-	AccountId, Aura, Balance, Bazi, Block, BlockNumber, Executive, Grandpa, InherentDataExt, Livestream, Nonce, Runtime,
+	AccountId, Arbitration, Aura, Balance, Bazi, Block, BlockNumber, ChatCore, Executive, Grandpa, Hash, InherentDataExt, Livestream,
+	Nonce, Reputation, Runtime,
 	RuntimeCall, RuntimeGenesisConfig, SessionKeys, StorageService, System, TransactionPayment, TeePrivacy, VERSION,
 };
 
@@ -474,6 +475,75 @@ impl_runtime_apis! {
 		}
 	}
 
+	// ============================================================================
+	// Chat Runtime API（私聊 + 群聊统一收件箱）
+	// ============================================================================
+
+	impl pallet_chat_core::runtime_api::ChatApi<Block, AccountId, Hash, BlockNumber> for Runtime {
+		fn get_unread_summary(user: AccountId) -> pallet_chat_core::runtime_api::UnreadSummary<Hash> {
+			ChatCore::get_unread_summary(user)
+		}
+
+		fn get_total_unread(user: AccountId) -> u32 {
+			ChatCore::get_total_unread(user)
+		}
+
+		fn get_group_unread(group_id: u64, user: AccountId) -> u32 {
+			ChatCore::get_group_unread(group_id, user)
+		}
+
+		fn get_inbox(
+			user: AccountId,
+			offset: u32,
+			limit: u32,
+		) -> Vec<pallet_chat_core::runtime_api::InboxEntry<Hash, BlockNumber>> {
+			ChatCore::get_inbox(user, offset, limit)
+		}
+	}
+
+	// ============================================================================
+	// Arbitration Runtime API（案件时间线）
+	// ============================================================================
+
+	impl pallet_arbitration::runtime_api::ArbitrationApi<Block, AccountId, BlockNumber, Hash> for Runtime {
+		fn get_case_timeline(
+			domain: [u8; 8],
+			id: u64,
+		) -> Option<pallet_arbitration::runtime_api::CaseTimeline<AccountId, BlockNumber, Hash>> {
+			let mut timeline = Arbitration::case_timeline(domain, id)?;
+			// 双方存在私聊会话时附带会话ID，便于委员会调阅沟通记录
+			if let Some((ref a, ref b)) = timeline.parties {
+				let session_id = ChatCore::get_session_id(a, b);
+				if pallet_chat_core::Sessions::<Runtime>::contains_key(session_id) {
+					timeline.chat_session = Some(session_id);
+				}
+			}
+			Some(timeline)
+		}
+	}
+
+	// ============================================================================
+	// Reputation Runtime API（跨域信誉）
+	// ============================================================================
+
+	impl pallet_reputation::runtime_api::ReputationApi<Block, AccountId, BlockNumber> for Runtime {
+		fn get_reputation(who: AccountId) -> pallet_reputation::runtime_api::ReputationSummary<BlockNumber> {
+			Reputation::reputation_summary(&who)
+		}
+
+		fn get_credential(id: u64) -> Option<pallet_reputation::types::CreditAttestation<AccountId, BlockNumber>> {
+			pallet_reputation::Attestations::<Runtime>::get(id)
+		}
+
+		fn get_account_credentials(who: AccountId) -> Vec<u64> {
+			pallet_reputation::AccountAttestations::<Runtime>::get(&who).into_inner()
+		}
+
+		fn get_credential_json(id: u64) -> Option<alloc::string::String> {
+			Reputation::credential_json(id)
+		}
+	}
+
 	// ============================================================================
 	// Livestream Runtime API
 	// ============================================================================
@@ -518,6 +588,14 @@ impl_runtime_apis! {
 		fn get_co_hosts(room_id: u64) -> Vec<AccountId> {
 			Livestream::get_co_host_list(room_id)
 		}
+
+		fn get_room_invites(room_id: u64) -> Vec<pallet_livestream::runtime_api::InviteInfo<AccountId>> {
+			Livestream::get_room_invites(room_id)
+		}
+
+		fn can_view(room_id: u64, user: AccountId) -> bool {
+			Livestream::can_view(room_id, &user)
+		}
 	}
 
 	// ============================================================================
@@ -564,8 +642,7 @@ impl_runtime_apis! {
 		}
 
 		fn get_user_encryption_key(account: AccountId) -> Option<[u8; 32]> {
-			use pallet_divination_privacy::UserEncryptionKeys;
-			UserEncryptionKeys::<Runtime>::get(&account).map(|info| info.public_key)
+			Privacy::encryption_key_of(&account)
 		}
 
 		fn get_service_provider(account: AccountId) -> Option<alloc::string::String> {
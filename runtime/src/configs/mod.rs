@@ -41,10 +41,11 @@ use sp_version::RuntimeVersion;
 
 // Local module imports
 use super::{
-	AccountId, Aura, Balance, Balances, Block, BlockNumber, Hash, Nonce, PalletInfo, Runtime,
+	AccountId, Aura, Balance, Balances, Block, BlockNumber, Hash, Nonce, PalletInfo, Runtime, Signature,
 	RuntimeCall, RuntimeEvent, RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, RuntimeTask,
 	System, EXISTENTIAL_DEPOSIT, SLOT_DURATION, VERSION, UNIT, MINUTES, HOURS, DAYS,
 	TechnicalCommittee, ArbitrationCommittee, TreasuryCouncil, ContentCommittee,
+	Arbitration, ArbitrationMembership, FundingReporterMembership, KeyDirectory, Evidence,
 };
 
 const NORMAL_DISPATCH_RATIO: Perbill = Perbill::from_percent(75);
@@ -186,6 +187,12 @@ parameter_types! {
 	pub const MaxProvidersPerType: u32 = 10000;
 	pub const MaxGrantsPerProvider: u32 = 1000;
 	pub const MaxAuthorizationsPerBounty: u32 = 100;
+	/// 社交恢复最多 10 位守护人
+	pub const MaxRecoveryGuardians: u32 = 10;
+	/// 社交恢复最短等待期 3 天（所有者否决窗口）
+	pub const MinRecoveryDelay: BlockNumber = 3 * DAYS;
+	/// 所有者否决后守护人 7 天内不能再次发起恢复
+	pub const RecoveryVetoCooldown: BlockNumber = 7 * DAYS;
 }
 
 impl pallet_divination_privacy::Config for Runtime {
@@ -197,6 +204,10 @@ impl pallet_divination_privacy::Config for Runtime {
 	type MaxGrantsPerProvider = MaxGrantsPerProvider;
 	type MaxAuthorizationsPerBounty = MaxAuthorizationsPerBounty;
 	type EventHandler = ();
+	type KeyDirectory = KeyDirectory;
+	type MaxGuardians = MaxRecoveryGuardians;
+	type MinRecoveryDelay = MinRecoveryDelay;
+	type RecoveryVetoCooldown = RecoveryVetoCooldown;
 	type WeightInfo = ();
 }
 
@@ -223,6 +234,10 @@ impl pallet_tee_privacy::Config for Runtime {
 	type BaseComputeFee = ConstU128<{ UNIT / 10 }>;
 	type SlashRatio = TeeSlashRatio;
 	type MaxBatchSize = ConstU32<100>;
+	type AcceptConfigurationNeeded = ConstBool<false>;
+	type Randomness = CollectiveFlipRandomness;
+	type UnixTime = TimestampProvider;
+	type KeyDirectory = KeyDirectory;
 	type WeightInfo = pallet_tee_privacy::weights::SubstrateWeight<Runtime>;
 }
 
@@ -292,6 +307,7 @@ impl pallet_divination_market::Config for Runtime {
 	type ChatPermission = pallet_chat_permission::Pallet<Runtime>;
 	// 🆕 订单聊天授权有效期（30天 ≈ 432000 blocks，按6秒/块）
 	type OrderChatDuration = ConstU32<{ 30 * DAYS }>;
+	type Reputation = pallet_reputation::Pallet<Runtime>;
 }
 
 // Stub implementation for AffiliateDistributor until pallet_affiliate is integrated
@@ -583,6 +599,14 @@ impl pallet_chat_core::Config for Runtime {
 	type UnixTime = TimestampProvider;
 	type MaxNicknameLength = ConstU32<64>;
 	type MaxSignatureLength = ConstU32<256>;
+	type EditWindow = ConstU32<{ 15 * MINUTES }>;
+	type MaxEditsPerMessage = ConstU32<10>;
+	type MaxReactionLen = ConstU32<32>;
+	type MaxReactionsPerUser = ConstU32<8>;
+	type MinDisappearTimer = ConstU32<{ MINUTES }>;
+	type MaxDisappearTimer = ConstU32<{ 30 * DAYS }>;
+	type MaxExpiredPerBlock = ConstU32<50>;
+	type GroupInbox = pallet_chat_group::Pallet<Runtime>;
 }
 
 // -------------------- Chat Group (群聊) --------------------
@@ -594,6 +618,8 @@ parameter_types! {
 parameter_types! {
 	pub const GroupDeposit: Balance = 50 * UNIT; // 创建群组保证金兜底值 50 DUST
 	pub const GroupDepositUsd: u64 = 5_000_000; // 创建群组保证金 5 USDT（精度10^6）
+	pub const BotDeposit: Balance = 20 * UNIT; // 注册群聊机器人保证金兜底值 20 DUST
+	pub const BotDepositUsd: u64 = 2_000_000; // 注册群聊机器人保证金 2 USDT（精度10^6）
 }
 
 impl pallet_chat_group::Config for Runtime {
@@ -608,6 +634,14 @@ impl pallet_chat_group::Config for Runtime {
 	type MaxGroupMessageHistory = ConstU32<10000>;
 	type MaxCidLen = ConstU32<128>;
 	type MaxKeyLen = ConstU32<256>;
+	type MaxPqKeyLen = ConstU32<1184>; // ML-KEM-768 公钥
+	type MaxWrappedKeyLen = ConstU32<1184>; // 94 字节 X25519 包装 + ML-KEM-768 密文
+	type MaxWrapsPerCall = ConstU32<100>;
+	type MaxBotsPerGroup = ConstU32<10>;
+	type BotRateWindow = ConstU32<{ MINUTES }>; // 机器人发言频率窗口 1 分钟
+	type MaxBotMessagesPerWindow = ConstU32<30>;
+	type BotDeposit = BotDeposit;
+	type BotDepositUsd = BotDepositUsd;
 	type PalletId = ChatGroupPalletId;
 	type MessageRateLimit = ConstU32<60>; // 每分钟最多60条消息
 	type GroupCreationCooldown = ConstU32<{ 10 * MINUTES }>; // 创建群组冷却时间
@@ -616,6 +650,7 @@ impl pallet_chat_group::Config for Runtime {
 	type DepositCalculator = pallet_trading_common::DepositCalculatorImpl<TradingPricingProvider, Balance>;
 	type TreasuryAccount = TreasuryAccountId;
 	type GovernanceOrigin = EnsureRoot<AccountId>;
+	type KeyDirectory = KeyDirectory;
 	type WeightInfo = ();
 }
 
@@ -641,6 +676,12 @@ impl pallet_livestream::Config for Runtime {
 	type PalletId = LivestreamPalletId;
 	// 🆕 封禁权限：内容委员会 1/2 多数
 	type GovernanceOrigin = pallet_collective::EnsureProportionAtLeast<AccountId, ContentCollectiveInstance, 1, 2>;
+	// 私密直播邀请码：主播账户签名
+	type InviteSignature = Signature;
+	type InviteSigner = <Signature as sp_runtime::traits::Verify>::Signer;
+	type MaxInvitesPerRoom = ConstU32<1000>;
+	type MaxRevokedInviteCodes = ConstU32<100>;
+	type Reputation = pallet_reputation::Pallet<Runtime>;
 	type WeightInfo = ();
 }
 
@@ -674,6 +715,7 @@ impl pallet_trading_credit::Config for Runtime {
 	type MakerSuspensionThreshold = ConstU16<750>;
 	type MakerWarningThreshold = ConstU16<800>;
 	type CreditWeightInfo = ();
+	type Reputation = pallet_reputation::Pallet<Runtime>;
 }
 
 // -------------------- Maker (做市商管理) --------------------
@@ -743,6 +785,13 @@ impl pallet_trading_common::MakerInterface<AccountId, Balance> for BridgeMakerAd
 	fn get_deposit_usd_value(maker_id: u64) -> Result<u64, sp_runtime::DispatchError> {
 		pallet_trading_maker::Pallet::<Runtime>::get_deposit_usd_value(maker_id)
 	}
+	fn get_maker_quote(maker_id: u64) -> Option<pallet_trading_common::MakerQuote<Balance>> {
+		pallet_trading_maker::Pallet::<Runtime>::get_quote(maker_id)
+	}
+
+	fn serves_side(maker_id: u64, side: pallet_trading_common::OrderSide) -> bool {
+		pallet_trading_maker::Pallet::<Runtime>::serves_side(maker_id, side)
+	}
 }
 
 /// Bridge Credit 接口适配器
@@ -760,6 +809,10 @@ impl pallet_trading_common::MakerCreditInterface for BridgeCreditAdapter {
 	fn record_maker_dispute_result(maker_id: u64, order_id: u64, maker_win: bool) -> sp_runtime::DispatchResult {
 		pallet_trading_credit::Pallet::<Runtime>::record_maker_dispute_result(maker_id, order_id, maker_win)
 	}
+
+	fn query_maker_credit_score(maker_id: u64) -> Option<u16> {
+		pallet_trading_credit::Pallet::<Runtime>::query_maker_credit_score(maker_id)
+	}
 }
 
 impl pallet_trading_swap::Config for Runtime {
@@ -782,6 +835,8 @@ impl pallet_trading_swap::Config for Runtime {
 	type WeightInfo = ();
 	// 🆕 P3: 仲裁证据 CID 锁定管理器（预留，待 submit_evidence 函数实现后启用）
 	type CidLockManager = pallet_storage_service::Pallet<Runtime>;
+	// 🆕 案件时间线（仲裁委员会查阅）
+	type Timeline = Arbitration;
 }
 
 // -------------------- OTC (场外交易) --------------------
@@ -812,6 +867,13 @@ impl pallet_trading_common::MakerInterface<AccountId, Balance> for OtcMakerAdapt
 	fn get_deposit_usd_value(maker_id: u64) -> Result<u64, sp_runtime::DispatchError> {
 		pallet_trading_maker::Pallet::<Runtime>::get_deposit_usd_value(maker_id)
 	}
+	fn get_maker_quote(maker_id: u64) -> Option<pallet_trading_common::MakerQuote<Balance>> {
+		pallet_trading_maker::Pallet::<Runtime>::get_quote(maker_id)
+	}
+
+	fn serves_side(maker_id: u64, side: pallet_trading_common::OrderSide) -> bool {
+		pallet_trading_maker::Pallet::<Runtime>::serves_side(maker_id, side)
+	}
 }
 
 /// OTC Maker Credit 接口适配器
@@ -829,6 +891,30 @@ impl pallet_trading_common::MakerCreditInterface for OtcMakerCreditAdapter {
 	fn record_maker_dispute_result(maker_id: u64, order_id: u64, maker_win: bool) -> sp_runtime::DispatchResult {
 		pallet_trading_credit::Pallet::<Runtime>::record_maker_dispute_result(maker_id, order_id, maker_win)
 	}
+
+	fn query_maker_credit_score(maker_id: u64) -> Option<u16> {
+		pallet_trading_credit::Pallet::<Runtime>::query_maker_credit_score(maker_id)
+	}
+}
+
+/// OTC 付款凭证适配器（桥接 pallet-evidence 私密内容）
+pub struct OtcPrivateEvidenceAdapter;
+
+impl pallet_trading_common::PrivateEvidenceInterface<AccountId> for OtcPrivateEvidenceAdapter {
+	fn is_shared_with(
+		content_id: u64,
+		ns: [u8; 8],
+		subject_id: u64,
+		creator: &AccountId,
+		readers: &[AccountId],
+	) -> bool {
+		pallet_evidence::PrivateContents::<Runtime>::get(content_id).is_some_and(|content| {
+			content.ns == ns
+				&& content.subject_id == subject_id
+				&& &content.creator == creator
+				&& readers.iter().all(|reader| content.encrypted_keys.iter().any(|(user, _)| user == reader))
+		})
+	}
 }
 
 /// OTC Identity Provider - 暂时跳过 KYC 验证
@@ -881,6 +967,13 @@ impl pallet_trading_otc::Config for Runtime {
 	type WeightInfo = ();
 	// 🆕 P3: 争议证据 CID 锁定管理器
 	type CidLockManager = pallet_storage_service::Pallet<Runtime>;
+	// 订单级加密收款资料与付款凭证
+	type KeyDirectory = KeyDirectory;
+	type PrivateEvidence = OtcPrivateEvidenceAdapter;
+	type ArbitrationCommittee = ArbitrationMembership;
+	type MaxPaymentDetailsLen = ConstU32<1024>;
+	// 🆕 案件时间线（仲裁委员会查阅）
+	type Timeline = Arbitration;
 }
 
 // ============================================================================
@@ -958,6 +1051,17 @@ impl pallet_referral::Config for Runtime {
 	type MembershipProvider = BalanceBasedMembership;
 	type MaxCodeLen = ConstU32<32>;
 	type MaxSearchHops = ConstU32<20>;
+	type GovernanceOrigin = frame_system::EnsureRoot<AccountId>;
+	// 资金来源由链下索引器读取转账图后上报，上报账户由 FundingReporterMembership 管理
+	type FundingReporterOrigin = frame_system::EnsureSignedBy<FundingReporterMembership, AccountId>;
+	// 每个推荐人每小时最多接受 10 个绑定
+	type BindingWindow = ConstU32<{ 1 * HOURS }>;
+	type MaxBindingsPerWindow = ConstU32<10>;
+	// 激活积分：下单 3、会员 5、签到 1
+	type ActivationThreshold = ConstU32<10>;
+	// 同一资金来源注资并绑定到同一推荐人的账户达到 5 个即标记
+	type ClusterThreshold = ConstU32<5>;
+	type MaxFundingReports = ConstU32<100>;
 	type WeightInfo = pallet_referral::weights::SubstrateWeight<Runtime>;
 }
 
@@ -996,6 +1100,7 @@ impl pallet_storage_service::Config for Runtime {
 	type MonthlyPublicFeeQuota = ConstU128<{ 10 * UNIT }>;
 	type QuotaResetPeriod = ConstU32<{ 30 * DAYS }>;
 	type DefaultBillingPeriod = ConstU32<{ 30 * DAYS }>;
+	type RecurringPayments = pallet_recurring_payment::Pallet<Runtime>;
 }
 
 // -------------------- Evidence (证据存证) --------------------
@@ -1043,6 +1148,7 @@ impl pallet_evidence::Config for Runtime {
 	type DefaultStoragePrice = ConstU128<{ UNIT / 10 }>;
 	// 🆕 证据修改窗口（2天 ≈ 28800 blocks，按6秒/块）
 	type EvidenceEditWindow = ConstU32<28800>;
+	type KeyDirectory = KeyDirectory;
 }
 
 // -------------------- Arbitration (仲裁) --------------------
@@ -1233,6 +1339,23 @@ impl pallet_arbitration::pallet::ArbitrationRouter<AccountId, Balance> for Unifi
 			_ => None,
 		}
 	}
+
+	/// 获取案件双方账户（用于案件时间线与聊天会话关联）
+	fn get_parties(domain: [u8; 8], id: u64) -> Option<(AccountId, AccountId)> {
+		use pallet_arbitration::pallet::domains;
+
+		match domain {
+			d if d == domains::OTC_ORDER => {
+				pallet_trading_otc::Orders::<Runtime>::get(id)
+					.map(|order| (order.taker, order.maker))
+			},
+			d if d == domains::SWAP => {
+				pallet_trading_swap::MakerSwaps::<Runtime>::get(id)
+					.map(|record| (record.user, record.maker))
+			},
+			_ => None,
+		}
+	}
 }
 
 /// 信用分更新器实现
@@ -1267,6 +1390,11 @@ impl pallet_arbitration::pallet::Config for Runtime {
 	type CidLockManager = pallet_storage_service::Pallet<Runtime>;
 	// 🆕 信用分更新器
 	type CreditUpdater = TradingCreditUpdater;
+	// 🆕 案件时间线
+	type Timestamp = TimestampProvider;
+	type MaxTimelineEntries = ConstU32<64>;
+	// 跨域信誉
+	type Reputation = pallet_reputation::Pallet<Runtime>;
 }
 
 // ============================================================================
@@ -1451,6 +1579,26 @@ impl pallet_collective_membership::Config<ContentMembershipInstance> for Runtime
 	type WeightInfo = pallet_collective_membership::weights::SubstrateWeight<Runtime>;
 }
 
+// 资金来源上报人管理（链下索引器账户，上报 pallet-referral 首笔资金来源）
+pub type FundingReporterMembershipInstance = pallet_collective_membership::Instance5;
+
+parameter_types! {
+	pub const FundingReporterMaxMembers: u32 = 5;
+}
+
+impl pallet_collective_membership::Config<FundingReporterMembershipInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AddOrigin = frame_system::EnsureRoot<AccountId>;
+	type RemoveOrigin = frame_system::EnsureRoot<AccountId>;
+	type SwapOrigin = frame_system::EnsureRoot<AccountId>;
+	type ResetOrigin = frame_system::EnsureRoot<AccountId>;
+	type PrimeOrigin = frame_system::EnsureRoot<AccountId>;
+	type MembershipInitialized = ();
+	type MembershipChanged = ();
+	type MaxMembers = FundingReporterMaxMembers;
+	type WeightInfo = pallet_collective_membership::weights::SubstrateWeight<Runtime>;
+}
+
 // ============================================================================
 // Divination Membership Pallet Configuration
 // ============================================================================
@@ -1485,6 +1633,7 @@ impl pallet_divination_membership::Config for Runtime {
 	type MaxEncryptedDataLength = MaxEncryptedDataLength;
 	type MaxRewardHistorySize = MaxRewardHistorySize;
 	type Pricing = TradingPricingProvider;
+	type RecurringPayments = pallet_recurring_payment::Pallet<Runtime>;
 }
 
 // ============================================================================
@@ -1503,6 +1652,11 @@ parameter_types! {
 	pub const ProfileDepositUsd: u64 = 50_000_000; // 50 USDT
 	pub const ProfileMonthlyFee: Balance = 20 * UNIT; // 兜底值 20 DUST
 	pub const ProfileMonthlyFeeUsd: u64 = 2_000_000; // 2 USDT
+	// Profile 认证配置
+	pub const ProfileVerifierStake: Balance = 1_000 * UNIT; // 认证员质押 1000 DUST
+	pub const ProfileVerificationFee: Balance = 10 * UNIT; // 单次认证费 10 DUST
+	pub const ProfileVerificationCancelPenaltyBps: u16 = 5_000; // 分配后取消，认证员获得 50% 认证费
+	pub const ProfileVerificationValidity: BlockNumber = 365 * DAYS; // 认证有效期 1 年
 }
 
 impl pallet_matchmaking_membership::Config for Runtime {
@@ -1517,6 +1671,7 @@ impl pallet_matchmaking_membership::Config for Runtime {
 	type LifetimeFee = MatchmakingLifetimeFee;
 	type LifetimeFeeUsd = MatchmakingLifetimeFeeUsd;
 	type Pricing = TradingPricingProvider;
+	type RecurringPayments = pallet_recurring_payment::Pallet<Runtime>;
 	type TreasuryAccount = TreasuryAccountId;
 	type BurnAccount = BurnAccountId;
 	type UserFundingProvider = StorageUserFundingProvider;
@@ -1546,6 +1701,8 @@ impl pallet_matchmaking_profile::Config for Runtime {
 	type MonthlyFee = ProfileMonthlyFee;
 	type MonthlyFeeUsd = ProfileMonthlyFeeUsd;
 	type Pricing = TradingPricingProvider;
+	type RecurringPayments = pallet_recurring_payment::Pallet<Runtime>;
+	type Reputation = pallet_reputation::Pallet<Runtime>;
 	type TreasuryAccount = TreasuryAccountId;
 	type BurnAccount = BurnAccountId;
 	type StorageAccount = StoragePoolAccountId;
@@ -1554,6 +1711,26 @@ impl pallet_matchmaking_profile::Config for Runtime {
 	type GovernanceOrigin = EnsureRoot<AccountId>;
 	type BlocksPerDay = MatchmakingBlocksPerDay;
 	type Balance = Balance;
+	type EvidenceAccess = Evidence;
+	type VerifierStake = ProfileVerifierStake;
+	type VerificationFee = ProfileVerificationFee;
+	type VerificationCancelPenaltyBps = ProfileVerificationCancelPenaltyBps;
+	type VerificationValidity = ProfileVerificationValidity;
+	type MaxVerifiers = ConstU32<200>;
+	type Randomness = CollectiveFlipRandomness;
+}
+
+// ============================================================================
+// Matchmaking Matching Pallet Configuration
+// ============================================================================
+
+impl pallet_matchmaking_matching::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type BaziProvider = Bazi;
+	type ZiweiProvider = Ziwei;
+	type MaxRequestsPerUser = ConstU32<100>;
+	type RequestExpiration = ConstU32<{ 7 * DAYS }>;
+	type WeightInfo = pallet_matchmaking_matching::weights::SubstrateWeight<Runtime>;
 }
 
 // ============================================================================
@@ -1568,3 +1745,167 @@ impl pallet_storage_lifecycle::Config for Runtime {
 	type EnablePurge = ConstBool<false>;             // 默认不启用清除
 	type MaxBatchSize = ConstU32<100>;               // 每次最多处理100条
 }
+
+// ============================================================================
+// Key Directory Pallet Configuration
+// ============================================================================
+
+impl pallet_key_directory::Config for Runtime {
+	type MaxKeyLen = ConstU32<512>;
+	// 轮换重叠窗口 7 天：授权方在此期间用新公钥重新包装数据密钥
+	type RotationOverlap = ConstU32<{ 7 * DAYS }>;
+	type GovernanceOrigin = EnsureRoot<AccountId>;
+	// 隐私授权 / 证据 / 群聊 / TEE 节点各自处理轮换与吊销
+	type OnKeyChange = (Privacy, Evidence, ChatGroup, TeePrivacy);
+	type WeightInfo = pallet_key_directory::weights::SubstrateWeight<Runtime>;
+}
+
+// ============================================================================
+// Recurring Payment Pallet Configuration
+// ============================================================================
+
+/// 周期扣费回调路由：按服务类型分发到各订阅模块
+pub struct RecurringPaymentRouter;
+
+impl pallet_recurring_payment::traits::RecurringPaymentHandler<AccountId, Balance>
+	for RecurringPaymentRouter
+{
+	fn max_weight() -> Weight {
+		// 费用分配（销毁 / 国库 / 存储 / 推荐链）+ 会员状态更新
+		Weight::from_parts(100_000_000, 0).saturating_add(RocksDbWeight::get().reads_writes(8, 8))
+	}
+
+	fn on_payment_due(
+		service: pallet_recurring_payment::types::RecurringService,
+		payer: &AccountId,
+		subject: u64,
+		amount: Balance,
+	) -> sp_runtime::DispatchResult {
+		use pallet_recurring_payment::{traits::RecurringPaymentHandler, types::RecurringService};
+		match service {
+			RecurringService::DivinationMembership =>
+				pallet_divination_membership::Pallet::<Runtime>::on_payment_due(
+					service, payer, subject, amount,
+				),
+			RecurringService::MatchmakingMembership =>
+				pallet_matchmaking_membership::Pallet::<Runtime>::on_payment_due(
+					service, payer, subject, amount,
+				),
+			RecurringService::MatchmakingProfile =>
+				pallet_matchmaking_profile::Pallet::<Runtime>::on_payment_due(
+					service, payer, subject, amount,
+				),
+			RecurringService::StorageService =>
+				pallet_storage_service::Pallet::<Runtime>::on_payment_due(
+					service, payer, subject, amount,
+				),
+		}
+	}
+
+	fn on_payment_lapsed(
+		service: pallet_recurring_payment::types::RecurringService,
+		payer: &AccountId,
+		subject: u64,
+		reason: pallet_recurring_payment::types::LapseReason,
+	) {
+		use pallet_recurring_payment::{traits::RecurringPaymentHandler, types::RecurringService};
+		match service {
+			RecurringService::DivinationMembership =>
+				pallet_divination_membership::Pallet::<Runtime>::on_payment_lapsed(
+					service, payer, subject, reason,
+				),
+			RecurringService::MatchmakingMembership =>
+				pallet_matchmaking_membership::Pallet::<Runtime>::on_payment_lapsed(
+					service, payer, subject, reason,
+				),
+			RecurringService::MatchmakingProfile =>
+				pallet_matchmaking_profile::Pallet::<Runtime>::on_payment_lapsed(
+					service, payer, subject, reason,
+				),
+			RecurringService::StorageService =>
+				pallet_storage_service::Pallet::<Runtime>::on_payment_lapsed(
+					service, payer, subject, reason,
+				),
+		}
+	}
+}
+
+impl pallet_recurring_payment::Config for Runtime {
+	type Balance = Balance;
+	type Pricing = TradingPricingProvider;
+	type Handler = RecurringPaymentRouter;
+	type MaxSchedulesPerPayer = ConstU32<16>;
+	type MaxChargesPerBlock = ConstU32<50>;
+	// 最短周期 1 天；失败后每天重试，宽限期 7 天
+	type MinPeriod = ConstU32<DAYS>;
+	type RetryInterval = ConstU32<DAYS>;
+	type GracePeriod = ConstU32<{ 7 * DAYS }>;
+	type WeightInfo = pallet_recurring_payment::weights::SubstrateWeight<Runtime>;
+}
+
+// ============================================================================
+// Reputation Pallet Configuration
+// ============================================================================
+
+impl pallet_reputation::Config for Runtime {
+	// 风险分 30 天减半
+	type PenaltyHalfLife = ConstU32<{ 30 * DAYS }>;
+	type MaxCredit = ConstU32<100>;
+	type GovernanceOrigin = EnsureRoot<AccountId>;
+	type CreditSource = CreditSnapshotAdapter;
+	// 信用凭证 30 天有效，每账户最多 10 张
+	type CredentialValidity = ConstU32<{ 30 * DAYS }>;
+	type MaxCredentialsPerAccount = ConstU32<10>;
+	type WeightInfo = pallet_reputation::weights::SubstrateWeight<Runtime>;
+}
+
+/// 信用凭证快照适配器
+///
+/// - `TradingMaker`: 账户 → 做市商 ID（pallet-trading-maker）→ 信用记录（pallet-trading-credit）
+/// - `MarketProvider`: 占卜服务提供者信用档案（pallet-divination-market）
+pub struct CreditSnapshotAdapter;
+
+impl pallet_reputation::traits::CreditSnapshotProvider<AccountId> for CreditSnapshotAdapter {
+	fn credit_snapshot(
+		who: &AccountId,
+		source: pallet_reputation::types::CredentialSource,
+	) -> Option<pallet_reputation::types::CreditSnapshot> {
+		use pallet_reputation::types::{CredentialSource, CreditSnapshot, ScoreBand};
+
+		match source {
+			CredentialSource::TradingMaker => {
+				use pallet_trading_credit::maker::CreditLevel;
+
+				let maker_id = pallet_trading_maker::AccountToMaker::<Runtime>::get(who)?;
+				let record = pallet_trading_credit::MakerCredits::<Runtime>::get(maker_id)?;
+				let band = match record.level {
+					CreditLevel::Diamond | CreditLevel::Platinum => ScoreBand::Excellent,
+					CreditLevel::Gold => ScoreBand::Good,
+					CreditLevel::Silver => ScoreBand::Fair,
+					CreditLevel::Bronze => ScoreBand::Poor,
+				};
+				Some(CreditSnapshot {
+					band,
+					order_count: record.total_orders,
+					dispute_count: record.dispute_loss_count as u32,
+				})
+			},
+			CredentialSource::MarketProvider => {
+				use pallet_divination_market::types::CreditLevel;
+
+				let profile = pallet_divination_market::CreditProfiles::<Runtime>::get(who)?;
+				let band = match profile.level {
+					CreditLevel::Excellent => ScoreBand::Excellent,
+					CreditLevel::Good => ScoreBand::Good,
+					CreditLevel::Fair => ScoreBand::Fair,
+					CreditLevel::Warning | CreditLevel::Poor | CreditLevel::Bad => ScoreBand::Poor,
+				};
+				Some(CreditSnapshot {
+					band,
+					order_count: profile.total_orders,
+					dispute_count: profile.complaint_upheld_count,
+				})
+			},
+		}
+	}
+}
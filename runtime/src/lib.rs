@@ -172,7 +172,10 @@ pub type SignedPayload = generic::SignedPayload<RuntimeCall, TxExtension>;
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
 #[allow(unused_parens)]
-pub type Migrations = ();
+pub type Migrations = (
+	pallet_chat_core::migrations::MigrateV0ToV1<Runtime>,
+	pallet_trading_maker::migrations::MigrateV0ToV1<Runtime>,
+);
 
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
@@ -254,6 +257,10 @@ mod runtime {
 	#[runtime::pallet_index(77)]
 	pub type ContentMembership = pallet_collective_membership<Instance4>;
 
+	// 5. 资金来源上报人 (Funding Reporters，仅成员列表，无委员会)
+	#[runtime::pallet_index(78)]
+	pub type FundingReporterMembership = pallet_collective_membership<Instance5>;
+
 	// ============================================================================
 	// Divination Pallets
 	// ============================================================================
@@ -365,6 +372,15 @@ mod runtime {
 	#[runtime::pallet_index(65)]
 	pub type StorageLifecycle = pallet_storage_lifecycle;
 
+	#[runtime::pallet_index(66)]
+	pub type KeyDirectory = pallet_key_directory;
+
+	#[runtime::pallet_index(67)]
+	pub type RecurringPayment = pallet_recurring_payment;
+
+	#[runtime::pallet_index(68)]
+	pub type Reputation = pallet_reputation;
+
 	// ============================================================================
 	// Matchmaking Pallets
 	// ============================================================================
@@ -374,4 +390,7 @@ mod runtime {
 
 	#[runtime::pallet_index(81)]
 	pub type MatchmakingProfile = pallet_matchmaking_profile;
+
+	#[runtime::pallet_index(82)]
+	pub type MatchmakingMatching = pallet_matchmaking_matching;
 }